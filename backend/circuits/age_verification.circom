@@ -0,0 +1,61 @@
+pragma circom 2.2.2;
+
+/*
+    Age Verification Circuit
+
+    This circuit proves that currentYear - birthYear >= minimumAge without
+    revealing birthYear:
+    1. birthYear stays private; only its commitment birthYearHash is public
+    2. birthYearHash = Poseidon(birthYear, salt), with a random private salt so
+       the ~150 plausible birth years cannot be brute-forced from the hash
+    3. birthYear is not in the future
+    4. ageVerified is 1 if the age requirement holds, 0 otherwise
+*/
+
+include "../node_modules/circomlib/circuits/poseidon.circom";
+include "../node_modules/circomlib/circuits/comparators.circom";
+include "../node_modules/circomlib/circuits/bitify.circom";
+
+// Years and ages fit in 12 bits (< 4096); the comparators need bounded inputs
+template AgeVerification() {
+    // Private inputs
+    signal input birthYear;
+    signal input salt;
+
+    // Public inputs
+    signal input birthYearHash;
+    signal input minimumAge;
+    signal input currentYear;
+
+    // Public output
+    signal output ageVerified;
+
+    // Range checks so the comparisons cannot wrap around the field
+    component birthYearBits = Num2Bits(12);
+    birthYearBits.in <== birthYear;
+    component minimumAgeBits = Num2Bits(12);
+    minimumAgeBits.in <== minimumAge;
+    component currentYearBits = Num2Bits(12);
+    currentYearBits.in <== currentYear;
+
+    // The commitment must match the private birth year
+    component commitment = Poseidon(2);
+    commitment.inputs[0] <== birthYear;
+    commitment.inputs[1] <== salt;
+    commitment.out === birthYearHash;
+
+    // birthYear <= currentYear
+    component notInFuture = LessEqThan(12);
+    notInFuture.in[0] <== birthYear;
+    notInFuture.in[1] <== currentYear;
+    notInFuture.out === 1;
+
+    // currentYear - birthYear >= minimumAge
+    component oldEnough = GreaterEqThan(12);
+    oldEnough.in[0] <== currentYear - birthYear;
+    oldEnough.in[1] <== minimumAge;
+
+    ageVerified <== oldEnough.out;
+}
+
+component main { public [ birthYearHash, minimumAge, currentYear ] } = AgeVerification();
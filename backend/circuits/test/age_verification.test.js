@@ -0,0 +1,85 @@
+const chai = require("chai");
+const path = require("path");
+const wasm_tester = require("circom_tester").wasm;
+const circomlibjs = require("circomlibjs");
+
+const assert = chai.assert;
+
+describe("Age Verification Circuit", function() {
+    let circuit;
+    let poseidon;
+    let F;
+
+    const CURRENT_YEAR = 2026;
+    const SALT = "918273645546372819";
+
+    // --- Helper function to build inputs with a matching commitment ---
+    function inputsFor(birthYear, minimumAge) {
+        const birthYearHash = F.toObject(poseidon([birthYear, SALT])).toString();
+        return {
+            birthYear: birthYear.toString(),
+            salt: SALT,
+            birthYearHash: birthYearHash,
+            minimumAge: minimumAge.toString(),
+            currentYear: CURRENT_YEAR.toString()
+        };
+    }
+
+    this.timeout(200000);
+
+    before(async function () {
+        poseidon = await circomlibjs.buildPoseidon();
+        F = poseidon.F;
+        circuit = await wasm_tester(
+            path.join(__dirname, "../age_verification.circom"),
+            { include: [path.join(__dirname, "../../node_modules/circomlib/circuits")] }
+        );
+    });
+
+    context("when checking the age requirement", function() {
+        it("Should verify a 20-year-old for an 18+ check", async function() {
+            const witness = await circuit.calculateWitness(inputsFor(CURRENT_YEAR - 20, 18));
+            await circuit.checkConstraints(witness);
+            const ageVerified = witness[1];
+            assert.equal(ageVerified.toString(), "1", "A 20-year-old should pass an 18+ check");
+        });
+
+        it("Should reject a 16-year-old for an 18+ check", async function() {
+            const witness = await circuit.calculateWitness(inputsFor(CURRENT_YEAR - 16, 18));
+            await circuit.checkConstraints(witness);
+            const ageVerified = witness[1];
+            assert.equal(ageVerified.toString(), "0", "A 16-year-old should fail an 18+ check");
+        });
+
+        it("Should handle the exact boundary age", async function() {
+            const witness = await circuit.calculateWitness(inputsFor(CURRENT_YEAR - 18, 18));
+            await circuit.checkConstraints(witness);
+            const ageVerified = witness[1];
+            assert.equal(ageVerified.toString(), "1", "Turning 18 this year should pass an 18+ check");
+        });
+    });
+
+    context("when checking the birth year commitment", function() {
+        it("Should fail when the commitment belongs to another birth year", async function() {
+            const input = inputsFor(CURRENT_YEAR - 16, 18);
+            // Claim an older birth year while keeping the real commitment
+            input.birthYear = (CURRENT_YEAR - 20).toString();
+
+            try {
+                await circuit.calculateWitness(input);
+                assert.fail("Should have thrown an error for a mismatched commitment");
+            } catch (error) {
+                assert.include(error.message, "Error: Assert Failed", "Should fail with a constraint assertion error");
+            }
+        });
+
+        it("Should fail when the birth year is in the future", async function() {
+            try {
+                await circuit.calculateWitness(inputsFor(CURRENT_YEAR + 1, 0));
+                assert.fail("Should have thrown an error for a future birth year");
+            } catch (error) {
+                assert.include(error.message, "Error: Assert Failed", "Should fail with a constraint assertion error");
+            }
+        });
+    });
+});
@@ -0,0 +1,64 @@
+-- Migration: 032_venture_proposals.sql
+-- Description: Shareholder voting on venture decisions (proposals, voting power snapshot, immutable votes)
+-- Date: 2026-10-14
+
+-- 1. Proposals Table
+CREATE TABLE IF NOT EXISTS venture_proposals (
+    id UUID PRIMARY KEY,
+    venture_id UUID NOT NULL REFERENCES artist_ventures(id),
+    artist_id UUID NOT NULL REFERENCES users(id),
+    title VARCHAR(255) NOT NULL,
+    description TEXT,
+    options JSONB NOT NULL, -- Stores Vec<ProposalOption>
+    deadline TIMESTAMP WITH TIME ZONE NOT NULL,
+    quorum_percentage DOUBLE PRECISION NOT NULL CHECK (quorum_percentage >= 0 AND quorum_percentage <= 100),
+    tally_visibility VARCHAR(50) NOT NULL, -- live, hidden_until_finalized
+    status VARCHAR(50) NOT NULL DEFAULT 'open', -- open, finalized
+    outcome JSONB, -- Stores ProposalOutcome once finalized
+    snapshot_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    finalized_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_proposals_venture_id ON venture_proposals(venture_id);
+CREATE INDEX IF NOT EXISTS idx_proposals_open_deadline ON venture_proposals(deadline) WHERE status = 'open';
+
+-- 2. Voting power frozen at proposal creation
+CREATE TABLE IF NOT EXISTS proposal_voting_power (
+    proposal_id UUID NOT NULL REFERENCES venture_proposals(id) ON DELETE CASCADE,
+    holder_id UUID NOT NULL REFERENCES users(id),
+    weight DOUBLE PRECISION NOT NULL CHECK (weight > 0),
+    PRIMARY KEY (proposal_id, holder_id)
+);
+
+-- 3. Votes (one per shareholder, immutable)
+CREATE TABLE IF NOT EXISTS proposal_votes (
+    proposal_id UUID NOT NULL REFERENCES venture_proposals(id),
+    voter_id UUID NOT NULL REFERENCES users(id),
+    option_id UUID NOT NULL,
+    weight DOUBLE PRECISION NOT NULL,
+    cast_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (proposal_id, voter_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_proposal_votes_option ON proposal_votes(proposal_id, option_id);
+
+-- Votes cannot be changed once cast
+CREATE OR REPLACE FUNCTION prevent_proposal_vote_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'proposal votes are immutable';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER proposal_votes_immutable
+    BEFORE UPDATE OR DELETE ON proposal_votes
+    FOR EACH ROW
+    EXECUTE FUNCTION prevent_proposal_vote_changes();
+
+-- Trigger for updated_at
+CREATE TRIGGER update_venture_proposals_updated_at
+    BEFORE UPDATE ON venture_proposals
+    FOR EACH ROW
+    EXECUTE FUNCTION update_updated_at_column();
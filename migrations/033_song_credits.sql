@@ -0,0 +1,14 @@
+-- Migration: 033_song_credits.sql
+-- Description: Per-contributor credits (featured artists, producers, writers) with royalty shares
+-- Date: 2026-10-14
+
+CREATE TABLE IF NOT EXISTS song_credits (
+    song_id UUID NOT NULL REFERENCES songs(id) ON DELETE CASCADE,
+    contributor_id UUID NOT NULL,
+    credit_type VARCHAR(50) NOT NULL, -- main_artist, featured_artist, producer, songwriter, remixer
+    royalty_share DOUBLE PRECISION NOT NULL CHECK (royalty_share >= 0 AND royalty_share <= 100),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    PRIMARY KEY (song_id, contributor_id, credit_type)
+);
+
+CREATE INDEX IF NOT EXISTS idx_song_credits_contributor ON song_credits(contributor_id);
@@ -0,0 +1,14 @@
+-- Migration: 034_artist_genre_read_model.sql
+-- Description: Fan Ventures read model of artist genres, projected from SongUploaded events
+-- Date: 2026-10-14
+
+CREATE TABLE IF NOT EXISTS artist_genre_read_model (
+    artist_id UUID NOT NULL,
+    genre VARCHAR(50) NOT NULL,
+    song_count INTEGER NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    PRIMARY KEY (artist_id, genre)
+);
+
+-- Market stats filter confirmed investments by creation date
+CREATE INDEX IF NOT EXISTS idx_fan_investments_status_created_at ON fan_investments(status, created_at);
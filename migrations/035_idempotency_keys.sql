@@ -0,0 +1,17 @@
+-- Migration: 035_idempotency_keys.sql
+-- Description: Idempotency-Key store for payment creation/processing (and other opt-in endpoints)
+-- Date: 2026-10-14
+
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    key VARCHAR(255) PRIMARY KEY,
+    request_hash VARCHAR(64) NOT NULL,
+    status VARCHAR(20) NOT NULL CHECK (status IN ('in_progress', 'completed')),
+    response_status INTEGER,
+    response_content_type VARCHAR(255),
+    response_body BYTEA,
+    resource_id VARCHAR(255),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_expires_at ON idempotency_keys(expires_at);
@@ -0,0 +1,10 @@
+-- Migration: 036_payment_partially_paid.sql
+-- Description: PartiallyPaid/OnHold payment statuses (USDC deposits); status_details holds variant fields
+-- Date: 2026-10-14
+
+ALTER TABLE payments DROP CONSTRAINT IF EXISTS payments_status_check;
+ALTER TABLE payments ADD CONSTRAINT payments_status_check
+    CHECK (status IN ('Pending', 'Processing', 'Completed', 'Failed', 'Cancelled', 'Refunding', 'Refunded', 'OnHold', 'PartiallyPaid'));
+
+ALTER TABLE payments ADD COLUMN IF NOT EXISTS status_details JSONB;
+
@@ -0,0 +1,24 @@
+-- Migration: 037_investment_reservations.sql
+-- Description: Escrow for venture purchases (pending reservations with confirm/cancel/expiry)
+-- Date: 2026-10-15
+
+CREATE TABLE IF NOT EXISTS investment_reservations (
+    investment_id UUID PRIMARY KEY REFERENCES fan_investments(id) ON DELETE CASCADE,
+    venture_id UUID NOT NULL REFERENCES artist_ventures(id) ON DELETE CASCADE,
+    fan_id UUID NOT NULL REFERENCES users(id),
+    shares DOUBLE PRECISION NOT NULL CHECK (shares > 0),
+    payment_id UUID,
+    auto_confirm BOOLEAN NOT NULL DEFAULT FALSE,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'confirmed', 'cancelled', 'expired')),
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_investment_reservations_pending_venture
+    ON investment_reservations(venture_id) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_investment_reservations_pending_expiry
+    ON investment_reservations(expires_at) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_investment_reservations_payment_id ON investment_reservations(payment_id);
+
+COMMENT ON TABLE investment_reservations IS 'Shares held in escrow while an investment payment is pending confirmation';
@@ -0,0 +1,52 @@
+-- Migration: 038_refund_workflow.sql
+-- Description: Refund lifecycle per gateway, crypto manual-payout queue and royalty reversals
+-- Date: 2026-10-15
+
+-- Refunds: gateway que lo ejecuta, quién lo pidió y ciclo de vida completo
+ALTER TABLE refunds ALTER COLUMN amount TYPE DECIMAL(15, 6);
+ALTER TABLE refunds ALTER COLUMN currency TYPE VARCHAR(10);
+ALTER TABLE refunds ADD COLUMN IF NOT EXISTS gateway VARCHAR(50);
+ALTER TABLE refunds ADD COLUMN IF NOT EXISTS initiated_by UUID;
+ALTER TABLE refunds ADD COLUMN IF NOT EXISTS failure_reason TEXT;
+ALTER TABLE refunds ADD COLUMN IF NOT EXISTS completed_at TIMESTAMP WITH TIME ZONE;
+
+ALTER TABLE refunds DROP CONSTRAINT IF EXISTS refunds_status_check;
+ALTER TABLE refunds ADD CONSTRAINT refunds_status_check
+    CHECK (status IN ('pending', 'processing', 'awaiting_payout', 'completed', 'failed'));
+ALTER TABLE refunds ADD CONSTRAINT refunds_amount_positive CHECK (amount > 0);
+
+-- Reembolsos cripto: no hay API de reembolso, un operador envía la transferencia
+CREATE TABLE IF NOT EXISTS crypto_refund_payouts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    refund_id UUID NOT NULL UNIQUE REFERENCES refunds(id) ON DELETE RESTRICT,
+    payment_id UUID NOT NULL REFERENCES payments(id) ON DELETE RESTRICT,
+    blockchain VARCHAR(20) NOT NULL,
+    recipient_address VARCHAR(100) NOT NULL,
+    amount DECIMAL(15, 6) NOT NULL CHECK (amount > 0),
+    currency VARCHAR(10) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'queued' CHECK (status IN ('queued', 'sent', 'failed')),
+    tx_hash VARCHAR(128),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_crypto_refund_payouts_queued
+    ON crypto_refund_payouts(created_at) WHERE status = 'queued';
+
+-- Reversión de royalties calculadas a partir de ingresos reembolsados
+CREATE TABLE IF NOT EXISTS royalty_reversals (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    refund_id UUID NOT NULL REFERENCES refunds(id) ON DELETE RESTRICT,
+    distribution_id UUID NOT NULL REFERENCES royalty_distributions(id) ON DELETE CASCADE,
+    artist_id UUID NOT NULL,
+    artist_amount_value DECIMAL(15, 6) NOT NULL CHECK (artist_amount_value >= 0),
+    platform_fee_value DECIMAL(15, 6) NOT NULL CHECK (platform_fee_value >= 0),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    UNIQUE (refund_id, distribution_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_royalty_reversals_distribution ON royalty_reversals(distribution_id);
+CREATE INDEX IF NOT EXISTS idx_royalty_reversals_artist ON royalty_reversals(artist_id);
+
+COMMENT ON TABLE crypto_refund_payouts IS 'Crypto refunds waiting for a manual on-chain payout';
+COMMENT ON TABLE royalty_reversals IS 'Royalty claw-backs proportional to refunded revenue';
@@ -0,0 +1,53 @@
+-- Migration: 039_royalty_distribution_runs.sql
+-- Description: Royalty distribution runs per (song, period) with per-recipient payouts
+-- Date: 2026-10-15
+
+CREATE EXTENSION IF NOT EXISTS btree_gist;
+
+-- Una ejecución reparte los ingresos de una canción en un periodo. El split se
+-- guarda tal como se derivó de royalty_percentage + créditos de la canción.
+CREATE TABLE IF NOT EXISTS royalty_distribution_runs (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    song_id UUID NOT NULL REFERENCES songs(id) ON DELETE RESTRICT,
+    period_start TIMESTAMP WITH TIME ZONE NOT NULL,
+    period_end TIMESTAMP WITH TIME ZONE NOT NULL,
+    total_revenue DECIMAL(15, 6) NOT NULL CHECK (total_revenue > 0),
+    currency VARCHAR(10) NOT NULL,
+    splits JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'active' CHECK (status IN ('active', 'voided')),
+    created_by UUID NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    voided_by UUID,
+    voided_at TIMESTAMP WITH TIME ZONE,
+    void_reason TEXT,
+
+    CONSTRAINT royalty_runs_period_order CHECK (period_end > period_start),
+    -- Dos ejecuciones activas de la misma canción no pueden solaparse (periodo [inicio, fin))
+    CONSTRAINT royalty_runs_no_overlap EXCLUDE USING gist (
+        song_id WITH =,
+        tstzrange(period_start, period_end, '[)') WITH &&
+    ) WHERE (status = 'active')
+);
+
+CREATE INDEX IF NOT EXISTS idx_royalty_runs_song_period
+    ON royalty_distribution_runs(song_id, period_start, period_end);
+
+CREATE TABLE IF NOT EXISTS royalty_payouts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    run_id UUID NOT NULL REFERENCES royalty_distribution_runs(id) ON DELETE RESTRICT,
+    song_id UUID NOT NULL,
+    recipient_id UUID NOT NULL,
+    recipient_type VARCHAR(20) NOT NULL CHECK (recipient_type IN ('artist', 'contributor', 'platform')),
+    percentage DECIMAL(9, 6) NOT NULL CHECK (percentage >= 0 AND percentage <= 100),
+    amount DECIMAL(15, 6) NOT NULL CHECK (amount >= 0),
+    currency VARCHAR(10) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'scheduled', 'retained', 'cancelled')),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+
+    UNIQUE (run_id, recipient_id, recipient_type)
+);
+
+CREATE INDEX IF NOT EXISTS idx_royalty_payouts_recipient ON royalty_payouts(recipient_id);
+CREATE INDEX IF NOT EXISTS idx_royalty_payouts_pending
+    ON royalty_payouts(run_id) WHERE status = 'pending';
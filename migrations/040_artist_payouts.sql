@@ -0,0 +1,54 @@
+-- Migration: 040_artist_payouts.sql
+-- Description: Artist payout methods, balance ledger and scheduled payouts
+-- Date: 2026-10-15
+
+CREATE TABLE IF NOT EXISTS artist_payout_methods (
+    artist_id UUID PRIMARY KEY,
+    method_type VARCHAR(20) NOT NULL CHECK (method_type IN ('stripe_connect', 'solana_wallet')),
+    details JSONB NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS artist_payouts (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    artist_id UUID NOT NULL,
+    method JSONB NOT NULL,
+    amount DECIMAL(15, 6) NOT NULL CHECK (amount > 0),
+    currency VARCHAR(10) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'submitting', 'submitted', 'confirmed', 'failed')),
+    tx_signature VARCHAR(128),
+    failure_reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    submitting_at TIMESTAMP WITH TIME ZONE,
+    submitted_at TIMESTAMP WITH TIME ZONE,
+    confirmed_at TIMESTAMP WITH TIME ZONE
+);
+
+-- Un solo payout abierto por artista y moneda: el saldo no se puede reservar dos veces
+CREATE UNIQUE INDEX IF NOT EXISTS idx_artist_payouts_one_open
+    ON artist_payouts(artist_id, currency)
+    WHERE status IN ('pending', 'submitting', 'submitted');
+CREATE INDEX IF NOT EXISTS idx_artist_payouts_artist ON artist_payouts(artist_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_artist_payouts_status ON artist_payouts(status);
+
+-- Ledger de ingresos: cada crédito viene de una fuente (pago de royalty) y se
+-- acredita una sola vez
+CREATE TABLE IF NOT EXISTS artist_balance_entries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    artist_id UUID NOT NULL,
+    source_id UUID NOT NULL UNIQUE,
+    amount DECIMAL(15, 6) NOT NULL CHECK (amount > 0),
+    currency VARCHAR(10) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'available' CHECK (status IN ('available', 'reserved', 'settled')),
+    payout_id UUID REFERENCES artist_payouts(id) ON DELETE RESTRICT,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    settled_at TIMESTAMP WITH TIME ZONE,
+
+    CONSTRAINT artist_balance_entries_payout CHECK ((status = 'available') = (payout_id IS NULL))
+);
+
+CREATE INDEX IF NOT EXISTS idx_artist_balance_entries_available
+    ON artist_balance_entries(artist_id, currency) WHERE status = 'available';
+CREATE INDEX IF NOT EXISTS idx_artist_balance_entries_payout ON artist_balance_entries(payout_id);
@@ -0,0 +1,10 @@
+-- Migration: 041_listen_session_versioning.sql
+-- Description: Optimistic locking version for listen_sessions and index for expired active sessions
+-- Date: 2026-10-15
+
+-- 006 ya crea la columna; las bases creadas desde 012 no la tienen
+ALTER TABLE listen_sessions ADD COLUMN IF NOT EXISTS version INTEGER NOT NULL DEFAULT 1;
+
+CREATE INDEX IF NOT EXISTS idx_listen_sessions_active_started
+    ON listen_sessions(started_at)
+    WHERE status = 'active';
@@ -0,0 +1,46 @@
+-- Migration: 042_audit_log.sql
+-- Description: Append-only audit log for share purchases, share transfers and revenue distributions
+-- Date: 2026-10-15
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    event_id UUID PRIMARY KEY,
+    aggregate_id UUID NOT NULL,
+    event_type VARCHAR(100) NOT NULL,
+    actor_id UUID,
+    before_state JSONB NOT NULL DEFAULT 'null'::jsonb,
+    after_state JSONB NOT NULL DEFAULT 'null'::jsonb,
+    occurred_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_aggregate ON audit_log(aggregate_id, occurred_at);
+
+-- La API sólo puede insertar y leer: sin UPDATE ni DELETE a nivel de rol
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_roles WHERE rolname = 'vibestream_api') THEN
+        CREATE ROLE vibestream_api;
+    END IF;
+END $$;
+
+REVOKE ALL ON audit_log FROM PUBLIC;
+REVOKE ALL ON audit_log FROM vibestream_api;
+GRANT SELECT, INSERT ON audit_log TO vibestream_api;
+
+-- El propietario de la tabla no está sujeto a los GRANTs: el trigger cubre ese caso
+CREATE OR REPLACE FUNCTION prevent_audit_log_changes()
+RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'audit log entries are immutable';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_log_immutable
+    BEFORE UPDATE OR DELETE ON audit_log
+    FOR EACH ROW
+    EXECUTE FUNCTION prevent_audit_log_changes();
+
+CREATE TRIGGER audit_log_no_truncate
+    BEFORE TRUNCATE ON audit_log
+    FOR EACH STATEMENT
+    EXECUTE FUNCTION prevent_audit_log_changes();
@@ -0,0 +1,33 @@
+-- Migration: 043_venture_funding_status.sql
+-- Description: 'funded' status for ventures that reach their goal and link ventures to a song
+-- Date: 2026-10-15
+
+-- =============================================================================
+-- 1. FUNDED STATUS
+-- =============================================================================
+-- Rust Enum: Draft, Open, Funded, Closed, Cancelled
+-- The repository writes the enum's Display form ('Open'), so compare case-insensitively
+
+ALTER TABLE artist_ventures
+DROP CONSTRAINT IF EXISTS artist_ventures_status_check;
+
+ALTER TABLE artist_ventures
+ADD CONSTRAINT artist_ventures_status_check
+CHECK (lower(status) IN ('draft', 'open', 'funded', 'closed', 'cancelled'));
+
+COMMENT ON CONSTRAINT artist_ventures_status_check ON artist_ventures IS
+'Status aligned with Rust Enum: Draft, Open, Funded, Closed, Cancelled';
+
+-- Ventures already at their goal stop accepting investments
+UPDATE artist_ventures
+SET status = 'funded'
+WHERE lower(status) = 'open' AND current_funding >= funding_goal;
+
+-- =============================================================================
+-- 2. SONG BEING FINANCED
+-- =============================================================================
+
+ALTER TABLE artist_ventures
+ADD COLUMN IF NOT EXISTS song_id UUID REFERENCES songs(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_artist_ventures_song_id ON artist_ventures(song_id);
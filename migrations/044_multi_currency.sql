@@ -0,0 +1,45 @@
+-- Migration: 044_multi_currency.sql
+-- Description: Fiat currencies beyond USD and the exchange rates captured to convert payments to the base currency
+-- Date: 2026-10-15
+
+-- =============================================================================
+-- 1. PAYMENT CURRENCIES
+-- =============================================================================
+-- Rust Enum: USD, EUR, GBP, ETH, SOL, USDC, VIBES
+
+ALTER TABLE payments
+DROP CONSTRAINT IF EXISTS payments_amount_currency_check;
+
+ALTER TABLE payments
+ADD CONSTRAINT payments_amount_currency_check
+CHECK (amount_currency IN ('USD', 'EUR', 'GBP', 'ETH', 'SOL', 'USDC', 'VIBES'));
+
+-- =============================================================================
+-- 2. CAPTURED EXCHANGE RATES
+-- =============================================================================
+-- One row per payment completed in a currency other than the base currency.
+-- Refunds and royalty reversals reuse this rate instead of the market rate.
+
+CREATE TABLE IF NOT EXISTS payment_exchange_rates (
+    payment_id UUID PRIMARY KEY REFERENCES payments(id) ON DELETE CASCADE,
+    original_value DECIMAL(20,9) NOT NULL CHECK (original_value >= 0),
+    original_currency VARCHAR(10) NOT NULL,
+    base_value DECIMAL(20,9) NOT NULL CHECK (base_value >= 0),
+    base_currency VARCHAR(10) NOT NULL,
+    rate DOUBLE PRECISION NOT NULL CHECK (rate > 0),
+    quoted_at TIMESTAMPTZ NOT NULL,
+    captured_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE payment_exchange_rates IS
+'Rate used to convert a payment to the base currency when it completed (original_currency -> base_currency)';
+
+-- =============================================================================
+-- 3. ROYALTY RUNS REPORTED IN ANOTHER CURRENCY
+-- =============================================================================
+-- total_revenue/currency stay in the base currency; the reported revenue is kept for display
+
+ALTER TABLE royalty_distribution_runs
+ADD COLUMN IF NOT EXISTS original_revenue DECIMAL(20,9),
+ADD COLUMN IF NOT EXISTS original_currency VARCHAR(10),
+ADD COLUMN IF NOT EXISTS exchange_rate DOUBLE PRECISION CHECK (exchange_rate > 0);
@@ -0,0 +1,37 @@
+-- Migration: 045_remix_licenses.sql
+-- Description: Song stems and remix licenses that give producers access to them
+-- Date: 2026-10-15
+
+-- =============================================================================
+-- 1. SONG STEMS
+-- =============================================================================
+
+ALTER TABLE songs
+ADD COLUMN IF NOT EXISTS has_stems BOOLEAN NOT NULL DEFAULT FALSE,
+ADD COLUMN IF NOT EXISTS stems_ipfs_hash VARCHAR(100);
+
+ALTER TABLE songs
+DROP CONSTRAINT IF EXISTS songs_stems_hash_check;
+
+ALTER TABLE songs
+ADD CONSTRAINT songs_stems_hash_check
+CHECK (NOT has_stems OR stems_ipfs_hash IS NOT NULL);
+
+-- =============================================================================
+-- 2. REMIX LICENSES
+-- =============================================================================
+-- Rust Enum: Standard, Exclusive
+
+CREATE TABLE IF NOT EXISTS remix_licenses (
+    id UUID PRIMARY KEY,
+    song_id UUID NOT NULL REFERENCES songs(id) ON DELETE CASCADE,
+    licensee_id UUID NOT NULL,
+    license_type VARCHAR(20) NOT NULL CHECK (license_type IN ('standard', 'exclusive')),
+    price DECIMAL(15,6) NOT NULL CHECK (price > 0),
+    expiry TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CHECK (expiry > created_at)
+);
+
+CREATE INDEX IF NOT EXISTS idx_remix_licenses_song ON remix_licenses(song_id, expiry DESC);
+CREATE INDEX IF NOT EXISTS idx_remix_licenses_licensee ON remix_licenses(licensee_id, song_id);
@@ -0,0 +1,22 @@
+-- Migration: 046_song_audio_features.sql
+-- Description: Mood, tempo and audio features (valence/energy) of songs
+-- Date: 2026-10-15
+
+ALTER TABLE songs
+ADD COLUMN IF NOT EXISTS mood VARCHAR(30),
+ADD COLUMN IF NOT EXISTS tempo_bpm SMALLINT,
+ADD COLUMN IF NOT EXISTS valence REAL,
+ADD COLUMN IF NOT EXISTS energy REAL;
+
+ALTER TABLE songs
+DROP CONSTRAINT IF EXISTS songs_audio_features_check;
+
+ALTER TABLE songs
+ADD CONSTRAINT songs_audio_features_check
+CHECK (
+    (valence IS NULL OR valence BETWEEN 0 AND 1)
+    AND (energy IS NULL OR energy BETWEEN 0 AND 1)
+    AND (tempo_bpm IS NULL OR tempo_bpm BETWEEN 60 AND 200)
+);
+
+CREATE INDEX IF NOT EXISTS idx_songs_mood ON songs(mood) WHERE mood IS NOT NULL;
@@ -0,0 +1,32 @@
+-- Migration: 047_refresh_tokens.sql
+-- Description: Opaque refresh tokens with rotation, one family per device session
+-- Date: 2026-10-15
+
+-- =============================================================================
+-- REFRESH TOKENS
+-- =============================================================================
+-- Only the SHA-256 of the token is stored. Each refresh replaces the token
+-- with a new one of the same family (`replaced_by`); presenting a replaced
+-- token revokes the whole family.
+
+CREATE TABLE IF NOT EXISTS refresh_tokens (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    family_id UUID NOT NULL,
+    token_hash CHAR(64) NOT NULL UNIQUE,
+    device_name VARCHAR(100),
+    user_agent TEXT,
+    ip_address VARCHAR(45),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    last_used_at TIMESTAMPTZ,
+    replaced_by UUID REFERENCES refresh_tokens(id) ON DELETE SET NULL,
+    revoked_at TIMESTAMPTZ,
+    revoked_reason VARCHAR(30),
+    CHECK (expires_at > created_at)
+);
+
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_family ON refresh_tokens(family_id);
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_active_user
+ON refresh_tokens(user_id, created_at DESC)
+WHERE replaced_by IS NULL AND revoked_at IS NULL;
@@ -0,0 +1,33 @@
+-- Migration: 048_playlist_recommendations.sql
+-- Description: Playlist followers and precomputed user similarities for collaborative filtering
+-- Date: 2026-10-15
+
+-- =============================================================================
+-- 1. PLAYLIST FOLLOWERS
+-- =============================================================================
+
+CREATE TABLE IF NOT EXISTS playlist_followers (
+    playlist_id UUID NOT NULL REFERENCES playlists(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    followed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (playlist_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_playlist_followers_user ON playlist_followers(user_id);
+
+-- =============================================================================
+-- 2. USER SIMILARITIES
+-- =============================================================================
+-- Jaccard similarity between the sets of songs two users listened to.
+-- Rewritten by the nightly refresh job: the 50 most similar users per user.
+
+CREATE TABLE IF NOT EXISTS user_similarities (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    similar_user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    similarity REAL NOT NULL CHECK (similarity > 0 AND similarity <= 1),
+    computed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, similar_user_id),
+    CHECK (user_id <> similar_user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_similarities_rank ON user_similarities(user_id, similarity DESC);
@@ -0,0 +1,16 @@
+-- Migration: 049_album_playlists.sql
+-- Description: Link playlists to albums for the playlist auto-generated on album creation
+-- Date: 2026-10-15
+
+ALTER TABLE playlists
+    ADD COLUMN IF NOT EXISTS album_id UUID REFERENCES albums(id) ON DELETE CASCADE,
+    ADD COLUMN IF NOT EXISTS is_auto_generated BOOLEAN NOT NULL DEFAULT false;
+
+CREATE INDEX IF NOT EXISTS idx_playlists_album_id ON playlists(album_id);
+
+-- One auto-generated playlist per album, so a redelivered AlbumCreated is a no-op
+CREATE UNIQUE INDEX IF NOT EXISTS idx_playlists_album_auto_generated
+    ON playlists(album_id) WHERE is_auto_generated;
+
+COMMENT ON COLUMN playlists.album_id IS 'Álbum del que procede la playlist (si la hay)';
+COMMENT ON COLUMN playlists.is_auto_generated IS 'Playlist creada automáticamente al crear el álbum';
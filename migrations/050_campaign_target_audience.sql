@@ -0,0 +1,8 @@
+-- Migration: 050_campaign_target_audience.sql
+-- Description: Store campaign target audiences and the listen country used to match users against them
+-- Date: 2026-10-15
+
+ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS target_audience JSONB;
+
+-- Declared in 012 but skipped there because 006 had already created the table
+ALTER TABLE listen_sessions ADD COLUMN IF NOT EXISTS location_country VARCHAR(10);
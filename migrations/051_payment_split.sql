@@ -0,0 +1,12 @@
+-- Migration: 051_payment_split.sql
+-- Description: Store the artist and platform shares of each payment after the platform fee
+-- Date: 2026-10-15
+
+ALTER TABLE payments ADD COLUMN IF NOT EXISTS artist_share NUMERIC(20,9);
+ALTER TABLE payments ADD COLUMN IF NOT EXISTS platform_share NUMERIC(20,9);
+
+-- Payments saved before this migration keep their split in net_amount_value / platform_fee_value
+UPDATE payments
+SET artist_share = net_amount_value,
+    platform_share = COALESCE(platform_fee_value, 0)
+WHERE artist_share IS NULL;
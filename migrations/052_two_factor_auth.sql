@@ -0,0 +1,31 @@
+-- Migration: 052_two_factor_auth.sql
+-- Description: TOTP two-factor authentication and single-use recovery codes
+-- Date: 2026-10-15
+
+-- =============================================================================
+-- TWO-FACTOR AUTHENTICATION
+-- =============================================================================
+-- One row per user that started the setup; 2FA is only required at login
+-- once `enabled_at` is set. `last_used_step` is the last accepted TOTP step,
+-- so a code cannot be used twice.
+
+CREATE TABLE IF NOT EXISTS user_two_factor (
+    user_id UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    secret VARCHAR(64) NOT NULL,
+    enabled_at TIMESTAMPTZ,
+    last_used_step BIGINT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Only the SHA-256 of each recovery code is stored
+CREATE TABLE IF NOT EXISTS user_recovery_codes (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    code_hash CHAR(64) NOT NULL,
+    used_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, code_hash)
+);
+
+COMMENT ON COLUMN user_two_factor.secret IS 'Secreto TOTP en base32';
+COMMENT ON COLUMN user_recovery_codes.used_at IS 'Momento en que se usó el código (de un solo uso)';
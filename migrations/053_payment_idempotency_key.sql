@@ -0,0 +1,22 @@
+-- Migration: 053_payment_idempotency_key.sql
+-- Description: Idempotency key per payment, so a retried request cannot charge twice
+-- Date: 2026-10-15
+
+ALTER TABLE payments ADD COLUMN IF NOT EXISTS idempotency_key VARCHAR(255);
+
+-- Keys stored in metadata before this migration; if two payments share one,
+-- only the earliest keeps it
+UPDATE payments p
+SET idempotency_key = LEFT(p.metadata->>'idempotency_key', 255)
+WHERE p.idempotency_key IS NULL
+  AND p.metadata->>'idempotency_key' IS NOT NULL
+  AND p.id = (
+      SELECT first.id FROM payments first
+      WHERE first.metadata->>'idempotency_key' = p.metadata->>'idempotency_key'
+      ORDER BY first.created_at, first.id
+      LIMIT 1
+  );
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_payments_idempotency_key
+    ON payments(idempotency_key)
+    WHERE idempotency_key IS NOT NULL;
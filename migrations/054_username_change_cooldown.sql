@@ -0,0 +1,5 @@
+-- Migration: 054_username_change_cooldown.sql
+-- Description: Last username change, for the cooldown between changes
+-- Date: 2026-10-15
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS username_changed_at TIMESTAMPTZ;
@@ -0,0 +1,26 @@
+-- Migration: 055_account_deletion_and_data_export.sql
+-- Description: Soft-deleted accounts and asynchronous personal data exports (GDPR)
+-- Date: 2026-10-15
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS is_active BOOLEAN NOT NULL DEFAULT TRUE;
+ALTER TABLE users ADD COLUMN IF NOT EXISTS deleted_at TIMESTAMPTZ;
+
+-- Shared account for records kept only for statistics (listen sessions):
+-- every deleted user's rows point here, so they no longer link to anyone
+INSERT INTO users (id, email, username, password_hash, is_active, deleted_at)
+VALUES ('00000000-0000-0000-0000-000000000000', 'deleted-user@deleted.vibestream.app', 'deleted_user', '!deleted', FALSE, NOW())
+ON CONFLICT (id) DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS user_data_exports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'ready', 'failed')),
+    archive JSONB,
+    error TEXT,
+    requested_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMPTZ,
+    -- The archive is removed after this date
+    expires_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_data_exports_user_id ON user_data_exports(user_id, requested_at DESC);
@@ -0,0 +1,34 @@
+-- Migration: 056_outbound_webhooks.sql
+-- Description: Webhooks that notify third-party integrations of payment events, with their delivery log
+-- Date: 2026-10-15
+
+CREATE TABLE IF NOT EXISTS webhooks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    endpoint_url TEXT NOT NULL,
+    -- HMAC key for X-VibeStream-Signature; the integrator keeps a copy
+    secret TEXT NOT NULL,
+    subscribed_events TEXT[] NOT NULL,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    -- Failed deliveries in a row; the webhook is deactivated at 5
+    consecutive_failures INTEGER NOT NULL DEFAULT 0 CHECK (consecutive_failures >= 0),
+    created_by UUID NOT NULL REFERENCES users(id),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhooks_active_events ON webhooks USING GIN (subscribed_events) WHERE is_active;
+
+-- One row per HTTP attempt (retries included)
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    webhook_id UUID NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+    event_id UUID NOT NULL,
+    event_type VARCHAR(50) NOT NULL,
+    attempt INTEGER NOT NULL CHECK (attempt >= 1),
+    status_code INTEGER,
+    succeeded BOOLEAN NOT NULL,
+    error TEXT,
+    attempted_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_webhook ON webhook_deliveries(webhook_id, attempted_at DESC);
@@ -0,0 +1,39 @@
+-- Migration: 057_follows.sql
+-- Description: Follow graph in `follows` (replaces user_followers) and denormalized follower counts on artists
+-- Date: 2026-10-15
+
+CREATE TABLE IF NOT EXISTS follows (
+    follower_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    followee_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (follower_id, followee_id),
+    CHECK (follower_id != followee_id)
+);
+
+-- La PK cubre "a quién sigue X"; este índice cubre "quién sigue a X"
+CREATE INDEX IF NOT EXISTS idx_follows_followee ON follows(followee_id, created_at DESC);
+
+DO $$
+BEGIN
+    IF EXISTS (SELECT 1 FROM information_schema.tables WHERE table_name = 'user_followers') THEN
+        INSERT INTO follows (follower_id, followee_id, created_at)
+        SELECT follower_id, followee_id, COALESCE(created_at, NOW())
+        FROM user_followers
+        ON CONFLICT (follower_id, followee_id) DO NOTHING;
+
+        DROP TABLE user_followers;
+    END IF;
+END $$;
+
+-- Seguidores del usuario del artista; lo mantienen follow/unfollow en la misma transacción
+ALTER TABLE artists ADD COLUMN IF NOT EXISTS follower_count BIGINT NOT NULL DEFAULT 0;
+
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_constraint WHERE conname = 'chk_artists_follower_count') THEN
+        ALTER TABLE artists ADD CONSTRAINT chk_artists_follower_count CHECK (follower_count >= 0);
+    END IF;
+END $$;
+
+UPDATE artists a
+SET follower_count = (SELECT COUNT(*) FROM follows f WHERE f.followee_id = a.user_id);
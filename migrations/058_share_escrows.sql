@@ -0,0 +1,27 @@
+-- Migration: 058_share_escrows.sql
+-- Description: Escrow for peer-to-peer share transfers (seller shares locked until the buyer pays)
+-- Date: 2026-10-15
+
+CREATE TABLE IF NOT EXISTS share_escrows (
+    escrow_id UUID PRIMARY KEY,
+    venture_id UUID NOT NULL REFERENCES artist_ventures(id) ON DELETE CASCADE,
+    seller_id UUID NOT NULL REFERENCES users(id),
+    seller_investment_id UUID NOT NULL REFERENCES fan_investments(id) ON DELETE CASCADE,
+    buyer_id UUID NOT NULL REFERENCES users(id),
+    quantity DOUBLE PRECISION NOT NULL CHECK (quantity > 0),
+    price DOUBLE PRECISION NOT NULL CHECK (price >= 0),
+    payment_id UUID,
+    -- Inversión creada para el comprador al completarse
+    buyer_investment_id UUID REFERENCES fan_investments(id) ON DELETE SET NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'completed', 'cancelled')),
+    expires_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMP WITH TIME ZONE,
+    CONSTRAINT chk_share_escrows_distinct_parties CHECK (seller_id != buyer_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_share_escrows_seller_investment ON share_escrows(seller_investment_id);
+CREATE INDEX IF NOT EXISTS idx_share_escrows_pending_expiry
+    ON share_escrows(expires_at) WHERE status = 'pending';
+
+COMMENT ON TABLE share_escrows IS 'Seller shares held in escrow while the buyer of a peer-to-peer transfer pays';
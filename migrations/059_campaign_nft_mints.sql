@@ -0,0 +1,36 @@
+-- Migration: 059_campaign_nft_mints.sql
+-- Description: Campaign NFT collection settings and per-participant Solana mint tracking
+-- Date: 2026-10-15
+
+-- La tabla de participantes la usa PostgresCampaignParticipationRepository pero
+-- ninguna migración la creaba
+CREATE TABLE IF NOT EXISTS campaign_participants (
+    campaign_id UUID NOT NULL REFERENCES campaigns(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    joined_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (campaign_id, user_id)
+);
+
+-- Colección NFT de la campaña; sin nft_collection_size el límite es max_nfts
+ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS nft_collection_size INTEGER CHECK (nft_collection_size > 0);
+ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS nft_metadata_url TEXT;
+ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS nfts_minted INTEGER NOT NULL DEFAULT 0 CHECK (nfts_minted >= 0);
+
+-- Un NFT por participación. nft_mint_request_id es la clave de idempotencia
+-- que viaja en el mensaje a solana-integration
+ALTER TABLE campaign_participants ADD COLUMN IF NOT EXISTS nft_mint_request_id UUID UNIQUE;
+ALTER TABLE campaign_participants ADD COLUMN IF NOT EXISTS nft_mint_status VARCHAR(20)
+    CHECK (nft_mint_status IN ('pending', 'minted', 'failed'));
+ALTER TABLE campaign_participants ADD COLUMN IF NOT EXISTS nft_wallet_address VARCHAR(255);
+ALTER TABLE campaign_participants ADD COLUMN IF NOT EXISTS nft_metadata JSONB;
+ALTER TABLE campaign_participants ADD COLUMN IF NOT EXISTS nft_mint_attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE campaign_participants ADD COLUMN IF NOT EXISTS nft_mint_address VARCHAR(255);
+ALTER TABLE campaign_participants ADD COLUMN IF NOT EXISTS nft_mint_signature VARCHAR(255);
+ALTER TABLE campaign_participants ADD COLUMN IF NOT EXISTS nft_mint_error TEXT;
+ALTER TABLE campaign_participants ADD COLUMN IF NOT EXISTS nft_minted_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX IF NOT EXISTS idx_campaign_participants_user ON campaign_participants(user_id);
+CREATE INDEX IF NOT EXISTS idx_campaign_participants_pending_mints
+    ON campaign_participants(campaign_id) WHERE nft_mint_status = 'pending';
+
+COMMENT ON COLUMN campaigns.nfts_minted IS 'NFTs reserved or minted for participants; never exceeds the collection size';
@@ -0,0 +1,46 @@
+-- Migration: 060_campaign_analytics_projection.sql
+-- Description: Campaign activity event store and the analytics projections built from it
+-- Date: 2026-10-15
+
+ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS budget NUMERIC(15,2) CHECK (budget >= 0);
+
+-- Event store: fuente de verdad para reconstruir las proyecciones
+CREATE TABLE IF NOT EXISTS campaign_events (
+    sequence BIGSERIAL PRIMARY KEY,
+    event_id UUID NOT NULL UNIQUE,
+    campaign_id UUID NOT NULL REFERENCES campaigns(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    event_type VARCHAR(50) NOT NULL,
+    event_data JSONB NOT NULL,
+    occurred_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_campaign_events_campaign ON campaign_events(campaign_id, sequence);
+
+-- Proyección por participante: etapa del embudo alcanzada y región
+CREATE TABLE IF NOT EXISTS campaign_analytics_participants (
+    campaign_id UUID NOT NULL REFERENCES campaigns(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL,
+    funnel_stage SMALLINT NOT NULL CHECK (funnel_stage BETWEEN 1 AND 4), -- 1 viewed, 2 participated, 3 rewarded, 4 nft claimed
+    first_participated_at TIMESTAMP WITH TIME ZONE,
+    region VARCHAR(100),
+    PRIMARY KEY (campaign_id, user_id)
+);
+
+CREATE TABLE IF NOT EXISTS campaign_analytics_actions (
+    campaign_id UUID NOT NULL REFERENCES campaigns(id) ON DELETE CASCADE,
+    action_type VARCHAR(50) NOT NULL,
+    action_count BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (campaign_id, action_type)
+);
+
+-- last_sequence evita aplicar dos veces el mismo evento
+CREATE TABLE IF NOT EXISTS campaign_analytics_totals (
+    campaign_id UUID PRIMARY KEY REFERENCES campaigns(id) ON DELETE CASCADE,
+    reward_spent NUMERIC(15,2) NOT NULL DEFAULT 0,
+    last_sequence BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE campaign_events IS 'Append-only campaign activity events (views, actions, rewards, NFT claims)';
@@ -0,0 +1,18 @@
+-- Migration: 061_campaign_budget_tracking.sql
+-- Description: Budget accounting for rewarded campaign participations
+-- Date: 2026-10-15
+
+-- 10.00 es la recompensa fija que se pagaba hasta ahora
+ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS reward_per_action NUMERIC(15,2) NOT NULL DEFAULT 10.00
+    CHECK (reward_per_action >= 0);
+ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS budget_spent NUMERIC(15,2) NOT NULL DEFAULT 0
+    CHECK (budget_spent >= 0);
+
+-- Red de seguridad: el presupuesto restante nunca es negativo
+ALTER TABLE campaigns DROP CONSTRAINT IF EXISTS chk_campaigns_budget_not_overspent;
+ALTER TABLE campaigns ADD CONSTRAINT chk_campaigns_budget_not_overspent
+    CHECK (budget IS NULL OR budget_spent <= budget);
+
+-- Recompensa pagada por cada participación
+ALTER TABLE campaign_participants ADD COLUMN IF NOT EXISTS reward_earned NUMERIC(15,2) NOT NULL DEFAULT 0;
+ALTER TABLE campaign_participants ADD COLUMN IF NOT EXISTS rewarded_actions INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,16 @@
+-- Migration: 062_campaign_scheduling.sql
+-- Description: Automatic activation and completion of campaigns by date
+-- Date: 2026-10-15
+
+-- Opt-in del artista: sin auto_start la activación sigue siendo manual
+ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS auto_start BOOLEAN NOT NULL DEFAULT FALSE;
+-- Repartir NFTs a los participantes que aún no tienen uno al terminar
+ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS distribute_nfts_on_end BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS activated_at TIMESTAMP WITH TIME ZONE;
+ALTER TABLE campaigns ADD COLUMN IF NOT EXISTS completed_at TIMESTAMP WITH TIME ZONE;
+
+-- Búsquedas del scheduler
+CREATE INDEX IF NOT EXISTS idx_campaigns_auto_start_due
+    ON campaigns(start_date) WHERE status = 'Draft' AND auto_start;
+CREATE INDEX IF NOT EXISTS idx_campaigns_running_end_date
+    ON campaigns(end_date) WHERE status IN ('Active', 'Paused');
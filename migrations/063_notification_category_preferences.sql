@@ -0,0 +1,22 @@
+-- Migration: 063_notification_category_preferences.sql
+-- Description: Per-category channel preferences (in-app, email, push) and read-state indexes for notifications
+-- Date: 2026-10-15
+
+-- Documento de preferencias por categoría:
+-- {"campaigns": {"in_app": true, "email": false, "push": true}, ...}
+-- Las categorías ausentes solo se entregan in-app
+ALTER TABLE notification_preferences
+    ADD COLUMN IF NOT EXISTS categories JSONB NOT NULL DEFAULT '{}'::jsonb;
+
+-- El estado se guardaba con mayúsculas ('Read') desde el repositorio
+UPDATE notifications SET status = LOWER(status) WHERE status <> LOWER(status);
+
+-- Listado de no leídas del usuario, de más reciente a más antigua
+CREATE INDEX IF NOT EXISTS idx_notifications_user_unread
+    ON notifications(user_id, created_at DESC)
+    WHERE read_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_notifications_user_created_at
+    ON notifications(user_id, created_at DESC);
+
+COMMENT ON COLUMN notification_preferences.categories IS 'Canales por categoría (in_app, email, push); las categorías ausentes solo llegan in-app';
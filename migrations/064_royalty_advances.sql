@@ -0,0 +1,33 @@
+-- Migration: 064_royalty_advances.sql
+-- Description: Royalty advances and the repayments withheld from royalty credits
+-- Date: 2026-10-15
+
+CREATE TABLE IF NOT EXISTS royalty_advances (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    artist_id UUID NOT NULL,
+    advance_amount DECIMAL(15, 6) NOT NULL CHECK (advance_amount > 0),
+    repayment_rate DOUBLE PRECISION NOT NULL CHECK (repayment_rate > 0 AND repayment_rate < 1),
+    outstanding_balance DECIMAL(15, 6) NOT NULL CHECK (outstanding_balance >= 0),
+    currency VARCHAR(10) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'active' CHECK (status IN ('active', 'repaid', 'defaulted')),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    repaid_at TIMESTAMP WITH TIME ZONE,
+
+    CONSTRAINT royalty_advances_repaid CHECK ((status = 'repaid') = (repaid_at IS NOT NULL))
+);
+
+-- Un solo adelanto en amortización por artista
+CREATE UNIQUE INDEX IF NOT EXISTS idx_royalty_advances_one_active
+    ON royalty_advances(artist_id)
+    WHERE status = 'active';
+CREATE INDEX IF NOT EXISTS idx_royalty_advances_artist ON royalty_advances(artist_id, created_at DESC);
+
+-- Lo retenido de cada pago de royalty (source_id del ledger); una sola vez por pago
+CREATE TABLE IF NOT EXISTS royalty_advance_repayments (
+    source_id UUID PRIMARY KEY,
+    advance_id UUID NOT NULL REFERENCES royalty_advances(id) ON DELETE CASCADE,
+    amount DECIMAL(15, 6) NOT NULL CHECK (amount > 0),
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_royalty_advance_repayments_advance ON royalty_advance_repayments(advance_id);
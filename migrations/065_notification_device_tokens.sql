@@ -0,0 +1,19 @@
+-- Migration: 065_notification_device_tokens.sql
+-- Description: Push device tokens (FCM / APNs) registered by users
+-- Date: 2026-10-15
+
+-- Un token identifica una instalación de la app: si otra cuenta lo registra
+-- en el mismo dispositivo, la fila pasa a esa cuenta
+CREATE TABLE IF NOT EXISTS notification_device_tokens (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    platform VARCHAR(16) NOT NULL CHECK (platform IN ('android', 'ios')),
+    token TEXT NOT NULL UNIQUE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_notification_device_tokens_user_id
+    ON notification_device_tokens(user_id);
+
+COMMENT ON TABLE notification_device_tokens IS 'Tokens de push por dispositivo; los rechazados por el proveedor como inválidos se borran';
@@ -0,0 +1,33 @@
+-- Migration: 066_notification_email_digests.sql
+-- Description: Email digest preferences and the queue of notifications waiting for their digest
+-- Date: 2026-10-15
+
+-- Categorías cuyo email se agrupa en un resumen: ["rewards", "ventures", ...]
+ALTER TABLE notification_preferences
+    ADD COLUMN IF NOT EXISTS digest_categories JSONB NOT NULL DEFAULT '[]'::jsonb,
+    ADD COLUMN IF NOT EXISTS digest_frequency VARCHAR(16) NOT NULL DEFAULT 'weekly'
+        CHECK (digest_frequency IN ('daily', 'weekly'));
+
+-- Una fila por notificación pendiente; se borra al enviarse el resumen.
+-- El periodo se fija al encolar, con la periodicidad que tenía el usuario.
+CREATE TABLE IF NOT EXISTS notification_digest_entries (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    notification_id UUID NOT NULL UNIQUE,
+    category VARCHAR(32) NOT NULL,
+    title TEXT NOT NULL,
+    message TEXT NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    period_start TIMESTAMP WITH TIME ZONE NOT NULL,
+    period_end TIMESTAMP WITH TIME ZONE NOT NULL,
+    -- Reservada por el job que está enviando el resumen
+    claimed_at TIMESTAMP WITH TIME ZONE
+);
+
+CREATE INDEX IF NOT EXISTS idx_notification_digest_entries_period_end
+    ON notification_digest_entries(period_end);
+
+CREATE INDEX IF NOT EXISTS idx_notification_digest_entries_user_id
+    ON notification_digest_entries(user_id);
+
+COMMENT ON COLUMN notification_preferences.digest_categories IS 'Categorías cuyo email llega en un resumen diario o semanal';
@@ -0,0 +1,26 @@
+-- Migration: 067_song_similarities.sql
+-- Description: Audio quality of songs and precomputed content-based song similarities
+-- Date: 2026-10-15
+
+ALTER TABLE songs
+ADD COLUMN IF NOT EXISTS audio_quality VARCHAR(20);
+
+ALTER TABLE songs
+DROP CONSTRAINT IF EXISTS songs_audio_quality_check;
+
+ALTER TABLE songs
+ADD CONSTRAINT songs_audio_quality_check
+CHECK (audio_quality IS NULL OR audio_quality IN ('low', 'medium', 'high', 'lossless'));
+
+-- Vecinos más parecidos de cada canción (score 0-1, 1 = mismas características)
+CREATE TABLE IF NOT EXISTS song_similarities (
+    song_a_id UUID NOT NULL REFERENCES songs(id) ON DELETE CASCADE,
+    song_b_id UUID NOT NULL REFERENCES songs(id) ON DELETE CASCADE,
+    score REAL NOT NULL CHECK (score BETWEEN 0 AND 1),
+    computed_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (song_a_id, song_b_id),
+    CHECK (song_a_id <> song_b_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_song_similarities_score
+    ON song_similarities(song_a_id, score DESC);
@@ -0,0 +1,17 @@
+-- Migration: 068_reward_distribution_recipients.sql
+-- Description: Recipient and shares of each reward distribution, plus the status/events columns the repository writes
+-- Date: 2026-10-15
+
+ALTER TABLE reward_distributions
+ADD COLUMN IF NOT EXISTS session_id UUID,
+ADD COLUMN IF NOT EXISTS user_id UUID,
+ADD COLUMN IF NOT EXISTS artist_id UUID,
+ADD COLUMN IF NOT EXISTS user_share DECIMAL(15,4) NOT NULL DEFAULT 0 CHECK (user_share >= 0),
+ADD COLUMN IF NOT EXISTS artist_share DECIMAL(15,4) NOT NULL DEFAULT 0 CHECK (artist_share >= 0),
+ADD COLUMN IF NOT EXISTS platform_fee DECIMAL(15,4) NOT NULL DEFAULT 0 CHECK (platform_fee >= 0),
+ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'active',
+ADD COLUMN IF NOT EXISTS events JSONB;
+
+-- Total ganado por usuario
+CREATE INDEX IF NOT EXISTS idx_reward_distributions_user_id
+    ON reward_distributions(user_id) WHERE user_id IS NOT NULL;
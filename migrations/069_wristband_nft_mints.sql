@@ -0,0 +1,21 @@
+-- Migration: 069_wristband_nft_mints.sql
+-- Description: Solana mint tracking for NFT wristbands
+-- Date: 2026-10-15
+
+-- Un NFT por pulsera. nft_mint_request_id es la clave de idempotencia que viaja
+-- en el mensaje a solana-integration; nft_token_id y transaction_hash guardan
+-- la dirección del mint y la firma cuando termina
+ALTER TABLE nft_wristbands ADD COLUMN IF NOT EXISTS fan_wallet_address VARCHAR(255);
+ALTER TABLE nft_wristbands ADD COLUMN IF NOT EXISTS nft_mint_request_id UUID UNIQUE;
+ALTER TABLE nft_wristbands ADD COLUMN IF NOT EXISTS nft_mint_status VARCHAR(20)
+    CHECK (nft_mint_status IN ('pending', 'minted', 'failed'));
+ALTER TABLE nft_wristbands ADD COLUMN IF NOT EXISTS nft_custody_wallet VARCHAR(255);
+ALTER TABLE nft_wristbands ADD COLUMN IF NOT EXISTS nft_metadata JSONB;
+ALTER TABLE nft_wristbands ADD COLUMN IF NOT EXISTS nft_mint_attempts INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE nft_wristbands ADD COLUMN IF NOT EXISTS nft_mint_error TEXT;
+ALTER TABLE nft_wristbands ADD COLUMN IF NOT EXISTS nft_minted_at TIMESTAMP WITH TIME ZONE;
+ALTER TABLE nft_wristbands ADD COLUMN IF NOT EXISTS nft_transferred_to VARCHAR(255);
+ALTER TABLE nft_wristbands ADD COLUMN IF NOT EXISTS nft_transfer_requested_at TIMESTAMP WITH TIME ZONE;
+
+CREATE INDEX IF NOT EXISTS idx_nft_wristbands_pending_mints
+    ON nft_wristbands(created_at) WHERE nft_mint_status = 'pending';
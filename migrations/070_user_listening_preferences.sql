@@ -0,0 +1,14 @@
+-- Migration: 070_user_listening_preferences.sql
+-- Description: Listening preference vector of each user, refreshed from listen_events
+-- Date: 2026-10-15
+
+-- listening_preferences_listens es cuántos listen_events ya están incluidos en
+-- el vector; el refresco solo procesa los siguientes
+ALTER TABLE users
+ADD COLUMN IF NOT EXISTS listening_preferences JSONB,
+ADD COLUMN IF NOT EXISTS listening_preferences_listens INTEGER NOT NULL DEFAULT 0,
+ADD COLUMN IF NOT EXISTS listening_preferences_updated_at TIMESTAMP WITH TIME ZONE;
+
+-- Historial de un usuario en orden para el refresco incremental
+CREATE INDEX IF NOT EXISTS idx_listen_events_user_created
+    ON listen_events(user_id, created_at, id) WHERE user_id IS NOT NULL;
@@ -0,0 +1,49 @@
+-- Migration: 071_fan_loyalty_benefits.sql
+-- Description: Fan loyalty benefits catalog, unlocked benefits and redemptions
+-- Date: 2026-10-15
+
+-- Catálogo gestionado por admins. El nombre es el que aparece en
+-- fan_verifications.benefits_unlocked. remaining_quantity es NULL si no hay
+-- límite; el CHECK impide venderlo por debajo de cero
+CREATE TABLE IF NOT EXISTS fan_benefit_catalog (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    name VARCHAR(255) NOT NULL UNIQUE,
+    description TEXT,
+    usage_type VARCHAR(20) NOT NULL CHECK (usage_type IN ('single_use', 'multi_use', 'time_windowed')),
+    uses_per_fan INTEGER CHECK (uses_per_fan > 0),
+    starts_at TIMESTAMP WITH TIME ZONE,
+    ends_at TIMESTAMP WITH TIME ZONE,
+    total_quantity INTEGER CHECK (total_quantity > 0),
+    remaining_quantity INTEGER CHECK (remaining_quantity >= 0),
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    CHECK (usage_type <> 'multi_use' OR uses_per_fan IS NOT NULL),
+    CHECK (usage_type <> 'time_windowed' OR (starts_at IS NOT NULL AND ends_at > starts_at))
+);
+
+-- Beneficios desbloqueados por fan; uses_remaining es NULL en los de ventana
+CREATE TABLE IF NOT EXISTS fan_benefits (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    fan_id UUID NOT NULL,
+    benefit_id UUID NOT NULL REFERENCES fan_benefit_catalog(id) ON DELETE CASCADE,
+    verification_id VARCHAR(255) NOT NULL,
+    uses_remaining INTEGER CHECK (uses_remaining >= 0),
+    unlocked_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    UNIQUE (fan_id, benefit_id)
+);
+
+CREATE TABLE IF NOT EXISTS fan_benefit_redemptions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    fan_benefit_id UUID NOT NULL REFERENCES fan_benefits(id) ON DELETE CASCADE,
+    benefit_id UUID NOT NULL REFERENCES fan_benefit_catalog(id) ON DELETE CASCADE,
+    fan_id UUID NOT NULL,
+    venue_id VARCHAR(255) NOT NULL,
+    staff_id UUID NOT NULL,
+    metadata JSONB NOT NULL DEFAULT '{}'::jsonb,
+    redeemed_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_fan_benefits_fan_id ON fan_benefits(fan_id);
+CREATE INDEX IF NOT EXISTS idx_fan_benefit_redemptions_benefit ON fan_benefit_redemptions(benefit_id, redeemed_at);
+CREATE INDEX IF NOT EXISTS idx_fan_benefit_redemptions_fan ON fan_benefit_redemptions(fan_id, redeemed_at);
@@ -0,0 +1,21 @@
+-- Migration: 072_campaign_analytics_snapshots.sql
+-- Description: Daily campaign analytics snapshots for historical reporting
+-- Date: 2026-10-15
+
+-- Métricas acumuladas al final de snapshot_date (UTC). Una fila por campaña
+-- y día; el job no sobrescribe las que ya existen
+CREATE TABLE IF NOT EXISTS campaign_analytics_snapshots (
+    campaign_id UUID NOT NULL REFERENCES campaigns(id) ON DELETE CASCADE,
+    snapshot_date DATE NOT NULL,
+    participants_count BIGINT NOT NULL DEFAULT 0 CHECK (participants_count >= 0),
+    nfts_minted BIGINT NOT NULL DEFAULT 0 CHECK (nfts_minted >= 0),
+    unique_listeners BIGINT NOT NULL DEFAULT 0 CHECK (unique_listeners >= 0),
+    total_listen_seconds BIGINT NOT NULL DEFAULT 0 CHECK (total_listen_seconds >= 0),
+    budget_spent NUMERIC(15,2) NOT NULL DEFAULT 0,
+    captured_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (campaign_id, snapshot_date)
+);
+
+-- Escuchas de la canción de la campaña dentro de su ventana
+CREATE INDEX IF NOT EXISTS idx_listen_sessions_song_started ON listen_sessions(song_id, started_at);
+CREATE INDEX IF NOT EXISTS idx_campaign_events_campaign_type ON campaign_events(campaign_id, event_type, occurred_at);
@@ -0,0 +1,28 @@
+-- Migration: 073_video_transcode_jobs.sql
+-- Description: Per-quality video transcoding jobs with progress and failures
+-- Date: 2026-10-15
+
+-- Un trabajo por vídeo (CID del manifiesto original) y calidad. Los completados
+-- guardan la rendición troceada; los interrumpidos vuelven a pending cuando
+-- dejan de actualizar updated_at
+CREATE TABLE IF NOT EXISTS video_transcode_jobs (
+    id UUID PRIMARY KEY,
+    video_hash VARCHAR(255) NOT NULL,
+    quality VARCHAR(20) NOT NULL CHECK (quality IN ('low', 'medium', 'high', 'ultra')),
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'processing', 'completed', 'failed', 'skipped')),
+    progress REAL NOT NULL DEFAULT 0 CHECK (progress >= 0 AND progress <= 1),
+    attempts INTEGER NOT NULL DEFAULT 0 CHECK (attempts >= 0),
+    error TEXT,
+    rendition JSONB,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    completed_at TIMESTAMP WITH TIME ZONE,
+    UNIQUE (video_hash, quality),
+    CHECK (status <> 'completed' OR rendition IS NOT NULL)
+);
+
+CREATE INDEX IF NOT EXISTS idx_video_transcode_jobs_pending
+    ON video_transcode_jobs(created_at) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_video_transcode_jobs_processing
+    ON video_transcode_jobs(updated_at) WHERE status = 'processing';
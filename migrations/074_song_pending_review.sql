@@ -0,0 +1,8 @@
+-- Migration: 074_song_pending_review.sql
+-- Description: Songs held for review after content moderation flags their metadata
+-- Date: 2026-10-15
+
+ALTER TABLE songs
+ADD COLUMN IF NOT EXISTS is_pending_review BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX IF NOT EXISTS idx_songs_pending_review ON songs(created_at) WHERE is_pending_review;
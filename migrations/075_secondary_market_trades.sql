@@ -0,0 +1,21 @@
+-- Migration: 075_secondary_market_trades.sql
+-- Description: Secondary market trade history (completed fan-to-fan share transfers) for price history
+-- Date: 2026-10-15
+
+CREATE TABLE IF NOT EXISTS secondary_market_trades (
+    -- Escrow de la transferencia: un ShareTransferred repetido no duplica la operación
+    id UUID PRIMARY KEY,
+    venture_id UUID NOT NULL REFERENCES artist_ventures(id) ON DELETE CASCADE,
+    seller_id UUID NOT NULL REFERENCES users(id),
+    buyer_id UUID NOT NULL REFERENCES users(id),
+    shares_quantity DOUBLE PRECISION NOT NULL CHECK (shares_quantity > 0),
+    price_per_share DOUBLE PRECISION NOT NULL CHECK (price_per_share >= 0),
+    executed_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+-- Historial paginado y precio vigente en un instante dado
+CREATE INDEX IF NOT EXISTS idx_secondary_market_trades_venture_executed
+    ON secondary_market_trades USING BTREE (venture_id, executed_at);
+
+COMMENT ON TABLE secondary_market_trades IS 'Completed peer-to-peer share transfers, one per share escrow';
@@ -0,0 +1,35 @@
+-- Migration: 076_outbox_events.sql
+-- Description: Transactional outbox for domain events, relayed to the event bus / Redis streams
+-- Date: 2026-10-15
+
+CREATE TABLE IF NOT EXISTS outbox_events (
+    -- Id del evento: los consumidores descartan duplicados por él
+    id UUID PRIMARY KEY,
+    -- Orden de escritura: el relay publica en el mismo orden
+    seq BIGSERIAL NOT NULL,
+    -- Contexto que escribió el evento; cada relay solo reparte los suyos
+    context VARCHAR(50) NOT NULL,
+    event_type VARCHAR(100) NOT NULL,
+    aggregate_type VARCHAR(100) NOT NULL,
+    aggregate_id UUID NOT NULL,
+    payload JSONB NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending'
+        CHECK (status IN ('pending', 'dispatched', 'dead_letter')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    occurred_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    dispatched_at TIMESTAMP WITH TIME ZONE
+);
+
+-- Lo único que lee el relay: pendientes de su contexto por orden de escritura
+CREATE INDEX IF NOT EXISTS idx_outbox_events_pending
+    ON outbox_events (context, seq)
+    WHERE status = 'pending';
+
+CREATE INDEX IF NOT EXISTS idx_outbox_events_dead_letter
+    ON outbox_events (context, created_at)
+    WHERE status = 'dead_letter';
+
+COMMENT ON TABLE outbox_events IS 'Domain events written in the same transaction as their aggregate, published by the outbox relay';
@@ -0,0 +1,34 @@
+-- Migration: 077_event_store.sql
+-- Description: Shared append-only event store and projection checkpoints for replays
+-- Date: 2026-10-15
+
+CREATE TABLE IF NOT EXISTS events (
+    -- Posición global: las proyecciones recorren el store en este orden
+    position BIGSERIAL PRIMARY KEY,
+    event_id UUID NOT NULL UNIQUE,
+    aggregate_id UUID NOT NULL,
+    aggregate_type VARCHAR(100) NOT NULL,
+    -- Número del evento dentro del stream del agregado, desde 1
+    sequence BIGINT NOT NULL CHECK (sequence > 0),
+    -- Nombre con el que se recupera el tipo concreto del evento
+    event_type VARCHAR(100) NOT NULL,
+    event_data JSONB NOT NULL,
+    correlation_id UUID NOT NULL,
+    user_id UUID,
+    occurred_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    -- Concurrencia optimista: dos escritores no pueden ocupar la misma secuencia
+    CONSTRAINT events_aggregate_sequence_key UNIQUE (aggregate_id, sequence)
+);
+
+CREATE INDEX IF NOT EXISTS idx_events_event_type ON events (event_type, position);
+
+CREATE TABLE IF NOT EXISTS projection_checkpoints (
+    projection_name VARCHAR(100) PRIMARY KEY,
+    -- Última posición del store aplicada a la proyección
+    position BIGINT NOT NULL DEFAULT 0,
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+COMMENT ON TABLE events IS 'Append-only domain event streams, one per aggregate';
+COMMENT ON TABLE projection_checkpoints IS 'Last event store position applied by each projection';
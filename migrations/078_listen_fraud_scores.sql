@@ -0,0 +1,36 @@
+-- Migration: 078_listen_fraud_scores.sql
+-- Description: Device fingerprint fraud scores for listen sessions, kept for admin review
+-- Date: 2026-10-15
+
+CREATE TABLE IF NOT EXISTS listen_fraud_scores (
+    id UUID PRIMARY KEY,
+    user_id UUID NOT NULL,
+    -- device_id del cliente o, si no lo envía, hash de user agent + IP
+    device_key VARCHAR(300) NOT NULL,
+    user_agent_hash VARCHAR(128) NOT NULL,
+    ip_hash VARCHAR(128) NOT NULL,
+    device_id VARCHAR(128),
+    sessions_today INTEGER NOT NULL DEFAULT 0,
+    score DOUBLE PRECISION NOT NULL CHECK (score >= 0 AND score <= 1),
+    flags TEXT[] NOT NULL DEFAULT '{}',
+    -- La sesión no llegó a iniciarse
+    rejected BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    reviewed_at TIMESTAMP WITH TIME ZONE,
+    reviewed_by UUID,
+    confirmed_fraud BOOLEAN
+);
+
+-- Sesiones y cuentas por dispositivo en el día
+CREATE INDEX IF NOT EXISTS idx_listen_fraud_scores_device
+    ON listen_fraud_scores (device_key, created_at);
+
+CREATE INDEX IF NOT EXISTS idx_listen_fraud_scores_user
+    ON listen_fraud_scores (user_id, created_at DESC);
+
+-- Cola de revisión
+CREATE INDEX IF NOT EXISTS idx_listen_fraud_scores_pending_review
+    ON listen_fraud_scores (score DESC, created_at DESC)
+    WHERE reviewed_at IS NULL;
+
+COMMENT ON TABLE listen_fraud_scores IS 'Device fraud scores computed when listen sessions start, for admin review';
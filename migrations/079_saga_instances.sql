@@ -0,0 +1,41 @@
+-- Migration: 079_saga_instances.sql
+-- Description: Persisted saga instances and their transition log (share purchase saga)
+-- Date: 2026-10-15
+
+CREATE TABLE IF NOT EXISTS saga_instances (
+    id UUID PRIMARY KEY,
+    saga_type VARCHAR(100) NOT NULL,
+    -- Proceso coordinado: para la compra de participaciones, la inversión
+    correlation_id UUID NOT NULL,
+    step VARCHAR(50) NOT NULL,
+    status VARCHAR(20) NOT NULL
+        CHECK (status IN ('running', 'compensating', 'completed', 'compensated', 'failed')),
+    data JSONB NOT NULL,
+    -- Si el paso actual no avanza antes de este instante, vence
+    deadline_at TIMESTAMP WITH TIME ZONE,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    -- Concurrencia optimista entre eventos y el job de timeouts
+    version INTEGER NOT NULL DEFAULT 0,
+    created_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    CONSTRAINT saga_instances_correlation_key UNIQUE (saga_type, correlation_id)
+);
+
+-- Instancias que el job de timeouts debe revisar
+CREATE INDEX IF NOT EXISTS idx_saga_instances_due
+    ON saga_instances (saga_type, deadline_at)
+    WHERE status IN ('running', 'compensating') AND deadline_at IS NOT NULL;
+
+CREATE TABLE IF NOT EXISTS saga_transitions (
+    id BIGSERIAL PRIMARY KEY,
+    saga_id UUID NOT NULL REFERENCES saga_instances(id) ON DELETE CASCADE,
+    from_step VARCHAR(50),
+    to_step VARCHAR(50) NOT NULL,
+    status VARCHAR(20) NOT NULL,
+    -- Evento, timeout o compensación que provocó la transición
+    trigger VARCHAR(100) NOT NULL,
+    recorded_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_saga_transitions_saga ON saga_transitions (saga_id, id);
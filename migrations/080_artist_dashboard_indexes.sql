@@ -0,0 +1,12 @@
+-- Migration: 080_artist_dashboard_indexes.sql
+-- Description: Indexes backing the single-query artist dashboard
+-- Date: 2026-10-15
+
+-- Escuchas por canción con el oyente incluido: streams y oyentes mensuales sin
+-- leer la tabla
+CREATE INDEX IF NOT EXISTS idx_listen_events_song_created
+    ON listen_events(song_id, created_at) INCLUDE (user_id);
+
+-- Ganancias totales del artista, en cualquier estado
+CREATE INDEX IF NOT EXISTS idx_artist_balance_entries_artist
+    ON artist_balance_entries(artist_id) INCLUDE (amount);
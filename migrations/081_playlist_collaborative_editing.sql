@@ -0,0 +1,29 @@
+-- Migration: 081_playlist_collaborative_editing.sql
+-- Description: Collaborative playlists, who added each song and the playlist edit history
+-- Date: 2026-10-15
+
+-- En una playlist colaborativa cualquier seguidor puede añadir y quitar canciones
+ALTER TABLE playlists
+    ADD COLUMN IF NOT EXISTS is_collaborative BOOLEAN NOT NULL DEFAULT false;
+
+-- Las filas anteriores quedan sin autor: se añadieron siempre por el creador
+ALTER TABLE playlist_songs
+    ADD COLUMN IF NOT EXISTS added_by UUID REFERENCES users(id) ON DELETE SET NULL;
+
+UPDATE playlist_songs ps
+SET added_by = p.created_by
+FROM playlists p
+WHERE p.id = ps.playlist_id AND ps.added_by IS NULL;
+
+CREATE TABLE IF NOT EXISTS playlist_edit_history (
+    id BIGSERIAL PRIMARY KEY,
+    playlist_id UUID NOT NULL REFERENCES playlists(id) ON DELETE CASCADE,
+    -- Sin FK: el historial se conserva aunque el usuario se borre
+    editor_id UUID NOT NULL,
+    action VARCHAR(20) NOT NULL CHECK (action IN ('add_song', 'remove_song')),
+    song_id UUID NOT NULL,
+    occurred_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_playlist_edit_history_playlist
+    ON playlist_edit_history(playlist_id, occurred_at, id);
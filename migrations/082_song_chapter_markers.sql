@@ -0,0 +1,7 @@
+-- Migration: 082_song_chapter_markers.sql
+-- Description: Section markers (intro, verse, chorus...) detected from the audio of each song
+-- Date: 2026-10-15
+
+-- [{"position_ms": 0, "label": "intro"}, ...]; NULL hasta que se analiza el audio
+ALTER TABLE songs
+    ADD COLUMN IF NOT EXISTS chapter_markers JSONB;
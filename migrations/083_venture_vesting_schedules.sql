@@ -0,0 +1,22 @@
+-- Migration: 083_venture_vesting_schedules.sql
+-- Description: Optional vesting schedule that gradually releases the transferable shares of a venture
+-- Date: 2026-10-15
+
+-- Sin vesting_start_date el venture no tiene calendario y todo es transferible
+ALTER TABLE artist_ventures
+    ADD COLUMN IF NOT EXISTS vesting_start_date TIMESTAMP WITH TIME ZONE,
+    ADD COLUMN IF NOT EXISTS vesting_cliff_days INTEGER,
+    ADD COLUMN IF NOT EXISTS vesting_total_duration_days INTEGER,
+    ADD COLUMN IF NOT EXISTS vesting_type VARCHAR(20);
+
+ALTER TABLE artist_ventures DROP CONSTRAINT IF EXISTS chk_artist_ventures_vesting;
+ALTER TABLE artist_ventures ADD CONSTRAINT chk_artist_ventures_vesting CHECK (
+    (vesting_start_date IS NULL AND vesting_cliff_days IS NULL
+        AND vesting_total_duration_days IS NULL AND vesting_type IS NULL)
+    OR (vesting_start_date IS NOT NULL
+        AND vesting_cliff_days >= 0
+        AND vesting_total_duration_days >= vesting_cliff_days
+        AND vesting_type IN ('linear', 'cliff'))
+);
+
+COMMENT ON COLUMN artist_ventures.vesting_type IS 'linear: released pro rata after the cliff; cliff: everything released at the cliff';
@@ -0,0 +1,12 @@
+-- Migration: 084_request_ids.sql
+-- Description: Record the X-Request-Id of the originating request on idempotency keys and audit log entries
+-- Date: 2026-10-15
+
+ALTER TABLE idempotency_keys
+    ADD COLUMN IF NOT EXISTS request_id UUID;
+
+-- NULL en las entradas de procesos sin petición (p.ej. el outbox relay)
+ALTER TABLE audit_log
+    ADD COLUMN IF NOT EXISTS request_id UUID;
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_request_id ON audit_log(request_id) WHERE request_id IS NOT NULL;
@@ -0,0 +1,5 @@
+-- Migration: 085_idempotency_key_scope.sql
+-- Description: Idempotency keys are stored per user as "{user_id}:{key}"; widen the column for the prefix
+-- Date: 2026-10-15
+
+ALTER TABLE idempotency_keys ALTER COLUMN key TYPE VARCHAR(300);
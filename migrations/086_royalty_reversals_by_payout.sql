@@ -0,0 +1,24 @@
+-- Migration: 086_royalty_reversals_by_payout.sql
+-- Description: Reverse refunded royalties against distribution run payouts and debit artist balances
+-- Date: 2026-10-15
+
+-- Las reversiones apuntan al pago de royalty de la ejecución (039), no a royalty_distributions
+ALTER TABLE royalty_reversals ALTER COLUMN distribution_id DROP NOT NULL;
+ALTER TABLE royalty_reversals ALTER COLUMN artist_amount_value DROP NOT NULL;
+ALTER TABLE royalty_reversals ALTER COLUMN platform_fee_value DROP NOT NULL;
+ALTER TABLE royalty_reversals RENAME COLUMN artist_id TO recipient_id;
+
+ALTER TABLE royalty_reversals ADD COLUMN IF NOT EXISTS royalty_payout_id UUID
+    REFERENCES royalty_payouts(id) ON DELETE RESTRICT;
+ALTER TABLE royalty_reversals ADD COLUMN IF NOT EXISTS recipient_type VARCHAR(20)
+    CHECK (recipient_type IN ('artist', 'contributor', 'platform'));
+ALTER TABLE royalty_reversals ADD COLUMN IF NOT EXISTS amount DECIMAL(15, 6) CHECK (amount > 0);
+ALTER TABLE royalty_reversals ADD COLUMN IF NOT EXISTS currency VARCHAR(10);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_royalty_reversals_refund_payout
+    ON royalty_reversals(refund_id, royalty_payout_id);
+CREATE INDEX IF NOT EXISTS idx_royalty_reversals_payout ON royalty_reversals(royalty_payout_id);
+
+-- Una reversión resta del saldo del creador con una entrada negativa (source_id = reversión)
+ALTER TABLE artist_balance_entries DROP CONSTRAINT IF EXISTS artist_balance_entries_amount_check;
+ALTER TABLE artist_balance_entries ADD CONSTRAINT artist_balance_entries_amount_check CHECK (amount <> 0);
@@ -0,0 +1,19 @@
+-- Migration: 087_payout_wallet_transfers.sql
+-- Description: Artist payout transfers handed to solana-integration and their reported outcome
+-- Date: 2026-10-15
+
+-- Una fila por referencia de payout; solana-integration informa firma y estado por cola
+CREATE TABLE IF NOT EXISTS payout_wallet_transfers (
+    reference VARCHAR(100) PRIMARY KEY,
+    recipient_wallet VARCHAR(64) NOT NULL,
+    amount DECIMAL(15, 6) NOT NULL CHECK (amount > 0),
+    currency VARCHAR(10) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'queued' CHECK (status IN ('queued', 'sent', 'confirmed', 'failed')),
+    signature VARCHAR(128),
+    failure_reason TEXT,
+    queued_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_payout_wallet_transfers_signature
+    ON payout_wallet_transfers(signature) WHERE signature IS NOT NULL;
@@ -0,0 +1,381 @@
+// Campaign analytics snapshots
+//
+// Calcular las métricas en vivo recorre el event store y las escuchas de la
+// canción, lo que con miles de participantes es lento. Un job guarda cada día
+// una foto acumulada por campaña: las campañas completadas se sirven desde la
+// última foto y el histórico sale de la tabla de fotos. Las que siguen en
+// marcha se calculan en vivo.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::bounded_contexts::campaign::domain::analytics::{
+    AnalyticsSource, CampaignAnalyticsOverview, CampaignAnalyticsSnapshot,
+};
+use crate::bounded_contexts::campaign::domain::entities::CampaignStatus;
+use crate::bounded_contexts::campaign::domain::repository::{
+    CampaignAnalyticsRepository, CampaignScheduleRepository, CampaignSnapshotRepository,
+};
+use crate::shared::domain::errors::AppError;
+
+/// Campañas que se fotografían por consulta del job
+pub const SNAPSHOT_BATCH_SIZE: u32 = 200;
+/// Rango máximo de `GET /campaigns/{id}/analytics/history`
+pub const MAX_HISTORY_DAYS: i64 = 366;
+
+pub struct CampaignAnalyticsSnapshotService {
+    snapshots: Arc<dyn CampaignSnapshotRepository>,
+    analytics: Arc<dyn CampaignAnalyticsRepository>,
+    schedules: Arc<dyn CampaignScheduleRepository>,
+}
+
+impl CampaignAnalyticsSnapshotService {
+    pub fn new(
+        snapshots: Arc<dyn CampaignSnapshotRepository>,
+        analytics: Arc<dyn CampaignAnalyticsRepository>,
+        schedules: Arc<dyn CampaignScheduleRepository>,
+    ) -> Self {
+        Self { snapshots, analytics, schedules }
+    }
+
+    /// Guardar la foto de `date` de cada campaña que estuvo en marcha ese día
+    /// y aún no la tiene. Devuelve cuántas se guardaron; las que fallan se
+    /// reintentan en la siguiente pasada.
+    pub async fn take_daily_snapshots(&self, date: NaiveDate) -> Result<usize, AppError> {
+        let mut failed = HashSet::new();
+        let mut taken = 0;
+        loop {
+            let batch = self.snapshots.find_campaigns_to_snapshot(date, SNAPSHOT_BATCH_SIZE).await?;
+            let pending: Vec<Uuid> = batch.into_iter().filter(|id| !failed.contains(id)).collect();
+            if pending.is_empty() {
+                break;
+            }
+            for campaign_id in pending {
+                match self.snapshot(campaign_id, date).await {
+                    Ok(Some(_)) => taken += 1,
+                    Ok(None) => {
+                        failed.insert(campaign_id);
+                    }
+                    Err(e) => {
+                        tracing::warn!("Failed to snapshot analytics of campaign {} for {}: {}", campaign_id, date, e);
+                        failed.insert(campaign_id);
+                    }
+                }
+            }
+        }
+        Ok(taken)
+    }
+
+    /// Live metrics for running campaigns; the final snapshot for completed ones
+    pub async fn get_analytics(&self, campaign_id: Uuid, now: DateTime<Utc>) -> Result<CampaignAnalyticsOverview, AppError> {
+        let schedule = self
+            .schedules
+            .find_schedule(campaign_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+        if schedule.status == CampaignStatus::Completed {
+            // La foto del día en que terminó es la definitiva; si el job aún
+            // no la ha tomado se toma ahora
+            let final_date = schedule.end_date.date_naive().min(now.date_naive());
+            let snapshot = match self.snapshots.find_latest_snapshot(campaign_id).await? {
+                Some(snapshot) if snapshot.date >= final_date => snapshot,
+                _ => self
+                    .snapshot(campaign_id, final_date)
+                    .await?
+                    .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?,
+            };
+            return Ok(CampaignAnalyticsOverview {
+                campaign_id,
+                source: AnalyticsSource::Snapshot,
+                metrics: snapshot,
+                report: None,
+            });
+        }
+
+        let metrics = self
+            .snapshots
+            .compute_snapshot(campaign_id, now.date_naive())
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+        let report = self.analytics.load_report(campaign_id).await?;
+        Ok(CampaignAnalyticsOverview { campaign_id, source: AnalyticsSource::Live, metrics, report })
+    }
+
+    pub async fn history(
+        &self,
+        campaign_id: Uuid,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> Result<Vec<CampaignAnalyticsSnapshot>, AppError> {
+        if start_date > end_date {
+            return Err(AppError::ValidationError("start_date must not be after end_date".to_string()));
+        }
+        if (end_date - start_date).num_days() >= MAX_HISTORY_DAYS {
+            return Err(AppError::ValidationError(format!(
+                "History is limited to {} days per request",
+                MAX_HISTORY_DAYS
+            )));
+        }
+        if self.schedules.find_schedule(campaign_id).await?.is_none() {
+            return Err(AppError::NotFound(format!("Campaign {} not found", campaign_id)));
+        }
+        self.snapshots.find_snapshots(campaign_id, start_date, end_date).await
+    }
+
+    async fn snapshot(&self, campaign_id: Uuid, date: NaiveDate) -> Result<Option<CampaignAnalyticsSnapshot>, AppError> {
+        let Some(snapshot) = self.snapshots.compute_snapshot(campaign_id, date).await? else {
+            return Ok(None);
+        };
+        self.snapshots.save_snapshot(&snapshot).await?;
+        Ok(Some(snapshot))
+    }
+}
+
+/// Worker que guarda la foto diaria del día anterior. Pasa cada `interval`;
+/// las campañas que ya tienen la foto del día no se recalculan.
+pub struct CampaignAnalyticsSnapshotJob {
+    service: Arc<CampaignAnalyticsSnapshotService>,
+    interval: Duration,
+}
+
+impl CampaignAnalyticsSnapshotJob {
+    pub fn new(service: Arc<CampaignAnalyticsSnapshotService>, interval: Duration) -> Self {
+        Self { service, interval }
+    }
+
+    pub fn start(&self) -> tokio::task::JoinHandle<()> {
+        let service = Arc::clone(&self.service);
+        let interval = self.interval;
+
+        tokio::spawn(async move {
+            tracing::info!("🚀 Campaign analytics snapshot job started");
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let Some(yesterday) = Utc::now().date_naive().pred_opt() else { continue };
+                match service.take_daily_snapshots(yesterday).await {
+                    Ok(count) if count > 0 => tracing::info!("✅ Saved {} campaign analytics snapshots for {}", count, yesterday),
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Campaign analytics snapshot job failed: {:?}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::{Duration as ChronoDuration, TimeZone};
+    use std::collections::BTreeMap;
+    use std::sync::Mutex;
+    use crate::bounded_contexts::campaign::domain::analytics::{
+        CampaignActivity, CampaignActivityEvent, CampaignAnalyticsReport, CampaignListen,
+    };
+    use crate::bounded_contexts::campaign::domain::schedule::{CampaignSchedule, ScheduledTransition};
+    use crate::shared::domain::repositories::RepoResult;
+
+    /// Calcula las fotos con `CampaignAnalyticsSnapshot::calculate` sobre los
+    /// eventos y escuchas sembrados
+    #[derive(Default)]
+    struct InMemoryCampaigns {
+        schedules: Mutex<Vec<CampaignSchedule>>,
+        events: Mutex<Vec<CampaignActivityEvent>>,
+        listens: Mutex<Vec<CampaignListen>>,
+        snapshots: Mutex<BTreeMap<(Uuid, NaiveDate), CampaignAnalyticsSnapshot>>,
+        computed: Mutex<usize>,
+    }
+
+    impl InMemoryCampaigns {
+        fn schedule(&self, campaign_id: Uuid) -> Option<CampaignSchedule> {
+            self.schedules.lock().unwrap().iter().find(|s| s.campaign_id == campaign_id).cloned()
+        }
+    }
+
+    #[async_trait]
+    impl CampaignSnapshotRepository for InMemoryCampaigns {
+        async fn find_campaigns_to_snapshot(&self, date: NaiveDate, limit: u32) -> RepoResult<Vec<Uuid>> {
+            let snapshots = self.snapshots.lock().unwrap();
+            Ok(self
+                .schedules
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|s| s.status != CampaignStatus::Draft)
+                .filter(|s| s.start_date.date_naive() <= date && s.end_date.date_naive() >= date)
+                .filter(|s| !snapshots.contains_key(&(s.campaign_id, date)))
+                .map(|s| s.campaign_id)
+                .take(limit as usize)
+                .collect())
+        }
+        async fn compute_snapshot(&self, campaign_id: Uuid, date: NaiveDate) -> RepoResult<Option<CampaignAnalyticsSnapshot>> {
+            *self.computed.lock().unwrap() += 1;
+            Ok(self.schedule(campaign_id).map(|s| {
+                CampaignAnalyticsSnapshot::calculate(
+                    campaign_id,
+                    date,
+                    s.start_date,
+                    s.end_date,
+                    &self.events.lock().unwrap(),
+                    &self.listens.lock().unwrap(),
+                )
+            }))
+        }
+        async fn save_snapshot(&self, snapshot: &CampaignAnalyticsSnapshot) -> RepoResult<()> {
+            self.snapshots.lock().unwrap().entry((snapshot.campaign_id, snapshot.date)).or_insert_with(|| snapshot.clone());
+            Ok(())
+        }
+        async fn find_latest_snapshot(&self, campaign_id: Uuid) -> RepoResult<Option<CampaignAnalyticsSnapshot>> {
+            Ok(self.snapshots.lock().unwrap().values().filter(|s| s.campaign_id == campaign_id).last().cloned())
+        }
+        async fn find_snapshots(&self, campaign_id: Uuid, start_date: NaiveDate, end_date: NaiveDate) -> RepoResult<Vec<CampaignAnalyticsSnapshot>> {
+            Ok(self
+                .snapshots
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|s| s.campaign_id == campaign_id && s.date >= start_date && s.date <= end_date)
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[async_trait]
+    impl CampaignAnalyticsRepository for InMemoryCampaigns {
+        async fn append(&self, event: &CampaignActivityEvent) -> RepoResult<()> {
+            self.events.lock().unwrap().push(event.clone());
+            Ok(())
+        }
+        async fn load_report(&self, _campaign_id: Uuid) -> RepoResult<Option<CampaignAnalyticsReport>> {
+            Ok(None)
+        }
+        async fn rebuild(&self, _campaign_id: Uuid) -> RepoResult<u64> {
+            Ok(0)
+        }
+    }
+
+    #[async_trait]
+    impl CampaignScheduleRepository for InMemoryCampaigns {
+        async fn find_schedule(&self, campaign_id: Uuid) -> RepoResult<Option<CampaignSchedule>> {
+            Ok(self.schedule(campaign_id))
+        }
+        async fn find_due(&self, _now: DateTime<Utc>, _limit: u32) -> RepoResult<Vec<Uuid>> {
+            Ok(Vec::new())
+        }
+        async fn apply_due_transition(&self, _campaign_id: Uuid, _now: DateTime<Utc>) -> RepoResult<Option<(ScheduledTransition, CampaignSchedule)>> {
+            Ok(None)
+        }
+        async fn activate(&self, campaign_id: Uuid, _now: DateTime<Utc>, _force: bool) -> RepoResult<CampaignSchedule> {
+            Ok(self.schedule(campaign_id).unwrap())
+        }
+        async fn update_options(&self, campaign_id: Uuid, _auto_start: bool, _distribute_nfts_on_end: bool) -> RepoResult<CampaignSchedule> {
+            Ok(self.schedule(campaign_id).unwrap())
+        }
+    }
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, day, hour, 0, 0).unwrap()
+    }
+
+    fn day(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    fn campaign(status: CampaignStatus) -> CampaignSchedule {
+        CampaignSchedule {
+            campaign_id: Uuid::new_v4(),
+            artist_id: Uuid::new_v4(),
+            status,
+            start_date: at(1, 0),
+            end_date: at(3, 18),
+            auto_start: false,
+            distribute_nfts_on_end: false,
+        }
+    }
+
+    fn seeded(campaign_id: Uuid) -> Arc<InMemoryCampaigns> {
+        let repository = Arc::new(InMemoryCampaigns::default());
+        let [ana, ben] = [(); 2].map(|_| Uuid::new_v4());
+        let event = |user_id, activity, occurred_at| CampaignActivityEvent {
+            occurred_at,
+            ..CampaignActivityEvent::new(campaign_id, user_id, activity)
+        };
+        repository.events.lock().unwrap().extend([
+            event(ana, CampaignActivity::ActionPerformed { action_type: "listen".to_string(), region: None }, at(1, 10)),
+            event(ana, CampaignActivity::RewardGranted { amount: 10.0 }, at(1, 10)),
+            event(ben, CampaignActivity::ActionPerformed { action_type: "share".to_string(), region: None }, at(2, 10)),
+            event(ben, CampaignActivity::NftClaimed { mint_address: "Mint1".to_string() }, at(3, 10)),
+        ]);
+        repository.listens.lock().unwrap().extend([
+            CampaignListen { user_id: ana, seconds: 200, started_at: at(1, 11) },
+            CampaignListen { user_id: ben, seconds: 100, started_at: at(2, 11) },
+            CampaignListen { user_id: ben, seconds: 50, started_at: at(3, 20) },
+        ]);
+        repository
+    }
+
+    fn service(repository: &Arc<InMemoryCampaigns>) -> CampaignAnalyticsSnapshotService {
+        CampaignAnalyticsSnapshotService::new(repository.clone(), repository.clone(), repository.clone())
+    }
+
+    #[tokio::test]
+    async fn test_daily_job_saves_one_snapshot_per_campaign_and_day() {
+        let running = campaign(CampaignStatus::Active);
+        let repository = seeded(running.campaign_id);
+        repository.schedules.lock().unwrap().extend([running.clone(), campaign(CampaignStatus::Draft)]);
+        let service = service(&repository);
+
+        assert_eq!(service.take_daily_snapshots(day(1)).await.unwrap(), 1);
+        assert_eq!(service.take_daily_snapshots(day(2)).await.unwrap(), 1);
+        // Segunda pasada del mismo día: nada que recalcular
+        let computed = *repository.computed.lock().unwrap();
+        assert_eq!(service.take_daily_snapshots(day(2)).await.unwrap(), 0);
+        assert_eq!(*repository.computed.lock().unwrap(), computed);
+        // Fuera de la ventana de la campaña
+        assert_eq!(service.take_daily_snapshots(day(9)).await.unwrap(), 0);
+
+        let history = service.history(running.campaign_id, day(1), day(30)).await.unwrap();
+        let totals: Vec<_> = history.iter().map(|s| (s.date, s.participants_count, s.unique_listeners, s.total_listen_seconds)).collect();
+        assert_eq!(totals, vec![(day(1), 1, 1, 200), (day(2), 2, 2, 300)]);
+        assert_eq!(history[1].budget_spent, 10.0);
+
+        assert!(matches!(service.history(running.campaign_id, day(2), day(1)).await, Err(AppError::ValidationError(_))));
+        let too_long = day(1) + ChronoDuration::days(MAX_HISTORY_DAYS);
+        assert!(matches!(service.history(running.campaign_id, day(1), too_long).await, Err(AppError::ValidationError(_))));
+        assert!(matches!(service.history(Uuid::new_v4(), day(1), day(2)).await, Err(AppError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_running_campaigns_are_live_and_completed_ones_use_the_final_snapshot() {
+        let running = campaign(CampaignStatus::Active);
+        let repository = seeded(running.campaign_id);
+        repository.schedules.lock().unwrap().push(running.clone());
+        let service = service(&repository);
+
+        let live = service.get_analytics(running.campaign_id, at(2, 12)).await.unwrap();
+        assert_eq!(live.source, AnalyticsSource::Live);
+        assert_eq!((live.metrics.participants_count, live.metrics.nfts_minted), (2, 0));
+        assert!(repository.snapshots.lock().unwrap().is_empty());
+
+        // Completada con la foto del día 2 ya guardada: la del día 3 falta
+        service.take_daily_snapshots(day(2)).await.unwrap();
+        repository.schedules.lock().unwrap()[0].status = CampaignStatus::Completed;
+        let completed = service.get_analytics(running.campaign_id, at(5, 9)).await.unwrap();
+        assert_eq!(completed.source, AnalyticsSource::Snapshot);
+        assert_eq!(completed.metrics.date, day(3));
+        assert_eq!(completed.metrics.nfts_minted, 1);
+        // La escucha de después del final no cuenta
+        assert_eq!(completed.metrics.total_listen_seconds, 300);
+        assert!(completed.report.is_none());
+
+        let computed = *repository.computed.lock().unwrap();
+        let again = service.get_analytics(running.campaign_id, at(6, 9)).await.unwrap();
+        assert_eq!(again.metrics, completed.metrics);
+        assert_eq!(*repository.computed.lock().unwrap(), computed);
+    }
+}
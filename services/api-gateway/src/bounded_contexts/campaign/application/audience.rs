@@ -0,0 +1,51 @@
+// Campaign target audience
+//
+// El artista define a quién va dirigida la campaña (países, géneros, nivel de
+// fan). Solo los fans que cumplen todos los criterios pueden participar, y
+// antes de activarla puede consultar cuántos fans alcanzaría.
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::bounded_contexts::campaign::domain::audience::{AudienceEligibility, AudienceEstimate, AudienceMatcher};
+use crate::bounded_contexts::campaign::domain::entities::Campaign;
+use crate::bounded_contexts::campaign::domain::repository::{AudienceRepository, CampaignRepository};
+use crate::bounded_contexts::campaign::domain::value_objects::TargetAudience;
+use crate::shared::domain::errors::AppError;
+
+pub struct CampaignAudienceService {
+    campaigns: Arc<dyn CampaignRepository>,
+    audience: Arc<dyn AudienceRepository>,
+}
+
+impl CampaignAudienceService {
+    pub fn new(campaigns: Arc<dyn CampaignRepository>, audience: Arc<dyn AudienceRepository>) -> Self {
+        Self { campaigns, audience }
+    }
+
+    /// Replace the campaign's targeting; an open audience removes it
+    pub async fn set_target_audience(&self, campaign_id: Uuid, audience: TargetAudience) -> Result<Campaign, AppError> {
+        let mut campaign = self.load(campaign_id).await?;
+        campaign.set_target_audience(Some(audience));
+        self.campaigns.save(&campaign).await?;
+        Ok(campaign)
+    }
+
+    pub async fn check_eligibility(&self, campaign_id: Uuid, user_id: Uuid) -> Result<AudienceEligibility, AppError> {
+        let campaign = self.load(campaign_id).await?;
+        let profile = self.audience.find_user_profile(user_id).await?;
+        Ok(AudienceMatcher::for_campaign(&campaign).evaluate(&profile))
+    }
+
+    pub async fn estimate_reach(&self, campaign_id: Uuid) -> Result<AudienceEstimate, AppError> {
+        let campaign = self.load(campaign_id).await?;
+        self.audience.estimate_reach(campaign.target_audience()).await
+    }
+
+    async fn load(&self, campaign_id: Uuid) -> Result<Campaign, AppError> {
+        self.campaigns
+            .find_by_id(campaign_id)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError(format!("Campaign {} not found", campaign_id)))
+    }
+}
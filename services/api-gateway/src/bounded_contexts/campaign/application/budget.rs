@@ -0,0 +1,157 @@
+// Campaign reward budget
+//
+// Cada participación recompensada descuenta `reward_per_action` del presupuesto.
+// Cuando lo que queda no cubre otra recompensa la campaña se pausa, se publica
+// `CampaignBudgetDepleted` y el artista decide si recarga y reanuda.
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::bounded_contexts::campaign::domain::budget::{CampaignBudget, RewardDecision};
+use crate::bounded_contexts::campaign::domain::repository::CampaignBudgetRepository;
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventBus};
+use crate::shared::domain::errors::AppError;
+
+pub struct CampaignBudgetService {
+    budgets: Arc<dyn CampaignBudgetRepository>,
+    event_bus: Arc<dyn EventBus>,
+}
+
+impl CampaignBudgetService {
+    pub fn new(budgets: Arc<dyn CampaignBudgetRepository>, event_bus: Arc<dyn EventBus>) -> Self {
+        Self { budgets, event_bus }
+    }
+
+    pub async fn find_budget(&self, campaign_id: Uuid) -> Result<Option<CampaignBudget>, AppError> {
+        self.budgets.find_budget(campaign_id).await
+    }
+
+    /// Pay the reward of a participation and record it. Fails with
+    /// `ConflictError` when the campaign is not active (e.g. paused for lack of budget).
+    pub async fn reward_participation(&self, campaign_id: Uuid, user_id: Uuid) -> Result<f64, AppError> {
+        let (charge, budget) = self.budgets.reward_participation(campaign_id, user_id).await?;
+        if charge.depleted {
+            self.publish_depleted(&budget).await;
+        }
+
+        match charge.decision {
+            RewardDecision::Rewarded { reward } => Ok(reward),
+            RewardDecision::Rejected => Err(AppError::ConflictError(format!(
+                "Campaign {} is {} and does not accept participations",
+                campaign_id,
+                budget.status.as_str()
+            ))),
+        }
+    }
+
+    pub async fn top_up(&self, campaign_id: Uuid, amount: f64) -> Result<CampaignBudget, AppError> {
+        self.budgets.top_up(campaign_id, amount).await
+    }
+
+    pub async fn resume(&self, campaign_id: Uuid) -> Result<CampaignBudget, AppError> {
+        self.budgets.resume(campaign_id).await
+    }
+
+    async fn publish_depleted(&self, budget: &CampaignBudget) {
+        tracing::warn!("Campaign {} paused: budget depleted ({:.2} spent)", budget.campaign_id, budget.spent);
+        let event = DomainEvent::CampaignBudgetDepleted {
+            campaign_id: budget.campaign_id,
+            artist_id: budget.artist_id,
+            budget: budget.budget.unwrap_or_default(),
+            spent: budget.spent,
+            reward_per_action: budget.reward_per_action,
+            occurred_at: chrono::Utc::now(),
+        };
+        // La pausa ya está guardada: un fallo del bus no la deshace
+        if let Err(e) = self.event_bus.publish(event).await {
+            tracing::error!("Failed to publish CampaignBudgetDepleted for {}: {}", budget.campaign_id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::{Duration, Utc};
+    use std::sync::Mutex;
+    use crate::bounded_contexts::campaign::domain::budget::RewardCharge;
+    use crate::bounded_contexts::campaign::domain::entities::CampaignStatus;
+    use crate::bounded_contexts::orchestrator::EventHandler;
+    use crate::shared::domain::repositories::RepoResult;
+
+    struct InMemoryBudgets {
+        budget: Mutex<CampaignBudget>,
+    }
+
+    #[async_trait]
+    impl CampaignBudgetRepository for InMemoryBudgets {
+        async fn find_budget(&self, _campaign_id: Uuid) -> RepoResult<Option<CampaignBudget>> {
+            Ok(Some(self.budget.lock().unwrap().clone()))
+        }
+        async fn reward_participation(&self, _campaign_id: Uuid, _user_id: Uuid) -> RepoResult<(RewardCharge, CampaignBudget)> {
+            let mut budget = self.budget.lock().unwrap();
+            let charge = budget.charge_reward();
+            Ok((charge, budget.clone()))
+        }
+        async fn top_up(&self, _campaign_id: Uuid, amount: f64) -> RepoResult<CampaignBudget> {
+            let mut budget = self.budget.lock().unwrap();
+            budget.top_up(amount)?;
+            Ok(budget.clone())
+        }
+        async fn resume(&self, _campaign_id: Uuid) -> RepoResult<CampaignBudget> {
+            let mut budget = self.budget.lock().unwrap();
+            budget.resume(Utc::now())?;
+            Ok(budget.clone())
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingEventBus {
+        published: Mutex<Vec<DomainEvent>>,
+    }
+
+    #[async_trait]
+    impl EventBus for RecordingEventBus {
+        async fn publish(&self, event: DomainEvent) -> Result<(), AppError> {
+            self.published.lock().unwrap().push(event);
+            Ok(())
+        }
+        async fn subscribe(&self, _event_type: &str, _handler: Arc<dyn EventHandler>) -> Result<(), AppError> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_depletion_pauses_publishes_once_and_rejects_with_conflict() {
+        let budgets = Arc::new(InMemoryBudgets {
+            budget: Mutex::new(CampaignBudget {
+                campaign_id: Uuid::new_v4(),
+                artist_id: Uuid::new_v4(),
+                status: CampaignStatus::Active,
+                budget: Some(20.0),
+                spent: 0.0,
+                reward_per_action: 10.0,
+                end_date: Utc::now() + Duration::days(7),
+            }),
+        });
+        let event_bus = Arc::new(RecordingEventBus::default());
+        let service = CampaignBudgetService::new(budgets.clone(), event_bus.clone());
+        let campaign_id = Uuid::new_v4();
+
+        assert_eq!(service.reward_participation(campaign_id, Uuid::new_v4()).await.unwrap(), 10.0);
+        assert_eq!(service.reward_participation(campaign_id, Uuid::new_v4()).await.unwrap(), 10.0);
+        let rejected = service.reward_participation(campaign_id, Uuid::new_v4()).await;
+        assert!(matches!(rejected, Err(AppError::ConflictError(message)) if message.contains("Paused")));
+
+        let published = event_bus.published.lock().unwrap();
+        assert_eq!(published.len(), 1);
+        assert!(matches!(published[0], DomainEvent::CampaignBudgetDepleted { spent, .. } if spent == 20.0));
+        drop(published);
+
+        service.top_up(campaign_id, 10.0).await.unwrap();
+        assert_eq!(service.resume(campaign_id).await.unwrap().status, CampaignStatus::Active);
+        assert_eq!(service.reward_participation(campaign_id, Uuid::new_v4()).await.unwrap(), 10.0);
+        assert_eq!(event_bus.published.lock().unwrap().len(), 2);
+    }
+}
@@ -8,7 +8,7 @@ use crate::shared::domain::errors::AppError;
 use crate::bounded_contexts::campaign::domain::{
     entities::Campaign,
     value_objects::DateRange,
-    repository::CampaignRepository,
+    repository::{CampaignAnalyticsRepository, CampaignRepository},
 };
 use vibestream_types::{SongContract, ArtistContract};
 
@@ -159,4 +159,36 @@ impl<R: CampaignRepository + Send + Sync> CommandHandler<MintCampaignNFTCommand>
     async fn handle(&self, _cmd: MintCampaignNFTCommand) -> Result<Self::Output, AppError> {
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+// ---------------- Analytics projections ----------------
+
+/// Rebuild a campaign's analytics projections from its event store
+#[derive(Debug, Clone)]
+pub struct RebuildCampaignAnalyticsCommand {
+    pub campaign_id: Uuid,
+}
+
+impl Command for RebuildCampaignAnalyticsCommand {}
+
+pub struct RebuildCampaignAnalyticsCommandHandler {
+    analytics: std::sync::Arc<dyn CampaignAnalyticsRepository>,
+}
+
+impl RebuildCampaignAnalyticsCommandHandler {
+    pub fn new(analytics: std::sync::Arc<dyn CampaignAnalyticsRepository>) -> Self {
+        Self { analytics }
+    }
+}
+
+#[async_trait]
+impl CommandHandler<RebuildCampaignAnalyticsCommand> for RebuildCampaignAnalyticsCommandHandler {
+    /// Events replayed
+    type Output = u64;
+
+    async fn handle(&self, cmd: RebuildCampaignAnalyticsCommand) -> Result<Self::Output, AppError> {
+        let replayed = self.analytics.rebuild(cmd.campaign_id).await?;
+        tracing::info!("Rebuilt analytics of campaign {} from {} events", cmd.campaign_id, replayed);
+        Ok(replayed)
+    }
+}
@@ -1,8 +1,18 @@
+pub mod analytics_snapshots;
+pub mod audience;
+pub mod budget;
 pub mod commands;
+pub mod nft_minting;
+pub mod scheduler;
 pub mod services;
 pub mod use_cases;
 
 pub use use_cases::*;
 pub use commands::*;
+pub use nft_minting::*;
+pub use analytics_snapshots::{CampaignAnalyticsSnapshotJob, CampaignAnalyticsSnapshotService};
+pub use audience::CampaignAudienceService;
+pub use budget::CampaignBudgetService;
+pub use scheduler::{CampaignSchedulerJob, CampaignSchedulerService};
 pub mod queries;
 pub use queries::*;
\ No newline at end of file
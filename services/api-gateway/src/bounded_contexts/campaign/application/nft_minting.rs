@@ -0,0 +1,416 @@
+// Campaign NFT minting through solana-integration
+//
+// El minteo no se hace en proceso: se publica un `NftMintRequest` en la cola de
+// Solana y el resultado vuelve por la cola de respuestas. El slot de la
+// colección se reserva antes de publicar y se devuelve si el minteo falla.
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+use vibestream_types::{NftMintOutcome, NftMintRequest, NftMintResult, QueueNames};
+
+use crate::bounded_contexts::campaign::domain::analytics::{CampaignActivity, CampaignActivityEvent};
+use crate::bounded_contexts::campaign::domain::nft_mint::{build_nft_metadata, CampaignNftMint, NftMintStatus};
+use crate::bounded_contexts::campaign::domain::repository::{CampaignAnalyticsRepository, CampaignNftMintRepository};
+use crate::shared::domain::errors::AppError;
+
+pub const DEFAULT_MAX_MINT_ATTEMPTS: u32 = 3;
+
+/// Outbound port to the solana-integration mint queue
+#[async_trait]
+pub trait NftMintQueue: Send + Sync {
+    async fn publish(&self, request: &NftMintRequest) -> Result<(), AppError>;
+}
+
+pub struct CampaignNftService {
+    mints: Arc<dyn CampaignNftMintRepository>,
+    queue: Arc<dyn NftMintQueue>,
+    reply_queue: String,
+    max_attempts: u32,
+    analytics: Option<Arc<dyn CampaignAnalyticsRepository>>,
+}
+
+impl CampaignNftService {
+    pub fn new(mints: Arc<dyn CampaignNftMintRepository>, queue: Arc<dyn NftMintQueue>) -> Self {
+        Self {
+            mints,
+            queue,
+            reply_queue: QueueNames::CAMPAIGN_NFT_MINT_RESULTS.to_string(),
+            max_attempts: DEFAULT_MAX_MINT_ATTEMPTS,
+            analytics: None,
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn with_reply_queue(mut self, reply_queue: impl Into<String>) -> Self {
+        self.reply_queue = reply_queue.into();
+        self
+    }
+
+    /// Record claimed NFTs in the campaign analytics
+    pub fn with_analytics(mut self, analytics: Arc<dyn CampaignAnalyticsRepository>) -> Self {
+        self.analytics = Some(analytics);
+        self
+    }
+
+    pub fn reply_queue(&self) -> &str {
+        &self.reply_queue
+    }
+
+    /// Participants of the campaign still waiting for an NFT
+    pub async fn participants_without_nft(&self, campaign_id: Uuid, limit: u32) -> Result<Vec<Uuid>, AppError> {
+        self.mints.find_participants_without_nft(campaign_id, limit).await
+    }
+
+    /// Reserve a collection slot for the participant and request the mint.
+    /// Calling it again for the same participation returns the existing mint
+    /// instead of minting a second NFT.
+    pub async fn mint_for_participant(
+        &self,
+        campaign_id: Uuid,
+        user_id: Uuid,
+        metadata_override: Option<&serde_json::Value>,
+    ) -> Result<CampaignNftMint, AppError> {
+        let participant = self.mints.find_participant(campaign_id, user_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("User {} is not participating in campaign {}", user_id, campaign_id)))?;
+        let wallet_address = participant.wallet_address.clone()
+            .filter(|wallet| !wallet.trim().is_empty())
+            .ok_or_else(|| AppError::ValidationError(format!("User {} has no wallet to receive the NFT", user_id)))?;
+        let collection = self.mints.find_collection(campaign_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+        if collection.is_sold_out() {
+            return Err(sold_out(campaign_id));
+        }
+
+        let metadata = build_nft_metadata(&collection, &participant, metadata_override);
+        let candidate = CampaignNftMint::pending(campaign_id, user_id, wallet_address, metadata);
+        let mint = self.mints.reserve_mint(&candidate).await?;
+        if mint.request_id != candidate.request_id {
+            return Ok(mint);
+        }
+
+        if let Err(e) = self.queue.publish(&mint.to_request(&self.reply_queue)).await {
+            // Nunca llegó a Solana: el slot vuelve a la colección
+            self.mints.mark_failed(mint.request_id, &e.to_string()).await?;
+            return Err(AppError::ExternalServiceError(format!("Failed to request NFT mint: {}", e)));
+        }
+
+        tracing::info!("Requested NFT mint {} for user {} in campaign {}", mint.request_id, user_id, campaign_id);
+        Ok(mint)
+    }
+
+    /// Apply a result from solana-integration. Results for mints that are no
+    /// longer pending are duplicates of an earlier delivery and are ignored.
+    pub async fn handle_result(&self, result: NftMintResult) -> Result<NftMintStatus, AppError> {
+        let mint = self.mints.find_mint(result.request_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("NFT mint {} not found", result.request_id)))?;
+        if mint.status != NftMintStatus::Pending {
+            tracing::info!("Ignoring duplicate result for NFT mint {} ({})", mint.request_id, mint.status);
+            return Ok(mint.status);
+        }
+
+        match result.outcome {
+            NftMintOutcome::Minted { mint_address, signature } => {
+                if !self.mints.mark_minted(mint.request_id, &mint_address, &signature).await? {
+                    return Ok(self.current_status(mint.request_id).await?);
+                }
+                tracing::info!("NFT mint {} minted at {} ({})", mint.request_id, mint_address, signature);
+                if let Some(analytics) = &self.analytics {
+                    let event = CampaignActivityEvent::new(
+                        mint.campaign_id,
+                        mint.user_id,
+                        CampaignActivity::NftClaimed { mint_address },
+                    );
+                    if let Err(e) = analytics.append(&event).await {
+                        tracing::warn!("Failed to record NFT claim for campaign {}: {}", mint.campaign_id, e);
+                    }
+                }
+                Ok(NftMintStatus::Minted)
+            }
+            NftMintOutcome::Failed { reason, retryable } if retryable && mint.attempts < self.max_attempts => {
+                // Mismo request_id: si el intento anterior sí llegó a mintear, Solana no lo repite
+                let attempts = self.mints.record_retry(mint.request_id, &reason).await?;
+                let retry = CampaignNftMint { attempts, ..mint };
+                self.queue.publish(&retry.to_request(&self.reply_queue)).await?;
+                tracing::warn!("Retrying NFT mint {} (attempt {}): {}", retry.request_id, attempts, reason);
+                Ok(NftMintStatus::Pending)
+            }
+            NftMintOutcome::Failed { reason, .. } => {
+                self.mints.mark_failed(mint.request_id, &reason).await?;
+                tracing::error!("NFT mint {} failed after {} attempt(s): {}", mint.request_id, mint.attempts, reason);
+                Ok(NftMintStatus::Failed)
+            }
+        }
+    }
+
+    async fn current_status(&self, request_id: Uuid) -> Result<NftMintStatus, AppError> {
+        Ok(self.mints.find_mint(request_id).await?
+            .map(|mint| mint.status)
+            .unwrap_or(NftMintStatus::Failed))
+    }
+}
+
+pub(crate) fn sold_out(campaign_id: Uuid) -> AppError {
+    AppError::DomainRuleViolation(format!("NFT collection of campaign {} is sold out", campaign_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::campaign::domain::nft_mint::{MintParticipant, NftCollectionSettings};
+    use crate::shared::domain::repositories::RepoResult;
+    use chrono::Utc;
+    use std::collections::{HashMap, VecDeque};
+    use std::sync::Mutex;
+
+    struct InMemoryMints {
+        collection: Mutex<NftCollectionSettings>,
+        participants: Vec<MintParticipant>,
+        mints: Mutex<HashMap<Uuid, CampaignNftMint>>,
+    }
+
+    impl InMemoryMints {
+        fn new(collection_size: u32, participants: usize) -> Self {
+            Self {
+                collection: Mutex::new(NftCollectionSettings {
+                    campaign_id: Uuid::new_v4(),
+                    campaign_name: "Launch".to_string(),
+                    description: None,
+                    song_id: Uuid::new_v4(),
+                    artist_id: Uuid::new_v4(),
+                    collection_address: None,
+                    metadata_url: None,
+                    collection_size,
+                    minted: 0,
+                }),
+                participants: (0..participants)
+                    .map(|i| MintParticipant {
+                        user_id: Uuid::new_v4(),
+                        username: format!("fan{}", i),
+                        wallet_address: Some(format!("Wallet{}", i)),
+                        joined_at: Utc::now(),
+                    })
+                    .collect(),
+                mints: Mutex::new(HashMap::new()),
+            }
+        }
+
+        fn campaign_id(&self) -> Uuid {
+            self.collection.lock().unwrap().campaign_id
+        }
+
+        fn minted(&self) -> u32 {
+            self.collection.lock().unwrap().minted
+        }
+    }
+
+    #[async_trait]
+    impl CampaignNftMintRepository for InMemoryMints {
+        async fn find_collection(&self, _campaign_id: Uuid) -> RepoResult<Option<NftCollectionSettings>> {
+            Ok(Some(self.collection.lock().unwrap().clone()))
+        }
+
+        async fn find_participant(&self, _campaign_id: Uuid, user_id: Uuid) -> RepoResult<Option<MintParticipant>> {
+            Ok(self.participants.iter().find(|p| p.user_id == user_id).cloned())
+        }
+
+        async fn find_participants_without_nft(&self, _campaign_id: Uuid, limit: u32) -> RepoResult<Vec<Uuid>> {
+            let mints = self.mints.lock().unwrap();
+            Ok(self.participants.iter()
+                .filter(|p| !mints.values().any(|m| m.user_id == p.user_id && m.status != NftMintStatus::Failed))
+                .take(limit as usize)
+                .map(|p| p.user_id)
+                .collect())
+        }
+
+        async fn reserve_mint(&self, mint: &CampaignNftMint) -> RepoResult<CampaignNftMint> {
+            let mut mints = self.mints.lock().unwrap();
+            if let Some(existing) = mints.values().find(|m| m.user_id == mint.user_id && m.status != NftMintStatus::Failed) {
+                return Ok(existing.clone());
+            }
+            let mut collection = self.collection.lock().unwrap();
+            if collection.is_sold_out() {
+                return Err(sold_out(collection.campaign_id));
+            }
+            collection.minted += 1;
+            mints.retain(|_, m| m.user_id != mint.user_id);
+            mints.insert(mint.request_id, mint.clone());
+            Ok(mint.clone())
+        }
+
+        async fn find_mint(&self, request_id: Uuid) -> RepoResult<Option<CampaignNftMint>> {
+            Ok(self.mints.lock().unwrap().get(&request_id).cloned())
+        }
+
+        async fn record_retry(&self, request_id: Uuid, reason: &str) -> RepoResult<u32> {
+            let mut mints = self.mints.lock().unwrap();
+            let mint = mints.get_mut(&request_id).unwrap();
+            mint.attempts += 1;
+            mint.last_error = Some(reason.to_string());
+            Ok(mint.attempts)
+        }
+
+        async fn mark_minted(&self, request_id: Uuid, mint_address: &str, signature: &str) -> RepoResult<bool> {
+            let mut mints = self.mints.lock().unwrap();
+            let mint = mints.get_mut(&request_id).unwrap();
+            if mint.status != NftMintStatus::Pending {
+                return Ok(false);
+            }
+            mint.status = NftMintStatus::Minted;
+            mint.mint_address = Some(mint_address.to_string());
+            mint.signature = Some(signature.to_string());
+            mint.minted_at = Some(Utc::now());
+            Ok(true)
+        }
+
+        async fn mark_failed(&self, request_id: Uuid, reason: &str) -> RepoResult<bool> {
+            let mut mints = self.mints.lock().unwrap();
+            let mint = mints.get_mut(&request_id).unwrap();
+            if mint.status != NftMintStatus::Pending {
+                return Ok(false);
+            }
+            mint.status = NftMintStatus::Failed;
+            mint.last_error = Some(reason.to_string());
+            self.collection.lock().unwrap().minted -= 1;
+            Ok(true)
+        }
+    }
+
+    /// Cola de Solana en memoria
+    #[derive(Default)]
+    struct QueuedMints {
+        requests: Mutex<VecDeque<NftMintRequest>>,
+    }
+
+    #[async_trait]
+    impl NftMintQueue for QueuedMints {
+        async fn publish(&self, request: &NftMintRequest) -> Result<(), AppError> {
+            self.requests.lock().unwrap().push_back(request.clone());
+            Ok(())
+        }
+    }
+
+    /// Responder simulado de solana-integration: idempotente por `request_id`,
+    /// con fallos transitorios programados para los primeros intentos
+    struct MockMintResponder {
+        transient_failures: u32,
+        minted: HashMap<Uuid, (String, String)>,
+        minted_tokens: u32,
+    }
+
+    impl MockMintResponder {
+        fn new(transient_failures: u32) -> Self {
+            Self { transient_failures, minted: HashMap::new(), minted_tokens: 0 }
+        }
+
+        fn respond(&mut self, request: &NftMintRequest) -> NftMintResult {
+            if request.attempt <= self.transient_failures {
+                return NftMintResult {
+                    request_id: request.request_id,
+                    outcome: NftMintOutcome::Failed { reason: "RPC node timeout".to_string(), retryable: true },
+                };
+            }
+            let (mint_address, signature) = match self.minted.get(&request.request_id) {
+                Some(existing) => existing.clone(),
+                None => {
+                    self.minted_tokens += 1;
+                    let minted = (format!("Mint{}", self.minted_tokens), format!("Sig{}", self.minted_tokens));
+                    self.minted.insert(request.request_id, minted.clone());
+                    minted
+                }
+            };
+            NftMintResult { request_id: request.request_id, outcome: NftMintOutcome::Minted { mint_address, signature } }
+        }
+    }
+
+    async fn drain(service: &CampaignNftService, queue: &QueuedMints, responder: &mut MockMintResponder) {
+        loop {
+            let next = queue.requests.lock().unwrap().pop_front();
+            let Some(request) = next else { break };
+            service.handle_result(responder.respond(&request)).await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_transient_failures_are_retried_with_the_same_request_id() {
+        let mints = Arc::new(InMemoryMints::new(10, 1));
+        let queue = Arc::new(QueuedMints::default());
+        let service = CampaignNftService::new(mints.clone(), queue.clone());
+        let mut responder = MockMintResponder::new(2);
+        let user_id = mints.participants[0].user_id;
+
+        let mint = service.mint_for_participant(mints.campaign_id(), user_id, None).await.unwrap();
+        drain(&service, &queue, &mut responder).await;
+
+        let stored = mints.find_mint(mint.request_id).await.unwrap().unwrap();
+        assert_eq!(stored.status, NftMintStatus::Minted);
+        assert_eq!(stored.attempts, 3);
+        assert_eq!(stored.mint_address.as_deref(), Some("Mint1"));
+        assert_eq!(stored.signature.as_deref(), Some("Sig1"));
+        assert_eq!(responder.minted_tokens, 1);
+        assert_eq!(mints.minted(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_redelivered_request_never_mints_twice() {
+        let mints = Arc::new(InMemoryMints::new(10, 1));
+        let queue = Arc::new(QueuedMints::default());
+        let service = CampaignNftService::new(mints.clone(), queue.clone());
+        let mut responder = MockMintResponder::new(0);
+        let user_id = mints.participants[0].user_id;
+
+        let mint = service.mint_for_participant(mints.campaign_id(), user_id, None).await.unwrap();
+        let request = queue.requests.lock().unwrap().front().cloned().unwrap();
+        queue.requests.lock().unwrap().push_back(request);
+        drain(&service, &queue, &mut responder).await;
+
+        // Pedirlo otra vez devuelve el NFT ya minteado
+        let again = service.mint_for_participant(mints.campaign_id(), user_id, None).await.unwrap();
+        assert_eq!(again.request_id, mint.request_id);
+        assert_eq!(again.status, NftMintStatus::Minted);
+        assert!(queue.requests.lock().unwrap().is_empty());
+        assert_eq!(responder.minted_tokens, 1);
+        assert_eq!(mints.minted(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_retries_fail_the_mint_and_release_the_slot() {
+        let mints = Arc::new(InMemoryMints::new(1, 2));
+        let queue = Arc::new(QueuedMints::default());
+        let service = CampaignNftService::new(mints.clone(), queue.clone()).with_max_attempts(2);
+        let mut responder = MockMintResponder::new(5);
+
+        let first = service.mint_for_participant(mints.campaign_id(), mints.participants[0].user_id, None).await.unwrap();
+        drain(&service, &queue, &mut responder).await;
+        assert_eq!(mints.find_mint(first.request_id).await.unwrap().unwrap().status, NftMintStatus::Failed);
+        assert_eq!(mints.minted(), 0);
+
+        // El slot liberado queda disponible para otro participante
+        responder.transient_failures = 0;
+        service.mint_for_participant(mints.campaign_id(), mints.participants[1].user_id, None).await.unwrap();
+        drain(&service, &queue, &mut responder).await;
+        assert_eq!(mints.minted(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sold_out_collection_rejects_further_mints() {
+        let mints = Arc::new(InMemoryMints::new(2, 3));
+        let queue = Arc::new(QueuedMints::default());
+        let service = CampaignNftService::new(mints.clone(), queue.clone());
+        let mut responder = MockMintResponder::new(0);
+
+        for participant in &mints.participants[..2] {
+            service.mint_for_participant(mints.campaign_id(), participant.user_id, None).await.unwrap();
+        }
+        let result = service.mint_for_participant(mints.campaign_id(), mints.participants[2].user_id, None).await;
+        drain(&service, &queue, &mut responder).await;
+
+        assert!(matches!(result, Err(AppError::DomainRuleViolation(_))));
+        assert_eq!(responder.minted_tokens, 2);
+        assert_eq!(mints.minted(), 2);
+    }
+}
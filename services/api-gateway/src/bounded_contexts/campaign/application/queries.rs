@@ -5,7 +5,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::shared::application::query::{Query, QueryHandler};
 use crate::shared::domain::errors::AppError;
-use crate::bounded_contexts::campaign::domain::repository::CampaignRepository;
+use std::sync::Arc;
+
+use crate::bounded_contexts::campaign::domain::analytics::CampaignAnalyticsReport;
+use crate::bounded_contexts::campaign::domain::repository::{CampaignAnalyticsRepository, CampaignRepository};
 
 // =========================================================================
 // Queries
@@ -100,16 +103,27 @@ impl<R: CampaignRepository + Send + Sync> QueryHandler<SearchCampaignsQuery> for
     }
 }
 
-pub struct GetCampaignAnalyticsQueryHandler<R: CampaignRepository> {
-    pub repo: R,
+/// Reads the analytics projections; the aggregation happens when events are
+/// appended, not here
+pub struct GetCampaignAnalyticsQueryHandler {
+    analytics: Arc<dyn CampaignAnalyticsRepository>,
 }
 
-#[async_trait]
-impl<R: CampaignRepository + Send + Sync> QueryHandler<GetCampaignAnalyticsQuery> for GetCampaignAnalyticsQueryHandler<R> {
-    type Output = serde_json::Value;
+impl GetCampaignAnalyticsQueryHandler {
+    pub fn new(analytics: Arc<dyn CampaignAnalyticsRepository>) -> Self {
+        Self { analytics }
+    }
+}
 
-    async fn handle(&self, _query: GetCampaignAnalyticsQuery) -> Result<Self::Output, AppError> {
-        Ok(serde_json::json!({}))
+#[async_trait]
+impl QueryHandler<GetCampaignAnalyticsQuery> for GetCampaignAnalyticsQueryHandler {
+    type Output = CampaignAnalyticsReport;
+
+    async fn handle(&self, query: GetCampaignAnalyticsQuery) -> Result<Self::Output, AppError> {
+        self.analytics
+            .load_report(query.campaign_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", query.campaign_id)))
     }
 }
 
@@ -123,7 +137,7 @@ pub mod get_campaign {
 }
 
 pub mod get_campaign_analytics {
-    pub use super::GetCampaignAnalyticsQuery;
+    pub use super::{GetCampaignAnalyticsQuery, GetCampaignAnalyticsQueryHandler};
 }
 
 pub mod get_trending_campaigns {
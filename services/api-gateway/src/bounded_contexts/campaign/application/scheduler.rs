@@ -0,0 +1,331 @@
+// Campaign scheduling
+//
+// Las campañas con `auto_start` se activan al llegar `start_date` y todas las
+// que siguen en marcha se completan en `end_date`. Al completar se cierran las
+// analytics, se reparten los NFTs pendientes si el artista lo pidió y se
+// publica `CampaignCompleted`. Todo recibe `now` para poder probarlo sin reloj.
+
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::bounded_contexts::campaign::application::nft_minting::CampaignNftService;
+use crate::bounded_contexts::campaign::domain::repository::{CampaignAnalyticsRepository, CampaignScheduleRepository};
+use crate::bounded_contexts::campaign::domain::schedule::{CampaignSchedule, ScheduledTransition};
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventBus};
+use crate::shared::domain::errors::AppError;
+
+/// Campañas que se transicionan en cada pasada del job
+pub const SCHEDULER_BATCH_SIZE: u32 = 100;
+/// NFTs que se piden por campaña al completarla; el resto, a mano
+pub const END_OF_CAMPAIGN_NFT_BATCH: u32 = 500;
+
+pub struct CampaignSchedulerService {
+    schedules: Arc<dyn CampaignScheduleRepository>,
+    analytics: Arc<dyn CampaignAnalyticsRepository>,
+    event_bus: Arc<dyn EventBus>,
+    nft_service: Option<Arc<CampaignNftService>>,
+}
+
+impl CampaignSchedulerService {
+    pub fn new(
+        schedules: Arc<dyn CampaignScheduleRepository>,
+        analytics: Arc<dyn CampaignAnalyticsRepository>,
+        event_bus: Arc<dyn EventBus>,
+    ) -> Self {
+        Self { schedules, analytics, event_bus, nft_service: None }
+    }
+
+    /// Repartir NFTs al completar las campañas con `distribute_nfts_on_end`
+    pub fn with_nft_distribution(mut self, nft_service: Arc<CampaignNftService>) -> Self {
+        self.nft_service = Some(nft_service);
+        self
+    }
+
+    pub async fn find_schedule(&self, campaign_id: Uuid) -> Result<Option<CampaignSchedule>, AppError> {
+        self.schedules.find_schedule(campaign_id).await
+    }
+
+    pub async fn update_options(&self, campaign_id: Uuid, auto_start: bool, distribute_nfts_on_end: bool) -> Result<CampaignSchedule, AppError> {
+        self.schedules.update_options(campaign_id, auto_start, distribute_nfts_on_end).await
+    }
+
+    /// Activación manual. Antes de `start_date` solo con `force`, que el
+    /// controlador concede con la confirmación del artista.
+    pub async fn activate(&self, campaign_id: Uuid, now: DateTime<Utc>, force: bool) -> Result<CampaignSchedule, AppError> {
+        let schedule = self.schedules.activate(campaign_id, now, force).await?;
+        self.publish_activated(&schedule, now).await;
+        Ok(schedule)
+    }
+
+    /// Aplicar las transiciones que tocan a la hora `now`. Devuelve cuántas
+    /// campañas cambiaron; los fallos se reintentan en la siguiente pasada.
+    pub async fn run_due_transitions(&self, now: DateTime<Utc>) -> Result<usize, AppError> {
+        let due = self.schedules.find_due(now, SCHEDULER_BATCH_SIZE).await?;
+
+        let mut transitioned = 0;
+        for campaign_id in due {
+            match self.schedules.apply_due_transition(campaign_id, now).await {
+                Ok(Some((ScheduledTransition::Activate, schedule))) => {
+                    self.publish_activated(&schedule, now).await;
+                    transitioned += 1;
+                }
+                Ok(Some((ScheduledTransition::Complete, schedule))) => {
+                    self.finish(&schedule, now).await;
+                    transitioned += 1;
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Could not transition campaign {}: {}", campaign_id, e),
+            }
+        }
+
+        Ok(transitioned)
+    }
+
+    async fn publish_activated(&self, schedule: &CampaignSchedule, now: DateTime<Utc>) {
+        let event = DomainEvent::CampaignActivated {
+            campaign_id: schedule.campaign_id,
+            // El contrato se despliega al reaccionar al evento
+            nft_contract_address: "pending".to_string(),
+            occurred_at: now,
+        };
+        if let Err(e) = self.event_bus.publish(event).await {
+            tracing::error!("Failed to publish CampaignActivated for {}: {}", schedule.campaign_id, e);
+        }
+    }
+
+    /// Efectos de completar una campaña. Ya está guardada como `Completed`:
+    /// ningún fallo de aquí la devuelve a activa.
+    async fn finish(&self, schedule: &CampaignSchedule, now: DateTime<Utc>) {
+        let campaign_id = schedule.campaign_id;
+
+        if schedule.distribute_nfts_on_end {
+            self.distribute_nfts(campaign_id).await;
+        }
+
+        // Reconstruir las proyecciones deja el informe final coherente con el event store
+        if let Err(e) = self.analytics.rebuild(campaign_id).await {
+            tracing::warn!("Failed to finalize analytics of campaign {}: {}", campaign_id, e);
+        }
+        let report = match self.analytics.load_report(campaign_id).await {
+            Ok(report) => report,
+            Err(e) => {
+                tracing::warn!("Failed to load final analytics of campaign {}: {}", campaign_id, e);
+                None
+            }
+        };
+
+        let funnel = report.as_ref().map(|r| &r.conversion_funnel);
+        let event = DomainEvent::CampaignCompleted {
+            campaign_id,
+            artist_id: schedule.artist_id,
+            participants: funnel.map_or(0, |f| f.participated),
+            rewarded: funnel.map_or(0, |f| f.rewarded),
+            nfts_claimed: funnel.map_or(0, |f| f.nft_claimed),
+            total_spend: report.as_ref().map_or(0.0, |r| r.roi_analysis.total_spend),
+            occurred_at: now,
+        };
+        if let Err(e) = self.event_bus.publish(event).await {
+            tracing::error!("Failed to publish CampaignCompleted for {}: {}", campaign_id, e);
+        }
+        tracing::info!("Campaign {} completed at its end date", campaign_id);
+    }
+
+    async fn distribute_nfts(&self, campaign_id: Uuid) {
+        let Some(nft_service) = &self.nft_service else {
+            tracing::warn!("Campaign {} asked for NFT distribution but minting is not configured", campaign_id);
+            return;
+        };
+        let participants = match nft_service.participants_without_nft(campaign_id, END_OF_CAMPAIGN_NFT_BATCH).await {
+            Ok(participants) => participants,
+            Err(e) => {
+                tracing::warn!("Failed to load participants of campaign {}: {}", campaign_id, e);
+                return;
+            }
+        };
+
+        for user_id in participants {
+            match nft_service.mint_for_participant(campaign_id, user_id, None).await {
+                Ok(_) => {}
+                // Colección agotada: no quedan NFTs para nadie más
+                Err(AppError::DomainRuleViolation(reason)) => {
+                    tracing::info!("Stopped NFT distribution of campaign {}: {}", campaign_id, reason);
+                    break;
+                }
+                Err(e) => tracing::warn!("Skipped end-of-campaign NFT for user {} in {}: {}", user_id, campaign_id, e),
+            }
+        }
+    }
+}
+
+/// Worker que activa y completa campañas según su calendario
+pub struct CampaignSchedulerJob {
+    service: Arc<CampaignSchedulerService>,
+    interval: Duration,
+}
+
+impl CampaignSchedulerJob {
+    pub fn new(service: Arc<CampaignSchedulerService>, interval: Duration) -> Self {
+        Self { service, interval }
+    }
+
+    pub fn start(&self) -> tokio::task::JoinHandle<()> {
+        let service = Arc::clone(&self.service);
+        let interval = self.interval;
+
+        tokio::spawn(async move {
+            tracing::info!("🚀 Campaign scheduler job started");
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match service.run_due_transitions(Utc::now()).await {
+                    Ok(count) if count > 0 => tracing::info!("✅ Transitioned {} campaigns", count),
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Campaign scheduler failed: {:?}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::{Duration as ChronoDuration, TimeZone};
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use crate::bounded_contexts::campaign::domain::analytics::{CampaignActivityEvent, CampaignAnalyticsReport};
+    use crate::bounded_contexts::campaign::domain::entities::CampaignStatus;
+    use crate::bounded_contexts::orchestrator::EventHandler;
+    use crate::shared::domain::repositories::RepoResult;
+
+    #[derive(Default)]
+    struct InMemorySchedules {
+        campaigns: Mutex<HashMap<Uuid, CampaignSchedule>>,
+    }
+
+    #[async_trait]
+    impl CampaignScheduleRepository for InMemorySchedules {
+        async fn find_schedule(&self, campaign_id: Uuid) -> RepoResult<Option<CampaignSchedule>> {
+            Ok(self.campaigns.lock().unwrap().get(&campaign_id).cloned())
+        }
+        async fn find_due(&self, now: DateTime<Utc>, _limit: u32) -> RepoResult<Vec<Uuid>> {
+            let campaigns = self.campaigns.lock().unwrap();
+            Ok(campaigns.values().filter(|c| c.due_transition(now).is_some()).map(|c| c.campaign_id).collect())
+        }
+        async fn apply_due_transition(&self, campaign_id: Uuid, now: DateTime<Utc>) -> RepoResult<Option<(ScheduledTransition, CampaignSchedule)>> {
+            let mut campaigns = self.campaigns.lock().unwrap();
+            let campaign = campaigns.get_mut(&campaign_id).unwrap();
+            Ok(campaign.apply_due_transition(now).map(|transition| (transition, campaign.clone())))
+        }
+        async fn activate(&self, campaign_id: Uuid, now: DateTime<Utc>, force: bool) -> RepoResult<CampaignSchedule> {
+            let mut campaigns = self.campaigns.lock().unwrap();
+            let campaign = campaigns.get_mut(&campaign_id).unwrap();
+            campaign.activate(now, force)?;
+            Ok(campaign.clone())
+        }
+        async fn update_options(&self, campaign_id: Uuid, auto_start: bool, distribute_nfts_on_end: bool) -> RepoResult<CampaignSchedule> {
+            let mut campaigns = self.campaigns.lock().unwrap();
+            let campaign = campaigns.get_mut(&campaign_id).unwrap();
+            campaign.auto_start = auto_start;
+            campaign.distribute_nfts_on_end = distribute_nfts_on_end;
+            Ok(campaign.clone())
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingAnalytics {
+        rebuilt: Mutex<Vec<Uuid>>,
+    }
+
+    #[async_trait]
+    impl CampaignAnalyticsRepository for RecordingAnalytics {
+        async fn append(&self, _event: &CampaignActivityEvent) -> RepoResult<()> { Ok(()) }
+        async fn load_report(&self, _campaign_id: Uuid) -> RepoResult<Option<CampaignAnalyticsReport>> { Ok(None) }
+        async fn rebuild(&self, campaign_id: Uuid) -> RepoResult<u64> {
+            self.rebuilt.lock().unwrap().push(campaign_id);
+            Ok(0)
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingEventBus {
+        published: Mutex<Vec<DomainEvent>>,
+    }
+
+    #[async_trait]
+    impl EventBus for RecordingEventBus {
+        async fn publish(&self, event: DomainEvent) -> Result<(), AppError> {
+            self.published.lock().unwrap().push(event);
+            Ok(())
+        }
+        async fn subscribe(&self, _event_type: &str, _handler: Arc<dyn EventHandler>) -> Result<(), AppError> {
+            Ok(())
+        }
+    }
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 11, 1, 12, 0, 0).unwrap()
+    }
+
+    fn draft(auto_start: bool) -> CampaignSchedule {
+        CampaignSchedule {
+            campaign_id: Uuid::new_v4(),
+            artist_id: Uuid::new_v4(),
+            status: CampaignStatus::Draft,
+            start_date: start(),
+            end_date: start() + ChronoDuration::days(7),
+            auto_start,
+            distribute_nfts_on_end: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_clock_drives_activation_and_completion() {
+        let (auto, manual) = (draft(true), draft(false));
+        let schedules = Arc::new(InMemorySchedules::default());
+        schedules.campaigns.lock().unwrap().extend([(auto.campaign_id, auto.clone()), (manual.campaign_id, manual.clone())]);
+        let analytics = Arc::new(RecordingAnalytics::default());
+        let event_bus = Arc::new(RecordingEventBus::default());
+        let service = CampaignSchedulerService::new(schedules.clone(), analytics.clone(), event_bus.clone());
+
+        assert_eq!(service.run_due_transitions(start() - ChronoDuration::minutes(1)).await.unwrap(), 0);
+        assert_eq!(service.run_due_transitions(start()).await.unwrap(), 1);
+        assert_eq!(service.find_schedule(auto.campaign_id).await.unwrap().unwrap().status, CampaignStatus::Active);
+        assert_eq!(service.find_schedule(manual.campaign_id).await.unwrap().unwrap().status, CampaignStatus::Draft);
+        // Una segunda pasada a la misma hora no repite la activación
+        assert_eq!(service.run_due_transitions(start()).await.unwrap(), 0);
+
+        assert_eq!(service.run_due_transitions(auto.end_date).await.unwrap(), 1);
+        assert_eq!(service.find_schedule(auto.campaign_id).await.unwrap().unwrap().status, CampaignStatus::Completed);
+        assert_eq!(*analytics.rebuilt.lock().unwrap(), vec![auto.campaign_id]);
+
+        let published = event_bus.published.lock().unwrap();
+        assert_eq!(published.len(), 2);
+        assert!(matches!(published[0], DomainEvent::CampaignActivated { campaign_id, .. } if campaign_id == auto.campaign_id));
+        assert!(matches!(
+            published[1],
+            DomainEvent::CampaignCompleted { campaign_id, occurred_at, .. } if campaign_id == auto.campaign_id && occurred_at == auto.end_date
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_manual_activation_before_start_needs_force() {
+        let campaign = draft(false);
+        let schedules = Arc::new(InMemorySchedules::default());
+        schedules.campaigns.lock().unwrap().insert(campaign.campaign_id, campaign.clone());
+        let event_bus = Arc::new(RecordingEventBus::default());
+        let service = CampaignSchedulerService::new(schedules, Arc::new(RecordingAnalytics::default()), event_bus.clone());
+        let early = start() - ChronoDuration::days(2);
+
+        let rejected = service.activate(campaign.campaign_id, early, false).await;
+        assert!(matches!(rejected, Err(AppError::DomainRuleViolation(_))));
+        assert!(event_bus.published.lock().unwrap().is_empty());
+
+        let activated = service.activate(campaign.campaign_id, early, true).await.unwrap();
+        assert_eq!(activated.status, CampaignStatus::Active);
+        assert_eq!(event_bus.published.lock().unwrap().len(), 1);
+    }
+}
@@ -1,6 +1,12 @@
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use crate::bounded_contexts::campaign::domain::repository::{CampaignRepository, CampaignParticipationRepository};
+use crate::bounded_contexts::campaign::application::budget::CampaignBudgetService;
+use crate::bounded_contexts::campaign::domain::analytics::{region_from_action_data, CampaignActivity, CampaignActivityEvent};
+use crate::bounded_contexts::campaign::domain::audience::AudienceMatcher;
+use crate::bounded_contexts::campaign::domain::repository::{
+    AudienceRepository, CampaignAnalyticsRepository, CampaignRepository,
+};
+use crate::shared::domain::errors::AppError;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParticipateCampaignCommand {
@@ -25,17 +31,23 @@ pub struct ParticipateCampaignResult {
 
 pub struct ParticipateCampaignCommandHandler {
     campaign_repository: Arc<dyn CampaignRepository>,
-    participation_repository: Arc<dyn CampaignParticipationRepository>,
+    budget_service: Arc<CampaignBudgetService>,
+    audience_repository: Arc<dyn AudienceRepository>,
+    analytics_repository: Arc<dyn CampaignAnalyticsRepository>,
 }
 
 impl ParticipateCampaignCommandHandler {
     pub fn new(
         campaign_repository: Arc<dyn CampaignRepository>,
-        participation_repository: Arc<dyn CampaignParticipationRepository>
+        budget_service: Arc<CampaignBudgetService>,
+        audience_repository: Arc<dyn AudienceRepository>,
+        analytics_repository: Arc<dyn CampaignAnalyticsRepository>,
     ) -> Self {
         Self { 
             campaign_repository,
-            participation_repository
+            budget_service,
+            audience_repository,
+            analytics_repository,
         }
     }
 
@@ -46,14 +58,52 @@ impl ParticipateCampaignCommandHandler {
         })
     }
 
-    pub async fn handle(&self, _command: ParticipateCampaignCommand) -> Result<ParticipateCampaignResult, crate::shared::domain::errors::AppError> {
-        // Stub implementation
+    pub async fn handle(&self, command: ParticipateCampaignCommand) -> Result<ParticipateCampaignResult, AppError> {
+        let campaign = self.campaign_repository
+            .find_by_id(command.campaign_id)
+            .await?
+            .ok_or_else(|| AppError::NotFoundError(format!("Campaign {} not found", command.campaign_id)))?;
+
+        // La ventana se valida aquí aunque el scheduler aún no haya completado la campaña
+        if !campaign.date_range().contains(chrono::Utc::now()) {
+            return Err(AppError::ConflictError(format!(
+                "Campaign {} is outside its active window", command.campaign_id
+            )));
+        }
+
+        // Only the campaign's target audience can take part
+        let profile = self.audience_repository.find_user_profile(command.user_id).await?;
+        let eligibility = AudienceMatcher::for_campaign(&campaign).evaluate(&profile);
+        if !eligibility.is_eligible() {
+            return Err(AppError::Forbidden(format!(
+                "User is not part of the campaign's target audience: {}",
+                eligibility.reasons().join("; ")
+            )));
+        }
+
+        // Registra la participación y cobra la recompensa del presupuesto en la misma transacción
+        let reward_earned = self.budget_service
+            .reward_participation(command.campaign_id, command.user_id)
+            .await?;
+        let region = region_from_action_data(command.action_data.as_ref());
+        let mut activity = vec![CampaignActivity::ActionPerformed { action_type: command.action_type.clone(), region }];
+        if reward_earned > 0.0 {
+            activity.push(CampaignActivity::RewardGranted { amount: reward_earned });
+        }
+        for activity in activity {
+            // La participación ya está guardada: un fallo de analytics no la deshace
+            let event = CampaignActivityEvent::new(command.campaign_id, command.user_id, activity);
+            if let Err(e) = self.analytics_repository.append(&event).await {
+                tracing::warn!("Failed to record {} for campaign {}: {}", event.event_type(), command.campaign_id, e);
+            }
+        }
+
         Ok(ParticipateCampaignResult {
             participation_id: uuid::Uuid::new_v4(),
-            campaign_id: _command.campaign_id,
-            user_id: _command.user_id,
-            action_type: _command.action_type,
-            reward_earned: 10.0,
+            campaign_id: command.campaign_id,
+            user_id: command.user_id,
+            action_type: command.action_type,
+            reward_earned,
             is_eligible_for_nft: true,
             total_actions: 1,
             created_at: chrono::Utc::now(),
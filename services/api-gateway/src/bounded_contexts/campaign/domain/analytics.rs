@@ -0,0 +1,499 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use uuid::Uuid;
+
+// =============================================================================
+// CAMPAIGN ACTIVITY EVENTS (event store de analytics)
+// =============================================================================
+
+/// What a user did in a campaign. Each kind is one step of the conversion
+/// funnel: viewed → participated → rewarded → NFT claimed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum CampaignActivity {
+    Viewed,
+    ActionPerformed { action_type: String, region: Option<String> },
+    RewardGranted { amount: f64 },
+    NftClaimed { mint_address: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum FunnelStage {
+    Viewed,
+    Participated,
+    Rewarded,
+    NftClaimed,
+}
+
+impl FunnelStage {
+    /// 1..=4, the value stored in `campaign_analytics_participants.funnel_stage`
+    pub fn level(self) -> i16 {
+        match self {
+            FunnelStage::Viewed => 1,
+            FunnelStage::Participated => 2,
+            FunnelStage::Rewarded => 3,
+            FunnelStage::NftClaimed => 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CampaignActivityEvent {
+    pub event_id: Uuid,
+    pub campaign_id: Uuid,
+    pub user_id: Uuid,
+    pub activity: CampaignActivity,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl CampaignActivityEvent {
+    pub fn new(campaign_id: Uuid, user_id: Uuid, activity: CampaignActivity) -> Self {
+        Self {
+            event_id: Uuid::new_v4(),
+            campaign_id,
+            user_id,
+            activity,
+            occurred_at: Utc::now(),
+        }
+    }
+
+    pub fn event_type(&self) -> &'static str {
+        match self.activity {
+            CampaignActivity::Viewed => "CampaignViewed",
+            CampaignActivity::ActionPerformed { .. } => "CampaignActionPerformed",
+            CampaignActivity::RewardGranted { .. } => "CampaignRewardGranted",
+            CampaignActivity::NftClaimed { .. } => "CampaignNftClaimed",
+        }
+    }
+
+    /// Reaching a stage implies the earlier ones: a participant who never
+    /// sent a view event still counts as having viewed the campaign
+    pub fn funnel_stage(&self) -> FunnelStage {
+        match self.activity {
+            CampaignActivity::Viewed => FunnelStage::Viewed,
+            CampaignActivity::ActionPerformed { .. } => FunnelStage::Participated,
+            CampaignActivity::RewardGranted { .. } => FunnelStage::Rewarded,
+            CampaignActivity::NftClaimed { .. } => FunnelStage::NftClaimed,
+        }
+    }
+}
+
+/// Region of a participation, taken from its `action_data`
+pub fn region_from_action_data(action_data: Option<&serde_json::Value>) -> Option<String> {
+    let data = action_data?;
+    ["region", "country", "location"]
+        .iter()
+        .find_map(|key| data.get(*key).and_then(|value| value.as_str()))
+        .map(|region| region.trim().to_uppercase())
+        .filter(|region| !region.is_empty())
+}
+
+// =============================================================================
+// ANALYTICS REPORT (read model)
+// =============================================================================
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CampaignAnalyticsReport {
+    pub campaign_id: Uuid,
+    pub performance_metrics: PerformanceMetrics,
+    pub audience_insights: AudienceInsights,
+    pub engagement_data: EngagementData,
+    pub conversion_funnel: ConversionFunnel,
+    pub roi_analysis: BudgetAnalysis,
+    pub time_series_data: Vec<ParticipantsDataPoint>,
+}
+
+impl CampaignAnalyticsReport {
+    pub fn assemble(
+        campaign_id: Uuid,
+        funnel: ConversionFunnel,
+        actions_breakdown: BTreeMap<String, u64>,
+        participants_per_region: BTreeMap<String, u64>,
+        joined_per_day: BTreeMap<NaiveDate, u64>,
+        budget: BudgetAnalysis,
+    ) -> Self {
+        Self {
+            campaign_id,
+            performance_metrics: PerformanceMetrics {
+                total_participants: funnel.participated,
+                total_actions: actions_breakdown.values().sum(),
+                budget_utilization: budget.utilization(),
+            },
+            audience_insights: AudienceInsights { location_distribution: participants_per_region },
+            engagement_data: EngagementData { actions_breakdown },
+            conversion_funnel: funnel,
+            roi_analysis: budget,
+            time_series_data: participants_over_time(joined_per_day),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PerformanceMetrics {
+    pub total_participants: u64,
+    pub total_actions: u64,
+    pub budget_utilization: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudienceInsights {
+    /// Participants per region
+    pub location_distribution: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EngagementData {
+    pub actions_breakdown: BTreeMap<String, u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConversionFunnel {
+    pub viewed: u64,
+    pub participated: u64,
+    pub rewarded: u64,
+    pub nft_claimed: u64,
+    pub participation_rate: f64,
+    pub reward_rate: f64,
+    pub nft_claim_rate: f64,
+}
+
+impl ConversionFunnel {
+    pub fn new(viewed: u64, participated: u64, rewarded: u64, nft_claimed: u64) -> Self {
+        let rate = |part: u64, whole: u64| if whole == 0 { 0.0 } else { part as f64 / whole as f64 };
+        Self {
+            viewed,
+            participated,
+            rewarded,
+            nft_claimed,
+            participation_rate: rate(participated, viewed),
+            reward_rate: rate(rewarded, participated),
+            nft_claim_rate: rate(nft_claimed, participated),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BudgetAnalysis {
+    pub budget: Option<f64>,
+    pub total_spend: f64,
+    pub remaining_budget: Option<f64>,
+}
+
+impl BudgetAnalysis {
+    pub fn new(budget: Option<f64>, total_spend: f64) -> Self {
+        Self {
+            budget,
+            total_spend,
+            remaining_budget: budget.map(|budget| (budget - total_spend).max(0.0)),
+        }
+    }
+
+    pub fn utilization(&self) -> f64 {
+        match self.budget {
+            Some(budget) if budget > 0.0 => self.total_spend / budget,
+            _ => 0.0,
+        }
+    }
+}
+
+/// New participants per day and the running total
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParticipantsDataPoint {
+    pub date: NaiveDate,
+    pub new_participants: u64,
+    pub total_participants: u64,
+}
+
+/// Per-participant projection state, the same one the Postgres projection
+/// keeps in `campaign_analytics_participants`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParticipantProgress {
+    pub stage: Option<FunnelStage>,
+    pub first_participated_at: Option<DateTime<Utc>>,
+    pub region: Option<String>,
+}
+
+/// In-memory projection of a campaign's activity events. The report it builds
+/// is the reference for the Postgres projection.
+#[derive(Debug, Clone, Default)]
+pub struct CampaignAnalyticsProjection {
+    participants: HashMap<Uuid, ParticipantProgress>,
+    actions: BTreeMap<String, u64>,
+    reward_spent: f64,
+}
+
+impl CampaignAnalyticsProjection {
+    pub fn apply(&mut self, event: &CampaignActivityEvent) {
+        let progress = self.participants.entry(event.user_id).or_default();
+        progress.stage = progress.stage.max(Some(event.funnel_stage()));
+
+        match &event.activity {
+            CampaignActivity::ActionPerformed { action_type, region } => {
+                *self.actions.entry(action_type.clone()).or_insert(0) += 1;
+                if progress.first_participated_at.is_none() {
+                    progress.first_participated_at = Some(event.occurred_at);
+                }
+                if progress.region.is_none() {
+                    progress.region = region.clone();
+                }
+            }
+            CampaignActivity::RewardGranted { amount } => self.reward_spent += amount,
+            CampaignActivity::Viewed | CampaignActivity::NftClaimed { .. } => {}
+        }
+    }
+
+    pub fn report(&self, campaign_id: Uuid, budget: Option<f64>) -> CampaignAnalyticsReport {
+        let reached = |stage: FunnelStage| {
+            self.participants.values().filter(|progress| progress.stage >= Some(stage)).count() as u64
+        };
+
+        let mut regions = BTreeMap::new();
+        let mut joined_per_day = BTreeMap::new();
+        for progress in self.participants.values() {
+            let Some(joined_at) = progress.first_participated_at else { continue };
+            *joined_per_day.entry(joined_at.date_naive()).or_insert(0u64) += 1;
+            if let Some(region) = &progress.region {
+                *regions.entry(region.clone()).or_insert(0u64) += 1;
+            }
+        }
+
+        CampaignAnalyticsReport::assemble(
+            campaign_id,
+            ConversionFunnel::new(
+                reached(FunnelStage::Viewed),
+                reached(FunnelStage::Participated),
+                reached(FunnelStage::Rewarded),
+                reached(FunnelStage::NftClaimed),
+            ),
+            self.actions.clone(),
+            regions,
+            joined_per_day,
+            BudgetAnalysis::new(budget, self.reward_spent),
+        )
+    }
+}
+
+// =============================================================================
+// DAILY SNAPSHOTS (histórico)
+// =============================================================================
+
+/// Cumulative campaign metrics as of the end of `date` (UTC)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CampaignAnalyticsSnapshot {
+    pub campaign_id: Uuid,
+    pub date: NaiveDate,
+    pub participants_count: u64,
+    pub nfts_minted: u64,
+    pub unique_listeners: u64,
+    pub total_listen_seconds: u64,
+    pub budget_spent: f64,
+}
+
+/// A finished listen of the campaign's song
+#[derive(Debug, Clone, PartialEq)]
+pub struct CampaignListen {
+    pub user_id: Uuid,
+    pub seconds: u64,
+    pub started_at: DateTime<Utc>,
+}
+
+/// First instant after `date`
+pub fn end_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.succ_opt()
+        .unwrap_or(NaiveDate::MAX)
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight is a valid time")
+        .and_utc()
+}
+
+impl CampaignAnalyticsSnapshot {
+    /// Snapshot of `date` from the campaign's activity events and the listens
+    /// of its song. Listens only count inside `[starts_at, ends_at)`. This is
+    /// the reference for the SQL the Postgres repository runs.
+    pub fn calculate(
+        campaign_id: Uuid,
+        date: NaiveDate,
+        starts_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+        events: &[CampaignActivityEvent],
+        listens: &[CampaignListen],
+    ) -> Self {
+        let until = end_of_day(date);
+        let events: Vec<_> = events.iter().filter(|e| e.campaign_id == campaign_id && e.occurred_at < until).collect();
+
+        // Participar es haber alcanzado al menos esa etapa del embudo
+        let participants: HashSet<Uuid> = events
+            .iter()
+            .filter(|e| e.funnel_stage() >= FunnelStage::Participated)
+            .map(|e| e.user_id)
+            .collect();
+        let nfts_minted = events.iter().filter(|e| matches!(e.activity, CampaignActivity::NftClaimed { .. })).count();
+        let budget_spent = events
+            .iter()
+            .map(|e| match e.activity {
+                CampaignActivity::RewardGranted { amount } => amount,
+                _ => 0.0,
+            })
+            .sum();
+
+        let listens: Vec<_> = listens
+            .iter()
+            .filter(|l| l.started_at >= starts_at && l.started_at < ends_at.min(until))
+            .collect();
+        let listeners: HashSet<Uuid> = listens.iter().map(|l| l.user_id).collect();
+
+        Self {
+            campaign_id,
+            date,
+            participants_count: participants.len() as u64,
+            nfts_minted: nfts_minted as u64,
+            unique_listeners: listeners.len() as u64,
+            total_listen_seconds: listens.iter().map(|l| l.seconds).sum(),
+            budget_spent,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalyticsSource {
+    /// Calculado al pedirlo (campañas en curso)
+    Live,
+    /// Última foto diaria guardada (campañas completadas)
+    Snapshot,
+}
+
+/// What `GET /campaigns/{id}/analytics` returns. The detailed report is only
+/// built for live analytics.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CampaignAnalyticsOverview {
+    pub campaign_id: Uuid,
+    pub source: AnalyticsSource,
+    pub metrics: CampaignAnalyticsSnapshot,
+    pub report: Option<CampaignAnalyticsReport>,
+}
+
+fn participants_over_time(joined_per_day: BTreeMap<NaiveDate, u64>) -> Vec<ParticipantsDataPoint> {
+    let mut total = 0;
+    joined_per_day
+        .into_iter()
+        .map(|(date, new_participants)| {
+            total += new_participants;
+            ParticipantsDataPoint { date, new_participants, total_participants: total }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+
+    fn event(campaign_id: Uuid, user_id: Uuid, activity: CampaignActivity, day: u32) -> CampaignActivityEvent {
+        CampaignActivityEvent {
+            occurred_at: Utc.with_ymd_and_hms(2026, 10, day, 12, 0, 0).unwrap(),
+            ..CampaignActivityEvent::new(campaign_id, user_id, activity)
+        }
+    }
+
+    fn action(action_type: &str, region: &str) -> CampaignActivity {
+        CampaignActivity::ActionPerformed { action_type: action_type.to_string(), region: Some(region.to_string()) }
+    }
+
+    #[test]
+    fn test_funnel_from_scripted_participation_events() {
+        let campaign_id = Uuid::new_v4();
+        let [ana, ben, cai, dee, eli] = [(); 5].map(|_| Uuid::new_v4());
+        let script = vec![
+            event(campaign_id, ana, CampaignActivity::Viewed, 1),
+            event(campaign_id, ben, CampaignActivity::Viewed, 1),
+            event(campaign_id, cai, CampaignActivity::Viewed, 1),
+            event(campaign_id, dee, CampaignActivity::Viewed, 2),
+            event(campaign_id, ana, action("listen", "ES"), 1),
+            event(campaign_id, ana, action("share", "FR"), 2),
+            event(campaign_id, ben, action("listen", "ES"), 2),
+            // Participa sin evento de vista previo: cuenta como vista
+            event(campaign_id, eli, action("listen", "MX"), 3),
+            event(campaign_id, ana, CampaignActivity::RewardGranted { amount: 10.0 }, 2),
+            event(campaign_id, ben, CampaignActivity::RewardGranted { amount: 5.0 }, 3),
+            event(campaign_id, ana, CampaignActivity::NftClaimed { mint_address: "Mint1".to_string() }, 3),
+        ];
+
+        let mut projection = CampaignAnalyticsProjection::default();
+        script.iter().for_each(|event| projection.apply(event));
+        let report = projection.report(campaign_id, Some(100.0));
+
+        let funnel = &report.conversion_funnel;
+        assert_eq!((funnel.viewed, funnel.participated, funnel.rewarded, funnel.nft_claimed), (5, 3, 2, 1));
+        assert_eq!(funnel.participation_rate, 0.6);
+        assert_eq!(report.performance_metrics.total_participants, 3);
+        assert_eq!(report.performance_metrics.total_actions, 4);
+        assert_eq!(report.engagement_data.actions_breakdown.get("listen"), Some(&3));
+        assert_eq!(report.engagement_data.actions_breakdown.get("share"), Some(&1));
+        // La región es la de la primera participación
+        assert_eq!(report.audience_insights.location_distribution.get("ES"), Some(&2));
+        assert_eq!(report.audience_insights.location_distribution.get("FR"), None);
+        assert_eq!(report.roi_analysis.total_spend, 15.0);
+        assert_eq!(report.roi_analysis.remaining_budget, Some(85.0));
+        assert_eq!(report.performance_metrics.budget_utilization, 0.15);
+        let totals: Vec<_> = report.time_series_data.iter().map(|p| (p.new_participants, p.total_participants)).collect();
+        assert_eq!(totals, vec![(1, 1), (1, 2), (1, 3)]);
+    }
+
+    #[test]
+    fn test_daily_snapshot_from_seeded_events_and_listens() {
+        let campaign_id = Uuid::new_v4();
+        let other_campaign = Uuid::new_v4();
+        let [ana, ben, cai] = [(); 3].map(|_| Uuid::new_v4());
+        let events = vec![
+            event(campaign_id, ana, CampaignActivity::Viewed, 1),
+            event(campaign_id, ana, action("listen", "ES"), 1),
+            event(campaign_id, ben, action("listen", "ES"), 2),
+            event(campaign_id, ana, CampaignActivity::RewardGranted { amount: 10.0 }, 1),
+            event(campaign_id, ben, CampaignActivity::RewardGranted { amount: 2.5 }, 3),
+            event(campaign_id, ana, CampaignActivity::NftClaimed { mint_address: "Mint1".to_string() }, 2),
+            // Solo vista: no participa
+            event(campaign_id, cai, CampaignActivity::Viewed, 2),
+            event(other_campaign, cai, action("listen", "MX"), 2),
+        ];
+        let at = |day: u32, hour: u32| Utc.with_ymd_and_hms(2026, 10, day, hour, 0, 0).unwrap();
+        let listen = |user_id: Uuid, seconds: u64, started_at: DateTime<Utc>| CampaignListen { user_id, seconds, started_at };
+        let listens = vec![
+            // Antes de empezar la campaña
+            listen(cai, 500, at(1, 8)),
+            listen(ana, 180, at(1, 10)),
+            listen(ana, 200, at(2, 23)),
+            listen(ben, 240, at(2, 9)),
+            listen(cai, 60, at(3, 9)),
+            // Después de terminar
+            listen(ben, 999, at(4, 12)),
+        ];
+        let (starts_at, ends_at) = (at(1, 9), at(4, 0));
+        let day = |day: u32| NaiveDate::from_ymd_opt(2026, 10, day).unwrap();
+
+        let first = CampaignAnalyticsSnapshot::calculate(campaign_id, day(1), starts_at, ends_at, &events, &listens);
+        assert_eq!((first.participants_count, first.nfts_minted, first.unique_listeners), (1, 0, 1));
+        assert_eq!(first.total_listen_seconds, 180);
+        assert_eq!(first.budget_spent, 10.0);
+
+        let second = CampaignAnalyticsSnapshot::calculate(campaign_id, day(2), starts_at, ends_at, &events, &listens);
+        assert_eq!((second.participants_count, second.nfts_minted, second.unique_listeners), (2, 1, 2));
+        assert_eq!(second.total_listen_seconds, 620);
+
+        // Los días tras el final ya no suman escuchas
+        let last = CampaignAnalyticsSnapshot::calculate(campaign_id, day(5), starts_at, ends_at, &events, &listens);
+        assert_eq!((last.unique_listeners, last.total_listen_seconds), (3, 680));
+        assert_eq!(last.budget_spent, 12.5);
+        assert_eq!(last.date, day(5));
+    }
+
+    #[test]
+    fn test_region_from_action_data() {
+        assert_eq!(region_from_action_data(Some(&json!({ "region": " es " }))), Some("ES".to_string()));
+        assert_eq!(region_from_action_data(Some(&json!({ "country": "MX", "song": "x" }))), Some("MX".to_string()));
+        assert_eq!(region_from_action_data(Some(&json!({ "duration": 120 }))), None);
+        assert_eq!(region_from_action_data(None), None);
+    }
+}
@@ -0,0 +1,258 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::entities::Campaign;
+use super::value_objects::{FanLevel, TargetAudience};
+
+// User Profile as seen by campaign targeting. Built from the user's listening
+// activity, so it only carries what audience matching needs.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct UserProfile {
+    pub user_id: Uuid,
+    pub country: Option<String>,
+    pub genre_preferences: Vec<String>,
+    pub listen_count: u64,
+}
+
+impl UserProfile {
+    pub fn fan_level(&self) -> FanLevel {
+        FanLevel::from_listen_count(self.listen_count)
+    }
+}
+
+/// Targeting dimension a user can fail to match
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AudienceCriterion {
+    Location,
+    Genre,
+    FanLevel,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AudienceMismatch {
+    pub criterion: AudienceCriterion,
+    pub reason: String,
+}
+
+/// Result of matching a user against a campaign's targeting: eligible when
+/// no criterion failed.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AudienceEligibility {
+    pub mismatches: Vec<AudienceMismatch>,
+}
+
+impl AudienceEligibility {
+    pub fn is_eligible(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    pub fn failed_criteria(&self) -> Vec<AudienceCriterion> {
+        self.mismatches.iter().map(|mismatch| mismatch.criterion).collect()
+    }
+
+    pub fn reasons(&self) -> Vec<String> {
+        self.mismatches.iter().map(|mismatch| mismatch.reason.clone()).collect()
+    }
+}
+
+/// Evaluates users against a target audience. Every dimension the audience
+/// restricts must match: country, at least one shared genre and a minimum
+/// fan level. Without a target audience everyone is eligible.
+pub struct AudienceMatcher<'a> {
+    audience: Option<&'a TargetAudience>,
+}
+
+impl<'a> AudienceMatcher<'a> {
+    pub fn new(audience: Option<&'a TargetAudience>) -> Self {
+        Self { audience }
+    }
+
+    pub fn for_campaign(campaign: &'a Campaign) -> Self {
+        Self::new(campaign.target_audience())
+    }
+
+    pub fn evaluate(&self, user: &UserProfile) -> AudienceEligibility {
+        let Some(audience) = self.audience else {
+            return AudienceEligibility::default();
+        };
+        let mut mismatches = Vec::new();
+
+        let location_matches = audience.locations.is_empty()
+            || user.country.as_deref().map_or(false, |country| {
+                audience.locations.iter().any(|location| location.trim().eq_ignore_ascii_case(country.trim()))
+            });
+        if !location_matches {
+            mismatches.push(AudienceMismatch {
+                criterion: AudienceCriterion::Location,
+                reason: format!(
+                    "Country {} is not among the targeted locations ({})",
+                    user.country.as_deref().unwrap_or("unknown"),
+                    audience.locations.join(", ")
+                ),
+            });
+        }
+
+        let genre_matches = audience.genres.is_empty()
+            || user.genre_preferences.iter().any(|preference| {
+                audience.genres.iter().any(|genre| genre.trim().eq_ignore_ascii_case(preference.trim()))
+            });
+        if !genre_matches {
+            mismatches.push(AudienceMismatch {
+                criterion: AudienceCriterion::Genre,
+                reason: format!("None of the top listened genres matches the targeted genres ({})", audience.genres.join(", ")),
+            });
+        }
+
+        if let Some(minimum) = audience.fan_level {
+            if user.fan_level() < minimum {
+                mismatches.push(AudienceMismatch {
+                    criterion: AudienceCriterion::FanLevel,
+                    reason: format!("Fan level {} is below the required {}", user.fan_level(), minimum),
+                });
+            }
+        }
+
+        AudienceEligibility { mismatches }
+    }
+}
+
+/// Whether a user belongs to the campaign's target audience
+pub fn matches_audience(campaign: &Campaign, user: &UserProfile) -> bool {
+    AudienceMatcher::for_campaign(campaign).evaluate(user).is_eligible()
+}
+
+/// Estimated reach of a target audience over the fans with listening
+/// activity: how many match each criterion on its own and how many match all.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct AudienceEstimate {
+    pub total_fans: u64,
+    pub location_matches: u64,
+    pub genre_matches: u64,
+    pub fan_level_matches: u64,
+    pub estimated_reach: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::campaign::domain::value_objects::DateRange;
+    use chrono::Utc;
+    use vibestream_types::{ArtistContract, SongContract};
+
+    fn campaign_for(audience: TargetAudience) -> Campaign {
+        let start = Utc::now() + chrono::Duration::days(1);
+        let (mut campaign, _) = Campaign::create(
+            SongContract::new(Uuid::new_v4(), "Test Song".to_string(), Uuid::new_v4(), "Test Artist".to_string()),
+            ArtistContract::new(Uuid::new_v4(), Uuid::new_v4(), "Test Artist".to_string()),
+            "Targeted Campaign".to_string(),
+            "Only for some fans".to_string(),
+            DateRange::new(start, start + chrono::Duration::days(30)).unwrap(),
+            2.0,
+            10.0,
+            1000,
+            None,
+        )
+        .unwrap();
+        campaign.set_target_audience(Some(audience));
+        campaign
+    }
+
+    fn fan(country: Option<&str>, genres: &[&str], listen_count: u64) -> UserProfile {
+        UserProfile {
+            user_id: Uuid::new_v4(),
+            country: country.map(str::to_string),
+            genre_preferences: genres.iter().map(|g| g.to_string()).collect(),
+            listen_count,
+        }
+    }
+
+    #[test]
+    fn test_open_campaign_accepts_everyone() {
+        let campaign = campaign_for(TargetAudience::default());
+        assert!(campaign.target_audience().is_none());
+        assert!(matches_audience(&campaign, &fan(None, &[], 0)));
+    }
+
+    #[test]
+    fn test_matches_by_location() {
+        let campaign = campaign_for(TargetAudience::new(vec!["ES".to_string(), "MX".to_string()], vec![], None));
+
+        assert!(matches_audience(&campaign, &fan(Some("es"), &[], 0)));
+        assert!(!matches_audience(&campaign, &fan(Some("US"), &[], 0)));
+        assert!(!matches_audience(&campaign, &fan(None, &[], 0)));
+    }
+
+    #[test]
+    fn test_matches_by_genre_overlap() {
+        let campaign = campaign_for(TargetAudience::new(vec![], vec!["Jazz".to_string(), "Soul".to_string()], None));
+
+        assert!(matches_audience(&campaign, &fan(None, &["rock", "jazz"], 0)));
+        assert!(!matches_audience(&campaign, &fan(None, &["rock", "pop"], 0)));
+        assert!(!matches_audience(&campaign, &fan(None, &[], 0)));
+    }
+
+    #[test]
+    fn test_matches_by_minimum_fan_level() {
+        let campaign = campaign_for(TargetAudience::new(vec![], vec![], Some(FanLevel::Regular)));
+
+        assert!(!matches_audience(&campaign, &fan(None, &[], 50)));
+        assert!(matches_audience(&campaign, &fan(None, &[], 51)));
+        assert!(matches_audience(&campaign, &fan(None, &[], 501)));
+    }
+
+    #[test]
+    fn test_every_dimension_must_match() {
+        let campaign = campaign_for(TargetAudience::new(
+            vec!["ES".to_string()],
+            vec!["jazz".to_string()],
+            Some(FanLevel::Superfan),
+        ));
+
+        assert!(matches_audience(&campaign, &fan(Some("ES"), &["jazz"], 600)));
+        assert!(!matches_audience(&campaign, &fan(Some("ES"), &["jazz"], 100)));
+        assert!(!matches_audience(&campaign, &fan(Some("FR"), &["jazz"], 600)));
+        assert!(!matches_audience(&campaign, &fan(Some("ES"), &["rock"], 600)));
+    }
+
+    #[test]
+    fn test_each_failed_criterion_is_reported() {
+        let location = TargetAudience::new(vec!["ES".to_string()], vec![], None);
+        let eligibility = AudienceMatcher::new(Some(&location)).evaluate(&fan(Some("US"), &[], 0));
+        assert_eq!(eligibility.failed_criteria(), vec![AudienceCriterion::Location]);
+        assert!(eligibility.reasons()[0].contains("US"));
+
+        let genre = TargetAudience::new(vec![], vec!["jazz".to_string()], None);
+        let eligibility = AudienceMatcher::new(Some(&genre)).evaluate(&fan(None, &["rock"], 0));
+        assert_eq!(eligibility.failed_criteria(), vec![AudienceCriterion::Genre]);
+
+        let fan_level = TargetAudience::new(vec![], vec![], Some(FanLevel::Superfan));
+        let eligibility = AudienceMatcher::new(Some(&fan_level)).evaluate(&fan(None, &[], 100));
+        assert_eq!(eligibility.failed_criteria(), vec![AudienceCriterion::FanLevel]);
+        assert_eq!(eligibility.reasons(), vec!["Fan level regular is below the required superfan".to_string()]);
+
+        assert!(AudienceMatcher::new(None).evaluate(&fan(None, &[], 0)).is_eligible());
+    }
+
+    #[test]
+    fn test_partial_match_lists_only_the_failing_criteria() {
+        let audience = TargetAudience::new(
+            vec!["ES".to_string(), "MX".to_string()],
+            vec!["jazz".to_string(), "soul".to_string()],
+            Some(FanLevel::Regular),
+        );
+        let matcher = AudienceMatcher::new(Some(&audience));
+
+        // País y nivel cumplen, el género no
+        let eligibility = matcher.evaluate(&fan(Some("mx"), &["rock", "pop"], 80));
+        assert!(!eligibility.is_eligible());
+        assert_eq!(eligibility.failed_criteria(), vec![AudienceCriterion::Genre]);
+
+        // Sin país conocido y casual: dos criterios fallan, el género sí cumple
+        let eligibility = matcher.evaluate(&fan(None, &["Soul"], 10));
+        assert_eq!(eligibility.failed_criteria(), vec![AudienceCriterion::Location, AudienceCriterion::FanLevel]);
+        assert_eq!(eligibility.reasons().len(), 2);
+
+        assert!(matcher.evaluate(&fan(Some("ES"), &["jazz"], 51)).is_eligible());
+    }
+}
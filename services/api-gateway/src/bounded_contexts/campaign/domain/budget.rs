@@ -0,0 +1,153 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::entities::CampaignStatus;
+use crate::shared::domain::errors::AppError;
+
+/// Importes en céntimos, como `NUMERIC(15,2)` en base de datos
+fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+/// Presupuesto de recompensas de una campaña. Sin `budget` la campaña no
+/// tiene límite de gasto.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CampaignBudget {
+    pub campaign_id: Uuid,
+    pub artist_id: Uuid,
+    pub status: CampaignStatus,
+    pub budget: Option<f64>,
+    pub spent: f64,
+    pub reward_per_action: f64,
+    pub end_date: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RewardDecision {
+    Rewarded { reward: f64 },
+    /// La campaña no está activa: no se registra la participación
+    Rejected,
+}
+
+/// Resultado de cobrar una recompensa. `depleted` indica que esta operación
+/// pausó la campaña por falta de presupuesto.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RewardCharge {
+    pub decision: RewardDecision,
+    pub depleted: bool,
+}
+
+impl CampaignBudget {
+    pub fn remaining(&self) -> Option<f64> {
+        self.budget.map(|budget| round_cents(budget - self.spent))
+    }
+
+    pub fn can_cover_reward(&self) -> bool {
+        self.remaining().map_or(true, |remaining| remaining >= self.reward_per_action)
+    }
+
+    /// Cobrar la recompensa de una participación. Si después no queda para
+    /// otra, la campaña pasa a `Paused`.
+    pub fn charge_reward(&mut self) -> RewardCharge {
+        if self.status != CampaignStatus::Active {
+            return RewardCharge { decision: RewardDecision::Rejected, depleted: false };
+        }
+        // Presupuesto reducido por debajo de una recompensa
+        if !self.can_cover_reward() {
+            self.status = CampaignStatus::Paused;
+            return RewardCharge { decision: RewardDecision::Rejected, depleted: true };
+        }
+
+        self.spent = round_cents(self.spent + self.reward_per_action);
+        let depleted = !self.can_cover_reward();
+        if depleted {
+            self.status = CampaignStatus::Paused;
+        }
+        RewardCharge { decision: RewardDecision::Rewarded { reward: self.reward_per_action }, depleted }
+    }
+
+    pub fn top_up(&mut self, amount: f64) -> Result<(), AppError> {
+        if !amount.is_finite() || amount <= 0.0 {
+            return Err(AppError::ValidationError("Top-up amount must be positive".to_string()));
+        }
+        let budget = self.budget.unwrap_or(self.spent);
+        self.budget = Some(round_cents(budget + amount));
+        Ok(())
+    }
+
+    pub fn resume(&mut self, now: DateTime<Utc>) -> Result<(), AppError> {
+        if self.status != CampaignStatus::Paused {
+            return Err(AppError::InvalidState("Only paused campaigns can be resumed".to_string()));
+        }
+        if self.end_date <= now {
+            return Err(AppError::DomainRuleViolation("Cannot resume expired campaign".to_string()));
+        }
+        if !self.can_cover_reward() {
+            return Err(AppError::DomainRuleViolation(format!(
+                "Remaining budget {:.2} cannot cover a reward of {:.2}; top up the campaign first",
+                self.remaining().unwrap_or_default(),
+                self.reward_per_action
+            )));
+        }
+        self.status = CampaignStatus::Active;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn budget(amount: f64, reward: f64) -> CampaignBudget {
+        CampaignBudget {
+            campaign_id: Uuid::new_v4(),
+            artist_id: Uuid::new_v4(),
+            status: CampaignStatus::Active,
+            budget: Some(amount),
+            spent: 0.0,
+            reward_per_action: reward,
+            end_date: Utc::now() + Duration::days(30),
+        }
+    }
+
+    #[test]
+    fn test_charging_pauses_when_next_reward_is_not_covered() {
+        let mut campaign = budget(25.0, 10.0);
+
+        assert_eq!(campaign.charge_reward(), RewardCharge { decision: RewardDecision::Rewarded { reward: 10.0 }, depleted: false });
+        assert_eq!(campaign.charge_reward(), RewardCharge { decision: RewardDecision::Rewarded { reward: 10.0 }, depleted: true });
+        assert_eq!(campaign.status, CampaignStatus::Paused);
+        assert_eq!(campaign.remaining(), Some(5.0));
+
+        // Pausada: se rechaza sin volver a notificar
+        assert_eq!(campaign.charge_reward(), RewardCharge { decision: RewardDecision::Rejected, depleted: false });
+        assert_eq!(campaign.spent, 20.0);
+    }
+
+    #[test]
+    fn test_resume_requires_budget_for_another_reward() {
+        let mut campaign = budget(10.0, 10.0);
+        campaign.charge_reward();
+        assert_eq!(campaign.status, CampaignStatus::Paused);
+
+        assert!(matches!(campaign.resume(Utc::now()), Err(AppError::DomainRuleViolation(_))));
+        assert!(campaign.top_up(0.0).is_err());
+        campaign.top_up(15.0).unwrap();
+        assert_eq!(campaign.remaining(), Some(15.0));
+        campaign.resume(Utc::now()).unwrap();
+        assert_eq!(campaign.status, CampaignStatus::Active);
+        assert!(matches!(campaign.resume(Utc::now()), Err(AppError::InvalidState(_))));
+    }
+
+    #[test]
+    fn test_unlimited_budget_never_depletes() {
+        let mut campaign = CampaignBudget { budget: None, ..budget(0.0, 10.0) };
+        for _ in 0..100 {
+            assert!(!campaign.charge_reward().depleted);
+        }
+        assert_eq!(campaign.spent, 1000.0);
+        assert_eq!(campaign.status, CampaignStatus::Active);
+    }
+}
@@ -19,6 +19,35 @@ pub enum CampaignStatus {
     Failed,     // Failed to meet minimum requirements
 }
 
+impl CampaignStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CampaignStatus::Draft => "Draft",
+            CampaignStatus::Active => "Active",
+            CampaignStatus::Paused => "Paused",
+            CampaignStatus::Completed => "Completed",
+            CampaignStatus::Cancelled => "Cancelled",
+            CampaignStatus::Failed => "Failed",
+        }
+    }
+}
+
+impl std::str::FromStr for CampaignStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Draft" => Ok(CampaignStatus::Draft),
+            "Active" => Ok(CampaignStatus::Active),
+            "Paused" => Ok(CampaignStatus::Paused),
+            "Completed" => Ok(CampaignStatus::Completed),
+            "Cancelled" => Ok(CampaignStatus::Cancelled),
+            "Failed" => Ok(CampaignStatus::Failed),
+            _ => Err(format!("Invalid campaign status: {}", s)),
+        }
+    }
+}
+
 // Campaign Entity (Rich Domain Model)
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Campaign {
@@ -32,6 +61,8 @@ pub struct Campaign {
     nft_price: NFTPrice,
     nft_supply: NFTSupply,
     target: Option<CampaignTarget>,
+    #[serde(default)]
+    target_audience: Option<TargetAudience>,
     status: CampaignStatus,
     nft_contract_address: Option<String>,
     created_at: DateTime<Utc>,
@@ -90,6 +121,7 @@ impl Campaign {
             nft_price: nft_price.clone(),
             nft_supply,
             target,
+            target_audience: None,
             status: CampaignStatus::Draft,
             nft_contract_address: None,
             created_at: now,
@@ -346,6 +378,16 @@ impl Campaign {
         self.target.as_ref()
     }
 
+    pub fn target_audience(&self) -> Option<&TargetAudience> {
+        self.target_audience.as_ref()
+    }
+
+    /// Restrict who can participate; `None` opens the campaign to everyone
+    pub fn set_target_audience(&mut self, target_audience: Option<TargetAudience>) {
+        self.target_audience = target_audience.filter(|audience| !audience.is_open());
+        self.updated_at = Utc::now();
+    }
+
     pub fn nft_contract_address(&self) -> Option<&str> {
         self.nft_contract_address.as_deref()
     }
@@ -3,9 +3,19 @@ pub mod value_objects;
 pub mod events;
 pub mod repository;
 pub mod aggregates;
+pub mod audience;
+pub mod nft_mint;
+pub mod analytics;
+pub mod budget;
+pub mod schedule;
 
 pub use entities::*;
 pub use value_objects::*;
 pub use events::*;
 pub use repository::*;
-pub use aggregates::*; 
\ No newline at end of file
+pub use aggregates::*;
+pub use audience::*;
+pub use nft_mint::*;
+pub use analytics::*;
+pub use budget::*;
+pub use schedule::*;
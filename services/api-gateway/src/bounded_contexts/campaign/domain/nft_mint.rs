@@ -0,0 +1,236 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+use vibestream_types::{NFTAttribute, NFTMetadata, NftMintRequest};
+
+pub const CAMPAIGN_NFT_SYMBOL: &str = "VIBE";
+pub const CAMPAIGN_NFT_SELLER_FEE_BASIS_POINTS: u16 = 500;
+// Límite de Metaplex para el nombre del token
+const MAX_NFT_NAME_LEN: usize = 32;
+
+/// Collection settings of a campaign as seen by minting. `collection_size` is
+/// `nft_collection_size` when the campaign sets one, `max_nfts` otherwise.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NftCollectionSettings {
+    pub campaign_id: Uuid,
+    pub campaign_name: String,
+    pub description: Option<String>,
+    pub song_id: Uuid,
+    pub artist_id: Uuid,
+    pub collection_address: Option<String>,
+    pub metadata_url: Option<String>,
+    pub collection_size: u32,
+    pub minted: u32,
+}
+
+impl NftCollectionSettings {
+    pub fn is_sold_out(&self) -> bool {
+        self.minted >= self.collection_size
+    }
+}
+
+/// Participant receiving a campaign NFT
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MintParticipant {
+    pub user_id: Uuid,
+    pub username: String,
+    pub wallet_address: Option<String>,
+    pub joined_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NftMintStatus {
+    Pending,
+    Minted,
+    Failed,
+}
+
+impl fmt::Display for NftMintStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NftMintStatus::Pending => write!(f, "pending"),
+            NftMintStatus::Minted => write!(f, "minted"),
+            NftMintStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+impl FromStr for NftMintStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(NftMintStatus::Pending),
+            "minted" => Ok(NftMintStatus::Minted),
+            "failed" => Ok(NftMintStatus::Failed),
+            other => Err(format!("Unknown NFT mint status: {}", other)),
+        }
+    }
+}
+
+/// Mint of a campaign NFT for one participation. `request_id` is the
+/// idempotency key sent to solana-integration, so retried messages for the
+/// same mint never produce a second token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CampaignNftMint {
+    pub request_id: Uuid,
+    pub campaign_id: Uuid,
+    pub user_id: Uuid,
+    pub wallet_address: String,
+    pub metadata: NFTMetadata,
+    pub status: NftMintStatus,
+    pub attempts: u32,
+    pub mint_address: Option<String>,
+    pub signature: Option<String>,
+    pub last_error: Option<String>,
+    pub minted_at: Option<DateTime<Utc>>,
+}
+
+impl CampaignNftMint {
+    /// New mint about to be sent for the first time
+    pub fn pending(campaign_id: Uuid, user_id: Uuid, wallet_address: String, metadata: NFTMetadata) -> Self {
+        Self {
+            request_id: Uuid::new_v4(),
+            campaign_id,
+            user_id,
+            wallet_address,
+            metadata,
+            status: NftMintStatus::Pending,
+            attempts: 1,
+            mint_address: None,
+            signature: None,
+            last_error: None,
+            minted_at: None,
+        }
+    }
+
+    pub fn to_request(&self, reply_queue: &str) -> NftMintRequest {
+        NftMintRequest {
+            request_id: self.request_id,
+            recipient_wallet: self.wallet_address.clone(),
+            metadata: self.metadata.clone(),
+            reply_queue: reply_queue.to_string(),
+            attempt: self.attempts,
+        }
+    }
+}
+
+/// Metadata for a participant's NFT. Campaign settings give the defaults; the
+/// optional override (`name`, `description`, `uri`, `attributes`) replaces them
+/// field by field.
+pub fn build_nft_metadata(
+    collection: &NftCollectionSettings,
+    participant: &MintParticipant,
+    metadata_override: Option<&serde_json::Value>,
+) -> NFTMetadata {
+    let text = |key: &str| {
+        metadata_override
+            .and_then(|value| value.get(key))
+            .and_then(|value| value.as_str())
+            .map(str::to_string)
+    };
+
+    let name = text("name").unwrap_or_else(|| format!("{} - {}", collection.campaign_name, participant.username));
+    let uri = text("uri").or_else(|| text("image")).unwrap_or_else(|| match &collection.metadata_url {
+        Some(base) => format!("{}/{}.json", base.trim_end_matches('/'), participant.user_id),
+        None => format!("ipfs://campaign-{}/participant-{}", collection.campaign_id, participant.user_id),
+    });
+
+    let mut attributes = vec![
+        NFTAttribute { trait_type: "Campaign".to_string(), value: collection.campaign_name.clone() },
+        NFTAttribute { trait_type: "Song".to_string(), value: collection.song_id.to_string() },
+        NFTAttribute { trait_type: "Participant".to_string(), value: participant.username.clone() },
+        NFTAttribute { trait_type: "Joined".to_string(), value: participant.joined_at.date_naive().to_string() },
+    ];
+    let extra = metadata_override
+        .and_then(|value| value.get("attributes"))
+        .and_then(|value| value.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|attribute| {
+            let trait_type = attribute.get("trait_type")?.as_str()?.to_string();
+            let value = match attribute.get("value")? {
+                serde_json::Value::String(value) => value.clone(),
+                other => other.to_string(),
+            };
+            Some(NFTAttribute { trait_type, value })
+        });
+    for attribute in extra {
+        attributes.retain(|existing| existing.trait_type != attribute.trait_type);
+        attributes.push(attribute);
+    }
+
+    NFTMetadata {
+        name: name.chars().take(MAX_NFT_NAME_LEN).collect(),
+        symbol: CAMPAIGN_NFT_SYMBOL.to_string(),
+        description: text("description")
+            .or_else(|| collection.description.clone())
+            .unwrap_or_else(|| format!("Participation NFT for {}", collection.campaign_name)),
+        uri,
+        attributes,
+        collection: collection.collection_address.clone(),
+        seller_fee_basis_points: CAMPAIGN_NFT_SELLER_FEE_BASIS_POINTS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn collection() -> NftCollectionSettings {
+        NftCollectionSettings {
+            campaign_id: Uuid::new_v4(),
+            campaign_name: "Summer Release Campaign".to_string(),
+            description: None,
+            song_id: Uuid::new_v4(),
+            artist_id: Uuid::new_v4(),
+            collection_address: Some("CoLLecti0n1111111111111111111111111111111111".to_string()),
+            metadata_url: Some("https://metadata.vibestream.test/summer/".to_string()),
+            collection_size: 100,
+            minted: 0,
+        }
+    }
+
+    fn participant() -> MintParticipant {
+        MintParticipant {
+            user_id: Uuid::new_v4(),
+            username: "superfan".to_string(),
+            wallet_address: Some("Wa11et111111111111111111111111111111111111".to_string()),
+            joined_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_metadata_comes_from_collection_settings_and_participant() {
+        let collection = collection();
+        let participant = participant();
+
+        let metadata = build_nft_metadata(&collection, &participant, None);
+
+        assert_eq!(metadata.name, "Summer Release Campaign - superf");
+        assert_eq!(metadata.symbol, CAMPAIGN_NFT_SYMBOL);
+        assert_eq!(metadata.uri, format!("https://metadata.vibestream.test/summer/{}.json", participant.user_id));
+        assert_eq!(metadata.collection, collection.collection_address);
+        assert!(metadata.attributes.iter().any(|a| a.trait_type == "Participant" && a.value == "superfan"));
+    }
+
+    #[test]
+    fn test_metadata_override_replaces_defaults() {
+        let override_value = json!({
+            "name": "Personal Campaign NFT",
+            "description": "Special NFT for top participant",
+            "attributes": [{ "trait_type": "Rarity", "value": "Rare" }, { "trait_type": "Campaign", "value": "Custom" }]
+        });
+
+        let metadata = build_nft_metadata(&collection(), &participant(), Some(&override_value));
+
+        assert_eq!(metadata.name, "Personal Campaign NFT");
+        assert_eq!(metadata.description, "Special NFT for top participant");
+        assert!(metadata.attributes.iter().any(|a| a.trait_type == "Rarity" && a.value == "Rare"));
+        assert_eq!(metadata.attributes.iter().filter(|a| a.trait_type == "Campaign").count(), 1);
+        assert!(metadata.attributes.iter().any(|a| a.trait_type == "Campaign" && a.value == "Custom"));
+    }
+}
@@ -1,7 +1,14 @@
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
 use uuid::Uuid;
 
+use super::analytics::{CampaignActivityEvent, CampaignAnalyticsReport, CampaignAnalyticsSnapshot};
+use super::budget::{CampaignBudget, RewardCharge};
+use super::schedule::{CampaignSchedule, ScheduledTransition};
+use super::audience::{AudienceEstimate, UserProfile};
+use super::value_objects::TargetAudience;
 use super::entities::Campaign;
+use super::nft_mint::{CampaignNftMint, MintParticipant, NftCollectionSettings};
 use crate::shared::domain::repositories::RepoResult;
 
 #[async_trait]
@@ -19,4 +26,83 @@ pub trait CampaignParticipationRepository: Send + Sync {
     async fn record_participation(&self, campaign_id: Uuid, user_id: Uuid) -> RepoResult<()>;
     async fn is_participating(&self, campaign_id: Uuid, user_id: Uuid) -> RepoResult<bool>;
 }
- 
\ No newline at end of file
+
+/// NFT mints recorded on campaign participations
+#[async_trait]
+pub trait CampaignNftMintRepository: Send + Sync {
+    async fn find_collection(&self, campaign_id: Uuid) -> RepoResult<Option<NftCollectionSettings>>;
+    async fn find_participant(&self, campaign_id: Uuid, user_id: Uuid) -> RepoResult<Option<MintParticipant>>;
+    /// Participants without a pending or minted NFT, oldest first
+    async fn find_participants_without_nft(&self, campaign_id: Uuid, limit: u32) -> RepoResult<Vec<Uuid>>;
+    /// Take one slot of the collection and attach `mint` to the participation,
+    /// atomically. If the participation already has a pending or minted NFT that
+    /// mint is returned unchanged and no slot is taken; a sold-out collection is
+    /// a `DomainRuleViolation`.
+    async fn reserve_mint(&self, mint: &CampaignNftMint) -> RepoResult<CampaignNftMint>;
+    async fn find_mint(&self, request_id: Uuid) -> RepoResult<Option<CampaignNftMint>>;
+    /// Count another delivery attempt of a pending mint; returns the new count
+    async fn record_retry(&self, request_id: Uuid, reason: &str) -> RepoResult<u32>;
+    /// `false` when the mint was no longer pending (duplicate result)
+    async fn mark_minted(&self, request_id: Uuid, mint_address: &str, signature: &str) -> RepoResult<bool>;
+    /// Fail a pending mint and give its slot back to the collection
+    async fn mark_failed(&self, request_id: Uuid, reason: &str) -> RepoResult<bool>;
+}
+
+#[async_trait]
+pub trait AudienceRepository: Send + Sync {
+    /// Audience attributes of a user; users without activity get an empty profile
+    async fn find_user_profile(&self, user_id: Uuid) -> RepoResult<UserProfile>;
+    /// Reach of `audience` among users with listening activity; `None` targets everyone
+    async fn estimate_reach(&self, audience: Option<&TargetAudience>) -> RepoResult<AudienceEstimate>;
+}
+
+/// Event store of campaign activity and the analytics projections built from it
+#[async_trait]
+pub trait CampaignAnalyticsRepository: Send + Sync {
+    /// Store the event and apply it to the projections in the same transaction
+    async fn append(&self, event: &CampaignActivityEvent) -> RepoResult<()>;
+    async fn load_report(&self, campaign_id: Uuid) -> RepoResult<Option<CampaignAnalyticsReport>>;
+    /// Drop the campaign's projections and replay its events from the store;
+    /// returns how many events were replayed
+    async fn rebuild(&self, campaign_id: Uuid) -> RepoResult<u64>;
+}
+
+/// Daily analytics snapshots of campaigns, kept for historical reporting
+#[async_trait]
+pub trait CampaignSnapshotRepository: Send + Sync {
+    /// Campaigns that ran during `date` and have no snapshot for it yet
+    async fn find_campaigns_to_snapshot(&self, date: NaiveDate, limit: u32) -> RepoResult<Vec<Uuid>>;
+    /// Calculate the snapshot of `date` from the event store and the listens
+    /// of the campaign's song; `None` if the campaign does not exist
+    async fn compute_snapshot(&self, campaign_id: Uuid, date: NaiveDate) -> RepoResult<Option<CampaignAnalyticsSnapshot>>;
+    /// Store a snapshot; an existing one for the same date is kept
+    async fn save_snapshot(&self, snapshot: &CampaignAnalyticsSnapshot) -> RepoResult<()>;
+    async fn find_latest_snapshot(&self, campaign_id: Uuid) -> RepoResult<Option<CampaignAnalyticsSnapshot>>;
+    /// Snapshots between both dates, inclusive, oldest first
+    async fn find_snapshots(&self, campaign_id: Uuid, start_date: NaiveDate, end_date: NaiveDate) -> RepoResult<Vec<CampaignAnalyticsSnapshot>>;
+}
+
+/// Reward budget of campaigns. Every operation locks the campaign row, so
+/// concurrent participations never spend more than the budget.
+#[async_trait]
+pub trait CampaignBudgetRepository: Send + Sync {
+    async fn find_budget(&self, campaign_id: Uuid) -> RepoResult<Option<CampaignBudget>>;
+    /// Charge the reward and record the participation in the same transaction.
+    /// Rejected charges record nothing but may still pause the campaign.
+    async fn reward_participation(&self, campaign_id: Uuid, user_id: Uuid) -> RepoResult<(RewardCharge, CampaignBudget)>;
+    async fn top_up(&self, campaign_id: Uuid, amount: f64) -> RepoResult<CampaignBudget>;
+    async fn resume(&self, campaign_id: Uuid) -> RepoResult<CampaignBudget>;
+}
+
+/// Date-driven lifecycle of campaigns. Transitions lock the campaign row and
+/// re-check the schedule, so a manual change racing the scheduler wins or loses cleanly.
+#[async_trait]
+pub trait CampaignScheduleRepository: Send + Sync {
+    async fn find_schedule(&self, campaign_id: Uuid) -> RepoResult<Option<CampaignSchedule>>;
+    /// Campaigns with a transition due at `now`, oldest deadline first
+    async fn find_due(&self, now: DateTime<Utc>, limit: u32) -> RepoResult<Vec<Uuid>>;
+    /// Apply the transition due at `now`; `None` if there is none any more
+    async fn apply_due_transition(&self, campaign_id: Uuid, now: DateTime<Utc>) -> RepoResult<Option<(ScheduledTransition, CampaignSchedule)>>;
+    async fn activate(&self, campaign_id: Uuid, now: DateTime<Utc>, force: bool) -> RepoResult<CampaignSchedule>;
+    async fn update_options(&self, campaign_id: Uuid, auto_start: bool, distribute_nfts_on_end: bool) -> RepoResult<CampaignSchedule>;
+}
@@ -0,0 +1,127 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::entities::CampaignStatus;
+use crate::shared::domain::errors::AppError;
+
+/// Calendario de una campaña: ventana activa `[start_date, end_date)` y
+/// opciones del artista para las transiciones automáticas.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CampaignSchedule {
+    pub campaign_id: Uuid,
+    pub artist_id: Uuid,
+    pub status: CampaignStatus,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    pub auto_start: bool,
+    pub distribute_nfts_on_end: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScheduledTransition {
+    Activate,
+    Complete,
+}
+
+impl CampaignSchedule {
+    pub fn is_within_window(&self, now: DateTime<Utc>) -> bool {
+        self.start_date <= now && now < self.end_date
+    }
+
+    /// Transición que toca a la hora `now`, si hay alguna
+    pub fn due_transition(&self, now: DateTime<Utc>) -> Option<ScheduledTransition> {
+        match self.status {
+            CampaignStatus::Draft if self.auto_start && self.is_within_window(now) => Some(ScheduledTransition::Activate),
+            CampaignStatus::Active | CampaignStatus::Paused if now >= self.end_date => Some(ScheduledTransition::Complete),
+            _ => None,
+        }
+    }
+
+    /// Aplicar la transición pendiente. Devuelve la que se aplicó.
+    pub fn apply_due_transition(&mut self, now: DateTime<Utc>) -> Option<ScheduledTransition> {
+        let transition = self.due_transition(now)?;
+        self.status = match transition {
+            ScheduledTransition::Activate => CampaignStatus::Active,
+            ScheduledTransition::Complete => CampaignStatus::Completed,
+        };
+        Some(transition)
+    }
+
+    /// Activación manual. Antes de `start_date` solo con `force`.
+    pub fn activate(&mut self, now: DateTime<Utc>, force: bool) -> Result<(), AppError> {
+        if self.status != CampaignStatus::Draft {
+            return Err(AppError::InvalidState("Only draft campaigns can be activated".to_string()));
+        }
+        if now >= self.end_date {
+            return Err(AppError::DomainRuleViolation("Cannot activate a campaign after its end date".to_string()));
+        }
+        if now < self.start_date && !force {
+            return Err(AppError::DomainRuleViolation(format!(
+                "Campaign starts at {}; confirm an early start to activate it now",
+                self.start_date.to_rfc3339()
+            )));
+        }
+        self.status = CampaignStatus::Active;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 11, 1, 12, 0, 0).unwrap()
+    }
+
+    fn schedule(status: CampaignStatus, auto_start: bool) -> CampaignSchedule {
+        CampaignSchedule {
+            campaign_id: Uuid::new_v4(),
+            artist_id: Uuid::new_v4(),
+            status,
+            start_date: start(),
+            end_date: start() + Duration::days(7),
+            auto_start,
+            distribute_nfts_on_end: false,
+        }
+    }
+
+    #[test]
+    fn test_auto_start_campaign_follows_the_clock() {
+        let mut campaign = schedule(CampaignStatus::Draft, true);
+
+        assert_eq!(campaign.apply_due_transition(start() - Duration::seconds(1)), None);
+        assert_eq!(campaign.apply_due_transition(start()), Some(ScheduledTransition::Activate));
+        assert_eq!(campaign.status, CampaignStatus::Active);
+        assert_eq!(campaign.apply_due_transition(start() + Duration::days(3)), None);
+        assert_eq!(campaign.apply_due_transition(campaign.end_date), Some(ScheduledTransition::Complete));
+        assert_eq!(campaign.status, CampaignStatus::Completed);
+        assert_eq!(campaign.apply_due_transition(campaign.end_date + Duration::days(1)), None);
+    }
+
+    #[test]
+    fn test_without_auto_start_only_completion_is_automatic() {
+        let mut draft = schedule(CampaignStatus::Draft, false);
+        assert_eq!(draft.due_transition(start() + Duration::hours(1)), None);
+
+        let paused = schedule(CampaignStatus::Paused, false);
+        assert_eq!(paused.due_transition(paused.end_date), Some(ScheduledTransition::Complete));
+        assert!(!paused.is_within_window(paused.end_date));
+    }
+
+    #[test]
+    fn test_early_manual_activation_requires_force() {
+        let mut campaign = schedule(CampaignStatus::Draft, false);
+        let early = start() - Duration::days(1);
+
+        assert!(matches!(campaign.activate(early, false), Err(AppError::DomainRuleViolation(_))));
+        campaign.activate(early, true).unwrap();
+        assert_eq!(campaign.status, CampaignStatus::Active);
+        assert!(matches!(campaign.activate(start(), false), Err(AppError::InvalidState(_))));
+
+        let mut expired = schedule(CampaignStatus::Draft, false);
+        assert!(matches!(expired.activate(expired.end_date, true), Err(AppError::DomainRuleViolation(_))));
+    }
+}
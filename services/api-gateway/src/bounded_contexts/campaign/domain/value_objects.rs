@@ -82,6 +82,11 @@ impl DateRange {
         now >= self.start && now <= self.end
     }
 
+    /// Ventana activa `[start, end)` a la hora `now`
+    pub fn contains(&self, now: DateTime<Utc>) -> bool {
+        self.start <= now && now < self.end
+    }
+
     pub fn is_future(&self) -> bool {
         Utc::now() < self.start
     }
@@ -383,6 +388,76 @@ impl CampaignTarget {
     }
 }
 
+// Fan Level Value Object (derived from how much a user listens)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FanLevel {
+    Casual,   // 0-50 listens
+    Regular,  // 51-500 listens
+    Superfan, // 501+ listens
+}
+
+impl FanLevel {
+    pub fn from_listen_count(count: u64) -> Self {
+        match count {
+            0..=50 => FanLevel::Casual,
+            51..=500 => FanLevel::Regular,
+            _ => FanLevel::Superfan,
+        }
+    }
+
+    /// Fewest completed listens that reach this level
+    pub fn min_listen_count(&self) -> u64 {
+        match self {
+            FanLevel::Casual => 0,
+            FanLevel::Regular => 51,
+            FanLevel::Superfan => 501,
+        }
+    }
+
+    pub fn from_string(value: &str) -> Result<Self, AppError> {
+        match value.trim().to_lowercase().as_str() {
+            "casual" => Ok(FanLevel::Casual),
+            "regular" => Ok(FanLevel::Regular),
+            "superfan" => Ok(FanLevel::Superfan),
+            other => Err(AppError::ValidationError(format!("Unknown fan level: {}", other))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FanLevel::Casual => "casual",
+            FanLevel::Regular => "regular",
+            FanLevel::Superfan => "superfan",
+        }
+    }
+}
+
+impl fmt::Display for FanLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+// Target Audience Value Object. An empty list (or no fan level) means
+// the campaign does not restrict on that dimension.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TargetAudience {
+    pub locations: Vec<String>,
+    pub genres: Vec<String>,
+    pub fan_level: Option<FanLevel>,
+}
+
+impl TargetAudience {
+    pub fn new(locations: Vec<String>, genres: Vec<String>, fan_level: Option<FanLevel>) -> Self {
+        Self { locations, genres, fan_level }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.locations.is_empty() && self.genres.is_empty() && self.fan_level.is_none()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -410,6 +485,17 @@ mod tests {
         assert!(DateRange::new(start, end).is_err());
     }
 
+    #[test]
+    fn test_date_range_contains_excludes_end() {
+        let start = Utc::now();
+        let end = start + chrono::Duration::days(7);
+        let date_range = DateRange::new(start, end).unwrap();
+
+        assert!(!date_range.contains(start - chrono::Duration::seconds(1)));
+        assert!(date_range.contains(start));
+        assert!(!date_range.contains(end));
+    }
+
     #[test]
     fn test_boost_multiplier_validation() {
         assert!(BoostMultiplier::new(2.5).is_ok());
@@ -446,4 +532,17 @@ mod tests {
         target.update_progress(1000.0);
         assert!(target.is_achieved());
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_fan_level_thresholds() {
+        assert_eq!(FanLevel::from_listen_count(0), FanLevel::Casual);
+        assert_eq!(FanLevel::from_listen_count(50), FanLevel::Casual);
+        assert_eq!(FanLevel::from_listen_count(51), FanLevel::Regular);
+        assert_eq!(FanLevel::from_listen_count(500), FanLevel::Regular);
+        assert_eq!(FanLevel::from_listen_count(501), FanLevel::Superfan);
+        assert!(FanLevel::Superfan > FanLevel::Regular && FanLevel::Regular > FanLevel::Casual);
+        for level in [FanLevel::Casual, FanLevel::Regular, FanLevel::Superfan] {
+            assert_eq!(FanLevel::from_listen_count(level.min_listen_count()), level);
+        }
+    }
+}
@@ -0,0 +1,388 @@
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::{PgPool, Postgres, Row, Transaction};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+use crate::bounded_contexts::campaign::domain::analytics::{
+    end_of_day, BudgetAnalysis, CampaignActivity, CampaignActivityEvent, CampaignAnalyticsReport,
+    CampaignAnalyticsSnapshot, ConversionFunnel,
+};
+use crate::bounded_contexts::campaign::domain::repository::{CampaignAnalyticsRepository, CampaignSnapshotRepository};
+use crate::shared::domain::errors::AppError;
+use crate::shared::domain::repositories::RepoResult;
+
+/// Event store `campaign_events` más las proyecciones `campaign_analytics_*`.
+///
+/// Cada evento se guarda y se aplica en la misma transacción, con la fila de
+/// totales de la campaña bloqueada: los eventos de una campaña se aplican en
+/// el orden de su `sequence` y `last_sequence` impide aplicarlos dos veces.
+pub struct PostgresCampaignAnalyticsRepository {
+    pool: PgPool,
+}
+
+impl PostgresCampaignAnalyticsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Bloquear (creándola si hace falta) la fila de totales de la campaña
+    async fn lock_totals(tx: &mut Transaction<'_, Postgres>, campaign_id: Uuid) -> RepoResult<()> {
+        sqlx::query("INSERT INTO campaign_analytics_totals (campaign_id) VALUES ($1) ON CONFLICT (campaign_id) DO NOTHING")
+            .bind(campaign_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to initialize campaign analytics: {}", e)))?;
+        sqlx::query("SELECT campaign_id FROM campaign_analytics_totals WHERE campaign_id = $1 FOR UPDATE")
+            .bind(campaign_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to lock campaign analytics: {}", e)))?;
+        Ok(())
+    }
+
+    async fn apply(tx: &mut Transaction<'_, Postgres>, sequence: i64, event: &CampaignActivityEvent) -> RepoResult<()> {
+        let reward = match event.activity {
+            CampaignActivity::RewardGranted { amount } => amount,
+            _ => 0.0,
+        };
+        let fresh = sqlx::query(
+            r#"UPDATE campaign_analytics_totals
+               SET last_sequence = $2, reward_spent = reward_spent + $3, updated_at = NOW()
+               WHERE campaign_id = $1 AND last_sequence < $2"#,
+        )
+        .bind(event.campaign_id)
+        .bind(sequence)
+        .bind(reward)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update campaign analytics totals: {}", e)))?;
+        if fresh.rows_affected() == 0 {
+            return Ok(());
+        }
+
+        let (first_participated_at, region) = match &event.activity {
+            CampaignActivity::ActionPerformed { region, .. } => (Some(event.occurred_at), region.clone()),
+            _ => (None, None),
+        };
+        sqlx::query(
+            r#"INSERT INTO campaign_analytics_participants (campaign_id, user_id, funnel_stage, first_participated_at, region)
+               VALUES ($1, $2, $3, $4, $5)
+               ON CONFLICT (campaign_id, user_id) DO UPDATE SET
+                   funnel_stage = GREATEST(campaign_analytics_participants.funnel_stage, EXCLUDED.funnel_stage),
+                   first_participated_at = COALESCE(campaign_analytics_participants.first_participated_at, EXCLUDED.first_participated_at),
+                   region = COALESCE(campaign_analytics_participants.region, EXCLUDED.region)"#,
+        )
+        .bind(event.campaign_id)
+        .bind(event.user_id)
+        .bind(event.funnel_stage().level())
+        .bind(first_participated_at)
+        .bind(region)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update campaign participant analytics: {}", e)))?;
+
+        if let CampaignActivity::ActionPerformed { action_type, .. } = &event.activity {
+            sqlx::query(
+                r#"INSERT INTO campaign_analytics_actions (campaign_id, action_type, action_count)
+                   VALUES ($1, $2, 1)
+                   ON CONFLICT (campaign_id, action_type)
+                   DO UPDATE SET action_count = campaign_analytics_actions.action_count + 1"#,
+            )
+            .bind(event.campaign_id)
+            .bind(action_type)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to update campaign action analytics: {}", e)))?;
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CampaignAnalyticsRepository for PostgresCampaignAnalyticsRepository {
+    async fn append(&self, event: &CampaignActivityEvent) -> RepoResult<()> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to start transaction: {}", e)))?;
+        Self::lock_totals(&mut tx, event.campaign_id).await?;
+
+        let event_data = serde_json::to_value(&event.activity)
+            .map_err(|e| AppError::SerializationError(e.to_string()))?;
+        let sequence: i64 = sqlx::query_scalar(
+            r#"INSERT INTO campaign_events (event_id, campaign_id, user_id, event_type, event_data, occurred_at)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               RETURNING sequence"#,
+        )
+        .bind(event.event_id)
+        .bind(event.campaign_id)
+        .bind(event.user_id)
+        .bind(event.event_type())
+        .bind(event_data)
+        .bind(event.occurred_at)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to store campaign event: {}", e)))?;
+
+        Self::apply(&mut tx, sequence, event).await?;
+
+        tx.commit().await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit campaign event: {}", e)))
+    }
+
+    async fn load_report(&self, campaign_id: Uuid) -> RepoResult<Option<CampaignAnalyticsReport>> {
+        let Some(totals) = sqlx::query(
+            r#"SELECT c.budget::FLOAT8 AS budget, COALESCE(t.reward_spent, 0)::FLOAT8 AS reward_spent
+               FROM campaigns c
+               LEFT JOIN campaign_analytics_totals t ON t.campaign_id = c.id
+               WHERE c.id = $1"#,
+        )
+        .bind(campaign_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load campaign analytics: {}", e)))?
+        else {
+            return Ok(None);
+        };
+
+        let funnel = sqlx::query(
+            r#"SELECT COUNT(*) FILTER (WHERE funnel_stage >= 1) AS viewed,
+                      COUNT(*) FILTER (WHERE funnel_stage >= 2) AS participated,
+                      COUNT(*) FILTER (WHERE funnel_stage >= 3) AS rewarded,
+                      COUNT(*) FILTER (WHERE funnel_stage >= 4) AS nft_claimed
+               FROM campaign_analytics_participants WHERE campaign_id = $1"#,
+        )
+        .bind(campaign_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load campaign funnel: {}", e)))?;
+
+        let actions: BTreeMap<String, u64> = sqlx::query(
+            "SELECT action_type, action_count FROM campaign_analytics_actions WHERE campaign_id = $1",
+        )
+        .bind(campaign_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load campaign actions: {}", e)))?
+        .into_iter()
+        .map(|row| (row.get("action_type"), row.get::<i64, _>("action_count") as u64))
+        .collect();
+
+        let regions: BTreeMap<String, u64> = sqlx::query(
+            r#"SELECT region, COUNT(*) AS participants
+               FROM campaign_analytics_participants
+               WHERE campaign_id = $1 AND first_participated_at IS NOT NULL AND region IS NOT NULL
+               GROUP BY region"#,
+        )
+        .bind(campaign_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load campaign regions: {}", e)))?
+        .into_iter()
+        .map(|row| (row.get("region"), row.get::<i64, _>("participants") as u64))
+        .collect();
+
+        let joined_per_day: BTreeMap<NaiveDate, u64> = sqlx::query(
+            r#"SELECT (first_participated_at AT TIME ZONE 'UTC')::DATE AS day, COUNT(*) AS participants
+               FROM campaign_analytics_participants
+               WHERE campaign_id = $1 AND first_participated_at IS NOT NULL
+               GROUP BY day"#,
+        )
+        .bind(campaign_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load campaign participants over time: {}", e)))?
+        .into_iter()
+        .map(|row| (row.get("day"), row.get::<i64, _>("participants") as u64))
+        .collect();
+
+        let count = |column: &str| funnel.get::<i64, _>(column) as u64;
+        Ok(Some(CampaignAnalyticsReport::assemble(
+            campaign_id,
+            ConversionFunnel::new(count("viewed"), count("participated"), count("rewarded"), count("nft_claimed")),
+            actions,
+            regions,
+            joined_per_day,
+            BudgetAnalysis::new(totals.get("budget"), totals.get("reward_spent")),
+        )))
+    }
+
+    async fn rebuild(&self, campaign_id: Uuid) -> RepoResult<u64> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to start transaction: {}", e)))?;
+        Self::lock_totals(&mut tx, campaign_id).await?;
+
+        for table in ["campaign_analytics_participants", "campaign_analytics_actions"] {
+            sqlx::query(&format!("DELETE FROM {} WHERE campaign_id = $1", table))
+                .bind(campaign_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to clear {}: {}", table, e)))?;
+        }
+        sqlx::query("UPDATE campaign_analytics_totals SET reward_spent = 0, last_sequence = 0, updated_at = NOW() WHERE campaign_id = $1")
+            .bind(campaign_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to reset campaign analytics totals: {}", e)))?;
+
+        let rows = sqlx::query(
+            r#"SELECT sequence, event_id, user_id, event_data, occurred_at
+               FROM campaign_events WHERE campaign_id = $1 ORDER BY sequence"#,
+        )
+        .bind(campaign_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load campaign events: {}", e)))?;
+
+        let replayed = rows.len() as u64;
+        for row in rows {
+            let activity = serde_json::from_value(row.get("event_data"))
+                .map_err(|e| AppError::SerializationError(format!("Invalid campaign event: {}", e)))?;
+            let event = CampaignActivityEvent {
+                event_id: row.get("event_id"),
+                campaign_id,
+                user_id: row.get("user_id"),
+                activity,
+                occurred_at: row.get("occurred_at"),
+            };
+            Self::apply(&mut tx, row.get("sequence"), &event).await?;
+        }
+
+        tx.commit().await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit analytics rebuild: {}", e)))?;
+        Ok(replayed)
+    }
+}
+
+/// Escuchas que cuentan para las campañas (igual que las recompensas)
+const FINISHED_LISTEN_STATUSES: &str = "('completed', 'verified', 'rewarded')";
+
+impl PostgresCampaignAnalyticsRepository {
+    fn row_to_snapshot(row: &sqlx::postgres::PgRow) -> CampaignAnalyticsSnapshot {
+        CampaignAnalyticsSnapshot {
+            campaign_id: row.get("campaign_id"),
+            date: row.get("snapshot_date"),
+            participants_count: row.get::<i64, _>("participants_count") as u64,
+            nfts_minted: row.get::<i64, _>("nfts_minted") as u64,
+            unique_listeners: row.get::<i64, _>("unique_listeners") as u64,
+            total_listen_seconds: row.get::<i64, _>("total_listen_seconds") as u64,
+            budget_spent: row.get("budget_spent"),
+        }
+    }
+}
+
+#[async_trait]
+impl CampaignSnapshotRepository for PostgresCampaignAnalyticsRepository {
+    async fn find_campaigns_to_snapshot(&self, date: NaiveDate, limit: u32) -> RepoResult<Vec<Uuid>> {
+        sqlx::query_scalar(
+            r#"SELECT c.id FROM campaigns c
+               WHERE c.status <> 'Draft'
+                 AND c.start_date < $2
+                 AND COALESCE(c.completed_at, c.end_date) >= $2 - INTERVAL '1 day'
+                 AND NOT EXISTS (
+                     SELECT 1 FROM campaign_analytics_snapshots s
+                     WHERE s.campaign_id = c.id AND s.snapshot_date = $1
+                 )
+               ORDER BY c.start_date
+               LIMIT $3"#,
+        )
+        .bind(date)
+        .bind(end_of_day(date))
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find campaigns to snapshot: {}", e)))
+    }
+
+    async fn compute_snapshot(&self, campaign_id: Uuid, date: NaiveDate) -> RepoResult<Option<CampaignAnalyticsSnapshot>> {
+        // Mismo cálculo que CampaignAnalyticsSnapshot::calculate
+        let query = format!(
+            r#"SELECT c.id AS campaign_id, $2::DATE AS snapshot_date,
+                      (SELECT COUNT(DISTINCT e.user_id) FROM campaign_events e
+                       WHERE e.campaign_id = c.id AND e.occurred_at < $3
+                         AND e.event_type IN ('CampaignActionPerformed', 'CampaignRewardGranted', 'CampaignNftClaimed')
+                      ) AS participants_count,
+                      (SELECT COUNT(*) FROM campaign_events e
+                       WHERE e.campaign_id = c.id AND e.occurred_at < $3 AND e.event_type = 'CampaignNftClaimed'
+                      ) AS nfts_minted,
+                      (SELECT COALESCE(SUM((e.event_data->>'amount')::FLOAT8), 0) FROM campaign_events e
+                       WHERE e.campaign_id = c.id AND e.occurred_at < $3 AND e.event_type = 'CampaignRewardGranted'
+                      ) AS budget_spent,
+                      listens.unique_listeners,
+                      listens.total_listen_seconds
+               FROM campaigns c
+               CROSS JOIN LATERAL (
+                   SELECT COUNT(DISTINCT l.user_id) AS unique_listeners,
+                          COALESCE(SUM(l.listen_duration_seconds), 0)::BIGINT AS total_listen_seconds
+                   FROM listen_sessions l
+                   WHERE l.song_id = c.song_id
+                     AND l.status IN {}
+                     AND l.started_at >= c.start_date
+                     AND l.started_at < LEAST($3, COALESCE(c.completed_at, c.end_date))
+               ) listens
+               WHERE c.id = $1"#,
+            FINISHED_LISTEN_STATUSES
+        );
+        let row = sqlx::query(&query)
+            .bind(campaign_id)
+            .bind(date)
+            .bind(end_of_day(date))
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to compute campaign snapshot: {}", e)))?;
+        Ok(row.as_ref().map(Self::row_to_snapshot))
+    }
+
+    async fn save_snapshot(&self, snapshot: &CampaignAnalyticsSnapshot) -> RepoResult<()> {
+        sqlx::query(
+            r#"INSERT INTO campaign_analytics_snapshots
+                   (campaign_id, snapshot_date, participants_count, nfts_minted, unique_listeners, total_listen_seconds, budget_spent)
+               VALUES ($1, $2, $3, $4, $5, $6, $7)
+               ON CONFLICT (campaign_id, snapshot_date) DO NOTHING"#,
+        )
+        .bind(snapshot.campaign_id)
+        .bind(snapshot.date)
+        .bind(snapshot.participants_count as i64)
+        .bind(snapshot.nfts_minted as i64)
+        .bind(snapshot.unique_listeners as i64)
+        .bind(snapshot.total_listen_seconds as i64)
+        .bind(snapshot.budget_spent)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to save campaign snapshot: {}", e)))?;
+        Ok(())
+    }
+
+    async fn find_latest_snapshot(&self, campaign_id: Uuid) -> RepoResult<Option<CampaignAnalyticsSnapshot>> {
+        let row = sqlx::query(
+            r#"SELECT campaign_id, snapshot_date, participants_count, nfts_minted, unique_listeners,
+                      total_listen_seconds, budget_spent::FLOAT8 AS budget_spent
+               FROM campaign_analytics_snapshots
+               WHERE campaign_id = $1
+               ORDER BY snapshot_date DESC
+               LIMIT 1"#,
+        )
+        .bind(campaign_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load campaign snapshot: {}", e)))?;
+        Ok(row.as_ref().map(Self::row_to_snapshot))
+    }
+
+    async fn find_snapshots(&self, campaign_id: Uuid, start_date: NaiveDate, end_date: NaiveDate) -> RepoResult<Vec<CampaignAnalyticsSnapshot>> {
+        let rows = sqlx::query(
+            r#"SELECT campaign_id, snapshot_date, participants_count, nfts_minted, unique_listeners,
+                      total_listen_seconds, budget_spent::FLOAT8 AS budget_spent
+               FROM campaign_analytics_snapshots
+               WHERE campaign_id = $1 AND snapshot_date BETWEEN $2 AND $3
+               ORDER BY snapshot_date"#,
+        )
+        .bind(campaign_id)
+        .bind(start_date)
+        .bind(end_date)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load campaign snapshots: {}", e)))?;
+        Ok(rows.iter().map(Self::row_to_snapshot).collect())
+    }
+}
@@ -1,8 +1,12 @@
 //! Implementaciones de infraestructura para Campaign (repositorios, mensajería, etc.)
 
+pub mod analytics_repository;
 pub mod in_memory_repository;
 pub mod postgres_repository;
 pub mod event_publisher;
+pub mod nft_mint_queue;
 
+pub use analytics_repository::*;
 pub use postgres_repository::*;
-pub use event_publisher::*; 
\ No newline at end of file
+pub use event_publisher::*;
+pub use nft_mint_queue::*; 
\ No newline at end of file
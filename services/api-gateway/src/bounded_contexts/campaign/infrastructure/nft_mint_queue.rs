@@ -0,0 +1,93 @@
+//! Cola Redis hacia solana-integration para el minteo de NFTs de campañas
+
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use vibestream_types::{NftMintRequest, NftMintResult, QueueNames};
+
+use crate::bounded_contexts::campaign::application::nft_minting::{CampaignNftService, NftMintQueue};
+use crate::services::MessageQueue;
+use crate::shared::domain::errors::AppError;
+
+pub struct RedisNftMintQueue {
+    message_queue: MessageQueue,
+    queue_name: String,
+}
+
+impl RedisNftMintQueue {
+    pub fn new(message_queue: MessageQueue) -> Self {
+        Self {
+            message_queue,
+            queue_name: QueueNames::SOLANA_NFT_MINT.to_string(),
+        }
+    }
+
+    pub fn with_queue_name(mut self, queue_name: impl Into<String>) -> Self {
+        self.queue_name = queue_name.into();
+        self
+    }
+}
+
+#[async_trait]
+impl NftMintQueue for RedisNftMintQueue {
+    async fn publish(&self, request: &NftMintRequest) -> Result<(), AppError> {
+        let message = serde_json::to_string(request)
+            .map_err(|e| AppError::SerializationError(e.to_string()))?;
+        self.message_queue
+            .send_message(&self.queue_name, &message)
+            .await
+            .map_err(|e| AppError::ExternalServiceError(format!("Failed to enqueue NFT mint: {}", e)))
+    }
+}
+
+/// Consume los resultados de minteo que publica solana-integration
+pub struct CampaignNftMintResultWorker {
+    service: Arc<CampaignNftService>,
+    message_queue: MessageQueue,
+    running: Arc<AtomicBool>,
+}
+
+impl CampaignNftMintResultWorker {
+    pub fn new(service: Arc<CampaignNftService>, message_queue: MessageQueue) -> Self {
+        Self {
+            service,
+            message_queue,
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub async fn start(&self) -> Result<(), AppError> {
+        self.running.store(true, Ordering::Relaxed);
+        let queue_name = self.service.reply_queue().to_string();
+
+        tracing::info!("Starting campaign NFT mint result worker on {}", queue_name);
+
+        while self.running.load(Ordering::Relaxed) {
+            match self.message_queue.receive_message(&queue_name, 5).await {
+                Ok(Some(message_json)) => match serde_json::from_str::<NftMintResult>(&message_json) {
+                    Ok(result) => {
+                        let request_id = result.request_id;
+                        if let Err(e) = self.service.handle_result(result).await {
+                            tracing::error!("Failed to apply NFT mint result {}: {}", request_id, e);
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to deserialize NFT mint result: {}", e);
+                    }
+                },
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::error!("Error receiving message from queue {}: {}", queue_name, e);
+                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                }
+            }
+        }
+
+        tracing::info!("Campaign NFT mint result worker stopped");
+        Ok(())
+    }
+
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
@@ -26,6 +26,7 @@ struct CampaignRow {
     max_nfts: i32,
     nfts_sold: i32,
     target_revenue: Option<f64>,
+    target_audience: Option<serde_json::Value>,
     status: String,
     nft_contract_address: Option<String>,
     created_at: DateTime<Utc>,
@@ -49,8 +50,8 @@ impl CampaignRepository for PostgresCampaignRepository {
             r#"
             INSERT INTO campaigns (id, song_id, artist_id, name, description, start_date, end_date,
                                    boost_multiplier, nft_price, max_nfts, nfts_sold, target_revenue,
-                                   status, nft_contract_address, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)
+                                   status, nft_contract_address, created_at, updated_at, target_audience)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
             ON CONFLICT (id) DO UPDATE SET
                 song_id = EXCLUDED.song_id,
                 artist_id = EXCLUDED.artist_id,
@@ -65,7 +66,8 @@ impl CampaignRepository for PostgresCampaignRepository {
                 target_revenue = EXCLUDED.target_revenue,
                 status = EXCLUDED.status,
                 nft_contract_address = EXCLUDED.nft_contract_address,
-                updated_at = EXCLUDED.updated_at
+                updated_at = EXCLUDED.updated_at,
+                target_audience = EXCLUDED.target_audience
             "#,
         )
         .bind(campaign.id().value())
@@ -94,6 +96,7 @@ impl CampaignRepository for PostgresCampaignRepository {
         .bind(campaign.nft_contract_address())
         .bind(campaign.created_at())
         .bind(campaign.updated_at())
+        .bind(campaign.target_audience().map(|audience| serde_json::json!(audience)))
         .execute(&self.pool)
         .await
         .map_err(|e| crate::shared::domain::errors::AppError::Infrastructure(e.to_string()))?;
@@ -106,7 +109,7 @@ impl CampaignRepository for PostgresCampaignRepository {
             r#"
             SELECT id, song_id, artist_id, name, description, start_date, end_date,
                    boost_multiplier, nft_price, max_nfts, nfts_sold, target_revenue,
-                   status, nft_contract_address, created_at, updated_at
+                   status, nft_contract_address, created_at, updated_at, target_audience
             FROM campaigns
             WHERE id = $1
             "#
@@ -130,7 +133,7 @@ impl CampaignRepository for PostgresCampaignRepository {
             r#"
             SELECT id, song_id, artist_id, name, description, start_date, end_date,
                    boost_multiplier, nft_price, max_nfts, nfts_sold, target_revenue,
-                   status, nft_contract_address, created_at, updated_at
+                   status, nft_contract_address, created_at, updated_at, target_audience
             FROM campaigns
             WHERE artist_id = $1
             ORDER BY created_at DESC
@@ -156,7 +159,7 @@ impl CampaignRepository for PostgresCampaignRepository {
             r#"
             SELECT id, song_id, artist_id, name, description, start_date, end_date,
                    boost_multiplier, nft_price, max_nfts, nfts_sold, target_revenue,
-                   status, nft_contract_address, created_at, updated_at
+                   status, nft_contract_address, created_at, updated_at, target_audience
             FROM campaigns
             WHERE status = 'Active' 
             AND start_date <= NOW() 
@@ -183,7 +186,7 @@ impl CampaignRepository for PostgresCampaignRepository {
             r#"
             SELECT id, song_id, artist_id, name, description, start_date, end_date,
                    boost_multiplier, nft_price, max_nfts, nfts_sold, target_revenue,
-                   status, nft_contract_address, created_at, updated_at
+                   status, nft_contract_address, created_at, updated_at, target_audience
             FROM campaigns
             ORDER BY created_at DESC
             "#
@@ -310,6 +313,7 @@ impl CampaignRow {
             max_nfts: row.try_get("max_nfts").map_err(|e| format!("Failed to get max_nfts: {}", e))?,
             nfts_sold: row.try_get("nfts_sold").map_err(|e| format!("Failed to get nfts_sold: {}", e))?,
             target_revenue: row.try_get("target_revenue").map_err(|e| format!("Failed to get target_revenue: {}", e))?,
+            target_audience: row.try_get("target_audience").map_err(|e| format!("Failed to get target_audience: {}", e))?,
             status: row.try_get("status").map_err(|e| format!("Failed to get status: {}", e))?,
             nft_contract_address: row.try_get("nft_contract_address").map_err(|e| format!("Failed to get nft_contract_address: {}", e))?,
             created_at: row.try_get("created_at").map_err(|e| format!("Failed to get created_at: {}", e))?,
@@ -381,8 +385,14 @@ impl CampaignRow {
             None => None,
         };
 
+        let target_audience = match self.target_audience {
+            Some(value) => Some(serde_json::from_value::<TargetAudience>(value)
+                .map_err(|e| format!("Invalid target audience: {}", e))?),
+            None => None,
+        };
+
         // Create campaign using create method with contracts
-        let (campaign, _) = Campaign::create(
+        let (mut campaign, _) = Campaign::create(
             song_contract,
             artist_contract,
             name.value().to_string(),
@@ -393,6 +403,7 @@ impl CampaignRow {
             self.max_nfts as u32,
             self.target_revenue,
         ).map_err(|e| format!("Failed to create campaign: {}", e))?;
+        campaign.set_target_audience(target_audience);
 
         Ok(campaign)
     }
@@ -496,4 +507,637 @@ impl CampaignParticipationRepository for PostgresCampaignParticipationRepository
 
         Ok(count > 0)
     }
-}
\ No newline at end of file
+}
+
+// ============================================================================
+// NFT MINTS (sobre campaign_participants)
+// ============================================================================
+
+use crate::bounded_contexts::campaign::application::nft_minting::sold_out;
+use crate::bounded_contexts::campaign::domain::nft_mint::{CampaignNftMint, MintParticipant, NftCollectionSettings};
+use crate::bounded_contexts::campaign::domain::repository::CampaignNftMintRepository;
+use crate::shared::domain::errors::AppError;
+
+const MINT_COLUMNS: &str = r#"nft_mint_request_id, campaign_id, user_id, nft_wallet_address, nft_metadata, nft_mint_status,
+       nft_mint_attempts, nft_mint_address, nft_mint_signature, nft_mint_error, nft_minted_at"#;
+
+impl PostgresCampaignParticipationRepository {
+    fn row_to_mint(row: sqlx::postgres::PgRow) -> RepoResult<CampaignNftMint> {
+        let metadata = serde_json::from_value(row.get("nft_metadata"))
+            .map_err(|e| AppError::SerializationError(format!("Invalid NFT metadata: {}", e)))?;
+
+        Ok(CampaignNftMint {
+            request_id: row.get("nft_mint_request_id"),
+            campaign_id: row.get("campaign_id"),
+            user_id: row.get("user_id"),
+            wallet_address: row.get("nft_wallet_address"),
+            metadata,
+            status: row.get::<String, _>("nft_mint_status").parse().map_err(AppError::SerializationError)?,
+            attempts: row.get::<i32, _>("nft_mint_attempts") as u32,
+            mint_address: row.get("nft_mint_address"),
+            signature: row.get("nft_mint_signature"),
+            last_error: row.get("nft_mint_error"),
+            minted_at: row.get("nft_minted_at"),
+        })
+    }
+}
+
+#[async_trait]
+impl CampaignNftMintRepository for PostgresCampaignParticipationRepository {
+    async fn find_collection(&self, campaign_id: Uuid) -> RepoResult<Option<NftCollectionSettings>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, name, description, song_id, artist_id, nft_contract_address, nft_metadata_url,
+                   COALESCE(nft_collection_size, max_nfts) AS collection_size, nfts_minted
+            FROM campaigns
+            WHERE id = $1
+            "#
+        )
+        .bind(campaign_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+
+        Ok(row.map(|row| NftCollectionSettings {
+            campaign_id: row.get("id"),
+            campaign_name: row.get("name"),
+            description: row.get("description"),
+            song_id: row.get("song_id"),
+            artist_id: row.get("artist_id"),
+            collection_address: row.get("nft_contract_address"),
+            metadata_url: row.get("nft_metadata_url"),
+            collection_size: row.get::<i32, _>("collection_size") as u32,
+            minted: row.get::<i32, _>("nfts_minted") as u32,
+        }))
+    }
+
+    async fn find_participant(&self, campaign_id: Uuid, user_id: Uuid) -> RepoResult<Option<MintParticipant>> {
+        let row = sqlx::query(
+            r#"
+            SELECT cp.user_id, u.username, u.wallet_address, cp.joined_at
+            FROM campaign_participants cp
+            JOIN users u ON u.id = cp.user_id
+            WHERE cp.campaign_id = $1 AND cp.user_id = $2
+            "#
+        )
+        .bind(campaign_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+
+        Ok(row.map(|row| MintParticipant {
+            user_id: row.get("user_id"),
+            username: row.get("username"),
+            wallet_address: row.get("wallet_address"),
+            joined_at: row.get("joined_at"),
+        }))
+    }
+
+    async fn find_participants_without_nft(&self, campaign_id: Uuid, limit: u32) -> RepoResult<Vec<Uuid>> {
+        sqlx::query_scalar(
+            r#"
+            SELECT user_id FROM campaign_participants
+            WHERE campaign_id = $1 AND (nft_mint_status IS NULL OR nft_mint_status = 'failed')
+            ORDER BY joined_at
+            LIMIT $2
+            "#
+        )
+        .bind(campaign_id)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Infrastructure(e.to_string()))
+    }
+
+    async fn reserve_mint(&self, mint: &CampaignNftMint) -> RepoResult<CampaignNftMint> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+
+        let current = sqlx::query_scalar::<_, Option<String>>(
+            "SELECT nft_mint_status FROM campaign_participants WHERE campaign_id = $1 AND user_id = $2 FOR UPDATE"
+        )
+        .bind(mint.campaign_id)
+        .bind(mint.user_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::Infrastructure(e.to_string()))?
+        .ok_or_else(|| AppError::NotFound(format!(
+            "User {} is not participating in campaign {}", mint.user_id, mint.campaign_id
+        )))?;
+
+        // Ya tiene un NFT en curso o minteado: idempotente, no se toma otro slot
+        if matches!(current.as_deref(), Some("pending") | Some("minted")) {
+            let row = sqlx::query(&format!(
+                "SELECT {} FROM campaign_participants WHERE campaign_id = $1 AND user_id = $2", MINT_COLUMNS
+            ))
+            .bind(mint.campaign_id)
+            .bind(mint.user_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+            return Self::row_to_mint(row);
+        }
+
+        // Reserva condicional: el lock de fila de la campaña serializa a los
+        // compradores concurrentes y nunca se supera el tamaño de la colección
+        let reserved = sqlx::query(
+            r#"
+            UPDATE campaigns SET nfts_minted = nfts_minted + 1, updated_at = NOW()
+            WHERE id = $1 AND nfts_minted < COALESCE(nft_collection_size, max_nfts)
+            "#
+        )
+        .bind(mint.campaign_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+        if reserved.rows_affected() == 0 {
+            return Err(sold_out(mint.campaign_id));
+        }
+
+        let metadata = serde_json::to_value(&mint.metadata)
+            .map_err(|e| AppError::SerializationError(e.to_string()))?;
+        sqlx::query(
+            r#"
+            UPDATE campaign_participants
+            SET nft_mint_request_id = $3, nft_mint_status = $4, nft_wallet_address = $5, nft_metadata = $6,
+                nft_mint_attempts = $7, nft_mint_address = NULL, nft_mint_signature = NULL,
+                nft_mint_error = NULL, nft_minted_at = NULL
+            WHERE campaign_id = $1 AND user_id = $2
+            "#
+        )
+        .bind(mint.campaign_id)
+        .bind(mint.user_id)
+        .bind(mint.request_id)
+        .bind(mint.status.to_string())
+        .bind(&mint.wallet_address)
+        .bind(metadata)
+        .bind(mint.attempts as i32)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+
+        tx.commit().await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+        Ok(mint.clone())
+    }
+
+    async fn find_mint(&self, request_id: Uuid) -> RepoResult<Option<CampaignNftMint>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM campaign_participants WHERE nft_mint_request_id = $1", MINT_COLUMNS
+        ))
+        .bind(request_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+
+        row.map(Self::row_to_mint).transpose()
+    }
+
+    async fn record_retry(&self, request_id: Uuid, reason: &str) -> RepoResult<u32> {
+        let attempts: i32 = sqlx::query_scalar(
+            r#"
+            UPDATE campaign_participants
+            SET nft_mint_attempts = nft_mint_attempts + 1, nft_mint_error = $2
+            WHERE nft_mint_request_id = $1 AND nft_mint_status = 'pending'
+            RETURNING nft_mint_attempts
+            "#
+        )
+        .bind(request_id)
+        .bind(reason)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Infrastructure(e.to_string()))?
+        .ok_or_else(|| AppError::ConcurrencyConflict(format!("NFT mint {} is no longer pending", request_id)))?;
+
+        Ok(attempts as u32)
+    }
+
+    async fn mark_minted(&self, request_id: Uuid, mint_address: &str, signature: &str) -> RepoResult<bool> {
+        let result = sqlx::query(
+            r#"
+            UPDATE campaign_participants
+            SET nft_mint_status = 'minted', nft_mint_address = $2, nft_mint_signature = $3,
+                nft_mint_error = NULL, nft_minted_at = NOW()
+            WHERE nft_mint_request_id = $1 AND nft_mint_status = 'pending'
+            "#
+        )
+        .bind(request_id)
+        .bind(mint_address)
+        .bind(signature)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn mark_failed(&self, request_id: Uuid, reason: &str) -> RepoResult<bool> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+
+        let campaign_id: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            UPDATE campaign_participants SET nft_mint_status = 'failed', nft_mint_error = $2
+            WHERE nft_mint_request_id = $1 AND nft_mint_status = 'pending'
+            RETURNING campaign_id
+            "#
+        )
+        .bind(request_id)
+        .bind(reason)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+        let Some(campaign_id) = campaign_id else {
+            return Ok(false);
+        };
+
+        sqlx::query("UPDATE campaigns SET nfts_minted = nfts_minted - 1, updated_at = NOW() WHERE id = $1 AND nfts_minted > 0")
+            .bind(campaign_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+
+        tx.commit().await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+        Ok(true)
+    }
+}
+
+// ============================================================================
+// PRESUPUESTO DE RECOMPENSAS (sobre campaigns)
+// ============================================================================
+
+use crate::bounded_contexts::campaign::domain::budget::{CampaignBudget, RewardCharge, RewardDecision};
+use crate::bounded_contexts::campaign::domain::repository::CampaignBudgetRepository;
+
+const BUDGET_COLUMNS: &str = r#"id, artist_id, status, budget::FLOAT8 AS budget, budget_spent::FLOAT8 AS budget_spent,
+       reward_per_action::FLOAT8 AS reward_per_action, end_date"#;
+
+impl PostgresCampaignParticipationRepository {
+    fn row_to_budget(row: sqlx::postgres::PgRow) -> RepoResult<CampaignBudget> {
+        Ok(CampaignBudget {
+            campaign_id: row.get("id"),
+            artist_id: row.get("artist_id"),
+            status: row.get::<String, _>("status").parse().map_err(AppError::SerializationError)?,
+            budget: row.get("budget"),
+            spent: row.get("budget_spent"),
+            reward_per_action: row.get("reward_per_action"),
+            end_date: row.get("end_date"),
+        })
+    }
+
+    /// Bloquear la fila de la campaña: serializa a todos los que tocan su presupuesto
+    async fn lock_budget(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, campaign_id: Uuid) -> RepoResult<CampaignBudget> {
+        let row = sqlx::query(&format!("SELECT {} FROM campaigns WHERE id = $1 FOR UPDATE", BUDGET_COLUMNS))
+            .bind(campaign_id)
+            .fetch_optional(&mut **tx)
+            .await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?
+            .ok_or_else(|| AppError::NotFoundError(format!("Campaign {} not found", campaign_id)))?;
+        Self::row_to_budget(row)
+    }
+
+    async fn save_budget(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, budget: &CampaignBudget) -> RepoResult<()> {
+        sqlx::query(
+            "UPDATE campaigns SET status = $2, budget = $3, budget_spent = $4, updated_at = NOW() WHERE id = $1"
+        )
+        .bind(budget.campaign_id)
+        .bind(budget.status.as_str())
+        .bind(budget.budget)
+        .bind(budget.spent)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn update_budget<F>(&self, campaign_id: Uuid, change: F) -> RepoResult<CampaignBudget>
+    where
+        F: FnOnce(&mut CampaignBudget) -> Result<(), AppError> + Send,
+    {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+        let mut budget = Self::lock_budget(&mut tx, campaign_id).await?;
+        change(&mut budget)?;
+        Self::save_budget(&mut tx, &budget).await?;
+        tx.commit().await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+        Ok(budget)
+    }
+}
+
+#[async_trait]
+impl CampaignBudgetRepository for PostgresCampaignParticipationRepository {
+    async fn find_budget(&self, campaign_id: Uuid) -> RepoResult<Option<CampaignBudget>> {
+        sqlx::query(&format!("SELECT {} FROM campaigns WHERE id = $1", BUDGET_COLUMNS))
+            .bind(campaign_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?
+            .map(Self::row_to_budget)
+            .transpose()
+    }
+
+    async fn reward_participation(&self, campaign_id: Uuid, user_id: Uuid) -> RepoResult<(RewardCharge, CampaignBudget)> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+        let mut budget = Self::lock_budget(&mut tx, campaign_id).await?;
+
+        let charge = budget.charge_reward();
+        if let RewardDecision::Rewarded { reward } = charge.decision {
+            sqlx::query(
+                r#"
+                INSERT INTO campaign_participants (campaign_id, user_id, joined_at, reward_earned, rewarded_actions)
+                VALUES ($1, $2, NOW(), $3, 1)
+                ON CONFLICT (campaign_id, user_id) DO UPDATE SET
+                    reward_earned = campaign_participants.reward_earned + EXCLUDED.reward_earned,
+                    rewarded_actions = campaign_participants.rewarded_actions + 1
+                "#
+            )
+            .bind(campaign_id)
+            .bind(user_id)
+            .bind(reward)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+        }
+        if charge.decision != RewardDecision::Rejected || charge.depleted {
+            Self::save_budget(&mut tx, &budget).await?;
+        }
+
+        tx.commit().await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+        Ok((charge, budget))
+    }
+
+    async fn top_up(&self, campaign_id: Uuid, amount: f64) -> RepoResult<CampaignBudget> {
+        self.update_budget(campaign_id, |budget| budget.top_up(amount)).await
+    }
+
+    async fn resume(&self, campaign_id: Uuid) -> RepoResult<CampaignBudget> {
+        self.update_budget(campaign_id, |budget| budget.resume(Utc::now())).await
+    }
+}
+
+// ============================================================================
+// CALENDARIO (sobre campaigns)
+// ============================================================================
+
+use crate::bounded_contexts::campaign::domain::schedule::{CampaignSchedule, ScheduledTransition};
+use crate::bounded_contexts::campaign::domain::repository::CampaignScheduleRepository;
+
+const SCHEDULE_COLUMNS: &str = "id, artist_id, status, start_date, end_date, auto_start, distribute_nfts_on_end";
+
+impl PostgresCampaignRepository {
+    fn row_to_schedule(row: sqlx::postgres::PgRow) -> RepoResult<CampaignSchedule> {
+        Ok(CampaignSchedule {
+            campaign_id: row.get("id"),
+            artist_id: row.get("artist_id"),
+            status: row.get::<String, _>("status").parse().map_err(AppError::SerializationError)?,
+            start_date: row.get("start_date"),
+            end_date: row.get("end_date"),
+            auto_start: row.get("auto_start"),
+            distribute_nfts_on_end: row.get("distribute_nfts_on_end"),
+        })
+    }
+
+    async fn lock_schedule(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, campaign_id: Uuid) -> RepoResult<CampaignSchedule> {
+        let row = sqlx::query(&format!("SELECT {} FROM campaigns WHERE id = $1 FOR UPDATE", SCHEDULE_COLUMNS))
+            .bind(campaign_id)
+            .fetch_optional(&mut **tx)
+            .await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?
+            .ok_or_else(|| AppError::NotFoundError(format!("Campaign {} not found", campaign_id)))?;
+        Self::row_to_schedule(row)
+    }
+
+    /// Guardar el nuevo estado sellando cuándo empezó o terminó la campaña
+    async fn save_status(tx: &mut sqlx::Transaction<'_, sqlx::Postgres>, schedule: &CampaignSchedule, now: DateTime<Utc>) -> RepoResult<()> {
+        sqlx::query(
+            r#"
+            UPDATE campaigns SET
+                status = $2,
+                activated_at = CASE WHEN $2 = 'Active' THEN COALESCE(activated_at, $3) ELSE activated_at END,
+                completed_at = CASE WHEN $2 = 'Completed' THEN $3 ELSE completed_at END,
+                updated_at = NOW()
+            WHERE id = $1
+            "#
+        )
+        .bind(schedule.campaign_id)
+        .bind(schedule.status.as_str())
+        .bind(now)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CampaignScheduleRepository for PostgresCampaignRepository {
+    async fn find_schedule(&self, campaign_id: Uuid) -> RepoResult<Option<CampaignSchedule>> {
+        sqlx::query(&format!("SELECT {} FROM campaigns WHERE id = $1", SCHEDULE_COLUMNS))
+            .bind(campaign_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?
+            .map(Self::row_to_schedule)
+            .transpose()
+    }
+
+    async fn find_due(&self, now: DateTime<Utc>, limit: u32) -> RepoResult<Vec<Uuid>> {
+        sqlx::query_scalar(
+            r#"
+            SELECT id FROM (
+                SELECT id, start_date AS due_at FROM campaigns
+                WHERE status = 'Draft' AND auto_start AND start_date <= $1 AND end_date > $1
+                UNION ALL
+                SELECT id, end_date AS due_at FROM campaigns
+                WHERE status IN ('Active', 'Paused') AND end_date <= $1
+            ) due
+            ORDER BY due_at
+            LIMIT $2
+            "#
+        )
+        .bind(now)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::Infrastructure(e.to_string()))
+    }
+
+    async fn apply_due_transition(&self, campaign_id: Uuid, now: DateTime<Utc>) -> RepoResult<Option<(ScheduledTransition, CampaignSchedule)>> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+        let mut schedule = Self::lock_schedule(&mut tx, campaign_id).await?;
+
+        // Otro proceso pudo cambiar la campaña desde `find_due`
+        let Some(transition) = schedule.apply_due_transition(now) else {
+            return Ok(None);
+        };
+        Self::save_status(&mut tx, &schedule, now).await?;
+
+        tx.commit().await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+        Ok(Some((transition, schedule)))
+    }
+
+    async fn activate(&self, campaign_id: Uuid, now: DateTime<Utc>, force: bool) -> RepoResult<CampaignSchedule> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+        let mut schedule = Self::lock_schedule(&mut tx, campaign_id).await?;
+        schedule.activate(now, force)?;
+        Self::save_status(&mut tx, &schedule, now).await?;
+        tx.commit().await
+            .map_err(|e| AppError::Infrastructure(e.to_string()))?;
+        Ok(schedule)
+    }
+
+    async fn update_options(&self, campaign_id: Uuid, auto_start: bool, distribute_nfts_on_end: bool) -> RepoResult<CampaignSchedule> {
+        let row = sqlx::query(&format!(
+            "UPDATE campaigns SET auto_start = $2, distribute_nfts_on_end = $3, updated_at = NOW() WHERE id = $1 RETURNING {}",
+            SCHEDULE_COLUMNS
+        ))
+        .bind(campaign_id)
+        .bind(auto_start)
+        .bind(distribute_nfts_on_end)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::Infrastructure(e.to_string()))?
+        .ok_or_else(|| AppError::NotFoundError(format!("Campaign {} not found", campaign_id)))?;
+        Self::row_to_schedule(row)
+    }
+}
+
+// ============================================================================
+// AUDIENCE REPOSITORY
+// ============================================================================
+
+use crate::bounded_contexts::campaign::domain::{
+    audience::{AudienceEstimate, UserProfile},
+    repository::AudienceRepository,
+    value_objects::TargetAudience,
+};
+
+/// Genres of a user that count for targeting: the most listened ones
+const TOP_GENRES_PER_USER: i64 = 3;
+
+/// Derives audience attributes from listening activity: the country of the
+/// latest listen, the most listened genres and the number of completed listens.
+pub struct PostgresAudienceRepository {
+    pool: PgPool,
+}
+
+impl PostgresAudienceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AudienceRepository for PostgresAudienceRepository {
+    async fn find_user_profile(&self, user_id: Uuid) -> RepoResult<UserProfile> {
+        let row = sqlx::query(
+            r#"
+            SELECT
+                (SELECT location_country FROM listen_sessions
+                 WHERE user_id = $1 AND location_country IS NOT NULL
+                 ORDER BY started_at DESC LIMIT 1) AS country,
+                (SELECT COUNT(*) FROM listen_sessions
+                 WHERE user_id = $1 AND completed_at IS NOT NULL) AS listen_count,
+                ARRAY(SELECT s.genre
+                      FROM listen_sessions ls JOIN songs s ON s.id = ls.song_id
+                      WHERE ls.user_id = $1 AND s.genre IS NOT NULL
+                      GROUP BY s.genre
+                      ORDER BY COUNT(*) DESC, s.genre
+                      LIMIT $2) AS genres
+            "#
+        )
+        .bind(user_id)
+        .bind(TOP_GENRES_PER_USER)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| crate::shared::domain::errors::AppError::Infrastructure(e.to_string()))?;
+
+        let listen_count: i64 = row.try_get("listen_count")
+            .map_err(|e| crate::shared::domain::errors::AppError::Infrastructure(e.to_string()))?;
+
+        Ok(UserProfile {
+            user_id,
+            country: row.try_get("country")
+                .map_err(|e| crate::shared::domain::errors::AppError::Infrastructure(e.to_string()))?,
+            genre_preferences: row.try_get("genres")
+                .map_err(|e| crate::shared::domain::errors::AppError::Infrastructure(e.to_string()))?,
+            listen_count: listen_count.max(0) as u64,
+        })
+    }
+
+    async fn estimate_reach(&self, audience: Option<&TargetAudience>) -> RepoResult<AudienceEstimate> {
+        // Mismo perfil que find_user_profile, calculado para todos los fans a la vez
+        let normalize = |values: &[String]| values.iter().map(|value| value.trim().to_lowercase()).collect::<Vec<_>>();
+        let (locations, genres, min_listens) = match audience {
+            Some(audience) => (
+                normalize(&audience.locations),
+                normalize(&audience.genres),
+                audience.fan_level.map_or(0, |level| level.min_listen_count()),
+            ),
+            None => (Vec::new(), Vec::new(), 0),
+        };
+
+        let row = sqlx::query(
+            r#"
+            WITH profiles AS (
+                SELECT
+                    (SELECT LOWER(TRIM(ls.location_country)) FROM listen_sessions ls
+                     WHERE ls.user_id = fans.user_id AND ls.location_country IS NOT NULL
+                     ORDER BY ls.started_at DESC LIMIT 1) AS country,
+                    (SELECT COUNT(*) FROM listen_sessions ls
+                     WHERE ls.user_id = fans.user_id AND ls.completed_at IS NOT NULL) AS listen_count,
+                    ARRAY(SELECT LOWER(TRIM(s.genre))
+                          FROM listen_sessions ls JOIN songs s ON s.id = ls.song_id
+                          WHERE ls.user_id = fans.user_id AND s.genre IS NOT NULL
+                          GROUP BY s.genre
+                          ORDER BY COUNT(*) DESC, s.genre
+                          LIMIT $4) AS genres
+                FROM (SELECT DISTINCT user_id FROM listen_sessions) fans
+            ),
+            matches AS (
+                SELECT
+                    (cardinality($1::TEXT[]) = 0 OR country = ANY($1::TEXT[])) AS location_ok,
+                    (cardinality($2::TEXT[]) = 0 OR genres && $2::TEXT[]) AS genre_ok,
+                    listen_count >= $3 AS fan_level_ok
+                FROM profiles
+            )
+            SELECT
+                COUNT(*) AS total_fans,
+                COUNT(*) FILTER (WHERE location_ok) AS location_matches,
+                COUNT(*) FILTER (WHERE genre_ok) AS genre_matches,
+                COUNT(*) FILTER (WHERE fan_level_ok) AS fan_level_matches,
+                COUNT(*) FILTER (WHERE location_ok AND genre_ok AND fan_level_ok) AS estimated_reach
+            FROM matches
+            "#
+        )
+        .bind(&locations)
+        .bind(&genres)
+        .bind(min_listens as i64)
+        .bind(TOP_GENRES_PER_USER)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| crate::shared::domain::errors::AppError::Infrastructure(e.to_string()))?;
+
+        let count = |column: &str| -> RepoResult<u64> {
+            let value: i64 = row.try_get(column)
+                .map_err(|e| crate::shared::domain::errors::AppError::Infrastructure(e.to_string()))?;
+            Ok(value.max(0) as u64)
+        };
+
+        Ok(AudienceEstimate {
+            total_fans: count("total_fans")?,
+            location_matches: count("location_matches")?,
+            genre_matches: count("genre_matches")?,
+            fan_level_matches: count("fan_level_matches")?,
+            estimated_reach: count("estimated_reach")?,
+        })
+    }
+}
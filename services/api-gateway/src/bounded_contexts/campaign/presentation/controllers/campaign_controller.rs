@@ -1,9 +1,9 @@
 use axum::{
     extract::{Query, Path, State},
     http::StatusCode,
-    response::Json,
+    response::{IntoResponse, Json, Response},
     routing::{get, post, put, delete},
-    Router, Extension,
+    Router,
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -13,24 +13,39 @@ use chrono::{DateTime, Utc};
 use crate::bounded_contexts::campaign::application::{
     // Commands - using explicit paths to avoid ambiguity
     use_cases::create_campaign::{CreateCampaignCommand, CreateCampaignCommandHandler},
-    use_cases::activate_campaign::{ActivateCampaignCommand, ActivateCampaignCommandHandler},
     use_cases::update_campaign::{UpdateCampaignCommand, UpdateCampaignCommandHandler},
     use_cases::participate_campaign::{ParticipateCampaignCommand, ParticipateCampaignCommandHandler},
     use_cases::boost_campaign::{BoostCampaignCommand, BoostCampaignCommandHandler},
-    use_cases::purchase_nft::{MintCampaignNFTCommand, MintCampaignNFTCommandHandler},
+    nft_minting::CampaignNftService,
+    audience::CampaignAudienceService,
+    budget::CampaignBudgetService,
+    scheduler::CampaignSchedulerService,
+    analytics_snapshots::CampaignAnalyticsSnapshotService,
+    commands::{RebuildCampaignAnalyticsCommand, RebuildCampaignAnalyticsCommandHandler},
     // Queries
     queries::get_campaign::{GetCampaignQuery, GetCampaignQueryHandler, CampaignDetailDTO},
     queries::search_campaigns::{SearchCampaignsQuery, SearchCampaignsQueryHandler, SearchCampaignsResult},
-    queries::get_campaign_analytics::{GetCampaignAnalyticsQuery, GetCampaignAnalyticsQueryHandler},
     queries::get_trending_campaigns::GetTrendingCampaignsQuery,
     queries::get_user_campaigns::GetUserCampaignsQuery,
 };
 
 use crate::bounded_contexts::campaign::infrastructure::{
-    PostgresCampaignRepository, PostgresCampaignParticipationRepository,
+    PostgresAudienceRepository, PostgresCampaignAnalyticsRepository, PostgresCampaignRepository,
 };
 
+use crate::bounded_contexts::campaign::domain::analytics::{
+    CampaignActivity, CampaignActivityEvent, CampaignAnalyticsOverview, CampaignAnalyticsSnapshot,
+};
+use crate::bounded_contexts::campaign::domain::audience::{AudienceCriterion, AudienceEligibility, AudienceEstimate};
+use crate::bounded_contexts::campaign::domain::budget::CampaignBudget;
+use crate::bounded_contexts::campaign::domain::value_objects::{FanLevel, TargetAudience as CampaignTargetAudience};
+use crate::bounded_contexts::campaign::domain::schedule::CampaignSchedule;
+use crate::bounded_contexts::campaign::domain::repository::{CampaignAnalyticsRepository, CampaignRepository};
+use crate::shared::application::command::CommandHandler;
+use crate::shared::application::query::QueryHandler;
+use crate::bounded_contexts::user::domain::UserRole;
 use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::auth::{authorize_owner, authorize_role, AuthenticatedUser, OwnedResource};
 
 // =============================================================================
 // REQUEST/RESPONSE DTOs
@@ -58,10 +73,19 @@ pub struct TargetAudience {
     pub age_range: Option<AgeRange>,
     pub locations: Vec<String>,
     pub genres: Vec<String>,
-    pub fan_level: Option<String>, // "new", "casual", "dedicated", "superfan"
+    pub fan_level: Option<String>, // minimum level: "casual", "regular", "superfan"
     pub platform_activity: Option<String>, // "high", "medium", "low"
 }
 
+impl TargetAudience {
+    /// Targeting the campaign enforces; `age_range` and `platform_activity`
+    /// are not matched yet
+    fn into_domain(self) -> Result<CampaignTargetAudience, AppError> {
+        let fan_level = self.fan_level.as_deref().map(FanLevel::from_string).transpose()?;
+        Ok(CampaignTargetAudience::new(self.locations, self.genres, fan_level))
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct AgeRange {
     pub min_age: u8,
@@ -165,104 +189,124 @@ pub struct NFTRecipient {
     pub mint_status: String,
 }
 
-// Search DTOs
+// Budget DTOs
 #[derive(Debug, Deserialize)]
-pub struct SearchCampaignsRequest {
-    pub search_text: Option<String>,
-    pub campaign_type: Option<String>,
-    pub artist_id: Option<Uuid>,
-    pub status: Option<String>,
-    pub min_budget: Option<f64>,
-    pub max_budget: Option<f64>,
-    pub date_from: Option<DateTime<Utc>>,
-    pub date_to: Option<DateTime<Utc>>,
-    pub is_active: Option<bool>,
-    pub limit: Option<usize>,
-    pub offset: Option<usize>,
-    pub sort_by: Option<String>, // "created_at", "budget", "participants", "performance"
-    pub sort_order: Option<String>, // "asc", "desc"
+pub struct TopUpCampaignBudgetRequest {
+    pub amount: f64,
 }
 
-// Analytics DTOs
 #[derive(Debug, Serialize)]
-pub struct CampaignAnalytics {
+pub struct CampaignBudgetResponse {
     pub campaign_id: Uuid,
-    pub performance_metrics: PerformanceMetrics,
-    pub audience_insights: AudienceInsights,
-    pub engagement_data: EngagementData,
-    pub conversion_funnel: ConversionFunnel,
-    pub roi_analysis: ROIAnalysis,
-    pub time_series_data: Vec<TimeSeriesDataPoint>,
+    pub status: String,
+    pub budget: Option<f64>,
+    pub budget_spent: f64,
+    pub remaining_budget: Option<f64>,
+    pub reward_per_action: f64,
 }
 
-#[derive(Debug, Serialize)]
-pub struct PerformanceMetrics {
-    pub total_reach: u32,
-    pub unique_participants: u32,
-    pub total_actions: u32,
-    pub completion_rate: f64,
-    pub engagement_rate: f64,
-    pub viral_coefficient: f64,
-    pub cost_per_action: f64,
-    pub budget_utilization: f64,
+impl From<CampaignBudget> for CampaignBudgetResponse {
+    fn from(budget: CampaignBudget) -> Self {
+        Self {
+            campaign_id: budget.campaign_id,
+            status: budget.status.as_str().to_string(),
+            remaining_budget: budget.remaining(),
+            budget: budget.budget,
+            budget_spent: budget.spent,
+            reward_per_action: budget.reward_per_action,
+        }
+    }
 }
 
+// Audience DTOs
 #[derive(Debug, Serialize)]
-pub struct AudienceInsights {
-    pub age_distribution: std::collections::HashMap<String, u32>,
-    pub location_distribution: std::collections::HashMap<String, u32>,
-    pub genre_preferences: std::collections::HashMap<String, u32>,
-    pub platform_activity: std::collections::HashMap<String, u32>,
-    pub new_vs_returning: NewVsReturning,
+pub struct AudienceEligibilityResponse {
+    pub eligible: bool,
+    pub failed_criteria: Vec<AudienceCriterion>,
+    pub reasons: Vec<String>,
 }
 
-#[derive(Debug, Serialize)]
-pub struct NewVsReturning {
-    pub new_users: u32,
-    pub returning_users: u32,
-    pub percentage_new: f64,
+impl From<AudienceEligibility> for AudienceEligibilityResponse {
+    fn from(eligibility: AudienceEligibility) -> Self {
+        Self {
+            eligible: eligibility.is_eligible(),
+            failed_criteria: eligibility.failed_criteria(),
+            reasons: eligibility.reasons(),
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
-pub struct EngagementData {
-    pub actions_breakdown: std::collections::HashMap<String, u32>,
-    pub average_session_duration: f64,
-    pub repeat_action_rate: f64,
-    pub social_sharing_rate: f64,
-    pub playlist_addition_rate: f64,
+pub struct AudienceEstimateResponse {
+    pub campaign_id: Uuid,
+    #[serde(flatten)]
+    pub estimate: AudienceEstimate,
 }
 
-#[derive(Debug, Serialize)]
-pub struct ConversionFunnel {
-    pub impressions: u32,
-    pub clicks: u32,
-    pub participations: u32,
-    pub completions: u32,
-    pub conversions: u32,
-    pub click_through_rate: f64,
-    pub participation_rate: f64,
-    pub completion_rate: f64,
-    pub conversion_rate: f64,
+// Schedule DTOs
+#[derive(Debug, Default, Deserialize)]
+pub struct ActivateCampaignRequest {
+    /// Activar antes de `start_date`; exige `confirm_early_start` del artista
+    #[serde(default)]
+    pub force: bool,
+    #[serde(default)]
+    pub confirm_early_start: bool,
 }
 
-#[derive(Debug, Serialize)]
-pub struct ROIAnalysis {
-    pub total_spend: f64,
-    pub revenue_generated: f64,
-    pub roi_percentage: f64,
-    pub cost_per_acquisition: f64,
-    pub lifetime_value_increase: f64,
-    pub break_even_point: Option<DateTime<Utc>>,
+#[derive(Debug, Deserialize)]
+pub struct UpdateCampaignScheduleRequest {
+    pub auto_start: bool,
+    #[serde(default)]
+    pub distribute_nfts_on_end: bool,
 }
 
 #[derive(Debug, Serialize)]
-pub struct TimeSeriesDataPoint {
-    pub timestamp: DateTime<Utc>,
-    pub reach: u32,
-    pub participants: u32,
-    pub actions: u32,
-    pub spend: f64,
-    pub engagement_score: f64,
+pub struct CampaignScheduleResponse {
+    pub campaign_id: Uuid,
+    pub status: String,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    pub auto_start: bool,
+    pub distribute_nfts_on_end: bool,
+}
+
+impl From<CampaignSchedule> for CampaignScheduleResponse {
+    fn from(schedule: CampaignSchedule) -> Self {
+        Self {
+            campaign_id: schedule.campaign_id,
+            status: schedule.status.as_str().to_string(),
+            start_date: schedule.start_date,
+            end_date: schedule.end_date,
+            auto_start: schedule.auto_start,
+            distribute_nfts_on_end: schedule.distribute_nfts_on_end,
+        }
+    }
+}
+
+// Analytics DTOs
+/// Sin fechas: los últimos 30 días
+#[derive(Debug, Deserialize)]
+pub struct AnalyticsHistoryQuery {
+    pub start_date: Option<chrono::NaiveDate>,
+    pub end_date: Option<chrono::NaiveDate>,
+}
+
+// Search DTOs
+#[derive(Debug, Deserialize)]
+pub struct SearchCampaignsRequest {
+    pub search_text: Option<String>,
+    pub campaign_type: Option<String>,
+    pub artist_id: Option<Uuid>,
+    pub status: Option<String>,
+    pub min_budget: Option<f64>,
+    pub max_budget: Option<f64>,
+    pub date_from: Option<DateTime<Utc>>,
+    pub date_to: Option<DateTime<Utc>>,
+    pub is_active: Option<bool>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+    pub sort_by: Option<String>, // "created_at", "budget", "participants", "performance"
+    pub sort_order: Option<String>, // "asc", "desc"
 }
 
 // API Response wrapper
@@ -300,17 +344,32 @@ impl<T> ApiResponse<T> {
 
 pub struct CampaignController {
     campaign_repository: Arc<PostgresCampaignRepository>,
-    participation_repository: Arc<PostgresCampaignParticipationRepository>,
+    budget_service: Arc<CampaignBudgetService>,
+    scheduler_service: Arc<CampaignSchedulerService>,
+    audience_repository: Arc<PostgresAudienceRepository>,
+    analytics_repository: Arc<PostgresCampaignAnalyticsRepository>,
+    snapshot_service: Arc<CampaignAnalyticsSnapshotService>,
+    nft_service: Arc<CampaignNftService>,
 }
 
 impl CampaignController {
     pub fn new(
         campaign_repository: Arc<PostgresCampaignRepository>,
-        participation_repository: Arc<PostgresCampaignParticipationRepository>,
+        budget_service: Arc<CampaignBudgetService>,
+        scheduler_service: Arc<CampaignSchedulerService>,
+        audience_repository: Arc<PostgresAudienceRepository>,
+        analytics_repository: Arc<PostgresCampaignAnalyticsRepository>,
+        snapshot_service: Arc<CampaignAnalyticsSnapshotService>,
+        nft_service: Arc<CampaignNftService>,
     ) -> Self {
         Self {
             campaign_repository,
-            participation_repository,
+            budget_service,
+            scheduler_service,
+            audience_repository,
+            analytics_repository,
+            snapshot_service,
+            nft_service,
         }
     }
 
@@ -322,12 +381,19 @@ impl CampaignController {
             
             // Campaign operations
             .route("/campaigns/:campaign_id/activate", post(Self::activate_campaign))
+            .route("/campaigns/:campaign_id/schedule", put(Self::update_campaign_schedule))
+            .route("/campaigns/:campaign_id/audience", put(Self::update_campaign_audience))
+            .route("/campaigns/:campaign_id/audience-estimate", get(Self::get_audience_estimate))
             .route("/campaigns/:campaign_id/participate", post(Self::participate_campaign))
             .route("/campaigns/:campaign_id/boost", post(Self::boost_campaign))
+            .route("/campaigns/:campaign_id/budget/top-up", post(Self::top_up_campaign_budget))
+            .route("/campaigns/:campaign_id/resume", post(Self::resume_campaign))
             .route("/campaigns/:campaign_id/nft/mint", post(Self::mint_campaign_nft))
             
             // Campaign analytics
             .route("/campaigns/:campaign_id/analytics", get(Self::get_campaign_analytics))
+            .route("/campaigns/:campaign_id/analytics/history", get(Self::get_campaign_analytics_history))
+            .route("/campaigns/:campaign_id/analytics/rebuild", post(Self::rebuild_campaign_analytics))
             .route("/campaigns/:campaign_id/participants", get(Self::get_campaign_participants))
             .route("/campaigns/:campaign_id/leaderboard", get(Self::get_campaign_leaderboard))
             
@@ -343,15 +409,41 @@ impl CampaignController {
             .with_state(controller)
     }
 
+    /// Servicio de audiencia sobre los repositorios del controlador
+    fn audience_service(&self) -> CampaignAudienceService {
+        CampaignAudienceService::new(self.campaign_repository.clone(), self.audience_repository.clone())
+    }
+
+    // =============================================================================
+    // AUTHORIZATION
+    // =============================================================================
+
+    /// Solo el artista de la campaña (o un admin) puede gestionarla
+    async fn authorize_campaign_owner(&self, user: &AuthenticatedUser, campaign_id: Uuid) -> Result<(), StatusCode> {
+        let campaign = self.campaign_repository
+            .find_by_id(campaign_id)
+            .await
+            .map_err(|err| {
+                eprintln!("Load campaign error: {:?}", err);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?
+            .ok_or(StatusCode::NOT_FOUND)?;
+
+        authorize_owner(user, OwnedResource::Campaign, campaign.artist_id()).map_err(authorization_status)
+    }
+
     // =============================================================================
     // CAMPAIGN CRUD
     // =============================================================================
 
     async fn create_campaign(
         State(controller): State<Arc<Self>>,
-        Extension(current_user_id): Extension<Uuid>,
+        user: AuthenticatedUser,
         Json(request): Json<CreateCampaignRequest>,
     ) -> Result<Json<ApiResponse<CreateCampaignResponse>>, StatusCode> {
+        authorize_role(&user, &[UserRole::Artist]).map_err(authorization_status)?;
+        authorize_owner(&user, OwnedResource::Campaign, request.artist_id).map_err(authorization_status)?;
+
         let command = CreateCampaignCommand {
             name: request.name,
             description: request.description,
@@ -365,7 +457,7 @@ impl CampaignController {
             end_date: request.end_date,
             campaign_parameters: request.campaign_parameters,
             metadata: request.metadata,
-            created_by: current_user_id,
+            created_by: user.user_id,
         };
 
         let handler = CreateCampaignCommandHandler::new(controller.campaign_repository.clone());
@@ -400,12 +492,22 @@ impl CampaignController {
     async fn get_campaign(
         State(controller): State<Arc<Self>>,
         Path(campaign_id): Path<Uuid>,
+        user: Option<AuthenticatedUser>,
     ) -> Result<Json<ApiResponse<CampaignDetailDTO>>, StatusCode> {
         let query = GetCampaignQuery { campaign_id };
         let handler = GetCampaignQueryHandler::new(controller.campaign_repository.clone());
 
         match handler.handle(query).await {
-            Ok(Some(campaign)) => Ok(Json(ApiResponse::success(campaign))),
+            Ok(Some(campaign)) => {
+                // Las visitas anónimas no entran en el embudo: no hay usuario que seguir
+                if let Some(user) = user {
+                    let event = CampaignActivityEvent::new(campaign_id, user.user_id, CampaignActivity::Viewed);
+                    if let Err(err) = controller.analytics_repository.append(&event).await {
+                        eprintln!("Record campaign view error: {:?}", err);
+                    }
+                }
+                Ok(Json(ApiResponse::success(campaign)))
+            }
             Ok(None) => Err(StatusCode::NOT_FOUND),
             Err(err) => {
                 eprintln!("Get campaign error: {:?}", err);
@@ -448,9 +550,11 @@ impl CampaignController {
     async fn update_campaign(
         State(controller): State<Arc<Self>>,
         Path(campaign_id): Path<Uuid>,
-        Extension(current_user_id): Extension<Uuid>,
+        user: AuthenticatedUser,
         Json(request): Json<UpdateCampaignRequest>,
     ) -> Result<Json<ApiResponse<CampaignDetailDTO>>, StatusCode> {
+        controller.authorize_campaign_owner(&user, campaign_id).await?;
+
         let command = UpdateCampaignCommand {
             campaign_id,
             name: request.name,
@@ -459,7 +563,7 @@ impl CampaignController {
             end_date: request.end_date,
             target_audience: request.target_audience,
             campaign_parameters: request.campaign_parameters,
-            updated_by: current_user_id,
+            updated_by: user.user_id,
         };
 
         let handler = UpdateCampaignCommandHandler::new(controller.campaign_repository.clone());
@@ -479,10 +583,12 @@ impl CampaignController {
     }
 
     async fn delete_campaign(
-        State(_controller): State<Arc<Self>>,
+        State(controller): State<Arc<Self>>,
         Path(campaign_id): Path<Uuid>,
-        Extension(current_user_id): Extension<Uuid>,
+        user: AuthenticatedUser,
     ) -> Result<Json<ApiResponse<()>>, StatusCode> {
+        controller.authorize_campaign_owner(&user, campaign_id).await?;
+
         // Delete campaign logic would be implemented here
         Ok(Json(ApiResponse::success(())))
     }
@@ -494,25 +600,94 @@ impl CampaignController {
     async fn activate_campaign(
         State(controller): State<Arc<Self>>,
         Path(campaign_id): Path<Uuid>,
-        Extension(current_user_id): Extension<Uuid>,
-    ) -> Result<Json<ApiResponse<CampaignDetailDTO>>, StatusCode> {
-        let command = ActivateCampaignCommand {
-            campaign_id,
-            activated_by: current_user_id,
+        user: AuthenticatedUser,
+        request: Option<Json<ActivateCampaignRequest>>,
+    ) -> Result<Json<ApiResponse<CampaignScheduleResponse>>, StatusCode> {
+        controller.authorize_campaign_owner(&user, campaign_id).await?;
+        let request = request.map(|Json(request)| request).unwrap_or_default();
+
+        // Adelantar el inicio es decisión del artista: un admin no puede forzarlo
+        if request.force {
+            if !request.confirm_early_start {
+                return Err(StatusCode::BAD_REQUEST);
+            }
+            let schedule = controller.scheduler_service
+                .find_schedule(campaign_id)
+                .await
+                .map_err(|err| {
+                    eprintln!("Load campaign schedule error: {:?}", err);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?
+                .ok_or(StatusCode::NOT_FOUND)?;
+            if schedule.artist_id != user.user_id {
+                return Err(StatusCode::FORBIDDEN);
+            }
+        }
+
+        match controller.scheduler_service.activate(campaign_id, Utc::now(), request.force).await {
+            Ok(schedule) => Ok(Json(ApiResponse::success(schedule.into()))),
+            Err(err) => {
+                eprintln!("Activate campaign error: {:?}", err);
+                Err(lifecycle_error_status(err))
+            }
+        }
+    }
+
+    async fn update_campaign_schedule(
+        State(controller): State<Arc<Self>>,
+        Path(campaign_id): Path<Uuid>,
+        user: AuthenticatedUser,
+        Json(request): Json<UpdateCampaignScheduleRequest>,
+    ) -> Result<Json<ApiResponse<CampaignScheduleResponse>>, StatusCode> {
+        controller.authorize_campaign_owner(&user, campaign_id).await?;
+
+        match controller.scheduler_service
+            .update_options(campaign_id, request.auto_start, request.distribute_nfts_on_end)
+            .await
+        {
+            Ok(schedule) => Ok(Json(ApiResponse::success(schedule.into()))),
+            Err(err) => {
+                eprintln!("Update campaign schedule error: {:?}", err);
+                Err(lifecycle_error_status(err))
+            }
+        }
+    }
+
+    async fn update_campaign_audience(
+        State(controller): State<Arc<Self>>,
+        Path(campaign_id): Path<Uuid>,
+        user: AuthenticatedUser,
+        Json(request): Json<TargetAudience>,
+    ) -> Result<Json<ApiResponse<AudienceEstimateResponse>>, StatusCode> {
+        controller.authorize_campaign_owner(&user, campaign_id).await?;
+        let audience = request.into_domain().map_err(lifecycle_error_status)?;
+
+        let service = controller.audience_service();
+        let result = match service.set_target_audience(campaign_id, audience).await {
+            Ok(_) => service.estimate_reach(campaign_id).await,
+            Err(err) => Err(err),
         };
+        match result {
+            Ok(estimate) => Ok(Json(ApiResponse::success(AudienceEstimateResponse { campaign_id, estimate }))),
+            Err(err) => {
+                eprintln!("Update campaign audience error: {:?}", err);
+                Err(lifecycle_error_status(err))
+            }
+        }
+    }
 
-        let handler = ActivateCampaignCommandHandler::new(controller.campaign_repository.clone());
+    async fn get_audience_estimate(
+        State(controller): State<Arc<Self>>,
+        Path(campaign_id): Path<Uuid>,
+        user: AuthenticatedUser,
+    ) -> Result<Json<ApiResponse<AudienceEstimateResponse>>, StatusCode> {
+        controller.authorize_campaign_owner(&user, campaign_id).await?;
 
-        match handler.handle(command).await {
-            Ok(campaign) => Ok(Json(ApiResponse::success(campaign))),
+        match controller.audience_service().estimate_reach(campaign_id).await {
+            Ok(estimate) => Ok(Json(ApiResponse::success(AudienceEstimateResponse { campaign_id, estimate }))),
             Err(err) => {
-                eprintln!("Activate campaign error: {:?}", err);
-                match err {
-                    AppError::NotFoundError(_) => Err(StatusCode::NOT_FOUND),
-                    AppError::ValidationError(_) => Err(StatusCode::BAD_REQUEST),
-                    AppError::UnauthorizedError(_) => Err(StatusCode::FORBIDDEN),
-                    _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
-                }
+                eprintln!("Estimate campaign audience error: {:?}", err);
+                Err(lifecycle_error_status(err))
             }
         }
     }
@@ -520,12 +695,12 @@ impl CampaignController {
     async fn participate_campaign(
         State(controller): State<Arc<Self>>,
         Path(campaign_id): Path<Uuid>,
-        Extension(current_user_id): Extension<Uuid>,
+        user: AuthenticatedUser,
         Json(request): Json<ParticipateCampaignRequest>,
-    ) -> Result<Json<ApiResponse<ParticipateCampaignResponse>>, StatusCode> {
+    ) -> Result<Json<ApiResponse<ParticipateCampaignResponse>>, Response> {
         let command = ParticipateCampaignCommand {
             campaign_id,
-            user_id: current_user_id,
+            user_id: user.user_id,
             action_type: request.action_type,
             action_data: request.action_data,
             proof_of_action: request.proof_of_action,
@@ -533,7 +708,9 @@ impl CampaignController {
 
         let handler = ParticipateCampaignCommandHandler::new(
             controller.campaign_repository.clone(),
-            controller.participation_repository.clone()
+            controller.budget_service.clone(),
+            controller.audience_repository.clone(),
+            controller.analytics_repository.clone(),
         );
 
         match handler.handle(command).await {
@@ -553,10 +730,30 @@ impl CampaignController {
             Err(err) => {
                 eprintln!("Participate campaign error: {:?}", err);
                 match err {
-                    AppError::NotFoundError(_) => Err(StatusCode::NOT_FOUND),
-                    AppError::ValidationError(_) => Err(StatusCode::BAD_REQUEST),
-                    AppError::ConflictError(_) => Err(StatusCode::CONFLICT),
-                    _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+                    AppError::NotFoundError(_) => Err(StatusCode::NOT_FOUND.into_response()),
+                    // Fuera del público objetivo: devolver qué criterios no cumple
+                    AppError::Forbidden(message) => {
+                        let eligibility = controller.audience_service()
+                            .check_eligibility(campaign_id, user.user_id)
+                            .await
+                            .ok()
+                            .map(AudienceEligibilityResponse::from);
+                        let body = ApiResponse { success: false, data: eligibility, error: Some(message), timestamp: Utc::now() };
+                        Err((StatusCode::FORBIDDEN, Json(body)).into_response())
+                    }
+                    AppError::ValidationError(_) => Err(StatusCode::BAD_REQUEST.into_response()),
+                    // Campaña no activa (p. ej. pausada sin presupuesto): devolver su estado
+                    AppError::ConflictError(message) => {
+                        let state = controller.budget_service
+                            .find_budget(campaign_id)
+                            .await
+                            .ok()
+                            .flatten()
+                            .map(CampaignBudgetResponse::from);
+                        let body = ApiResponse { success: false, data: state, error: Some(message), timestamp: Utc::now() };
+                        Err((StatusCode::CONFLICT, Json(body)).into_response())
+                    }
+                    _ => Err(StatusCode::INTERNAL_SERVER_ERROR.into_response()),
                 }
             }
         }
@@ -565,15 +762,17 @@ impl CampaignController {
     async fn boost_campaign(
         State(controller): State<Arc<Self>>,
         Path(campaign_id): Path<Uuid>,
-        Extension(current_user_id): Extension<Uuid>,
+        user: AuthenticatedUser,
         Json(request): Json<BoostCampaignRequest>,
     ) -> Result<Json<ApiResponse<BoostCampaignResponse>>, StatusCode> {
+        controller.authorize_campaign_owner(&user, campaign_id).await?;
+
         let command = BoostCampaignCommand {
             campaign_id,
             boost_amount: request.boost_amount,
             boost_duration_hours: request.boost_duration_hours,
             target_metrics: request.target_metrics,
-            boosted_by: current_user_id,
+            boosted_by: user.user_id,
         };
 
         let handler = BoostCampaignCommandHandler::new(controller.campaign_repository.clone());
@@ -605,36 +804,105 @@ impl CampaignController {
     async fn mint_campaign_nft(
         State(controller): State<Arc<Self>>,
         Path(campaign_id): Path<Uuid>,
-        Extension(current_user_id): Extension<Uuid>,
+        user: AuthenticatedUser,
         Json(request): Json<MintNFTRequest>,
     ) -> Result<Json<ApiResponse<MintNFTResponse>>, StatusCode> {
-        let command = MintCampaignNFTCommand {
-            campaign_id,
-            recipient_id: request.recipient_id,
-            nft_count: request.nft_count,
-            metadata_override: request.metadata_override,
-            minted_by: current_user_id,
+        controller.authorize_campaign_owner(&user, campaign_id).await?;
+
+        if request.nft_count == 0 {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        let recipient_ids = match request.recipient_id {
+            Some(recipient_id) => vec![recipient_id],
+            None => controller.nft_service
+                .participants_without_nft(campaign_id, request.nft_count)
+                .await
+                .map_err(|err| {
+                    eprintln!("Load campaign participants error: {:?}", err);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?,
         };
 
-        let handler = MintCampaignNFTCommandHandler::new(controller.campaign_repository.clone());
+        let mut recipients = Vec::new();
+        for recipient_id in recipient_ids {
+            match controller.nft_service
+                .mint_for_participant(campaign_id, recipient_id, request.metadata_override.as_ref())
+                .await
+            {
+                Ok(mint) => recipients.push(NFTRecipient {
+                    user_id: mint.user_id,
+                    nft_token_id: mint.mint_address.unwrap_or_else(|| mint.request_id.to_string()),
+                    metadata_url: mint.metadata.uri,
+                    mint_status: mint.status.to_string(),
+                }),
+                // Colección agotada a mitad del lote: se devuelve lo ya reservado
+                Err(AppError::DomainRuleViolation(_)) if !recipients.is_empty() => break,
+                Err(err) => {
+                    eprintln!("Mint campaign NFT error: {:?}", err);
+                    return match err {
+                        AppError::DomainRuleViolation(_) => Err(StatusCode::CONFLICT),
+                        AppError::NotFound(_) => Err(StatusCode::NOT_FOUND),
+                        AppError::ValidationError(_) => Err(StatusCode::BAD_REQUEST),
+                        AppError::ExternalServiceError(_) => Err(StatusCode::BAD_GATEWAY),
+                        _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+                    };
+                }
+            }
+        }
 
-        match handler.handle(command).await {
-            Ok(result) => {
-                let response = MintNFTResponse {
-                    mint_batch_id: result.mint_batch_id,
-                    campaign_id: result.campaign_id,
-                    nft_count: result.nft_count,
-                    recipients: result.recipients,
-                    blockchain: result.blockchain,
-                    transaction_hash: result.transaction_hash,
-                    created_at: result.created_at,
-                };
-                Ok(Json(ApiResponse::success(response)))
+        // El minteo es asíncrono: las direcciones llegan con el resultado de Solana
+        Ok(Json(ApiResponse::success(MintNFTResponse {
+            mint_batch_id: Uuid::new_v4(),
+            campaign_id,
+            nft_count: recipients.len() as u32,
+            recipients,
+            blockchain: "solana".to_string(),
+            transaction_hash: None,
+            created_at: Utc::now(),
+        })))
+    }
+
+    // =============================================================================
+    // CAMPAIGN ANALYTICS
+    // =============================================================================
+
+    /// En vivo para campañas en curso; última foto diaria para las completadas
+    async fn get_campaign_analytics(
+        State(controller): State<Arc<Self>>,
+        Path(campaign_id): Path<Uuid>,
+        user: AuthenticatedUser,
+    ) -> Result<Json<ApiResponse<CampaignAnalyticsOverview>>, StatusCode> {
+        controller.authorize_campaign_owner(&user, campaign_id).await?;
+
+        match controller.snapshot_service.get_analytics(campaign_id, Utc::now()).await {
+            Ok(analytics) => Ok(Json(ApiResponse::success(analytics))),
+            Err(err) => {
+                eprintln!("Get campaign analytics error: {:?}", err);
+                match err {
+                    AppError::NotFound(_) => Err(StatusCode::NOT_FOUND),
+                    _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
+                }
             }
+        }
+    }
+
+    /// Fotos diarias entre `start_date` y `end_date` (ambas incluidas)
+    async fn get_campaign_analytics_history(
+        State(controller): State<Arc<Self>>,
+        Path(campaign_id): Path<Uuid>,
+        Query(params): Query<AnalyticsHistoryQuery>,
+        user: AuthenticatedUser,
+    ) -> Result<Json<ApiResponse<Vec<CampaignAnalyticsSnapshot>>>, StatusCode> {
+        controller.authorize_campaign_owner(&user, campaign_id).await?;
+
+        let end_date = params.end_date.unwrap_or_else(|| Utc::now().date_naive());
+        let start_date = params.start_date.unwrap_or(end_date - chrono::Duration::days(29));
+        match controller.snapshot_service.history(campaign_id, start_date, end_date).await {
+            Ok(snapshots) => Ok(Json(ApiResponse::success(snapshots))),
             Err(err) => {
-                eprintln!("Mint campaign NFT error: {:?}", err);
+                eprintln!("Get campaign analytics history error: {:?}", err);
                 match err {
-                    AppError::BlockchainError(_) => Err(StatusCode::BAD_GATEWAY),
+                    AppError::NotFound(_) => Err(StatusCode::NOT_FOUND),
                     AppError::ValidationError(_) => Err(StatusCode::BAD_REQUEST),
                     _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
                 }
@@ -642,28 +910,58 @@ impl CampaignController {
         }
     }
 
+    /// Reconstruir las proyecciones de analytics a partir de `campaign_events`
+    async fn rebuild_campaign_analytics(
+        State(controller): State<Arc<Self>>,
+        Path(campaign_id): Path<Uuid>,
+        user: AuthenticatedUser,
+    ) -> Result<Json<ApiResponse<u64>>, StatusCode> {
+        controller.authorize_campaign_owner(&user, campaign_id).await?;
+
+        let handler = RebuildCampaignAnalyticsCommandHandler::new(controller.analytics_repository.clone());
+
+        match handler.handle(RebuildCampaignAnalyticsCommand { campaign_id }).await {
+            Ok(replayed_events) => Ok(Json(ApiResponse::success(replayed_events))),
+            Err(err) => {
+                eprintln!("Rebuild campaign analytics error: {:?}", err);
+                Err(StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        }
+    }
+
     // =============================================================================
-    // CAMPAIGN ANALYTICS
+    // BUDGET
     // =============================================================================
 
-    async fn get_campaign_analytics(
+    async fn top_up_campaign_budget(
         State(controller): State<Arc<Self>>,
         Path(campaign_id): Path<Uuid>,
-        Query(params): Query<std::collections::HashMap<String, String>>,
-    ) -> Result<Json<ApiResponse<CampaignAnalytics>>, StatusCode> {
-        let query = GetCampaignAnalyticsQuery {
-            campaign_id,
-            time_range: params.get("time_range").cloned(),
-            metrics: params.get("metrics").map(|m| m.split(',').map(|s| s.to_string()).collect()),
-        };
+        user: AuthenticatedUser,
+        Json(request): Json<TopUpCampaignBudgetRequest>,
+    ) -> Result<Json<ApiResponse<CampaignBudgetResponse>>, StatusCode> {
+        controller.authorize_campaign_owner(&user, campaign_id).await?;
 
-        let handler = GetCampaignAnalyticsQueryHandler::new(controller.campaign_repository.clone());
+        match controller.budget_service.top_up(campaign_id, request.amount).await {
+            Ok(budget) => Ok(Json(ApiResponse::success(budget.into()))),
+            Err(err) => {
+                eprintln!("Top up campaign budget error: {:?}", err);
+                Err(lifecycle_error_status(err))
+            }
+        }
+    }
 
-        match handler.handle(query).await {
-            Ok(analytics) => Ok(Json(ApiResponse::success(analytics))),
+    async fn resume_campaign(
+        State(controller): State<Arc<Self>>,
+        Path(campaign_id): Path<Uuid>,
+        user: AuthenticatedUser,
+    ) -> Result<Json<ApiResponse<CampaignBudgetResponse>>, StatusCode> {
+        controller.authorize_campaign_owner(&user, campaign_id).await?;
+
+        match controller.budget_service.resume(campaign_id).await {
+            Ok(budget) => Ok(Json(ApiResponse::success(budget.into()))),
             Err(err) => {
-                eprintln!("Get campaign analytics error: {:?}", err);
-                Err(StatusCode::INTERNAL_SERVER_ERROR)
+                eprintln!("Resume campaign error: {:?}", err);
+                Err(lifecycle_error_status(err))
             }
         }
     }
@@ -777,24 +1075,61 @@ impl CampaignController {
     }
 }
 
+fn authorization_status(err: AppError) -> StatusCode {
+    match err {
+        AppError::Forbidden(_) => StatusCode::FORBIDDEN,
+        AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+fn lifecycle_error_status(err: AppError) -> StatusCode {
+    match err {
+        AppError::NotFoundError(_) => StatusCode::NOT_FOUND,
+        AppError::ValidationError(_) => StatusCode::BAD_REQUEST,
+        AppError::InvalidState(_) | AppError::DomainRuleViolation(_) => StatusCode::CONFLICT,
+        _ => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
 // Factory functions
 pub fn create_campaign_controller(
     campaign_repository: Arc<PostgresCampaignRepository>,
-    participation_repository: Arc<PostgresCampaignParticipationRepository>,
+    budget_service: Arc<CampaignBudgetService>,
+    scheduler_service: Arc<CampaignSchedulerService>,
+    audience_repository: Arc<PostgresAudienceRepository>,
+    analytics_repository: Arc<PostgresCampaignAnalyticsRepository>,
+    snapshot_service: Arc<CampaignAnalyticsSnapshotService>,
+    nft_service: Arc<CampaignNftService>,
 ) -> Arc<CampaignController> {
     Arc::new(CampaignController::new(
         campaign_repository,
-        participation_repository,
+        budget_service,
+        scheduler_service,
+        audience_repository,
+        analytics_repository,
+        snapshot_service,
+        nft_service,
     ))
 }
 
 pub fn create_campaign_routes(
     campaign_repository: Arc<PostgresCampaignRepository>,
-    participation_repository: Arc<PostgresCampaignParticipationRepository>,
+    budget_service: Arc<CampaignBudgetService>,
+    scheduler_service: Arc<CampaignSchedulerService>,
+    audience_repository: Arc<PostgresAudienceRepository>,
+    analytics_repository: Arc<PostgresCampaignAnalyticsRepository>,
+    snapshot_service: Arc<CampaignAnalyticsSnapshotService>,
+    nft_service: Arc<CampaignNftService>,
 ) -> Router {
     let controller = create_campaign_controller(
         campaign_repository,
-        participation_repository,
+        budget_service,
+        scheduler_service,
+        audience_repository,
+        analytics_repository,
+        snapshot_service,
+        nft_service,
     );
     
     CampaignController::routes(controller)
@@ -0,0 +1,264 @@
+// Fan loyalty benefits: catalog, unlock on verification and redemption at the venue
+//
+// El repositorio hace el canje de forma atómica (usos del fan, stock del
+// catálogo y registro); aquí se valida la petición y se publica
+// `FanBenefitRedeemed` para analítica.
+
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_loyalty::domain::benefits::{Benefit, BenefitRedemption, BenefitUsage, FanBenefit};
+use crate::bounded_contexts::fan_loyalty::domain::entities::{FanId, FanVerificationResult};
+use crate::bounded_contexts::fan_loyalty::domain::repositories::BenefitRepository;
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventBus};
+use crate::shared::domain::errors::AppError;
+
+pub struct BenefitRedemptionService {
+    repository: Arc<dyn BenefitRepository>,
+    event_bus: Option<Arc<dyn EventBus>>,
+}
+
+impl BenefitRedemptionService {
+    pub fn new(repository: Arc<dyn BenefitRepository>) -> Self {
+        Self { repository, event_bus: None }
+    }
+
+    /// Publicar `FanBenefitRedeemed` en cada canje
+    pub fn with_event_bus(mut self, event_bus: Arc<dyn EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    pub async fn create_benefit(
+        &self,
+        name: String,
+        description: Option<String>,
+        usage: BenefitUsage,
+        total_quantity: Option<u32>,
+    ) -> Result<Benefit, AppError> {
+        let benefit = Benefit::new(name, description, usage, total_quantity)?;
+        self.repository.create_benefit(&benefit).await?;
+        tracing::info!("Created fan benefit {} ({})", benefit.id, benefit.name);
+        Ok(benefit)
+    }
+
+    pub async fn list_benefits(&self) -> Result<Vec<Benefit>, AppError> {
+        self.repository.list_benefits().await
+    }
+
+    pub async fn fan_benefits(&self, fan_id: &FanId) -> Result<Vec<FanBenefit>, AppError> {
+        self.repository.get_fan_benefits(fan_id).await
+    }
+
+    /// Unlock the catalog benefits named in a verification. Names without a
+    /// catalog entry are ignored; a failed verification unlocks nothing.
+    pub async fn unlock_for_verification(
+        &self,
+        fan_id: &FanId,
+        verification: &FanVerificationResult,
+    ) -> Result<Vec<FanBenefit>, AppError> {
+        if !verification.is_verified || verification.benefits_unlocked.is_empty() {
+            return Ok(Vec::new());
+        }
+        let unlocked = self
+            .repository
+            .unlock_benefits(fan_id, &verification.verification_id, &verification.benefits_unlocked)
+            .await?;
+        if !unlocked.is_empty() {
+            tracing::info!("Unlocked {} benefits for fan {}", unlocked.len(), fan_id.0);
+        }
+        Ok(unlocked)
+    }
+
+    /// Redeem a benefit of `fan_id` at `venue_id`, scanned by `staff_id`
+    pub async fn redeem(
+        &self,
+        benefit_id: Uuid,
+        fan_id: &FanId,
+        venue_id: &str,
+        staff_id: Uuid,
+        metadata: Option<serde_json::Value>,
+    ) -> Result<BenefitRedemption, AppError> {
+        let venue_id = venue_id.trim();
+        if venue_id.is_empty() {
+            return Err(AppError::ValidationError("venue_id is required".to_string()));
+        }
+        let metadata = metadata.unwrap_or_else(|| serde_json::json!({}));
+        if !metadata.is_object() {
+            return Err(AppError::ValidationError("metadata must be a JSON object".to_string()));
+        }
+
+        let redemption = self.repository.redeem(benefit_id, fan_id, venue_id, staff_id, metadata).await?;
+        tracing::info!(
+            "Fan {} redeemed benefit {} at venue {} (staff {})",
+            fan_id.0, benefit_id, redemption.venue_id, staff_id
+        );
+        self.publish_redeemed(&redemption).await;
+        Ok(redemption)
+    }
+
+    async fn publish_redeemed(&self, redemption: &BenefitRedemption) {
+        let Some(event_bus) = &self.event_bus else {
+            return;
+        };
+        let event = DomainEvent::FanBenefitRedeemed {
+            redemption_id: redemption.id,
+            benefit_id: redemption.benefit_id,
+            fan_id: redemption.fan_id.0,
+            venue_id: redemption.venue_id.clone(),
+            staff_id: redemption.staff_id,
+            occurred_at: redemption.redeemed_at,
+        };
+        // El canje ya está guardado: un fallo del bus no lo deshace
+        if let Err(e) = event_bus.publish(event).await {
+            tracing::error!("Failed to publish FanBenefitRedeemed for {}: {}", redemption.id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use std::sync::Mutex;
+    use crate::bounded_contexts::fan_loyalty::domain::benefits::check_redeemable;
+    use crate::bounded_contexts::orchestrator::EventHandler;
+    use crate::shared::domain::errors::BenefitRejection;
+
+    #[derive(Default)]
+    struct InMemoryBenefits {
+        catalog: Mutex<Vec<Benefit>>,
+        unlocked: Mutex<Vec<FanBenefit>>,
+    }
+
+    #[async_trait]
+    impl BenefitRepository for InMemoryBenefits {
+        async fn create_benefit(&self, benefit: &Benefit) -> Result<(), AppError> {
+            self.catalog.lock().unwrap().push(benefit.clone());
+            Ok(())
+        }
+        async fn find_benefit(&self, benefit_id: Uuid) -> Result<Option<Benefit>, AppError> {
+            Ok(self.catalog.lock().unwrap().iter().find(|b| b.id == benefit_id).cloned())
+        }
+        async fn list_benefits(&self) -> Result<Vec<Benefit>, AppError> {
+            Ok(self.catalog.lock().unwrap().clone())
+        }
+        async fn unlock_benefits(&self, fan_id: &FanId, verification_id: &str, names: &[String]) -> Result<Vec<FanBenefit>, AppError> {
+            let catalog = self.catalog.lock().unwrap();
+            let mut unlocked = self.unlocked.lock().unwrap();
+            let mut added = Vec::new();
+            for benefit in catalog.iter().filter(|b| b.is_active && names.iter().any(|n| n.eq_ignore_ascii_case(&b.name))) {
+                if unlocked.iter().any(|f| f.fan_id == *fan_id && f.benefit_id == benefit.id) {
+                    continue;
+                }
+                let fan_benefit = FanBenefit {
+                    id: Uuid::new_v4(),
+                    fan_id: fan_id.clone(),
+                    benefit_id: benefit.id,
+                    verification_id: verification_id.to_string(),
+                    uses_remaining: benefit.usage.uses_per_fan(),
+                    unlocked_at: Utc::now(),
+                };
+                unlocked.push(fan_benefit.clone());
+                added.push(fan_benefit);
+            }
+            Ok(added)
+        }
+        async fn get_fan_benefits(&self, fan_id: &FanId) -> Result<Vec<FanBenefit>, AppError> {
+            Ok(self.unlocked.lock().unwrap().iter().filter(|f| f.fan_id == *fan_id).cloned().collect())
+        }
+        async fn redeem(&self, benefit_id: Uuid, fan_id: &FanId, venue_id: &str, staff_id: Uuid, metadata: serde_json::Value) -> Result<BenefitRedemption, AppError> {
+            let mut catalog = self.catalog.lock().unwrap();
+            let mut unlocked = self.unlocked.lock().unwrap();
+            let benefit = catalog.iter_mut().find(|b| b.id == benefit_id)
+                .ok_or_else(|| AppError::NotFound(format!("Benefit {} not found", benefit_id)))?;
+            let fan_benefit = unlocked.iter_mut().find(|f| f.fan_id == *fan_id && f.benefit_id == benefit_id);
+            check_redeemable(benefit, fan_benefit.as_deref(), Utc::now()).map_err(AppError::BenefitNotRedeemable)?;
+            let fan_benefit = fan_benefit.unwrap();
+            fan_benefit.uses_remaining = fan_benefit.uses_remaining.map(|uses| uses - 1);
+            benefit.remaining_quantity = benefit.remaining_quantity.map(|quantity| quantity - 1);
+            Ok(BenefitRedemption {
+                id: Uuid::new_v4(),
+                benefit_id,
+                fan_id: fan_id.clone(),
+                venue_id: venue_id.to_string(),
+                staff_id,
+                metadata,
+                uses_remaining: fan_benefit.uses_remaining,
+                redeemed_at: Utc::now(),
+            })
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingEventBus {
+        published: Mutex<Vec<DomainEvent>>,
+    }
+
+    #[async_trait]
+    impl EventBus for RecordingEventBus {
+        async fn publish(&self, event: DomainEvent) -> Result<(), AppError> {
+            self.published.lock().unwrap().push(event);
+            Ok(())
+        }
+        async fn subscribe(&self, _event_type: &str, _handler: Arc<dyn EventHandler>) -> Result<(), AppError> {
+            Ok(())
+        }
+    }
+
+    fn verification(is_verified: bool, benefits: &[&str]) -> FanVerificationResult {
+        FanVerificationResult {
+            is_verified,
+            confidence_score: 0.95,
+            verification_id: "verification_1".to_string(),
+            wristband_eligible: is_verified,
+            benefits_unlocked: benefits.iter().map(|b| b.to_string()).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn verification_unlocks_catalog_benefits_once() {
+        let service = BenefitRedemptionService::new(Arc::new(InMemoryBenefits::default()));
+        service.create_benefit("VIP Access".to_string(), None, BenefitUsage::SingleUse, None).await.unwrap();
+        let fan_id = FanId::new();
+
+        assert!(service.unlock_for_verification(&fan_id, &verification(false, &["VIP Access"])).await.unwrap().is_empty());
+        // "Verified Fan Status" no está en el catálogo
+        let unlocked = service.unlock_for_verification(&fan_id, &verification(true, &["Verified Fan Status", "vip access"])).await.unwrap();
+        assert_eq!(unlocked.len(), 1);
+        assert!(service.unlock_for_verification(&fan_id, &verification(true, &["VIP Access"])).await.unwrap().is_empty());
+        assert_eq!(service.fan_benefits(&fan_id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn redemption_uses_up_the_benefit_and_publishes_an_event() {
+        let event_bus = Arc::new(RecordingEventBus::default());
+        let service = BenefitRedemptionService::new(Arc::new(InMemoryBenefits::default()))
+            .with_event_bus(event_bus.clone());
+        let benefit = service.create_benefit("Merch Discount".to_string(), None, BenefitUsage::MultiUse { uses: 2 }, None).await.unwrap();
+        let fan_id = FanId::new();
+        let staff_id = Uuid::new_v4();
+
+        let not_unlocked = service.redeem(benefit.id, &fan_id, "venue_1", staff_id, None).await;
+        assert!(matches!(not_unlocked, Err(AppError::BenefitNotRedeemable(BenefitRejection::NotUnlocked))));
+        assert!(matches!(
+            service.redeem(benefit.id, &fan_id, " ", staff_id, None).await,
+            Err(AppError::ValidationError(_))
+        ));
+
+        service.unlock_for_verification(&fan_id, &verification(true, &["Merch Discount"])).await.unwrap();
+        let first = service.redeem(benefit.id, &fan_id, "venue_1", staff_id, Some(serde_json::json!({"gate": "B"}))).await.unwrap();
+        assert_eq!(first.uses_remaining, Some(1));
+        service.redeem(benefit.id, &fan_id, "venue_1", staff_id, None).await.unwrap();
+        let third = service.redeem(benefit.id, &fan_id, "venue_1", staff_id, None).await;
+        assert!(matches!(third, Err(AppError::BenefitNotRedeemable(BenefitRejection::AlreadyRedeemed))));
+
+        let published = event_bus.published.lock().unwrap();
+        assert_eq!(published.len(), 2);
+        assert!(matches!(
+            &published[0],
+            DomainEvent::FanBenefitRedeemed { redemption_id, venue_id, .. } if *redemption_id == first.id && venue_id == "venue_1"
+        ));
+    }
+}
@@ -30,6 +30,33 @@ impl VerifyFanCommand {
     }
 }
 
+/// Verify Age Command - the birth year is only used to build the proof, never stored
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifyAgeCommand {
+    pub fan_id: FanId,
+    pub birth_year: u32,
+    pub minimum_age: u32,
+}
+
+impl VerifyAgeCommand {
+    pub fn new(fan_id: FanId, birth_year: u32, minimum_age: u32) -> Self {
+        Self {
+            fan_id,
+            birth_year,
+            minimum_age,
+        }
+    }
+
+    /// Age check required to unlock explicit content
+    pub fn for_explicit_content(fan_id: FanId, birth_year: u32) -> Self {
+        Self::new(
+            fan_id,
+            birth_year,
+            crate::bounded_contexts::fan_loyalty::domain::services::EXPLICIT_CONTENT_MINIMUM_AGE,
+        )
+    }
+}
+
 /// Create Wristband Command - TDD GREEN PHASE
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateWristbandCommand {
@@ -81,18 +108,11 @@ impl ActivateWristbandCommand {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerateQrCodeCommand {
     pub wristband_id: crate::bounded_contexts::fan_loyalty::domain::WristbandId,
-    pub expiration_hours: Option<u32>,
 }
 
 impl GenerateQrCodeCommand {
-    pub fn new(
-        wristband_id: crate::bounded_contexts::fan_loyalty::domain::WristbandId,
-        expiration_hours: Option<u32>,
-    ) -> Self {
-        Self {
-            wristband_id,
-            expiration_hours,
-        }
+    pub fn new(wristband_id: crate::bounded_contexts::fan_loyalty::domain::WristbandId) -> Self {
+        Self { wristband_id }
     }
 }
 
@@ -3,6 +3,8 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use async_trait::async_trait;
 
+use crate::bounded_contexts::fan_loyalty::application::wristband_minting::WristbandMintService;
+use crate::bounded_contexts::fan_loyalty::application::benefit_redemption::BenefitRedemptionService;
 use crate::bounded_contexts::fan_loyalty::{
     domain::{
         repositories::{
@@ -41,6 +43,10 @@ pub struct FanLoyaltyContainer {
     pub nft_service: Arc<dyn NftService>,
     pub zk_proof_service: Arc<dyn ZkProofService>,
     pub event_publisher: Arc<dyn EventPublisher>,
+    /// Solana mint of the wristbands; without it wristbands are not minted
+    pub wristband_minting: Option<Arc<WristbandMintService>>,
+    /// Benefits catalog and redemptions; without it verification unlocks nothing
+    pub benefits: Option<Arc<BenefitRedemptionService>>,
 
     // Handlers
     pub fan_loyalty_handlers: Arc<FanLoyaltyHandlers>,
@@ -78,6 +84,8 @@ impl FanLoyaltyContainer {
             nft_service: nft_service.clone(),
             zk_proof_service: zk_proof_service.clone(),
             event_publisher: event_publisher.clone(),
+            wristband_minting: None,
+            benefits: None,
         }));
         
         let wristband_handler = WristbandHandler::new(Arc::new(Self {
@@ -92,6 +100,8 @@ impl FanLoyaltyContainer {
             nft_service: nft_service.clone(),
             zk_proof_service: zk_proof_service.clone(),
             event_publisher: event_publisher.clone(),
+            wristband_minting: None,
+            benefits: None,
         }));
         
         let qr_handler = QrCodeHandler::new(Arc::new(Self {
@@ -106,6 +116,8 @@ impl FanLoyaltyContainer {
             nft_service: nft_service.clone(),
             zk_proof_service: zk_proof_service.clone(),
             event_publisher: event_publisher.clone(),
+            wristband_minting: None,
+            benefits: None,
         }));
         
         let fan_loyalty_handlers = Arc::new(FanLoyaltyHandlers::new(
@@ -128,10 +140,24 @@ impl FanLoyaltyContainer {
             nft_service,
             zk_proof_service,
             event_publisher,
+            wristband_minting: None,
+            benefits: None,
             fan_loyalty_handlers,
         }
     }
 
+    /// Mint wristbands on Solana and require the NFT before activation
+    pub fn with_wristband_minting(mut self, wristband_minting: Arc<WristbandMintService>) -> Self {
+        self.wristband_minting = Some(wristband_minting);
+        self
+    }
+
+    /// Unlock benefits on verification and allow redeeming them
+    pub fn with_benefits(mut self, benefits: Arc<BenefitRedemptionService>) -> Self {
+        self.benefits = Some(benefits);
+        self
+    }
+
     /// Get fan verification repository
     pub fn fan_verification_repository(&self) -> Arc<dyn FanVerificationRepository> {
         self.fan_verification_repository.clone()
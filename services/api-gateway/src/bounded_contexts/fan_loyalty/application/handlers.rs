@@ -4,8 +4,10 @@
 
 use std::sync::Arc;
 use crate::bounded_contexts::fan_loyalty::application::dependency_injection::FanLoyaltyContainer;
-use crate::bounded_contexts::fan_loyalty::application::commands::{VerifyFanCommand, CreateWristbandCommand};
+use crate::bounded_contexts::fan_loyalty::application::commands::{VerifyFanCommand, VerifyAgeCommand, CreateWristbandCommand, GenerateQrCodeCommand, ValidateQrCodeCommand};
 use crate::bounded_contexts::fan_loyalty::domain::{FanVerificationResult, NftWristband};
+use crate::bounded_contexts::fan_loyalty::domain::entities::{QrCode, QrCodeRejection, QrCodeUse, QrScanOutcome};
+use crate::bounded_contexts::fan_loyalty::domain::services::ZkAgeProof;
 
 /// Fan Verification Handler - TDD GREEN PHASE
 #[derive(Clone)]
@@ -34,7 +36,14 @@ impl FanVerificationHandler {
             &verification_result,
         ).await?;
 
-        // 3. Publish domain event
+        // 3. Unlock the catalog benefits; the verification stands even if this fails
+        if let Some(benefits) = &self.container.benefits {
+            if let Err(e) = benefits.unlock_for_verification(&command.fan_id, &verification_result).await {
+                tracing::error!("Failed to unlock benefits for fan {}: {}", command.fan_id.0, e);
+            }
+        }
+
+        // 4. Publish domain event
         let event = crate::bounded_contexts::fan_loyalty::domain::events::FanVerifiedEvent {
             fan_id: command.fan_id.clone(),
             verification_id: verification_result.verification_id.clone(),
@@ -47,6 +56,16 @@ impl FanVerificationHandler {
 
         Ok(verification_result)
     }
+
+    /// Prove age eligibility (e.g. explicit content) with a ZK proof.
+    /// Only the proof is returned; the birth year is neither stored nor published.
+    pub async fn handle_verify_age(&self, command: &VerifyAgeCommand) -> Result<ZkAgeProof, String> {
+        self.container.zk_proof_service.generate_age_proof(
+            &command.fan_id,
+            command.birth_year,
+            command.minimum_age,
+        ).await
+    }
 }
 
 /// Wristband Handler - TDD GREEN PHASE
@@ -75,8 +94,20 @@ impl WristbandHandler {
         // 2. Save wristband using repository
         self.container.wristband_repository.save_wristband(&wristband).await?;
 
-        // 3. Create NFT using domain service
-        let nft_result = self.container.nft_service.create_nft(&wristband, &command.fan_wallet_address).await?;
+        // 3. Mint the NFT on Solana for verified fans; a failed request stays
+        //    visible as the wristband's mint status
+        if let Some(minting) = &self.container.wristband_minting {
+            let verified = self.container.fan_verification_repository
+                .get_verification_result(&command.fan_id).await
+                .map_err(|e| e.to_string())?
+                .map_or(false, |result| result.is_verified);
+            if verified {
+                let fan_wallet = Some(command.fan_wallet_address.as_str());
+                if let Err(e) = minting.request_mint(&wristband, fan_wallet).await {
+                    tracing::error!("Failed to request NFT mint for wristband {}: {}", wristband.id.0, e);
+                }
+            }
+        }
 
         // 4. Publish domain event
         let event = crate::bounded_contexts::fan_loyalty::domain::events::WristbandCreatedEvent {
@@ -100,14 +131,26 @@ impl WristbandHandler {
         let mut wristband = self.container.wristband_repository.get_wristband(wristband_id).await?
             .ok_or_else(|| "Wristband not found".to_string())?;
 
-        // 2. Activate wristband
+        // 2. Only minted wristbands can be activated
+        if let Some(minting) = &self.container.wristband_minting {
+            minting.ensure_activatable(&wristband.id).await.map_err(|e| e.to_string())?;
+        }
+
+        // 3. Activate wristband
         wristband.is_active = true;
         wristband.activated_at = Some(chrono::Utc::now());
 
-        // 3. Save updated wristband
+        // 4. Save updated wristband
         self.container.wristband_repository.save_wristband(&wristband).await?;
 
-        // 4. Publish domain event
+        // 5. Hand the NFT to the fan when the wristband type allows it
+        if let Some(minting) = &self.container.wristband_minting {
+            if let Err(e) = minting.transfer_on_activation(&wristband).await {
+                tracing::error!("Failed to transfer NFT of wristband {}: {}", wristband.id.0, e);
+            }
+        }
+
+        // 6. Publish domain event
         let event = crate::bounded_contexts::fan_loyalty::domain::events::WristbandActivatedEvent {
             wristband_id: wristband.id.clone(),
             fan_id: wristband.fan_id.clone(),
@@ -119,6 +162,73 @@ impl WristbandHandler {
         Ok(())
     }
 
+    /// Issue a short-lived signed QR code for an active wristband
+    pub async fn handle_generate_qr_code(&self, command: &GenerateQrCodeCommand) -> Result<QrCode, String> {
+        // 1. Only active wristbands get codes
+        let wristband = self.container.wristband_repository.get_wristband(&command.wristband_id).await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| QrCodeRejection::WristbandNotFound.message().to_string())?;
+        if !wristband.is_active {
+            return Err(QrCodeRejection::WristbandInactive.message().to_string());
+        }
+
+        // 2. Sign the code
+        let qr_code = self.container.qr_code_service.generate_qr_code(&wristband.id).await?;
+        let expires_at = qr_code.expires_at.ok_or_else(|| "QR code has no expiry".to_string())?;
+
+        // 3. Record it so it can only be scanned once
+        self.container.qr_code_repository.save_qr_code(&wristband.id, &qr_code.code, expires_at).await
+            .map_err(|e| e.to_string())?;
+
+        Ok(qr_code)
+    }
+
+    /// Venue scan: signature and expiry, wristband state, then single use.
+    /// Rejections are an `Invalid` outcome, not an error.
+    pub async fn handle_validate_qr_code(&self, command: &ValidateQrCodeCommand) -> Result<QrScanOutcome, String> {
+        let scanned_at = chrono::Utc::now();
+
+        // 1. Signature and expiry (same check a venue can run offline)
+        let claims = match self.container.qr_code_service.verify_qr_code(&command.qr_code, scanned_at) {
+            Ok(claims) => claims,
+            Err(reason) => return Ok(QrScanOutcome::Invalid { reason }),
+        };
+
+        // 2. The wristband must still be active
+        let Some(wristband) = self.container.wristband_repository.get_wristband(&claims.wristband_id).await
+            .map_err(|e| e.to_string())? else {
+            return Ok(QrScanOutcome::Invalid { reason: QrCodeRejection::WristbandNotFound });
+        };
+        if !wristband.is_active {
+            return Ok(QrScanOutcome::Invalid { reason: QrCodeRejection::WristbandInactive });
+        }
+
+        // 3. Spend the code
+        let outcome = match self.container.qr_code_repository.use_qr_code(&command.qr_code, scanned_at).await
+            .map_err(|e| e.to_string())?
+        {
+            QrCodeUse::Accepted => QrScanOutcome::Valid { wristband, scanned_at },
+            QrCodeUse::AlreadyUsed(used_at) => QrScanOutcome::AlreadyUsed { wristband_id: claims.wristband_id, used_at },
+            QrCodeUse::Unknown => QrScanOutcome::Invalid { reason: QrCodeRejection::UnknownCode },
+        };
+
+        // 4. Publish domain event for granted access
+        if let QrScanOutcome::Valid { wristband, .. } = &outcome {
+            let event = crate::bounded_contexts::fan_loyalty::domain::events::QrCodeScannedEvent {
+                qr_code: command.qr_code.clone(),
+                wristband_id: Some(wristband.id.clone()),
+                fan_id: Some(wristband.fan_id.clone()),
+                scanner_id: String::new(),
+                location: command.validation_context.as_ref().and_then(|context| context.location.clone()),
+                access_granted: true,
+                scanned_at,
+            };
+            self.container.event_publisher.publish_qr_code_scanned(&event).await?;
+        }
+
+        Ok(outcome)
+    }
+
     /// Handle wristband retrieval
     pub async fn handle_get_wristband(&self, wristband_id: &crate::bounded_contexts::fan_loyalty::domain::WristbandId) -> Result<Option<NftWristband>, String> {
         // TDD GREEN PHASE: Real implementation
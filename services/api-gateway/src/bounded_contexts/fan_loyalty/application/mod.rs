@@ -4,4 +4,5 @@ pub mod dependency_injection;
 pub mod mock_dependency_injection;
 pub mod real_dependency_injection;
 pub mod event_handlers;
-pub mod handlers;
\ No newline at end of file
+pub mod handlers;
+pub mod wristband_minting;pub mod benefit_redemption;
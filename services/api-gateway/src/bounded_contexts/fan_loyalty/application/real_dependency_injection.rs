@@ -14,6 +14,8 @@ use crate::bounded_contexts::fan_loyalty::infrastructure::mock_services::{
     MockBiometricVerificationService, MockWristbandService, MockQrCodeService, 
     MockZkProofService, MockEventPublisher
 };
+use crate::bounded_contexts::fan_loyalty::domain::services::QrCodeService;
+use crate::bounded_contexts::fan_loyalty::infrastructure::qr_service::QrCodeService as SignedQrCodeService;
 
 use crate::bounded_contexts::fan_loyalty::infrastructure::nft_service::BlockchainNftService;
 use crate::shared::infrastructure::clients::blockchain_client::BlockchainClient;
@@ -56,10 +58,19 @@ impl RealFanLoyaltyFactory {
             event_publisher.clone(),
         ));
         
-        let qr_code_service = Arc::new(MockQrCodeService::new(
-            qr_code_repository.clone(),
-            event_publisher.clone(),
-        ));
+        // QR codes firmados con HMAC; sin secreto configurado se usa el mock
+        let qr_base_url = std::env::var("WRISTBAND_QR_BASE_URL")
+            .unwrap_or_else(|_| "http://localhost:3000".to_string());
+        let qr_code_service: Arc<dyn QrCodeService> = match SignedQrCodeService::from_env(qr_base_url) {
+            Ok(service) => Arc::new(service),
+            Err(e) => {
+                tracing::error!("Wristband QR signing disabled, falling back to mock: {}", e);
+                Arc::new(MockQrCodeService::new(
+                    qr_code_repository.clone(),
+                    event_publisher.clone(),
+                ))
+            }
+        };
         
         let zk_proof_service = Arc::new(MockZkProofService::new(
             zk_proof_repository.clone(),
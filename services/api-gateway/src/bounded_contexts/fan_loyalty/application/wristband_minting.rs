@@ -0,0 +1,382 @@
+// Wristband NFT minting through solana-integration
+//
+// Igual que las campañas: se publica un `NftMintRequest` en la cola de Solana
+// y el resultado vuelve por la cola de respuestas. La pulsera se mintea a la
+// wallet de custodia y solo se puede activar cuando el minteo ha terminado.
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+use vibestream_types::{NftMintOutcome, NftMintRequest, NftMintResult, NftTransferRequest, QueueNames};
+
+use crate::bounded_contexts::fan_loyalty::domain::entities::{NftWristband, WristbandId};
+use crate::bounded_contexts::fan_loyalty::domain::nft_mint::{
+    build_wristband_metadata, WristbandMintStatus, WristbandNftMint,
+};
+use crate::bounded_contexts::fan_loyalty::domain::repositories::WristbandMintRepository;
+use crate::shared::domain::errors::AppError;
+
+pub const DEFAULT_MAX_MINT_ATTEMPTS: u32 = 3;
+
+/// Outbound port to the solana-integration queues
+#[async_trait]
+pub trait WristbandMintQueue: Send + Sync {
+    async fn publish_mint(&self, request: &NftMintRequest) -> Result<(), AppError>;
+    async fn publish_transfer(&self, request: &NftTransferRequest) -> Result<(), AppError>;
+}
+
+pub struct WristbandMintService {
+    mints: Arc<dyn WristbandMintRepository>,
+    queue: Arc<dyn WristbandMintQueue>,
+    custody_wallet: String,
+    metadata_base_url: Option<String>,
+    reply_queue: String,
+    max_attempts: u32,
+}
+
+impl WristbandMintService {
+    pub fn new(mints: Arc<dyn WristbandMintRepository>, queue: Arc<dyn WristbandMintQueue>, custody_wallet: String) -> Self {
+        Self {
+            mints,
+            queue,
+            custody_wallet,
+            metadata_base_url: None,
+            reply_queue: QueueNames::WRISTBAND_NFT_MINT_RESULTS.to_string(),
+            max_attempts: DEFAULT_MAX_MINT_ATTEMPTS,
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn with_reply_queue(mut self, reply_queue: impl Into<String>) -> Self {
+        self.reply_queue = reply_queue.into();
+        self
+    }
+
+    pub fn with_metadata_base_url(mut self, metadata_base_url: impl Into<String>) -> Self {
+        self.metadata_base_url = Some(metadata_base_url.into());
+        self
+    }
+
+    pub fn reply_queue(&self) -> &str {
+        &self.reply_queue
+    }
+
+    /// Current mint of a wristband, if any
+    pub async fn find_mint(&self, wristband_id: &WristbandId) -> Result<Option<WristbandNftMint>, AppError> {
+        self.mints.find_mint_by_wristband(wristband_id).await
+    }
+
+    /// Request the NFT of a wristband. Calling it again returns the existing
+    /// mint unless the previous one failed, in which case it starts over.
+    pub async fn request_mint(
+        &self,
+        wristband: &NftWristband,
+        fan_wallet_address: Option<&str>,
+    ) -> Result<WristbandNftMint, AppError> {
+        let metadata = build_wristband_metadata(wristband, self.metadata_base_url.as_deref());
+        let candidate = WristbandNftMint::pending(
+            wristband,
+            self.custody_wallet.clone(),
+            fan_wallet_address.map(str::to_string),
+            metadata,
+        );
+        let mint = self.mints.reserve_mint(&candidate).await?;
+        if mint.request_id != candidate.request_id {
+            return Ok(mint);
+        }
+
+        if let Err(e) = self.queue.publish_mint(&mint.to_request(&self.reply_queue)).await {
+            self.mints.mark_failed(mint.request_id, &e.to_string()).await?;
+            return Err(AppError::ExternalServiceError(format!("Failed to request wristband NFT mint: {}", e)));
+        }
+
+        tracing::info!("Requested NFT mint {} for wristband {}", mint.request_id, wristband.id.0);
+        Ok(mint)
+    }
+
+    /// The wristband can only be activated once its NFT exists
+    pub async fn ensure_activatable(&self, wristband_id: &WristbandId) -> Result<WristbandNftMint, AppError> {
+        let mint = self.mints.find_mint_by_wristband(wristband_id).await?.ok_or_else(|| {
+            AppError::DomainRuleViolation(format!("Wristband {} has no NFT; the fan must be verified first", wristband_id.0))
+        })?;
+        match mint.status {
+            WristbandMintStatus::Minted => Ok(mint),
+            WristbandMintStatus::Pending => Err(AppError::DomainRuleViolation(format!(
+                "NFT of wristband {} is still being minted",
+                wristband_id.0
+            ))),
+            WristbandMintStatus::Failed => Err(AppError::DomainRuleViolation(format!(
+                "NFT mint of wristband {} failed: {}",
+                wristband_id.0,
+                mint.last_error.as_deref().unwrap_or("unknown error")
+            ))),
+        }
+    }
+
+    /// Send the NFT to the fan's wallet if the wristband type allows it and the
+    /// fan linked one. Returns the transfer that was requested, if any.
+    pub async fn transfer_on_activation(&self, wristband: &NftWristband) -> Result<Option<NftTransferRequest>, AppError> {
+        if !wristband.wristband_type.transfers_on_activation() {
+            return Ok(None);
+        }
+        let mint = self.ensure_activatable(&wristband.id).await?;
+        let (Some(mint_address), Some(recipient_wallet)) = (mint.mint_address.clone(), mint.fan_wallet_address.clone()) else {
+            return Ok(None);
+        };
+
+        // Mismo request_id que el minteo: solana-integration no transfiere dos veces
+        let transfer = NftTransferRequest { request_id: mint.request_id, mint_address, recipient_wallet };
+        self.queue.publish_transfer(&transfer).await?;
+        self.mints.record_transfer(mint.request_id, &transfer.recipient_wallet).await?;
+        tracing::info!("Requested transfer of wristband {} NFT to {}", wristband.id.0, transfer.recipient_wallet);
+        Ok(Some(transfer))
+    }
+
+    /// Apply a result from solana-integration. Results for mints that are no
+    /// longer pending are duplicates of an earlier delivery and are ignored.
+    pub async fn handle_result(&self, result: NftMintResult) -> Result<WristbandMintStatus, AppError> {
+        let mint = self.mints.find_mint(result.request_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Wristband NFT mint {} not found", result.request_id)))?;
+        if mint.status != WristbandMintStatus::Pending {
+            tracing::info!("Ignoring duplicate result for wristband NFT mint {} ({})", mint.request_id, mint.status);
+            return Ok(mint.status);
+        }
+
+        match result.outcome {
+            NftMintOutcome::Minted { mint_address, signature } => {
+                if !self.mints.mark_minted(mint.request_id, &mint_address, &signature).await? {
+                    return self.current_status(mint.request_id).await;
+                }
+                tracing::info!("Wristband {} minted at {} ({})", mint.wristband_id.0, mint_address, signature);
+                Ok(WristbandMintStatus::Minted)
+            }
+            NftMintOutcome::Failed { reason, retryable } if retryable && mint.attempts < self.max_attempts => {
+                let attempts = self.mints.record_retry(mint.request_id, &reason).await?;
+                let retry = WristbandNftMint { attempts, ..mint };
+                self.queue.publish_mint(&retry.to_request(&self.reply_queue)).await?;
+                tracing::warn!("Retrying wristband NFT mint {} (attempt {}): {}", retry.request_id, attempts, reason);
+                Ok(WristbandMintStatus::Pending)
+            }
+            NftMintOutcome::Failed { reason, .. } => {
+                self.mints.mark_failed(mint.request_id, &reason).await?;
+                tracing::error!("Wristband NFT mint {} failed after {} attempt(s): {}", mint.request_id, mint.attempts, reason);
+                Ok(WristbandMintStatus::Failed)
+            }
+        }
+    }
+
+    async fn current_status(&self, request_id: Uuid) -> Result<WristbandMintStatus, AppError> {
+        Ok(self.mints.find_mint(request_id).await?
+            .map(|mint| mint.status)
+            .unwrap_or(WristbandMintStatus::Failed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::fan_loyalty::domain::entities::{FanId, WristbandType};
+    use chrono::Utc;
+    use std::collections::{HashMap, VecDeque};
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct InMemoryMints {
+        mints: Mutex<HashMap<Uuid, WristbandNftMint>>,
+        transfers: Mutex<Vec<(Uuid, String)>>,
+    }
+
+    #[async_trait]
+    impl WristbandMintRepository for InMemoryMints {
+        async fn reserve_mint(&self, mint: &WristbandNftMint) -> Result<WristbandNftMint, AppError> {
+            let mut mints = self.mints.lock().unwrap();
+            if let Some(existing) = mints.values().find(|m| m.wristband_id == mint.wristband_id && m.status != WristbandMintStatus::Failed) {
+                return Ok(existing.clone());
+            }
+            mints.retain(|_, m| m.wristband_id != mint.wristband_id);
+            mints.insert(mint.request_id, mint.clone());
+            Ok(mint.clone())
+        }
+
+        async fn find_mint(&self, request_id: Uuid) -> Result<Option<WristbandNftMint>, AppError> {
+            Ok(self.mints.lock().unwrap().get(&request_id).cloned())
+        }
+
+        async fn find_mint_by_wristband(&self, wristband_id: &WristbandId) -> Result<Option<WristbandNftMint>, AppError> {
+            Ok(self.mints.lock().unwrap().values().find(|m| &m.wristband_id == wristband_id).cloned())
+        }
+
+        async fn record_retry(&self, request_id: Uuid, reason: &str) -> Result<u32, AppError> {
+            let mut mints = self.mints.lock().unwrap();
+            let mint = mints.get_mut(&request_id).unwrap();
+            mint.attempts += 1;
+            mint.last_error = Some(reason.to_string());
+            Ok(mint.attempts)
+        }
+
+        async fn mark_minted(&self, request_id: Uuid, mint_address: &str, signature: &str) -> Result<bool, AppError> {
+            let mut mints = self.mints.lock().unwrap();
+            let mint = mints.get_mut(&request_id).unwrap();
+            if mint.status != WristbandMintStatus::Pending {
+                return Ok(false);
+            }
+            mint.status = WristbandMintStatus::Minted;
+            mint.mint_address = Some(mint_address.to_string());
+            mint.signature = Some(signature.to_string());
+            mint.minted_at = Some(Utc::now());
+            Ok(true)
+        }
+
+        async fn mark_failed(&self, request_id: Uuid, reason: &str) -> Result<bool, AppError> {
+            let mut mints = self.mints.lock().unwrap();
+            let mint = mints.get_mut(&request_id).unwrap();
+            if mint.status != WristbandMintStatus::Pending {
+                return Ok(false);
+            }
+            mint.status = WristbandMintStatus::Failed;
+            mint.last_error = Some(reason.to_string());
+            Ok(true)
+        }
+
+        async fn record_transfer(&self, request_id: Uuid, recipient_wallet: &str) -> Result<(), AppError> {
+            self.transfers.lock().unwrap().push((request_id, recipient_wallet.to_string()));
+            Ok(())
+        }
+    }
+
+    /// Colas de Solana en memoria
+    #[derive(Default)]
+    struct QueuedMints {
+        requests: Mutex<VecDeque<NftMintRequest>>,
+        transfers: Mutex<Vec<NftTransferRequest>>,
+    }
+
+    #[async_trait]
+    impl WristbandMintQueue for QueuedMints {
+        async fn publish_mint(&self, request: &NftMintRequest) -> Result<(), AppError> {
+            self.requests.lock().unwrap().push_back(request.clone());
+            Ok(())
+        }
+
+        async fn publish_transfer(&self, request: &NftTransferRequest) -> Result<(), AppError> {
+            self.transfers.lock().unwrap().push(request.clone());
+            Ok(())
+        }
+    }
+
+    /// Responder simulado de solana-integration que falla los primeros intentos
+    fn respond(request: &NftMintRequest, failing_attempts: u32, retryable: bool) -> NftMintResult {
+        let outcome = if request.attempt <= failing_attempts {
+            NftMintOutcome::Failed { reason: "RPC node timeout".to_string(), retryable }
+        } else {
+            NftMintOutcome::Minted { mint_address: "WristMint1".to_string(), signature: "WristSig1".to_string() }
+        };
+        NftMintResult { request_id: request.request_id, outcome }
+    }
+
+    async fn drain(service: &WristbandMintService, queue: &QueuedMints, failing_attempts: u32, retryable: bool) {
+        loop {
+            let next = queue.requests.lock().unwrap().pop_front();
+            let Some(request) = next else { break };
+            service.handle_result(respond(&request, failing_attempts, retryable)).await.unwrap();
+        }
+    }
+
+    fn setup() -> (Arc<InMemoryMints>, Arc<QueuedMints>, WristbandMintService) {
+        let mints = Arc::new(InMemoryMints::default());
+        let queue = Arc::new(QueuedMints::default());
+        let service = WristbandMintService::new(mints.clone(), queue.clone(), "Custody111".to_string());
+        (mints, queue, service)
+    }
+
+    fn wristband(wristband_type: WristbandType) -> NftWristband {
+        NftWristband::new(FanId::new(), "concert_456".to_string(), "artist_789".to_string(), wristband_type)
+    }
+
+    #[tokio::test]
+    async fn test_successful_mint_makes_the_wristband_activatable_and_transfers_it() {
+        let (mints, queue, service) = setup();
+        let wristband = wristband(WristbandType::VIP);
+
+        let mint = service.request_mint(&wristband, Some("FanWallet1")).await.unwrap();
+        assert_eq!(queue.requests.lock().unwrap()[0].recipient_wallet, "Custody111");
+        assert!(service.ensure_activatable(&wristband.id).await.is_err());
+
+        drain(&service, &queue, 0, true).await;
+        let minted = service.ensure_activatable(&wristband.id).await.unwrap();
+        assert_eq!(minted.request_id, mint.request_id);
+        assert_eq!(minted.mint_address.as_deref(), Some("WristMint1"));
+        assert_eq!(minted.signature.as_deref(), Some("WristSig1"));
+
+        let transfer = service.transfer_on_activation(&wristband).await.unwrap().unwrap();
+        assert_eq!(transfer.mint_address, "WristMint1");
+        assert_eq!(transfer.recipient_wallet, "FanWallet1");
+        assert_eq!(mints.transfers.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_general_wristband_stays_in_custody() {
+        let (_, queue, service) = setup();
+        let wristband = wristband(WristbandType::General);
+
+        service.request_mint(&wristband, Some("FanWallet1")).await.unwrap();
+        drain(&service, &queue, 0, true).await;
+
+        assert!(service.transfer_on_activation(&wristband).await.unwrap().is_none());
+        assert!(queue.transfers.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transient_failures_are_retried_with_the_same_request_id() {
+        let (_, queue, service) = setup();
+        let wristband = wristband(WristbandType::Backstage);
+
+        let mint = service.request_mint(&wristband, None).await.unwrap();
+        drain(&service, &queue, 2, true).await;
+
+        let stored = service.find_mint(&wristband.id).await.unwrap().unwrap();
+        assert_eq!(stored.request_id, mint.request_id);
+        assert_eq!(stored.status, WristbandMintStatus::Minted);
+        assert_eq!(stored.attempts, 3);
+        // Sin wallet del fan la pulsera se activa pero el NFT no se mueve
+        assert!(service.transfer_on_activation(&wristband).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_retries_leave_a_terminal_failure_that_blocks_activation() {
+        let (_, queue, service) = setup();
+        let service = service.with_max_attempts(2);
+        let wristband = wristband(WristbandType::VIP);
+
+        service.request_mint(&wristband, Some("FanWallet1")).await.unwrap();
+        drain(&service, &queue, 5, true).await;
+
+        let failed = service.find_mint(&wristband.id).await.unwrap().unwrap();
+        assert_eq!(failed.status, WristbandMintStatus::Failed);
+        assert_eq!(failed.attempts, 2);
+        assert_eq!(failed.last_error.as_deref(), Some("RPC node timeout"));
+        assert!(matches!(service.ensure_activatable(&wristband.id).await, Err(AppError::DomainRuleViolation(_))));
+
+        // Un nuevo intento empieza un minteo distinto
+        let again = service.request_mint(&wristband, Some("FanWallet1")).await.unwrap();
+        assert_ne!(again.request_id, failed.request_id);
+        assert_eq!(again.status, WristbandMintStatus::Pending);
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_failure_is_terminal_immediately() {
+        let (_, queue, service) = setup();
+        let wristband = wristband(WristbandType::VIP);
+
+        service.request_mint(&wristband, None).await.unwrap();
+        drain(&service, &queue, 1, false).await;
+
+        let failed = service.find_mint(&wristband.id).await.unwrap().unwrap();
+        assert_eq!(failed.status, WristbandMintStatus::Failed);
+        assert_eq!(failed.attempts, 1);
+    }
+}
@@ -0,0 +1,236 @@
+//! Fan loyalty benefits and their redemption
+//!
+//! The catalog is managed by admins. When a fan is verified, every catalog
+//! benefit named in `FanVerificationResult::benefits_unlocked` is unlocked for
+//! that fan, and venue staff redeem it on site. A benefit is single-use,
+//! multi-use with a number of uses per fan, or usable any number of times
+//! within a time window; on top of that the catalog can cap the total number
+//! of redemptions across all fans.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_loyalty::domain::entities::FanId;
+use crate::shared::domain::errors::{AppError, BenefitRejection};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BenefitUsage {
+    SingleUse,
+    MultiUse { uses: u32 },
+    TimeWindowed { starts_at: DateTime<Utc>, ends_at: DateTime<Utc> },
+}
+
+impl BenefitUsage {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BenefitUsage::SingleUse => "single_use",
+            BenefitUsage::MultiUse { .. } => "multi_use",
+            BenefitUsage::TimeWindowed { .. } => "time_windowed",
+        }
+    }
+
+    /// Redemptions each fan gets; `None` when only the window limits them
+    pub fn uses_per_fan(&self) -> Option<u32> {
+        match self {
+            BenefitUsage::SingleUse => Some(1),
+            BenefitUsage::MultiUse { uses } => Some(*uses),
+            BenefitUsage::TimeWindowed { .. } => None,
+        }
+    }
+
+    pub fn window(&self) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+        match self {
+            BenefitUsage::TimeWindowed { starts_at, ends_at } => Some((*starts_at, *ends_at)),
+            _ => None,
+        }
+    }
+
+    /// Rebuild from the catalog columns
+    pub fn from_parts(
+        kind: &str,
+        uses_per_fan: Option<u32>,
+        starts_at: Option<DateTime<Utc>>,
+        ends_at: Option<DateTime<Utc>>,
+    ) -> Result<Self, AppError> {
+        match (kind, uses_per_fan, starts_at, ends_at) {
+            ("single_use", _, _, _) => Ok(BenefitUsage::SingleUse),
+            ("multi_use", Some(uses), _, _) => Ok(BenefitUsage::MultiUse { uses }),
+            ("time_windowed", _, Some(starts_at), Some(ends_at)) => Ok(BenefitUsage::TimeWindowed { starts_at, ends_at }),
+            _ => Err(AppError::SerializationError(format!("Invalid benefit usage: {}", kind))),
+        }
+    }
+}
+
+/// Catalog entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Benefit {
+    pub id: Uuid,
+    /// Matches the strings in `FanVerificationResult::benefits_unlocked`
+    pub name: String,
+    pub description: Option<String>,
+    pub usage: BenefitUsage,
+    /// Cap on redemptions across all fans; `None` is unlimited
+    pub total_quantity: Option<u32>,
+    pub remaining_quantity: Option<u32>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Benefit {
+    pub fn new(
+        name: String,
+        description: Option<String>,
+        usage: BenefitUsage,
+        total_quantity: Option<u32>,
+    ) -> Result<Self, AppError> {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(AppError::ValidationError("Benefit name is required".to_string()));
+        }
+        match &usage {
+            BenefitUsage::MultiUse { uses } if *uses == 0 => {
+                return Err(AppError::ValidationError("A multi-use benefit needs at least one use".to_string()));
+            }
+            BenefitUsage::TimeWindowed { starts_at, ends_at } if starts_at >= ends_at => {
+                return Err(AppError::ValidationError("The benefit window must end after it starts".to_string()));
+            }
+            _ => {}
+        }
+        if total_quantity == Some(0) {
+            return Err(AppError::ValidationError("A limited benefit needs at least one unit".to_string()));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            name,
+            description,
+            usage,
+            total_quantity,
+            remaining_quantity: total_quantity,
+            is_active: true,
+            created_at: Utc::now(),
+        })
+    }
+}
+
+/// A benefit unlocked for one fan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FanBenefit {
+    pub id: Uuid,
+    pub fan_id: FanId,
+    pub benefit_id: Uuid,
+    /// Verification that unlocked it
+    pub verification_id: String,
+    /// `None` for time-windowed benefits
+    pub uses_remaining: Option<u32>,
+    pub unlocked_at: DateTime<Utc>,
+}
+
+/// Who redeemed what, where
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenefitRedemption {
+    pub id: Uuid,
+    pub benefit_id: Uuid,
+    pub fan_id: FanId,
+    pub venue_id: String,
+    pub staff_id: Uuid,
+    /// Gate, device or anything else the venue wants to keep
+    pub metadata: serde_json::Value,
+    pub uses_remaining: Option<u32>,
+    pub redeemed_at: DateTime<Utc>,
+}
+
+/// Whether `fan_benefit` can be redeemed right now. The quantity and use
+/// counters are checked again when they are decremented, since other
+/// redemptions may be in flight.
+pub fn check_redeemable(
+    benefit: &Benefit,
+    fan_benefit: Option<&FanBenefit>,
+    now: DateTime<Utc>,
+) -> Result<(), BenefitRejection> {
+    let fan_benefit = fan_benefit.ok_or(BenefitRejection::NotUnlocked)?;
+    if !benefit.is_active {
+        return Err(BenefitRejection::Inactive);
+    }
+    if let Some((starts_at, ends_at)) = benefit.usage.window() {
+        if now < starts_at {
+            return Err(BenefitRejection::NotYetAvailable);
+        }
+        if now >= ends_at {
+            return Err(BenefitRejection::Expired);
+        }
+    }
+    if fan_benefit.uses_remaining == Some(0) {
+        return Err(BenefitRejection::AlreadyRedeemed);
+    }
+    if benefit.remaining_quantity == Some(0) {
+        return Err(BenefitRejection::SoldOut);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 15, 20, 0, 0).unwrap()
+    }
+
+    fn unlocked(benefit: &Benefit) -> FanBenefit {
+        FanBenefit {
+            id: Uuid::new_v4(),
+            fan_id: FanId::new(),
+            benefit_id: benefit.id,
+            verification_id: "verification_1".to_string(),
+            uses_remaining: benefit.usage.uses_per_fan(),
+            unlocked_at: now(),
+        }
+    }
+
+    #[test]
+    fn test_usage_limits_each_fan() {
+        let single = Benefit::new("Free Drink".to_string(), None, BenefitUsage::SingleUse, None).unwrap();
+        let mut fan_benefit = unlocked(&single);
+        assert_eq!(check_redeemable(&single, Some(&fan_benefit), now()), Ok(()));
+        fan_benefit.uses_remaining = Some(0);
+        assert_eq!(check_redeemable(&single, Some(&fan_benefit), now()), Err(BenefitRejection::AlreadyRedeemed));
+
+        let multi = Benefit::new("Merch Discount".to_string(), None, BenefitUsage::MultiUse { uses: 3 }, None).unwrap();
+        assert_eq!(unlocked(&multi).uses_remaining, Some(3));
+        assert_eq!(check_redeemable(&multi, None, now()), Err(BenefitRejection::NotUnlocked));
+    }
+
+    #[test]
+    fn test_time_window_and_stock() {
+        let usage = BenefitUsage::TimeWindowed { starts_at: now() - Duration::hours(1), ends_at: now() + Duration::hours(2) };
+        let mut lounge = Benefit::new("VIP Lounge".to_string(), None, usage, Some(50)).unwrap();
+        let fan_benefit = unlocked(&lounge);
+        assert_eq!(fan_benefit.uses_remaining, None);
+
+        assert_eq!(check_redeemable(&lounge, Some(&fan_benefit), now()), Ok(()));
+        assert_eq!(check_redeemable(&lounge, Some(&fan_benefit), now() - Duration::hours(2)), Err(BenefitRejection::NotYetAvailable));
+        assert_eq!(check_redeemable(&lounge, Some(&fan_benefit), now() + Duration::hours(2)), Err(BenefitRejection::Expired));
+
+        lounge.remaining_quantity = Some(0);
+        assert_eq!(check_redeemable(&lounge, Some(&fan_benefit), now()), Err(BenefitRejection::SoldOut));
+        lounge.is_active = false;
+        assert_eq!(check_redeemable(&lounge, Some(&fan_benefit), now()), Err(BenefitRejection::Inactive));
+    }
+
+    #[test]
+    fn test_catalog_entries_are_validated() {
+        assert!(Benefit::new("  ".to_string(), None, BenefitUsage::SingleUse, None).is_err());
+        assert!(Benefit::new("Meet".to_string(), None, BenefitUsage::MultiUse { uses: 0 }, None).is_err());
+        assert!(Benefit::new("Meet".to_string(), None, BenefitUsage::SingleUse, Some(0)).is_err());
+        let inverted = BenefitUsage::TimeWindowed { starts_at: now(), ends_at: now() - Duration::minutes(1) };
+        assert!(Benefit::new("Meet".to_string(), None, inverted, None).is_err());
+
+        let usage = BenefitUsage::from_parts("multi_use", Some(2), None, None).unwrap();
+        assert_eq!(usage, BenefitUsage::MultiUse { uses: 2 });
+        assert!(BenefitUsage::from_parts("time_windowed", None, Some(now()), None).is_err());
+    }
+}
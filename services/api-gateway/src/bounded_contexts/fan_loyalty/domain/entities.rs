@@ -81,6 +81,12 @@ impl WristbandType {
             ],
         }
     }
+
+    /// Whether the minted NFT moves from platform custody to the fan's linked
+    /// wallet on activation. General admission stays in custody as a plain ticket.
+    pub fn transfers_on_activation(&self) -> bool {
+        !matches!(self, WristbandType::General)
+    }
 }
 
 // ============================================================================
@@ -345,6 +351,59 @@ pub struct QrCodeScanResult {
     pub scan_timestamp: DateTime<Utc>,
 }
 
+/// Verified contents of a signed wristband QR code
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QrCodeClaims {
+    pub wristband_id: WristbandId,
+    pub expires_at: DateTime<Utc>,
+    pub nonce: String,
+}
+
+/// Why a scanned QR code does not grant access
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QrCodeRejection {
+    Malformed,
+    InvalidSignature,
+    Expired,
+    /// Signed by us but never issued, or revoked
+    UnknownCode,
+    WristbandNotFound,
+    WristbandInactive,
+}
+
+impl QrCodeRejection {
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::Malformed => "Invalid QR code format",
+            Self::InvalidSignature => "Invalid QR code signature",
+            Self::Expired => "QR code has expired",
+            Self::UnknownCode => "QR code was not issued or has been revoked",
+            Self::WristbandNotFound => "Wristband not found",
+            Self::WristbandInactive => "Wristband is not active",
+        }
+    }
+}
+
+/// Outcome of a venue scan. A code scanned twice is `AlreadyUsed`, not `Invalid`,
+/// so staff can tell a replayed code from a forged one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum QrScanOutcome {
+    Valid { wristband: NftWristband, scanned_at: DateTime<Utc> },
+    AlreadyUsed { wristband_id: WristbandId, used_at: DateTime<Utc> },
+    Invalid { reason: QrCodeRejection },
+}
+
+/// Result of marking a QR code as used
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QrCodeUse {
+    /// First scan: the code is now spent
+    Accepted,
+    AlreadyUsed(DateTime<Utc>),
+    /// Not issued, or revoked
+    Unknown,
+}
+
 /// Wristband activation result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WristbandActivationResult {
@@ -2,6 +2,8 @@ pub mod aggregates;
 pub mod entities;
 pub mod events;
 pub mod services;
+pub mod nft_mint;
+pub mod benefits;
 
 pub use entities::*;
 pub use services::*;
@@ -0,0 +1,181 @@
+//! Solana NFT of a wristband
+//!
+//! The wristband is minted to the platform custody wallet when it is created
+//! for a verified fan. Only a minted wristband can be activated; on activation
+//! the NFT optionally moves to the fan's linked wallet (see
+//! `WristbandType::transfers_on_activation`).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+use vibestream_types::{NFTAttribute, NFTMetadata, NftMintRequest};
+
+use crate::bounded_contexts::fan_loyalty::domain::entities::{NftWristband, WristbandId, WristbandType};
+
+pub const WRISTBAND_NFT_SYMBOL: &str = "VIBEBAND";
+// Las pulseras no generan royalties en reventa
+pub const WRISTBAND_NFT_SELLER_FEE_BASIS_POINTS: u16 = 0;
+// Límite de Metaplex para el nombre del token
+const MAX_NFT_NAME_LEN: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WristbandMintStatus {
+    Pending,
+    Minted,
+    Failed,
+}
+
+impl fmt::Display for WristbandMintStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WristbandMintStatus::Pending => write!(f, "pending"),
+            WristbandMintStatus::Minted => write!(f, "minted"),
+            WristbandMintStatus::Failed => write!(f, "failed"),
+        }
+    }
+}
+
+impl FromStr for WristbandMintStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(WristbandMintStatus::Pending),
+            "minted" => Ok(WristbandMintStatus::Minted),
+            "failed" => Ok(WristbandMintStatus::Failed),
+            other => Err(format!("Unknown wristband mint status: {}", other)),
+        }
+    }
+}
+
+/// Mint of a wristband NFT. `request_id` is the idempotency key sent to
+/// solana-integration, so retried messages never mint a second token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WristbandNftMint {
+    pub request_id: Uuid,
+    pub wristband_id: WristbandId,
+    pub custody_wallet: String,
+    /// Fan wallet the NFT moves to on activation, when the wristband type allows it
+    pub fan_wallet_address: Option<String>,
+    pub metadata: NFTMetadata,
+    pub status: WristbandMintStatus,
+    pub attempts: u32,
+    pub mint_address: Option<String>,
+    pub signature: Option<String>,
+    pub last_error: Option<String>,
+    pub minted_at: Option<DateTime<Utc>>,
+}
+
+impl WristbandNftMint {
+    /// New mint about to be sent for the first time
+    pub fn pending(
+        wristband: &NftWristband,
+        custody_wallet: String,
+        fan_wallet_address: Option<String>,
+        metadata: NFTMetadata,
+    ) -> Self {
+        Self {
+            request_id: Uuid::new_v4(),
+            wristband_id: wristband.id.clone(),
+            custody_wallet,
+            fan_wallet_address: fan_wallet_address.filter(|wallet| !wallet.trim().is_empty()),
+            metadata,
+            status: WristbandMintStatus::Pending,
+            attempts: 1,
+            mint_address: None,
+            signature: None,
+            last_error: None,
+            minted_at: None,
+        }
+    }
+
+    /// Minted and therefore ready to be activated
+    pub fn is_activatable(&self) -> bool {
+        self.status == WristbandMintStatus::Minted
+    }
+
+    pub fn to_request(&self, reply_queue: &str) -> NftMintRequest {
+        NftMintRequest {
+            request_id: self.request_id,
+            recipient_wallet: self.custody_wallet.clone(),
+            metadata: self.metadata.clone(),
+            reply_queue: reply_queue.to_string(),
+            attempt: self.attempts,
+        }
+    }
+}
+
+fn type_label(wristband_type: &WristbandType) -> &'static str {
+    match wristband_type {
+        WristbandType::General => "General",
+        WristbandType::VIP => "VIP",
+        WristbandType::Backstage => "Backstage",
+        WristbandType::MeetAndGreet => "Meet & Greet",
+    }
+}
+
+/// Metadata of a wristband NFT, derived from its type and event
+pub fn build_wristband_metadata(wristband: &NftWristband, metadata_base_url: Option<&str>) -> NFTMetadata {
+    let label = type_label(&wristband.wristband_type);
+    let name = format!("VibeStream {} Wristband", label);
+    let uri = match metadata_base_url {
+        Some(base) => format!("{}/{}.json", base.trim_end_matches('/'), wristband.id.0),
+        None => format!("ipfs://wristband-{}/{}", wristband.concert_id, wristband.id.0),
+    };
+
+    NFTMetadata {
+        name: name.chars().take(MAX_NFT_NAME_LEN).collect(),
+        symbol: WRISTBAND_NFT_SYMBOL.to_string(),
+        description: format!(
+            "{} wristband for concert {}. Benefits: {}",
+            label,
+            wristband.concert_id,
+            wristband.wristband_type.benefits().join(", ")
+        ),
+        uri,
+        attributes: vec![
+            NFTAttribute { trait_type: "Type".to_string(), value: label.to_string() },
+            NFTAttribute { trait_type: "Concert".to_string(), value: wristband.concert_id.clone() },
+            NFTAttribute { trait_type: "Artist".to_string(), value: wristband.artist_id.clone() },
+            NFTAttribute { trait_type: "Benefits".to_string(), value: wristband.wristband_type.benefits().len().to_string() },
+            NFTAttribute { trait_type: "Issued".to_string(), value: wristband.created_at.date_naive().to_string() },
+        ],
+        collection: None,
+        seller_fee_basis_points: WRISTBAND_NFT_SELLER_FEE_BASIS_POINTS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::fan_loyalty::domain::entities::FanId;
+
+    #[test]
+    fn test_metadata_comes_from_wristband_type_and_event() {
+        let wristband = NftWristband::new(FanId::new(), "concert_456".to_string(), "artist_789".to_string(), WristbandType::MeetAndGreet);
+
+        let metadata = build_wristband_metadata(&wristband, Some("https://metadata.vibestream.test/wristbands/"));
+
+        assert_eq!(metadata.name, "VibeStream Meet & Greet Wristband");
+        assert_eq!(metadata.symbol, WRISTBAND_NFT_SYMBOL);
+        assert_eq!(metadata.uri, format!("https://metadata.vibestream.test/wristbands/{}.json", wristband.id.0));
+        assert!(metadata.description.contains("concert_456"));
+        assert!(metadata.attributes.iter().any(|a| a.trait_type == "Type" && a.value == "Meet & Greet"));
+        assert!(metadata.attributes.iter().any(|a| a.trait_type == "Benefits" && a.value == "7"));
+    }
+
+    #[test]
+    fn test_pending_mint_goes_to_custody_and_ignores_blank_fan_wallet() {
+        let wristband = NftWristband::new(FanId::new(), "concert".to_string(), "artist".to_string(), WristbandType::VIP);
+        let mint = WristbandNftMint::pending(&wristband, "Custody111".to_string(), Some("  ".to_string()), build_wristband_metadata(&wristband, None));
+
+        let request = mint.to_request("replies");
+        assert_eq!(request.recipient_wallet, "Custody111");
+        assert_eq!(request.attempt, 1);
+        assert_eq!(mint.fan_wallet_address, None);
+        assert!(!mint.is_activatable());
+    }
+}
@@ -81,6 +81,9 @@ pub trait QrCodeRepository: Send + Sync {
 
     /// Invalidate QR code
     async fn invalidate_qr_code(&self, code: &str) -> Result<(), AppError>;
+
+    /// Spend a QR code on its first scan (atomic, so two scanners cannot both accept it)
+    async fn use_qr_code(&self, code: &str, scanned_at: DateTime<Utc>) -> Result<QrCodeUse, AppError>;
 }
 
 /// Repository trait for ZK proof operations
@@ -121,14 +124,79 @@ pub trait NftRepository: Send + Sync {
     async fn verify_nft_ownership(&self, wristband_id: &WristbandId, fan_wallet_address: &str) -> Result<bool, AppError>;
 }
 
+/// Repository trait for the Solana mint of each wristband
+#[async_trait]
+pub trait WristbandMintRepository: Send + Sync {
+    /// Store a pending mint. If the wristband already has a mint that has not
+    /// failed, that one is returned instead.
+    async fn reserve_mint(&self, mint: &WristbandNftMint) -> Result<WristbandNftMint, AppError>;
+
+    /// Get mint by its request ID
+    async fn find_mint(&self, request_id: Uuid) -> Result<Option<WristbandNftMint>, AppError>;
+
+    /// Get the current mint of a wristband
+    async fn find_mint_by_wristband(&self, wristband_id: &WristbandId) -> Result<Option<WristbandNftMint>, AppError>;
+
+    /// Count one more attempt; returns the new number of attempts
+    async fn record_retry(&self, request_id: Uuid, reason: &str) -> Result<u32, AppError>;
+
+    /// Pending -> minted. Returns false if the mint was no longer pending.
+    async fn mark_minted(&self, request_id: Uuid, mint_address: &str, signature: &str) -> Result<bool, AppError>;
+
+    /// Pending -> failed. Returns false if the mint was no longer pending.
+    async fn mark_failed(&self, request_id: Uuid, reason: &str) -> Result<bool, AppError>;
+
+    /// Record that the NFT was sent to the fan's wallet
+    async fn record_transfer(&self, request_id: Uuid, recipient_wallet: &str) -> Result<(), AppError>;
+}
+
+/// Repository trait for the benefits catalog and its redemptions
+#[async_trait]
+pub trait BenefitRepository: Send + Sync {
+    /// Add a catalog entry; names are unique
+    async fn create_benefit(&self, benefit: &Benefit) -> Result<(), AppError>;
+
+    /// Get catalog entry by ID
+    async fn find_benefit(&self, benefit_id: Uuid) -> Result<Option<Benefit>, AppError>;
+
+    /// List the catalog
+    async fn list_benefits(&self) -> Result<Vec<Benefit>, AppError>;
+
+    /// Unlock the active catalog entries with these names (case-insensitive)
+    /// for the fan. Benefits the fan already has are left as they are.
+    async fn unlock_benefits(
+        &self,
+        fan_id: &FanId,
+        verification_id: &str,
+        benefit_names: &[String],
+    ) -> Result<Vec<FanBenefit>, AppError>;
+
+    /// Benefits unlocked for a fan
+    async fn get_fan_benefits(&self, fan_id: &FanId) -> Result<Vec<FanBenefit>, AppError>;
+
+    /// Check eligibility, take one use from the fan and one unit from the
+    /// catalog, and record the redemption, all or nothing. Fails with
+    /// `AppError::BenefitNotRedeemable` when the benefit cannot be redeemed.
+    async fn redeem(
+        &self,
+        benefit_id: Uuid,
+        fan_id: &FanId,
+        venue_id: &str,
+        staff_id: Uuid,
+        metadata: serde_json::Value,
+    ) -> Result<BenefitRedemption, AppError>;
+}
+
 // ============================================================================
 // SUPPORTING TYPES
 // ============================================================================
 
 use crate::bounded_contexts::fan_loyalty::domain::entities::{
     FanId, WristbandId, WristbandType, NftWristband, FanVerificationResult,
-    ZkProof, NftMetadata, QrCode, ZkProofType
+    ZkProof, NftMetadata, QrCode, QrCodeUse, ZkProofType
 };
+use crate::bounded_contexts::fan_loyalty::domain::nft_mint::WristbandNftMint;
+use crate::bounded_contexts::fan_loyalty::domain::benefits::{Benefit, BenefitRedemption, FanBenefit};
 use crate::shared::domain::errors::AppError;
 
 // QrScanLog kept here if not in entities
@@ -63,6 +63,9 @@ pub trait QrCodeService: Send + Sync {
 
     /// Check QR code expiration
     async fn is_qr_code_expired(&self, qr_code: &str) -> Result<bool, String>;
+
+    /// Check signature and expiry; needs no storage, so it also works offline
+    fn verify_qr_code(&self, qr_code: &str, now: DateTime<Utc>) -> Result<QrCodeClaims, QrCodeRejection>;
 }
 
 /// Service trait for NFT operations
@@ -96,6 +99,9 @@ pub trait ZkProofService: Send + Sync {
     /// Generate ZK proof for wristband ownership
     async fn generate_wristband_proof(&self, wristband_id: &WristbandId, fan_id: &FanId) -> Result<ZkWristbandProof, String>;
 
+    /// Prove that the fan is at least `minimum_age` without revealing the birth year
+    async fn generate_age_proof(&self, fan_id: &FanId, birth_year: u32, minimum_age: u32) -> Result<ZkAgeProof, String>;
+
     /// Verify ZK proof
     async fn verify_zk_proof(&self, proof: &ZkProof) -> Result<bool, String>;
 
@@ -125,7 +131,7 @@ pub trait EventPublisher: Send + Sync {
 use crate::bounded_contexts::fan_loyalty::domain::entities::{
     FanId, WristbandId, WristbandType, NftWristband, FanVerificationResult, BiometricProofData,
     ZkProof, ZkProofType, ZkProofStatus, BiometricData, BehavioralPatterns, DeviceCharacteristics, LocationData,
-    QrCode, QrCodeValidation, QrCodeScanResult, NftCreationResult, NftMetadata, NftAttribute,
+    QrCode, QrCodeClaims, QrCodeRejection, QrCodeValidation, QrCodeScanResult, NftCreationResult, NftMetadata, NftAttribute,
     WristbandActivationResult,
 };
 
@@ -159,6 +165,20 @@ pub struct ZkWristbandProof {
     pub generated_at: DateTime<Utc>,
 }
 
+/// Minimum age to unlock explicit content
+pub const EXPLICIT_CONTENT_MINIMUM_AGE: u32 = 18;
+
+/// ZK age proof: only the birth year commitment is public, never the birth year
+#[derive(Debug, Clone)]
+pub struct ZkAgeProof {
+    pub proof_data: String,
+    pub public_inputs: Vec<String>,
+    pub fan_id: Uuid,
+    pub minimum_age: u32,
+    pub age_verified: bool,
+    pub generated_at: DateTime<Utc>,
+}
+
 // ============================================================================
 // DOMAIN EVENTS
 // ============================================================================
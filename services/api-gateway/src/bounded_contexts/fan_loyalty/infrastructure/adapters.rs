@@ -15,7 +15,7 @@ use crate::bounded_contexts::fan_loyalty::{
         services::{
             BiometricVerificationService, WristbandService, QrCodeService,
             NftService, ZkProofService, EventPublisher,
-            ZkWristbandProof, ZkBiometricProof,
+            ZkWristbandProof, ZkBiometricProof, ZkAgeProof,
         },
         entities::{
             FanId, WristbandId, WristbandType, NftWristband, FanVerificationResult,
@@ -463,6 +463,61 @@ impl ZkProofService for ExternalZkAdapter {
         })
     }
 
+    async fn generate_age_proof(&self, fan_id: &FanId, birth_year: u32, minimum_age: u32) -> Result<ZkAgeProof, String> {
+        // The birth year only travels to the ZK service, which keeps it out of the proof
+        let payload = serde_json::json!({
+            "proof_type": {
+                "AgeVerification": { "birth_year": birth_year, "minimum_age": minimum_age }
+            }
+        });
+
+        let response = reqwest::Client::new()
+            .post(format!("{}/generate", self.zk_service_url))
+            .timeout(std::time::Duration::from_secs(self.timeout_seconds))
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send request to ZK service: {}", e))?;
+
+        // 422: the statement is false, so no proof can exist
+        if response.status() == reqwest::StatusCode::UNPROCESSABLE_ENTITY {
+            return Ok(ZkAgeProof {
+                proof_data: String::new(),
+                public_inputs: vec![],
+                fan_id: fan_id.0,
+                minimum_age,
+                age_verified: false,
+                generated_at: Utc::now(),
+            });
+        }
+        if !response.status().is_success() {
+            return Err(format!("ZK service returned error: {}", response.status()));
+        }
+
+        let proof: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse ZK service response: {}", e))?;
+        let public_inputs: Vec<String> = proof["public_inputs"]
+            .as_array()
+            .unwrap_or(&vec![])
+            .iter()
+            .filter_map(|v| v.as_str())
+            .map(|s| s.to_string())
+            .collect();
+        // Circuit outputs come first: [ageVerified, birthYearHash, minimumAge, currentYear]
+        let age_verified = public_inputs.first().map(String::as_str) == Some("1");
+
+        Ok(ZkAgeProof {
+            proof_data: proof["proof"].as_str().unwrap_or("").to_string(),
+            public_inputs,
+            fan_id: fan_id.0,
+            minimum_age,
+            age_verified,
+            generated_at: Utc::now(),
+        })
+    }
+
     async fn verify_zk_proof(&self, proof: &ZkProof) -> Result<bool, String> {
         // Prepare payload for external ZK service
         let payload = serde_json::json!({
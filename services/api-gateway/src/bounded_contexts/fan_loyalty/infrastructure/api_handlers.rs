@@ -4,8 +4,8 @@
 
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
-    response::Json,
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    response::{IntoResponse, Json, Response},
     routing::{get, post},
     Router,
 };
@@ -13,9 +13,16 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
 use crate::bounded_contexts::fan_loyalty::application::dependency_injection::FanLoyaltyContainer;
-use crate::bounded_contexts::fan_loyalty::domain::entities::{FanId, WristbandId, WristbandType, BiometricData, BehavioralPatterns, DeviceCharacteristics};
+use crate::bounded_contexts::fan_loyalty::domain::entities::{FanId, WristbandId, WristbandType, BiometricData, BehavioralPatterns, DeviceCharacteristics, QrCodeRejection, QrScanOutcome};
+use crate::bounded_contexts::fan_loyalty::infrastructure::qr_service::render_qr_png;
 use crate::bounded_contexts::fan_loyalty::application::commands::{VerifyFanCommand, CreateWristbandCommand, ActivateWristbandCommand, GenerateQrCodeCommand, ValidateQrCodeCommand};
 use crate::bounded_contexts::fan_loyalty::application::handlers::{FanVerificationHandler, WristbandHandler};
+use crate::bounded_contexts::fan_loyalty::application::benefit_redemption::BenefitRedemptionService;
+use crate::bounded_contexts::fan_loyalty::domain::benefits::{Benefit, BenefitRedemption, BenefitUsage, FanBenefit};
+use crate::bounded_contexts::user::domain::UserRole;
+use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::auth::authorization::Principal;
+use crate::shared::infrastructure::auth::AuthenticatedUser;
 
 // ============================================================================
 // REQUEST/RESPONSE DTOs
@@ -96,23 +103,19 @@ pub struct GetWristbandResponse {
     pub wristband_type: String,
     pub is_active: bool,
     pub created_at: String,
-}
-
-#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
-pub struct GenerateQrCodeResponse {
-    pub qr_code: String,
-    pub wristband_id: String,
-    pub expires_at: Option<String>,
-}
-
-#[derive(Debug, Deserialize, Serialize, utoipa::ToSchema)]
-pub struct ValidateQrCodeRequest {
-    pub qr_code: String,
+    /// "pending", "minted" or "failed"; absent until the NFT is requested
+    pub nft_mint_status: Option<String>,
+    pub nft_mint_address: Option<String>,
+    pub nft_mint_error: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct ValidateQrCodeResponse {
     pub is_valid: bool,
+    /// "valid", "already_used" or "invalid"
+    pub status: String,
+    pub reason: Option<String>,
+    pub used_at: Option<String>,
     pub wristband_id: String,
     pub fan_id: String,
     pub concert_id: String,
@@ -121,6 +124,23 @@ pub struct ValidateQrCodeResponse {
     pub benefits: Vec<String>,
 }
 
+impl ValidateQrCodeResponse {
+    fn rejected() -> Self {
+        Self {
+            is_valid: false,
+            status: "invalid".to_string(),
+            reason: None,
+            used_at: None,
+            wristband_id: String::new(),
+            fan_id: String::new(),
+            concert_id: String::new(),
+            artist_id: String::new(),
+            wristband_type: String::new(),
+            benefits: vec![],
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct GetFanVerificationResponse {
     pub is_verified: bool,
@@ -138,6 +158,105 @@ pub struct HealthCheckResponse {
     pub version: String,
 }
 
+#[derive(Debug, Deserialize, Serialize, utoipa::ToSchema)]
+pub struct CreateBenefitRequest {
+    pub name: String,
+    pub description: Option<String>,
+    /// "single_use", "multi_use" or "time_windowed"
+    pub usage_type: String,
+    /// Uses per fan, for multi-use benefits
+    pub uses_per_fan: Option<u32>,
+    pub starts_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub ends_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Total redemptions across all fans; unlimited when absent
+    pub total_quantity: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BenefitResponse {
+    pub benefit_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub usage_type: String,
+    pub uses_per_fan: Option<u32>,
+    pub starts_at: Option<String>,
+    pub ends_at: Option<String>,
+    pub total_quantity: Option<u32>,
+    pub remaining_quantity: Option<u32>,
+    pub is_active: bool,
+}
+
+impl From<Benefit> for BenefitResponse {
+    fn from(benefit: Benefit) -> Self {
+        let window = benefit.usage.window();
+        Self {
+            benefit_id: benefit.id.to_string(),
+            usage_type: benefit.usage.kind().to_string(),
+            uses_per_fan: benefit.usage.uses_per_fan(),
+            starts_at: window.map(|(starts_at, _)| starts_at.to_rfc3339()),
+            ends_at: window.map(|(_, ends_at)| ends_at.to_rfc3339()),
+            name: benefit.name,
+            description: benefit.description,
+            total_quantity: benefit.total_quantity,
+            remaining_quantity: benefit.remaining_quantity,
+            is_active: benefit.is_active,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FanBenefitResponse {
+    pub benefit_id: String,
+    pub verification_id: String,
+    /// Absent for time-windowed benefits
+    pub uses_remaining: Option<u32>,
+    pub unlocked_at: String,
+}
+
+impl From<FanBenefit> for FanBenefitResponse {
+    fn from(fan_benefit: FanBenefit) -> Self {
+        Self {
+            benefit_id: fan_benefit.benefit_id.to_string(),
+            verification_id: fan_benefit.verification_id,
+            uses_remaining: fan_benefit.uses_remaining,
+            unlocked_at: fan_benefit.unlocked_at.to_rfc3339(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, utoipa::ToSchema)]
+pub struct RedeemBenefitRequest {
+    pub fan_id: Uuid,
+    pub venue_id: String,
+    /// Anything the venue wants to keep with the redemption (gate, device...)
+    pub metadata: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RedeemBenefitResponse {
+    pub redemption_id: String,
+    pub benefit_id: String,
+    pub fan_id: String,
+    pub venue_id: String,
+    pub staff_id: String,
+    pub uses_remaining: Option<u32>,
+    pub redeemed_at: String,
+}
+
+impl From<BenefitRedemption> for RedeemBenefitResponse {
+    fn from(redemption: BenefitRedemption) -> Self {
+        Self {
+            redemption_id: redemption.id.to_string(),
+            benefit_id: redemption.benefit_id.to_string(),
+            fan_id: redemption.fan_id.0.to_string(),
+            venue_id: redemption.venue_id,
+            staff_id: redemption.staff_id.to_string(),
+            uses_remaining: redemption.uses_remaining,
+            redeemed_at: redemption.redeemed_at.to_rfc3339(),
+        }
+    }
+}
+
 // ============================================================================
 // API HANDLERS
 // ============================================================================
@@ -253,6 +372,7 @@ pub async fn create_wristband_handler(
     responses(
         (status = 200, description = "Wristband activated successfully", body = ActivateWristbandResponse),
         (status = 400, description = "Invalid request"),
+        (status = 409, description = "Wristband NFT is not minted yet or its mint failed"),
         (status = 500, description = "Internal server error")
     ),
     tag = "fan-loyalty"
@@ -262,19 +382,21 @@ pub async fn activate_wristband_handler(
     Path(wristband_id): Path<String>,
 ) -> Result<Json<ActivateWristbandResponse>, StatusCode> {
     let wristband_id = match Uuid::parse_str(&wristband_id) {
-        Ok(id) => WristbandId::new(id.to_string()),
+        Ok(id) => WristbandId(id),
         Err(_) => return Err(StatusCode::BAD_REQUEST),
     };
 
-    let command = ActivateWristbandCommand::new(wristband_id.clone());
+    let command = ActivateWristbandCommand::new(wristband_id.clone(), None);
     let handler = WristbandHandler::new(container.clone());
     
-    match handler.handle_activate_wristband(&command).await {
+    match handler.handle_activate_wristband(&command.wristband_id).await {
         Ok(_) => Ok(Json(ActivateWristbandResponse {
             success: true,
             wristband_id: wristband_id.to_string(),
             message: "Wristband activated successfully".to_string(),
         })),
+        // NFT todavía sin mintear o minteo fallido
+        Err(e) if e.starts_with("Domain rule violation") => Err(StatusCode::CONFLICT),
         Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
@@ -303,31 +425,42 @@ pub async fn get_wristband_handler(
         Err(_) => return Err(StatusCode::BAD_REQUEST),
     };
 
-    match container.wristband_repository.get_wristband(&wristband_id).await {
-        Ok(Some(wristband)) => Ok(Json(GetWristbandResponse {
-            wristband_id: wristband.id.to_string(),
-            fan_id: wristband.fan_id.to_string(),
-            concert_id: wristband.concert_id,
-            artist_id: wristband.artist_id,
-            wristband_type: format!("{:?}", wristband.wristband_type),
-            is_active: wristband.is_active,
-            created_at: wristband.created_at.to_rfc3339(),
-        })),
-        Ok(None) => Err(StatusCode::NOT_FOUND),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
-    }
+    let wristband = match container.wristband_repository.get_wristband(&wristband_id).await {
+        Ok(Some(wristband)) => wristband,
+        Ok(None) => return Err(StatusCode::NOT_FOUND),
+        Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+    let mint = match &container.wristband_minting {
+        Some(minting) => minting.find_mint(&wristband.id).await.map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        None => None,
+    };
+
+    Ok(Json(GetWristbandResponse {
+        wristband_id: wristband.id.to_string(),
+        fan_id: wristband.fan_id.to_string(),
+        concert_id: wristband.concert_id,
+        artist_id: wristband.artist_id,
+        wristband_type: format!("{:?}", wristband.wristband_type),
+        is_active: wristband.is_active,
+        created_at: wristband.created_at.to_rfc3339(),
+        nft_mint_status: mint.as_ref().map(|mint| mint.status.to_string()),
+        nft_mint_address: mint.as_ref().and_then(|mint| mint.mint_address.clone()),
+        nft_mint_error: mint.and_then(|mint| mint.last_error),
+    }))
 }
 
-/// Generate QR code for wristband
+/// Generate a signed, short-lived QR code for a wristband (PNG)
 #[utoipa::path(
     post,
-    path = "/api/v1/fan-loyalty/wristbands/{wristband_id}/qr-code",
+    path = "/api/v1/fan-loyalty/wristbands/{wristband_id}/qr",
     params(
         ("wristband_id" = String, Path, description = "Wristband ID")
     ),
     responses(
-        (status = 200, description = "QR code generated", body = GenerateQrCodeResponse),
+        (status = 200, description = "QR code image", content_type = "image/png", body = Vec<u8>),
         (status = 400, description = "Invalid request"),
+        (status = 404, description = "Wristband not found"),
+        (status = 409, description = "Wristband is not active"),
         (status = 500, description = "Internal server error")
     ),
     tag = "fan-loyalty"
@@ -335,30 +468,39 @@ pub async fn get_wristband_handler(
 pub async fn generate_qr_code_handler(
     State(container): State<Arc<FanLoyaltyContainer>>,
     Path(wristband_id): Path<String>,
-) -> Result<Json<GenerateQrCodeResponse>, StatusCode> {
+) -> Result<Response, StatusCode> {
     let wristband_id = match Uuid::parse_str(&wristband_id) {
-        Ok(id) => WristbandId::new(id.to_string()),
+        Ok(id) => WristbandId(id),
         Err(_) => return Err(StatusCode::BAD_REQUEST),
     };
 
-    let command = GenerateQrCodeCommand::new(wristband_id.clone());
+    let command = GenerateQrCodeCommand::new(wristband_id);
     let handler = WristbandHandler::new(container.clone());
-    
-    match handler.handle_generate_qr_code(&command).await {
-        Ok(qr_code) => Ok(Json(GenerateQrCodeResponse {
-            qr_code: qr_code.code,
-            wristband_id: wristband_id.to_string(),
-            expires_at: qr_code.expires_at.map(|dt| dt.to_rfc3339()),
-        })),
-        Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
+
+    let qr_code = match handler.handle_generate_qr_code(&command).await {
+        Ok(qr_code) => qr_code,
+        Err(e) if e == QrCodeRejection::WristbandNotFound.message() => return Err(StatusCode::NOT_FOUND),
+        Err(e) if e == QrCodeRejection::WristbandInactive.message() => return Err(StatusCode::CONFLICT),
+        Err(_) => return Err(StatusCode::INTERNAL_SERVER_ERROR),
+    };
+    let png = render_qr_png(&qr_code.code).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    let mut headers = HeaderMap::new();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("image/png"));
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    if let Some(value) = qr_code.expires_at.and_then(|dt| HeaderValue::from_str(&dt.to_rfc3339()).ok()) {
+        headers.insert("x-qr-expires-at", value);
     }
+    Ok((headers, png).into_response())
 }
 
-/// Validate QR code
+/// Validate a scanned QR code at the venue. Each code is accepted only once.
 #[utoipa::path(
-    post,
-    path = "/api/v1/fan-loyalty/validate-qr",
-    request_body = ValidateQrCodeRequest,
+    get,
+    path = "/api/v1/fan-loyalty/validate-qr/{code}",
+    params(
+        ("code" = String, Path, description = "Scanned QR code")
+    ),
     responses(
         (status = 200, description = "QR code validation result", body = ValidateQrCodeResponse),
         (status = 500, description = "Internal server error")
@@ -367,44 +509,35 @@ pub async fn generate_qr_code_handler(
 )]
 pub async fn validate_qr_code_handler(
     State(container): State<Arc<FanLoyaltyContainer>>,
-    Json(request): Json<ValidateQrCodeRequest>,
+    Path(code): Path<String>,
 ) -> Result<Json<ValidateQrCodeResponse>, StatusCode> {
-    let command = ValidateQrCodeCommand::new(request.qr_code, None);
+    let command = ValidateQrCodeCommand::new(code, None);
     let handler = WristbandHandler::new(container.clone());
-    
+
     match handler.handle_validate_qr_code(&command).await {
-        Ok(is_valid) => {
-            if is_valid {
-                // Get wristband details for valid QR code
-                match container.qr_code_repository.get_qr_code(&command.qr_code).await {
-                    Ok(Some(qr_code)) => {
-                        match container.wristband_repository.get_wristband(&qr_code.wristband_id).await {
-                            Ok(Some(wristband)) => Ok(Json(ValidateQrCodeResponse {
-                                is_valid: true,
-                                wristband_id: wristband.id.to_string(),
-                                fan_id: wristband.fan_id.to_string(),
-                                concert_id: wristband.concert_id,
-                                artist_id: wristband.artist_id,
-                                wristband_type: format!("{:?}", wristband.wristband_type),
-                                benefits: vec!["Concert Access".to_string()], // Mock benefits
-                            })),
-                            _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
-                        }
-                    },
-                    _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
-                }
-            } else {
-                Ok(Json(ValidateQrCodeResponse {
-                    is_valid: false,
-                    wristband_id: String::new(),
-                    fan_id: String::new(),
-                    concert_id: String::new(),
-                    artist_id: String::new(),
-                    wristband_type: String::new(),
-                    benefits: vec![],
-                }))
-            }
-        },
+        Ok(QrScanOutcome::Valid { wristband, .. }) => Ok(Json(ValidateQrCodeResponse {
+            is_valid: true,
+            status: "valid".to_string(),
+            reason: None,
+            used_at: None,
+            wristband_id: wristband.id.to_string(),
+            fan_id: wristband.fan_id.to_string(),
+            concert_id: wristband.concert_id,
+            artist_id: wristband.artist_id,
+            wristband_type: format!("{:?}", wristband.wristband_type),
+            benefits: wristband.wristband_type.benefits(),
+        })),
+        Ok(QrScanOutcome::AlreadyUsed { wristband_id, used_at }) => Ok(Json(ValidateQrCodeResponse {
+            status: "already_used".to_string(),
+            reason: Some("QR code has already been used".to_string()),
+            used_at: Some(used_at.to_rfc3339()),
+            wristband_id: wristband_id.to_string(),
+            ..ValidateQrCodeResponse::rejected()
+        })),
+        Ok(QrScanOutcome::Invalid { reason }) => Ok(Json(ValidateQrCodeResponse {
+            reason: Some(reason.message().to_string()),
+            ..ValidateQrCodeResponse::rejected()
+        })),
         Err(_) => Err(StatusCode::INTERNAL_SERVER_ERROR),
     }
 }
@@ -442,6 +575,125 @@ pub async fn get_fan_verification_handler(
     }
 }
 
+fn benefits_service(container: &FanLoyaltyContainer) -> Result<&Arc<BenefitRedemptionService>, AppError> {
+    container.benefits.as_ref()
+        .ok_or_else(|| AppError::ConfigurationError("Fan loyalty benefits are not configured".to_string()))
+}
+
+// Los canjes los hace el personal del recinto
+fn is_venue_staff(user: &AuthenticatedUser) -> bool {
+    matches!(user.role(), Some(UserRole::Admin) | Some(UserRole::Moderator))
+}
+
+/// Add a benefit to the catalog (admins only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/fan-loyalty/benefits",
+    request_body = CreateBenefitRequest,
+    responses(
+        (status = 200, description = "Benefit created", body = BenefitResponse),
+        (status = 400, description = "Invalid benefit"),
+        (status = 403, description = "Only admins can manage the catalog"),
+        (status = 409, description = "A benefit with this name already exists")
+    ),
+    tag = "fan-loyalty"
+)]
+pub async fn create_benefit_handler(
+    State(container): State<Arc<FanLoyaltyContainer>>,
+    user: AuthenticatedUser,
+    Json(request): Json<CreateBenefitRequest>,
+) -> Result<Json<BenefitResponse>, AppError> {
+    if !user.is_admin() {
+        return Err(AppError::Forbidden("Only admins can manage fan benefits".to_string()));
+    }
+    let usage = BenefitUsage::from_parts(&request.usage_type, request.uses_per_fan, request.starts_at, request.ends_at)
+        .map_err(|_| AppError::ValidationError(format!(
+            "Invalid usage '{}': multi_use needs uses_per_fan and time_windowed needs starts_at and ends_at",
+            request.usage_type
+        )))?;
+
+    let benefit = benefits_service(&container)?
+        .create_benefit(request.name, request.description, usage, request.total_quantity)
+        .await?;
+    Ok(Json(benefit.into()))
+}
+
+/// List the benefits catalog
+#[utoipa::path(
+    get,
+    path = "/api/v1/fan-loyalty/benefits",
+    responses(
+        (status = 200, description = "Benefits catalog", body = [BenefitResponse])
+    ),
+    tag = "fan-loyalty"
+)]
+pub async fn list_benefits_handler(
+    State(container): State<Arc<FanLoyaltyContainer>>,
+    _user: AuthenticatedUser,
+) -> Result<Json<Vec<BenefitResponse>>, AppError> {
+    let benefits = benefits_service(&container)?.list_benefits().await?;
+    Ok(Json(benefits.into_iter().map(BenefitResponse::from).collect()))
+}
+
+/// Redeem a fan's benefit at the venue (venue staff only)
+#[utoipa::path(
+    post,
+    path = "/api/v1/fan-loyalty/benefits/{benefit_id}/redeem",
+    params(
+        ("benefit_id" = Uuid, Path, description = "Benefit ID")
+    ),
+    request_body = RedeemBenefitRequest,
+    responses(
+        (status = 200, description = "Benefit redeemed", body = RedeemBenefitResponse),
+        (status = 403, description = "Not venue staff, or the fan has not unlocked the benefit (BENEFIT_NOT_UNLOCKED)"),
+        (status = 404, description = "Benefit not found"),
+        (status = 409, description = "Already redeemed, sold out, inactive or not yet available"),
+        (status = 410, description = "The benefit window has ended (BENEFIT_EXPIRED)")
+    ),
+    tag = "fan-loyalty"
+)]
+pub async fn redeem_benefit_handler(
+    State(container): State<Arc<FanLoyaltyContainer>>,
+    user: AuthenticatedUser,
+    Path(benefit_id): Path<Uuid>,
+    Json(request): Json<RedeemBenefitRequest>,
+) -> Result<Json<RedeemBenefitResponse>, AppError> {
+    if !is_venue_staff(&user) {
+        return Err(AppError::Forbidden("Only venue staff can redeem benefits".to_string()));
+    }
+
+    let redemption = benefits_service(&container)?
+        .redeem(benefit_id, &FanId(request.fan_id), &request.venue_id, user.user_id, request.metadata)
+        .await?;
+    Ok(Json(redemption.into()))
+}
+
+/// Benefits unlocked for a fan (the fan themselves or venue staff)
+#[utoipa::path(
+    get,
+    path = "/api/v1/fan-loyalty/fans/{fan_id}/benefits",
+    params(
+        ("fan_id" = Uuid, Path, description = "Fan ID")
+    ),
+    responses(
+        (status = 200, description = "Unlocked benefits", body = [FanBenefitResponse]),
+        (status = 403, description = "Not allowed to see this fan's benefits")
+    ),
+    tag = "fan-loyalty"
+)]
+pub async fn get_fan_benefits_handler(
+    State(container): State<Arc<FanLoyaltyContainer>>,
+    user: AuthenticatedUser,
+    Path(fan_id): Path<Uuid>,
+) -> Result<Json<Vec<FanBenefitResponse>>, AppError> {
+    if user.user_id != fan_id && !is_venue_staff(&user) {
+        return Err(AppError::Forbidden("Not allowed to see this fan's benefits".to_string()));
+    }
+
+    let benefits = benefits_service(&container)?.fan_benefits(&FanId(fan_id)).await?;
+    Ok(Json(benefits.into_iter().map(FanBenefitResponse::from).collect()))
+}
+
 /// Health check
 pub async fn health_check_handler() -> Json<HealthCheckResponse> {
     Json(HealthCheckResponse {
@@ -463,9 +715,12 @@ pub fn create_fan_loyalty_router(container: Arc<FanLoyaltyContainer>) -> Router
         .route("/wristbands", post(create_wristband_handler))
         .route("/wristbands/:wristband_id", get(get_wristband_handler))
         .route("/wristbands/:wristband_id/activate", post(activate_wristband_handler))
-        .route("/wristbands/:wristband_id/qr-code", post(generate_qr_code_handler))
-        .route("/validate-qr", post(validate_qr_code_handler))
+        .route("/wristbands/:wristband_id/qr", post(generate_qr_code_handler))
+        .route("/validate-qr/:code", get(validate_qr_code_handler))
         .route("/verify/:fan_id", get(get_fan_verification_handler))
+        .route("/benefits", post(create_benefit_handler).get(list_benefits_handler))
+        .route("/benefits/:benefit_id/redeem", post(redeem_benefit_handler))
+        .route("/fans/:fan_id/benefits", get(get_fan_benefits_handler))
         .route("/health", get(health_check_handler))
         .with_state(container)
 }
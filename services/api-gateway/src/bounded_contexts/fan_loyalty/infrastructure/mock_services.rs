@@ -135,6 +135,11 @@ impl QrCodeRepository for MockQrCodeRepository {
         Ok(())
     }
 
+    async fn use_qr_code(&self, code: &str, _scanned_at: chrono::DateTime<chrono::Utc>) -> Result<crate::bounded_contexts::fan_loyalty::domain::entities::QrCodeUse, AppError> {
+        println!("Mock: Using QR code: {}", code);
+        Ok(crate::bounded_contexts::fan_loyalty::domain::entities::QrCodeUse::Unknown)
+    }
+
     async fn validate_qr_code(&self, _qr_code: &str) -> Result<bool, AppError> {
         Ok(false)
     }
@@ -388,6 +393,19 @@ impl QrCodeService for MockQrCodeService {
     async fn is_qr_code_expired(&self, qr_code: &str) -> Result<bool, String> {
         Ok(false)
     }
+
+    fn verify_qr_code(&self, qr_code: &str, now: chrono::DateTime<Utc>) -> Result<crate::bounded_contexts::fan_loyalty::domain::entities::QrCodeClaims, crate::bounded_contexts::fan_loyalty::domain::entities::QrCodeRejection> {
+        // Mock codes are `QR_<wristband_id>` (see `QrCode::new`)
+        let wristband_id = qr_code
+            .strip_prefix("QR_")
+            .and_then(|id| WristbandId::from_string(id).ok())
+            .ok_or(crate::bounded_contexts::fan_loyalty::domain::entities::QrCodeRejection::Malformed)?;
+        Ok(crate::bounded_contexts::fan_loyalty::domain::entities::QrCodeClaims {
+            wristband_id,
+            expires_at: now + chrono::Duration::hours(24),
+            nonce: String::new(),
+        })
+    }
 }
 
 pub struct MockNftService {
@@ -491,6 +509,19 @@ impl ZkProofService for MockZkProofService {
         })
     }
 
+    async fn generate_age_proof(&self, fan_id: &FanId, birth_year: u32, minimum_age: u32) -> Result<crate::bounded_contexts::fan_loyalty::domain::services::ZkAgeProof, String> {
+        use chrono::Datelike;
+        let age = (Utc::now().year() as u32).saturating_sub(birth_year);
+        Ok(crate::bounded_contexts::fan_loyalty::domain::services::ZkAgeProof {
+            proof_data: "mock_proof".to_string(),
+            public_inputs: vec![],
+            fan_id: fan_id.0,
+            minimum_age,
+            age_verified: age >= minimum_age,
+            generated_at: Utc::now(),
+        })
+    }
+
     async fn get_proof_status(&self, proof_id: &uuid::Uuid) -> Result<Option<crate::bounded_contexts::fan_loyalty::domain::entities::ZkProofStatus>, String> {
         Ok(None)
     }
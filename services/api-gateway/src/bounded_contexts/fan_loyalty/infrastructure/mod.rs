@@ -1,4 +1,5 @@
 pub mod nft_service;
+pub mod nft_mint_queue;
 pub mod qr_service;
 pub mod rest_handlers;
 pub mod zk_integration;
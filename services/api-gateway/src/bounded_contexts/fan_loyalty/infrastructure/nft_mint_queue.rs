@@ -0,0 +1,99 @@
+//! Colas Redis hacia solana-integration para el NFT de las pulseras
+
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use vibestream_types::{NftMintRequest, NftMintResult, NftTransferRequest, QueueNames};
+
+use crate::bounded_contexts::fan_loyalty::application::wristband_minting::{WristbandMintQueue, WristbandMintService};
+use crate::services::MessageQueue;
+use crate::shared::domain::errors::AppError;
+
+pub struct RedisWristbandMintQueue {
+    message_queue: MessageQueue,
+    mint_queue: String,
+    transfer_queue: String,
+}
+
+impl RedisWristbandMintQueue {
+    pub fn new(message_queue: MessageQueue) -> Self {
+        Self {
+            message_queue,
+            mint_queue: QueueNames::SOLANA_NFT_MINT.to_string(),
+            transfer_queue: QueueNames::SOLANA_NFT_TRANSFER.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl WristbandMintQueue for RedisWristbandMintQueue {
+    async fn publish_mint(&self, request: &NftMintRequest) -> Result<(), AppError> {
+        let message = serde_json::to_string(request)
+            .map_err(|e| AppError::SerializationError(e.to_string()))?;
+        self.message_queue
+            .send_message(&self.mint_queue, &message)
+            .await
+            .map_err(|e| AppError::ExternalServiceError(format!("Failed to enqueue wristband NFT mint: {}", e)))
+    }
+
+    async fn publish_transfer(&self, request: &NftTransferRequest) -> Result<(), AppError> {
+        let message = serde_json::to_string(request)
+            .map_err(|e| AppError::SerializationError(e.to_string()))?;
+        self.message_queue
+            .send_message(&self.transfer_queue, &message)
+            .await
+            .map_err(|e| AppError::ExternalServiceError(format!("Failed to enqueue wristband NFT transfer: {}", e)))
+    }
+}
+
+/// Consume los resultados de minteo de pulseras que publica solana-integration
+pub struct WristbandNftMintResultWorker {
+    service: Arc<WristbandMintService>,
+    message_queue: MessageQueue,
+    running: Arc<AtomicBool>,
+}
+
+impl WristbandNftMintResultWorker {
+    pub fn new(service: Arc<WristbandMintService>, message_queue: MessageQueue) -> Self {
+        Self {
+            service,
+            message_queue,
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub async fn start(&self) -> Result<(), AppError> {
+        self.running.store(true, Ordering::Relaxed);
+        let queue_name = self.service.reply_queue().to_string();
+
+        tracing::info!("Starting wristband NFT mint result worker on {}", queue_name);
+
+        while self.running.load(Ordering::Relaxed) {
+            match self.message_queue.receive_message(&queue_name, 5).await {
+                Ok(Some(message_json)) => match serde_json::from_str::<NftMintResult>(&message_json) {
+                    Ok(result) => {
+                        let request_id = result.request_id;
+                        if let Err(e) = self.service.handle_result(result).await {
+                            tracing::error!("Failed to apply wristband NFT mint result {}: {}", request_id, e);
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to deserialize wristband NFT mint result: {}", e);
+                    }
+                },
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::error!("Error receiving message from queue {}: {}", queue_name, e);
+                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                }
+            }
+        }
+
+        tracing::info!("Wristband NFT mint result worker stopped");
+        Ok(())
+    }
+
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
@@ -8,13 +8,17 @@ use sqlx::{PgPool, Row};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use crate::bounded_contexts::fan_loyalty::domain::entities::{
-    FanId, WristbandId, FanVerificationResult, NftWristband, QrCode, NftMetadata
+    FanId, WristbandId, FanVerificationResult, NftWristband, QrCode, QrCodeUse, NftMetadata
 };
 use crate::bounded_contexts::fan_loyalty::domain::repositories::{
     FanVerificationRepository, WristbandRepository, QrCodeRepository, 
-    ZkProofRepository, NftRepository
+    ZkProofRepository, NftRepository, WristbandMintRepository, BenefitRepository
 };
-use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::fan_loyalty::domain::nft_mint::WristbandNftMint;
+use crate::bounded_contexts::fan_loyalty::domain::benefits::{
+    check_redeemable, Benefit, BenefitRedemption, BenefitUsage, FanBenefit
+};
+use crate::shared::domain::errors::{AppError, BenefitRejection};
 
 // ============================================================================
 // POSTGRES FAN VERIFICATION REPOSITORY
@@ -290,6 +294,37 @@ impl QrCodeRepository for PostgresQrCodeRepository {
         
         Ok(())
     }
+
+    async fn use_qr_code(&self, code: &str, scanned_at: DateTime<Utc>) -> Result<QrCodeUse, AppError> {
+        let accepted = sqlx::query(
+            r#"
+            UPDATE qr_codes
+            SET used_at = $2, updated_at = NOW()
+            WHERE code = $1 AND is_valid = TRUE AND used_at IS NULL
+            "#,
+        )
+        .bind(code)
+        .bind(scanned_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to use QR code: {}", e)))?;
+        if accepted.rows_affected() == 1 {
+            return Ok(QrCodeUse::Accepted);
+        }
+
+        let used_at: Option<Option<DateTime<Utc>>> = sqlx::query_scalar(
+            "SELECT used_at FROM qr_codes WHERE code = $1 AND is_valid = TRUE",
+        )
+        .bind(code)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to get QR code: {}", e)))?;
+
+        Ok(match used_at.flatten() {
+            Some(used_at) => QrCodeUse::AlreadyUsed(used_at),
+            None => QrCodeUse::Unknown,
+        })
+    }
     async fn validate_qr_code(&self, _qr_code: &str) -> Result<bool, AppError> {
         Ok(false)
     }
@@ -449,3 +484,411 @@ impl NftRepository for PostgresNftRepository {
         Ok(count > 0)
     }
 }
+
+// ============================================================================
+// POSTGRES WRISTBAND MINT REPOSITORY
+// ============================================================================
+
+// La dirección del mint y la firma se guardan en nft_token_id / transaction_hash
+const MINT_COLUMNS: &str = r#"id, nft_mint_request_id, nft_custody_wallet, fan_wallet_address, nft_metadata, nft_mint_status,
+       nft_mint_attempts, nft_token_id, transaction_hash, nft_mint_error, nft_minted_at"#;
+
+pub struct PostgresWristbandMintRepository {
+    pool: PgPool,
+}
+
+impl PostgresWristbandMintRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_mint(row: sqlx::postgres::PgRow) -> Result<WristbandNftMint, AppError> {
+        let metadata = serde_json::from_value(row.get("nft_metadata"))
+            .map_err(|e| AppError::SerializationError(format!("Invalid wristband NFT metadata: {}", e)))?;
+
+        Ok(WristbandNftMint {
+            request_id: row.get("nft_mint_request_id"),
+            wristband_id: WristbandId(row.get("id")),
+            custody_wallet: row.get("nft_custody_wallet"),
+            fan_wallet_address: row.get("fan_wallet_address"),
+            metadata,
+            status: row.get::<String, _>("nft_mint_status").parse().map_err(AppError::SerializationError)?,
+            attempts: row.get::<i32, _>("nft_mint_attempts") as u32,
+            mint_address: row.get("nft_token_id"),
+            signature: row.get("transaction_hash"),
+            last_error: row.get("nft_mint_error"),
+            minted_at: row.get("nft_minted_at"),
+        })
+    }
+}
+
+#[async_trait]
+impl WristbandMintRepository for PostgresWristbandMintRepository {
+    async fn reserve_mint(&self, mint: &WristbandNftMint) -> Result<WristbandNftMint, AppError> {
+        let metadata = serde_json::to_value(&mint.metadata)
+            .map_err(|e| AppError::SerializationError(e.to_string()))?;
+
+        // Solo se reemplaza un minteo inexistente o fallido
+        let reserved = sqlx::query(&format!(
+            r#"
+            UPDATE nft_wristbands
+            SET nft_mint_request_id = $2, nft_mint_status = $3, nft_custody_wallet = $4, fan_wallet_address = $5,
+                nft_metadata = $6, nft_mint_attempts = $7, nft_token_id = NULL, transaction_hash = NULL,
+                nft_mint_error = NULL, nft_minted_at = NULL, blockchain_network = 'solana', updated_at = NOW()
+            WHERE id = $1 AND (nft_mint_status IS NULL OR nft_mint_status = 'failed')
+            RETURNING {}
+            "#,
+            MINT_COLUMNS
+        ))
+        .bind(&mint.wristband_id.0)
+        .bind(mint.request_id)
+        .bind(mint.status.to_string())
+        .bind(&mint.custody_wallet)
+        .bind(&mint.fan_wallet_address)
+        .bind(metadata)
+        .bind(mint.attempts as i32)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to reserve wristband NFT mint: {}", e)))?;
+        if let Some(row) = reserved {
+            return Self::row_to_mint(row);
+        }
+
+        // Ya tiene un NFT en curso o minteado: idempotente
+        self.find_mint_by_wristband(&mint.wristband_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Wristband {} not found", mint.wristband_id.0)))
+    }
+
+    async fn find_mint(&self, request_id: Uuid) -> Result<Option<WristbandNftMint>, AppError> {
+        let row = sqlx::query(&format!("SELECT {} FROM nft_wristbands WHERE nft_mint_request_id = $1", MINT_COLUMNS))
+            .bind(request_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to get wristband NFT mint: {}", e)))?;
+
+        row.map(Self::row_to_mint).transpose()
+    }
+
+    async fn find_mint_by_wristband(&self, wristband_id: &WristbandId) -> Result<Option<WristbandNftMint>, AppError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM nft_wristbands WHERE id = $1 AND nft_mint_request_id IS NOT NULL",
+            MINT_COLUMNS
+        ))
+        .bind(&wristband_id.0)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to get wristband NFT mint: {}", e)))?;
+
+        row.map(Self::row_to_mint).transpose()
+    }
+
+    async fn record_retry(&self, request_id: Uuid, reason: &str) -> Result<u32, AppError> {
+        let attempts: i32 = sqlx::query_scalar(
+            r#"
+            UPDATE nft_wristbands
+            SET nft_mint_attempts = nft_mint_attempts + 1, nft_mint_error = $2, updated_at = NOW()
+            WHERE nft_mint_request_id = $1 AND nft_mint_status = 'pending'
+            RETURNING nft_mint_attempts
+            "#,
+        )
+        .bind(request_id)
+        .bind(reason)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to record wristband NFT mint retry: {}", e)))?
+        .ok_or_else(|| AppError::ConcurrencyConflict(format!("Wristband NFT mint {} is no longer pending", request_id)))?;
+
+        Ok(attempts as u32)
+    }
+
+    async fn mark_minted(&self, request_id: Uuid, mint_address: &str, signature: &str) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE nft_wristbands
+            SET nft_mint_status = 'minted', nft_token_id = $2, transaction_hash = $3,
+                nft_mint_error = NULL, nft_minted_at = NOW(), updated_at = NOW()
+            WHERE nft_mint_request_id = $1 AND nft_mint_status = 'pending'
+            "#,
+        )
+        .bind(request_id)
+        .bind(mint_address)
+        .bind(signature)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to mark wristband NFT minted: {}", e)))?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn mark_failed(&self, request_id: Uuid, reason: &str) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE nft_wristbands
+            SET nft_mint_status = 'failed', nft_mint_error = $2, updated_at = NOW()
+            WHERE nft_mint_request_id = $1 AND nft_mint_status = 'pending'
+            "#,
+        )
+        .bind(request_id)
+        .bind(reason)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to mark wristband NFT mint failed: {}", e)))?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn record_transfer(&self, request_id: Uuid, recipient_wallet: &str) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE nft_wristbands
+            SET nft_transferred_to = $2, nft_transfer_requested_at = NOW(), updated_at = NOW()
+            WHERE nft_mint_request_id = $1
+            "#,
+        )
+        .bind(request_id)
+        .bind(recipient_wallet)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to record wristband NFT transfer: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// POSTGRES BENEFIT REPOSITORY
+// ============================================================================
+
+const BENEFIT_COLUMNS: &str = r#"id, name, description, usage_type, uses_per_fan, starts_at, ends_at,
+       total_quantity, remaining_quantity, is_active, created_at"#;
+
+const FAN_BENEFIT_COLUMNS: &str = "id, fan_id, benefit_id, verification_id, uses_remaining, unlocked_at";
+
+pub struct PostgresBenefitRepository {
+    pool: PgPool,
+}
+
+impl PostgresBenefitRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_benefit(row: sqlx::postgres::PgRow) -> Result<Benefit, AppError> {
+        let usage = BenefitUsage::from_parts(
+            row.get("usage_type"),
+            row.get::<Option<i32>, _>("uses_per_fan").map(|uses| uses as u32),
+            row.get("starts_at"),
+            row.get("ends_at"),
+        )?;
+
+        Ok(Benefit {
+            id: row.get("id"),
+            name: row.get("name"),
+            description: row.get("description"),
+            usage,
+            total_quantity: row.get::<Option<i32>, _>("total_quantity").map(|quantity| quantity as u32),
+            remaining_quantity: row.get::<Option<i32>, _>("remaining_quantity").map(|quantity| quantity as u32),
+            is_active: row.get("is_active"),
+            created_at: row.get::<Option<DateTime<Utc>>, _>("created_at").unwrap_or_else(Utc::now),
+        })
+    }
+
+    fn row_to_fan_benefit(row: sqlx::postgres::PgRow) -> FanBenefit {
+        FanBenefit {
+            id: row.get("id"),
+            fan_id: FanId(row.get("fan_id")),
+            benefit_id: row.get("benefit_id"),
+            verification_id: row.get("verification_id"),
+            uses_remaining: row.get::<Option<i32>, _>("uses_remaining").map(|uses| uses as u32),
+            unlocked_at: row.get::<Option<DateTime<Utc>>, _>("unlocked_at").unwrap_or_else(Utc::now),
+        }
+    }
+}
+
+#[async_trait]
+impl BenefitRepository for PostgresBenefitRepository {
+    async fn create_benefit(&self, benefit: &Benefit) -> Result<(), AppError> {
+        let window = benefit.usage.window();
+        sqlx::query(
+            r#"
+            INSERT INTO fan_benefit_catalog (
+                id, name, description, usage_type, uses_per_fan, starts_at, ends_at,
+                total_quantity, remaining_quantity, is_active, created_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+        )
+        .bind(benefit.id)
+        .bind(&benefit.name)
+        .bind(&benefit.description)
+        .bind(benefit.usage.kind())
+        .bind(benefit.usage.uses_per_fan().map(|uses| uses as i32))
+        .bind(window.map(|(starts_at, _)| starts_at))
+        .bind(window.map(|(_, ends_at)| ends_at))
+        .bind(benefit.total_quantity.map(|quantity| quantity as i32))
+        .bind(benefit.remaining_quantity.map(|quantity| quantity as i32))
+        .bind(benefit.is_active)
+        .bind(benefit.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                AppError::ConflictError(format!("Benefit '{}' already exists", benefit.name))
+            }
+            e => AppError::DatabaseError(format!("Failed to create benefit: {}", e)),
+        })?;
+
+        Ok(())
+    }
+
+    async fn find_benefit(&self, benefit_id: Uuid) -> Result<Option<Benefit>, AppError> {
+        let row = sqlx::query(&format!("SELECT {} FROM fan_benefit_catalog WHERE id = $1", BENEFIT_COLUMNS))
+            .bind(benefit_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to get benefit: {}", e)))?;
+
+        row.map(Self::row_to_benefit).transpose()
+    }
+
+    async fn list_benefits(&self) -> Result<Vec<Benefit>, AppError> {
+        let rows = sqlx::query(&format!("SELECT {} FROM fan_benefit_catalog ORDER BY name", BENEFIT_COLUMNS))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to list benefits: {}", e)))?;
+
+        rows.into_iter().map(Self::row_to_benefit).collect()
+    }
+
+    async fn unlock_benefits(
+        &self,
+        fan_id: &FanId,
+        verification_id: &str,
+        benefit_names: &[String],
+    ) -> Result<Vec<FanBenefit>, AppError> {
+        let names: Vec<String> = benefit_names.iter().map(|name| name.trim().to_lowercase()).collect();
+        let rows = sqlx::query(&format!(
+            r#"
+            INSERT INTO fan_benefits (fan_id, benefit_id, verification_id, uses_remaining)
+            SELECT $1, c.id, $2, c.uses_per_fan
+            FROM fan_benefit_catalog c
+            WHERE c.is_active AND LOWER(c.name) = ANY($3)
+            ON CONFLICT (fan_id, benefit_id) DO NOTHING
+            RETURNING {}
+            "#,
+            FAN_BENEFIT_COLUMNS
+        ))
+        .bind(&fan_id.0)
+        .bind(verification_id)
+        .bind(&names)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to unlock benefits: {}", e)))?;
+
+        Ok(rows.into_iter().map(Self::row_to_fan_benefit).collect())
+    }
+
+    async fn get_fan_benefits(&self, fan_id: &FanId) -> Result<Vec<FanBenefit>, AppError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM fan_benefits WHERE fan_id = $1 ORDER BY unlocked_at",
+            FAN_BENEFIT_COLUMNS
+        ))
+        .bind(&fan_id.0)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to get fan benefits: {}", e)))?;
+
+        Ok(rows.into_iter().map(Self::row_to_fan_benefit).collect())
+    }
+
+    async fn redeem(
+        &self,
+        benefit_id: Uuid,
+        fan_id: &FanId,
+        venue_id: &str,
+        staff_id: Uuid,
+        metadata: serde_json::Value,
+    ) -> Result<BenefitRedemption, AppError> {
+        let db_error = |e: sqlx::Error| AppError::DatabaseError(format!("Failed to redeem benefit: {}", e));
+        let benefit = self.find_benefit(benefit_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Benefit {} not found", benefit_id)))?;
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        // La fila del fan se bloquea: sus canjes van de uno en uno
+        let fan_benefit = sqlx::query(&format!(
+            "SELECT {} FROM fan_benefits WHERE fan_id = $1 AND benefit_id = $2 FOR UPDATE",
+            FAN_BENEFIT_COLUMNS
+        ))
+        .bind(&fan_id.0)
+        .bind(benefit_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .map(Self::row_to_fan_benefit);
+        check_redeemable(&benefit, fan_benefit.as_ref(), Utc::now()).map_err(AppError::BenefitNotRedeemable)?;
+        let fan_benefit = fan_benefit.ok_or(AppError::BenefitNotRedeemable(BenefitRejection::NotUnlocked))?;
+
+        // El stock se descuenta con una condición: si otro canje se llevó la
+        // última unidad entre la lectura y aquí, no se actualiza nada
+        let in_stock: Option<bool> = sqlx::query_scalar(
+            r#"
+            UPDATE fan_benefit_catalog
+            SET remaining_quantity = remaining_quantity - 1, updated_at = NOW()
+            WHERE id = $1 AND (remaining_quantity IS NULL OR remaining_quantity > 0)
+            RETURNING TRUE
+            "#,
+        )
+        .bind(benefit_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        if in_stock.is_none() {
+            return Err(AppError::BenefitNotRedeemable(BenefitRejection::SoldOut));
+        }
+
+        let uses_remaining: Option<i32> = sqlx::query_scalar(
+            r#"
+            UPDATE fan_benefits
+            SET uses_remaining = uses_remaining - 1
+            WHERE id = $1
+            RETURNING uses_remaining
+            "#,
+        )
+        .bind(fan_benefit.id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        let redemption = BenefitRedemption {
+            id: Uuid::new_v4(),
+            benefit_id,
+            fan_id: fan_id.clone(),
+            venue_id: venue_id.to_string(),
+            staff_id,
+            metadata,
+            uses_remaining: uses_remaining.map(|uses| uses as u32),
+            redeemed_at: Utc::now(),
+        };
+        sqlx::query(
+            r#"
+            INSERT INTO fan_benefit_redemptions (
+                id, fan_benefit_id, benefit_id, fan_id, venue_id, staff_id, metadata, redeemed_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(redemption.id)
+        .bind(fan_benefit.id)
+        .bind(benefit_id)
+        .bind(&fan_id.0)
+        .bind(&redemption.venue_id)
+        .bind(staff_id)
+        .bind(&redemption.metadata)
+        .bind(redemption.redeemed_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)?;
+        Ok(redemption)
+    }
+}
@@ -1,71 +1,217 @@
-use serde::{Deserialize, Serialize};
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use uuid::Uuid;
-use chrono::{DateTime, Utc, Duration};
-use sha2::{Sha256, Digest};
-use base64::{Engine as _, engine::general_purpose};
 
 use crate::bounded_contexts::fan_loyalty::domain::entities::{
-    WristbandId, FanId, QrCode, QrCodeValidation, QrCodeScanResult, LocationData
+    WristbandId, QrCode, QrCodeClaims, QrCodeRejection, QrCodeValidation, QrCodeScanResult, LocationData
 };
+use crate::bounded_contexts::fan_loyalty::domain::services::QrCodeService as QrCodeServiceTrait;
+use crate::shared::infrastructure::auth::get_jwt_secret;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Lifetime of a wristband QR code; the fan app asks for a new one before it expires
+pub const DEFAULT_QR_CODE_TTL_SECONDS: i64 = 300;
+/// Side of the rendered PNG, in pixels
+const QR_IMAGE_SIZE: u32 = 320;
+const CODE_PREFIX: &str = "VS";
+/// Bytes of the HMAC-SHA256 kept in the code (128 bits)
+const SIGNATURE_BYTES: usize = 16;
+
+/// Signs and verifies wristband QR codes.
+///
+/// A code is `VS<payload>.<signature>`, both base64url: the payload is
+/// `wristband_id:expires_at:nonce` and the signature its truncated HMAC.
+/// Verification only needs the secret and a clock, so a venue-local scanner
+/// can check codes while offline and keep its own record of used codes.
+#[derive(Clone)]
+pub struct QrCodeSigner {
+    secret: Vec<u8>,
+    ttl: Duration,
+}
+
+impl QrCodeSigner {
+    pub fn new(secret: &str, ttl: Duration) -> Self {
+        Self { secret: secret.as_bytes().to_vec(), ttl }
+    }
+
+    /// New code for the wristband, valid for the signer's TTL from `now`
+    pub fn issue(&self, wristband_id: &WristbandId, now: DateTime<Utc>) -> (String, QrCodeClaims) {
+        let claims = QrCodeClaims {
+            wristband_id: wristband_id.clone(),
+            expires_at: now + self.ttl,
+            nonce: hex::encode(rand::random::<[u8; 12]>()),
+        };
+        let payload = format!("{}:{}:{}", claims.wristband_id.0, claims.expires_at.timestamp(), claims.nonce);
+        let signature = self.mac(&payload).finalize().into_bytes();
+        let code = format!(
+            "{}{}.{}",
+            CODE_PREFIX,
+            URL_SAFE_NO_PAD.encode(payload.as_bytes()),
+            URL_SAFE_NO_PAD.encode(&signature[..SIGNATURE_BYTES]),
+        );
+        (code, claims)
+    }
+
+    /// Claims of a genuine, unexpired code. The signature is checked before
+    /// anything in the payload is trusted.
+    pub fn verify(&self, code: &str, now: DateTime<Utc>) -> Result<QrCodeClaims, QrCodeRejection> {
+        let (payload, signature) = code
+            .strip_prefix(CODE_PREFIX)
+            .and_then(|rest| rest.split_once('.'))
+            .ok_or(QrCodeRejection::Malformed)?;
+        let payload = URL_SAFE_NO_PAD.decode(payload).map_err(|_| QrCodeRejection::Malformed)?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| QrCodeRejection::Malformed)?;
+        if signature.len() != SIGNATURE_BYTES {
+            return Err(QrCodeRejection::Malformed);
+        }
+        let payload = String::from_utf8(payload).map_err(|_| QrCodeRejection::Malformed)?;
+        self.mac(&payload)
+            .verify_truncated_left(&signature)
+            .map_err(|_| QrCodeRejection::InvalidSignature)?;
+
+        let mut parts = payload.splitn(3, ':');
+        let (Some(wristband_id), Some(expires_at), Some(nonce)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(QrCodeRejection::Malformed);
+        };
+        let wristband_id = Uuid::parse_str(wristband_id).map_err(|_| QrCodeRejection::Malformed)?;
+        let expires_at = expires_at
+            .parse::<i64>()
+            .ok()
+            .and_then(|timestamp| Utc.timestamp_opt(timestamp, 0).single())
+            .ok_or(QrCodeRejection::Malformed)?;
+        if now >= expires_at {
+            return Err(QrCodeRejection::Expired);
+        }
+
+        Ok(QrCodeClaims {
+            wristband_id: WristbandId(wristband_id),
+            expires_at,
+            nonce: nonce.to_string(),
+        })
+    }
+
+    fn mac(&self, payload: &str) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+/// PNG image of the QR code
+pub fn render_qr_png(code: &str) -> Result<Vec<u8>, String> {
+    let qr = qrcode::QrCode::new(code.as_bytes()).map_err(|e| format!("Failed to encode QR code: {}", e))?;
+    let image = qr
+        .render::<image::Luma<u8>>()
+        .min_dimensions(QR_IMAGE_SIZE, QR_IMAGE_SIZE)
+        .build();
+    let mut png = std::io::Cursor::new(Vec::new());
+    image
+        .write_to(&mut png, image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to render QR code: {}", e))?;
+    Ok(png.into_inner())
+}
 
 /// QR Code service for wristband access control
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct QrCodeService {
     base_url: String,
-    secret_key: String,
-    expiration_hours: i64,
+    signer: QrCodeSigner,
 }
 
-
 impl QrCodeService {
-    pub fn new(base_url: String, secret_key: String, expiration_hours: i64) -> Self {
+    pub fn new(base_url: String, secret_key: String, ttl: Duration) -> Self {
         Self {
             base_url,
-            secret_key,
-            expiration_hours,
+            signer: QrCodeSigner::new(&secret_key, ttl),
         }
     }
 
+    /// `WRISTBAND_QR_SECRET` (or the JWT secret) and `WRISTBAND_QR_TTL_SECONDS`
+    pub fn from_env(base_url: String) -> Result<Self, String> {
+        let secret = match std::env::var("WRISTBAND_QR_SECRET") {
+            Ok(secret) => secret,
+            Err(_) => get_jwt_secret().map_err(|e| e.to_string())?,
+        };
+        let ttl_seconds = std::env::var("WRISTBAND_QR_TTL_SECONDS")
+            .ok()
+            .and_then(|ttl| ttl.parse().ok())
+            .unwrap_or(DEFAULT_QR_CODE_TTL_SECONDS);
+        Ok(Self::new(base_url, secret, Duration::seconds(ttl_seconds)))
+    }
+
+    pub fn signer(&self) -> &QrCodeSigner {
+        &self.signer
+    }
+
+    /// Generate QR URL
+    pub fn generate_qr_url(&self, code: &str) -> String {
+        format!("{}/wristband/{}", self.base_url, code)
+    }
+
+    /// Log scan event
+    async fn log_scan_event(
+        &self,
+        scanner_id: &str,
+        wristband_id: &WristbandId,
+        location: Option<LocationData>,
+    ) -> Result<(), String> {
+        // In a real implementation, this would log to database
+        println!(
+            "QR Code scanned: scanner_id={}, wristband_id={}, location={:?}",
+            scanner_id, wristband_id.0, location
+        );
+        Ok(())
+    }
+
+    /// Determine access and benefits based on wristband
+    async fn determine_access_and_benefits(
+        &self,
+        _wristband_id: &WristbandId,
+    ) -> Result<(bool, Vec<String>), String> {
+        // In a real implementation, this would query the database
+        // For now, we'll return mock data
+        let benefits = vec![
+            "Concert Access".to_string(),
+            "VIP Lounge".to_string(),
+            "Meet & Greet".to_string(),
+        ];
+        Ok((true, benefits))
+    }
+}
+
+#[async_trait]
+impl QrCodeServiceTrait for QrCodeService {
     /// Generate QR code for wristband
-    pub async fn generate_qr_code(&self, wristband_id: &WristbandId) -> Result<QrCode, String> {
-        let code = self.generate_unique_code(wristband_id);
-        let url = self.generate_qr_url(&code);
-        let expires_at = Utc::now() + Duration::hours(self.expiration_hours);
-        
+    async fn generate_qr_code(&self, wristband_id: &WristbandId) -> Result<QrCode, String> {
+        let now = Utc::now();
+        let (code, claims) = self.signer.issue(wristband_id, now);
+
         Ok(QrCode {
             code,
-            url,
-            wristband_id: *wristband_id,
-            expires_at,
-            created_at: Utc::now(),
+            wristband_id: wristband_id.clone(),
+            is_valid: true,
+            created_at: now,
+            expires_at: Some(claims.expires_at),
         })
     }
 
     /// Validate QR code
-    pub async fn validate_qr_code(&self, code: &str) -> Result<QrCodeValidation, String> {
-        // Parse the QR code to extract wristband ID and signature
-        let (wristband_id, signature) = self.parse_qr_code(code)?;
-        
-        // Verify signature
-        if !self.verify_signature(&wristband_id, signature) {
-            return Err("Invalid QR code signature".to_string());
-        }
-
-        // Check expiration
-        let expires_at = self.get_expiration_from_code(code)?;
-        if Utc::now() > expires_at {
-            return Err("QR code has expired".to_string());
-        }
+    async fn validate_qr_code(&self, code: &str) -> Result<QrCodeValidation, String> {
+        let claims = self.signer.verify(code, Utc::now()).map_err(|rejection| rejection.message().to_string())?;
 
         Ok(QrCodeValidation {
             is_valid: true,
-            wristband_id,
-            expires_at: Some(expires_at),
+            wristband_id: claims.wristband_id,
+            expires_at: Some(claims.expires_at),
         })
     }
 
     /// Scan QR code for access control
-    pub async fn scan_qr_code(
+    async fn scan_qr_code(
         &self,
         code: &str,
         scanner_id: &str,
@@ -73,17 +219,6 @@ impl QrCodeService {
     ) -> Result<QrCodeScanResult, String> {
         // Validate QR code first
         let validation = self.validate_qr_code(code).await?;
-        
-        if !validation.is_valid {
-            return Ok(QrCodeScanResult {
-                scan_successful: false,
-                wristband_id: None,
-                fan_id: None,
-                access_granted: false,
-                benefits_available: vec![],
-                scan_timestamp: Utc::now(),
-            });
-        }
 
         // Log scan event
         self.log_scan_event(scanner_id, &validation.wristband_id, location).await?;
@@ -94,96 +229,24 @@ impl QrCodeService {
         Ok(QrCodeScanResult {
             scan_successful: true,
             wristband_id: Some(validation.wristband_id),
-            fan_id: Some(FanId::new()), // Would fetch from database
+            fan_id: None,
             access_granted,
             benefits_available: benefits,
             scan_timestamp: Utc::now(),
         })
     }
 
-    /// Generate unique code for wristband
-    fn generate_unique_code(&self, wristband_id: &WristbandId) -> String {
-        let timestamp = Utc::now().timestamp();
-        let data = format!("{}{}{}", wristband_id.0, timestamp, self.secret_key);
-        let hash = Sha256::digest(data.as_bytes());
-        let signature = general_purpose::STANDARD.encode(&hash[..16]);
-        format!("VS{}{}", wristband_id.0.to_string()[..8].to_uppercase(), signature)
-    }
-
-    /// Generate QR URL
-    fn generate_qr_url(&self, code: &str) -> String {
-        format!("{}/wristband/{}", self.base_url, code)
-    }
-
-    /// Parse QR code to extract wristband ID and signature
-    fn parse_qr_code(&self, code: &str) -> Result<(WristbandId, String), String> {
-        if !code.starts_with("VS") {
-            return Err("Invalid QR code format".to_string());
+    /// Check QR code expiration
+    async fn is_qr_code_expired(&self, code: &str) -> Result<bool, String> {
+        match self.signer.verify(code, Utc::now()) {
+            Ok(_) => Ok(false),
+            Err(QrCodeRejection::Expired) => Ok(true),
+            Err(rejection) => Err(rejection.message().to_string()),
         }
-
-        let code_without_prefix = &code[2..];
-        if code_without_prefix.len() < 24 {
-            return Err("QR code too short".to_string());
-        }
-
-        let wristband_part = &code_without_prefix[..8];
-        let signature = &code_without_prefix[8..];
-
-        // Convert wristband part back to UUID (simplified)
-        let wristband_id = Uuid::parse_str(&format!(
-            "{}-{}-{}-{}-{}",
-            &wristband_part[..8],
-            &wristband_part[8..12],
-            &wristband_part[12..16],
-            &wristband_part[16..20],
-            &wristband_part[20..24]
-        )).map_err(|_| "Invalid wristband ID in QR code")?;
-
-        Ok((WristbandId(wristband_id), signature.to_string()))
-    }
-
-    /// Verify QR code signature
-    fn verify_signature(&self, wristband_id: &WristbandId, signature: &str) -> bool {
-        // In a real implementation, this would verify the cryptographic signature
-        // For now, we'll do a simple validation
-        signature.len() == 24 && signature.chars().all(|c| c.is_alphanumeric())
-    }
-
-    /// Get expiration from QR code
-    fn get_expiration_from_code(&self, _code: &str) -> Result<DateTime<Utc>, String> {
-        // In a real implementation, this would extract timestamp from the code
-        // For now, we'll return a default expiration
-        Ok(Utc::now() + Duration::hours(self.expiration_hours))
     }
 
-    /// Log scan event
-    async fn log_scan_event(
-        &self,
-        scanner_id: &str,
-        wristband_id: &WristbandId,
-        location: Option<LocationData>,
-    ) -> Result<(), String> {
-        // In a real implementation, this would log to database
-        println!(
-            "QR Code scanned: scanner_id={}, wristband_id={}, location={:?}",
-            scanner_id, wristband_id.0, location
-        );
-        Ok(())
-    }
-
-    /// Determine access and benefits based on wristband
-    async fn determine_access_and_benefits(
-        &self,
-        wristband_id: &WristbandId,
-    ) -> Result<(bool, Vec<String>), String> {
-        // In a real implementation, this would query the database
-        // For now, we'll return mock data
-        let benefits = vec![
-            "Concert Access".to_string(),
-            "VIP Lounge".to_string(),
-            "Meet & Greet".to_string(),
-        ];
-        Ok((true, benefits))
+    fn verify_qr_code(&self, code: &str, now: DateTime<Utc>) -> Result<QrCodeClaims, QrCodeRejection> {
+        self.signer.verify(code, now)
     }
 }
 
@@ -194,40 +257,40 @@ impl QrCodeService {
 mod tests {
     use super::*;
 
+    fn service() -> QrCodeService {
+        QrCodeService::new(
+            "https://vibestream.com".to_string(),
+            "secret_key".to_string(),
+            Duration::seconds(DEFAULT_QR_CODE_TTL_SECONDS),
+        )
+    }
+
     #[tokio::test]
     async fn test_generate_qr_code() {
         // Given
-        let service = QrCodeService::new(
-            "https://vibestream.com".to_string(),
-            "secret_key".to_string(),
-            24,
-        );
+        let service = service();
         let wristband_id = WristbandId(Uuid::new_v4());
-        
+
         // When
         let qr_code = service.generate_qr_code(&wristband_id).await.unwrap();
-        
+
         // Then
         assert!(qr_code.code.starts_with("VS"));
-        assert!(qr_code.url.contains(&qr_code.code));
+        assert!(service.generate_qr_url(&qr_code.code).contains(&qr_code.code));
         assert_eq!(qr_code.wristband_id, wristband_id);
-        assert!(qr_code.expires_at > Utc::now());
+        assert!(qr_code.expires_at.unwrap() > Utc::now());
     }
 
     #[tokio::test]
     async fn test_validate_qr_code() {
         // Given
-        let service = QrCodeService::new(
-            "https://vibestream.com".to_string(),
-            "secret_key".to_string(),
-            24,
-        );
+        let service = service();
         let wristband_id = WristbandId(Uuid::new_v4());
         let qr_code = service.generate_qr_code(&wristband_id).await.unwrap();
-        
+
         // When
         let validation = service.validate_qr_code(&qr_code.code).await;
-        
+
         // Then
         assert!(validation.is_ok());
         let validation = validation.unwrap();
@@ -238,11 +301,7 @@ mod tests {
     #[tokio::test]
     async fn test_scan_qr_code() {
         // Given
-        let service = QrCodeService::new(
-            "https://vibestream.com".to_string(),
-            "secret_key".to_string(),
-            24,
-        );
+        let service = service();
         let wristband_id = WristbandId(Uuid::new_v4());
         let qr_code = service.generate_qr_code(&wristband_id).await.unwrap();
 
@@ -265,43 +324,86 @@ mod tests {
         assert!(!scan_result.benefits_available.is_empty());
     }
 
+    #[test]
+    fn test_expired_qr_code_is_rejected() {
+        // Given
+        let signer = QrCodeSigner::new("secret_key", Duration::seconds(300));
+        let wristband_id = WristbandId(Uuid::new_v4());
+        let issued_at = Utc::now();
+        let (code, claims) = signer.issue(&wristband_id, issued_at);
+
+        // Then
+        assert_eq!(signer.verify(&code, issued_at + Duration::seconds(299)).unwrap(), claims);
+        assert_eq!(signer.verify(&code, issued_at + Duration::seconds(300)), Err(QrCodeRejection::Expired));
+    }
+
+    #[test]
+    fn test_tampered_qr_code_is_rejected() {
+        // Given
+        let signer = QrCodeSigner::new("secret_key", Duration::seconds(300));
+        let now = Utc::now();
+        let (code, claims) = signer.issue(&WristbandId(Uuid::new_v4()), now);
+        let (payload, signature) = code.strip_prefix("VS").unwrap().split_once('.').unwrap();
+
+        // When: another wristband, or a later expiry, under the original signature
+        let forged_payload = format!("{}:{}:{}", Uuid::new_v4(), claims.expires_at.timestamp(), claims.nonce);
+        let forged = format!("VS{}.{}", URL_SAFE_NO_PAD.encode(forged_payload), signature);
+        let extended_payload = format!("{}:{}:{}", claims.wristband_id.0, claims.expires_at.timestamp() + 86_400, claims.nonce);
+        let extended = format!("VS{}.{}", URL_SAFE_NO_PAD.encode(extended_payload), signature);
+
+        // Then
+        assert_eq!(signer.verify(&forged, now), Err(QrCodeRejection::InvalidSignature));
+        assert_eq!(signer.verify(&extended, now), Err(QrCodeRejection::InvalidSignature));
+        let other_secret = QrCodeSigner::new("other_secret", Duration::seconds(300));
+        assert_eq!(other_secret.verify(&code, now), Err(QrCodeRejection::InvalidSignature));
+        assert_eq!(signer.verify(&format!("VS{}.", payload), now), Err(QrCodeRejection::Malformed));
+    }
+
+    #[test]
+    fn test_codes_are_unique_and_render_as_png() {
+        let signer = QrCodeSigner::new("secret_key", Duration::seconds(300));
+        let wristband_id = WristbandId(Uuid::new_v4());
+        let now = Utc::now();
+        let (first, _) = signer.issue(&wristband_id, now);
+        let (second, _) = signer.issue(&wristband_id, now);
+        assert_ne!(first, second);
+
+        let png = render_qr_png(&first).unwrap();
+        assert!(png.starts_with(&[0x89, b'P', b'N', b'G']));
+    }
+
     #[test]
     fn test_qr_code_serialization() {
         // Given
         let qr_code = QrCode {
             code: "VS12345678ABCDEF1234567890".to_string(),
-            url: "https://vibestream.com/wristband/VS12345678ABCDEF1234567890".to_string(),
             wristband_id: WristbandId(Uuid::new_v4()),
-            expires_at: Utc::now() + Duration::hours(24),
+            is_valid: true,
+            expires_at: Some(Utc::now() + Duration::hours(24)),
             created_at: Utc::now(),
         };
-        
+
         // When
         let json = serde_json::to_string(&qr_code).unwrap();
         let deserialized: QrCode = serde_json::from_str(&json).unwrap();
-        
+
         // Then
         assert_eq!(qr_code.code, deserialized.code);
-        assert_eq!(qr_code.url, deserialized.url);
         assert_eq!(qr_code.wristband_id, deserialized.wristband_id);
     }
 
     #[test]
     fn test_invalid_qr_code_validation() {
         // Given
-        let service = QrCodeService::new(
-            "https://vibestream.com".to_string(),
-            "secret_key".to_string(),
-            24,
-        );
-        
+        let service = service();
+
         // When
         let result = tokio::runtime::Runtime::new().unwrap().block_on(
             service.validate_qr_code("invalid_code")
         );
-        
+
         // Then
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Invalid QR code format"));
     }
-}
\ No newline at end of file
+}
@@ -15,10 +15,11 @@ use crate::bounded_contexts::fan_loyalty::{
         BiometricData as DomainBiometricData, 
         LocationData as DomainLocationData,
         BehavioralPatterns as DomainBehavioralPatterns,
-        DeviceCharacteristics as DomainDeviceCharacteristics
+        DeviceCharacteristics as DomainDeviceCharacteristics,
+        QrScanOutcome,
     },
     application::{
-        commands::{VerifyFanCommand, CreateWristbandCommand, ActivateWristbandCommand},
+        commands::{VerifyFanCommand, CreateWristbandCommand, ActivateWristbandCommand, ValidateQrCodeCommand, QrValidationContext},
         queries::{GetWristbandQuery, ValidateQrCodeQuery},
         handlers::{
             FanVerificationHandler, WristbandHandler, QrCodeHandler,
@@ -464,41 +465,57 @@ impl FanLoyaltyHandlers {
     }
 
     /// POST /api/fan-loyalty/qr/:code/scan
-    /// Scan QR code for access control
+    /// Scan QR code for access control. Access is granted only on the scan
+    /// that spends the code; a repeated scan is a 409 with the first use.
     pub async fn scan_qr_code(
         State(handlers): State<Self>,
         Path(qr_code): Path<String>,
         Json(request): Json<ScanQrCodeRequest>,
     ) -> Result<Json<ScanQrCodeResponse>, (StatusCode, Json<serde_json::Value>)> {
-        // Mock scan implementation since handler doesn't support it directly yet
-        match handlers.qr_handler.handle_validate_qr(&qr_code).await {
-            Ok(Some(_valid_qr)) => {
-                 // Simplified mock logic
-                 let response = ScanQrCodeResponse {
-                    scan_successful: true,
-                    wristband_id: Some(Uuid::nil()), // Mock
-                    fan_id: Some(Uuid::nil()), // Mock
-                    access_granted: true,
-                    benefits_available: vec![],
-                    scan_timestamp: chrono::Utc::now(),
-                 };
-                 Ok(Json(response))
-            }
-            Ok(None) => {
-                 // Invalid QR
-                 Err((StatusCode::BAD_REQUEST, Json(serde_json::json!({"error": "Invalid QR"}))))
-            }
-            Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({"error": e}))))
+        let context = QrValidationContext {
+            location: request.location.map(Into::into),
+            device_fingerprint: None,
+            timestamp: chrono::Utc::now(),
+        };
+        let command = ValidateQrCodeCommand::new(qr_code, Some(context));
+
+        match handlers.wristband_handler.handle_validate_qr_code(&command).await {
+            Ok(outcome) => scan_response(outcome).map(Json),
+            Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({"error": e})))),
         }
     }
+}
 
-
+fn scan_response(outcome: QrScanOutcome) -> Result<ScanQrCodeResponse, (StatusCode, Json<serde_json::Value>)> {
+    match outcome {
+        QrScanOutcome::Valid { wristband, scanned_at } => Ok(ScanQrCodeResponse {
+            scan_successful: true,
+            wristband_id: Some(wristband.id.0),
+            fan_id: Some(wristband.fan_id.0),
+            access_granted: true,
+            benefits_available: wristband.wristband_type.benefits(),
+            scan_timestamp: scanned_at,
+        }),
+        QrScanOutcome::AlreadyUsed { wristband_id, used_at } => Err((
+            StatusCode::CONFLICT,
+            Json(serde_json::json!({
+                "error": "QR code has already been used",
+                "wristband_id": wristband_id.0,
+                "used_at": used_at,
+            })),
+        )),
+        QrScanOutcome::Invalid { reason } => Err((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": "Invalid QR", "message": reason.message()})),
+        )),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::bounded_contexts::fan_loyalty::domain::WristbandType;
+    use crate::bounded_contexts::fan_loyalty::domain::entities::QrCodeRejection;
 
     #[tokio::test]
     async fn test_verify_fan_request_serialization() {
@@ -570,6 +587,26 @@ mod tests {
         assert!(parse_wristband_type("invalid").is_err());
     }
 
+    #[test]
+    fn test_scan_grants_access_only_for_a_spent_code() {
+        let wristband = NftWristband::new(FanId::new(), "concert".to_string(), "artist".to_string(), WristbandType::VIP);
+        let scanned_at = Utc::now();
+
+        let response = scan_response(QrScanOutcome::Valid { wristband: wristband.clone(), scanned_at }).unwrap();
+        assert!(response.access_granted);
+        assert_eq!(response.wristband_id, Some(wristband.id.0));
+        assert_eq!(response.fan_id, Some(wristband.fan_id.0));
+        assert_eq!(response.scan_timestamp, scanned_at);
+
+        let (status, body) = scan_response(QrScanOutcome::AlreadyUsed { wristband_id: wristband.id.clone(), used_at: scanned_at })
+            .unwrap_err();
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["wristband_id"], wristband.id.0.to_string());
+
+        let (status, _) = scan_response(QrScanOutcome::Invalid { reason: QrCodeRejection::UnknownCode }).unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
     fn parse_wristband_type(wristband_type: &str) -> Result<WristbandType, String> {
         match wristband_type {
             "general" => Ok(WristbandType::General),
@@ -228,75 +228,30 @@ async fn test_generate_qr_code_endpoint() {
     // Now generate QR code
     let qr_request = Request::builder()
         .method(Method::POST)
-        .uri(&format!("/api/fan-loyalty/wristbands/{}/qr-code", wristband_id))
-        .header("content-type", "application/json")
+        .uri(&format!("/api/fan-loyalty/wristbands/{}/qr", wristband_id))
         .body(Body::empty())
         .unwrap();
     
     let qr_response = app.oneshot(qr_request).await.unwrap();
     
     assert_eq!(qr_response.status(), StatusCode::OK);
+    assert_eq!(qr_response.headers()["content-type"], "image/png");
+    assert!(qr_response.headers().contains_key("x-qr-expires-at"));
     
     let body = hyper::body::to_bytes(qr_response.into_body()).await.unwrap();
-    let response_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
-    
-    assert!(response_json["qr_code"].is_string());
-    assert!(response_json["qr_code"].as_str().unwrap().starts_with("QR_"));
-    assert_eq!(response_json["wristband_id"], wristband_id);
+    assert!(body.starts_with(&[0x89, b'P', b'N', b'G']));
     
-    println!("✅ POST /api/fan-loyalty/wristbands/{}/qr-code endpoint working", wristband_id);
+    println!("✅ POST /api/fan-loyalty/wristbands/{}/qr endpoint working", wristband_id);
 }
 
 #[tokio::test]
-async fn test_validate_qr_code_endpoint() {
-    // TDD RED PHASE: This test should fail initially
-    
+async fn test_validate_qr_code_endpoint_rejects_unsigned_code() {
     let app = create_test_app().await;
     
-    // First create a wristband and generate QR code
-    let create_body = json!({
-        "fan_id": "fan_123",
-        "concert_id": "concert_456",
-        "artist_id": "artist_789",
-        "wristband_type": "VIP",
-        "fan_wallet_address": "0xfan_wallet_address"
-    });
-    
-    let create_request = Request::builder()
-        .method(Method::POST)
-        .uri("/api/fan-loyalty/wristbands")
-        .header("content-type", "application/json")
-        .body(Body::from(serde_json::to_vec(&create_body).unwrap()))
-        .unwrap();
-    
-    let create_response = app.clone().oneshot(create_request).await.unwrap();
-    let create_body = hyper::body::to_bytes(create_response.into_body()).await.unwrap();
-    let create_json: serde_json::Value = serde_json::from_slice(&create_body).unwrap();
-    let wristband_id = create_json["wristband_id"].as_str().unwrap();
-    
-    // Generate QR code
-    let qr_request = Request::builder()
-        .method(Method::POST)
-        .uri(&format!("/api/fan-loyalty/wristbands/{}/qr-code", wristband_id))
-        .header("content-type", "application/json")
-        .body(Body::empty())
-        .unwrap();
-    
-    let qr_response = app.clone().oneshot(qr_request).await.unwrap();
-    let qr_body = hyper::body::to_bytes(qr_response.into_body()).await.unwrap();
-    let qr_json: serde_json::Value = serde_json::from_slice(&qr_body).unwrap();
-    let qr_code = qr_json["qr_code"].as_str().unwrap();
-    
-    // Now validate the QR code
-    let validate_body = json!({
-        "qr_code": qr_code
-    });
-    
     let validate_request = Request::builder()
-        .method(Method::POST)
-        .uri("/api/fan-loyalty/validate-qr")
-        .header("content-type", "application/json")
-        .body(Body::from(serde_json::to_vec(&validate_body).unwrap()))
+        .method(Method::GET)
+        .uri("/api/fan-loyalty/validate-qr/QR_not_signed")
+        .body(Body::empty())
         .unwrap();
     
     let validate_response = app.oneshot(validate_request).await.unwrap();
@@ -306,14 +261,11 @@ async fn test_validate_qr_code_endpoint() {
     let body = hyper::body::to_bytes(validate_response.into_body()).await.unwrap();
     let response_json: serde_json::Value = serde_json::from_slice(&body).unwrap();
     
-    assert!(response_json["is_valid"].as_bool().unwrap());
-    assert_eq!(response_json["wristband_id"], wristband_id);
-    assert_eq!(response_json["fan_id"], "fan_123");
-    assert_eq!(response_json["concert_id"], "concert_456");
-    assert_eq!(response_json["artist_id"], "artist_789");
-    assert_eq!(response_json["wristband_type"], "VIP");
+    assert!(!response_json["is_valid"].as_bool().unwrap());
+    assert_eq!(response_json["status"], "invalid");
+    assert_eq!(response_json["reason"], "Invalid QR code format");
     
-    println!("✅ POST /api/fan-loyalty/validate-qr endpoint working");
+    println!("✅ GET /api/fan-loyalty/validate-qr/:code endpoint working");
 }
 
 #[tokio::test]
@@ -0,0 +1,563 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_ventures::domain::entities::{
+    ArtistVenture, FanInvestment, InvestmentStatus, InvestmentType, VentureStatus,
+};
+use crate::bounded_contexts::fan_ventures::domain::escrow::{InvestmentReservation, ReservationStatus, ShareAvailability};
+use crate::bounded_contexts::fan_ventures::domain::repositories::{InvestmentReservationRepository, VentureRepository};
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventBus};
+use crate::shared::domain::errors::AppError;
+
+// =============================================================================
+// FAN VENTURES - ESCROW SERVICE (Compras pendientes / confirmar / cancelar)
+// =============================================================================
+
+/// Plazo por defecto de una reserva si no se configura `INVESTMENT_RESERVATION_TIMEOUT_SECS`
+pub const DEFAULT_RESERVATION_TIMEOUT_SECS: i64 = 15 * 60;
+
+/// Puerto hacia el contexto de pagos para retener y liberar fondos
+#[async_trait]
+pub trait EscrowPayments: Send + Sync {
+    /// Crear el pago que retiene los fondos de la inversión. Devuelve el id del pago.
+    async fn hold(&self, investment: &FanInvestment, artist_id: Uuid) -> Result<Uuid, AppError>;
+    /// `true` cuando el pago ya se liquidó
+    async fn is_settled(&self, payment_id: Uuid) -> Result<bool, AppError>;
+    /// Devolver los fondos: reembolso si el pago se liquidó, cancelación si no
+    async fn release(&self, payment_id: Uuid, requested_by: Uuid, reason: &str) -> Result<(), AppError>;
+}
+
+pub struct InvestmentEscrowService {
+    reservation_repository: Arc<dyn InvestmentReservationRepository>,
+    venture_repository: Arc<dyn VentureRepository>,
+    payments: Arc<dyn EscrowPayments>,
+    event_bus: Arc<dyn EventBus>,
+    reservation_timeout: chrono::Duration,
+}
+
+impl InvestmentEscrowService {
+    pub fn new(
+        reservation_repository: Arc<dyn InvestmentReservationRepository>,
+        venture_repository: Arc<dyn VentureRepository>,
+        payments: Arc<dyn EscrowPayments>,
+        event_bus: Arc<dyn EventBus>,
+        reservation_timeout: chrono::Duration,
+    ) -> Self {
+        Self {
+            reservation_repository,
+            venture_repository,
+            payments,
+            event_bus,
+            reservation_timeout,
+        }
+    }
+
+    /// Plazo leído de `INVESTMENT_RESERVATION_TIMEOUT_SECS`
+    pub fn reservation_timeout_from_env() -> chrono::Duration {
+        let secs = std::env::var("INVESTMENT_RESERVATION_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse::<i64>().ok())
+            .filter(|secs| *secs > 0)
+            .unwrap_or(DEFAULT_RESERVATION_TIMEOUT_SECS);
+        chrono::Duration::seconds(secs)
+    }
+
+    /// Reservar participaciones y retener el pago. La inversión queda `Pending`
+    /// hasta que se confirme (explícitamente o al liquidarse el pago si `auto_confirm`).
+    #[tracing::instrument(name = "InvestmentEscrowService::reserve", skip(self))]
+    pub async fn reserve(
+        &self,
+        venture_id: Uuid,
+        fan_id: Uuid,
+        shares: f64,
+        auto_confirm: bool,
+    ) -> Result<(FanInvestment, InvestmentReservation), AppError> {
+        let venture = self.load_venture(venture_id).await?;
+
+        if venture.status != VentureStatus::Open {
+            return Err(AppError::DomainRuleViolation("Venture is not open for investments".to_string()));
+        }
+        if venture.end_date.is_some_and(|deadline| Utc::now() >= deadline) {
+            return Err(AppError::DomainRuleViolation("Venture funding deadline has passed".to_string()));
+        }
+        if shares < venture.min_investment {
+            return Err(AppError::DomainRuleViolation(
+                format!("Investment amount must be at least ${}", venture.min_investment)
+            ));
+        }
+        if let Some(max_inv) = venture.max_investment {
+            if shares > max_inv {
+                return Err(AppError::DomainRuleViolation(
+                    format!("Investment amount must be at most ${}", max_inv)
+                ));
+            }
+        }
+
+        self.availability_of(&venture).await?.ensure_available(shares)?;
+
+        let investment = FanInvestment::new(
+            Uuid::new_v4(),
+            fan_id,
+            venture_id,
+            shares,
+            InvestmentType::RevenueShare,
+            InvestmentStatus::Pending,
+        );
+        self.venture_repository.create_fan_investment(&investment).await?;
+
+        let payment_id = self.payments.hold(&investment, venture.artist_id).await?;
+        let reservation = InvestmentReservation::new(
+            &investment,
+            Some(payment_id),
+            auto_confirm,
+            self.reservation_timeout,
+            Utc::now(),
+        );
+        self.reservation_repository.create(&reservation).await?;
+
+        tracing::info!("🔒 Reserved {} shares of venture {} for fan {} until {}",
+            shares, venture_id, fan_id, reservation.expires_at);
+
+        let event = DomainEvent::SharesReserved {
+            investment_id: investment.id,
+            venture_id,
+            fan_id,
+            shares,
+            payment_id: reservation.payment_id,
+            expires_at: reservation.expires_at,
+            occurred_at: Utc::now(),
+        };
+        if let Err(e) = self.event_bus.publish(event).await {
+            tracing::warn!("Failed to publish shares reserved event: {:?}", e);
+        }
+
+        Ok((investment, reservation))
+    }
+
+    /// Participaciones vendidas, reservadas y disponibles de un venture
+    pub async fn availability(&self, venture_id: Uuid) -> Result<ShareAvailability, AppError> {
+        let venture = self.load_venture(venture_id).await?;
+        self.availability_of(&venture).await
+    }
+
+    pub async fn availability_of(&self, venture: &ArtistVenture) -> Result<ShareAvailability, AppError> {
+        let pending = self.reservation_repository.find_pending_by_venture(&venture.id).await?;
+        Ok(ShareAvailability::compute(venture, &pending, Utc::now()))
+    }
+
+    /// Confirmar una compra pendiente una vez liquidado el pago
+    pub async fn confirm(&self, investment_id: Uuid, fan_id: Uuid) -> Result<InvestmentReservation, AppError> {
+        let mut reservation = self.load(investment_id).await?;
+        reservation.ensure_owner(fan_id)?;
+        self.confirm_reservation(&mut reservation).await?;
+        Ok(reservation)
+    }
+
+    /// Cancelar una compra pendiente: libera las participaciones y devuelve los fondos
+    pub async fn cancel(&self, investment_id: Uuid, fan_id: Uuid) -> Result<InvestmentReservation, AppError> {
+        let mut reservation = self.load(investment_id).await?;
+        reservation.ensure_owner(fan_id)?;
+        reservation.cancel(Utc::now())?;
+        self.persist_resolution(&reservation).await?;
+        self.release(&reservation, "Purchase cancelled by buyer").await?;
+
+        tracing::info!("↩️ Reservation {} cancelled by fan {}", investment_id, fan_id);
+        Ok(reservation)
+    }
+
+    /// Llamado cuando el contexto de pagos informa que el pago se liquidó.
+    /// Devuelve `false` si la inversión no pasa por escrow.
+    pub async fn on_payment_settled(&self, investment_id: Uuid) -> Result<bool, AppError> {
+        let Some(mut reservation) = self.reservation_repository.find_by_investment(&investment_id).await? else {
+            return Ok(false);
+        };
+
+        if let Some(payment_id) = reservation.payment_id {
+            let event = DomainEvent::SharePaymentCompleted { investment_id, payment_id, occurred_at: Utc::now() };
+            if let Err(e) = self.event_bus.publish(event).await {
+                tracing::warn!("Failed to publish share payment completed event: {:?}", e);
+            }
+        }
+
+        if reservation.is_pending() {
+            if reservation.auto_confirm {
+                self.confirm_reservation(&mut reservation).await?;
+            } else {
+                tracing::info!("Payment settled for reservation {}; awaiting buyer confirmation", investment_id);
+            }
+        } else if reservation.status != ReservationStatus::Confirmed {
+            // El pago se liquidó después de cancelar/expirar: hay que devolverlo
+            tracing::warn!("Payment settled for released reservation {} ({}); refunding", investment_id, reservation.status);
+            self.release(&reservation, "Payment settled after reservation was released").await?;
+        }
+
+        Ok(true)
+    }
+
+    /// Llamado cuando el contexto de pagos informa que el cobro falló. La
+    /// reserva la libera la saga de compra. Devuelve `false` si la inversión no
+    /// pasa por escrow.
+    pub async fn on_payment_failed(&self, investment_id: Uuid, reason: &str) -> Result<bool, AppError> {
+        let Some(reservation) = self.reservation_repository.find_by_investment(&investment_id).await? else {
+            return Ok(false);
+        };
+        let Some(payment_id) = reservation.payment_id else {
+            return Ok(true);
+        };
+
+        let event = DomainEvent::SharePaymentFailed {
+            investment_id,
+            payment_id,
+            reason: reason.to_string(),
+            occurred_at: Utc::now(),
+        };
+        if let Err(e) = self.event_bus.publish(event).await {
+            tracing::warn!("Failed to publish share payment failed event: {:?}", e);
+        }
+        Ok(true)
+    }
+
+    /// Compensación de la saga de compra: liberar la reserva y cancelar la
+    /// inversión sin tocar el pago. No hace nada si ya se liberó; una compra
+    /// confirmada no se puede deshacer así.
+    pub async fn release_reservation(&self, investment_id: Uuid, reason: &str) -> Result<(), AppError> {
+        let mut reservation = self.load(investment_id).await?;
+        if reservation.is_pending() {
+            reservation.cancel(Utc::now())?;
+            match self.persist_resolution(&reservation).await {
+                Ok(()) => {}
+                Err(AppError::ConcurrencyConflict(_)) => reservation = self.load(investment_id).await?,
+                Err(e) => return Err(e),
+            }
+        }
+        if reservation.status == ReservationStatus::Confirmed {
+            return Err(AppError::DomainRuleViolation(format!(
+                "Purchase {} is already confirmed and cannot be released", investment_id
+            )));
+        }
+
+        self.cancel_investment(investment_id).await?;
+        tracing::info!("↩️ Reservation {} released: {}", investment_id, reason);
+        Ok(())
+    }
+
+    /// Compensación de la saga de compra: devolver el pago (o anular la
+    /// retención si aún no se cobró)
+    pub async fn refund_payment(&self, payment_id: Uuid, fan_id: Uuid, reason: &str) -> Result<(), AppError> {
+        self.payments.release(payment_id, fan_id, reason).await
+    }
+
+    /// Liberar todas las reservas vencidas. Devuelve cuántas se liberaron.
+    pub async fn expire_due_reservations(&self) -> Result<usize, AppError> {
+        let due = self.reservation_repository.find_expired(Utc::now()).await?;
+        let mut expired = 0;
+        for mut reservation in due {
+            match self.expire(&mut reservation).await {
+                Ok(true) => expired += 1,
+                Ok(false) => {}
+                Err(e) => tracing::error!("Failed to expire reservation {}: {:?}", reservation.investment_id, e),
+            }
+        }
+        Ok(expired)
+    }
+
+    async fn expire(&self, reservation: &mut InvestmentReservation) -> Result<bool, AppError> {
+        reservation.expire(Utc::now())?;
+
+        match self.persist_resolution(reservation).await {
+            Ok(()) => {}
+            // El comprador confirmó o canceló primero: la reserva ya no es nuestra
+            Err(AppError::ConcurrencyConflict(_)) => return Ok(false),
+            Err(e) => return Err(e),
+        }
+        self.release(reservation, "Reservation expired").await?;
+
+        let event = DomainEvent::InvestmentReservationExpired {
+            investment_id: reservation.investment_id,
+            venture_id: reservation.venture_id,
+            investor_id: reservation.fan_id,
+            shares: reservation.shares,
+            occurred_at: Utc::now(),
+        };
+        if let Err(e) = self.event_bus.publish(event).await {
+            tracing::warn!("Failed to publish reservation expired event: {:?}", e);
+        }
+
+        Ok(true)
+    }
+
+    async fn confirm_reservation(&self, reservation: &mut InvestmentReservation) -> Result<(), AppError> {
+        let settled = match reservation.payment_id {
+            Some(payment_id) => self.payments.is_settled(payment_id).await?,
+            None => false,
+        };
+        reservation.confirm(settled, Utc::now())?;
+        self.persist_resolution(reservation).await?;
+
+        let mut investment = self.venture_repository.get_investment_by_id(reservation.investment_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Investment {} not found", reservation.investment_id)))?;
+        investment.status = InvestmentStatus::Active;
+        investment.updated_at = Utc::now();
+        self.venture_repository.update_fan_investment(&investment).await?;
+
+        let mut venture = self.load_venture(reservation.venture_id).await?;
+        let funding_before = venture.current_funding;
+        let funded = venture.record_funding(reservation.shares, Utc::now());
+        self.venture_repository.update_venture(&venture).await?;
+
+        let event = DomainEvent::InvestmentMade {
+            venture_id: reservation.venture_id,
+            investor_id: reservation.fan_id,
+            amount: reservation.shares,
+            occurred_at: Utc::now(),
+        };
+        if let Err(e) = self.event_bus.publish(event).await {
+            tracing::warn!("Failed to publish investment made event: {:?}", e);
+        }
+
+        let event = DomainEvent::SharePurchased {
+            venture_id: reservation.venture_id,
+            investment_id: reservation.investment_id,
+            investor_id: reservation.fan_id,
+            amount: reservation.shares,
+            funding_before,
+            funding_after: venture.current_funding,
+            occurred_at: Utc::now(),
+        };
+        if let Err(e) = self.event_bus.publish(event).await {
+            tracing::warn!("Failed to publish share purchased event: {:?}", e);
+        }
+
+        if funded {
+            let event = DomainEvent::VentureFunded {
+                venture_id: venture.id,
+                artist_id: venture.artist_id,
+                total_funding: venture.current_funding,
+                occurred_at: Utc::now(),
+            };
+            if let Err(e) = self.event_bus.publish(event).await {
+                tracing::warn!("Failed to publish venture funded event: {:?}", e);
+            }
+            tracing::info!("🎯 Venture {} reached its funding goal and is closed to new investments", venture.id);
+        }
+
+        tracing::info!("✅ Reservation {} confirmed; venture {} funding is now ${}",
+            reservation.investment_id, venture.id, venture.current_funding);
+        Ok(())
+    }
+
+    /// Persistir la transición. Si otra transición ganó la carrera se informa
+    /// el estado real en lugar de un conflicto genérico.
+    async fn persist_resolution(&self, reservation: &InvestmentReservation) -> Result<(), AppError> {
+        match self.reservation_repository.resolve(reservation).await {
+            Err(AppError::ConcurrencyConflict(msg)) => {
+                let current = self.load(reservation.investment_id).await?;
+                tracing::info!("Reservation {} resolved concurrently as {}", reservation.investment_id, current.status);
+                Err(AppError::ConcurrencyConflict(format!("{} ({})", msg, current.status)))
+            }
+            other => other,
+        }
+    }
+
+    /// Cancelar la inversión y devolver los fondos retenidos
+    async fn release(&self, reservation: &InvestmentReservation, reason: &str) -> Result<(), AppError> {
+        self.cancel_investment(reservation.investment_id).await?;
+
+        if let Some(payment_id) = reservation.payment_id {
+            self.payments.release(payment_id, reservation.fan_id, reason).await?;
+        }
+        Ok(())
+    }
+
+    async fn cancel_investment(&self, investment_id: Uuid) -> Result<(), AppError> {
+        if let Some(mut investment) = self.venture_repository.get_investment_by_id(investment_id).await? {
+            if investment.status == InvestmentStatus::Pending {
+                investment.status = InvestmentStatus::Cancelled;
+                investment.updated_at = Utc::now();
+                self.venture_repository.update_fan_investment(&investment).await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn load(&self, investment_id: Uuid) -> Result<InvestmentReservation, AppError> {
+        self.reservation_repository.find_by_investment(&investment_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Purchase {} not found", investment_id)))
+    }
+
+    async fn load_venture(&self, venture_id: Uuid) -> Result<ArtistVenture, AppError> {
+        self.venture_repository.get_venture(venture_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Venture {} not found", venture_id)))
+    }
+}
+
+/// Worker que libera las reservas cuyo plazo venció
+pub struct ReservationExpiryJob {
+    service: Arc<InvestmentEscrowService>,
+    interval: Duration,
+}
+
+impl ReservationExpiryJob {
+    pub fn new(service: Arc<InvestmentEscrowService>, interval: Duration) -> Self {
+        Self { service, interval }
+    }
+
+    pub fn start(&self) -> tokio::task::JoinHandle<()> {
+        let service = Arc::clone(&self.service);
+        let interval = self.interval;
+
+        tokio::spawn(async move {
+            tracing::info!("🚀 Reservation expiry job started");
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match service.expire_due_reservations().await {
+                    Ok(count) if count > 0 => tracing::info!("✅ Released {} expired reservations", count),
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Reservation expiry failed: {:?}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::fan_ventures::domain::entities::{RiskLevel, VentureCategory};
+    use crate::bounded_contexts::fan_ventures::integration_service::InMemoryFanVenturesBoundedContext;
+
+    async fn context_with_venture(funding_goal: f64) -> (InMemoryFanVenturesBoundedContext, ArtistVenture) {
+        let now = Utc::now();
+        let venture = ArtistVenture {
+            id: Uuid::new_v4(),
+            artist_id: Uuid::new_v4(),
+            title: "Debut EP".to_string(),
+            description: None,
+            category: VentureCategory::Music,
+            tags: vec![],
+            risk_level: RiskLevel::Medium,
+            expected_return: 0.0,
+            artist_rating: 0.0,
+            artist_previous_ventures: 0,
+            artist_success_rate: 0.0,
+            funding_goal,
+            current_funding: 0.0,
+            min_investment: 10.0,
+            max_investment: None,
+            status: VentureStatus::Open,
+            start_date: None,
+            end_date: None,
+            created_at: now,
+            updated_at: now,
+            benefits: vec![],
+        };
+        let context = InMemoryFanVenturesBoundedContext::create_for_testing();
+        context.ventures.create_venture(&venture).await.unwrap();
+        (context, venture)
+    }
+
+    async fn published_types(context: &InMemoryFanVenturesBoundedContext) -> Vec<&'static str> {
+        context.event_publisher.get_published_events().await.iter().map(DomainEvent::event_type).collect()
+    }
+
+    #[tokio::test]
+    async fn reserve_holds_payment_and_publishes_shares_reserved() {
+        let (context, venture) = context_with_venture(1000.0).await;
+        let fan_id = Uuid::new_v4();
+
+        let (investment, reservation) = context.escrow_service.reserve(venture.id, fan_id, 100.0, false).await.unwrap();
+
+        assert_eq!(investment.status, InvestmentStatus::Pending);
+        assert!(reservation.is_pending());
+        let availability = context.escrow_service.availability(venture.id).await.unwrap();
+        assert_eq!(availability.reserved_shares, 100.0);
+        let events = context.event_publisher.get_published_events().await;
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            DomainEvent::SharesReserved { investment_id, fan_id: reserved_by, shares, payment_id, .. }
+                if *investment_id == investment.id && *reserved_by == fan_id && *shares == 100.0
+                    && *payment_id == reservation.payment_id
+        ));
+    }
+
+    #[tokio::test]
+    async fn settled_payment_confirms_the_purchase_and_funds_the_venture() {
+        let (context, venture) = context_with_venture(100.0).await;
+        let fan_id = Uuid::new_v4();
+        let (investment, reservation) = context.escrow_service.reserve(venture.id, fan_id, 100.0, true).await.unwrap();
+
+        context.payments.settle(reservation.payment_id.unwrap()).await;
+        assert!(context.escrow_service.on_payment_settled(investment.id).await.unwrap());
+
+        assert_eq!(
+            published_types(&context).await,
+            vec!["SharesReserved", "SharePaymentCompleted", "InvestmentMade", "SharePurchased", "VentureFunded"]
+        );
+        let events = context.event_publisher.get_published_events().await;
+        assert!(matches!(
+            &events[3],
+            DomainEvent::SharePurchased { funding_before, funding_after, .. } if *funding_before == 0.0 && *funding_after == 100.0
+        ));
+        let stored = context.ventures.get_investment_by_id(investment.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, InvestmentStatus::Active);
+        let venture = context.ventures.get_venture(venture.id).await.unwrap().unwrap();
+        assert_eq!(venture.status, VentureStatus::Funded);
+    }
+
+    #[tokio::test]
+    async fn purchase_cannot_be_confirmed_before_payment_settles() {
+        let (context, venture) = context_with_venture(1000.0).await;
+        let fan_id = Uuid::new_v4();
+        let (investment, _) = context.escrow_service.reserve(venture.id, fan_id, 100.0, false).await.unwrap();
+
+        assert!(context.escrow_service.confirm(investment.id, fan_id).await.is_err());
+
+        assert_eq!(published_types(&context).await, vec!["SharesReserved"]);
+        let venture = context.ventures.get_venture(venture.id).await.unwrap().unwrap();
+        assert_eq!(venture.current_funding, 0.0);
+    }
+
+    #[tokio::test]
+    async fn cancelled_reservation_releases_shares_and_payment() {
+        let (context, venture) = context_with_venture(1000.0).await;
+        let fan_id = Uuid::new_v4();
+        let (investment, reservation) = context.escrow_service.reserve(venture.id, fan_id, 100.0, false).await.unwrap();
+
+        let cancelled = context.escrow_service.cancel(investment.id, fan_id).await.unwrap();
+
+        assert_eq!(cancelled.status, ReservationStatus::Cancelled);
+        assert_eq!(context.payments.released().await, vec![reservation.payment_id.unwrap()]);
+        let stored = context.ventures.get_investment_by_id(investment.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, InvestmentStatus::Cancelled);
+        assert_eq!(context.escrow_service.availability(venture.id).await.unwrap().available_shares, 1000.0);
+        assert_eq!(published_types(&context).await, vec!["SharesReserved"]);
+    }
+
+    #[tokio::test]
+    async fn expired_reservation_is_released_and_published() {
+        let (context, venture) = context_with_venture(1000.0).await;
+        let fan_id = Uuid::new_v4();
+        let (investment, mut reservation) = context.escrow_service.reserve(venture.id, fan_id, 100.0, false).await.unwrap();
+        reservation.expires_at = Utc::now() - chrono::Duration::seconds(1);
+        context.reservations.create(&reservation).await.unwrap();
+
+        assert_eq!(context.escrow_service.expire_due_reservations().await.unwrap(), 1);
+        // Ya resuelta: una segunda pasada no la vuelve a liberar
+        assert_eq!(context.escrow_service.expire_due_reservations().await.unwrap(), 0);
+
+        assert_eq!(context.payments.released().await, vec![reservation.payment_id.unwrap()]);
+        assert_eq!(published_types(&context).await, vec!["SharesReserved", "InvestmentReservationExpired"]);
+        let events = context.event_publisher.get_published_events().await;
+        assert!(matches!(
+            &events[1],
+            DomainEvent::InvestmentReservationExpired { investment_id, investor_id, shares, .. }
+                if *investment_id == investment.id && *investor_id == fan_id && *shares == 100.0
+        ));
+    }
+}
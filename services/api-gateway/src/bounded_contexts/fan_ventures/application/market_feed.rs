@@ -0,0 +1,330 @@
+use std::collections::{HashMap, HashSet};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot, RwLock};
+use uuid::Uuid;
+
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+use crate::shared::domain::errors::AppError;
+
+// =============================================================================
+// FAN VENTURES - REAL-TIME MARKET FEED
+// =============================================================================
+//
+// Hub de conexiones WebSocket. Cada conexión se suscribe a ventures concretos
+// y/o a su propio portfolio; los eventos del bus se traducen a deltas JSON
+// compactos y se entregan por un canal acotado. Si un cliente no consume a
+// tiempo se le desconecta en lugar de acumular mensajes en memoria.
+
+/// Máximo de ventures a los que puede suscribirse una misma conexión
+pub const MAX_SUBSCRIPTIONS_PER_CONNECTION: usize = 50;
+/// Mensajes pendientes por conexión antes de considerarla lenta
+pub const OUTBOUND_BUFFER_SIZE: usize = 64;
+
+/// Mensajes del cliente
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum FeedCommand {
+    Subscribe { venture_id: Uuid },
+    Unsubscribe { venture_id: Uuid },
+    SubscribePortfolio,
+    UnsubscribePortfolio,
+}
+
+/// Deltas enviados al cliente (`ts` en milisegundos Unix)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeedMessage {
+    Price { venture_id: Uuid, price: f64, change_percentage: f64, ts: i64 },
+    Purchase { venture_id: Uuid, amount: f64, ts: i64 },
+    Revenue { venture_id: Uuid, amount: f64, ts: i64 },
+    /// Cambio en la posición del propio usuario
+    Portfolio { venture_id: Uuid, invested_delta: f64, ts: i64 },
+    Subscribed { venture_id: Option<Uuid> },
+    Unsubscribed { venture_id: Option<Uuid> },
+    Error { message: String },
+}
+
+/// Motivo por el que el hub cierra una conexión
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedCloseReason {
+    SlowConsumer,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum FeedError {
+    #[error("Connection not found")]
+    ConnectionNotFound,
+    #[error("Subscription limit of {0} ventures reached")]
+    SubscriptionLimitReached(usize),
+}
+
+/// Extremo de la conexión que usa el socket
+pub struct FeedConnection {
+    pub id: Uuid,
+    pub messages: mpsc::Receiver<FeedMessage>,
+    pub closed: oneshot::Receiver<FeedCloseReason>,
+}
+
+struct ConnectionEntry {
+    user_id: Uuid,
+    sender: mpsc::Sender<FeedMessage>,
+    ventures: HashSet<Uuid>,
+    portfolio: bool,
+    close: Option<oneshot::Sender<FeedCloseReason>>,
+}
+
+enum Audience {
+    Venture(Uuid),
+    Portfolio(Uuid),
+}
+
+impl ConnectionEntry {
+    fn wants(&self, audience: &Audience) -> bool {
+        match audience {
+            Audience::Venture(venture_id) => self.ventures.contains(venture_id),
+            Audience::Portfolio(user_id) => self.portfolio && self.user_id == *user_id,
+        }
+    }
+}
+
+fn timestamp(at: &DateTime<Utc>) -> i64 {
+    at.timestamp_millis()
+}
+
+pub struct MarketFeed {
+    connections: RwLock<HashMap<Uuid, ConnectionEntry>>,
+    /// Último precio conocido por venture, para calcular la variación
+    last_prices: RwLock<HashMap<Uuid, f64>>,
+}
+
+impl Default for MarketFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MarketFeed {
+    pub fn new() -> Self {
+        Self {
+            connections: RwLock::new(HashMap::new()),
+            last_prices: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub async fn register(&self, user_id: Uuid) -> FeedConnection {
+        let id = Uuid::new_v4();
+        let (sender, messages) = mpsc::channel(OUTBOUND_BUFFER_SIZE);
+        let (close, closed) = oneshot::channel();
+
+        self.connections.write().await.insert(id, ConnectionEntry {
+            user_id,
+            sender,
+            ventures: HashSet::new(),
+            portfolio: false,
+            close: Some(close),
+        });
+
+        FeedConnection { id, messages, closed }
+    }
+
+    pub async fn unregister(&self, connection_id: Uuid) {
+        self.connections.write().await.remove(&connection_id);
+    }
+
+    pub async fn connection_count(&self) -> usize {
+        self.connections.read().await.len()
+    }
+
+    /// Aplicar un comando del cliente y devolver la confirmación a enviarle
+    pub async fn apply(&self, connection_id: Uuid, command: FeedCommand) -> Result<FeedMessage, FeedError> {
+        let mut connections = self.connections.write().await;
+        let entry = connections.get_mut(&connection_id).ok_or(FeedError::ConnectionNotFound)?;
+
+        Ok(match command {
+            FeedCommand::Subscribe { venture_id } => {
+                if !entry.ventures.contains(&venture_id) && entry.ventures.len() >= MAX_SUBSCRIPTIONS_PER_CONNECTION {
+                    return Err(FeedError::SubscriptionLimitReached(MAX_SUBSCRIPTIONS_PER_CONNECTION));
+                }
+                entry.ventures.insert(venture_id);
+                FeedMessage::Subscribed { venture_id: Some(venture_id) }
+            }
+            FeedCommand::Unsubscribe { venture_id } => {
+                entry.ventures.remove(&venture_id);
+                FeedMessage::Unsubscribed { venture_id: Some(venture_id) }
+            }
+            FeedCommand::SubscribePortfolio => {
+                entry.portfolio = true;
+                FeedMessage::Subscribed { venture_id: None }
+            }
+            FeedCommand::UnsubscribePortfolio => {
+                entry.portfolio = false;
+                FeedMessage::Unsubscribed { venture_id: None }
+            }
+        })
+    }
+
+    /// Entregar un mensaje sin bloquear; las conexiones con el buffer lleno se cierran
+    async fn deliver(&self, audience: Audience, message: FeedMessage) {
+        let mut dropped = Vec::new();
+        {
+            let connections = self.connections.read().await;
+            for (id, entry) in connections.iter().filter(|(_, entry)| entry.wants(&audience)) {
+                match entry.sender.try_send(message.clone()) {
+                    Ok(()) => {}
+                    Err(mpsc::error::TrySendError::Full(_)) => dropped.push((*id, Some(FeedCloseReason::SlowConsumer))),
+                    Err(mpsc::error::TrySendError::Closed(_)) => dropped.push((*id, None)),
+                }
+            }
+        }
+
+        if dropped.is_empty() {
+            return;
+        }
+        let mut connections = self.connections.write().await;
+        for (id, reason) in dropped {
+            if let Some(mut entry) = connections.remove(&id) {
+                if let (Some(reason), Some(close)) = (reason, entry.close.take()) {
+                    tracing::warn!("Dropping slow market feed consumer {} (user {})", id, entry.user_id);
+                    let _ = close.send(reason);
+                }
+            }
+        }
+    }
+
+    async fn publish_price(&self, venture_id: Uuid, price: f64, previous_price: Option<f64>, at: &DateTime<Utc>) {
+        let previous_price = match previous_price {
+            Some(previous) => Some(previous),
+            None => self.last_prices.read().await.get(&venture_id).copied(),
+        };
+        self.last_prices.write().await.insert(venture_id, price);
+
+        let change_percentage = match previous_price {
+            Some(previous) if previous > 0.0 => (price - previous) / previous * 100.0,
+            _ => 0.0,
+        };
+        self.deliver(Audience::Venture(venture_id), FeedMessage::Price {
+            venture_id,
+            price,
+            change_percentage,
+            ts: timestamp(at),
+        })
+        .await;
+    }
+}
+
+#[async_trait]
+impl EventHandler for MarketFeed {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        match event {
+            DomainEvent::SharePriceUpdated { venture_id, price, previous_price, occurred_at } => {
+                self.publish_price(*venture_id, *price, Some(*previous_price), occurred_at).await;
+            }
+            // Una compra marca el último precio de ejecución del venture
+            DomainEvent::InvestmentMade { venture_id, investor_id, amount, occurred_at } => {
+                let ts = timestamp(occurred_at);
+                self.deliver(Audience::Venture(*venture_id), FeedMessage::Purchase {
+                    venture_id: *venture_id,
+                    amount: *amount,
+                    ts,
+                })
+                .await;
+                self.publish_price(*venture_id, *amount, None, occurred_at).await;
+                self.deliver(Audience::Portfolio(*investor_id), FeedMessage::Portfolio {
+                    venture_id: *venture_id,
+                    invested_delta: *amount,
+                    ts,
+                })
+                .await;
+            }
+            DomainEvent::RevenueDistributed { venture_id, amount, occurred_at, .. } => {
+                self.deliver(Audience::Venture(*venture_id), FeedMessage::Revenue {
+                    venture_id: *venture_id,
+                    amount: *amount,
+                    ts: timestamp(occurred_at),
+                })
+                .await;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn purchase(venture_id: Uuid, investor_id: Uuid, amount: f64) -> DomainEvent {
+        DomainEvent::InvestmentMade { venture_id, investor_id, amount, occurred_at: Utc::now() }
+    }
+
+    #[tokio::test]
+    async fn subscription_limit_is_enforced() {
+        let feed = MarketFeed::new();
+        let connection = feed.register(Uuid::new_v4()).await;
+
+        for _ in 0..MAX_SUBSCRIPTIONS_PER_CONNECTION {
+            feed.apply(connection.id, FeedCommand::Subscribe { venture_id: Uuid::new_v4() }).await.unwrap();
+        }
+        let result = feed.apply(connection.id, FeedCommand::Subscribe { venture_id: Uuid::new_v4() }).await;
+
+        assert_eq!(result, Err(FeedError::SubscriptionLimitReached(MAX_SUBSCRIPTIONS_PER_CONNECTION)));
+    }
+
+    #[tokio::test]
+    async fn portfolio_updates_only_reach_the_investor() {
+        let feed = MarketFeed::new();
+        let investor = Uuid::new_v4();
+        let mut mine = feed.register(investor).await;
+        let mut other = feed.register(Uuid::new_v4()).await;
+        feed.apply(mine.id, FeedCommand::SubscribePortfolio).await.unwrap();
+        feed.apply(other.id, FeedCommand::SubscribePortfolio).await.unwrap();
+
+        let venture_id = Uuid::new_v4();
+        feed.handle(&purchase(venture_id, investor, 25.0)).await.unwrap();
+
+        assert!(matches!(
+            mine.messages.try_recv().unwrap(),
+            FeedMessage::Portfolio { venture_id: v, invested_delta, .. } if v == venture_id && invested_delta == 25.0
+        ));
+        assert!(other.messages.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn price_change_is_relative_to_last_purchase() {
+        let feed = MarketFeed::new();
+        let mut connection = feed.register(Uuid::new_v4()).await;
+        let venture_id = Uuid::new_v4();
+        feed.apply(connection.id, FeedCommand::Subscribe { venture_id }).await.unwrap();
+
+        feed.handle(&purchase(venture_id, Uuid::new_v4(), 100.0)).await.unwrap();
+        feed.handle(&purchase(venture_id, Uuid::new_v4(), 150.0)).await.unwrap();
+
+        let prices: Vec<f64> = std::iter::from_fn(|| connection.messages.try_recv().ok())
+            .filter_map(|message| match message {
+                FeedMessage::Price { change_percentage, .. } => Some(change_percentage),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(prices, vec![0.0, 50.0]);
+    }
+
+    #[tokio::test]
+    async fn slow_consumers_are_dropped_instead_of_buffered() {
+        let feed = MarketFeed::new();
+        let mut connection = feed.register(Uuid::new_v4()).await;
+        let venture_id = Uuid::new_v4();
+        feed.apply(connection.id, FeedCommand::Subscribe { venture_id }).await.unwrap();
+
+        // Cada compra genera dos mensajes para el suscriptor del venture
+        for _ in 0..OUTBOUND_BUFFER_SIZE {
+            feed.handle(&purchase(venture_id, Uuid::new_v4(), 10.0)).await.unwrap();
+        }
+
+        assert_eq!(connection.closed.try_recv(), Ok(FeedCloseReason::SlowConsumer));
+        assert_eq!(feed.connection_count().await, 0);
+    }
+}
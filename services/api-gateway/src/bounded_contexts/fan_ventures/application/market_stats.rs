@@ -0,0 +1,443 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Row};
+use tokio::sync::RwLock;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_ventures::infrastructure::genre_read_model::PostgresArtistGenreReadModel;
+use crate::shared::domain::errors::AppError;
+
+// =============================================================================
+// FAN VENTURES - MARKET STATISTICS
+// =============================================================================
+
+/// Ventana por defecto; es la única que se cachea
+pub const DEFAULT_PERIOD_DAYS: u32 = 30;
+const MAX_PERIOD_DAYS: u32 = 365;
+const CACHE_TTL: chrono::Duration = chrono::Duration::hours(1);
+const TRENDING_LIMIT: usize = 10;
+const UNKNOWN_GENRE: &str = "unknown";
+
+/// Pesos del trending score. Cada componente se normaliza contra el máximo
+/// del conjunto (0..1), así que el score final también queda en 0..1.
+/// - volumen: lo que más pesa, refleja demanda real
+/// - cambio de precio: subidas del ticket medio frente al periodo anterior (las bajadas cuentan 0)
+/// - compradores únicos: evita que una sola ballena domine el ranking
+pub const TRENDING_VOLUME_WEIGHT: f64 = 0.5;
+pub const TRENDING_PRICE_CHANGE_WEIGHT: f64 = 0.3;
+pub const TRENDING_UNIQUE_BUYERS_WEIGHT: f64 = 0.2;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GetMarketStatsQuery {
+    pub period_days: u32,
+    pub include_genre_breakdown: bool,
+    pub include_trending_songs: bool,
+}
+
+impl Default for GetMarketStatsQuery {
+    fn default() -> Self {
+        Self {
+            period_days: DEFAULT_PERIOD_DAYS,
+            include_genre_breakdown: false,
+            include_trending_songs: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GenreStats {
+    pub genre: String,
+    pub volume: f64,
+    pub previous_volume: f64,
+    /// Variación de volumen frente al periodo anterior de igual duración
+    pub growth_percentage: f64,
+    pub venture_count: u32,
+    pub investment_count: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TrendingSongItem {
+    pub venture_id: Uuid,
+    pub title: String,
+    pub genre: String,
+    pub volume: f64,
+    /// Variación del ticket medio de inversión frente al periodo anterior
+    pub price_change_percentage: f64,
+    pub unique_buyers: u32,
+    pub trending_score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MarketStatsResult {
+    pub period_days: u32,
+    pub total_volume: f64,
+    pub total_investments: u32,
+    pub unique_investors: u32,
+    pub active_ventures: u32,
+    /// Comisiones de plataforma de los pagos completados en el periodo
+    #[schema(value_type = String, example = "1250.40")]
+    pub platform_revenue_total: Decimal,
+    pub genre_breakdown: Option<Vec<GenreStats>>,
+    pub trending_songs: Option<Vec<TrendingSongItem>>,
+    pub generated_at: DateTime<Utc>,
+}
+
+/// Actividad agregada de un venture en el periodo actual y el anterior
+#[derive(Debug, Clone)]
+pub struct VentureActivity {
+    pub venture_id: Uuid,
+    pub title: String,
+    pub genre: String,
+    pub current_volume: f64,
+    pub current_investments: u32,
+    pub unique_buyers: u32,
+    pub previous_volume: f64,
+    pub previous_investments: u32,
+}
+
+impl VentureActivity {
+    fn average_ticket(volume: f64, investments: u32) -> Option<f64> {
+        (investments > 0).then(|| volume / investments as f64)
+    }
+
+    /// Cambio porcentual del ticket medio; 0 si no hay referencia en alguno de los periodos
+    pub fn price_change_percentage(&self) -> f64 {
+        match (
+            Self::average_ticket(self.previous_volume, self.previous_investments),
+            Self::average_ticket(self.current_volume, self.current_investments),
+        ) {
+            (Some(previous), Some(current)) if previous > 0.0 => (current - previous) / previous * 100.0,
+            _ => 0.0,
+        }
+    }
+}
+
+/// Crecimiento porcentual; un género sin volumen previo que empieza a moverse cuenta como +100%
+pub fn growth_percentage(current: f64, previous: f64) -> f64 {
+    if previous > 0.0 {
+        (current - previous) / previous * 100.0
+    } else if current > 0.0 {
+        100.0
+    } else {
+        0.0
+    }
+}
+
+/// Agrupar actividad por género, ordenado por volumen actual (desc)
+pub fn compute_genre_breakdown(activity: &[VentureActivity]) -> Vec<GenreStats> {
+    let mut by_genre: HashMap<&str, GenreStats> = HashMap::new();
+    for venture in activity {
+        let stats = by_genre.entry(venture.genre.as_str()).or_insert_with(|| GenreStats {
+            genre: venture.genre.clone(),
+            volume: 0.0,
+            previous_volume: 0.0,
+            growth_percentage: 0.0,
+            venture_count: 0,
+            investment_count: 0,
+        });
+        stats.volume += venture.current_volume;
+        stats.previous_volume += venture.previous_volume;
+        stats.investment_count += venture.current_investments;
+        if venture.current_investments > 0 {
+            stats.venture_count += 1;
+        }
+    }
+
+    let mut breakdown: Vec<GenreStats> = by_genre
+        .into_values()
+        .map(|mut stats| {
+            stats.growth_percentage = growth_percentage(stats.volume, stats.previous_volume);
+            stats
+        })
+        .collect();
+    breakdown.sort_by(|a, b| b.volume.total_cmp(&a.volume).then_with(|| a.genre.cmp(&b.genre)));
+    breakdown
+}
+
+/// Ranking de ventures con actividad en el periodo según `TRENDING_*_WEIGHT`
+pub fn compute_trending_songs(activity: &[VentureActivity], limit: usize) -> Vec<TrendingSongItem> {
+    let active: Vec<&VentureActivity> = activity.iter().filter(|v| v.current_investments > 0).collect();
+
+    let max_volume = active.iter().map(|v| v.current_volume).fold(0.0, f64::max);
+    let max_price_change = active.iter().map(|v| v.price_change_percentage().max(0.0)).fold(0.0, f64::max);
+    let max_buyers = active.iter().map(|v| v.unique_buyers).max().unwrap_or(0) as f64;
+
+    let normalize = |value: f64, max: f64| if max > 0.0 { value / max } else { 0.0 };
+
+    let mut items: Vec<TrendingSongItem> = active
+        .into_iter()
+        .map(|venture| {
+            let price_change = venture.price_change_percentage();
+            let trending_score = TRENDING_VOLUME_WEIGHT * normalize(venture.current_volume, max_volume)
+                + TRENDING_PRICE_CHANGE_WEIGHT * normalize(price_change.max(0.0), max_price_change)
+                + TRENDING_UNIQUE_BUYERS_WEIGHT * normalize(venture.unique_buyers as f64, max_buyers);
+
+            TrendingSongItem {
+                venture_id: venture.venture_id,
+                title: venture.title.clone(),
+                genre: venture.genre.clone(),
+                volume: venture.current_volume,
+                price_change_percentage: price_change,
+                unique_buyers: venture.unique_buyers,
+                trending_score,
+            }
+        })
+        .collect();
+
+    items.sort_by(|a, b| b.trending_score.total_cmp(&a.trending_score));
+    items.truncate(limit);
+    items
+}
+
+struct CachedStats {
+    result: MarketStatsResult,
+    cached_at: DateTime<Utc>,
+}
+
+/// Servicio de estadísticas de mercado. El género de cada venture se toma del
+/// read model local (`artist_genre_read_model`), nunca de las tablas de Music.
+pub struct MarketStatsService {
+    pool: PgPool,
+    genre_read_model: Arc<PostgresArtistGenreReadModel>,
+    cache: RwLock<Option<CachedStats>>,
+}
+
+impl MarketStatsService {
+    pub fn new(pool: PgPool, genre_read_model: Arc<PostgresArtistGenreReadModel>) -> Self {
+        Self {
+            pool,
+            genre_read_model,
+            cache: RwLock::new(None),
+        }
+    }
+
+    pub async fn get_market_stats(&self, query: GetMarketStatsQuery) -> Result<MarketStatsResult, AppError> {
+        if query.period_days == 0 || query.period_days > MAX_PERIOD_DAYS {
+            return Err(AppError::ValidationError(format!(
+                "period_days must be between 1 and {}", MAX_PERIOD_DAYS
+            )));
+        }
+
+        let mut result = if query.period_days == DEFAULT_PERIOD_DAYS {
+            match self.cached(Utc::now()).await {
+                Some(result) => result,
+                None => self.refresh().await?,
+            }
+        } else {
+            self.compute(query.period_days).await?
+        };
+
+        if !query.include_genre_breakdown {
+            result.genre_breakdown = None;
+        }
+        if !query.include_trending_songs {
+            result.trending_songs = None;
+        }
+        Ok(result)
+    }
+
+    /// Recalcular y cachear las estadísticas de la ventana por defecto
+    pub async fn refresh(&self) -> Result<MarketStatsResult, AppError> {
+        let result = self.compute(DEFAULT_PERIOD_DAYS).await?;
+        *self.cache.write().await = Some(CachedStats {
+            result: result.clone(),
+            cached_at: result.generated_at,
+        });
+        Ok(result)
+    }
+
+    async fn cached(&self, now: DateTime<Utc>) -> Option<MarketStatsResult> {
+        self.cache
+            .read()
+            .await
+            .as_ref()
+            .filter(|cached| now - cached.cached_at < CACHE_TTL)
+            .map(|cached| cached.result.clone())
+    }
+
+    async fn compute(&self, period_days: u32) -> Result<MarketStatsResult, AppError> {
+        let now = Utc::now();
+        let period_start = now - chrono::Duration::days(period_days as i64);
+        let previous_start = period_start - chrono::Duration::days(period_days as i64);
+
+        let activity = self.load_activity(period_start, previous_start).await?;
+
+        let unique_investors: i64 = sqlx::query_scalar(
+            "SELECT COUNT(DISTINCT fan_id) FROM fan_investments WHERE status = 'confirmed' AND created_at >= $1",
+        )
+        .bind(period_start)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to count investors: {}", e)))?;
+
+        let platform_revenue_total: Decimal = sqlx::query_scalar(
+            "SELECT COALESCE(SUM(platform_share), 0) FROM payments WHERE status = 'Completed' AND created_at >= $1",
+        )
+        .bind(period_start)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to sum platform revenue: {}", e)))?;
+
+        Ok(MarketStatsResult {
+            period_days,
+            total_volume: activity.iter().map(|v| v.current_volume).sum(),
+            total_investments: activity.iter().map(|v| v.current_investments).sum(),
+            unique_investors: unique_investors as u32,
+            active_ventures: activity.iter().filter(|v| v.current_investments > 0).count() as u32,
+            platform_revenue_total,
+            genre_breakdown: Some(compute_genre_breakdown(&activity)),
+            trending_songs: Some(compute_trending_songs(&activity, TRENDING_LIMIT)),
+            generated_at: now,
+        })
+    }
+
+    async fn load_activity(&self, period_start: DateTime<Utc>, previous_start: DateTime<Utc>) -> Result<Vec<VentureActivity>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT v.id, v.title, v.artist_id,
+                   COALESCE(SUM(fi.investment_amount) FILTER (WHERE fi.created_at >= $1), 0) AS current_volume,
+                   COUNT(fi.id) FILTER (WHERE fi.created_at >= $1) AS current_investments,
+                   COUNT(DISTINCT fi.fan_id) FILTER (WHERE fi.created_at >= $1) AS unique_buyers,
+                   COALESCE(SUM(fi.investment_amount) FILTER (WHERE fi.created_at < $1), 0) AS previous_volume,
+                   COUNT(fi.id) FILTER (WHERE fi.created_at < $1) AS previous_investments
+            FROM artist_ventures v
+            JOIN fan_investments fi ON fi.venture_id = v.id
+            WHERE fi.status = 'confirmed' AND fi.created_at >= $2
+            GROUP BY v.id, v.title, v.artist_id
+            "#,
+        )
+        .bind(period_start)
+        .bind(previous_start)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load market activity: {}", e)))?;
+
+        let genres = self.genre_read_model.dominant_genres().await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let artist_id: Uuid = row.get("artist_id");
+                VentureActivity {
+                    venture_id: row.get("id"),
+                    title: row.get("title"),
+                    genre: genres.get(&artist_id).cloned().unwrap_or_else(|| UNKNOWN_GENRE.to_string()),
+                    current_volume: row.get("current_volume"),
+                    current_investments: row.get::<i64, _>("current_investments") as u32,
+                    unique_buyers: row.get::<i64, _>("unique_buyers") as u32,
+                    previous_volume: row.get("previous_volume"),
+                    previous_investments: row.get::<i64, _>("previous_investments") as u32,
+                }
+            })
+            .collect())
+    }
+}
+
+/// Worker que refresca la caché de estadísticas de mercado
+pub struct MarketStatsRefreshJob {
+    service: Arc<MarketStatsService>,
+    interval: Duration,
+}
+
+impl MarketStatsRefreshJob {
+    pub fn new(service: Arc<MarketStatsService>, interval: Duration) -> Self {
+        Self { service, interval }
+    }
+
+    pub fn start(&self) -> tokio::task::JoinHandle<()> {
+        let service = Arc::clone(&self.service);
+        let interval = self.interval;
+
+        tokio::spawn(async move {
+            tracing::info!("🚀 Market stats refresh job started");
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = service.refresh().await {
+                    tracing::error!("Market stats refresh failed: {:?}", e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activity(genre: &str, current: (f64, u32), previous: (f64, u32), unique_buyers: u32) -> VentureActivity {
+        VentureActivity {
+            venture_id: Uuid::new_v4(),
+            title: format!("{} venture", genre),
+            genre: genre.to_string(),
+            current_volume: current.0,
+            current_investments: current.1,
+            unique_buyers,
+            previous_volume: previous.0,
+            previous_investments: previous.1,
+        }
+    }
+
+    #[test]
+    fn genre_breakdown_orders_by_volume_and_computes_growth() {
+        let seeded = vec![
+            activity("rock", (300.0, 3), (200.0, 2), 3),
+            activity("rock", (200.0, 2), (300.0, 3), 2),
+            activity("jazz", (900.0, 3), (300.0, 3), 3),
+        ];
+
+        let breakdown = compute_genre_breakdown(&seeded);
+
+        assert_eq!(breakdown.len(), 2);
+        assert_eq!(breakdown[0].genre, "jazz");
+        assert_eq!(breakdown[0].volume, 900.0);
+        assert!((breakdown[0].growth_percentage - 200.0).abs() < 1e-9);
+        assert_eq!(breakdown[1].genre, "rock");
+        assert_eq!(breakdown[1].volume, 500.0);
+        assert_eq!(breakdown[1].venture_count, 2);
+        assert_eq!(breakdown[1].investment_count, 5);
+        assert!(breakdown[1].growth_percentage.abs() < 1e-9);
+    }
+
+    #[test]
+    fn growth_without_previous_volume() {
+        assert_eq!(growth_percentage(150.0, 0.0), 100.0);
+        assert_eq!(growth_percentage(0.0, 0.0), 0.0);
+        assert!((growth_percentage(50.0, 200.0) + 75.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn trending_score_uses_documented_weights() {
+        // Mayor volumen pero ticket medio estable
+        let whale = activity("rock", (1000.0, 1), (1000.0, 1), 1);
+        // Menos volumen, ticket medio duplicado y más compradores
+        let rising = activity("jazz", (500.0, 5), (50.0, 1), 5);
+
+        let trending = compute_trending_songs(&[whale.clone(), rising.clone()], 10);
+
+        assert_eq!(trending[0].venture_id, rising.venture_id);
+        // 0.5 * 0.5 + 0.3 * 1.0 + 0.2 * 1.0
+        assert!((trending[0].trending_score - 0.75).abs() < 1e-9);
+        assert!((trending[0].price_change_percentage - 100.0).abs() < 1e-9);
+        // 0.5 * 1.0 + 0.3 * 0.0 + 0.2 * 0.2
+        assert!((trending[1].trending_score - 0.54).abs() < 1e-9);
+    }
+
+    #[test]
+    fn trending_skips_ventures_without_current_activity() {
+        let stale = activity("rock", (0.0, 0), (400.0, 4), 0);
+        let active = activity("jazz", (100.0, 1), (0.0, 0), 1);
+
+        let trending = compute_trending_songs(&[stale, active.clone()], 10);
+
+        assert_eq!(trending.len(), 1);
+        assert_eq!(trending[0].venture_id, active.venture_id);
+        assert_eq!(trending[0].price_change_percentage, 0.0);
+    }
+}
@@ -4,10 +4,24 @@
 
 pub mod simple_service;
 pub mod services;
+pub mod proposal_service;
+pub mod market_stats;
+pub mod market_feed;
+pub mod escrow_service;
+pub mod purchase_shares;
+pub mod purchase_saga;
+pub mod transfer_shares;
 
 // Re-export the fan ventures service
 pub use simple_service::{
     FanVenturesService,
     FanPortfolio,
     VentureAnalytics,
-}; 
\ No newline at end of file
+};
+pub use proposal_service::{ProposalService, ProposalFinalizationJob, NewProposal};
+pub use market_stats::{MarketStatsService, MarketStatsRefreshJob, GetMarketStatsQuery, MarketStatsResult};
+pub use market_feed::MarketFeed;
+pub use escrow_service::{InvestmentEscrowService, ReservationExpiryJob, EscrowPayments};
+pub use purchase_shares::{PurchaseSharesCommand, PurchaseSharesCommandHandler, ShareReservations};
+pub use purchase_saga::{SharePurchaseSaga, SharePurchaseSagaConfig, SharePurchaseCompensations, SHARE_PURCHASE_SAGA};
+pub use transfer_shares::{TransferSharesCommand, TransferSharesCommandHandler, ShareTransferPayments};
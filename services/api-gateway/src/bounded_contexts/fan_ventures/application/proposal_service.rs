@@ -0,0 +1,344 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_ventures::domain::proposals::{Proposal, TallyVisibility, Vote, VotingSnapshot};
+use crate::bounded_contexts::fan_ventures::domain::repositories::{ProposalRepository, VentureRepository};
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventBus};
+use crate::shared::domain::errors::AppError;
+
+// =============================================================================
+// FAN VENTURES - SHAREHOLDER VOTING SERVICE
+// =============================================================================
+
+/// Datos para crear una propuesta
+#[derive(Debug, Clone)]
+pub struct NewProposal {
+    pub title: String,
+    pub description: Option<String>,
+    pub options: Vec<String>,
+    pub deadline: DateTime<Utc>,
+    pub quorum_percentage: f64,
+    pub tally_visibility: TallyVisibility,
+}
+
+pub struct ProposalService {
+    proposal_repository: Arc<dyn ProposalRepository>,
+    venture_repository: Arc<dyn VentureRepository>,
+    event_bus: Arc<dyn EventBus>,
+}
+
+impl ProposalService {
+    pub fn new(
+        proposal_repository: Arc<dyn ProposalRepository>,
+        venture_repository: Arc<dyn VentureRepository>,
+        event_bus: Arc<dyn EventBus>,
+    ) -> Self {
+        Self {
+            proposal_repository,
+            venture_repository,
+            event_bus,
+        }
+    }
+
+    /// Crear una propuesta. Solo el artista dueño del venture puede hacerlo;
+    /// el poder de voto se congela con las inversiones confirmadas en este momento.
+    pub async fn create_proposal(&self, venture_id: Uuid, artist_id: Uuid, request: NewProposal) -> Result<Proposal, AppError> {
+        let venture = self.venture_repository.get_venture(venture_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Venture {} not found", venture_id)))?;
+
+        if venture.artist_id != artist_id {
+            return Err(AppError::Forbidden("Only the venture's artist can create proposals".to_string()));
+        }
+
+        let investments = self.venture_repository.get_fan_investments_by_venture(venture_id).await?;
+        let snapshot = VotingSnapshot::from_investments(&investments, Utc::now());
+
+        let proposal = Proposal::new(
+            venture_id,
+            artist_id,
+            request.title,
+            request.description,
+            request.options,
+            request.deadline,
+            request.quorum_percentage,
+            request.tally_visibility,
+            snapshot,
+        )?;
+
+        self.proposal_repository.create(&proposal).await?;
+        tracing::info!("🗳️ Proposal {} created for venture {} ({} shareholders)",
+            proposal.id, venture_id, proposal.snapshot.weights.len());
+
+        Ok(proposal)
+    }
+
+    /// Emitir un voto en nombre de un accionista
+    pub async fn vote(&self, proposal_id: Uuid, voter_id: Uuid, option_id: Uuid) -> Result<Vote, AppError> {
+        let mut proposal = self.load(proposal_id).await?;
+
+        // Si el plazo venció, cerramos la propuesta antes de rechazar el voto
+        if proposal.is_due(Utc::now()) {
+            self.finalize(&mut proposal).await?;
+        }
+
+        let vote = proposal.cast_vote(voter_id, option_id, Utc::now())?;
+        self.proposal_repository.record_vote(&proposal_id, &vote).await?;
+
+        Ok(vote)
+    }
+
+    /// Obtener una propuesta, finalizándola si su plazo ya venció
+    pub async fn get_proposal(&self, proposal_id: Uuid) -> Result<Proposal, AppError> {
+        let mut proposal = self.load(proposal_id).await?;
+        if proposal.is_due(Utc::now()) {
+            self.finalize(&mut proposal).await?;
+        }
+        Ok(proposal)
+    }
+
+    pub async fn list_for_venture(&self, venture_id: Uuid) -> Result<Vec<Proposal>, AppError> {
+        let mut proposals = self.proposal_repository.find_by_venture(&venture_id).await?;
+        let now = Utc::now();
+        for proposal in proposals.iter_mut().filter(|p| p.is_due(now)) {
+            self.finalize(proposal).await?;
+        }
+        Ok(proposals)
+    }
+
+    /// Cerrar todas las propuestas vencidas. Devuelve cuántas se finalizaron.
+    pub async fn finalize_due_proposals(&self) -> Result<usize, AppError> {
+        let due = self.proposal_repository.find_due(Utc::now()).await?;
+        let mut finalized = 0;
+        for mut proposal in due {
+            match self.finalize(&mut proposal).await {
+                Ok(()) => finalized += 1,
+                Err(e) => tracing::error!("Failed to finalize proposal {}: {:?}", proposal.id, e),
+            }
+        }
+        Ok(finalized)
+    }
+
+    async fn load(&self, proposal_id: Uuid) -> Result<Proposal, AppError> {
+        self.proposal_repository.find_by_id(&proposal_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Proposal {} not found", proposal_id)))
+    }
+
+    async fn finalize(&self, proposal: &mut Proposal) -> Result<(), AppError> {
+        let outcome = proposal.finalize(Utc::now())?;
+
+        match self.proposal_repository.mark_finalized(proposal).await {
+            Ok(()) => {}
+            // Otra instancia ya la cerró: recargamos el resultado persistido
+            Err(AppError::ConcurrencyConflict(_)) => {
+                *proposal = self.load(proposal.id).await?;
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        }
+
+        let event = DomainEvent::ProposalFinalized {
+            proposal_id: proposal.id,
+            venture_id: proposal.venture_id,
+            outcome: outcome.label().to_string(),
+            winning_option_id: outcome.winning_option(),
+            turnout_percentage: proposal.turnout_percentage(),
+            occurred_at: Utc::now(),
+        };
+        if let Err(e) = self.event_bus.publish(event).await {
+            tracing::warn!("Failed to publish proposal finalized event: {:?}", e);
+        }
+
+        Ok(())
+    }
+}
+
+/// Worker que finaliza propuestas cuando vence su plazo
+pub struct ProposalFinalizationJob {
+    service: Arc<ProposalService>,
+    interval: Duration,
+}
+
+impl ProposalFinalizationJob {
+    pub fn new(service: Arc<ProposalService>, interval: Duration) -> Self {
+        Self { service, interval }
+    }
+
+    pub fn start(&self) -> tokio::task::JoinHandle<()> {
+        let service = Arc::clone(&self.service);
+        let interval = self.interval;
+
+        tokio::spawn(async move {
+            tracing::info!("🚀 Proposal finalization job started");
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match service.finalize_due_proposals().await {
+                    Ok(count) if count > 0 => tracing::info!("✅ Finalized {} proposals", count),
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Proposal finalization failed: {:?}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::fan_ventures::domain::entities::{
+        ArtistVenture, FanInvestment, InvestmentStatus, InvestmentType, RiskLevel, VentureCategory, VentureStatus,
+    };
+    use crate::bounded_contexts::fan_ventures::domain::proposals::ProposalStatus;
+    use crate::bounded_contexts::fan_ventures::integration_service::InMemoryFanVenturesBoundedContext;
+
+    struct Fixture {
+        context: InMemoryFanVenturesBoundedContext,
+        venture: ArtistVenture,
+        /// Accionistas con 60 y 40 participaciones
+        holders: [Uuid; 2],
+    }
+
+    async fn fixture() -> Fixture {
+        let now = Utc::now();
+        let venture = ArtistVenture {
+            id: Uuid::new_v4(),
+            artist_id: Uuid::new_v4(),
+            title: "Debut EP".to_string(),
+            description: None,
+            category: VentureCategory::Music,
+            tags: vec![],
+            risk_level: RiskLevel::Medium,
+            expected_return: 0.0,
+            artist_rating: 0.0,
+            artist_previous_ventures: 0,
+            artist_success_rate: 0.0,
+            funding_goal: 100.0,
+            current_funding: 100.0,
+            min_investment: 10.0,
+            max_investment: None,
+            status: VentureStatus::Funded,
+            start_date: None,
+            end_date: None,
+            created_at: now,
+            updated_at: now,
+            benefits: vec![],
+        };
+        let context = InMemoryFanVenturesBoundedContext::create_for_testing();
+        context.ventures.create_venture(&venture).await.unwrap();
+
+        let holders = [Uuid::new_v4(), Uuid::new_v4()];
+        for (fan_id, shares) in holders.iter().zip([60.0, 40.0]) {
+            let investment = FanInvestment::new(
+                Uuid::new_v4(), *fan_id, venture.id, shares, InvestmentType::RevenueShare, InvestmentStatus::Active,
+            );
+            context.ventures.create_fan_investment(&investment).await.unwrap();
+        }
+        Fixture { context, venture, holders }
+    }
+
+    fn new_proposal() -> NewProposal {
+        NewProposal {
+            title: "Next single".to_string(),
+            description: None,
+            options: vec!["Ballad".to_string(), "Anthem".to_string()],
+            deadline: Utc::now() + chrono::Duration::hours(1),
+            quorum_percentage: 50.0,
+            tally_visibility: TallyVisibility::Live,
+        }
+    }
+
+    /// Adelantar el plazo de la propuesta guardada para que ya haya vencido
+    async fn pass_deadline(context: &InMemoryFanVenturesBoundedContext, proposal_id: Uuid) {
+        let mut stored = context.proposals.find_by_id(&proposal_id).await.unwrap().unwrap();
+        stored.deadline = Utc::now() - chrono::Duration::seconds(1);
+        context.proposals.create(&stored).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn finalized_proposal_publishes_its_outcome_once() {
+        let f = fixture().await;
+        let service = &f.context.proposal_service;
+        let proposal = service.create_proposal(f.venture.id, f.venture.artist_id, new_proposal()).await.unwrap();
+        let anthem = proposal.options[1].id;
+        service.vote(proposal.id, f.holders[0], anthem).await.unwrap();
+        pass_deadline(&f.context, proposal.id).await;
+
+        assert_eq!(service.finalize_due_proposals().await.unwrap(), 1);
+        assert_eq!(service.finalize_due_proposals().await.unwrap(), 0);
+
+        let events = f.context.event_publisher.get_published_events().await;
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            DomainEvent::ProposalFinalized { proposal_id, venture_id, outcome, winning_option_id, turnout_percentage, .. }
+                if *proposal_id == proposal.id && *venture_id == f.venture.id && outcome == "passed"
+                    && *winning_option_id == Some(anthem) && *turnout_percentage == 60.0
+        ));
+    }
+
+    #[tokio::test]
+    async fn proposal_without_quorum_is_published_as_such() {
+        let f = fixture().await;
+        let service = &f.context.proposal_service;
+        let proposal = service.create_proposal(f.venture.id, f.venture.artist_id, new_proposal()).await.unwrap();
+        service.vote(proposal.id, f.holders[1], proposal.options[0].id).await.unwrap();
+        pass_deadline(&f.context, proposal.id).await;
+
+        let finalized = service.get_proposal(proposal.id).await.unwrap();
+
+        assert_eq!(finalized.status, ProposalStatus::Finalized);
+        let events = f.context.event_publisher.get_published_events().await;
+        assert!(matches!(
+            events.as_slice(),
+            [DomainEvent::ProposalFinalized { outcome, winning_option_id: None, .. }] if outcome == "quorum_not_reached"
+        ));
+    }
+
+    #[tokio::test]
+    async fn shareholders_vote_only_once() {
+        let f = fixture().await;
+        let service = &f.context.proposal_service;
+        let proposal = service.create_proposal(f.venture.id, f.venture.artist_id, new_proposal()).await.unwrap();
+
+        service.vote(proposal.id, f.holders[0], proposal.options[0].id).await.unwrap();
+        assert!(service.vote(proposal.id, f.holders[0], proposal.options[1].id).await.is_err());
+
+        let stored = service.get_proposal(proposal.id).await.unwrap();
+        assert_eq!(stored.votes.len(), 1);
+        assert!(f.context.event_publisher.get_published_events().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn votes_cast_on_a_stale_copy_are_rejected_once_finalized() {
+        let f = fixture().await;
+        let service = &f.context.proposal_service;
+        let proposal = service.create_proposal(f.venture.id, f.venture.artist_id, new_proposal()).await.unwrap();
+        let mut stale = f.context.proposals.find_by_id(&proposal.id).await.unwrap().unwrap();
+
+        // Otra petición finaliza la propuesta entre la lectura y el registro del voto
+        pass_deadline(&f.context, proposal.id).await;
+        assert_eq!(service.finalize_due_proposals().await.unwrap(), 1);
+
+        let vote = stale.cast_vote(f.holders[0], proposal.options[0].id, Utc::now()).unwrap();
+        let result = f.context.proposals.record_vote(&proposal.id, &vote).await;
+
+        assert!(matches!(result, Err(AppError::InvalidState(_))));
+        let stored = f.context.proposals.find_by_id(&proposal.id).await.unwrap().unwrap();
+        assert_eq!(stored.status, ProposalStatus::Finalized);
+        assert!(stored.votes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn only_the_artist_can_create_proposals() {
+        let f = fixture().await;
+
+        let result = f.context.proposal_service.create_proposal(f.venture.id, f.holders[0], new_proposal()).await;
+
+        assert!(matches!(result, Err(AppError::Forbidden(_))));
+        assert!(f.context.proposals.find_by_venture(&f.venture.id).await.unwrap().is_empty());
+        assert!(f.context.event_publisher.get_published_events().await.is_empty());
+    }
+}
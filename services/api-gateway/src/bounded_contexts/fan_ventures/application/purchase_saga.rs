@@ -0,0 +1,660 @@
+// Saga de compra de participaciones
+//
+// Una compra cruza tres contextos: fan_ventures reserva las participaciones,
+// payment cobra y blockchain registra la compra on-chain. La saga (una
+// instancia por inversión) sigue esos pasos a partir de sus eventos y, si el
+// cobro falla o un paso no llega antes de su plazo, libera la reserva y
+// devuelve lo cobrado. Cada compensación ejecutada se persiste, así que tras un
+// reinicio solo se repite lo que faltaba.
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_ventures::application::escrow_service::{
+    InvestmentEscrowService, DEFAULT_RESERVATION_TIMEOUT_SECS,
+};
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::saga::{SagaInstance, SagaStatus, SagaStore, SagaTimeouts};
+
+pub const SHARE_PURCHASE_SAGA: &str = "share_purchase";
+
+const DEFAULT_RETRY_DELAY_SECS: i64 = 60;
+const DEFAULT_MAX_COMPENSATION_ATTEMPTS: i32 = 5;
+const TIMEOUT_BATCH_SIZE: i64 = 100;
+const MAX_CONFLICT_RETRIES: usize = 3;
+
+/// Paso al que avanzar y su plazo
+type NextStep = (SharePurchaseStep, Option<DateTime<Utc>>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SharePurchaseStep {
+    AwaitingPayment,
+    AwaitingSettlement,
+    Completed,
+    Compensating,
+    Compensated,
+    Failed,
+}
+
+impl SharePurchaseStep {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SharePurchaseStep::AwaitingPayment => "awaiting_payment",
+            SharePurchaseStep::AwaitingSettlement => "awaiting_settlement",
+            SharePurchaseStep::Completed => "completed",
+            SharePurchaseStep::Compensating => "compensating",
+            SharePurchaseStep::Compensated => "compensated",
+            SharePurchaseStep::Failed => "failed",
+        }
+    }
+
+    pub fn status(&self) -> SagaStatus {
+        match self {
+            SharePurchaseStep::AwaitingPayment | SharePurchaseStep::AwaitingSettlement => SagaStatus::Running,
+            SharePurchaseStep::Completed => SagaStatus::Completed,
+            SharePurchaseStep::Compensating => SagaStatus::Compensating,
+            SharePurchaseStep::Compensated => SagaStatus::Compensated,
+            SharePurchaseStep::Failed => SagaStatus::Failed,
+        }
+    }
+
+    pub fn of(instance: &SagaInstance) -> Result<Self, AppError> {
+        instance.step.parse().map_err(AppError::SerializationError)
+    }
+
+    fn enter(&self, instance: &mut SagaInstance, deadline_at: Option<DateTime<Utc>>) {
+        instance.step = self.as_str().to_string();
+        instance.status = self.status();
+        instance.deadline_at = deadline_at;
+    }
+}
+
+impl std::str::FromStr for SharePurchaseStep {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "awaiting_payment" => Ok(SharePurchaseStep::AwaitingPayment),
+            "awaiting_settlement" => Ok(SharePurchaseStep::AwaitingSettlement),
+            "completed" => Ok(SharePurchaseStep::Completed),
+            "compensating" => Ok(SharePurchaseStep::Compensating),
+            "compensated" => Ok(SharePurchaseStep::Compensated),
+            "failed" => Ok(SharePurchaseStep::Failed),
+            other => Err(format!("Unknown share purchase step: {}", other)),
+        }
+    }
+}
+
+/// Estado propio de la saga, guardado en `SagaInstance::data`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SharePurchaseState {
+    pub investment_id: Uuid,
+    pub venture_id: Uuid,
+    pub fan_id: Uuid,
+    pub shares: f64,
+    pub payment_id: Option<Uuid>,
+    pub payment_completed: bool,
+    /// El cobro falló: no hay nada que devolver
+    pub payment_failed: bool,
+    pub transaction_hash: Option<String>,
+    pub failure_reason: Option<String>,
+    pub reservation_released: bool,
+    /// Pago reembolsado o retención anulada
+    pub payment_refunded: bool,
+}
+
+impl SharePurchaseState {
+    fn needs_refund(&self) -> bool {
+        self.payment_id.is_some() && !self.payment_refunded && (self.payment_completed || !self.payment_failed)
+    }
+}
+
+/// Comandos de compensación. Ambos deben poder repetirse sin efecto: tras un
+/// reinicio se reintenta la compensación que no llegó a persistirse.
+#[async_trait]
+pub trait SharePurchaseCompensations: Send + Sync {
+    async fn release_reservation(&self, investment_id: Uuid, reason: &str) -> Result<(), AppError>;
+
+    /// Devolver el pago, o anular la retención si aún no se cobró
+    async fn refund_payment(&self, payment_id: Uuid, fan_id: Uuid, reason: &str) -> Result<(), AppError>;
+}
+
+#[async_trait]
+impl SharePurchaseCompensations for InvestmentEscrowService {
+    async fn release_reservation(&self, investment_id: Uuid, reason: &str) -> Result<(), AppError> {
+        InvestmentEscrowService::release_reservation(self, investment_id, reason).await
+    }
+
+    async fn refund_payment(&self, payment_id: Uuid, fan_id: Uuid, reason: &str) -> Result<(), AppError> {
+        InvestmentEscrowService::refund_payment(self, payment_id, fan_id, reason).await
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SharePurchaseSagaConfig {
+    pub payment_timeout: chrono::Duration,
+    /// Plazo para la confirmación on-chain tras el cobro. `None` si la compra
+    /// no se registra on-chain y termina al cobrarse. Una compra ya confirmada
+    /// no se puede liberar: si vence este plazo la saga acaba en `failed`.
+    pub settlement_timeout: Option<chrono::Duration>,
+    /// Espera antes de reintentar una compensación fallida
+    pub retry_delay: chrono::Duration,
+    /// Intentos antes de dejar la saga en `failed` para revisión manual
+    pub max_compensation_attempts: i32,
+}
+
+impl Default for SharePurchaseSagaConfig {
+    fn default() -> Self {
+        Self {
+            payment_timeout: chrono::Duration::seconds(DEFAULT_RESERVATION_TIMEOUT_SECS),
+            settlement_timeout: None,
+            retry_delay: chrono::Duration::seconds(DEFAULT_RETRY_DELAY_SECS),
+            max_compensation_attempts: DEFAULT_MAX_COMPENSATION_ATTEMPTS,
+        }
+    }
+}
+
+impl SharePurchaseSagaConfig {
+    /// Defaults más `SHARE_PURCHASE_PAYMENT_TIMEOUT_SECS` y
+    /// `SHARE_PURCHASE_SETTLEMENT_TIMEOUT_SECS` (sin él no hay paso on-chain)
+    pub fn from_env() -> Self {
+        let secs = |var: &str| {
+            std::env::var(var)
+                .ok()
+                .and_then(|v| v.parse::<i64>().ok())
+                .filter(|secs| *secs > 0)
+                .map(chrono::Duration::seconds)
+        };
+        let defaults = Self::default();
+        Self {
+            payment_timeout: secs("SHARE_PURCHASE_PAYMENT_TIMEOUT_SECS").unwrap_or(defaults.payment_timeout),
+            settlement_timeout: secs("SHARE_PURCHASE_SETTLEMENT_TIMEOUT_SECS"),
+            ..defaults
+        }
+    }
+}
+
+/// Process manager de la compra de participaciones
+pub struct SharePurchaseSaga {
+    store: Arc<dyn SagaStore>,
+    compensations: Arc<dyn SharePurchaseCompensations>,
+    config: SharePurchaseSagaConfig,
+}
+
+impl SharePurchaseSaga {
+    pub fn new(store: Arc<dyn SagaStore>, compensations: Arc<dyn SharePurchaseCompensations>) -> Self {
+        Self::with_config(store, compensations, SharePurchaseSagaConfig::default())
+    }
+
+    pub fn with_config(
+        store: Arc<dyn SagaStore>,
+        compensations: Arc<dyn SharePurchaseCompensations>,
+        config: SharePurchaseSagaConfig,
+    ) -> Self {
+        Self { store, compensations, config }
+    }
+
+    /// Iniciar la saga. Un `SharesReserved` repetido devuelve la instancia existente.
+    pub async fn on_shares_reserved(
+        &self,
+        investment_id: Uuid,
+        venture_id: Uuid,
+        fan_id: Uuid,
+        shares: f64,
+        payment_id: Option<Uuid>,
+    ) -> Result<SagaInstance, AppError> {
+        let state = SharePurchaseState {
+            investment_id,
+            venture_id,
+            fan_id,
+            shares,
+            payment_id,
+            payment_completed: false,
+            payment_failed: false,
+            transaction_hash: None,
+            failure_reason: None,
+            reservation_released: false,
+            payment_refunded: false,
+        };
+        let mut instance = SagaInstance::start(
+            SHARE_PURCHASE_SAGA,
+            investment_id,
+            SharePurchaseStep::AwaitingPayment.as_str(),
+            serde_json::Value::Null,
+            Some(Utc::now() + self.config.payment_timeout),
+        );
+        instance.set_data(&state)?;
+
+        if self.store.create(&instance, "SharesReserved").await? {
+            tracing::info!("Share purchase saga started for investment {}", investment_id);
+            return Ok(instance);
+        }
+        self.load(investment_id).await
+    }
+
+    pub async fn on_payment_completed(&self, investment_id: Uuid, payment_id: Uuid) -> Result<Option<SagaInstance>, AppError> {
+        let settlement_timeout = self.config.settlement_timeout;
+        let instance = self
+            .transition(investment_id, "SharePaymentCompleted", |_, step, state| {
+                if state.payment_completed {
+                    return None;
+                }
+                state.payment_completed = true;
+                state.payment_id.get_or_insert(payment_id);
+                match step {
+                    SharePurchaseStep::AwaitingPayment => Some(match settlement_timeout {
+                        Some(timeout) => (SharePurchaseStep::AwaitingSettlement, Some(Utc::now() + timeout)),
+                        None => (SharePurchaseStep::Completed, None),
+                    }),
+                    // Cobrado después de liberar la compra: hay que devolverlo
+                    SharePurchaseStep::Compensating | SharePurchaseStep::Compensated | SharePurchaseStep::Failed => {
+                        state.payment_refunded = false;
+                        state.failure_reason.get_or_insert_with(|| "Payment settled after the purchase was released".to_string());
+                        Some((SharePurchaseStep::Compensating, Some(Utc::now())))
+                    }
+                    SharePurchaseStep::AwaitingSettlement | SharePurchaseStep::Completed => None,
+                }
+            })
+            .await?;
+        self.compensate_if_needed(instance).await
+    }
+
+    pub async fn on_payment_failed(&self, investment_id: Uuid, reason: &str) -> Result<Option<SagaInstance>, AppError> {
+        let instance = self
+            .transition(investment_id, "SharePaymentFailed", |_, step, state| {
+                if step != SharePurchaseStep::AwaitingPayment {
+                    return None;
+                }
+                state.payment_failed = true;
+                state.failure_reason = Some(format!("Payment failed: {}", reason));
+                Some((SharePurchaseStep::Compensating, Some(Utc::now())))
+            })
+            .await?;
+        self.compensate_if_needed(instance).await
+    }
+
+    pub async fn on_settlement_confirmed(&self, investment_id: Uuid, transaction_hash: &str) -> Result<Option<SagaInstance>, AppError> {
+        self.transition(investment_id, "SettlementConfirmed", |_, step, state| {
+            if step != SharePurchaseStep::AwaitingSettlement {
+                return None;
+            }
+            state.transaction_hash = Some(transaction_hash.to_string());
+            Some((SharePurchaseStep::Completed, None))
+        })
+        .await
+    }
+
+    /// Compensar las sagas cuyo paso venció y reanudar las compensaciones
+    /// interrumpidas o pendientes de reintento
+    pub async fn process_timeouts(&self) -> Result<usize, AppError> {
+        let now = Utc::now();
+        let due = self.store.find_due(SHARE_PURCHASE_SAGA, now, TIMEOUT_BATCH_SIZE).await?;
+        let mut processed = 0;
+
+        for due_instance in due {
+            let investment_id = due_instance.correlation_id;
+            let result = self
+                .transition(investment_id, "Timeout", |instance, step, state| {
+                    // Otro evento pudo hacerla avanzar desde que se leyó
+                    if !instance.deadline_at.is_some_and(|deadline| deadline <= now) {
+                        return None;
+                    }
+                    let reason = match step {
+                        SharePurchaseStep::AwaitingPayment => "Payment was not completed before the deadline",
+                        SharePurchaseStep::AwaitingSettlement => "Settlement was not confirmed before the deadline",
+                        _ => return None,
+                    };
+                    state.failure_reason = Some(reason.to_string());
+                    Some((SharePurchaseStep::Compensating, Some(now)))
+                })
+                .await;
+
+            match result {
+                Ok(Some(instance)) if instance.deadline_at.is_some_and(|deadline| deadline <= now) => {
+                    match self.compensate_if_needed(Some(instance)).await {
+                        Ok(_) => processed += 1,
+                        Err(e) => tracing::error!("Share purchase saga {} failed to compensate: {:?}", investment_id, e),
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("Share purchase saga {} failed to time out: {:?}", investment_id, e),
+            }
+        }
+        Ok(processed)
+    }
+
+    pub async fn load(&self, investment_id: Uuid) -> Result<SagaInstance, AppError> {
+        self.store.find(SHARE_PURCHASE_SAGA, investment_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("No purchase saga for investment {}", investment_id)))
+    }
+
+    /// Aplicar `decide` a la instancia y persistir el paso que devuelva. Si otro
+    /// proceso la modificó entretanto se relee y se vuelve a decidir.
+    async fn transition<F>(&self, investment_id: Uuid, trigger: &str, decide: F) -> Result<Option<SagaInstance>, AppError>
+    where
+        F: Fn(&SagaInstance, SharePurchaseStep, &mut SharePurchaseState) -> Option<NextStep>,
+    {
+        for _ in 0..MAX_CONFLICT_RETRIES {
+            let Some(mut instance) = self.store.find(SHARE_PURCHASE_SAGA, investment_id).await? else {
+                tracing::debug!("{} for investment {} without purchase saga, ignoring", trigger, investment_id);
+                return Ok(None);
+            };
+            let step = SharePurchaseStep::of(&instance)?;
+            let mut state: SharePurchaseState = instance.data()?;
+            let Some((next, deadline_at)) = decide(&instance, step, &mut state) else {
+                return Ok(Some(instance));
+            };
+
+            if next == SharePurchaseStep::Compensating && step != SharePurchaseStep::Compensating {
+                instance.attempts = 0;
+                instance.last_error = None;
+            }
+            instance.set_data(&state)?;
+            next.enter(&mut instance, deadline_at);
+            match self.store.save(&mut instance, trigger).await {
+                Ok(()) => {
+                    tracing::info!("Share purchase saga {}: {} -> {} ({})", investment_id, step.as_str(), next.as_str(), trigger);
+                    return Ok(Some(instance));
+                }
+                Err(AppError::ConcurrencyConflict(_)) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        Err(AppError::ConcurrencyConflict(format!("Share purchase saga {} kept changing", investment_id)))
+    }
+
+    async fn compensate_if_needed(&self, instance: Option<SagaInstance>) -> Result<Option<SagaInstance>, AppError> {
+        match instance {
+            Some(instance) if instance.status == SagaStatus::Compensating => self.compensate(instance).await.map(Some),
+            other => Ok(other),
+        }
+    }
+
+    /// Liberar la reserva y después devolver el pago, persistiendo cada paso.
+    /// Si algo falla se reintenta tras `retry_delay` (el job de timeouts la
+    /// retoma) hasta agotar los intentos.
+    async fn compensate(&self, mut instance: SagaInstance) -> Result<SagaInstance, AppError> {
+        let mut state: SharePurchaseState = instance.data()?;
+        let reason = state.failure_reason.clone().unwrap_or_else(|| "Share purchase failed".to_string());
+
+        let result = async {
+            if !state.reservation_released {
+                self.compensations.release_reservation(state.investment_id, &reason).await?;
+                state.reservation_released = true;
+                instance.set_data(&state)?;
+                self.store.save(&mut instance, "ReservationReleased").await?;
+            }
+            if state.needs_refund() {
+                let payment_id = state.payment_id.expect("refund requires a payment");
+                self.compensations.refund_payment(payment_id, state.fan_id, &reason).await?;
+                state.payment_refunded = true;
+                instance.set_data(&state)?;
+                self.store.save(&mut instance, "PaymentRefunded").await?;
+            }
+            Ok::<(), AppError>(())
+        }
+        .await;
+
+        match result {
+            Ok(()) => {
+                SharePurchaseStep::Compensated.enter(&mut instance, None);
+                instance.last_error = None;
+                self.store.save(&mut instance, "CompensationCompleted").await?;
+                tracing::info!("↩️ Share purchase {} compensated: {}", state.investment_id, reason);
+            }
+            // Otro proceso avanzó la saga: la retomará quien la tenga
+            Err(e @ AppError::ConcurrencyConflict(_)) => return Err(e),
+            Err(e) => {
+                instance.attempts += 1;
+                instance.last_error = Some(e.to_string());
+                if instance.attempts >= self.config.max_compensation_attempts {
+                    SharePurchaseStep::Failed.enter(&mut instance, None);
+                    tracing::error!(
+                        "Share purchase {} could not be compensated after {} attempts, manual review needed: {}",
+                        state.investment_id, instance.attempts, e
+                    );
+                } else {
+                    instance.deadline_at = Some(Utc::now() + self.config.retry_delay);
+                    tracing::warn!("Compensation of share purchase {} failed (attempt {}): {}", state.investment_id, instance.attempts, e);
+                }
+                self.store.save(&mut instance, "CompensationFailed").await?;
+            }
+        }
+        Ok(instance)
+    }
+}
+
+#[async_trait]
+impl SagaTimeouts for SharePurchaseSaga {
+    fn saga_type(&self) -> &str {
+        SHARE_PURCHASE_SAGA
+    }
+
+    async fn handle_timeouts(&self) -> Result<usize, AppError> {
+        self.process_timeouts().await
+    }
+}
+
+#[async_trait]
+impl EventHandler for SharePurchaseSaga {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        match event {
+            DomainEvent::SharesReserved { investment_id, venture_id, fan_id, shares, payment_id, .. } => {
+                self.on_shares_reserved(*investment_id, *venture_id, *fan_id, *shares, *payment_id).await?;
+            }
+            DomainEvent::SharePaymentCompleted { investment_id, payment_id, .. } => {
+                self.on_payment_completed(*investment_id, *payment_id).await?;
+            }
+            DomainEvent::SharePaymentFailed { investment_id, reason, .. } => {
+                self.on_payment_failed(*investment_id, reason).await?;
+            }
+            DomainEvent::SettlementConfirmed { investment_id, transaction_hash, .. } => {
+                self.on_settlement_confirmed(*investment_id, transaction_hash).await?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::infrastructure::saga::InMemorySagaStore;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingCompensations {
+        calls: Mutex<Vec<String>>,
+        failing_refunds: Mutex<u32>,
+    }
+
+    impl RecordingCompensations {
+        fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl SharePurchaseCompensations for RecordingCompensations {
+        async fn release_reservation(&self, _investment_id: Uuid, _reason: &str) -> Result<(), AppError> {
+            self.calls.lock().unwrap().push("release".to_string());
+            Ok(())
+        }
+
+        async fn refund_payment(&self, _payment_id: Uuid, _fan_id: Uuid, _reason: &str) -> Result<(), AppError> {
+            let mut failing = self.failing_refunds.lock().unwrap();
+            if *failing > 0 {
+                *failing -= 1;
+                return Err(AppError::InternalError("payment provider unavailable".to_string()));
+            }
+            self.calls.lock().unwrap().push("refund".to_string());
+            Ok(())
+        }
+    }
+
+    fn config() -> SharePurchaseSagaConfig {
+        SharePurchaseSagaConfig {
+            payment_timeout: chrono::Duration::minutes(15),
+            settlement_timeout: Some(chrono::Duration::minutes(10)),
+            retry_delay: chrono::Duration::zero(),
+            max_compensation_attempts: 3,
+        }
+    }
+
+    fn saga(store: Arc<InMemorySagaStore>, compensations: Arc<RecordingCompensations>) -> SharePurchaseSaga {
+        SharePurchaseSaga::with_config(store, compensations, config())
+    }
+
+    async fn started(saga: &SharePurchaseSaga) -> (Uuid, Uuid) {
+        let (investment_id, payment_id) = (Uuid::new_v4(), Uuid::new_v4());
+        saga.on_shares_reserved(investment_id, Uuid::new_v4(), Uuid::new_v4(), 10.0, Some(payment_id)).await.unwrap();
+        (investment_id, payment_id)
+    }
+
+    /// Adelantar el plazo del paso actual, como si hubiera pasado el tiempo
+    async fn expire(store: &InMemorySagaStore, investment_id: Uuid) {
+        let mut instance = store.find(SHARE_PURCHASE_SAGA, investment_id).await.unwrap().unwrap();
+        instance.deadline_at = Some(Utc::now() - chrono::Duration::seconds(1));
+        store.save(&mut instance, "TestClock").await.unwrap();
+    }
+
+    async fn steps(store: &InMemorySagaStore, investment_id: Uuid) -> Vec<String> {
+        let instance = store.find(SHARE_PURCHASE_SAGA, investment_id).await.unwrap().unwrap();
+        store
+            .transitions(instance.id)
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|t| t.trigger != "TestClock")
+            .map(|t| format!("{}:{}", t.trigger, t.to_step))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn happy_path_completes_without_compensation() {
+        let (store, compensations) = (Arc::new(InMemorySagaStore::new()), Arc::new(RecordingCompensations::default()));
+        let saga = saga(store.clone(), compensations.clone());
+        let (investment_id, payment_id) = started(&saga).await;
+
+        let instance = saga.on_payment_completed(investment_id, payment_id).await.unwrap().unwrap();
+        assert_eq!(SharePurchaseStep::of(&instance).unwrap(), SharePurchaseStep::AwaitingSettlement);
+        let instance = saga.on_settlement_confirmed(investment_id, "0xabc").await.unwrap().unwrap();
+
+        assert_eq!(instance.status, SagaStatus::Completed);
+        assert_eq!(instance.data::<SharePurchaseState>().unwrap().transaction_hash.as_deref(), Some("0xabc"));
+        // Redelivery of any event leaves it completed
+        saga.on_payment_completed(investment_id, payment_id).await.unwrap();
+        saga.on_shares_reserved(investment_id, Uuid::new_v4(), Uuid::new_v4(), 10.0, Some(payment_id)).await.unwrap();
+        assert_eq!(saga.process_timeouts().await.unwrap(), 0);
+        assert_eq!(saga.load(investment_id).await.unwrap().status, SagaStatus::Completed);
+        assert!(compensations.calls().is_empty());
+        assert_eq!(
+            steps(&store, investment_id).await,
+            vec!["SharesReserved:awaiting_payment", "SharePaymentCompleted:awaiting_settlement", "SettlementConfirmed:completed"]
+        );
+    }
+
+    #[tokio::test]
+    async fn payment_failure_releases_the_reservation_only() {
+        let (store, compensations) = (Arc::new(InMemorySagaStore::new()), Arc::new(RecordingCompensations::default()));
+        let saga = saga(store.clone(), compensations.clone());
+        let (investment_id, _) = started(&saga).await;
+
+        let instance = saga.on_payment_failed(investment_id, "card declined").await.unwrap().unwrap();
+
+        assert_eq!(instance.status, SagaStatus::Compensated);
+        assert_eq!(compensations.calls(), vec!["release"]);
+        let state: SharePurchaseState = instance.data().unwrap();
+        assert_eq!(state.failure_reason.as_deref(), Some("Payment failed: card declined"));
+        assert_eq!(
+            steps(&store, investment_id).await,
+            vec![
+                "SharesReserved:awaiting_payment",
+                "SharePaymentFailed:compensating",
+                "ReservationReleased:compensating",
+                "CompensationCompleted:compensated",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn settlement_timeout_releases_and_refunds() {
+        let (store, compensations) = (Arc::new(InMemorySagaStore::new()), Arc::new(RecordingCompensations::default()));
+        let saga = saga(store.clone(), compensations.clone());
+        let (investment_id, payment_id) = started(&saga).await;
+        saga.on_payment_completed(investment_id, payment_id).await.unwrap();
+        assert_eq!(saga.process_timeouts().await.unwrap(), 0);
+
+        expire(&store, investment_id).await;
+        assert_eq!(saga.process_timeouts().await.unwrap(), 1);
+
+        let instance = saga.load(investment_id).await.unwrap();
+        assert_eq!(instance.status, SagaStatus::Compensated);
+        assert_eq!(compensations.calls(), vec!["release", "refund"]);
+        // A settlement arriving late no longer completes the purchase
+        saga.on_settlement_confirmed(investment_id, "0xlate").await.unwrap();
+        assert_eq!(saga.load(investment_id).await.unwrap().status, SagaStatus::Compensated);
+    }
+
+    #[tokio::test]
+    async fn interrupted_compensation_resumes_where_it_stopped() {
+        let (store, compensations) = (Arc::new(InMemorySagaStore::new()), Arc::new(RecordingCompensations::default()));
+        *compensations.failing_refunds.lock().unwrap() = 1;
+        let saga = saga(store.clone(), compensations.clone());
+        let (investment_id, _) = started(&saga).await;
+
+        // Payment timed out; the provider is down while cancelling the hold
+        expire(&store, investment_id).await;
+        saga.process_timeouts().await.unwrap();
+        let instance = saga.load(investment_id).await.unwrap();
+        assert_eq!(instance.status, SagaStatus::Compensating);
+        assert_eq!(instance.attempts, 1);
+        assert!(instance.data::<SharePurchaseState>().unwrap().reservation_released);
+
+        // After a restart a new saga over the same store picks it up
+        let restarted = SharePurchaseSaga::with_config(store.clone(), compensations.clone(), config());
+        assert_eq!(restarted.process_timeouts().await.unwrap(), 1);
+        assert_eq!(restarted.load(investment_id).await.unwrap().status, SagaStatus::Compensated);
+        assert_eq!(compensations.calls(), vec!["release", "refund"]);
+    }
+
+    #[tokio::test]
+    async fn payment_after_compensation_is_refunded() {
+        let (store, compensations) = (Arc::new(InMemorySagaStore::new()), Arc::new(RecordingCompensations::default()));
+        let saga = SharePurchaseSaga::with_config(
+            store.clone(),
+            compensations.clone(),
+            SharePurchaseSagaConfig { settlement_timeout: None, ..config() },
+        );
+        let (investment_id, payment_id) = started(&saga).await;
+        saga.on_payment_failed(investment_id, "timeout at provider").await.unwrap();
+
+        let instance = saga.on_payment_completed(investment_id, payment_id).await.unwrap().unwrap();
+
+        assert_eq!(instance.status, SagaStatus::Compensated);
+        assert_eq!(compensations.calls(), vec!["release", "refund"]);
+    }
+
+    #[tokio::test]
+    async fn compensation_gives_up_after_max_attempts() {
+        let (store, compensations) = (Arc::new(InMemorySagaStore::new()), Arc::new(RecordingCompensations::default()));
+        *compensations.failing_refunds.lock().unwrap() = 10;
+        let saga = saga(store.clone(), compensations);
+        let (investment_id, _) = started(&saga).await;
+        expire(&store, investment_id).await;
+
+        for _ in 0..5 {
+            saga.process_timeouts().await.unwrap();
+        }
+
+        let instance = saga.load(investment_id).await.unwrap();
+        assert_eq!(instance.status, SagaStatus::Failed);
+        assert_eq!(instance.attempts, 3);
+        assert_eq!(instance.last_error.as_deref(), Some("Internal error: payment provider unavailable"));
+    }
+}
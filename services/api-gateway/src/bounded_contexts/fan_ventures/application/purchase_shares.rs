@@ -0,0 +1,233 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_ventures::application::escrow_service::InvestmentEscrowService;
+use crate::bounded_contexts::fan_ventures::domain::entities::FanInvestment;
+use crate::bounded_contexts::fan_ventures::domain::escrow::InvestmentReservation;
+use crate::shared::application::command::{Command, CommandHandler};
+use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::distributed_lock::{acquire_lock, DistributedLock, LockError};
+
+// =============================================================================
+// FAN VENTURES - PURCHASE SHARES COMMAND
+// =============================================================================
+//
+// Comprobar disponibilidad y reservar no es atómico: dos compras simultáneas
+// de las últimas participaciones pasarían ambas la comprobación. Las compras de
+// un mismo venture se serializan con un lock distribuido
+// (`lock:purchase:{venture_id}`) para que valga entre instancias del gateway.
+
+/// TTL del lock; bastante más de lo que tarda una reserva (venture + pago + reserva)
+pub const PURCHASE_LOCK_TTL: Duration = Duration::from_millis(5000);
+/// Espera máxima por un lock ocupado antes de rendirse
+pub const PURCHASE_LOCK_WAIT: Duration = Duration::from_millis(100);
+
+pub const PURCHASE_LOCKED_MESSAGE: &str = "Share purchase is locked by another transaction";
+
+pub fn purchase_lock_key(venture_id: Uuid) -> String {
+    format!("lock:purchase:{}", venture_id)
+}
+
+#[derive(Debug, Clone)]
+pub struct PurchaseSharesCommand {
+    /// Identifica esta compra; es el valor del lock mientras se ejecuta
+    pub request_id: Uuid,
+    pub venture_id: Uuid,
+    pub fan_id: Uuid,
+    pub shares: f64,
+    pub auto_confirm: bool,
+}
+
+impl Command for PurchaseSharesCommand {}
+
+/// La reserva de participaciones que el lock protege
+#[async_trait]
+pub trait ShareReservations: Send + Sync {
+    async fn reserve(
+        &self,
+        venture_id: Uuid,
+        fan_id: Uuid,
+        shares: f64,
+        auto_confirm: bool,
+    ) -> Result<(FanInvestment, InvestmentReservation), AppError>;
+}
+
+#[async_trait]
+impl ShareReservations for InvestmentEscrowService {
+    async fn reserve(
+        &self,
+        venture_id: Uuid,
+        fan_id: Uuid,
+        shares: f64,
+        auto_confirm: bool,
+    ) -> Result<(FanInvestment, InvestmentReservation), AppError> {
+        InvestmentEscrowService::reserve(self, venture_id, fan_id, shares, auto_confirm).await
+    }
+}
+
+pub struct PurchaseSharesCommandHandler {
+    reservations: Arc<dyn ShareReservations>,
+    lock: Arc<dyn DistributedLock>,
+    lock_ttl: Duration,
+    lock_wait: Duration,
+}
+
+impl PurchaseSharesCommandHandler {
+    pub fn new(reservations: Arc<dyn ShareReservations>, lock: Arc<dyn DistributedLock>) -> Self {
+        Self {
+            reservations,
+            lock,
+            lock_ttl: PURCHASE_LOCK_TTL,
+            lock_wait: PURCHASE_LOCK_WAIT,
+        }
+    }
+
+    pub fn with_lock_timing(mut self, ttl: Duration, wait: Duration) -> Self {
+        self.lock_ttl = ttl;
+        self.lock_wait = wait;
+        self
+    }
+}
+
+#[async_trait]
+impl CommandHandler<PurchaseSharesCommand> for PurchaseSharesCommandHandler {
+    type Output = (FanInvestment, InvestmentReservation);
+
+    #[tracing::instrument(name = "PurchaseSharesCommand", skip_all, fields(request_id = %command.request_id, venture_id = %command.venture_id))]
+    async fn handle(&self, command: PurchaseSharesCommand) -> Result<Self::Output, AppError> {
+        let key = purchase_lock_key(command.venture_id);
+        let guard = acquire_lock(self.lock.clone(), &key, &command.request_id.to_string(), self.lock_ttl, self.lock_wait)
+            .await
+            .map_err(|e| match e {
+                LockError::AcquisitionTimeout(_) => AppError::ConcurrencyConflict(PURCHASE_LOCKED_MESSAGE.to_string()),
+                other => other.into(),
+            })?;
+
+        let purchase = self.reservations.reserve(command.venture_id, command.fan_id, command.shares, command.auto_confirm);
+        tokio::pin!(purchase);
+        let outcome = tokio::select! {
+            biased;
+            outcome = &mut purchase => outcome,
+            _ = tokio::time::sleep(self.lock_ttl) => {
+                // Cortarla ahora dejaría la inversión a medias: se termina, pero
+                // otra compra del venture pudo entrar al caducar el lock
+                tracing::error!("Share purchase {} outlived its lock {}", command.request_id, key);
+                purchase.await
+            }
+        };
+
+        // Equivalente al `finally`: se suelta tanto si la compra fue bien como si no
+        if let Err(e) = guard.release().await {
+            tracing::warn!("Releasing {} after purchase {}: {}", key, command.request_id, e);
+        }
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::fan_ventures::domain::entities::{InvestmentStatus, InvestmentType};
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct InMemoryLock {
+        held: Mutex<HashMap<String, String>>,
+    }
+
+    #[async_trait]
+    impl DistributedLock for InMemoryLock {
+        async fn try_acquire(&self, key: &str, token: &str, _ttl: Duration) -> Result<bool, LockError> {
+            let mut held = self.held.lock().unwrap();
+            if held.contains_key(key) {
+                return Ok(false);
+            }
+            held.insert(key.to_string(), token.to_string());
+            Ok(true)
+        }
+
+        async fn release(&self, key: &str, token: &str) -> Result<(), LockError> {
+            let mut held = self.held.lock().unwrap();
+            match held.get(key) {
+                Some(owner) if owner == token => {
+                    held.remove(key);
+                    Ok(())
+                }
+                _ => Err(LockError::LockNotHeld(key.to_string())),
+            }
+        }
+    }
+
+    /// Reserva lenta que falla o tiene éxito según se le pida
+    struct SlowReservations {
+        delay: Duration,
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl ShareReservations for SlowReservations {
+        async fn reserve(
+            &self,
+            venture_id: Uuid,
+            fan_id: Uuid,
+            shares: f64,
+            auto_confirm: bool,
+        ) -> Result<(FanInvestment, InvestmentReservation), AppError> {
+            tokio::time::sleep(self.delay).await;
+            if self.fail {
+                return Err(AppError::DomainRuleViolation("Not enough shares".to_string()));
+            }
+            let investment = FanInvestment::new(
+                Uuid::new_v4(), fan_id, venture_id, shares, InvestmentType::RevenueShare, InvestmentStatus::Pending,
+            );
+            let reservation = InvestmentReservation::new(&investment, None, auto_confirm, chrono::Duration::minutes(15), Utc::now());
+            Ok((investment, reservation))
+        }
+    }
+
+    fn command(venture_id: Uuid) -> PurchaseSharesCommand {
+        PurchaseSharesCommand { request_id: Uuid::new_v4(), venture_id, fan_id: Uuid::new_v4(), shares: 10.0, auto_confirm: true }
+    }
+
+    #[tokio::test]
+    async fn busy_venture_is_a_concurrency_conflict() {
+        let lock = Arc::new(InMemoryLock::default());
+        let handler = Arc::new(PurchaseSharesCommandHandler::new(
+            Arc::new(SlowReservations { delay: Duration::from_millis(300), fail: false }),
+            lock.clone(),
+        ));
+        let venture_id = Uuid::new_v4();
+
+        let first = tokio::spawn({
+            let handler = handler.clone();
+            async move { handler.handle(command(venture_id)).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let second = handler.handle(command(venture_id)).await;
+
+        match second {
+            Err(AppError::ConcurrencyConflict(message)) => assert_eq!(message, PURCHASE_LOCKED_MESSAGE),
+            other => panic!("expected a conflict, got {:?}", other.map(|_| ())),
+        }
+        assert!(first.await.unwrap().is_ok());
+        assert!(lock.held.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn lock_is_released_when_the_purchase_fails() {
+        let lock = Arc::new(InMemoryLock::default());
+        let handler = PurchaseSharesCommandHandler::new(
+            Arc::new(SlowReservations { delay: Duration::ZERO, fail: true }),
+            lock.clone(),
+        );
+        let venture_id = Uuid::new_v4();
+
+        assert!(handler.handle(command(venture_id)).await.is_err());
+        assert!(lock.held.lock().unwrap().is_empty());
+    }
+}
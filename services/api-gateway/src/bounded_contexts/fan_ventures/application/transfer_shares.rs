@@ -0,0 +1,298 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_ventures::domain::entities::{FanInvestment, InvestmentStatus};
+use crate::bounded_contexts::fan_ventures::domain::escrow::EscrowedShare;
+use crate::bounded_contexts::fan_ventures::domain::repositories::ShareEscrowRepository;
+use crate::shared::application::command::{Command, CommandHandler};
+use crate::shared::domain::errors::AppError;
+
+// =============================================================================
+// FAN VENTURES - TRANSFER SHARES COMMAND (Venta entre fans con escrow)
+// =============================================================================
+//
+// 0. Solo se pueden vender las ya liberadas por el vesting del venture.
+// 1. Las participaciones salen de la inversión del vendedor al abrir el escrow
+//    (`SharesEscrowed`): mientras se cobra no se pueden vender otra vez.
+// 2. Se cobra al comprador.
+// 3. Cobrado: pasan a la inversión del comprador (`ShareTransferred`).
+//    No cobrado: vuelven al vendedor (`EscrowCancelled`).
+//
+// El repositorio guarda cada evento en el outbox junto con el cambio de estado;
+// el relay del outbox los publica en el event bus.
+
+/// Plazo por defecto para cobrar una transferencia
+pub const DEFAULT_TRANSFER_ESCROW_TIMEOUT_SECS: i64 = 15 * 60;
+
+/// Puerto hacia el contexto de pagos para cobrar transferencias
+#[async_trait]
+pub trait ShareTransferPayments: Send + Sync {
+    /// Cobrar `price` al comprador a favor del vendedor. Devuelve el id del pago
+    /// liquidado; un error significa que no se cobró.
+    async fn collect(&self, escrow: &EscrowedShare) -> Result<Uuid, AppError>;
+    /// Devolver al comprador un pago ya cobrado
+    async fn refund(&self, payment_id: Uuid, requested_by: Uuid, reason: &str) -> Result<(), AppError>;
+}
+
+#[derive(Debug, Clone)]
+pub struct TransferSharesCommand {
+    pub seller_id: Uuid,
+    /// Inversión del vendedor de la que salen las participaciones
+    pub seller_investment_id: Uuid,
+    pub buyer_id: Uuid,
+    pub quantity: f64,
+    /// Precio total de la operación
+    pub price: f64,
+}
+
+impl Command for TransferSharesCommand {}
+
+pub struct TransferSharesCommandHandler {
+    escrows: Arc<dyn ShareEscrowRepository>,
+    payments: Arc<dyn ShareTransferPayments>,
+    escrow_timeout: chrono::Duration,
+}
+
+impl TransferSharesCommandHandler {
+    pub fn new(
+        escrows: Arc<dyn ShareEscrowRepository>,
+        payments: Arc<dyn ShareTransferPayments>,
+    ) -> Self {
+        Self {
+            escrows,
+            payments,
+            escrow_timeout: chrono::Duration::seconds(DEFAULT_TRANSFER_ESCROW_TIMEOUT_SECS),
+        }
+    }
+
+    pub fn with_escrow_timeout(mut self, escrow_timeout: chrono::Duration) -> Self {
+        self.escrow_timeout = escrow_timeout;
+        self
+    }
+
+    /// Devolver las participaciones al vendedor y, si se llegó a cobrar, el pago al comprador
+    async fn cancel(&self, escrow: &mut EscrowedShare, reason: &str, collected: Option<Uuid>) -> Result<(), AppError> {
+        if let Some(payment_id) = collected {
+            if let Err(e) = self.payments.refund(payment_id, escrow.buyer_id, reason).await {
+                // Las participaciones vuelven igualmente; el reembolso queda para conciliación
+                tracing::error!("Failed to refund payment {} of share escrow {}: {:?}", payment_id, escrow.escrow_id, e);
+            }
+        }
+
+        escrow.cancel(Utc::now())?;
+        self.escrows.cancel(escrow, reason).await
+    }
+}
+
+#[async_trait]
+impl CommandHandler<TransferSharesCommand> for TransferSharesCommandHandler {
+    /// El escrow completado y la inversión del comprador que recibió las participaciones
+    type Output = (EscrowedShare, FanInvestment);
+
+    async fn handle(&self, command: TransferSharesCommand) -> Result<Self::Output, AppError> {
+        let holder = self.escrows.find_share_holder(&command.seller_investment_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("Investment {} not found", command.seller_investment_id)))?;
+        let holding = &holder.investment;
+        if holding.fan_id != command.seller_id {
+            return Err(AppError::Forbidden("Only the owner can transfer these shares".to_string()));
+        }
+        if holding.status != InvestmentStatus::Active {
+            return Err(AppError::DomainRuleViolation("Only active investments can be transferred".to_string()));
+        }
+        // El vesting lo comprueba `open` dentro de la transacción
+
+        let mut escrow = EscrowedShare::new(
+            holding,
+            command.buyer_id,
+            command.quantity,
+            command.price,
+            self.escrow_timeout,
+            Utc::now(),
+        )?;
+        self.escrows.open(&escrow).await?;
+
+        let payment_id = match self.payments.collect(&escrow).await {
+            Ok(payment_id) => payment_id,
+            Err(e) => {
+                self.cancel(&mut escrow, &format!("Payment failed: {}", e), None).await?;
+                return Err(e);
+            }
+        };
+
+        let mut completed = escrow.clone();
+        if let Err(e) = completed.complete(payment_id, Utc::now()) {
+            self.cancel(&mut escrow, &e.to_string(), Some(payment_id)).await?;
+            return Err(e.into());
+        }
+        let buyer_investment = FanInvestment::new(
+            Uuid::new_v4(),
+            completed.buyer_id,
+            completed.venture_id,
+            completed.quantity,
+            holding.investment_type.clone(),
+            InvestmentStatus::Active,
+        );
+        let buyer_investment = match self.escrows.complete(&completed, &buyer_investment).await {
+            Ok(credited) => credited,
+            Err(e) => {
+                self.cancel(&mut escrow, "Transfer could not be completed", Some(payment_id)).await?;
+                return Err(e);
+            }
+        };
+
+        Ok((completed, buyer_investment))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::fan_ventures::domain::entities::InvestmentType;
+    use crate::bounded_contexts::fan_ventures::domain::escrow::EscrowStatus;
+    use crate::bounded_contexts::fan_ventures::domain::repositories::VentureRepository;
+    use crate::bounded_contexts::fan_ventures::domain::vesting::{VestingSchedule, VestingType};
+    use crate::bounded_contexts::fan_ventures::integration_service::InMemoryFanVenturesBoundedContext;
+    use crate::bounded_contexts::orchestrator::DomainEvent;
+    use std::time::Duration;
+
+    struct Fixture {
+        context: InMemoryFanVenturesBoundedContext,
+        holding: FanInvestment,
+    }
+
+    impl Fixture {
+        async fn balance(&self, investment_id: Uuid) -> f64 {
+            self.context.ventures.get_investment_by_id(investment_id).await.unwrap().unwrap().investment_amount
+        }
+
+        async fn published(&self) -> Vec<DomainEvent> {
+            self.context.event_publisher.get_published_events().await
+        }
+
+        async fn published_types(&self) -> Vec<&'static str> {
+            self.published().await.iter().map(DomainEvent::event_type).collect()
+        }
+    }
+
+    async fn fixture(shares: f64) -> Fixture {
+        let holding = FanInvestment::new(
+            Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), shares, InvestmentType::RevenueShare, InvestmentStatus::Active,
+        );
+        let context = InMemoryFanVenturesBoundedContext::create_for_testing();
+        context.ventures.create_fan_investment(&holding).await.unwrap();
+        Fixture { context, holding }
+    }
+
+    fn command(holding: &FanInvestment, quantity: f64) -> TransferSharesCommand {
+        TransferSharesCommand {
+            seller_id: holding.fan_id,
+            seller_investment_id: holding.id,
+            buyer_id: Uuid::new_v4(),
+            quantity,
+            price: quantity * 12.5,
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_payment_cancels_escrow_and_restores_seller_balance() {
+        let f = fixture(10.0).await;
+        f.context.payments.decline_collections().await;
+
+        let result = f.context.transfer_shares.handle(command(&f.holding, 4.0)).await;
+
+        assert!(matches!(result, Err(AppError::ExternalServiceError(_))));
+        assert_eq!(f.balance(f.holding.id).await, 10.0);
+        let escrows = f.context.escrows.escrows_for(f.holding.id).await;
+        assert_eq!(escrows.len(), 1);
+        assert_eq!(escrows[0].status, EscrowStatus::Cancelled);
+        assert_eq!(f.published_types().await, vec!["SharesEscrowed", "EscrowCancelled"]);
+        let published = f.published().await;
+        assert!(matches!(&published[1], DomainEvent::EscrowCancelled { reason, .. } if reason.starts_with("Payment failed")));
+    }
+
+    #[tokio::test]
+    async fn paid_transfer_moves_shares_to_the_buyer() {
+        let f = fixture(10.0).await;
+        let command = command(&f.holding, 4.0);
+        let buyer_id = command.buyer_id;
+
+        let (escrow, buyer_investment) = f.context.transfer_shares.handle(command).await.unwrap();
+
+        assert_eq!(escrow.status, EscrowStatus::Completed);
+        assert!(escrow.payment_id.is_some());
+        assert_eq!(f.balance(f.holding.id).await, 6.0);
+        assert_eq!(buyer_investment.fan_id, buyer_id);
+        assert_eq!(f.balance(buyer_investment.id).await, 4.0);
+        assert_eq!(f.published_types().await, vec!["SharesEscrowed", "ShareTransferred"]);
+        let published = f.published().await;
+        assert!(matches!(&published[1], DomainEvent::ShareTransferred { investment_id, .. } if *investment_id == buyer_investment.id));
+    }
+
+    #[tokio::test]
+    async fn escrowed_shares_cannot_be_sold_twice() {
+        let f = fixture(10.0).await;
+        f.context.payments.set_collect_delay(Duration::from_millis(100)).await;
+
+        let first = tokio::spawn({
+            let (handler, command) = (f.context.transfer_shares.clone(), command(&f.holding, 8.0));
+            async move { handler.handle(command).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        // Mientras se cobra la primera venta, las 8 participaciones ya no están en la inversión
+        let second = f.context.transfer_shares.handle(command(&f.holding, 8.0)).await;
+
+        assert!(matches!(second, Err(AppError::DomainRuleViolation(_))));
+        assert!(first.await.unwrap().is_ok());
+        assert_eq!(f.balance(f.holding.id).await, 2.0);
+    }
+
+    #[tokio::test]
+    async fn only_the_owner_can_transfer() {
+        let f = fixture(10.0).await;
+        let mut command = command(&f.holding, 4.0);
+        command.seller_id = Uuid::new_v4();
+
+        assert!(matches!(f.context.transfer_shares.handle(command).await, Err(AppError::Forbidden(_))));
+        assert_eq!(f.balance(f.holding.id).await, 10.0);
+        assert!(f.published().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn unvested_shares_cannot_be_transferred() {
+        let f = fixture(10.0).await;
+        // Mitad del calendario lineal: 5 de las 10 participaciones liberadas
+        let schedule = VestingSchedule::new(Utc::now() - chrono::Duration::days(180), 90, 360, VestingType::Linear);
+        f.context.escrows.set_vesting_schedule(f.holding.venture_id, schedule).await;
+
+        let result = f.context.transfer_shares.handle(command(&f.holding, 8.0)).await;
+
+        assert!(matches!(result, Err(AppError::DomainRuleViolation(message)) if message.contains("not yet vested")));
+        assert_eq!(f.balance(f.holding.id).await, 10.0);
+        assert!(f.published().await.is_empty());
+
+        f.context.transfer_shares.handle(command(&f.holding, 4.0)).await.unwrap();
+        assert_eq!(f.balance(f.holding.id).await, 6.0);
+    }
+
+    #[tokio::test]
+    async fn concurrent_transfers_cannot_both_spend_the_vested_shares() {
+        let f = fixture(10.0).await;
+        // 5 de las 10 liberadas; cada venta cabe sola, las dos juntas no
+        let schedule = VestingSchedule::new(Utc::now() - chrono::Duration::days(180), 90, 360, VestingType::Linear);
+        f.context.escrows.set_vesting_schedule(f.holding.venture_id, schedule).await;
+        f.context.payments.set_collect_delay(Duration::from_millis(50)).await;
+
+        let (first, second) = tokio::join!(
+            f.context.transfer_shares.handle(command(&f.holding, 4.0)),
+            f.context.transfer_shares.handle(command(&f.holding, 4.0)),
+        );
+
+        let results = [first, second];
+        assert_eq!(results.iter().filter(|r| r.is_ok()).count(), 1);
+        assert!(results.iter().any(|r| matches!(r, Err(AppError::DomainRuleViolation(message)) if message.contains("not yet vested"))));
+        assert_eq!(f.balance(f.holding.id).await, 6.0);
+    }
+}
@@ -0,0 +1,131 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::bounded_contexts::orchestrator::DomainEvent;
+use crate::shared::domain::events::current_request_id;
+
+// =============================================================================
+// FAN VENTURES - REGISTRO DE AUDITORÍA (Append-only)
+// =============================================================================
+
+/// Entrada inmutable del registro de auditoría. El agregado es siempre el
+/// venture, así que su historial reúne compras, traspasos y repartos.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AuditLog {
+    pub event_id: Uuid,
+    pub aggregate_id: Uuid,
+    pub event_type: String,
+    /// Usuario que originó el cambio (None si fue un proceso del sistema)
+    pub actor_id: Option<Uuid>,
+    #[schema(value_type = Object)]
+    pub before_state: Value,
+    #[schema(value_type = Object)]
+    pub after_state: Value,
+    pub occurred_at: DateTime<Utc>,
+    /// `X-Request-Id` de la petición que originó el cambio (None fuera de una
+    /// petición, p.ej. eventos que llegan por el outbox relay)
+    #[serde(default)]
+    pub request_id: Option<Uuid>,
+}
+
+impl AuditLog {
+    /// Construir la entrada para los eventos auditados; el resto devuelve None
+    pub fn from_event(event: &DomainEvent) -> Option<Self> {
+        let (aggregate_id, actor_id, before_state, after_state) = match event {
+            DomainEvent::SharePurchased { venture_id, investment_id, investor_id, amount, funding_before, funding_after, .. } => (
+                *venture_id,
+                Some(*investor_id),
+                json!({ "current_funding": funding_before }),
+                json!({
+                    "current_funding": funding_after,
+                    "investment_id": investment_id,
+                    "investor_id": investor_id,
+                    "amount": amount,
+                }),
+            ),
+            DomainEvent::ShareTransferred { venture_id, investment_id, from_user_id, to_user_id, amount, .. } => (
+                *venture_id,
+                Some(*from_user_id),
+                json!({ "investment_id": investment_id, "owner_id": from_user_id, "amount": amount }),
+                json!({ "investment_id": investment_id, "owner_id": to_user_id, "amount": amount }),
+            ),
+            DomainEvent::RevenueDistributed { venture_id, distribution_id, distributed_by, amount, .. } => (
+                *venture_id,
+                *distributed_by,
+                Value::Null,
+                json!({ "distribution_id": distribution_id, "amount": amount }),
+            ),
+            _ => return None,
+        };
+
+        Some(Self {
+            event_id: Uuid::new_v4(),
+            aggregate_id,
+            event_type: event.event_type().to_string(),
+            actor_id,
+            before_state,
+            after_state,
+            occurred_at: event.occurred_at(),
+            request_id: current_request_id().map(|id| id.0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn share_purchase_records_funding_change() {
+        let (venture_id, investor_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let entry = AuditLog::from_event(&DomainEvent::SharePurchased {
+            venture_id,
+            investment_id: Uuid::new_v4(),
+            investor_id,
+            amount: 50.0,
+            funding_before: 100.0,
+            funding_after: 150.0,
+            occurred_at: Utc::now(),
+        })
+        .unwrap();
+
+        assert_eq!(entry.aggregate_id, venture_id);
+        assert_eq!(entry.actor_id, Some(investor_id));
+        assert_eq!(entry.event_type, "SharePurchased");
+        assert_eq!(entry.before_state["current_funding"], 100.0);
+        assert_eq!(entry.after_state["current_funding"], 150.0);
+    }
+
+    #[test]
+    fn share_transfer_records_owner_change() {
+        let (from, to) = (Uuid::new_v4(), Uuid::new_v4());
+        let entry = AuditLog::from_event(&DomainEvent::ShareTransferred {
+            escrow_id: Uuid::new_v4(),
+            venture_id: Uuid::new_v4(),
+            investment_id: Uuid::new_v4(),
+            from_user_id: from,
+            to_user_id: to,
+            amount: 10.0,
+            price: 25.0,
+            occurred_at: Utc::now(),
+        })
+        .unwrap();
+
+        assert_eq!(entry.before_state["owner_id"], json!(from));
+        assert_eq!(entry.after_state["owner_id"], json!(to));
+    }
+
+    #[test]
+    fn unaudited_events_are_ignored() {
+        let event = DomainEvent::SharePriceUpdated {
+            venture_id: Uuid::new_v4(),
+            price: 1.0,
+            previous_price: 0.5,
+            occurred_at: Utc::now(),
+        };
+        assert!(AuditLog::from_event(&event).is_none());
+    }
+}
@@ -114,11 +114,32 @@ pub struct ArtistVenture {
     pub benefits: Vec<VentureBenefit>,
 }
 
+impl ArtistVenture {
+    /// Financiación que falta para alcanzar el objetivo
+    pub fn remaining_funding(&self) -> f64 {
+        (self.funding_goal - self.current_funding).max(0.0)
+    }
+
+    /// Sumar una inversión confirmada. Al alcanzar el objetivo el venture pasa a
+    /// `Funded` y se cierra a nuevas inversiones; devuelve `true` solo en esa transición.
+    pub fn record_funding(&mut self, amount: f64, now: DateTime<Utc>) -> bool {
+        self.current_funding += amount;
+        self.updated_at = now;
+
+        if self.status == VentureStatus::Open && self.current_funding >= self.funding_goal {
+            self.status = VentureStatus::Funded;
+            return true;
+        }
+        false
+    }
+}
+
 /// Estado del venture
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum VentureStatus {
     Draft,      // Borrador
     Open,       // Abierto para inversiones
+    Funded,     // Objetivo alcanzado; ya no acepta inversiones
     Closed,     // Cerrado
     Cancelled,  // Cancelado
 }
@@ -128,6 +149,7 @@ impl fmt::Display for VentureStatus {
         match self {
             VentureStatus::Draft => write!(f, "Draft"),
             VentureStatus::Open => write!(f, "Open"),
+            VentureStatus::Funded => write!(f, "Funded"),
             VentureStatus::Closed => write!(f, "Closed"),
             VentureStatus::Cancelled => write!(f, "Cancelled"),
         }
@@ -141,6 +163,7 @@ impl std::str::FromStr for VentureStatus {
         match s {
             "Draft" | "draft" => Ok(VentureStatus::Draft),
             "Open" | "open" => Ok(VentureStatus::Open),
+            "Funded" | "funded" => Ok(VentureStatus::Funded),
             "Closed" | "closed" => Ok(VentureStatus::Closed),
             "Cancelled" | "cancelled" => Ok(VentureStatus::Cancelled),
             _ => Err(format!("Invalid VentureStatus: {}", s)),
@@ -668,4 +691,66 @@ pub struct CreateVentureRequest {
     pub min_investment: f64,
     pub max_investment: f64,
     pub expires_at: Option<DateTime<Utc>>,
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_venture(funding_goal: f64) -> ArtistVenture {
+        let now = Utc::now();
+        ArtistVenture {
+            id: Uuid::new_v4(),
+            artist_id: Uuid::new_v4(),
+            title: "Debut EP".to_string(),
+            description: None,
+            category: VentureCategory::Music,
+            tags: vec![],
+            risk_level: RiskLevel::Medium,
+            expected_return: 0.0,
+            artist_rating: 0.0,
+            artist_previous_ventures: 0,
+            artist_success_rate: 0.0,
+            funding_goal,
+            current_funding: 0.0,
+            min_investment: 10.0,
+            max_investment: None,
+            status: VentureStatus::Open,
+            start_date: None,
+            end_date: None,
+            created_at: now,
+            updated_at: now,
+            benefits: vec![],
+        }
+    }
+
+    #[test]
+    fn under_funded_venture_stays_open() {
+        let mut venture = open_venture(1000.0);
+
+        assert!(!venture.record_funding(400.0, Utc::now()));
+        assert!(!venture.record_funding(599.0, Utc::now()));
+
+        assert_eq!(venture.status, VentureStatus::Open);
+        assert_eq!(venture.remaining_funding(), 1.0);
+    }
+
+    #[test]
+    fn reaching_the_target_closes_the_venture() {
+        let mut venture = open_venture(1000.0);
+        venture.record_funding(600.0, Utc::now());
+
+        assert!(venture.record_funding(400.0, Utc::now()));
+        assert_eq!(venture.status, VentureStatus::Funded);
+        assert_eq!(venture.remaining_funding(), 0.0);
+
+        // Solo la primera vez cuenta como transición
+        assert!(!venture.record_funding(0.0, Utc::now()));
+    }
+
+    #[test]
+    fn funded_status_round_trips() {
+        assert_eq!("funded".parse::<VentureStatus>(), Ok(VentureStatus::Funded));
+        assert_eq!(VentureStatus::Funded.to_string(), "Funded");
+    }
+}
@@ -0,0 +1,614 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_ventures::domain::entities::{ArtistVenture, FanInvestment};
+use crate::bounded_contexts::orchestrator::DomainEvent;
+use crate::shared::domain::errors::AppError;
+
+// =============================================================================
+// FAN VENTURES - ESCROW DE COMPRAS (Reservas pendientes de confirmación)
+// =============================================================================
+
+/// Errores de dominio del flujo de escrow
+#[derive(Error, Debug, PartialEq)]
+pub enum EscrowError {
+    #[error("Reservation {0} is no longer pending ({1})")]
+    NotPending(Uuid, ReservationStatus),
+
+    #[error("Reservation {0} expired at {1}")]
+    Expired(Uuid, DateTime<Utc>),
+
+    #[error("Reservation {0} has not expired yet")]
+    NotExpired(Uuid),
+
+    #[error("Payment for reservation {0} has not settled yet")]
+    PaymentNotSettled(Uuid),
+
+    #[error("User {0} does not own this reservation")]
+    NotOwner(Uuid),
+
+    #[error("Insufficient shares available: requested {requested}, available {available}")]
+    InsufficientShares { requested: f64, available: f64 },
+
+    #[error("Share escrow {0} is no longer pending ({1})")]
+    EscrowNotPending(Uuid, EscrowStatus),
+
+    #[error("Invalid share transfer: {0}")]
+    InvalidTransfer(String),
+
+    #[error("Shares not yet vested: requested {requested}, transferable from {vests_at}")]
+    SharesNotYetVested { requested: f64, vests_at: DateTime<Utc> },
+}
+
+impl From<EscrowError> for AppError {
+    fn from(err: EscrowError) -> Self {
+        match err {
+            EscrowError::NotOwner(_) => AppError::Forbidden(err.to_string()),
+            EscrowError::InsufficientShares { .. } | EscrowError::SharesNotYetVested { .. } => {
+                AppError::DomainRuleViolation(err.to_string())
+            }
+            EscrowError::InvalidTransfer(_) => AppError::ValidationError(err.to_string()),
+            EscrowError::NotPending(..)
+            | EscrowError::EscrowNotPending(..)
+            | EscrowError::Expired(..)
+            | EscrowError::NotExpired(_)
+            | EscrowError::PaymentNotSettled(_) => AppError::InvalidState(err.to_string()),
+        }
+    }
+}
+
+/// Estado de una reserva en escrow
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ReservationStatus {
+    Pending,
+    Confirmed,
+    Cancelled,
+    Expired,
+}
+
+impl std::fmt::Display for ReservationStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReservationStatus::Pending => write!(f, "pending"),
+            ReservationStatus::Confirmed => write!(f, "confirmed"),
+            ReservationStatus::Cancelled => write!(f, "cancelled"),
+            ReservationStatus::Expired => write!(f, "expired"),
+        }
+    }
+}
+
+impl std::str::FromStr for ReservationStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" | "Pending" => Ok(ReservationStatus::Pending),
+            "confirmed" | "Confirmed" => Ok(ReservationStatus::Confirmed),
+            "cancelled" | "Cancelled" => Ok(ReservationStatus::Cancelled),
+            "expired" | "Expired" => Ok(ReservationStatus::Expired),
+            _ => Err(format!("Invalid ReservationStatus: {}", s)),
+        }
+    }
+}
+
+/// Reserva de participaciones mientras el pago de una inversión está retenido.
+///
+/// Mientras está `Pending` las participaciones no están disponibles para otros
+/// compradores. Solo una transición sale de `Pending`: confirmar, cancelar o
+/// expirar; el repositorio persiste la primera y rechaza las demás.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InvestmentReservation {
+    /// Igual al id de la inversión reservada
+    pub investment_id: Uuid,
+    pub venture_id: Uuid,
+    pub fan_id: Uuid,
+    pub shares: f64,
+    pub payment_id: Option<Uuid>,
+    /// Confirmar automáticamente cuando el pago se liquide
+    pub auto_confirm: bool,
+    pub status: ReservationStatus,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl InvestmentReservation {
+    pub fn new(
+        investment: &FanInvestment,
+        payment_id: Option<Uuid>,
+        auto_confirm: bool,
+        timeout: Duration,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            investment_id: investment.id,
+            venture_id: investment.venture_id,
+            fan_id: investment.fan_id,
+            shares: investment.investment_amount,
+            payment_id,
+            auto_confirm,
+            status: ReservationStatus::Pending,
+            expires_at: now + timeout,
+            created_at: now,
+            resolved_at: None,
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.status == ReservationStatus::Pending
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.is_pending() && now >= self.expires_at
+    }
+
+    pub fn ensure_owner(&self, user_id: Uuid) -> Result<(), EscrowError> {
+        if self.fan_id != user_id {
+            return Err(EscrowError::NotOwner(user_id));
+        }
+        Ok(())
+    }
+
+    /// Finalizar la compra. Requiere el pago liquidado y que la reserva no haya vencido.
+    pub fn confirm(&mut self, payment_settled: bool, now: DateTime<Utc>) -> Result<(), EscrowError> {
+        self.ensure_pending()?;
+        if now >= self.expires_at {
+            return Err(EscrowError::Expired(self.investment_id, self.expires_at));
+        }
+        if !payment_settled {
+            return Err(EscrowError::PaymentNotSettled(self.investment_id));
+        }
+        self.resolve(ReservationStatus::Confirmed, now);
+        Ok(())
+    }
+
+    /// Liberar las participaciones a petición del comprador
+    pub fn cancel(&mut self, now: DateTime<Utc>) -> Result<(), EscrowError> {
+        self.ensure_pending()?;
+        self.resolve(ReservationStatus::Cancelled, now);
+        Ok(())
+    }
+
+    /// Liberar las participaciones al vencer el plazo de la reserva
+    pub fn expire(&mut self, now: DateTime<Utc>) -> Result<(), EscrowError> {
+        self.ensure_pending()?;
+        if now < self.expires_at {
+            return Err(EscrowError::NotExpired(self.investment_id));
+        }
+        self.resolve(ReservationStatus::Expired, now);
+        Ok(())
+    }
+
+    fn ensure_pending(&self) -> Result<(), EscrowError> {
+        if !self.is_pending() {
+            return Err(EscrowError::NotPending(self.investment_id, self.status));
+        }
+        Ok(())
+    }
+
+    fn resolve(&mut self, status: ReservationStatus, now: DateTime<Utc>) {
+        self.status = status;
+        self.resolved_at = Some(now);
+    }
+}
+
+/// Participaciones de un venture (1 participación = 1 unidad de financiación)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ShareAvailability {
+    pub total_shares: f64,
+    pub sold_shares: f64,
+    pub reserved_shares: f64,
+    pub available_shares: f64,
+}
+
+impl ShareAvailability {
+    /// Las reservas pendientes no cuentan como vendidas pero tampoco están disponibles.
+    /// Las vencidas que el job aún no ha liberado ya no bloquean participaciones.
+    pub fn compute(venture: &ArtistVenture, reservations: &[InvestmentReservation], now: DateTime<Utc>) -> Self {
+        let reserved_shares: f64 = reservations
+            .iter()
+            .filter(|r| r.venture_id == venture.id && r.is_pending() && !r.is_expired(now))
+            .map(|r| r.shares)
+            .sum();
+        let available_shares = (venture.funding_goal - venture.current_funding - reserved_shares).max(0.0);
+
+        Self {
+            total_shares: venture.funding_goal,
+            sold_shares: venture.current_funding,
+            reserved_shares,
+            available_shares,
+        }
+    }
+
+    pub fn ensure_available(&self, requested: f64) -> Result<(), EscrowError> {
+        if requested > self.available_shares {
+            return Err(EscrowError::InsufficientShares {
+                requested,
+                available: self.available_shares,
+            });
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// FAN VENTURES - ESCROW DE TRANSFERENCIAS (Venta de participaciones entre fans)
+// =============================================================================
+
+/// Estado de un escrow de transferencia
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum EscrowStatus {
+    Pending,
+    Completed,
+    Cancelled,
+}
+
+impl std::fmt::Display for EscrowStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EscrowStatus::Pending => write!(f, "pending"),
+            EscrowStatus::Completed => write!(f, "completed"),
+            EscrowStatus::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+impl std::str::FromStr for EscrowStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" | "Pending" => Ok(EscrowStatus::Pending),
+            "completed" | "Completed" => Ok(EscrowStatus::Completed),
+            "cancelled" | "Cancelled" => Ok(EscrowStatus::Cancelled),
+            _ => Err(format!("Invalid EscrowStatus: {}", s)),
+        }
+    }
+}
+
+/// Participaciones de un vendedor retenidas mientras el comprador paga.
+///
+/// Al abrirse el escrow las participaciones salen de la inversión del vendedor,
+/// así que no se pueden vender dos veces. Al completarse pasan a la inversión
+/// del comprador; al cancelarse vuelven a la del vendedor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EscrowedShare {
+    pub escrow_id: Uuid,
+    /// Sustituye al `fractional_song_id` de la antigua propiedad fraccionada
+    pub venture_id: Uuid,
+    pub seller_id: Uuid,
+    /// Inversión del vendedor de la que salen las participaciones
+    pub seller_investment_id: Uuid,
+    pub buyer_id: Uuid,
+    pub quantity: f64,
+    /// Precio total que paga el comprador
+    pub price: f64,
+    pub payment_id: Option<Uuid>,
+    pub status: EscrowStatus,
+    pub expires_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl EscrowedShare {
+    pub fn new(
+        seller_investment: &FanInvestment,
+        buyer_id: Uuid,
+        quantity: f64,
+        price: f64,
+        timeout: Duration,
+        now: DateTime<Utc>,
+    ) -> Result<Self, EscrowError> {
+        if seller_investment.fan_id == buyer_id {
+            return Err(EscrowError::InvalidTransfer("seller and buyer must be different users".to_string()));
+        }
+        if !quantity.is_finite() || quantity <= 0.0 {
+            return Err(EscrowError::InvalidTransfer("quantity must be positive".to_string()));
+        }
+        if !price.is_finite() || price < 0.0 {
+            return Err(EscrowError::InvalidTransfer("price cannot be negative".to_string()));
+        }
+        if quantity > seller_investment.investment_amount {
+            return Err(EscrowError::InsufficientShares {
+                requested: quantity,
+                available: seller_investment.investment_amount,
+            });
+        }
+
+        Ok(Self {
+            escrow_id: Uuid::new_v4(),
+            venture_id: seller_investment.venture_id,
+            seller_id: seller_investment.fan_id,
+            seller_investment_id: seller_investment.id,
+            buyer_id,
+            quantity,
+            price,
+            payment_id: None,
+            status: EscrowStatus::Pending,
+            expires_at: now + timeout,
+            created_at: now,
+            resolved_at: None,
+        })
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.status == EscrowStatus::Pending
+    }
+
+    /// Entregar las participaciones al comprador una vez cobrado el pago
+    pub fn complete(&mut self, payment_id: Uuid, now: DateTime<Utc>) -> Result<(), EscrowError> {
+        self.ensure_pending()?;
+        if now >= self.expires_at {
+            return Err(EscrowError::Expired(self.escrow_id, self.expires_at));
+        }
+        self.payment_id = Some(payment_id);
+        self.resolve(EscrowStatus::Completed, now);
+        Ok(())
+    }
+
+    /// Devolver las participaciones al vendedor
+    pub fn cancel(&mut self, now: DateTime<Utc>) -> Result<(), EscrowError> {
+        self.ensure_pending()?;
+        self.resolve(EscrowStatus::Cancelled, now);
+        Ok(())
+    }
+
+    fn ensure_pending(&self) -> Result<(), EscrowError> {
+        if !self.is_pending() {
+            return Err(EscrowError::EscrowNotPending(self.escrow_id, self.status));
+        }
+        Ok(())
+    }
+
+    fn resolve(&mut self, status: EscrowStatus, now: DateTime<Utc>) {
+        self.status = status;
+        self.resolved_at = Some(now);
+    }
+
+    /// `SharesEscrowed` de la apertura
+    pub fn escrowed_event(&self) -> DomainEvent {
+        DomainEvent::SharesEscrowed {
+            escrow_id: self.escrow_id,
+            venture_id: self.venture_id,
+            seller_id: self.seller_id,
+            buyer_id: self.buyer_id,
+            quantity: self.quantity,
+            price: self.price,
+            expires_at: self.expires_at,
+            occurred_at: self.created_at,
+        }
+    }
+
+    /// `ShareTransferred` de un escrow completado; `buyer_investment_id` es la
+    /// inversión que recibió las participaciones
+    pub fn transferred_event(&self, buyer_investment_id: Uuid) -> DomainEvent {
+        DomainEvent::ShareTransferred {
+            escrow_id: self.escrow_id,
+            venture_id: self.venture_id,
+            investment_id: buyer_investment_id,
+            from_user_id: self.seller_id,
+            to_user_id: self.buyer_id,
+            amount: self.quantity,
+            price: self.price,
+            occurred_at: self.resolved_at.unwrap_or(self.created_at),
+        }
+    }
+
+    /// `EscrowCancelled` de un escrow cancelado
+    pub fn cancelled_event(&self, reason: &str) -> DomainEvent {
+        DomainEvent::EscrowCancelled {
+            escrow_id: self.escrow_id,
+            venture_id: self.venture_id,
+            seller_id: self.seller_id,
+            buyer_id: self.buyer_id,
+            quantity: self.quantity,
+            reason: reason.to_string(),
+            occurred_at: self.resolved_at.unwrap_or(self.created_at),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::fan_ventures::domain::entities::{
+        InvestmentStatus, InvestmentType, RiskLevel, VentureCategory, VentureStatus,
+    };
+
+    fn venture(funding_goal: f64, current_funding: f64) -> ArtistVenture {
+        let now = Utc::now();
+        ArtistVenture {
+            id: Uuid::new_v4(),
+            artist_id: Uuid::new_v4(),
+            title: "Debut EP".to_string(),
+            description: None,
+            category: VentureCategory::Music,
+            tags: vec![],
+            risk_level: RiskLevel::Medium,
+            expected_return: 0.0,
+            artist_rating: 0.0,
+            artist_previous_ventures: 0,
+            artist_success_rate: 0.0,
+            funding_goal,
+            current_funding,
+            min_investment: 10.0,
+            max_investment: None,
+            status: VentureStatus::Open,
+            start_date: None,
+            end_date: None,
+            created_at: now,
+            updated_at: now,
+            benefits: vec![],
+        }
+    }
+
+    fn reservation(venture_id: Uuid, shares: f64, now: DateTime<Utc>) -> InvestmentReservation {
+        let investment = FanInvestment::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            venture_id,
+            shares,
+            InvestmentType::RevenueShare,
+            InvestmentStatus::Pending,
+        );
+        InvestmentReservation::new(&investment, Some(Uuid::new_v4()), false, Duration::minutes(15), now)
+    }
+
+    #[test]
+    fn pending_reservations_are_excluded_from_available_shares() {
+        let now = Utc::now();
+        let venture = venture(1000.0, 200.0);
+        let reservations = vec![reservation(venture.id, 300.0, now), reservation(venture.id, 100.0, now)];
+
+        let availability = ShareAvailability::compute(&venture, &reservations, now);
+
+        assert_eq!(availability.reserved_shares, 400.0);
+        assert_eq!(availability.available_shares, 400.0);
+        assert!(availability.ensure_available(400.0).is_ok());
+        assert_eq!(
+            availability.ensure_available(450.0),
+            Err(EscrowError::InsufficientShares { requested: 450.0, available: 400.0 })
+        );
+    }
+
+    #[test]
+    fn over_funding_is_rejected() {
+        let now = Utc::now();
+        let venture = venture(1000.0, 950.0);
+
+        let availability = ShareAvailability::compute(&venture, &[], now);
+
+        assert_eq!(
+            availability.ensure_available(100.0),
+            Err(EscrowError::InsufficientShares { requested: 100.0, available: 50.0 })
+        );
+        // Exactamente lo que falta para el objetivo sí se acepta
+        assert!(availability.ensure_available(50.0).is_ok());
+    }
+
+    #[test]
+    fn timeout_releases_reserved_shares() {
+        let now = Utc::now();
+        let venture = venture(1000.0, 0.0);
+        let mut held = reservation(venture.id, 600.0, now);
+
+        assert_eq!(held.expire(now + Duration::minutes(5)), Err(EscrowError::NotExpired(held.investment_id)));
+
+        let later = now + Duration::minutes(15);
+        // Vencida pero aún sin procesar: ya no bloquea participaciones
+        let availability = ShareAvailability::compute(&venture, std::slice::from_ref(&held), later);
+        assert_eq!(availability.available_shares, 1000.0);
+
+        held.expire(later).unwrap();
+        assert_eq!(held.status, ReservationStatus::Expired);
+        assert_eq!(held.resolved_at, Some(later));
+        let availability = ShareAvailability::compute(&venture, &[held], later);
+        assert_eq!(availability.reserved_shares, 0.0);
+    }
+
+    #[test]
+    fn confirm_requires_settled_payment() {
+        let now = Utc::now();
+        let mut held = reservation(Uuid::new_v4(), 50.0, now);
+
+        assert_eq!(held.confirm(false, now), Err(EscrowError::PaymentNotSettled(held.investment_id)));
+        assert!(held.is_pending());
+
+        held.confirm(true, now + Duration::minutes(1)).unwrap();
+        assert_eq!(held.status, ReservationStatus::Confirmed);
+    }
+
+    #[test]
+    fn confirm_after_deadline_loses_to_expiry() {
+        let now = Utc::now();
+        let mut held = reservation(Uuid::new_v4(), 50.0, now);
+        let deadline = held.expires_at;
+
+        // La confirmación llega justo al vencer: la expiración gana aunque el job no haya corrido
+        assert_eq!(held.confirm(true, deadline), Err(EscrowError::Expired(held.investment_id, deadline)));
+
+        held.expire(deadline).unwrap();
+        assert_eq!(
+            held.confirm(true, deadline),
+            Err(EscrowError::NotPending(held.investment_id, ReservationStatus::Expired))
+        );
+    }
+
+    #[test]
+    fn expiry_after_confirm_is_rejected() {
+        let now = Utc::now();
+        let mut held = reservation(Uuid::new_v4(), 50.0, now);
+        let deadline = held.expires_at;
+
+        // El comprador confirma un segundo antes del vencimiento; el job llega tarde
+        held.confirm(true, deadline - Duration::seconds(1)).unwrap();
+
+        assert_eq!(held.expire(deadline), Err(EscrowError::NotPending(held.investment_id, ReservationStatus::Confirmed)));
+        assert_eq!(held.cancel(deadline), Err(EscrowError::NotPending(held.investment_id, ReservationStatus::Confirmed)));
+        assert_eq!(held.status, ReservationStatus::Confirmed);
+    }
+
+    #[test]
+    fn only_the_buyer_can_act_on_a_reservation() {
+        let held = reservation(Uuid::new_v4(), 50.0, Utc::now());
+        let stranger = Uuid::new_v4();
+
+        assert!(held.ensure_owner(held.fan_id).is_ok());
+        assert_eq!(held.ensure_owner(stranger), Err(EscrowError::NotOwner(stranger)));
+    }
+
+    fn holding(shares: f64) -> FanInvestment {
+        FanInvestment::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            shares,
+            InvestmentType::RevenueShare,
+            InvestmentStatus::Active,
+        )
+    }
+
+    #[test]
+    fn share_escrow_cannot_exceed_the_seller_holding() {
+        let seller = holding(10.0);
+        let now = Utc::now();
+
+        assert_eq!(
+            EscrowedShare::new(&seller, Uuid::new_v4(), 12.0, 120.0, Duration::minutes(15), now),
+            Err(EscrowError::InsufficientShares { requested: 12.0, available: 10.0 })
+        );
+        assert!(matches!(
+            EscrowedShare::new(&seller, seller.fan_id, 5.0, 50.0, Duration::minutes(15), now),
+            Err(EscrowError::InvalidTransfer(_))
+        ));
+
+        let escrow = EscrowedShare::new(&seller, Uuid::new_v4(), 10.0, 100.0, Duration::minutes(15), now).unwrap();
+        assert_eq!(escrow.venture_id, seller.venture_id);
+        assert_eq!(escrow.seller_investment_id, seller.id);
+        assert!(escrow.is_pending());
+    }
+
+    #[test]
+    fn share_escrow_resolves_only_once() {
+        let now = Utc::now();
+        let mut escrow = EscrowedShare::new(&holding(10.0), Uuid::new_v4(), 4.0, 40.0, Duration::minutes(15), now).unwrap();
+        let payment_id = Uuid::new_v4();
+
+        escrow.complete(payment_id, now + Duration::minutes(1)).unwrap();
+        assert_eq!(escrow.status, EscrowStatus::Completed);
+        assert_eq!(escrow.payment_id, Some(payment_id));
+        assert_eq!(
+            escrow.cancel(now + Duration::minutes(2)),
+            Err(EscrowError::EscrowNotPending(escrow.escrow_id, EscrowStatus::Completed))
+        );
+
+        // Un pago cobrado tras el vencimiento ya no entrega las participaciones
+        let mut late = EscrowedShare::new(&holding(10.0), Uuid::new_v4(), 4.0, 40.0, Duration::minutes(15), now).unwrap();
+        let deadline = late.expires_at;
+        assert_eq!(late.complete(payment_id, deadline), Err(EscrowError::Expired(late.escrow_id, deadline)));
+        late.cancel(deadline).unwrap();
+        assert_eq!(late.status, EscrowStatus::Cancelled);
+    }
+}
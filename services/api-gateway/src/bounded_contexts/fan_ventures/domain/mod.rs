@@ -4,6 +4,12 @@
 
 pub mod entities;
 pub mod repositories;
+pub mod proposals;
+pub mod escrow;
+pub mod audit;
+pub mod trades;
+pub mod portfolio;
+pub mod vesting;
 
 // Re-export the fan ventures entities
 pub use entities::{
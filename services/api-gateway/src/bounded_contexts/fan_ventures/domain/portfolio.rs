@@ -0,0 +1,162 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_ventures::domain::entities::{
+    ArtistVenture, FanInvestment, InvestmentStatus, VentureStatus,
+};
+
+// =============================================================================
+// FAN VENTURES - PORTFOLIO (Inversiones agregadas por venture)
+// =============================================================================
+
+/// Posición de un usuario en un venture
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PortfolioPosition {
+    pub venture_id: Uuid,
+    pub artist_id: Option<Uuid>,
+    pub venture_title: Option<String>,
+    pub venture_status: Option<VentureStatus>,
+    /// Participaciones confirmadas (inversiones activas o completadas)
+    pub invested: f64,
+    /// Participaciones reservadas pendientes de confirmación
+    pub pending: f64,
+    /// Porcentaje del objetivo de financiación que representa lo invertido
+    pub ownership_percentage: f64,
+    pub investment_count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VenturePortfolio {
+    pub user_id: Uuid,
+    pub total_invested: f64,
+    pub total_pending: f64,
+    pub positions: Vec<PortfolioPosition>,
+}
+
+impl VenturePortfolio {
+    /// Agrupar las inversiones por venture. Las canceladas no cuentan; un venture
+    /// que ya no existe conserva la posición pero sin datos del venture.
+    pub fn from_investments(
+        user_id: Uuid,
+        investments: &[FanInvestment],
+        ventures: &HashMap<Uuid, ArtistVenture>,
+    ) -> Self {
+        let mut positions: BTreeMap<Uuid, PortfolioPosition> = BTreeMap::new();
+
+        for investment in investments.iter().filter(|i| i.fan_id == user_id) {
+            let (invested, pending) = match investment.status {
+                InvestmentStatus::Active | InvestmentStatus::Completed => (investment.investment_amount, 0.0),
+                InvestmentStatus::Pending => (0.0, investment.investment_amount),
+                InvestmentStatus::Cancelled => continue,
+            };
+
+            let venture = ventures.get(&investment.venture_id);
+            let position = positions.entry(investment.venture_id).or_insert_with(|| PortfolioPosition {
+                venture_id: investment.venture_id,
+                artist_id: venture.map(|v| v.artist_id),
+                venture_title: venture.map(|v| v.title.clone()),
+                venture_status: venture.map(|v| v.status.clone()),
+                invested: 0.0,
+                pending: 0.0,
+                ownership_percentage: 0.0,
+                investment_count: 0,
+            });
+            position.invested += invested;
+            position.pending += pending;
+            position.investment_count += 1;
+        }
+
+        for position in positions.values_mut() {
+            if let Some(venture) = ventures.get(&position.venture_id).filter(|v| v.funding_goal > 0.0) {
+                position.ownership_percentage = position.invested / venture.funding_goal * 100.0;
+            }
+        }
+
+        let positions: Vec<PortfolioPosition> = positions.into_values().collect();
+        Self {
+            user_id,
+            total_invested: positions.iter().map(|p| p.invested).sum(),
+            total_pending: positions.iter().map(|p| p.pending).sum(),
+            positions,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::bounded_contexts::fan_ventures::domain::entities::{InvestmentType, RiskLevel, VentureCategory};
+
+    fn venture(funding_goal: f64) -> ArtistVenture {
+        let now = Utc::now();
+        ArtistVenture {
+            id: Uuid::new_v4(),
+            artist_id: Uuid::new_v4(),
+            title: "Debut EP".to_string(),
+            description: None,
+            category: VentureCategory::Music,
+            tags: vec![],
+            risk_level: RiskLevel::Medium,
+            expected_return: 0.0,
+            artist_rating: 0.0,
+            artist_previous_ventures: 0,
+            artist_success_rate: 0.0,
+            funding_goal,
+            current_funding: 0.0,
+            min_investment: 10.0,
+            max_investment: None,
+            status: VentureStatus::Open,
+            start_date: None,
+            end_date: None,
+            created_at: now,
+            updated_at: now,
+            benefits: vec![],
+        }
+    }
+
+    fn investment(fan_id: Uuid, venture_id: Uuid, amount: f64, status: InvestmentStatus) -> FanInvestment {
+        FanInvestment::new(Uuid::new_v4(), fan_id, venture_id, amount, InvestmentType::RevenueShare, status)
+    }
+
+    #[test]
+    fn investments_are_grouped_per_venture() {
+        let fan = Uuid::new_v4();
+        let (ep, tour) = (venture(1000.0), venture(500.0));
+        let investments = vec![
+            investment(fan, ep.id, 100.0, InvestmentStatus::Active),
+            investment(fan, ep.id, 150.0, InvestmentStatus::Active),
+            investment(fan, ep.id, 50.0, InvestmentStatus::Pending),
+            investment(fan, tour.id, 50.0, InvestmentStatus::Completed),
+            investment(fan, tour.id, 200.0, InvestmentStatus::Cancelled),
+        ];
+        let ventures = HashMap::from([(ep.id, ep.clone()), (tour.id, tour.clone())]);
+
+        let portfolio = VenturePortfolio::from_investments(fan, &investments, &ventures);
+
+        assert_eq!(portfolio.total_invested, 300.0);
+        assert_eq!(portfolio.total_pending, 50.0);
+        let ep_position = portfolio.positions.iter().find(|p| p.venture_id == ep.id).unwrap();
+        assert_eq!(ep_position.invested, 250.0);
+        assert_eq!(ep_position.investment_count, 3);
+        assert_eq!(ep_position.ownership_percentage, 25.0);
+        let tour_position = portfolio.positions.iter().find(|p| p.venture_id == tour.id).unwrap();
+        assert_eq!(tour_position.investment_count, 1);
+        assert_eq!(tour_position.ownership_percentage, 10.0);
+    }
+
+    #[test]
+    fn missing_venture_keeps_the_position() {
+        let fan = Uuid::new_v4();
+        let orphan = Uuid::new_v4();
+        let investments = vec![investment(fan, orphan, 75.0, InvestmentStatus::Active)];
+
+        let portfolio = VenturePortfolio::from_investments(fan, &investments, &HashMap::new());
+
+        assert_eq!(portfolio.positions.len(), 1);
+        assert_eq!(portfolio.positions[0].venture_title, None);
+        assert_eq!(portfolio.positions[0].ownership_percentage, 0.0);
+        assert_eq!(portfolio.total_invested, 75.0);
+    }
+}
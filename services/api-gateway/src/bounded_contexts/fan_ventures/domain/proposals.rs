@@ -0,0 +1,576 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_ventures::domain::entities::{FanInvestment, InvestmentStatus};
+use crate::shared::domain::errors::AppError;
+
+// =============================================================================
+// FAN VENTURES - SHAREHOLDER GOVERNANCE (Propuestas y votaciones)
+// =============================================================================
+
+/// Errores de dominio para propuestas de votación
+#[derive(Error, Debug, PartialEq)]
+pub enum ProposalError {
+    #[error("Invalid proposal: {0}")]
+    InvalidProposal(String),
+
+    #[error("Proposal has no shareholders at the snapshot time")]
+    NoShareholders,
+
+    #[error("Proposal {0} is no longer open for voting")]
+    ProposalClosed(Uuid),
+
+    #[error("Voting deadline has passed")]
+    VotingDeadlinePassed,
+
+    #[error("Voting deadline has not been reached yet")]
+    DeadlineNotReached,
+
+    #[error("Unknown option {0}")]
+    UnknownOption(Uuid),
+
+    #[error("User {0} held no shares at the snapshot time")]
+    NotAShareholder(Uuid),
+
+    #[error("User {0} has already voted on this proposal")]
+    AlreadyVoted(Uuid),
+}
+
+impl From<ProposalError> for AppError {
+    fn from(err: ProposalError) -> Self {
+        match err {
+            ProposalError::InvalidProposal(_) | ProposalError::NoShareholders | ProposalError::UnknownOption(_) => {
+                AppError::ValidationError(err.to_string())
+            }
+            ProposalError::NotAShareholder(_) => AppError::Forbidden(err.to_string()),
+            ProposalError::AlreadyVoted(_) => AppError::ConflictError(err.to_string()),
+            ProposalError::ProposalClosed(_)
+            | ProposalError::VotingDeadlinePassed
+            | ProposalError::DeadlineNotReached => AppError::InvalidState(err.to_string()),
+        }
+    }
+}
+
+/// Opción votable dentro de una propuesta
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ProposalOption {
+    pub id: Uuid,
+    pub label: String,
+}
+
+/// Controla si los recuentos parciales son públicos mientras la votación está abierta
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum TallyVisibility {
+    Live,
+    HiddenUntilFinalized,
+}
+
+impl std::fmt::Display for TallyVisibility {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TallyVisibility::Live => write!(f, "live"),
+            TallyVisibility::HiddenUntilFinalized => write!(f, "hidden_until_finalized"),
+        }
+    }
+}
+
+impl std::str::FromStr for TallyVisibility {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "live" | "Live" => Ok(TallyVisibility::Live),
+            "hidden_until_finalized" | "HiddenUntilFinalized" | "hidden" => Ok(TallyVisibility::HiddenUntilFinalized),
+            _ => Err(format!("Invalid TallyVisibility: {}", s)),
+        }
+    }
+}
+
+/// Estado de la propuesta
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub enum ProposalStatus {
+    Open,
+    Finalized,
+}
+
+impl std::fmt::Display for ProposalStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProposalStatus::Open => write!(f, "open"),
+            ProposalStatus::Finalized => write!(f, "finalized"),
+        }
+    }
+}
+
+impl std::str::FromStr for ProposalStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "open" | "Open" => Ok(ProposalStatus::Open),
+            "finalized" | "Finalized" => Ok(ProposalStatus::Finalized),
+            _ => Err(format!("Invalid ProposalStatus: {}", s)),
+        }
+    }
+}
+
+/// Resultado final de una propuesta
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum ProposalOutcome {
+    Passed { option_id: Uuid },
+    Tied { option_ids: Vec<Uuid> },
+    QuorumNotReached,
+}
+
+impl ProposalOutcome {
+    pub fn winning_option(&self) -> Option<Uuid> {
+        match self {
+            ProposalOutcome::Passed { option_id } => Some(*option_id),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ProposalOutcome::Passed { .. } => "passed",
+            ProposalOutcome::Tied { .. } => "tied",
+            ProposalOutcome::QuorumNotReached => "quorum_not_reached",
+        }
+    }
+}
+
+/// Poder de voto congelado en el momento de crear la propuesta.
+///
+/// Las transferencias o nuevas inversiones posteriores al snapshot no alteran
+/// el peso de voto: solo cuenta lo que cada fan tenía en `taken_at`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VotingSnapshot {
+    pub taken_at: DateTime<Utc>,
+    pub weights: HashMap<Uuid, f64>,
+}
+
+impl VotingSnapshot {
+    /// Construir el snapshot a partir de las inversiones del venture.
+    /// Solo cuentan inversiones confirmadas (Active/Completed) creadas antes del snapshot.
+    pub fn from_investments(investments: &[FanInvestment], taken_at: DateTime<Utc>) -> Self {
+        let mut weights: HashMap<Uuid, f64> = HashMap::new();
+        for investment in investments {
+            let confirmed = matches!(investment.status, InvestmentStatus::Active | InvestmentStatus::Completed);
+            if confirmed && investment.created_at <= taken_at && investment.investment_amount > 0.0 {
+                *weights.entry(investment.fan_id).or_insert(0.0) += investment.investment_amount;
+            }
+        }
+        Self { taken_at, weights }
+    }
+
+    pub fn weight_of(&self, holder_id: &Uuid) -> f64 {
+        self.weights.get(holder_id).copied().unwrap_or(0.0)
+    }
+
+    pub fn total_weight(&self) -> f64 {
+        self.weights.values().sum()
+    }
+}
+
+/// Voto emitido. Inmutable una vez registrado.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Vote {
+    pub voter_id: Uuid,
+    pub option_id: Uuid,
+    pub weight: f64,
+    pub cast_at: DateTime<Utc>,
+}
+
+/// Recuento ponderado por opción
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct OptionTally {
+    pub option_id: Uuid,
+    pub label: String,
+    pub votes: u32,
+    pub weight: f64,
+    pub weight_percentage: f64,
+}
+
+/// Aggregate Root: propuesta sobre la que votan los accionistas de un venture
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Proposal {
+    pub id: Uuid,
+    pub venture_id: Uuid,
+    pub artist_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub options: Vec<ProposalOption>,
+    pub deadline: DateTime<Utc>,
+    /// Porcentaje (0-100) del poder de voto total que debe participar
+    pub quorum_percentage: f64,
+    pub tally_visibility: TallyVisibility,
+    pub snapshot: VotingSnapshot,
+    pub votes: Vec<Vote>,
+    pub status: ProposalStatus,
+    pub outcome: Option<ProposalOutcome>,
+    pub created_at: DateTime<Utc>,
+    pub finalized_at: Option<DateTime<Utc>>,
+}
+
+impl Proposal {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        venture_id: Uuid,
+        artist_id: Uuid,
+        title: String,
+        description: Option<String>,
+        option_labels: Vec<String>,
+        deadline: DateTime<Utc>,
+        quorum_percentage: f64,
+        tally_visibility: TallyVisibility,
+        snapshot: VotingSnapshot,
+    ) -> Result<Self, ProposalError> {
+        let now = snapshot.taken_at;
+
+        if title.trim().is_empty() {
+            return Err(ProposalError::InvalidProposal("title is required".to_string()));
+        }
+        if deadline <= now {
+            return Err(ProposalError::InvalidProposal("deadline must be in the future".to_string()));
+        }
+        if !(0.0..=100.0).contains(&quorum_percentage) {
+            return Err(ProposalError::InvalidProposal(format!(
+                "quorum must be between 0 and 100, got {}",
+                quorum_percentage
+            )));
+        }
+
+        let mut options: Vec<ProposalOption> = Vec::with_capacity(option_labels.len());
+        for label in option_labels {
+            let label = label.trim().to_string();
+            if label.is_empty() {
+                return Err(ProposalError::InvalidProposal("option labels cannot be empty".to_string()));
+            }
+            if options.iter().any(|o| o.label.eq_ignore_ascii_case(&label)) {
+                return Err(ProposalError::InvalidProposal(format!("duplicate option '{}'", label)));
+            }
+            options.push(ProposalOption { id: Uuid::new_v4(), label });
+        }
+        if options.len() < 2 {
+            return Err(ProposalError::InvalidProposal("at least two options are required".to_string()));
+        }
+
+        if snapshot.total_weight() <= 0.0 {
+            return Err(ProposalError::NoShareholders);
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            venture_id,
+            artist_id,
+            title,
+            description,
+            options,
+            deadline,
+            quorum_percentage,
+            tally_visibility,
+            snapshot,
+            votes: Vec::new(),
+            status: ProposalStatus::Open,
+            outcome: None,
+            created_at: now,
+            finalized_at: None,
+        })
+    }
+
+    /// Emitir un voto. El peso sale siempre del snapshot, nunca de las tenencias actuales.
+    pub fn cast_vote(&mut self, voter_id: Uuid, option_id: Uuid, now: DateTime<Utc>) -> Result<Vote, ProposalError> {
+        if self.status != ProposalStatus::Open {
+            return Err(ProposalError::ProposalClosed(self.id));
+        }
+        if now >= self.deadline {
+            return Err(ProposalError::VotingDeadlinePassed);
+        }
+        if !self.options.iter().any(|o| o.id == option_id) {
+            return Err(ProposalError::UnknownOption(option_id));
+        }
+        if self.has_voted(&voter_id) {
+            return Err(ProposalError::AlreadyVoted(voter_id));
+        }
+
+        let weight = self.snapshot.weight_of(&voter_id);
+        if weight <= 0.0 {
+            return Err(ProposalError::NotAShareholder(voter_id));
+        }
+
+        let vote = Vote { voter_id, option_id, weight, cast_at: now };
+        self.votes.push(vote.clone());
+        Ok(vote)
+    }
+
+    pub fn has_voted(&self, voter_id: &Uuid) -> bool {
+        self.votes.iter().any(|v| &v.voter_id == voter_id)
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.status == ProposalStatus::Open && now >= self.deadline
+    }
+
+    pub fn participating_weight(&self) -> f64 {
+        self.votes.iter().map(|v| v.weight).sum()
+    }
+
+    /// Participación como porcentaje del poder de voto total del snapshot
+    pub fn turnout_percentage(&self) -> f64 {
+        let total = self.snapshot.total_weight();
+        if total <= 0.0 {
+            return 0.0;
+        }
+        self.participating_weight() / total * 100.0
+    }
+
+    pub fn quorum_reached(&self) -> bool {
+        self.turnout_percentage() >= self.quorum_percentage
+    }
+
+    pub fn tally(&self) -> Vec<OptionTally> {
+        let participating = self.participating_weight();
+        self.options
+            .iter()
+            .map(|option| {
+                let option_votes: Vec<&Vote> = self.votes.iter().filter(|v| v.option_id == option.id).collect();
+                let weight: f64 = option_votes.iter().map(|v| v.weight).sum();
+                OptionTally {
+                    option_id: option.id,
+                    label: option.label.clone(),
+                    votes: option_votes.len() as u32,
+                    weight,
+                    weight_percentage: if participating > 0.0 { weight / participating * 100.0 } else { 0.0 },
+                }
+            })
+            .collect()
+    }
+
+    /// Recuentos visibles para los clientes según la configuración de la propuesta
+    pub fn visible_tally(&self) -> Option<Vec<OptionTally>> {
+        match (self.tally_visibility, self.status) {
+            (TallyVisibility::HiddenUntilFinalized, ProposalStatus::Open) => None,
+            _ => Some(self.tally()),
+        }
+    }
+
+    /// Cerrar la votación una vez vencido el plazo y calcular el resultado
+    pub fn finalize(&mut self, now: DateTime<Utc>) -> Result<ProposalOutcome, ProposalError> {
+        if self.status != ProposalStatus::Open {
+            return Err(ProposalError::ProposalClosed(self.id));
+        }
+        if now < self.deadline {
+            return Err(ProposalError::DeadlineNotReached);
+        }
+
+        let outcome = if !self.quorum_reached() || self.votes.is_empty() {
+            ProposalOutcome::QuorumNotReached
+        } else {
+            let tally = self.tally();
+            let max_weight = tally.iter().map(|t| t.weight).fold(0.0_f64, f64::max);
+            let leaders: Vec<Uuid> = tally
+                .iter()
+                .filter(|t| (t.weight - max_weight).abs() < f64::EPSILON)
+                .map(|t| t.option_id)
+                .collect();
+            if leaders.len() == 1 {
+                ProposalOutcome::Passed { option_id: leaders[0] }
+            } else {
+                ProposalOutcome::Tied { option_ids: leaders }
+            }
+        };
+
+        self.status = ProposalStatus::Finalized;
+        self.outcome = Some(outcome.clone());
+        self.finalized_at = Some(now);
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn investment(fan_id: Uuid, amount: f64, status: InvestmentStatus, created_at: DateTime<Utc>) -> FanInvestment {
+        let mut inv = FanInvestment::new(
+            Uuid::new_v4(),
+            fan_id,
+            Uuid::new_v4(),
+            amount,
+            crate::bounded_contexts::fan_ventures::domain::entities::InvestmentType::RevenueShare,
+            status,
+        );
+        inv.created_at = created_at;
+        inv
+    }
+
+    fn proposal_with(weights: &[(Uuid, f64)], quorum: f64, visibility: TallyVisibility) -> Proposal {
+        let now = Utc::now();
+        let snapshot = VotingSnapshot {
+            taken_at: now,
+            weights: weights.iter().cloned().collect(),
+        };
+        Proposal::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "Accept sync licensing offer".to_string(),
+            None,
+            vec!["Accept".to_string(), "Reject".to_string()],
+            now + Duration::days(7),
+            quorum,
+            visibility,
+            snapshot,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn snapshot_only_counts_confirmed_investments_before_snapshot() {
+        let now = Utc::now();
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+        let investments = vec![
+            investment(alice, 100.0, InvestmentStatus::Active, now - Duration::days(3)),
+            investment(alice, 50.0, InvestmentStatus::Completed, now - Duration::days(1)),
+            investment(bob, 300.0, InvestmentStatus::Pending, now - Duration::days(1)),
+            investment(bob, 25.0, InvestmentStatus::Active, now + Duration::hours(1)),
+        ];
+
+        let snapshot = VotingSnapshot::from_investments(&investments, now);
+
+        assert_eq!(snapshot.weight_of(&alice), 150.0);
+        assert_eq!(snapshot.weight_of(&bob), 0.0);
+        assert_eq!(snapshot.total_weight(), 150.0);
+    }
+
+    #[test]
+    fn votes_are_weighted_by_snapshot_shares() {
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+        let mut proposal = proposal_with(&[(alice, 300.0), (bob, 100.0)], 0.0, TallyVisibility::Live);
+        let accept = proposal.options[0].id;
+        let reject = proposal.options[1].id;
+        let now = proposal.created_at + Duration::hours(1);
+
+        proposal.cast_vote(alice, accept, now).unwrap();
+        proposal.cast_vote(bob, reject, now).unwrap();
+
+        let tally = proposal.tally();
+        assert_eq!(tally[0].weight, 300.0);
+        assert_eq!(tally[1].weight, 100.0);
+        assert_eq!(tally[0].weight_percentage, 75.0);
+    }
+
+    #[test]
+    fn share_changes_after_snapshot_do_not_change_voting_power() {
+        let alice = Uuid::new_v4();
+        let newcomer = Uuid::new_v4();
+        let mut proposal = proposal_with(&[(alice, 100.0)], 0.0, TallyVisibility::Live);
+        let accept = proposal.options[0].id;
+        let now = proposal.created_at + Duration::hours(1);
+
+        // El newcomer compró participaciones después del snapshot: no vota
+        assert_eq!(
+            proposal.cast_vote(newcomer, accept, now),
+            Err(ProposalError::NotAShareholder(newcomer))
+        );
+        let vote = proposal.cast_vote(alice, accept, now).unwrap();
+        assert_eq!(vote.weight, 100.0);
+    }
+
+    #[test]
+    fn double_vote_is_rejected() {
+        let alice = Uuid::new_v4();
+        let mut proposal = proposal_with(&[(alice, 100.0)], 0.0, TallyVisibility::Live);
+        let accept = proposal.options[0].id;
+        let reject = proposal.options[1].id;
+        let now = proposal.created_at + Duration::hours(1);
+
+        proposal.cast_vote(alice, accept, now).unwrap();
+        assert_eq!(proposal.cast_vote(alice, reject, now), Err(ProposalError::AlreadyVoted(alice)));
+        assert_eq!(proposal.votes.len(), 1);
+        assert_eq!(proposal.votes[0].option_id, accept);
+    }
+
+    #[test]
+    fn voting_after_deadline_is_rejected() {
+        let alice = Uuid::new_v4();
+        let mut proposal = proposal_with(&[(alice, 100.0)], 0.0, TallyVisibility::Live);
+        let accept = proposal.options[0].id;
+
+        let late = proposal.deadline + Duration::seconds(1);
+        assert_eq!(proposal.cast_vote(alice, accept, late), Err(ProposalError::VotingDeadlinePassed));
+    }
+
+    #[test]
+    fn finalize_requires_quorum() {
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+        let mut proposal = proposal_with(&[(alice, 40.0), (bob, 60.0)], 50.0, TallyVisibility::Live);
+        let accept = proposal.options[0].id;
+        proposal.cast_vote(alice, accept, proposal.created_at + Duration::hours(1)).unwrap();
+
+        assert_eq!(
+            proposal.finalize(proposal.created_at + Duration::hours(2)),
+            Err(ProposalError::DeadlineNotReached)
+        );
+        let outcome = proposal.finalize(proposal.deadline).unwrap();
+        assert_eq!(outcome, ProposalOutcome::QuorumNotReached);
+        assert_eq!(proposal.status, ProposalStatus::Finalized);
+    }
+
+    #[test]
+    fn finalize_picks_heaviest_option() {
+        let alice = Uuid::new_v4();
+        let bob = Uuid::new_v4();
+        let carol = Uuid::new_v4();
+        let mut proposal = proposal_with(&[(alice, 10.0), (bob, 15.0), (carol, 70.0)], 50.0, TallyVisibility::Live);
+        let accept = proposal.options[0].id;
+        let reject = proposal.options[1].id;
+        let now = proposal.created_at + Duration::hours(1);
+
+        // Dos votantes eligen Accept, pero el peso de Carol decide
+        proposal.cast_vote(alice, accept, now).unwrap();
+        proposal.cast_vote(bob, accept, now).unwrap();
+        proposal.cast_vote(carol, reject, now).unwrap();
+
+        let outcome = proposal.finalize(proposal.deadline).unwrap();
+        assert_eq!(outcome, ProposalOutcome::Passed { option_id: reject });
+    }
+
+    #[test]
+    fn hidden_tallies_are_revealed_only_after_finalization() {
+        let alice = Uuid::new_v4();
+        let mut proposal = proposal_with(&[(alice, 100.0)], 0.0, TallyVisibility::HiddenUntilFinalized);
+        let accept = proposal.options[0].id;
+        proposal.cast_vote(alice, accept, proposal.created_at + Duration::hours(1)).unwrap();
+
+        assert!(proposal.visible_tally().is_none());
+        proposal.finalize(proposal.deadline).unwrap();
+        assert_eq!(proposal.visible_tally().unwrap()[0].votes, 1);
+    }
+
+    #[test]
+    fn proposal_without_shareholders_is_rejected() {
+        let now = Utc::now();
+        let result = Proposal::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            "Remix contest".to_string(),
+            None,
+            vec!["Yes".to_string(), "No".to_string()],
+            now + Duration::days(1),
+            10.0,
+            TallyVisibility::Live,
+            VotingSnapshot { taken_at: now, weights: HashMap::new() },
+        );
+        assert_eq!(result.err(), Some(ProposalError::NoShareholders));
+    }
+}
@@ -1,6 +1,12 @@
 use async_trait::async_trait;
 use uuid::Uuid;
-use crate::bounded_contexts::fan_ventures::domain::entities::ArtistVenture;
+use crate::bounded_contexts::fan_ventures::domain::audit::AuditLog;
+use crate::bounded_contexts::fan_ventures::domain::entities::{ArtistVenture, FanInvestment};
+use crate::bounded_contexts::fan_ventures::domain::escrow::{EscrowedShare, InvestmentReservation};
+use crate::bounded_contexts::fan_ventures::domain::proposals::{Proposal, Vote};
+use crate::bounded_contexts::fan_ventures::domain::trades::SecondaryMarketTrade;
+use crate::bounded_contexts::fan_ventures::domain::vesting::ShareHolder;
+use crate::shared::domain::errors::AppError;
 
 #[async_trait]
 pub trait ArtistVentureRepository: Send + Sync {
@@ -11,3 +17,91 @@ pub trait ArtistVentureRepository: Send + Sync {
     async fn delete(&self, venture_id: &Uuid) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
     async fn find_all_active(&self) -> Result<Vec<ArtistVenture>, Box<dyn std::error::Error + Send + Sync>>;
 }
+
+/// Ventures e inversiones tal como los usan las reservas y las propuestas
+#[async_trait]
+pub trait VentureRepository: Send + Sync {
+    async fn create_venture(&self, venture: &ArtistVenture) -> Result<(), AppError>;
+    async fn get_venture(&self, venture_id: Uuid) -> Result<Option<ArtistVenture>, AppError>;
+    async fn update_venture(&self, venture: &ArtistVenture) -> Result<(), AppError>;
+    async fn create_fan_investment(&self, investment: &FanInvestment) -> Result<(), AppError>;
+    async fn get_investment_by_id(&self, investment_id: Uuid) -> Result<Option<FanInvestment>, AppError>;
+    async fn update_fan_investment(&self, investment: &FanInvestment) -> Result<(), AppError>;
+    async fn get_fan_investments_by_venture(&self, venture_id: Uuid) -> Result<Vec<FanInvestment>, AppError>;
+}
+
+#[async_trait]
+pub trait ProposalRepository: Send + Sync {
+    /// Persistir la propuesta junto con su snapshot de poder de voto
+    async fn create(&self, proposal: &Proposal) -> Result<(), AppError>;
+    async fn find_by_id(&self, proposal_id: &Uuid) -> Result<Option<Proposal>, AppError>;
+    async fn find_by_venture(&self, venture_id: &Uuid) -> Result<Vec<Proposal>, AppError>;
+    /// Registrar un voto. Debe fallar con `ConflictError` si el votante ya votó
+    /// y con `InvalidState` si la propuesta guardada ya no está abierta.
+    async fn record_vote(&self, proposal_id: &Uuid, vote: &Vote) -> Result<(), AppError>;
+    async fn mark_finalized(&self, proposal: &Proposal) -> Result<(), AppError>;
+    /// Propuestas abiertas cuyo plazo ya venció
+    async fn find_due(&self, now: chrono::DateTime<chrono::Utc>) -> Result<Vec<Proposal>, AppError>;
+}
+
+#[async_trait]
+pub trait InvestmentReservationRepository: Send + Sync {
+    async fn create(&self, reservation: &InvestmentReservation) -> Result<(), AppError>;
+    async fn find_by_investment(&self, investment_id: &Uuid) -> Result<Option<InvestmentReservation>, AppError>;
+    /// Reservas pendientes de un venture (incluye las vencidas aún sin liberar)
+    async fn find_pending_by_venture(&self, venture_id: &Uuid) -> Result<Vec<InvestmentReservation>, AppError>;
+    /// Persistir la salida de `Pending`. Debe fallar con `ConcurrencyConflict`
+    /// si otra transición (confirmar, cancelar, expirar) ya la resolvió.
+    async fn resolve(&self, reservation: &InvestmentReservation) -> Result<(), AppError>;
+    /// Reservas pendientes cuyo plazo ya venció
+    async fn find_expired(&self, now: chrono::DateTime<chrono::Utc>) -> Result<Vec<InvestmentReservation>, AppError>;
+}
+
+#[async_trait]
+pub trait ShareEscrowRepository: Send + Sync {
+    /// Inversión activa de la que un fan vende participaciones
+    async fn find_holding(&self, investment_id: &Uuid) -> Result<Option<FanInvestment>, AppError>;
+    /// La inversión con el calendario de vesting de su venture y las
+    /// participaciones que ya salieron de ella en escrows no cancelados
+    async fn find_share_holder(&self, investment_id: &Uuid) -> Result<Option<ShareHolder>, AppError>;
+    /// Guardar el escrow y descontar a la vez las participaciones de la inversión
+    /// del vendedor. Debe fallar con `DomainRuleViolation` si ya no le quedan o
+    /// si aún no están liberadas: el vesting se comprueba con la inversión
+    /// bloqueada, contando los escrows abiertos a la vez.
+    ///
+    /// Cada cambio de estado deja su evento en el outbox en la misma transacción.
+    async fn open(&self, escrow: &EscrowedShare) -> Result<(), AppError>;
+    /// Persistir el escrow completado y abonar las participaciones al comprador:
+    /// en `buyer_investment` o, si ya invierte en el venture, en su inversión
+    /// activa. Devuelve la inversión resultante. Debe fallar con
+    /// `ConcurrencyConflict` si el escrow ya no estaba pendiente.
+    async fn complete(&self, escrow: &EscrowedShare, buyer_investment: &FanInvestment) -> Result<FanInvestment, AppError>;
+    /// Persistir la cancelación y devolver las participaciones al vendedor.
+    /// Debe fallar con `ConcurrencyConflict` si el escrow ya no estaba pendiente.
+    async fn cancel(&self, escrow: &EscrowedShare, reason: &str) -> Result<(), AppError>;
+    async fn find_by_id(&self, escrow_id: &Uuid) -> Result<Option<EscrowedShare>, AppError>;
+}
+
+/// Registro append-only: no existen operaciones de modificación ni borrado
+#[async_trait]
+pub trait AuditLogRepository: Send + Sync {
+    async fn append(&self, entry: &AuditLog) -> Result<(), AppError>;
+    /// Historial del agregado en orden cronológico
+    async fn get_for_aggregate(&self, aggregate_id: Uuid) -> Result<Vec<AuditLog>, AppError>;
+}
+
+#[async_trait]
+pub trait SecondaryMarketTradeRepository: Send + Sync {
+    /// Registrar una operación; si ya estaba registrada no hace nada
+    async fn record(&self, trade: &SecondaryMarketTrade) -> Result<(), AppError>;
+    /// Operaciones del venture, la más reciente primero (`page` empieza en 0)
+    async fn find_by_venture(&self, venture_id: &Uuid, page: u32, page_size: u32) -> Result<Vec<SecondaryMarketTrade>, AppError>;
+    async fn count_by_venture(&self, venture_id: &Uuid) -> Result<u64, AppError>;
+    /// Las operaciones que fijan `price_change_24h` en `now`: la última hasta
+    /// `now` y la última hasta 24h antes
+    async fn find_price_reference_trades(
+        &self,
+        venture_id: &Uuid,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<SecondaryMarketTrade>, AppError>;
+}
@@ -0,0 +1,136 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::bounded_contexts::orchestrator::DomainEvent;
+
+// =============================================================================
+// FAN VENTURES - MERCADO SECUNDARIO (Historial de operaciones entre fans)
+// =============================================================================
+
+/// Ventana de la variación de precio que se muestra con el venture
+pub const PRICE_CHANGE_WINDOW_HOURS: i64 = 24;
+
+/// Venta de participaciones entre fans ya cobrada. Su id es el del escrow,
+/// así que registrar dos veces el mismo `ShareTransferred` no la duplica.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SecondaryMarketTrade {
+    pub id: Uuid,
+    pub venture_id: Uuid,
+    pub seller_id: Uuid,
+    pub buyer_id: Uuid,
+    pub shares_quantity: f64,
+    pub price_per_share: f64,
+    pub executed_at: DateTime<Utc>,
+}
+
+impl SecondaryMarketTrade {
+    /// Operación de un `ShareTransferred`; el resto de eventos devuelve None
+    pub fn from_event(event: &DomainEvent) -> Option<Self> {
+        match event {
+            DomainEvent::ShareTransferred { escrow_id, venture_id, from_user_id, to_user_id, amount, price, occurred_at, .. }
+                if *amount > 0.0 =>
+            {
+                Some(Self {
+                    id: *escrow_id,
+                    venture_id: *venture_id,
+                    seller_id: *from_user_id,
+                    buyer_id: *to_user_id,
+                    shares_quantity: *amount,
+                    price_per_share: price / amount,
+                    executed_at: *occurred_at,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Variación porcentual entre el último precio negociado hasta `now` y el
+/// vigente 24h antes (la última operación anterior a ese momento):
+/// `(latest - price_24h_ago) / price_24h_ago * 100`.
+///
+/// None si no hubo operaciones antes de la ventana o su precio era 0.
+pub fn price_change_24h(trades: &[SecondaryMarketTrade], now: DateTime<Utc>) -> Option<f64> {
+    let window_start = now - Duration::hours(PRICE_CHANGE_WINDOW_HOURS);
+    let last_until = |until: DateTime<Utc>| {
+        trades
+            .iter()
+            .filter(|trade| trade.executed_at <= until)
+            .max_by_key(|trade| trade.executed_at)
+            .map(|trade| trade.price_per_share)
+    };
+
+    let latest = last_until(now)?;
+    let price_24h_ago = last_until(window_start).filter(|price| *price > 0.0)?;
+    Some((latest - price_24h_ago) / price_24h_ago * 100.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 15, 12, 0, 0).unwrap()
+    }
+
+    fn trade(hours_ago: i64, price_per_share: f64) -> SecondaryMarketTrade {
+        SecondaryMarketTrade {
+            id: Uuid::new_v4(),
+            venture_id: Uuid::nil(),
+            seller_id: Uuid::new_v4(),
+            buyer_id: Uuid::new_v4(),
+            shares_quantity: 1.0,
+            price_per_share,
+            executed_at: now() - Duration::hours(hours_ago),
+        }
+    }
+
+    #[test]
+    fn transfer_event_becomes_a_trade_priced_per_share() {
+        let (escrow_id, seller, buyer) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let trade = SecondaryMarketTrade::from_event(&DomainEvent::ShareTransferred {
+            escrow_id,
+            venture_id: Uuid::new_v4(),
+            investment_id: Uuid::new_v4(),
+            from_user_id: seller,
+            to_user_id: buyer,
+            amount: 4.0,
+            price: 50.0,
+            occurred_at: now(),
+        })
+        .unwrap();
+
+        assert_eq!(trade.id, escrow_id);
+        assert_eq!((trade.seller_id, trade.buyer_id), (seller, buyer));
+        assert_eq!(trade.price_per_share, 12.5);
+        assert_eq!(trade.executed_at, now());
+    }
+
+    #[test]
+    fn price_change_compares_latest_trade_with_the_price_24h_ago() {
+        // 30h antes a 10, 25h antes a 8: el precio de hace 24h es 8
+        let trades = vec![trade(30, 10.0), trade(25, 8.0), trade(3, 9.0), trade(1, 10.0)];
+        assert_eq!(price_change_24h(&trades, now()), Some(25.0));
+
+        // Con `now` una hora antes la última operación aún no ha ocurrido
+        let earlier = now() - Duration::hours(1);
+        assert_eq!(price_change_24h(&trades, earlier), Some(12.5));
+    }
+
+    #[test]
+    fn price_drop_is_negative_and_unchanged_price_is_zero() {
+        assert_eq!(price_change_24h(&[trade(48, 20.0), trade(2, 15.0)], now()), Some(-25.0));
+        // Sin operaciones en la ventana el precio no ha cambiado
+        assert_eq!(price_change_24h(&[trade(48, 20.0)], now()), Some(0.0));
+    }
+
+    #[test]
+    fn no_price_change_without_a_price_before_the_window() {
+        assert_eq!(price_change_24h(&[], now()), None);
+        assert_eq!(price_change_24h(&[trade(23, 5.0), trade(1, 6.0)], now()), None);
+        assert_eq!(price_change_24h(&[trade(30, 0.0), trade(1, 6.0)], now()), None);
+    }
+}
@@ -0,0 +1,250 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::bounded_contexts::fan_ventures::domain::entities::FanInvestment;
+use crate::bounded_contexts::fan_ventures::domain::escrow::EscrowError;
+
+// =============================================================================
+// FAN VENTURES - VESTING (Liberación gradual de participaciones)
+// =============================================================================
+//
+// Un venture puede liberar sus participaciones poco a poco. Hasta el cliff no
+// se puede transferir ninguna; a partir de ahí `Cliff` libera todo de golpe y
+// `Linear` libera en proporción al tiempo transcurrido desde `start_date`
+// hasta completar `total_duration_days`.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VestingType {
+    Linear,
+    Cliff,
+}
+
+impl std::fmt::Display for VestingType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VestingType::Linear => write!(f, "linear"),
+            VestingType::Cliff => write!(f, "cliff"),
+        }
+    }
+}
+
+impl std::str::FromStr for VestingType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "linear" => Ok(VestingType::Linear),
+            "cliff" => Ok(VestingType::Cliff),
+            other => Err(format!("Unknown vesting type: {}", other)),
+        }
+    }
+}
+
+/// Calendario de liberación de las participaciones de un venture
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VestingSchedule {
+    pub start_date: DateTime<Utc>,
+    pub cliff_days: u32,
+    pub total_duration_days: u32,
+    pub vesting_type: VestingType,
+}
+
+impl VestingSchedule {
+    pub fn new(start_date: DateTime<Utc>, cliff_days: u32, total_duration_days: u32, vesting_type: VestingType) -> Self {
+        Self {
+            start_date,
+            cliff_days: cliff_days.min(total_duration_days),
+            total_duration_days,
+            vesting_type,
+        }
+    }
+
+    pub fn cliff_date(&self) -> DateTime<Utc> {
+        self.start_date + Duration::days(self.cliff_days as i64)
+    }
+
+    /// Fecha en la que todas las participaciones están liberadas
+    pub fn fully_vested_at(&self) -> DateTime<Utc> {
+        match self.vesting_type {
+            VestingType::Cliff => self.cliff_date(),
+            VestingType::Linear => self.start_date + Duration::days(self.total_duration_days as i64),
+        }
+    }
+
+    /// Porcentaje liberado (0-100) en `date`
+    pub fn vested_percentage_at(&self, date: DateTime<Utc>) -> f64 {
+        if date < self.cliff_date() {
+            return 0.0;
+        }
+        if date >= self.fully_vested_at() {
+            return 100.0;
+        }
+
+        let elapsed = (date - self.start_date).num_seconds() as f64;
+        let total = (self.fully_vested_at() - self.start_date).num_seconds() as f64;
+        elapsed / total * 100.0
+    }
+
+    /// Primera fecha en la que está liberado al menos `percentage`
+    pub fn vests_at(&self, percentage: f64) -> DateTime<Utc> {
+        if percentage <= 0.0 {
+            return self.start_date;
+        }
+        match self.vesting_type {
+            VestingType::Cliff => self.cliff_date(),
+            VestingType::Linear => {
+                let total = (self.fully_vested_at() - self.start_date).num_seconds() as f64;
+                let seconds = (total * percentage.min(100.0) / 100.0).ceil() as i64;
+                (self.start_date + Duration::seconds(seconds)).max(self.cliff_date())
+            }
+        }
+    }
+}
+
+/// Inversión de un fan junto con lo necesario para saber cuánto puede transferir
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShareHolder {
+    pub investment: FanInvestment,
+    /// Participaciones que ya salieron de la inversión (en escrow o transferidas)
+    pub transferred_shares: f64,
+    /// Calendario del venture; sin él todo es transferible
+    pub vesting_schedule: Option<VestingSchedule>,
+}
+
+impl ShareHolder {
+    /// Participaciones sujetas al calendario: las que tiene más las que ya transfirió
+    pub fn granted_shares(&self) -> f64 {
+        self.investment.investment_amount + self.transferred_shares
+    }
+
+    /// Participaciones liberadas en `date` que aún no ha transferido
+    pub fn transferable_shares_at(&self, date: DateTime<Utc>) -> f64 {
+        let held = self.investment.investment_amount;
+        match &self.vesting_schedule {
+            Some(schedule) => {
+                let vested = self.granted_shares() * schedule.vested_percentage_at(date) / 100.0;
+                (vested - self.transferred_shares).clamp(0.0, held)
+            }
+            None => held,
+        }
+    }
+
+    /// Comprobar que `quantity` se puede transferir en `now`
+    pub fn ensure_transferable(&self, quantity: f64, now: DateTime<Utc>) -> Result<(), EscrowError> {
+        let held = self.investment.investment_amount;
+        if quantity > held {
+            return Err(EscrowError::InsufficientShares { requested: quantity, available: held });
+        }
+        if quantity <= self.transferable_shares_at(now) {
+            return Ok(());
+        }
+
+        match &self.vesting_schedule {
+            Some(schedule) => {
+                let percentage = (self.transferred_shares + quantity) / self.granted_shares() * 100.0;
+                Err(EscrowError::SharesNotYetVested {
+                    requested: quantity,
+                    vests_at: schedule.vests_at(percentage),
+                })
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::fan_ventures::domain::entities::{InvestmentStatus, InvestmentType};
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap()
+    }
+
+    /// Cliff de 90 días sobre un año de liberación lineal
+    fn linear() -> VestingSchedule {
+        VestingSchedule::new(start(), 90, 360, VestingType::Linear)
+    }
+
+    fn holder(held: f64, transferred_shares: f64, vesting_schedule: Option<VestingSchedule>) -> ShareHolder {
+        ShareHolder {
+            investment: FanInvestment::new(
+                Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), held, InvestmentType::RevenueShare, InvestmentStatus::Active,
+            ),
+            transferred_shares,
+            vesting_schedule,
+        }
+    }
+
+    #[test]
+    fn linear_vesting_at_cliff_halfway_and_end() {
+        let schedule = linear();
+
+        assert_eq!(schedule.vested_percentage_at(start()), 0.0);
+        assert_eq!(schedule.vested_percentage_at(start() + Duration::days(89)), 0.0);
+        assert_eq!(schedule.vested_percentage_at(start() + Duration::days(90)), 25.0);
+        assert_eq!(schedule.vested_percentage_at(start() + Duration::days(180)), 50.0);
+        assert_eq!(schedule.vested_percentage_at(start() + Duration::days(360)), 100.0);
+        assert_eq!(schedule.vested_percentage_at(start() + Duration::days(500)), 100.0);
+    }
+
+    #[test]
+    fn cliff_vesting_releases_everything_at_the_cliff() {
+        let schedule = VestingSchedule::new(start(), 90, 360, VestingType::Cliff);
+
+        assert_eq!(schedule.vested_percentage_at(start() + Duration::days(89)), 0.0);
+        assert_eq!(schedule.vested_percentage_at(start() + Duration::days(90)), 100.0);
+        assert_eq!(schedule.vests_at(60.0), start() + Duration::days(90));
+    }
+
+    #[test]
+    fn transferable_shares_follow_the_schedule() {
+        let holder = holder(100.0, 0.0, Some(linear()));
+
+        assert_eq!(holder.transferable_shares_at(start() + Duration::days(30)), 0.0);
+        assert_eq!(holder.transferable_shares_at(start() + Duration::days(90)), 25.0);
+        assert_eq!(holder.transferable_shares_at(start() + Duration::days(180)), 50.0);
+        assert_eq!(holder.transferable_shares_at(start() + Duration::days(360)), 100.0);
+    }
+
+    #[test]
+    fn already_transferred_shares_count_against_the_vested_ones() {
+        // 40 de 100 ya vendidas: a mitad del calendario quedan 10 liberadas
+        let holder = holder(60.0, 40.0, Some(linear()));
+        let halfway = start() + Duration::days(180);
+
+        assert_eq!(holder.transferable_shares_at(halfway), 10.0);
+        assert!(holder.ensure_transferable(10.0, halfway).is_ok());
+        assert_eq!(
+            holder.ensure_transferable(20.0, halfway),
+            Err(EscrowError::SharesNotYetVested { requested: 20.0, vests_at: start() + Duration::days(216) })
+        );
+    }
+
+    #[test]
+    fn unvested_shares_cannot_be_transferred_before_the_cliff() {
+        let holder = holder(100.0, 0.0, Some(linear()));
+
+        let result = holder.ensure_transferable(10.0, start() + Duration::days(10));
+
+        // El 10% se alcanza antes del cliff, así que se libera con él
+        assert_eq!(
+            result,
+            Err(EscrowError::SharesNotYetVested { requested: 10.0, vests_at: start() + Duration::days(90) })
+        );
+        assert_eq!(
+            holder.ensure_transferable(120.0, start() + Duration::days(400)),
+            Err(EscrowError::InsufficientShares { requested: 120.0, available: 100.0 })
+        );
+    }
+
+    #[test]
+    fn without_schedule_everything_is_transferable() {
+        let holder = holder(10.0, 5.0, None);
+
+        assert_eq!(holder.transferable_shares_at(start()), 10.0);
+        assert!(holder.ensure_transferable(10.0, start()).is_ok());
+    }
+}
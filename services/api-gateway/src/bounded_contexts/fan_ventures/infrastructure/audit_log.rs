@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_ventures::domain::audit::AuditLog;
+use crate::bounded_contexts::fan_ventures::domain::repositories::AuditLogRepository;
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+use crate::shared::domain::errors::AppError;
+
+/// Repositorio PostgreSQL del registro de auditoría. La tabla sólo admite
+/// INSERT y SELECT (ver migración 042).
+pub struct PostgresAuditLogRepository {
+    pool: PgPool,
+}
+
+impl PostgresAuditLogRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl AuditLogRepository for PostgresAuditLogRepository {
+    async fn append(&self, entry: &AuditLog) -> Result<(), AppError> {
+        sqlx::query(
+            r#"INSERT INTO audit_log (
+                   event_id, aggregate_id, event_type, actor_id, before_state, after_state, occurred_at, request_id
+               ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"#,
+        )
+        .bind(entry.event_id)
+        .bind(entry.aggregate_id)
+        .bind(&entry.event_type)
+        .bind(entry.actor_id)
+        .bind(&entry.before_state)
+        .bind(&entry.after_state)
+        .bind(entry.occurred_at)
+        .bind(entry.request_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to append audit log: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn get_for_aggregate(&self, aggregate_id: Uuid) -> Result<Vec<AuditLog>, AppError> {
+        let rows = sqlx::query(
+            r#"SELECT event_id, aggregate_id, event_type, actor_id, before_state, after_state, occurred_at, request_id
+               FROM audit_log
+               WHERE aggregate_id = $1
+               ORDER BY occurred_at ASC, recorded_at ASC"#,
+        )
+        .bind(aggregate_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| AuditLog {
+                event_id: row.get("event_id"),
+                aggregate_id: row.get("aggregate_id"),
+                event_type: row.get("event_type"),
+                actor_id: row.get("actor_id"),
+                before_state: row.get("before_state"),
+                after_state: row.get("after_state"),
+                occurred_at: row.get("occurred_at"),
+                request_id: row.get("request_id"),
+            })
+            .collect())
+    }
+}
+
+/// Escucha `SharePurchased`, `ShareTransferred` y `RevenueDistributed` y los
+/// añade al registro de auditoría
+pub struct AuditTrailListener {
+    repository: Arc<dyn AuditLogRepository>,
+}
+
+impl AuditTrailListener {
+    pub fn new(repository: Arc<dyn AuditLogRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl EventHandler for AuditTrailListener {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let Some(entry) = AuditLog::from_event(event) else {
+            return Ok(());
+        };
+
+        self.repository.append(&entry).await.map_err(|e| {
+            tracing::error!("Failed to audit {} for aggregate {}: {}", entry.event_type, entry.aggregate_id, e);
+            e
+        })
+    }
+}
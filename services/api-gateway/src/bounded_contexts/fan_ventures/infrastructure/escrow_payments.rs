@@ -0,0 +1,182 @@
+//! Escrow payments for Fan Ventures
+//!
+//! Implements the `EscrowPayments` port on top of the Payment context: the
+//! investment payment holds the funds, cancellation or expiry releases them.
+//! Also implements `ShareTransferPayments`: the buyer of a peer-to-peer
+//! transfer pays the seller while the seller's shares sit in escrow.
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use sqlx::PgPool;
+use uuid::Uuid;
+use tracing::info;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::{
+    payment::{
+        application::{
+            commands::{
+                CancelPaymentCommand, InitiatePaymentCommand, InitiateRefundCommand, PaymentMetadataDto,
+                PaymentMethodDto, PaymentPurposeDto, StartPaymentProcessingCommand,
+            },
+            currency_conversion::CurrencyConverter,
+            handlers::command_handlers::PaymentCommandHandler,
+        },
+        domain::{
+            repository::PaymentRepository,
+            value_objects::{PaymentId, PaymentStatus},
+        },
+        infrastructure::repositories::PostgreSQLPaymentRepository,
+    },
+    fan_ventures::{
+        application::{escrow_service::EscrowPayments, transfer_shares::ShareTransferPayments},
+        domain::{entities::FanInvestment, escrow::EscrowedShare},
+        infrastructure::{
+            payment_helper::create_payment_command_handler,
+            payment_integration::FanVenturesPaymentIntegration,
+            postgres_repository::PostgresFanVenturesRepository,
+        },
+    },
+};
+
+/// Payment-context backed escrow
+pub struct PaymentContextEscrow {
+    payment_integration: Arc<FanVenturesPaymentIntegration>,
+    payment_handler: Arc<dyn PaymentCommandHandler>,
+    payment_repository: Arc<dyn PaymentRepository>,
+}
+
+impl PaymentContextEscrow {
+    pub fn new(pool: PgPool, venture_repository: Arc<PostgresFanVenturesRepository>) -> Self {
+        let payment_handler = create_payment_command_handler(pool.clone());
+        Self {
+            payment_integration: Arc::new(FanVenturesPaymentIntegration::new(
+                payment_handler.clone(),
+                venture_repository,
+            )),
+            payment_handler,
+            payment_repository: Arc::new(PostgreSQLPaymentRepository::new(pool)),
+        }
+    }
+}
+
+#[async_trait]
+impl EscrowPayments for PaymentContextEscrow {
+    async fn hold(&self, investment: &FanInvestment, artist_id: Uuid) -> Result<Uuid, AppError> {
+        self.payment_integration
+            .create_investment_payment(investment, investment.venture_id, artist_id)
+            .await
+    }
+
+    async fn is_settled(&self, payment_id: Uuid) -> Result<bool, AppError> {
+        let payment = self.payment_repository
+            .find_by_id(&PaymentId::from_uuid(payment_id))
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Payment {} not found", payment_id)))?;
+
+        Ok(payment.payment().status().is_successful())
+    }
+
+    async fn release(&self, payment_id: Uuid, requested_by: Uuid, reason: &str) -> Result<(), AppError> {
+        let payment = self.payment_repository
+            .find_by_id(&PaymentId::from_uuid(payment_id))
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Payment {} not found", payment_id)))?;
+
+        match payment.payment().status() {
+            PaymentStatus::Completed => {
+                let amount = payment.payment().amount();
+                let refund = self.payment_handler.handle_initiate_refund(InitiateRefundCommand {
+                    original_payment_id: payment_id,
+                    refund_amount: amount.value(),
+                    refund_currency: amount.currency().clone(),
+                    reason: reason.to_string(),
+                    initiated_by: requested_by,
+                }).await?;
+                info!("Refund {} initiated for escrowed payment {}", refund.refund_payment_id, payment_id);
+            }
+            PaymentStatus::Pending | PaymentStatus::Processing | PaymentStatus::OnHold => {
+                self.payment_handler.handle_cancel_payment(CancelPaymentCommand {
+                    payment_id,
+                    reason: reason.to_string(),
+                    cancelled_by: requested_by,
+                }).await?;
+                info!("Escrowed payment {} cancelled", payment_id);
+            }
+            // Ya fallido, cancelado o reembolsado: no hay fondos que devolver
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ShareTransferPayments for PaymentContextEscrow {
+    async fn collect(&self, escrow: &EscrowedShare) -> Result<Uuid, AppError> {
+        let purpose = PaymentPurposeDto {
+            purpose_type: "ShareTrade".to_string(),
+            campaign_id: None,
+            nft_quantity: None,
+            contract_id: None,
+            ownership_percentage: None,
+            share_id: Some(escrow.seller_investment_id),
+            from_user: Some(escrow.seller_id),
+            to_user: Some(escrow.buyer_id),
+            song_id: None,
+            artist_id: None,
+            session_id: None,
+            listen_duration: None,
+            distribution_id: None,
+            original_payment_id: None,
+            reason: None,
+        };
+        let metadata = PaymentMetadataDto {
+            user_ip: None,
+            user_agent: None,
+            platform_version: env!("CARGO_PKG_VERSION").to_string(),
+            reference_id: Some(escrow.escrow_id.to_string()),
+            additional_data: serde_json::json!({
+                "escrow_id": escrow.escrow_id,
+                "venture_id": escrow.venture_id,
+                "quantity": escrow.quantity,
+            }),
+        };
+
+        let payment = self.payment_handler.handle_initiate_payment(InitiatePaymentCommand {
+            payer_id: escrow.buyer_id,
+            payee_id: escrow.seller_id,
+            amount_value: escrow.price,
+            amount_currency: CurrencyConverter::base_currency_from_env(),
+            payment_method: PaymentMethodDto {
+                method_type: "PlatformBalance".to_string(),
+                card_details: None,
+                crypto_details: None,
+                bank_details: None,
+            },
+            purpose,
+            metadata,
+            idempotency_key: Some(format!("share_transfer_{}", escrow.escrow_id)),
+        }).await?;
+
+        let processed = self.payment_handler.handle_start_processing(StartPaymentProcessingCommand {
+            payment_id: payment.payment_id,
+            processor_id: "System".to_string(),
+            external_transaction_id: None,
+        }).await?;
+
+        if !self.is_settled(payment.payment_id).await? {
+            // Sin cobro inmediato no se entregan participaciones: el pago no debe quedar vivo
+            self.release(payment.payment_id, escrow.buyer_id, "Share transfer payment not settled").await?;
+            return Err(AppError::ExternalServiceError(format!(
+                "Share transfer payment {} was not settled ({})",
+                payment.payment_id, processed.status
+            )));
+        }
+        info!("Share transfer payment {} collected for escrow {}", payment.payment_id, escrow.escrow_id);
+        Ok(payment.payment_id)
+    }
+
+    async fn refund(&self, payment_id: Uuid, requested_by: Uuid, reason: &str) -> Result<(), AppError> {
+        self.release(payment_id, requested_by, reason).await
+    }
+}
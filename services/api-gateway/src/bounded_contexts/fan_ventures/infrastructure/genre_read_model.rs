@@ -0,0 +1,84 @@
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+use crate::shared::domain::errors::AppError;
+
+// =============================================================================
+// ARTIST GENRE READ MODEL
+// =============================================================================
+//
+// Fan Ventures no consulta las tablas de Music directamente: mantiene su propia
+// proyección de géneros por artista, alimentada por eventos `SongUploaded`.
+
+/// Proyección local de cuántas canciones tiene cada artista por género
+pub struct PostgresArtistGenreReadModel {
+    pool: PgPool,
+}
+
+impl PostgresArtistGenreReadModel {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Registrar una canción nueva del artista en el género indicado
+    pub async fn record_song(&self, artist_id: Uuid, genre: &str) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            INSERT INTO artist_genre_read_model (artist_id, genre, song_count, updated_at)
+            VALUES ($1, $2, 1, NOW())
+            ON CONFLICT (artist_id, genre)
+            DO UPDATE SET song_count = artist_genre_read_model.song_count + 1, updated_at = NOW()
+            "#,
+        )
+        .bind(artist_id)
+        .bind(genre.to_lowercase())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update artist genre read model: {}", e)))?;
+
+        Ok(())
+    }
+
+    /// Género dominante (más canciones) de cada artista conocido
+    pub async fn dominant_genres(&self) -> Result<HashMap<Uuid, String>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT DISTINCT ON (artist_id) artist_id, genre
+            FROM artist_genre_read_model
+            ORDER BY artist_id, song_count DESC, genre
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load artist genres: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.get::<Uuid, _>("artist_id"), row.get::<String, _>("genre")))
+            .collect())
+    }
+}
+
+/// Handler que mantiene el read model al día a partir del event bus
+pub struct ArtistGenreProjection {
+    read_model: std::sync::Arc<PostgresArtistGenreReadModel>,
+}
+
+impl ArtistGenreProjection {
+    pub fn new(read_model: std::sync::Arc<PostgresArtistGenreReadModel>) -> Self {
+        Self { read_model }
+    }
+}
+
+#[async_trait]
+impl EventHandler for ArtistGenreProjection {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        if let DomainEvent::SongUploaded { artist_id, genre, .. } = event {
+            self.read_model.record_song(*artist_id, genre).await?;
+        }
+        Ok(())
+    }
+}
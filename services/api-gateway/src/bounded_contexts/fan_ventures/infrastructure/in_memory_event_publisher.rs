@@ -0,0 +1,50 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventBus, EventHandler, InMemoryEventBus};
+use crate::shared::domain::errors::AppError;
+
+/// Event bus en memoria que además recuerda lo publicado, para que los tests
+/// comprueben qué eventos emitió cada operación
+pub struct InMemoryEventPublisher {
+    published_events: Arc<RwLock<Vec<DomainEvent>>>,
+    bus: InMemoryEventBus,
+}
+
+impl InMemoryEventPublisher {
+    pub fn new() -> Self {
+        Self {
+            published_events: Arc::new(RwLock::new(Vec::new())),
+            bus: InMemoryEventBus::new(),
+        }
+    }
+
+    /// Eventos publicados, en orden
+    pub async fn get_published_events(&self) -> Vec<DomainEvent> {
+        self.published_events.read().await.clone()
+    }
+
+    pub async fn clear_events(&self) {
+        self.published_events.write().await.clear();
+    }
+}
+
+impl Default for InMemoryEventPublisher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl EventBus for InMemoryEventPublisher {
+    async fn publish(&self, event: DomainEvent) -> Result<(), AppError> {
+        self.published_events.write().await.push(event.clone());
+        self.bus.publish(event).await
+    }
+
+    async fn subscribe(&self, event_type: &str, handler: Arc<dyn EventHandler>) -> Result<(), AppError> {
+        self.bus.subscribe(event_type, handler).await
+    }
+}
@@ -1,265 +1,422 @@
-use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
 use uuid::Uuid;
 
-use crate::shared::domain::repositories::RepoResult;
-use crate::bounded_contexts::{
-            vibestream_types::{SongContract, ArtistContract},
-    user::domain::value_objects::UserId,
-    fractional_ownership::domain::{
-        repository::{OwnershipContractRepository, ShareRepository},
-        value_objects::{OwnershipContractId, ShareId},
-        aggregates::{OwnershipContractAggregate, OwnershipAnalytics},
-        entities::FractionalShare,
-    },
+use crate::bounded_contexts::fan_ventures::application::escrow_service::EscrowPayments;
+use crate::bounded_contexts::fan_ventures::application::transfer_shares::ShareTransferPayments;
+use crate::bounded_contexts::fan_ventures::domain::entities::{ArtistVenture, FanInvestment, InvestmentStatus};
+use crate::bounded_contexts::fan_ventures::domain::escrow::{EscrowStatus, EscrowedShare, InvestmentReservation};
+use crate::bounded_contexts::fan_ventures::domain::proposals::{Proposal, ProposalError, ProposalStatus, Vote};
+use crate::bounded_contexts::fan_ventures::domain::repositories::{
+    InvestmentReservationRepository, ProposalRepository, ShareEscrowRepository, VentureRepository,
 };
+use crate::bounded_contexts::fan_ventures::domain::vesting::{ShareHolder, VestingSchedule};
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventBus};
+use crate::shared::domain::errors::AppError;
+
+// =============================================================================
+// FAN VENTURES - IN-MEMORY REPOSITORIES (tests y desarrollo)
+// =============================================================================
+//
+// Reproducen las garantías de los repositorios PostgreSQL que los servicios
+// dan por hechas: conflictos por voto duplicado, transiciones que solo ganan
+// una vez y descuento condicional de participaciones.
 
-/// In-memory implementation of OwnershipContractRepository for testing
-#[derive(Debug, Clone)]
-pub struct InMemoryOwnershipContractRepository {
-    contracts: Arc<RwLock<HashMap<Uuid, OwnershipContractAggregate>>>,
-    shares: Arc<RwLock<HashMap<Uuid, FractionalShare>>>,
+/// Ventures e inversiones. Los clones comparten los mismos datos.
+#[derive(Clone, Default)]
+pub struct InMemoryFanVenturesRepository {
+    ventures: Arc<RwLock<HashMap<Uuid, ArtistVenture>>>,
+    investments: Arc<RwLock<HashMap<Uuid, FanInvestment>>>,
 }
 
-impl InMemoryOwnershipContractRepository {
+impl InMemoryFanVenturesRepository {
     pub fn new() -> Self {
-        Self {
-            contracts: Arc::new(RwLock::new(HashMap::new())),
-            shares: Arc::new(RwLock::new(HashMap::new())),
-        }
+        Self::default()
     }
 }
 
 #[async_trait]
-impl OwnershipContractRepository for InMemoryOwnershipContractRepository {
-    async fn save(&self, aggregate: &OwnershipContractAggregate) -> RepoResult<()> {
-        let mut contracts = self.contracts.write().unwrap();
-        contracts.insert(aggregate.id().value(), aggregate.clone());
+impl VentureRepository for InMemoryFanVenturesRepository {
+    async fn create_venture(&self, venture: &ArtistVenture) -> Result<(), AppError> {
+        self.ventures.write().await.insert(venture.id, venture.clone());
         Ok(())
     }
 
-    async fn update(&self, aggregate: &OwnershipContractAggregate) -> RepoResult<()> {
-        let mut contracts = self.contracts.write().unwrap();
-        contracts.insert(aggregate.id().value(), aggregate.clone());
+    async fn get_venture(&self, venture_id: Uuid) -> Result<Option<ArtistVenture>, AppError> {
+        Ok(self.ventures.read().await.get(&venture_id).cloned())
+    }
+
+    async fn update_venture(&self, venture: &ArtistVenture) -> Result<(), AppError> {
+        match self.ventures.write().await.get_mut(&venture.id) {
+            Some(stored) => {
+                *stored = venture.clone();
+                Ok(())
+            }
+            None => Err(AppError::NotFound(format!("Venture {} not found", venture.id))),
+        }
+    }
+
+    async fn create_fan_investment(&self, investment: &FanInvestment) -> Result<(), AppError> {
+        self.investments.write().await.insert(investment.id, investment.clone());
         Ok(())
     }
 
-    async fn find_by_id(&self, id: &OwnershipContractId) -> RepoResult<Option<OwnershipContractAggregate>> {
-        let contracts = self.contracts.read().unwrap();
-        Ok(contracts.get(&id.value()).cloned())
+    async fn get_investment_by_id(&self, investment_id: Uuid) -> Result<Option<FanInvestment>, AppError> {
+        Ok(self.investments.read().await.get(&investment_id).cloned())
     }
 
-    async fn find_by_song_id(&self, song_id: &SongId) -> RepoResult<Vec<OwnershipContractAggregate>> {
-        let contracts = self.contracts.read().unwrap();
-        let results: Vec<OwnershipContractAggregate> = contracts
-            .values()
-            .filter(|contract| contract.song_id().value() == song_id.value())
-            .cloned()
-            .collect();
-        Ok(results)
+    async fn update_fan_investment(&self, investment: &FanInvestment) -> Result<(), AppError> {
+        match self.investments.write().await.get_mut(&investment.id) {
+            Some(stored) => {
+                *stored = investment.clone();
+                Ok(())
+            }
+            None => Err(AppError::NotFound(format!("Investment {} not found", investment.id))),
+        }
     }
 
-    async fn find_by_artist_id(&self, artist_id: &ArtistId) -> RepoResult<Vec<OwnershipContractAggregate>> {
-        let contracts = self.contracts.read().unwrap();
-        let results: Vec<OwnershipContractAggregate> = contracts
-            .values()
-            .filter(|contract| contract.artist_id().value() == artist_id.value())
+    async fn get_fan_investments_by_venture(&self, venture_id: Uuid) -> Result<Vec<FanInvestment>, AppError> {
+        Ok(self.investments.read().await.values()
+            .filter(|investment| investment.venture_id == venture_id)
             .cloned()
-            .collect();
-        Ok(results)
+            .collect())
     }
+}
 
-    async fn find_active_contracts(&self) -> RepoResult<Vec<OwnershipContractAggregate>> {
-        let contracts = self.contracts.read().unwrap();
-        let results: Vec<OwnershipContractAggregate> = contracts
-            .values()
-            .filter(|contract| contract.is_active())
-            .cloned()
-            .collect();
-        Ok(results)
+#[derive(Clone, Default)]
+pub struct InMemoryProposalRepository {
+    proposals: Arc<RwLock<HashMap<Uuid, Proposal>>>,
+}
+
+impl InMemoryProposalRepository {
+    pub fn new() -> Self {
+        Self::default()
     }
+}
 
-    async fn find_contracts_with_user_shares(&self, user_id: &UserId) -> RepoResult<Vec<OwnershipContractAggregate>> {
-        // This would require checking shares table in real implementation
-        let contracts = self.contracts.read().unwrap();
-        Ok(contracts.values().cloned().collect())
+#[async_trait]
+impl ProposalRepository for InMemoryProposalRepository {
+    async fn create(&self, proposal: &Proposal) -> Result<(), AppError> {
+        self.proposals.write().await.insert(proposal.id, proposal.clone());
+        Ok(())
     }
 
-    async fn find_by_status(&self, status: &str) -> RepoResult<Vec<OwnershipContractAggregate>> {
-        let contracts = self.contracts.read().unwrap();
-        let results: Vec<OwnershipContractAggregate> = contracts
-            .values()
-            .filter(|contract| contract.status().to_string() == status)
-            .cloned()
-            .collect();
-        Ok(results)
+    async fn find_by_id(&self, proposal_id: &Uuid) -> Result<Option<Proposal>, AppError> {
+        Ok(self.proposals.read().await.get(proposal_id).cloned())
     }
 
-    async fn exists_for_song(&self, song_id: &SongId) -> RepoResult<bool> {
-        let contracts = self.contracts.read().unwrap();
-        let exists = contracts
-            .values()
-            .any(|contract| contract.song_id().value() == song_id.value());
-        Ok(exists)
+    async fn find_by_venture(&self, venture_id: &Uuid) -> Result<Vec<Proposal>, AppError> {
+        let mut proposals: Vec<Proposal> = self.proposals.read().await.values()
+            .filter(|proposal| proposal.venture_id == *venture_id)
+            .cloned()
+            .collect();
+        proposals.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(proposals)
     }
 
-    async fn delete(&self, id: &OwnershipContractId) -> RepoResult<()> {
-        let mut contracts = self.contracts.write().unwrap();
-        contracts.remove(&id.value());
+    async fn record_vote(&self, proposal_id: &Uuid, vote: &Vote) -> Result<(), AppError> {
+        let mut proposals = self.proposals.write().await;
+        let proposal = proposals.get_mut(proposal_id)
+            .ok_or_else(|| AppError::NotFound(format!("Proposal {} not found", proposal_id)))?;
+        if proposal.has_voted(&vote.voter_id) {
+            return Err(ProposalError::AlreadyVoted(vote.voter_id).into());
+        }
+        // La copia con la que se emitió el voto puede estar desfasada: manda la guardada
+        if proposal.status != ProposalStatus::Open || proposal.deadline <= Utc::now() {
+            return Err(ProposalError::ProposalClosed(*proposal_id).into());
+        }
+        proposal.votes.push(vote.clone());
         Ok(())
     }
 
-    async fn get_contract_analytics(&self, id: &OwnershipContractId) -> RepoResult<Option<OwnershipAnalytics>> {
-        // Mock analytics data
-        if self.find_by_id(id).await?.is_some() {
-            Ok(Some(OwnershipAnalytics::default()))
-        } else {
-            Ok(None)
-        }
+    async fn mark_finalized(&self, proposal: &Proposal) -> Result<(), AppError> {
+        let mut proposals = self.proposals.write().await;
+        let stored = proposals.get_mut(&proposal.id)
+            .filter(|stored| stored.status == ProposalStatus::Open)
+            .ok_or_else(|| AppError::ConcurrencyConflict(format!("Proposal {} was already finalized", proposal.id)))?;
+        stored.status = proposal.status;
+        stored.outcome = proposal.outcome.clone();
+        stored.finalized_at = proposal.finalized_at;
+        Ok(())
     }
 
-    async fn find_paginated(&self, offset: u32, limit: u32) -> RepoResult<(Vec<OwnershipContractAggregate>, u64)> {
-        let contracts = self.contracts.read().unwrap();
-        let all_contracts: Vec<OwnershipContractAggregate> = contracts.values().cloned().collect();
-        let total_count = all_contracts.len() as u64;
-        
-        let start = offset as usize;
-        let end = std::cmp::min(start + limit as usize, all_contracts.len());
-        let page_contracts = if start < all_contracts.len() {
-            all_contracts[start..end].to_vec()
-        } else {
-            Vec::new()
-        };
-        
-        Ok((page_contracts, total_count))
-    }
-
-    async fn find_by_completion_range(&self, min_percentage: f64, max_percentage: f64) -> RepoResult<Vec<OwnershipContractAggregate>> {
-        let contracts = self.contracts.read().unwrap();
-        let results: Vec<OwnershipContractAggregate> = contracts
-            .values()
-            .filter(|contract| {
-                let completion = contract.completion_percentage();
-                completion >= min_percentage && completion <= max_percentage
-            })
+    async fn find_due(&self, now: DateTime<Utc>) -> Result<Vec<Proposal>, AppError> {
+        let mut due: Vec<Proposal> = self.proposals.read().await.values()
+            .filter(|proposal| proposal.status == ProposalStatus::Open && proposal.deadline <= now)
             .cloned()
             .collect();
-        Ok(results)
-    }
-
-    async fn get_total_market_value(&self) -> RepoResult<f64> {
-        let contracts = self.contracts.read().unwrap();
-        let total_value: f64 = contracts
-            .values()
-            .map(|contract| contract.total_value())
-            .sum();
-        Ok(total_value)
+        due.sort_by_key(|proposal| proposal.deadline);
+        Ok(due)
     }
 }
 
-/// In-memory implementation of ShareRepository for testing
-#[derive(Debug, Clone)]
-pub struct InMemoryShareRepository {
-    shares: Arc<RwLock<HashMap<Uuid, FractionalShare>>>,
+/// Reservas indexadas por la inversión que bloquean
+#[derive(Clone, Default)]
+pub struct InMemoryInvestmentReservationRepository {
+    reservations: Arc<RwLock<HashMap<Uuid, InvestmentReservation>>>,
 }
 
-impl InMemoryShareRepository {
+impl InMemoryInvestmentReservationRepository {
     pub fn new() -> Self {
-        Self {
-            shares: Arc::new(RwLock::new(HashMap::new())),
-        }
+        Self::default()
     }
 }
 
 #[async_trait]
-impl ShareRepository for InMemoryShareRepository {
-    async fn find_by_id(&self, id: &ShareId) -> RepoResult<Option<FractionalShare>> {
-        let shares = self.shares.read().unwrap();
-        Ok(shares.get(&id.value()).cloned())
+impl InvestmentReservationRepository for InMemoryInvestmentReservationRepository {
+    async fn create(&self, reservation: &InvestmentReservation) -> Result<(), AppError> {
+        self.reservations.write().await.insert(reservation.investment_id, reservation.clone());
+        Ok(())
     }
 
-    async fn find_by_owner(&self, user_id: &UserId) -> RepoResult<Vec<FractionalShare>> {
-        let shares = self.shares.read().unwrap();
-        let results: Vec<FractionalShare> = shares
-            .values()
-            .filter(|share| share.owner_id().value() == user_id.value())
-            .cloned()
-            .collect();
-        Ok(results)
+    async fn find_by_investment(&self, investment_id: &Uuid) -> Result<Option<InvestmentReservation>, AppError> {
+        Ok(self.reservations.read().await.get(investment_id).cloned())
     }
 
-    async fn find_by_contract(&self, contract_id: &OwnershipContractId) -> RepoResult<Vec<FractionalShare>> {
-        let shares = self.shares.read().unwrap();
-        let results: Vec<FractionalShare> = shares
-            .values()
-            .filter(|share| share.contract_id().value() == contract_id.value())
+    async fn find_pending_by_venture(&self, venture_id: &Uuid) -> Result<Vec<InvestmentReservation>, AppError> {
+        Ok(self.reservations.read().await.values()
+            .filter(|reservation| reservation.venture_id == *venture_id && reservation.is_pending())
             .cloned()
-            .collect();
-        Ok(results)
+            .collect())
     }
 
-    async fn find_tradeable_shares(&self) -> RepoResult<Vec<FractionalShare>> {
-        let shares = self.shares.read().unwrap();
-        let results: Vec<FractionalShare> = shares
-            .values()
-            .filter(|share| share.is_tradeable())
-            .cloned()
-            .collect();
-        Ok(results)
+    async fn resolve(&self, reservation: &InvestmentReservation) -> Result<(), AppError> {
+        let mut reservations = self.reservations.write().await;
+        let stored = reservations.get_mut(&reservation.investment_id)
+            .filter(|stored| stored.is_pending())
+            .ok_or_else(|| AppError::ConcurrencyConflict(format!(
+                "Reservation {} is no longer pending",
+                reservation.investment_id
+            )))?;
+        *stored = reservation.clone();
+        Ok(())
     }
 
-    async fn find_by_song(&self, song_id: &SongId) -> RepoResult<Vec<FractionalShare>> {
-        let shares = self.shares.read().unwrap();
-        let results: Vec<FractionalShare> = shares
-            .values()
-            .filter(|share| share.song_id().value() == song_id.value())
+    async fn find_expired(&self, now: DateTime<Utc>) -> Result<Vec<InvestmentReservation>, AppError> {
+        let mut expired: Vec<InvestmentReservation> = self.reservations.read().await.values()
+            .filter(|reservation| reservation.is_pending() && reservation.expires_at <= now)
             .cloned()
             .collect();
-        Ok(results)
+        expired.sort_by_key(|reservation| reservation.expires_at);
+        Ok(expired)
     }
+}
 
-    async fn find_locked_shares(&self) -> RepoResult<Vec<FractionalShare>> {
-        let shares = self.shares.read().unwrap();
-        let results: Vec<FractionalShare> = shares
-            .values()
-            .filter(|share| share.is_locked())
+/// Escrows de transferencias entre fans sobre las inversiones de
+/// `InMemoryFanVenturesRepository`. En lugar de pasar por el outbox, cada
+/// cambio de estado publica su evento en el event bus.
+#[derive(Clone)]
+pub struct InMemoryShareEscrowRepository {
+    investments: Arc<RwLock<HashMap<Uuid, FanInvestment>>>,
+    escrows: Arc<RwLock<HashMap<Uuid, EscrowedShare>>>,
+    /// Calendario de vesting por venture
+    vesting: Arc<RwLock<HashMap<Uuid, VestingSchedule>>>,
+    event_bus: Arc<dyn EventBus>,
+}
+
+impl InMemoryShareEscrowRepository {
+    pub fn new(ventures: &InMemoryFanVenturesRepository, event_bus: Arc<dyn EventBus>) -> Self {
+        Self {
+            investments: Arc::clone(&ventures.investments),
+            escrows: Arc::new(RwLock::new(HashMap::new())),
+            vesting: Arc::new(RwLock::new(HashMap::new())),
+            event_bus,
+        }
+    }
+
+    pub async fn set_vesting_schedule(&self, venture_id: Uuid, schedule: VestingSchedule) {
+        self.vesting.write().await.insert(venture_id, schedule);
+    }
+
+    pub async fn escrows_for(&self, seller_investment_id: Uuid) -> Vec<EscrowedShare> {
+        self.escrows.read().await.values()
+            .filter(|escrow| escrow.seller_investment_id == seller_investment_id)
             .cloned()
-            .collect();
-        Ok(results)
+            .collect()
     }
 
-    async fn update(&self, share: &FractionalShare) -> RepoResult<()> {
-        let mut shares = self.shares.write().unwrap();
-        shares.insert(share.id().value(), share.clone());
+    async fn publish(&self, event: DomainEvent) -> Result<(), AppError> {
+        self.event_bus.publish(event).await
+    }
+
+    /// Guardar la salida de `Pending`; falla si otro ya lo resolvió
+    async fn resolve(&self, escrow: &EscrowedShare) -> Result<(), AppError> {
+        let mut escrows = self.escrows.write().await;
+        let stored = escrows.get_mut(&escrow.escrow_id)
+            .filter(|stored| stored.is_pending())
+            .ok_or_else(|| AppError::ConcurrencyConflict(format!("Share escrow {} was already resolved", escrow.escrow_id)))?;
+        *stored = escrow.clone();
         Ok(())
     }
+}
 
-    async fn get_user_portfolio_value(&self, user_id: &UserId) -> RepoResult<f64> {
-        let shares = self.shares.read().unwrap();
-        let total_value: f64 = shares
-            .values()
-            .filter(|share| share.owner_id().value() == user_id.value())
-            .map(|share| share.current_market_value().value())
+#[async_trait]
+impl ShareEscrowRepository for InMemoryShareEscrowRepository {
+    async fn find_holding(&self, investment_id: &Uuid) -> Result<Option<FanInvestment>, AppError> {
+        Ok(self.investments.read().await.get(investment_id).cloned())
+    }
+
+    async fn find_share_holder(&self, investment_id: &Uuid) -> Result<Option<ShareHolder>, AppError> {
+        let Some(investment) = self.investments.read().await.get(investment_id).cloned() else {
+            return Ok(None);
+        };
+        let transferred_shares = self.escrows.read().await.values()
+            .filter(|escrow| escrow.seller_investment_id == *investment_id && escrow.status != EscrowStatus::Cancelled)
+            .map(|escrow| escrow.quantity)
             .sum();
-        Ok(total_value)
-    }
-
-    async fn find_shares_vesting_soon(&self, days: u32) -> RepoResult<Vec<FractionalShare>> {
-        let shares = self.shares.read().unwrap();
-        let cutoff_date = chrono::Utc::now() + chrono::Duration::days(days as i64);
-        
-        let results: Vec<FractionalShare> = shares
-            .values()
-            .filter(|share| {
-                if let Some(locked_until) = share.locked_until() {
-                    locked_until <= cutoff_date
-                } else {
-                    false
+        let vesting_schedule = self.vesting.read().await.get(&investment.venture_id).cloned();
+        Ok(Some(ShareHolder { investment, transferred_shares, vesting_schedule }))
+    }
+
+    async fn open(&self, escrow: &EscrowedShare) -> Result<(), AppError> {
+        {
+            let mut investments = self.investments.write().await;
+            let holding = investments.get_mut(&escrow.seller_investment_id)
+                .filter(|holding| holding.fan_id == escrow.seller_id
+                    && holding.venture_id == escrow.venture_id
+                    && holding.status == InvestmentStatus::Active
+                    && holding.investment_amount >= escrow.quantity)
+                .ok_or_else(|| AppError::DomainRuleViolation(format!(
+                    "Seller no longer holds {} shares in investment {}",
+                    escrow.quantity, escrow.seller_investment_id
+                )))?;
+            // Con la inversión bloqueada, igual que el `FOR UPDATE` de PostgreSQL
+            let transferred_shares = self.escrows.read().await.values()
+                .filter(|e| e.seller_investment_id == escrow.seller_investment_id && e.status != EscrowStatus::Cancelled)
+                .map(|e| e.quantity)
+                .sum();
+            let holder = ShareHolder {
+                investment: holding.clone(),
+                transferred_shares,
+                vesting_schedule: self.vesting.read().await.get(&escrow.venture_id).cloned(),
+            };
+            holder.ensure_transferable(escrow.quantity, escrow.created_at)?;
+            holding.investment_amount -= escrow.quantity;
+            holding.updated_at = Utc::now();
+            self.escrows.write().await.insert(escrow.escrow_id, escrow.clone());
+        }
+        self.publish(escrow.escrowed_event()).await
+    }
+
+    async fn complete(&self, escrow: &EscrowedShare, buyer_investment: &FanInvestment) -> Result<FanInvestment, AppError> {
+        let credited = {
+            let mut investments = self.investments.write().await;
+            let investment_type = investments.get(&escrow.seller_investment_id)
+                .map(|seller| seller.investment_type.clone())
+                .unwrap_or_else(|| buyer_investment.investment_type.clone());
+
+            // Una inversión por fan y venture: si el comprador ya tiene una se acumulan en ella
+            let existing = investments.values()
+                .find(|investment| investment.fan_id == buyer_investment.fan_id && investment.venture_id == escrow.venture_id);
+            let credited = match existing {
+                Some(investment) if investment.status == InvestmentStatus::Active => FanInvestment {
+                    investment_amount: investment.investment_amount + buyer_investment.investment_amount,
+                    updated_at: buyer_investment.updated_at,
+                    ..investment.clone()
+                },
+                Some(_) => {
+                    return Err(AppError::DomainRuleViolation(
+                        "Buyer has an investment in this venture that is not active".to_string(),
+                    ));
                 }
-            })
-            .cloned()
-            .collect();
-        Ok(results)
+                None => FanInvestment { investment_type, ..buyer_investment.clone() },
+            };
+
+            self.resolve(escrow).await?;
+            investments.insert(credited.id, credited.clone());
+            credited
+        };
+        self.publish(escrow.transferred_event(credited.id)).await?;
+        Ok(credited)
+    }
+
+    async fn cancel(&self, escrow: &EscrowedShare, reason: &str) -> Result<(), AppError> {
+        self.resolve(escrow).await?;
+        if let Some(holding) = self.investments.write().await.get_mut(&escrow.seller_investment_id) {
+            holding.investment_amount += escrow.quantity;
+            holding.updated_at = Utc::now();
+        }
+        self.publish(escrow.cancelled_event(reason)).await
+    }
+
+    async fn find_by_id(&self, escrow_id: &Uuid) -> Result<Option<EscrowedShare>, AppError> {
+        Ok(self.escrows.read().await.get(escrow_id).cloned())
+    }
+}
+
+/// Contexto de pagos simulado para reservas y transferencias. Las retenciones
+/// no se liquidan hasta `settle`; los cobros se liquidan al momento salvo que
+/// se configuren para rechazarse.
+#[derive(Clone, Default)]
+pub struct InMemoryPayments {
+    settled: Arc<RwLock<HashSet<Uuid>>>,
+    released: Arc<RwLock<Vec<Uuid>>>,
+    collect_delay: Arc<RwLock<Duration>>,
+    decline_collections: Arc<RwLock<bool>>,
+}
+
+impl InMemoryPayments {
+    pub fn new() -> Self {
+        Self::default()
     }
-} 
\ No newline at end of file
+
+    /// Marcar como liquidado un pago retenido
+    pub async fn settle(&self, payment_id: Uuid) {
+        self.settled.write().await.insert(payment_id);
+    }
+
+    /// Pagos devueltos o anulados, en orden
+    pub async fn released(&self) -> Vec<Uuid> {
+        self.released.read().await.clone()
+    }
+
+    /// Tiempo que tarda cada cobro de una transferencia
+    pub async fn set_collect_delay(&self, delay: Duration) {
+        *self.collect_delay.write().await = delay;
+    }
+
+    /// Rechazar los cobros siguientes
+    pub async fn decline_collections(&self) {
+        *self.decline_collections.write().await = true;
+    }
+}
+
+#[async_trait]
+impl EscrowPayments for InMemoryPayments {
+    async fn hold(&self, _investment: &FanInvestment, _artist_id: Uuid) -> Result<Uuid, AppError> {
+        Ok(Uuid::new_v4())
+    }
+
+    async fn is_settled(&self, payment_id: Uuid) -> Result<bool, AppError> {
+        Ok(self.settled.read().await.contains(&payment_id))
+    }
+
+    async fn release(&self, payment_id: Uuid, _requested_by: Uuid, _reason: &str) -> Result<(), AppError> {
+        self.released.write().await.push(payment_id);
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ShareTransferPayments for InMemoryPayments {
+    async fn collect(&self, _escrow: &EscrowedShare) -> Result<Uuid, AppError> {
+        let delay = *self.collect_delay.read().await;
+        tokio::time::sleep(delay).await;
+        if *self.decline_collections.read().await {
+            return Err(AppError::ExternalServiceError("Insufficient platform balance".to_string()));
+        }
+        let payment_id = Uuid::new_v4();
+        self.settled.write().await.insert(payment_id);
+        Ok(payment_id)
+    }
+
+    async fn refund(&self, payment_id: Uuid, _requested_by: Uuid, _reason: &str) -> Result<(), AppError> {
+        self.released.write().await.push(payment_id);
+        Ok(())
+    }
+}
@@ -7,10 +7,35 @@ pub mod mock_repository;
 pub mod payment_integration;
 pub mod payment_helper;
 pub mod payment_event_listener;
+pub mod proposal_repository;
+pub mod genre_read_model;
+pub mod reservation_repository;
+pub mod share_escrow_repository;
+pub mod escrow_payments;
+pub mod audit_log;
+pub mod trade_history;
+pub mod venture_event_stream;
+pub mod user_deletion_listener;
+pub mod in_memory_repository;
+pub mod in_memory_event_publisher;
 
 // Re-export the fan ventures repository
 pub use postgres_repository::PostgresFanVenturesRepository; 
 pub use mock_repository::MockArtistVentureRepository;
 pub use payment_integration::FanVenturesPaymentIntegration;
 pub use payment_helper::create_payment_command_handler;
-pub use payment_event_listener::FanVenturesPaymentEventListener;
\ No newline at end of file
+pub use payment_event_listener::FanVenturesPaymentEventListener;
+pub use proposal_repository::PostgresProposalRepository;
+pub use genre_read_model::{PostgresArtistGenreReadModel, ArtistGenreProjection};
+pub use reservation_repository::PostgresInvestmentReservationRepository;
+pub use share_escrow_repository::{PostgresShareEscrowRepository, FAN_VENTURES_OUTBOX_CONTEXT};
+pub use escrow_payments::PaymentContextEscrow;
+pub use audit_log::{PostgresAuditLogRepository, AuditTrailListener};
+pub use trade_history::{PostgresSecondaryMarketTradeRepository, SecondaryMarketTradeRecorder};
+pub use venture_event_stream::{RedisVentureEventStream, FAN_VENTURES_STREAM, FAN_VENTURES_STREAM_EVENTS};
+pub use user_deletion_listener::HoldingsUserDeletionListener;
+pub use in_memory_repository::{
+    InMemoryFanVenturesRepository, InMemoryProposalRepository, InMemoryInvestmentReservationRepository,
+    InMemoryShareEscrowRepository, InMemoryPayments,
+};
+pub use in_memory_event_publisher::InMemoryEventPublisher;
\ No newline at end of file
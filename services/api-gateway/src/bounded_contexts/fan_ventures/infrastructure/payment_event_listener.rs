@@ -10,23 +10,54 @@ use tracing::{info, warn, error};
 use crate::shared::domain::{errors::AppError, events::DomainEvent};
 use crate::bounded_contexts::{
     orchestrator::EventHandler,
-    payment::domain::events::{PaymentCompleted, PaymentFailed},
-    fan_ventures::infrastructure::{
-        postgres_repository::PostgresFanVenturesRepository,
-        payment_integration::FanVenturesPaymentIntegration,
+    payment::domain::{
+        events::{PaymentCompleted, PaymentFailed},
+        value_objects::Amount,
+    },
+    fan_ventures::{
+        application::escrow_service::InvestmentEscrowService,
+        infrastructure::{
+            postgres_repository::PostgresFanVenturesRepository,
+            payment_integration::FanVenturesPaymentIntegration,
+        },
     },
 };
 
 /// Event listener for payment events related to fan ventures
 pub struct FanVenturesPaymentEventListener {
     payment_integration: Arc<FanVenturesPaymentIntegration>,
+    escrow_service: Arc<InvestmentEscrowService>,
 }
 
 impl FanVenturesPaymentEventListener {
-    pub fn new(payment_integration: Arc<FanVenturesPaymentIntegration>) -> Self {
+    pub fn new(
+        payment_integration: Arc<FanVenturesPaymentIntegration>,
+        escrow_service: Arc<InvestmentEscrowService>,
+    ) -> Self {
         Self {
             payment_integration,
+            escrow_service,
+        }
+    }
+
+    /// Settle an investment payment. Escrowed purchases follow their reservation
+    /// (auto-confirm or wait for the buyer); the rest are activated directly.
+    async fn settle_investment(
+        &self,
+        payment_id: Uuid,
+        investment_id: Uuid,
+        venture_id: Uuid,
+        amount: &Amount,
+    ) -> Result<(), AppError> {
+        if self.escrow_service.on_payment_settled(investment_id).await? {
+            return Ok(());
         }
+        self.payment_integration.handle_payment_confirmed(
+            payment_id,
+            investment_id,
+            venture_id,
+            amount,
+        ).await
     }
 
     /// Handle PaymentCompleted event
@@ -68,11 +99,11 @@ impl FanVenturesPaymentEventListener {
             );
 
             // Update investment and funding
-            self.payment_integration.handle_payment_confirmed(
+            self.settle_investment(
                 *event.payment_id.value(),
                 inv_id,
                 v_id,
-                event.net_amount.value(),
+                &event.net_amount,
             ).await?;
 
             info!(
@@ -89,21 +120,23 @@ impl FanVenturesPaymentEventListener {
 
     /// Handle PaymentFailed event
     /// 
-    /// Checks if the payment is for a venture investment and cancels it.
+    /// Escrowed purchases are handed to the share purchase saga, which releases
+    /// the reservation.
     async fn handle_payment_failed_internal(&self, event: &PaymentFailed) -> Result<(), AppError> {
-        // Similar logic to handle_payment_completed
-        // For now, we'll need to query the payment to get metadata
-        // This is a simplified version - in production, you'd want to store
-        // the investment_id in a more accessible way
-        
-        warn!(
-            "Payment {} failed - need to check if it's a venture investment",
-            event.payment_id.value()
-        );
+        let investment_id = event.metadata
+            .event_data()
+            .get("additional_data")
+            .and_then(|ad| ad.get("investment_id"))
+            .and_then(|id| id.as_str())
+            .and_then(|s| Uuid::parse_str(s).ok());
 
-        // TODO: Query payment repository to get metadata and find investment_id
-        // For now, we'll log and return OK (the payment system will handle the failure)
-        
+        let Some(investment_id) = investment_id else {
+            info!("Payment {} is not a venture investment, ignoring", event.payment_id.value());
+            return Ok(());
+        };
+
+        warn!("Payment {} for investment {} failed: {}", event.payment_id.value(), investment_id, event.error_message);
+        self.escrow_service.on_payment_failed(investment_id, &event.error_message).await?;
         Ok(())
     }
 }
@@ -160,11 +193,11 @@ impl EventHandler for FanVenturesPaymentEventListener {
                             );
                             
                             // Update investment and funding
-                            if let Err(e) = self.payment_integration.handle_payment_confirmed(
+                            if let Err(e) = self.settle_investment(
                                 payment_id,
                                 investment_id,
                                 venture_id,
-                                share_purchase_event.purchase_amount.value(),
+                                &share_purchase_event.purchase_amount,
                             ).await {
                                 error!("Failed to handle payment confirmation: {:?}", e);
                             } else {
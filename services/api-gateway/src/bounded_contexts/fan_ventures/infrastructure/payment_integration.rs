@@ -14,9 +14,10 @@ use crate::bounded_contexts::{
     payment::{
         application::{
             commands::{InitiatePaymentCommand, PaymentPurposeDto, PaymentMetadataDto},
+            currency_conversion::CurrencyConverter,
             handlers::command_handlers::PaymentCommandHandler,
         },
-        domain::value_objects::Currency,
+        domain::value_objects::{Amount, Currency},
     },
     fan_ventures::{
         domain::entities::{FanInvestment, InvestmentStatus},
@@ -28,6 +29,7 @@ use crate::bounded_contexts::{
 pub struct FanVenturesPaymentIntegration {
     payment_handler: Arc<dyn PaymentCommandHandler>,
     venture_repository: Arc<PostgresFanVenturesRepository>,
+    currency_converter: Option<Arc<CurrencyConverter>>,
 }
 
 impl FanVenturesPaymentIntegration {
@@ -38,9 +40,38 @@ impl FanVenturesPaymentIntegration {
         Self {
             payment_handler,
             venture_repository,
+            currency_converter: None,
         }
     }
 
+    /// Funding goals and investments are kept in the base currency; payments
+    /// settled in another currency are converted before they count as funding
+    pub fn with_currency_converter(mut self, currency_converter: Arc<CurrencyConverter>) -> Self {
+        self.currency_converter = Some(currency_converter);
+        self
+    }
+
+    fn base_currency(&self) -> Currency {
+        match &self.currency_converter {
+            Some(converter) => converter.base_currency().clone(),
+            None => CurrencyConverter::base_currency_from_env(),
+        }
+    }
+
+    /// Base-currency value of a settled payment
+    async fn funding_value(&self, amount: &Amount) -> Result<f64, AppError> {
+        if amount.currency() == &self.base_currency() {
+            return Ok(amount.value());
+        }
+        let converter = self.currency_converter.as_ref().ok_or_else(|| {
+            AppError::InvalidInput(format!(
+                "Cannot fund a venture with {}: no currency converter configured",
+                amount.currency()
+            ))
+        })?;
+        Ok(converter.normalize(amount).await?.base.value())
+    }
+
     /// Create a payment for a venture investment
     pub async fn create_investment_payment(
         &self,
@@ -91,7 +122,7 @@ impl FanVenturesPaymentIntegration {
             payer_id: investment.fan_id,
             payee_id: artist_id,
             amount_value: investment.investment_amount,
-            amount_currency: Currency::USD, // TODO: Make configurable
+            amount_currency: self.base_currency(),
             payment_method: crate::bounded_contexts::payment::application::commands::PaymentMethodDto {
                 method_type: "PlatformBalance".to_string(), // Default for now
                 card_details: None,
@@ -120,13 +151,15 @@ impl FanVenturesPaymentIntegration {
         payment_id: Uuid,
         investment_id: Uuid,
         venture_id: Uuid,
-        amount: f64,
+        amount: &Amount,
     ) -> Result<(), AppError> {
         info!(
             "Payment {} confirmed for investment {} in venture {}",
             payment_id, investment_id, venture_id
         );
 
+        let amount = self.funding_value(amount).await?;
+
         // Get investment
         let investments = self.venture_repository.get_fan_investments_by_venture(venture_id).await?;
         let investment = investments
@@ -1,6 +1,7 @@
 use crate::bounded_contexts::fan_ventures::domain::entities::{
     ArtistVenture, FanInvestment, VentureTier, VentureBenefit, VentureCategory, RiskLevel, VentureStatus, InvestmentType, InvestmentStatus, BenefitType, DeliveryMethod
 };
+use crate::bounded_contexts::fan_ventures::domain::repositories::VentureRepository;
 use crate::shared::domain::errors::AppError;
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -58,7 +59,8 @@ fn parse_venture_status(s: &str) -> VentureStatus {
     match s.to_lowercase().as_str() {
         "draft" => VentureStatus::Draft,
         "open" | "active" => VentureStatus::Open,
-        "closed" | "funded" | "completed" => VentureStatus::Closed,
+        "funded" => VentureStatus::Funded,
+        "closed" | "completed" => VentureStatus::Closed,
         "cancelled" => VentureStatus::Cancelled,
         _ => VentureStatus::Draft,
     }
@@ -151,6 +153,27 @@ impl PostgresFanVenturesRepository {
         Ok(())
     }
 
+    /// Asociar el venture a la canción que financia
+    pub async fn set_venture_song(&self, venture_id: Uuid, song_id: Uuid) -> Result<(), AppError> {
+        sqlx::query("UPDATE artist_ventures SET song_id = $2 WHERE id = $1")
+            .bind(venture_id)
+            .bind(song_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to set venture song: {}", e)))?;
+        Ok(())
+    }
+
+    pub async fn get_venture_song(&self, venture_id: Uuid) -> Result<Option<Uuid>, AppError> {
+        let song_id: Option<Option<Uuid>> = sqlx::query_scalar("SELECT song_id FROM artist_ventures WHERE id = $1")
+            .bind(venture_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to get venture song: {}", e)))?;
+        Ok(song_id.flatten())
+    }
+
+    #[tracing::instrument(name = "venture_repository.get_venture", skip(self))]
     pub async fn get_venture(&self, venture_id: Uuid) -> Result<Option<ArtistVenture>, AppError> {
         let row = sqlx::query!(
             r#"SELECT id, artist_id, title, description, category, tags, risk_level,
@@ -621,6 +644,7 @@ benefits: vec![], // TODO: Load benefits separately
     // FAN INVESTMENTS
     // =============================================================================
 
+    #[tracing::instrument(name = "venture_repository.create_fan_investment", skip_all, fields(investment_id = %investment.id))]
     pub async fn create_fan_investment(&self, investment: &FanInvestment) -> Result<(), AppError> {
         sqlx::query!(
             r#"INSERT INTO fan_investments (
@@ -1373,4 +1397,35 @@ benefits: serde_json::from_value(row.benefits.unwrap_or(serde_json::Value::Null)
         // Keeps stub
         Ok(None)
     }
-} 
\ No newline at end of file
+} 
+// Las llamadas `Self::...` resuelven a los métodos inherentes de arriba
+#[async_trait::async_trait]
+impl VentureRepository for PostgresFanVenturesRepository {
+    async fn create_venture(&self, venture: &ArtistVenture) -> Result<(), AppError> {
+        Self::create_venture(self, venture).await
+    }
+
+    async fn get_venture(&self, venture_id: Uuid) -> Result<Option<ArtistVenture>, AppError> {
+        Self::get_venture(self, venture_id).await
+    }
+
+    async fn update_venture(&self, venture: &ArtistVenture) -> Result<(), AppError> {
+        Self::update_venture(self, venture).await
+    }
+
+    async fn create_fan_investment(&self, investment: &FanInvestment) -> Result<(), AppError> {
+        Self::create_fan_investment(self, investment).await
+    }
+
+    async fn get_investment_by_id(&self, investment_id: Uuid) -> Result<Option<FanInvestment>, AppError> {
+        Self::get_investment_by_id(self, investment_id).await
+    }
+
+    async fn update_fan_investment(&self, investment: &FanInvestment) -> Result<(), AppError> {
+        Self::update_fan_investment(self, investment).await
+    }
+
+    async fn get_fan_investments_by_venture(&self, venture_id: Uuid) -> Result<Vec<FanInvestment>, AppError> {
+        Self::get_fan_investments_by_venture(self, venture_id).await
+    }
+}
@@ -0,0 +1,255 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, PgPool, Row};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_ventures::domain::proposals::{
+    Proposal, ProposalError, ProposalOption, ProposalOutcome, Vote, VotingSnapshot,
+};
+use crate::bounded_contexts::fan_ventures::domain::repositories::ProposalRepository;
+use crate::shared::domain::errors::AppError;
+
+const PROPOSAL_COLUMNS: &str = r#"id, venture_id, artist_id, title, description, options, deadline,
+       quorum_percentage, tally_visibility, status, outcome, snapshot_at, finalized_at, created_at"#;
+
+/// Repositorio PostgreSQL para propuestas de votación de accionistas
+pub struct PostgresProposalRepository {
+    pool: PgPool,
+}
+
+impl PostgresProposalRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn load_snapshot(&self, proposal_id: Uuid, taken_at: DateTime<Utc>) -> Result<VotingSnapshot, AppError> {
+        let rows = sqlx::query("SELECT holder_id, weight FROM proposal_voting_power WHERE proposal_id = $1")
+            .bind(proposal_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to load voting power: {}", e)))?;
+
+        let mut weights = HashMap::with_capacity(rows.len());
+        for row in rows {
+            weights.insert(row.get::<Uuid, _>("holder_id"), row.get::<f64, _>("weight"));
+        }
+        Ok(VotingSnapshot { taken_at, weights })
+    }
+
+    async fn load_votes(&self, proposal_id: Uuid) -> Result<Vec<Vote>, AppError> {
+        let rows = sqlx::query(
+            "SELECT voter_id, option_id, weight, cast_at FROM proposal_votes WHERE proposal_id = $1 ORDER BY cast_at",
+        )
+        .bind(proposal_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load votes: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| Vote {
+                voter_id: row.get("voter_id"),
+                option_id: row.get("option_id"),
+                weight: row.get("weight"),
+                cast_at: row.get("cast_at"),
+            })
+            .collect())
+    }
+
+    async fn row_to_proposal(&self, row: PgRow) -> Result<Proposal, AppError> {
+        let id: Uuid = row.get("id");
+        let options: Vec<ProposalOption> = serde_json::from_value(row.get("options"))
+            .map_err(|e| AppError::SerializationError(format!("Failed to parse proposal options: {}", e)))?;
+        let outcome: Option<ProposalOutcome> = row
+            .get::<Option<serde_json::Value>, _>("outcome")
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| AppError::SerializationError(format!("Failed to parse proposal outcome: {}", e)))?;
+        let tally_visibility = row
+            .get::<String, _>("tally_visibility")
+            .parse()
+            .map_err(AppError::SerializationError)?;
+        let status = row.get::<String, _>("status").parse().map_err(AppError::SerializationError)?;
+
+        let snapshot = self.load_snapshot(id, row.get("snapshot_at")).await?;
+        let votes = self.load_votes(id).await?;
+
+        Ok(Proposal {
+            id,
+            venture_id: row.get("venture_id"),
+            artist_id: row.get("artist_id"),
+            title: row.get("title"),
+            description: row.get("description"),
+            options,
+            deadline: row.get("deadline"),
+            quorum_percentage: row.get("quorum_percentage"),
+            tally_visibility,
+            snapshot,
+            votes,
+            status,
+            outcome,
+            created_at: row.get("created_at"),
+            finalized_at: row.get("finalized_at"),
+        })
+    }
+
+    async fn rows_to_proposals(&self, rows: Vec<PgRow>) -> Result<Vec<Proposal>, AppError> {
+        let mut proposals = Vec::with_capacity(rows.len());
+        for row in rows {
+            proposals.push(self.row_to_proposal(row).await?);
+        }
+        Ok(proposals)
+    }
+}
+
+#[async_trait]
+impl ProposalRepository for PostgresProposalRepository {
+    async fn create(&self, proposal: &Proposal) -> Result<(), AppError> {
+        let options = serde_json::to_value(&proposal.options)
+            .map_err(|e| AppError::SerializationError(e.to_string()))?;
+
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        sqlx::query(
+            r#"INSERT INTO venture_proposals (
+                   id, venture_id, artist_id, title, description, options, deadline,
+                   quorum_percentage, tally_visibility, status, snapshot_at, created_at
+               ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)"#,
+        )
+        .bind(proposal.id)
+        .bind(proposal.venture_id)
+        .bind(proposal.artist_id)
+        .bind(&proposal.title)
+        .bind(&proposal.description)
+        .bind(options)
+        .bind(proposal.deadline)
+        .bind(proposal.quorum_percentage)
+        .bind(proposal.tally_visibility.to_string())
+        .bind(proposal.status.to_string())
+        .bind(proposal.snapshot.taken_at)
+        .bind(proposal.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to create proposal: {}", e)))?;
+
+        for (holder_id, weight) in &proposal.snapshot.weights {
+            sqlx::query("INSERT INTO proposal_voting_power (proposal_id, holder_id, weight) VALUES ($1, $2, $3)")
+                .bind(proposal.id)
+                .bind(holder_id)
+                .bind(weight)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to store voting power: {}", e)))?;
+        }
+
+        tx.commit().await.map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, proposal_id: &Uuid) -> Result<Option<Proposal>, AppError> {
+        let row = sqlx::query(&format!("SELECT {} FROM venture_proposals WHERE id = $1", PROPOSAL_COLUMNS))
+            .bind(proposal_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        match row {
+            Some(row) => Ok(Some(self.row_to_proposal(row).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn find_by_venture(&self, venture_id: &Uuid) -> Result<Vec<Proposal>, AppError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM venture_proposals WHERE venture_id = $1 ORDER BY created_at DESC",
+            PROPOSAL_COLUMNS
+        ))
+        .bind(venture_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        self.rows_to_proposals(rows).await
+    }
+
+    async fn record_vote(&self, proposal_id: &Uuid, vote: &Vote) -> Result<(), AppError> {
+        // La PK (proposal_id, voter_id) garantiza un único voto aun con peticiones concurrentes,
+        // y el EXISTS que solo se vote mientras la propuesta sigue abierta en la base de datos
+        let result = sqlx::query(
+            r#"INSERT INTO proposal_votes (proposal_id, voter_id, option_id, weight, cast_at)
+               SELECT $1, $2, $3, $4, $5
+               WHERE EXISTS (
+                   SELECT 1 FROM venture_proposals
+                   WHERE id = $1 AND status = 'open' AND deadline > NOW()
+               )
+               ON CONFLICT (proposal_id, voter_id) DO NOTHING"#,
+        )
+        .bind(proposal_id)
+        .bind(vote.voter_id)
+        .bind(vote.option_id)
+        .bind(vote.weight)
+        .bind(vote.cast_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to record vote: {}", e)))?;
+
+        if result.rows_affected() == 0 {
+            let already_voted: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM proposal_votes WHERE proposal_id = $1 AND voter_id = $2)",
+            )
+            .bind(proposal_id)
+            .bind(vote.voter_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to record vote: {}", e)))?;
+
+            return Err(if already_voted {
+                ProposalError::AlreadyVoted(vote.voter_id).into()
+            } else {
+                ProposalError::ProposalClosed(*proposal_id).into()
+            });
+        }
+        Ok(())
+    }
+
+    async fn mark_finalized(&self, proposal: &Proposal) -> Result<(), AppError> {
+        let outcome = serde_json::to_value(&proposal.outcome)
+            .map_err(|e| AppError::SerializationError(e.to_string()))?;
+
+        let result = sqlx::query(
+            r#"UPDATE venture_proposals
+               SET status = $2, outcome = $3, finalized_at = $4
+               WHERE id = $1 AND status = 'open'"#,
+        )
+        .bind(proposal.id)
+        .bind(proposal.status.to_string())
+        .bind(outcome)
+        .bind(proposal.finalized_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to finalize proposal: {}", e)))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::ConcurrencyConflict(format!(
+                "Proposal {} was already finalized",
+                proposal.id
+            )));
+        }
+        Ok(())
+    }
+
+    async fn find_due(&self, now: DateTime<Utc>) -> Result<Vec<Proposal>, AppError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM venture_proposals WHERE status = 'open' AND deadline <= $1 ORDER BY deadline",
+            PROPOSAL_COLUMNS
+        ))
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        self.rows_to_proposals(rows).await
+    }
+}
@@ -1,6 +1,5 @@
 // Re-export implementations from parent directory
 pub use super::postgres_repository;
-// pub use super::in_memory_repository;
 
 use std::sync::Arc;
 use async_trait::async_trait;
@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, PgPool, Row};
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_ventures::domain::escrow::InvestmentReservation;
+use crate::bounded_contexts::fan_ventures::domain::repositories::InvestmentReservationRepository;
+use crate::shared::domain::errors::AppError;
+
+const RESERVATION_COLUMNS: &str = r#"investment_id, venture_id, fan_id, shares, payment_id, auto_confirm,
+       status, expires_at, created_at, resolved_at"#;
+
+/// Repositorio PostgreSQL para reservas de participaciones en escrow
+pub struct PostgresInvestmentReservationRepository {
+    pool: PgPool,
+}
+
+impl PostgresInvestmentReservationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_reservation(row: PgRow) -> Result<InvestmentReservation, AppError> {
+        let status = row.get::<String, _>("status").parse().map_err(AppError::SerializationError)?;
+
+        Ok(InvestmentReservation {
+            investment_id: row.get("investment_id"),
+            venture_id: row.get("venture_id"),
+            fan_id: row.get("fan_id"),
+            shares: row.get("shares"),
+            payment_id: row.get("payment_id"),
+            auto_confirm: row.get("auto_confirm"),
+            status,
+            expires_at: row.get("expires_at"),
+            created_at: row.get("created_at"),
+            resolved_at: row.get("resolved_at"),
+        })
+    }
+
+    fn rows_to_reservations(rows: Vec<PgRow>) -> Result<Vec<InvestmentReservation>, AppError> {
+        rows.into_iter().map(Self::row_to_reservation).collect()
+    }
+}
+
+#[async_trait]
+impl InvestmentReservationRepository for PostgresInvestmentReservationRepository {
+    #[tracing::instrument(name = "reservation_repository.create", skip_all, fields(investment_id = %reservation.investment_id))]
+    async fn create(&self, reservation: &InvestmentReservation) -> Result<(), AppError> {
+        sqlx::query(
+            r#"INSERT INTO investment_reservations (
+                   investment_id, venture_id, fan_id, shares, payment_id, auto_confirm,
+                   status, expires_at, created_at
+               ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#,
+        )
+        .bind(reservation.investment_id)
+        .bind(reservation.venture_id)
+        .bind(reservation.fan_id)
+        .bind(reservation.shares)
+        .bind(reservation.payment_id)
+        .bind(reservation.auto_confirm)
+        .bind(reservation.status.to_string())
+        .bind(reservation.expires_at)
+        .bind(reservation.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to create reservation: {}", e)))?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "reservation_repository.find_by_investment", skip(self))]
+    async fn find_by_investment(&self, investment_id: &Uuid) -> Result<Option<InvestmentReservation>, AppError> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM investment_reservations WHERE investment_id = $1",
+            RESERVATION_COLUMNS
+        ))
+        .bind(investment_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        row.map(Self::row_to_reservation).transpose()
+    }
+
+    #[tracing::instrument(name = "reservation_repository.find_pending_by_venture", skip(self))]
+    async fn find_pending_by_venture(&self, venture_id: &Uuid) -> Result<Vec<InvestmentReservation>, AppError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM investment_reservations WHERE venture_id = $1 AND status = 'pending'",
+            RESERVATION_COLUMNS
+        ))
+        .bind(venture_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Self::rows_to_reservations(rows)
+    }
+
+    #[tracing::instrument(name = "reservation_repository.resolve", skip_all, fields(investment_id = %reservation.investment_id))]
+    async fn resolve(&self, reservation: &InvestmentReservation) -> Result<(), AppError> {
+        // La condición sobre `status` arbitra la carrera entre confirmar y expirar:
+        // solo la primera transición que llega a la base de datos se aplica
+        let result = sqlx::query(
+            r#"UPDATE investment_reservations
+               SET status = $2, resolved_at = $3
+               WHERE investment_id = $1 AND status = 'pending'"#,
+        )
+        .bind(reservation.investment_id)
+        .bind(reservation.status.to_string())
+        .bind(reservation.resolved_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to resolve reservation: {}", e)))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::ConcurrencyConflict(format!(
+                "Reservation {} was already resolved",
+                reservation.investment_id
+            )));
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "reservation_repository.find_expired", skip(self))]
+    async fn find_expired(&self, now: DateTime<Utc>) -> Result<Vec<InvestmentReservation>, AppError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM investment_reservations WHERE status = 'pending' AND expires_at <= $1 ORDER BY expires_at",
+            RESERVATION_COLUMNS
+        ))
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Self::rows_to_reservations(rows)
+    }
+}
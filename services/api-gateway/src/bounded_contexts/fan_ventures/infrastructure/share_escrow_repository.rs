@@ -0,0 +1,302 @@
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_ventures::domain::entities::{FanInvestment, InvestmentType};
+use crate::bounded_contexts::fan_ventures::domain::escrow::EscrowedShare;
+use crate::bounded_contexts::fan_ventures::domain::repositories::ShareEscrowRepository;
+use crate::bounded_contexts::fan_ventures::domain::vesting::{ShareHolder, VestingSchedule};
+use crate::bounded_contexts::orchestrator::DomainEvent;
+use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::outbox::{self, OutboxMessage};
+
+/// Contexto con el que se guardan sus eventos en el outbox
+pub const FAN_VENTURES_OUTBOX_CONTEXT: &str = "fan_ventures";
+
+const ESCROW_COLUMNS: &str = r#"escrow_id, venture_id, seller_id, seller_investment_id, buyer_id, quantity, price,
+       payment_id, status, expires_at, created_at, resolved_at"#;
+
+/// Repositorio PostgreSQL para escrows de transferencias entre fans.
+///
+/// Cada cambio de estado del escrow, el movimiento de participaciones que
+/// implica y su evento en el outbox van en la misma transacción.
+pub struct PostgresShareEscrowRepository {
+    pool: PgPool,
+}
+
+impl PostgresShareEscrowRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_holding(row: &PgRow) -> Result<FanInvestment, AppError> {
+        Ok(FanInvestment {
+            id: row.get("id"),
+            fan_id: row.get("fan_id"),
+            venture_id: row.get("venture_id"),
+            investment_amount: row.get("investment_amount"),
+            // Default type; `complete` copia en SQL el tipo real de la inversión
+            investment_type: InvestmentType::RevenueShare,
+            status: row.get::<String, _>("status").parse().map_err(AppError::SerializationError)?,
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+    }
+
+    async fn load_share_holder<'e, E>(executor: E, investment_id: &Uuid) -> Result<Option<ShareHolder>, AppError>
+    where
+        E: sqlx::PgExecutor<'e>,
+    {
+        let row = sqlx::query(
+            r#"SELECT fi.id, fi.fan_id, fi.venture_id, fi.investment_amount, fi.status, fi.created_at, fi.updated_at,
+                      av.vesting_start_date, av.vesting_cliff_days, av.vesting_total_duration_days, av.vesting_type,
+                      COALESCE((SELECT SUM(se.quantity) FROM share_escrows se
+                                WHERE se.seller_investment_id = fi.id AND se.status <> 'cancelled'), 0) AS transferred_shares
+               FROM fan_investments fi
+               JOIN artist_ventures av ON av.id = fi.venture_id
+               WHERE fi.id = $1"#,
+        )
+        .bind(investment_id)
+        .fetch_optional(executor)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load share holder: {}", e)))?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let vesting_schedule = match row.get::<Option<chrono::DateTime<chrono::Utc>>, _>("vesting_start_date") {
+            Some(start_date) => Some(VestingSchedule::new(
+                start_date,
+                row.get::<i32, _>("vesting_cliff_days") as u32,
+                row.get::<i32, _>("vesting_total_duration_days") as u32,
+                row.get::<String, _>("vesting_type").parse().map_err(AppError::SerializationError)?,
+            )),
+            None => None,
+        };
+
+        Ok(Some(ShareHolder {
+            investment: Self::row_to_holding(&row)?,
+            transferred_shares: row.get("transferred_shares"),
+            vesting_schedule,
+        }))
+    }
+
+    fn row_to_escrow(row: PgRow) -> Result<EscrowedShare, AppError> {
+        let status = row.get::<String, _>("status").parse().map_err(AppError::SerializationError)?;
+
+        Ok(EscrowedShare {
+            escrow_id: row.get("escrow_id"),
+            venture_id: row.get("venture_id"),
+            seller_id: row.get("seller_id"),
+            seller_investment_id: row.get("seller_investment_id"),
+            buyer_id: row.get("buyer_id"),
+            quantity: row.get("quantity"),
+            price: row.get("price"),
+            payment_id: row.get("payment_id"),
+            status,
+            expires_at: row.get("expires_at"),
+            created_at: row.get("created_at"),
+            resolved_at: row.get("resolved_at"),
+        })
+    }
+
+    /// Marcar la salida de `Pending`; `false` si otro ya lo resolvió
+    async fn resolve(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        escrow: &EscrowedShare,
+        buyer_investment_id: Option<Uuid>,
+    ) -> Result<bool, AppError> {
+        let result = sqlx::query(
+            r#"UPDATE share_escrows
+               SET status = $2, payment_id = $3, buyer_investment_id = $4, resolved_at = $5
+               WHERE escrow_id = $1 AND status = 'pending'"#,
+        )
+        .bind(escrow.escrow_id)
+        .bind(escrow.status.to_string())
+        .bind(escrow.payment_id)
+        .bind(buyer_investment_id)
+        .bind(escrow.resolved_at)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to resolve share escrow: {}", e)))?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    async fn record_event(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        escrow: &EscrowedShare,
+        event: &DomainEvent,
+    ) -> Result<(), AppError> {
+        let message = OutboxMessage::from_event(FAN_VENTURES_OUTBOX_CONTEXT, "ShareEscrow", escrow.escrow_id, event)?;
+        outbox::enqueue(tx, &message).await
+    }
+}
+
+#[async_trait]
+impl ShareEscrowRepository for PostgresShareEscrowRepository {
+    #[tracing::instrument(name = "share_escrow_repository.find_holding", skip(self))]
+    async fn find_holding(&self, investment_id: &Uuid) -> Result<Option<FanInvestment>, AppError> {
+        let row = sqlx::query(
+            r#"SELECT id, fan_id, venture_id, investment_amount, status, created_at, updated_at
+               FROM fan_investments WHERE id = $1"#,
+        )
+        .bind(investment_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load investment: {}", e)))?;
+
+        row.as_ref().map(Self::row_to_holding).transpose()
+    }
+
+    #[tracing::instrument(name = "share_escrow_repository.find_share_holder", skip(self))]
+    async fn find_share_holder(&self, investment_id: &Uuid) -> Result<Option<ShareHolder>, AppError> {
+        Self::load_share_holder(&self.pool, investment_id).await
+    }
+
+    #[tracing::instrument(name = "share_escrow_repository.open", skip_all, fields(escrow_id = %escrow.escrow_id))]
+    async fn open(&self, escrow: &EscrowedShare) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to start transaction: {}", e)))?;
+
+        // Con la inversión bloqueada, lo ya vendido no cambia hasta el commit:
+        // dos ventas a la vez no pueden contar con las mismas participaciones liberadas
+        sqlx::query("SELECT id FROM fan_investments WHERE id = $1 FOR UPDATE")
+            .bind(escrow.seller_investment_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to lock seller investment: {}", e)))?;
+        if let Some(holder) = Self::load_share_holder(&mut *tx, &escrow.seller_investment_id).await? {
+            holder.ensure_transferable(escrow.quantity, escrow.created_at)?;
+        }
+
+        // Descuento condicional: dos ventas a la vez no pueden llevarse las mismas participaciones
+        let taken = sqlx::query(
+            r#"UPDATE fan_investments
+               SET investment_amount = investment_amount - $4, updated_at = NOW()
+               WHERE id = $1 AND fan_id = $2 AND venture_id = $3
+                 AND LOWER(status) = 'active' AND investment_amount >= $4"#,
+        )
+        .bind(escrow.seller_investment_id)
+        .bind(escrow.seller_id)
+        .bind(escrow.venture_id)
+        .bind(escrow.quantity)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to escrow shares: {}", e)))?;
+        if taken.rows_affected() == 0 {
+            return Err(AppError::DomainRuleViolation(format!(
+                "Seller no longer holds {} shares in investment {}",
+                escrow.quantity, escrow.seller_investment_id
+            )));
+        }
+
+        sqlx::query(
+            r#"INSERT INTO share_escrows (
+                   escrow_id, venture_id, seller_id, seller_investment_id, buyer_id, quantity, price,
+                   status, expires_at, created_at
+               ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)"#,
+        )
+        .bind(escrow.escrow_id)
+        .bind(escrow.venture_id)
+        .bind(escrow.seller_id)
+        .bind(escrow.seller_investment_id)
+        .bind(escrow.buyer_id)
+        .bind(escrow.quantity)
+        .bind(escrow.price)
+        .bind(escrow.status.to_string())
+        .bind(escrow.expires_at)
+        .bind(escrow.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to create share escrow: {}", e)))?;
+        Self::record_event(&mut tx, escrow, &escrow.escrowed_event()).await?;
+
+        tx.commit().await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit share escrow: {}", e)))
+    }
+
+    #[tracing::instrument(name = "share_escrow_repository.complete", skip_all, fields(escrow_id = %escrow.escrow_id))]
+    async fn complete(&self, escrow: &EscrowedShare, buyer_investment: &FanInvestment) -> Result<FanInvestment, AppError> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to start transaction: {}", e)))?;
+
+        // Una inversión por fan y venture: si el comprador ya tiene una activa se
+        // acumulan en ella; la nueva hereda el tipo de la del vendedor
+        let row = sqlx::query(
+            r#"INSERT INTO fan_investments (
+                   id, fan_id, venture_id, investment_amount, investment_type, status, created_at, updated_at
+               )
+               SELECT $1, $2, venture_id, $3, investment_type, 'active', $4, $4
+               FROM fan_investments WHERE id = $5
+               ON CONFLICT (fan_id, venture_id) DO UPDATE SET
+                   investment_amount = fan_investments.investment_amount + EXCLUDED.investment_amount,
+                   updated_at = EXCLUDED.updated_at
+               WHERE LOWER(fan_investments.status) = 'active'
+               RETURNING id, investment_amount, created_at, updated_at"#,
+        )
+        .bind(buyer_investment.id)
+        .bind(buyer_investment.fan_id)
+        .bind(buyer_investment.investment_amount)
+        .bind(buyer_investment.created_at)
+        .bind(escrow.seller_investment_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to credit buyer investment: {}", e)))?
+        .ok_or_else(|| AppError::DomainRuleViolation(
+            "Buyer has an investment in this venture that is not active".to_string(),
+        ))?;
+
+        let credited = FanInvestment {
+            id: row.get("id"),
+            investment_amount: row.get("investment_amount"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+            ..buyer_investment.clone()
+        };
+        if !Self::resolve(&mut tx, escrow, Some(credited.id)).await? {
+            return Err(AppError::ConcurrencyConflict(format!("Share escrow {} was already resolved", escrow.escrow_id)));
+        }
+        Self::record_event(&mut tx, escrow, &escrow.transferred_event(credited.id)).await?;
+
+        tx.commit().await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit share transfer: {}", e)))?;
+        Ok(credited)
+    }
+
+    #[tracing::instrument(name = "share_escrow_repository.cancel", skip(self, escrow), fields(escrow_id = %escrow.escrow_id))]
+    async fn cancel(&self, escrow: &EscrowedShare, reason: &str) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to start transaction: {}", e)))?;
+
+        if !Self::resolve(&mut tx, escrow, None).await? {
+            return Err(AppError::ConcurrencyConflict(format!("Share escrow {} was already resolved", escrow.escrow_id)));
+        }
+
+        sqlx::query(
+            r#"UPDATE fan_investments
+               SET investment_amount = investment_amount + $2, updated_at = NOW()
+               WHERE id = $1"#,
+        )
+        .bind(escrow.seller_investment_id)
+        .bind(escrow.quantity)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to return escrowed shares: {}", e)))?;
+        Self::record_event(&mut tx, escrow, &escrow.cancelled_event(reason)).await?;
+
+        tx.commit().await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit escrow cancellation: {}", e)))
+    }
+
+    #[tracing::instrument(name = "share_escrow_repository.find_by_id", skip(self))]
+    async fn find_by_id(&self, escrow_id: &Uuid) -> Result<Option<EscrowedShare>, AppError> {
+        let row = sqlx::query(&format!("SELECT {} FROM share_escrows WHERE escrow_id = $1", ESCROW_COLUMNS))
+            .bind(escrow_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        row.map(Self::row_to_escrow).transpose()
+    }
+}
@@ -0,0 +1,140 @@
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{postgres::PgRow, PgPool, Row};
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_ventures::domain::repositories::SecondaryMarketTradeRepository;
+use crate::bounded_contexts::fan_ventures::domain::trades::{SecondaryMarketTrade, PRICE_CHANGE_WINDOW_HOURS};
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+use crate::shared::domain::errors::AppError;
+
+const TRADE_COLUMNS: &str = "id, venture_id, seller_id, buyer_id, shares_quantity, price_per_share, executed_at";
+
+/// Repositorio PostgreSQL del historial de operaciones del mercado secundario
+pub struct PostgresSecondaryMarketTradeRepository {
+    pool: PgPool,
+}
+
+impl PostgresSecondaryMarketTradeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_trade(row: PgRow) -> SecondaryMarketTrade {
+        SecondaryMarketTrade {
+            id: row.get("id"),
+            venture_id: row.get("venture_id"),
+            seller_id: row.get("seller_id"),
+            buyer_id: row.get("buyer_id"),
+            shares_quantity: row.get("shares_quantity"),
+            price_per_share: row.get("price_per_share"),
+            executed_at: row.get("executed_at"),
+        }
+    }
+}
+
+#[async_trait]
+impl SecondaryMarketTradeRepository for PostgresSecondaryMarketTradeRepository {
+    async fn record(&self, trade: &SecondaryMarketTrade) -> Result<(), AppError> {
+        sqlx::query(
+            r#"INSERT INTO secondary_market_trades (
+                   id, venture_id, seller_id, buyer_id, shares_quantity, price_per_share, executed_at
+               ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+               ON CONFLICT (id) DO NOTHING"#,
+        )
+        .bind(trade.id)
+        .bind(trade.venture_id)
+        .bind(trade.seller_id)
+        .bind(trade.buyer_id)
+        .bind(trade.shares_quantity)
+        .bind(trade.price_per_share)
+        .bind(trade.executed_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to record secondary market trade: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn find_by_venture(&self, venture_id: &Uuid, page: u32, page_size: u32) -> Result<Vec<SecondaryMarketTrade>, AppError> {
+        let rows = sqlx::query(&format!(
+            r#"SELECT {} FROM secondary_market_trades
+               WHERE venture_id = $1
+               ORDER BY executed_at DESC, id
+               LIMIT $2 OFFSET $3"#,
+            TRADE_COLUMNS
+        ))
+        .bind(venture_id)
+        .bind(page_size as i64)
+        .bind(page as i64 * page_size as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(rows.into_iter().map(Self::row_to_trade).collect())
+    }
+
+    async fn count_by_venture(&self, venture_id: &Uuid) -> Result<u64, AppError> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM secondary_market_trades WHERE venture_id = $1")
+            .bind(venture_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(count as u64)
+    }
+
+    async fn find_price_reference_trades(
+        &self,
+        venture_id: &Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<SecondaryMarketTrade>, AppError> {
+        // Dos lecturas por el índice (venture_id, executed_at)
+        let rows = sqlx::query(&format!(
+            r#"(SELECT {columns} FROM secondary_market_trades
+                WHERE venture_id = $1 AND executed_at <= $2
+                ORDER BY executed_at DESC LIMIT 1)
+               UNION ALL
+               (SELECT {columns} FROM secondary_market_trades
+                WHERE venture_id = $1 AND executed_at <= $3
+                ORDER BY executed_at DESC LIMIT 1)"#,
+            columns = TRADE_COLUMNS
+        ))
+        .bind(venture_id)
+        .bind(now)
+        .bind(now - Duration::hours(PRICE_CHANGE_WINDOW_HOURS))
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(rows.into_iter().map(Self::row_to_trade).collect())
+    }
+}
+
+/// Escucha `ShareTransferred` y registra la operación en el historial del
+/// mercado secundario
+pub struct SecondaryMarketTradeRecorder {
+    repository: Arc<dyn SecondaryMarketTradeRepository>,
+}
+
+impl SecondaryMarketTradeRecorder {
+    pub fn new(repository: Arc<dyn SecondaryMarketTradeRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl EventHandler for SecondaryMarketTradeRecorder {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let Some(trade) = SecondaryMarketTrade::from_event(event) else {
+            return Ok(());
+        };
+
+        self.repository.record(&trade).await.map_err(|e| {
+            tracing::error!("Failed to record trade {} of venture {}: {}", trade.id, trade.venture_id, e);
+            e
+        })
+    }
+}
@@ -0,0 +1,76 @@
+//! Holdings User Deletion Listener
+//!
+//! Las inversiones en ventures son registros financieros: se conservan, pero a
+//! nombre del pseudónimo del evento en lugar del usuario eliminado.
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+use crate::shared::domain::errors::AppError;
+
+pub struct HoldingsUserDeletionListener {
+    pool: PgPool,
+}
+
+impl HoldingsUserDeletionListener {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_error(e: sqlx::Error) -> AppError {
+    AppError::DatabaseError(format!("Failed to pseudonymize fan venture holdings: {}", e))
+}
+
+#[async_trait]
+impl EventHandler for HoldingsUserDeletionListener {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let DomainEvent::UserDeletionRequested { user_id, pseudonym_id, .. } = event else {
+            return Ok(());
+        };
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let investments = sqlx::query("UPDATE fan_investments SET fan_id = $2, updated_at = NOW() WHERE fan_id = $1")
+            .bind(user_id)
+            .bind(pseudonym_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        sqlx::query("UPDATE investment_reservations SET fan_id = $2 WHERE fan_id = $1")
+            .bind(user_id)
+            .bind(pseudonym_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        // Las transferencias entre fans y su historial de precios guardan a las dos partes
+        sqlx::query("UPDATE share_escrows SET seller_id = $2 WHERE seller_id = $1")
+            .bind(user_id)
+            .bind(pseudonym_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        sqlx::query("UPDATE share_escrows SET buyer_id = $2 WHERE buyer_id = $1")
+            .bind(user_id)
+            .bind(pseudonym_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        sqlx::query("UPDATE secondary_market_trades SET seller_id = $2 WHERE seller_id = $1")
+            .bind(user_id)
+            .bind(pseudonym_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        sqlx::query("UPDATE secondary_market_trades SET buyer_id = $2 WHERE buyer_id = $1")
+            .bind(user_id)
+            .bind(pseudonym_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        tx.commit().await.map_err(db_error)?;
+
+        tracing::info!("Pseudonymized {} investments of deleted user {}", investments.rows_affected(), user_id);
+        Ok(())
+    }
+}
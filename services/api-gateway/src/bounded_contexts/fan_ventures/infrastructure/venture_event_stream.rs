@@ -0,0 +1,69 @@
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+use crate::shared::domain::errors::AppError;
+
+// =============================================================================
+// FAN VENTURES - REDIS STREAM DEL CONTEXTO (`fan_ventures_events`)
+// =============================================================================
+
+/// Stream donde se replican los eventos de fan ventures para consumidores externos
+pub const FAN_VENTURES_STREAM: &str = "fan_ventures_events";
+
+/// Eventos del contexto que se replican en el stream
+pub const FAN_VENTURES_STREAM_EVENTS: &[&str] = &[
+    "VentureCreated",
+    "InvestmentMade",
+    "SharePurchased",
+    "VentureFunded",
+    "InvestmentReservationExpired",
+    "SharesEscrowed",
+    "ShareTransferred",
+    "EscrowCancelled",
+    "BenefitDelivered",
+    "RevenueDistributed",
+];
+
+/// Publica cada evento como un entry (`XADD`) con los campos `type`,
+/// `occurred_at` y `data` (el evento serializado en JSON).
+pub struct RedisVentureEventStream {
+    connection: ConnectionManager,
+    stream_name: String,
+}
+
+impl RedisVentureEventStream {
+    pub fn new(connection: ConnectionManager) -> Self {
+        Self {
+            connection,
+            stream_name: FAN_VENTURES_STREAM.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl EventHandler for RedisVentureEventStream {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let data = serde_json::to_string(event)
+            .map_err(|e| AppError::SerializationError(e.to_string()))?;
+
+        let mut conn = self.connection.clone();
+        let _: String = redis::cmd("XADD")
+            .arg(&self.stream_name)
+            .arg("*")
+            .arg("type")
+            .arg(event.event_type())
+            .arg("occurred_at")
+            .arg(event.occurred_at().to_rfc3339())
+            .arg("data")
+            .arg(data)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to append {} to {}: {}", event.event_type(), self.stream_name, e);
+                AppError::ExternalServiceError(format!("Redis stream error: {}", e))
+            })?;
+
+        Ok(())
+    }
+}
@@ -6,6 +6,7 @@ pub mod domain;
 pub mod application;
 pub mod infrastructure;
 pub mod presentation;
+pub mod integration_service;
 
 // Re-export the fan ventures service
 pub use application::simple_service::FanVenturesService;
@@ -0,0 +1,43 @@
+use axum::{
+    extract::{Path, State},
+    response::Json as ResponseJson,
+};
+use uuid::Uuid;
+
+use crate::auth::Claims;
+use crate::bounded_contexts::user::domain::UserRole;
+use crate::bounded_contexts::fan_ventures::domain::audit::AuditLog;
+use crate::openapi::{ApiResponse, ApiError};
+use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::app_state::FanVenturesAppState;
+use crate::shared::infrastructure::auth::authorize_role;
+
+/// Get the audit trail of a venture
+///
+/// Append-only history of share purchases, share transfers and revenue
+/// distributions for the venture, oldest first. Admin only.
+#[utoipa::path(
+    get,
+    path = "/api/v1/fan-ventures/admin/audit/{aggregate_id}",
+    params(
+        ("aggregate_id" = Uuid, Path, description = "Venture ID")
+    ),
+    responses(
+        (status = 200, description = "Audit trail", body = ApiResponse<Vec<AuditLog>>),
+        (status = 403, description = "Admin role required", body = ApiError)
+    ),
+    tag = "fan-ventures",
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn get_audit_trail(
+    State(state): State<FanVenturesAppState>,
+    Path(aggregate_id): Path<Uuid>,
+    claims: Claims,
+) -> Result<ResponseJson<ApiResponse<Vec<AuditLog>>>, AppError> {
+    authorize_role(&claims, &[UserRole::Admin])?;
+
+    let entries = state.audit_log_repository.get_for_aggregate(aggregate_id).await?;
+    Ok(ResponseJson(ApiResponse::success(entries)))
+}
@@ -14,6 +14,13 @@ use chrono::{DateTime, Utc};
 
 use crate::shared::infrastructure::app_state::FanVenturesAppState;
 use crate::bounded_contexts::orchestrator::DomainEvent;
+use crate::bounded_contexts::fan_ventures::domain::portfolio::VenturePortfolio;
+use crate::bounded_contexts::fan_ventures::domain::trades::price_change_24h;
+use crate::bounded_contexts::fan_ventures::application::PurchaseSharesCommand;
+use crate::shared::application::command::CommandHandler;
+use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::auth::AuthenticatedUser;
+use std::collections::{HashMap, HashSet};
 
 // =============================================================================
 // REQUEST/RESPONSE DTOs
@@ -22,10 +29,40 @@ use crate::bounded_contexts::orchestrator::DomainEvent;
 #[derive(Debug, Deserialize)]
 pub struct CreateVentureRequest {
     pub artist_id: Uuid,
+    /// Canción que financia el venture
+    pub song_id: Option<Uuid>,
     pub title: String,
     pub description: String,
     pub funding_goal: f64,
     pub equity_percentage: f64,
+    /// Inversión mínima por compra (por defecto: 10)
+    pub min_investment: Option<f64>,
+    pub max_investment: Option<f64>,
+    /// Fecha límite para alcanzar el objetivo
+    pub deadline: Option<DateTime<Utc>>,
+}
+
+impl CreateVentureRequest {
+    fn validate(&self, now: DateTime<Utc>) -> Result<(), String> {
+        if self.funding_goal <= 0.0 {
+            return Err("funding_goal must be positive".to_string());
+        }
+        let min_investment = self.min_investment.unwrap_or(DEFAULT_MIN_INVESTMENT);
+        if min_investment <= 0.0 || min_investment > self.funding_goal {
+            return Err("min_investment must be positive and not exceed funding_goal".to_string());
+        }
+        if let Some(max_investment) = self.max_investment {
+            if max_investment < min_investment {
+                return Err("max_investment must be at least min_investment".to_string());
+            }
+        }
+        if let Some(deadline) = self.deadline {
+            if deadline <= now {
+                return Err("deadline must be in the future".to_string());
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -37,17 +74,41 @@ pub struct VentureResponse {
     pub funding_goal: f64,
     pub current_funding: f64,
     pub equity_percentage: f64,
+    /// Participaciones retenidas por compras pendientes de confirmación
+    pub reserved_shares: f64,
+    /// Participaciones que aún se pueden comprar (excluye las reservadas)
+    pub available_shares: f64,
     pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub song_id: Option<Uuid>,
+    pub min_investment: f64,
+    pub max_investment: Option<f64>,
+    pub deadline: Option<DateTime<Utc>>,
+    /// Variación (%) del precio por participación en el mercado secundario
+    /// en las últimas 24h; None sin operaciones anteriores a la ventana
+    pub price_change_24h: Option<f64>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct InvestRequest {
-    pub investor_id: Uuid,
     pub amount: f64,
+    /// Confirmar la compra en cuanto se liquide el pago (por defecto: true).
+    /// Con `false` queda pendiente hasta `POST /purchases/:id/confirm`.
+    pub auto_confirm: Option<bool>,
 }
 
+/// Body de `POST /investments`: como `InvestRequest` pero con el venture en el body
+#[derive(Debug, Deserialize)]
+pub struct CreateInvestmentRequest {
+    pub venture_id: Uuid,
+    #[serde(flatten)]
+    pub investment: InvestRequest,
+}
+
+const DEFAULT_MIN_INVESTMENT: f64 = 10.0;
+
 // =============================================================================
 // FAN VENTURES CONTROLLER
 // =============================================================================
@@ -62,6 +123,11 @@ impl FanVenturesController {
     ) -> Result<ResponseJson<VentureResponse>, (StatusCode, ResponseJson<serde_json::Value>)> {
         let venture_id = Uuid::new_v4();
         let now = Utc::now();
+
+        if let Err(message) = request.validate(now) {
+            return Err((StatusCode::BAD_REQUEST, ResponseJson(serde_json::json!({"error": message}))));
+        }
+        let min_investment = request.min_investment.unwrap_or(DEFAULT_MIN_INVESTMENT);
         
         // Map request to domain entity
         // Note: Using defaults for fields not present in simple request
@@ -79,11 +145,12 @@ impl FanVenturesController {
             artist_success_rate: 0.0,
             funding_goal: request.funding_goal,
             current_funding: 0.0,
-            min_investment: 10.0, // Default min
-            max_investment: None,
-            status: crate::bounded_contexts::fan_ventures::domain::entities::VentureStatus::Draft,
-            start_date: None,
-            end_date: None,
+            min_investment,
+            max_investment: request.max_investment,
+            // Abierto desde su creación; pasa a Funded al alcanzar el objetivo
+            status: crate::bounded_contexts::fan_ventures::domain::entities::VentureStatus::Open,
+            start_date: Some(now),
+            end_date: request.deadline,
             created_at: now,
             updated_at: now,
             benefits: vec![],
@@ -94,6 +161,12 @@ impl FanVenturesController {
             tracing::error!("Failed to create venture: {:?}", e);
             return Err((StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({"error": "Database error"}))));
         }
+        if let Some(song_id) = request.song_id {
+            if let Err(e) = state.venture_repository.set_venture_song(venture_id, song_id).await {
+                tracing::error!("Failed to link venture {} to song {}: {:?}", venture_id, song_id, e);
+                return Err((StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({"error": "Database error"}))));
+            }
+        }
         
         // Publish domain event
         let event = DomainEvent::VentureCreated {
@@ -114,7 +187,14 @@ impl FanVenturesController {
             funding_goal: request.funding_goal,
             current_funding: 0.0,
             equity_percentage: request.equity_percentage,
-            status: "Draft".to_string(),
+            reserved_shares: 0.0,
+            available_shares: request.funding_goal,
+            status: venture.status.to_string(),
+            song_id: request.song_id,
+            min_investment,
+            max_investment: request.max_investment,
+            deadline: request.deadline,
+            price_change_24h: None,
             created_at: now,
             updated_at: now,
         };
@@ -149,6 +229,22 @@ impl FanVenturesController {
     ) -> Result<ResponseJson<VentureResponse>, (StatusCode, ResponseJson<serde_json::Value>)> {
         match state.venture_repository.get_venture(venture_id).await {
             Ok(Some(venture)) => {
+                let availability = state.escrow_service.availability_of(&venture).await
+                    .map_err(|e| {
+                        tracing::error!("Failed to compute share availability: {:?}", e);
+                        (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({"error": "Database error"})))
+                    })?;
+                let song_id = state.venture_repository.get_venture_song(venture.id).await
+                    .map_err(|e| {
+                        tracing::error!("Failed to get venture song: {:?}", e);
+                        (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({"error": "Database error"})))
+                    })?;
+                let now = Utc::now();
+                let reference_trades = state.trade_repository.find_price_reference_trades(&venture.id, now).await
+                    .map_err(|e| {
+                        tracing::error!("Failed to get venture price history: {:?}", e);
+                        (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({"error": "Database error"})))
+                    })?;
                 let response = VentureResponse {
                     venture_id: venture.id,
                     artist_id: venture.artist_id,
@@ -157,7 +253,14 @@ impl FanVenturesController {
                     funding_goal: venture.funding_goal,
                     current_funding: venture.current_funding,
                     equity_percentage: 0.0, // TODO: Store this in DB or calculate
+                    reserved_shares: availability.reserved_shares,
+                    available_shares: availability.available_shares,
                     status: venture.status.to_string(),
+                    song_id,
+                    min_investment: venture.min_investment,
+                    max_investment: venture.max_investment,
+                    deadline: venture.end_date,
+                    price_change_24h: price_change_24h(&reference_trades, now),
                     created_at: venture.created_at,
                     updated_at: venture.updated_at,
                 };
@@ -172,46 +275,64 @@ impl FanVenturesController {
     }
     
     /// POST /api/v1/fan-ventures/ventures/:id/invest - Invest in a venture
+    ///
+    /// Las participaciones quedan reservadas y el pago retenido hasta que la
+    /// compra se confirme, se cancele o venza la reserva.
     pub async fn invest_in_venture(
+        AuthenticatedUser { user_id, .. }: AuthenticatedUser,
         State(state): State<FanVenturesAppState>,
         Path(venture_id): Path<Uuid>,
         axum::extract::Json(request): axum::extract::Json<InvestRequest>,
     ) -> Result<ResponseJson<serde_json::Value>, (StatusCode, ResponseJson<serde_json::Value>)> {
-        let investment_id = Uuid::new_v4();
-        let now = Utc::now();
+        Self::reserve_investment(&state, venture_id, user_id, request).await
+    }
 
-        let investment = crate::bounded_contexts::fan_ventures::domain::entities::FanInvestment::new(
-            investment_id,
-            request.investor_id,
-            venture_id,
-            request.amount,
-            crate::bounded_contexts::fan_ventures::domain::entities::InvestmentType::RevenueShare, // Default
-            crate::bounded_contexts::fan_ventures::domain::entities::InvestmentStatus::Pending,
-        );
+    /// POST /api/v1/fan-ventures/investments - Invest in the venture given in the body
+    pub async fn create_investment(
+        AuthenticatedUser { user_id, .. }: AuthenticatedUser,
+        State(state): State<FanVenturesAppState>,
+        axum::extract::Json(request): axum::extract::Json<CreateInvestmentRequest>,
+    ) -> Result<ResponseJson<serde_json::Value>, (StatusCode, ResponseJson<serde_json::Value>)> {
+        Self::reserve_investment(&state, request.venture_id, user_id, request.investment).await
+    }
 
-        if let Err(e) = state.venture_repository.create_fan_investment(&investment).await {
-             tracing::error!("Failed to create investment: {:?}", e);
-             return Err((StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({"error": "Database error"}))));
-        }
-        
-        // Publish domain event
-        let event = DomainEvent::InvestmentMade {
+    async fn reserve_investment(
+        state: &FanVenturesAppState,
+        venture_id: Uuid,
+        investor_id: Uuid,
+        request: InvestRequest,
+    ) -> Result<ResponseJson<serde_json::Value>, (StatusCode, ResponseJson<serde_json::Value>)> {
+        let auto_confirm = request.auto_confirm.unwrap_or(true);
+
+        let command = PurchaseSharesCommand {
+            request_id: Uuid::new_v4(),
             venture_id,
-            investor_id: request.investor_id,
-            amount: request.amount,
-            occurred_at: now,
+            fan_id: investor_id,
+            shares: request.amount,
+            auto_confirm,
         };
-        
-        if let Err(e) = state.app_state.publish_event(event).await {
-            tracing::warn!("Failed to publish investment made event: {:?}", e);
-        }
-        
+        let (investment, reservation) = state.purchase_shares_handler
+            .handle(command)
+            .await
+            .map_err(|e| {
+                let message = e.to_string();
+                let status = StatusCode::from(e);
+                if status.is_server_error() {
+                    tracing::error!("Failed to reserve investment: {}", message);
+                }
+                (status, ResponseJson(serde_json::json!({"error": message})))
+            })?;
+
         Ok(ResponseJson(serde_json::json!({
-            "message": "Investment successful",
+            "message": "Investment pending confirmation",
             "venture_id": venture_id,
-            "investor_id": request.investor_id,
+            "investor_id": investor_id,
             "amount": request.amount,
-            "investment_id": investment_id
+            "investment_id": investment.id,
+            "status": investment.status.to_string(),
+            "payment_id": reservation.payment_id,
+            "auto_confirm": reservation.auto_confirm,
+            "reserved_until": reservation.expires_at
         })))
     }
     
@@ -280,6 +401,28 @@ impl FanVenturesController {
         }
     }
     
+    /// GET /api/v1/fan-ventures/portfolios/:user_id - Investments aggregated per venture
+    pub async fn get_portfolio(
+        State(state): State<FanVenturesAppState>,
+        Path(user_id): Path<Uuid>,
+    ) -> Result<ResponseJson<VenturePortfolio>, (StatusCode, ResponseJson<serde_json::Value>)> {
+        let database_error = |e: AppError| {
+            tracing::error!("Failed to build portfolio for {}: {:?}", user_id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({"error": "Database error"})))
+        };
+
+        let investments = state.venture_repository.get_fan_investments(user_id).await.map_err(database_error)?;
+
+        let mut ventures = HashMap::new();
+        for venture_id in investments.iter().map(|i| i.venture_id).collect::<HashSet<_>>() {
+            if let Some(venture) = state.venture_repository.get_venture(venture_id).await.map_err(database_error)? {
+                ventures.insert(venture_id, venture);
+            }
+        }
+
+        Ok(ResponseJson(VenturePortfolio::from_investments(user_id, &investments, &ventures)))
+    }
+    
     /// GET /api/v1/fan-ventures/analytics/venture/:id - Get venture analytics
     pub async fn get_venture_analytics(
         State(state): State<FanVenturesAppState>,
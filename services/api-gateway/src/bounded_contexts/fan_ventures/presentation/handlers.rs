@@ -9,6 +9,9 @@ use chrono::{DateTime, Utc};
 use std::sync::Arc;
 
 use crate::auth::Claims;
+use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::auth::{authorize_owner, OwnedResource};
+use crate::bounded_contexts::orchestrator::DomainEvent;
 use crate::bounded_contexts::fan_ventures::infrastructure::postgres_repository::PostgresFanVenturesRepository;
 use crate::bounded_contexts::fan_ventures::domain::entities::{
     ArtistVenture, FanInvestment, VentureStatus, RevenueDistribution, 
@@ -162,6 +165,22 @@ pub async fn invest_in_venture_handler(
     }))
 }
 
+/// Only the venture's artist (or an admin) manages its revenue and deliveries
+async fn authorize_venture_owner(
+    repo: &PostgresFanVenturesRepository,
+    claims: &Claims,
+    venture_id: Uuid,
+) -> Result<(), StatusCode> {
+    let venture = repo.get_venture(venture_id).await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    authorize_owner(claims, OwnedResource::Venture, venture.artist_id).map_err(|err| match err {
+        AppError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+        _ => StatusCode::FORBIDDEN,
+    })
+}
+
 /// POST /api/v1/ventures/{id}/revenue - Distribute Revenue
 pub async fn distribute_revenue_handler(
     State(state): State<AppState>,
@@ -169,12 +188,9 @@ pub async fn distribute_revenue_handler(
     claims: Claims,
     Json(request): Json<DistributeRevenueRequest>,
 ) -> Result<ResponseJson<DistributeRevenueResponse>, StatusCode> {
-    // Only admin or artist
-    if claims.role != "admin" && claims.role != "artist" {
-        return Err(StatusCode::FORBIDDEN);
-    }
-
     let repo = PostgresFanVenturesRepository::new(state.get_db_pool());
+    // Only the venture's artist or an admin
+    authorize_venture_owner(&repo, &claims, venture_id).await?;
 
     let distribution_id = Uuid::new_v4();
     let distribution = RevenueDistribution {
@@ -192,6 +208,17 @@ pub async fn distribute_revenue_handler(
     repo.create_revenue_distribution(&distribution).await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
+    let event = DomainEvent::RevenueDistributed {
+        venture_id,
+        distribution_id,
+        distributed_by: Uuid::parse_str(&claims.sub).ok(),
+        amount: request.total_revenue,
+        occurred_at: distribution.distributed_at,
+    };
+    if let Err(e) = state.event_bus.publish(event).await {
+        tracing::warn!("Failed to publish revenue distributed event: {:?}", e);
+    }
+
     // TODO: Trigger Async Job to actually transfer funds/tokens to all investors
     
     Ok(ResponseJson(DistributeRevenueResponse {
@@ -208,6 +235,7 @@ pub async fn get_venture_deliveries_handler(
     claims: Claims,
 ) -> Result<ResponseJson<Vec<BenefitDelivery>>, StatusCode> {
     let repo = PostgresFanVenturesRepository::new(state.get_db_pool());
+    authorize_venture_owner(&repo, &claims, venture_id).await?;
     
     let deliveries = repo.get_venture_deliveries(venture_id).await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -228,6 +256,7 @@ pub async fn update_delivery_handler(
     let mut delivery = repo.get_benefit_delivery(delivery_id).await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
         .ok_or(StatusCode::NOT_FOUND)?;
+    authorize_venture_owner(&repo, &claims, delivery.venture_id).await?;
 
     // Update fields
     delivery.delivery_status = request.status;
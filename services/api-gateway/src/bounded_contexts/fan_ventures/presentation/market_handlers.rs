@@ -0,0 +1,60 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::Json as ResponseJson,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::bounded_contexts::fan_ventures::application::market_stats::{
+    GetMarketStatsQuery, MarketStatsResult, DEFAULT_PERIOD_DAYS,
+};
+use crate::openapi::ApiResponse;
+use crate::shared::infrastructure::app_state::FanVenturesAppState;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct MarketStatsParams {
+    /// Ventana en días (por defecto 30, el único periodo cacheado)
+    pub period_days: Option<u32>,
+    pub include_genre_breakdown: Option<bool>,
+    pub include_trending_songs: Option<bool>,
+}
+
+/// Get market statistics
+///
+/// Aggregated investment volume for the period, optionally broken down by
+/// genre and with a ranking of trending ventures.
+#[utoipa::path(
+    get,
+    path = "/api/v1/fan-ventures/market/stats",
+    params(MarketStatsParams),
+    responses(
+        (status = 200, description = "Market statistics", body = ApiResponse<MarketStatsResult>),
+        (status = 400, description = "Invalid period", body = ApiError)
+    ),
+    tag = "fan-ventures"
+)]
+pub async fn get_market_stats(
+    State(state): State<FanVenturesAppState>,
+    Query(params): Query<MarketStatsParams>,
+) -> Result<ResponseJson<ApiResponse<MarketStatsResult>>, (StatusCode, ResponseJson<serde_json::Value>)> {
+    let query = GetMarketStatsQuery {
+        period_days: params.period_days.unwrap_or(DEFAULT_PERIOD_DAYS),
+        include_genre_breakdown: params.include_genre_breakdown.unwrap_or(false),
+        include_trending_songs: params.include_trending_songs.unwrap_or(false),
+    };
+
+    let stats = state.market_stats_service
+        .get_market_stats(query)
+        .await
+        .map_err(|error| {
+            let message = error.to_string();
+            let status = StatusCode::from(error);
+            if status.is_server_error() {
+                tracing::error!("Market stats request failed: {}", message);
+            }
+            (status, ResponseJson(serde_json::json!({"error": message})))
+        })?;
+
+    Ok(ResponseJson(ApiResponse::success(stats)))
+}
@@ -0,0 +1,199 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    http::StatusCode,
+    response::{Json as ResponseJson, Response},
+    routing::get,
+    Router,
+};
+use uuid::Uuid;
+
+use crate::auth::Claims;
+use crate::bounded_contexts::fan_ventures::application::market_feed::{
+    FeedCloseReason, FeedCommand, FeedMessage, MarketFeed,
+};
+
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+/// Sin tráfico (ni pongs) durante este tiempo se da la conexión por muerta
+const IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+/// 1013 "Try Again Later": el cliente no consumía los mensajes a tiempo
+const SLOW_CONSUMER_CLOSE_CODE: u16 = 1013;
+const GOING_AWAY_CLOSE_CODE: u16 = 1001;
+
+/// Rutas del feed en tiempo real (`/ws`), con su propio estado
+pub fn routes(feed: Arc<MarketFeed>) -> Router {
+    Router::new()
+        .route("/ws", get(market_feed_ws))
+        .with_state(feed)
+}
+
+/// GET /api/v1/fan-ventures/ws - Price and portfolio updates over WebSocket
+///
+/// Clients send `{"action":"subscribe","venture_id":"..."}` or
+/// `{"action":"subscribe_portfolio"}` and receive JSON deltas tagged by `type`.
+pub async fn market_feed_ws(
+    ws: WebSocketUpgrade,
+    State(feed): State<Arc<MarketFeed>>,
+    claims: Claims,
+) -> Result<Response, (StatusCode, ResponseJson<serde_json::Value>)> {
+    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            ResponseJson(serde_json::json!({"error": "Invalid user ID"})),
+        )
+    })?;
+
+    Ok(ws.on_upgrade(move |socket| run_connection(socket, feed, user_id)))
+}
+
+async fn send(socket: &mut WebSocket, message: &FeedMessage) -> bool {
+    match serde_json::to_string(message) {
+        Ok(text) => socket.send(Message::Text(text)).await.is_ok(),
+        Err(e) => {
+            tracing::error!("Failed to serialize market feed message: {}", e);
+            true
+        }
+    }
+}
+
+async fn close(socket: &mut WebSocket, code: u16, reason: &'static str) {
+    let _ = socket
+        .send(Message::Close(Some(CloseFrame { code, reason: reason.into() })))
+        .await;
+}
+
+async fn run_connection(mut socket: WebSocket, feed: Arc<MarketFeed>, user_id: Uuid) {
+    let mut connection = feed.register(user_id).await;
+    let mut ping = tokio::time::interval_at(tokio::time::Instant::now() + PING_INTERVAL, PING_INTERVAL);
+    let mut last_seen = Instant::now();
+
+    loop {
+        tokio::select! {
+            incoming = socket.recv() => {
+                let message = match incoming {
+                    Some(Ok(message)) => message,
+                    _ => break,
+                };
+                last_seen = Instant::now();
+                match message {
+                    Message::Text(text) => {
+                        let reply = match serde_json::from_str::<FeedCommand>(&text) {
+                            Ok(command) => feed
+                                .apply(connection.id, command)
+                                .await
+                                .unwrap_or_else(|e| FeedMessage::Error { message: e.to_string() }),
+                            Err(e) => FeedMessage::Error { message: format!("Invalid command: {}", e) },
+                        };
+                        if !send(&mut socket, &reply).await {
+                            break;
+                        }
+                    }
+                    Message::Close(_) => break,
+                    _ => {}
+                }
+            }
+            Some(message) = connection.messages.recv() => {
+                if !send(&mut socket, &message).await {
+                    break;
+                }
+            }
+            reason = &mut connection.closed => {
+                if let Ok(FeedCloseReason::SlowConsumer) = reason {
+                    close(&mut socket, SLOW_CONSUMER_CLOSE_CODE, "slow consumer").await;
+                }
+                break;
+            }
+            _ = ping.tick() => {
+                if last_seen.elapsed() > IDLE_TIMEOUT {
+                    close(&mut socket, GOING_AWAY_CLOSE_CODE, "idle timeout").await;
+                    break;
+                }
+                if socket.send(Message::Ping(Vec::new())).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    feed.unregister(connection.id).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::{self, client::IntoClientRequest};
+
+    async fn spawn_server(feed: Arc<MarketFeed>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, routes(feed)).await.unwrap();
+        });
+        format!("ws://{}/ws", addr)
+    }
+
+    async fn next_feed_message<S>(socket: &mut S) -> FeedMessage
+    where
+        S: StreamExt<Item = Result<tungstenite::Message, tungstenite::Error>> + Unpin,
+    {
+        loop {
+            let message = tokio::time::timeout(Duration::from_secs(5), socket.next())
+                .await
+                .expect("timed out waiting for feed message")
+                .unwrap()
+                .unwrap();
+            if let tungstenite::Message::Text(text) = message {
+                return serde_json::from_str(&text).unwrap();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn purchase_pushes_price_to_subscribed_client() {
+        let feed = Arc::new(MarketFeed::new());
+        let url = spawn_server(Arc::clone(&feed)).await;
+
+        let user_id = Uuid::new_v4();
+        let token = Claims::new(user_id, "fan".to_string(), "fan@vibestream.test".to_string(), "user".to_string(), "access".to_string())
+            .to_jwt()
+            .unwrap();
+        let mut request = url.into_client_request().unwrap();
+        request
+            .headers_mut()
+            .insert("Authorization", format!("Bearer {}", token).parse().unwrap());
+        let (mut socket, _) = tokio_tungstenite::connect_async(request).await.unwrap();
+
+        let venture_id = Uuid::new_v4();
+        let subscribe = serde_json::json!({"action": "subscribe", "venture_id": venture_id});
+        socket.send(tungstenite::Message::Text(subscribe.to_string())).await.unwrap();
+        assert_eq!(next_feed_message(&mut socket).await, FeedMessage::Subscribed { venture_id: Some(venture_id) });
+
+        feed.handle(&DomainEvent::InvestmentMade {
+            venture_id,
+            investor_id: Uuid::new_v4(),
+            amount: 120.0,
+            occurred_at: chrono::Utc::now(),
+        })
+        .await
+        .unwrap();
+
+        loop {
+            match next_feed_message(&mut socket).await {
+                FeedMessage::Price { venture_id: v, price, .. } => {
+                    assert_eq!(v, venture_id);
+                    assert_eq!(price, 120.0);
+                    break;
+                }
+                FeedMessage::Purchase { .. } => continue,
+                other => panic!("unexpected message: {:?}", other),
+            }
+        }
+    }
+}
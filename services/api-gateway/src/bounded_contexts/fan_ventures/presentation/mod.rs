@@ -3,6 +3,12 @@ pub mod routes;
 pub mod handlers;
 pub mod ownership_routes;
 pub mod venture_handlers;
+pub mod proposal_handlers;
+pub mod purchase_handlers;
+pub mod market_handlers;
+pub mod market_ws;
+pub mod audit_handlers;
+pub mod trade_handlers;
 
 use crate::bounded_contexts::fan_ventures::application::services::MockFanVenturesApplicationService;
 
@@ -0,0 +1,272 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json as ResponseJson,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::auth::Claims;
+use crate::bounded_contexts::fan_ventures::application::NewProposal;
+use crate::bounded_contexts::fan_ventures::domain::proposals::{
+    OptionTally, Proposal, ProposalOption, ProposalOutcome, ProposalStatus, TallyVisibility,
+};
+use crate::openapi::ApiResponse;
+use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::app_state::FanVenturesAppState;
+
+// =============================================================================
+// REQUEST/RESPONSE TYPES
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateProposalRequest {
+    pub title: String,
+    pub description: Option<String>,
+    pub options: Vec<String>,
+    pub deadline: DateTime<Utc>,
+    /// Porcentaje (0-100) del poder de voto que debe participar
+    pub quorum_percentage: f64,
+    /// Ocultar recuentos parciales hasta que se cierre la votación (por defecto: false)
+    pub hide_tally_until_finalized: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CastVoteRequest {
+    pub option_id: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CastVoteResponse {
+    pub proposal_id: Uuid,
+    pub option_id: Uuid,
+    pub weight: f64,
+    pub cast_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProposalResponse {
+    pub proposal_id: Uuid,
+    pub venture_id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub options: Vec<ProposalOption>,
+    pub deadline: DateTime<Utc>,
+    pub quorum_percentage: f64,
+    pub tally_visibility: TallyVisibility,
+    pub status: ProposalStatus,
+    pub snapshot_at: DateTime<Utc>,
+    pub eligible_voters: u32,
+    pub votes_cast: u32,
+    pub turnout_percentage: f64,
+    /// `None` mientras la votación esté abierta con recuentos ocultos
+    pub tally: Option<Vec<OptionTally>>,
+    pub outcome: Option<ProposalOutcome>,
+    pub finalized_at: Option<DateTime<Utc>>,
+}
+
+impl From<Proposal> for ProposalResponse {
+    fn from(proposal: Proposal) -> Self {
+        Self {
+            proposal_id: proposal.id,
+            venture_id: proposal.venture_id,
+            tally: proposal.visible_tally(),
+            turnout_percentage: proposal.turnout_percentage(),
+            eligible_voters: proposal.snapshot.weights.len() as u32,
+            votes_cast: proposal.votes.len() as u32,
+            snapshot_at: proposal.snapshot.taken_at,
+            title: proposal.title,
+            description: proposal.description,
+            options: proposal.options,
+            deadline: proposal.deadline,
+            quorum_percentage: proposal.quorum_percentage,
+            tally_visibility: proposal.tally_visibility,
+            status: proposal.status,
+            outcome: proposal.outcome,
+            finalized_at: proposal.finalized_at,
+        }
+    }
+}
+
+fn error_response(error: AppError) -> (StatusCode, ResponseJson<serde_json::Value>) {
+    let message = error.to_string();
+    let status = StatusCode::from(error);
+    if status.is_server_error() {
+        tracing::error!("Proposal request failed: {}", message);
+    }
+    (status, ResponseJson(serde_json::json!({"error": message})))
+}
+
+fn user_id_from_claims(claims: &Claims) -> Result<Uuid, (StatusCode, ResponseJson<serde_json::Value>)> {
+    Uuid::parse_str(&claims.sub).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            ResponseJson(serde_json::json!({"error": "Invalid user ID"})),
+        )
+    })
+}
+
+// =============================================================================
+// HANDLERS
+// =============================================================================
+
+/// Create a shareholder proposal
+///
+/// Only the venture's artist can open a proposal. Voting power is snapshotted
+/// from confirmed investments at creation time.
+#[utoipa::path(
+    post,
+    path = "/api/v1/fan-ventures/ventures/{id}/proposals",
+    params(
+        ("id" = Uuid, Path, description = "Venture ID")
+    ),
+    request_body = CreateProposalRequest,
+    responses(
+        (status = 200, description = "Proposal created", body = ApiResponse<ProposalResponse>),
+        (status = 400, description = "Invalid proposal", body = ApiError),
+        (status = 403, description = "Forbidden - Only the venture's artist can create proposals", body = ApiError),
+        (status = 404, description = "Venture not found", body = ApiError)
+    ),
+    tag = "fan-ventures",
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn create_proposal(
+    State(state): State<FanVenturesAppState>,
+    Path(venture_id): Path<Uuid>,
+    claims: Claims,
+    axum::extract::Json(request): axum::extract::Json<CreateProposalRequest>,
+) -> Result<ResponseJson<ApiResponse<ProposalResponse>>, (StatusCode, ResponseJson<serde_json::Value>)> {
+    if claims.role != "artist" {
+        return Err((
+            StatusCode::FORBIDDEN,
+            ResponseJson(serde_json::json!({"error": "Only artists can create proposals"})),
+        ));
+    }
+    let artist_id = user_id_from_claims(&claims)?;
+
+    let tally_visibility = if request.hide_tally_until_finalized.unwrap_or(false) {
+        TallyVisibility::HiddenUntilFinalized
+    } else {
+        TallyVisibility::Live
+    };
+
+    let proposal = state.proposal_service
+        .create_proposal(venture_id, artist_id, NewProposal {
+            title: request.title,
+            description: request.description,
+            options: request.options,
+            deadline: request.deadline,
+            quorum_percentage: request.quorum_percentage,
+            tally_visibility,
+        })
+        .await
+        .map_err(error_response)?;
+
+    Ok(ResponseJson(ApiResponse::success(proposal.into())))
+}
+
+/// List proposals for a venture
+#[utoipa::path(
+    get,
+    path = "/api/v1/fan-ventures/ventures/{id}/proposals",
+    params(
+        ("id" = Uuid, Path, description = "Venture ID")
+    ),
+    responses(
+        (status = 200, description = "Venture proposals", body = ApiResponse<Vec<ProposalResponse>>)
+    ),
+    tag = "fan-ventures",
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn list_venture_proposals(
+    State(state): State<FanVenturesAppState>,
+    Path(venture_id): Path<Uuid>,
+    _claims: Claims,
+) -> Result<ResponseJson<ApiResponse<Vec<ProposalResponse>>>, (StatusCode, ResponseJson<serde_json::Value>)> {
+    let proposals = state.proposal_service
+        .list_for_venture(venture_id)
+        .await
+        .map_err(error_response)?;
+
+    Ok(ResponseJson(ApiResponse::success(
+        proposals.into_iter().map(ProposalResponse::from).collect(),
+    )))
+}
+
+/// Get a proposal with its current (or final) tally
+#[utoipa::path(
+    get,
+    path = "/api/v1/fan-ventures/proposals/{id}",
+    params(
+        ("id" = Uuid, Path, description = "Proposal ID")
+    ),
+    responses(
+        (status = 200, description = "Proposal details", body = ApiResponse<ProposalResponse>),
+        (status = 404, description = "Proposal not found", body = ApiError)
+    ),
+    tag = "fan-ventures",
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn get_proposal(
+    State(state): State<FanVenturesAppState>,
+    Path(proposal_id): Path<Uuid>,
+    _claims: Claims,
+) -> Result<ResponseJson<ApiResponse<ProposalResponse>>, (StatusCode, ResponseJson<serde_json::Value>)> {
+    let proposal = state.proposal_service
+        .get_proposal(proposal_id)
+        .await
+        .map_err(error_response)?;
+
+    Ok(ResponseJson(ApiResponse::success(proposal.into())))
+}
+
+/// Cast a vote on a proposal
+///
+/// Each shareholder votes once; the vote is weighted by their shares at the
+/// proposal's snapshot and cannot be changed afterwards.
+#[utoipa::path(
+    post,
+    path = "/api/v1/fan-ventures/proposals/{id}/votes",
+    params(
+        ("id" = Uuid, Path, description = "Proposal ID")
+    ),
+    request_body = CastVoteRequest,
+    responses(
+        (status = 200, description = "Vote recorded", body = ApiResponse<CastVoteResponse>),
+        (status = 400, description = "Voting closed or unknown option", body = ApiError),
+        (status = 403, description = "Not a shareholder at snapshot time", body = ApiError),
+        (status = 409, description = "Already voted", body = ApiError)
+    ),
+    tag = "fan-ventures",
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn cast_vote(
+    State(state): State<FanVenturesAppState>,
+    Path(proposal_id): Path<Uuid>,
+    claims: Claims,
+    axum::extract::Json(request): axum::extract::Json<CastVoteRequest>,
+) -> Result<ResponseJson<ApiResponse<CastVoteResponse>>, (StatusCode, ResponseJson<serde_json::Value>)> {
+    let voter_id = user_id_from_claims(&claims)?;
+
+    let vote = state.proposal_service
+        .vote(proposal_id, voter_id, request.option_id)
+        .await
+        .map_err(error_response)?;
+
+    Ok(ResponseJson(ApiResponse::success(CastVoteResponse {
+        proposal_id,
+        option_id: vote.option_id,
+        weight: vote.weight,
+        cast_at: vote.cast_at,
+    })))
+}
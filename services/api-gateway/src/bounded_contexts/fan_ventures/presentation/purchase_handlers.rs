@@ -0,0 +1,140 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::Json as ResponseJson,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::auth::Claims;
+use crate::bounded_contexts::fan_ventures::domain::escrow::InvestmentReservation;
+use crate::openapi::{ApiResponse, ApiError};
+use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::app_state::FanVenturesAppState;
+
+// =============================================================================
+// REQUEST/RESPONSE TYPES
+// =============================================================================
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PurchaseResponse {
+    pub purchase_id: Uuid,
+    pub venture_id: Uuid,
+    pub shares: f64,
+    pub payment_id: Option<Uuid>,
+    /// pending, confirmed, cancelled o expired
+    pub status: String,
+    pub reserved_until: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl From<InvestmentReservation> for PurchaseResponse {
+    fn from(reservation: InvestmentReservation) -> Self {
+        Self {
+            purchase_id: reservation.investment_id,
+            venture_id: reservation.venture_id,
+            shares: reservation.shares,
+            payment_id: reservation.payment_id,
+            status: reservation.status.to_string(),
+            reserved_until: reservation.expires_at,
+            resolved_at: reservation.resolved_at,
+        }
+    }
+}
+
+fn error_response(error: AppError) -> (StatusCode, ResponseJson<serde_json::Value>) {
+    let message = error.to_string();
+    let status = StatusCode::from(error);
+    if status.is_server_error() {
+        tracing::error!("Purchase request failed: {}", message);
+    }
+    (status, ResponseJson(serde_json::json!({"error": message})))
+}
+
+fn user_id_from_claims(claims: &Claims) -> Result<Uuid, (StatusCode, ResponseJson<serde_json::Value>)> {
+    Uuid::parse_str(&claims.sub).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            ResponseJson(serde_json::json!({"error": "Invalid user ID"})),
+        )
+    })
+}
+
+// =============================================================================
+// HANDLERS
+// =============================================================================
+
+/// Confirm a pending purchase
+///
+/// Finalizes the purchase once its payment has settled. Fails if the
+/// reservation already expired or was cancelled.
+#[utoipa::path(
+    post,
+    path = "/api/v1/fan-ventures/purchases/{id}/confirm",
+    params(
+        ("id" = Uuid, Path, description = "Purchase (investment) ID")
+    ),
+    responses(
+        (status = 200, description = "Purchase confirmed", body = ApiResponse<PurchaseResponse>),
+        (status = 400, description = "Payment not settled or reservation expired", body = ApiError),
+        (status = 403, description = "Not the buyer of this purchase", body = ApiError),
+        (status = 404, description = "Purchase not found", body = ApiError),
+        (status = 409, description = "Purchase was resolved concurrently", body = ApiError)
+    ),
+    tag = "fan-ventures",
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn confirm_purchase(
+    State(state): State<FanVenturesAppState>,
+    Path(purchase_id): Path<Uuid>,
+    claims: Claims,
+) -> Result<ResponseJson<ApiResponse<PurchaseResponse>>, (StatusCode, ResponseJson<serde_json::Value>)> {
+    let fan_id = user_id_from_claims(&claims)?;
+
+    let reservation = state.escrow_service
+        .confirm(purchase_id, fan_id)
+        .await
+        .map_err(error_response)?;
+
+    Ok(ResponseJson(ApiResponse::success(reservation.into())))
+}
+
+/// Cancel a pending purchase
+///
+/// Releases the reserved shares and refunds (or cancels) the held payment.
+#[utoipa::path(
+    post,
+    path = "/api/v1/fan-ventures/purchases/{id}/cancel",
+    params(
+        ("id" = Uuid, Path, description = "Purchase (investment) ID")
+    ),
+    responses(
+        (status = 200, description = "Purchase cancelled", body = ApiResponse<PurchaseResponse>),
+        (status = 400, description = "Purchase is no longer pending", body = ApiError),
+        (status = 403, description = "Not the buyer of this purchase", body = ApiError),
+        (status = 404, description = "Purchase not found", body = ApiError),
+        (status = 409, description = "Purchase was resolved concurrently", body = ApiError)
+    ),
+    tag = "fan-ventures",
+    security(
+        ("bearer" = [])
+    )
+)]
+pub async fn cancel_purchase(
+    State(state): State<FanVenturesAppState>,
+    Path(purchase_id): Path<Uuid>,
+    claims: Claims,
+) -> Result<ResponseJson<ApiResponse<PurchaseResponse>>, (StatusCode, ResponseJson<serde_json::Value>)> {
+    let fan_id = user_id_from_claims(&claims)?;
+
+    let reservation = state.escrow_service
+        .cancel(purchase_id, fan_id)
+        .await
+        .map_err(error_response)?;
+
+    Ok(ResponseJson(ApiResponse::success(reservation.into())))
+}
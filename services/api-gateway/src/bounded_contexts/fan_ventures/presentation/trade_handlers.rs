@@ -0,0 +1,73 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::Json as ResponseJson,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_ventures::domain::trades::SecondaryMarketTrade;
+use crate::openapi::ApiResponse;
+use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::app_state::FanVenturesAppState;
+
+const DEFAULT_PAGE_SIZE: u32 = 20;
+const MAX_PAGE_SIZE: u32 = 100;
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct TradeHistoryParams {
+    /// Página, empezando en 0
+    pub page: Option<u32>,
+    /// Tamaño de página (1-100, por defecto 20)
+    pub page_size: Option<u32>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TradeHistoryResponse {
+    pub trades: Vec<SecondaryMarketTrade>,
+    pub page: u32,
+    pub page_size: u32,
+    pub total_count: u64,
+    pub total_pages: u32,
+}
+
+/// Get the secondary market trade history of a venture
+///
+/// Completed fan-to-fan share sales, most recent first, with the price per
+/// share each one was executed at.
+#[utoipa::path(
+    get,
+    path = "/api/v1/fan-ventures/ventures/{id}/trade-history",
+    params(
+        ("id" = Uuid, Path, description = "Venture ID"),
+        TradeHistoryParams
+    ),
+    responses(
+        (status = 200, description = "Trade history", body = ApiResponse<TradeHistoryResponse>),
+        (status = 404, description = "Venture not found", body = ApiError)
+    ),
+    tag = "fan-ventures"
+)]
+pub async fn get_trade_history(
+    State(state): State<FanVenturesAppState>,
+    Path(venture_id): Path<Uuid>,
+    Query(params): Query<TradeHistoryParams>,
+) -> Result<ResponseJson<ApiResponse<TradeHistoryResponse>>, AppError> {
+    let page = params.page.unwrap_or(0);
+    let page_size = params.page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+    if state.venture_repository.get_venture(venture_id).await?.is_none() {
+        return Err(AppError::NotFound(format!("Venture {} not found", venture_id)));
+    }
+
+    let trades = state.trade_repository.find_by_venture(&venture_id, page, page_size).await?;
+    let total_count = state.trade_repository.count_by_venture(&venture_id).await?;
+
+    Ok(ResponseJson(ApiResponse::success(TradeHistoryResponse {
+        trades,
+        page,
+        page_size,
+        total_count,
+        total_pages: total_count.div_ceil(page_size as u64) as u32,
+    })))
+}
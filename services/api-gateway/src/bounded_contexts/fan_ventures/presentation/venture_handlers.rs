@@ -9,6 +9,7 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
 use crate::auth::Claims;
+use crate::shared::infrastructure::auth::{authorize_owner, OwnedResource};
 use crate::shared::infrastructure::app_state::AppState;
 use crate::bounded_contexts::fan_ventures::{
     domain::entities::{ArtistVenture, FanInvestment, VentureStatus, InvestmentType, InvestmentStatus},
@@ -692,12 +693,12 @@ pub async fn update_venture(
         })?;
 
     // Verify ownership (artist or admin)
-    if claims.sub != venture.artist_id.to_string() && claims.role != "admin" {
-        return Err((
+    authorize_owner(&claims, OwnedResource::Venture, venture.artist_id).map_err(|err| {
+        (
             StatusCode::FORBIDDEN,
-            ResponseJson(serde_json::json!({"error": "Forbidden - Not the venture owner"})),
-        ));
-    }
+            ResponseJson(serde_json::json!({"error": err.to_string()})),
+        )
+    })?;
 
     // Update fields if provided
     if let Some(title) = request.title {
@@ -834,12 +835,12 @@ pub async fn delete_venture(
         })?;
 
     // Verify ownership (artist or admin)
-    if claims.sub != venture.artist_id.to_string() && claims.role != "admin" {
-        return Err((
+    authorize_owner(&claims, OwnedResource::Venture, venture.artist_id).map_err(|err| {
+        (
             StatusCode::FORBIDDEN,
-            ResponseJson(serde_json::json!({"error": "Forbidden - Not the venture owner"})),
-        ));
-    }
+            ResponseJson(serde_json::json!({"error": err.to_string()})),
+        )
+    })?;
 
     // Soft delete (set status to cancelled)
     repository.delete_venture(venture_id).await
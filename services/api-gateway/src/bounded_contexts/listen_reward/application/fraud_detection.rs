@@ -0,0 +1,274 @@
+// Listen Fraud Detection
+//
+// Device fingerprint checks when a listening session starts. A single device
+// farming rewards shows up as many sessions per day or as several accounts
+// sharing the same fingerprint. Each signal adds to the session score; sessions
+// scoring above the threshold are rejected with `AppError::FraudDetected`.
+// Every score is stored so admins can review them.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::bounded_contexts::listen_reward::domain::value_objects::{DeviceFingerprint, FraudFlag, FraudScore};
+use crate::bounded_contexts::listen_reward::infrastructure::repositories::{FraudScoreRecord, FraudScoreRepository};
+use crate::shared::domain::errors::{AppError, FraudDetails, FraudReasonCode};
+
+/// Sessions scoring above this are rejected
+pub const DEFAULT_DEVICE_FRAUD_THRESHOLD: f64 = 0.8;
+
+const TOO_MANY_SESSIONS_WEIGHT: f64 = 0.4;
+const BOT_LIKE_WEIGHT: f64 = 0.6;
+const VPN_WEIGHT: f64 = 0.2;
+/// Weight of the first account over the limit; each further one adds `EXTRA_ACCOUNT_WEIGHT`
+const SHARED_DEVICE_WEIGHT: f64 = 0.5;
+const EXTRA_ACCOUNT_WEIGHT: f64 = 0.1;
+const MAX_SHARED_DEVICE_WEIGHT: f64 = 0.9;
+
+#[derive(Debug, Clone)]
+pub struct FraudDetectionConfig {
+    pub threshold: f64,
+    /// Sessions per device and day before the device counts as farming
+    pub max_sessions_per_device: u32,
+    /// Accounts allowed on one device per day (a shared family tablet is fine)
+    pub max_accounts_per_device: u32,
+    /// Hashes of user agents of headless browsers and automation tools
+    pub bot_user_agent_hashes: HashSet<String>,
+    /// Hashes of VPN and datacenter IPs
+    pub vpn_ip_hashes: HashSet<String>,
+}
+
+impl Default for FraudDetectionConfig {
+    fn default() -> Self {
+        Self {
+            threshold: DEFAULT_DEVICE_FRAUD_THRESHOLD,
+            max_sessions_per_device: 50,
+            max_accounts_per_device: 3,
+            bot_user_agent_hashes: HashSet::new(),
+            vpn_ip_hashes: HashSet::new(),
+        }
+    }
+}
+
+impl FraudDetectionConfig {
+    /// Defaults plus the hash lists in `LISTEN_FRAUD_BOT_UA_HASHES` and
+    /// `LISTEN_FRAUD_VPN_IP_HASHES` (comma separated)
+    pub fn from_env() -> Self {
+        let hashes = |var: &str| -> HashSet<String> {
+            std::env::var(var)
+                .unwrap_or_default()
+                .split(',')
+                .map(|hash| hash.trim().to_string())
+                .filter(|hash| !hash.is_empty())
+                .collect()
+        };
+        Self {
+            bot_user_agent_hashes: hashes("LISTEN_FRAUD_BOT_UA_HASHES"),
+            vpn_ip_hashes: hashes("LISTEN_FRAUD_VPN_IP_HASHES"),
+            ..Self::default()
+        }
+    }
+}
+
+pub struct FraudDetectionService {
+    repository: Arc<dyn FraudScoreRepository>,
+    config: FraudDetectionConfig,
+}
+
+impl FraudDetectionService {
+    pub fn new(repository: Arc<dyn FraudScoreRepository>) -> Self {
+        Self::with_config(repository, FraudDetectionConfig::default())
+    }
+
+    pub fn with_config(repository: Arc<dyn FraudScoreRepository>, config: FraudDetectionConfig) -> Self {
+        Self { repository, config }
+    }
+
+    /// Score a session about to start on `fingerprint`. `sessions_today` is the
+    /// number of sessions the device already started today.
+    pub async fn is_suspicious(&self, fingerprint: &DeviceFingerprint, user_id: Uuid, sessions_today: u32) -> FraudScore {
+        let mut signals: Vec<(FraudFlag, f64)> = Vec::new();
+
+        if sessions_today >= self.config.max_sessions_per_device {
+            signals.push((FraudFlag::TooManySessionsFromDevice, TOO_MANY_SESSIONS_WEIGHT));
+        }
+        if self.config.bot_user_agent_hashes.contains(&fingerprint.user_agent_hash) {
+            signals.push((FraudFlag::BotLikePattern, BOT_LIKE_WEIGHT));
+        }
+        if self.config.vpn_ip_hashes.contains(&fingerprint.ip_hash) {
+            signals.push((FraudFlag::VpnDetected, VPN_WEIGHT));
+        }
+
+        let other_users = self
+            .repository
+            .count_other_users_on_device(&fingerprint.device_key(), user_id, start_of_day())
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("Skipping shared device check for user {}: {}", user_id, e);
+                0
+            });
+        let accounts = other_users as u32 + 1;
+        if accounts > self.config.max_accounts_per_device {
+            let extra = (accounts - self.config.max_accounts_per_device - 1) as f64;
+            let weight = (SHARED_DEVICE_WEIGHT + extra * EXTRA_ACCOUNT_WEIGHT).min(MAX_SHARED_DEVICE_WEIGHT);
+            signals.push((FraudFlag::DuplicateDeviceAcrossUsers, weight));
+        }
+
+        FraudScore {
+            score: signals.iter().map(|(_, weight)| weight).sum::<f64>().min(1.0),
+            flags: signals.into_iter().map(|(flag, _)| flag).collect(),
+        }
+    }
+
+    /// Score the session, store the score and fail with `FraudDetected` when it
+    /// is above the threshold
+    pub async fn screen(&self, fingerprint: &DeviceFingerprint, user_id: Uuid) -> Result<FraudScore, AppError> {
+        fingerprint.validate().map_err(AppError::ValidationError)?;
+
+        let sessions_today = self
+            .repository
+            .count_device_sessions_since(&fingerprint.device_key(), start_of_day())
+            .await
+            .map_err(AppError::DatabaseError)?
+            .max(0) as u32;
+        let score = self.is_suspicious(fingerprint, user_id, sessions_today).await;
+        let rejected = score.score > self.config.threshold;
+
+        self.repository
+            .record(&FraudScoreRecord::new(user_id, fingerprint, sessions_today, &score, rejected))
+            .await
+            .map_err(AppError::DatabaseError)?;
+
+        if rejected {
+            tracing::warn!("Listen session rejected for user {}: score {:.2}, flags {:?}", user_id, score.score, score.flags);
+            return Err(AppError::FraudDetected(
+                FraudDetails::new(reason_code(&score), "Listening session rejected by device fraud detection")
+                    .with_risk_score(score.score),
+            ));
+        }
+        Ok(score)
+    }
+}
+
+fn start_of_day() -> chrono::DateTime<chrono::Utc> {
+    chrono::Utc::now().date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc()
+}
+
+/// The reason reported for a rejection, from the strongest signal
+fn reason_code(score: &FraudScore) -> FraudReasonCode {
+    if score.has_flag(FraudFlag::DuplicateDeviceAcrossUsers) {
+        FraudReasonCode::SharedDevice
+    } else if score.has_flag(FraudFlag::BotLikePattern) {
+        FraudReasonCode::BotBehavior
+    } else if score.has_flag(FraudFlag::TooManySessionsFromDevice) {
+        FraudReasonCode::VelocityExceeded
+    } else {
+        FraudReasonCode::HighRiskScore
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::listen_reward::infrastructure::repositories::InMemoryFraudScoreRepository;
+
+    fn fingerprint(device_id: &str) -> DeviceFingerprint {
+        DeviceFingerprint::new("ua-hash".to_string(), "ip-hash".to_string(), Some(device_id.to_string())).unwrap()
+    }
+
+    fn service(repository: Arc<InMemoryFraudScoreRepository>) -> FraudDetectionService {
+        FraudDetectionService::new(repository)
+    }
+
+    #[tokio::test]
+    async fn household_sharing_a_device_is_not_flagged() {
+        let repository = Arc::new(InMemoryFraudScoreRepository::new());
+        let service = service(repository.clone());
+        let tablet = fingerprint("family-tablet");
+
+        for _ in 0..3 {
+            let score = service.screen(&tablet, Uuid::new_v4()).await.unwrap();
+            assert_eq!(score, FraudScore::clean());
+        }
+        assert_eq!(repository.records().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn device_farm_across_accounts_is_rejected() {
+        let repository = Arc::new(InMemoryFraudScoreRepository::new());
+        let service = service(repository.clone());
+        let farm = fingerprint("farm-phone");
+
+        // Accounts 4 to 7 are flagged but stay under the threshold
+        let mut scores = Vec::new();
+        for _ in 0..7 {
+            scores.push(service.screen(&farm, Uuid::new_v4()).await.unwrap().score);
+        }
+        assert_eq!(&scores[..3], &[0.0, 0.0, 0.0]);
+        assert!(scores[3..].windows(2).all(|pair| pair[0] < pair[1]));
+
+        let err = service.screen(&farm, Uuid::new_v4()).await.unwrap_err();
+        match err {
+            AppError::FraudDetected(details) => {
+                assert_eq!(details.reason_code, FraudReasonCode::SharedDevice);
+                assert_eq!(details.risk_score, Some(0.9));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+
+        // The rejection is kept for review, other devices are unaffected
+        let pending = repository.find_pending_review(0.8, 10).await.unwrap();
+        assert_eq!(pending.len(), 1);
+        assert!(pending[0].rejected);
+        assert!(pending[0].flags.contains(&FraudFlag::DuplicateDeviceAcrossUsers));
+        assert!(service.screen(&fingerprint("own-phone"), Uuid::new_v4()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn returning_user_is_not_counted_twice() {
+        let repository = Arc::new(InMemoryFraudScoreRepository::new());
+        let service = service(repository);
+        let phone = fingerprint("phone");
+        let owner = Uuid::new_v4();
+
+        for _ in 0..10 {
+            assert_eq!(service.screen(&phone, owner).await.unwrap(), FraudScore::clean());
+        }
+    }
+
+    #[tokio::test]
+    async fn shared_device_with_heavy_use_crosses_the_threshold() {
+        let repository = Arc::new(InMemoryFraudScoreRepository::new());
+        let service = service(repository);
+        let device = fingerprint("shared");
+        for _ in 0..3 {
+            service.screen(&device, Uuid::new_v4()).await.unwrap();
+        }
+
+        // A fourth account alone stays under the threshold...
+        let fourth = service.is_suspicious(&device, Uuid::new_v4(), 3).await;
+        assert_eq!(fourth.flags, vec![FraudFlag::DuplicateDeviceAcrossUsers]);
+        assert!(fourth.score <= DEFAULT_DEVICE_FRAUD_THRESHOLD);
+
+        // ...but not on a device already past its daily sessions
+        let busy = service.is_suspicious(&device, Uuid::new_v4(), 50).await;
+        assert!(busy.has_flag(FraudFlag::TooManySessionsFromDevice));
+        assert!(busy.score > DEFAULT_DEVICE_FRAUD_THRESHOLD);
+    }
+
+    #[tokio::test]
+    async fn bot_user_agent_and_vpn_add_up() {
+        let config = FraudDetectionConfig {
+            bot_user_agent_hashes: HashSet::from(["ua-hash".to_string()]),
+            vpn_ip_hashes: HashSet::from(["ip-hash".to_string()]),
+            ..FraudDetectionConfig::default()
+        };
+        let service = FraudDetectionService::with_config(Arc::new(InMemoryFraudScoreRepository::new()), config);
+
+        let score = service.is_suspicious(&fingerprint("headless"), Uuid::new_v4(), 0).await;
+        assert_eq!(score.flags, vec![FraudFlag::BotLikePattern, FraudFlag::VpnDetected]);
+        assert!((score.score - 0.8).abs() < 1e-9);
+        // Exactly at the threshold is still allowed
+        assert!(service.screen(&fingerprint("headless"), Uuid::new_v4()).await.is_ok());
+    }
+}
@@ -0,0 +1,137 @@
+// Listen Fraud Screening
+//
+// Rule-based risk scoring for completed listening sessions. Each rule yields a
+// score in [0, 1] and a reason code; the session risk is the highest score and
+// sessions at or above the threshold are rejected with `AppError::FraudDetected`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::domain::errors::{AppError, FraudDetails, FraudReasonCode};
+use super::listen_reward_application_service::CompleteListeningCommand;
+
+/// Same default as the stream processor's `fraud_threshold`
+pub const DEFAULT_LISTEN_FRAUD_THRESHOLD: f64 = 0.8;
+
+/// Tolerance for players reporting slightly more than the song length (buffering, seeks)
+const DURATION_TOLERANCE: f64 = 1.1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListenFraudAssessment {
+    pub risk_score: f64,
+    pub reason_code: Option<FraudReasonCode>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ListenFraudScorer {
+    threshold: f64,
+}
+
+impl Default for ListenFraudScorer {
+    fn default() -> Self {
+        Self::new(DEFAULT_LISTEN_FRAUD_THRESHOLD)
+    }
+}
+
+impl ListenFraudScorer {
+    pub fn new(threshold: f64) -> Self {
+        Self { threshold }
+    }
+
+    /// `elapsed_seconds` is the wall-clock time since the session started, when known
+    pub fn assess(&self, command: &CompleteListeningCommand, elapsed_seconds: Option<i64>) -> ListenFraudAssessment {
+        let mut signals: Vec<(f64, FraudReasonCode)> = Vec::new();
+
+        // Reported more listening than the song lasts
+        if command.listen_duration_seconds as f64 > command.song_duration_seconds as f64 * DURATION_TOLERANCE {
+            signals.push((0.9, FraudReasonCode::SuspiciousListenPattern));
+        }
+
+        // Reported more listening than time has passed since the session started
+        if let Some(elapsed) = elapsed_seconds {
+            if (command.listen_duration_seconds as i64) > elapsed.max(0) + 5 {
+                signals.push((0.95, FraudReasonCode::SuspiciousListenPattern));
+            }
+        }
+
+        // Scripted clients tend to report a perfect, uninterrupted play
+        if command.quality_score >= 0.999 && command.completion_percentage >= 100.0 {
+            signals.push((0.5, FraudReasonCode::BotBehavior));
+        }
+
+        signals
+            .into_iter()
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(risk_score, reason)| ListenFraudAssessment { risk_score, reason_code: Some(reason) })
+            .unwrap_or(ListenFraudAssessment { risk_score: 0.0, reason_code: None })
+    }
+
+    /// Fails with `FraudDetected` when the session risk reaches the threshold
+    pub fn screen(&self, command: &CompleteListeningCommand, elapsed_seconds: Option<i64>) -> Result<ListenFraudAssessment, AppError> {
+        let assessment = self.assess(command, elapsed_seconds);
+        match assessment.reason_code {
+            Some(reason_code) if assessment.risk_score >= self.threshold => Err(AppError::FraudDetected(
+                FraudDetails::new(reason_code, "Listening session rejected by fraud screening")
+                    .with_risk_score(assessment.risk_score),
+            )),
+            _ => Ok(assessment),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    fn command(listen_duration_seconds: u32, song_duration_seconds: u32) -> CompleteListeningCommand {
+        CompleteListeningCommand {
+            session_id: Uuid::new_v4(),
+            listen_duration_seconds,
+            quality_score: 0.8,
+            zk_proof_hash: "a".repeat(64),
+            song_duration_seconds,
+            completion_percentage: 90.0,
+        }
+    }
+
+    #[test]
+    fn normal_session_passes() {
+        let assessment = ListenFraudScorer::default().screen(&command(170, 180), Some(200)).unwrap();
+        assert_eq!(assessment.risk_score, 0.0);
+        assert!(assessment.reason_code.is_none());
+    }
+
+    #[test]
+    fn listening_longer_than_elapsed_time_is_fraud() {
+        let err = ListenFraudScorer::default().screen(&command(180, 180), Some(30)).unwrap_err();
+        match err {
+            AppError::FraudDetected(details) => {
+                assert_eq!(details.reason_code, FraudReasonCode::SuspiciousListenPattern);
+                assert_eq!(details.risk_score, Some(0.95));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn listening_longer_than_song_is_fraud() {
+        assert!(matches!(
+            ListenFraudScorer::default().screen(&command(400, 180), None),
+            Err(AppError::FraudDetected(_))
+        ));
+    }
+
+    #[test]
+    fn perfect_play_alone_stays_below_threshold() {
+        let mut perfect = command(180, 180);
+        perfect.quality_score = 1.0;
+        perfect.completion_percentage = 100.0;
+
+        let assessment = ListenFraudScorer::default().screen(&perfect, None).unwrap();
+        assert_eq!(assessment.reason_code, Some(FraudReasonCode::BotBehavior));
+        assert!(matches!(
+            ListenFraudScorer::new(0.4).screen(&perfect, None),
+            Err(AppError::FraudDetected(_))
+        ));
+    }
+}
@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 use crate::bounded_contexts::listen_reward::{
     domain::{
         entities::ListenSession,
-        value_objects::RewardAmount,
+        value_objects::{DeviceFingerprint, RewardAmount},
         aggregates::RewardPool,
     },
     infrastructure::{
@@ -26,7 +26,10 @@ use crate::bounded_contexts::listen_reward::{
     application::use_cases::{
         StartListenSessionUseCase,
     },
+    application::fraud_screening::ListenFraudScorer,
+    application::fraud_detection::FraudDetectionService,
 };
+use crate::bounded_contexts::orchestrator::{DomainEvent as IntegrationEvent, EventBus};
 use crate::shared::domain::errors::AppError;
 
 // Application Service Commands
@@ -36,7 +39,7 @@ pub struct StartListeningCommand {
     pub song_id: Uuid,
     pub artist_id: Uuid,
     pub user_tier: String,
-    pub device_fingerprint: Option<String>,
+    pub device_fingerprint: Option<DeviceFingerprint>,
     pub geo_location: Option<String>,
 }
 
@@ -202,6 +205,9 @@ pub struct ListenRewardApplicationService {
     distribution_repository: Arc<dyn RewardDistributionRepository>,
     analytics_repository: Arc<dyn RewardAnalyticsRepository>,
     event_publisher: Arc<dyn EventPublisher>,
+    fraud_scorer: ListenFraudScorer,
+    fraud_detection: Option<Arc<FraudDetectionService>>,
+    integration_event_bus: Option<Arc<dyn EventBus>>,
     // TODO: Add back when ZkProofVerificationService is implemented
     // zk_verification_service: Arc<dyn ZkProofVerificationService>,
 }
@@ -227,6 +233,9 @@ impl ListenRewardApplicationService {
             distribution_repository,
             analytics_repository,
             event_publisher,
+            fraud_scorer: ListenFraudScorer::default(),
+            fraud_detection: None,
+            integration_event_bus: None,
             // TODO: Add back when ZkProofVerificationService is implemented
             // zk_verification_service,
         }
@@ -260,11 +269,31 @@ impl ListenRewardApplicationService {
             distribution_repository,
             analytics_repository,
             event_publisher,
+            fraud_scorer: ListenFraudScorer::default(),
+            fraud_detection: None,
+            integration_event_bus: None,
             // TODO: Add back when ZkProofVerificationService is implemented
             // zk_verification_service,
         }
     }
 
+    pub fn with_fraud_scorer(mut self, fraud_scorer: ListenFraudScorer) -> Self {
+        self.fraud_scorer = fraud_scorer;
+        self
+    }
+
+    /// Device fingerprint checks run when a session starts with a fingerprint
+    pub fn with_fraud_detection(mut self, fraud_detection: Arc<FraudDetectionService>) -> Self {
+        self.fraud_detection = Some(fraud_detection);
+        self
+    }
+
+    /// Bus used to notify other contexts (notifications) about rejected sessions
+    pub fn with_integration_event_bus(mut self, event_bus: Arc<dyn EventBus>) -> Self {
+        self.integration_event_bus = Some(event_bus);
+        self
+    }
+
     /// Start a new listening session
     pub async fn start_listening_session(
         &self,
@@ -273,6 +302,16 @@ impl ListenRewardApplicationService {
         // Validate rate limits
         self.validate_user_rate_limits(command.user_id).await?;
 
+        // Device fraud detection
+        if let (Some(fraud_detection), Some(fingerprint)) = (&self.fraud_detection, &command.device_fingerprint) {
+            if let Err(err) = fraud_detection.screen(fingerprint, command.user_id).await {
+                if let AppError::FraudDetected(details) = &err {
+                    self.publish_fraud_detected(command.user_id, Some(command.user_id), details).await;
+                }
+                return Err(err);
+            }
+        }
+
         // Parse reward tier
         // TODO: Add back when RewardTier is implemented
         // let reward_tier = RewardTier::from_string(&command.user_tier)
@@ -437,6 +476,14 @@ impl ListenRewardApplicationService {
             completion_percentage: 100.0, // TODO: Calculate actual percentage
         };
 
+        // Fraud screening (elapsed time unknown until sessions are loaded from the repository)
+        if let Err(err) = self.fraud_scorer.screen(&command, None) {
+            if let AppError::FraudDetected(details) = &err {
+                self.publish_fraud_detected(command.session_id, None, details).await;
+            }
+            return Err(err);
+        }
+
         // TODO: Add back when complete_session_use_case is implemented
         // Ejecutar caso de uso (síncrono) pasando la copia mutable
         // let (_updated_session, response, _event) = self
@@ -575,6 +622,26 @@ impl ListenRewardApplicationService {
     }
 
     // Private helper methods
+    async fn publish_fraud_detected(
+        &self,
+        subject_id: Uuid,
+        user_id: Option<Uuid>,
+        details: &crate::shared::domain::errors::FraudDetails,
+    ) {
+        let Some(event_bus) = &self.integration_event_bus else { return };
+        let event = IntegrationEvent::FraudDetected {
+            context: "listen_reward".to_string(),
+            subject_id,
+            user_id,
+            reason_code: details.reason_code,
+            risk_score: details.risk_score.unwrap_or_default(),
+            occurred_at: Utc::now(),
+        };
+        if let Err(e) = event_bus.publish(event).await {
+            tracing::error!("Failed to publish FraudDetected event: {:?}", e);
+        }
+    }
+
     async fn validate_user_rate_limits(&self, user_id: Uuid) -> Result<(), AppError> {
         let today_start = chrono::Utc::now().date_naive().and_hms_opt(0, 0, 0).unwrap().and_utc();
         let today_end = today_start + chrono::Duration::days(1);
@@ -1,5 +1,8 @@
 pub mod use_cases;
 pub mod listen_reward_application_service;
+pub mod fraud_screening;
+pub mod fraud_detection;
+pub mod session_cleanup;
 
 pub use use_cases::*;
 pub use listen_reward_application_service::{
@@ -7,4 +10,7 @@ pub use listen_reward_application_service::{
     ProcessRewardsCommand, GetUserListeningHistoryQuery, GetArtistAnalyticsQuery,
     StartListeningResponse, CompleteListeningResponse, ProcessRewardsResponse, 
     UserListeningHistory, ArtistAnalytics,
-}; 
\ No newline at end of file
+};
+pub use fraud_screening::{ListenFraudScorer, ListenFraudAssessment, DEFAULT_LISTEN_FRAUD_THRESHOLD};
+pub use fraud_detection::{FraudDetectionConfig, FraudDetectionService, DEFAULT_DEVICE_FRAUD_THRESHOLD};
+pub use session_cleanup::{ExpiredSessionCleanupJob, fail_expired_sessions, DEFAULT_SESSION_TIMEOUT_MINUTES};
@@ -0,0 +1,136 @@
+// Expired Listen Session Cleanup
+//
+// Sessions that stay `Active` longer than the timeout were abandoned by the
+// client (closed app, lost connection). They are moved to `Failed` so they no
+// longer count as open sessions for the anti-fraud checks.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::bounded_contexts::listen_reward::infrastructure::repositories::repository_traits::ListenSessionRepository;
+
+/// Sessions older than this are considered abandoned
+pub const DEFAULT_SESSION_TIMEOUT_MINUTES: i64 = 60;
+
+/// Fails every expired session and returns how many were transitioned.
+/// Sessions modified concurrently (version conflict) are skipped and retried
+/// on the next run.
+pub async fn fail_expired_sessions(
+    repository: &dyn ListenSessionRepository,
+    session_timeout_minutes: i64,
+) -> Result<usize, String> {
+    let expired = repository.find_expired_sessions(session_timeout_minutes).await?;
+
+    let mut failed = 0;
+    for mut session in expired {
+        let session_id = session.id().value();
+        if let Err(e) = session.expire() {
+            tracing::warn!("Skipping expired session {}: {}", session_id, e);
+            continue;
+        }
+        match repository.save(&session).await {
+            Ok(()) => failed += 1,
+            Err(e) => tracing::warn!("Could not expire session {}: {}", session_id, e),
+        }
+    }
+
+    Ok(failed)
+}
+
+/// Worker que marca como fallidas las sesiones activas caducadas
+pub struct ExpiredSessionCleanupJob {
+    repository: Arc<dyn ListenSessionRepository>,
+    session_timeout_minutes: i64,
+    interval: Duration,
+}
+
+impl ExpiredSessionCleanupJob {
+    pub fn new(repository: Arc<dyn ListenSessionRepository>, session_timeout_minutes: i64, interval: Duration) -> Self {
+        Self {
+            repository,
+            session_timeout_minutes,
+            interval,
+        }
+    }
+
+    pub fn start(&self) -> tokio::task::JoinHandle<()> {
+        let repository = Arc::clone(&self.repository);
+        let session_timeout_minutes = self.session_timeout_minutes;
+        let interval = self.interval;
+
+        tokio::spawn(async move {
+            tracing::info!("🚀 Expired listen session cleanup job started");
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match fail_expired_sessions(repository.as_ref(), session_timeout_minutes).await {
+                    Ok(count) if count > 0 => tracing::info!("✅ Marked {} expired listen sessions as failed", count),
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Listen session cleanup failed: {}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use std::sync::Mutex;
+    use uuid::Uuid;
+    use vibestream_types::{ArtistContract, SongContract};
+
+    use crate::bounded_contexts::listen_reward::domain::entities::{ListenSession, SessionStatus};
+    use crate::bounded_contexts::listen_reward::domain::value_objects::{ListenSessionId, RewardTier};
+    use crate::bounded_contexts::listen_reward::infrastructure::repositories::RepositoryResult;
+
+    struct InMemorySessions {
+        expired: Vec<ListenSession>,
+        conflicting: Option<Uuid>,
+        saved: Mutex<Vec<ListenSession>>,
+    }
+
+    #[async_trait]
+    impl ListenSessionRepository for InMemorySessions {
+        async fn save(&self, session: &ListenSession) -> RepositoryResult<()> {
+            if self.conflicting == Some(session.id().value()) {
+                return Err("Concurrency conflict: Session was modified concurrently".to_string());
+            }
+            self.saved.lock().unwrap().push(session.clone());
+            Ok(())
+        }
+        async fn update(&self, session: &ListenSession, _expected_version: i32) -> RepositoryResult<()> { self.save(session).await }
+        async fn find_by_id(&self, _id: &ListenSessionId) -> RepositoryResult<Option<ListenSession>> { Ok(None) }
+        async fn delete(&self, _id: &ListenSessionId) -> RepositoryResult<()> { Ok(()) }
+        async fn exists(&self, _id: &ListenSessionId) -> RepositoryResult<bool> { Ok(false) }
+        async fn find_active_sessions_for_user(&self, _user_id: Uuid) -> RepositoryResult<Vec<ListenSession>> { Ok(vec![]) }
+        async fn find_by_user_and_status(&self, _user_id: Uuid, _status: &SessionStatus) -> RepositoryResult<Vec<ListenSession>> { Ok(vec![]) }
+        async fn find_expired_sessions(&self, _session_timeout_minutes: i64) -> RepositoryResult<Vec<ListenSession>> { Ok(self.expired.clone()) }
+        async fn count_user_sessions_in_period(&self, _user_id: Uuid, _start: DateTime<Utc>, _end: DateTime<Utc>) -> RepositoryResult<i64> { Ok(0) }
+    }
+
+    fn active_session() -> ListenSession {
+        let song = SongContract::new(Uuid::new_v4(), "Song".to_string(), Uuid::new_v4(), "Artist".to_string());
+        let artist = ArtistContract::new(song.artist_id, Uuid::new_v4(), "Artist".to_string());
+        ListenSession::new(Uuid::new_v4(), song, artist, RewardTier::Basic).0.with_version(1)
+    }
+
+    #[tokio::test]
+    async fn expired_sessions_are_failed_and_conflicts_skipped() {
+        let (first, second) = (active_session(), active_session());
+        let repository = InMemorySessions {
+            conflicting: Some(second.id().value()),
+            expired: vec![first.clone(), second],
+            saved: Mutex::new(Vec::new()),
+        };
+
+        let failed = fail_expired_sessions(&repository, DEFAULT_SESSION_TIMEOUT_MINUTES).await.unwrap();
+
+        assert_eq!(failed, 1);
+        let saved = repository.saved.lock().unwrap();
+        assert_eq!(saved[0].id().value(), first.id().value());
+        assert_eq!(saved[0].status(), &SessionStatus::Failed);
+    }
+}
@@ -5,6 +5,7 @@ use crate::bounded_contexts::listen_reward::domain::entities::ListenSession;
 use crate::bounded_contexts::listen_reward::domain::value_objects::{
     ListenDuration, QualityScore, ZkProofHash
 };
+use crate::bounded_contexts::music::domain::value_objects::{SongDuration, DEFAULT_STREAM_TOLERANCE_PCT};
 use crate::shared::domain::events::DomainEvent;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,11 +27,21 @@ pub struct CompleteListenSessionResponse {
     pub completed_at: String,
 }
 
-pub struct CompleteListenSessionUseCase;
+pub struct CompleteListenSessionUseCase {
+    stream_tolerance_pct: f64,
+}
 
 impl CompleteListenSessionUseCase {
     pub fn new() -> Self {
-        Self
+        Self {
+            stream_tolerance_pct: DEFAULT_STREAM_TOLERANCE_PCT,
+        }
+    }
+
+    /// Margin over the wall-clock time since the session started (0.05 = 5%)
+    pub fn with_stream_tolerance(mut self, tolerance_pct: f64) -> Self {
+        self.stream_tolerance_pct = tolerance_pct;
+        self
     }
 
     pub fn execute(
@@ -41,6 +52,15 @@ impl CompleteListenSessionUseCase {
         // Validate command
         self.validate_command(&command)?;
 
+        // Anti-skip: no more listening than the time elapsed since the session started
+        let wall_clock_seconds = (chrono::Utc::now() - session.started_at()).num_seconds().max(0) as u32;
+        SongDuration::validate_stream_progress(
+            command.listen_duration_seconds,
+            wall_clock_seconds,
+            self.stream_tolerance_pct,
+        )
+        .map_err(|e| e.to_string())?;
+
         // Parse value objects
         let listen_duration = ListenDuration::new(command.listen_duration_seconds)
             .map_err(|e| format!("Invalid listen duration: {}", e))?;
@@ -107,10 +127,16 @@ impl CompleteListenSessionUseCase {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bounded_contexts::listen_reward::domain::RewardTier;
+    use crate::bounded_contexts::listen_reward::domain::{ListenSessionId, RewardTier};
+    use crate::bounded_contexts::listen_reward::domain::entities::SessionStatus;
     use vibestream_types::{SongContract, ArtistContract};
 
+    /// Active session started five minutes ago
     fn create_test_session() -> ListenSession {
+        create_session_started_secs_ago(300)
+    }
+
+    fn create_session_started_secs_ago(seconds: i64) -> ListenSession {
         let song_contract = SongContract {
             id: Uuid::new_v4(),
             title: "Test Song".to_string(),
@@ -137,13 +163,22 @@ mod tests {
             created_at: chrono::Utc::now(),
         };
         
-        let (session, _) = ListenSession::new(
+        ListenSession::from_parts(
+            ListenSessionId::new(),
             Uuid::new_v4(),
             song_contract,
             artist_contract,
             RewardTier::Basic,
-        );
-        session
+            SessionStatus::Active,
+            None,
+            None,
+            None,
+            None,
+            None,
+            chrono::Utc::now() - chrono::Duration::seconds(seconds),
+            None,
+            None,
+        )
     }
 
     fn create_valid_command() -> CompleteListenSessionCommand {
@@ -241,4 +276,18 @@ mod tests {
         let (_, response, _) = result.unwrap();
         assert!(response.is_eligible_for_reward);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_complete_session_claiming_more_than_wall_clock_fails() {
+        let use_case = CompleteListenSessionUseCase::new();
+        let mut command = create_valid_command();
+        command.listen_duration_seconds = 180;
+
+        // 180s claimed 50s after the session started
+        let result = use_case.execute(create_session_started_secs_ago(50), command.clone());
+        assert!(result.unwrap_err().contains("Claimed 180s of listening but only 50s have passed"));
+
+        // Within the 5% buffering margin
+        assert!(use_case.execute(create_session_started_secs_ago(175), command).is_ok());
+    }
+}
@@ -186,6 +186,27 @@ impl Default for DistributionLimits {
 
 impl RewardDistribution {
     pub fn new(reward_pool: RewardPool) -> Self {
+        Self::for_recipient(
+            reward_pool,
+            ListenSessionId::new(), // placeholder
+            Uuid::new_v4(), // placeholder user_id
+            Uuid::new_v4(), // placeholder artist_id
+            RewardAmount::zero(),
+            RewardAmount::zero(),
+            RewardAmount::zero(),
+        )
+    }
+
+    /// Distribution of a pool to a single recipient, with the shares it pays out
+    pub fn for_recipient(
+        reward_pool: RewardPool,
+        session_id: ListenSessionId,
+        user_id: Uuid,
+        artist_id: Uuid,
+        user_share: RewardAmount,
+        artist_share: RewardAmount,
+        platform_fee: RewardAmount,
+    ) -> Self {
         let mut aggregate = Self {
             id: Uuid::new_v4(),
             reward_pool,
@@ -199,13 +220,13 @@ impl RewardDistribution {
 
         aggregate.apply_event(RewardDistributionCreated::new(
             aggregate.id,
-            ListenSessionId::new(), // placeholder
-            Uuid::new_v4(), // placeholder user_id
-            Uuid::new_v4(), // placeholder artist_id
+            session_id,
+            user_id,
+            artist_id,
             aggregate.reward_pool.total_tokens().clone(),
-            RewardAmount::zero(),
-            RewardAmount::zero(),
-            RewardAmount::zero(),
+            user_share,
+            artist_share,
+            platform_fee,
             aggregate.created_at,
         ));
 
@@ -274,6 +295,14 @@ impl RewardDistribution {
         &self.uncommitted_events
     }
 
+    /// The uncommitted `RewardDistributionCreated` event, if the aggregate was just created
+    pub fn created_event(&self) -> Option<RewardDistributionCreated> {
+        self.uncommitted_events
+            .iter()
+            .find(|event| event.event_type() == "RewardDistributionCreated")
+            .and_then(|event| serde_json::from_value(event.event_data()).ok())
+    }
+
     pub fn queue_reward_distribution(
         &mut self,
         session: &ListenSession,
@@ -628,4 +657,27 @@ mod tests {
         let pending_royalties = distribution.get_artist_pending_royalties(&session.artist_id());
         assert!(pending_royalties.tokens() > 0.0);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_created_event_carries_recipient_shares() {
+        let user_id = Uuid::new_v4();
+        let artist_id = Uuid::new_v4();
+        let distribution = RewardDistribution::for_recipient(
+            create_test_pool(),
+            ListenSessionId::new(),
+            user_id,
+            artist_id,
+            RewardAmount::new(7.0).unwrap(),
+            RewardAmount::new(2.5).unwrap(),
+            RewardAmount::new(0.5).unwrap(),
+        );
+
+        let created = distribution.created_event().expect("Creation event");
+        assert_eq!(created.distribution_id, distribution.id());
+        assert_eq!(created.user_id, user_id);
+        assert_eq!(created.artist_id, artist_id);
+        assert_eq!(created.user_share.tokens(), 7.0);
+        assert_eq!(created.artist_share.tokens(), 2.5);
+        assert_eq!(created.platform_fee.tokens(), 0.5);
+    }
+} 
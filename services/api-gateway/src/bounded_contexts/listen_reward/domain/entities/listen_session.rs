@@ -38,6 +38,9 @@ pub struct ListenSession {
     started_at: DateTime<Utc>,
     completed_at: Option<DateTime<Utc>>,
     verified_at: Option<DateTime<Utc>>,
+    // Versión para concurrencia optimista; 0 = aún no persistida
+    #[serde(default)]
+    version: i32,
 }
 
 impl ListenSession {
@@ -65,6 +68,7 @@ impl ListenSession {
             started_at,
             completed_at: None,
             verified_at: None,
+            version: 0,
         };
 
         let event = Box::new(ListenSessionStarted::new(
@@ -158,7 +162,13 @@ impl ListenSession {
     }
 
     pub fn version(&self) -> i32 {
-        0 // Versión por defecto
+        self.version
+    }
+
+    /// Fija la versión leída de la base de datos al rehidratar la sesión
+    pub fn with_version(mut self, version: i32) -> Self {
+        self.version = version;
+        self
     }
 
     // Business logic methods
@@ -318,6 +328,16 @@ impl ListenSession {
         }
     }
 
+    /// Una sesión activa que superó el tiempo máximo se da por fallida
+    pub fn expire(&mut self) -> Result<(), String> {
+        if self.status != SessionStatus::Active {
+            return Err("Only active sessions can expire".to_string());
+        }
+
+        self.status = SessionStatus::Failed;
+        Ok(())
+    }
+
     pub fn mark_rewarded(&mut self) -> Result<(), String> {
         if self.status != SessionStatus::Verified {
             return Err("Session must be verified before marking as rewarded".to_string());
@@ -387,6 +407,7 @@ impl ListenSession {
             started_at,
             completed_at,
             verified_at,
+            version: 0,
         }
     }
 }
@@ -554,6 +575,16 @@ mod tests {
         assert_eq!(session.status, SessionStatus::Failed);
     }
 
+    #[test]
+    fn test_expire_only_active_sessions() {
+        let mut session = create_test_session();
+        assert_eq!(session.version(), 0);
+
+        assert!(session.expire().is_ok());
+        assert_eq!(session.status, SessionStatus::Failed);
+        assert!(session.expire().is_err());
+    }
+
     #[test]
     fn test_reward_calculation_with_tiers() {
         let mut basic_session = create_test_session();
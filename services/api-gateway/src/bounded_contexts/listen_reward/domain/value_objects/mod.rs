@@ -159,6 +159,103 @@ impl ZkProofHash {
     }
 }
 
+// Device fingerprint sent when a session starts. The client only sends hashes,
+// never the raw user agent or IP.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DeviceFingerprint {
+    pub user_agent_hash: String,
+    pub ip_hash: String,
+    pub device_id: Option<String>,
+}
+
+impl DeviceFingerprint {
+    pub fn new(user_agent_hash: String, ip_hash: String, device_id: Option<String>) -> Result<Self, String> {
+        let fingerprint = Self { user_agent_hash, ip_hash, device_id };
+        fingerprint.validate()?;
+        Ok(fingerprint)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.user_agent_hash.trim().is_empty() {
+            return Err("User agent hash cannot be empty".to_string());
+        }
+        if self.ip_hash.trim().is_empty() {
+            return Err("IP hash cannot be empty".to_string());
+        }
+        if matches!(&self.device_id, Some(id) if id.trim().is_empty()) {
+            return Err("Device id cannot be empty".to_string());
+        }
+        Ok(())
+    }
+
+    /// Key that identifies the device across users: the device id when the
+    /// client has one, otherwise the user agent + IP pair
+    pub fn device_key(&self) -> String {
+        match &self.device_id {
+            Some(device_id) => format!("device:{}", device_id),
+            None => format!("ua:{}:ip:{}", self.user_agent_hash, self.ip_hash),
+        }
+    }
+}
+
+// Señales de fraude al iniciar una sesión de escucha
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FraudFlag {
+    TooManySessionsFromDevice,
+    BotLikePattern,
+    VpnDetected,
+    DuplicateDeviceAcrossUsers,
+}
+
+impl FraudFlag {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FraudFlag::TooManySessionsFromDevice => "too_many_sessions_from_device",
+            FraudFlag::BotLikePattern => "bot_like_pattern",
+            FraudFlag::VpnDetected => "vpn_detected",
+            FraudFlag::DuplicateDeviceAcrossUsers => "duplicate_device_across_users",
+        }
+    }
+}
+
+impl fmt::Display for FraudFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for FraudFlag {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "too_many_sessions_from_device" => Ok(FraudFlag::TooManySessionsFromDevice),
+            "bot_like_pattern" => Ok(FraudFlag::BotLikePattern),
+            "vpn_detected" => Ok(FraudFlag::VpnDetected),
+            "duplicate_device_across_users" => Ok(FraudFlag::DuplicateDeviceAcrossUsers),
+            other => Err(format!("Unknown fraud flag: {}", other)),
+        }
+    }
+}
+
+// Riesgo de una sesión en [0, 1] y las señales que lo explican
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FraudScore {
+    pub score: f64,
+    pub flags: Vec<FraudFlag>,
+}
+
+impl FraudScore {
+    pub fn clean() -> Self {
+        Self { score: 0.0, flags: Vec::new() }
+    }
+
+    pub fn has_flag(&self, flag: FraudFlag) -> bool {
+        self.flags.contains(&flag)
+    }
+}
+
 // Reward Pool ID
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct RewardPoolId(Uuid);
@@ -415,6 +512,16 @@ mod tests {
         assert_eq!(RewardTier::Platinum.multiplier(), 3.0);
     }
 
+    #[test]
+    fn test_device_fingerprint_key() {
+        let with_id = DeviceFingerprint::new("ua".into(), "ip".into(), Some("abc".into())).unwrap();
+        let without_id = DeviceFingerprint::new("ua".into(), "ip".into(), None).unwrap();
+        assert_eq!(with_id.device_key(), "device:abc");
+        assert_eq!(without_id.device_key(), "ua:ua:ip:ip");
+        assert!(DeviceFingerprint::new("".into(), "ip".into(), None).is_err());
+        assert!(DeviceFingerprint::new("ua".into(), "ip".into(), Some(" ".into())).is_err());
+    }
+
     #[test]
     fn test_validation_period() {
         let period = ValidationPeriod::daily();
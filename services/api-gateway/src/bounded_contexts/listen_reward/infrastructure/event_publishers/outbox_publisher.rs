@@ -0,0 +1,168 @@
+// Outbox -> EventPublisher
+//
+// El relay del outbox entrega los eventos del contexto al publicador
+// configurado (Postgres o Redis Stream). El evento se reconstruye con el id con
+// el que se guardó, de modo que un reenvío tras una caída del relay llega con
+// el mismo id y el consumidor puede descartarlo.
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use crate::shared::domain::events::{DomainEvent, EventMetadata};
+use crate::shared::infrastructure::outbox::{OutboxMessage, OutboxPublisher};
+use super::EventPublisher;
+
+/// Evento de dominio leído del outbox
+#[derive(Debug, Clone)]
+pub struct RelayedDomainEvent {
+    metadata: EventMetadata,
+    data: serde_json::Value,
+}
+
+impl RelayedDomainEvent {
+    pub fn from_message(message: &OutboxMessage) -> Self {
+        Self {
+            metadata: EventMetadata {
+                event_id: message.id,
+                event_type: message.event_type.clone(),
+                aggregate_id: message.aggregate_id,
+                aggregate_type: message.aggregate_type.clone(),
+                occurred_at: message.occurred_at,
+                ..EventMetadata::new()
+            },
+            data: message.payload.clone(),
+        }
+    }
+}
+
+impl DomainEvent for RelayedDomainEvent {
+    fn metadata(&self) -> &EventMetadata {
+        &self.metadata
+    }
+
+    fn event_type(&self) -> &str {
+        &self.metadata.event_type
+    }
+
+    fn aggregate_id(&self) -> Uuid {
+        self.metadata.aggregate_id
+    }
+
+    fn aggregate_type(&self) -> &str {
+        &self.metadata.aggregate_type
+    }
+
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.metadata.occurred_at
+    }
+
+    fn event_data(&self) -> serde_json::Value {
+        self.data.clone()
+    }
+}
+
+/// Publica los eventos del outbox con un `EventPublisher` del contexto
+pub struct EventPublisherOutboxAdapter {
+    publisher: Arc<dyn EventPublisher>,
+}
+
+impl EventPublisherOutboxAdapter {
+    pub fn new(publisher: Arc<dyn EventPublisher>) -> Self {
+        Self { publisher }
+    }
+}
+
+#[async_trait]
+impl OutboxPublisher for EventPublisherOutboxAdapter {
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), AppError> {
+        let result = self
+            .publisher
+            .publish_event(Box::new(RelayedDomainEvent::from_message(message)))
+            .await
+            .map_err(AppError::ExternalServiceError)?;
+
+        if result.success {
+            return Ok(());
+        }
+        Err(AppError::ExternalServiceError(
+            result.error_message.unwrap_or_else(|| format!("Event {} was not published", message.id)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::EventPublishResult;
+    use std::sync::Mutex;
+
+    /// Guarda el id de cada evento; falla mientras `failing` sea true
+    #[derive(Default)]
+    struct RecordingPublisher {
+        received: Mutex<Vec<(Uuid, String, serde_json::Value)>>,
+        failing: bool,
+    }
+
+    #[async_trait]
+    impl EventPublisher for RecordingPublisher {
+        async fn publish_event(&self, event: Box<dyn DomainEvent>) -> Result<EventPublishResult, String> {
+            let event_id = event.metadata().event_id;
+            if self.failing {
+                return Ok(EventPublishResult::failure(event_id, "stream unavailable".to_string()));
+            }
+            self.received.lock().unwrap().push((event_id, event.event_type().to_string(), event.event_data()));
+            Ok(EventPublishResult::success(event_id))
+        }
+
+        async fn publish_events(&self, events: Vec<Box<dyn DomainEvent>>) -> Vec<Result<EventPublishResult, String>> {
+            let mut results = Vec::new();
+            for event in events {
+                results.push(self.publish_event(event).await);
+            }
+            results
+        }
+
+        async fn is_healthy(&self) -> bool {
+            true
+        }
+    }
+
+    fn message() -> OutboxMessage {
+        OutboxMessage {
+            id: Uuid::new_v4(),
+            context: "listen_reward".to_string(),
+            event_type: "RewardDistributed".to_string(),
+            aggregate_type: "RewardDistribution".to_string(),
+            aggregate_id: Uuid::new_v4(),
+            payload: serde_json::json!({ "amount": 12.5 }),
+            occurred_at: Utc::now(),
+            attempts: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn relayed_event_keeps_the_outbox_id_and_payload() {
+        let publisher = Arc::new(RecordingPublisher::default());
+        let adapter = EventPublisherOutboxAdapter::new(publisher.clone());
+        let message = message();
+
+        adapter.publish(&message).await.unwrap();
+        // Un reenvío llega con el mismo id
+        adapter.publish(&message).await.unwrap();
+
+        let received = publisher.received.lock().unwrap();
+        assert_eq!(received.len(), 2);
+        assert!(received.iter().all(|(id, event_type, data)| {
+            *id == message.id && event_type == "RewardDistributed" && *data == message.payload
+        }));
+    }
+
+    #[tokio::test]
+    async fn unsuccessful_publish_is_an_error() {
+        let adapter = EventPublisherOutboxAdapter::new(Arc::new(RecordingPublisher { failing: true, ..Default::default() }));
+
+        assert!(matches!(adapter.publish(&message()).await, Err(AppError::ExternalServiceError(_))));
+    }
+}
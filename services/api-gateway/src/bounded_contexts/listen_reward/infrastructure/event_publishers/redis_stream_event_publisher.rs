@@ -1,15 +1,20 @@
 use async_trait::async_trait;
 use redis::{Client, aio::Connection};
 use crate::shared::domain::events::DomainEvent;
+use crate::shared::domain::integration_events::MapperRegistry;
 use super::{EventPublisher, EventPublishResult};
 
 /// Publicador de eventos usando Redis Streams (`XADD`).
 /// Cada evento se guarda como un entry con los campos:
 ///  - metadata: JSON con id, type, aggregate, timestamp…
 ///  - data:     JSON con el payload del evento
+///
+/// Los eventos con traducción en el `MapperRegistry` salen como evento de
+/// integración; el resto se publica tal cual.
 pub struct RedisStreamEventPublisher {
     client: Client,
     stream_name: String,
+    mappers: MapperRegistry,
 }
 
 impl RedisStreamEventPublisher {
@@ -22,8 +27,14 @@ impl RedisStreamEventPublisher {
         Ok(Self {
             client,
             stream_name,
+            mappers: MapperRegistry::with_default_mappings(),
         })
     }
+
+    pub fn with_mappers(mut self, mappers: MapperRegistry) -> Self {
+        self.mappers = mappers;
+        self
+    }
 }
 
 #[async_trait]
@@ -35,9 +46,12 @@ impl EventPublisher for RedisStreamEventPublisher {
             .await
             .map_err(|e| e.to_string())?;
 
-        let event_id = event.aggregate_id();
-        let event_type = event.event_type();
-        let event_data = event.event_data();
+        // Id del evento de dominio: los consumidores descartan reenvíos por él
+        let event_id = event.metadata().event_id;
+        let (event_type, event_data) = match self.mappers.map(event.as_ref()) {
+            Some(integration_event) => (integration_event.event_type().to_string(), integration_event.event_data()),
+            None => (event.event_type().to_string(), event.event_data()),
+        };
         
         // Publish to Redis stream
         let _: () = redis::cmd("XADD")
@@ -46,7 +60,7 @@ impl EventPublisher for RedisStreamEventPublisher {
             .arg("id")
             .arg(&event_id.to_string())
             .arg("type")
-            .arg(event_type)
+            .arg(&event_type)
             .arg("data")
             .arg(event_data.to_string())
             .query_async(&mut conn)
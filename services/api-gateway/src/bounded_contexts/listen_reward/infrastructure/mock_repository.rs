@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use crate::bounded_contexts::listen_reward::{
-    domain::entities::listen_session::ListenSession,
+    domain::entities::listen_session::{ListenSession, SessionStatus},
     domain::value_objects::ListenSessionId,
     infrastructure::repositories::repository_traits::ListenSessionRepository,
     infrastructure::repositories::{RepositoryResult, Pagination, ListenSessionFilter},
@@ -37,6 +37,14 @@ impl ListenSessionRepository for MockListenSessionRepository {
     async fn find_active_sessions_for_user(&self, _user_id: Uuid) -> RepositoryResult<Vec<ListenSession>> {
         Ok(vec![])
     }
+
+    async fn find_by_user_and_status(&self, _user_id: Uuid, _status: &SessionStatus) -> RepositoryResult<Vec<ListenSession>> {
+        Ok(vec![])
+    }
+
+    async fn find_expired_sessions(&self, _session_timeout_minutes: i64) -> RepositoryResult<Vec<ListenSession>> {
+        Ok(vec![])
+    }
     
     async fn count_user_sessions_in_period(
         &self,
@@ -10,18 +10,20 @@ pub mod repositories;
 pub mod event_publishers;
 pub mod integration;
 pub mod mock_repository;
+pub mod user_deletion_listener;
 
 pub use repositories::{
     PostgresListenSessionRepository, PostgresRewardDistributionRepository,
     PostgresRewardAnalyticsRepository,
 };
-pub use event_publishers::{InMemoryEventPublisher, EventPublisher};
+pub use event_publishers::{InMemoryEventPublisher, EventPublisher, EventPublisherOutboxAdapter};
 pub use integration::{
     ListenRewardIntegration, ListenRewardFractionalOwnershipIntegration,
     // TODO: Add back when fan ventures is fully integrated
     // FractionalOwnershipIntegrationHandler, RevenueDistributionTriggered,
 };
 pub use mock_repository::*;
+pub use user_deletion_listener::ListenSessionUserDeletionListener;
 
 // Health check utilities
 use serde::{Deserialize, Serialize};
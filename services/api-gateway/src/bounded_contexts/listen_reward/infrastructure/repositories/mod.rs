@@ -6,11 +6,13 @@
 pub mod postgres_listen_session_repository;
 pub mod postgres_reward_distribution_repository;
 pub mod postgres_analytics_repository;
+pub mod postgres_fraud_score_repository;
 pub mod repository_traits;
 
 pub use postgres_listen_session_repository::PostgresListenSessionRepository;
-pub use postgres_reward_distribution_repository::PostgresRewardDistributionRepository;
+pub use postgres_reward_distribution_repository::{PostgresRewardDistributionRepository, LISTEN_REWARD_OUTBOX_CONTEXT};
 pub use postgres_analytics_repository::PostgresRewardAnalyticsRepository;
+pub use postgres_fraud_score_repository::{FraudScoreRecord, InMemoryFraudScoreRepository, PostgresFraudScoreRepository};
 pub use repository_traits::*;
 
 // Common repository utilities
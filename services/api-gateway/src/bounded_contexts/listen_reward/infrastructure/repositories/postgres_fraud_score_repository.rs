@@ -0,0 +1,251 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Row};
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use crate::bounded_contexts::listen_reward::domain::value_objects::{DeviceFingerprint, FraudFlag, FraudScore};
+
+use super::{FraudScoreRepository, RepositoryResult};
+
+/// Puntuación de fraude guardada para revisión
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct FraudScoreRecord {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub device_key: String,
+    pub fingerprint: DeviceFingerprint,
+    pub sessions_today: u32,
+    pub score: f64,
+    pub flags: Vec<FraudFlag>,
+    /// La sesión no llegó a iniciarse
+    pub rejected: bool,
+    pub created_at: DateTime<Utc>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub reviewed_by: Option<Uuid>,
+    pub confirmed_fraud: Option<bool>,
+}
+
+impl FraudScoreRecord {
+    pub fn new(
+        user_id: Uuid,
+        fingerprint: &DeviceFingerprint,
+        sessions_today: u32,
+        score: &FraudScore,
+        rejected: bool,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            device_key: fingerprint.device_key(),
+            fingerprint: fingerprint.clone(),
+            sessions_today,
+            score: score.score,
+            flags: score.flags.clone(),
+            rejected,
+            created_at: Utc::now(),
+            reviewed_at: None,
+            reviewed_by: None,
+            confirmed_fraud: None,
+        }
+    }
+}
+
+pub struct PostgresFraudScoreRepository {
+    pool: PgPool,
+}
+
+impl PostgresFraudScoreRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl FraudScoreRepository for PostgresFraudScoreRepository {
+    async fn record(&self, record: &FraudScoreRecord) -> RepositoryResult<()> {
+        let flags: Vec<String> = record.flags.iter().map(|flag| flag.as_str().to_string()).collect();
+        sqlx::query(
+            r#"INSERT INTO listen_fraud_scores (
+                   id, user_id, device_key, user_agent_hash, ip_hash, device_id,
+                   sessions_today, score, flags, rejected, created_at
+               ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)"#,
+        )
+        .bind(record.id)
+        .bind(record.user_id)
+        .bind(&record.device_key)
+        .bind(&record.fingerprint.user_agent_hash)
+        .bind(&record.fingerprint.ip_hash)
+        .bind(&record.fingerprint.device_id)
+        .bind(record.sessions_today as i32)
+        .bind(record.score)
+        .bind(&flags)
+        .bind(record.rejected)
+        .bind(record.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to record fraud score: {}", e))?;
+        Ok(())
+    }
+
+    async fn count_device_sessions_since(&self, device_key: &str, since: DateTime<Utc>) -> RepositoryResult<i64> {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM listen_fraud_scores WHERE device_key = $1 AND created_at >= $2 AND NOT rejected",
+        )
+        .bind(device_key)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to count device sessions: {}", e))
+    }
+
+    async fn count_other_users_on_device(
+        &self,
+        device_key: &str,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> RepositoryResult<i64> {
+        sqlx::query_scalar(
+            r#"SELECT COUNT(DISTINCT user_id) FROM listen_fraud_scores
+               WHERE device_key = $1 AND user_id <> $2 AND created_at >= $3"#,
+        )
+        .bind(device_key)
+        .bind(user_id)
+        .bind(since)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to count device users: {}", e))
+    }
+
+    async fn find_pending_review(&self, min_score: f64, limit: i64) -> RepositoryResult<Vec<FraudScoreRecord>> {
+        let rows = sqlx::query(
+            r#"SELECT id, user_id, device_key, user_agent_hash, ip_hash, device_id, sessions_today,
+                      score, flags, rejected, created_at, reviewed_at, reviewed_by, confirmed_fraud
+               FROM listen_fraud_scores
+               WHERE reviewed_at IS NULL AND score >= $1
+               ORDER BY score DESC, created_at DESC
+               LIMIT $2"#,
+        )
+        .bind(min_score)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load fraud scores: {}", e))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let flags: Vec<String> = row.get("flags");
+                Ok(FraudScoreRecord {
+                    id: row.get("id"),
+                    user_id: row.get("user_id"),
+                    device_key: row.get("device_key"),
+                    fingerprint: DeviceFingerprint {
+                        user_agent_hash: row.get("user_agent_hash"),
+                        ip_hash: row.get("ip_hash"),
+                        device_id: row.get("device_id"),
+                    },
+                    sessions_today: row.get::<i32, _>("sessions_today").max(0) as u32,
+                    score: row.get("score"),
+                    flags: flags.iter().map(|flag| flag.parse()).collect::<Result<_, _>>()?,
+                    rejected: row.get("rejected"),
+                    created_at: row.get("created_at"),
+                    reviewed_at: row.get("reviewed_at"),
+                    reviewed_by: row.get("reviewed_by"),
+                    confirmed_fraud: row.get("confirmed_fraud"),
+                })
+            })
+            .collect()
+    }
+
+    async fn mark_reviewed(&self, id: Uuid, reviewer_id: Uuid, confirmed_fraud: bool) -> RepositoryResult<bool> {
+        let result = sqlx::query(
+            r#"UPDATE listen_fraud_scores
+               SET reviewed_at = NOW(), reviewed_by = $2, confirmed_fraud = $3
+               WHERE id = $1 AND reviewed_at IS NULL"#,
+        )
+        .bind(id)
+        .bind(reviewer_id)
+        .bind(confirmed_fraud)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to review fraud score: {}", e))?;
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+/// Repositorio en memoria para tests
+#[derive(Default)]
+pub struct InMemoryFraudScoreRepository {
+    records: Mutex<Vec<FraudScoreRecord>>,
+}
+
+impl InMemoryFraudScoreRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn records(&self) -> Vec<FraudScoreRecord> {
+        self.records.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl FraudScoreRepository for InMemoryFraudScoreRepository {
+    async fn record(&self, record: &FraudScoreRecord) -> RepositoryResult<()> {
+        self.records.lock().unwrap().push(record.clone());
+        Ok(())
+    }
+
+    async fn count_device_sessions_since(&self, device_key: &str, since: DateTime<Utc>) -> RepositoryResult<i64> {
+        Ok(self
+            .records
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| r.device_key == device_key && r.created_at >= since && !r.rejected)
+            .count() as i64)
+    }
+
+    async fn count_other_users_on_device(
+        &self,
+        device_key: &str,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> RepositoryResult<i64> {
+        let records = self.records.lock().unwrap();
+        let users: std::collections::HashSet<Uuid> = records
+            .iter()
+            .filter(|r| r.device_key == device_key && r.user_id != user_id && r.created_at >= since)
+            .map(|r| r.user_id)
+            .collect();
+        Ok(users.len() as i64)
+    }
+
+    async fn find_pending_review(&self, min_score: f64, limit: i64) -> RepositoryResult<Vec<FraudScoreRecord>> {
+        let mut pending: Vec<FraudScoreRecord> = self
+            .records
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|r| r.reviewed_at.is_none() && r.score >= min_score)
+            .cloned()
+            .collect();
+        pending.sort_by(|a, b| b.score.total_cmp(&a.score).then(b.created_at.cmp(&a.created_at)));
+        pending.truncate(limit.max(0) as usize);
+        Ok(pending)
+    }
+
+    async fn mark_reviewed(&self, id: Uuid, reviewer_id: Uuid, confirmed_fraud: bool) -> RepositoryResult<bool> {
+        let mut records = self.records.lock().unwrap();
+        match records.iter_mut().find(|r| r.id == id && r.reviewed_at.is_none()) {
+            Some(record) => {
+                record.reviewed_at = Some(Utc::now());
+                record.reviewed_by = Some(reviewer_id);
+                record.confirmed_fraud = Some(confirmed_fraud);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+}
@@ -10,6 +10,7 @@ use crate::bounded_contexts::listen_reward::domain::{
     },
 };
 use vibestream_types::{SongContract, ArtistContract};
+use crate::shared::domain::errors::AppError;
 // These imports are used in the file
 
 use super::{
@@ -155,10 +156,88 @@ impl PostgresListenSessionRepository {
             row.started_at,
             row.completed_at,
             row.verified_at,
-        );
+        ).with_version(row.version);
         
         Ok(session)
     }
+
+    /// Inserta una sesión nueva (versión 0) o actualiza una persistida solo si
+    /// la versión almacenada coincide. Devuelve la versión tras la escritura.
+    pub async fn save_versioned(&self, session: &ListenSession) -> Result<i32, AppError> {
+        let row = self.session_to_row(session);
+
+        if row.version == 0 {
+            let query = r#"
+                INSERT INTO listen_sessions (
+                    id, user_id, song_id, artist_id, user_tier, status, 
+                    listen_duration_seconds, quality_score, zk_proof_hash,
+                    base_reward_tokens, final_reward_tokens, started_at,
+                    completed_at, verified_at, version
+                ) VALUES (
+                    $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, 1
+                )
+                ON CONFLICT (id) DO NOTHING
+                RETURNING version
+            "#;
+
+            let version: Option<i32> = sqlx::query_scalar(query)
+                .bind(row.id)
+                .bind(row.user_id)
+                .bind(row.song_id)
+                .bind(row.artist_id)
+                .bind(row.user_tier)
+                .bind(row.status)
+                .bind(row.listen_duration_seconds)
+                .bind(row.quality_score)
+                .bind(row.zk_proof_hash)
+                .bind(row.base_reward_tokens)
+                .bind(row.final_reward_tokens)
+                .bind(row.started_at)
+                .bind(row.completed_at)
+                .bind(row.verified_at)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to save listen session: {}", e)))?;
+
+            return version.ok_or_else(|| {
+                AppError::ConcurrencyConflict("Session was created concurrently".to_string())
+            });
+        }
+
+        let query = r#"
+            UPDATE listen_sessions SET
+                user_tier = $3,
+                status = $4,
+                listen_duration_seconds = $5,
+                quality_score = $6,
+                zk_proof_hash = $7,
+                base_reward_tokens = $8,
+                final_reward_tokens = $9,
+                completed_at = $10,
+                verified_at = $11,
+                version = version + 1
+            WHERE id = $1 AND version = $2
+            RETURNING version
+        "#;
+
+        let version: Option<i32> = sqlx::query_scalar(query)
+            .bind(row.id)
+            .bind(row.version)
+            .bind(row.user_tier)
+            .bind(row.status)
+            .bind(row.listen_duration_seconds)
+            .bind(row.quality_score)
+            .bind(row.zk_proof_hash)
+            .bind(row.base_reward_tokens)
+            .bind(row.final_reward_tokens)
+            .bind(row.completed_at)
+            .bind(row.verified_at)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to update listen session: {}", e)))?;
+
+        version.ok_or_else(|| AppError::ConcurrencyConflict("Session was modified concurrently".to_string()))
+    }
 }
 
 #[async_trait]
@@ -179,82 +258,17 @@ impl ListenSessionRepository for PostgresListenSessionRepository {
     }
 
     async fn save(&self, session: &ListenSession) -> RepositoryResult<()> {
-        let row = self.session_to_row(session);
-        
-        let query = r#"
-            INSERT INTO listen_sessions (
-                id, user_id, song_id, artist_id, user_tier, status, 
-                listen_duration_seconds, quality_score, zk_proof_hash,
-                base_reward_tokens, final_reward_tokens, started_at,
-                completed_at, verified_at, version
-            ) VALUES (
-                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15
-            )
-            ON CONFLICT (id) DO NOTHING
-        "#;
-        
-        sqlx::query(query)
-            .bind(row.id)
-            .bind(row.user_id)
-            .bind(row.song_id)
-            .bind(row.artist_id)
-            .bind(row.user_tier)
-            .bind(row.status)
-            .bind(row.listen_duration_seconds)
-            .bind(row.quality_score)
-            .bind(row.zk_proof_hash)
-            .bind(row.base_reward_tokens)
-            .bind(row.final_reward_tokens)
-            .bind(row.started_at)
-            .bind(row.completed_at)
-            .bind(row.verified_at)
-            .bind(row.version)
-            .execute(&self.pool)
+        self.save_versioned(session)
             .await
-            .map_err(|e| format!("Failed to save listen session: {}", e))?;
-            
-        Ok(())
+            .map(|_| ())
+            .map_err(|e| e.to_string())
     }
 
     async fn update(&self, session: &ListenSession, expected_version: i32) -> RepositoryResult<()> {
-        let row = self.session_to_row(session);
-        
-        let query = r#"
-            UPDATE listen_sessions SET
-                user_tier = $1,
-                status = $2,
-                listen_duration_seconds = $3,
-                quality_score = $4,
-                zk_proof_hash = $5,
-                base_reward_tokens = $6,
-                final_reward_tokens = $7,
-                completed_at = $8,
-                verified_at = $9,
-                version = version + 1
-            WHERE id = $10 AND version = $11
-        "#;
-        
-        let result = sqlx::query(query)
-            .bind(row.user_tier)
-            .bind(row.status)
-            .bind(row.listen_duration_seconds)
-            .bind(row.quality_score)
-            .bind(row.zk_proof_hash)
-            .bind(row.base_reward_tokens)
-            .bind(row.final_reward_tokens)
-            .bind(row.completed_at)
-            .bind(row.verified_at)
-            .bind(row.id)
-            .bind(expected_version)
-            .execute(&self.pool)
+        self.save_versioned(&session.clone().with_version(expected_version))
             .await
-            .map_err(|e| format!("Failed to update listen session: {}", e))?;
-            
-        if result.rows_affected() == 0 {
-            return Err("Session was modified by another process or not found".to_string());
-        }
-        
-        Ok(())
+            .map(|_| ())
+            .map_err(|e| e.to_string())
     }
 
     async fn delete(&self, id: &ListenSessionId) -> RepositoryResult<()> {
@@ -303,6 +317,42 @@ impl ListenSessionRepository for PostgresListenSessionRepository {
         Ok(sessions)
     }
 
+    async fn find_by_user_and_status(&self, user_id: Uuid, status: &SessionStatus) -> RepositoryResult<Vec<ListenSession>> {
+        let query = "SELECT * FROM listen_sessions WHERE user_id = $1 AND status = $2 ORDER BY started_at DESC";
+        
+        let rows = sqlx::query_as::<_, ListenSessionRow>(query)
+            .bind(user_id)
+            .bind(status.to_string())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+            
+        let mut sessions = Vec::new();
+        for row in rows {
+            sessions.push(self.row_to_entity(&row)?);
+        }
+        
+        Ok(sessions)
+    }
+
+    async fn find_expired_sessions(&self, session_timeout_minutes: i64) -> RepositoryResult<Vec<ListenSession>> {
+        let query = "SELECT * FROM listen_sessions WHERE status = 'active' AND started_at < $1 ORDER BY started_at";
+        let cutoff = Utc::now() - chrono::Duration::minutes(session_timeout_minutes);
+        
+        let rows = sqlx::query_as::<_, ListenSessionRow>(query)
+            .bind(cutoff)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Database error: {}", e))?;
+            
+        let mut sessions = Vec::new();
+        for row in rows {
+            sessions.push(self.row_to_entity(&row)?);
+        }
+        
+        Ok(sessions)
+    }
+
     async fn count_user_sessions_in_period(&self, user_id: Uuid, start: DateTime<Utc>, end: DateTime<Utc>) -> RepositoryResult<i64> {
         let query = "SELECT COUNT(*) FROM listen_sessions WHERE user_id = $1 AND started_at >= $2 AND started_at <= $3";
         
@@ -14,7 +14,10 @@ use crate::bounded_contexts::listen_reward::domain::value_objects::{
     RewardAmount, RewardPoolId, ValidationPeriod
 };
 use crate::bounded_contexts::listen_reward::domain::aggregates::reward_distribution::RewardPool;
+use crate::bounded_contexts::listen_reward::domain::events::RewardDistributionCreated;
+use crate::shared::domain::errors::AppError;
 use crate::shared::domain::events::DomainEvent;
+use crate::shared::infrastructure::outbox::{self, OutboxMessage};
 use super::{RewardDistributionRepository, RepositoryResult, Pagination};
 
 // Estructura para mapear la tabla reward_distributions
@@ -34,6 +37,97 @@ struct RewardDistributionRow {
     updated_at: DateTime<Utc>,
 }
 
+// Fila de `reward_distributions` con el destinatario de la distribución
+#[derive(Debug)]
+struct RecipientRecord {
+    id: Uuid,
+    pool_id: Uuid,
+    total_tokens: f64,
+    distributed_tokens: f64,
+    reserved_tokens: f64,
+    validation_period_start: DateTime<Utc>,
+    validation_period_end: DateTime<Utc>,
+    session_id: Uuid,
+    user_id: Uuid,
+    artist_id: Uuid,
+    user_share: f64,
+    artist_share: f64,
+    platform_fee: f64,
+    status: String,
+    created_at: DateTime<Utc>,
+}
+
+impl RecipientRecord {
+    fn from_distribution(distribution: &RewardDistribution) -> Result<Self, AppError> {
+        let created: RewardDistributionCreated = distribution.created_event().ok_or_else(|| {
+            AppError::ValidationError(format!(
+                "Reward distribution {} has no RewardDistributionCreated event",
+                distribution.id()
+            ))
+        })?;
+
+        Ok(Self {
+            id: distribution.id(),
+            pool_id: distribution.pool_id(),
+            total_tokens: created.total_amount.tokens(),
+            distributed_tokens: distribution.distributed_amount(),
+            reserved_tokens: distribution.reserved_amount(),
+            validation_period_start: distribution.period_start(),
+            validation_period_end: distribution.period_end(),
+            session_id: created.session_id.value(),
+            user_id: created.user_id,
+            artist_id: created.artist_id,
+            user_share: created.user_share.tokens(),
+            artist_share: created.artist_share.tokens(),
+            platform_fee: created.platform_fee.tokens(),
+            status: distribution.status(),
+            created_at: created.created_at,
+        })
+    }
+}
+
+/// Contexto con el que se guardan sus eventos en el outbox
+pub const LISTEN_REWARD_OUTBOX_CONTEXT: &str = "listen_reward";
+
+// Microsegundos entre el epoch Unix y el de PostgreSQL (2000-01-01)
+const PG_EPOCH_OFFSET_MICROS: i64 = 946_684_800_000_000;
+
+// Formato binario de COPY: cabecera, una tupla por registro y trailer
+fn encode_copy_binary(records: &[RecipientRecord]) -> Vec<u8> {
+    fn field(buffer: &mut Vec<u8>, bytes: &[u8]) {
+        buffer.extend_from_slice(&(bytes.len() as i32).to_be_bytes());
+        buffer.extend_from_slice(bytes);
+    }
+    fn timestamp(buffer: &mut Vec<u8>, value: DateTime<Utc>) {
+        field(buffer, &(value.timestamp_micros() - PG_EPOCH_OFFSET_MICROS).to_be_bytes());
+    }
+
+    let mut buffer = Vec::with_capacity(19 + records.len() * 256);
+    buffer.extend_from_slice(b"PGCOPY\n\xff\r\n\0");
+    buffer.extend_from_slice(&0i32.to_be_bytes()); // flags
+    buffer.extend_from_slice(&0i32.to_be_bytes()); // header extension
+    for record in records {
+        buffer.extend_from_slice(&15i16.to_be_bytes());
+        field(&mut buffer, record.id.as_bytes());
+        field(&mut buffer, record.pool_id.as_bytes());
+        field(&mut buffer, &record.total_tokens.to_be_bytes());
+        field(&mut buffer, &record.distributed_tokens.to_be_bytes());
+        field(&mut buffer, &record.reserved_tokens.to_be_bytes());
+        timestamp(&mut buffer, record.validation_period_start);
+        timestamp(&mut buffer, record.validation_period_end);
+        field(&mut buffer, record.session_id.as_bytes());
+        field(&mut buffer, record.user_id.as_bytes());
+        field(&mut buffer, record.artist_id.as_bytes());
+        field(&mut buffer, &record.user_share.to_be_bytes());
+        field(&mut buffer, &record.artist_share.to_be_bytes());
+        field(&mut buffer, &record.platform_fee.to_be_bytes());
+        field(&mut buffer, record.status.as_bytes());
+        timestamp(&mut buffer, record.created_at);
+    }
+    buffer.extend_from_slice(&(-1i16).to_be_bytes());
+    buffer
+}
+
 pub struct PostgresRewardDistributionRepository {
     pool: PgPool,
 }
@@ -74,6 +168,129 @@ impl PostgresRewardDistributionRepository {
         }
     }
 
+    /// Deja en el outbox los eventos pendientes de `distributions`
+    async fn record_events(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        distributions: &[RewardDistribution],
+    ) -> Result<(), AppError> {
+        for event in distributions.iter().flat_map(|distribution| distribution.get_events()) {
+            outbox::enqueue(tx, &OutboxMessage::from_domain_event(LISTEN_REWARD_OUTBOX_CONTEXT, event.as_ref())).await?;
+        }
+        Ok(())
+    }
+
+    /// Inserta una distribución recién creada con el destinatario y los repartos
+    /// de su `RewardDistributionCreated`; sus eventos van al outbox en la misma
+    /// transacción.
+    pub async fn save_distribution(&self, distribution: &RewardDistribution) -> Result<(), AppError> {
+        let record = RecipientRecord::from_distribution(distribution)?;
+        let db_error = |e: sqlx::Error| AppError::DatabaseError(format!("Failed to save reward distribution: {}", e));
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO reward_distributions (
+                id, pool_id, total_tokens, distributed_tokens, reserved_tokens,
+                validation_period_start, validation_period_end, session_id, user_id, artist_id,
+                user_share, artist_share, platform_fee, status, created_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+            "#,
+        )
+        .bind(record.id)
+        .bind(record.pool_id)
+        .bind(record.total_tokens)
+        .bind(record.distributed_tokens)
+        .bind(record.reserved_tokens)
+        .bind(record.validation_period_start)
+        .bind(record.validation_period_end)
+        .bind(record.session_id)
+        .bind(record.user_id)
+        .bind(record.artist_id)
+        .bind(record.user_share)
+        .bind(record.artist_share)
+        .bind(record.platform_fee)
+        .bind(&record.status)
+        .bind(record.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        Self::record_events(&mut tx, std::slice::from_ref(distribution)).await?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(())
+    }
+
+    /// Inserta muchas distribuciones de golpe (p. ej. un reparto entre todos los
+    /// shareholders) con `COPY ... FROM STDIN` binario sobre una tabla temporal.
+    /// Los eventos de todas van al outbox en la misma transacción.
+    pub async fn save_batch(&self, distributions: &[RewardDistribution]) -> Result<(), AppError> {
+        if distributions.is_empty() {
+            return Ok(());
+        }
+        let records = distributions
+            .iter()
+            .map(RecipientRecord::from_distribution)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let db_error = |e: sqlx::Error| AppError::DatabaseError(format!("Failed to save reward distribution batch: {}", e));
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        // COPY binario no sabe codificar DECIMAL desde f64: se copia a FLOAT8 y se convierte al insertar
+        sqlx::query(
+            r#"
+            CREATE TEMP TABLE reward_distributions_batch (
+                id UUID, pool_id UUID, total_tokens FLOAT8, distributed_tokens FLOAT8, reserved_tokens FLOAT8,
+                validation_period_start TIMESTAMPTZ, validation_period_end TIMESTAMPTZ,
+                session_id UUID, user_id UUID, artist_id UUID,
+                user_share FLOAT8, artist_share FLOAT8, platform_fee FLOAT8,
+                status TEXT, created_at TIMESTAMPTZ
+            ) ON COMMIT DROP
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        let mut copy = tx
+            .copy_in_raw("COPY reward_distributions_batch FROM STDIN WITH (FORMAT binary)")
+            .await
+            .map_err(db_error)?;
+        copy.send(encode_copy_binary(&records)).await.map_err(db_error)?;
+        copy.finish().await.map_err(db_error)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO reward_distributions (
+                id, pool_id, total_tokens, distributed_tokens, reserved_tokens,
+                validation_period_start, validation_period_end, session_id, user_id, artist_id,
+                user_share, artist_share, platform_fee, status, created_at
+            )
+            SELECT id, pool_id, total_tokens, distributed_tokens, reserved_tokens,
+                   validation_period_start, validation_period_end, session_id, user_id, artist_id,
+                   user_share, artist_share, platform_fee, status, created_at
+            FROM reward_distributions_batch
+            "#,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        Self::record_events(&mut tx, distributions).await?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(())
+    }
+
+    /// Total de tokens que ha recibido un usuario en todas sus distribuciones
+    pub async fn get_user_total_earned(&self, user_id: Uuid) -> Result<f64, AppError> {
+        sqlx::query_scalar::<_, f64>(
+            "SELECT COALESCE(SUM(user_share), 0)::FLOAT8 FROM reward_distributions WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to get user total earned: {}", e)))
+    }
+
     // Convierte una fila de base de datos a una entidad de dominio
     fn row_to_entity(&self, row: &RewardDistributionRow) -> RepositoryResult<RewardDistribution> {
         // Crear el pool de recompensas
@@ -7,19 +7,20 @@ use async_trait::async_trait;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
-use crate::bounded_contexts::listen_reward::domain::entities::ListenSession;
+use crate::bounded_contexts::listen_reward::domain::entities::{ListenSession, listen_session::SessionStatus};
 use crate::bounded_contexts::listen_reward::domain::aggregates::RewardDistribution;
 use crate::bounded_contexts::listen_reward::domain::value_objects::{
     ListenSessionId, RewardPoolId,
 };
 use super::{
-    RepositoryResult, Pagination, ListenSessionFilter, RewardAnalytics,
+    RepositoryResult, Pagination, ListenSessionFilter, RewardAnalytics, FraudScoreRecord,
 };
 
 /// Repository for persisting and retrieving ListenSession entities
 #[async_trait]
 pub trait ListenSessionRepository: Send + Sync {
-    /// Save a new listen session
+    /// Save a listen session. New sessions (version 0) are inserted; persisted
+    /// ones are updated only if the stored version still matches
     async fn save(&self, session: &ListenSession) -> RepositoryResult<()>;
 
     /// Update an existing listen session with optimistic locking
@@ -37,6 +38,12 @@ pub trait ListenSessionRepository: Send + Sync {
     /// Find active sessions for a user (anti-fraud check)
     async fn find_active_sessions_for_user(&self, user_id: Uuid) -> RepositoryResult<Vec<ListenSession>>;
 
+    /// Find a user's sessions in the given status
+    async fn find_by_user_and_status(&self, user_id: Uuid, status: &SessionStatus) -> RepositoryResult<Vec<ListenSession>>;
+
+    /// Find sessions still active more than `session_timeout_minutes` after they started
+    async fn find_expired_sessions(&self, session_timeout_minutes: i64) -> RepositoryResult<Vec<ListenSession>>;
+
     /// Count sessions for a user in a time period (rate limiting)
     async fn count_user_sessions_in_period(
         &self,
@@ -46,6 +53,30 @@ pub trait ListenSessionRepository: Send + Sync {
     ) -> RepositoryResult<i64>;
 }
 
+/// Fraud scores computed when sessions start, kept for admin review. The
+/// same records tell which accounts have used a device.
+#[async_trait]
+pub trait FraudScoreRepository: Send + Sync {
+    async fn record(&self, record: &FraudScoreRecord) -> RepositoryResult<()>;
+
+    /// Sessions accepted from `device_key` since `since`
+    async fn count_device_sessions_since(&self, device_key: &str, since: DateTime<Utc>) -> RepositoryResult<i64>;
+
+    /// Accounts other than `user_id` seen on `device_key` since `since`
+    async fn count_other_users_on_device(
+        &self,
+        device_key: &str,
+        user_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> RepositoryResult<i64>;
+
+    /// Unreviewed records at or above `min_score`, highest score first
+    async fn find_pending_review(&self, min_score: f64, limit: i64) -> RepositoryResult<Vec<FraudScoreRecord>>;
+
+    /// Returns false when the record does not exist or was already reviewed
+    async fn mark_reviewed(&self, id: Uuid, reviewer_id: Uuid, confirmed_fraud: bool) -> RepositoryResult<bool>;
+}
+
 /// Repository for querying listen sessions with complex filters
 #[async_trait]
 pub trait ListenSessionQueryRepository: Send + Sync {
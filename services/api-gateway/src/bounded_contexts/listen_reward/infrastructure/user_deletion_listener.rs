@@ -0,0 +1,51 @@
+//! Listen Session User Deletion Listener
+//!
+//! Las sesiones de escucha de un usuario eliminado y las recompensas que le
+//! repartieron se conservan para las estadísticas y la contabilidad del pool,
+//! pero pasan a la cuenta compartida de usuarios eliminados y dejan de estar
+//! ligadas a la persona.
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler, DELETED_USER_ID};
+use crate::shared::domain::errors::AppError;
+
+pub struct ListenSessionUserDeletionListener {
+    pool: PgPool,
+}
+
+impl ListenSessionUserDeletionListener {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl EventHandler for ListenSessionUserDeletionListener {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let DomainEvent::UserDeletionRequested { user_id, .. } = event else {
+            return Ok(());
+        };
+
+        let db_error = |e: sqlx::Error| AppError::DatabaseError(format!("Failed to unlink listen sessions: {}", e));
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let result = sqlx::query("UPDATE listen_sessions SET user_id = $2, updated_at = NOW() WHERE user_id = $1")
+            .bind(user_id)
+            .bind(DELETED_USER_ID)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        // Las recompensas repartidas por esas sesiones siguen cuadrando con el pool
+        sqlx::query("UPDATE reward_distributions SET user_id = $2, updated_at = NOW() WHERE user_id = $1")
+            .bind(user_id)
+            .bind(DELETED_USER_ID)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        tx.commit().await.map_err(db_error)?;
+
+        tracing::info!("Unlinked {} listen sessions of deleted user {}", result.rows_affected(), user_id);
+        Ok(())
+    }
+}
@@ -17,8 +17,9 @@ use chrono::{DateTime, Utc};
 use crate::bounded_contexts::listen_reward::application::{
     ListenRewardApplicationService, GetUserListeningHistoryQuery,
 };
+use crate::shared::domain::errors::AppError;
 use super::{
-    ErrorResponse, SuccessResponse, PaginationParams, DateRangeParams,
+    SuccessResponse, PaginationParams, DateRangeParams,
     validate_uuid,
 };
 
@@ -227,7 +228,7 @@ impl AnalyticsController {
         State(controller): State<Arc<Self>>,
         Path(user_id): Path<String>,
         Query(request): Query<UserHistoryRequest>,
-    ) -> Result<Json<SuccessResponse<UserHistoryResponse>>, ErrorResponse> {
+    ) -> Result<Json<SuccessResponse<UserHistoryResponse>>, AppError> {
         // Validate user_id
         let user_id = validate_uuid(&user_id, "user_id")?;
 
@@ -278,11 +279,7 @@ impl AnalyticsController {
 
                 Ok(Json(SuccessResponse::new(response)))
             }
-            Err(e) => Err(ErrorResponse::new(
-                "UserHistoryError".to_string(),
-                e.to_string(),
-                400,
-            )),
+            Err(e) => Err(e),
         }
     }
 
@@ -292,7 +289,7 @@ impl AnalyticsController {
         State(controller): State<Arc<Self>>,
         Path(artist_id): Path<String>,
         Query(request): Query<ArtistAnalyticsRequest>,
-    ) -> Result<Json<SuccessResponse<ArtistAnalyticsResponse>>, ErrorResponse> {
+    ) -> Result<Json<SuccessResponse<ArtistAnalyticsResponse>>, AppError> {
         // Validate artist_id
         let artist_id = validate_uuid(&artist_id, "artist_id")?;
 
@@ -369,7 +366,7 @@ impl AnalyticsController {
     pub async fn get_platform_stats(
         State(controller): State<Arc<Self>>,
         Query(request): Query<DateRangeParams>,
-    ) -> Result<Json<SuccessResponse<PlatformStatsResponse>>, ErrorResponse> {
+    ) -> Result<Json<SuccessResponse<PlatformStatsResponse>>, AppError> {
         let response = PlatformStatsResponse {
             period_start: request.start_date.unwrap_or_else(|| Utc::now() - chrono::Duration::days(30)),
             period_end: request.end_date.unwrap_or_else(|| Utc::now()),
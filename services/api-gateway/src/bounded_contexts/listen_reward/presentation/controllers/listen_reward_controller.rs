@@ -18,8 +18,10 @@ use crate::bounded_contexts::listen_reward::application::{
     ListenRewardApplicationService, StartListeningCommand, CompleteListeningCommand,
     GetUserListeningHistoryQuery,
 };
+use crate::bounded_contexts::listen_reward::domain::value_objects::DeviceFingerprint;
+use crate::shared::domain::errors::AppError;
 use super::{
-    ErrorResponse, SuccessResponse, PaginationParams, DateRangeParams,
+    SuccessResponse, PaginationParams, DateRangeParams,
     validate_uuid, validate_positive_number, validate_range,
 };
 
@@ -29,7 +31,7 @@ pub struct StartSessionRequest {
     pub song_id: String,
     pub artist_id: String,
     pub user_tier: String,
-    pub device_fingerprint: Option<String>,
+    pub device_fingerprint: Option<DeviceFingerprint>,
     pub geo_location: Option<String>,
 }
 
@@ -130,7 +132,7 @@ impl ListenRewardController {
         State(controller): State<Arc<Self>>,
         Path(user_id): Path<String>,
         Json(request): Json<StartSessionRequest>,
-    ) -> Result<Json<SuccessResponse<StartSessionResponse>>, ErrorResponse> {
+    ) -> Result<Json<SuccessResponse<StartSessionResponse>>, AppError> {
         // Validate user_id
         let user_id = validate_uuid(&user_id, "user_id")?;
 
@@ -162,11 +164,7 @@ impl ListenRewardController {
                 Ok(Json(SuccessResponse::new(http_response)
                     .with_message("Session started successfully".to_string())))
             }
-            Err(e) => Err(ErrorResponse::new(
-                "SessionStartError".to_string(),
-                e.to_string(),
-                400,
-            )),
+            Err(e) => Err(e),
         }
     }
 
@@ -176,7 +174,7 @@ impl ListenRewardController {
         State(controller): State<Arc<Self>>,
         Path(session_id): Path<String>,
         Json(request): Json<CompleteSessionRequest>,
-    ) -> Result<Json<SuccessResponse<CompleteSessionResponse>>, ErrorResponse> {
+    ) -> Result<Json<SuccessResponse<CompleteSessionResponse>>, AppError> {
         // Validate session_id
         let session_id = validate_uuid(&session_id, "session_id")?;
 
@@ -187,11 +185,7 @@ impl ListenRewardController {
         validate_range(request.completion_percentage, 0.0, 100.0, "completion_percentage")?;
 
         if request.zk_proof_hash.is_empty() {
-            return Err(ErrorResponse::new(
-                "ValidationError".to_string(),
-                "ZK proof hash cannot be empty".to_string(),
-                400,
-            ));
+            return Err(AppError::ValidationError("ZK proof hash cannot be empty".to_string()));
         }
 
         // Create command
@@ -219,11 +213,7 @@ impl ListenRewardController {
                 Ok(Json(SuccessResponse::new(http_response)
                     .with_message("Session completed successfully".to_string())))
             }
-            Err(e) => Err(ErrorResponse::new(
-                "SessionCompleteError".to_string(),
-                e.to_string(),
-                400,
-            )),
+            Err(e) => Err(e),
         }
     }
 
@@ -233,7 +223,7 @@ impl ListenRewardController {
         State(controller): State<Arc<Self>>,
         Path(user_id): Path<String>,
         Query(query): Query<UserHistoryQuery>,
-    ) -> Result<Json<SuccessResponse<UserHistoryResponse>>, ErrorResponse> {
+    ) -> Result<Json<SuccessResponse<UserHistoryResponse>>, AppError> {
         // Validate user_id
         let user_id = validate_uuid(&user_id, "user_id")?;
 
@@ -277,11 +267,7 @@ impl ListenRewardController {
 
                 Ok(Json(SuccessResponse::new(http_response)))
             }
-            Err(e) => Err(ErrorResponse::new(
-                "HistoryQueryError".to_string(),
-                e.to_string(),
-                400,
-            )),
+            Err(e) => Err(e),
         }
     }
 
@@ -290,7 +276,7 @@ impl ListenRewardController {
     pub async fn get_session_details(
         State(controller): State<Arc<Self>>,
         Path(session_id): Path<String>,
-    ) -> Result<Json<SuccessResponse<SessionDetailsResponse>>, ErrorResponse> {
+    ) -> Result<Json<SuccessResponse<SessionDetailsResponse>>, AppError> {
         // Validate session_id
         let session_id = validate_uuid(&session_id, "session_id")?;
 
@@ -317,7 +303,7 @@ impl ListenRewardController {
     pub async fn get_user_sessions(
         State(controller): State<Arc<Self>>,
         Path(user_id): Path<String>,
-    ) -> Result<Json<SuccessResponse<Vec<SessionDetailsResponse>>>, ErrorResponse> {
+    ) -> Result<Json<SuccessResponse<Vec<SessionDetailsResponse>>>, AppError> {
         // Validate user_id
         let user_id = validate_uuid(&user_id, "user_id")?;
 
@@ -358,7 +344,7 @@ impl ListenRewardController {
     /// Health check endpoint
     pub async fn health_check(
         State(controller): State<Arc<Self>>,
-    ) -> Result<Json<SuccessResponse<serde_json::Value>>, ErrorResponse> {
+    ) -> Result<Json<SuccessResponse<serde_json::Value>>, AppError> {
         let health_data = serde_json::json!({
             "status": "healthy",
             "service": "listen_reward",
@@ -405,7 +391,7 @@ mod tests {
     fn test_request_validation() {
         // Test UUID validation
         let result = validate_uuid("invalid-uuid", "test_field");
-        assert!(result.is_err());
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
 
         let result = validate_uuid("550e8400-e29b-41d4-a716-446655440000", "test_field");
         assert!(result.is_ok());
@@ -1,6 +1,5 @@
 use axum::{
     extract::{Path, Query},
-    http::StatusCode,
     response::Json,
     Router,
     routing::{post, get},
@@ -9,6 +8,8 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use std::collections::HashMap;
 
+use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::auth::AuthenticatedUser;
 use crate::bounded_contexts::listen_reward::application::{
     StartListenSessionUseCase, StartListenSessionCommand,
     CompleteListenSessionUseCase,
@@ -22,10 +23,8 @@ pub use crate::bounded_contexts::listen_reward::application::{
 // DTOs for API requests/responses
 #[derive(Debug, Deserialize)]
 pub struct StartListenSessionRequest {
-    pub user_id: Uuid,
     pub song_id: String,
     pub artist_id: String,
-    pub user_tier: String,
 }
 
 #[derive(Debug, Deserialize)]
@@ -78,9 +77,11 @@ impl ListenSessionController {
         }
     }
 
+    /// El oyente y su tier salen del token, nunca del body
     pub async fn start_session(
+        user: AuthenticatedUser,
         Json(request): Json<StartListenSessionRequest>,
-    ) -> Result<Json<ApiResponse<StartListenSessionResponse>>, StatusCode> {
+    ) -> Result<Json<ApiResponse<StartListenSessionResponse>>, AppError> {
         let use_case = StartListenSessionUseCase::new();
         
         // Convert request to command - create mock contracts for now
@@ -111,10 +112,10 @@ impl ListenSessionController {
         };
 
         let command = StartListenSessionCommand {
-            user_id: request.user_id,
+            user_id: user.user_id,
             song_contract,
             artist_contract,
-            user_tier: request.user_tier,
+            user_tier: user.tier,
         };
 
         // Execute use case
@@ -123,36 +124,34 @@ impl ListenSessionController {
                 // In a real implementation, we would publish the event here
                 Ok(Json(ApiResponse::success(response)))
             }
-            Err(error) => {
-                Ok(Json(ApiResponse::error(error)))
-            }
+            Err(error) => Err(AppError::ValidationError(error)),
         }
     }
 
     pub async fn complete_session(
         Path(_session_id): Path<String>,
         Json(_request): Json<CompleteListenSessionRequest>,
-    ) -> Result<Json<ApiResponse<CompleteListenSessionResponse>>, StatusCode> {
+    ) -> Result<Json<ApiResponse<CompleteListenSessionResponse>>, AppError> {
         // In a real implementation, we would fetch the session from repository
         // For now, we'll return an error indicating this endpoint needs session state
-        Ok(Json(ApiResponse::error(
+        Err(AppError::NotImplemented(
             "Session completion requires session state management - not implemented in this demo".to_string()
-        )))
+        ))
     }
 
     pub async fn get_session_status(
         Path(_session_id): Path<String>,
-    ) -> Result<Json<ApiResponse<SessionStatusResponse>>, StatusCode> {
+    ) -> Result<Json<ApiResponse<SessionStatusResponse>>, AppError> {
         // In a real implementation, we would fetch session from repository
-        Ok(Json(ApiResponse::error(
+        Err(AppError::NotImplemented(
             "Session status retrieval requires repository implementation".to_string()
-        )))
+        ))
     }
 
     pub async fn get_user_sessions(
         Path(_user_id): Path<Uuid>,
         Query(params): Query<HashMap<String, String>>,
-    ) -> Result<Json<ApiResponse<Vec<UserSessionSummary>>>, StatusCode> {
+    ) -> Result<Json<ApiResponse<Vec<UserSessionSummary>>>, AppError> {
         // Parse query parameters
         let _limit: usize = params.get("limit")
             .and_then(|s| s.parse().ok())
@@ -162,21 +161,21 @@ impl ListenSessionController {
         let _status_filter = params.get("status");
 
         // In a real implementation, we would fetch from repository
-        Ok(Json(ApiResponse::error(
+        Err(AppError::NotImplemented(
             "User sessions retrieval requires repository implementation".to_string()
-        )))
+        ))
     }
 
     pub async fn get_session_analytics(
         Path(_session_id): Path<String>,
-    ) -> Result<Json<ApiResponse<SessionAnalyticsResponse>>, StatusCode> {
+    ) -> Result<Json<ApiResponse<SessionAnalyticsResponse>>, AppError> {
         // In a real implementation, we would fetch session and calculate analytics
-        Ok(Json(ApiResponse::error(
+        Err(AppError::NotImplemented(
             "Session analytics requires repository implementation".to_string()
-        )))
+        ))
     }
 
-    pub async fn health_check() -> Result<Json<ApiResponse<HealthCheckResponse>>, StatusCode> {
+    pub async fn health_check() -> Result<Json<ApiResponse<HealthCheckResponse>>, AppError> {
         let health_response = HealthCheckResponse {
             service: "listen-reward-service".to_string(),
             status: "healthy".to_string(),
@@ -37,40 +37,7 @@ use axum::http::StatusCode;
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 use serde::{Deserialize, Serialize};
-
-// Standard HTTP error response
-#[derive(Debug, Serialize)]
-pub struct ErrorResponse {
-    pub error: String,
-    pub message: String,
-    pub code: u16,
-    pub timestamp: chrono::DateTime<chrono::Utc>,
-    pub request_id: Option<String>,
-}
-
-impl ErrorResponse {
-    pub fn new(error: String, message: String, code: u16) -> Self {
-        Self {
-            error,
-            message,
-            code,
-            timestamp: chrono::Utc::now(),
-            request_id: None,
-        }
-    }
-
-    pub fn with_request_id(mut self, request_id: String) -> Self {
-        self.request_id = Some(request_id);
-        self
-    }
-}
-
-impl IntoResponse for ErrorResponse {
-    fn into_response(self) -> Response {
-        let status = StatusCode::from_u16(self.code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
-        (status, Json(self)).into_response()
-    }
-}
+use crate::shared::domain::errors::AppError;
 
 // Success response wrapper
 #[derive(Debug, Serialize)]
@@ -132,34 +99,22 @@ pub struct DateRangeParams {
 }
 
 // Request validation utilities
-pub fn validate_uuid(id: &str, field_name: &str) -> Result<uuid::Uuid, ErrorResponse> {
+pub fn validate_uuid(id: &str, field_name: &str) -> Result<uuid::Uuid, AppError> {
     uuid::Uuid::parse_str(id).map_err(|_| {
-        ErrorResponse::new(
-            "ValidationError".to_string(),
-            format!("Invalid UUID format for {}", field_name),
-            400,
-        )
+        AppError::ValidationError(format!("Invalid UUID format for {}", field_name))
     })
 }
 
-pub fn validate_positive_number(value: f64, field_name: &str) -> Result<(), ErrorResponse> {
+pub fn validate_positive_number(value: f64, field_name: &str) -> Result<(), AppError> {
     if value <= 0.0 {
-        return Err(ErrorResponse::new(
-            "ValidationError".to_string(),
-            format!("{} must be a positive number", field_name),
-            400,
-        ));
+        return Err(AppError::ValidationError(format!("{} must be a positive number", field_name)));
     }
     Ok(())
 }
 
-pub fn validate_range(value: f64, min: f64, max: f64, field_name: &str) -> Result<(), ErrorResponse> {
+pub fn validate_range(value: f64, min: f64, max: f64, field_name: &str) -> Result<(), AppError> {
     if value < min || value > max {
-        return Err(ErrorResponse::new(
-            "ValidationError".to_string(),
-            format!("{} must be between {} and {}", field_name, min, max),
-            400,
-        ));
+        return Err(AppError::ValidationError(format!("{} must be between {} and {}", field_name, min, max)));
     }
     Ok(())
 }
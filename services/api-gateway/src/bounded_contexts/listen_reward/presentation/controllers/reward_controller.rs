@@ -1,6 +1,5 @@
 use axum::{
     extract::{Path, Query},
-    http::StatusCode,
     response::Json,
     routing::{get, post},
     Router,
@@ -9,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use std::collections::HashMap;
 
+use crate::shared::domain::errors::AppError;
 use crate::bounded_contexts::listen_reward::application::use_cases::{
     ProcessRewardDistributionUseCase, ProcessRewardDistributionResponse, QueueRewardDistributionResponse,
 };
@@ -126,7 +126,7 @@ impl RewardController {
 
     pub async fn create_reward_pool(
         Json(request): Json<CreateRewardPoolRequest>,
-    ) -> Result<Json<ApiResponse<CreateRewardPoolResponse>>, StatusCode> {
+    ) -> Result<Json<ApiResponse<CreateRewardPoolResponse>>, AppError> {
         // In a real implementation, we would:
         // 1. Create a new reward pool
         // 2. Save it to repository
@@ -144,7 +144,7 @@ impl RewardController {
 
     pub async fn queue_reward_distribution(
         Json(_request): Json<QueueRewardRequest>,
-    ) -> Result<Json<ApiResponse<QueueRewardDistributionResponse>>, StatusCode> {
+    ) -> Result<Json<ApiResponse<QueueRewardDistributionResponse>>, AppError> {
         // In a real implementation, we would:
         // 1. Fetch the reward distribution from repository
         // 2. Fetch the listen session from repository
@@ -152,15 +152,15 @@ impl RewardController {
         // 4. Save the updated distribution
         // 5. Publish events
 
-        Ok(Json(ApiResponse::error(
+        Err(AppError::NotImplemented(
             "Queue reward distribution requires repository implementation".to_string()
-        )))
+        ))
     }
 
     pub async fn process_reward_distribution(
         Path(_session_id): Path<String>,
         Json(_request): Json<ProcessRewardRequest>,
-    ) -> Result<Json<ApiResponse<ProcessRewardDistributionResponse>>, StatusCode> {
+    ) -> Result<Json<ApiResponse<ProcessRewardDistributionResponse>>, AppError> {
         // In a real implementation, we would:
         // 1. Fetch the reward distribution from repository
         // 2. Fetch the listen session from repository
@@ -168,66 +168,66 @@ impl RewardController {
         // 4. Save the updated distribution and session
         // 5. Publish events
 
-        Ok(Json(ApiResponse::error(
+        Err(AppError::NotImplemented(
             "Process reward distribution requires repository implementation".to_string()
-        )))
+        ))
     }
 
     pub async fn get_reward_pool_status(
         Path(_pool_id): Path<String>,
-    ) -> Result<Json<ApiResponse<RewardPoolStatusResponse>>, StatusCode> {
+    ) -> Result<Json<ApiResponse<RewardPoolStatusResponse>>, AppError> {
         // In a real implementation, we would fetch from repository
-        Ok(Json(ApiResponse::error(
+        Err(AppError::NotImplemented(
             "Reward pool status requires repository implementation".to_string()
-        )))
+        ))
     }
 
     pub async fn get_user_reward_summary(
         Path(_user_id): Path<Uuid>,
-    ) -> Result<Json<ApiResponse<UserRewardSummaryResponse>>, StatusCode> {
+    ) -> Result<Json<ApiResponse<UserRewardSummaryResponse>>, AppError> {
         // In a real implementation, we would calculate from repository data
-        Ok(Json(ApiResponse::error(
+        Err(AppError::NotImplemented(
             "User reward summary requires repository implementation".to_string()
-        )))
+        ))
     }
 
     pub async fn get_artist_royalty_summary(
         Path(_artist_id): Path<String>,
-    ) -> Result<Json<ApiResponse<ArtistRoyaltySummaryResponse>>, StatusCode> {
+    ) -> Result<Json<ApiResponse<ArtistRoyaltySummaryResponse>>, AppError> {
         // In a real implementation, we would calculate from repository data
-        Ok(Json(ApiResponse::error(
+        Err(AppError::NotImplemented(
             "Artist royalty summary requires repository implementation".to_string()
-        )))
+        ))
     }
 
     pub async fn get_distribution_analytics(
         Query(params): Query<HashMap<String, String>>,
-    ) -> Result<Json<ApiResponse<DistributionAnalyticsResponse>>, StatusCode> {
+    ) -> Result<Json<ApiResponse<DistributionAnalyticsResponse>>, AppError> {
         // Parse optional time range parameters
         let _start_date = params.get("start_date");
         let _end_date = params.get("end_date");
 
         // In a real implementation, we would calculate analytics from repository data
-        Ok(Json(ApiResponse::error(
+        Err(AppError::NotImplemented(
             "Distribution analytics requires repository implementation".to_string()
-        )))
+        ))
     }
 
     pub async fn get_pending_distributions(
         Query(params): Query<HashMap<String, String>>,
-    ) -> Result<Json<ApiResponse<Vec<PendingDistributionResponse>>>, StatusCode> {
+    ) -> Result<Json<ApiResponse<Vec<PendingDistributionResponse>>>, AppError> {
         let _limit: usize = params.get("limit")
             .and_then(|s| s.parse().ok())
             .unwrap_or(10)
             .min(100);
 
         // In a real implementation, we would fetch from repository
-        Ok(Json(ApiResponse::error(
+        Err(AppError::NotImplemented(
             "Pending distributions requires repository implementation".to_string()
-        )))
+        ))
     }
 
-    pub async fn health_check() -> Result<Json<ApiResponse<HealthCheckResponse>>, StatusCode> {
+    pub async fn health_check() -> Result<Json<ApiResponse<HealthCheckResponse>>, AppError> {
         let health_response = HealthCheckResponse {
             service: "reward-distribution-service".to_string(),
             status: "healthy".to_string(),
@@ -1,6 +1,5 @@
 use axum::{
     extract::{Path, Query, State, Json},
-    http::StatusCode,
     response::Json as ResponseJson,
 };
 use serde::{Deserialize, Serialize};
@@ -9,6 +8,12 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
 use crate::auth::Claims;
+use crate::bounded_contexts::listen_reward::application::{FraudDetectionConfig, FraudDetectionService};
+use crate::bounded_contexts::listen_reward::domain::value_objects::DeviceFingerprint;
+use crate::bounded_contexts::listen_reward::infrastructure::repositories::{
+    FraudScoreRecord, FraudScoreRepository, PostgresFraudScoreRepository,
+};
+use crate::shared::domain::errors::AppError;
 use crate::shared::infrastructure::app_state::AppState;
 
 // ====== REQUEST/RESPONSE TYPES ======
@@ -21,6 +26,8 @@ pub struct StartListenSessionRequest {
     pub user_tier: String, // "free", "premium", "vip"
     pub boost_multiplier: Option<f64>,
     pub location: Option<Location>,
+    /// Hashed device signals for fraud detection
+    pub device_fingerprint: Option<DeviceFingerprint>,
 }
 
 
@@ -219,6 +226,18 @@ pub struct TierDistribution {
     pub vip: u32,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct FraudReviewQuery {
+    /// Only scores at or above this value (default 0.5)
+    pub min_score: Option<f64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ReviewFraudScoreRequest {
+    pub confirmed_fraud: bool,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct RewardsQuery {
     pub user_id: Option<Uuid>,
@@ -239,7 +258,8 @@ pub struct RewardsQuery {
     request_body = StartListenSessionRequest,
     responses(
         (status = 200, description = "Session started successfully", body = StartListenSessionResponse),
-        (status = 400, description = "Invalid request")
+        (status = 400, description = "Invalid request"),
+        (status = 403, description = "Session rejected by fraud detection")
     ),
     tag = "listen-rewards"
 )]
@@ -247,9 +267,18 @@ pub async fn start_listen_session(
     State(_state): State<AppState>,
     claims: Claims,
     Json(request): Json<StartListenSessionRequest>,
-) -> Result<ResponseJson<StartListenSessionResponse>, StatusCode> {
+) -> Result<ResponseJson<StartListenSessionResponse>, AppError> {
     let session_id = Uuid::new_v4();
-    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::ValidationError("Invalid user id in token".to_string()))?;
+
+    if let Some(fingerprint) = &request.device_fingerprint {
+        let fraud_detection = FraudDetectionService::with_config(
+            std::sync::Arc::new(PostgresFraudScoreRepository::new(_state.get_db_pool().clone())),
+            FraudDetectionConfig::from_env(),
+        );
+        fraud_detection.screen(fingerprint, user_id).await?;
+    }
     
     // Calculate expected reward based on user tier and boost multiplier
     let base_reward = match request.user_tier.as_str() {
@@ -304,8 +333,9 @@ pub async fn complete_listen_session(
     Path(session_id): Path<Uuid>,
     claims: Claims,
     Json(request): Json<CompleteListenSessionRequest>,
-) -> Result<ResponseJson<CompleteListenSessionResponse>, StatusCode> {
-    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| StatusCode::BAD_REQUEST)?;
+) -> Result<ResponseJson<CompleteListenSessionResponse>, AppError> {
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::ValidationError("Invalid user id in token".to_string()))?;
     
     // Verify ZK proof via ZK Service
     let proof = crate::shared::infrastructure::clients::zk_service_client::ZkProof {
@@ -329,7 +359,7 @@ pub async fn complete_listen_session(
     };
     
     if verification_status == "failed" {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(AppError::ValidationError("ZK proof verification failed".to_string()));
     }
     
     // Calculate reward breakdown
@@ -404,10 +434,10 @@ pub async fn get_user_rewards(
     State(_state): State<AppState>,
     Path(user_id): Path<Uuid>,
     claims: Claims,
-) -> Result<ResponseJson<UserRewardsResponse>, StatusCode> {
+) -> Result<ResponseJson<UserRewardsResponse>, AppError> {
     // Verify user can access this data
     if claims.sub != user_id.to_string() && claims.role != "admin" {
-        return Err(StatusCode::FORBIDDEN);
+        return Err(AppError::Forbidden("Cannot access another user's rewards".to_string()));
     }
 
     // Fetch real data from repository
@@ -424,7 +454,7 @@ pub async fn get_user_rewards(
     let history = analytics_repo.get_user_reward_history(user_id, &pagination).await
         .map_err(|e| {
              tracing::error!("Failed to fetch user rewards: {}", e);
-             StatusCode::INTERNAL_SERVER_ERROR
+             AppError::InternalError(format!("Failed to fetch user rewards: {}", e))
         })?;
 
     let recent_sessions: Vec<SessionSummary> = history.into_iter().map(|h| SessionSummary {
@@ -507,10 +537,10 @@ pub async fn distribute_rewards(
     State(_state): State<AppState>,
     claims: Claims,
     Json(request): Json<DistributeRewardsRequest>,
-) -> Result<ResponseJson<DistributeRewardsResponse>, StatusCode> {
+) -> Result<ResponseJson<DistributeRewardsResponse>, AppError> {
     // Only admins can distribute rewards
     if claims.role != "admin" {
-        return Err(StatusCode::FORBIDDEN);
+        return Err(AppError::Forbidden("Admin role required".to_string()));
     }
 
     let distribution_id = Uuid::new_v4();
@@ -584,10 +614,10 @@ pub async fn get_listen_analytics(
     State(_state): State<AppState>,
     Query(params): Query<RewardsQuery>,
     claims: Claims,
-) -> Result<ResponseJson<AnalyticsResponse>, StatusCode> {
+) -> Result<ResponseJson<AnalyticsResponse>, AppError> {
     // Only admins can view system analytics
     if claims.role != "admin" {
-        return Err(StatusCode::FORBIDDEN);
+        return Err(AppError::Forbidden("Admin role required".to_string()));
     }
 
     let response = AnalyticsResponse {
@@ -649,4 +679,76 @@ pub struct FraudDetectionStats {
     pub suspicious_sessions: u32,
     pub blocked_sessions: u32,
     pub fraud_rate: f64,
-} 
\ No newline at end of file
+} 
+/// GET /api/v1/listen-rewards/admin/fraud-scores - Device fraud scores pending review
+#[utoipa::path(
+    get,
+    path = "/api/v1/listen-rewards/admin/fraud-scores",
+    params(
+        ("min_score" = Option<f64>, Query, description = "Minimum score (default 0.5)"),
+        ("limit" = Option<i64>, Query, description = "Maximum results (default 50, max 200)")
+    ),
+    responses(
+        (status = 200, description = "Unreviewed fraud scores, highest first", body = Vec<FraudScoreRecord>),
+        (status = 403, description = "Forbidden - Admin only")
+    ),
+    tag = "listen-rewards"
+)]
+pub async fn get_fraud_scores_for_review(
+    State(state): State<AppState>,
+    Query(params): Query<FraudReviewQuery>,
+    claims: Claims,
+) -> Result<ResponseJson<Vec<FraudScoreRecord>>, AppError> {
+    if claims.role != "admin" {
+        return Err(AppError::Forbidden("Admin role required".to_string()));
+    }
+
+    let repository = PostgresFraudScoreRepository::new(state.get_db_pool().clone());
+    let records = repository
+        .find_pending_review(params.min_score.unwrap_or(0.5), params.limit.unwrap_or(50).clamp(1, 200))
+        .await
+        .map_err(AppError::DatabaseError)?;
+    Ok(ResponseJson(records))
+}
+
+/// POST /api/v1/listen-rewards/admin/fraud-scores/{id}/review - Record the admin decision
+#[utoipa::path(
+    post,
+    path = "/api/v1/listen-rewards/admin/fraud-scores/{id}/review",
+    request_body = ReviewFraudScoreRequest,
+    params(
+        ("id" = Uuid, Path, description = "Fraud score ID")
+    ),
+    responses(
+        (status = 200, description = "Review recorded"),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Fraud score not found or already reviewed")
+    ),
+    tag = "listen-rewards"
+)]
+pub async fn review_fraud_score(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    claims: Claims,
+    Json(request): Json<ReviewFraudScoreRequest>,
+) -> Result<ResponseJson<serde_json::Value>, AppError> {
+    if claims.role != "admin" {
+        return Err(AppError::Forbidden("Admin role required".to_string()));
+    }
+    let reviewer_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| AppError::ValidationError("Invalid user id in token".to_string()))?;
+
+    let repository = PostgresFraudScoreRepository::new(state.get_db_pool().clone());
+    let reviewed = repository
+        .mark_reviewed(id, reviewer_id, request.confirmed_fraud)
+        .await
+        .map_err(AppError::DatabaseError)?;
+    if !reviewed {
+        return Err(AppError::NotFound(format!("Fraud score {} not found or already reviewed", id)));
+    }
+
+    Ok(ResponseJson(serde_json::json!({
+        "id": id,
+        "confirmed_fraud": request.confirmed_fraud,
+    })))
+}
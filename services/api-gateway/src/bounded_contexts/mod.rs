@@ -21,4 +21,7 @@ pub mod fan_loyalty;
 pub mod payment;
 
 // Cross-context orchestrator
-pub mod orchestrator; 
\ No newline at end of file
+pub mod orchestrator;
+
+// Unified health reporting across contexts
+pub mod registry; 
\ No newline at end of file
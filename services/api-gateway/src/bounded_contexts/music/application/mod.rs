@@ -1,5 +1,7 @@
 pub mod commands;
 pub mod queries;
+pub mod recommendations;
+pub mod video_signaling;
 // pub mod services; // Commented out since file doesn't exist
 pub mod use_cases;
 
@@ -21,4 +23,7 @@ pub use queries::{
 
 pub use use_cases::{
     UploadSongCommand, UploadSongResult,
-}; 
\ No newline at end of file
+}; 
+
+pub use recommendations::{UserSimilarityRefreshJob, SIMILARITY_REFRESH_HOUR_UTC};
+pub use video_signaling::{SignalingHub, SignalingCommand, SignalingMessage};
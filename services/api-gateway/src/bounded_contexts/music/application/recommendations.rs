@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+
+use crate::bounded_contexts::music::infrastructure::repositories::PostgresPlaylistRecommendationReadModel;
+
+// =============================================================================
+// MUSIC - USER SIMILARITY REFRESH
+// =============================================================================
+
+/// Hora (UTC) a la que se recalcula `user_similarities`, fuera del pico de escuchas
+pub const SIMILARITY_REFRESH_HOUR_UTC: u32 = 3;
+
+/// Siguiente ejecución diaria a `hour` UTC estrictamente posterior a `now`
+pub fn next_nightly_run(now: DateTime<Utc>, hour: u32) -> DateTime<Utc> {
+    let at = NaiveTime::from_hms_opt(hour, 0, 0).unwrap_or(NaiveTime::MIN);
+    let today = now.date_naive().and_time(at).and_utc();
+    if today > now {
+        today
+    } else {
+        today + Duration::days(1)
+    }
+}
+
+/// Recalcula cada noche los vecinos por Jaccard que usa el recomendador de playlists
+pub struct UserSimilarityRefreshJob {
+    read_model: Arc<PostgresPlaylistRecommendationReadModel>,
+    hour_utc: u32,
+}
+
+impl UserSimilarityRefreshJob {
+    pub fn new(read_model: Arc<PostgresPlaylistRecommendationReadModel>, hour_utc: u32) -> Self {
+        Self { read_model, hour_utc }
+    }
+
+    pub fn start(&self) -> tokio::task::JoinHandle<()> {
+        let read_model = Arc::clone(&self.read_model);
+        let hour_utc = self.hour_utc;
+
+        tokio::spawn(async move {
+            tracing::info!("🚀 User similarity refresh job started");
+            loop {
+                let now = Utc::now();
+                let wait = (next_nightly_run(now, hour_utc) - now).to_std().unwrap_or_default();
+                tokio::time::sleep(wait).await;
+                match read_model.refresh_user_similarities().await {
+                    Ok(users) => tracing::info!("User similarities refreshed for {} users", users),
+                    Err(e) => tracing::error!("User similarity refresh failed: {:?}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn next_run_is_today_or_tomorrow_at_the_given_hour() {
+        let before = Utc.with_ymd_and_hms(2026, 10, 15, 1, 30, 0).unwrap();
+        assert_eq!(next_nightly_run(before, 3), Utc.with_ymd_and_hms(2026, 10, 15, 3, 0, 0).unwrap());
+
+        let after = Utc.with_ymd_and_hms(2026, 10, 15, 9, 0, 0).unwrap();
+        assert_eq!(next_nightly_run(after, 3), Utc.with_ymd_and_hms(2026, 10, 16, 3, 0, 0).unwrap());
+
+        // Justo a la hora no se repite la ejecución recién hecha
+        let exactly = Utc.with_ymd_and_hms(2026, 10, 15, 3, 0, 0).unwrap();
+        assert_eq!(next_nightly_run(exactly, 3), Utc.with_ymd_and_hms(2026, 10, 16, 3, 0, 0).unwrap());
+    }
+}
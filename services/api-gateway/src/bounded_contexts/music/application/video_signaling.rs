@@ -0,0 +1,485 @@
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot, RwLock};
+use uuid::Uuid;
+
+// =============================================================================
+// MUSIC - SEÑALIZACIÓN WEBRTC PARA INTERCAMBIO P2P DE SEGMENTOS
+// =============================================================================
+//
+// Salas por contenido (CID del manifiesto) donde los navegadores que ven el
+// mismo vídeo negocian conexiones directas: ofertas/respuestas SDP y
+// candidatos ICE se reenvían al peer destino sin tocarlos. El servidor nunca
+// ve vídeo, solo metadatos. Cada sala lleva su tabla de peers con los rangos
+// de segmentos que anuncia cada uno, y quien entra recibe esa tabla para saber
+// a quién pedir qué.
+
+/// Peers por sala; el resto espera a que alguien salga
+pub const MAX_PEERS_PER_ROOM: usize = 50;
+/// Rangos que puede anunciar un peer en un mismo `have`
+pub const MAX_RANGES_PER_PEER: usize = 64;
+/// Las SDP reales ocupan unos pocos KB; más es abuso del canal
+pub const MAX_SIGNAL_PAYLOAD_BYTES: usize = 16 * 1024;
+/// Ofertas que un peer puede enviar por ventana
+pub const DEFAULT_OFFER_LIMIT: usize = 10;
+pub const DEFAULT_OFFER_WINDOW: Duration = Duration::from_secs(10);
+/// Mensajes pendientes por conexión antes de considerarla lenta
+pub const OUTBOUND_BUFFER_SIZE: usize = 128;
+
+/// Segmentos `[start, end)` de una calidad
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkRange {
+    pub quality: String,
+    pub start: u32,
+    pub end: u32,
+}
+
+impl ChunkRange {
+    pub fn contains(&self, quality: &str, chunk_index: u32) -> bool {
+        self.quality == quality && (self.start..self.end).contains(&chunk_index)
+    }
+}
+
+/// Entrada de la tabla de peers de una sala
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoomPeer {
+    pub peer_id: Uuid,
+    pub ranges: Vec<ChunkRange>,
+}
+
+/// Mensajes del cliente
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SignalingCommand {
+    /// Sustituye los rangos que el peer anuncia
+    Have { ranges: Vec<ChunkRange> },
+    Offer { to: Uuid, sdp: String },
+    Answer { to: Uuid, sdp: String },
+    IceCandidate {
+        to: Uuid,
+        candidate: String,
+        #[serde(default)]
+        sdp_mid: Option<String>,
+        #[serde(default)]
+        sdp_mline_index: Option<u16>,
+    },
+}
+
+/// Mensajes al cliente
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SignalingMessage {
+    /// Primer mensaje tras entrar: el id propio y quién tiene qué
+    Welcome { peer_id: Uuid, content_id: String, peers: Vec<RoomPeer> },
+    PeerJoined { peer_id: Uuid },
+    PeerLeft { peer_id: Uuid },
+    PeerChunks { peer_id: Uuid, ranges: Vec<ChunkRange> },
+    Offer { from: Uuid, sdp: String },
+    Answer { from: Uuid, sdp: String },
+    IceCandidate {
+        from: Uuid,
+        candidate: String,
+        sdp_mid: Option<String>,
+        sdp_mline_index: Option<u16>,
+    },
+    Error { message: String },
+}
+
+/// Motivo por el que el hub cierra una conexión
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignalingCloseReason {
+    SlowConsumer,
+}
+
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum SignalingError {
+    #[error("Room {0} is full")]
+    RoomFull(String),
+    #[error("Peer not found in this room")]
+    PeerNotFound,
+    #[error("Target peer {0} is not in this room")]
+    TargetNotFound(Uuid),
+    #[error("Offer limit of {limit} per {window:?} reached")]
+    RateLimited { limit: usize, window: Duration },
+    #[error("Invalid signal: {0}")]
+    InvalidSignal(String),
+}
+
+/// Extremo de la conexión que usa el socket
+pub struct SignalingConnection {
+    pub peer_id: Uuid,
+    pub messages: mpsc::Receiver<SignalingMessage>,
+    pub closed: oneshot::Receiver<SignalingCloseReason>,
+}
+
+struct PeerEntry {
+    user_id: Uuid,
+    sender: mpsc::Sender<SignalingMessage>,
+    ranges: Vec<ChunkRange>,
+    /// Instantes de las ofertas dentro de la ventana
+    recent_offers: VecDeque<Instant>,
+    close: Option<oneshot::Sender<SignalingCloseReason>>,
+}
+
+pub struct SignalingHub {
+    rooms: RwLock<HashMap<String, HashMap<Uuid, PeerEntry>>>,
+    offer_limit: usize,
+    offer_window: Duration,
+}
+
+impl Default for SignalingHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SignalingHub {
+    pub fn new() -> Self {
+        Self {
+            rooms: RwLock::new(HashMap::new()),
+            offer_limit: DEFAULT_OFFER_LIMIT,
+            offer_window: DEFAULT_OFFER_WINDOW,
+        }
+    }
+
+    pub fn with_offer_limit(mut self, limit: usize, window: Duration) -> Self {
+        self.offer_limit = limit;
+        self.offer_window = window;
+        self
+    }
+
+    /// Entrar en la sala del contenido. El peer recibe `welcome` con la tabla
+    /// de la sala y el resto `peer_joined`.
+    pub async fn join(&self, content_id: &str, user_id: Uuid) -> Result<SignalingConnection, SignalingError> {
+        let peer_id = Uuid::new_v4();
+        let (sender, messages) = mpsc::channel(OUTBOUND_BUFFER_SIZE);
+        let (close, closed) = oneshot::channel();
+
+        let mut rooms = self.rooms.write().await;
+        let room = rooms.entry(content_id.to_string()).or_default();
+        if room.len() >= MAX_PEERS_PER_ROOM {
+            return Err(SignalingError::RoomFull(content_id.to_string()));
+        }
+
+        let mut peers: Vec<RoomPeer> = room
+            .iter()
+            .map(|(id, entry)| RoomPeer { peer_id: *id, ranges: entry.ranges.clone() })
+            .collect();
+        // Primero quien más segmentos anuncia
+        peers.sort_by_key(|peer| std::cmp::Reverse(peer.ranges.iter().map(|r| (r.end - r.start) as u64).sum::<u64>()));
+
+        let welcome = SignalingMessage::Welcome { peer_id, content_id: content_id.to_string(), peers };
+        // El canal recién creado tiene hueco de sobra
+        let _ = sender.try_send(welcome);
+
+        room.insert(peer_id, PeerEntry {
+            user_id,
+            sender,
+            ranges: Vec::new(),
+            recent_offers: VecDeque::new(),
+            close: Some(close),
+        });
+        let dropped = Self::broadcast(room, peer_id, SignalingMessage::PeerJoined { peer_id });
+        Self::drop_peers(&mut rooms, content_id, dropped);
+
+        Ok(SignalingConnection { peer_id, messages, closed })
+    }
+
+    /// Salir de la sala; el resto recibe `peer_left` y la sala vacía desaparece
+    pub async fn leave(&self, content_id: &str, peer_id: Uuid) {
+        let mut rooms = self.rooms.write().await;
+        Self::drop_peers(&mut rooms, content_id, vec![(peer_id, None)]);
+    }
+
+    pub async fn room_peers(&self, content_id: &str) -> Vec<RoomPeer> {
+        self.rooms
+            .read()
+            .await
+            .get(content_id)
+            .map(|room| {
+                room.iter()
+                    .map(|(id, entry)| RoomPeer { peer_id: *id, ranges: entry.ranges.clone() })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Peers de la sala que anuncian el segmento
+    pub async fn holders(&self, content_id: &str, quality: &str, chunk_index: u32) -> Vec<Uuid> {
+        self.rooms
+            .read()
+            .await
+            .get(content_id)
+            .map(|room| {
+                room.iter()
+                    .filter(|(_, entry)| entry.ranges.iter().any(|range| range.contains(quality, chunk_index)))
+                    .map(|(id, _)| *id)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Aplicar un mensaje del peer: los anuncios se difunden a la sala y las
+    /// señales se reenvían solo al destinatario
+    pub async fn apply(&self, content_id: &str, peer_id: Uuid, command: SignalingCommand) -> Result<(), SignalingError> {
+        let mut rooms = self.rooms.write().await;
+        let room = rooms.get_mut(content_id).ok_or(SignalingError::PeerNotFound)?;
+        if !room.contains_key(&peer_id) {
+            return Err(SignalingError::PeerNotFound);
+        }
+
+        let dropped = match command {
+            SignalingCommand::Have { ranges } => {
+                validate_ranges(&ranges)?;
+                if let Some(entry) = room.get_mut(&peer_id) {
+                    entry.ranges = ranges.clone();
+                }
+                Self::broadcast(room, peer_id, SignalingMessage::PeerChunks { peer_id, ranges })
+            }
+            SignalingCommand::Offer { to, sdp } => {
+                validate_payload(&sdp)?;
+                Self::check_target(room, peer_id, to)?;
+                self.record_offer(room, peer_id)?;
+                Self::relay(room, to, SignalingMessage::Offer { from: peer_id, sdp })
+            }
+            SignalingCommand::Answer { to, sdp } => {
+                validate_payload(&sdp)?;
+                Self::check_target(room, peer_id, to)?;
+                Self::relay(room, to, SignalingMessage::Answer { from: peer_id, sdp })
+            }
+            SignalingCommand::IceCandidate { to, candidate, sdp_mid, sdp_mline_index } => {
+                validate_payload(&candidate)?;
+                Self::check_target(room, peer_id, to)?;
+                Self::relay(room, to, SignalingMessage::IceCandidate { from: peer_id, candidate, sdp_mid, sdp_mline_index })
+            }
+        };
+        Self::drop_peers(&mut rooms, content_id, dropped);
+
+        Ok(())
+    }
+
+    fn check_target(room: &HashMap<Uuid, PeerEntry>, peer_id: Uuid, to: Uuid) -> Result<(), SignalingError> {
+        if to == peer_id {
+            return Err(SignalingError::InvalidSignal("Cannot signal yourself".to_string()));
+        }
+        if !room.contains_key(&to) {
+            return Err(SignalingError::TargetNotFound(to));
+        }
+        Ok(())
+    }
+
+    /// Ventana deslizante de ofertas por peer
+    fn record_offer(&self, room: &mut HashMap<Uuid, PeerEntry>, peer_id: Uuid) -> Result<(), SignalingError> {
+        let entry = room.get_mut(&peer_id).ok_or(SignalingError::PeerNotFound)?;
+        let now = Instant::now();
+        while entry
+            .recent_offers
+            .front()
+            .is_some_and(|sent| now.duration_since(*sent) >= self.offer_window)
+        {
+            entry.recent_offers.pop_front();
+        }
+        if entry.recent_offers.len() >= self.offer_limit {
+            tracing::debug!("Signaling peer {} (user {}) hit the offer limit", peer_id, entry.user_id);
+            return Err(SignalingError::RateLimited { limit: self.offer_limit, window: self.offer_window });
+        }
+        entry.recent_offers.push_back(now);
+        Ok(())
+    }
+
+    fn relay(room: &HashMap<Uuid, PeerEntry>, to: Uuid, message: SignalingMessage) -> Vec<(Uuid, Option<SignalingCloseReason>)> {
+        room.get(&to)
+            .and_then(|entry| Self::deliver(to, entry, message))
+            .into_iter()
+            .collect()
+    }
+
+    fn broadcast(room: &HashMap<Uuid, PeerEntry>, except: Uuid, message: SignalingMessage) -> Vec<(Uuid, Option<SignalingCloseReason>)> {
+        room.iter()
+            .filter(|(id, _)| **id != except)
+            .filter_map(|(id, entry)| Self::deliver(*id, entry, message.clone()))
+            .collect()
+    }
+
+    /// Entregar sin bloquear; devuelve el peer si hay que sacarlo de la sala
+    fn deliver(peer_id: Uuid, entry: &PeerEntry, message: SignalingMessage) -> Option<(Uuid, Option<SignalingCloseReason>)> {
+        match entry.sender.try_send(message) {
+            Ok(()) => None,
+            Err(mpsc::error::TrySendError::Full(_)) => Some((peer_id, Some(SignalingCloseReason::SlowConsumer))),
+            Err(mpsc::error::TrySendError::Closed(_)) => Some((peer_id, None)),
+        }
+    }
+
+    /// Sacar peers de la sala avisando al resto (que a su vez puede caer)
+    fn drop_peers(
+        rooms: &mut HashMap<String, HashMap<Uuid, PeerEntry>>,
+        content_id: &str,
+        mut dropped: Vec<(Uuid, Option<SignalingCloseReason>)>,
+    ) {
+        let Some(room) = rooms.get_mut(content_id) else {
+            return;
+        };
+        while let Some((peer_id, reason)) = dropped.pop() {
+            let Some(mut entry) = room.remove(&peer_id) else {
+                continue;
+            };
+            if let (Some(reason), Some(close)) = (reason, entry.close.take()) {
+                tracing::warn!("Dropping slow signaling peer {} (user {}) from {}", peer_id, entry.user_id, content_id);
+                let _ = close.send(reason);
+            }
+            dropped.extend(Self::broadcast(room, peer_id, SignalingMessage::PeerLeft { peer_id }));
+        }
+        if room.is_empty() {
+            rooms.remove(content_id);
+        }
+    }
+}
+
+fn validate_payload(payload: &str) -> Result<(), SignalingError> {
+    if payload.trim().is_empty() {
+        return Err(SignalingError::InvalidSignal("Empty payload".to_string()));
+    }
+    if payload.len() > MAX_SIGNAL_PAYLOAD_BYTES {
+        return Err(SignalingError::InvalidSignal(format!("Payload exceeds {} bytes", MAX_SIGNAL_PAYLOAD_BYTES)));
+    }
+    Ok(())
+}
+
+fn validate_ranges(ranges: &[ChunkRange]) -> Result<(), SignalingError> {
+    if ranges.len() > MAX_RANGES_PER_PEER {
+        return Err(SignalingError::InvalidSignal(format!("At most {} ranges can be announced", MAX_RANGES_PER_PEER)));
+    }
+    if let Some(range) = ranges.iter().find(|range| range.start >= range.end) {
+        return Err(SignalingError::InvalidSignal(format!("Empty chunk range {}..{}", range.start, range.end)));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(start: u32, end: u32) -> ChunkRange {
+        ChunkRange { quality: "high".to_string(), start, end }
+    }
+
+    async fn next(connection: &mut SignalingConnection) -> SignalingMessage {
+        tokio::time::timeout(Duration::from_secs(1), connection.messages.recv())
+            .await
+            .expect("timed out waiting for signaling message")
+            .expect("connection closed")
+    }
+
+    #[tokio::test]
+    async fn joining_peer_learns_who_holds_which_chunks() {
+        let hub = SignalingHub::new();
+        let mut seeder = hub.join("QmVideo", Uuid::new_v4()).await.unwrap();
+        assert!(matches!(next(&mut seeder).await, SignalingMessage::Welcome { ref peers, .. } if peers.is_empty()));
+        hub.apply("QmVideo", seeder.peer_id, SignalingCommand::Have { ranges: vec![range(0, 40)] }).await.unwrap();
+
+        let mut viewer = hub.join("QmVideo", Uuid::new_v4()).await.unwrap();
+        assert_eq!(
+            next(&mut viewer).await,
+            SignalingMessage::Welcome {
+                peer_id: viewer.peer_id,
+                content_id: "QmVideo".to_string(),
+                peers: vec![RoomPeer { peer_id: seeder.peer_id, ranges: vec![range(0, 40)] }],
+            }
+        );
+        assert_eq!(next(&mut seeder).await, SignalingMessage::PeerJoined { peer_id: viewer.peer_id });
+        assert_eq!(hub.holders("QmVideo", "high", 12).await, vec![seeder.peer_id]);
+        assert!(hub.holders("QmVideo", "high", 40).await.is_empty());
+        assert!(hub.holders("QmVideo", "low", 12).await.is_empty());
+
+        // Rooms are isolated per content
+        let mut other = hub.join("QmOther", Uuid::new_v4()).await.unwrap();
+        assert!(matches!(next(&mut other).await, SignalingMessage::Welcome { ref peers, .. } if peers.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn offers_and_answers_are_relayed_only_to_their_target() {
+        let hub = SignalingHub::new();
+        let mut seeder = hub.join("QmVideo", Uuid::new_v4()).await.unwrap();
+        next(&mut seeder).await;
+        let mut viewer = hub.join("QmVideo", Uuid::new_v4()).await.unwrap();
+        next(&mut viewer).await;
+        next(&mut seeder).await;
+
+        let offer = SignalingCommand::Offer { to: seeder.peer_id, sdp: "v=0 offer".to_string() };
+        hub.apply("QmVideo", viewer.peer_id, offer).await.unwrap();
+        assert_eq!(next(&mut seeder).await, SignalingMessage::Offer { from: viewer.peer_id, sdp: "v=0 offer".to_string() });
+
+        let answer = SignalingCommand::Answer { to: viewer.peer_id, sdp: "v=0 answer".to_string() };
+        hub.apply("QmVideo", seeder.peer_id, answer).await.unwrap();
+        assert_eq!(next(&mut viewer).await, SignalingMessage::Answer { from: seeder.peer_id, sdp: "v=0 answer".to_string() });
+
+        // Both sides trickle their candidates to each other
+        let candidate = |to: Uuid, candidate: &str| SignalingCommand::IceCandidate {
+            to,
+            candidate: candidate.to_string(),
+            sdp_mid: Some("0".to_string()),
+            sdp_mline_index: Some(0),
+        };
+        hub.apply("QmVideo", viewer.peer_id, candidate(seeder.peer_id, "candidate:1 udp 10.0.0.2")).await.unwrap();
+        hub.apply("QmVideo", seeder.peer_id, candidate(viewer.peer_id, "candidate:1 udp 10.0.0.3")).await.unwrap();
+        assert!(matches!(
+            next(&mut seeder).await,
+            SignalingMessage::IceCandidate { from, ref candidate, .. } if from == viewer.peer_id && candidate.ends_with("10.0.0.2")
+        ));
+        assert!(matches!(
+            next(&mut viewer).await,
+            SignalingMessage::IceCandidate { from, ref candidate, .. } if from == seeder.peer_id && candidate.ends_with("10.0.0.3")
+        ));
+
+        let stranger = Uuid::new_v4();
+        let result = hub.apply("QmVideo", viewer.peer_id, SignalingCommand::Offer { to: stranger, sdp: "v=0".to_string() }).await;
+        assert_eq!(result, Err(SignalingError::TargetNotFound(stranger)));
+    }
+
+    #[tokio::test]
+    async fn offers_are_rate_limited_per_peer() {
+        let hub = SignalingHub::new().with_offer_limit(2, Duration::from_millis(200));
+        let seeder = hub.join("QmVideo", Uuid::new_v4()).await.unwrap();
+        let viewer = hub.join("QmVideo", Uuid::new_v4()).await.unwrap();
+        let offer = || SignalingCommand::Offer { to: seeder.peer_id, sdp: "v=0".to_string() };
+
+        hub.apply("QmVideo", viewer.peer_id, offer()).await.unwrap();
+        hub.apply("QmVideo", viewer.peer_id, offer()).await.unwrap();
+        assert!(matches!(
+            hub.apply("QmVideo", viewer.peer_id, offer()).await,
+            Err(SignalingError::RateLimited { limit: 2, .. })
+        ));
+
+        // Candidates are not offers
+        let candidate = SignalingCommand::IceCandidate { to: seeder.peer_id, candidate: "candidate:1".to_string(), sdp_mid: None, sdp_mline_index: None };
+        hub.apply("QmVideo", viewer.peer_id, candidate).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        hub.apply("QmVideo", viewer.peer_id, offer()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn leaving_notifies_the_room_and_removes_empty_rooms() {
+        let hub = SignalingHub::new();
+        let mut seeder = hub.join("QmVideo", Uuid::new_v4()).await.unwrap();
+        next(&mut seeder).await;
+        let viewer = hub.join("QmVideo", Uuid::new_v4()).await.unwrap();
+        next(&mut seeder).await;
+
+        hub.leave("QmVideo", viewer.peer_id).await;
+        assert_eq!(next(&mut seeder).await, SignalingMessage::PeerLeft { peer_id: viewer.peer_id });
+        assert_eq!(hub.room_peers("QmVideo").await.len(), 1);
+
+        hub.leave("QmVideo", seeder.peer_id).await;
+        assert!(hub.rooms.read().await.is_empty());
+    }
+
+    #[test]
+    fn invalid_announcements_are_rejected() {
+        assert!(validate_ranges(&[range(0, 10), range(20, 30)]).is_ok());
+        assert!(matches!(validate_ranges(&[range(5, 5)]), Err(SignalingError::InvalidSignal(_))));
+        assert!(matches!(validate_payload(&"a".repeat(MAX_SIGNAL_PAYLOAD_BYTES + 1)), Err(SignalingError::InvalidSignal(_))));
+    }
+}
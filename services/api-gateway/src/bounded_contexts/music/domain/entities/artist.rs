@@ -1,16 +1,17 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc, Datelike};
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 use uuid::Uuid;
 
 use crate::bounded_contexts::music::domain::value_objects::{
     ArtistId, SongId, AlbumId, Genre, IpfsHash
 };
+use crate::bounded_contexts::music::domain::entities::social_link::{Platform, SocialLink, SocialLinkAdded};
 use crate::bounded_contexts::music::domain::events::{
     ArtistProfileUpdated, ArtistGenreAdded, ArtistGenreRemoved, ArtistVerified,
     ArtistFollowed, ArtistUnfollowed
 };
-use crate::shared::domain::events::{DomainEvent, EventMetadata};
+use crate::shared::domain::events::{correlation_id_or_new, DomainEvent, EventMetadata};
 
 /// Artist entity representing musicians and content creators
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -36,7 +37,7 @@ pub struct ArtistProfile {
     bio: Option<String>,
     location: Option<String>,
     website: Option<String>,
-    social_links: HashMap<String, String>,
+    social_links: Vec<SocialLink>,
     avatar_ipfs: Option<IpfsHash>,
     banner_ipfs: Option<IpfsHash>,
     debut_year: Option<u16>,
@@ -137,7 +138,7 @@ impl Artist {
             bio: None,
             location: None,
             website: None,
-            social_links: HashMap::new(),
+            social_links: Vec::new(),
             avatar_ipfs: None,
             banner_ipfs: None,
             debut_year: None,
@@ -318,7 +319,7 @@ impl Artist {
             aggregate_id: *self.id.value(),
             aggregate_type: "Artist".to_string(),
             occurred_at: self.updated_at,
-            correlation_id: None,
+            correlation_id: correlation_id_or_new(),
             user_id: Some(self.user_id),
             version: 1,
         };
@@ -344,29 +345,30 @@ impl Artist {
     }
 
     /// Add social media link
-    pub fn add_social_link(&mut self, platform: String, url: String) -> Result<(), String> {
-        if platform.trim().is_empty() {
-            return Err("Platform name cannot be empty".to_string());
-        }
-
-        if url.trim().is_empty() {
-            return Err("URL cannot be empty".to_string());
-        }
-
-        // Basic URL validation
-        if !url.starts_with("http://") && !url.starts_with("https://") {
-            return Err("URL must be a valid URL starting with http:// or https://".to_string());
-        }
-
-        self.profile.social_links.insert(platform.to_lowercase(), url);
+    pub fn add_social_link(&mut self, platform: String, url: String) -> Result<SocialLinkAdded, String> {
+        let platform = Platform::from_string(&platform)?;
+        let added = self.profile.add_social_link(SocialLink::new(platform, url)?)?;
         self.updated_at = Utc::now();
-        Ok(())
+        Ok(added)
     }
 
     /// Remove social media link
     pub fn remove_social_link(&mut self, platform: &str) {
-        self.profile.social_links.remove(&platform.to_lowercase());
+        if let Ok(platform) = Platform::from_string(platform) {
+            self.profile.social_links.retain(|link| link.platform != platform);
+            self.updated_at = Utc::now();
+        }
+    }
+
+    /// Mark a social link as verified once its challenge code was found on it
+    pub fn verify_social_link(&mut self, platform: Platform) -> Result<(), String> {
+        let link = self.profile.social_links
+            .iter_mut()
+            .find(|link| link.platform == platform)
+            .ok_or_else(|| format!("No {} link on the profile", platform))?;
+        link.verified = true;
         self.updated_at = Utc::now();
+        Ok(())
     }
 
     /// Add primary genre
@@ -388,7 +390,7 @@ impl Artist {
             aggregate_id: *self.id.value(),
             aggregate_type: "Artist".to_string(),
             occurred_at: self.updated_at,
-            correlation_id: None,
+            correlation_id: correlation_id_or_new(),
             user_id: Some(self.user_id),
             version: 1,
         };
@@ -421,7 +423,7 @@ impl Artist {
             aggregate_id: *self.id.value(),
             aggregate_type: "Artist".to_string(),
             occurred_at: self.updated_at,
-            correlation_id: None,
+            correlation_id: correlation_id_or_new(),
             user_id: Some(self.user_id),
             version: 1,
         };
@@ -453,7 +455,7 @@ impl Artist {
             aggregate_id: *self.id.value(),
             aggregate_type: "Artist".to_string(),
             occurred_at: self.updated_at,
-            correlation_id: None,
+            correlation_id: correlation_id_or_new(),
             user_id: Some(self.user_id),
             version: 1,
         };
@@ -572,7 +574,7 @@ impl Artist {
             aggregate_id: *self.id.value(),
             aggregate_type: "Artist".to_string(),
             occurred_at: self.updated_at,
-            correlation_id: None,
+            correlation_id: correlation_id_or_new(),
             user_id: Some(follower_id),
             version: 1,
         };
@@ -597,7 +599,7 @@ impl Artist {
                 aggregate_id: *self.id.value(),
                 aggregate_type: "Artist".to_string(),
                 occurred_at: self.updated_at,
-                correlation_id: None,
+                correlation_id: correlation_id_or_new(),
                 user_id: Some(follower_id),
                 version: 1,
             };
@@ -679,10 +681,30 @@ impl ArtistProfile {
         self.website.as_deref()
     }
 
-    pub fn social_links(&self) -> &HashMap<String, String> {
+    pub fn social_links(&self) -> &[SocialLink] {
         &self.social_links
     }
 
+    pub fn social_link(&self, platform: Platform) -> Option<&SocialLink> {
+        self.social_links.iter().find(|link| link.platform == platform)
+    }
+
+    /// Add or replace the profile's link for the platform. The URL is checked
+    /// again against the platform format, and a replaced link loses its
+    /// verification since it may point to someone else's profile.
+    pub fn add_social_link(&mut self, link: SocialLink) -> Result<SocialLinkAdded, String> {
+        link.platform.validate_url(&link.url)?;
+        let link = SocialLink { verified: false, ..link };
+
+        let replaced = self.social_links
+            .iter()
+            .position(|existing| existing.platform == link.platform)
+            .map(|index| self.social_links.remove(index));
+        self.social_links.push(link.clone());
+
+        Ok(SocialLinkAdded { link, replaced })
+    }
+
     pub fn avatar_ipfs(&self) -> Option<&IpfsHash> {
         self.avatar_ipfs.as_ref()
     }
@@ -846,10 +868,30 @@ mod tests {
             "https://twitter.com/jazzartist".to_string(),
         );
         assert!(result.is_ok());
-        assert!(artist.profile().social_links().contains_key("twitter"));
+        assert!(artist.profile().social_link(Platform::Twitter).is_some());
 
         // Remove social link
         artist.remove_social_link("twitter");
-        assert!(!artist.profile().social_links().contains_key("twitter"));
+        assert!(artist.profile().social_link(Platform::Twitter).is_none());
+    }
+
+    #[test]
+    fn test_social_link_replacement_resets_verification() {
+        let genre = Genre::new("jazz".to_string()).unwrap();
+        let mut artist = Artist::new(Uuid::new_v4(), "Jazz Artist".to_string(), genre).unwrap();
+
+        artist.add_social_link("instagram".to_string(), "https://instagram.com/jazzartist".to_string()).unwrap();
+        artist.verify_social_link(Platform::Instagram).unwrap();
+        assert!(artist.profile().social_link(Platform::Instagram).unwrap().verified);
+
+        let added = artist
+            .add_social_link("instagram".to_string(), "https://instagram.com/jazz.artist.official".to_string())
+            .unwrap();
+        assert_eq!(added.replaced.unwrap().url, "https://instagram.com/jazzartist");
+        assert_eq!(artist.profile().social_links().len(), 1);
+        assert!(!artist.profile().social_link(Platform::Instagram).unwrap().verified);
+
+        // Wrong format for the platform
+        assert!(artist.add_social_link("tiktok".to_string(), "https://instagram.com/jazzartist".to_string()).is_err());
     }
 } 
\ No newline at end of file
@@ -3,10 +3,14 @@ pub mod album;
 pub mod playlist;
 pub mod artist;
 pub mod genre_stats;
+pub mod remix_license;
+pub mod social_link;
 
 // Re-export main entities and value objects
-pub use song::{Song, SongMetadata};
+pub use song::{Song, SongMetadata, SongError};
 pub use album::{Album, AlbumTrack};
 pub use playlist::{Playlist, PlaylistTrack};
 pub use artist::{Artist, ArtistProfile, ArtistStats, ArtistTier};
-pub use genre_stats::{GenreStats}; 
\ No newline at end of file
+pub use genre_stats::{GenreStats};
+pub use remix_license::{RemixLicense, RemixLicenseType, RemixLicenseError, RemixLicensePricing};
+pub use social_link::{Platform, SocialLink, SocialLinkAdded}; 
\ No newline at end of file
@@ -0,0 +1,308 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use uuid::Uuid;
+
+use crate::bounded_contexts::music::domain::entities::Song;
+use crate::bounded_contexts::music::domain::events::RemixLicensePurchased;
+use crate::bounded_contexts::music::domain::value_objects::{IpfsHash, SongId};
+use crate::shared::domain::events::EventMetadata;
+
+/// Errors raised by remix license rules
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum RemixLicenseError {
+    #[error("License price must be positive, got {price}")]
+    InvalidPrice { price: f64 },
+    #[error("License expiry {expiry} is not in the future")]
+    InvalidExpiry { expiry: DateTime<Utc> },
+    #[error("Song {song_id} is exclusively licensed until {expiry}")]
+    ExclusivelyLicensed { song_id: Uuid, expiry: DateTime<Utc> },
+    #[error("Song {song_id} already has active remix licenses and cannot be licensed exclusively")]
+    AlreadyLicensed { song_id: Uuid },
+    #[error("Remix license {license_id} expired at {expiry}")]
+    Expired { license_id: Uuid, expiry: DateTime<Utc> },
+    #[error("Song {song_id} has no stems available")]
+    NoStems { song_id: Uuid },
+    #[error("Remix license {license_id} does not cover song {song_id}")]
+    WrongSong { license_id: Uuid, song_id: Uuid },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RemixLicenseType {
+    /// Any number of producers can remix the song
+    Standard,
+    /// Only this licensee can remix the song while the license lasts
+    Exclusive,
+}
+
+impl RemixLicenseType {
+    /// Term used when the buyer does not ask for a specific expiry
+    pub fn default_term(&self) -> Duration {
+        match self {
+            RemixLicenseType::Standard => Duration::days(365),
+            RemixLicenseType::Exclusive => Duration::days(180),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RemixLicenseType::Standard => "standard",
+            RemixLicenseType::Exclusive => "exclusive",
+        }
+    }
+
+    pub fn from_string(value: &str) -> Result<Self, String> {
+        match value.to_lowercase().as_str() {
+            "standard" => Ok(RemixLicenseType::Standard),
+            "exclusive" => Ok(RemixLicenseType::Exclusive),
+            other => Err(format!("Unknown remix license type: {}", other)),
+        }
+    }
+}
+
+impl fmt::Display for RemixLicenseType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Catalogue price of each license type
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemixLicensePricing {
+    pub standard: f64,
+    pub exclusive: f64,
+}
+
+impl Default for RemixLicensePricing {
+    fn default() -> Self {
+        Self { standard: 49.0, exclusive: 499.0 }
+    }
+}
+
+impl RemixLicensePricing {
+    /// `REMIX_LICENSE_STANDARD_PRICE` / `REMIX_LICENSE_EXCLUSIVE_PRICE`, in the base currency
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let price = |key: &str, default: f64| {
+            std::env::var(key).ok().and_then(|v| v.parse().ok()).unwrap_or(default)
+        };
+        Self {
+            standard: price("REMIX_LICENSE_STANDARD_PRICE", defaults.standard),
+            exclusive: price("REMIX_LICENSE_EXCLUSIVE_PRICE", defaults.exclusive),
+        }
+    }
+
+    pub fn price_for(&self, license_type: RemixLicenseType) -> f64 {
+        match license_type {
+            RemixLicenseType::Standard => self.standard,
+            RemixLicenseType::Exclusive => self.exclusive,
+        }
+    }
+}
+
+/// Right of a producer to remix a song until `expiry`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RemixLicense {
+    pub id: Uuid,
+    pub song_id: SongId,
+    pub licensee_id: Uuid,
+    pub license_type: RemixLicenseType,
+    pub price: f64,
+    pub expiry: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RemixLicense {
+    /// License `song` to `licensee_id`. `existing` are the song's licenses:
+    /// an active exclusive license blocks any other, and an exclusive license
+    /// can only be sold while nobody else holds an active one.
+    pub fn purchase(
+        song: &Song,
+        licensee_id: Uuid,
+        license_type: RemixLicenseType,
+        price: f64,
+        expiry: Option<DateTime<Utc>>,
+        existing: &[RemixLicense],
+        now: DateTime<Utc>,
+    ) -> Result<(Self, RemixLicensePurchased), RemixLicenseError> {
+        if !price.is_finite() || price <= 0.0 {
+            return Err(RemixLicenseError::InvalidPrice { price });
+        }
+        let expiry = expiry.unwrap_or_else(|| now + license_type.default_term());
+        if expiry <= now {
+            return Err(RemixLicenseError::InvalidExpiry { expiry });
+        }
+
+        let song_id = song.id().to_uuid();
+        let mut active = existing
+            .iter()
+            .filter(|license| license.song_id == *song.id() && !license.is_expired(now));
+        if let Some(exclusive) = active.clone().find(|l| l.license_type == RemixLicenseType::Exclusive) {
+            return Err(RemixLicenseError::ExclusivelyLicensed { song_id, expiry: exclusive.expiry });
+        }
+        if license_type == RemixLicenseType::Exclusive && active.any(|l| l.licensee_id != licensee_id) {
+            return Err(RemixLicenseError::AlreadyLicensed { song_id });
+        }
+
+        let license = Self {
+            id: Uuid::new_v4(),
+            song_id: song.id().clone(),
+            licensee_id,
+            license_type,
+            price,
+            expiry,
+            created_at: now,
+        };
+        let event = RemixLicensePurchased {
+            metadata: EventMetadata::with_type_and_aggregate("RemixLicensePurchased", song_id, "Song"),
+            license_id: license.id,
+            song_id: song.id().clone(),
+            artist_id: song.artist_id().clone(),
+            licensee_id,
+            license_type,
+            price,
+            expiry,
+            purchased_at: now,
+        };
+        Ok((license, event))
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expiry
+    }
+
+    /// Stems archive the licensee may download right now
+    pub fn authorize_stem_download<'a>(&self, song: &'a Song, now: DateTime<Utc>) -> Result<&'a IpfsHash, RemixLicenseError> {
+        if self.song_id != *song.id() {
+            return Err(RemixLicenseError::WrongSong {
+                license_id: self.id,
+                song_id: song.id().to_uuid(),
+            });
+        }
+        if self.is_expired(now) {
+            return Err(RemixLicenseError::Expired { license_id: self.id, expiry: self.expiry });
+        }
+        song.stems_ipfs_hash()
+            .filter(|_| song.has_stems())
+            .ok_or(RemixLicenseError::NoStems { song_id: song.id().to_uuid() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::music::domain::value_objects::{
+        ArtistId, Genre, RoyaltyPercentage, SongDuration, SongTitle,
+    };
+
+    fn song_with_stems() -> Song {
+        let mut song = Song::new(
+            SongTitle::new("Night Drive".to_string()).unwrap(),
+            ArtistId::new(),
+            SongDuration::new(210).unwrap(),
+            Genre::new("electronic".to_string()).unwrap(),
+            RoyaltyPercentage::new(70.0).unwrap(),
+        );
+        song.set_stems(IpfsHash::new("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_string()).unwrap());
+        song
+    }
+
+    fn purchase(song: &Song, license_type: RemixLicenseType, existing: &[RemixLicense], now: DateTime<Utc>) -> Result<RemixLicense, RemixLicenseError> {
+        RemixLicense::purchase(song, Uuid::new_v4(), license_type, 49.0, None, existing, now).map(|(license, _)| license)
+    }
+
+    #[test]
+    fn purchase_emits_event_for_the_artist() {
+        let song = song_with_stems();
+        let now = Utc::now();
+        let licensee = Uuid::new_v4();
+
+        let (license, event) = RemixLicense::purchase(&song, licensee, RemixLicenseType::Standard, 49.0, None, &[], now).unwrap();
+
+        assert_eq!(license.expiry, now + Duration::days(365));
+        assert_eq!(event.license_id, license.id);
+        assert_eq!(event.artist_id, *song.artist_id());
+        assert_eq!(event.licensee_id, licensee);
+        assert_eq!(event.price, 49.0);
+    }
+
+    #[test]
+    fn stems_are_available_until_the_license_expires() {
+        let song = song_with_stems();
+        let now = Utc::now();
+        let license = purchase(&song, RemixLicenseType::Standard, &[], now).unwrap();
+
+        assert_eq!(license.authorize_stem_download(&song, now).unwrap(), song.stems_ipfs_hash().unwrap());
+
+        let after_expiry = license.expiry + Duration::seconds(1);
+        assert!(matches!(
+            license.authorize_stem_download(&song, after_expiry),
+            Err(RemixLicenseError::Expired { .. })
+        ));
+        // Justo en el instante de expiración ya no vale
+        assert!(license.authorize_stem_download(&song, license.expiry).is_err());
+    }
+
+    #[test]
+    fn expired_license_is_rejected_even_with_stems() {
+        let song = song_with_stems();
+        let bought = Utc::now() - Duration::days(400);
+        let license = purchase(&song, RemixLicenseType::Standard, &[], bought).unwrap();
+
+        assert!(license.is_expired(Utc::now()));
+        assert!(matches!(
+            license.authorize_stem_download(&song, Utc::now()),
+            Err(RemixLicenseError::Expired { .. })
+        ));
+    }
+
+    #[test]
+    fn license_for_another_song_or_without_stems_is_rejected() {
+        let song = song_with_stems();
+        let now = Utc::now();
+        let license = purchase(&song, RemixLicenseType::Standard, &[], now).unwrap();
+
+        let other = song_with_stems();
+        assert!(matches!(license.authorize_stem_download(&other, now), Err(RemixLicenseError::WrongSong { .. })));
+
+        let mut without_stems = song.clone();
+        without_stems.remove_stems();
+        assert!(matches!(license.authorize_stem_download(&without_stems, now), Err(RemixLicenseError::NoStems { .. })));
+    }
+
+    #[test]
+    fn exclusive_license_blocks_other_licensees() {
+        let song = song_with_stems();
+        let now = Utc::now();
+
+        let standard = purchase(&song, RemixLicenseType::Standard, &[], now).unwrap();
+        assert!(matches!(
+            purchase(&song, RemixLicenseType::Exclusive, &[standard.clone()], now),
+            Err(RemixLicenseError::AlreadyLicensed { .. })
+        ));
+
+        let exclusive = purchase(&song, RemixLicenseType::Exclusive, &[], now).unwrap();
+        assert!(matches!(
+            purchase(&song, RemixLicenseType::Standard, &[exclusive.clone()], now),
+            Err(RemixLicenseError::ExclusivelyLicensed { .. })
+        ));
+
+        // Una exclusiva vencida ya no bloquea
+        let later = exclusive.expiry + Duration::days(1);
+        assert!(purchase(&song, RemixLicenseType::Standard, &[exclusive], later).is_ok());
+    }
+
+    #[test]
+    fn price_and_expiry_are_validated() {
+        let song = song_with_stems();
+        let now = Utc::now();
+        assert!(matches!(
+            RemixLicense::purchase(&song, Uuid::new_v4(), RemixLicenseType::Standard, 0.0, None, &[], now),
+            Err(RemixLicenseError::InvalidPrice { .. })
+        ));
+        assert!(matches!(
+            RemixLicense::purchase(&song, Uuid::new_v4(), RemixLicenseType::Standard, 10.0, Some(now - Duration::days(1)), &[], now),
+            Err(RemixLicenseError::InvalidExpiry { .. })
+        ));
+    }
+}
@@ -0,0 +1,137 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Social platforms an artist can link from their profile
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Platform {
+    Twitter,
+    Instagram,
+    YouTube,
+    TikTok,
+    Website,
+}
+
+impl Platform {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Platform::Twitter => "twitter",
+            Platform::Instagram => "instagram",
+            Platform::YouTube => "youtube",
+            Platform::TikTok => "tiktok",
+            Platform::Website => "website",
+        }
+    }
+
+    pub fn from_string(value: &str) -> Result<Self, String> {
+        match value.trim().to_lowercase().as_str() {
+            "twitter" | "x" => Ok(Platform::Twitter),
+            "instagram" => Ok(Platform::Instagram),
+            "youtube" => Ok(Platform::YouTube),
+            "tiktok" => Ok(Platform::TikTok),
+            "website" => Ok(Platform::Website),
+            other => Err(format!("Unknown social platform: {}", other)),
+        }
+    }
+
+    /// Profile URL shape accepted for the platform. Only https, and only
+    /// profile pages (not posts), since that is where the challenge is posted.
+    fn url_pattern(&self) -> &'static str {
+        match self {
+            Platform::Twitter => r"^https://(www\.)?(twitter|x)\.com/[A-Za-z0-9_]{1,15}/?$",
+            Platform::Instagram => r"^https://(www\.)?instagram\.com/[A-Za-z0-9_.]{1,30}/?$",
+            Platform::YouTube => {
+                r"^https://(www\.|m\.)?youtube\.com/(@[A-Za-z0-9_.\-]{3,30}|channel/UC[A-Za-z0-9_\-]{22}|c/[A-Za-z0-9_.\-]+|user/[A-Za-z0-9_.\-]+)/?$"
+            }
+            Platform::TikTok => r"^https://(www\.)?tiktok\.com/@[A-Za-z0-9_.]{2,24}/?$",
+            Platform::Website => r"^https?://([A-Za-z0-9\-]+\.)+[A-Za-z]{2,}(:\d+)?(/\S*)?$",
+        }
+    }
+
+    pub fn validate_url(&self, url: &str) -> Result<(), String> {
+        let pattern = Regex::new(self.url_pattern()).expect("valid social link pattern");
+        if pattern.is_match(url.trim()) {
+            Ok(())
+        } else {
+            Err(format!("{} is not a valid {} profile URL", url, self))
+        }
+    }
+}
+
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Link from an artist profile to one of their social profiles
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SocialLink {
+    pub platform: Platform,
+    pub url: String,
+    /// The artist proved ownership by posting their challenge code on it
+    pub verified: bool,
+}
+
+impl SocialLink {
+    /// Unverified link, validated against the platform's URL format
+    pub fn new(platform: Platform, url: String) -> Result<Self, String> {
+        let url = url.trim().to_string();
+        platform.validate_url(&url)?;
+        Ok(Self { platform, url, verified: false })
+    }
+}
+
+/// Outcome of `ArtistProfile::add_social_link`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SocialLinkAdded {
+    pub link: SocialLink,
+    /// Link the new one replaced (one link per platform)
+    pub replaced: Option<SocialLink>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_profile_urls_of_each_platform() {
+        let valid = [
+            (Platform::Twitter, "https://twitter.com/jazzartist"),
+            (Platform::Twitter, "https://x.com/jazz_artist/"),
+            (Platform::Instagram, "https://www.instagram.com/jazz.artist"),
+            (Platform::YouTube, "https://www.youtube.com/@jazzartist"),
+            (Platform::YouTube, "https://youtube.com/channel/UC1234567890abcdefghijKL"),
+            (Platform::TikTok, "https://www.tiktok.com/@jazzartist"),
+            (Platform::Website, "https://jazzartist.music/about"),
+        ];
+        for (platform, url) in valid {
+            assert!(SocialLink::new(platform, url.to_string()).is_ok(), "{} should be valid", url);
+        }
+    }
+
+    #[test]
+    fn rejects_urls_of_other_sites_or_shapes() {
+        let invalid = [
+            (Platform::Twitter, "https://instagram.com/jazzartist"),
+            (Platform::Twitter, "http://twitter.com/jazzartist"),
+            (Platform::Twitter, "https://twitter.com/jazzartist/status/123"),
+            (Platform::Instagram, "https://instagram.com/"),
+            (Platform::YouTube, "https://youtube.com/watch?v=abc"),
+            (Platform::TikTok, "https://tiktok.com/jazzartist"),
+            (Platform::Website, "not a url"),
+        ];
+        for (platform, url) in invalid {
+            assert!(SocialLink::new(platform, url.to_string()).is_err(), "{} should be rejected", url);
+        }
+    }
+
+    #[test]
+    fn parses_platform_names() {
+        assert_eq!(Platform::from_string("Twitter").unwrap(), Platform::Twitter);
+        assert_eq!(Platform::from_string("x").unwrap(), Platform::Twitter);
+        assert_eq!(Platform::from_string("YouTube").unwrap(), Platform::YouTube);
+        assert!(Platform::from_string("myspace").is_err());
+    }
+}
@@ -4,10 +4,20 @@ use uuid::Uuid;
 
 use crate::bounded_contexts::music::domain::{
     events::*,
+    services::{ContentModerationService, ModerationError, ModerationResult},
     value_objects::*,
 };
 use crate::shared::domain::events::DomainEvent;
 
+/// Errors raised by song invariants
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum SongError {
+    #[error("Total royalty shares would be {total}%, which exceeds 100%")]
+    RoyaltySharesExceeded { total: f64 },
+    #[error("Contributor {contributor_id} is already credited as {credit_type}")]
+    DuplicateCredit { contributor_id: Uuid, credit_type: CreditType },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Song {
     id: SongId,
@@ -19,13 +29,31 @@ pub struct Song {
     file_format: Option<FileFormat>,
     audio_quality: Option<AudioQuality>,
     tempo: Option<Tempo>,
+    /// Audio features (0.0-1.0) from `AudioMetadataExtractor`
+    #[serde(default)]
+    valence: Option<f32>,
+    #[serde(default)]
+    energy: Option<f32>,
     release_type: Option<ReleaseType>,
     ipfs_hash: Option<IpfsHash>,
+    /// Individual stems (drums, bass, vocals...) available for remix licenses
+    #[serde(default)]
+    has_stems: bool,
+    #[serde(default)]
+    stems_ipfs_hash: Option<IpfsHash>,
     royalty_percentage: RoyaltyPercentage,
     listen_count: ListenCount,
     revenue_generated: f64,
     is_available_for_campaign: bool,
     is_available_for_ownership: bool,
+    #[serde(default)]
+    credits: Vec<SongCredit>,
+    /// Metadata flagged by content moderation; hidden until a moderator reviews it
+    #[serde(default)]
+    is_pending_review: bool,
+    /// Sections detected from the audio; `None` until it has been analysed
+    #[serde(default)]
+    chapter_markers: Option<Vec<ChapterMarker>>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
 }
@@ -37,10 +65,22 @@ impl Song {
         duration: SongDuration,
         genre: Genre,
         royalty_percentage: RoyaltyPercentage,
+    ) -> Self {
+        Self::with_id(SongId::new(), title, artist_id, duration, genre, royalty_percentage)
+    }
+
+    /// Create song with specific ID (for loading from repository)
+    pub fn with_id(
+        id: SongId,
+        title: SongTitle,
+        artist_id: ArtistId,
+        duration: SongDuration,
+        genre: Genre,
+        royalty_percentage: RoyaltyPercentage,
     ) -> Self {
         let now = Utc::now();
         Self {
-            id: SongId::new(),
+            id,
             title,
             artist_id,
             duration,
@@ -49,13 +89,20 @@ impl Song {
             file_format: None,
             audio_quality: None,
             tempo: None,
+            valence: None,
+            energy: None,
             release_type: None,
             ipfs_hash: None,
+            has_stems: false,
+            stems_ipfs_hash: None,
             royalty_percentage,
             listen_count: ListenCount::new(),
             revenue_generated: 0.0,
             is_available_for_campaign: false,
             is_available_for_ownership: false,
+            credits: Vec::new(),
+            is_pending_review: false,
+            chapter_markers: None,
             created_at: now,
             updated_at: now,
         }
@@ -98,6 +145,37 @@ impl Song {
         self.tempo.as_ref()
     }
 
+    pub fn valence(&self) -> Option<f32> {
+        self.valence
+    }
+
+    pub fn energy(&self) -> Option<f32> {
+        self.energy
+    }
+
+    /// Mood implied by the audio features, whatever mood was tagged.
+    /// Loudness is not kept on the song, so a streaming-normalized -14 LUFS is assumed.
+    pub fn detected_mood(&self) -> Option<SongMood> {
+        let (valence, energy) = (self.valence?, self.energy?);
+        Some(SongMood::detect_from_audio_features(&self.tempo_or_default(), NORMALIZED_LOUDNESS_LUFS, valence, energy))
+    }
+
+    /// Point of the song on the mood circumplex: from its audio features when
+    /// analysed, otherwise from its tagged mood
+    pub fn circumplex_position(&self) -> Option<(f32, f32)> {
+        match (self.valence, self.energy) {
+            (Some(valence), Some(energy)) => Some((
+                valence,
+                SongMood::arousal(&self.tempo_or_default(), NORMALIZED_LOUDNESS_LUFS, energy),
+            )),
+            _ => self.mood.as_ref().map(SongMood::circumplex_position),
+        }
+    }
+
+    fn tempo_or_default(&self) -> Tempo {
+        self.tempo.clone().unwrap_or_else(|| Tempo::new(120).expect("120 BPM is a valid tempo"))
+    }
+
     pub fn release_type(&self) -> Option<&ReleaseType> {
         self.release_type.as_ref()
     }
@@ -106,6 +184,18 @@ impl Song {
         self.ipfs_hash.as_ref()
     }
 
+    pub fn has_stems(&self) -> bool {
+        self.has_stems
+    }
+
+    pub fn stems_ipfs_hash(&self) -> Option<&IpfsHash> {
+        self.stems_ipfs_hash.as_ref()
+    }
+
+    pub fn chapter_markers(&self) -> Option<&[ChapterMarker]> {
+        self.chapter_markers.as_deref()
+    }
+
     pub fn royalty_percentage(&self) -> &RoyaltyPercentage {
         &self.royalty_percentage
     }
@@ -136,6 +226,24 @@ impl Song {
         self.is_available_for_ownership
     }
 
+    pub fn credits(&self) -> &[SongCredit] {
+        &self.credits
+    }
+
+    pub fn is_pending_review(&self) -> bool {
+        self.is_pending_review
+    }
+
+    pub fn set_pending_review(&mut self, pending: bool) {
+        self.is_pending_review = pending;
+        self.updated_at = Utc::now();
+    }
+
+    /// Sum of royalty shares (0-100) assigned through credits
+    pub fn total_credited_share(&self) -> f64 {
+        self.credits.iter().map(|c| c.royalty_share.value()).sum()
+    }
+
     pub fn created_at(&self) -> DateTime<Utc> {
         self.created_at
     }
@@ -218,11 +326,134 @@ impl Song {
         total_revenue * self.royalty_percentage.as_decimal()
     }
 
+    /// Credit a contributor. Shares across all credits can never exceed 100%.
+    pub fn add_credit(&mut self, credit: SongCredit) -> Result<CreditAdded, SongError> {
+        if self.credits.iter().any(|c| c.contributor_id == credit.contributor_id && c.credit_type == credit.credit_type) {
+            return Err(SongError::DuplicateCredit {
+                contributor_id: credit.contributor_id,
+                credit_type: credit.credit_type,
+            });
+        }
+
+        let total = self.total_credited_share() + credit.royalty_share.value();
+        if total > 100.0 + 1e-9 {
+            return Err(SongError::RoyaltySharesExceeded { total });
+        }
+
+        let event = CreditAdded {
+            song_id: self.id.clone(),
+            contributor_id: credit.contributor_id,
+            credit_type: credit.credit_type.clone(),
+            royalty_share: credit.royalty_share.value(),
+            total_credited_share: total,
+            added_at: Utc::now(),
+            metadata: crate::shared::domain::events::EventMetadata::with_type_and_aggregate(
+                "CreditAdded",
+                self.id.to_uuid(),
+                "Song",
+            ),
+        };
+
+        self.credits.push(credit);
+        self.updated_at = Utc::now();
+        Ok(event)
+    }
+
+    /// Restore persisted credits, re-validating the 100% cap
+    pub fn set_credits(&mut self, credits: Vec<SongCredit>) -> Result<(), SongError> {
+        self.credits.clear();
+        for credit in credits {
+            self.add_credit(credit)?;
+        }
+        Ok(())
+    }
+
+    pub fn set_mood(&mut self, mood: SongMood) {
+        self.mood = Some(mood);
+        self.updated_at = Utc::now();
+    }
+
+    pub fn set_tempo(&mut self, tempo: Tempo) {
+        self.tempo = Some(tempo);
+        self.updated_at = Utc::now();
+    }
+
+    pub fn set_audio_quality(&mut self, audio_quality: AudioQuality) {
+        self.audio_quality = Some(audio_quality);
+        self.updated_at = Utc::now();
+    }
+
+    /// Valence and energy from audio analysis, both 0.0-1.0. A song without a
+    /// tagged mood takes the detected one.
+    pub fn set_audio_features(&mut self, valence: f32, energy: f32) -> Result<(), String> {
+        for (name, value) in [("valence", valence), ("energy", energy)] {
+            if !(0.0..=1.0).contains(&value) {
+                return Err(format!("{} must be between 0.0 and 1.0, got {}", name, value));
+            }
+        }
+        self.valence = Some(valence);
+        self.energy = Some(energy);
+        if self.mood.is_none() {
+            self.mood = self.detected_mood();
+        }
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
     pub fn set_ipfs_hash(&mut self, ipfs_hash: IpfsHash) {
         self.ipfs_hash = Some(ipfs_hash);
         self.updated_at = Utc::now();
     }
 
+    /// Archive with the song's individual stems
+    pub fn set_stems(&mut self, stems_ipfs_hash: IpfsHash) {
+        self.has_stems = true;
+        self.stems_ipfs_hash = Some(stems_ipfs_hash);
+        self.updated_at = Utc::now();
+    }
+
+    pub fn remove_stems(&mut self) {
+        self.has_stems = false;
+        self.stems_ipfs_hash = None;
+        self.updated_at = Utc::now();
+    }
+
+    /// Markers come from the uploaded audio, whose length may differ from the
+    /// declared duration, so only their order is checked
+    pub fn set_chapter_markers(&mut self, markers: Vec<ChapterMarker>) -> Result<(), String> {
+        if markers.windows(2).any(|pair| pair[1].position_ms <= pair[0].position_ms) {
+            return Err("Chapter markers must be in ascending order".to_string());
+        }
+        self.chapter_markers = Some(markers);
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    /// Scan the title and description for policy violations before the song
+    /// is made public. A flagged song is held for review; if the provider
+    /// fails the song is not held and the error is returned for logging.
+    pub async fn explicit_content_scan(
+        &mut self,
+        moderation: &dyn ContentModerationService,
+        description: Option<&str>,
+    ) -> Result<ModerationResult, ModerationError> {
+        let text = match description.map(str::trim).filter(|d| !d.is_empty()) {
+            Some(description) => format!("{}\n\n{}", self.title.value(), description),
+            None => self.title.value().to_string(),
+        };
+
+        match moderation.scan_text(&text).await {
+            Ok(result) => {
+                self.set_pending_review(result.is_flagged);
+                Ok(result)
+            }
+            Err(e) => {
+                self.set_pending_review(false);
+                Err(e)
+            }
+        }
+    }
+
     pub fn update_title(&mut self, new_title: SongTitle) -> Result<(), String> {
         // Domain rule: Can't change title if song has significant listens
         if self.listen_count.value() > 1000 {
@@ -276,6 +507,9 @@ impl Song {
     }
 }
 
+/// Loudness assumed for songs analysed before playback normalization
+const NORMALIZED_LOUDNESS_LUFS: f32 = -14.0;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SongMetadata {
     pub id: SongId,
@@ -363,6 +597,88 @@ mod tests {
         assert_eq!(artist_revenue, 700.0); // 70% royalty
     }
 
+    fn credit(share: f64, credit_type: CreditType) -> SongCredit {
+        SongCredit::new(Uuid::new_v4(), credit_type, RoyaltyPercentage::new(share).unwrap())
+    }
+
+    #[test]
+    fn test_add_credit_within_cap() {
+        let mut song = create_test_song();
+        let event = song.add_credit(credit(60.0, CreditType::MainArtist)).unwrap();
+        assert_eq!(event.total_credited_share, 60.0);
+
+        song.add_credit(credit(25.0, CreditType::FeaturedArtist)).unwrap();
+        song.add_credit(credit(15.0, CreditType::Producer)).unwrap();
+        assert_eq!(song.credits().len(), 3);
+        assert_eq!(song.total_credited_share(), 100.0);
+    }
+
+    #[test]
+    fn test_add_credit_rejects_over_100_percent() {
+        let mut song = create_test_song();
+        song.add_credit(credit(70.0, CreditType::MainArtist)).unwrap();
+        song.add_credit(credit(30.0, CreditType::Songwriter)).unwrap();
+
+        let result = song.add_credit(credit(0.5, CreditType::Remixer));
+        assert!(matches!(result, Err(SongError::RoyaltySharesExceeded { .. })));
+        assert_eq!(song.credits().len(), 2);
+    }
+
+    #[test]
+    fn test_duplicate_credit_rejected() {
+        let mut song = create_test_song();
+        let producer = credit(10.0, CreditType::Producer);
+        song.add_credit(producer.clone()).unwrap();
+        assert!(matches!(song.add_credit(producer), Err(SongError::DuplicateCredit { .. })));
+    }
+
+    #[test]
+    fn test_audio_features_fill_in_missing_mood() {
+        let mut song = create_test_song();
+        song.set_tempo(Tempo::new(128).unwrap());
+        song.set_audio_features(0.2, 0.9).unwrap();
+        assert_eq!(song.mood(), Some(&SongMood::Aggressive));
+        assert_eq!(song.detected_mood(), Some(SongMood::Aggressive));
+
+        // Un mood etiquetado no se sobreescribe, pero el detectado sigue disponible
+        let mut tagged = create_test_song();
+        tagged.set_mood(SongMood::Romantic);
+        tagged.set_audio_features(0.8, 0.1).unwrap();
+        assert_eq!(tagged.mood(), Some(&SongMood::Romantic));
+        assert_eq!(tagged.detected_mood(), Some(SongMood::Calm));
+
+        assert!(song.set_audio_features(1.2, 0.5).is_err());
+        assert!(song.set_audio_features(0.5, f32::NAN).is_err());
+    }
+
+    #[test]
+    fn test_stems_flag_follows_archive() {
+        let mut song = create_test_song();
+        assert!(!song.has_stems());
+
+        song.set_stems(IpfsHash::new("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_string()).unwrap());
+        assert!(song.has_stems());
+        assert!(song.stems_ipfs_hash().is_some());
+
+        song.remove_stems();
+        assert!(!song.has_stems());
+        assert!(song.stems_ipfs_hash().is_none());
+    }
+
+    #[test]
+    fn test_chapter_markers_must_be_ascending() {
+        let mut song = create_test_song();
+        assert!(song.chapter_markers().is_none());
+
+        let markers = vec![ChapterMarker::new(0, "intro"), ChapterMarker::new(12_000, "verse")];
+        song.set_chapter_markers(markers.clone()).unwrap();
+        assert_eq!(song.chapter_markers(), Some(markers.as_slice()));
+
+        let unordered = vec![ChapterMarker::new(12_000, "verse"), ChapterMarker::new(12_000, "chorus")];
+        assert!(song.set_chapter_markers(unordered).is_err());
+        assert_eq!(song.chapter_markers(), Some(markers.as_slice()));
+    }
+
     #[test]
     fn test_title_update_restrictions() {
         let mut song = create_test_song();
@@ -380,4 +696,42 @@ mod tests {
         let another_title = SongTitle::new("Another Title".to_string()).unwrap();
         assert!(song.update_title(another_title).is_err());
     }
+
+    struct FixedModeration(Result<ModerationResult, ModerationError>, std::sync::Mutex<Vec<String>>);
+
+    #[async_trait::async_trait]
+    impl ContentModerationService for FixedModeration {
+        async fn scan_text(&self, text: &str) -> Result<ModerationResult, ModerationError> {
+            self.1.lock().unwrap().push(text.to_string());
+            self.0.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flagged_metadata_holds_song_for_review() {
+        let mut song = create_test_song();
+        let moderation = FixedModeration(
+            Ok(ModerationResult { is_flagged: true, categories: vec!["hate".to_string()], confidence: 0.93 }),
+            Default::default(),
+        );
+
+        let result = song.explicit_content_scan(&moderation, Some("  liner notes ")).await.unwrap();
+
+        assert!(result.is_flagged);
+        assert!(song.is_pending_review());
+        assert_eq!(moderation.1.lock().unwrap().as_slice(), ["Test Song\n\nliner notes"]);
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_moderation_does_not_hold_song() {
+        let mut song = create_test_song();
+        song.set_pending_review(true);
+        let moderation = FixedModeration(Err(ModerationError::ServiceUnavailable("timeout".to_string())), Default::default());
+
+        let error = song.explicit_content_scan(&moderation, None).await.unwrap_err();
+
+        assert_eq!(error, ModerationError::ServiceUnavailable("timeout".to_string()));
+        assert!(!song.is_pending_review());
+        assert_eq!(moderation.1.lock().unwrap().as_slice(), ["Test Song"]);
+    }
 } 
\ No newline at end of file
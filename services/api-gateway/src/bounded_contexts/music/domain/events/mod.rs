@@ -4,8 +4,9 @@ use serde_json::Value;
 use uuid::Uuid;
 
 use crate::bounded_contexts::music::domain::value_objects::{
-    SongId, AlbumId, ArtistId, SongTitle, Genre, PlaylistId
+    SongId, AlbumId, ArtistId, SongTitle, Genre, PlaylistId, CreditType
 };
+use crate::bounded_contexts::music::domain::entities::remix_license::RemixLicenseType;
 use crate::shared::domain::events::{DomainEvent, EventMetadata};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -360,6 +361,82 @@ impl DomainEvent for SongAvailableForOwnership {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreditAdded {
+    pub metadata: EventMetadata,
+    pub song_id: SongId,
+    pub contributor_id: Uuid,
+    pub credit_type: CreditType,
+    pub royalty_share: f64,
+    pub total_credited_share: f64,
+    pub added_at: DateTime<Utc>,
+}
+
+impl DomainEvent for CreditAdded {
+    fn metadata(&self) -> &EventMetadata {
+        &self.metadata
+    }
+    
+    fn event_type(&self) -> &str {
+        "music.song.credit_added"
+    }
+    
+    fn aggregate_id(&self) -> Uuid {
+        *self.song_id.value()
+    }
+    
+    fn aggregate_type(&self) -> &str {
+        "Song"
+    }
+    
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.added_at
+    }
+    
+    fn event_data(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemixLicensePurchased {
+    pub metadata: EventMetadata,
+    pub license_id: Uuid,
+    pub song_id: SongId,
+    pub artist_id: ArtistId,
+    pub licensee_id: Uuid,
+    pub license_type: RemixLicenseType,
+    pub price: f64,
+    pub expiry: DateTime<Utc>,
+    pub purchased_at: DateTime<Utc>,
+}
+
+impl DomainEvent for RemixLicensePurchased {
+    fn metadata(&self) -> &EventMetadata {
+        &self.metadata
+    }
+    
+    fn event_type(&self) -> &str {
+        "music.song.remix_license_purchased"
+    }
+    
+    fn aggregate_id(&self) -> Uuid {
+        *self.song_id.value()
+    }
+    
+    fn aggregate_type(&self) -> &str {
+        "Song"
+    }
+    
+    fn occurred_at(&self) -> DateTime<Utc> {
+        self.purchased_at
+    }
+    
+    fn event_data(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlaylistCreated {
     pub metadata: EventMetadata,
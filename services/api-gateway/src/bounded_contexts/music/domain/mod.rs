@@ -7,7 +7,10 @@ pub mod services;
 
 // Re-export specific items to avoid naming conflicts
 pub use value_objects::*;
-pub use entities::{Song, SongMetadata, AlbumTrack, PlaylistTrack, Artist, ArtistProfile, ArtistStats, ArtistTier, GenreStats};
+pub use entities::{
+    Song, SongMetadata, SongError, AlbumTrack, PlaylistTrack, Artist, ArtistProfile, ArtistStats, ArtistTier, GenreStats,
+    RemixLicense, RemixLicenseType, RemixLicenseError, RemixLicensePricing,
+};
 pub use events::*;
 // Re-export specific aggregates to avoid conflicts with entities
 pub use aggregates::MusicCatalogAggregate; 
\ No newline at end of file
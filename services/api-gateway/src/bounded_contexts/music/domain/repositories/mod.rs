@@ -1,7 +1,9 @@
 pub mod song_repository;
 pub mod album_repository;
 pub mod playlist_repository;
+pub mod remix_license_repository;
 
 pub use song_repository::*;
 pub use album_repository::*;
-pub use playlist_repository::*; 
\ No newline at end of file
+pub use playlist_repository::*;
+pub use remix_license_repository::*; 
\ No newline at end of file
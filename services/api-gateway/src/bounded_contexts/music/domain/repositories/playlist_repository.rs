@@ -1,7 +1,9 @@
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
+use crate::bounded_contexts::music::domain::value_objects::AlbumId;
 use crate::shared::domain::errors::AppError;
 
 // =============================================================================
@@ -14,8 +16,13 @@ pub struct Playlist {
     pub name: String,
     pub description: Option<String>,
     pub is_public: bool,
+    /// Followers can add and remove songs, not just the creator
+    pub is_collaborative: bool,
     pub song_count: u32,
     pub created_by: Uuid,
+    /// Album the playlist was generated from
+    pub album_id: Option<AlbumId>,
+    pub is_auto_generated: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -34,12 +41,71 @@ impl Playlist {
             name,
             description,
             is_public,
+            is_collaborative: false,
             song_count: 0,
             created_by,
+            album_id: None,
+            is_auto_generated: false,
             created_at: now,
             updated_at: now,
         }
     }
+
+    /// Public playlist named after the album, owned by its artist
+    pub fn for_album(id: Uuid, album_id: AlbumId, title: String, artist_id: Uuid) -> Self {
+        Self {
+            album_id: Some(album_id),
+            is_auto_generated: true,
+            ..Self::new(id, title, None, true, artist_id)
+        }
+    }
+
+    /// The creator can always edit; followers only when the playlist is collaborative
+    pub fn can_be_edited_by(&self, user_id: Uuid, is_follower: bool) -> bool {
+        user_id == self.created_by || (self.is_collaborative && is_follower)
+    }
+}
+
+// =============================================================================
+// EDIT HISTORY
+// =============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaylistEditAction {
+    AddSong,
+    RemoveSong,
+}
+
+impl PlaylistEditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PlaylistEditAction::AddSong => "add_song",
+            PlaylistEditAction::RemoveSong => "remove_song",
+        }
+    }
+}
+
+impl std::str::FromStr for PlaylistEditAction {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "add_song" => Ok(PlaylistEditAction::AddSong),
+            "remove_song" => Ok(PlaylistEditAction::RemoveSong),
+            other => Err(AppError::InternalError(format!("Unknown playlist edit action: {}", other))),
+        }
+    }
+}
+
+/// A song added to or removed from a playlist, and by whom
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlaylistEditEvent {
+    pub playlist_id: Uuid,
+    pub editor_id: Uuid,
+    pub action: PlaylistEditAction,
+    pub song_id: Uuid,
+    pub timestamp: DateTime<Utc>,
 }
 
 // =============================================================================
@@ -57,6 +123,9 @@ pub trait PlaylistRepository: Send + Sync {
     /// Find playlists by creator ID
     async fn find_by_creator(&self, creator_id: &Uuid) -> Result<Vec<Playlist>, AppError>;
     
+    /// Find playlists generated from an album
+    async fn find_by_album(&self, album_id: &AlbumId) -> Result<Vec<Playlist>, AppError>;
+    
     /// Find public playlists
     async fn find_public_playlists(&self, page: u32, page_size: u32) -> Result<Vec<Playlist>, AppError>;
     
@@ -75,12 +144,50 @@ pub trait PlaylistRepository: Send + Sync {
     /// Search playlists by name
     async fn search_by_name(&self, name: &str) -> Result<Vec<Playlist>, AppError>;
     
-    /// Add song to playlist
-    async fn add_song(&self, playlist_id: &Uuid, song_id: &Uuid) -> Result<(), AppError>;
+    /// Add song to playlist, recording the edit in its history
+    async fn add_song(&self, playlist_id: &Uuid, song_id: &Uuid, added_by: &Uuid) -> Result<(), AppError>;
     
-    /// Remove song from playlist
-    async fn remove_song(&self, playlist_id: &Uuid, song_id: &Uuid) -> Result<(), AppError>;
+    /// Remove song from playlist, recording the edit in its history
+    async fn remove_song(&self, playlist_id: &Uuid, song_id: &Uuid, removed_by: &Uuid) -> Result<(), AppError>;
     
     /// Get songs in playlist
     async fn get_songs(&self, playlist_id: &Uuid) -> Result<Vec<Uuid>, AppError>;
+    
+    /// Follow a playlist (idempotent)
+    async fn follow(&self, playlist_id: &Uuid, user_id: &Uuid) -> Result<(), AppError>;
+    
+    /// Stop following a playlist
+    async fn unfollow(&self, playlist_id: &Uuid, user_id: &Uuid) -> Result<(), AppError>;
+    
+    /// Whether the user follows the playlist
+    async fn is_follower(&self, playlist_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError>;
+    
+    /// Songs added and removed, oldest first
+    async fn edit_history(&self, playlist_id: &Uuid) -> Result<Vec<PlaylistEditEvent>, AppError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_followers_of_collaborative_playlists_edit_besides_the_creator() {
+        let (creator, follower, stranger) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let mut playlist = Playlist::new(Uuid::new_v4(), "Road Trip".to_string(), None, true, creator);
+
+        assert!(playlist.can_be_edited_by(creator, false));
+        assert!(!playlist.can_be_edited_by(follower, true));
+
+        playlist.is_collaborative = true;
+        assert!(playlist.can_be_edited_by(follower, true));
+        assert!(!playlist.can_be_edited_by(stranger, false));
+    }
+
+    #[test]
+    fn edit_actions_round_trip() {
+        for action in [PlaylistEditAction::AddSong, PlaylistEditAction::RemoveSong] {
+            assert_eq!(action.as_str().parse::<PlaylistEditAction>().unwrap(), action);
+        }
+        assert!("reorder".parse::<PlaylistEditAction>().is_err());
+    }
 }
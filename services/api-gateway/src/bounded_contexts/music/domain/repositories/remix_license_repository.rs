@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::bounded_contexts::music::domain::{RemixLicense, SongId};
+use super::song_repository::RepositoryResult;
+
+#[async_trait]
+pub trait RemixLicenseRepository: Send + Sync {
+    async fn save(&self, license: &RemixLicense) -> RepositoryResult<()>;
+    async fn find_by_song(&self, song_id: &SongId) -> RepositoryResult<Vec<RemixLicense>>;
+    /// Licenses held by `licensee_id` for the song, most recent expiry first
+    async fn find_for_licensee(&self, song_id: &SongId, licensee_id: Uuid) -> RepositoryResult<Vec<RemixLicense>>;
+}
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::bounded_contexts::music::domain::{
-    Song, SongId, ArtistId, Genre, MusicCatalogAggregate
+    Song, SongId, ArtistId, Genre, MusicCatalogAggregate, SongCredit, ChapterMarker
 };
 use crate::shared::domain::events::DomainEvent;
 
@@ -33,6 +33,13 @@ pub trait SongRepository: Send + Sync {
     async fn find_popular(&self, limit: Option<usize>) -> RepositoryResult<Vec<Song>>;
     async fn search_by_title(&self, query: &str, limit: Option<usize>) -> RepositoryResult<Vec<Song>>;
     
+    // Credits
+    async fn find_credits(&self, song_id: &SongId) -> RepositoryResult<Vec<SongCredit>>;
+    async fn save_credits(&self, song_id: &SongId, credits: &[SongCredit]) -> RepositoryResult<()>;
+    
+    // Sections detected from the uploaded audio
+    async fn save_chapter_markers(&self, song_id: &SongId, markers: &[ChapterMarker]) -> RepositoryResult<()>;
+    
     // Analytics
     async fn count(&self) -> RepositoryResult<usize>;
     async fn count_by_artist(&self, artist_id: &ArtistId) -> RepositoryResult<usize>;
@@ -0,0 +1,35 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// Verdict of a moderation provider on a piece of text
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModerationResult {
+    pub is_flagged: bool,
+    /// Policy categories that were violated, empty when clean
+    pub categories: Vec<String>,
+    /// Highest score (0.0-1.0) among the violated categories, or among all
+    /// of them when the text is clean
+    pub confidence: f64,
+}
+
+impl ModerationResult {
+    pub fn clean() -> Self {
+        Self { is_flagged: false, categories: Vec::new(), confidence: 0.0 }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ModerationError {
+    /// The provider could not be reached or refused the request; uploads
+    /// fall back to not holding the song for review
+    #[error("Moderation service unavailable: {0}")]
+    ServiceUnavailable(String),
+    #[error("Invalid moderation response: {0}")]
+    InvalidResponse(String),
+}
+
+/// Scans user-provided text for policy violations before it is made public
+#[async_trait]
+pub trait ContentModerationService: Send + Sync {
+    async fn scan_text(&self, text: &str) -> Result<ModerationResult, ModerationError>;
+}
@@ -1 +1,14 @@
-// Domain services will be implemented here 
\ No newline at end of file
+pub mod content_moderation;
+pub mod mood_playlist;
+pub mod playlist_recommendation;
+pub mod remix_license_payments;
+pub mod song_similarity;
+
+pub use content_moderation::{ContentModerationService, ModerationError, ModerationResult};
+pub use mood_playlist::{MoodPlaylistGenerator, DEFAULT_MOOD_RADIUS};
+pub use playlist_recommendation::{
+    CollaborativeFilterRecommender, PlaylistRecommendationEngine, PlaylistRecommendationReadModel,
+    RecommendationResult, SimilarUser, SIMILAR_USERS_LIMIT,
+};
+pub use remix_license_payments::RemixLicensePayments;
+pub use song_similarity::{most_similar_songs, song_similarity, SimilarSong, SongFeatures, SIMILAR_SONGS_LIMIT};
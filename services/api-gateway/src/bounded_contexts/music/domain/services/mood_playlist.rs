@@ -0,0 +1,101 @@
+use crate::bounded_contexts::music::domain::entities::Song;
+use crate::bounded_contexts::music::domain::value_objects::{circumplex_distance, SongMood};
+
+/// How far (on the circumplex) a song may sit from the requested mood
+pub const DEFAULT_MOOD_RADIUS: f32 = 0.3;
+
+/// Picks the songs of a mood playlist: those whose point on the circumplex
+/// (from audio features, or from the tagged mood when not analysed) is
+/// closest to the mood, most listened first on ties
+pub struct MoodPlaylistGenerator {
+    radius: f32,
+}
+
+impl Default for MoodPlaylistGenerator {
+    fn default() -> Self {
+        Self { radius: DEFAULT_MOOD_RADIUS }
+    }
+}
+
+impl MoodPlaylistGenerator {
+    pub fn new(radius: f32) -> Self {
+        Self { radius }
+    }
+
+    pub fn select<'a>(&self, mood: &SongMood, candidates: &'a [Song], limit: usize) -> Vec<&'a Song> {
+        let target = mood.circumplex_position();
+        let mut ranked: Vec<(f32, &Song)> = candidates
+            .iter()
+            .filter_map(|song| {
+                let distance = circumplex_distance(song.circumplex_position()?, target);
+                (distance <= self.radius).then_some((distance, song))
+            })
+            .collect();
+
+        ranked.sort_by(|(da, a), (db, b)| {
+            da.total_cmp(db)
+                .then_with(|| b.listen_count().value().cmp(&a.listen_count().value()))
+        });
+        ranked.into_iter().take(limit).map(|(_, song)| song).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::music::domain::value_objects::{
+        ArtistId, Genre, ListenCount, RoyaltyPercentage, SongDuration, SongTitle, Tempo,
+    };
+
+    fn song(title: &str, features: Option<(f32, f32)>, mood: Option<SongMood>, listens: u64) -> Song {
+        let mut song = Song::new(
+            SongTitle::new(title.to_string()).unwrap(),
+            ArtistId::new(),
+            SongDuration::new(200).unwrap(),
+            Genre::new("pop".to_string()).unwrap(),
+            RoyaltyPercentage::new(70.0).unwrap(),
+        );
+        song.set_tempo(Tempo::new(120).unwrap());
+        if let Some(mood) = mood {
+            song.set_mood(mood);
+        }
+        if let Some((valence, energy)) = features {
+            song.set_audio_features(valence, energy).unwrap();
+        }
+        song.set_listen_count(ListenCount::from_value(listens));
+        song
+    }
+
+    #[test]
+    fn picks_songs_near_the_mood_closest_first() {
+        let candidates = vec![
+            song("Ballad", Some((0.2, 0.1)), None, 10),
+            song("Anthem", Some((0.85, 0.95)), None, 10),
+            song("Sunny", Some((0.75, 0.9)), None, 10),
+            song("Riot", Some((0.1, 0.95)), None, 10),
+            song("Tagged", None, Some(SongMood::Happy), 10),
+            song("Unknown", None, None, 10_000),
+        ];
+
+        let titles: Vec<&str> = MoodPlaylistGenerator::default()
+            .select(&SongMood::Happy, &candidates, 10)
+            .iter()
+            .map(|s| s.title().value())
+            .collect();
+
+        assert_eq!(titles, vec!["Tagged", "Sunny", "Anthem"]);
+    }
+
+    #[test]
+    fn ties_go_to_the_most_listened_and_limit_applies() {
+        let candidates = vec![
+            song("Quiet", None, Some(SongMood::Calm), 5),
+            song("Popular", None, Some(SongMood::Calm), 500),
+            song("Middle", None, Some(SongMood::Calm), 50),
+        ];
+
+        let selected = MoodPlaylistGenerator::default().select(&SongMood::Calm, &candidates, 2);
+        let titles: Vec<&str> = selected.iter().map(|s| s.title().value()).collect();
+        assert_eq!(titles, vec!["Popular", "Middle"]);
+    }
+}
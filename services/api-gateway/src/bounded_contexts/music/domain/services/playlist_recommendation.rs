@@ -0,0 +1,324 @@
+use async_trait::async_trait;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+
+/// Neighbours kept per user, both when precomputing and when recommending
+pub const SIMILAR_USERS_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecommendationResult {
+    pub playlist_id: Uuid,
+    pub score: f64,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimilarUser {
+    pub user_id: Uuid,
+    pub similarity: f64,
+}
+
+/// |A ∩ B| / |A ∪ B|, 0 when both sets are empty
+pub fn jaccard_similarity(a: &HashSet<Uuid>, b: &HashSet<Uuid>) -> f64 {
+    let intersection = a.intersection(b).count();
+    let union = a.len() + b.len() - intersection;
+    if union == 0 {
+        return 0.0;
+    }
+    intersection as f64 / union as f64
+}
+
+/// The `k` most similar users of every user, best first, from the songs each
+/// one listened to. Only pairs with at least one song in common are compared.
+pub fn compute_user_similarities(
+    listens: &HashMap<Uuid, HashSet<Uuid>>,
+    k: usize,
+) -> HashMap<Uuid, Vec<SimilarUser>> {
+    let mut listeners_by_song: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+    for (user_id, songs) in listens {
+        for song_id in songs {
+            listeners_by_song.entry(*song_id).or_default().push(*user_id);
+        }
+    }
+
+    let mut similarities = HashMap::new();
+    for (user_id, songs) in listens {
+        let mut shared: HashMap<Uuid, usize> = HashMap::new();
+        for song_id in songs {
+            for other in &listeners_by_song[song_id] {
+                if other != user_id {
+                    *shared.entry(*other).or_default() += 1;
+                }
+            }
+        }
+
+        let mut neighbours: Vec<SimilarUser> = shared
+            .into_iter()
+            .map(|(other, intersection)| {
+                let union = songs.len() + listens[&other].len() - intersection;
+                SimilarUser { user_id: other, similarity: intersection as f64 / union as f64 }
+            })
+            .collect();
+        neighbours.sort_by(|a, b| {
+            b.similarity
+                .partial_cmp(&a.similarity)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| a.user_id.cmp(&b.user_id))
+        });
+        neighbours.truncate(k);
+        if !neighbours.is_empty() {
+            similarities.insert(*user_id, neighbours);
+        }
+    }
+    similarities
+}
+
+/// Playlists followed by the neighbours, most frequent first, skipping `seen`.
+/// The score is the share of neighbours following the playlist; ties go to the
+/// playlist whose followers are most similar to the user.
+pub fn rank_by_neighbours(
+    neighbours: &[SimilarUser],
+    follows: &HashMap<Uuid, Vec<Uuid>>,
+    seen: &HashSet<Uuid>,
+    limit: usize,
+) -> Vec<RecommendationResult> {
+    let mut tally: HashMap<Uuid, (usize, f64)> = HashMap::new();
+    for neighbour in neighbours {
+        let followed: HashSet<&Uuid> = follows.get(&neighbour.user_id).into_iter().flatten().collect();
+        for playlist_id in followed {
+            if seen.contains(playlist_id) {
+                continue;
+            }
+            let entry = tally.entry(*playlist_id).or_default();
+            entry.0 += 1;
+            entry.1 += neighbour.similarity;
+        }
+    }
+
+    let mut ranked: Vec<(Uuid, usize, f64)> = tally
+        .into_iter()
+        .map(|(playlist_id, (count, similarity))| (playlist_id, count, similarity))
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then_with(|| b.2.partial_cmp(&a.2).unwrap_or(Ordering::Equal))
+            .then_with(|| a.0.cmp(&b.0))
+    });
+
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(playlist_id, count, _)| RecommendationResult {
+            playlist_id,
+            score: count as f64 / neighbours.len() as f64,
+            reason: format!("Followed by {} of {} listeners with similar taste", count, neighbours.len()),
+        })
+        .collect()
+}
+
+/// Data the recommender reads
+#[async_trait]
+pub trait PlaylistRecommendationReadModel: Send + Sync {
+    /// Precomputed neighbours of the user, most similar first
+    async fn similar_users(&self, user_id: Uuid, limit: usize) -> Result<Vec<SimilarUser>, AppError>;
+
+    /// Public playlists followed by each of the users
+    async fn followed_playlists(&self, user_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Uuid>>, AppError>;
+
+    /// Playlists the user created or follows
+    async fn seen_playlists(&self, user_id: Uuid) -> Result<HashSet<Uuid>, AppError>;
+
+    /// Public playlists with their follower count, most followed first
+    async fn popular_playlists(&self, excluding: &[Uuid], limit: usize) -> Result<Vec<(Uuid, u64)>, AppError>;
+}
+
+#[async_trait]
+pub trait PlaylistRecommendationEngine: Send + Sync {
+    async fn recommend(&self, user_id: Uuid, limit: usize) -> Result<Vec<RecommendationResult>, AppError>;
+}
+
+/// Recommends what similar listeners follow, topped up with the most followed
+/// playlists when the user has no neighbours yet (or they follow too little)
+pub struct CollaborativeFilterRecommender {
+    read_model: Arc<dyn PlaylistRecommendationReadModel>,
+}
+
+impl CollaborativeFilterRecommender {
+    pub fn new(read_model: Arc<dyn PlaylistRecommendationReadModel>) -> Self {
+        Self { read_model }
+    }
+}
+
+#[async_trait]
+impl PlaylistRecommendationEngine for CollaborativeFilterRecommender {
+    async fn recommend(&self, user_id: Uuid, limit: usize) -> Result<Vec<RecommendationResult>, AppError> {
+        let seen = self.read_model.seen_playlists(user_id).await?;
+        let neighbours = self.read_model.similar_users(user_id, SIMILAR_USERS_LIMIT).await?;
+
+        let mut results = if neighbours.is_empty() {
+            Vec::new()
+        } else {
+            let ids: Vec<Uuid> = neighbours.iter().map(|n| n.user_id).collect();
+            let follows = self.read_model.followed_playlists(&ids).await?;
+            rank_by_neighbours(&neighbours, &follows, &seen, limit)
+        };
+
+        if results.len() < limit {
+            let excluding: Vec<Uuid> = seen
+                .iter()
+                .copied()
+                .chain(results.iter().map(|r| r.playlist_id))
+                .collect();
+            let popular = self.read_model.popular_playlists(&excluding, limit - results.len()).await?;
+            let most_followed = popular.first().map(|(_, followers)| *followers).unwrap_or(0).max(1);
+            results.extend(popular.into_iter().map(|(playlist_id, followers)| RecommendationResult {
+                playlist_id,
+                score: followers as f64 / most_followed as f64,
+                reason: format!("Popular playlist with {} followers", followers),
+            }));
+        }
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct InMemoryReadModel {
+        similarities: HashMap<Uuid, Vec<SimilarUser>>,
+        follows: HashMap<Uuid, Vec<Uuid>>,
+        created: HashMap<Uuid, Vec<Uuid>>,
+    }
+
+    #[async_trait]
+    impl PlaylistRecommendationReadModel for InMemoryReadModel {
+        async fn similar_users(&self, user_id: Uuid, limit: usize) -> Result<Vec<SimilarUser>, AppError> {
+            Ok(self.similarities.get(&user_id).into_iter().flatten().take(limit).copied().collect())
+        }
+
+        async fn followed_playlists(&self, user_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Uuid>>, AppError> {
+            Ok(user_ids
+                .iter()
+                .filter_map(|id| self.follows.get(id).map(|playlists| (*id, playlists.clone())))
+                .collect())
+        }
+
+        async fn seen_playlists(&self, user_id: Uuid) -> Result<HashSet<Uuid>, AppError> {
+            Ok(self
+                .follows
+                .get(&user_id)
+                .into_iter()
+                .chain(self.created.get(&user_id))
+                .flatten()
+                .copied()
+                .collect())
+        }
+
+        async fn popular_playlists(&self, excluding: &[Uuid], limit: usize) -> Result<Vec<(Uuid, u64)>, AppError> {
+            let mut followers: HashMap<Uuid, u64> = HashMap::new();
+            for playlist_id in self.follows.values().flatten() {
+                *followers.entry(*playlist_id).or_default() += 1;
+            }
+            let mut popular: Vec<(Uuid, u64)> = followers
+                .into_iter()
+                .filter(|(id, _)| !excluding.contains(id))
+                .collect();
+            popular.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            popular.truncate(limit);
+            Ok(popular)
+        }
+    }
+
+    fn set(ids: &[Uuid]) -> HashSet<Uuid> {
+        ids.iter().copied().collect()
+    }
+
+    #[test]
+    fn jaccard_similarity_of_listened_songs() {
+        let songs: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        // {0,1,2} vs {1,2,3}: 2 en común de 4 distintas
+        assert_eq!(jaccard_similarity(&set(&songs[..3]), &set(&songs[1..])), 0.5);
+        assert_eq!(jaccard_similarity(&set(&songs[..2]), &set(&songs[2..])), 0.0);
+        assert_eq!(jaccard_similarity(&HashSet::new(), &HashSet::new()), 0.0);
+    }
+
+    #[test]
+    fn similarities_keep_the_closest_users_first() {
+        let songs: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let (ana, bea, carla, dani) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let listens = HashMap::from([
+            (ana, set(&songs[..3])),
+            (bea, set(&songs[..3])),
+            (carla, set(&songs[2..])),
+            (dani, set(&songs[3..])),
+        ]);
+
+        let similarities = compute_user_similarities(&listens, 1);
+        assert_eq!(similarities[&ana], vec![SimilarUser { user_id: bea, similarity: 1.0 }]);
+        // Carla comparte la canción 2 con Ana y Bea (1/4) y la 3 con Dani (1/2)
+        assert_eq!(similarities[&carla], vec![SimilarUser { user_id: dani, similarity: 0.5 }]);
+
+        let all = compute_user_similarities(&listens, SIMILAR_USERS_LIMIT);
+        assert_eq!(all[&carla].len(), 3);
+        assert!(all.values().flatten().all(|n| n.similarity > 0.0));
+        assert!(all.iter().all(|(user, neighbours)| neighbours.iter().all(|n| n.user_id != *user)));
+    }
+
+    #[tokio::test]
+    async fn neighbours_playlists_are_ranked_by_frequency_without_seen_ones() {
+        let user = Uuid::new_v4();
+        let neighbours: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let (shared, niche, already_followed, own) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        let read_model = InMemoryReadModel {
+            similarities: HashMap::from([(
+                user,
+                neighbours.iter().map(|&id| SimilarUser { user_id: id, similarity: 0.4 }).collect(),
+            )]),
+            follows: HashMap::from([
+                (user, vec![already_followed]),
+                (neighbours[0], vec![shared, already_followed, own]),
+                (neighbours[1], vec![shared, niche]),
+                (neighbours[2], vec![shared]),
+            ]),
+            created: HashMap::from([(user, vec![own])]),
+        };
+        let recommender = CollaborativeFilterRecommender::new(Arc::new(read_model));
+
+        let results = recommender.recommend(user, 2).await.unwrap();
+        assert_eq!(results.iter().map(|r| r.playlist_id).collect::<Vec<_>>(), vec![shared, niche]);
+        assert_eq!(results[0].score, 1.0);
+        assert_eq!(results[0].reason, "Followed by 3 of 3 listeners with similar taste");
+    }
+
+    #[tokio::test]
+    async fn user_without_history_gets_popular_playlists() {
+        let newcomer = Uuid::new_v4();
+        let listeners: Vec<Uuid> = (0..3).map(|_| Uuid::new_v4()).collect();
+        let (hits, indie) = (Uuid::new_v4(), Uuid::new_v4());
+
+        let read_model = InMemoryReadModel {
+            follows: HashMap::from([
+                (listeners[0], vec![hits, indie]),
+                (listeners[1], vec![hits]),
+                (listeners[2], vec![hits]),
+            ]),
+            ..Default::default()
+        };
+        let recommender = CollaborativeFilterRecommender::new(Arc::new(read_model));
+
+        let results = recommender.recommend(newcomer, 10).await.unwrap();
+        assert_eq!(results.iter().map(|r| r.playlist_id).collect::<Vec<_>>(), vec![hits, indie]);
+        assert_eq!(results[0].score, 1.0);
+        assert_eq!(results[0].reason, "Popular playlist with 3 followers");
+        assert!(results[1].score < results[0].score);
+    }
+}
@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+
+use crate::bounded_contexts::music::domain::entities::RemixLicense;
+use crate::shared::domain::errors::AppError;
+
+/// Port to the payment context for charging remix licenses. The licensee is
+/// charged before the license exists, so stems are never handed out unpaid.
+#[async_trait]
+pub trait RemixLicensePayments: Send + Sync {
+    /// Charge the licensee and pay the artist the license price as a royalty.
+    /// Returns the settled payment id; fails if the payment did not settle.
+    async fn charge(&self, license: &RemixLicense, artist_id: Uuid) -> Result<Uuid, AppError>;
+
+    /// Give the money back when the license could not be granted after all
+    async fn refund(&self, payment_id: Uuid, requested_by: Uuid, reason: &str) -> Result<(), AppError>;
+}
@@ -0,0 +1,142 @@
+use std::cmp::Ordering;
+use uuid::Uuid;
+
+use crate::bounded_contexts::music::domain::value_objects::{AudioQuality, SongMood, Tempo};
+
+/// Neighbours stored per song in `song_similarities`
+pub const SIMILAR_SONGS_LIMIT: usize = 50;
+
+/// Weight of each feature in the distance. Genre dominates, then mood
+/// (split between valence and arousal), tempo and audio quality.
+const GENRE_WEIGHT: f64 = 0.4;
+const MOOD_WEIGHT: f64 = 0.3;
+const TEMPO_WEIGHT: f64 = 0.2;
+const QUALITY_WEIGHT: f64 = 0.1;
+
+/// Content features of a song compared for similarity
+#[derive(Debug, Clone, PartialEq)]
+pub struct SongFeatures {
+    pub song_id: Uuid,
+    pub genre: String,
+    pub mood: Option<SongMood>,
+    pub tempo: Option<Tempo>,
+    pub audio_quality: Option<AudioQuality>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimilarSong {
+    pub song_id: Uuid,
+    pub score: f64,
+}
+
+impl SongFeatures {
+    /// Mood as its (valence, arousal) point of the circumplex; unknown is neutral
+    fn mood_position(&self) -> (f64, f64) {
+        self.mood
+            .as_ref()
+            .map(|mood| {
+                let (valence, arousal) = mood.circumplex_position();
+                (valence as f64, arousal as f64)
+            })
+            .unwrap_or((0.5, 0.5))
+    }
+
+    /// Tempo classification as 0.0 (slow) - 1.0 (extremely fast); unknown is moderate
+    fn tempo_level(&self) -> f64 {
+        let class = match self.tempo.as_ref().map(Tempo::classification) {
+            Some("Slow") => 0.0,
+            Some("Moderate") | None => 1.0,
+            Some("Fast") => 2.0,
+            Some("Very Fast") => 3.0,
+            Some(_) => 4.0,
+        };
+        class / 4.0
+    }
+
+    /// Audio quality as 0.0 (low) - 1.0 (lossless); unknown sits in the middle
+    fn quality_level(&self) -> f64 {
+        match self.audio_quality {
+            Some(AudioQuality::Low) => 0.0,
+            Some(AudioQuality::Medium) => 1.0 / 3.0,
+            Some(AudioQuality::High) => 2.0 / 3.0,
+            Some(AudioQuality::Lossless) => 1.0,
+            None => 0.5,
+        }
+    }
+}
+
+/// Weighted Euclidean distance between two songs, 0.0 - 1.0
+pub fn song_distance(a: &SongFeatures, b: &SongFeatures) -> f64 {
+    let genre = if a.genre.eq_ignore_ascii_case(&b.genre) { 0.0 } else { 1.0 };
+    let (a_valence, a_arousal) = a.mood_position();
+    let (b_valence, b_arousal) = b.mood_position();
+    let tempo = a.tempo_level() - b.tempo_level();
+    let quality = a.quality_level() - b.quality_level();
+
+    let squared = GENRE_WEIGHT * genre
+        + MOOD_WEIGHT / 2.0 * ((a_valence - b_valence).powi(2) + (a_arousal - b_arousal).powi(2))
+        + TEMPO_WEIGHT * tempo.powi(2)
+        + QUALITY_WEIGHT * quality.powi(2);
+    // Every term is at most its weight, and the weights add up to 1
+    squared.sqrt()
+}
+
+/// Similarity score, 1.0 for identical features
+pub fn song_similarity(a: &SongFeatures, b: &SongFeatures) -> f64 {
+    1.0 - song_distance(a, b)
+}
+
+/// The `k` songs most similar to `song`, best first. The song itself is skipped.
+pub fn most_similar_songs(song: &SongFeatures, catalog: &[SongFeatures], k: usize) -> Vec<SimilarSong> {
+    let mut similar: Vec<SimilarSong> = catalog
+        .iter()
+        .filter(|other| other.song_id != song.song_id)
+        .map(|other| SimilarSong { song_id: other.song_id, score: song_similarity(song, other) })
+        .collect();
+    similar.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(Ordering::Equal));
+    similar.truncate(k);
+    similar
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn song(genre: &str, mood: SongMood, bpm: u16, quality: AudioQuality) -> SongFeatures {
+        SongFeatures {
+            song_id: Uuid::new_v4(),
+            genre: genre.to_string(),
+            mood: Some(mood),
+            tempo: Some(Tempo::new(bpm).unwrap()),
+            audio_quality: Some(quality),
+        }
+    }
+
+    #[test]
+    fn same_genre_and_mood_is_more_similar_than_another_genre() {
+        let track = song("rock", SongMood::Energetic, 130, AudioQuality::High);
+        let same_style = song("rock", SongMood::Energetic, 150, AudioQuality::Medium);
+        let other_genre = song("classical", SongMood::Energetic, 130, AudioQuality::High);
+        let other_everything = song("classical", SongMood::Calm, 70, AudioQuality::Low);
+
+        assert!(song_similarity(&track, &same_style) > song_similarity(&track, &other_genre));
+        assert!(song_similarity(&track, &other_genre) > song_similarity(&track, &other_everything));
+        assert!((song_similarity(&track, &track) - 1.0).abs() < 1e-9);
+        assert!(song_similarity(&track, &other_everything) >= 0.0);
+    }
+
+    #[test]
+    fn most_similar_songs_are_ranked_and_exclude_the_song() {
+        let track = song("jazz", SongMood::Calm, 80, AudioQuality::Lossless);
+        let close = song("jazz", SongMood::Calm, 85, AudioQuality::High);
+        let far = song("metal", SongMood::Aggressive, 180, AudioQuality::Low);
+        let middle = song("jazz", SongMood::Happy, 110, AudioQuality::Lossless);
+        let catalog = vec![far.clone(), track.clone(), middle.clone(), close.clone()];
+
+        let similar = most_similar_songs(&track, &catalog, 2);
+
+        let ids: Vec<Uuid> = similar.iter().map(|s| s.song_id).collect();
+        assert_eq!(ids, vec![close.song_id, middle.song_id]);
+        assert!(similar[0].score >= similar[1].score);
+    }
+}
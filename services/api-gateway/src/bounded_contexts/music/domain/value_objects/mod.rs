@@ -228,6 +228,16 @@ impl PlaylistName {
     }
 }
 
+/// Margin over wall-clock time allowed for buffering when checking claimed listening time
+pub const DEFAULT_STREAM_TOLERANCE_PCT: f64 = 0.05;
+
+/// Errors raised when checking claimed listening time
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DurationValidationError {
+    #[error("Claimed {claimed_seconds}s of listening but only {wall_clock_seconds}s have passed")]
+    StreamTooFast { claimed_seconds: u32, wall_clock_seconds: u32 },
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SongDuration {
     seconds: u32,
@@ -272,6 +282,23 @@ impl SongDuration {
         let seconds = self.remaining_seconds();
         format!("{}:{:02}", minutes, seconds)
     }
+
+    /// Anti-skip check: a client cannot claim more listening time than has
+    /// passed since the session started, plus `tolerance_pct` (0.05 = 5%)
+    pub fn validate_stream_progress(
+        claimed_duration: u32,
+        actual_elapsed_wall_clock: u32,
+        tolerance_pct: f64,
+    ) -> Result<(), DurationValidationError> {
+        let allowed = actual_elapsed_wall_clock as f64 * (1.0 + tolerance_pct.max(0.0));
+        if claimed_duration as f64 > allowed {
+            return Err(DurationValidationError::StreamTooFast {
+                claimed_seconds: claimed_duration,
+                wall_clock_seconds: actual_elapsed_wall_clock,
+            });
+        }
+        Ok(())
+    }
 }
 
 impl fmt::Display for SongDuration {
@@ -466,6 +493,25 @@ impl AudioQuality {
             _ => Self::Lossless,
         }
     }
+
+    pub fn from_string(quality: &str) -> Result<Self, String> {
+        match quality.to_lowercase().as_str() {
+            "low" => Ok(Self::Low),
+            "medium" => Ok(Self::Medium),
+            "high" => Ok(Self::High),
+            "lossless" => Ok(Self::Lossless),
+            _ => Err(format!("Invalid audio quality: {}", quality)),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+            Self::Lossless => "lossless",
+        }
+    }
 }
 
 impl fmt::Display for AudioQuality {
@@ -572,6 +618,59 @@ impl SongMood {
             _ => Err(format!("Invalid mood: {}", mood)),
         }
     }
+
+    /// Mood from audio analysis, on Russell's circumplex model: valence
+    /// (negative → positive) against arousal (calm → excited).
+    ///
+    /// `valence` and `energy` are in 0.0-1.0. Arousal is mostly `energy`,
+    /// nudged by tempo and integrated loudness (-24 LUFS quiet, -4 LUFS loud),
+    /// and the mood is the quadrant prototype closest to the resulting point.
+    pub fn detect_from_audio_features(tempo: &Tempo, loudness_lufs: f32, valence: f32, energy: f32) -> SongMood {
+        let point = (unit(valence), Self::arousal(tempo, loudness_lufs, energy));
+        [Self::Happy, Self::Sad, Self::Aggressive, Self::Calm]
+            .into_iter()
+            .min_by(|a, b| {
+                let da = circumplex_distance(point, a.circumplex_position());
+                let db = circumplex_distance(point, b.circumplex_position());
+                da.total_cmp(&db)
+            })
+            .unwrap_or(Self::Calm)
+    }
+
+    /// Arousal (0.0-1.0) of a track from its energy, tempo and loudness
+    pub fn arousal(tempo: &Tempo, loudness_lufs: f32, energy: f32) -> f32 {
+        let tempo_level = (tempo.bpm() as f32 - 60.0) / 120.0;
+        let loudness_level = if loudness_lufs.is_finite() { (loudness_lufs + 24.0) / 20.0 } else { 0.5 };
+        unit(0.7 * unit(energy) + 0.15 * unit(tempo_level) + 0.15 * unit(loudness_level))
+    }
+
+    /// (valence, arousal) of the mood on the circumplex, both 0.0-1.0
+    pub fn circumplex_position(&self) -> (f32, f32) {
+        match self {
+            Self::Happy => (0.75, 0.75),
+            Self::Sad => (0.25, 0.25),
+            Self::Aggressive => (0.25, 0.75),
+            Self::Calm => (0.75, 0.25),
+            Self::Energetic => (0.6, 0.9),
+            Self::Romantic => (0.75, 0.4),
+            Self::Melancholic => (0.3, 0.35),
+            Self::Uplifting => (0.85, 0.65),
+            Self::Dark => (0.15, 0.55),
+            Self::Nostalgic => (0.55, 0.35),
+            Self::Triumphant => (0.8, 0.85),
+            Self::Mysterious => (0.4, 0.5),
+        }
+    }
+}
+
+/// Distance between two (valence, arousal) points of the circumplex
+pub fn circumplex_distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()
+}
+
+/// Clamp to 0.0-1.0; NaN counts as neutral
+fn unit(value: f32) -> f32 {
+    if value.is_nan() { 0.5 } else { value.clamp(0.0, 1.0) }
 }
 
 impl fmt::Display for SongMood {
@@ -676,6 +775,72 @@ impl fmt::Display for ReleaseType {
     }
 }
 
+/// Role of a contributor credited on a song
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CreditType {
+    MainArtist,
+    FeaturedArtist,
+    Producer,
+    Songwriter,
+    Remixer,
+}
+
+impl CreditType {
+    pub fn from_string(credit_type: &str) -> Result<Self, String> {
+        match credit_type.to_lowercase().as_str() {
+            "main_artist" | "mainartist" => Ok(Self::MainArtist),
+            "featured_artist" | "featuredartist" | "featured" => Ok(Self::FeaturedArtist),
+            "producer" => Ok(Self::Producer),
+            "songwriter" | "writer" => Ok(Self::Songwriter),
+            "remixer" => Ok(Self::Remixer),
+            _ => Err(format!("Invalid credit type: {}", credit_type)),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MainArtist => "main_artist",
+            Self::FeaturedArtist => "featured_artist",
+            Self::Producer => "producer",
+            Self::Songwriter => "songwriter",
+            Self::Remixer => "remixer",
+        }
+    }
+}
+
+impl fmt::Display for CreditType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Credit for a contributor on a song, with their share of the song's royalties
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SongCredit {
+    pub contributor_id: Uuid,
+    pub credit_type: CreditType,
+    pub royalty_share: RoyaltyPercentage,
+}
+
+impl SongCredit {
+    pub fn new(contributor_id: Uuid, credit_type: CreditType, royalty_share: RoyaltyPercentage) -> Self {
+        Self { contributor_id, credit_type, royalty_share }
+    }
+}
+
+/// Start of a song section (intro, verse, chorus, bridge, outro)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChapterMarker {
+    pub position_ms: u64,
+    pub label: String,
+}
+
+impl ChapterMarker {
+    pub fn new(position_ms: u64, label: impl Into<String>) -> Self {
+        Self { position_ms, label: label.into() }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -696,6 +861,23 @@ mod tests {
         assert_eq!(duration.as_formatted_string(), "3:45");
     }
 
+    #[test]
+    fn test_stream_progress_faster_than_wall_clock_fails() {
+        assert_eq!(
+            SongDuration::validate_stream_progress(180, 50, DEFAULT_STREAM_TOLERANCE_PCT),
+            Err(DurationValidationError::StreamTooFast { claimed_seconds: 180, wall_clock_seconds: 50 })
+        );
+    }
+
+    #[test]
+    fn test_stream_progress_within_tolerance_passes() {
+        assert!(SongDuration::validate_stream_progress(180, 180, DEFAULT_STREAM_TOLERANCE_PCT).is_ok());
+        // 5% of buffering margin: 189s claimed over 180s of wall clock
+        assert!(SongDuration::validate_stream_progress(189, 180, DEFAULT_STREAM_TOLERANCE_PCT).is_ok());
+        assert!(SongDuration::validate_stream_progress(190, 180, DEFAULT_STREAM_TOLERANCE_PCT).is_err());
+        assert!(SongDuration::validate_stream_progress(190, 180, 0.1).is_ok());
+    }
+
     #[test]
     fn test_genre_validation() {
         assert!(Genre::new("rock".to_string()).is_ok());
@@ -734,6 +916,32 @@ mod tests {
         assert!(Tempo::new(250).is_err());
     }
 
+    #[test]
+    fn test_mood_detection_covers_each_circumplex_quadrant() {
+        let tempo = Tempo::new(120).unwrap();
+        // Alta energía + alta valencia
+        assert_eq!(SongMood::detect_from_audio_features(&tempo, -8.0, 0.85, 0.9), SongMood::Happy);
+        // Baja energía + baja valencia
+        assert_eq!(SongMood::detect_from_audio_features(&tempo, -20.0, 0.15, 0.2), SongMood::Sad);
+        // Alta energía + baja valencia
+        assert_eq!(SongMood::detect_from_audio_features(&tempo, -6.0, 0.1, 0.95), SongMood::Aggressive);
+        // Baja energía + alta valencia
+        assert_eq!(SongMood::detect_from_audio_features(&tempo, -22.0, 0.8, 0.15), SongMood::Calm);
+    }
+
+    #[test]
+    fn test_mood_detection_weighs_tempo_and_loudness() {
+        // Energía intermedia: el tempo y la sonoridad deciden el cuadrante
+        let slow = Tempo::new(70).unwrap();
+        let fast = Tempo::new(175).unwrap();
+        assert_eq!(SongMood::detect_from_audio_features(&slow, -23.0, 0.7, 0.55), SongMood::Calm);
+        assert_eq!(SongMood::detect_from_audio_features(&fast, -5.0, 0.7, 0.55), SongMood::Happy);
+
+        // Valores fuera de rango se acotan en lugar de fallar
+        assert_eq!(SongMood::detect_from_audio_features(&fast, f32::NAN, 7.0, 3.0), SongMood::Happy);
+        assert_eq!(SongMood::detect_from_audio_features(&slow, -60.0, -1.0, -1.0), SongMood::Sad);
+    }
+
     #[test]
     fn test_file_format() {
         assert_eq!(FileFormat::from_extension("mp3").unwrap(), FileFormat::Mp3);
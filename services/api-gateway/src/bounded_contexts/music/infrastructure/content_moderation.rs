@@ -0,0 +1,231 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::bounded_contexts::music::domain::services::{ContentModerationService, ModerationError, ModerationResult};
+
+pub const DEFAULT_OPENAI_BASE_URL: &str = "https://api.openai.com";
+pub const DEFAULT_MODERATION_MODEL: &str = "omni-moderation-latest";
+const MODERATION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// OpenAI Moderation API (`POST /v1/moderations`)
+pub struct OpenAIModerationService {
+    client: Client,
+    api_key: String,
+    base_url: String,
+    model: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModerationResponse {
+    results: Vec<ModerationVerdict>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModerationVerdict {
+    flagged: bool,
+    #[serde(default)]
+    categories: HashMap<String, bool>,
+    #[serde(default)]
+    category_scores: HashMap<String, f64>,
+}
+
+impl OpenAIModerationService {
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(MODERATION_TIMEOUT)
+                .build()
+                .unwrap_or_else(|_| Client::new()),
+            api_key: api_key.into(),
+            base_url: DEFAULT_OPENAI_BASE_URL.to_string(),
+            model: DEFAULT_MODERATION_MODEL.to_string(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.model = model.into();
+        self
+    }
+
+    /// `OPENAI_API_KEY` (required), `OPENAI_BASE_URL` and `CONTENT_MODERATION_MODEL`
+    pub fn from_env() -> Option<Self> {
+        let api_key = std::env::var("OPENAI_API_KEY").ok().filter(|key| !key.trim().is_empty())?;
+        let mut service = Self::new(api_key);
+        if let Ok(base_url) = std::env::var("OPENAI_BASE_URL") {
+            service = service.with_base_url(base_url);
+        }
+        if let Ok(model) = std::env::var("CONTENT_MODERATION_MODEL") {
+            service = service.with_model(model);
+        }
+        Some(service)
+    }
+
+    fn to_result(verdict: ModerationVerdict) -> ModerationResult {
+        let mut categories: Vec<String> = verdict
+            .categories
+            .into_iter()
+            .filter(|(_, violated)| *violated)
+            .map(|(category, _)| category)
+            .collect();
+        categories.sort();
+
+        let score = |category: &String| verdict.category_scores.get(category).copied().unwrap_or(0.0);
+        let confidence = if categories.is_empty() {
+            verdict.category_scores.values().copied().fold(0.0, f64::max)
+        } else {
+            categories.iter().map(score).fold(0.0, f64::max)
+        };
+
+        ModerationResult { is_flagged: verdict.flagged, categories, confidence }
+    }
+}
+
+#[async_trait]
+impl ContentModerationService for OpenAIModerationService {
+    async fn scan_text(&self, text: &str) -> Result<ModerationResult, ModerationError> {
+        let response = self
+            .client
+            .post(format!("{}/v1/moderations", self.base_url))
+            .bearer_auth(&self.api_key)
+            .json(&serde_json::json!({ "model": self.model, "input": text }))
+            .send()
+            .await
+            .map_err(|e| ModerationError::ServiceUnavailable(e.to_string()))?;
+
+        // Rate limits, outages and bad credentials all leave us without a verdict
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(ModerationError::ServiceUnavailable(format!("{}: {}", status, body)));
+        }
+
+        let body: ModerationResponse = response
+            .json()
+            .await
+            .map_err(|e| ModerationError::InvalidResponse(e.to_string()))?;
+        let verdict = body
+            .results
+            .into_iter()
+            .next()
+            .ok_or_else(|| ModerationError::InvalidResponse("No moderation results".to_string()))?;
+
+        Ok(Self::to_result(verdict))
+    }
+}
+
+/// Always clean; used when no provider is configured
+#[derive(Debug, Default)]
+pub struct MockModerationService;
+
+impl MockModerationService {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl ContentModerationService for MockModerationService {
+    async fn scan_text(&self, _text: &str) -> Result<ModerationResult, ModerationError> {
+        Ok(ModerationResult::clean())
+    }
+}
+
+/// OpenAI when `OPENAI_API_KEY` is set, otherwise nothing is flagged
+pub fn content_moderation_from_env() -> Arc<dyn ContentModerationService> {
+    match OpenAIModerationService::from_env() {
+        Some(service) => Arc::new(service),
+        None => {
+            tracing::warn!("OPENAI_API_KEY not set, song metadata will not be moderated");
+            Arc::new(MockModerationService::new())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn moderation_api(status: u16, body: serde_json::Value) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/moderations"))
+            .and(header("authorization", "Bearer sk-test"))
+            .and(body_partial_json(serde_json::json!({ "model": DEFAULT_MODERATION_MODEL })))
+            .respond_with(ResponseTemplate::new(status).set_body_json(body))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    fn service(server: &MockServer) -> OpenAIModerationService {
+        OpenAIModerationService::new("sk-test").with_base_url(server.uri())
+    }
+
+    #[tokio::test]
+    async fn flagged_text_reports_violated_categories() {
+        let server = moderation_api(200, serde_json::json!({
+            "id": "modr-123",
+            "model": DEFAULT_MODERATION_MODEL,
+            "results": [{
+                "flagged": true,
+                "categories": { "hate": true, "harassment": true, "violence": false },
+                "category_scores": { "hate": 0.91, "harassment": 0.64, "violence": 0.97 }
+            }]
+        }))
+        .await;
+
+        let result = service(&server).scan_text("offensive title").await.unwrap();
+
+        assert!(result.is_flagged);
+        assert_eq!(result.categories, vec!["harassment".to_string(), "hate".to_string()]);
+        // Only the violated categories count towards the confidence
+        assert_eq!(result.confidence, 0.91);
+    }
+
+    #[tokio::test]
+    async fn clean_text_is_not_flagged() {
+        let server = moderation_api(200, serde_json::json!({
+            "results": [{
+                "flagged": false,
+                "categories": { "hate": false, "sexual": false },
+                "category_scores": { "hate": 0.01, "sexual": 0.03 }
+            }]
+        }))
+        .await;
+
+        let result = service(&server).scan_text("Summer Breeze").await.unwrap();
+
+        assert!(!result.is_flagged);
+        assert!(result.categories.is_empty());
+        assert_eq!(result.confidence, 0.03);
+    }
+
+    #[tokio::test]
+    async fn outages_and_rate_limits_are_service_unavailable() {
+        for status in [429, 503] {
+            let server = moderation_api(status, serde_json::json!({ "error": { "message": "try again" } })).await;
+            let result = service(&server).scan_text("Summer Breeze").await;
+            assert!(matches!(result, Err(ModerationError::ServiceUnavailable(_))), "status {}", status);
+        }
+
+        let unreachable = OpenAIModerationService::new("sk-test").with_base_url("http://127.0.0.1:9");
+        assert!(matches!(unreachable.scan_text("Summer Breeze").await, Err(ModerationError::ServiceUnavailable(_))));
+    }
+
+    #[tokio::test]
+    async fn mock_service_never_flags() {
+        let result = MockModerationService::new().scan_text("anything at all").await.unwrap();
+        assert_eq!(result, ModerationResult::clean());
+    }
+}
@@ -0,0 +1,126 @@
+use reqwest::Client;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+
+/// Profile pages are scanned up to this size; the code is expected in the bio
+const MAX_PAGE_BYTES: usize = 2 * 1024 * 1024;
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Proof that an artist controls a social profile: they post a short code
+/// on it and we look for the code on the public page.
+pub struct LinkVerification;
+
+impl LinkVerification {
+    /// Code the artist must post on their profile, e.g. `vibestream-3fa85f-9c1e07d2`.
+    /// The artist prefix makes it recognisable; the random part makes it unguessable.
+    pub fn generate_challenge(artist_id: Uuid) -> String {
+        let artist = artist_id.simple().to_string();
+        let nonce = Uuid::new_v4().simple().to_string();
+        format!("vibestream-{}-{}", &artist[..6], &nonce[..8])
+    }
+
+    /// Fetch the profile page and look for the code. A page that does not
+    /// exist (4xx) simply does not prove anything; network failures and 5xx
+    /// are errors so the artist can retry.
+    pub async fn verify(url: &str, expected_code: &str) -> Result<bool, AppError> {
+        if expected_code.trim().is_empty() {
+            return Err(AppError::ValidationError("Challenge code cannot be empty".to_string()));
+        }
+
+        let client = Client::builder()
+            .timeout(FETCH_TIMEOUT)
+            .user_agent("VibeStream-LinkVerification/1.0")
+            .build()
+            .map_err(|e| AppError::InternalError(format!("HTTP client error: {}", e)))?;
+
+        let mut response = client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| AppError::NetworkError(format!("Could not fetch {}: {}", url, e)))?;
+
+        let status = response.status();
+        if status.is_server_error() {
+            return Err(AppError::ExternalServiceError(format!("{} answered {}", url, status)));
+        }
+        if !status.is_success() {
+            tracing::debug!("Link verification of {} got {}", url, status);
+            return Ok(false);
+        }
+
+        let mut page = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| AppError::NetworkError(format!("Could not read {}: {}", url, e)))?
+        {
+            page.extend_from_slice(&chunk);
+            if page.len() >= MAX_PAGE_BYTES {
+                page.truncate(MAX_PAGE_BYTES);
+                break;
+            }
+        }
+
+        Ok(String::from_utf8_lossy(&page).contains(expected_code.trim()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn profile_page(status: u16, body: &str) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/jazzartist"))
+            .respond_with(ResponseTemplate::new(status).set_body_string(body))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[test]
+    fn challenges_are_unique_per_request() {
+        let artist_id = Uuid::new_v4();
+        let first = LinkVerification::generate_challenge(artist_id);
+        let second = LinkVerification::generate_challenge(artist_id);
+
+        assert!(first.starts_with(&format!("vibestream-{}", &artist_id.simple().to_string()[..6])));
+        assert_eq!(first.len(), "vibestream-".len() + 6 + 1 + 8);
+        assert_ne!(first, second);
+    }
+
+    #[tokio::test]
+    async fn finds_the_code_posted_on_the_profile() {
+        let code = LinkVerification::generate_challenge(Uuid::new_v4());
+        let server = profile_page(200, &format!("<html><p class=\"bio\">Jazz from Madrid {}</p></html>", code)).await;
+
+        let verified = LinkVerification::verify(&format!("{}/jazzartist", server.uri()), &code).await.unwrap();
+        assert!(verified);
+    }
+
+    #[tokio::test]
+    async fn profile_without_the_code_is_not_verified() {
+        let server = profile_page(200, "<html><p class=\"bio\">Jazz from Madrid</p></html>").await;
+        let code = LinkVerification::generate_challenge(Uuid::new_v4());
+
+        let verified = LinkVerification::verify(&format!("{}/jazzartist", server.uri()), &code).await.unwrap();
+        assert!(!verified);
+
+        // Missing profile pages prove nothing either
+        let missing = LinkVerification::verify(&format!("{}/someone-else", server.uri()), &code).await.unwrap();
+        assert!(!missing);
+    }
+
+    #[tokio::test]
+    async fn platform_outage_is_an_error() {
+        let server = profile_page(503, "Service Unavailable").await;
+
+        let result = LinkVerification::verify(&format!("{}/jazzartist", server.uri()), "vibestream-abc").await;
+        assert!(matches!(result, Err(AppError::ExternalServiceError(_))));
+    }
+}
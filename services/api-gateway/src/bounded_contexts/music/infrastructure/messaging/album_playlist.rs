@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::bounded_contexts::music::domain::repositories::playlist_repository::{Playlist, PlaylistRepository};
+use crate::bounded_contexts::music::domain::value_objects::AlbumId;
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+use crate::shared::domain::errors::AppError;
+
+/// Creates the album's playlist: same name as the album, owned by the
+/// artist, with the tracks in order. An album only gets one, so a
+/// redelivered event does nothing.
+pub struct AlbumCreatedEventHandler {
+    playlist_repository: Arc<dyn PlaylistRepository>,
+}
+
+impl AlbumCreatedEventHandler {
+    pub fn new(playlist_repository: Arc<dyn PlaylistRepository>) -> Self {
+        Self { playlist_repository }
+    }
+}
+
+#[async_trait]
+impl EventHandler for AlbumCreatedEventHandler {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let DomainEvent::AlbumCreated { album_id, artist_id, title, song_ids, .. } = event else {
+            return Ok(());
+        };
+
+        let album_id = AlbumId::from_uuid(*album_id);
+        let existing = self.playlist_repository.find_by_album(&album_id).await?;
+        if existing.iter().any(|playlist| playlist.is_auto_generated) {
+            tracing::debug!("Album {} already has its playlist", album_id);
+            return Ok(());
+        }
+
+        let playlist = Playlist::for_album(Uuid::new_v4(), album_id.clone(), title.clone(), *artist_id);
+        self.playlist_repository.save(&playlist).await?;
+        for song_id in song_ids {
+            self.playlist_repository.add_song(&playlist.id, song_id, &playlist.created_by).await?;
+        }
+
+        tracing::info!(
+            "Playlist {} generated for album {} with {} songs",
+            playlist.id, album_id, song_ids.len()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::music::domain::repositories::PlaylistEditEvent;
+    use chrono::Utc;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct InMemoryPlaylistRepository {
+        playlists: Mutex<Vec<Playlist>>,
+        songs: Mutex<HashMap<Uuid, Vec<Uuid>>>,
+    }
+
+    #[async_trait]
+    impl PlaylistRepository for InMemoryPlaylistRepository {
+        async fn save(&self, playlist: &Playlist) -> Result<(), AppError> {
+            self.playlists.lock().unwrap().push(playlist.clone());
+            Ok(())
+        }
+        async fn find_by_id(&self, id: &Uuid) -> Result<Option<Playlist>, AppError> {
+            Ok(self.playlists.lock().unwrap().iter().find(|p| p.id == *id).cloned())
+        }
+        async fn find_by_creator(&self, _creator_id: &Uuid) -> Result<Vec<Playlist>, AppError> { Ok(Vec::new()) }
+        async fn find_by_album(&self, album_id: &AlbumId) -> Result<Vec<Playlist>, AppError> {
+            Ok(self.playlists.lock().unwrap().iter().filter(|p| p.album_id.as_ref() == Some(album_id)).cloned().collect())
+        }
+        async fn find_public_playlists(&self, _page: u32, _page_size: u32) -> Result<Vec<Playlist>, AppError> { Ok(Vec::new()) }
+        async fn find_all(&self, _page: u32, _page_size: u32) -> Result<Vec<Playlist>, AppError> { Ok(Vec::new()) }
+        async fn update(&self, _playlist: &Playlist) -> Result<(), AppError> { Ok(()) }
+        async fn delete(&self, _id: &Uuid) -> Result<(), AppError> { Ok(()) }
+        async fn count(&self) -> Result<u64, AppError> { Ok(self.playlists.lock().unwrap().len() as u64) }
+        async fn search_by_name(&self, _name: &str) -> Result<Vec<Playlist>, AppError> { Ok(Vec::new()) }
+        async fn add_song(&self, playlist_id: &Uuid, song_id: &Uuid, _added_by: &Uuid) -> Result<(), AppError> {
+            self.songs.lock().unwrap().entry(*playlist_id).or_default().push(*song_id);
+            Ok(())
+        }
+        async fn remove_song(&self, _playlist_id: &Uuid, _song_id: &Uuid, _removed_by: &Uuid) -> Result<(), AppError> { Ok(()) }
+        async fn get_songs(&self, playlist_id: &Uuid) -> Result<Vec<Uuid>, AppError> {
+            Ok(self.songs.lock().unwrap().get(playlist_id).cloned().unwrap_or_default())
+        }
+        async fn follow(&self, _playlist_id: &Uuid, _user_id: &Uuid) -> Result<(), AppError> { Ok(()) }
+        async fn unfollow(&self, _playlist_id: &Uuid, _user_id: &Uuid) -> Result<(), AppError> { Ok(()) }
+        async fn is_follower(&self, _playlist_id: &Uuid, _user_id: &Uuid) -> Result<bool, AppError> { Ok(false) }
+        async fn edit_history(&self, _playlist_id: &Uuid) -> Result<Vec<PlaylistEditEvent>, AppError> { Ok(Vec::new()) }
+    }
+
+    fn album_created(album_id: Uuid, artist_id: Uuid, song_ids: Vec<Uuid>) -> DomainEvent {
+        DomainEvent::AlbumCreated {
+            album_id,
+            artist_id,
+            title: "Night Drive".to_string(),
+            song_ids,
+            occurred_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn album_playlist_keeps_track_order() {
+        let repository = Arc::new(InMemoryPlaylistRepository::default());
+        let handler = AlbumCreatedEventHandler::new(repository.clone());
+        let (album_id, artist_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let tracks = vec![Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4()];
+
+        handler.handle(&album_created(album_id, artist_id, tracks.clone())).await.unwrap();
+
+        let playlists = repository.find_by_album(&AlbumId::from_uuid(album_id)).await.unwrap();
+        assert_eq!(playlists.len(), 1);
+        let playlist = &playlists[0];
+        assert_eq!(playlist.name, "Night Drive");
+        assert_eq!(playlist.created_by, artist_id);
+        assert!(playlist.is_auto_generated && playlist.is_public);
+        assert_eq!(repository.get_songs(&playlist.id).await.unwrap(), tracks);
+    }
+
+    #[tokio::test]
+    async fn redelivered_event_does_not_duplicate_the_playlist() {
+        let repository = Arc::new(InMemoryPlaylistRepository::default());
+        let handler = AlbumCreatedEventHandler::new(repository.clone());
+        let event = album_created(Uuid::new_v4(), Uuid::new_v4(), vec![Uuid::new_v4()]);
+
+        handler.handle(&event).await.unwrap();
+        handler.handle(&event).await.unwrap();
+
+        assert_eq!(repository.count().await.unwrap(), 1);
+    }
+}
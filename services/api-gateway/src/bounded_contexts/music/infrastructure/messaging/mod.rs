@@ -1,3 +1,9 @@
 pub mod event_bus;
+pub mod album_playlist;
+pub mod user_deletion;
+pub mod song_similarity;
 
-pub use event_bus::*; 
\ No newline at end of file
+pub use event_bus::*;
+pub use album_playlist::AlbumCreatedEventHandler;
+pub use user_deletion::PlaylistUserDeletionListener;
+pub use song_similarity::SongSimilarityRefreshHandler;
@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::bounded_contexts::music::infrastructure::repositories::PostgresSongSimilarityReadModel;
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+use crate::shared::domain::errors::AppError;
+
+/// Recomputes the similar songs of every uploaded song. Comparing against the
+/// whole catalog is slow, so it runs in the background instead of holding up
+/// the event bus.
+pub struct SongSimilarityRefreshHandler {
+    read_model: Arc<PostgresSongSimilarityReadModel>,
+}
+
+impl SongSimilarityRefreshHandler {
+    pub fn new(read_model: Arc<PostgresSongSimilarityReadModel>) -> Self {
+        Self { read_model }
+    }
+}
+
+#[async_trait]
+impl EventHandler for SongSimilarityRefreshHandler {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let DomainEvent::SongUploaded { song_id, .. } = event else {
+            return Ok(());
+        };
+
+        let song_id = *song_id;
+        let read_model = Arc::clone(&self.read_model);
+        tokio::spawn(async move {
+            match read_model.refresh_song(song_id).await {
+                Ok(neighbours) => tracing::info!("Song {} has {} similar songs", song_id, neighbours),
+                Err(e) => tracing::error!("Failed to compute similar songs for {}: {}", song_id, e),
+            }
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+//! Playlist User Deletion Listener
+//!
+//! Las playlists de un usuario eliminado son contenido suyo: se borran, junto
+//! con las playlists de otros que seguía.
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+use crate::shared::domain::errors::AppError;
+
+pub struct PlaylistUserDeletionListener {
+    pool: PgPool,
+}
+
+impl PlaylistUserDeletionListener {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_error(e: sqlx::Error) -> AppError {
+    AppError::DatabaseError(format!("Failed to delete playlists: {}", e))
+}
+
+#[async_trait]
+impl EventHandler for PlaylistUserDeletionListener {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let DomainEvent::UserDeletionRequested { user_id, .. } = event else {
+            return Ok(());
+        };
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        sqlx::query("DELETE FROM playlist_followers WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        // playlist_songs y los seguidores de estas playlists se borran en cascada
+        let playlists = sqlx::query("DELETE FROM playlists WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        tx.commit().await.map_err(db_error)?;
+
+        tracing::info!("Deleted {} playlists of deleted user {}", playlists.rows_affected(), user_id);
+        Ok(())
+    }
+}
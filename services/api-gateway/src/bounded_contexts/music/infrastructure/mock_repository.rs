@@ -50,4 +50,7 @@ impl SongRepository for MockMusicRepository {
     async fn get_total_listens(&self) -> RepositoryResult<u64> { Ok(0) }
     async fn find_all(&self, _limit: usize, _offset: usize) -> RepositoryResult<Vec<Song>> { Ok(vec![]) }
     async fn count(&self) -> RepositoryResult<usize> { Ok(0) }
+    async fn find_credits(&self, _song_id: &crate::bounded_contexts::music::domain::value_objects::SongId) -> RepositoryResult<Vec<SongCredit>> { Ok(vec![]) }
+    async fn save_credits(&self, _song_id: &crate::bounded_contexts::music::domain::value_objects::SongId, _credits: &[SongCredit]) -> RepositoryResult<()> { Ok(()) }
+    async fn save_chapter_markers(&self, _song_id: &crate::bounded_contexts::music::domain::value_objects::SongId, _markers: &[ChapterMarker]) -> RepositoryResult<()> { Ok(()) }
 } 
\ No newline at end of file
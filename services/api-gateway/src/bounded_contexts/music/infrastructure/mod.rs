@@ -1,9 +1,16 @@
 pub mod repositories;
 pub mod messaging;
 pub mod storage;
+pub mod search;
 pub mod mock_repository;
+pub mod link_verification;
+pub mod content_moderation;
+pub mod remix_license_payments;
 
 pub use repositories::*;
 pub use messaging::*;
 pub use storage::*; 
-pub use mock_repository::*;
\ No newline at end of file
+pub use mock_repository::*;
+pub use link_verification::LinkVerification;
+pub use content_moderation::{content_moderation_from_env, MockModerationService, OpenAIModerationService};
+pub use remix_license_payments::PaymentContextRemixLicensePayments;
\ No newline at end of file
@@ -0,0 +1,145 @@
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::bounded_contexts::fan_ventures::infrastructure::payment_helper::create_payment_command_handler;
+use crate::bounded_contexts::music::domain::entities::RemixLicense;
+use crate::bounded_contexts::music::domain::services::RemixLicensePayments;
+use crate::bounded_contexts::payment::application::{
+    commands::{
+        CancelPaymentCommand, InitiatePaymentCommand, InitiateRefundCommand, PaymentMetadataDto, PaymentMethodDto,
+        PaymentPurposeDto, StartPaymentProcessingCommand,
+    },
+    currency_conversion::CurrencyConverter,
+    handlers::command_handlers::PaymentCommandHandler,
+};
+use crate::bounded_contexts::payment::domain::{
+    aggregates::PaymentAggregate,
+    repository::PaymentRepository,
+    value_objects::{PaymentId, PaymentStatus},
+};
+use crate::bounded_contexts::payment::infrastructure::repositories::PostgreSQLPaymentRepository;
+use crate::shared::domain::errors::AppError;
+
+/// Charges the licensee and pays the price of a remix license to the artist
+/// as a royalty. The payment is keyed by the license, so retrying the same
+/// license does not charge twice.
+pub struct PaymentContextRemixLicensePayments {
+    payment_handler: Arc<dyn PaymentCommandHandler>,
+    payment_repository: Arc<dyn PaymentRepository>,
+}
+
+impl PaymentContextRemixLicensePayments {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            payment_handler: create_payment_command_handler(pool.clone()),
+            payment_repository: Arc::new(PostgreSQLPaymentRepository::new(pool)),
+        }
+    }
+
+    fn royalty_payment(license: &RemixLicense, artist_id: Uuid) -> InitiatePaymentCommand {
+        InitiatePaymentCommand {
+            payer_id: license.licensee_id,
+            payee_id: artist_id,
+            amount_value: license.price,
+            amount_currency: CurrencyConverter::base_currency_from_env(),
+            payment_method: PaymentMethodDto {
+                method_type: "PlatformBalance".to_string(),
+                card_details: None,
+                crypto_details: None,
+                bank_details: None,
+            },
+            purpose: PaymentPurposeDto {
+                purpose_type: "RoyaltyDistribution".to_string(),
+                campaign_id: None,
+                nft_quantity: None,
+                contract_id: None,
+                ownership_percentage: None,
+                share_id: None,
+                from_user: None,
+                to_user: None,
+                song_id: Some(license.song_id.to_uuid()),
+                artist_id: Some(artist_id),
+                session_id: None,
+                listen_duration: None,
+                distribution_id: None,
+                original_payment_id: None,
+                reason: None,
+            },
+            metadata: PaymentMetadataDto {
+                user_ip: None,
+                user_agent: None,
+                platform_version: env!("CARGO_PKG_VERSION").to_string(),
+                reference_id: Some(license.id.to_string()),
+                additional_data: serde_json::json!({
+                    "remix_license_id": license.id,
+                    "license_type": license.license_type.to_string(),
+                }),
+            },
+            idempotency_key: Some(format!("remix_license_{}", license.id)),
+        }
+    }
+
+    async fn load(&self, payment_id: Uuid) -> Result<PaymentAggregate, AppError> {
+        self.payment_repository
+            .find_by_id(&PaymentId::from_uuid(payment_id))
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Payment {} not found", payment_id)))
+    }
+}
+
+#[async_trait]
+impl RemixLicensePayments for PaymentContextRemixLicensePayments {
+    async fn charge(&self, license: &RemixLicense, artist_id: Uuid) -> Result<Uuid, AppError> {
+        let payment = self.payment_handler
+            .handle_initiate_payment(Self::royalty_payment(license, artist_id))
+            .await?;
+
+        let processed = self.payment_handler.handle_start_processing(StartPaymentProcessingCommand {
+            payment_id: payment.payment_id,
+            processor_id: "System".to_string(),
+            external_transaction_id: None,
+        }).await?;
+
+        if !self.load(payment.payment_id).await?.payment().status().is_successful() {
+            // Sin cobro no hay licencia: el pago no debe quedar vivo
+            self.refund(payment.payment_id, license.licensee_id, "Remix license payment not settled").await?;
+            return Err(AppError::ExternalServiceError(format!(
+                "Remix license payment {} was not settled ({})",
+                payment.payment_id, processed.status
+            )));
+        }
+        tracing::info!(
+            "Royalty payment {} collected for remix license {} of song {}",
+            payment.payment_id, license.id, license.song_id.to_uuid()
+        );
+        Ok(payment.payment_id)
+    }
+
+    async fn refund(&self, payment_id: Uuid, requested_by: Uuid, reason: &str) -> Result<(), AppError> {
+        let payment = self.load(payment_id).await?;
+        match payment.payment().status() {
+            PaymentStatus::Completed => {
+                let amount = payment.payment().amount();
+                self.payment_handler.handle_initiate_refund(InitiateRefundCommand {
+                    original_payment_id: payment_id,
+                    refund_amount: amount.value(),
+                    refund_currency: amount.currency().clone(),
+                    reason: reason.to_string(),
+                    initiated_by: requested_by,
+                }).await?;
+            }
+            PaymentStatus::Pending | PaymentStatus::Processing | PaymentStatus::OnHold => {
+                self.payment_handler.handle_cancel_payment(CancelPaymentCommand {
+                    payment_id,
+                    reason: reason.to_string(),
+                    cancelled_by: requested_by,
+                }).await?;
+            }
+            // Ya fallido, cancelado o reembolsado: no hay fondos que devolver
+            _ => {}
+        }
+        Ok(())
+    }
+}
@@ -1,10 +1,26 @@
 pub mod postgres_song_repository;
 pub mod postgres_album_repository;
 pub mod postgres_playlist_repository;
+pub mod postgres_remix_license_repository;
+pub mod postgres_recommendation_read_model;
+pub mod postgres_artist_followers_read_model;
+pub mod postgres_artist_dashboard_read_model;
+pub mod postgres_song_analytics_read_model;
+pub mod postgres_song_similarity_read_model;
+pub mod postgres_video_transcode_job_repository;
 
 pub use postgres_song_repository::*;
 pub use postgres_album_repository::*;
 pub use postgres_playlist_repository::*;
+pub use postgres_remix_license_repository::PostgresRemixLicenseRepository;
+pub use postgres_recommendation_read_model::PostgresPlaylistRecommendationReadModel;
+pub use postgres_artist_followers_read_model::{ArtistFollower, PostgresArtistFollowersReadModel};
+pub use postgres_artist_dashboard_read_model::{
+    ArtistDashboard, CampaignSummary, PostgresArtistDashboardReadModel, SongKpi, DASHBOARD_TOP_SONGS,
+};
+pub use postgres_song_analytics_read_model::{AnalyticsPeriod, Granularity, PlayCountDataPoint, PostgresSongAnalyticsReadModel};
+pub use postgres_song_similarity_read_model::PostgresSongSimilarityReadModel;
+pub use postgres_video_transcode_job_repository::PostgresTranscodeJobRepository;
 
 // Temporary implementation of MusicCatalogRepository for compilation
 use async_trait::async_trait;
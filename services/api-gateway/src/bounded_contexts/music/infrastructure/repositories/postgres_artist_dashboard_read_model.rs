@@ -0,0 +1,161 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+
+/// Songs listed in `ArtistDashboard::top_songs`
+pub const DASHBOARD_TOP_SONGS: i64 = 5;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SongKpi {
+    pub song_id: Uuid,
+    pub title: String,
+    pub total_streams: u64,
+    /// Distinct listeners over the last 30 days
+    pub monthly_listeners: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CampaignSummary {
+    pub campaign_id: Uuid,
+    pub song_id: Uuid,
+    pub name: String,
+    pub nfts_sold: u32,
+    pub max_nfts: u32,
+    pub end_date: DateTime<Utc>,
+}
+
+/// An artist's KPIs across contexts, in one response
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ArtistDashboard {
+    pub artist_id: Uuid,
+    /// Everything credited to the artist's balance, paid out or not
+    #[schema(value_type = String, example = "1520.75")]
+    pub total_earnings: Decimal,
+    /// Distinct listeners over the last 30 days
+    pub monthly_listeners: u64,
+    pub total_streams: u64,
+    pub top_songs: Vec<SongKpi>,
+    pub active_campaigns: Vec<CampaignSummary>,
+    /// Artist share of the revenue distributed by their fan ventures
+    #[schema(value_type = String, example = "310.00")]
+    pub fractional_ownership_revenue: Decimal,
+    pub follower_count: u64,
+    /// Still owed on active royalty advances
+    #[schema(value_type = String, example = "500.00")]
+    pub royalty_advances_outstanding: Decimal,
+}
+
+/// Artist dashboard built in one query. Songs and the listen log are keyed by
+/// the artist id; follows, ventures and payments point at the artist's user,
+/// so those CTEs match on either id.
+pub struct PostgresArtistDashboardReadModel {
+    pool: PgPool,
+}
+
+const DASHBOARD_QUERY: &str = r#"
+    WITH artist AS (
+        SELECT id, user_id, follower_count FROM artists WHERE id = $1
+    ),
+    song_listens AS (
+        SELECT s.id, s.title,
+               COUNT(le.id) AS total_streams,
+               COUNT(DISTINCT le.user_id) FILTER (WHERE le.created_at >= NOW() - INTERVAL '30 days') AS monthly_listeners
+        FROM songs s
+        LEFT JOIN listen_events le ON le.song_id = s.id
+        WHERE s.artist_id = $1
+        GROUP BY s.id, s.title
+    ),
+    listeners AS (
+        SELECT COUNT(DISTINCT le.user_id) AS monthly_listeners
+        FROM listen_events le
+        INNER JOIN songs s ON s.id = le.song_id
+        WHERE s.artist_id = $1 AND le.created_at >= NOW() - INTERVAL '30 days'
+    ),
+    top_songs AS (
+        SELECT COALESCE(json_agg(json_build_object(
+                   'song_id', t.id, 'title', t.title,
+                   'total_streams', t.total_streams, 'monthly_listeners', t.monthly_listeners
+               ) ORDER BY t.total_streams DESC, t.title), '[]'::json) AS songs
+        FROM (SELECT * FROM song_listens ORDER BY total_streams DESC, title LIMIT $2) t
+    ),
+    active_campaigns AS (
+        SELECT COALESCE(json_agg(json_build_object(
+                   'campaign_id', c.id, 'song_id', c.song_id, 'name', c.name,
+                   'nfts_sold', COALESCE(c.nfts_sold, 0), 'max_nfts', c.max_nfts, 'end_date', c.end_date
+               ) ORDER BY c.end_date), '[]'::json) AS campaigns
+        FROM campaigns c, artist a
+        WHERE c.artist_id IN (a.id, a.user_id) AND c.status = 'Active'
+    ),
+    earnings AS (
+        SELECT COALESCE(SUM(e.amount), 0) AS total
+        FROM artist_balance_entries e, artist a
+        WHERE e.artist_id IN (a.id, a.user_id)
+    ),
+    venture_revenue AS (
+        SELECT COALESCE(SUM(rd.artist_share), 0) AS total
+        FROM revenue_distributions rd
+        INNER JOIN artist_ventures v ON v.id = rd.venture_id, artist a
+        WHERE v.artist_id IN (a.id, a.user_id)
+    ),
+    advances AS (
+        SELECT COALESCE(SUM(ra.outstanding_balance), 0) AS total
+        FROM royalty_advances ra, artist a
+        WHERE ra.artist_id IN (a.id, a.user_id) AND ra.status = 'active'
+    )
+    SELECT a.id,
+           a.follower_count,
+           earnings.total AS total_earnings,
+           listeners.monthly_listeners,
+           (SELECT COALESCE(SUM(total_streams), 0)::BIGINT FROM song_listens) AS total_streams,
+           top_songs.songs AS top_songs,
+           active_campaigns.campaigns AS active_campaigns,
+           venture_revenue.total AS fractional_ownership_revenue,
+           advances.total AS royalty_advances_outstanding
+    FROM artist a, earnings, listeners, top_songs, active_campaigns, venture_revenue, advances
+"#;
+
+impl PostgresArtistDashboardReadModel {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// The user behind the artist, to authorize the request before building
+    /// the dashboard. `None` if the artist does not exist.
+    pub async fn artist_user_id(&self, artist_id: Uuid) -> Result<Option<Uuid>, AppError> {
+        sqlx::query_scalar("SELECT user_id FROM artists WHERE id = $1")
+            .bind(artist_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to load artist: {}", e)))
+    }
+
+    /// `None` if the artist does not exist
+    pub async fn dashboard(&self, artist_id: Uuid) -> Result<Option<ArtistDashboard>, AppError> {
+        let Some(row) = sqlx::query(DASHBOARD_QUERY)
+            .bind(artist_id)
+            .bind(DASHBOARD_TOP_SONGS)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to load artist dashboard: {}", e)))?
+        else {
+            return Ok(None);
+        };
+
+        let decode = |e: serde_json::Error| AppError::SerializationError(format!("Invalid artist dashboard: {}", e));
+        Ok(Some(ArtistDashboard {
+            artist_id: row.get("id"),
+            total_earnings: row.get("total_earnings"),
+            monthly_listeners: row.get::<i64, _>("monthly_listeners").max(0) as u64,
+            total_streams: row.get::<i64, _>("total_streams").max(0) as u64,
+            top_songs: serde_json::from_value(row.get("top_songs")).map_err(decode)?,
+            active_campaigns: serde_json::from_value(row.get("active_campaigns")).map_err(decode)?,
+            fractional_ownership_revenue: row.get("fractional_ownership_revenue"),
+            follower_count: row.get::<i64, _>("follower_count").max(0) as u64,
+            royalty_advances_outstanding: row.get("royalty_advances_outstanding"),
+        }))
+    }
+}
@@ -0,0 +1,106 @@
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::bounded_contexts::music::infrastructure::search::ArtistSearchResult;
+use crate::shared::domain::errors::AppError;
+
+/// A user following an artist
+#[derive(Debug, Clone, Serialize)]
+pub struct ArtistFollower {
+    pub user_id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub followed_at: DateTime<Utc>,
+}
+
+/// Followers of an artist. The follow graph (`follows`) belongs to the user
+/// context and points at the artist's user; `artists.follower_count` is kept
+/// in step by the follow/unfollow writes.
+pub struct PostgresArtistFollowersReadModel {
+    pool: PgPool,
+}
+
+impl PostgresArtistFollowersReadModel {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// `None` if the artist does not exist
+    pub async fn follower_count(&self, artist_id: Uuid) -> Result<Option<u64>, AppError> {
+        let count: Option<i64> = sqlx::query_scalar("SELECT follower_count FROM artists WHERE id = $1")
+            .bind(artist_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to load follower count: {}", e)))?;
+        Ok(count.map(|count| count.max(0) as u64))
+    }
+
+    /// Most recent followers first; `page` starts at 0
+    pub async fn followers(&self, artist_id: Uuid, page: u32, page_size: u32) -> Result<Vec<ArtistFollower>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT u.id, u.username, u.display_name, u.avatar_url, f.created_at
+            FROM artists a
+            INNER JOIN follows f ON f.followee_id = a.user_id
+            INNER JOIN users u ON u.id = f.follower_id
+            WHERE a.id = $1
+            ORDER BY f.created_at DESC, u.id
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(artist_id)
+        .bind(page_size as i64)
+        .bind(page as i64 * page_size as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load artist followers: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| ArtistFollower {
+                user_id: row.get("id"),
+                username: row.get("username"),
+                display_name: row.get("display_name"),
+                avatar_url: row.get("avatar_url"),
+                followed_at: row.get("created_at"),
+            })
+            .collect())
+    }
+
+    /// Search document for the artist, with the denormalized follower count;
+    /// what indexers pass to `ElasticsearchMusicSearchService::index_artist`
+    pub async fn search_document(&self, artist_id: Uuid) -> Result<Option<ArtistSearchResult>, AppError> {
+        let row = sqlx::query(
+            r#"
+            SELECT a.id, a.stage_name, a.bio, COALESCE(a.verified, FALSE) AS verified, a.follower_count,
+                   (SELECT COUNT(*) FROM songs s WHERE s.artist_id = a.id) AS song_count,
+                   (SELECT COUNT(*) FROM albums al WHERE al.artist_id = a.id) AS album_count,
+                   ARRAY(SELECT DISTINCT s.genre FROM songs s
+                         WHERE s.artist_id = a.id AND s.genre IS NOT NULL) AS genres
+            FROM artists a
+            WHERE a.id = $1
+            "#,
+        )
+        .bind(artist_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load artist: {}", e)))?;
+
+        Ok(row.map(|row| ArtistSearchResult {
+            id: row.get("id"),
+            name: row.get("stage_name"),
+            bio: row.get("bio"),
+            genres: row.get("genres"),
+            follower_count: row.get::<i64, _>("follower_count").max(0) as u64,
+            song_count: row.get::<i64, _>("song_count") as u32,
+            album_count: row.get::<i64, _>("album_count") as u32,
+            is_verified: row.get("verified"),
+            relevance_score: 0.0,
+            highlight: None,
+            phonetic_match: false,
+        }))
+    }
+}
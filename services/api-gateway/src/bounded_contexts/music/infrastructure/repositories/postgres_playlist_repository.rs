@@ -4,8 +4,10 @@ use sqlx::{PgPool, FromRow};
 use uuid::Uuid;
 
 use crate::bounded_contexts::music::domain::{
-    repositories::{playlist_repository::{Playlist, PlaylistRepository as DomainPlaylistRepository}},
-    value_objects::{PlaylistId, PlaylistName},
+    repositories::{playlist_repository::{
+        Playlist, PlaylistEditAction, PlaylistEditEvent, PlaylistRepository as DomainPlaylistRepository,
+    }},
+    value_objects::{AlbumId, PlaylistId, PlaylistName},
 };
 use crate::bounded_contexts::user::domain::UserId;
 use crate::shared::domain::errors::AppError;
@@ -17,8 +19,11 @@ struct PlaylistRow {
     name: String,
     description: Option<String>,
     is_public: bool,
+    is_collaborative: bool,
     song_count: i32,
     created_by: Uuid,
+    album_id: Option<Uuid>,
+    is_auto_generated: bool,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
 }
@@ -32,14 +37,41 @@ impl PostgresPlaylistRepository {
         Self { pool }
     }
 
+    /// Records an edit inside the transaction that made it
+    async fn record_edit(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        playlist_id: &Uuid,
+        editor_id: &Uuid,
+        action: PlaylistEditAction,
+        song_id: &Uuid,
+    ) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO playlist_edit_history (playlist_id, editor_id, action, song_id, occurred_at) VALUES ($1, $2, $3, $4, $5)"
+        )
+        .bind(playlist_id)
+        .bind(editor_id)
+        .bind(action.as_str())
+        .bind(song_id)
+        .bind(Utc::now())
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
     fn row_to_playlist(&self, row: PlaylistRow) -> Result<Playlist, AppError> {
-        Ok(Playlist::new(
+        let mut playlist = Playlist::new(
             row.id,
             row.name,
             row.description,
             row.is_public,
             row.created_by,
-        ))
+        );
+        playlist.is_collaborative = row.is_collaborative;
+        playlist.album_id = row.album_id.map(AlbumId::from_uuid);
+        playlist.is_auto_generated = row.is_auto_generated;
+        Ok(playlist)
     }
 }
 
@@ -47,10 +79,11 @@ impl PostgresPlaylistRepository {
 impl DomainPlaylistRepository for PostgresPlaylistRepository {
     async fn save(&self, playlist: &Playlist) -> Result<(), AppError> {
         sqlx::query(
-            r#"INSERT INTO playlists (id, name, description, is_public, song_count, created_by, created_at, updated_at)
-               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            r#"INSERT INTO playlists (id, name, description, is_public, song_count, created_by, album_id, is_auto_generated, created_at, updated_at, is_collaborative)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
                ON CONFLICT (id) DO UPDATE SET
-               name = EXCLUDED.name, description = EXCLUDED.description, is_public = EXCLUDED.is_public, updated_at = EXCLUDED.updated_at"#
+               name = EXCLUDED.name, description = EXCLUDED.description, is_public = EXCLUDED.is_public,
+               is_collaborative = EXCLUDED.is_collaborative, updated_at = EXCLUDED.updated_at"#
         )
         .bind(playlist.id)
         .bind(&playlist.name)
@@ -58,8 +91,11 @@ impl DomainPlaylistRepository for PostgresPlaylistRepository {
         .bind(playlist.is_public)
         .bind(playlist.song_count as i32)
         .bind(playlist.created_by)
+        .bind(playlist.album_id.as_ref().map(AlbumId::to_uuid))
+        .bind(playlist.is_auto_generated)
         .bind(playlist.created_at)
         .bind(playlist.updated_at)
+        .bind(playlist.is_collaborative)
         .execute(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -69,7 +105,7 @@ impl DomainPlaylistRepository for PostgresPlaylistRepository {
 
     async fn find_by_id(&self, id: &Uuid) -> Result<Option<Playlist>, AppError> {
         let row: Option<PlaylistRow> = sqlx::query_as(
-            "SELECT id, name, description, is_public, song_count, created_by, created_at, updated_at FROM playlists WHERE id = $1"
+            "SELECT id, name, description, is_public, is_collaborative, song_count, created_by, album_id, is_auto_generated, created_at, updated_at FROM playlists WHERE id = $1"
         )
         .bind(id)
         .fetch_optional(&self.pool)
@@ -85,7 +121,7 @@ impl DomainPlaylistRepository for PostgresPlaylistRepository {
 
     async fn find_by_creator(&self, creator_id: &Uuid) -> Result<Vec<Playlist>, AppError> {
         let rows: Vec<PlaylistRow> = sqlx::query_as(
-            "SELECT id, name, description, is_public, song_count, created_by, created_at, updated_at FROM playlists WHERE created_by = $1"
+            "SELECT id, name, description, is_public, is_collaborative, song_count, created_by, album_id, is_auto_generated, created_at, updated_at FROM playlists WHERE created_by = $1"
         )
         .bind(creator_id)
         .fetch_all(&self.pool)
@@ -99,10 +135,24 @@ impl DomainPlaylistRepository for PostgresPlaylistRepository {
         playlists
     }
 
+    async fn find_by_album(&self, album_id: &AlbumId) -> Result<Vec<Playlist>, AppError> {
+        let rows: Vec<PlaylistRow> = sqlx::query_as(
+            "SELECT id, name, description, is_public, is_collaborative, song_count, created_by, album_id, is_auto_generated, created_at, updated_at FROM playlists WHERE album_id = $1 ORDER BY is_auto_generated DESC, created_at ASC"
+        )
+        .bind(album_id.value())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| self.row_to_playlist(row))
+            .collect()
+    }
+
     async fn find_public_playlists(&self, page: u32, page_size: u32) -> Result<Vec<Playlist>, AppError> {
         let offset = (page - 1) * page_size;
         let rows: Vec<PlaylistRow> = sqlx::query_as(
-            "SELECT id, name, description, is_public, song_count, created_by, created_at, updated_at FROM playlists WHERE is_public = true ORDER BY created_at DESC LIMIT $1 OFFSET $2"
+            "SELECT id, name, description, is_public, is_collaborative, song_count, created_by, album_id, is_auto_generated, created_at, updated_at FROM playlists WHERE is_public = true ORDER BY created_at DESC LIMIT $1 OFFSET $2"
         )
         .bind(page_size as i64)
         .bind(offset as i64)
@@ -120,7 +170,7 @@ impl DomainPlaylistRepository for PostgresPlaylistRepository {
     async fn find_all(&self, page: u32, page_size: u32) -> Result<Vec<Playlist>, AppError> {
         let offset = (page - 1) * page_size;
         let rows: Vec<PlaylistRow> = sqlx::query_as(
-            "SELECT id, name, description, is_public, song_count, created_by, created_at, updated_at FROM playlists ORDER BY created_at DESC LIMIT $1 OFFSET $2"
+            "SELECT id, name, description, is_public, is_collaborative, song_count, created_by, album_id, is_auto_generated, created_at, updated_at FROM playlists ORDER BY created_at DESC LIMIT $1 OFFSET $2"
         )
         .bind(page_size as i64)
         .bind(offset as i64)
@@ -137,7 +187,7 @@ impl DomainPlaylistRepository for PostgresPlaylistRepository {
 
     async fn update(&self, playlist: &Playlist) -> Result<(), AppError> {
         sqlx::query(
-            r#"UPDATE playlists SET name = $2, description = $3, is_public = $4, song_count = $5, updated_at = $6 WHERE id = $1"#
+            r#"UPDATE playlists SET name = $2, description = $3, is_public = $4, song_count = $5, updated_at = $6, is_collaborative = $7 WHERE id = $1"#
         )
         .bind(playlist.id)
         .bind(&playlist.name)
@@ -145,6 +195,7 @@ impl DomainPlaylistRepository for PostgresPlaylistRepository {
         .bind(playlist.is_public)
         .bind(playlist.song_count as i32)
         .bind(playlist.updated_at)
+        .bind(playlist.is_collaborative)
         .execute(&self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(e.to_string()))?;
@@ -173,7 +224,7 @@ impl DomainPlaylistRepository for PostgresPlaylistRepository {
 
     async fn search_by_name(&self, name: &str) -> Result<Vec<Playlist>, AppError> {
         let rows: Vec<PlaylistRow> = sqlx::query_as(
-            "SELECT id, name, description, is_public, song_count, created_by, created_at, updated_at FROM playlists WHERE name ILIKE $1"
+            "SELECT id, name, description, is_public, is_collaborative, song_count, created_by, album_id, is_auto_generated, created_at, updated_at FROM playlists WHERE name ILIKE $1"
         )
         .bind(format!("%{}%", name))
         .fetch_all(&self.pool)
@@ -187,20 +238,30 @@ impl DomainPlaylistRepository for PostgresPlaylistRepository {
         playlists
     }
 
-    async fn add_song(&self, playlist_id: &Uuid, song_id: &Uuid) -> Result<(), AppError> {
+    async fn add_song(&self, playlist_id: &Uuid, song_id: &Uuid, added_by: &Uuid) -> Result<(), AppError> {
         let mut tx = self.pool.begin().await
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
-        // Add song to playlist
-        sqlx::query(
-            "INSERT INTO playlist_songs (playlist_id, song_id, added_at) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING"
+        // Add song to playlist, at the end
+        let inserted = sqlx::query(
+            r#"INSERT INTO playlist_songs (playlist_id, song_id, position, added_at, added_by)
+               SELECT $1, $2, COALESCE(MAX(position), 0) + 1, $3, $4 FROM playlist_songs WHERE playlist_id = $1
+               ON CONFLICT DO NOTHING"#
         )
         .bind(playlist_id)
         .bind(song_id)
         .bind(Utc::now())
+        .bind(added_by)
         .execute(&mut *tx)
         .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .rows_affected();
+
+        // Already in the playlist: nothing changed, nothing to record
+        if inserted == 0 {
+            return Ok(());
+        }
+        Self::record_edit(&mut tx, playlist_id, added_by, PlaylistEditAction::AddSong, song_id).await?;
 
         // Update song count
         sqlx::query(
@@ -218,19 +279,25 @@ impl DomainPlaylistRepository for PostgresPlaylistRepository {
         Ok(())
     }
 
-    async fn remove_song(&self, playlist_id: &Uuid, song_id: &Uuid) -> Result<(), AppError> {
+    async fn remove_song(&self, playlist_id: &Uuid, song_id: &Uuid, removed_by: &Uuid) -> Result<(), AppError> {
         let mut tx = self.pool.begin().await
             .map_err(|e| AppError::DatabaseError(e.to_string()))?;
 
         // Remove song from playlist
-        sqlx::query(
+        let removed = sqlx::query(
             "DELETE FROM playlist_songs WHERE playlist_id = $1 AND song_id = $2"
         )
         .bind(playlist_id)
         .bind(song_id)
         .execute(&mut *tx)
         .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .rows_affected();
+
+        if removed == 0 {
+            return Ok(());
+        }
+        Self::record_edit(&mut tx, playlist_id, removed_by, PlaylistEditAction::RemoveSong, song_id).await?;
 
         // Update song count
         sqlx::query(
@@ -250,7 +317,7 @@ impl DomainPlaylistRepository for PostgresPlaylistRepository {
 
     async fn get_songs(&self, playlist_id: &Uuid) -> Result<Vec<Uuid>, AppError> {
         let rows: Vec<(Uuid,)> = sqlx::query_as(
-            "SELECT song_id FROM playlist_songs WHERE playlist_id = $1 ORDER BY added_at ASC"
+            "SELECT song_id FROM playlist_songs WHERE playlist_id = $1 ORDER BY position ASC, added_at ASC"
         )
         .bind(playlist_id)
         .fetch_all(&self.pool)
@@ -260,4 +327,62 @@ impl DomainPlaylistRepository for PostgresPlaylistRepository {
         let song_ids: Vec<Uuid> = rows.into_iter().map(|(song_id,)| song_id).collect();
         Ok(song_ids)
     }
-} 
\ No newline at end of file
+
+    async fn follow(&self, playlist_id: &Uuid, user_id: &Uuid) -> Result<(), AppError> {
+        sqlx::query(
+            "INSERT INTO playlist_followers (playlist_id, user_id, followed_at) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING"
+        )
+        .bind(playlist_id)
+        .bind(user_id)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn unfollow(&self, playlist_id: &Uuid, user_id: &Uuid) -> Result<(), AppError> {
+        sqlx::query("DELETE FROM playlist_followers WHERE playlist_id = $1 AND user_id = $2")
+            .bind(playlist_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn is_follower(&self, playlist_id: &Uuid, user_id: &Uuid) -> Result<bool, AppError> {
+        sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM playlist_followers WHERE playlist_id = $1 AND user_id = $2)"
+        )
+        .bind(playlist_id)
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn edit_history(&self, playlist_id: &Uuid) -> Result<Vec<PlaylistEditEvent>, AppError> {
+        let rows: Vec<(Uuid, String, Uuid, DateTime<Utc>)> = sqlx::query_as(
+            "SELECT editor_id, action, song_id, occurred_at FROM playlist_edit_history WHERE playlist_id = $1 ORDER BY occurred_at ASC, id ASC"
+        )
+        .bind(playlist_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter()
+            .map(|(editor_id, action, song_id, timestamp)| {
+                Ok(PlaylistEditEvent {
+                    playlist_id: *playlist_id,
+                    editor_id,
+                    action: action.parse()?,
+                    song_id,
+                    timestamp,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,182 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::{PgPool, Row};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::bounded_contexts::music::domain::services::playlist_recommendation::{
+    compute_user_similarities, PlaylistRecommendationReadModel, SimilarUser, SIMILAR_USERS_LIMIT,
+};
+use crate::shared::domain::errors::AppError;
+
+/// Listening history considered when comparing users
+const SIMILARITY_WINDOW_DAYS: i64 = 180;
+
+pub struct PostgresPlaylistRecommendationReadModel {
+    pool: PgPool,
+}
+
+impl PostgresPlaylistRecommendationReadModel {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Recompute `user_similarities` from the recent listening history.
+    /// Returns how many users got neighbours.
+    pub async fn refresh_user_similarities(&self) -> Result<usize, AppError> {
+        let now = Utc::now();
+        let listens = self.listened_songs(now - Duration::days(SIMILARITY_WINDOW_DAYS)).await?;
+        let similarities = compute_user_similarities(&listens, SIMILAR_USERS_LIMIT);
+        self.replace_similarities(&similarities, now).await?;
+        Ok(similarities.len())
+    }
+
+    async fn listened_songs(&self, since: DateTime<Utc>) -> Result<HashMap<Uuid, HashSet<Uuid>>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT DISTINCT user_id, song_id
+            FROM listen_sessions
+            WHERE started_at >= $1 AND status NOT IN ('failed', 'deleted')
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load listening history: {}", e)))?;
+
+        let mut listens: HashMap<Uuid, HashSet<Uuid>> = HashMap::new();
+        for row in rows {
+            listens.entry(row.get("user_id")).or_default().insert(row.get("song_id"));
+        }
+        Ok(listens)
+    }
+
+    async fn replace_similarities(
+        &self,
+        similarities: &HashMap<Uuid, Vec<SimilarUser>>,
+        computed_at: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let mut user_ids = Vec::new();
+        let mut similar_user_ids = Vec::new();
+        let mut scores = Vec::new();
+        for (user_id, neighbours) in similarities {
+            for neighbour in neighbours {
+                user_ids.push(*user_id);
+                similar_user_ids.push(neighbour.user_id);
+                scores.push(neighbour.similarity as f32);
+            }
+        }
+
+        let db_error = |e: sqlx::Error| AppError::DatabaseError(format!("Failed to store user similarities: {}", e));
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        sqlx::query("DELETE FROM user_similarities")
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        sqlx::query(
+            r#"
+            INSERT INTO user_similarities (user_id, similar_user_id, similarity, computed_at)
+            SELECT u.user_id, u.similar_user_id, u.similarity, $4
+            FROM UNNEST($1::uuid[], $2::uuid[], $3::real[]) AS u(user_id, similar_user_id, similarity)
+            "#,
+        )
+        .bind(&user_ids)
+        .bind(&similar_user_ids)
+        .bind(&scores)
+        .bind(computed_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        tx.commit().await.map_err(db_error)?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl PlaylistRecommendationReadModel for PostgresPlaylistRecommendationReadModel {
+    async fn similar_users(&self, user_id: Uuid, limit: usize) -> Result<Vec<SimilarUser>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT similar_user_id, similarity
+            FROM user_similarities
+            WHERE user_id = $1
+            ORDER BY similarity DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load similar users: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| SimilarUser {
+                user_id: row.get("similar_user_id"),
+                similarity: row.get::<f32, _>("similarity") as f64,
+            })
+            .collect())
+    }
+
+    async fn followed_playlists(&self, user_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Uuid>>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT f.user_id, f.playlist_id
+            FROM playlist_followers f
+            JOIN playlists p ON p.id = f.playlist_id
+            WHERE f.user_id = ANY($1) AND p.is_public = true
+            "#,
+        )
+        .bind(user_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load followed playlists: {}", e)))?;
+
+        let mut follows: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for row in rows {
+            follows.entry(row.get("user_id")).or_default().push(row.get("playlist_id"));
+        }
+        Ok(follows)
+    }
+
+    async fn seen_playlists(&self, user_id: Uuid) -> Result<HashSet<Uuid>, AppError> {
+        let ids: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM playlists WHERE created_by = $1
+            UNION
+            SELECT playlist_id FROM playlist_followers WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load user playlists: {}", e)))?;
+
+        Ok(ids.into_iter().collect())
+    }
+
+    async fn popular_playlists(&self, excluding: &[Uuid], limit: usize) -> Result<Vec<(Uuid, u64)>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT p.id, COUNT(f.user_id) AS followers
+            FROM playlists p
+            LEFT JOIN playlist_followers f ON f.playlist_id = p.id
+            WHERE p.is_public = true AND NOT (p.id = ANY($1))
+            GROUP BY p.id
+            ORDER BY followers DESC, MAX(p.updated_at) DESC
+            LIMIT $2
+            "#,
+        )
+        .bind(excluding)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load popular playlists: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.get("id"), row.get::<i64, _>("followers") as u64))
+            .collect())
+    }
+}
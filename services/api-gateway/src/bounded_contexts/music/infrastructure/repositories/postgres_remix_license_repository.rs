@@ -0,0 +1,82 @@
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use uuid::Uuid;
+
+use crate::bounded_contexts::music::domain::{
+    RemixLicense, RemixLicenseType, SongId,
+    repositories::{RemixLicenseRepository, RepositoryError, RepositoryResult},
+};
+
+pub struct PostgresRemixLicenseRepository {
+    pool: PgPool,
+}
+
+impl PostgresRemixLicenseRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_license(row: PgRow) -> Result<RemixLicense, RepositoryError> {
+        let license_type: String = row.try_get("license_type").map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
+        Ok(RemixLicense {
+            id: row.try_get("id").map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+            song_id: SongId::from_uuid(row.try_get("song_id").map_err(|e| RepositoryError::SerializationError(e.to_string()))?),
+            licensee_id: row.try_get("licensee_id").map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+            license_type: RemixLicenseType::from_string(&license_type).map_err(RepositoryError::SerializationError)?,
+            price: row.try_get("price").map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+            expiry: row.try_get("expiry").map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+            created_at: row.try_get("created_at").map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+        })
+    }
+}
+
+#[async_trait]
+impl RemixLicenseRepository for PostgresRemixLicenseRepository {
+    async fn save(&self, license: &RemixLicense) -> RepositoryResult<()> {
+        sqlx::query(
+            r#"INSERT INTO remix_licenses (id, song_id, licensee_id, license_type, price, expiry, created_at)
+               VALUES ($1, $2, $3, $4, $5::float8, $6, $7)"#
+        )
+        .bind(license.id)
+        .bind(license.song_id.to_uuid())
+        .bind(license.licensee_id)
+        .bind(license.license_type.as_str())
+        .bind(license.price)
+        .bind(license.expiry)
+        .bind(license.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        Ok(())
+    }
+
+    async fn find_by_song(&self, song_id: &SongId) -> RepositoryResult<Vec<RemixLicense>> {
+        let rows = sqlx::query(
+            r#"SELECT id, song_id, licensee_id, license_type, price::float8 AS price, expiry, created_at
+               FROM remix_licenses WHERE song_id = $1
+               ORDER BY created_at"#
+        )
+        .bind(song_id.to_uuid())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(Self::row_to_license).collect()
+    }
+
+    async fn find_for_licensee(&self, song_id: &SongId, licensee_id: Uuid) -> RepositoryResult<Vec<RemixLicense>> {
+        let rows = sqlx::query(
+            r#"SELECT id, song_id, licensee_id, license_type, price::float8 AS price, expiry, created_at
+               FROM remix_licenses WHERE song_id = $1 AND licensee_id = $2
+               ORDER BY expiry DESC"#
+        )
+        .bind(song_id.to_uuid())
+        .bind(licensee_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(Self::row_to_license).collect()
+    }
+}
@@ -0,0 +1,143 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+
+/// A listen counts as completed once it covers this share of the song
+pub const COMPLETION_THRESHOLD: f64 = 0.9;
+
+/// How far back the analytics look, ending now
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AnalyticsPeriod {
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+}
+
+impl AnalyticsPeriod {
+    fn interval(&self) -> &'static str {
+        match self {
+            AnalyticsPeriod::Day => "1 day",
+            AnalyticsPeriod::Week => "7 days",
+            AnalyticsPeriod::Month => "1 month",
+            AnalyticsPeriod::Quarter => "3 months",
+            AnalyticsPeriod::Year => "1 year",
+        }
+    }
+}
+
+/// Width of each data point
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Granularity {
+    Hourly,
+    Daily,
+    Weekly,
+}
+
+impl Granularity {
+    /// `DATE_TRUNC` field, also the step between buckets
+    fn unit(&self) -> &'static str {
+        match self {
+            Granularity::Hourly => "hour",
+            Granularity::Daily => "day",
+            Granularity::Weekly => "week",
+        }
+    }
+
+    /// Hourly points are only offered up to a week (168 points)
+    pub fn supports(&self, period: AnalyticsPeriod) -> bool {
+        *self != Granularity::Hourly || matches!(period, AnalyticsPeriod::Day | AnalyticsPeriod::Week)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlayCountDataPoint {
+    pub period_start: DateTime<Utc>,
+    pub play_count: u64,
+    pub unique_listeners: u64,
+    /// Completed listens over plays; 0 for buckets without plays
+    pub completion_rate: f64,
+}
+
+/// Play counts of a song over time, from the raw `listen_events` log
+pub struct PostgresSongAnalyticsReadModel {
+    pool: PgPool,
+}
+
+impl PostgresSongAnalyticsReadModel {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// One point per bucket from the start of the period to now, oldest
+    /// first; buckets without plays are included with zeros. The first bucket
+    /// starts at the truncated period start, so it may reach slightly further
+    /// back than the period itself.
+    pub async fn get_play_count_by_period(
+        &self,
+        song_id: Uuid,
+        period: AnalyticsPeriod,
+        granularity: Granularity,
+    ) -> Result<Vec<PlayCountDataPoint>, AppError> {
+        if !granularity.supports(period) {
+            return Err(AppError::ValidationError(format!(
+                "{:?} granularity is not available for a {:?} period",
+                granularity, period
+            )));
+        }
+
+        let rows = sqlx::query(
+            r#"
+            WITH buckets AS (
+                SELECT generate_series(
+                    DATE_TRUNC($2, NOW() - $3::interval),
+                    DATE_TRUNC($2, NOW()),
+                    ('1 ' || $2)::interval
+                ) AS period_start
+            )
+            SELECT b.period_start,
+                   COUNT(le.id) AS play_count,
+                   COUNT(DISTINCT le.user_id) AS unique_listeners,
+                   COUNT(le.id) FILTER (
+                       WHERE s.duration_seconds > 0
+                         AND le.listen_duration_seconds >= s.duration_seconds * $4
+                   ) AS completed_count
+            FROM buckets b
+            LEFT JOIN listen_events le
+                   ON le.song_id = $1
+                  AND le.created_at >= DATE_TRUNC($2, NOW() - $3::interval)
+                  AND DATE_TRUNC($2, le.created_at) = b.period_start
+            LEFT JOIN songs s ON s.id = le.song_id
+            GROUP BY b.period_start
+            ORDER BY b.period_start
+            "#,
+        )
+        .bind(song_id)
+        .bind(granularity.unit())
+        .bind(period.interval())
+        .bind(COMPLETION_THRESHOLD)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load play counts: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let play_count = row.get::<i64, _>("play_count").max(0) as u64;
+                let completed = row.get::<i64, _>("completed_count").max(0) as u64;
+                PlayCountDataPoint {
+                    period_start: row.get("period_start"),
+                    play_count,
+                    unique_listeners: row.get::<i64, _>("unique_listeners").max(0) as u64,
+                    completion_rate: if play_count == 0 { 0.0 } else { completed as f64 / play_count as f64 },
+                }
+            })
+            .collect())
+    }
+}
@@ -3,7 +3,7 @@ use sqlx::{PgPool, Row};
 
 use crate::bounded_contexts::music::domain::{
     Song, SongId, ArtistId, Genre, 
-    value_objects::{SongTitle, SongDuration, RoyaltyPercentage, ListenCount}
+    value_objects::{SongTitle, SongDuration, RoyaltyPercentage, ListenCount, SongCredit, CreditType, IpfsHash, SongMood, Tempo, AudioQuality, ChapterMarker}
 };
 use crate::bounded_contexts::music::domain::repositories::{SongRepository, RepositoryResult, RepositoryError};
 
@@ -43,7 +43,7 @@ impl PostgresSongRepository {
         ).map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
 
         // Create song with basic fields first
-        let mut song = Song::new(title, artist_id, duration, genre, royalty_percentage);
+        let mut song = Song::with_id(id, title, artist_id, duration, genre, royalty_percentage);
 
         // Set additional fields from database
         let listen_count: i64 = row.try_get("listen_count").unwrap_or(0);
@@ -52,6 +52,36 @@ impl PostgresSongRepository {
         let revenue: f64 = row.try_get("revenue_generated").unwrap_or(0.0);
         song.set_revenue_generated(revenue);
 
+        let stems_ipfs_hash: Option<String> = row.try_get("stems_ipfs_hash").unwrap_or(None);
+        if let Some(hash) = stems_ipfs_hash {
+            if row.try_get("has_stems").unwrap_or(false) {
+                song.set_stems(IpfsHash::new(hash).map_err(RepositoryError::ValidationError)?);
+            }
+        }
+
+        let mood: Option<String> = row.try_get("mood").unwrap_or(None);
+        if let Some(mood) = mood {
+            song.set_mood(SongMood::from_string(&mood).map_err(RepositoryError::ValidationError)?);
+        }
+        let tempo_bpm: Option<i16> = row.try_get("tempo_bpm").unwrap_or(None);
+        if let Some(bpm) = tempo_bpm {
+            song.set_tempo(Tempo::new(bpm as u16).map_err(RepositoryError::ValidationError)?);
+        }
+        let valence: Option<f32> = row.try_get("valence").unwrap_or(None);
+        let energy: Option<f32> = row.try_get("energy").unwrap_or(None);
+        if let (Some(valence), Some(energy)) = (valence, energy) {
+            song.set_audio_features(valence, energy).map_err(RepositoryError::ValidationError)?;
+        }
+        let audio_quality: Option<String> = row.try_get("audio_quality").unwrap_or(None);
+        if let Some(quality) = audio_quality {
+            song.set_audio_quality(AudioQuality::from_string(&quality).map_err(RepositoryError::ValidationError)?);
+        }
+        song.set_pending_review(row.try_get("is_pending_review").unwrap_or(false));
+        let chapter_markers: Option<sqlx::types::Json<Vec<ChapterMarker>>> = row.try_get("chapter_markers").unwrap_or(None);
+        if let Some(sqlx::types::Json(markers)) = chapter_markers {
+            song.set_chapter_markers(markers).map_err(RepositoryError::ValidationError)?;
+        }
+
         Ok(song)
     }
 }
@@ -62,8 +92,9 @@ impl SongRepository for PostgresSongRepository {
         sqlx::query(
             r#"INSERT INTO songs (id, title, artist_id, duration_seconds, genre, royalty_percentage, 
                                   listen_count, revenue_generated, is_available_for_campaign, 
-                                  is_available_for_ownership, created_at, updated_at)
-               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+                                  is_available_for_ownership, has_stems, stems_ipfs_hash, mood, tempo_bpm,
+                                  valence, energy, audio_quality, is_pending_review, created_at, updated_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
                ON CONFLICT (id) DO UPDATE SET
                    title = EXCLUDED.title,
                    genre = EXCLUDED.genre,
@@ -72,6 +103,14 @@ impl SongRepository for PostgresSongRepository {
                    revenue_generated = EXCLUDED.revenue_generated,
                    is_available_for_campaign = EXCLUDED.is_available_for_campaign,
                    is_available_for_ownership = EXCLUDED.is_available_for_ownership,
+                   has_stems = EXCLUDED.has_stems,
+                   stems_ipfs_hash = EXCLUDED.stems_ipfs_hash,
+                   mood = EXCLUDED.mood,
+                   tempo_bpm = EXCLUDED.tempo_bpm,
+                   valence = EXCLUDED.valence,
+                   energy = EXCLUDED.energy,
+                   audio_quality = EXCLUDED.audio_quality,
+                   is_pending_review = EXCLUDED.is_pending_review,
                    updated_at = EXCLUDED.updated_at"#
         )
         .bind(song.id().to_uuid())
@@ -84,6 +123,14 @@ impl SongRepository for PostgresSongRepository {
         .bind(song.revenue_generated())
         .bind(song.is_available_for_campaign())
         .bind(song.is_available_for_ownership())
+        .bind(song.has_stems())
+        .bind(song.stems_ipfs_hash().map(|h| h.value().to_string()))
+        .bind(song.mood().map(|m| m.to_string()))
+        .bind(song.tempo().map(|t| t.bpm() as i16))
+        .bind(song.valence())
+        .bind(song.energy())
+        .bind(song.audio_quality().map(|q| q.as_str()))
+        .bind(song.is_pending_review())
         .bind(song.created_at())
         .bind(song.updated_at())
         .execute(&self.pool)
@@ -103,7 +150,15 @@ impl SongRepository for PostgresSongRepository {
                    revenue_generated = $6,
                    is_available_for_campaign = $7,
                    is_available_for_ownership = $8,
-                   updated_at = $9
+                   has_stems = $9,
+                   stems_ipfs_hash = $10,
+                   mood = $11,
+                   tempo_bpm = $12,
+                   valence = $13,
+                   energy = $14,
+                   audio_quality = $15,
+                   is_pending_review = $16,
+                   updated_at = $17
                WHERE id = $1"#
         )
         .bind(song.id().to_uuid())
@@ -114,6 +169,14 @@ impl SongRepository for PostgresSongRepository {
         .bind(song.revenue_generated())
         .bind(song.is_available_for_campaign())
         .bind(song.is_available_for_ownership())
+        .bind(song.has_stems())
+        .bind(song.stems_ipfs_hash().map(|h| h.value().to_string()))
+        .bind(song.mood().map(|m| m.to_string()))
+        .bind(song.tempo().map(|t| t.bpm() as i16))
+        .bind(song.valence())
+        .bind(song.energy())
+        .bind(song.audio_quality().map(|q| q.as_str()))
+        .bind(song.is_pending_review())
         .bind(song.updated_at())
         .execute(&self.pool)
         .await
@@ -130,7 +193,8 @@ impl SongRepository for PostgresSongRepository {
         let row = sqlx::query(
             r#"SELECT id, title, artist_id, duration_seconds, genre, royalty_percentage,
                       listen_count, revenue_generated, is_available_for_campaign,
-                      is_available_for_ownership, created_at, updated_at
+                      is_available_for_ownership, has_stems, stems_ipfs_hash, mood, tempo_bpm,
+                      valence, energy, audio_quality, is_pending_review, chapter_markers, created_at, updated_at
                FROM songs WHERE id = $1"#
         )
         .bind(id.to_uuid())
@@ -139,7 +203,13 @@ impl SongRepository for PostgresSongRepository {
         .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
 
         match row {
-            Some(row) => Ok(Some(self.row_to_song(row)?)),
+            Some(row) => {
+                let mut song = self.row_to_song(row)?;
+                let credits = self.find_credits(id).await?;
+                song.set_credits(credits)
+                    .map_err(|e| RepositoryError::ValidationError(e.to_string()))?;
+                Ok(Some(song))
+            }
             None => Ok(None),
         }
     }
@@ -165,7 +235,8 @@ impl SongRepository for PostgresSongRepository {
         let rows = sqlx::query(
             r#"SELECT id, title, artist_id, duration_seconds, genre, royalty_percentage,
                       listen_count, revenue_generated, is_available_for_campaign,
-                      is_available_for_ownership, created_at, updated_at
+                      is_available_for_ownership, has_stems, stems_ipfs_hash, mood, tempo_bpm,
+                      valence, energy, audio_quality, is_pending_review, chapter_markers, created_at, updated_at
                FROM songs 
                ORDER BY created_at DESC
                LIMIT $1 OFFSET $2"#
@@ -188,7 +259,8 @@ impl SongRepository for PostgresSongRepository {
         let rows = sqlx::query(
             r#"SELECT id, title, artist_id, duration_seconds, genre, royalty_percentage,
                       listen_count, revenue_generated, is_available_for_campaign,
-                      is_available_for_ownership, created_at, updated_at
+                      is_available_for_ownership, has_stems, stems_ipfs_hash, mood, tempo_bpm,
+                      valence, energy, audio_quality, is_pending_review, chapter_markers, created_at, updated_at
                FROM songs WHERE artist_id = $1
                ORDER BY created_at DESC"#
         )
@@ -209,7 +281,8 @@ impl SongRepository for PostgresSongRepository {
         let rows = sqlx::query(
             r#"SELECT id, title, artist_id, duration_seconds, genre, royalty_percentage,
                       listen_count, revenue_generated, is_available_for_campaign,
-                      is_available_for_ownership, created_at, updated_at
+                      is_available_for_ownership, has_stems, stems_ipfs_hash, mood, tempo_bpm,
+                      valence, energy, audio_quality, is_pending_review, chapter_markers, created_at, updated_at
                FROM songs WHERE genre = $1
                ORDER BY created_at DESC"#
         )
@@ -232,7 +305,8 @@ impl SongRepository for PostgresSongRepository {
         let rows = sqlx::query(
             r#"SELECT id, title, artist_id, duration_seconds, genre, royalty_percentage,
                       listen_count, revenue_generated, is_available_for_campaign,
-                      is_available_for_ownership, created_at, updated_at
+                      is_available_for_ownership, has_stems, stems_ipfs_hash, mood, tempo_bpm,
+                      valence, energy, audio_quality, is_pending_review, chapter_markers, created_at, updated_at
                FROM songs 
                WHERE created_at > NOW() - INTERVAL '7 days'
                ORDER BY listen_count DESC, created_at DESC
@@ -257,7 +331,8 @@ impl SongRepository for PostgresSongRepository {
         let rows = sqlx::query(
             r#"SELECT id, title, artist_id, duration_seconds, genre, royalty_percentage,
                       listen_count, revenue_generated, is_available_for_campaign,
-                      is_available_for_ownership, created_at, updated_at
+                      is_available_for_ownership, has_stems, stems_ipfs_hash, mood, tempo_bpm,
+                      valence, energy, audio_quality, is_pending_review, chapter_markers, created_at, updated_at
                FROM songs 
                ORDER BY listen_count DESC, revenue_generated DESC
                LIMIT $1"#
@@ -282,7 +357,8 @@ impl SongRepository for PostgresSongRepository {
         let rows = sqlx::query(
             r#"SELECT id, title, artist_id, duration_seconds, genre, royalty_percentage,
                       listen_count, revenue_generated, is_available_for_campaign,
-                      is_available_for_ownership, created_at, updated_at
+                      is_available_for_ownership, has_stems, stems_ipfs_hash, mood, tempo_bpm,
+                      valence, energy, audio_quality, is_pending_review, chapter_markers, created_at, updated_at
                FROM songs 
                WHERE title ILIKE $1
                ORDER BY listen_count DESC, created_at DESC
@@ -332,6 +408,72 @@ impl SongRepository for PostgresSongRepository {
         let total: Option<i64> = row.try_get("total").ok();
         Ok(total.unwrap_or(0) as u64)
     }
+
+    async fn find_credits(&self, song_id: &SongId) -> RepositoryResult<Vec<SongCredit>> {
+        let rows = sqlx::query(
+            r#"SELECT contributor_id, credit_type, royalty_share
+               FROM song_credits WHERE song_id = $1
+               ORDER BY created_at"#
+        )
+        .bind(song_id.to_uuid())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        let mut credits = Vec::with_capacity(rows.len());
+        for row in rows {
+            let credit_type: String = row.try_get("credit_type").map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
+            let royalty_share: f64 = row.try_get("royalty_share").map_err(|e| RepositoryError::SerializationError(e.to_string()))?;
+            credits.push(SongCredit::new(
+                row.try_get("contributor_id").map_err(|e| RepositoryError::SerializationError(e.to_string()))?,
+                CreditType::from_string(&credit_type).map_err(RepositoryError::SerializationError)?,
+                RoyaltyPercentage::new(royalty_share).map_err(RepositoryError::ValidationError)?,
+            ));
+        }
+        Ok(credits)
+    }
+
+    async fn save_credits(&self, song_id: &SongId, credits: &[SongCredit]) -> RepositoryResult<()> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        sqlx::query("DELETE FROM song_credits WHERE song_id = $1")
+            .bind(song_id.to_uuid())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        for credit in credits {
+            sqlx::query(
+                r#"INSERT INTO song_credits (song_id, contributor_id, credit_type, royalty_share)
+                   VALUES ($1, $2, $3, $4)"#
+            )
+            .bind(song_id.to_uuid())
+            .bind(credit.contributor_id)
+            .bind(credit.credit_type.as_str())
+            .bind(credit.royalty_share.value())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        }
+
+        tx.commit().await.map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn save_chapter_markers(&self, song_id: &SongId, markers: &[ChapterMarker]) -> RepositoryResult<()> {
+        let result = sqlx::query("UPDATE songs SET chapter_markers = $2, updated_at = NOW() WHERE id = $1")
+            .bind(song_id.to_uuid())
+            .bind(sqlx::types::Json(markers))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| RepositoryError::DatabaseError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(RepositoryError::NotFound);
+        }
+        Ok(())
+    }
 }
 
 // SQL Migration for songs table
@@ -0,0 +1,158 @@
+use chrono::Utc;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::bounded_contexts::music::domain::services::song_similarity::{
+    most_similar_songs, SongFeatures, SIMILAR_SONGS_LIMIT,
+};
+use crate::bounded_contexts::music::domain::value_objects::{AudioQuality, SongMood, Tempo};
+use crate::bounded_contexts::music::infrastructure::search::SongSearchResult;
+use crate::shared::domain::errors::AppError;
+
+/// Precomputed content-based neighbours of each song (`song_similarities`)
+pub struct PostgresSongSimilarityReadModel {
+    pool: PgPool,
+}
+
+impl PostgresSongSimilarityReadModel {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Compare `song_id` against the whole catalog: store its best neighbours
+    /// and offer it as a neighbour to every other song, keeping each song's
+    /// top `SIMILAR_SONGS_LIMIT`. Returns how many neighbours the song got.
+    pub async fn refresh_song(&self, song_id: Uuid) -> Result<usize, AppError> {
+        let catalog = self.song_features().await?;
+        let Some(song) = catalog.iter().find(|features| features.song_id == song_id) else {
+            return Ok(0);
+        };
+        let scored = most_similar_songs(song, &catalog, catalog.len());
+        let neighbours = scored.len().min(SIMILAR_SONGS_LIMIT);
+
+        let other_ids: Vec<Uuid> = scored.iter().map(|similar| similar.song_id).collect();
+        let scores: Vec<f32> = scored.iter().map(|similar| similar.score.clamp(0.0, 1.0) as f32).collect();
+
+        let db_error = |e: sqlx::Error| AppError::DatabaseError(format!("Failed to store song similarities: {}", e));
+        let computed_at = Utc::now();
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        sqlx::query("DELETE FROM song_similarities WHERE song_a_id = $1")
+            .bind(song_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        // Similarity is symmetric: store both directions
+        sqlx::query(
+            r#"
+            INSERT INTO song_similarities (song_a_id, song_b_id, score, computed_at)
+            SELECT $1, u.other_id, u.score, $4
+            FROM UNNEST($2::uuid[], $3::real[]) WITH ORDINALITY AS u(other_id, score, rank)
+            WHERE u.rank <= $5
+            UNION ALL
+            SELECT u.other_id, $1, u.score, $4
+            FROM UNNEST($2::uuid[], $3::real[]) AS u(other_id, score)
+            ON CONFLICT (song_a_id, song_b_id)
+            DO UPDATE SET score = EXCLUDED.score, computed_at = EXCLUDED.computed_at
+            "#,
+        )
+        .bind(song_id)
+        .bind(&other_ids)
+        .bind(&scores)
+        .bind(computed_at)
+        .bind(SIMILAR_SONGS_LIMIT as i64)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        sqlx::query(
+            r#"
+            DELETE FROM song_similarities s
+            USING (
+                SELECT song_a_id, song_b_id,
+                       ROW_NUMBER() OVER (PARTITION BY song_a_id ORDER BY score DESC, song_b_id) AS rank
+                FROM song_similarities
+                WHERE song_a_id = ANY($1)
+            ) ranked
+            WHERE s.song_a_id = ranked.song_a_id
+              AND s.song_b_id = ranked.song_b_id
+              AND ranked.rank > $2
+            "#,
+        )
+        .bind(&other_ids)
+        .bind(SIMILAR_SONGS_LIMIT as i64)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(neighbours)
+    }
+
+    /// Most similar songs to `song_id`, best first
+    pub async fn get_similar_songs(&self, song_id: Uuid, limit: usize) -> Result<Vec<SongSearchResult>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT s.id, s.title, s.artist_id, COALESCE(a.stage_name, '') AS artist_name,
+                   COALESCE(s.duration_seconds, 0) AS duration_seconds, COALESCE(s.genre, '') AS genre,
+                   s.mood, s.audio_quality, COALESCE(s.listen_count, 0) AS listen_count, ss.score
+            FROM song_similarities ss
+            JOIN songs s ON s.id = ss.song_b_id
+            LEFT JOIN artists a ON a.id = s.artist_id
+            WHERE ss.song_a_id = $1
+            ORDER BY ss.score DESC, s.id
+            LIMIT $2
+            "#,
+        )
+        .bind(song_id)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load similar songs: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| SongSearchResult {
+                id: row.get("id"),
+                title: row.get("title"),
+                artist_id: row.get("artist_id"),
+                artist_name: row.get("artist_name"),
+                album_id: None,
+                album_title: None,
+                duration_seconds: row.get::<i32, _>("duration_seconds") as u32,
+                genre: row.get("genre"),
+                mood: row.get("mood"),
+                detected_mood: None,
+                audio_quality: row.get("audio_quality"),
+                listen_count: row.get::<i64, _>("listen_count") as u64,
+                is_trending: false,
+                is_popular: false,
+                tags: Vec::new(),
+                relevance_score: row.get::<f32, _>("score") as f64,
+                highlight: None,
+            })
+            .collect())
+    }
+
+    async fn song_features(&self) -> Result<Vec<SongFeatures>, AppError> {
+        let rows = sqlx::query("SELECT id, COALESCE(genre, '') AS genre, mood, tempo_bpm, audio_quality FROM songs")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to load song features: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| SongFeatures {
+                song_id: row.get("id"),
+                genre: row.get("genre"),
+                mood: row
+                    .get::<Option<String>, _>("mood")
+                    .and_then(|mood| SongMood::from_string(&mood).ok()),
+                tempo: row
+                    .get::<Option<i16>, _>("tempo_bpm")
+                    .and_then(|bpm| Tempo::new(bpm as u16).ok()),
+                audio_quality: row
+                    .get::<Option<String>, _>("audio_quality")
+                    .and_then(|quality| AudioQuality::from_string(&quality).ok()),
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,192 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, types::Json, PgPool, Row};
+use std::io::{Error, ErrorKind, Result as IoResult};
+use uuid::Uuid;
+
+use crate::bounded_contexts::music::infrastructure::storage::{
+    TranscodeJobRepository, TranscodeJobStatus, VideoQuality, VideoRendition, VideoTranscodeJob,
+};
+
+const JOB_COLUMNS: &str = "id, video_hash, quality, status, progress, attempts, error, rendition, created_at, updated_at, completed_at";
+
+/// Transcode jobs in `video_transcode_jobs`, so they survive restarts
+pub struct PostgresTranscodeJobRepository {
+    pool: PgPool,
+}
+
+impl PostgresTranscodeJobRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_job(row: PgRow) -> IoResult<VideoTranscodeJob> {
+        let quality: String = row.try_get("quality").map_err(invalid_data)?;
+        let status: String = row.try_get("status").map_err(invalid_data)?;
+        let attempts: i32 = row.try_get("attempts").map_err(invalid_data)?;
+        let rendition: Option<Json<VideoRendition>> = row.try_get("rendition").map_err(invalid_data)?;
+        Ok(VideoTranscodeJob {
+            id: row.try_get("id").map_err(invalid_data)?,
+            video_hash: row.try_get("video_hash").map_err(invalid_data)?,
+            quality: VideoQuality::ladder()
+                .into_iter()
+                .find(|q| q.as_str() == quality)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("Unknown video quality: {}", quality)))?,
+            status: TranscodeJobStatus::parse(&status)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("Unknown transcode status: {}", status)))?,
+            progress: row.try_get("progress").map_err(invalid_data)?,
+            attempts: attempts.max(0) as u32,
+            error: row.try_get("error").map_err(invalid_data)?,
+            rendition: rendition.map(|Json(rendition)| rendition),
+            created_at: row.try_get("created_at").map_err(invalid_data)?,
+            updated_at: row.try_get("updated_at").map_err(invalid_data)?,
+            completed_at: row.try_get("completed_at").map_err(invalid_data)?,
+        })
+    }
+
+    async fn update_job(&self, query: sqlx::query::Query<'_, sqlx::Postgres, sqlx::postgres::PgArguments>, job_id: Uuid) -> IoResult<()> {
+        let result = query.execute(&self.pool).await.map_err(database_error)?;
+        if result.rows_affected() == 0 {
+            return Err(Error::new(ErrorKind::NotFound, format!("Transcode job {} not found", job_id)));
+        }
+        Ok(())
+    }
+}
+
+fn database_error(error: sqlx::Error) -> Error {
+    Error::new(ErrorKind::Other, format!("Transcode job query failed: {}", error))
+}
+
+fn invalid_data(error: sqlx::Error) -> Error {
+    Error::new(ErrorKind::InvalidData, error.to_string())
+}
+
+#[async_trait]
+impl TranscodeJobRepository for PostgresTranscodeJobRepository {
+    async fn enqueue(&self, video_hash: &str, qualities: &[VideoQuality]) -> IoResult<Vec<VideoTranscodeJob>> {
+        let mut tx = self.pool.begin().await.map_err(database_error)?;
+        let mut queued = Vec::with_capacity(qualities.len());
+        for quality in qualities {
+            // Los fallidos vuelven a empezar; el resto se deja como está.
+            // clock_timestamp para que la escalera conserve su orden en la transacción
+            let row = sqlx::query(&format!(
+                r#"INSERT INTO video_transcode_jobs (id, video_hash, quality, status, created_at, updated_at)
+                   VALUES ($1, $2, $3, 'pending', clock_timestamp(), clock_timestamp())
+                   ON CONFLICT (video_hash, quality) DO UPDATE
+                   SET status = CASE WHEN video_transcode_jobs.status = 'failed' THEN 'pending' ELSE video_transcode_jobs.status END,
+                       attempts = CASE WHEN video_transcode_jobs.status = 'failed' THEN 0 ELSE video_transcode_jobs.attempts END,
+                       progress = CASE WHEN video_transcode_jobs.status = 'failed' THEN 0 ELSE video_transcode_jobs.progress END,
+                       error = CASE WHEN video_transcode_jobs.status = 'failed' THEN NULL ELSE video_transcode_jobs.error END,
+                       updated_at = CASE WHEN video_transcode_jobs.status = 'failed' THEN NOW() ELSE video_transcode_jobs.updated_at END
+                   RETURNING {}"#,
+                JOB_COLUMNS
+            ))
+            .bind(Uuid::new_v4())
+            .bind(video_hash)
+            .bind(quality.as_str())
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(database_error)?;
+            queued.push(Self::row_to_job(row)?);
+        }
+        tx.commit().await.map_err(database_error)?;
+        Ok(queued)
+    }
+
+    async fn claim_next(&self) -> IoResult<Option<VideoTranscodeJob>> {
+        // SKIP LOCKED: varios workers no reclaman el mismo trabajo
+        let row = sqlx::query(&format!(
+            r#"UPDATE video_transcode_jobs
+               SET status = 'processing', attempts = attempts + 1, progress = 0, updated_at = NOW()
+               WHERE id = (
+                   SELECT id FROM video_transcode_jobs
+                   WHERE status = 'pending'
+                   ORDER BY updated_at, created_at
+                   LIMIT 1
+                   FOR UPDATE SKIP LOCKED
+               )
+               RETURNING {}"#,
+            JOB_COLUMNS
+        ))
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(database_error)?;
+        row.map(Self::row_to_job).transpose()
+    }
+
+    async fn requeue_stale(&self, stale_before: DateTime<Utc>) -> IoResult<u64> {
+        let result = sqlx::query(
+            r#"UPDATE video_transcode_jobs SET status = 'pending', updated_at = NOW()
+               WHERE status = 'processing' AND updated_at < $1"#,
+        )
+        .bind(stale_before)
+        .execute(&self.pool)
+        .await
+        .map_err(database_error)?;
+        Ok(result.rows_affected())
+    }
+
+    async fn update_progress(&self, job_id: Uuid, progress: f32) -> IoResult<()> {
+        let query = sqlx::query("UPDATE video_transcode_jobs SET progress = $2, updated_at = NOW() WHERE id = $1")
+            .bind(job_id)
+            .bind(progress.clamp(0.0, 1.0));
+        self.update_job(query, job_id).await
+    }
+
+    async fn complete(&self, job_id: Uuid, rendition: &VideoRendition) -> IoResult<()> {
+        let query = sqlx::query(
+            r#"UPDATE video_transcode_jobs
+               SET status = 'completed', progress = 1, error = NULL, rendition = $2,
+                   updated_at = NOW(), completed_at = NOW()
+               WHERE id = $1"#,
+        )
+        .bind(job_id)
+        .bind(Json(rendition));
+        self.update_job(query, job_id).await
+    }
+
+    async fn skip(&self, job_id: Uuid, reason: &str) -> IoResult<()> {
+        let query = sqlx::query(
+            r#"UPDATE video_transcode_jobs
+               SET status = 'skipped', error = $2, updated_at = NOW(), completed_at = NOW()
+               WHERE id = $1"#,
+        )
+        .bind(job_id)
+        .bind(reason);
+        self.update_job(query, job_id).await
+    }
+
+    async fn fail(&self, job_id: Uuid, error: &str, retry: bool) -> IoResult<()> {
+        let query = sqlx::query(
+            r#"UPDATE video_transcode_jobs
+               SET status = CASE WHEN $3 THEN 'pending' ELSE 'failed' END,
+                   progress = 0, error = $2, updated_at = NOW()
+               WHERE id = $1"#,
+        )
+        .bind(job_id)
+        .bind(error)
+        .bind(retry);
+        self.update_job(query, job_id).await
+    }
+
+    async fn find_by_video(&self, video_hash: &str) -> IoResult<Vec<VideoTranscodeJob>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM video_transcode_jobs WHERE video_hash = $1 ORDER BY created_at, id",
+            JOB_COLUMNS
+        ))
+        .bind(video_hash)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(database_error)?;
+        rows.into_iter().map(Self::row_to_job).collect()
+    }
+
+    async fn delete_by_video(&self, video_hash: &str) -> IoResult<()> {
+        sqlx::query("DELETE FROM video_transcode_jobs WHERE video_hash = $1")
+            .bind(video_hash)
+            .execute(&self.pool)
+            .await
+            .map_err(database_error)?;
+        Ok(())
+    }
+}
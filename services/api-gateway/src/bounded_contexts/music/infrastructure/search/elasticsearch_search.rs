@@ -0,0 +1,1112 @@
+// =============================================================================
+// ELASTICSEARCH MUSIC SEARCH SERVICE
+// =============================================================================
+//
+// Implementación de `MusicSearchService` sobre la API REST de Elasticsearch.
+// Cada tipo de resultado vive en su propio índice (`{prefix}_songs`,
+// `{prefix}_artists`, ...) y los documentos indexados tienen la misma forma que
+// los `*SearchResult` (sin `relevance_score`/`highlight`, que se calculan aquí).
+//
+// Los artistas se buscan además por fonética: el campo `name` tiene un sub-campo
+// `phonetic_name` analizado con `double_metaphone`, de modo que "Beyonse"
+// encuentra a "Beyoncé" aunque la distancia de edición no baste.
+//
+// Las búsquedas en tendencia se agregan desde el log de búsquedas
+// (`{prefix}_search_queries`) y se cachean; `TrendingDecayJob` las recalcula
+// cada hora y al consultarlas se aplica el decaimiento hasta el momento actual.
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+use super::{
+    AlbumSearchResult, ArtistSearchResult, MusicSearchService, PlaylistSearchResult, SearchCategory,
+    SearchError, SearchFacet, SearchFilters, SearchHighlight, SearchPagination, SearchQuery, SearchResults,
+    SearchSort, SearchSuggestion, SongSearchResult, TagFilter, TrendingSearch,
+    trending::{rank_trending, DEFAULT_TRENDING_HALF_LIFE_HOURS},
+};
+
+/// Peso de una coincidencia exacta del nombre frente a una solo fonética
+pub const EXACT_MATCH_WEIGHT: f64 = 10.0;
+pub const PHONETIC_MATCH_WEIGHT: f64 = 1.0;
+
+/// Límite duro de Elasticsearch (`index.max_result_window`)
+const MAX_RESULT_WINDOW: u32 = 10_000;
+const MAX_SUGGESTIONS: usize = 10;
+const TOP_TAGS: usize = 10;
+/// Con el decaimiento, lo que queda fuera de esta ventana ya apenas puntúa
+const TRENDING_WINDOW_HOURS: i64 = 7 * 24;
+/// Candidatos agregados antes de aplicar el decaimiento y recortar
+const TRENDING_CANDIDATES: usize = 100;
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Nombres de las named queries, usados para saber qué cláusula casó cada hit
+const EXACT_QUERY: &str = "exact";
+const FUZZY_QUERY: &str = "fuzzy";
+const PHONETIC_QUERY: &str = "phonetic";
+
+pub struct ElasticsearchMusicSearchService {
+    client: reqwest::Client,
+    base_url: String,
+    index_prefix: String,
+    trending_half_life_hours: f64,
+    trending_cache: RwLock<Vec<TrendingSearch>>,
+}
+
+impl ElasticsearchMusicSearchService {
+    pub fn new(base_url: impl Into<String>, index_prefix: impl Into<String>) -> Result<Self, SearchError> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| SearchError::InternalError(format!("Failed to create HTTP client: {}", e)))?;
+
+        Ok(Self {
+            client,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            index_prefix: index_prefix.into(),
+            trending_half_life_hours: DEFAULT_TRENDING_HALF_LIFE_HOURS,
+            trending_cache: RwLock::new(Vec::new()),
+        })
+    }
+
+    /// Vida media (en horas) del decaimiento de las búsquedas en tendencia
+    pub fn with_trending_half_life(mut self, half_life_hours: f64) -> Self {
+        self.trending_half_life_hours = half_life_hours;
+        self
+    }
+
+    /// `ELASTICSEARCH_URL` (por defecto `http://localhost:9200`) y `ELASTICSEARCH_INDEX_PREFIX`
+    pub fn from_env() -> Result<Self, SearchError> {
+        let base_url = std::env::var("ELASTICSEARCH_URL").unwrap_or_else(|_| "http://localhost:9200".to_string());
+        let index_prefix = std::env::var("ELASTICSEARCH_INDEX_PREFIX").unwrap_or_else(|_| "vibestream".to_string());
+        Self::new(base_url, index_prefix)
+    }
+
+    pub fn index_name(&self, category: &SearchCategory) -> String {
+        let suffix = match category {
+            SearchCategory::Song => "songs",
+            SearchCategory::Artist => "artists",
+            SearchCategory::Album => "albums",
+            SearchCategory::Playlist => "playlists",
+            SearchCategory::Genre | SearchCategory::Mood => "songs",
+        };
+        format!("{}_{}", self.index_prefix, suffix)
+    }
+
+    fn search_log_index(&self) -> String {
+        format!("{}_search_queries", self.index_prefix)
+    }
+
+    /// Crea el índice de artistas con el analizador fonético si aún no existe
+    pub async fn ensure_artist_index(&self) -> Result<(), SearchError> {
+        self.ensure_index(SearchCategory::Artist, artist_index_definition()).await
+    }
+
+    /// Crea el índice de canciones (con `tags` como keyword) si aún no existe
+    pub async fn ensure_song_index(&self) -> Result<(), SearchError> {
+        self.ensure_index(SearchCategory::Song, song_index_definition()).await
+    }
+
+    async fn ensure_index(&self, category: SearchCategory, definition: Value) -> Result<(), SearchError> {
+        let url = format!("{}/{}", self.base_url, self.index_name(&category));
+
+        let exists = self.client.head(&url).send().await.map_err(map_transport_error)?;
+        if exists.status().is_success() {
+            return Ok(());
+        }
+
+        let response = self
+            .client
+            .put(&url)
+            .json(&definition)
+            .send()
+            .await
+            .map_err(map_transport_error)?;
+        check_status(response).await.map(|_| ())
+    }
+
+    /// Indexa (o reemplaza) un artista; pensado para los proyectores del catálogo
+    pub async fn index_artist(&self, artist: &ArtistSearchResult) -> Result<(), SearchError> {
+        let mut document = serde_json::to_value(artist)
+            .map_err(|e| SearchError::InternalError(e.to_string()))?;
+        if let Some(object) = document.as_object_mut() {
+            object.remove("relevance_score");
+            object.remove("highlight");
+            object.remove("phonetic_match");
+        }
+
+        let url = format!(
+            "{}/{}/_doc/{}",
+            self.base_url,
+            self.index_name(&SearchCategory::Artist),
+            artist.id
+        );
+        let response = self
+            .client
+            .put(&url)
+            .json(&document)
+            .send()
+            .await
+            .map_err(map_transport_error)?;
+        check_status(response).await.map(|_| ())
+    }
+
+    /// Registra una búsqueda para el cálculo de tendencias
+    pub async fn record_search(&self, text: &str) -> Result<(), SearchError> {
+        let text = text.trim().to_lowercase();
+        if text.is_empty() {
+            return Ok(());
+        }
+        let url = format!("{}/{}/_doc", self.base_url, self.search_log_index());
+        let response = self
+            .client
+            .post(&url)
+            .json(&json!({ "text": text, "searched_at": chrono::Utc::now() }))
+            .send()
+            .await
+            .map_err(map_transport_error)?;
+        check_status(response).await.map(|_| ())
+    }
+
+    /// Recalcula las búsquedas en tendencia desde el log y refresca la caché
+    pub async fn refresh_trending_searches(&self) -> Result<Vec<TrendingSearch>, SearchError> {
+        let now = chrono::Utc::now();
+        let body = trending_search_body(now - chrono::Duration::hours(TRENDING_WINDOW_HOURS));
+        let response = self.execute(&self.search_log_index(), &body).await?;
+
+        let ranked = rank_trending(
+            trending_from_aggregation(&response),
+            |search| search.search_count as f64,
+            now,
+            self.trending_half_life_hours,
+            TRENDING_CANDIDATES,
+        );
+        *self.trending_cache.write().await = ranked.clone();
+        Ok(ranked)
+    }
+
+    async fn execute(&self, indices: &str, body: &Value) -> Result<Value, SearchError> {
+        let url = format!("{}/{}/_search", self.base_url, indices);
+        let response = self
+            .client
+            .post(&url)
+            .json(body)
+            .send()
+            .await
+            .map_err(map_transport_error)?;
+        check_status(response).await
+    }
+
+    async fn run_search<T: DeserializeOwned>(
+        &self,
+        category: SearchCategory,
+        query: &SearchQuery,
+        body: Value,
+        facet_names: &[&str],
+    ) -> Result<SearchResults<T>, SearchError> {
+        let started = Instant::now();
+        let response = self.execute(&self.index_name(&category), &body).await?;
+
+        let results = hits(&response)
+            .iter()
+            .map(|hit| hit_to_result(hit, |_, _| {}))
+            .collect::<Result<Vec<T>, _>>()?;
+
+        Ok(build_results(
+            results,
+            &response,
+            &query.pagination,
+            facet_names,
+            started.elapsed(),
+        ))
+    }
+}
+
+#[async_trait]
+impl MusicSearchService for ElasticsearchMusicSearchService {
+    async fn search_songs(&self, query: SearchQuery) -> Result<SearchResults<SongSearchResult>, SearchError> {
+        let body = song_search_body(&query)?;
+        self.run_search(
+            SearchCategory::Song,
+            &query,
+            body,
+            &["genres", "moods", "tags"],
+        )
+        .await
+    }
+
+    async fn search_artists(&self, query: SearchQuery) -> Result<SearchResults<ArtistSearchResult>, SearchError> {
+        let started = Instant::now();
+        let body = artist_search_body(&query)?;
+        let response = self.execute(&self.index_name(&SearchCategory::Artist), &body).await?;
+
+        let results = hits(&response)
+            .iter()
+            .map(|hit| {
+                hit_to_result(hit, |hit, source| {
+                    source.insert("phonetic_match".to_string(), Value::Bool(is_phonetic_only(hit)));
+                })
+            })
+            .collect::<Result<Vec<ArtistSearchResult>, _>>()?;
+
+        Ok(build_results(
+            results,
+            &response,
+            &query.pagination,
+            &["genres"],
+            started.elapsed(),
+        ))
+    }
+
+    async fn search_albums(&self, query: SearchQuery) -> Result<SearchResults<AlbumSearchResult>, SearchError> {
+        let body = text_search_body(
+            &query,
+            &["title^3", "artist_name^2"],
+            "title",
+            common_filters(&query.filters, "genre"),
+        )?;
+        self.run_search(SearchCategory::Album, &query, body, &["genres"]).await
+    }
+
+    async fn search_playlists(&self, query: SearchQuery) -> Result<SearchResults<PlaylistSearchResult>, SearchError> {
+        let body = text_search_body(
+            &query,
+            &["name^3", "description", "creator_name"],
+            "name",
+            Vec::new(),
+        )?;
+        self.run_search(SearchCategory::Playlist, &query, body, &[]).await
+    }
+
+    async fn get_suggestions(&self, partial_query: &str) -> Result<Vec<SearchSuggestion>, SearchError> {
+        let partial = partial_query.trim();
+        if partial.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let indices = [SearchCategory::Song, SearchCategory::Artist, SearchCategory::Album]
+            .iter()
+            .map(|category| self.index_name(category))
+            .collect::<Vec<_>>()
+            .join(",");
+        let body = json!({
+            "size": MAX_SUGGESTIONS * 3,
+            "_source": ["title", "name"],
+            "query": {
+                "multi_match": {
+                    "query": partial,
+                    "type": "phrase_prefix",
+                    "fields": ["title", "name"]
+                }
+            }
+        });
+        let response = self.execute(&indices, &body).await?;
+        Ok(suggestions_from_hits(self, &response))
+    }
+
+    async fn get_trending_searches(&self) -> Result<Vec<TrendingSearch>, SearchError> {
+        let cached = self.trending_cache.read().await.clone();
+        let candidates = if cached.is_empty() {
+            self.refresh_trending_searches().await?
+        } else {
+            cached
+        };
+
+        // La caché puede tener hasta una hora: se decae de nuevo hasta ahora
+        Ok(rank_trending(
+            candidates,
+            |search| search.search_count as f64,
+            chrono::Utc::now(),
+            self.trending_half_life_hours,
+            MAX_SUGGESTIONS,
+        ))
+    }
+}
+
+// -----------------------------------------------------------------------------
+// Trending
+// -----------------------------------------------------------------------------
+
+/// Búsquedas más frecuentes desde `since`, con su primera y última aparición
+pub fn trending_search_body(since: chrono::DateTime<chrono::Utc>) -> Value {
+    json!({
+        "size": 0,
+        "query": { "range": { "searched_at": { "gte": since } } },
+        "aggs": {
+            "trending": {
+                "terms": { "field": "text", "size": TRENDING_CANDIDATES },
+                "aggs": {
+                    "first_seen": { "min": { "field": "searched_at" } },
+                    "last_seen": { "max": { "field": "searched_at" } }
+                }
+            }
+        }
+    })
+}
+
+/// Los agregados `min`/`max` de fechas vienen como epoch millis en `value`
+fn trending_from_aggregation(response: &Value) -> Vec<TrendingSearch> {
+    let millis = |value: &Value| {
+        value["value"]
+            .as_f64()
+            .and_then(|ms| chrono::DateTime::from_timestamp_millis(ms as i64))
+    };
+
+    response["aggregations"]["trending"]["buckets"]
+        .as_array()
+        .map(|buckets| {
+            buckets
+                .iter()
+                .filter_map(|bucket| {
+                    let search_count = bucket["doc_count"].as_u64()?;
+                    Some(TrendingSearch {
+                        text: bucket["key"].as_str()?.to_string(),
+                        search_count,
+                        trend_score: search_count as f64,
+                        first_seen_at: millis(&bucket["first_seen"])?,
+                        last_seen_at: millis(&bucket["last_seen"])?,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// -----------------------------------------------------------------------------
+// Mapping e index settings
+// -----------------------------------------------------------------------------
+
+/// Settings + mappings del índice de artistas.
+///
+/// `name` se indexa tres veces: texto normal (exacto/fuzzy), `name.phonetic_name`
+/// con `double_metaphone` y `name.keyword` para ordenar.
+pub fn artist_index_definition() -> Value {
+    json!({
+        "settings": {
+            "analysis": {
+                "filter": {
+                    "name_double_metaphone": {
+                        "type": "phonetic",
+                        "encoder": "double_metaphone",
+                        "replace": true
+                    }
+                },
+                "analyzer": {
+                    "name_folded": {
+                        "type": "custom",
+                        "tokenizer": "standard",
+                        "filter": ["lowercase", "asciifolding"]
+                    },
+                    "name_phonetic": {
+                        "type": "custom",
+                        "tokenizer": "standard",
+                        "filter": ["lowercase", "asciifolding", "name_double_metaphone"]
+                    }
+                }
+            }
+        },
+        "mappings": {
+            "properties": {
+                "id": { "type": "keyword" },
+                "name": {
+                    "type": "text",
+                    "analyzer": "name_folded",
+                    "fields": {
+                        "phonetic_name": { "type": "text", "analyzer": "name_phonetic" },
+                        "keyword": { "type": "keyword" }
+                    }
+                },
+                "bio": { "type": "text" },
+                "genres": { "type": "keyword" },
+                "follower_count": { "type": "long" },
+                "song_count": { "type": "integer" },
+                "album_count": { "type": "integer" },
+                "is_verified": { "type": "boolean" }
+            }
+        }
+    })
+}
+
+/// Settings + mappings del índice de canciones.
+///
+/// `tags` son keywords normalizados a minúsculas: los tags de usuario llegan
+/// con cualquier capitalización y el filtro debe casar igual.
+pub fn song_index_definition() -> Value {
+    json!({
+        "settings": {
+            "analysis": {
+                "normalizer": {
+                    "tag_normalizer": {
+                        "type": "custom",
+                        "filter": ["lowercase", "asciifolding"]
+                    }
+                }
+            }
+        },
+        "mappings": {
+            "properties": {
+                "id": { "type": "keyword" },
+                "title": { "type": "text", "fields": { "keyword": { "type": "keyword" } } },
+                "artist_id": { "type": "keyword" },
+                "artist_name": { "type": "text" },
+                "album_id": { "type": "keyword" },
+                "album_title": { "type": "text" },
+                "duration_seconds": { "type": "integer" },
+                "genre": { "type": "keyword" },
+                "mood": { "type": "keyword" },
+                "detected_mood": { "type": "keyword" },
+                "valence": { "type": "float" },
+                "energy": { "type": "float" },
+                "audio_quality": { "type": "keyword" },
+                "listen_count": { "type": "long" },
+                "is_trending": { "type": "boolean" },
+                "is_popular": { "type": "boolean" },
+                "tags": { "type": "keyword", "normalizer": "tag_normalizer" },
+                "release_date": { "type": "date" },
+                "language": { "type": "keyword" },
+                "explicit_content": { "type": "boolean" }
+            }
+        }
+    })
+}
+
+// -----------------------------------------------------------------------------
+// Construcción de queries
+// -----------------------------------------------------------------------------
+
+fn page_window(pagination: &SearchPagination) -> Result<(u32, u32), SearchError> {
+    if pagination.page_size == 0 {
+        return Err(SearchError::InvalidQuery("page_size must be greater than zero".to_string()));
+    }
+    let page = pagination.page.max(1);
+    let size = match pagination.max_results {
+        Some(max) => pagination.page_size.min(max),
+        None => pagination.page_size,
+    };
+    let from = (page - 1).saturating_mul(pagination.page_size);
+
+    let window = from.saturating_add(size);
+    if window > MAX_RESULT_WINDOW {
+        return Err(SearchError::TooManyResults(window));
+    }
+    Ok((from, size))
+}
+
+/// Campo de ordenación por tipo de documento; `None` deja el orden por `_score`
+fn sort_clause(sort: &SearchSort, title_field: &str) -> Option<Value> {
+    let (field, order) = match sort {
+        SearchSort::Relevance => return None,
+        SearchSort::PopularityDesc => ("follower_count", "desc"),
+        SearchSort::PopularityAsc => ("follower_count", "asc"),
+        SearchSort::ReleaseDateDesc => ("release_date", "desc"),
+        SearchSort::ReleaseDateAsc => ("release_date", "asc"),
+        SearchSort::DurationDesc => ("duration_seconds", "desc"),
+        SearchSort::DurationAsc => ("duration_seconds", "asc"),
+        SearchSort::TitleAsc => (title_field, "asc"),
+        SearchSort::TitleDesc => (title_field, "desc"),
+        SearchSort::ListenCountDesc => ("listen_count", "desc"),
+        SearchSort::ListenCountAsc => ("listen_count", "asc"),
+    };
+    Some(json!([{ field: { "order": order, "unmapped_type": "keyword" } }, "_score"]))
+}
+
+fn common_filters(filters: &SearchFilters, genre_field: &str) -> Vec<Value> {
+    let mut clauses = Vec::new();
+    if let Some(genres) = filters.genres.as_ref().filter(|g| !g.is_empty()) {
+        let values: Vec<&str> = genres.iter().map(|g| g.value()).collect();
+        clauses.push(json!({ "terms": { genre_field: values } }));
+    }
+    if let Some(artist_ids) = filters.artist_ids.as_ref().filter(|a| !a.is_empty()) {
+        clauses.push(json!({ "terms": { "artist_id": artist_ids } }));
+    }
+    if let Some(range) = &filters.release_date_range {
+        clauses.push(json!({ "range": { "release_date": { "gte": range.from, "lte": range.to } } }));
+    }
+    clauses
+}
+
+fn song_filters(filters: &SearchFilters) -> Vec<Value> {
+    let mut clauses = common_filters(filters, "genre");
+    if let Some(moods) = filters.moods.as_ref().filter(|m| !m.is_empty()) {
+        // El mood etiquetado o, si no lo hay, el detectado del audio
+        clauses.push(json!({
+            "bool": {
+                "should": [
+                    { "terms": { "mood": moods } },
+                    { "terms": { "detected_mood": moods } }
+                ],
+                "minimum_should_match": 1
+            }
+        }));
+    }
+    if let Some(qualities) = filters.audio_qualities.as_ref().filter(|q| !q.is_empty()) {
+        clauses.push(json!({ "terms": { "audio_quality": qualities } }));
+    }
+    if let Some(range) = &filters.duration_range {
+        clauses.push(json!({
+            "range": { "duration_seconds": { "gte": range.min_seconds, "lte": range.max_seconds } }
+        }));
+    }
+    if let Some(is_trending) = filters.is_trending {
+        clauses.push(json!({ "term": { "is_trending": is_trending } }));
+    }
+    if let Some(is_popular) = filters.is_popular {
+        clauses.push(json!({ "term": { "is_popular": is_popular } }));
+    }
+    if let Some(min_listens) = filters.min_listen_count {
+        clauses.push(json!({ "range": { "listen_count": { "gte": min_listens } } }));
+    }
+    if let Some(language) = &filters.language {
+        clauses.push(json!({ "term": { "language": language } }));
+    }
+    if let Some(explicit) = filters.explicit_content {
+        clauses.push(json!({ "term": { "explicit_content": explicit } }));
+    }
+    if let Some(tag_filter) = filters.tag_filter() {
+        clauses.push(tag_clause(&tag_filter));
+    }
+    clauses
+}
+
+/// `All` exige que casen tantos tags como se pidieron (`terms_set`); `Any`, uno
+fn tag_clause(filter: &TagFilter) -> Value {
+    match filter {
+        TagFilter::All(tags) => json!({
+            "terms_set": {
+                "tags": { "terms": tags, "minimum_should_match": tags.len() }
+            }
+        }),
+        TagFilter::Any(tags) => json!({ "terms": { "tags": tags } }),
+    }
+}
+
+fn text_search_body(
+    query: &SearchQuery,
+    fields: &[&str],
+    title_field: &str,
+    filters: Vec<Value>,
+) -> Result<Value, SearchError> {
+    let (from, size) = page_window(&query.pagination)?;
+    let text = query.text.trim();
+
+    let must = if text.is_empty() {
+        json!({ "match_all": {} })
+    } else {
+        json!({ "multi_match": { "query": text, "fields": fields, "fuzziness": "AUTO" } })
+    };
+
+    let mut body = json!({
+        "from": from,
+        "size": size,
+        "track_total_hits": true,
+        "query": { "bool": { "must": must, "filter": filters } },
+        "highlight": { "fields": { title_field: {} } }
+    });
+    if let Some(sort) = sort_clause(&query.sort, &format!("{}.keyword", title_field)) {
+        body["sort"] = sort;
+    }
+    Ok(body)
+}
+
+fn song_search_body(query: &SearchQuery) -> Result<Value, SearchError> {
+    let mut body = text_search_body(
+        query,
+        &["title^3", "artist_name^2", "album_title"],
+        "title",
+        song_filters(&query.filters),
+    )?;
+    body["aggs"] = json!({
+        "genres": { "terms": { "field": "genre", "size": 10 } },
+        "moods": { "terms": { "field": "mood", "size": 10 } },
+        "tags": { "terms": { "field": "tags", "size": TOP_TAGS } }
+    });
+    Ok(body)
+}
+
+/// Query de artistas: `bool` con coincidencia exacta, fuzzy y fonética (al
+/// menos una debe casar) envuelta en un `function_score` que multiplica por
+/// [`EXACT_MATCH_WEIGHT`] los nombres que casan sin aproximación.
+pub fn artist_search_body(query: &SearchQuery) -> Result<Value, SearchError> {
+    let text = query.text.trim();
+    if text.is_empty() {
+        return Err(SearchError::InvalidQuery("Artist search requires a query".to_string()));
+    }
+    let (from, size) = page_window(&query.pagination)?;
+
+    let mut filters = Vec::new();
+    if let Some(genres) = query.filters.genres.as_ref().filter(|g| !g.is_empty()) {
+        let values: Vec<&str> = genres.iter().map(|g| g.value()).collect();
+        filters.push(json!({ "terms": { "genres": values } }));
+    }
+    if let Some(artist_ids) = query.filters.artist_ids.as_ref().filter(|a| !a.is_empty()) {
+        filters.push(json!({ "terms": { "id": artist_ids } }));
+    }
+
+    let exact = json!({ "match": { "name": { "query": text, "operator": "and" } } });
+    let phonetic = json!({ "match": { "name.phonetic_name": { "query": text } } });
+
+    let mut body = json!({
+        "from": from,
+        "size": size,
+        "track_total_hits": true,
+        "query": {
+            "function_score": {
+                "query": {
+                    "bool": {
+                        "should": [
+                            { "match": { "name": { "query": text, "operator": "and", "_name": EXACT_QUERY } } },
+                            {
+                                "multi_match": {
+                                    "query": text,
+                                    "fields": ["name^3", "bio"],
+                                    "fuzziness": "AUTO",
+                                    "_name": FUZZY_QUERY
+                                }
+                            },
+                            { "match": { "name.phonetic_name": { "query": text, "_name": PHONETIC_QUERY } } }
+                        ],
+                        "minimum_should_match": 1,
+                        "filter": filters
+                    }
+                },
+                "functions": [
+                    { "filter": exact, "weight": EXACT_MATCH_WEIGHT },
+                    { "filter": phonetic, "weight": PHONETIC_MATCH_WEIGHT }
+                ],
+                "score_mode": "max",
+                "boost_mode": "multiply"
+            }
+        },
+        "aggs": { "genres": { "terms": { "field": "genres", "size": 10 } } },
+        "highlight": { "fields": { "name": {}, "name.phonetic_name": {} } }
+    });
+    if let Some(sort) = sort_clause(&query.sort, "name.keyword") {
+        body["sort"] = sort;
+    }
+    Ok(body)
+}
+
+// -----------------------------------------------------------------------------
+// Parsing de respuestas
+// -----------------------------------------------------------------------------
+
+fn hits(response: &Value) -> Vec<Value> {
+    response["hits"]["hits"].as_array().cloned().unwrap_or_default()
+}
+
+/// Un artista es "aproximado" si solo casó por fonética o fuzzy, nunca exacto
+fn is_phonetic_only(hit: &Value) -> bool {
+    let matched: Vec<&str> = hit["matched_queries"]
+        .as_array()
+        .map(|queries| queries.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    matched.contains(&PHONETIC_QUERY) && !matched.contains(&EXACT_QUERY)
+}
+
+fn highlight_of(hit: &Value) -> Option<SearchHighlight> {
+    let highlight = hit["highlight"].as_object()?;
+    highlight.iter().find_map(|(field, fragments)| {
+        let text = fragments.as_array()?.first()?.as_str()?;
+        Some(SearchHighlight {
+            field: field.clone(),
+            highlighted_text: text.to_string(),
+        })
+    })
+}
+
+/// Convierte un hit en el tipo de resultado completando `id`, score y highlight
+fn hit_to_result<T, F>(hit: &Value, extend: F) -> Result<T, SearchError>
+where
+    T: DeserializeOwned,
+    F: FnOnce(&Value, &mut Map<String, Value>),
+{
+    let mut source = hit["_source"].as_object().cloned().unwrap_or_default();
+    if !source.contains_key("id") {
+        source.insert("id".to_string(), hit["_id"].clone());
+    }
+    source.insert(
+        "relevance_score".to_string(),
+        json!(hit["_score"].as_f64().unwrap_or(0.0)),
+    );
+    source.insert(
+        "highlight".to_string(),
+        serde_json::to_value(highlight_of(hit)).unwrap_or(Value::Null),
+    );
+    extend(hit, &mut source);
+
+    serde_json::from_value(Value::Object(source))
+        .map_err(|e| SearchError::InternalError(format!("Malformed search document: {}", e)))
+}
+
+fn build_results<T>(
+    results: Vec<T>,
+    response: &Value,
+    pagination: &SearchPagination,
+    facet_names: &[&str],
+    elapsed: Duration,
+) -> SearchResults<T> {
+    let total_count = response["hits"]["total"]["value"]
+        .as_u64()
+        .or_else(|| response["hits"]["total"].as_u64())
+        .unwrap_or(results.len() as u64);
+    let page_size = pagination.page_size.max(1);
+    let total_pages = ((total_count + page_size as u64 - 1) / page_size as u64) as u32;
+
+    let mut facets = HashMap::new();
+    for name in facet_names {
+        let buckets = response["aggregations"][*name]["buckets"]
+            .as_array()
+            .map(|buckets| {
+                buckets
+                    .iter()
+                    .filter_map(|bucket| {
+                        Some(SearchFacet {
+                            value: bucket["key"].as_str()?.to_string(),
+                            count: bucket["doc_count"].as_u64()?,
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if !buckets.is_empty() {
+            facets.insert(name.to_string(), buckets);
+        }
+    }
+
+    SearchResults {
+        results,
+        total_count,
+        page: pagination.page.max(1),
+        page_size,
+        total_pages,
+        search_time_ms: response["took"].as_u64().unwrap_or(elapsed.as_millis() as u64),
+        facets,
+    }
+}
+
+fn suggestions_from_hits(service: &ElasticsearchMusicSearchService, response: &Value) -> Vec<SearchSuggestion> {
+    let categories = [SearchCategory::Song, SearchCategory::Artist, SearchCategory::Album];
+    let mut suggestions: Vec<SearchSuggestion> = Vec::new();
+
+    for hit in hits(response) {
+        let index = hit["_index"].as_str().unwrap_or_default();
+        let Some(category) = categories.iter().find(|c| service.index_name(c) == index) else {
+            continue;
+        };
+        let Some(text) = hit["_source"]["title"].as_str().or_else(|| hit["_source"]["name"].as_str()) else {
+            continue;
+        };
+
+        if let Some(existing) = suggestions.iter_mut().find(|s| s.text.eq_ignore_ascii_case(text)) {
+            existing.count += 1;
+        } else if suggestions.len() < MAX_SUGGESTIONS {
+            suggestions.push(SearchSuggestion {
+                text: text.to_string(),
+                category: category.clone(),
+                count: 1,
+            });
+        }
+    }
+    suggestions
+}
+
+fn map_transport_error(error: reqwest::Error) -> SearchError {
+    if error.is_timeout() {
+        SearchError::Timeout
+    } else if error.is_connect() {
+        SearchError::ServiceUnavailable
+    } else {
+        SearchError::InternalError(error.to_string())
+    }
+}
+
+async fn check_status(response: reqwest::Response) -> Result<Value, SearchError> {
+    let status = response.status();
+    let body: Value = response.json().await.unwrap_or(Value::Null);
+    if status.is_success() {
+        return Ok(body);
+    }
+
+    let reason = body["error"]["reason"]
+        .as_str()
+        .map(str::to_string)
+        .unwrap_or_else(|| status.to_string());
+    Err(match status.as_u16() {
+        400 => SearchError::InvalidQuery(reason),
+        408 | 504 => SearchError::Timeout,
+        503 => SearchError::ServiceUnavailable,
+        _ => SearchError::InternalError(reason),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::TagMatch;
+    use crate::bounded_contexts::music::domain::value_objects::SongMood;
+    use uuid::Uuid;
+
+    fn empty_filters() -> SearchFilters {
+        SearchFilters {
+            genres: None,
+            moods: None,
+            audio_qualities: None,
+            duration_range: None,
+            release_date_range: None,
+            artist_ids: None,
+            is_trending: None,
+            is_popular: None,
+            min_listen_count: None,
+            language: None,
+            explicit_content: None,
+            tags: None,
+            tag_match: TagMatch::Any,
+        }
+    }
+
+    fn artist_query(text: &str) -> SearchQuery {
+        SearchQuery {
+            text: text.to_string(),
+            filters: empty_filters(),
+            sort: SearchSort::Relevance,
+            pagination: SearchPagination { page: 1, page_size: 10, max_results: None },
+        }
+    }
+
+    fn artist_hit(id: Uuid, name: &str, score: f64, matched: &[&str]) -> Value {
+        json!({
+            "_index": "vibestream_artists",
+            "_id": id.to_string(),
+            "_score": score,
+            "matched_queries": matched,
+            "_source": {
+                "id": id,
+                "name": name,
+                "bio": null,
+                "genres": ["pop", "r&b"],
+                "follower_count": 1_000_000,
+                "song_count": 120,
+                "album_count": 8,
+                "is_verified": true
+            }
+        })
+    }
+
+    #[test]
+    fn artist_mapping_declares_phonetic_sub_field() {
+        let definition = artist_index_definition();
+
+        let filter = &definition["settings"]["analysis"]["filter"]["name_double_metaphone"];
+        assert_eq!(filter["type"], "phonetic");
+        assert_eq!(filter["encoder"], "double_metaphone");
+
+        let name = &definition["mappings"]["properties"]["name"];
+        assert_eq!(name["fields"]["phonetic_name"]["analyzer"], "name_phonetic");
+        let analyzer = &definition["settings"]["analysis"]["analyzer"]["name_phonetic"]["filter"];
+        assert!(analyzer.as_array().unwrap().contains(&json!("name_double_metaphone")));
+    }
+
+    #[test]
+    fn artist_query_weights_exact_matches_over_phonetic() {
+        let body = artist_search_body(&artist_query("Beyonse")).unwrap();
+        let function_score = &body["query"]["function_score"];
+
+        let should = function_score["query"]["bool"]["should"].as_array().unwrap();
+        assert_eq!(should.len(), 3);
+        assert_eq!(should[1]["multi_match"]["fuzziness"], "AUTO");
+        assert_eq!(should[2]["match"]["name.phonetic_name"]["_name"], PHONETIC_QUERY);
+        assert_eq!(function_score["query"]["bool"]["minimum_should_match"], 1);
+
+        let functions = function_score["functions"].as_array().unwrap();
+        let exact = functions[0]["weight"].as_f64().unwrap();
+        let phonetic = functions[1]["weight"].as_f64().unwrap();
+        assert_eq!(exact / phonetic, 10.0);
+    }
+
+    #[test]
+    fn artist_query_rejects_empty_text() {
+        assert!(matches!(
+            artist_search_body(&artist_query("   ")),
+            Err(SearchError::InvalidQuery(_))
+        ));
+    }
+
+    #[test]
+    fn pagination_beyond_result_window_is_rejected() {
+        let mut query = artist_query("Beyonse");
+        query.pagination = SearchPagination { page: 2_000, page_size: 10, max_results: None };
+        assert!(matches!(artist_search_body(&query), Err(SearchError::TooManyResults(_))));
+    }
+
+    #[test]
+    fn artist_hits_flag_phonetic_only_matches() {
+        let beyonce = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let response = json!({
+            "took": 4,
+            "hits": {
+                "total": { "value": 2, "relation": "eq" },
+                "hits": [
+                    artist_hit(beyonce, "Beyoncé", 8.2, &[FUZZY_QUERY, PHONETIC_QUERY]),
+                    artist_hit(other, "Beyond", 1.1, &[EXACT_QUERY, FUZZY_QUERY])
+                ]
+            }
+        });
+
+        let results = hits(&response)
+            .iter()
+            .map(|hit| hit_to_result::<ArtistSearchResult, _>(hit, |hit, source| {
+                source.insert("phonetic_match".to_string(), Value::Bool(is_phonetic_only(hit)));
+            }))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let page = build_results(results, &response, &artist_query("Beyonse").pagination, &[], Duration::ZERO);
+
+        assert_eq!(page.total_count, 2);
+        assert_eq!(page.search_time_ms, 4);
+        assert_eq!(page.results[0].id, beyonce);
+        assert_eq!(page.results[0].name, "Beyoncé");
+        assert!(page.results[0].phonetic_match);
+        assert!(!page.results[1].phonetic_match);
+    }
+
+    fn song_query(tags: &[&str], tag_match: TagMatch) -> SearchQuery {
+        let mut query = artist_query("");
+        query.filters.tags = Some(tags.iter().map(|t| t.to_string()).collect());
+        query.filters.tag_match = tag_match;
+        query
+    }
+
+    fn filter_clauses(body: &Value) -> Vec<Value> {
+        body["query"]["bool"]["filter"].as_array().cloned().unwrap_or_default()
+    }
+
+    #[test]
+    fn song_mapping_declares_tags_as_keyword() {
+        let definition = song_index_definition();
+        let tags = &definition["mappings"]["properties"]["tags"];
+        assert_eq!(tags["type"], "keyword");
+        assert_eq!(tags["normalizer"], "tag_normalizer");
+    }
+
+    #[test]
+    fn all_tags_filter_uses_terms_set_requiring_every_tag() {
+        let body = song_search_body(&song_query(&["workout", "Indie", "2024"], TagMatch::All)).unwrap();
+        let clauses = filter_clauses(&body);
+        assert_eq!(clauses.len(), 1);
+
+        let terms_set = &clauses[0]["terms_set"]["tags"];
+        assert_eq!(terms_set["terms"], json!(["workout", "indie", "2024"]));
+        assert_eq!(terms_set["minimum_should_match"], 3);
+    }
+
+    #[test]
+    fn any_tags_filter_uses_terms_query() {
+        let body = song_search_body(&song_query(&["workout", "indie"], TagMatch::Any)).unwrap();
+        let clauses = filter_clauses(&body);
+        assert_eq!(clauses, vec![json!({ "terms": { "tags": ["workout", "indie"] } })]);
+    }
+
+    #[test]
+    fn mood_filter_also_matches_detected_mood() {
+        let mut query = song_query(&[], TagMatch::Any);
+        query.filters.moods = Some(vec![SongMood::Calm]);
+        let clauses = filter_clauses(&song_search_body(&query).unwrap());
+
+        let should = clauses[0]["bool"]["should"].as_array().unwrap();
+        assert!(should.contains(&json!({ "terms": { "mood": ["Calm"] } })));
+        assert!(should.contains(&json!({ "terms": { "detected_mood": ["Calm"] } })));
+        assert_eq!(song_index_definition()["mappings"]["properties"]["detected_mood"]["type"], "keyword");
+    }
+
+    #[test]
+    fn blank_and_duplicate_tags_are_ignored() {
+        let query = song_query(&[" Workout ", "workout", "  "], TagMatch::All);
+        assert_eq!(query.filters.tag_filter(), Some(TagFilter::All(vec!["workout".to_string()])));
+
+        let empty = song_query(&["  "], TagMatch::All);
+        assert!(filter_clauses(&song_search_body(&empty).unwrap()).is_empty());
+    }
+
+    #[test]
+    fn song_search_returns_top_tags_facet() {
+        let body = song_search_body(&song_query(&[], TagMatch::Any)).unwrap();
+        assert_eq!(body["aggs"]["tags"]["terms"]["field"], "tags");
+        assert_eq!(body["aggs"]["tags"]["terms"]["size"], 10);
+
+        let response = json!({
+            "took": 2,
+            "hits": { "total": { "value": 0, "relation": "eq" }, "hits": [] },
+            "aggregations": {
+                "tags": { "buckets": [ { "key": "workout", "doc_count": 12 }, { "key": "indie", "doc_count": 5 } ] }
+            }
+        });
+        let page: SearchResults<SongSearchResult> =
+            build_results(Vec::new(), &response, &artist_query("").pagination, &["genres", "moods", "tags"], Duration::ZERO);
+        let tags = &page.facets["tags"];
+        assert_eq!(tags[0].value, "workout");
+        assert_eq!(tags[0].count, 12);
+        assert_eq!(tags.len(), 2);
+    }
+
+    #[test]
+    fn trending_aggregation_reads_first_and_last_seen() {
+        let body = trending_search_body(chrono::Utc::now());
+        assert_eq!(body["aggs"]["trending"]["aggs"]["last_seen"]["max"]["field"], "searched_at");
+
+        let response = json!({
+            "aggregations": {
+                "trending": { "buckets": [ {
+                    "key": "lofi",
+                    "doc_count": 42,
+                    "first_seen": { "value": 1_760_000_000_000.0_f64 },
+                    "last_seen": { "value": 1_760_003_600_000.0_f64 }
+                } ] }
+            }
+        });
+        let trending = trending_from_aggregation(&response);
+        assert_eq!(trending[0].search_count, 42);
+        assert_eq!(trending[0].last_seen_at - trending[0].first_seen_at, chrono::Duration::hours(1));
+    }
+
+    /// Requiere un Elasticsearch con el plugin `analysis-phonetic`
+    /// (`ELASTICSEARCH_URL=http://localhost:9200 cargo test -- --ignored`)
+    #[tokio::test]
+    #[ignore]
+    async fn misspelled_artist_returns_correct_top_result() {
+        let base_url = std::env::var("ELASTICSEARCH_URL").unwrap_or_else(|_| "http://localhost:9200".to_string());
+        let prefix = format!("vibestream_test_{}", Uuid::new_v4().simple());
+        let service = ElasticsearchMusicSearchService::new(base_url.clone(), prefix).unwrap();
+        service.ensure_artist_index().await.unwrap();
+
+        let beyonce = Uuid::new_v4();
+        for (id, name) in [
+            (beyonce, "Beyoncé"),
+            (Uuid::new_v4(), "Beyond The Black"),
+            (Uuid::new_v4(), "Bonsai Kid"),
+            (Uuid::new_v4(), "Bayside"),
+        ] {
+            let artist: ArtistSearchResult = hit_to_result(&artist_hit(id, name, 0.0, &[]), |_, source| {
+                source.insert("phonetic_match".to_string(), Value::Bool(false));
+            })
+            .unwrap();
+            service.index_artist(&artist).await.unwrap();
+        }
+        let index = service.index_name(&SearchCategory::Artist);
+        service
+            .client
+            .post(format!("{}/{}/_refresh", base_url, index))
+            .send()
+            .await
+            .unwrap();
+
+        let results = service.search_artists(artist_query("Beyonse")).await.unwrap();
+
+        service.client.delete(format!("{}/{}", base_url, index)).send().await.unwrap();
+        assert_eq!(results.results[0].id, beyonce);
+        assert!(results.results[0].phonetic_match);
+    }
+}
@@ -1,10 +1,8 @@
 pub mod elasticsearch_search;
-pub mod search_engine;
-pub mod search_filters;
+pub mod trending;
 
 pub use elasticsearch_search::*;
-pub use search_engine::*;
-pub use search_filters::*;
+pub use trending::*;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -58,6 +56,50 @@ pub struct SearchFilters {
     pub min_listen_count: Option<u64>,
     pub language: Option<String>,
     pub explicit_content: Option<bool>,
+    /// Tags de usuario (p.ej. `["workout", "indie", "2024"]`)
+    #[serde(default)]
+    pub tags: Option<Vec<String>>,
+    /// Si la canción debe tener todos los `tags` o basta con alguno
+    #[serde(default)]
+    pub tag_match: TagMatch,
+}
+
+impl SearchFilters {
+    /// Filtro de tags normalizado (minúsculas, sin vacíos ni duplicados)
+    pub fn tag_filter(&self) -> Option<TagFilter> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in self.tags.iter().flatten() {
+            let tag = tag.trim().to_lowercase();
+            if !tag.is_empty() && !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        if tags.is_empty() {
+            return None;
+        }
+        Some(match self.tag_match {
+            TagMatch::All => TagFilter::All(tags),
+            TagMatch::Any => TagFilter::Any(tags),
+        })
+    }
+}
+
+/// AND vs OR semantics for the `tags` filter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TagMatch {
+    All,
+    #[default]
+    Any,
+}
+
+/// Tag filter resolved from `SearchFilters`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TagFilter {
+    /// The song must carry every tag
+    All(Vec<String>),
+    /// The song must carry at least one tag
+    Any(Vec<String>),
 }
 
 /// Search sorting options
@@ -108,10 +150,15 @@ pub struct SongSearchResult {
     pub duration_seconds: u32,
     pub genre: String,
     pub mood: Option<String>,
+    /// Mood detected from the audio features (`SongMood::detect_from_audio_features`)
+    #[serde(default)]
+    pub detected_mood: Option<String>,
     pub audio_quality: Option<String>,
     pub listen_count: u64,
     pub is_trending: bool,
     pub is_popular: bool,
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub relevance_score: f64,
     pub highlight: Option<SearchHighlight>,
 }
@@ -129,6 +176,9 @@ pub struct ArtistSearchResult {
     pub is_verified: bool,
     pub relevance_score: f64,
     pub highlight: Option<SearchHighlight>,
+    /// El nombre solo casó por similitud fonética/fuzzy (p.ej. "Beyonse" → "Beyoncé")
+    #[serde(default)]
+    pub phonetic_match: bool,
 }
 
 /// Album search result
@@ -175,7 +225,10 @@ pub struct SearchSuggestion {
 pub struct TrendingSearch {
     pub text: String,
     pub search_count: u64,
+    /// Puntuación con decaimiento temporal aplicado (ver `TrendingScoreDecay`)
     pub trend_score: f64,
+    pub first_seen_at: chrono::DateTime<chrono::Utc>,
+    pub last_seen_at: chrono::DateTime<chrono::Utc>,
 }
 
 /// Search category
@@ -0,0 +1,149 @@
+// =============================================================================
+// TRENDING SEARCHES - DECAIMIENTO TEMPORAL
+// =============================================================================
+//
+// La puntuación base de una búsqueda es su número de apariciones; al ordenar se
+// le aplica un decaimiento exponencial según el tiempo transcurrido desde la
+// última vez que se buscó, de modo que una búsqueda reciente supera a otra más
+// popular pero antigua.
+
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::{ElasticsearchMusicSearchService, TrendingSearch};
+
+/// Vida media por defecto: la puntuación se reduce a la mitad cada 24 horas
+pub const DEFAULT_TRENDING_HALF_LIFE_HOURS: f64 = 24.0;
+
+pub struct TrendingScoreDecay;
+
+impl TrendingScoreDecay {
+    /// `score * 0.5^(age_hours / half_life_hours)`. Edades negativas (relojes
+    /// desfasados) cuentan como 0; una vida media no positiva no decae.
+    pub fn apply(score: f64, age_hours: f64, half_life_hours: f64) -> f64 {
+        if half_life_hours <= 0.0 {
+            return score;
+        }
+        score * 0.5_f64.powf(age_hours.max(0.0) / half_life_hours)
+    }
+}
+
+/// Horas transcurridas desde `since`
+pub fn age_hours(since: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+    (now - since).num_seconds() as f64 / 3600.0
+}
+
+/// Aplica el decaimiento a `base_score(search)` según su `last_seen_at` y
+/// devuelve las `limit` búsquedas con mayor puntuación
+pub fn rank_trending(
+    searches: Vec<TrendingSearch>,
+    base_score: impl Fn(&TrendingSearch) -> f64,
+    now: DateTime<Utc>,
+    half_life_hours: f64,
+    limit: usize,
+) -> Vec<TrendingSearch> {
+    let mut ranked: Vec<TrendingSearch> = searches
+        .into_iter()
+        .map(|search| {
+            let trend_score = TrendingScoreDecay::apply(
+                base_score(&search),
+                age_hours(search.last_seen_at, now),
+                half_life_hours,
+            );
+            TrendingSearch { trend_score, ..search }
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.trend_score.total_cmp(&a.trend_score));
+    ranked.truncate(limit);
+    ranked
+}
+
+/// Worker que recalcula cada hora las puntuaciones de tendencia
+pub struct TrendingDecayJob {
+    service: Arc<ElasticsearchMusicSearchService>,
+    interval: Duration,
+}
+
+impl TrendingDecayJob {
+    pub fn new(service: Arc<ElasticsearchMusicSearchService>) -> Self {
+        Self::with_interval(service, Duration::from_secs(3600))
+    }
+
+    pub fn with_interval(service: Arc<ElasticsearchMusicSearchService>, interval: Duration) -> Self {
+        Self { service, interval }
+    }
+
+    pub fn start(&self) -> tokio::task::JoinHandle<()> {
+        let service = Arc::clone(&self.service);
+        let interval = self.interval;
+
+        tokio::spawn(async move {
+            tracing::info!("Trending decay job started");
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = service.refresh_trending_searches().await {
+                    tracing::error!("Trending searches refresh failed: {:?}", e);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn search(text: &str, search_count: u64, hours_ago: i64, now: DateTime<Utc>) -> TrendingSearch {
+        let last_seen_at = now - chrono::Duration::hours(hours_ago);
+        TrendingSearch {
+            text: text.to_string(),
+            search_count,
+            trend_score: search_count as f64,
+            first_seen_at: last_seen_at - chrono::Duration::hours(1),
+            last_seen_at,
+        }
+    }
+
+    #[test]
+    fn decay_halves_every_half_life() {
+        assert_eq!(TrendingScoreDecay::apply(100.0, 0.0, 24.0), 100.0);
+        assert!((TrendingScoreDecay::apply(100.0, 24.0, 24.0) - 50.0).abs() < 1e-9);
+        assert!((TrendingScoreDecay::apply(100.0, 48.0, 24.0) - 25.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn decay_converges_to_zero() {
+        let mut previous = f64::MAX;
+        for days in [1.0, 7.0, 30.0, 365.0] {
+            let score = TrendingScoreDecay::apply(1_000.0, days * 24.0, DEFAULT_TRENDING_HALF_LIFE_HOURS);
+            assert!(score < previous);
+            previous = score;
+        }
+        assert!(previous < 1e-100);
+        assert_eq!(TrendingScoreDecay::apply(1_000.0, f64::INFINITY, 24.0), 0.0);
+    }
+
+    #[test]
+    fn recent_queries_outrank_stale_ones() {
+        let now = Utc::now();
+        let ranked = rank_trending(
+            vec![search("stale hit", 120, 48, now), search("fresh hit", 100, 2, now)],
+            |s| s.search_count as f64,
+            now,
+            DEFAULT_TRENDING_HALF_LIFE_HOURS,
+            10,
+        );
+        assert_eq!(ranked[0].text, "fresh hit");
+        assert!((ranked[1].trend_score - 30.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn ranking_is_limited() {
+        let now = Utc::now();
+        let searches = (0..5).map(|i| search(&format!("q{}", i), 10, i, now)).collect();
+        let ranked = rank_trending(searches, |s| s.search_count as f64, now, 24.0, 3);
+        assert_eq!(ranked.iter().map(|s| s.text.as_str()).collect::<Vec<_>>(), ["q0", "q1", "q2"]);
+    }
+}
@@ -1,15 +1,23 @@
 use std::path::Path;
 use std::fs::File;
-use symphonia::core::formats::FormatOptions;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{CodecParameters, DecoderOptions};
+use symphonia::core::formats::{FormatOptions, FormatReader};
 use symphonia::core::io::MediaSourceStream;
 use symphonia::core::meta::MetadataOptions;
 use symphonia::core::probe::Hint;
 
 use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::music::domain::entities::Song;
 use crate::bounded_contexts::music::domain::value_objects::{
     SongDuration, AudioQuality, FileFormat, SongMood, Tempo,
 };
 
+/// Seconds of audio decoded for feature analysis
+const ANALYSIS_WINDOW_SECS: usize = 30;
+/// Samples per analysis frame (~23 ms at 44.1 kHz)
+const FRAME_SIZE: usize = 1024;
+
 /// Extracted audio metadata
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct AudioMetadata {
@@ -34,9 +42,45 @@ pub struct AudioMetadata {
     pub tempo: Option<Tempo>,
     pub bpm: Option<f64>,
     pub key: Option<String>,
+    /// Integrated loudness (approximate, ungated)
+    #[serde(default)]
+    pub loudness_lufs: Option<f32>,
+    /// Musical positiveness, 0.0-1.0
+    #[serde(default)]
+    pub valence: Option<f32>,
+    /// Intensity and activity, 0.0-1.0
+    #[serde(default)]
+    pub energy: Option<f32>,
     pub tags: std::collections::HashMap<String, String>,
 }
 
+impl AudioMetadata {
+    /// Copy tempo, mood and audio features onto the song. A mood tagged on
+    /// the song is kept; otherwise it takes the one detected here.
+    pub fn apply_to(&self, song: &mut Song) -> Result<(), String> {
+        if let Some(tempo) = &self.tempo {
+            song.set_tempo(tempo.clone());
+        }
+        if let (Some(valence), Some(energy)) = (self.valence, self.energy) {
+            song.set_audio_features(valence, energy)?;
+        }
+        if song.mood().is_none() {
+            if let Some(mood) = &self.mood {
+                song.set_mood(mood.clone());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Features computed from decoded samples
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct AudioFeatures {
+    loudness_lufs: f32,
+    valence: f32,
+    energy: f32,
+}
+
 /// Audio metadata extractor using symphonia
 pub struct AudioMetadataExtractor;
 
@@ -65,7 +109,7 @@ impl AudioMetadataExtractor {
             .map_err(|e| AppError::InternalError(format!("Failed to probe audio format: {}", e)))?;
 
         // Get the instantiated format reader
-        let format_reader = probed.format;
+        let mut format_reader = probed.format;
 
         // Find the first audio track with a known (decodeable) codec
         let track = format_reader
@@ -75,7 +119,8 @@ impl AudioMetadataExtractor {
             .ok_or_else(|| AppError::InvalidInput("No supported audio tracks found".to_string()))?;
 
         // Get the codec parameters
-        let codec_params = &track.codec_params;
+        let track_id = track.id;
+        let codec_params = track.codec_params.clone();
 
         // Extract basic audio information
         let duration = if let Some(n_frames) = codec_params.n_frames {
@@ -89,7 +134,7 @@ impl AudioMetadataExtractor {
         };
 
         let file_format = Self::detect_format(file_path)?;
-        let quality = Self::detect_quality(codec_params)?;
+        let quality = Self::detect_quality(&codec_params)?;
         let bitrate = None; // bit_rate field doesn't exist in CodecParameters
         let sample_rate = codec_params.sample_rate;
         let channels = codec_params.channels.map(|c| c.count() as u16);
@@ -117,6 +162,9 @@ impl AudioMetadataExtractor {
             tempo: None,
             bpm: None,
             key: None,
+            loudness_lufs: None,
+            valence: None,
+            energy: None,
             tags: std::collections::HashMap::new(),
         };
 
@@ -124,10 +172,9 @@ impl AudioMetadataExtractor {
         // Note: We're not using metadata for now to avoid borrow checker issues
         // In a real implementation, you would process the metadata here
         
-        // Analyze audio for mood and tempo if not found in tags
-        if metadata.mood.is_none() || metadata.tempo.is_none() {
-            Self::analyze_audio_characteristics(&mut metadata, codec_params);
-        }
+        // Analyze audio for tempo, audio features and mood
+        let samples = Self::decode_samples(format_reader.as_mut(), track_id, &codec_params, ANALYSIS_WINDOW_SECS);
+        Self::analyze_audio_characteristics(&mut metadata, &codec_params, samples.as_deref());
 
         Ok(metadata)
     }
@@ -234,10 +281,108 @@ impl AudioMetadataExtractor {
         }
     }
 
-    /// Analyze audio characteristics for mood and tempo detection
+    /// Decode up to `max_seconds` of interleaved samples of the track
+    fn decode_samples(
+        format_reader: &mut dyn FormatReader,
+        track_id: u32,
+        codec_params: &CodecParameters,
+        max_seconds: usize,
+    ) -> Option<Vec<f32>> {
+        let mut decoder = symphonia::default::get_codecs()
+            .make(codec_params, &DecoderOptions::default())
+            .ok()?;
+        let channels = codec_params.channels.map(|c| c.count()).unwrap_or(1).max(1);
+        let limit = codec_params.sample_rate? as usize * channels * max_seconds;
+
+        let mut samples = Vec::with_capacity(limit);
+        while samples.len() < limit {
+            let packet = match format_reader.next_packet() {
+                Ok(packet) => packet,
+                Err(_) => break,
+            };
+            if packet.track_id() != track_id {
+                continue;
+            }
+            let decoded = match decoder.decode(&packet) {
+                Ok(decoded) => decoded,
+                // Paquete corrupto: se salta y se sigue
+                Err(symphonia::core::errors::Error::DecodeError(_)) => continue,
+                Err(_) => break,
+            };
+            let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, *decoded.spec());
+            buffer.copy_interleaved_ref(decoded);
+            samples.extend_from_slice(buffer.samples());
+        }
+        samples.truncate(limit);
+        (!samples.is_empty()).then_some(samples)
+    }
+
+    /// Loudness, energy and valence of a window of interleaved samples.
+    ///
+    /// - loudness: mean power as LUFS (no K-weighting nor gating)
+    /// - energy: loudness, onset density (sharp rises of frame RMS) and
+    ///   brightness (zero-crossing rate as a spectral centroid proxy)
+    /// - valence: major/minor mode from the key tag, brightness and tempo
+    fn analyze_samples(
+        samples: &[f32],
+        channels: usize,
+        sample_rate: u32,
+        tempo: Option<&Tempo>,
+        key: Option<&str>,
+    ) -> Option<AudioFeatures> {
+        let channels = channels.max(1);
+        let mono: Vec<f32> = samples
+            .chunks(channels)
+            .map(|frame| frame.iter().sum::<f32>() / frame.len() as f32)
+            .collect();
+        if mono.len() < FRAME_SIZE || sample_rate == 0 {
+            return None;
+        }
+
+        let mean_square = mono.iter().map(|s| s * s).sum::<f32>() / mono.len() as f32;
+        let loudness_lufs = if mean_square > 1e-10 {
+            -0.691 + 10.0 * mean_square.log10()
+        } else {
+            -70.0
+        };
+
+        let frame_rms: Vec<f32> = mono
+            .chunks_exact(FRAME_SIZE)
+            .map(|frame| (frame.iter().map(|s| s * s).sum::<f32>() / FRAME_SIZE as f32).sqrt())
+            .collect();
+        let onsets = frame_rms
+            .windows(2)
+            .filter(|pair| pair[1] > 0.01 && pair[1] > pair[0] * 1.5)
+            .count();
+        let seconds = mono.len() as f32 / sample_rate as f32;
+        let onset_level = (onsets as f32 / seconds / 4.0).clamp(0.0, 1.0);
+
+        let crossings = mono.windows(2).filter(|pair| (pair[0] >= 0.0) != (pair[1] >= 0.0)).count();
+        let brightness = (crossings as f32 / mono.len() as f32 / 0.15).clamp(0.0, 1.0);
+
+        let loudness_level = ((loudness_lufs + 24.0) / 20.0).clamp(0.0, 1.0);
+        let energy = 0.5 * loudness_level + 0.3 * onset_level + 0.2 * brightness;
+
+        let mode = match key.map(|k| k.trim().to_lowercase()) {
+            Some(k) if k.contains("min") || (k.ends_with('m') && !k.ends_with("maj")) => 0.25,
+            Some(k) if !k.is_empty() => 0.75,
+            _ => 0.5,
+        };
+        let tempo_level = tempo.map_or(0.5, |t| ((t.bpm() as f32 - 60.0) / 120.0).clamp(0.0, 1.0));
+        let valence = 0.5 * mode + 0.25 * brightness + 0.25 * tempo_level;
+
+        Some(AudioFeatures {
+            loudness_lufs,
+            valence: valence.clamp(0.0, 1.0),
+            energy: energy.clamp(0.0, 1.0),
+        })
+    }
+
+    /// Analyze audio characteristics for tempo, audio features and mood detection
     fn analyze_audio_characteristics(
         metadata: &mut AudioMetadata,
-        codec_params: &symphonia::core::codecs::CodecParameters,
+        codec_params: &CodecParameters,
+        samples: Option<&[f32]>,
     ) {
         // This is a simplified analysis - in a real implementation,
         // you would decode the audio and perform spectral analysis
@@ -259,6 +404,27 @@ impl AudioMetadataExtractor {
             }
         }
 
+        if let Some(samples) = samples {
+            let channels = codec_params.channels.map(|c| c.count()).unwrap_or(1);
+            let sample_rate = codec_params.sample_rate.unwrap_or(44100);
+            if let Some(features) = Self::analyze_samples(
+                samples, channels, sample_rate, metadata.tempo.as_ref(), metadata.key.as_deref(),
+            ) {
+                metadata.loudness_lufs = Some(features.loudness_lufs);
+                metadata.valence = Some(features.valence);
+                metadata.energy = Some(features.energy);
+            }
+        }
+
+        // Mood from the circumplex when the audio could be analysed
+        if metadata.mood.is_none() {
+            if let (Some(tempo), Some(loudness), Some(valence), Some(energy)) =
+                (&metadata.tempo, metadata.loudness_lufs, metadata.valence, metadata.energy)
+            {
+                metadata.mood = Some(SongMood::detect_from_audio_features(tempo, loudness, valence, energy));
+            }
+        }
+
         // Determine mood from audio characteristics (simplified)
         if metadata.mood.is_none() {
             // Use sample rate as a proxy for energy level since bitrate is not available
@@ -321,6 +487,7 @@ impl AudioMetadataExtractor {
 mod tests {
     use super::*;
     use std::path::PathBuf;
+    use crate::bounded_contexts::music::domain::value_objects::{ArtistId, Genre, RoyaltyPercentage, SongTitle};
 
     #[tokio::test]
     async fn test_detect_format() {
@@ -333,6 +500,61 @@ mod tests {
         assert_eq!(format, FileFormat::Wav);
     }
 
+    fn sine(frequency: f32, amplitude: f32, seconds: f32, sample_rate: u32) -> Vec<f32> {
+        (0..(seconds * sample_rate as f32) as usize)
+            .map(|i| amplitude * (2.0 * std::f32::consts::PI * frequency * i as f32 / sample_rate as f32).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_quiet_minor_track_is_low_energy_and_low_valence() {
+        let samples = sine(110.0, 0.05, 5.0, 44100);
+        let slow = Tempo::new(70).unwrap();
+        let features = AudioMetadataExtractor::analyze_samples(&samples, 1, 44100, Some(&slow), Some("A minor")).unwrap();
+
+        // Seno de amplitud 0.05: potencia 0.00125 → ≈ -29.7 LUFS
+        assert!((features.loudness_lufs + 29.7).abs() < 0.2);
+        assert!(features.energy < 0.2);
+        assert!(features.valence < 0.3);
+        assert_eq!(
+            SongMood::detect_from_audio_features(&slow, features.loudness_lufs, features.valence, features.energy),
+            SongMood::Sad
+        );
+    }
+
+    #[test]
+    fn test_loud_bright_major_track_is_high_energy_and_high_valence() {
+        // Ráfagas de un tono agudo: sonoro, brillante y con ataques frecuentes
+        let mut samples = Vec::new();
+        for _ in 0..20 {
+            samples.extend(sine(3000.0, 0.9, 0.2, 44100));
+            samples.extend(vec![0.0; 4410]);
+        }
+        // Estéreo intercalado
+        let stereo: Vec<f32> = samples.iter().flat_map(|s| [*s, *s]).collect();
+        let fast = Tempo::new(150).unwrap();
+        let features = AudioMetadataExtractor::analyze_samples(&stereo, 2, 44100, Some(&fast), Some("C major")).unwrap();
+
+        assert!(features.energy > 0.6);
+        assert!(features.valence > 0.6);
+
+        let mut song = Song::new(
+            SongTitle::new("Sunrise".to_string()).unwrap(),
+            ArtistId::new(),
+            SongDuration::new(200).unwrap(),
+            Genre::new("pop".to_string()).unwrap(),
+            RoyaltyPercentage::new(70.0).unwrap(),
+        );
+        song.set_tempo(fast);
+        song.set_audio_features(features.valence, features.energy).unwrap();
+        assert_eq!(song.mood(), Some(&SongMood::Happy));
+    }
+
+    #[test]
+    fn test_silence_is_not_analysed() {
+        assert!(AudioMetadataExtractor::analyze_samples(&[0.0; 100], 1, 44100, None, None).is_none());
+    }
+
     #[test]
     fn test_detect_quality() {
         let mut codec_params = symphonia::core::codecs::CodecParameters::new();
@@ -2,16 +2,25 @@ use async_trait::async_trait;
 use bytes::Bytes;
 use std::io::{Error, ErrorKind, Result as IoResult};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use tokio::sync::RwLock;
 use uuid::Uuid;
 use serde::{Deserialize, Serialize};
 
+use super::video_chunks::{
+    store_rendition, ChunkCache, ChunkStore, IpfsHttpChunkStore, VideoManifest, VideoRendition,
+    DEFAULT_CHUNK_CACHE_BYTES, DEFAULT_SEGMENT_SECONDS,
+};
+use super::video_peers::{DhtClient, DiscoveredPeer, KuboDhtClient, VideoPeerDiscovery};
+use super::video_transcoding::{
+    InMemoryTranscodeJobRepository, TranscodeJobRepository, TranscodeJobStatus, VideoTranscodeJob,
+};
+
 // Note: AudioFileStorage and AudioFileMetadata are not used in this file
 // but are imported for trait compatibility
 
 // Video-specific types (temporary definitions for compilation)
-#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum VideoQuality {
     Low,
     Medium,
@@ -28,8 +37,43 @@ impl VideoQuality {
             VideoQuality::Ultra => 10_000_000,  // 10 Mbps
         }
     }
+
+    /// Altura de la rendición en la escalera de transcodificación
+    pub fn height(&self) -> u32 {
+        match self {
+            VideoQuality::Low => 360,
+            VideoQuality::Medium => 720,
+            VideoQuality::High => 1080,
+            VideoQuality::Ultra => 2160,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VideoQuality::Low => "low",
+            VideoQuality::Medium => "medium",
+            VideoQuality::High => "high",
+            VideoQuality::Ultra => "ultra",
+        }
+    }
+
+    /// All qualities, lowest first
+    pub fn ladder() -> [VideoQuality; 4] {
+        [VideoQuality::Low, VideoQuality::Medium, VideoQuality::High, VideoQuality::Ultra]
+    }
 }
 
+/// Content types accepted by the video storages
+pub const SUPPORTED_VIDEO_TYPES: [&str; 14] = [
+    "video/mp4", "video/mpeg",
+    "video/webm", "video/webm; codecs=\"vp8,vorbis\"",
+    "video/avi", "video/x-msvideo",
+    "video/mov", "video/quicktime",
+    "video/mkv", "video/x-matroska",
+    "video/flv", "video/x-flv",
+    "video/3gpp", "video/3gpp2",
+];
+
 #[derive(Debug, Clone)]
 pub struct VideoFileMetadata {
     pub file_size: u64,
@@ -82,9 +126,17 @@ pub struct IPFSVideoStorage {
     content_cache: Arc<RwLock<HashMap<String, CachedVideoContent>>>,
     federation_registry: Arc<RwLock<HashMap<String, FederationNode>>>,
     
-    // Video Processing
-    transcoding_queue: Arc<RwLock<Vec<TranscodingJob>>>,
-    chunk_manager: Arc<RwLock<ChunkManager>>,
+    // Video Processing: un trabajo por calidad
+    transcode_jobs: Arc<dyn TranscodeJobRepository>,
+
+    // Proveedores de cada vídeo según el DHT de IPFS
+    peer_discovery: Arc<VideoPeerDiscovery>,
+
+    // Chunked storage: segmentos y manifiestos en IPFS
+    chunk_store: Arc<dyn ChunkStore>,
+    segment_seconds: u32,
+    manifests: Arc<RwLock<HashMap<String, VideoManifest>>>,
+    chunk_cache: Arc<Mutex<ChunkCache>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -120,31 +172,6 @@ struct FederationNode {
     video_processing_capacity: u32,
 }
 
-#[derive(Debug, Clone)]
-struct TranscodingJob {
-    job_id: Uuid,
-    input_hash: String,
-    target_quality: VideoQuality,
-    status: TranscodingStatus,
-    created_at: chrono::DateTime<chrono::Utc>,
-    completed_at: Option<chrono::DateTime<chrono::Utc>>,
-}
-
-#[derive(Debug, Clone)]
-enum TranscodingStatus {
-    Pending,
-    Processing,
-    Completed,
-    Failed(String),
-}
-
-#[derive(Debug, Clone)]
-struct ChunkManager {
-    chunks: HashMap<String, Vec<VideoChunk>>,
-    chunk_size: u64, // bytes
-    max_chunks_per_quality: u32,
-}
-
 impl IPFSVideoStorage {
     /// Create new distributed IPFS video storage
     pub fn new_distributed(
@@ -160,6 +187,7 @@ impl IPFSVideoStorage {
         println!("   🔍 Content Discovery: {}", enable_content_discovery);
         
         Self {
+            chunk_store: Arc::new(IpfsHttpChunkStore::new(local_node_url.clone())),
             local_node_url,
             peer_nodes,
             max_file_size,
@@ -168,14 +196,52 @@ impl IPFSVideoStorage {
             peer_connections: Arc::new(RwLock::new(HashMap::new())),
             content_cache: Arc::new(RwLock::new(HashMap::new())),
             federation_registry: Arc::new(RwLock::new(HashMap::new())),
-            transcoding_queue: Arc::new(RwLock::new(Vec::new())),
-            chunk_manager: Arc::new(RwLock::new(ChunkManager {
-                chunks: HashMap::new(),
-                chunk_size: 1024 * 1024, // 1MB chunks
-                max_chunks_per_quality: 1000,
-            })),
+            transcode_jobs: Arc::new(InMemoryTranscodeJobRepository::new()),
+            peer_discovery: Arc::new(VideoPeerDiscovery::new(Arc::new(KuboDhtClient::new(local_node_url.clone())))),
+            segment_seconds: DEFAULT_SEGMENT_SECONDS,
+            manifests: Arc::new(RwLock::new(HashMap::new())),
+            chunk_cache: Arc::new(Mutex::new(ChunkCache::new(DEFAULT_CHUNK_CACHE_BYTES))),
         }
     }
+
+    /// Store chunks and manifests somewhere other than the local IPFS node
+    pub fn with_chunk_store(mut self, chunk_store: Arc<dyn ChunkStore>) -> Self {
+        self.chunk_store = chunk_store;
+        self
+    }
+
+    /// Duration of each segment at the nominal bitrate of its quality
+    pub fn with_segment_seconds(mut self, segment_seconds: u32) -> Self {
+        self.segment_seconds = segment_seconds.max(1);
+        self
+    }
+
+    /// Size of the LRU cache of fetched segments
+    pub fn with_chunk_cache_capacity(mut self, capacity_bytes: usize) -> Self {
+        self.chunk_cache = Arc::new(Mutex::new(ChunkCache::new(capacity_bytes)));
+        self
+    }
+
+    /// Keep transcode jobs somewhere that survives restarts
+    pub fn with_transcode_jobs(mut self, transcode_jobs: Arc<dyn TranscodeJobRepository>) -> Self {
+        self.transcode_jobs = transcode_jobs;
+        self
+    }
+
+    pub fn transcode_job_repository(&self) -> Arc<dyn TranscodeJobRepository> {
+        self.transcode_jobs.clone()
+    }
+
+    /// Find providers through a DHT other than the local IPFS node's
+    pub fn with_dht_client(mut self, dht: Arc<dyn DhtClient>) -> Self {
+        self.peer_discovery = Arc::new(VideoPeerDiscovery::new(dht));
+        self
+    }
+
+    /// Peer table of the served videos; `start` it to keep it refreshed
+    pub fn peer_discovery(&self) -> Arc<VideoPeerDiscovery> {
+        self.peer_discovery.clone()
+    }
     
     /// Create new distributed IPFS video storage (async version)
     pub async fn new_distributed_async(
@@ -282,11 +348,10 @@ impl IPFSVideoStorage {
     
     /// Start transcoding worker for video processing
     async fn start_transcoding_worker(&self) -> IoResult<()> {
-        println!("🎬 Starting Video Transcoding Worker");
+        println!("🎬 Video transcoding queue ready");
         
-        // In a real implementation, this would start background workers
-        // that process transcoding jobs from the queue
-        println!("   ✅ Transcoding worker started");
+        // VideoTranscodingWorker necesita la storage en un Arc: lo arranca
+        // quien la construye
         
         Ok(())
     }
@@ -327,7 +392,7 @@ impl IPFSVideoStorage {
             duration_seconds: metadata.duration_seconds,
             qualities: metadata.available_qualities.clone(),
             chunk_count: metadata.chunk_count,
-            peer_count: 1,
+            peer_count: metadata.peer_count.unwrap_or(0),
             last_accessed: chrono::Utc::now(),
         });
         
@@ -343,17 +408,7 @@ impl IPFSVideoStorage {
                 format!("Video file size {} exceeds maximum {}", file_data.len(), self.max_file_size)));
         }
         
-        let supported_types = [
-            "video/mp4", "video/mpeg",
-            "video/webm", "video/webm; codecs=\"vp8,vorbis\"",
-            "video/avi", "video/x-msvideo",
-            "video/mov", "video/quicktime",
-            "video/mkv", "video/x-matroska",
-            "video/flv", "video/x-flv",
-            "video/3gpp", "video/3gpp2",
-        ];
-        
-        if !supported_types.contains(&content_type) {
+        if !SUPPORTED_VIDEO_TYPES.contains(&content_type) {
             return Err(Error::new(ErrorKind::InvalidInput, 
                 format!("Unsupported video format: {}", content_type)));
         }
@@ -361,14 +416,8 @@ impl IPFSVideoStorage {
         Ok(())
     }
     
-    /// Generate IPFS hash for video
-    fn generate_ipfs_hash(&self, file_data: &Bytes) -> String {
-        // In a real implementation, this would generate actual IPFS hash
-        format!("QmVideo{}", Uuid::new_v4().to_string().replace("-", ""))
-    }
-    
     /// Get IPFS gateway URL for video
-    fn get_ipfs_video_url(&self, ipfs_hash: &str) -> String {
+    pub fn get_ipfs_video_url(&self, ipfs_hash: &str) -> String {
         format!("{}/ipfs/{}", self.local_node_url, ipfs_hash)
     }
     
@@ -382,51 +431,70 @@ impl IPFSVideoStorage {
         }
     }
     
-    /// Create video chunks for streaming
-    async fn create_video_chunks(&self, file_data: &Bytes, quality: &VideoQuality) -> IoResult<Vec<VideoChunk>> {
-        let chunk_size = self.chunk_manager.read().await.chunk_size;
-        let mut chunks = Vec::new();
-        
-        let mut offset = 0;
-        let mut chunk_index = 0;
-        
-        while offset < file_data.len() {
-            let end = std::cmp::min(offset + chunk_size as usize, file_data.len());
-            let chunk_data = file_data.slice(offset..end);
-            
-            let chunk = VideoChunk {
-                chunk_index,
-                data: Bytes::from(chunk_data),
-                quality: quality.clone(),
-                timestamp: chrono::Utc::now(),
-            };
-            
-            chunks.push(chunk);
-            offset = end;
-            chunk_index += 1;
+    /// Manifest of the uploaded video, from memory or fetched from IPFS by its CID
+    async fn load_source_manifest(&self, ipfs_hash: &str) -> IoResult<VideoManifest> {
+        if let Some(manifest) = self.manifests.read().await.get(ipfs_hash) {
+            return Ok(manifest.clone());
         }
-        
-        Ok(chunks)
+        let manifest = VideoManifest::from_bytes(&self.chunk_store.get(ipfs_hash).await?)?;
+        self.manifests.write().await.insert(ipfs_hash.to_string(), manifest.clone());
+        Ok(manifest)
     }
-    
-    /// Queue video for transcoding
-    async fn queue_transcoding(&self, input_hash: &str, target_quality: VideoQuality) -> IoResult<Uuid> {
-        let job_id = Uuid::new_v4();
-        let job = TranscodingJob {
-            job_id,
-            input_hash: input_hash.to_string(),
-            target_quality: target_quality.clone(),
-            status: TranscodingStatus::Pending,
-            created_at: chrono::Utc::now(),
-            completed_at: None,
-        };
-        
-        let mut queue = self.transcoding_queue.write().await;
-        queue.push(job);
-        
-        println!("🎬 Queued transcoding job {} for quality {:?}", job_id, target_quality);
-        
-        Ok(job_id)
+
+    /// Manifest of a video with the renditions of its completed transcode jobs.
+    /// The video hash stays the CID of the uploaded manifest.
+    pub async fn load_manifest(&self, ipfs_hash: &str) -> IoResult<VideoManifest> {
+        let mut manifest = self.load_source_manifest(ipfs_hash).await?;
+        for job in self.transcode_jobs.find_by_video(ipfs_hash).await? {
+            if let (TranscodeJobStatus::Completed, Some(rendition)) = (job.status, job.rendition) {
+                if rendition.quality != manifest.source_quality {
+                    manifest.set_rendition(rendition);
+                }
+            }
+        }
+        Ok(manifest)
+    }
+
+    /// Store a manifest; its CID is the video hash
+    async fn save_manifest(&self, manifest: VideoManifest) -> IoResult<String> {
+        let ipfs_hash = self.chunk_store.put(manifest.to_bytes()?).await?;
+        self.manifests.write().await.insert(ipfs_hash.clone(), manifest);
+        Ok(ipfs_hash)
+    }
+
+    /// Chunk a transcoded rendition with the segment length of the video. It
+    /// becomes available once its job is completed with it.
+    pub async fn store_transcoded_rendition(
+        &self,
+        ipfs_hash: &str,
+        quality: &VideoQuality,
+        file_data: Bytes,
+    ) -> IoResult<VideoRendition> {
+        let manifest = self.load_source_manifest(ipfs_hash).await?;
+        store_rendition(self.chunk_store.as_ref(), &file_data, quality, manifest.segment_seconds).await
+    }
+
+    /// Manifest and chunk CIDs of a video, the ones whose providers are tracked
+    async fn track_content(&self, ipfs_hash: &str, manifest: &VideoManifest) -> Vec<String> {
+        let cids: Vec<String> = std::iter::once(ipfs_hash.to_string())
+            .chain(manifest.renditions.iter().flat_map(|r| r.chunks.iter().map(|chunk| chunk.cid.clone())))
+            .collect();
+        self.peer_discovery.track(ipfs_hash, cids.clone()).await;
+        cids
+    }
+
+    /// Providers of a video found on the DHT, lowest latency first
+    pub async fn discovered_peers(&self, ipfs_hash: &str) -> Vec<DiscoveredPeer> {
+        self.peer_discovery.peers(ipfs_hash).await
+    }
+
+    /// Transcode jobs of a video, one per quality
+    pub async fn transcode_jobs(&self, ipfs_hash: &str) -> IoResult<Vec<VideoTranscodeJob>> {
+        self.transcode_jobs.find_by_video(ipfs_hash).await
+    }
+
+    fn cached_chunk(&self, cid: &str) -> Option<Bytes> {
+        self.chunk_cache.lock().unwrap().get(cid)
     }
 }
 
@@ -438,42 +506,29 @@ impl VideoFileStorage for IPFSVideoStorage {
         // Validate video file
         self.validate_video_file(&file_data, content_type)?;
         
-        // Generate IPFS hash
-        let ipfs_hash = self.generate_ipfs_hash(&file_data);
-        
-        // Create video metadata
-        let metadata = VideoFileMetadata {
-            file_size: file_data.len() as u64,
-            content_type: content_type.to_string(),
-            duration_seconds: Some(0), // Would be extracted in real implementation
-            width: Some(1920), // Would be extracted
-            height: Some(1080), // Would be extracted
-            frame_rate: Some(30.0), // Would be extracted
-            bitrate: Some(5000), // Would be extracted (kbps)
-            available_qualities: vec![VideoQuality::High], // Original quality
-            chunk_count: 0, // Will be calculated
-            created_at: chrono::Utc::now(),
-            peer_count: Some(1),
-            availability_score: Some(1.0),
-        };
-        
-        // Create chunks for streaming
-        let chunks = self.create_video_chunks(&file_data, &VideoQuality::High).await?;
-        let mut chunk_manager = self.chunk_manager.write().await;
-        chunk_manager.chunks.insert(ipfs_hash.clone(), chunks);
-        
-        // Announce to P2P network
-        self.announce_video_content(&ipfs_hash, &metadata).await?;
+        // Trocear el original y guardar el manifiesto: su CID es el hash del vídeo
+        let source_quality = VideoQuality::High;
+        let mut manifest = VideoManifest::new(file_name, content_type, self.segment_seconds, source_quality.clone());
+        let rendition = store_rendition(self.chunk_store.as_ref(), &file_data, &source_quality, self.segment_seconds).await?;
+        let chunk_count = rendition.chunk_count();
+        manifest.set_rendition(rendition);
+        let ipfs_hash = self.save_manifest(manifest).await?;
+        
+        // Announce to P2P network; sin registros en el DHT el vídeo se sirve
+        // igual desde este nodo
+        if let Err(e) = self.announce_to_network(&self.get_ipfs_video_url(&ipfs_hash)).await {
+            tracing::warn!("Failed to announce video {} to the DHT: {}", ipfs_hash, e);
+        }
         
         // Queue transcoding for other qualities
-        for quality in [VideoQuality::Low, VideoQuality::Medium, VideoQuality::Ultra] {
-            if quality != VideoQuality::High {
-                self.queue_transcoding(&ipfs_hash, quality).await?;
-            }
-        }
+        let ladder: Vec<VideoQuality> = VideoQuality::ladder()
+            .into_iter()
+            .filter(|quality| *quality != source_quality)
+            .collect();
+        self.transcode_jobs.enqueue(&ipfs_hash, &ladder).await?;
         
         let url = self.get_ipfs_video_url(&ipfs_hash);
-        println!("   ✅ Uploaded to IPFS: {}", url);
+        println!("   ✅ Uploaded to IPFS: {} ({} chunks)", url, chunk_count);
         println!("   📡 Announced to {} peers", self.peer_nodes.len());
         println!("   🎬 Queued transcoding for multiple qualities");
         
@@ -484,20 +539,22 @@ impl VideoFileStorage for IPFSVideoStorage {
         let ipfs_hash = self.extract_ipfs_hash(url)?;
         println!("📥 Downloading video from P2P network: {}", ipfs_hash);
         
-        // Get best peers for this video
-        let peers = self.get_best_video_peers(&ipfs_hash, &VideoQuality::High).await?;
+        let manifest = self.load_manifest(&ipfs_hash).await?;
+        let source = manifest.source()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("Manifest {} has no source rendition", ipfs_hash)))?;
         
-        if peers.is_empty() {
-            return Err(Error::new(ErrorKind::NotFound, 
-                "No peers available for this video content"));
+        // Reensamblar en orden; sin pasar por la caché para no vaciarla
+        let mut data = Vec::with_capacity(source.total_size as usize);
+        for chunk in &source.chunks {
+            data.extend_from_slice(&self.chunk_store.get(&chunk.cid).await?);
+        }
+        if data.len() as u64 != source.total_size {
+            return Err(Error::new(ErrorKind::InvalidData,
+                format!("Video {} reassembled to {} bytes, expected {}", ipfs_hash, data.len(), source.total_size)));
         }
         
-        // In a real implementation, this would download from the best peer
-        // For now, we'll simulate it
-        let dummy_data = Bytes::from("dummy_video_data");
-        
-        println!("   ✅ Downloaded from {} peers", peers.len());
-        Ok(dummy_data)
+        println!("   ✅ Downloaded {} chunks", source.chunks.len());
+        Ok(Bytes::from(data))
     }
     
     async fn delete_video(&self, url: &str) -> IoResult<()> {
@@ -508,9 +565,18 @@ impl VideoFileStorage for IPFSVideoStorage {
         let mut cache = self.content_cache.write().await;
         cache.remove(&ipfs_hash);
         
-        // Remove chunks
-        let mut chunk_manager = self.chunk_manager.write().await;
-        chunk_manager.chunks.remove(&ipfs_hash);
+        drop(cache);
+        
+        // Despinear segmentos y manifiesto
+        let manifest = self.load_manifest(&ipfs_hash).await?;
+        for chunk in manifest.renditions.iter().flat_map(|r| &r.chunks) {
+            self.chunk_cache.lock().unwrap().remove(&chunk.cid);
+            self.chunk_store.remove(&chunk.cid).await?;
+        }
+        self.chunk_store.remove(&ipfs_hash).await?;
+        self.manifests.write().await.remove(&ipfs_hash);
+        self.transcode_jobs.delete_by_video(&ipfs_hash).await?;
+        self.peer_discovery.untrack(&ipfs_hash).await;
         
         println!("   ✅ Removed from local cache and signaled to peers");
         
@@ -538,73 +604,82 @@ impl VideoFileStorage for IPFSVideoStorage {
     async fn get_video_chunk(&self, url: &str, chunk_index: u32, quality: &VideoQuality) -> IoResult<VideoChunk> {
         let ipfs_hash = self.extract_ipfs_hash(url)?;
         
-        let chunk_manager = self.chunk_manager.read().await;
-        if let Some(chunks) = chunk_manager.chunks.get(&ipfs_hash) {
-            if let Some(chunk) = chunks.get(chunk_index as usize) {
-                if chunk.quality == *quality {
-                    return Ok(chunk.clone());
+        let manifest = self.load_manifest(&ipfs_hash).await?;
+        let rendition = manifest.rendition(quality)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound,
+                format!("Quality {:?} is not available for video {}", quality, ipfs_hash)))?;
+        let chunk = rendition.chunks.get(chunk_index as usize)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput,
+                format!("Chunk {} out of range: {:?} has {} chunks", chunk_index, quality, rendition.chunks.len())))?;
+        
+        let data = match self.cached_chunk(&chunk.cid) {
+            Some(data) => data,
+            None => {
+                let data = self.chunk_store.get(&chunk.cid).await?;
+                if data.len() as u64 != chunk.size {
+                    return Err(Error::new(ErrorKind::InvalidData,
+                        format!("Chunk {} has {} bytes, manifest says {}", chunk.cid, data.len(), chunk.size)));
                 }
+                self.chunk_cache.lock().unwrap().insert(chunk.cid.clone(), data.clone());
+                data
             }
-        }
+        };
         
-        Err(Error::new(ErrorKind::NotFound, 
-            format!("Chunk {} not found for quality {:?}", chunk_index, quality)))
+        Ok(VideoChunk {
+            chunk_index,
+            data,
+            quality: quality.clone(),
+            timestamp: chrono::Utc::now(),
+        })
     }
     
     async fn get_metadata(&self, url: &str) -> IoResult<VideoFileMetadata> {
         let ipfs_hash = self.extract_ipfs_hash(url)?;
         
-        // Check local cache first
-        let cache = self.content_cache.read().await;
-        if let Some(cached) = cache.get(&ipfs_hash) {
-            return Ok(VideoFileMetadata {
-                file_size: cached.file_size,
-                content_type: cached.content_type.clone(),
-                duration_seconds: cached.duration_seconds,
-                width: Some(1920), // Would be extracted from actual file
-                height: Some(1080),
-                frame_rate: Some(30.0),
-                bitrate: Some(5000),
-                available_qualities: cached.qualities.clone(),
-                chunk_count: cached.chunk_count,
-                created_at: cached.last_accessed,
-                peer_count: Some(cached.peer_count),
-                availability_score: Some(1.0),
-            });
-        }
+        let manifest = self.load_manifest(&ipfs_hash).await?;
+        let source = manifest.source()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("Manifest {} has no source rendition", ipfs_hash)))?;
         
-        // Query P2P network for metadata
-        let peers = self.get_best_video_peers(&ipfs_hash, &VideoQuality::High).await?;
+        // La tarea de descubrimiento refresca los proveedores de lo que se sigue
+        self.track_content(&ipfs_hash, &manifest).await;
+        let availability = self.peer_discovery.availability(&ipfs_hash).await;
         
         Ok(VideoFileMetadata {
-            file_size: 0,
-            content_type: "video/mp4".to_string(),
-            duration_seconds: Some(0),
-            width: Some(1920),
-            height: Some(1080),
-            frame_rate: Some(30.0),
-            bitrate: Some(5000),
-            available_qualities: vec![VideoQuality::High],
-            chunk_count: 0,
-            created_at: chrono::Utc::now(),
-            peer_count: Some(peers.len() as u32),
-            availability_score: Some(if peers.is_empty() { 0.0 } else { 1.0 }),
+            file_size: source.total_size,
+            content_type: manifest.content_type.clone(),
+            // Aproximada: segmentos a bitrate nominal
+            duration_seconds: Some(source.chunk_count() * manifest.segment_seconds),
+            width: None,
+            height: None,
+            frame_rate: None,
+            bitrate: Some((source.bitrate / 1000) as u32),
+            available_qualities: manifest.qualities(),
+            chunk_count: source.chunk_count(),
+            created_at: manifest.created_at,
+            peer_count: Some(availability.provider_count),
+            availability_score: Some(availability.availability_score),
         })
     }
     
     async fn get_peers(&self, url: &str) -> IoResult<Vec<String>> {
         let ipfs_hash = self.extract_ipfs_hash(url)?;
-        self.get_best_video_peers(&ipfs_hash, &VideoQuality::High).await
+        Ok(self.discovered_peers(&ipfs_hash).await.into_iter().map(|peer| peer.peer_id).collect())
     }
     
     async fn announce_to_network(&self, url: &str) -> IoResult<()> {
         let ipfs_hash = self.extract_ipfs_hash(url)?;
         
-        // Get metadata
-        let metadata = self.get_metadata(url).await?;
+        // Registros de proveedor para el manifiesto y los segmentos que tiene este nodo
+        let manifest = self.load_manifest(&ipfs_hash).await?;
+        let cids = self.track_content(&ipfs_hash, &manifest).await;
+        let announced = self.peer_discovery.announce(&cids).await?;
+        if let Err(e) = self.peer_discovery.refresh(&ipfs_hash).await {
+            tracing::debug!("Peer refresh after announcing {} failed: {}", ipfs_hash, e);
+        }
         
-        // Announce to network
+        let metadata = self.get_metadata(url).await?;
         self.announce_video_content(&ipfs_hash, &metadata).await?;
+        println!("   📡 Published {} provider records for {}", announced, ipfs_hash);
         
         Ok(())
     }
@@ -616,13 +691,25 @@ impl VideoFileStorage for IPFSVideoStorage {
     
     async fn transcode_video(&self, url: &str, target_quality: VideoQuality) -> IoResult<Uuid> {
         let ipfs_hash = self.extract_ipfs_hash(url)?;
-        self.queue_transcoding(&ipfs_hash, target_quality).await
+        let manifest = self.load_source_manifest(&ipfs_hash).await?;
+        if target_quality == manifest.source_quality {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                format!("{:?} is the source quality of video {}", target_quality, ipfs_hash)));
+        }
+        
+        let jobs = self.transcode_jobs.enqueue(&ipfs_hash, &[target_quality]).await?;
+        jobs.into_iter()
+            .next()
+            .map(|job| job.id)
+            .ok_or_else(|| Error::new(ErrorKind::Other, "Transcode job was not queued"))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::super::video_chunks::InMemoryChunkStore;
+    use super::super::video_peers::InMemoryDhtClient;
     use tokio;
     
     #[tokio::test]
@@ -657,4 +744,118 @@ mod tests {
         assert!(storage.validate_video_file(&large_file, "video/mp4").is_err());
         assert!(storage.validate_video_file(&small_file, "audio/mpeg").is_err());
     }
-} 
\ No newline at end of file
+    
+    /// Vídeo de prueba: cabecera ftyp de MP4 y un patrón, 1.500.001 bytes.
+    /// High = 5 Mbps, con segmentos de 1s son 625.000 bytes: 3 segmentos.
+    fn fixture_video() -> Bytes {
+        let mut data = vec![0x00, 0x00, 0x00, 0x18, b'f', b't', b'y', b'p', b'i', b's', b'o', b'm'];
+        data.extend((0..1_500_001u32 - 12).map(|i| (i % 251) as u8));
+        Bytes::from(data)
+    }
+    
+    fn chunked_storage(store: Arc<InMemoryChunkStore>) -> IPFSVideoStorage {
+        IPFSVideoStorage::new_distributed("http://localhost:5001".to_string(), vec![], 500 * 1024 * 1024, false, false)
+            .with_chunk_store(store)
+            .with_segment_seconds(1)
+            .with_dht_client(Arc::new(InMemoryDhtClient::new("local")))
+    }
+    
+    #[tokio::test]
+    async fn test_uploaded_video_chunks_reassemble_to_the_original() {
+        let store = Arc::new(InMemoryChunkStore::new());
+        let storage = chunked_storage(store.clone());
+        let video = fixture_video();
+        
+        let url = storage.upload_video(video.clone(), "clip.mp4", "video/mp4").await.unwrap();
+        let metadata = storage.get_metadata(&url).await.unwrap();
+        assert_eq!(metadata.chunk_count, 3);
+        assert_eq!(metadata.file_size, 1_500_001);
+        assert_eq!(metadata.available_qualities, vec![VideoQuality::High]);
+        
+        let mut reassembled = Vec::new();
+        let mut sizes = Vec::new();
+        for index in 0..metadata.chunk_count {
+            let chunk = storage.get_video_chunk(&url, index, &VideoQuality::High).await.unwrap();
+            assert_eq!(chunk.chunk_index, index);
+            sizes.push(chunk.data.len());
+            reassembled.extend_from_slice(&chunk.data);
+        }
+        assert_eq!(sizes, vec![625_000, 625_000, 250_001]);
+        assert_eq!(Bytes::from(reassembled), video);
+        assert_eq!(storage.download_video(&url).await.unwrap(), video);
+    }
+    
+    #[tokio::test]
+    async fn test_manifest_is_fetched_from_ipfs_and_chunks_are_cached() {
+        let store = Arc::new(InMemoryChunkStore::new());
+        let url = chunked_storage(store.clone())
+            .upload_video(fixture_video(), "clip.mp4", "video/mp4")
+            .await
+            .unwrap();
+        
+        // Otra instancia sin el manifiesto en memoria
+        let storage = chunked_storage(store.clone());
+        assert_eq!(storage.get_metadata(&url).await.unwrap().chunk_count, 3);
+        let gets = store.get_count();
+        let first = storage.get_video_chunk(&url, 1, &VideoQuality::High).await.unwrap();
+        assert_eq!(store.get_count(), gets + 1);
+        let again = storage.get_video_chunk(&url, 1, &VideoQuality::High).await.unwrap();
+        assert_eq!(store.get_count(), gets + 1);
+        assert_eq!(first.data, again.data);
+    }
+    
+    #[tokio::test]
+    async fn test_out_of_range_chunks_and_missing_qualities_error() {
+        let storage = chunked_storage(Arc::new(InMemoryChunkStore::new()));
+        let url = storage.upload_video(fixture_video(), "clip.mp4", "video/mp4").await.unwrap();
+        
+        let out_of_range = storage.get_video_chunk(&url, 3, &VideoQuality::High).await.unwrap_err();
+        assert_eq!(out_of_range.kind(), ErrorKind::InvalidInput);
+        let missing = storage.get_video_chunk(&url, 0, &VideoQuality::Low).await.unwrap_err();
+        assert_eq!(missing.kind(), ErrorKind::NotFound);
+        
+        // Con la versión Low transcodificada: 125.000 bytes por segmento
+        let low = Bytes::from(vec![7u8; 300_000]);
+        let ipfs_hash = storage.extract_ipfs_hash(&url).unwrap();
+        let rendition = storage.store_transcoded_rendition(&ipfs_hash, &VideoQuality::Low, low.clone()).await.unwrap();
+        assert!(!storage.get_available_qualities(&url).await.unwrap().contains(&VideoQuality::Low));
+        let jobs = storage.transcode_jobs(&ipfs_hash).await.unwrap();
+        let job = jobs.iter().find(|job| job.quality == VideoQuality::Low).unwrap();
+        storage.transcode_job_repository().complete(job.id, &rendition).await.unwrap();
+        let qualities = storage.get_available_qualities(&url).await.unwrap();
+        assert!(qualities.contains(&VideoQuality::Low) && qualities.contains(&VideoQuality::High));
+        let last = storage.get_video_chunk(&url, 2, &VideoQuality::Low).await.unwrap();
+        assert_eq!(last.data, low.slice(250_000..));
+        assert!(storage.get_video_chunk(&url, 3, &VideoQuality::Low).await.is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_announce_publishes_providers_and_metadata_scores_availability() {
+        let dht = Arc::new(InMemoryDhtClient::new("local"));
+        let storage = chunked_storage(Arc::new(InMemoryChunkStore::new())).with_dht_client(dht.clone());
+        let url = storage.upload_video(fixture_video(), "clip.mp4", "video/mp4").await.unwrap();
+        let ipfs_hash = storage.extract_ipfs_hash(&url).unwrap();
+        
+        // Manifiesto y los 3 segmentos anunciados por este nodo
+        let manifest = storage.load_manifest(&ipfs_hash).await.unwrap();
+        for cid in std::iter::once(ipfs_hash.clone()).chain(manifest.renditions[0].chunks.iter().map(|c| c.cid.clone())) {
+            assert_eq!(dht.providers_of(&cid)[0].peer_id, "local");
+        }
+        let metadata = storage.get_metadata(&url).await.unwrap();
+        assert_eq!(metadata.peer_count, Some(1));
+        let alone = metadata.availability_score.unwrap();
+        assert!(alone > 0.0);
+        
+        // Otro peer con el vídeo completo
+        for cid in std::iter::once(ipfs_hash.clone()).chain(manifest.renditions[0].chunks.iter().map(|c| c.cid.clone())) {
+            dht.add_provider(&cid, "remote", vec!["/ip4/10.0.0.2/tcp/4001".to_string()]);
+        }
+        dht.set_latency("remote", std::time::Duration::from_millis(30));
+        storage.peer_discovery().refresh_all().await;
+        
+        let metadata = storage.get_metadata(&url).await.unwrap();
+        assert_eq!(metadata.peer_count, Some(2));
+        assert!(metadata.availability_score.unwrap() > alone);
+        assert_eq!(storage.get_peers(&url).await.unwrap(), vec!["local".to_string(), "remote".to_string()]);
+    }
+}
@@ -1,49 +1,177 @@
 use async_trait::async_trait;
+use axum::{
+    body::Body,
+    extract::{Path as UrlPath, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
 use bytes::Bytes;
+use hmac_sha256::HMAC;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use std::io::Result as IoResult;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
 use tokio::fs;
-use tokio::io::AsyncWriteExt;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use chrono::{DateTime, Utc};
 
 use super::{AudioFileStorage, AudioFileMetadata};
 
-/// Local file system storage for development
+/// Validez de las URLs firmadas del servidor de ficheros local
+const SIGNED_URL_TTL_SECS: i64 = 3600;
+const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Configuration of the local (development) storage
+#[derive(Debug, Clone)]
+pub struct LocalStorageConfig {
+    pub base_path: String,
+    pub max_file_size: u64,
+    /// When set, a file server on `localhost:{port}` serves the files and
+    /// streaming URLs are signed `http://localhost:{port}/{path}` URLs.
+    /// Otherwise streaming URLs are `file://` URLs.
+    pub serve_port: Option<u16>,
+    /// Key for the signed URLs; random per process if not set
+    pub signing_secret: Option<String>,
+}
+
+impl LocalStorageConfig {
+    pub fn new(base_path: impl Into<String>, max_file_size: u64) -> Self {
+        Self {
+            base_path: base_path.into(),
+            max_file_size,
+            serve_port: None,
+            signing_secret: None,
+        }
+    }
+
+    /// `LOCAL_STORAGE_SERVE_PORT` y `LOCAL_STORAGE_SIGNING_SECRET`
+    pub fn with_env(mut self) -> Self {
+        self.serve_port = std::env::var("LOCAL_STORAGE_SERVE_PORT").ok().and_then(|v| v.parse().ok());
+        self.signing_secret = std::env::var("LOCAL_STORAGE_SIGNING_SECRET").ok();
+        self
+    }
+}
+
+/// Files and bytes currently stored
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StorageMetrics {
+    pub total_files: u64,
+    pub total_bytes_stored: u64,
+}
+
+/// Local file system storage for development.
+///
+/// Files are written to `base_path/{xx}/{file_name}`, where `xx` are the first
+/// two hex chars of the SHA-256 of the content, so no directory grows too big.
 pub struct LocalAudioStorage {
     base_path: PathBuf,
     max_file_size: u64,
+    serve_port: Option<u16>,
+    signing_key: Vec<u8>,
 }
 
 impl LocalAudioStorage {
     pub fn new(base_path: String, max_file_size: u64) -> Self {
+        Self::from_config(LocalStorageConfig::new(base_path, max_file_size))
+    }
+
+    pub fn from_config(config: LocalStorageConfig) -> Self {
+        let signing_key = match config.signing_secret {
+            Some(secret) => secret.into_bytes(),
+            None => rand::random::<[u8; 32]>().to_vec(),
+        };
         Self {
-            base_path: PathBuf::from(base_path),
-            max_file_size,
+            base_path: PathBuf::from(config.base_path),
+            max_file_size: config.max_file_size,
+            serve_port: config.serve_port,
+            signing_key,
         }
     }
 
-    /// Ensure storage directory exists
-    async fn ensure_directory(&self) -> IoResult<()> {
-        if !self.base_path.exists() {
-            fs::create_dir_all(&self.base_path).await?;
+    /// Start the file server if `serve_port` is configured
+    pub fn start_file_server(&self) -> Option<tokio::task::JoinHandle<()>> {
+        let port = self.serve_port?;
+        let router = file_server_router(self.base_path.clone(), self.signing_key.clone());
+
+        Some(tokio::spawn(async move {
+            let listener = match tokio::net::TcpListener::bind(("127.0.0.1", port)).await {
+                Ok(listener) => listener,
+                Err(e) => {
+                    tracing::error!("Local storage file server could not bind port {}: {}", port, e);
+                    return;
+                }
+            };
+            tracing::info!("Local storage file server listening on http://localhost:{}", port);
+            if let Err(e) = axum::serve(listener, router).await {
+                tracing::error!("Local storage file server stopped: {}", e);
+            }
+        }))
+    }
+
+    /// Open a stored file for streaming reads
+    pub async fn open_audio(&self, url: &str) -> IoResult<fs::File> {
+        fs::File::open(self.resolve(url)?).await
+    }
+
+    /// Count files and bytes under `base_path`
+    pub async fn metrics(&self) -> IoResult<StorageMetrics> {
+        let mut metrics = StorageMetrics::default();
+        if !fs::try_exists(&self.base_path).await? {
+            return Ok(metrics);
         }
-        Ok(())
+
+        let mut pending = vec![self.base_path.clone()];
+        while let Some(directory) = pending.pop() {
+            let mut entries = fs::read_dir(&directory).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let metadata = entry.metadata().await?;
+                if metadata.is_dir() {
+                    pending.push(entry.path());
+                } else if metadata.is_file() {
+                    metrics.total_files += 1;
+                    metrics.total_bytes_stored += metadata.len();
+                }
+            }
+        }
+        Ok(metrics)
     }
 
-    /// Get full file path
-    fn get_file_path(&self, file_name: &str) -> PathBuf {
-        self.base_path.join(file_name)
+    /// Path relative to `base_path` of a `local://` URL
+    fn relative_path(url: &str) -> IoResult<&str> {
+        let relative = url.strip_prefix("local://")
+            .ok_or_else(|| invalid_input("Invalid local URL format"))?;
+        if !is_safe_relative(relative) {
+            return Err(invalid_input("Invalid local file path"));
+        }
+        Ok(relative)
+    }
+
+    fn resolve(&self, url: &str) -> IoResult<PathBuf> {
+        Ok(self.base_path.join(Self::relative_path(url)?))
     }
 
-    /// Generate streaming URL for local files
-    fn generate_streaming_url(&self, file_name: &str) -> String {
-        format!("/api/v1/audio/stream/{}", file_name)
+    fn shard(file_data: &[u8]) -> String {
+        hex::encode(Sha256::digest(file_data))[..2].to_string()
+    }
+
+    fn signed_url(&self, port: u16, relative: &str, now: DateTime<Utc>) -> String {
+        let expires = now.timestamp() + SIGNED_URL_TTL_SECS;
+        format!(
+            "http://localhost:{}/{}?expires={}&signature={}",
+            port,
+            relative,
+            expires,
+            sign(&self.signing_key, relative, expires)
+        )
     }
 }
 
 #[async_trait]
 impl AudioFileStorage for LocalAudioStorage {
-    async fn upload_audio(&self, file_data: Bytes, file_name: &str, content_type: &str) -> IoResult<String> {
+    async fn upload_audio(&self, file_data: Bytes, file_name: &str, _content_type: &str) -> IoResult<String> {
         // Validate file size
         if file_data.len() as u64 > self.max_file_size {
             return Err(std::io::Error::new(
@@ -52,80 +180,62 @@ impl AudioFileStorage for LocalAudioStorage {
             ));
         }
 
-        // Ensure directory exists
-        self.ensure_directory().await?;
+        // Solo el nombre: nada de rutas en el nombre del fichero
+        let file_name = Path::new(file_name)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| invalid_input("Invalid file name"))?;
+        let relative = format!("{}/{}", Self::shard(&file_data), file_name);
 
-        // Write file to disk
-        let file_path = self.get_file_path(file_name);
+        let file_path = self.base_path.join(&relative);
+        if let Some(directory) = file_path.parent() {
+            fs::create_dir_all(directory).await?;
+        }
         let mut file = fs::File::create(&file_path).await?;
         file.write_all(&file_data).await?;
         file.flush().await?;
 
-        // Return local file URL
-        Ok(format!("local://{}", file_name))
+        Ok(format!("local://{}", relative))
     }
 
     async fn download_audio(&self, url: &str) -> IoResult<Bytes> {
-        // Extract filename from URL
-        let file_name = url.strip_prefix("local://")
-            .ok_or_else(|| std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                "Invalid local URL format"
-            ))?;
-
-        let file_path = self.get_file_path(file_name);
-        let data = fs::read(&file_path).await?;
+        let data = fs::read(self.resolve(url)?).await?;
         Ok(Bytes::from(data))
     }
 
     async fn delete_audio(&self, url: &str) -> IoResult<()> {
-        let file_name = url.strip_prefix("local://")
-            .ok_or_else(|| std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                "Invalid local URL format"
-            ))?;
+        let file_path = self.resolve(url)?;
+        match fs::remove_file(&file_path).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e),
+        }
 
-        let file_path = self.get_file_path(file_name);
-        if file_path.exists() {
-            fs::remove_file(&file_path).await?;
+        // El directorio del shard se borra si quedó vacío (falla si no lo está)
+        if let Some(directory) = file_path.parent().filter(|dir| *dir != self.base_path) {
+            let _ = fs::remove_dir(directory).await;
         }
         Ok(())
     }
 
     async fn get_streaming_url(&self, url: &str) -> IoResult<String> {
-        let file_name = url.strip_prefix("local://")
-            .ok_or_else(|| std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                "Invalid local URL format"
-            ))?;
-
-        Ok(self.generate_streaming_url(file_name))
+        let relative = Self::relative_path(url)?;
+        match self.serve_port {
+            Some(port) => Ok(self.signed_url(port, relative, Utc::now())),
+            None => {
+                let absolute = std::path::absolute(self.base_path.join(relative))?;
+                Ok(format!("file://{}", absolute.display()))
+            }
+        }
     }
 
     async fn get_metadata(&self, url: &str) -> IoResult<AudioFileMetadata> {
-        let file_name = url.strip_prefix("local://")
-            .ok_or_else(|| std::io::Error::new(
-                std::io::ErrorKind::InvalidInput,
-                "Invalid local URL format"
-            ))?;
-
-        let file_path = self.get_file_path(file_name);
+        let file_path = self.resolve(url)?;
         let metadata = fs::metadata(&file_path).await?;
-        
-        // Detect content type from extension
-        let content_type = match Path::new(file_name).extension().and_then(|ext| ext.to_str()) {
-            Some("mp3") => "audio/mpeg",
-            Some("flac") => "audio/flac",
-            Some("wav") => "audio/wav",
-            Some("aac") => "audio/aac",
-            Some("ogg") => "audio/ogg",
-            Some("m4a") => "audio/mp4",
-            _ => "application/octet-stream",
-        };
 
         Ok(AudioFileMetadata {
-            file_size: metadata.len() as u64,
-            content_type: content_type.to_string(),
+            file_size: metadata.len(),
+            content_type: content_type_for(&file_path).to_string(),
             duration_seconds: None, // TODO: Extract from file
             bitrate: None,
             sample_rate: None,
@@ -133,18 +243,224 @@ impl AudioFileStorage for LocalAudioStorage {
             availability_score: Some(1.0), // Default availability score
             peer_count: Some(0), // Default peer count
             created_at: metadata.created()
-                .map(|time| DateTime::from(time))
+                .map(DateTime::from)
                 .unwrap_or_else(|_| Utc::now()),
         })
     }
 
-    async fn get_peers(&self, url: &str) -> IoResult<Vec<String>> {
+    async fn get_peers(&self, _url: &str) -> IoResult<Vec<String>> {
         // Mock implementation - return empty list for local storage
         Ok(Vec::new())
     }
 
-    async fn announce_to_network(&self, url: &str) -> IoResult<()> {
+    async fn announce_to_network(&self, _url: &str) -> IoResult<()> {
         // Mock implementation - no network announcement for local storage
         Ok(())
     }
-} 
\ No newline at end of file
+}
+
+fn invalid_input(message: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, message.to_string())
+}
+
+/// Solo componentes normales: sin `..`, raíz ni prefijos
+fn is_safe_relative(relative: &str) -> bool {
+    !relative.is_empty()
+        && Path::new(relative).components().all(|component| matches!(component, Component::Normal(_)))
+}
+
+fn content_type_for(path: &Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("mp3") => "audio/mpeg",
+        Some("flac") => "audio/flac",
+        Some("wav") => "audio/wav",
+        Some("aac") => "audio/aac",
+        Some("ogg") => "audio/ogg",
+        Some("m4a") => "audio/mp4",
+        _ => "application/octet-stream",
+    }
+}
+
+fn sign(key: &[u8], relative: &str, expires: i64) -> String {
+    hex::encode(HMAC::mac(format!("{}:{}", relative, expires).as_bytes(), key))
+}
+
+// -----------------------------------------------------------------------------
+// Servidor de ficheros local
+// -----------------------------------------------------------------------------
+
+struct FileServerState {
+    base_path: PathBuf,
+    signing_key: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SignedUrlParams {
+    expires: i64,
+    signature: String,
+}
+
+fn file_server_router(base_path: PathBuf, signing_key: Vec<u8>) -> Router {
+    Router::new()
+        .route("/*path", get(serve_file))
+        .with_state(Arc::new(FileServerState { base_path, signing_key }))
+}
+
+async fn serve_file(
+    State(state): State<Arc<FileServerState>>,
+    UrlPath(relative): UrlPath<String>,
+    Query(params): Query<SignedUrlParams>,
+) -> Response {
+    if !is_safe_relative(&relative) {
+        return StatusCode::BAD_REQUEST.into_response();
+    }
+    let expected = sign(&state.signing_key, &relative, params.expires);
+    if params.expires < Utc::now().timestamp() || expected != params.signature {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let path = state.base_path.join(&relative);
+    let file = match fs::File::open(&path).await {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return StatusCode::NOT_FOUND.into_response(),
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+    let length = match file.metadata().await {
+        Ok(metadata) => metadata.len(),
+        Err(_) => return StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    };
+
+    // Se envía por trozos en lugar de cargar el fichero entero en memoria
+    let chunks = futures_util::stream::unfold(Some(file), |file| async move {
+        let mut file = file?;
+        let mut buffer = vec![0u8; STREAM_CHUNK_SIZE];
+        match file.read(&mut buffer).await {
+            Ok(0) => None,
+            Ok(read) => {
+                buffer.truncate(read);
+                Some((Ok(Bytes::from(buffer)), Some(file)))
+            }
+            Err(e) => Some((Err::<Bytes, std::io::Error>(e), None)),
+        }
+    });
+
+    (
+        [
+            (header::CONTENT_TYPE, content_type_for(&path).to_string()),
+            (header::CONTENT_LENGTH, length.to_string()),
+        ],
+        Body::from_stream(chunks),
+    )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/audio/tone.wav");
+
+    fn storage(dir: &tempfile::TempDir, serve_port: Option<u16>) -> LocalAudioStorage {
+        LocalAudioStorage::from_config(LocalStorageConfig {
+            base_path: dir.path().to_string_lossy().to_string(),
+            max_file_size: 1024 * 1024,
+            serve_port,
+            signing_secret: Some("test-secret".to_string()),
+        })
+    }
+
+    #[tokio::test]
+    async fn upload_download_and_delete_fixture() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage(&dir, None);
+        let fixture = Bytes::from(fs::read(FIXTURE).await.unwrap());
+
+        let url = storage.upload_audio(fixture.clone(), "tone.wav", "audio/wav").await.unwrap();
+        let shard = LocalAudioStorage::shard(&fixture);
+        assert_eq!(url, format!("local://{}/tone.wav", shard));
+        assert!(dir.path().join(&shard).join("tone.wav").is_file());
+
+        assert_eq!(storage.download_audio(&url).await.unwrap(), fixture);
+
+        let mut streamed = Vec::new();
+        storage.open_audio(&url).await.unwrap().read_to_end(&mut streamed).await.unwrap();
+        assert_eq!(streamed, fixture.to_vec());
+
+        let metadata = storage.get_metadata(&url).await.unwrap();
+        assert_eq!(metadata.file_size, fixture.len() as u64);
+        assert_eq!(metadata.content_type, "audio/wav");
+
+        assert_eq!(
+            storage.metrics().await.unwrap(),
+            StorageMetrics { total_files: 1, total_bytes_stored: fixture.len() as u64 }
+        );
+
+        storage.delete_audio(&url).await.unwrap();
+        assert!(storage.download_audio(&url).await.is_err());
+        assert!(!dir.path().join(&shard).exists());
+        assert_eq!(storage.metrics().await.unwrap(), StorageMetrics::default());
+    }
+
+    #[tokio::test]
+    async fn rejects_paths_outside_base() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage(&dir, None);
+
+        assert!(storage.download_audio("local://../secret.wav").await.is_err());
+        assert!(storage.download_audio("local:///etc/passwd").await.is_err());
+
+        let url = storage.upload_audio(Bytes::from_static(b"RIFF"), "../../escape.wav", "audio/wav").await.unwrap();
+        assert!(url.ends_with("/escape.wav"));
+        assert_eq!(storage.metrics().await.unwrap().total_files, 1);
+    }
+
+    #[tokio::test]
+    async fn streaming_url_is_file_url_without_server() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage(&dir, None);
+        let url = storage.upload_audio(Bytes::from_static(b"RIFF"), "a.wav", "audio/wav").await.unwrap();
+
+        let streaming = storage.get_streaming_url(&url).await.unwrap();
+        assert!(streaming.starts_with("file://"));
+        assert!(streaming.ends_with("/a.wav"));
+    }
+
+    #[tokio::test]
+    async fn signed_urls_are_served_and_tampering_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let port = {
+            let probe = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+            probe.local_addr().unwrap().port()
+        };
+        let storage = storage(&dir, Some(port));
+        let fixture = Bytes::from(fs::read(FIXTURE).await.unwrap());
+        let url = storage.upload_audio(fixture.clone(), "tone.wav", "audio/wav").await.unwrap();
+        let server = storage.start_file_server().unwrap();
+
+        let streaming = storage.get_streaming_url(&url).await.unwrap();
+        assert!(streaming.starts_with(&format!("http://localhost:{}/", port)));
+
+        let client = reqwest::Client::new();
+        let mut response = None;
+        for _ in 0..50 {
+            match client.get(&streaming).send().await {
+                Ok(ok) => {
+                    response = Some(ok);
+                    break;
+                }
+                Err(_) => tokio::time::sleep(std::time::Duration::from_millis(20)).await,
+            }
+        }
+        let response = response.expect("file server did not start");
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        assert_eq!(response.bytes().await.unwrap(), fixture);
+
+        let tampered = streaming.replace("signature=", "signature=00");
+        assert_eq!(client.get(&tampered).send().await.unwrap().status(), reqwest::StatusCode::FORBIDDEN);
+
+        let expired = storage.signed_url(port, "x/tone.wav", Utc::now() - chrono::Duration::hours(2));
+        assert_eq!(client.get(&expired).send().await.unwrap().status(), reqwest::StatusCode::FORBIDDEN);
+
+        server.abort();
+    }
+}
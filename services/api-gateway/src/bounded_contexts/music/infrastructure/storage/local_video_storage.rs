@@ -0,0 +1,483 @@
+// Almacenamiento de vídeo en disco para desarrollo
+//
+// Cada vídeo vive en `base_path/{id}/`, con `id` el SHA-256 del original:
+// el original (`source.{ext}`), una rendición por calidad transcodificada
+// (`{quality}.mp4`) y el manifiesto (`manifest.json`). Los segmentos no se
+// guardan por separado: el manifiesto describe su offset y tamaño dentro del
+// fichero de su calidad y se leen de ahí.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::{Error, ErrorKind, Result as IoResult, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::ipfs_video_storage::{
+    VideoChunk, VideoFileMetadata, VideoFileStorage, VideoQuality, SUPPORTED_VIDEO_TYPES,
+};
+use super::video_chunks::{segment_size, ManifestChunk, VideoManifest, VideoRendition, DEFAULT_SEGMENT_SECONDS};
+use super::video_transcoding::{
+    InMemoryTranscodeJobRepository, TranscodeJobRepository, TranscodeJobStatus, VideoEncoder, VideoProbe,
+};
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+/// Video storage on the local file system, so video features work without an
+/// IPFS daemon. Nothing is announced: the only peer is this process.
+pub struct LocalVideoStorage {
+    base_path: PathBuf,
+    max_file_size: u64,
+    segment_seconds: u32,
+    // ffprobe para ancho, alto y duración; sin él salen del manifiesto
+    encoder: Option<Arc<dyn VideoEncoder>>,
+    transcode_jobs: Arc<dyn TranscodeJobRepository>,
+    manifests: RwLock<HashMap<String, VideoManifest>>,
+    probes: RwLock<HashMap<String, VideoProbe>>,
+}
+
+impl LocalVideoStorage {
+    pub fn new(base_path: impl Into<PathBuf>, max_file_size: u64) -> Self {
+        Self {
+            base_path: base_path.into(),
+            max_file_size,
+            segment_seconds: DEFAULT_SEGMENT_SECONDS,
+            encoder: None,
+            transcode_jobs: Arc::new(InMemoryTranscodeJobRepository::new()),
+            manifests: RwLock::new(HashMap::new()),
+            probes: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Duration of each segment at the nominal bitrate of its quality
+    pub fn with_segment_seconds(mut self, segment_seconds: u32) -> Self {
+        self.segment_seconds = segment_seconds.max(1);
+        self
+    }
+
+    /// Probe the originals (e.g. with ffprobe) for their resolution and duration
+    pub fn with_encoder(mut self, encoder: Arc<dyn VideoEncoder>) -> Self {
+        self.encoder = Some(encoder);
+        self
+    }
+
+    pub fn with_transcode_jobs(mut self, transcode_jobs: Arc<dyn TranscodeJobRepository>) -> Self {
+        self.transcode_jobs = transcode_jobs;
+        self
+    }
+
+    pub fn base_path(&self) -> &Path {
+        &self.base_path
+    }
+
+    /// Create `base_path` and check that files can be written to it
+    pub async fn ensure_writable(&self) -> IoResult<()> {
+        fs::create_dir_all(&self.base_path).await?;
+        let probe = self.base_path.join(format!(".write-check-{}", Uuid::new_v4().simple()));
+        fs::write(&probe, b"ok").await.map_err(|e| {
+            Error::new(e.kind(), format!("Video storage directory {} is not writable: {}", self.base_path.display(), e))
+        })?;
+        fs::remove_file(&probe).await
+    }
+
+    pub fn video_url(video_id: &str) -> String {
+        format!("local://{}", video_id)
+    }
+
+    /// Id of a `local://{id}` URL; only hex ids, so it can't leave `base_path`
+    fn extract_video_id(url: &str) -> IoResult<String> {
+        url.strip_prefix("local://")
+            .filter(|id| id.len() == 64 && id.chars().all(|c| c.is_ascii_hexdigit()))
+            .map(str::to_string)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, format!("Invalid local video URL format: {}", url)))
+    }
+
+    fn video_dir(&self, video_id: &str) -> PathBuf {
+        self.base_path.join(video_id)
+    }
+
+    /// File holding the rendition of `quality`: the original for the source quality
+    fn rendition_path(&self, video_id: &str, manifest: &VideoManifest, quality: &VideoQuality) -> PathBuf {
+        let file_name = if *quality == manifest.source_quality {
+            source_file_name(&manifest.file_name)
+        } else {
+            format!("{}.mp4", quality.as_str())
+        };
+        self.video_dir(video_id).join(file_name)
+    }
+
+    fn validate_video_file(&self, file_data: &Bytes, content_type: &str) -> IoResult<()> {
+        if file_data.len() as u64 > self.max_file_size {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                format!("Video file size {} exceeds maximum {}", file_data.len(), self.max_file_size)));
+        }
+        if !SUPPORTED_VIDEO_TYPES.contains(&content_type) {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                format!("Unsupported video format: {}", content_type)));
+        }
+        Ok(())
+    }
+
+    /// Manifest from memory or from `manifest.json`
+    pub async fn load_manifest(&self, video_id: &str) -> IoResult<VideoManifest> {
+        if let Some(manifest) = self.manifests.read().await.get(video_id) {
+            return Ok(manifest.clone());
+        }
+        let data = fs::read(self.video_dir(video_id).join(MANIFEST_FILE)).await.map_err(|e| match e.kind() {
+            ErrorKind::NotFound => Error::new(ErrorKind::NotFound, format!("Video {} not found", video_id)),
+            _ => e,
+        })?;
+        let manifest = VideoManifest::from_bytes(&data)?;
+        self.manifests.write().await.insert(video_id.to_string(), manifest.clone());
+        Ok(manifest)
+    }
+
+    async fn save_manifest(&self, video_id: &str, manifest: VideoManifest) -> IoResult<()> {
+        write_file(&self.video_dir(video_id).join(MANIFEST_FILE), &manifest.to_bytes()?).await?;
+        self.manifests.write().await.insert(video_id.to_string(), manifest);
+        Ok(())
+    }
+
+    /// Write a transcoded rendition next to the original and complete its job
+    pub async fn store_transcoded_rendition(
+        &self,
+        url: &str,
+        quality: &VideoQuality,
+        file_data: Bytes,
+    ) -> IoResult<VideoRendition> {
+        let video_id = Self::extract_video_id(url)?;
+        let mut manifest = self.load_manifest(&video_id).await?;
+        if *quality == manifest.source_quality {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                format!("{:?} is the source quality of video {}", quality, video_id)));
+        }
+
+        let rendition = describe_rendition(&file_data, quality, manifest.segment_seconds)?;
+        write_file(&self.rendition_path(&video_id, &manifest, quality), &file_data).await?;
+        manifest.set_rendition(rendition.clone());
+        self.save_manifest(&video_id, manifest).await?;
+
+        for job in self.transcode_jobs.find_by_video(&video_id).await? {
+            if job.quality == *quality && job.status != TranscodeJobStatus::Completed {
+                self.transcode_jobs.complete(job.id, &rendition).await?;
+            }
+        }
+        Ok(rendition)
+    }
+
+    /// Resolution and duration of the original, probed once per process
+    async fn probe_source(&self, video_id: &str, manifest: &VideoManifest) -> Option<VideoProbe> {
+        let encoder = self.encoder.as_ref()?;
+        if let Some(probe) = self.probes.read().await.get(video_id) {
+            return Some(probe.clone());
+        }
+        let source = self.rendition_path(video_id, manifest, &manifest.source_quality);
+        match encoder.probe(&source).await {
+            Ok(probe) => {
+                self.probes.write().await.insert(video_id.to_string(), probe.clone());
+                Some(probe)
+            }
+            Err(e) => {
+                tracing::debug!("Could not probe video {}, using its manifest: {}", video_id, e);
+                None
+            }
+        }
+    }
+
+    async fn read_chunk(&self, path: &Path, chunk: &ManifestChunk) -> IoResult<Bytes> {
+        let mut file = fs::File::open(path).await?;
+        file.seek(SeekFrom::Start(chunk.offset)).await?;
+        let mut data = vec![0u8; chunk.size as usize];
+        file.read_exact(&mut data).await.map_err(|e| match e.kind() {
+            ErrorKind::UnexpectedEof => Error::new(ErrorKind::InvalidData,
+                format!("{} is shorter than chunk {} of the manifest", path.display(), chunk.index)),
+            _ => e,
+        })?;
+        Ok(Bytes::from(data))
+    }
+}
+
+#[async_trait]
+impl VideoFileStorage for LocalVideoStorage {
+    async fn upload_video(&self, file_data: Bytes, file_name: &str, content_type: &str) -> IoResult<String> {
+        self.validate_video_file(&file_data, content_type)?;
+
+        let video_id = hex::encode(Sha256::digest(&file_data));
+        let source_quality = VideoQuality::High;
+        let mut manifest = VideoManifest::new(file_name, content_type, self.segment_seconds, source_quality.clone());
+        manifest.set_rendition(describe_rendition(&file_data, &source_quality, self.segment_seconds)?);
+
+        write_file(&self.rendition_path(&video_id, &manifest, &source_quality), &file_data).await?;
+        self.save_manifest(&video_id, manifest).await?;
+
+        // Quedan en cola como en IPFS; se completan con `store_transcoded_rendition`
+        let ladder: Vec<VideoQuality> = VideoQuality::ladder()
+            .into_iter()
+            .filter(|quality| *quality != source_quality)
+            .collect();
+        self.transcode_jobs.enqueue(&video_id, &ladder).await?;
+
+        tracing::info!("Stored video {} at {}", video_id, self.video_dir(&video_id).display());
+        Ok(Self::video_url(&video_id))
+    }
+
+    async fn download_video(&self, url: &str) -> IoResult<Bytes> {
+        let video_id = Self::extract_video_id(url)?;
+        let manifest = self.load_manifest(&video_id).await?;
+        let data = fs::read(self.rendition_path(&video_id, &manifest, &manifest.source_quality)).await?;
+        Ok(Bytes::from(data))
+    }
+
+    async fn delete_video(&self, url: &str) -> IoResult<()> {
+        let video_id = Self::extract_video_id(url)?;
+        self.manifests.write().await.remove(&video_id);
+        self.probes.write().await.remove(&video_id);
+        self.transcode_jobs.delete_by_video(&video_id).await?;
+        match fs::remove_dir_all(self.video_dir(&video_id)).await {
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+
+    async fn get_streaming_url(&self, url: &str, quality: &VideoQuality) -> IoResult<String> {
+        let video_id = Self::extract_video_id(url)?;
+        let manifest = self.load_manifest(&video_id).await?;
+        // Sin esa calidad todavía, el original
+        let quality = manifest.rendition(quality).map(|r| &r.quality).unwrap_or(&manifest.source_quality);
+        let absolute = std::path::absolute(self.rendition_path(&video_id, &manifest, quality))?;
+        Ok(format!("file://{}", absolute.display()))
+    }
+
+    async fn get_video_chunk(&self, url: &str, chunk_index: u32, quality: &VideoQuality) -> IoResult<VideoChunk> {
+        let video_id = Self::extract_video_id(url)?;
+        let manifest = self.load_manifest(&video_id).await?;
+        let rendition = manifest.rendition(quality)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound,
+                format!("Quality {:?} is not available for video {}", quality, video_id)))?;
+        let chunk = rendition.chunks.get(chunk_index as usize)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput,
+                format!("Chunk {} out of range: {:?} has {} chunks", chunk_index, quality, rendition.chunks.len())))?;
+
+        let data = self.read_chunk(&self.rendition_path(&video_id, &manifest, quality), chunk).await?;
+        Ok(VideoChunk {
+            chunk_index,
+            data,
+            quality: quality.clone(),
+            timestamp: chrono::Utc::now(),
+        })
+    }
+
+    async fn get_metadata(&self, url: &str) -> IoResult<VideoFileMetadata> {
+        let video_id = Self::extract_video_id(url)?;
+        let manifest = self.load_manifest(&video_id).await?;
+        let source = manifest.source()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("Manifest of {} has no source rendition", video_id)))?;
+        let probe = self.probe_source(&video_id, &manifest).await;
+
+        // Sin ffprobe, aproximada: segmentos a bitrate nominal
+        let duration_seconds = probe
+            .as_ref()
+            .and_then(|probe| probe.duration_seconds)
+            .map(|seconds| seconds.round() as u32)
+            .unwrap_or(source.chunk_count() * manifest.segment_seconds);
+
+        Ok(VideoFileMetadata {
+            file_size: source.total_size,
+            content_type: manifest.content_type.clone(),
+            duration_seconds: Some(duration_seconds),
+            width: probe.as_ref().map(|probe| probe.width),
+            height: probe.as_ref().map(|probe| probe.height),
+            frame_rate: None,
+            bitrate: Some((source.bitrate / 1000) as u32),
+            available_qualities: manifest.qualities(),
+            chunk_count: source.chunk_count(),
+            created_at: manifest.created_at,
+            peer_count: Some(0),
+            availability_score: Some(1.0),
+        })
+    }
+
+    async fn get_peers(&self, url: &str) -> IoResult<Vec<String>> {
+        Self::extract_video_id(url)?;
+        Ok(Vec::new())
+    }
+
+    async fn announce_to_network(&self, url: &str) -> IoResult<()> {
+        // No hay red que avisar; solo se comprueba que el vídeo existe
+        let video_id = Self::extract_video_id(url)?;
+        self.load_manifest(&video_id).await.map(|_| ())
+    }
+
+    async fn get_available_qualities(&self, url: &str) -> IoResult<Vec<VideoQuality>> {
+        let video_id = Self::extract_video_id(url)?;
+        Ok(self.load_manifest(&video_id).await?.qualities())
+    }
+
+    async fn transcode_video(&self, url: &str, target_quality: VideoQuality) -> IoResult<Uuid> {
+        let video_id = Self::extract_video_id(url)?;
+        let manifest = self.load_manifest(&video_id).await?;
+        if target_quality == manifest.source_quality {
+            return Err(Error::new(ErrorKind::InvalidInput,
+                format!("{:?} is the source quality of video {}", target_quality, video_id)));
+        }
+
+        let jobs = self.transcode_jobs.enqueue(&video_id, &[target_quality]).await?;
+        jobs.into_iter()
+            .next()
+            .map(|job| job.id)
+            .ok_or_else(|| Error::new(ErrorKind::Other, "Transcode job was not queued"))
+    }
+}
+
+/// Segments of `data` as offsets into its file; the CID of each one is its SHA-256
+fn describe_rendition(data: &Bytes, quality: &VideoQuality, segment_seconds: u32) -> IoResult<VideoRendition> {
+    if data.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidInput, "Video file is empty"));
+    }
+    let size = segment_size(quality, segment_seconds);
+    let chunks = (0..data.len())
+        .step_by(size)
+        .enumerate()
+        .map(|(index, offset)| {
+            let segment = &data[offset..(offset + size).min(data.len())];
+            ManifestChunk {
+                index: index as u32,
+                cid: hex::encode(Sha256::digest(segment)),
+                offset: offset as u64,
+                size: segment.len() as u64,
+            }
+        })
+        .collect();
+    Ok(VideoRendition {
+        quality: quality.clone(),
+        bitrate: quality.minimum_bandwidth(),
+        total_size: data.len() as u64,
+        chunks,
+    })
+}
+
+/// `source.{ext}` keeping the extension of the uploaded name when it is plain
+fn source_file_name(file_name: &str) -> String {
+    let extension = Path::new(file_name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .filter(|ext| !ext.is_empty() && ext.chars().all(|c| c.is_ascii_alphanumeric()));
+    match extension {
+        Some(ext) => format!("source.{}", ext.to_ascii_lowercase()),
+        None => "source".to_string(),
+    }
+}
+
+async fn write_file(path: &Path, data: &[u8]) -> IoResult<()> {
+    if let Some(directory) = path.parent() {
+        fs::create_dir_all(directory).await?;
+    }
+    let mut file = fs::File::create(path).await?;
+    file.write_all(data).await?;
+    file.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::sync::watch;
+
+    /// ffprobe de mentira: siempre 1920x1080 y 12,4s
+    struct FixedProbe;
+
+    #[async_trait]
+    impl VideoEncoder for FixedProbe {
+        async fn probe(&self, _input: &Path) -> IoResult<VideoProbe> {
+            Ok(VideoProbe { width: 1920, height: 1080, duration_seconds: Some(12.4) })
+        }
+
+        async fn encode(
+            &self,
+            _input: &Path,
+            _source: &VideoProbe,
+            _quality: &VideoQuality,
+            _output: &Path,
+            _progress: watch::Sender<f32>,
+        ) -> IoResult<()> {
+            Err(Error::new(ErrorKind::Unsupported, "probe only"))
+        }
+    }
+
+    fn clip() -> Bytes {
+        Bytes::from((0..700_000u32).map(|i| (i % 251) as u8).collect::<Vec<_>>())
+    }
+
+    #[tokio::test]
+    async fn files_are_laid_out_per_video_and_reloaded_from_the_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = LocalVideoStorage::new(dir.path(), 10 * 1024 * 1024).with_segment_seconds(1);
+        let url = storage.upload_video(clip(), "My Clip.MP4", "video/mp4").await.unwrap();
+        let video_id = LocalVideoStorage::extract_video_id(&url).unwrap();
+
+        let video_dir = dir.path().join(&video_id);
+        assert!(video_dir.join("source.mp4").is_file());
+        assert!(video_dir.join(MANIFEST_FILE).is_file());
+
+        let low = Bytes::from(vec![3u8; 200_000]);
+        storage.store_transcoded_rendition(&url, &VideoQuality::Low, low.clone()).await.unwrap();
+        assert!(video_dir.join("low.mp4").is_file());
+        let jobs = storage.transcode_jobs.find_by_video(&video_id).await.unwrap();
+        let low_job = jobs.iter().find(|job| job.quality == VideoQuality::Low).unwrap();
+        assert_eq!(low_job.status, TranscodeJobStatus::Completed);
+
+        // Otra instancia sobre el mismo directorio lo lee todo del disco
+        let reopened = LocalVideoStorage::new(dir.path(), 10 * 1024 * 1024);
+        let qualities = reopened.get_available_qualities(&url).await.unwrap();
+        assert!(qualities.contains(&VideoQuality::High) && qualities.contains(&VideoQuality::Low));
+        let last = reopened.get_video_chunk(&url, 1, &VideoQuality::Low).await.unwrap();
+        assert_eq!(last.data, low.slice(125_000..));
+        assert!(reopened.get_streaming_url(&url, &VideoQuality::Low).await.unwrap().ends_with("/low.mp4"));
+        assert!(reopened.get_streaming_url(&url, &VideoQuality::Ultra).await.unwrap().ends_with("/source.mp4"));
+    }
+
+    #[tokio::test]
+    async fn metadata_uses_the_probe_when_available() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = LocalVideoStorage::new(dir.path(), 10 * 1024 * 1024).with_segment_seconds(1);
+        let url = plain.upload_video(clip(), "clip.mp4", "video/mp4").await.unwrap();
+
+        let metadata = plain.get_metadata(&url).await.unwrap();
+        assert_eq!((metadata.width, metadata.height), (None, None));
+        assert_eq!(metadata.duration_seconds, Some(2));
+
+        let probed = LocalVideoStorage::new(dir.path(), 10 * 1024 * 1024).with_encoder(Arc::new(FixedProbe));
+        let metadata = probed.get_metadata(&url).await.unwrap();
+        assert_eq!((metadata.width, metadata.height), (Some(1920), Some(1080)));
+        assert_eq!(metadata.duration_seconds, Some(12));
+    }
+
+    #[tokio::test]
+    async fn urls_outside_the_base_path_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let storage = LocalVideoStorage::new(dir.path(), 1024);
+
+        for url in ["local://../etc", "local:///etc/passwd", "http://localhost:5001/ipfs/Qm"] {
+            let error = storage.download_video(url).await.unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidInput, "{}", url);
+        }
+    }
+
+    #[tokio::test]
+    async fn ensure_writable_creates_the_directory_and_rejects_read_only_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let nested = dir.path().join("videos").join("dev");
+        LocalVideoStorage::new(&nested, 1024).ensure_writable().await.unwrap();
+        assert!(nested.is_dir());
+        assert_eq!(std::fs::read_dir(&nested).unwrap().count(), 0);
+
+        // Un fichero donde debería ir el directorio
+        let blocked = dir.path().join("blocked");
+        std::fs::write(&blocked, b"not a directory").unwrap();
+        assert!(LocalVideoStorage::new(blocked.join("videos"), 1024).ensure_writable().await.is_err());
+    }
+}
@@ -2,21 +2,42 @@ pub mod file_storage;
 pub mod ipfs_storage;
 pub mod local_storage;
 pub mod ipfs_video_storage;
+pub mod local_video_storage;
+pub mod video_chunks;
+pub mod video_peers;
+pub mod video_transcoding;
 pub mod audio_metadata_extractor;
 pub mod audio_transcoder;
+pub mod waveform_generator;
 pub mod cdn_storage;
+pub mod stems_urls;
+
+#[cfg(test)]
+mod video_storage_conformance;
 
 pub use file_storage::*;
 pub use ipfs_storage::*;
 pub use local_storage::*;
 pub use ipfs_video_storage::*;
+pub use local_video_storage::LocalVideoStorage;
+pub use video_chunks::{ChunkStore, IpfsHttpChunkStore, InMemoryChunkStore, VideoManifest, VideoRendition};
+pub use video_peers::{
+    ContentAvailability, DhtClient, DiscoveredPeer, InMemoryDhtClient, KuboDhtClient, VideoPeerDiscovery,
+};
+pub use video_transcoding::{
+    FfmpegVideoEncoder, InMemoryTranscodeJobRepository, TranscodeJobRepository, TranscodeJobStatus,
+    VideoEncoder, VideoTranscodeJob, VideoTranscodingWorker,
+};
 pub use audio_metadata_extractor::{AudioMetadataExtractor, AudioMetadata};
 pub use audio_transcoder::{AudioTranscoder, TranscodeConfig};
+pub use waveform_generator::{AmplitudeEnvelope, Waveform, WaveformGenerator};
 pub use cdn_storage::CDNAudioStorage;
+pub use stems_urls::{StemsUrlSigner, SignedStemsUrl};
 
 use async_trait::async_trait;
 use std::io::Result as IoResult;
 use bytes::Bytes;
+use std::sync::Arc;
 
 /// Unified storage interface for audio files
 #[async_trait]
@@ -60,10 +81,7 @@ pub struct AudioFileMetadata {
 #[derive(Debug, Clone)]
 pub enum StorageConfig {
     /// Local storage for development
-    Local {
-        base_path: String,
-        max_file_size: u64,
-    },
+    Local(LocalStorageConfig),
     /// Distributed IPFS for production P2P
     DistributedIPFS {
         local_node_url: String,
@@ -83,12 +101,17 @@ pub enum StorageConfig {
 /// Revolutionary P2P-first approach
 pub fn create_storage(config: StorageConfig) -> Box<dyn AudioFileStorage> {
     match config {
-        StorageConfig::Local { base_path, max_file_size } => {
+        StorageConfig::Local(config) => {
             println!("📁 Initializing Local Storage");
-            println!("   Base Path: {}", base_path);
-            println!("   Max File Size: {} MB", max_file_size);
+            println!("   Base Path: {}", config.base_path);
+            println!("   Max File Size: {} MB", config.max_file_size);
             
-            Box::new(LocalAudioStorage::new(base_path, max_file_size))
+            let storage = LocalAudioStorage::from_config(config);
+            // El servidor de ficheros necesita un runtime de tokio
+            if tokio::runtime::Handle::try_current().is_ok() {
+                storage.start_file_server();
+            }
+            Box::new(storage)
         },
         StorageConfig::DistributedIPFS { 
             local_node_url, 
@@ -124,9 +147,11 @@ pub fn create_storage(config: StorageConfig) -> Box<dyn AudioFileStorage> {
 /// Create storage instance asynchronously
 pub async fn create_storage_async(config: StorageConfig) -> std::io::Result<Box<dyn AudioFileStorage>> {
     match config {
-        StorageConfig::Local { base_path, max_file_size } => {
-            println!("🏠 Initializing Local Storage at: {}", base_path);
-            Ok(Box::new(LocalAudioStorage::new(base_path, max_file_size)))
+        StorageConfig::Local(config) => {
+            println!("🏠 Initializing Local Storage at: {}", config.base_path);
+            let storage = LocalAudioStorage::from_config(config);
+            storage.start_file_server();
+            Ok(Box::new(storage))
         }
         StorageConfig::DistributedIPFS { 
             local_node_url, 
@@ -157,6 +182,79 @@ pub async fn create_storage_async(config: StorageConfig) -> std::io::Result<Box<
     }
 }
 
+/// Video storage configuration
+#[derive(Debug, Clone)]
+pub enum P2PStorageConfig {
+    /// Files under `base_path`, for development without an IPFS daemon
+    Local {
+        base_path: String,
+        max_file_size: u64,
+    },
+    /// Chunks and manifests on IPFS, providers on its DHT
+    DistributedIPFS {
+        local_node_url: String,
+        peer_nodes: Vec<String>,
+        max_file_size: u64,
+        enable_federation: bool,
+        enable_content_discovery: bool,
+    },
+}
+
+/// Create video storage instance based on configuration
+pub fn create_p2p_storage(config: P2PStorageConfig) -> Box<dyn VideoFileStorage> {
+    match config {
+        P2PStorageConfig::Local { base_path, max_file_size } => {
+            println!("📁 Initializing Local Video Storage");
+            println!("   Base Path: {}", base_path);
+            println!("   Max File Size: {} MB", max_file_size / 1024 / 1024);
+
+            Box::new(LocalVideoStorage::new(base_path, max_file_size).with_encoder(Arc::new(FfmpegVideoEncoder::new())))
+        }
+        P2PStorageConfig::DistributedIPFS {
+            local_node_url,
+            peer_nodes,
+            max_file_size,
+            enable_federation,
+            enable_content_discovery,
+        } => Box::new(IPFSVideoStorage::new_distributed(
+            local_node_url,
+            peer_nodes,
+            max_file_size,
+            enable_federation,
+            enable_content_discovery,
+        )),
+    }
+}
+
+/// Create video storage instance asynchronously; a local `base_path` must be writable
+pub async fn create_p2p_storage_async(config: P2PStorageConfig) -> std::io::Result<Box<dyn VideoFileStorage>> {
+    match config {
+        P2PStorageConfig::Local { base_path, max_file_size } => {
+            println!("🏠 Initializing Local Video Storage at: {}", base_path);
+            let storage = LocalVideoStorage::new(base_path, max_file_size).with_encoder(Arc::new(FfmpegVideoEncoder::new()));
+            storage.ensure_writable().await?;
+            Ok(Box::new(storage))
+        }
+        P2PStorageConfig::DistributedIPFS {
+            local_node_url,
+            peer_nodes,
+            max_file_size,
+            enable_federation,
+            enable_content_discovery,
+        } => {
+            let ipfs_storage = IPFSVideoStorage::new_distributed_async(
+                local_node_url,
+                peer_nodes,
+                max_file_size,
+                enable_federation,
+                enable_content_discovery,
+            ).await?;
+
+            Ok(Box::new(ipfs_storage))
+        }
+    }
+}
+
 /// Get recommended storage configuration based on environment
 /// Revolutionary P2P-first approach
 pub fn get_recommended_storage_config() -> StorageConfig {
@@ -190,9 +288,9 @@ pub fn get_recommended_storage_config() -> StorageConfig {
         }
     } else {
         println!("🏠 Development mode: Using local storage");
-        StorageConfig::Local {
-            base_path: "./storage/audio".to_string(),
-            max_file_size: 100 * 1024 * 1024, // 100MB for development
-        }
+        StorageConfig::Local(
+            LocalStorageConfig::new("./storage/audio", 100 * 1024 * 1024) // 100MB for development
+                .with_env(),
+        )
     }
 } 
\ No newline at end of file
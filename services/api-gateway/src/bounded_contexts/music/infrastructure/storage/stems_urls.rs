@@ -0,0 +1,107 @@
+//! Signed, time-limited URLs to stems archives on the IPFS gateway
+//!
+//! The gateway (or the proxy in front of it) checks `expires` and
+//! `signature = HMAC-SHA256(key, "{cid}:{licensee}:{expires}")` before serving
+//! the content, so a link only works for the licensee it was issued to and
+//! never outlives the license.
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use hmac_sha256::HMAC;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::bounded_contexts::music::domain::value_objects::IpfsHash;
+
+const DEFAULT_IPFS_GATEWAY_URL: &str = "https://ipfs.io";
+const DEFAULT_STEMS_URL_TTL_SECS: i64 = 900;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SignedStemsUrl {
+    pub url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+pub struct StemsUrlSigner {
+    gateway_url: String,
+    signing_key: Vec<u8>,
+    ttl: Duration,
+}
+
+impl StemsUrlSigner {
+    pub fn new(gateway_url: impl Into<String>, signing_key: Vec<u8>, ttl: Duration) -> Self {
+        Self {
+            gateway_url: gateway_url.into().trim_end_matches('/').to_string(),
+            signing_key,
+            ttl,
+        }
+    }
+
+    /// `IPFS_GATEWAY_URL`, `STEMS_URL_SIGNING_SECRET` (random per process if
+    /// not set) and `STEMS_URL_TTL_SECS`
+    pub fn from_env() -> Self {
+        let signing_key = match std::env::var("STEMS_URL_SIGNING_SECRET") {
+            Ok(secret) => secret.into_bytes(),
+            Err(_) => rand::random::<[u8; 32]>().to_vec(),
+        };
+        let ttl = std::env::var("STEMS_URL_TTL_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_STEMS_URL_TTL_SECS);
+        Self::new(
+            std::env::var("IPFS_GATEWAY_URL").unwrap_or_else(|_| DEFAULT_IPFS_GATEWAY_URL.to_string()),
+            signing_key,
+            Duration::seconds(ttl),
+        )
+    }
+
+    /// URL valid for the TTL, but never past `valid_until` (the license expiry)
+    pub fn sign(&self, stems: &IpfsHash, licensee_id: Uuid, valid_until: DateTime<Utc>, now: DateTime<Utc>) -> SignedStemsUrl {
+        let expires = (now + self.ttl).min(valid_until).timestamp();
+        SignedStemsUrl {
+            url: format!(
+                "{}/ipfs/{}?licensee={}&expires={}&signature={}",
+                self.gateway_url,
+                stems.value(),
+                licensee_id,
+                expires,
+                self.signature(stems.value(), licensee_id, expires)
+            ),
+            expires_at: Utc.timestamp_opt(expires, 0).single().unwrap_or(now),
+        }
+    }
+
+    pub fn verify(&self, cid: &str, licensee_id: Uuid, expires: i64, signature: &str, now: DateTime<Utc>) -> bool {
+        expires > now.timestamp() && self.signature(cid, licensee_id, expires) == signature
+    }
+
+    fn signature(&self, cid: &str, licensee_id: Uuid, expires: i64) -> String {
+        hex::encode(HMAC::mac(format!("{}:{}:{}", cid, licensee_id, expires).as_bytes(), &self.signing_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn signed_url_expires_with_the_license() {
+        let signer = StemsUrlSigner::new("https://gateway.test/", b"secret".to_vec(), Duration::minutes(15));
+        let stems = IpfsHash::new("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG".to_string()).unwrap();
+        let licensee = Uuid::new_v4();
+        let now = Utc.with_ymd_and_hms(2026, 10, 15, 12, 0, 0).unwrap();
+
+        let signed = signer.sign(&stems, licensee, now + Duration::days(30), now);
+        assert_eq!(signed.expires_at, now + Duration::minutes(15));
+        assert!(signed.url.starts_with("https://gateway.test/ipfs/QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG?"));
+
+        // La licencia vence antes que el TTL
+        let closing = signer.sign(&stems, licensee, now + Duration::minutes(5), now);
+        assert_eq!(closing.expires_at, now + Duration::minutes(5));
+
+        let signature = signed.url.rsplit("signature=").next().unwrap();
+        let expires = signed.expires_at.timestamp();
+        assert!(signer.verify(stems.value(), licensee, expires, signature, now));
+        assert!(!signer.verify(stems.value(), Uuid::new_v4(), expires, signature, now));
+        assert!(!signer.verify(stems.value(), licensee, expires, signature, signed.expires_at));
+    }
+}
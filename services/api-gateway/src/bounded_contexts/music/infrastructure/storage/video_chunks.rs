@@ -0,0 +1,386 @@
+// Troceado de vídeo para streaming P2P
+//
+// Cada calidad se parte en segmentos de duración fija a su bitrate nominal
+// (`VideoQuality::minimum_bandwidth`) y cada segmento se guarda como un bloque
+// propio en IPFS. El manifiesto describe los segmentos (CID, offset y tamaño)
+// y se guarda también en IPFS: su CID es el hash del vídeo.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::io::{Error, ErrorKind, Result as IoResult};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use uuid::Uuid;
+
+use super::ipfs_video_storage::VideoQuality;
+
+/// Duración por defecto de cada segmento
+pub const DEFAULT_SEGMENT_SECONDS: u32 = 4;
+
+/// Capacidad por defecto de la caché de segmentos (64MB)
+pub const DEFAULT_CHUNK_CACHE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Content-addressed block store the chunks and manifests live in
+#[async_trait]
+pub trait ChunkStore: Send + Sync {
+    /// Store a block and return its CID
+    async fn put(&self, data: Bytes) -> IoResult<String>;
+
+    /// Fetch a block by CID
+    async fn get(&self, cid: &str) -> IoResult<Bytes>;
+
+    /// Unpin a block so the node can garbage-collect it
+    async fn remove(&self, cid: &str) -> IoResult<()>;
+}
+
+/// Chunk store backed by the HTTP RPC API of an IPFS (Kubo) node
+pub struct IpfsHttpChunkStore {
+    api_url: String,
+    client: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct IpfsAddResponse {
+    #[serde(rename = "Hash")]
+    hash: String,
+}
+
+impl IpfsHttpChunkStore {
+    pub fn new(api_url: impl Into<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap_or_default();
+        Self { api_url: api_url.into().trim_end_matches('/').to_string(), client }
+    }
+
+    async fn call(&self, path: &str, body: Option<(String, Vec<u8>)>) -> IoResult<Bytes> {
+        let mut request = self.client.post(format!("{}/api/v0/{}", self.api_url, path));
+        if let Some((content_type, body)) = body {
+            request = request.header(reqwest::header::CONTENT_TYPE, content_type).body(body);
+        }
+        let response = request.send().await.map_err(|e| Error::new(ErrorKind::Other, e))?;
+        let status = response.status();
+        let body = response.bytes().await.map_err(|e| Error::new(ErrorKind::Other, e))?;
+        if !status.is_success() {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("IPFS {} failed with {}: {}", path, status, String::from_utf8_lossy(&body)),
+            ));
+        }
+        Ok(body)
+    }
+}
+
+#[async_trait]
+impl ChunkStore for IpfsHttpChunkStore {
+    async fn put(&self, data: Bytes) -> IoResult<String> {
+        // /add espera multipart; un único fichero, así que se monta a mano
+        let boundary = format!("vibestream-{}", Uuid::new_v4().simple());
+        let mut body = Vec::with_capacity(data.len() + 256);
+        body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"chunk\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+                boundary
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(&data);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+        let response = self
+            .call(
+                "add?pin=true&cid-version=1&raw-leaves=true",
+                Some((format!("multipart/form-data; boundary={}", boundary), body)),
+            )
+            .await?;
+        let added: IpfsAddResponse =
+            serde_json::from_slice(&response).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        Ok(added.hash)
+    }
+
+    async fn get(&self, cid: &str) -> IoResult<Bytes> {
+        self.call(&format!("cat?arg={}", cid), None).await
+    }
+
+    async fn remove(&self, cid: &str) -> IoResult<()> {
+        self.call(&format!("pin/rm?arg={}", cid), None).await.map(|_| ())
+    }
+}
+
+/// In-process chunk store for development and tests. CIDs are the SHA-256 of
+/// the block, so the same content always gets the same CID.
+#[derive(Default)]
+pub struct InMemoryChunkStore {
+    blocks: Mutex<HashMap<String, Bytes>>,
+    gets: AtomicUsize,
+}
+
+impl InMemoryChunkStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of `get` calls served, to check cache hits
+    pub fn get_count(&self) -> usize {
+        self.gets.load(Ordering::SeqCst)
+    }
+
+    pub fn len(&self) -> usize {
+        self.blocks.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[async_trait]
+impl ChunkStore for InMemoryChunkStore {
+    async fn put(&self, data: Bytes) -> IoResult<String> {
+        let cid = hex::encode(Sha256::digest(&data));
+        self.blocks.lock().unwrap().insert(cid.clone(), data);
+        Ok(cid)
+    }
+
+    async fn get(&self, cid: &str) -> IoResult<Bytes> {
+        self.gets.fetch_add(1, Ordering::SeqCst);
+        self.blocks
+            .lock()
+            .unwrap()
+            .get(cid)
+            .cloned()
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("Block {} not found", cid)))
+    }
+
+    async fn remove(&self, cid: &str) -> IoResult<()> {
+        self.blocks.lock().unwrap().remove(cid);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestChunk {
+    pub index: u32,
+    pub cid: String,
+    pub offset: u64,
+    pub size: u64,
+}
+
+/// Segments of one quality
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VideoRendition {
+    pub quality: VideoQuality,
+    /// Bitrate nominal en bits/s con el que se calculó el tamaño de segmento
+    pub bitrate: u64,
+    pub total_size: u64,
+    pub chunks: Vec<ManifestChunk>,
+}
+
+impl VideoRendition {
+    pub fn chunk_count(&self) -> u32 {
+        self.chunks.len() as u32
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VideoManifest {
+    pub version: u32,
+    pub file_name: String,
+    pub content_type: String,
+    pub segment_seconds: u32,
+    /// Calidad del fichero subido; el resto llegan de la transcodificación
+    pub source_quality: VideoQuality,
+    pub renditions: Vec<VideoRendition>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl VideoManifest {
+    pub const VERSION: u32 = 1;
+
+    pub fn new(file_name: &str, content_type: &str, segment_seconds: u32, source_quality: VideoQuality) -> Self {
+        Self {
+            version: Self::VERSION,
+            file_name: file_name.to_string(),
+            content_type: content_type.to_string(),
+            segment_seconds,
+            source_quality,
+            renditions: Vec::new(),
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    pub fn rendition(&self, quality: &VideoQuality) -> Option<&VideoRendition> {
+        self.renditions.iter().find(|r| r.quality == *quality)
+    }
+
+    pub fn source(&self) -> Option<&VideoRendition> {
+        self.rendition(&self.source_quality)
+    }
+
+    pub fn qualities(&self) -> Vec<VideoQuality> {
+        self.renditions.iter().map(|r| r.quality.clone()).collect()
+    }
+
+    /// Add or replace the rendition of a quality
+    pub fn set_rendition(&mut self, rendition: VideoRendition) {
+        self.renditions.retain(|r| r.quality != rendition.quality);
+        self.renditions.push(rendition);
+    }
+
+    pub fn to_bytes(&self) -> IoResult<Bytes> {
+        serde_json::to_vec(self)
+            .map(Bytes::from)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, e))
+    }
+
+    pub fn from_bytes(data: &[u8]) -> IoResult<Self> {
+        let manifest: Self = serde_json::from_slice(data)
+            .map_err(|e| Error::new(ErrorKind::InvalidData, format!("Invalid video manifest: {}", e)))?;
+        if manifest.version != Self::VERSION {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Unsupported video manifest version {}", manifest.version),
+            ));
+        }
+        Ok(manifest)
+    }
+}
+
+/// Bytes in one segment of `segment_seconds` at the nominal bitrate of `quality`
+pub fn segment_size(quality: &VideoQuality, segment_seconds: u32) -> usize {
+    (quality.minimum_bandwidth() / 8 * segment_seconds.max(1) as u64) as usize
+}
+
+/// Split `data` into segments, store each one and describe them
+pub async fn store_rendition(
+    store: &dyn ChunkStore,
+    data: &Bytes,
+    quality: &VideoQuality,
+    segment_seconds: u32,
+) -> IoResult<VideoRendition> {
+    if data.is_empty() {
+        return Err(Error::new(ErrorKind::InvalidInput, "Video file is empty"));
+    }
+    let size = segment_size(quality, segment_seconds);
+    let mut chunks = Vec::with_capacity(data.len().div_ceil(size));
+    for (index, offset) in (0..data.len()).step_by(size).enumerate() {
+        let segment = data.slice(offset..(offset + size).min(data.len()));
+        let segment_len = segment.len() as u64;
+        let cid = store.put(segment).await?;
+        chunks.push(ManifestChunk { index: index as u32, cid, offset: offset as u64, size: segment_len });
+    }
+    Ok(VideoRendition {
+        quality: quality.clone(),
+        bitrate: quality.minimum_bandwidth(),
+        total_size: data.len() as u64,
+        chunks,
+    })
+}
+
+/// LRU cache of segments by CID, bounded by total bytes
+pub struct ChunkCache {
+    capacity_bytes: usize,
+    used_bytes: usize,
+    entries: HashMap<String, Bytes>,
+    // Del menos al más reciente
+    order: VecDeque<String>,
+}
+
+impl ChunkCache {
+    pub fn new(capacity_bytes: usize) -> Self {
+        Self { capacity_bytes, used_bytes: 0, entries: HashMap::new(), order: VecDeque::new() }
+    }
+
+    pub fn get(&mut self, cid: &str) -> Option<Bytes> {
+        let data = self.entries.get(cid)?.clone();
+        self.touch(cid);
+        Some(data)
+    }
+
+    pub fn insert(&mut self, cid: String, data: Bytes) {
+        // Un segmento mayor que la caché no se guarda
+        if data.len() > self.capacity_bytes {
+            return;
+        }
+        if let Some(previous) = self.entries.insert(cid.clone(), data.clone()) {
+            self.used_bytes -= previous.len();
+            self.touch(&cid);
+        } else {
+            self.order.push_back(cid);
+        }
+        self.used_bytes += data.len();
+        while self.used_bytes > self.capacity_bytes {
+            let Some(oldest) = self.order.pop_front() else { break };
+            if let Some(evicted) = self.entries.remove(&oldest) {
+                self.used_bytes -= evicted.len();
+            }
+        }
+    }
+
+    pub fn remove(&mut self, cid: &str) {
+        if let Some(removed) = self.entries.remove(cid) {
+            self.used_bytes -= removed.len();
+            self.order.retain(|c| c != cid);
+        }
+    }
+
+    pub fn contains(&self, cid: &str) -> bool {
+        self.entries.contains_key(cid)
+    }
+
+    pub fn used_bytes(&self) -> usize {
+        self.used_bytes
+    }
+
+    fn touch(&mut self, cid: &str) {
+        if let Some(position) = self.order.iter().position(|c| c == cid) {
+            if let Some(cid) = self.order.remove(position) {
+                self.order.push_back(cid);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_evicts_least_recently_used_chunks() {
+        let mut cache = ChunkCache::new(10);
+        cache.insert("a".to_string(), Bytes::from(vec![0u8; 4]));
+        cache.insert("b".to_string(), Bytes::from(vec![1u8; 4]));
+        assert!(cache.get("a").is_some());
+
+        cache.insert("c".to_string(), Bytes::from(vec![2u8; 4]));
+        assert!(cache.contains("a"));
+        assert!(!cache.contains("b"));
+        assert!(cache.contains("c"));
+        assert_eq!(cache.used_bytes(), 8);
+
+        // Mayor que la caché: no se guarda ni expulsa nada
+        cache.insert("d".to_string(), Bytes::from(vec![3u8; 11]));
+        assert!(!cache.contains("d"));
+        assert_eq!(cache.used_bytes(), 8);
+    }
+
+    #[tokio::test]
+    async fn renditions_are_split_into_fixed_size_segments() {
+        let store = InMemoryChunkStore::new();
+        let data = Bytes::from((0..300_001u32).map(|i| (i % 251) as u8).collect::<Vec<_>>());
+
+        // Low = 1 Mbps: 125.000 bytes por segundo
+        let rendition = store_rendition(&store, &data, &VideoQuality::Low, 1).await.unwrap();
+        let sizes: Vec<u64> = rendition.chunks.iter().map(|c| c.size).collect();
+        assert_eq!(sizes, vec![125_000, 125_000, 50_001]);
+        assert_eq!(rendition.chunks[2].offset, 250_000);
+        assert_eq!(rendition.total_size, 300_001);
+
+        assert!(store_rendition(&store, &Bytes::new(), &VideoQuality::Low, 1).await.is_err());
+    }
+}
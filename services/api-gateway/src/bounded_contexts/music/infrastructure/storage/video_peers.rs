@@ -0,0 +1,596 @@
+// Descubrimiento de peers de la red P2P de vídeo
+//
+// Los proveedores de cada vídeo salen de los registros de proveedor del DHT de
+// IPFS para el manifiesto y cada segmento. La tabla de peers guarda por
+// contenido quién lo tiene, cuántos segmentos y cuándo se le vio por última
+// vez; una tarea en segundo plano la refresca y expulsa los peers que dejan de
+// anunciarse. La puntuación de disponibilidad combina las réplicas efectivas
+// con la latencia medida a los proveedores.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::io::{Error, ErrorKind, Result as IoResult};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::RwLock;
+
+/// Proveedores pedidos al DHT por CID
+pub const DEFAULT_PROVIDERS_PER_CID: usize = 20;
+
+/// Un peer que no se anuncia en este tiempo sale de la tabla
+pub const DEFAULT_PEER_MAX_AGE_MINUTES: i64 = 30;
+
+/// Réplicas completas a partir de las que la parte de proveedores puntúa 1.0
+pub const TARGET_REPLICAS: f64 = 5.0;
+
+/// Muestras de latencia que se guardan por peer
+const LATENCY_SAMPLES_PER_PEER: usize = 10;
+
+/// Latencias de referencia: por debajo puntúa 1.0, por encima 0.0
+const FAST_RETRIEVAL_MS: f64 = 100.0;
+const SLOW_RETRIEVAL_MS: f64 = 2000.0;
+
+/// A peer that announced itself as provider of a CID
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProviderRecord {
+    pub peer_id: String,
+    pub addrs: Vec<String>,
+}
+
+/// Provider records and peer reachability on the IPFS DHT
+#[async_trait]
+pub trait DhtClient: Send + Sync {
+    async fn find_providers(&self, cid: &str, limit: usize) -> IoResult<Vec<ProviderRecord>>;
+
+    /// Publish a provider record for a CID held by the local node
+    async fn provide(&self, cid: &str) -> IoResult<()>;
+
+    /// Round-trip time to a peer
+    async fn ping(&self, peer_id: &str) -> IoResult<Duration>;
+}
+
+/// DHT client over the HTTP RPC API of an IPFS (Kubo) node
+pub struct KuboDhtClient {
+    api_url: String,
+    client: reqwest::Client,
+}
+
+#[derive(Deserialize)]
+struct KuboRoutingEvent {
+    #[serde(rename = "Type")]
+    kind: i32,
+    #[serde(rename = "Responses", default)]
+    responses: Option<Vec<KuboPeerInfo>>,
+}
+
+#[derive(Deserialize)]
+struct KuboPeerInfo {
+    #[serde(rename = "ID")]
+    id: String,
+    #[serde(rename = "Addrs", default)]
+    addrs: Option<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct KuboPingResult {
+    #[serde(rename = "Success")]
+    success: bool,
+    /// Nanosegundos
+    #[serde(rename = "Time")]
+    time: u64,
+}
+
+/// Tipo de evento de routing de Kubo con los proveedores
+const KUBO_PROVIDER_EVENT: i32 = 4;
+
+impl KuboDhtClient {
+    pub fn new(api_url: impl Into<String>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap_or_default();
+        Self { api_url: api_url.into().trim_end_matches('/').to_string(), client }
+    }
+
+    /// Las respuestas de routing y ping son JSON por líneas
+    async fn call_lines(&self, path: &str) -> IoResult<Vec<String>> {
+        let response = self
+            .client
+            .post(format!("{}/api/v0/{}", self.api_url, path))
+            .send()
+            .await
+            .map_err(|e| Error::new(ErrorKind::Other, e))?;
+        let status = response.status();
+        let body = response.text().await.map_err(|e| Error::new(ErrorKind::Other, e))?;
+        if !status.is_success() {
+            return Err(Error::new(ErrorKind::Other, format!("IPFS {} failed with {}: {}", path, status, body)));
+        }
+        Ok(body.lines().filter(|line| !line.trim().is_empty()).map(str::to_string).collect())
+    }
+}
+
+#[async_trait]
+impl DhtClient for KuboDhtClient {
+    async fn find_providers(&self, cid: &str, limit: usize) -> IoResult<Vec<ProviderRecord>> {
+        let lines = self.call_lines(&format!("routing/findprovs?arg={}&num-providers={}", cid, limit)).await?;
+        let mut providers = Vec::new();
+        for line in lines {
+            let event: KuboRoutingEvent =
+                serde_json::from_str(&line).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+            if event.kind != KUBO_PROVIDER_EVENT {
+                continue;
+            }
+            for peer in event.responses.unwrap_or_default() {
+                if !providers.iter().any(|p: &ProviderRecord| p.peer_id == peer.id) {
+                    providers.push(ProviderRecord { peer_id: peer.id, addrs: peer.addrs.unwrap_or_default() });
+                }
+            }
+        }
+        Ok(providers)
+    }
+
+    async fn provide(&self, cid: &str) -> IoResult<()> {
+        self.call_lines(&format!("routing/provide?arg={}", cid)).await.map(|_| ())
+    }
+
+    async fn ping(&self, peer_id: &str) -> IoResult<Duration> {
+        let lines = self.call_lines(&format!("ping?arg={}&count=3", peer_id)).await?;
+        let times: Vec<u64> = lines
+            .iter()
+            .filter_map(|line| serde_json::from_str::<KuboPingResult>(line).ok())
+            .filter(|result| result.success && result.time > 0)
+            .map(|result| result.time)
+            .collect();
+        if times.is_empty() {
+            return Err(Error::new(ErrorKind::TimedOut, format!("Peer {} did not answer", peer_id)));
+        }
+        Ok(Duration::from_nanos(times.iter().sum::<u64>() / times.len() as u64))
+    }
+}
+
+/// In-process DHT for development and tests
+pub struct InMemoryDhtClient {
+    local_peer_id: String,
+    providers: Mutex<HashMap<String, Vec<ProviderRecord>>>,
+    latencies: Mutex<HashMap<String, Duration>>,
+}
+
+impl InMemoryDhtClient {
+    pub fn new(local_peer_id: impl Into<String>) -> Self {
+        let local_peer_id = local_peer_id.into();
+        let latencies = HashMap::from([(local_peer_id.clone(), Duration::from_millis(1))]);
+        Self { local_peer_id, providers: Mutex::new(HashMap::new()), latencies: Mutex::new(latencies) }
+    }
+
+    pub fn add_provider(&self, cid: &str, peer_id: &str, addrs: Vec<String>) {
+        let mut providers = self.providers.lock().unwrap();
+        let records = providers.entry(cid.to_string()).or_default();
+        records.retain(|record| record.peer_id != peer_id);
+        records.push(ProviderRecord { peer_id: peer_id.to_string(), addrs });
+    }
+
+    /// Forget every provider record of a peer, as if it went offline
+    pub fn remove_peer(&self, peer_id: &str) {
+        for records in self.providers.lock().unwrap().values_mut() {
+            records.retain(|record| record.peer_id != peer_id);
+        }
+    }
+
+    /// Peers without a latency don't answer pings
+    pub fn set_latency(&self, peer_id: &str, latency: Duration) {
+        self.latencies.lock().unwrap().insert(peer_id.to_string(), latency);
+    }
+
+    pub fn providers_of(&self, cid: &str) -> Vec<ProviderRecord> {
+        self.providers.lock().unwrap().get(cid).cloned().unwrap_or_default()
+    }
+}
+
+#[async_trait]
+impl DhtClient for InMemoryDhtClient {
+    async fn find_providers(&self, cid: &str, limit: usize) -> IoResult<Vec<ProviderRecord>> {
+        Ok(self.providers_of(cid).into_iter().take(limit).collect())
+    }
+
+    async fn provide(&self, cid: &str) -> IoResult<()> {
+        let local_peer_id = self.local_peer_id.clone();
+        self.add_provider(cid, &local_peer_id, Vec::new());
+        Ok(())
+    }
+
+    async fn ping(&self, peer_id: &str) -> IoResult<Duration> {
+        self.latencies
+            .lock()
+            .unwrap()
+            .get(peer_id)
+            .copied()
+            .ok_or_else(|| Error::new(ErrorKind::TimedOut, format!("Peer {} did not answer", peer_id)))
+    }
+}
+
+/// A provider of a video in the peer table
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiscoveredPeer {
+    pub peer_id: String,
+    pub addrs: Vec<String>,
+    /// Segmentos (y manifiesto) del vídeo que anuncia
+    pub chunks_held: usize,
+    pub last_seen: DateTime<Utc>,
+    pub median_latency_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ContentAvailability {
+    pub provider_count: u32,
+    /// Copias completas equivalentes: la suma de la fracción que tiene cada peer
+    pub replicas: f64,
+    /// 0.0-1.0
+    pub availability_score: f32,
+}
+
+#[derive(Debug, Clone)]
+struct PeerEntry {
+    addrs: Vec<String>,
+    chunks_held: usize,
+    last_seen: DateTime<Utc>,
+    latency_samples_ms: VecDeque<u64>,
+}
+
+#[derive(Debug, Default)]
+struct ContentPeers {
+    cids: Vec<String>,
+    peers: HashMap<String, PeerEntry>,
+}
+
+fn median(samples: &mut [u64]) -> Option<u64> {
+    if samples.is_empty() {
+        return None;
+    }
+    samples.sort_unstable();
+    Some(samples[samples.len() / 2])
+}
+
+/// Score from effective replicas and the median retrieval latency. With no
+/// latency samples the latency half counts as neutral.
+pub fn availability_score(replicas: f64, median_latency_ms: Option<u64>) -> f32 {
+    if replicas <= 0.0 {
+        return 0.0;
+    }
+    let provider_score = (replicas / TARGET_REPLICAS).min(1.0);
+    let latency_score = match median_latency_ms {
+        Some(latency) => {
+            let latency = latency as f64;
+            (1.0 - (latency - FAST_RETRIEVAL_MS) / (SLOW_RETRIEVAL_MS - FAST_RETRIEVAL_MS)).clamp(0.0, 1.0)
+        }
+        None => 0.5,
+    };
+    (0.7 * provider_score + 0.3 * latency_score) as f32
+}
+
+/// Providers per video, by content hash (the manifest CID)
+#[derive(Debug, Default)]
+pub struct PeerTable {
+    contents: HashMap<String, ContentPeers>,
+}
+
+impl PeerTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start tracking a video; `cids` are its manifest and chunk CIDs
+    pub fn track(&mut self, content_hash: &str, cids: Vec<String>) {
+        self.contents.entry(content_hash.to_string()).or_default().cids = cids;
+    }
+
+    pub fn untrack(&mut self, content_hash: &str) {
+        self.contents.remove(content_hash);
+    }
+
+    pub fn tracked(&self) -> Vec<(String, Vec<String>)> {
+        self.contents.iter().map(|(hash, content)| (hash.clone(), content.cids.clone())).collect()
+    }
+
+    /// Record the providers found in a refresh: peer -> (addrs, chunks held)
+    pub fn record_providers(&mut self, content_hash: &str, providers: HashMap<String, (Vec<String>, usize)>, now: DateTime<Utc>) {
+        let content = self.contents.entry(content_hash.to_string()).or_default();
+        for (peer_id, (addrs, chunks_held)) in providers {
+            let entry = content.peers.entry(peer_id).or_insert_with(|| PeerEntry {
+                addrs: Vec::new(),
+                chunks_held: 0,
+                last_seen: now,
+                latency_samples_ms: VecDeque::new(),
+            });
+            if !addrs.is_empty() {
+                entry.addrs = addrs;
+            }
+            entry.chunks_held = chunks_held;
+            entry.last_seen = now;
+        }
+    }
+
+    pub fn record_latency(&mut self, content_hash: &str, peer_id: &str, latency: Duration) {
+        let Some(entry) = self.contents.get_mut(content_hash).and_then(|content| content.peers.get_mut(peer_id)) else {
+            return;
+        };
+        if entry.latency_samples_ms.len() == LATENCY_SAMPLES_PER_PEER {
+            entry.latency_samples_ms.pop_front();
+        }
+        entry.latency_samples_ms.push_back(latency.as_millis() as u64);
+    }
+
+    /// Drop peers not seen since `now - max_age`; returns how many
+    pub fn evict_stale(&mut self, now: DateTime<Utc>, max_age: chrono::Duration) -> usize {
+        let mut evicted = 0;
+        for content in self.contents.values_mut() {
+            let before = content.peers.len();
+            content.peers.retain(|_, entry| now - entry.last_seen <= max_age);
+            evicted += before - content.peers.len();
+        }
+        evicted
+    }
+
+    /// Providers of a video, lowest latency first
+    pub fn peers(&self, content_hash: &str) -> Vec<DiscoveredPeer> {
+        let Some(content) = self.contents.get(content_hash) else {
+            return Vec::new();
+        };
+        let mut peers: Vec<DiscoveredPeer> = content
+            .peers
+            .iter()
+            .map(|(peer_id, entry)| DiscoveredPeer {
+                peer_id: peer_id.clone(),
+                addrs: entry.addrs.clone(),
+                chunks_held: entry.chunks_held,
+                last_seen: entry.last_seen,
+                median_latency_ms: median(&mut entry.latency_samples_ms.iter().copied().collect::<Vec<_>>()),
+            })
+            .collect();
+        peers.sort_by(|a, b| {
+            let latency = |peer: &DiscoveredPeer| peer.median_latency_ms.unwrap_or(u64::MAX);
+            latency(a)
+                .cmp(&latency(b))
+                .then(b.chunks_held.cmp(&a.chunks_held))
+                .then(a.peer_id.cmp(&b.peer_id))
+        });
+        peers
+    }
+
+    pub fn availability(&self, content_hash: &str) -> ContentAvailability {
+        let Some(content) = self.contents.get(content_hash) else {
+            return ContentAvailability { provider_count: 0, replicas: 0.0, availability_score: 0.0 };
+        };
+        let total = content.cids.len().max(1) as f64;
+        let replicas: f64 = content.peers.values().map(|entry| (entry.chunks_held as f64 / total).min(1.0)).sum();
+        let mut samples: Vec<u64> =
+            content.peers.values().flat_map(|entry| entry.latency_samples_ms.iter().copied()).collect();
+        ContentAvailability {
+            provider_count: content.peers.len() as u32,
+            replicas,
+            availability_score: availability_score(replicas, median(&mut samples)),
+        }
+    }
+}
+
+/// Keeps the peer table of the videos this node serves up to date
+pub struct VideoPeerDiscovery {
+    dht: Arc<dyn DhtClient>,
+    table: RwLock<PeerTable>,
+    providers_per_cid: usize,
+    max_age: chrono::Duration,
+}
+
+impl VideoPeerDiscovery {
+    pub fn new(dht: Arc<dyn DhtClient>) -> Self {
+        Self {
+            dht,
+            table: RwLock::new(PeerTable::new()),
+            providers_per_cid: DEFAULT_PROVIDERS_PER_CID,
+            max_age: chrono::Duration::minutes(DEFAULT_PEER_MAX_AGE_MINUTES),
+        }
+    }
+
+    pub fn with_max_age(mut self, max_age: chrono::Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    pub async fn track(&self, content_hash: &str, cids: Vec<String>) {
+        self.table.write().await.track(content_hash, cids);
+    }
+
+    pub async fn untrack(&self, content_hash: &str) {
+        self.table.write().await.untrack(content_hash);
+    }
+
+    /// Publish provider records for CIDs the local node holds
+    pub async fn announce(&self, cids: &[String]) -> IoResult<usize> {
+        for cid in cids {
+            self.dht.provide(cid).await?;
+        }
+        Ok(cids.len())
+    }
+
+    /// Look up the providers of every CID of a tracked video and ping them
+    pub async fn refresh(&self, content_hash: &str) -> IoResult<()> {
+        let cids = self
+            .table
+            .read()
+            .await
+            .tracked()
+            .into_iter()
+            .find(|(hash, _)| hash == content_hash)
+            .map(|(_, cids)| cids)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("Video {} is not tracked", content_hash)))?;
+
+        let mut providers: HashMap<String, (Vec<String>, usize)> = HashMap::new();
+        let mut lookups_failed = 0;
+        for cid in &cids {
+            match self.dht.find_providers(cid, self.providers_per_cid).await {
+                Ok(records) => {
+                    for record in records {
+                        let entry = providers.entry(record.peer_id).or_default();
+                        if entry.0.is_empty() {
+                            entry.0 = record.addrs;
+                        }
+                        entry.1 += 1;
+                    }
+                }
+                Err(e) => {
+                    lookups_failed += 1;
+                    tracing::debug!("Provider lookup for {} failed: {}", cid, e);
+                }
+            }
+        }
+        if lookups_failed == cids.len() && !cids.is_empty() {
+            return Err(Error::new(ErrorKind::Other, format!("Every provider lookup for {} failed", content_hash)));
+        }
+
+        let mut latencies = Vec::new();
+        for peer_id in providers.keys() {
+            if let Ok(latency) = self.dht.ping(peer_id).await {
+                latencies.push((peer_id.clone(), latency));
+            }
+        }
+
+        let mut table = self.table.write().await;
+        table.record_providers(content_hash, providers, Utc::now());
+        for (peer_id, latency) in latencies {
+            table.record_latency(content_hash, &peer_id, latency);
+        }
+        Ok(())
+    }
+
+    /// Refresh every tracked video, then evict stale peers
+    pub async fn refresh_all(&self) -> usize {
+        let tracked = self.table.read().await.tracked();
+        for (content_hash, _) in tracked {
+            if let Err(e) = self.refresh(&content_hash).await {
+                tracing::warn!("Peer refresh for video {} failed: {}", content_hash, e);
+            }
+        }
+        self.evict_stale(Utc::now()).await
+    }
+
+    pub async fn evict_stale(&self, now: DateTime<Utc>) -> usize {
+        self.table.write().await.evict_stale(now, self.max_age)
+    }
+
+    pub async fn peers(&self, content_hash: &str) -> Vec<DiscoveredPeer> {
+        self.table.read().await.peers(content_hash)
+    }
+
+    pub async fn availability(&self, content_hash: &str) -> ContentAvailability {
+        self.table.read().await.availability(content_hash)
+    }
+
+    pub fn start(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let evicted = self.refresh_all().await;
+                if evicted > 0 {
+                    tracing::info!("Evicted {} stale video peers", evicted);
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cids(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("cid{}", i)).collect()
+    }
+
+    #[test]
+    fn score_grows_with_replicas_and_falls_with_latency() {
+        assert_eq!(availability_score(0.0, Some(10)), 0.0);
+        // Cinco réplicas rápidas: máximo
+        assert!((availability_score(5.0, Some(50)) - 1.0).abs() < 1e-6);
+        // Una réplica sin muestras: 0.7 * 0.2 + 0.3 * 0.5
+        assert!((availability_score(1.0, None) - 0.29).abs() < 1e-6);
+        // Latencia a mitad de camino entre 100ms y 2s
+        assert!((availability_score(5.0, Some(1050)) - 0.85).abs() < 1e-6);
+        assert!((availability_score(10.0, Some(5000)) - 0.7).abs() < 1e-6);
+
+        assert!(availability_score(2.0, Some(200)) > availability_score(1.0, Some(200)));
+        assert!(availability_score(2.0, Some(200)) > availability_score(2.0, Some(900)));
+    }
+
+    #[tokio::test]
+    async fn refresh_builds_the_peer_table_from_provider_records() {
+        let dht = Arc::new(InMemoryDhtClient::new("local"));
+        // El vídeo son 4 CIDs: "fast" los tiene todos, "slow" la mitad y
+        // "mute" uno pero no responde al ping
+        for cid in cids(4) {
+            dht.add_provider(&cid, "fast", vec!["/ip4/10.0.0.1/tcp/4001".to_string()]);
+        }
+        dht.add_provider("cid0", "slow", vec![]);
+        dht.add_provider("cid1", "slow", vec![]);
+        dht.add_provider("cid3", "mute", vec![]);
+        dht.set_latency("fast", Duration::from_millis(40));
+        dht.set_latency("slow", Duration::from_millis(1050));
+
+        let discovery = VideoPeerDiscovery::new(dht.clone());
+        discovery.track("video", cids(4)).await;
+        discovery.refresh("video").await.unwrap();
+        discovery.refresh("video").await.unwrap();
+
+        let peers = discovery.peers("video").await;
+        let order: Vec<&str> = peers.iter().map(|peer| peer.peer_id.as_str()).collect();
+        assert_eq!(order, vec!["fast", "slow", "mute"]);
+        assert_eq!(peers[0].chunks_held, 4);
+        assert_eq!(peers[0].addrs, vec!["/ip4/10.0.0.1/tcp/4001".to_string()]);
+        assert_eq!(peers[0].median_latency_ms, Some(40));
+        assert_eq!(peers[2].median_latency_ms, None);
+
+        // 1 + 0.5 + 0.25 réplicas; mediana de las muestras [40, 40, 1050, 1050] = 1050
+        let availability = discovery.availability("video").await;
+        assert_eq!(availability.provider_count, 3);
+        assert!((availability.replicas - 1.75).abs() < 1e-9);
+        assert!((availability.availability_score - availability_score(1.75, Some(1050))).abs() < 1e-6);
+        assert_eq!(discovery.availability("unknown").await.availability_score, 0.0);
+    }
+
+    #[tokio::test]
+    async fn peers_that_stop_providing_are_evicted_once_stale() {
+        let dht = Arc::new(InMemoryDhtClient::new("local"));
+        dht.add_provider("cid0", "stays", vec![]);
+        dht.add_provider("cid0", "leaves", vec![]);
+        let discovery = VideoPeerDiscovery::new(dht.clone()).with_max_age(chrono::Duration::minutes(30));
+        discovery.track("video", cids(1)).await;
+        discovery.refresh("video").await.unwrap();
+        let first_seen = Utc::now();
+
+        dht.remove_peer("leaves");
+        discovery.refresh("video").await.unwrap();
+        // Aún fresco: sigue en la tabla
+        assert_eq!(discovery.evict_stale(first_seen + chrono::Duration::minutes(10)).await, 0);
+        assert_eq!(discovery.availability("video").await.provider_count, 2);
+
+        let later = Utc::now() + chrono::Duration::minutes(31);
+        let mut table = discovery.table.write().await;
+        table.record_providers("video", HashMap::from([("stays".to_string(), (vec![], 1))]), later);
+        drop(table);
+        assert_eq!(discovery.evict_stale(later).await, 1);
+        let peers: Vec<String> = discovery.peers("video").await.into_iter().map(|peer| peer.peer_id).collect();
+        assert_eq!(peers, vec!["stays".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn announce_publishes_provider_records() {
+        let dht = Arc::new(InMemoryDhtClient::new("local"));
+        let discovery = VideoPeerDiscovery::new(dht.clone());
+
+        assert_eq!(discovery.announce(&cids(3)).await.unwrap(), 3);
+        for cid in cids(3) {
+            assert_eq!(dht.providers_of(&cid)[0].peer_id, "local");
+        }
+    }
+}
@@ -0,0 +1,132 @@
+// Comportamiento común de las implementaciones de `VideoFileStorage`
+//
+// Cada comprobación recibe la storage como `dyn VideoFileStorage` y se ejecuta
+// contra la local (en un directorio temporal) y contra la de IPFS (con un
+// block store y un DHT en memoria en lugar del daemon).
+
+use bytes::Bytes;
+use std::io::ErrorKind;
+use std::sync::Arc;
+
+use super::ipfs_video_storage::{IPFSVideoStorage, VideoFileStorage, VideoQuality};
+use super::local_video_storage::LocalVideoStorage;
+use super::video_chunks::InMemoryChunkStore;
+use super::video_peers::InMemoryDhtClient;
+
+const MAX_FILE_SIZE: u64 = 2 * 1024 * 1024;
+
+/// Cabecera ftyp de MP4 y un patrón, 1.500.001 bytes. High = 5 Mbps, con
+/// segmentos de 1s son 625.000 bytes: 3 segmentos.
+fn fixture_video() -> Bytes {
+    let mut data = vec![0x00, 0x00, 0x00, 0x18, b'f', b't', b'y', b'p', b'i', b's', b'o', b'm'];
+    data.extend((0..1_500_001u32 - 12).map(|i| (i % 251) as u8));
+    Bytes::from(data)
+}
+
+fn local_storage(dir: &tempfile::TempDir) -> LocalVideoStorage {
+    LocalVideoStorage::new(dir.path(), MAX_FILE_SIZE).with_segment_seconds(1)
+}
+
+fn ipfs_storage() -> IPFSVideoStorage {
+    IPFSVideoStorage::new_distributed("http://localhost:5001".to_string(), vec![], MAX_FILE_SIZE, false, false)
+        .with_chunk_store(Arc::new(InMemoryChunkStore::new()))
+        .with_segment_seconds(1)
+        .with_dht_client(Arc::new(InMemoryDhtClient::new("local")))
+}
+
+async fn chunks_reassemble_to_the_original(storage: &dyn VideoFileStorage) {
+    let video = fixture_video();
+    let url = storage.upload_video(video.clone(), "clip.mp4", "video/mp4").await.unwrap();
+
+    let metadata = storage.get_metadata(&url).await.unwrap();
+    assert_eq!(metadata.file_size, 1_500_001);
+    assert_eq!(metadata.content_type, "video/mp4");
+    assert_eq!(metadata.chunk_count, 3);
+    assert_eq!(metadata.available_qualities, vec![VideoQuality::High]);
+
+    let mut reassembled = Vec::new();
+    let mut sizes = Vec::new();
+    for index in 0..metadata.chunk_count {
+        let chunk = storage.get_video_chunk(&url, index, &VideoQuality::High).await.unwrap();
+        assert_eq!(chunk.chunk_index, index);
+        assert_eq!(chunk.quality, VideoQuality::High);
+        sizes.push(chunk.data.len());
+        reassembled.extend_from_slice(&chunk.data);
+    }
+    assert_eq!(sizes, vec![625_000, 625_000, 250_001]);
+    assert_eq!(Bytes::from(reassembled), video);
+    assert_eq!(storage.download_video(&url).await.unwrap(), video);
+    assert!(!storage.get_streaming_url(&url, &VideoQuality::High).await.unwrap().is_empty());
+}
+
+async fn invalid_uploads_are_rejected(storage: &dyn VideoFileStorage) {
+    let too_big = Bytes::from(vec![0u8; MAX_FILE_SIZE as usize + 1]);
+    let error = storage.upload_video(too_big, "big.mp4", "video/mp4").await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+    let error = storage.upload_video(fixture_video(), "song.mp3", "audio/mpeg").await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+    let error = storage.upload_video(Bytes::new(), "empty.mp4", "video/mp4").await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+}
+
+async fn missing_chunks_and_qualities_error(storage: &dyn VideoFileStorage) {
+    let url = storage.upload_video(fixture_video(), "clip.mp4", "video/mp4").await.unwrap();
+
+    let out_of_range = storage.get_video_chunk(&url, 3, &VideoQuality::High).await.unwrap_err();
+    assert_eq!(out_of_range.kind(), ErrorKind::InvalidInput);
+    let missing = storage.get_video_chunk(&url, 0, &VideoQuality::Low).await.unwrap_err();
+    assert_eq!(missing.kind(), ErrorKind::NotFound);
+}
+
+async fn transcoding_is_queued_for_other_qualities(storage: &dyn VideoFileStorage) {
+    let url = storage.upload_video(fixture_video(), "clip.mp4", "video/mp4").await.unwrap();
+
+    let job = storage.transcode_video(&url, VideoQuality::Low).await.unwrap();
+    // Ya estaba en cola desde la subida
+    assert_eq!(storage.transcode_video(&url, VideoQuality::Low).await.unwrap(), job);
+    let error = storage.transcode_video(&url, VideoQuality::High).await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+}
+
+async fn deleted_videos_are_gone(storage: &dyn VideoFileStorage) {
+    let url = storage.upload_video(fixture_video(), "clip.mp4", "video/mp4").await.unwrap();
+    storage.announce_to_network(&url).await.unwrap();
+
+    storage.delete_video(&url).await.unwrap();
+    assert!(storage.download_video(&url).await.is_err());
+    assert!(storage.get_metadata(&url).await.is_err());
+    assert!(storage.get_video_chunk(&url, 0, &VideoQuality::High).await.is_err());
+}
+
+macro_rules! conformance_tests {
+    ($($name:ident),* $(,)?) => {
+        mod local {
+            $(
+                #[tokio::test]
+                async fn $name() {
+                    let dir = tempfile::tempdir().unwrap();
+                    super::$name(&super::local_storage(&dir)).await;
+                }
+            )*
+        }
+
+        mod ipfs {
+            $(
+                #[tokio::test]
+                async fn $name() {
+                    super::$name(&super::ipfs_storage()).await;
+                }
+            )*
+        }
+    };
+}
+
+conformance_tests!(
+    chunks_reassemble_to_the_original,
+    invalid_uploads_are_rejected,
+    missing_chunks_and_qualities_error,
+    transcoding_is_queued_for_other_qualities,
+    deleted_videos_are_gone,
+);
@@ -0,0 +1,745 @@
+// Transcodificación de vídeo a la escalera de `VideoQuality`
+//
+// Cada calidad es un trabajo propio: el worker reclama uno pendiente, descarga
+// el original, lo escala con ffmpeg y trocea el resultado como una rendición
+// más. Los completados no se repiten y los interrumpidos (sin actualizar
+// durante `stale_after`) vuelven a pending, así que tras una caída se retoma
+// calidad a calidad. Las calidades por encima de la resolución del original
+// se saltan en lugar de escalar hacia arriba.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{Error, ErrorKind, Result as IoResult};
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::watch;
+use uuid::Uuid;
+
+use super::ipfs_video_storage::{IPFSVideoStorage, VideoQuality};
+use super::video_chunks::VideoRendition;
+
+/// Intentos por calidad antes de marcar el trabajo como fallido
+pub const DEFAULT_MAX_TRANSCODE_ATTEMPTS: u32 = 3;
+
+/// Un trabajo en processing sin actualizarse en este tiempo se da por interrumpido
+pub const DEFAULT_TRANSCODE_STALE_SECONDS: i64 = 600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranscodeJobStatus {
+    Pending,
+    Processing,
+    Completed,
+    Failed,
+    Skipped,
+}
+
+impl TranscodeJobStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TranscodeJobStatus::Pending => "pending",
+            TranscodeJobStatus::Processing => "processing",
+            TranscodeJobStatus::Completed => "completed",
+            TranscodeJobStatus::Failed => "failed",
+            TranscodeJobStatus::Skipped => "skipped",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "pending" => Some(TranscodeJobStatus::Pending),
+            "processing" => Some(TranscodeJobStatus::Processing),
+            "completed" => Some(TranscodeJobStatus::Completed),
+            "failed" => Some(TranscodeJobStatus::Failed),
+            "skipped" => Some(TranscodeJobStatus::Skipped),
+            _ => None,
+        }
+    }
+}
+
+/// Transcoding of one video to one quality
+#[derive(Debug, Clone)]
+pub struct VideoTranscodeJob {
+    pub id: Uuid,
+    /// CID del manifiesto del original
+    pub video_hash: String,
+    pub quality: VideoQuality,
+    pub status: TranscodeJobStatus,
+    /// 0.0-1.0
+    pub progress: f32,
+    pub attempts: u32,
+    pub error: Option<String>,
+    /// Segmentos de la calidad, solo en los completados
+    pub rendition: Option<VideoRendition>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl VideoTranscodeJob {
+    pub fn new(video_hash: &str, quality: VideoQuality) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            video_hash: video_hash.to_string(),
+            quality,
+            status: TranscodeJobStatus::Pending,
+            progress: 0.0,
+            attempts: 0,
+            error: None,
+            rendition: None,
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+        }
+    }
+}
+
+#[async_trait]
+pub trait TranscodeJobRepository: Send + Sync {
+    /// Create the jobs that don't exist yet for the video (failed ones are
+    /// reset to pending) and return the jobs of the requested qualities
+    async fn enqueue(&self, video_hash: &str, qualities: &[VideoQuality]) -> IoResult<Vec<VideoTranscodeJob>>;
+
+    /// Move the pending job updated longest ago to processing, counting the
+    /// attempt. Retries go to the back of the queue.
+    async fn claim_next(&self) -> IoResult<Option<VideoTranscodeJob>>;
+
+    /// Return processing jobs not updated since `stale_before` to pending
+    async fn requeue_stale(&self, stale_before: DateTime<Utc>) -> IoResult<u64>;
+
+    async fn update_progress(&self, job_id: Uuid, progress: f32) -> IoResult<()>;
+
+    async fn complete(&self, job_id: Uuid, rendition: &VideoRendition) -> IoResult<()>;
+
+    async fn skip(&self, job_id: Uuid, reason: &str) -> IoResult<()>;
+
+    /// Record a failure; with `retry` the job goes back to pending
+    async fn fail(&self, job_id: Uuid, error: &str, retry: bool) -> IoResult<()>;
+
+    async fn find_by_video(&self, video_hash: &str) -> IoResult<Vec<VideoTranscodeJob>>;
+
+    async fn delete_by_video(&self, video_hash: &str) -> IoResult<()>;
+}
+
+/// Job repository for development and tests; jobs are lost on restart
+#[derive(Default)]
+pub struct InMemoryTranscodeJobRepository {
+    // En orden de creación
+    jobs: Mutex<Vec<VideoTranscodeJob>>,
+}
+
+impl InMemoryTranscodeJobRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn update<F: FnOnce(&mut VideoTranscodeJob)>(&self, job_id: Uuid, apply: F) -> IoResult<()> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let job = jobs
+            .iter_mut()
+            .find(|job| job.id == job_id)
+            .ok_or_else(|| Error::new(ErrorKind::NotFound, format!("Transcode job {} not found", job_id)))?;
+        apply(job);
+        job.updated_at = Utc::now();
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl TranscodeJobRepository for InMemoryTranscodeJobRepository {
+    async fn enqueue(&self, video_hash: &str, qualities: &[VideoQuality]) -> IoResult<Vec<VideoTranscodeJob>> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let mut queued = Vec::with_capacity(qualities.len());
+        for quality in qualities {
+            let existing = jobs.iter_mut().find(|job| job.video_hash == video_hash && job.quality == *quality);
+            let job = match existing {
+                Some(job) => {
+                    if job.status == TranscodeJobStatus::Failed {
+                        job.status = TranscodeJobStatus::Pending;
+                        job.attempts = 0;
+                        job.progress = 0.0;
+                        job.error = None;
+                        job.updated_at = Utc::now();
+                    }
+                    job.clone()
+                }
+                None => {
+                    let job = VideoTranscodeJob::new(video_hash, quality.clone());
+                    jobs.push(job.clone());
+                    job
+                }
+            };
+            queued.push(job);
+        }
+        Ok(queued)
+    }
+
+    async fn claim_next(&self) -> IoResult<Option<VideoTranscodeJob>> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let next = jobs
+            .iter_mut()
+            .filter(|job| job.status == TranscodeJobStatus::Pending)
+            .reduce(|oldest, job| if job.updated_at < oldest.updated_at { job } else { oldest });
+        let Some(job) = next else {
+            return Ok(None);
+        };
+        job.status = TranscodeJobStatus::Processing;
+        job.attempts += 1;
+        job.progress = 0.0;
+        job.updated_at = Utc::now();
+        Ok(Some(job.clone()))
+    }
+
+    async fn requeue_stale(&self, stale_before: DateTime<Utc>) -> IoResult<u64> {
+        let mut requeued = 0;
+        for job in self.jobs.lock().unwrap().iter_mut() {
+            if job.status == TranscodeJobStatus::Processing && job.updated_at < stale_before {
+                job.status = TranscodeJobStatus::Pending;
+                job.updated_at = Utc::now();
+                requeued += 1;
+            }
+        }
+        Ok(requeued)
+    }
+
+    async fn update_progress(&self, job_id: Uuid, progress: f32) -> IoResult<()> {
+        self.update(job_id, |job| job.progress = progress.clamp(0.0, 1.0))
+    }
+
+    async fn complete(&self, job_id: Uuid, rendition: &VideoRendition) -> IoResult<()> {
+        self.update(job_id, |job| {
+            job.status = TranscodeJobStatus::Completed;
+            job.progress = 1.0;
+            job.error = None;
+            job.rendition = Some(rendition.clone());
+            job.completed_at = Some(Utc::now());
+        })
+    }
+
+    async fn skip(&self, job_id: Uuid, reason: &str) -> IoResult<()> {
+        self.update(job_id, |job| {
+            job.status = TranscodeJobStatus::Skipped;
+            job.error = Some(reason.to_string());
+            job.completed_at = Some(Utc::now());
+        })
+    }
+
+    async fn fail(&self, job_id: Uuid, error: &str, retry: bool) -> IoResult<()> {
+        self.update(job_id, |job| {
+            job.status = if retry { TranscodeJobStatus::Pending } else { TranscodeJobStatus::Failed };
+            job.progress = 0.0;
+            job.error = Some(error.to_string());
+        })
+    }
+
+    async fn find_by_video(&self, video_hash: &str) -> IoResult<Vec<VideoTranscodeJob>> {
+        Ok(self.jobs.lock().unwrap().iter().filter(|job| job.video_hash == video_hash).cloned().collect())
+    }
+
+    async fn delete_by_video(&self, video_hash: &str) -> IoResult<()> {
+        self.jobs.lock().unwrap().retain(|job| job.video_hash != video_hash);
+        Ok(())
+    }
+}
+
+/// Video stream properties of a source file
+#[derive(Debug, Clone, PartialEq)]
+pub struct VideoProbe {
+    pub width: u32,
+    pub height: u32,
+    pub duration_seconds: Option<f64>,
+}
+
+#[async_trait]
+pub trait VideoEncoder: Send + Sync {
+    async fn probe(&self, input: &Path) -> IoResult<VideoProbe>;
+
+    /// Encode `input` at `quality` into `output`, sending progress (0.0-1.0)
+    async fn encode(
+        &self,
+        input: &Path,
+        source: &VideoProbe,
+        quality: &VideoQuality,
+        output: &Path,
+        progress: watch::Sender<f32>,
+    ) -> IoResult<()>;
+}
+
+/// H.264/AAC MP4 renditions with the ffmpeg and ffprobe binaries
+pub struct FfmpegVideoEncoder {
+    ffmpeg: PathBuf,
+    ffprobe: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct FfprobeOutput {
+    #[serde(default)]
+    streams: Vec<FfprobeStream>,
+    format: Option<FfprobeFormat>,
+}
+
+#[derive(Deserialize)]
+struct FfprobeStream {
+    width: Option<u32>,
+    height: Option<u32>,
+}
+
+#[derive(Deserialize)]
+struct FfprobeFormat {
+    duration: Option<String>,
+}
+
+impl FfmpegVideoEncoder {
+    pub fn new() -> Self {
+        Self::with_binaries("ffmpeg", "ffprobe")
+    }
+
+    pub fn with_binaries(ffmpeg: impl Into<PathBuf>, ffprobe: impl Into<PathBuf>) -> Self {
+        Self { ffmpeg: ffmpeg.into(), ffprobe: ffprobe.into() }
+    }
+
+    pub async fn is_available(&self) -> bool {
+        let version = |binary: &PathBuf| {
+            Command::new(binary).arg("-version").stdout(Stdio::null()).stderr(Stdio::null()).status()
+        };
+        matches!(version(&self.ffmpeg).await, Ok(status) if status.success())
+            && matches!(version(&self.ffprobe).await, Ok(status) if status.success())
+    }
+}
+
+impl Default for FfmpegVideoEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl VideoEncoder for FfmpegVideoEncoder {
+    async fn probe(&self, input: &Path) -> IoResult<VideoProbe> {
+        let output = Command::new(&self.ffprobe)
+            .args(["-v", "error", "-select_streams", "v:0"])
+            .args(["-show_entries", "stream=width,height:format=duration", "-of", "json"])
+            .arg(input)
+            .output()
+            .await?;
+        if !output.status.success() {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("ffprobe failed: {}", String::from_utf8_lossy(&output.stderr).trim()),
+            ));
+        }
+
+        let probe: FfprobeOutput =
+            serde_json::from_slice(&output.stdout).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        let (width, height) = probe
+            .streams
+            .first()
+            .and_then(|stream| Some((stream.width?, stream.height?)))
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Source has no video stream"))?;
+        let duration_seconds = probe.format.and_then(|format| format.duration?.parse().ok());
+        Ok(VideoProbe { width, height, duration_seconds })
+    }
+
+    async fn encode(
+        &self,
+        input: &Path,
+        source: &VideoProbe,
+        quality: &VideoQuality,
+        output: &Path,
+        progress: watch::Sender<f32>,
+    ) -> IoResult<()> {
+        // El audio va a 128k; el resto del bitrate nominal es para el vídeo
+        let video_kbps = quality.minimum_bandwidth() / 1000 - 128;
+        let mut child = Command::new(&self.ffmpeg)
+            .args(["-y", "-nostdin", "-loglevel", "error", "-i"])
+            .arg(input)
+            .args(["-map", "0:v:0", "-map", "0:a:0?"])
+            // Ancho par para libx264
+            .args(["-vf", &format!("scale=-2:{}", quality.height())])
+            .args(["-c:v", "libx264", "-preset", "veryfast"])
+            .args(["-b:v", &format!("{}k", video_kbps), "-maxrate", &format!("{}k", video_kbps)])
+            .args(["-bufsize", &format!("{}k", video_kbps * 2)])
+            .args(["-c:a", "aac", "-b:a", "128k", "-movflags", "+faststart"])
+            .args(["-progress", "pipe:1", "-nostats"])
+            .arg(output)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()?;
+
+        let stderr = child.stderr.take();
+        let errors = tokio::spawn(async move {
+            let mut errors = String::new();
+            if let Some(mut stderr) = stderr {
+                let _ = stderr.read_to_string(&mut errors).await;
+            }
+            errors
+        });
+
+        // -progress escribe bloques clave=valor; out_time_us es la posición
+        if let Some(stdout) = child.stdout.take() {
+            let mut lines = BufReader::new(stdout).lines();
+            while let Some(line) = lines.next_line().await? {
+                let position = line
+                    .strip_prefix("out_time_us=")
+                    .or_else(|| line.strip_prefix("out_time_ms="))
+                    .and_then(|value| value.parse::<f64>().ok());
+                if let (Some(position), Some(duration)) = (position, source.duration_seconds) {
+                    if duration > 0.0 {
+                        let _ = progress.send((position / 1_000_000.0 / duration).clamp(0.0, 1.0) as f32);
+                    }
+                }
+            }
+        }
+
+        let status = child.wait().await?;
+        let errors = errors.await.unwrap_or_default();
+        if !status.success() {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("ffmpeg exited with {} encoding {:?}: {}", status, quality, errors.trim()),
+            ));
+        }
+        let _ = progress.send(1.0);
+        Ok(())
+    }
+}
+
+enum TranscodeOutcome {
+    Completed(VideoRendition),
+    Skipped(String),
+}
+
+/// Processes transcode jobs one at a time
+pub struct VideoTranscodingWorker {
+    storage: Arc<IPFSVideoStorage>,
+    jobs: Arc<dyn TranscodeJobRepository>,
+    encoder: Arc<dyn VideoEncoder>,
+    work_dir: PathBuf,
+    max_attempts: u32,
+    stale_after: chrono::Duration,
+}
+
+impl VideoTranscodingWorker {
+    pub fn new(storage: Arc<IPFSVideoStorage>, encoder: Arc<dyn VideoEncoder>) -> Self {
+        Self {
+            jobs: storage.transcode_job_repository(),
+            storage,
+            encoder,
+            work_dir: std::env::temp_dir().join("vibestream-transcode"),
+            max_attempts: DEFAULT_MAX_TRANSCODE_ATTEMPTS,
+            stale_after: chrono::Duration::seconds(DEFAULT_TRANSCODE_STALE_SECONDS),
+        }
+    }
+
+    /// Directory for the source and encoded files while a job runs
+    pub fn with_work_dir(mut self, work_dir: impl Into<PathBuf>) -> Self {
+        self.work_dir = work_dir.into();
+        self
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn with_stale_after(mut self, stale_after: chrono::Duration) -> Self {
+        self.stale_after = stale_after;
+        self
+    }
+
+    /// Claim and run the next pending job; returns it in its final state
+    pub async fn process_next(&self) -> IoResult<Option<VideoTranscodeJob>> {
+        let requeued = self.jobs.requeue_stale(Utc::now() - self.stale_after).await?;
+        if requeued > 0 {
+            tracing::warn!("Requeued {} interrupted transcode jobs", requeued);
+        }
+
+        let Some(job) = self.jobs.claim_next().await? else {
+            return Ok(None);
+        };
+        let job_dir = self.work_dir.join(job.id.to_string());
+        let outcome = self.run(&job, &job_dir).await;
+        let _ = tokio::fs::remove_dir_all(&job_dir).await;
+
+        match outcome {
+            Ok(TranscodeOutcome::Completed(rendition)) => {
+                tracing::info!("Transcoded {} to {:?} in {} chunks", job.video_hash, job.quality, rendition.chunk_count());
+                self.jobs.complete(job.id, &rendition).await?;
+            }
+            Ok(TranscodeOutcome::Skipped(reason)) => {
+                tracing::info!("Skipped {:?} for {}: {}", job.quality, job.video_hash, reason);
+                self.jobs.skip(job.id, &reason).await?;
+            }
+            Err(e) => {
+                let retry = job.attempts < self.max_attempts;
+                tracing::error!(
+                    "Transcoding {} to {:?} failed (attempt {}/{}): {}",
+                    job.video_hash, job.quality, job.attempts, self.max_attempts, e
+                );
+                self.jobs.fail(job.id, &e.to_string(), retry).await?;
+            }
+        }
+
+        let jobs = self.jobs.find_by_video(&job.video_hash).await?;
+        Ok(jobs.into_iter().find(|stored| stored.id == job.id))
+    }
+
+    async fn run(&self, job: &VideoTranscodeJob, job_dir: &Path) -> IoResult<TranscodeOutcome> {
+        tokio::fs::create_dir_all(job_dir).await?;
+        let source = self.storage.download_video(&self.storage.get_ipfs_video_url(&job.video_hash)).await?;
+        let input = job_dir.join("source");
+        tokio::fs::write(&input, &source).await?;
+        drop(source);
+
+        let probe = self.encoder.probe(&input).await?;
+        if probe.height < job.quality.height() {
+            return Ok(TranscodeOutcome::Skipped(format!(
+                "Source is {}p, below {}p",
+                probe.height,
+                job.quality.height()
+            )));
+        }
+
+        let output = job_dir.join(format!("{}.mp4", job.quality.as_str()));
+        let (progress_tx, mut progress_rx) = watch::channel(0.0f32);
+        let encode = self.encoder.encode(&input, &probe, &job.quality, &output, progress_tx);
+        tokio::pin!(encode);
+
+        // Guardar el progreso cada 5% también sirve de latido del trabajo
+        let mut reported = 0.0f32;
+        loop {
+            tokio::select! {
+                result = &mut encode => {
+                    result?;
+                    break;
+                }
+                changed = progress_rx.changed() => {
+                    if changed.is_err() {
+                        (&mut encode).await?;
+                        break;
+                    }
+                    let progress = *progress_rx.borrow();
+                    if progress - reported >= 0.05 && progress < 1.0 {
+                        reported = progress;
+                        self.jobs.update_progress(job.id, progress).await?;
+                    }
+                }
+            }
+        }
+
+        let encoded = Bytes::from(tokio::fs::read(&output).await?);
+        let rendition = self.storage.store_transcoded_rendition(&job.video_hash, &job.quality, encoded).await?;
+        Ok(TranscodeOutcome::Completed(rendition))
+    }
+
+    /// Poll for jobs; after a crash the jobs left in processing are picked up
+    /// again once stale
+    pub fn start(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                // Vaciar la cola antes de esperar al siguiente tick
+                loop {
+                    match self.process_next().await {
+                        Ok(Some(_)) => continue,
+                        Ok(None) => break,
+                        Err(e) => {
+                            tracing::error!("Video transcoding worker error: {}", e);
+                            break;
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::ipfs_video_storage::VideoFileStorage;
+    use super::super::video_chunks::InMemoryChunkStore;
+    use super::super::video_peers::InMemoryDhtClient;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Encoder de prueba: el original es de `source_height` y cada calidad
+    /// produce 200.000 bytes; las calidades de `failing` fallan siempre
+    struct FakeEncoder {
+        source_height: u32,
+        failing: Vec<VideoQuality>,
+        encodes: AtomicUsize,
+    }
+
+    impl FakeEncoder {
+        fn new(source_height: u32) -> Self {
+            Self { source_height, failing: Vec::new(), encodes: AtomicUsize::new(0) }
+        }
+    }
+
+    #[async_trait]
+    impl VideoEncoder for FakeEncoder {
+        async fn probe(&self, _input: &Path) -> IoResult<VideoProbe> {
+            Ok(VideoProbe { width: self.source_height * 16 / 9, height: self.source_height, duration_seconds: Some(1.0) })
+        }
+
+        async fn encode(
+            &self,
+            _input: &Path,
+            _source: &VideoProbe,
+            quality: &VideoQuality,
+            output: &Path,
+            progress: watch::Sender<f32>,
+        ) -> IoResult<()> {
+            self.encodes.fetch_add(1, Ordering::SeqCst);
+            let _ = progress.send(0.5);
+            if self.failing.contains(quality) {
+                return Err(Error::new(ErrorKind::Other, "encoder crashed"));
+            }
+            tokio::fs::write(output, vec![quality.height() as u8; 200_000]).await
+        }
+    }
+
+    /// Clip de prueba: cabecera ftyp de MP4 y 300.001 bytes en total
+    fn fixture_clip() -> Bytes {
+        let mut data = vec![0x00, 0x00, 0x00, 0x18, b'f', b't', b'y', b'p', b'i', b's', b'o', b'm'];
+        data.extend((0..300_001u32 - 12).map(|i| (i % 251) as u8));
+        Bytes::from(data)
+    }
+
+    async fn uploaded(encoder: Arc<FakeEncoder>) -> (Arc<IPFSVideoStorage>, VideoTranscodingWorker, String, tempfile::TempDir) {
+        let storage = Arc::new(
+            IPFSVideoStorage::new_distributed("http://localhost:5001".to_string(), vec![], 500 * 1024 * 1024, false, false)
+                .with_chunk_store(Arc::new(InMemoryChunkStore::new()))
+                .with_segment_seconds(1)
+                .with_dht_client(Arc::new(InMemoryDhtClient::new("local"))),
+        );
+        let url = storage.upload_video(fixture_clip(), "clip.mp4", "video/mp4").await.unwrap();
+        let work_dir = tempfile::tempdir().unwrap();
+        let worker = VideoTranscodingWorker::new(storage.clone(), encoder).with_work_dir(work_dir.path());
+        (storage, worker, url, work_dir)
+    }
+
+    #[tokio::test]
+    async fn qualities_grow_as_jobs_complete_and_higher_rungs_are_skipped() {
+        let encoder = Arc::new(FakeEncoder::new(1080));
+        let (storage, worker, url, _work_dir) = uploaded(encoder.clone()).await;
+        assert_eq!(storage.get_available_qualities(&url).await.unwrap(), vec![VideoQuality::High]);
+
+        let low = worker.process_next().await.unwrap().unwrap();
+        assert_eq!((low.quality.clone(), low.status), (VideoQuality::Low, TranscodeJobStatus::Completed));
+        assert_eq!(low.progress, 1.0);
+        assert_eq!(storage.get_available_qualities(&url).await.unwrap(), vec![VideoQuality::High, VideoQuality::Low]);
+        // 200.000 bytes a 1 Mbps con segmentos de 1s
+        let chunk = storage.get_video_chunk(&url, 1, &VideoQuality::Low).await.unwrap();
+        assert_eq!(chunk.data.len(), 75_000);
+
+        worker.process_next().await.unwrap().unwrap();
+        assert_eq!(
+            storage.get_available_qualities(&url).await.unwrap(),
+            vec![VideoQuality::High, VideoQuality::Low, VideoQuality::Medium]
+        );
+
+        // 2160p sobre un original de 1080p: no se escala hacia arriba
+        let ultra = worker.process_next().await.unwrap().unwrap();
+        assert_eq!((ultra.quality.clone(), ultra.status), (VideoQuality::Ultra, TranscodeJobStatus::Skipped));
+        assert_eq!(ultra.error.as_deref(), Some("Source is 1080p, below 2160p"));
+        assert_eq!(storage.get_available_qualities(&url).await.unwrap().len(), 3);
+
+        assert!(worker.process_next().await.unwrap().is_none());
+        assert_eq!(encoder.encodes.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn interrupted_jobs_resume_without_redoing_completed_qualities() {
+        let encoder = Arc::new(FakeEncoder::new(720));
+        let (storage, worker, url, work_dir) = uploaded(encoder.clone()).await;
+        worker.process_next().await.unwrap().unwrap();
+
+        // Caída con Medium a medias: queda en processing
+        let jobs = storage.transcode_job_repository();
+        let interrupted = jobs.claim_next().await.unwrap().unwrap();
+        assert_eq!(interrupted.quality, VideoQuality::Medium);
+
+        // Aún no está caducado: el worker sigue con Ultra
+        let ultra = worker.process_next().await.unwrap().unwrap();
+        assert_eq!(ultra.quality, VideoQuality::Ultra);
+        assert!(worker.process_next().await.unwrap().is_none());
+
+        let restarted = VideoTranscodingWorker::new(storage.clone(), encoder.clone())
+            .with_work_dir(work_dir.path())
+            .with_stale_after(chrono::Duration::zero());
+        let medium = restarted.process_next().await.unwrap().unwrap();
+        assert_eq!((medium.id, medium.status), (interrupted.id, TranscodeJobStatus::Completed));
+        assert_eq!(medium.attempts, 2);
+        assert!(restarted.process_next().await.unwrap().is_none());
+
+        assert_eq!(encoder.encodes.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            storage.get_available_qualities(&url).await.unwrap(),
+            vec![VideoQuality::High, VideoQuality::Low, VideoQuality::Medium]
+        );
+    }
+
+    #[tokio::test]
+    async fn failing_quality_is_retried_then_marked_failed() {
+        let encoder = Arc::new(FakeEncoder { failing: vec![VideoQuality::Low], ..FakeEncoder::new(1080) });
+        let (storage, worker, url, _work_dir) = uploaded(encoder.clone()).await;
+        let worker = worker.with_max_attempts(2);
+
+        let first = worker.process_next().await.unwrap().unwrap();
+        assert_eq!((first.status, first.attempts), (TranscodeJobStatus::Pending, 1));
+        assert_eq!(first.error.as_deref(), Some("encoder crashed"));
+
+        // El reintento de Low va al final de la cola
+        assert_eq!(worker.process_next().await.unwrap().unwrap().quality, VideoQuality::Medium);
+        assert_eq!(worker.process_next().await.unwrap().unwrap().quality, VideoQuality::Ultra);
+        let last = worker.process_next().await.unwrap().unwrap();
+        assert_eq!((last.quality.clone(), last.status, last.attempts), (VideoQuality::Low, TranscodeJobStatus::Failed, 2));
+        assert!(!storage.get_available_qualities(&url).await.unwrap().contains(&VideoQuality::Low));
+
+        // Volver a pedirla reinicia el trabajo
+        let hash = url.rsplit('/').next().unwrap();
+        let job_id = storage.transcode_video(&url, VideoQuality::Low).await.unwrap();
+        let requeued = storage.transcode_jobs(hash).await.unwrap().into_iter().find(|job| job.id == job_id).unwrap();
+        assert_eq!((requeued.status, requeued.attempts), (TranscodeJobStatus::Pending, 0));
+        assert_eq!(storage.transcode_video(&url, VideoQuality::High).await.unwrap_err().kind(), ErrorKind::InvalidInput);
+    }
+
+    #[tokio::test]
+    async fn ffmpeg_scales_a_tiny_clip_down_to_the_rung_height() {
+        let encoder = FfmpegVideoEncoder::new();
+        if !encoder.is_available().await {
+            eprintln!("ffmpeg not installed, skipping");
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let clip = dir.path().join("clip.mp4");
+        let status = Command::new("ffmpeg")
+            .args(["-y", "-loglevel", "error", "-f", "lavfi", "-i", "testsrc=size=1280x720:rate=10:duration=1"])
+            .args(["-pix_fmt", "yuv420p"])
+            .arg(&clip)
+            .status()
+            .await
+            .unwrap();
+        assert!(status.success());
+
+        let source = encoder.probe(&clip).await.unwrap();
+        assert_eq!((source.width, source.height), (1280, 720));
+
+        let output = dir.path().join("low.mp4");
+        let (progress_tx, progress_rx) = watch::channel(0.0);
+        encoder.encode(&clip, &source, &VideoQuality::Low, &output, progress_tx).await.unwrap();
+        assert_eq!(*progress_rx.borrow(), 1.0);
+        let low = encoder.probe(&output).await.unwrap();
+        assert_eq!((low.width, low.height), (640, 360));
+    }
+}
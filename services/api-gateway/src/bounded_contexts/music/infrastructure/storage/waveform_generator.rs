@@ -0,0 +1,301 @@
+use std::io::Cursor;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::DecoderOptions;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+use tokio::task::JoinHandle;
+
+use crate::bounded_contexts::music::domain::repositories::SongRepository;
+use crate::bounded_contexts::music::domain::value_objects::{ChapterMarker, SongId};
+use crate::shared::domain::errors::AppError;
+
+/// RMS window of the amplitude envelope
+pub const ENVELOPE_WINDOW_MS: u64 = 100;
+/// Peaks in a generated waveform
+pub const WAVEFORM_PEAKS: usize = 200;
+/// Power drop below the track's median that counts as a break (10 dB)
+const SECTION_BREAK_DB: f32 = 10.0;
+/// Shorter dips are pauses inside a section, not a boundary
+const MIN_SECTION_BREAK_MS: u64 = 2_000;
+/// Below this median power the track is silence and has no sections
+const SILENCE_POWER: f32 = 1e-8;
+
+/// Waveform peaks for the player and the sections detected in the audio
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Waveform {
+    /// Normalized 0.0-1.0
+    pub peaks: Vec<f32>,
+    pub duration_ms: u64,
+    pub chapter_markers: Vec<ChapterMarker>,
+}
+
+/// RMS of the mono mix, one value per `ENVELOPE_WINDOW_MS`
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmplitudeEnvelope {
+    pub rms: Vec<f32>,
+}
+
+impl AmplitudeEnvelope {
+    pub fn duration_ms(&self) -> u64 {
+        self.rms.len() as u64 * ENVELOPE_WINDOW_MS
+    }
+
+    /// Loudest window of each of `count` equal buckets, relative to the loudest overall
+    pub fn peaks(&self, count: usize) -> Vec<f32> {
+        if self.rms.is_empty() || count == 0 {
+            return Vec::new();
+        }
+        let bucket = self.rms.len().div_ceil(count);
+        let max = self.rms.iter().cloned().fold(0.0f32, f32::max);
+        self.rms
+            .chunks(bucket)
+            .map(|chunk| {
+                let peak = chunk.iter().cloned().fold(0.0f32, f32::max);
+                if max > 0.0 { peak / max } else { 0.0 }
+            })
+            .collect()
+    }
+
+    /// Section boundaries are where the audio comes back after staying at
+    /// least `SECTION_BREAK_DB` below the median power for longer than
+    /// `MIN_SECTION_BREAK_MS`. Quiet stretches at the very start or end are
+    /// the edges of the song, not boundaries. Without any boundary there is no
+    /// structure to mark and the result is empty.
+    ///
+    /// Labels follow the usual pop form by position: intro first, outro last,
+    /// verse and chorus alternating in between and, with four or more middle
+    /// sections, a bridge before the last chorus.
+    pub fn chapter_markers(&self) -> Vec<ChapterMarker> {
+        let powers: Vec<f32> = self.rms.iter().map(|rms| rms * rms).collect();
+        let mut sorted = powers.clone();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+        let Some(&median) = sorted.get(sorted.len() / 2) else {
+            return Vec::new();
+        };
+        if median <= SILENCE_POWER {
+            return Vec::new();
+        }
+        let threshold = median * 10f32.powf(-SECTION_BREAK_DB / 10.0);
+        let min_windows = MIN_SECTION_BREAK_MS.div_ceil(ENVELOPE_WINDOW_MS) as usize;
+
+        let mut boundaries = Vec::new();
+        let mut start = 0;
+        while start < powers.len() {
+            if powers[start] >= threshold {
+                start += 1;
+                continue;
+            }
+            let end = powers[start..]
+                .iter()
+                .position(|&power| power >= threshold)
+                .map_or(powers.len(), |offset| start + offset);
+            if end - start > min_windows && start > 0 && end < powers.len() {
+                boundaries.push(end as u64 * ENVELOPE_WINDOW_MS);
+            }
+            start = end;
+        }
+        if boundaries.is_empty() {
+            return Vec::new();
+        }
+
+        let labels = section_labels(boundaries.len() + 1);
+        std::iter::once(0)
+            .chain(boundaries)
+            .zip(labels)
+            .map(|(position_ms, label)| ChapterMarker::new(position_ms, label))
+            .collect()
+    }
+}
+
+/// Audio decoding and envelope analysis for uploaded songs
+pub struct WaveformGenerator;
+
+impl WaveformGenerator {
+    /// Decode the whole track and build its envelope, window by window so the
+    /// decoded samples are never held in memory at once
+    pub fn envelope_from_bytes(data: &[u8], file_extension: &str) -> Result<AmplitudeEnvelope, AppError> {
+        let mut hint = Hint::new();
+        hint.with_extension(file_extension);
+        let source = MediaSourceStream::new(Box::new(Cursor::new(data.to_vec())), Default::default());
+        let probed = symphonia::default::get_probe()
+            .format(&hint, source, &FormatOptions::default(), &MetadataOptions::default())
+            .map_err(|e| AppError::InvalidInput(format!("Failed to probe audio format: {}", e)))?;
+        let mut format_reader = probed.format;
+
+        let track = format_reader
+            .tracks()
+            .iter()
+            .find(|t| t.codec_params.codec != symphonia::core::codecs::CODEC_TYPE_NULL)
+            .ok_or_else(|| AppError::InvalidInput("No supported audio tracks found".to_string()))?;
+        let track_id = track.id;
+        let codec_params = track.codec_params.clone();
+        let sample_rate = codec_params
+            .sample_rate
+            .ok_or_else(|| AppError::InvalidInput("Audio track has no sample rate".to_string()))?;
+        let channels = codec_params.channels.map(|c| c.count()).unwrap_or(1).max(1);
+        let mut decoder = symphonia::default::get_codecs()
+            .make(&codec_params, &DecoderOptions::default())
+            .map_err(|e| AppError::InvalidInput(format!("Unsupported audio codec: {}", e)))?;
+
+        let window = (sample_rate as u64 * ENVELOPE_WINDOW_MS / 1000).max(1) as usize;
+        let mut rms = Vec::new();
+        let (mut sum_squares, mut frames) = (0.0f64, 0usize);
+        loop {
+            let packet = match format_reader.next_packet() {
+                Ok(packet) => packet,
+                Err(_) => break,
+            };
+            if packet.track_id() != track_id {
+                continue;
+            }
+            let decoded = match decoder.decode(&packet) {
+                Ok(decoded) => decoded,
+                // Paquete corrupto: se salta y se sigue
+                Err(symphonia::core::errors::Error::DecodeError(_)) => continue,
+                Err(_) => break,
+            };
+            let mut buffer = SampleBuffer::<f32>::new(decoded.capacity() as u64, *decoded.spec());
+            buffer.copy_interleaved_ref(decoded);
+            for frame in buffer.samples().chunks(channels) {
+                let mono = frame.iter().sum::<f32>() / frame.len() as f32;
+                sum_squares += (mono * mono) as f64;
+                frames += 1;
+                if frames == window {
+                    rms.push((sum_squares / frames as f64).sqrt() as f32);
+                    (sum_squares, frames) = (0.0, 0);
+                }
+            }
+        }
+        if frames > 0 {
+            rms.push((sum_squares / frames as f64).sqrt() as f32);
+        }
+        if rms.is_empty() {
+            return Err(AppError::InvalidInput("No audio could be decoded".to_string()));
+        }
+        Ok(AmplitudeEnvelope { rms })
+    }
+
+    pub fn generate(data: &[u8], file_extension: &str) -> Result<Waveform, AppError> {
+        let envelope = Self::envelope_from_bytes(data, file_extension)?;
+        Ok(Waveform {
+            peaks: envelope.peaks(WAVEFORM_PEAKS),
+            duration_ms: envelope.duration_ms(),
+            chapter_markers: envelope.chapter_markers(),
+        })
+    }
+
+    /// Detect the song's sections in the background and store them. Decoding
+    /// runs on the blocking pool; a failure only leaves the song without
+    /// markers.
+    pub fn spawn_chapter_detection(
+        song_repository: Arc<dyn SongRepository>,
+        song_id: SongId,
+        audio: Bytes,
+        file_extension: String,
+    ) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let detected = tokio::task::spawn_blocking(move || {
+                Self::envelope_from_bytes(&audio, &file_extension).map(|envelope| envelope.chapter_markers())
+            })
+            .await;
+            let markers = match detected {
+                Ok(Ok(markers)) => markers,
+                Ok(Err(e)) => {
+                    tracing::warn!("Chapter detection failed for song {}: {}", song_id, e);
+                    return;
+                }
+                Err(e) => {
+                    tracing::error!("Chapter detection task for song {} panicked: {}", song_id, e);
+                    return;
+                }
+            };
+            match song_repository.save_chapter_markers(&song_id, &markers).await {
+                Ok(()) => tracing::info!("Detected {} chapter markers for song {}", markers.len(), song_id),
+                Err(e) => tracing::warn!("Failed to store chapter markers for song {}: {:?}", song_id, e),
+            }
+        })
+    }
+}
+
+fn section_labels(count: usize) -> Vec<&'static str> {
+    if count < 2 {
+        return vec!["verse"; count];
+    }
+    let middle = count - 2;
+    let mut labels = Vec::with_capacity(count);
+    labels.push("intro");
+    for i in 0..middle {
+        labels.push(if middle >= 4 && i == middle - 2 {
+            "bridge"
+        } else if i % 2 == 0 {
+            "verse"
+        } else {
+            "chorus"
+        });
+    }
+    labels.push("outro");
+    labels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Four loud sections split by 2.5 s quiet stretches at 8.5, 16.5 and
+    /// 24.5 s, plus a 1 s dip at 19 s inside the third one
+    const SECTIONS_FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/audio/sections.wav");
+
+    fn envelope_of(sections: &[(u64, f32)]) -> AmplitudeEnvelope {
+        let rms = sections
+            .iter()
+            .flat_map(|&(ms, level)| std::iter::repeat(level).take((ms / ENVELOPE_WINDOW_MS) as usize))
+            .collect();
+        AmplitudeEnvelope { rms }
+    }
+
+    #[test]
+    fn detects_the_fixture_sections() {
+        let audio = std::fs::read(SECTIONS_FIXTURE).unwrap();
+        let waveform = WaveformGenerator::generate(&audio, "wav").unwrap();
+
+        assert_eq!(waveform.duration_ms, 28_000);
+        assert_eq!(
+            waveform.chapter_markers,
+            vec![
+                ChapterMarker::new(0, "intro"),
+                ChapterMarker::new(8_500, "verse"),
+                ChapterMarker::new(16_500, "chorus"),
+                ChapterMarker::new(24_500, "outro"),
+            ]
+        );
+        assert_eq!(waveform.peaks.len(), WAVEFORM_PEAKS);
+        assert!(waveform.peaks.iter().all(|peak| (0.0..=1.0).contains(peak)));
+    }
+
+    #[test]
+    fn short_dips_and_edge_silence_are_not_boundaries() {
+        let envelope = envelope_of(&[(3_000, 0.0), (10_000, 0.5), (2_000, 0.01), (10_000, 0.5), (4_000, 0.0)]);
+        assert!(envelope.chapter_markers().is_empty());
+
+        let envelope = envelope_of(&[(10_000, 0.5), (2_100, 0.01), (10_000, 0.5)]);
+        assert_eq!(envelope.chapter_markers(), vec![ChapterMarker::new(0, "intro"), ChapterMarker::new(12_100, "outro")]);
+    }
+
+    #[test]
+    fn silent_track_has_no_sections() {
+        assert!(envelope_of(&[(30_000, 0.0)]).chapter_markers().is_empty());
+        assert!(AmplitudeEnvelope { rms: Vec::new() }.chapter_markers().is_empty());
+    }
+
+    #[test]
+    fn long_songs_get_a_bridge_before_the_last_chorus() {
+        assert_eq!(section_labels(6), vec!["intro", "verse", "chorus", "bridge", "chorus", "outro"]);
+        assert_eq!(section_labels(5), vec!["intro", "verse", "chorus", "verse", "outro"]);
+        assert_eq!(section_labels(2), vec!["intro", "outro"]);
+    }
+}
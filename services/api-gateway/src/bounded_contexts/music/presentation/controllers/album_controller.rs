@@ -8,8 +8,12 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
 use crate::shared::infrastructure::app_state::MusicAppState;
-use crate::shared::infrastructure::auth::AuthenticatedUser;
-use crate::bounded_contexts::music::domain::repositories::AlbumRepository;
+use crate::shared::infrastructure::auth::{authorize_owner, authorize_role, AuthenticatedUser, OwnedResource};
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::user::domain::UserRole;
+use crate::bounded_contexts::music::domain::repositories::{AlbumRepository, SongRepository};
+use crate::bounded_contexts::music::domain::value_objects::SongId;
+use crate::bounded_contexts::orchestrator::DomainEvent;
 
 // =============================================================================
 // REQUEST/RESPONSE DTOs
@@ -21,6 +25,9 @@ pub struct CreateAlbumRequest {
     pub artist_id: Uuid,
     pub description: Option<String>,
     pub release_date: Option<DateTime<Utc>>,
+    /// Songs of the album in track order
+    #[serde(default)]
+    pub song_ids: Vec<Uuid>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -56,6 +63,13 @@ pub struct AlbumListResponse {
     pub offset: usize,
 }
 
+fn forbidden(error: AppError) -> (StatusCode, ResponseJson<serde_json::Value>) {
+    (StatusCode::FORBIDDEN, ResponseJson(serde_json::json!({
+        "error": "Forbidden",
+        "message": error.to_string()
+    })))
+}
+
 // =============================================================================
 // ALBUM CONTROLLER
 // =============================================================================
@@ -125,31 +139,13 @@ impl AlbumController {
     /// POST /api/v1/music/albums - Create a new album
     /// Requires authentication - only artists can create albums
     pub async fn create_album(
-        AuthenticatedUser { user_id, role, .. }: AuthenticatedUser,
+        user: AuthenticatedUser,
         State(state): State<MusicAppState>,
         axum::extract::Json(request): axum::extract::Json<CreateAlbumRequest>,
     ) -> Result<ResponseJson<AlbumResponse>, (StatusCode, ResponseJson<serde_json::Value>)> {
-        // Validate that user is an artist or admin
-        if role != "artist" && role != "admin" {
-            return Err((
-                StatusCode::FORBIDDEN,
-                ResponseJson(serde_json::json!({
-                    "error": "Forbidden",
-                    "message": "Only artists can create albums"
-                })),
-            ));
-        }
-
-        // Validate that the artist_id matches the authenticated user (unless admin)
-        if role != "admin" && request.artist_id != user_id {
-            return Err((
-                StatusCode::FORBIDDEN,
-                ResponseJson(serde_json::json!({
-                    "error": "Forbidden",
-                    "message": "You can only create albums for yourself"
-                })),
-            ));
-        }
+        // Only artists (or admins) create albums, and only for their own artist_id
+        authorize_role(&user, &[UserRole::Artist]).map_err(forbidden)?;
+        authorize_owner(&user, OwnedResource::Album, request.artist_id).map_err(forbidden)?;
 
         // Validate request
         if request.title.trim().is_empty() {
@@ -162,15 +158,49 @@ impl AlbumController {
             ));
         }
 
+        // Tracks must be songs of this artist, each listed once
+        let mut seen = std::collections::HashSet::new();
+        for song_id in &request.song_ids {
+            if !seen.insert(*song_id) {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    ResponseJson(serde_json::json!({
+                        "error": "Invalid request",
+                        "message": format!("Song {} is listed more than once", song_id)
+                    })),
+                ));
+            }
+            let song = state.song_repository
+                .find_by_id(&SongId::from_uuid(*song_id))
+                .await
+                .map_err(|e| {
+                    tracing::error!("Error fetching song {}: {:?}", song_id, e);
+                    (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
+                        "error": "Failed to create album",
+                        "message": format!("{:?}", e)
+                    })))
+                })?;
+            if song.map(|song| song.artist_id().to_uuid()) != Some(request.artist_id) {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    ResponseJson(serde_json::json!({
+                        "error": "Invalid request",
+                        "message": format!("Song {} does not exist or belongs to another artist", song_id)
+                    })),
+                ));
+            }
+        }
+
         // Create new album entity
         let album_id = Uuid::new_v4();
-        let album = crate::bounded_contexts::music::domain::repositories::album_repository::Album::new(
+        let mut album = crate::bounded_contexts::music::domain::repositories::album_repository::Album::new(
             album_id,
             request.title,
             request.artist_id,
             request.description,
             request.release_date,
         );
+        album.song_count = request.song_ids.len() as u32;
 
         // Save to repository
         state.album_repository
@@ -184,6 +214,18 @@ impl AlbumController {
                 })))
             })?;
 
+        // The album playlist is generated by AlbumCreatedEventHandler
+        let event = DomainEvent::AlbumCreated {
+            album_id: album.id,
+            artist_id: album.artist_id,
+            title: album.title.clone(),
+            song_ids: request.song_ids,
+            occurred_at: album.created_at,
+        };
+        if let Err(e) = state.app_state.publish_event(event).await {
+            tracing::warn!("Failed to publish album created event: {:?}", e);
+        }
+
         // Return response
         let response = AlbumResponse {
             album_id: album.id,
@@ -239,7 +281,7 @@ impl AlbumController {
     /// PUT /api/v1/music/albums/:id - Update album by ID
     /// Requires authentication - only album owner or admin can update
     pub async fn update_album(
-        AuthenticatedUser { user_id, role, .. }: AuthenticatedUser,
+        user: AuthenticatedUser,
         State(state): State<MusicAppState>,
         Path(album_id): Path<Uuid>,
         axum::extract::Json(request): axum::extract::Json<UpdateAlbumRequest>,
@@ -263,15 +305,7 @@ impl AlbumController {
             })?;
 
         // Validate permissions: only album owner (artist) or admin can update
-        if role != "admin" && album.artist_id != user_id {
-            return Err((
-                StatusCode::FORBIDDEN,
-                ResponseJson(serde_json::json!({
-                    "error": "Forbidden",
-                    "message": "You can only update your own albums"
-                })),
-            ));
-        }
+        authorize_owner(&user, OwnedResource::Album, album.artist_id).map_err(forbidden)?;
 
         // Update fields if provided
         if let Some(title) = request.title {
@@ -326,7 +360,7 @@ impl AlbumController {
     /// DELETE /api/v1/music/albums/:id - Delete album by ID
     /// Requires authentication - only album owner or admin can delete
     pub async fn delete_album(
-        AuthenticatedUser { user_id, role, .. }: AuthenticatedUser,
+        user: AuthenticatedUser,
         State(state): State<MusicAppState>,
         Path(album_id): Path<Uuid>,
     ) -> Result<ResponseJson<serde_json::Value>, (StatusCode, ResponseJson<serde_json::Value>)> {
@@ -349,15 +383,7 @@ impl AlbumController {
             })?;
 
         // Validate permissions: only album owner (artist) or admin can delete
-        if role != "admin" && album.artist_id != user_id {
-            return Err((
-                StatusCode::FORBIDDEN,
-                ResponseJson(serde_json::json!({
-                    "error": "Forbidden",
-                    "message": "You can only delete your own albums"
-                })),
-            ));
-        }
+        authorize_owner(&user, OwnedResource::Album, album.artist_id).map_err(forbidden)?;
 
         // Check if album has songs (optional validation)
         if album.song_count > 0 {
@@ -1,13 +1,19 @@
 use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::Json as ResponseJson,
+    extract::{Path, Query, State},
+    http::{header, HeaderValue, StatusCode},
+    response::{IntoResponse, Json as ResponseJson},
 };
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
+use crate::bounded_contexts::music::infrastructure::repositories::ArtistFollower;
+use crate::shared::domain::errors::AppError;
 use crate::shared::infrastructure::app_state::MusicAppState;
+use crate::shared::infrastructure::auth::{authorize_owner, AuthenticatedUser, OwnedResource};
+
+/// The dashboard aggregates several contexts; a minute of staleness is fine
+const DASHBOARD_CACHE_CONTROL: &str = "private, max-age=60";
 
 // =============================================================================
 // REQUEST/RESPONSE DTOs
@@ -27,6 +33,23 @@ pub struct ArtistResponse {
     pub updated_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct ArtistFollowersQuery {
+    /// Starts at 0
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ArtistFollowersResponse {
+    pub artist_id: Uuid,
+    pub followers: Vec<ArtistFollower>,
+    pub follower_count: u64,
+    pub page: u32,
+    pub page_size: u32,
+    pub has_next_page: bool,
+}
+
 // =============================================================================
 // ARTIST CONTROLLER
 // =============================================================================
@@ -139,4 +162,60 @@ impl ArtistController {
             "total": albums.len()
         })))
     }
+
+    /// GET /api/v1/music/artists/:id/followers?page=0&page_size=20 - Users following the artist
+    pub async fn get_artist_followers(
+        State(state): State<MusicAppState>,
+        Path(artist_id): Path<Uuid>,
+        Query(query): Query<ArtistFollowersQuery>,
+    ) -> Result<ResponseJson<ArtistFollowersResponse>, (StatusCode, ResponseJson<serde_json::Value>)> {
+        let page = query.page.unwrap_or(0);
+        let page_size = query.page_size.unwrap_or(20).clamp(1, 100);
+        let database_error = |e| {
+            tracing::error!("Error fetching followers of artist {}: {:?}", artist_id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
+                "error": "Failed to fetch artist followers"
+            })))
+        };
+
+        let follower_count = state.artist_followers
+            .follower_count(artist_id)
+            .await
+            .map_err(database_error)?
+            .ok_or_else(|| (StatusCode::NOT_FOUND, ResponseJson(serde_json::json!({
+                "error": "Artist not found"
+            }))))?;
+        let followers = state.artist_followers
+            .followers(artist_id, page, page_size)
+            .await
+            .map_err(database_error)?;
+
+        Ok(ResponseJson(ArtistFollowersResponse {
+            artist_id,
+            has_next_page: (page as u64 + 1) * (page_size as u64) < follower_count,
+            followers,
+            follower_count,
+            page,
+            page_size,
+        }))
+    }
+
+    /// GET /api/v1/music/artists/:id/dashboard - KPIs of the artist in one response
+    ///
+    /// Requires authentication - only the artist or admin
+    pub async fn get_artist_dashboard(
+        user: AuthenticatedUser,
+        State(state): State<MusicAppState>,
+        Path(artist_id): Path<Uuid>,
+    ) -> Result<impl IntoResponse, AppError> {
+        let not_found = || AppError::NotFound(format!("Artist with ID {} not found", artist_id));
+        let owner_id = state.artist_dashboard.artist_user_id(artist_id).await?.ok_or_else(not_found)?;
+        authorize_owner(&user, OwnedResource::Artist, owner_id)?;
+
+        let dashboard = state.artist_dashboard.dashboard(artist_id).await?.ok_or_else(not_found)?;
+        Ok((
+            [(header::CACHE_CONTROL, HeaderValue::from_static(DASHBOARD_CACHE_CONTROL))],
+            ResponseJson(dashboard),
+        ))
+    }
 }
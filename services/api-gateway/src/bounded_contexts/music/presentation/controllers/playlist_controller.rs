@@ -8,8 +8,19 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
 use crate::shared::infrastructure::app_state::MusicAppState;
-use crate::shared::infrastructure::auth::AuthenticatedUser;
-use crate::bounded_contexts::music::domain::repositories::PlaylistRepository;
+use crate::shared::infrastructure::auth::{authorize_owner, AuthenticatedUser, OwnedResource, Principal};
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::music::domain::repositories::{
+    playlist_repository::Playlist, PlaylistEditEvent, PlaylistRepository, SongRepository,
+};
+use crate::bounded_contexts::music::domain::services::{MoodPlaylistGenerator, PlaylistRecommendationEngine, RecommendationResult};
+use crate::bounded_contexts::music::domain::value_objects::{AlbumId, SongMood};
+
+/// Songs considered when generating a playlist by mood
+const MOOD_PLAYLIST_CANDIDATES: usize = 1000;
+const DEFAULT_MOOD_PLAYLIST_SIZE: usize = 25;
+const DEFAULT_RECOMMENDATIONS: usize = 10;
+const MAX_RECOMMENDATIONS: usize = 50;
 
 // =============================================================================
 // REQUEST/RESPONSE DTOs
@@ -20,6 +31,27 @@ pub struct CreatePlaylistRequest {
     pub name: String,
     pub description: Option<String>,
     pub is_public: bool,
+    /// Lets followers add and remove songs
+    #[serde(default)]
+    pub is_collaborative: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GeneratePlaylistRequest {
+    pub mood: String,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub is_public: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GeneratedPlaylistResponse {
+    #[serde(flatten)]
+    pub playlist: PlaylistResponse,
+    pub mood: String,
+    pub song_ids: Vec<Uuid>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -33,8 +65,11 @@ pub struct PlaylistResponse {
     pub name: String,
     pub description: Option<String>,
     pub is_public: bool,
+    pub is_collaborative: bool,
     pub song_count: u32,
     pub created_by: Uuid,
+    pub album_id: Option<Uuid>,
+    pub is_auto_generated: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -43,6 +78,27 @@ pub struct PlaylistResponse {
 pub struct PlaylistQuery {
     pub limit: Option<usize>,
     pub offset: Option<usize>,
+    /// Only playlists generated from this album
+    pub album_id: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RecommendedPlaylistsQuery {
+    /// Defaults to the authenticated user; only admins can ask for someone else
+    pub user_id: Option<Uuid>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecommendedPlaylistsResponse {
+    pub user_id: Uuid,
+    pub recommendations: Vec<RecommendationResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PlaylistHistoryResponse {
+    pub playlist_id: Uuid,
+    pub edits: Vec<PlaylistEditEvent>,
 }
 
 #[derive(Debug, Serialize)]
@@ -53,6 +109,42 @@ pub struct PlaylistListResponse {
     pub offset: usize,
 }
 
+fn forbidden(error: AppError) -> (StatusCode, ResponseJson<serde_json::Value>) {
+    (StatusCode::FORBIDDEN, ResponseJson(serde_json::json!({
+        "error": "Forbidden",
+        "message": error.to_string()
+    })))
+}
+
+/// The creator (or an admin) can always edit; followers only when the
+/// playlist is collaborative
+async fn authorize_edit(
+    state: &MusicAppState,
+    user: &AuthenticatedUser,
+    playlist: &Playlist,
+) -> Result<(), (StatusCode, ResponseJson<serde_json::Value>)> {
+    if authorize_owner(user, OwnedResource::Playlist, playlist.created_by).is_ok() {
+        return Ok(());
+    }
+    let follows = playlist.is_collaborative
+        && state.playlist_repository
+            .is_follower(&playlist.id, &user.user_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Error checking playlist follower: {:?}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
+                    "error": "Failed to check playlist permissions",
+                    "message": format!("{:?}", e)
+                })))
+            })?;
+    if playlist.can_be_edited_by(user.user_id, follows) {
+        return Ok(());
+    }
+    Err(forbidden(AppError::Forbidden(
+        "Only the creator, or followers of a collaborative playlist, can edit it".to_string(),
+    )))
+}
+
 // =============================================================================
 // PLAYLIST CONTROLLER
 // =============================================================================
@@ -70,29 +162,47 @@ impl PlaylistController {
         let page = (offset / limit) + 1;
         let page_size = limit;
         
-        // Get playlists from repository
-        let playlists = state.playlist_repository
-            .find_all(page, page_size)
-            .await
-            .map_err(|e| {
-                tracing::error!("Error fetching playlists: {:?}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
-                    "error": "Failed to fetch playlists",
-                    "message": format!("{:?}", e)
-                })))
-            })?;
-        
-        // Get total count for pagination
-        let total = state.playlist_repository
-            .count()
-            .await
-            .map_err(|e| {
-                tracing::error!("Error counting playlists: {:?}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
-                    "error": "Failed to count playlists",
-                    "message": format!("{:?}", e)
-                })))
-            })? as usize;
+        let fetch_error = |e| {
+            tracing::error!("Error fetching playlists: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
+                "error": "Failed to fetch playlists",
+                "message": format!("{:?}", e)
+            })))
+        };
+
+        let (playlists, total) = if let Some(album_id) = query.album_id {
+            // Album playlists are few: filter, then paginate in memory
+            let album_playlists = state.playlist_repository
+                .find_by_album(&AlbumId::from_uuid(album_id))
+                .await
+                .map_err(fetch_error)?;
+            let total = album_playlists.len();
+            let playlists = album_playlists
+                .into_iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .collect::<Vec<_>>();
+            (playlists, total)
+        } else {
+            // Get playlists from repository
+            let playlists = state.playlist_repository
+                .find_all(page, page_size)
+                .await
+                .map_err(fetch_error)?;
+
+            // Get total count for pagination
+            let total = state.playlist_repository
+                .count()
+                .await
+                .map_err(|e| {
+                    tracing::error!("Error counting playlists: {:?}", e);
+                    (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
+                        "error": "Failed to count playlists",
+                        "message": format!("{:?}", e)
+                    })))
+                })? as usize;
+            (playlists, total)
+        };
         
         // Convert to response DTOs
         let playlist_responses: Vec<PlaylistResponse> = playlists
@@ -102,8 +212,11 @@ impl PlaylistController {
                 name: playlist.name,
                 description: playlist.description,
                 is_public: playlist.is_public,
+                is_collaborative: playlist.is_collaborative,
                 song_count: playlist.song_count,
                 created_by: playlist.created_by,
+                album_id: playlist.album_id.as_ref().map(|id| id.to_uuid()),
+                is_auto_generated: playlist.is_auto_generated,
                 created_at: playlist.created_at,
                 updated_at: playlist.updated_at,
             })
@@ -142,13 +255,14 @@ impl PlaylistController {
 
         // Create new playlist entity
         let playlist_id = Uuid::new_v4();
-        let playlist = crate::bounded_contexts::music::domain::repositories::playlist_repository::Playlist::new(
+        let mut playlist = Playlist::new(
             playlist_id,
             request.name,
             request.description,
             request.is_public,
             created_by,
         );
+        playlist.is_collaborative = request.is_collaborative;
 
         // Save to repository
         state.playlist_repository
@@ -168,8 +282,11 @@ impl PlaylistController {
             name: playlist.name,
             description: playlist.description,
             is_public: playlist.is_public,
+                is_collaborative: playlist.is_collaborative,
             song_count: playlist.song_count,
             created_by: playlist.created_by,
+            album_id: playlist.album_id.as_ref().map(|id| id.to_uuid()),
+            is_auto_generated: playlist.is_auto_generated,
             created_at: playlist.created_at,
             updated_at: playlist.updated_at,
         };
@@ -177,6 +294,93 @@ impl PlaylistController {
         Ok(ResponseJson(response))
     }
     
+    /// POST /api/v1/music/playlists/generate - Create a playlist of the songs
+    /// closest to a mood (detected from audio features, or the tagged mood)
+    pub async fn generate_playlist(
+        AuthenticatedUser { user_id, .. }: AuthenticatedUser,
+        State(state): State<MusicAppState>,
+        axum::extract::Json(request): axum::extract::Json<GeneratePlaylistRequest>,
+    ) -> Result<ResponseJson<GeneratedPlaylistResponse>, (StatusCode, ResponseJson<serde_json::Value>)> {
+        let mood = SongMood::from_string(&request.mood).map_err(|e| {
+            (StatusCode::BAD_REQUEST, ResponseJson(serde_json::json!({
+                "error": "Invalid request",
+                "message": e
+            })))
+        })?;
+        let limit = request.limit.unwrap_or(DEFAULT_MOOD_PLAYLIST_SIZE).clamp(1, 100);
+
+        let candidates = state.song_repository
+            .find_all(MOOD_PLAYLIST_CANDIDATES, 0)
+            .await
+            .map_err(|e| {
+                tracing::error!("Error fetching songs for mood playlist: {:?}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
+                    "error": "Failed to generate playlist",
+                    "message": format!("{:?}", e)
+                })))
+            })?;
+
+        let song_ids: Vec<Uuid> = MoodPlaylistGenerator::default()
+            .select(&mood, &candidates, limit)
+            .into_iter()
+            .map(|song| song.id().to_uuid())
+            .collect();
+        if song_ids.is_empty() {
+            return Err((
+                StatusCode::NOT_FOUND,
+                ResponseJson(serde_json::json!({
+                    "error": "No songs found",
+                    "message": format!("No songs match the mood {}", mood)
+                })),
+            ));
+        }
+
+        let name = request
+            .name
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| format!("{} mix", mood));
+        let mut playlist = Playlist::new(
+            Uuid::new_v4(),
+            name,
+            request.description,
+            request.is_public,
+            user_id,
+        );
+
+        let db_error = |e| {
+            tracing::error!("Error saving generated playlist: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
+                "error": "Failed to generate playlist",
+                "message": format!("{:?}", e)
+            })))
+        };
+        state.playlist_repository.save(&playlist).await.map_err(db_error)?;
+        for song_id in &song_ids {
+            state.playlist_repository.add_song(&playlist.id, song_id, &user_id).await.map_err(db_error)?;
+        }
+        playlist.song_count = song_ids.len() as u32;
+        playlist.updated_at = Utc::now();
+        state.playlist_repository.update(&playlist).await.map_err(db_error)?;
+
+        Ok(ResponseJson(GeneratedPlaylistResponse {
+            playlist: PlaylistResponse {
+                playlist_id: playlist.id,
+                name: playlist.name,
+                description: playlist.description,
+                is_public: playlist.is_public,
+                is_collaborative: playlist.is_collaborative,
+                song_count: playlist.song_count,
+                created_by: playlist.created_by,
+                album_id: playlist.album_id.as_ref().map(|id| id.to_uuid()),
+                is_auto_generated: playlist.is_auto_generated,
+                created_at: playlist.created_at,
+                updated_at: playlist.updated_at,
+            },
+            mood: mood.to_string(),
+            song_ids,
+        }))
+    }
+
     /// GET /api/v1/music/playlists/:id - Get playlist by ID
     pub async fn get_playlist(
         State(state): State<MusicAppState>,
@@ -205,8 +409,11 @@ impl PlaylistController {
             name: playlist.name,
             description: playlist.description,
             is_public: playlist.is_public,
+                is_collaborative: playlist.is_collaborative,
             song_count: playlist.song_count,
             created_by: playlist.created_by,
+            album_id: playlist.album_id.as_ref().map(|id| id.to_uuid()),
+            is_auto_generated: playlist.is_auto_generated,
             created_at: playlist.created_at,
             updated_at: playlist.updated_at,
         };
@@ -215,9 +422,9 @@ impl PlaylistController {
     }
     
     /// POST /api/v1/music/playlists/:id/songs - Add song to playlist
-    /// Requires authentication - only playlist owner can add songs
+    /// Requires authentication - the owner, or followers of a collaborative playlist
     pub async fn add_song_to_playlist(
-        AuthenticatedUser { user_id, .. }: AuthenticatedUser,
+        user: AuthenticatedUser,
         State(state): State<MusicAppState>,
         Path(playlist_id): Path<Uuid>,
         axum::extract::Json(request): axum::extract::Json<AddSongToPlaylistRequest>,
@@ -240,20 +447,11 @@ impl PlaylistController {
                 })))
             })?;
 
-        // Verify ownership (only creator can add songs)
-        if playlist.created_by != user_id {
-            return Err((
-                StatusCode::FORBIDDEN,
-                ResponseJson(serde_json::json!({
-                    "error": "Forbidden",
-                    "message": "Only the playlist owner can add songs"
-                })),
-            ));
-        }
+        authorize_edit(&state, &user, &playlist).await?;
 
         // Add song to playlist
         state.playlist_repository
-            .add_song(&playlist_id, &request.song_id)
+            .add_song(&playlist_id, &request.song_id, &user.user_id)
             .await
             .map_err(|e| {
                 tracing::error!("Error adding song to playlist: {:?}", e);
@@ -287,9 +485,9 @@ impl PlaylistController {
     }
     
     /// DELETE /api/v1/music/playlists/:id/songs/:song_id - Remove song from playlist
-    /// Requires authentication - only playlist owner can remove songs
+    /// Requires authentication - the owner, or followers of a collaborative playlist
     pub async fn remove_song_from_playlist(
-        AuthenticatedUser { user_id, .. }: AuthenticatedUser,
+        user: AuthenticatedUser,
         State(state): State<MusicAppState>,
         Path((playlist_id, song_id)): Path<(Uuid, Uuid)>,
     ) -> Result<ResponseJson<serde_json::Value>, (StatusCode, ResponseJson<serde_json::Value>)> {
@@ -311,16 +509,7 @@ impl PlaylistController {
                 })))
             })?;
 
-        // Verify ownership (only creator can remove songs)
-        if playlist.created_by != user_id {
-            return Err((
-                StatusCode::FORBIDDEN,
-                ResponseJson(serde_json::json!({
-                    "error": "Forbidden",
-                    "message": "Only the playlist owner can remove songs"
-                })),
-            ));
-        }
+        authorize_edit(&state, &user, &playlist).await?;
 
         // Verify song exists in playlist
         let playlist_songs = state.playlist_repository
@@ -346,7 +535,7 @@ impl PlaylistController {
 
         // Remove song from playlist
         state.playlist_repository
-            .remove_song(&playlist_id, &song_id)
+            .remove_song(&playlist_id, &song_id, &user.user_id)
             .await
             .map_err(|e| {
                 tracing::error!("Error removing song from playlist: {:?}", e);
@@ -380,4 +569,143 @@ impl PlaylistController {
             "song_id": song_id
         })))
     }
+
+    /// GET /api/v1/music/playlists/:id/history - Songs added and removed, and by whom
+    /// Public playlists show it to anyone; private ones only to their editors
+    pub async fn get_playlist_history(
+        user: AuthenticatedUser,
+        State(state): State<MusicAppState>,
+        Path(playlist_id): Path<Uuid>,
+    ) -> Result<ResponseJson<PlaylistHistoryResponse>, (StatusCode, ResponseJson<serde_json::Value>)> {
+        let playlist = state.playlist_repository
+            .find_by_id(&playlist_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Error fetching playlist: {:?}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
+                    "error": "Failed to fetch playlist",
+                    "message": format!("{:?}", e)
+                })))
+            })?
+            .ok_or_else(|| {
+                (StatusCode::NOT_FOUND, ResponseJson(serde_json::json!({
+                    "error": "Playlist not found",
+                    "message": format!("Playlist with ID {} not found", playlist_id)
+                })))
+            })?;
+
+        if !playlist.is_public {
+            authorize_edit(&state, &user, &playlist).await?;
+        }
+
+        let edits = state.playlist_repository
+            .edit_history(&playlist_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Error fetching playlist history: {:?}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
+                    "error": "Failed to fetch playlist history",
+                    "message": format!("{:?}", e)
+                })))
+            })?;
+
+        Ok(ResponseJson(PlaylistHistoryResponse { playlist_id, edits }))
+    }
+
+    /// GET /api/v1/music/playlists/recommended?user_id={id}&limit=10
+    /// Playlists followed by listeners with similar taste, or popular ones
+    /// when the user has no listening history yet
+    pub async fn get_recommended_playlists(
+        user: AuthenticatedUser,
+        State(state): State<MusicAppState>,
+        Query(query): Query<RecommendedPlaylistsQuery>,
+    ) -> Result<ResponseJson<RecommendedPlaylistsResponse>, (StatusCode, ResponseJson<serde_json::Value>)> {
+        let target_user = query.user_id.unwrap_or(user.user_id);
+        if target_user != user.user_id && !user.is_admin() {
+            return Err(forbidden(AppError::Forbidden("You can only see your own recommendations".to_string())));
+        }
+
+        let limit = query.limit.unwrap_or(DEFAULT_RECOMMENDATIONS).clamp(1, MAX_RECOMMENDATIONS);
+        let recommendations = state.playlist_recommender
+            .recommend(target_user, limit)
+            .await
+            .map_err(|e| {
+                tracing::error!("Error recommending playlists for {}: {:?}", target_user, e);
+                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
+                    "error": "Failed to recommend playlists",
+                    "message": format!("{:?}", e)
+                })))
+            })?;
+
+        Ok(ResponseJson(RecommendedPlaylistsResponse {
+            user_id: target_user,
+            recommendations,
+        }))
+    }
+
+    /// POST /api/v1/music/playlists/:id/follow - Follow a public playlist
+    pub async fn follow_playlist(
+        AuthenticatedUser { user_id, .. }: AuthenticatedUser,
+        State(state): State<MusicAppState>,
+        Path(playlist_id): Path<Uuid>,
+    ) -> Result<ResponseJson<serde_json::Value>, (StatusCode, ResponseJson<serde_json::Value>)> {
+        let playlist = state.playlist_repository
+            .find_by_id(&playlist_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Error fetching playlist: {:?}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
+                    "error": "Failed to fetch playlist",
+                    "message": format!("{:?}", e)
+                })))
+            })?
+            .filter(|playlist| playlist.is_public || playlist.created_by == user_id)
+            .ok_or_else(|| {
+                (StatusCode::NOT_FOUND, ResponseJson(serde_json::json!({
+                    "error": "Playlist not found",
+                    "message": format!("Playlist with ID {} not found", playlist_id)
+                })))
+            })?;
+
+        state.playlist_repository
+            .follow(&playlist.id, &user_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Error following playlist: {:?}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
+                    "error": "Failed to follow playlist",
+                    "message": format!("{:?}", e)
+                })))
+            })?;
+
+        Ok(ResponseJson(serde_json::json!({
+            "success": true,
+            "message": "Playlist followed successfully",
+            "playlist_id": playlist_id
+        })))
+    }
+
+    /// DELETE /api/v1/music/playlists/:id/follow - Stop following a playlist
+    pub async fn unfollow_playlist(
+        AuthenticatedUser { user_id, .. }: AuthenticatedUser,
+        State(state): State<MusicAppState>,
+        Path(playlist_id): Path<Uuid>,
+    ) -> Result<ResponseJson<serde_json::Value>, (StatusCode, ResponseJson<serde_json::Value>)> {
+        state.playlist_repository
+            .unfollow(&playlist_id, &user_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Error unfollowing playlist: {:?}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
+                    "error": "Failed to unfollow playlist",
+                    "message": format!("{:?}", e)
+                })))
+            })?;
+
+        Ok(ResponseJson(serde_json::json!({
+            "success": true,
+            "message": "Playlist unfollowed successfully",
+            "playlist_id": playlist_id
+        })))
+    }
 }
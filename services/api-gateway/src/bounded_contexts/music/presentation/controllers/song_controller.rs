@@ -1,6 +1,5 @@
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
     response::Json as ResponseJson,
     Json,
 };
@@ -9,10 +8,15 @@ use uuid::Uuid;
 use std::collections::HashMap;
 
 use crate::shared::infrastructure::app_state::MusicAppState;
-use crate::shared::infrastructure::auth::AuthenticatedUser;
+use crate::shared::infrastructure::auth::{authorize_owner, authorize_role, AuthenticatedUser, OwnedResource};
+use crate::bounded_contexts::user::domain::UserRole;
 use crate::bounded_contexts::music::domain::entities::Song;
-use crate::bounded_contexts::music::domain::value_objects::{SongTitle, SongDuration, Genre, RoyaltyPercentage};
-use crate::bounded_contexts::music::domain::repositories::SongRepository;
+use crate::bounded_contexts::music::domain::value_objects::{SongTitle, SongDuration, Genre, RoyaltyPercentage, ChapterMarker};
+use crate::bounded_contexts::music::domain::entities::{RemixLicense, RemixLicenseType};
+use crate::bounded_contexts::music::domain::repositories::{RemixLicenseRepository, SongRepository};
+use crate::bounded_contexts::music::infrastructure::storage::SignedStemsUrl;
+use crate::bounded_contexts::music::infrastructure::repositories::{AnalyticsPeriod, Granularity, PlayCountDataPoint};
+use crate::bounded_contexts::music::infrastructure::search::SongSearchResult;
 use crate::bounded_contexts::orchestrator::DomainEvent;
 use crate::shared::domain::errors::AppError;
 
@@ -27,6 +31,9 @@ pub struct CreateSongRequest {
     pub duration_seconds: u32,
     pub genre: String,
     pub royalty_percentage: f64,
+    /// Only scanned by content moderation together with the title
+    #[serde(default)]
+    pub description: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -37,6 +44,8 @@ pub struct CreateSongResponse {
     pub duration_seconds: u32,
     pub genre: String,
     pub royalty_percentage: f64,
+    /// Content moderation flagged the metadata; the song stays hidden until reviewed
+    pub is_pending_review: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -50,10 +59,25 @@ pub struct SongResponse {
     pub royalty_percentage: f64,
     pub listen_count: u64,
     pub revenue_generated: f64,
+    pub has_stems: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct SongCreditResponse {
+    pub contributor_id: Uuid,
+    pub credit_type: String,
+    pub royalty_share: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SongCreditsResponse {
+    pub song_id: Uuid,
+    pub credits: Vec<SongCreditResponse>,
+    pub total_credited_share: f64,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct UpdateSongRequest {
     pub title: Option<String>,
@@ -61,6 +85,73 @@ pub struct UpdateSongRequest {
     pub royalty_percentage: Option<f64>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct PurchaseRemixLicenseRequest {
+    /// "standard" o "exclusive"
+    pub license_type: String,
+    /// Por defecto, el plazo del tipo de licencia
+    pub expiry: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RemixLicenseResponse {
+    pub license_id: Uuid,
+    pub song_id: Uuid,
+    pub licensee_id: Uuid,
+    pub license_type: String,
+    pub price: f64,
+    pub expiry: chrono::DateTime<chrono::Utc>,
+    /// Descarga de los stems, solo si la canción los tiene
+    pub stems: Option<SignedStemsUrl>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct StemsDownloadResponse {
+    pub song_id: Uuid,
+    pub license_id: Uuid,
+    pub url: String,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SongAnalyticsQuery {
+    /// Por defecto, el último mes
+    pub period: Option<AnalyticsPeriod>,
+    /// Por defecto, un punto por día
+    pub granularity: Option<Granularity>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SongAnalyticsResponse {
+    pub song_id: Uuid,
+    pub period: AnalyticsPeriod,
+    pub granularity: Granularity,
+    pub total_plays: u64,
+    pub data_points: Vec<PlayCountDataPoint>,
+}
+
+/// Máximo de canciones similares por petición
+const MAX_SIMILAR_SONGS: usize = 50;
+
+#[derive(Debug, Deserialize)]
+pub struct SimilarSongsQuery {
+    /// Por defecto, 10
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SimilarSongsResponse {
+    pub song_id: Uuid,
+    pub songs: Vec<SongSearchResult>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SongChaptersResponse {
+    pub song_id: Uuid,
+    /// `None` while the uploaded audio is still being analysed
+    pub chapters: Option<Vec<ChapterMarker>>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct SongQuery {
     pub genre: Option<String>,
@@ -91,7 +182,7 @@ impl SongController {
     pub async fn get_songs(
         State(state): State<MusicAppState>,
         Query(query): Query<SongQuery>,
-    ) -> Result<ResponseJson<SongListResponse>, (StatusCode, ResponseJson<serde_json::Value>)> {
+    ) -> Result<ResponseJson<SongListResponse>, AppError> {
         let limit = query.limit.unwrap_or(20);
         let offset = query.offset.unwrap_or(0);
         
@@ -100,61 +191,27 @@ impl SongController {
             // Search by title
             state.song_repository
                 .search_by_title(search_query, Some(limit + offset))
-                .await
-                .map_err(|e| {
-                    tracing::error!("Error searching songs: {:?}", e);
-                    (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
-                        "error": "Failed to search songs",
-                        "message": format!("{:?}", e)
-                    })))
-                })?
+                .await?
         } else if let Some(artist_id) = query.artist_id {
             // Filter by artist
             use crate::bounded_contexts::music::domain::ArtistId;
             let artist_id_vo = ArtistId::from_uuid(artist_id);
             state.song_repository
                 .find_by_artist(&artist_id_vo)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Error fetching songs by artist: {:?}", e);
-                    (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
-                        "error": "Failed to fetch songs",
-                        "message": format!("{:?}", e)
-                    })))
-                })?
+                .await?
         } else if let Some(genre_str) = &query.genre {
             // Filter by genre
             use crate::bounded_contexts::music::domain::Genre;
             let genre = Genre::new(genre_str.clone())
-                .map_err(|e| {
-                    tracing::error!("Invalid genre: {:?}", e);
-                    (StatusCode::BAD_REQUEST, ResponseJson(serde_json::json!({
-                        "error": "Invalid genre",
-                        "message": format!("{:?}", e)
-                    })))
-                })?;
+                .map_err(AppError::ValidationError)?;
             state.song_repository
                 .find_by_genre(&genre)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Error fetching songs by genre: {:?}", e);
-                    (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
-                        "error": "Failed to fetch songs",
-                        "message": format!("{:?}", e)
-                    })))
-                })?
+                .await?
         } else {
             // Get all songs with pagination
             state.song_repository
                 .find_all(limit, offset)
-                .await
-                .map_err(|e| {
-                    tracing::error!("Error fetching songs: {:?}", e);
-                    (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
-                        "error": "Failed to fetch songs",
-                        "message": format!("{:?}", e)
-                    })))
-                })?
+                .await?
         };
         
         // Apply pagination to filtered results if needed
@@ -168,14 +225,7 @@ impl SongController {
             // For find_all, get total count
             let total = state.song_repository
                 .count()
-                .await
-                .map_err(|e| {
-                    tracing::error!("Error counting songs: {:?}", e);
-                    (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
-                        "error": "Failed to count songs",
-                        "message": format!("{:?}", e)
-                    })))
-                })?;
+                .await?;
             (songs, total)
         };
         
@@ -191,6 +241,7 @@ impl SongController {
                 royalty_percentage: song.royalty_percentage().value(),
                 listen_count: song.listen_count().value(),
                 revenue_generated: song.revenue_generated(),
+                has_stems: song.has_stems(),
                 created_at: song.created_at(),
                 updated_at: song.updated_at(),
             })
@@ -211,70 +262,29 @@ impl SongController {
     /// OpenAPI documentation is in `openapi/paths.rs::_create_song_doc`
     /// Requires authentication - only artists can create songs
     pub async fn create_song(
-        AuthenticatedUser { user_id, role, .. }: AuthenticatedUser,
+        user: AuthenticatedUser,
         State(state): State<MusicAppState>,
         Json(request): Json<CreateSongRequest>,
-    ) -> Result<ResponseJson<CreateSongResponse>, (StatusCode, ResponseJson<serde_json::Value>)> {
-        // Validate that user is an artist or admin
-        if role != "artist" && role != "admin" {
-            return Err((
-                StatusCode::FORBIDDEN,
-                ResponseJson(serde_json::json!({
-                    "error": "Forbidden",
-                    "message": "Only artists can create songs"
-                })),
-            ));
-        }
-
-        // Validate that the artist_id matches the authenticated user (unless admin)
-        if role != "admin" && request.artist_id != user_id {
-            return Err((
-                StatusCode::FORBIDDEN,
-                ResponseJson(serde_json::json!({
-                    "error": "Forbidden",
-                    "message": "You can only create songs for yourself"
-                })),
-            ));
-        }
+    ) -> Result<ResponseJson<CreateSongResponse>, AppError> {
+        // Only artists (or admins) create songs, and only for their own artist_id
+        authorize_role(&user, &[UserRole::Artist])?;
+        authorize_owner(&user, OwnedResource::Song, request.artist_id)?;
+        let user_id = user.user_id;
         // Validate input
         let title = SongTitle::new(request.title.clone())
-            .map_err(|e| {
-                tracing::error!("Invalid song title: {}", e);
-                (StatusCode::BAD_REQUEST, ResponseJson(serde_json::json!({
-                    "error": "Invalid song title",
-                    "message": e
-                })))
-            })?;
+            .map_err(AppError::ValidationError)?;
         
         let duration = SongDuration::new(request.duration_seconds)
-            .map_err(|e| {
-                tracing::error!("Invalid song duration: {}", e);
-                (StatusCode::BAD_REQUEST, ResponseJson(serde_json::json!({
-                    "error": "Invalid song duration",
-                    "message": e
-                })))
-            })?;
+            .map_err(AppError::ValidationError)?;
         
         let genre = Genre::new(request.genre.clone())
-            .map_err(|e| {
-                tracing::error!("Invalid genre: {}", e);
-                (StatusCode::BAD_REQUEST, ResponseJson(serde_json::json!({
-                    "error": "Invalid genre",
-                    "message": e
-                })))
-            })?;
+            .map_err(AppError::ValidationError)?;
         
         let royalty_percentage = RoyaltyPercentage::new(request.royalty_percentage)
-            .map_err(|e| {
-                tracing::error!("Invalid royalty percentage: {}", e);
-                (StatusCode::BAD_REQUEST, ResponseJson(serde_json::json!({
-                    "error": "Invalid royalty percentage",
-                    "message": e
-                })))
-            })?;
+            .map_err(AppError::ValidationError)?;
         
         // Create song entity
-        let song = Song::new(
+        let mut song = Song::new(
             title,
             crate::bounded_contexts::music::domain::value_objects::ArtistId::from_uuid(request.artist_id),
             duration,
@@ -282,17 +292,35 @@ impl SongController {
             royalty_percentage,
         );
         
+        // Scan metadata before persisting; without a verdict the song is not held
+        let moderation = match song.explicit_content_scan(state.content_moderation.as_ref(), request.description.as_deref()).await {
+            Ok(result) => Some(result),
+            Err(e) => {
+                tracing::warn!("Content moderation failed for song {}, publishing without review: {}", song.id().to_uuid(), e);
+                None
+            }
+        };
+        
         // Save to repository
         state.song_repository
             .save(&song)
-            .await
-            .map_err(|e| {
-                tracing::error!("Error saving song: {:?}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
-                    "error": "Failed to save song",
-                    "message": format!("{:?}", e)
-                })))
-            })?;
+            .await?;
+        
+        if let Some(result) = moderation.filter(|result| result.is_flagged) {
+            let flagged = DomainEvent::SongFlaggedForReview {
+                song_id: song.id().to_uuid(),
+                artist_id: song.artist_id().to_uuid(),
+                uploaded_by: user_id,
+                title: song.title().to_string(),
+                categories: result.categories,
+                confidence: result.confidence,
+                occurred_at: chrono::Utc::now(),
+            };
+            
+            if let Err(e) = state.app_state.publish_event(flagged).await {
+                tracing::warn!("Failed to publish song flagged event: {:?}", e);
+            }
+        }
         
         // Publish domain event
         let event = DomainEvent::SongListened {
@@ -307,6 +335,17 @@ impl SongController {
             tracing::warn!("Failed to publish song created event: {:?}", e);
         }
         
+        let uploaded = DomainEvent::SongUploaded {
+            song_id: song.id().to_uuid(),
+            artist_id: song.artist_id().to_uuid(),
+            genre: song.genre().to_string(),
+            occurred_at: chrono::Utc::now(),
+        };
+        
+        if let Err(e) = state.app_state.publish_event(uploaded).await {
+            tracing::warn!("Failed to publish song uploaded event: {:?}", e);
+        }
+        
         let response = CreateSongResponse {
             song_id: song.id().to_uuid(),
             title: song.title().to_string(),
@@ -314,6 +353,7 @@ impl SongController {
             duration_seconds: song.duration().seconds(),
             genre: song.genre().to_string(),
             royalty_percentage: song.royalty_percentage().value(),
+            is_pending_review: song.is_pending_review(),
             created_at: song.created_at(),
         };
         
@@ -326,24 +366,12 @@ impl SongController {
     pub async fn get_song(
         State(state): State<MusicAppState>,
         Path(song_id): Path<Uuid>,
-    ) -> Result<ResponseJson<SongResponse>, (StatusCode, ResponseJson<serde_json::Value>)> {
+    ) -> Result<ResponseJson<SongResponse>, AppError> {
         // Get song from repository
         let song = state.song_repository
             .find_by_id(&crate::bounded_contexts::music::domain::value_objects::SongId::from_uuid(song_id))
-            .await
-            .map_err(|e| {
-                tracing::error!("Error fetching song: {:?}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
-                    "error": "Failed to fetch song",
-                    "message": format!("{:?}", e)
-                })))
-            })?
-            .ok_or_else(|| {
-                (StatusCode::NOT_FOUND, ResponseJson(serde_json::json!({
-                    "error": "Song not found",
-                    "message": format!("Song with ID {} not found", song_id)
-                })))
-            })?;
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Song with ID {} not found", song_id)))?;
         
         let response = SongResponse {
             song_id: song.id().to_uuid(),
@@ -354,6 +382,7 @@ impl SongController {
             royalty_percentage: song.royalty_percentage().value(),
             listen_count: song.listen_count().value(),
             revenue_generated: song.revenue_generated(),
+            has_stems: song.has_stems(),
             created_at: song.created_at(),
             updated_at: song.updated_at(),
         };
@@ -361,93 +390,139 @@ impl SongController {
         Ok(ResponseJson(response))
     }
     
+    /// GET /api/v1/music/songs/:id/credits - Get song credits
+    /// 
+    /// OpenAPI documentation is in `openapi/paths.rs::_get_song_credits_doc`
+    pub async fn get_song_credits(
+        State(state): State<MusicAppState>,
+        Path(song_id): Path<Uuid>,
+    ) -> Result<ResponseJson<SongCreditsResponse>, AppError> {
+        let song = state.song_repository
+            .find_by_id(&crate::bounded_contexts::music::domain::value_objects::SongId::from_uuid(song_id))
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Song with ID {} not found", song_id)))?;
+        
+        let credits = song.credits()
+            .iter()
+            .map(|credit| SongCreditResponse {
+                contributor_id: credit.contributor_id,
+                credit_type: credit.credit_type.to_string(),
+                royalty_share: credit.royalty_share.value(),
+            })
+            .collect();
+        
+        Ok(ResponseJson(SongCreditsResponse {
+            song_id,
+            credits,
+            total_credited_share: song.total_credited_share(),
+        }))
+    }
+    
+    /// GET /api/v1/music/songs/:id/chapters - Sections detected in the audio
+    pub async fn get_song_chapters(
+        State(state): State<MusicAppState>,
+        Path(song_id): Path<Uuid>,
+    ) -> Result<ResponseJson<SongChaptersResponse>, AppError> {
+        let song = state.song_repository
+            .find_by_id(&crate::bounded_contexts::music::domain::value_objects::SongId::from_uuid(song_id))
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Song with ID {} not found", song_id)))?;
+
+        Ok(ResponseJson(SongChaptersResponse {
+            song_id,
+            chapters: song.chapter_markers().map(<[ChapterMarker]>::to_vec),
+        }))
+    }
+    
+    /// GET /api/v1/music/songs/:id/analytics - Play counts over time
+    /// 
+    /// Requires authentication - only song owner or admin
+    pub async fn get_song_analytics(
+        user: AuthenticatedUser,
+        State(state): State<MusicAppState>,
+        Path(song_id): Path<Uuid>,
+        Query(query): Query<SongAnalyticsQuery>,
+    ) -> Result<ResponseJson<SongAnalyticsResponse>, AppError> {
+        let song = state.song_repository
+            .find_by_id(&crate::bounded_contexts::music::domain::value_objects::SongId::from_uuid(song_id))
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Song with ID {} not found", song_id)))?;
+        authorize_owner(&user, OwnedResource::Song, song.artist_id().to_uuid())?;
+
+        let period = query.period.unwrap_or(AnalyticsPeriod::Month);
+        let granularity = query.granularity.unwrap_or(Granularity::Daily);
+        let data_points = state.song_analytics
+            .get_play_count_by_period(song_id, period, granularity)
+            .await?;
+
+        Ok(ResponseJson(SongAnalyticsResponse {
+            song_id,
+            period,
+            granularity,
+            total_plays: data_points.iter().map(|point| point.play_count).sum(),
+            data_points,
+        }))
+    }
+    
+    /// GET /api/v1/music/songs/:id/similar - Related songs by genre, mood, tempo and audio quality
+    pub async fn get_similar_songs(
+        State(state): State<MusicAppState>,
+        Path(song_id): Path<Uuid>,
+        Query(query): Query<SimilarSongsQuery>,
+    ) -> Result<ResponseJson<SimilarSongsResponse>, AppError> {
+        state.song_repository
+            .find_by_id(&crate::bounded_contexts::music::domain::value_objects::SongId::from_uuid(song_id))
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Song with ID {} not found", song_id)))?;
+
+        let limit = query.limit.unwrap_or(10).clamp(1, MAX_SIMILAR_SONGS);
+        let songs = state.song_similarities.get_similar_songs(song_id, limit).await?;
+
+        Ok(ResponseJson(SimilarSongsResponse { song_id, songs }))
+    }
+    
     /// PUT /api/v1/music/songs/:id - Update song
     /// 
     /// OpenAPI documentation is in `openapi/paths.rs::_update_song_doc`
     /// Requires authentication - only song owner or admin can update
     pub async fn update_song(
-        AuthenticatedUser { user_id, role, .. }: AuthenticatedUser,
+        user: AuthenticatedUser,
         State(state): State<MusicAppState>,
         Path(song_id): Path<Uuid>,
         Json(request): Json<UpdateSongRequest>,
-    ) -> Result<ResponseJson<SongResponse>, (StatusCode, ResponseJson<serde_json::Value>)> {
+    ) -> Result<ResponseJson<SongResponse>, AppError> {
         // Get existing song
         let mut song = state.song_repository
             .find_by_id(&crate::bounded_contexts::music::domain::value_objects::SongId::from_uuid(song_id))
-            .await
-            .map_err(|e| {
-                tracing::error!("Error fetching song: {:?}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
-                    "error": "Failed to fetch song",
-                    "message": format!("{:?}", e)
-                })))
-            })?
-            .ok_or_else(|| {
-                (StatusCode::NOT_FOUND, ResponseJson(serde_json::json!({
-                    "error": "Song not found",
-                    "message": format!("Song with ID {} not found", song_id)
-                })))
-            })?;
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Song with ID {} not found", song_id)))?;
 
         // Validate permissions: only song owner (artist) or admin can update
-        if role != "admin" && song.artist_id().to_uuid() != user_id {
-            return Err((
-                StatusCode::FORBIDDEN,
-                ResponseJson(serde_json::json!({
-                    "error": "Forbidden",
-                    "message": "You can only update your own songs"
-                })),
-            ));
-        }
+        authorize_owner(&user, OwnedResource::Song, song.artist_id().to_uuid())?;
         
         // Update fields if provided
         if let Some(title) = request.title {
             let new_title = SongTitle::new(title)
-                .map_err(|e| {
-                    tracing::error!("Invalid song title: {}", e);
-                    (StatusCode::BAD_REQUEST, ResponseJson(serde_json::json!({
-                        "error": "Invalid song title",
-                        "message": e
-                    })))
-                })?;
+                .map_err(AppError::ValidationError)?;
             // TODO: Implement set_title method in Song entity
         }
         
         if let Some(genre) = request.genre {
             let new_genre = Genre::new(genre)
-                .map_err(|e| {
-                    tracing::error!("Invalid genre: {}", e);
-                    (StatusCode::BAD_REQUEST, ResponseJson(serde_json::json!({
-                        "error": "Invalid genre",
-                        "message": e
-                    })))
-                })?;
+                .map_err(AppError::ValidationError)?;
             // TODO: Implement set_genre method in Song entity
         }
         
         if let Some(royalty_percentage) = request.royalty_percentage {
             let new_royalty = RoyaltyPercentage::new(royalty_percentage)
-                .map_err(|e| {
-                    tracing::error!("Invalid royalty percentage: {}", e);
-                    (StatusCode::BAD_REQUEST, ResponseJson(serde_json::json!({
-                        "error": "Invalid royalty percentage",
-                        "message": e
-                    })))
-                })?;
+                .map_err(AppError::ValidationError)?;
             // TODO: Implement set_royalty_percentage method in Song entity
         }
         
         // Save updated song
         state.song_repository
             .save(&song)
-            .await
-            .map_err(|e| {
-                tracing::error!("Error updating song: {:?}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
-                    "error": "Failed to update song",
-                    "message": format!("{:?}", e)
-                })))
-            })?;
+            .await?;
         
         let response = SongResponse {
             song_id: song.id().to_uuid(),
@@ -458,6 +533,7 @@ impl SongController {
             royalty_percentage: song.royalty_percentage().value(),
             listen_count: song.listen_count().value(),
             revenue_generated: song.revenue_generated(),
+            has_stems: song.has_stems(),
             created_at: song.created_at(),
             updated_at: song.updated_at(),
         };
@@ -470,50 +546,23 @@ impl SongController {
     /// OpenAPI documentation is in `openapi/paths.rs::_delete_song_doc`
     /// Requires authentication - only song owner or admin can delete
     pub async fn delete_song(
-        AuthenticatedUser { user_id, role, .. }: AuthenticatedUser,
+        user: AuthenticatedUser,
         State(state): State<MusicAppState>,
         Path(song_id): Path<Uuid>,
-    ) -> Result<ResponseJson<serde_json::Value>, (StatusCode, ResponseJson<serde_json::Value>)> {
+    ) -> Result<ResponseJson<serde_json::Value>, AppError> {
         // Check if song exists
         let song = state.song_repository
             .find_by_id(&crate::bounded_contexts::music::domain::value_objects::SongId::from_uuid(song_id))
-            .await
-            .map_err(|e| {
-                tracing::error!("Error fetching song: {:?}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
-                    "error": "Failed to fetch song",
-                    "message": format!("{:?}", e)
-                })))
-            })?
-            .ok_or_else(|| {
-                (StatusCode::NOT_FOUND, ResponseJson(serde_json::json!({
-                    "error": "Song not found",
-                    "message": format!("Song with ID {} not found", song_id)
-                })))
-            })?;
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Song with ID {} not found", song_id)))?;
 
         // Validate permissions: only song owner (artist) or admin can delete
-        if role != "admin" && song.artist_id().to_uuid() != user_id {
-            return Err((
-                StatusCode::FORBIDDEN,
-                ResponseJson(serde_json::json!({
-                    "error": "Forbidden",
-                    "message": "You can only delete your own songs"
-                })),
-            ));
-        }
+        authorize_owner(&user, OwnedResource::Song, song.artist_id().to_uuid())?;
         
         // Delete from repository
         state.song_repository
             .delete(&crate::bounded_contexts::music::domain::value_objects::SongId::from_uuid(song_id))
-            .await
-            .map_err(|e| {
-                tracing::error!("Error deleting song: {:?}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
-                    "error": "Failed to delete song",
-                    "message": format!("{:?}", e)
-                })))
-            })?;
+            .await?;
         
         Ok(ResponseJson(serde_json::json!({
             "message": "Song deleted successfully",
@@ -525,20 +574,13 @@ impl SongController {
     pub async fn discover_songs(
         State(state): State<MusicAppState>,
         Query(query): Query<SongQuery>,
-    ) -> Result<ResponseJson<SongListResponse>, (StatusCode, ResponseJson<serde_json::Value>)> {
+    ) -> Result<ResponseJson<SongListResponse>, AppError> {
         let limit = query.limit.unwrap_or(20);
         let offset = query.offset.unwrap_or(0);
         
         let songs = state.song_repository
             .find_popular(Some(limit))
-            .await
-            .map_err(|e| {
-                tracing::error!("Error fetching popular songs: {:?}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
-                    "error": "Failed to fetch popular songs",
-                    "message": format!("{:?}", e)
-                })))
-            })?;
+            .await?;
             
         let total = songs.len(); // Approximate total for these specific lists
         
@@ -554,6 +596,7 @@ impl SongController {
                 royalty_percentage: song.royalty_percentage().value(),
                 listen_count: song.listen_count().value(),
                 revenue_generated: song.revenue_generated(),
+                has_stems: song.has_stems(),
                 created_at: song.created_at(),
                 updated_at: song.updated_at(),
             })
@@ -571,20 +614,13 @@ impl SongController {
     pub async fn get_trending_songs(
         State(state): State<MusicAppState>,
         Query(query): Query<SongQuery>,
-    ) -> Result<ResponseJson<SongListResponse>, (StatusCode, ResponseJson<serde_json::Value>)> {
+    ) -> Result<ResponseJson<SongListResponse>, AppError> {
         let limit = query.limit.unwrap_or(20);
         let offset = query.offset.unwrap_or(0);
         
         let songs = state.song_repository
             .find_trending(Some(limit))
-            .await
-            .map_err(|e| {
-                tracing::error!("Error fetching trending songs: {:?}", e);
-                (StatusCode::INTERNAL_SERVER_ERROR, ResponseJson(serde_json::json!({
-                    "error": "Failed to fetch trending songs",
-                    "message": format!("{:?}", e)
-                })))
-            })?;
+            .await?;
             
         let total = songs.len();
         
@@ -600,6 +636,7 @@ impl SongController {
                 royalty_percentage: song.royalty_percentage().value(),
                 listen_count: song.listen_count().value(),
                 revenue_generated: song.revenue_generated(),
+                has_stems: song.has_stems(),
                 created_at: song.created_at(),
                 updated_at: song.updated_at(),
             })
@@ -613,11 +650,116 @@ impl SongController {
         }))
     }
     
+    /// POST /api/v1/music/songs/:id/remix-license - Buy a remix license
+    /// 
+    /// Requires authentication. The price comes from the license catalogue and
+    /// is charged to the caller and paid to the artist as a royalty before the
+    /// license is granted; a failed charge grants nothing.
+    pub async fn purchase_remix_license(
+        AuthenticatedUser { user_id, .. }: AuthenticatedUser,
+        State(state): State<MusicAppState>,
+        Path(song_id): Path<Uuid>,
+        Json(request): Json<PurchaseRemixLicenseRequest>,
+    ) -> Result<ResponseJson<RemixLicenseResponse>, AppError> {
+        let license_type = RemixLicenseType::from_string(&request.license_type)
+            .map_err(AppError::ValidationError)?;
+        let song_id_vo = crate::bounded_contexts::music::domain::value_objects::SongId::from_uuid(song_id);
+        let song = state.song_repository
+            .find_by_id(&song_id_vo)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Song with ID {} not found", song_id)))?;
+
+        let now = chrono::Utc::now();
+        let existing = state.remix_license_repository.find_by_song(&song_id_vo).await?;
+        let (license, purchased) = RemixLicense::purchase(
+            &song,
+            user_id,
+            license_type,
+            state.remix_license_pricing.price_for(license_type),
+            request.expiry,
+            &existing,
+            now,
+        )?;
+
+        // Cobrar antes de guardar: la licencia (y los stems) solo existen pagadas
+        let payment_id = state.remix_license_payments
+            .charge(&license, purchased.artist_id.to_uuid())
+            .await?;
+        if let Err(e) = state.remix_license_repository.save(&license).await {
+            if let Err(refund_error) = state.remix_license_payments
+                .refund(payment_id, user_id, "Remix license could not be granted")
+                .await
+            {
+                tracing::error!("Failed to refund remix license payment {}: {:?}", payment_id, refund_error);
+            }
+            return Err(e.into());
+        }
+
+        let event = DomainEvent::RemixLicensePurchased {
+            license_id: purchased.license_id,
+            song_id,
+            artist_id: purchased.artist_id.to_uuid(),
+            licensee_id: purchased.licensee_id,
+            license_type: purchased.license_type.to_string(),
+            price: purchased.price,
+            occurred_at: purchased.purchased_at,
+        };
+        if let Err(e) = state.app_state.publish_event(event).await {
+            tracing::warn!("Failed to publish remix license purchased event: {:?}", e);
+        }
+
+        let stems = license
+            .authorize_stem_download(&song, now)
+            .ok()
+            .map(|stems| state.stems_url_signer.sign(stems, user_id, license.expiry, now));
+
+        Ok(ResponseJson(RemixLicenseResponse {
+            license_id: license.id,
+            song_id,
+            licensee_id: license.licensee_id,
+            license_type: license.license_type.to_string(),
+            price: license.price,
+            expiry: license.expiry,
+            stems,
+        }))
+    }
+
+    /// GET /api/v1/music/songs/:id/stems - Signed download URL of the stems
+    /// 
+    /// Requires authentication and a remix license of the caller that has not expired
+    pub async fn download_stems(
+        AuthenticatedUser { user_id, .. }: AuthenticatedUser,
+        State(state): State<MusicAppState>,
+        Path(song_id): Path<Uuid>,
+    ) -> Result<ResponseJson<StemsDownloadResponse>, AppError> {
+        let song_id_vo = crate::bounded_contexts::music::domain::value_objects::SongId::from_uuid(song_id);
+        let song = state.song_repository
+            .find_by_id(&song_id_vo)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Song with ID {} not found", song_id)))?;
+
+        let now = chrono::Utc::now();
+        let licenses = state.remix_license_repository.find_for_licensee(&song_id_vo, user_id).await?;
+        // La licencia que vence más tarde: si esa ha expirado, todas lo han hecho
+        let license = licenses
+            .first()
+            .ok_or_else(|| AppError::Forbidden(format!("No remix license for song {}", song_id)))?;
+        let stems = license.authorize_stem_download(&song, now)?;
+        let signed = state.stems_url_signer.sign(stems, user_id, license.expiry, now);
+
+        Ok(ResponseJson(StemsDownloadResponse {
+            song_id,
+            license_id: license.id,
+            url: signed.url,
+            expires_at: signed.expires_at,
+        }))
+    }
+    
     /// POST /api/v1/music/songs/:id/like - Like a song
     pub async fn like_song(
         State(state): State<MusicAppState>,
         Path(song_id): Path<Uuid>,
-    ) -> Result<ResponseJson<serde_json::Value>, (StatusCode, ResponseJson<serde_json::Value>)> {
+    ) -> Result<ResponseJson<serde_json::Value>, AppError> {
         // TODO: Implement like functionality
         // For now, just publish an event
         let event = DomainEvent::SongLiked {
@@ -640,7 +782,7 @@ impl SongController {
     pub async fn unlike_song(
         State(state): State<MusicAppState>,
         Path(song_id): Path<Uuid>,
-    ) -> Result<ResponseJson<serde_json::Value>, (StatusCode, ResponseJson<serde_json::Value>)> {
+    ) -> Result<ResponseJson<serde_json::Value>, AppError> {
         // TODO: Implement unlike functionality
         Ok(ResponseJson(serde_json::json!({
             "message": "Song unliked successfully",
@@ -653,7 +795,7 @@ impl SongController {
         State(state): State<MusicAppState>,
         Path(song_id): Path<Uuid>,
         Json(share_data): Json<HashMap<String, String>>,
-    ) -> Result<ResponseJson<serde_json::Value>, (StatusCode, ResponseJson<serde_json::Value>)> {
+    ) -> Result<ResponseJson<serde_json::Value>, AppError> {
         let platform = share_data.get("platform").unwrap_or(&"unknown".to_string()).clone();
         
         let event = DomainEvent::SongShared {
@@ -1,6 +1,6 @@
 use axum::{
     extract::{Multipart, State, Path},
-    http::StatusCode,
+    http::{HeaderMap, HeaderValue},
     response::Json,
 };
 use serde::{Deserialize, Serialize};
@@ -9,15 +9,22 @@ use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use std::sync::Arc;
 
+use crate::shared::domain::errors::AppError;
 use crate::bounded_contexts::music::infrastructure::storage::{
-    AudioFileService, StorageConfig, create_storage
+    AudioFileService, StorageConfig, WaveformGenerator, create_storage
 };
-use crate::bounded_contexts::music::domain::value_objects::{FileFormat, AudioQuality};
+use crate::bounded_contexts::music::domain::repositories::SongRepository;
+use crate::bounded_contexts::music::domain::value_objects::{FileFormat, AudioQuality, SongId};
 use super::video_upload_controller::VideoUploadController;
 
+/// JSON chapter markers of the song, for the player's chapter skipping
+pub const SONG_CHAPTERS_HEADER: &str = "x-song-chapters";
+
 /// Audio upload controller
 pub struct AudioUploadController {
     audio_service: Arc<AudioFileService>,
+    /// Sin repositorio no se detectan secciones ni se envían en el streaming
+    song_repository: Option<Arc<dyn SongRepository>>,
 }
 
 impl AudioUploadController {
@@ -26,7 +33,12 @@ impl AudioUploadController {
         let max_file_size = 100 * 1024 * 1024; // 100MB
         let audio_service = Arc::new(AudioFileService::new(storage, max_file_size));
         
-        Self { audio_service }
+        Self { audio_service, song_repository: None }
+    }
+
+    pub fn with_song_repository(mut self, song_repository: Arc<dyn SongRepository>) -> Self {
+        self.song_repository = Some(song_repository);
+        self
     }
 }
 
@@ -92,25 +104,25 @@ pub struct ApiResponse<T> {
 pub async fn upload_audio(
     State((controller, _)): State<(Arc<AudioUploadController>, Arc<VideoUploadController>)>,
     mut multipart: Multipart,
-) -> Result<Json<ApiResponse<UploadAudioResponse>>, StatusCode> {
+) -> Result<Json<ApiResponse<UploadAudioResponse>>, AppError> {
     
     let mut file_data: Option<Bytes> = None;
     let mut filename: Option<String> = None;
     let mut metadata: Option<UploadAudioRequest> = None;
 
     // Parse multipart form data
-    while let Some(field) = multipart.next_field().await.map_err(|_| StatusCode::BAD_REQUEST)? {
+    while let Some(field) = multipart.next_field().await.map_err(|e| AppError::ValidationError(e.to_string()))? {
         let field_name = field.name().unwrap_or("unknown");
         
         match field_name {
             "audio_file" => {
                 filename = field.file_name().map(|s| s.to_string());
-                let data = field.bytes().await.map_err(|_| StatusCode::BAD_REQUEST)?;
+                let data = field.bytes().await.map_err(|e| AppError::ValidationError(e.to_string()))?;
                 file_data = Some(data);
             },
             "metadata" => {
-                let data = field.bytes().await.map_err(|_| StatusCode::BAD_REQUEST)?;
-                let metadata_str = String::from_utf8(data.to_vec()).map_err(|_| StatusCode::BAD_REQUEST)?;
+                let data = field.bytes().await.map_err(|e| AppError::ValidationError(e.to_string()))?;
+                let metadata_str = String::from_utf8(data.to_vec()).map_err(|e| AppError::ValidationError(e.to_string()))?;
                 metadata = serde_json::from_str(&metadata_str).ok();
             },
             _ => {
@@ -121,18 +133,33 @@ pub async fn upload_audio(
     }
 
     // Validate required fields
-    let file_data = file_data.ok_or(StatusCode::BAD_REQUEST)?;
-    let filename = filename.ok_or(StatusCode::BAD_REQUEST)?;
-    let metadata = metadata.ok_or(StatusCode::BAD_REQUEST)?;
+    let file_data = file_data.ok_or_else(|| AppError::ValidationError("Missing file_data".to_string()))?;
+    let filename = filename.ok_or_else(|| AppError::ValidationError("Missing filename".to_string()))?;
+    let metadata = metadata.ok_or_else(|| AppError::ValidationError("Missing metadata".to_string()))?;
 
     // Process upload
     match controller.audio_service.upload_audio_file(
-        file_data,
+        file_data.clone(),
         &filename,
         metadata.artist_id,
         metadata.song_id,
     ).await {
         Ok(result) => {
+            // Las secciones se detectan en background; la respuesta no espera
+            if let Some(song_repository) = &controller.song_repository {
+                let extension = std::path::Path::new(&filename)
+                    .extension()
+                    .and_then(|ext| ext.to_str())
+                    .unwrap_or("")
+                    .to_lowercase();
+                WaveformGenerator::spawn_chapter_detection(
+                    song_repository.clone(),
+                    SongId::from_uuid(metadata.song_id),
+                    file_data,
+                    extension,
+                );
+            }
+
             let response = UploadAudioResponse {
                 upload_id: Uuid::new_v4().to_string(),
                 song_id: metadata.song_id,
@@ -154,15 +181,7 @@ pub async fn upload_audio(
                 errors: None,
             }))
         },
-        Err(e) => {
-            eprintln!("Upload error: {}", e);
-            Ok(Json(ApiResponse {
-                success: false,
-                data: None,
-                message: Some("Upload failed".to_string()),
-                errors: Some(vec![e.to_string()]),
-            }))
-        }
+        Err(e) => Err(AppError::InternalError(format!("Upload failed: {}", e))),
     }
 }
 
@@ -170,7 +189,7 @@ pub async fn upload_audio(
 pub async fn get_upload_progress(
     State((_controller, _)): State<(Arc<AudioUploadController>, Arc<VideoUploadController>)>,
     Path(upload_id): Path<String>,
-) -> Result<Json<ApiResponse<UploadProgressResponse>>, StatusCode> {
+) -> Result<Json<ApiResponse<UploadProgressResponse>>, AppError> {
     // TODO: Implement actual progress tracking
     let progress = UploadProgressResponse {
         upload_id: upload_id.clone(),
@@ -188,31 +207,38 @@ pub async fn get_upload_progress(
     }))
 }
 
-/// Get streaming URL for uploaded audio
+/// Get streaming URL for uploaded audio. Once the audio has been analysed
+/// the song's chapter markers travel in `X-Song-Chapters`.
 pub async fn get_streaming_url(
     State((controller, _)): State<(Arc<AudioUploadController>, Arc<VideoUploadController>)>,
     Path(song_id): Path<Uuid>,
-) -> Result<Json<ApiResponse<String>>, StatusCode> {
+) -> Result<(HeaderMap, Json<ApiResponse<String>>), AppError> {
     // TODO: Get storage URL from database using song_id
     let storage_url = format!("local://song_{}.mp3", song_id);
     
+    let mut headers = HeaderMap::new();
+    if let Some(song_repository) = &controller.song_repository {
+        let song = song_repository.find_by_id(&SongId::from_uuid(song_id)).await?;
+        if let Some(markers) = song.as_ref().and_then(|song| song.chapter_markers()) {
+            let chapters = serde_json::to_string(markers)
+                .map_err(|e| AppError::SerializationError(e.to_string()))?;
+            // Las etiquetas son ASCII; si no cupiera en una cabecera, se omite
+            if let Ok(value) = HeaderValue::from_str(&chapters) {
+                headers.insert(SONG_CHAPTERS_HEADER, value);
+            }
+        }
+    }
+    
     match controller.audio_service.get_streaming_url(&storage_url).await {
         Ok(streaming_url) => {
-            Ok(Json(ApiResponse {
+            Ok((headers, Json(ApiResponse {
                 success: true,
                 data: Some(streaming_url),
                 message: None,
                 errors: None,
-            }))
+            })))
         },
-        Err(e) => {
-            Ok(Json(ApiResponse {
-                success: false,
-                data: None,
-                message: Some("Failed to get streaming URL".to_string()),
-                errors: Some(vec![e.to_string()]),
-            }))
-        }
+        Err(e) => Err(AppError::InternalError(format!("Failed to get streaming URL: {}", e))),
     }
 }
 
@@ -220,7 +246,7 @@ pub async fn get_streaming_url(
 pub async fn delete_audio(
     State((controller, _)): State<(Arc<AudioUploadController>, Arc<VideoUploadController>)>,
     Path(song_id): Path<Uuid>,
-) -> Result<Json<ApiResponse<()>>, StatusCode> {
+) -> Result<Json<ApiResponse<()>>, AppError> {
     // TODO: Get storage URL from database using song_id
     let storage_url = format!("local://song_{}.mp3", song_id);
     
@@ -233,14 +259,7 @@ pub async fn delete_audio(
                 errors: None,
             }))
         },
-        Err(e) => {
-            Ok(Json(ApiResponse {
-                success: false,
-                data: None,
-                message: Some("Failed to delete audio file".to_string()),
-                errors: Some(vec![e.to_string()]),
-            }))
-        }
+        Err(e) => Err(AppError::InternalError(format!("Failed to delete audio file: {}", e))),
     }
 }
 
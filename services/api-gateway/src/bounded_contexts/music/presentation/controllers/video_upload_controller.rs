@@ -1,6 +1,5 @@
 use axum::{
     extract::{Multipart, State, Path, Query},
-    http::StatusCode,
     response::Json,
 };
 use serde::{Deserialize, Serialize};
@@ -9,8 +8,10 @@ use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use std::sync::Arc;
 
+use crate::shared::domain::errors::AppError;
 use crate::bounded_contexts::music::infrastructure::storage::{
-    IPFSVideoStorage, VideoQuality, VideoFileStorage
+    DiscoveredPeer, FfmpegVideoEncoder, IPFSVideoStorage, TranscodeJobStatus, VideoQuality, VideoFileStorage,
+    VideoTranscodingWorker,
 };
 use super::upload_controller::AudioUploadController;
 
@@ -32,6 +33,13 @@ impl VideoUploadController {
             true,  // enable content discovery
         ));
         
+        // El worker necesita un runtime de tokio
+        if tokio::runtime::Handle::try_current().is_ok() {
+            let worker = VideoTranscodingWorker::new(video_storage.clone(), Arc::new(FfmpegVideoEncoder::new()));
+            Arc::new(worker).start(std::time::Duration::from_secs(10));
+            video_storage.peer_discovery().start(std::time::Duration::from_secs(300));
+        }
+        
         Self { video_storage }
     }
 }
@@ -91,6 +99,35 @@ pub struct VideoChunkResponse {
     pub data_size: u64,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TranscodeJobResponse {
+    pub job_id: Uuid,
+    pub quality: String,
+    pub status: TranscodeJobStatus,
+    pub progress_percentage: f32,
+    pub attempts: u32,
+    pub error: Option<String>,
+    pub chunk_count: Option<u32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VideoTranscodeJobsResponse {
+    pub video_id: Uuid,
+    pub jobs: Vec<TranscodeJobResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct VideoPeersResponse {
+    pub video_id: Uuid,
+    pub peer_count: u32,
+    pub availability_score: f32,
+    /// Lowest latency first
+    pub peers: Vec<DiscoveredPeer>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UploadProgressResponse {
     pub upload_id: String,
@@ -128,7 +165,7 @@ pub struct ApiResponse<T> {
 pub async fn upload_video(
     State((_, controller)): State<(Arc<AudioUploadController>, Arc<VideoUploadController>)>,
     mut multipart: Multipart,
-) -> Result<Json<ApiResponse<UploadVideoResponse>>, StatusCode> {
+) -> Result<Json<ApiResponse<UploadVideoResponse>>, AppError> {
     
     let mut file_data: Option<Bytes> = None;
     let mut filename: Option<String> = None;
@@ -136,19 +173,19 @@ pub async fn upload_video(
     let mut metadata: Option<UploadVideoRequest> = None;
 
     // Parse multipart form data
-    while let Some(field) = multipart.next_field().await.map_err(|_| StatusCode::BAD_REQUEST)? {
+    while let Some(field) = multipart.next_field().await.map_err(|e| AppError::ValidationError(e.to_string()))? {
         let field_name = field.name().unwrap_or("unknown");
         
         match field_name {
             "video_file" => {
                 filename = field.file_name().map(|s| s.to_string());
                 content_type = field.content_type().map(|ct| ct.to_string());
-                let data = field.bytes().await.map_err(|_| StatusCode::BAD_REQUEST)?;
+                let data = field.bytes().await.map_err(|e| AppError::ValidationError(e.to_string()))?;
                 file_data = Some(data);
             },
             "metadata" => {
-                let data = field.bytes().await.map_err(|_| StatusCode::BAD_REQUEST)?;
-                let metadata_str = String::from_utf8(data.to_vec()).map_err(|_| StatusCode::BAD_REQUEST)?;
+                let data = field.bytes().await.map_err(|e| AppError::ValidationError(e.to_string()))?;
+                let metadata_str = String::from_utf8(data.to_vec()).map_err(|e| AppError::ValidationError(e.to_string()))?;
                 metadata = serde_json::from_str(&metadata_str).ok();
             },
             _ => {
@@ -159,10 +196,10 @@ pub async fn upload_video(
     }
 
     // Validate required fields
-    let file_data = file_data.ok_or(StatusCode::BAD_REQUEST)?;
-    let filename = filename.ok_or(StatusCode::BAD_REQUEST)?;
-    let content_type = content_type.ok_or(StatusCode::BAD_REQUEST)?;
-    let metadata = metadata.ok_or(StatusCode::BAD_REQUEST)?;
+    let file_data = file_data.ok_or_else(|| AppError::ValidationError("Missing file_data".to_string()))?;
+    let filename = filename.ok_or_else(|| AppError::ValidationError("Missing filename".to_string()))?;
+    let content_type = content_type.ok_or_else(|| AppError::ValidationError("Missing content_type".to_string()))?;
+    let metadata = metadata.ok_or_else(|| AppError::ValidationError("Missing metadata".to_string()))?;
 
     // Validate video file
     validate_video_upload(file_data.len() as u64, &filename, 500 * 1024 * 1024)?;
@@ -172,15 +209,15 @@ pub async fn upload_video(
         Ok(ipfs_hash) => {
             // Get metadata
             let video_metadata = controller.video_storage.get_metadata(&ipfs_hash).await
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
             
             // Get available qualities
             let qualities = controller.video_storage.get_available_qualities(&ipfs_hash).await
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
             
             // Get peers
             let peers = controller.video_storage.get_peers(&ipfs_hash).await
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
 
             let response = UploadVideoResponse {
                 upload_id: Uuid::new_v4().to_string(),
@@ -208,15 +245,7 @@ pub async fn upload_video(
                 errors: None,
             }))
         },
-        Err(e) => {
-            eprintln!("Video upload error: {}", e);
-            Ok(Json(ApiResponse {
-                success: false,
-                data: None,
-                message: Some("Video upload failed".to_string()),
-                errors: Some(vec![e.to_string()]),
-            }))
-        }
+        Err(e) => Err(AppError::InternalError(format!("Video upload failed: {}", e))),
     }
 }
 
@@ -225,7 +254,7 @@ pub async fn get_video_streaming(
     State((_, controller)): State<(Arc<AudioUploadController>, Arc<VideoUploadController>)>,
     Path(video_id): Path<Uuid>,
     Query(params): Query<std::collections::HashMap<String, String>>,
-) -> Result<Json<ApiResponse<VideoStreamingResponse>>, StatusCode> {
+) -> Result<Json<ApiResponse<VideoStreamingResponse>>, AppError> {
     let quality = params.get("quality")
         .and_then(|q| parse_video_quality(q))
         .unwrap_or(VideoQuality::Medium);
@@ -236,13 +265,13 @@ pub async fn get_video_streaming(
     match controller.video_storage.get_streaming_url(&ipfs_hash, &quality).await {
         Ok(streaming_url) => {
             let qualities = controller.video_storage.get_available_qualities(&ipfs_hash).await
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
             
             let peers = controller.video_storage.get_peers(&ipfs_hash).await
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
             
             let metadata = controller.video_storage.get_metadata(&ipfs_hash).await
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                .map_err(|e| AppError::InternalError(e.to_string()))?;
             
             // Generate chunk URLs
             let chunk_urls = (0..metadata.chunk_count)
@@ -266,14 +295,7 @@ pub async fn get_video_streaming(
                 errors: None,
             }))
         },
-        Err(e) => {
-            Ok(Json(ApiResponse {
-                success: false,
-                data: None,
-                message: Some("Failed to get streaming URL".to_string()),
-                errors: Some(vec![e.to_string()]),
-            }))
-        }
+        Err(e) => Err(AppError::InternalError(format!("Failed to get streaming URL: {}", e))),
     }
 }
 
@@ -282,7 +304,7 @@ pub async fn get_video_chunk(
     State((_, controller)): State<(Arc<AudioUploadController>, Arc<VideoUploadController>)>,
     Path((video_id, chunk_index)): Path<(Uuid, u32)>,
     Query(params): Query<std::collections::HashMap<String, String>>,
-) -> Result<Json<ApiResponse<VideoChunkResponse>>, StatusCode> {
+) -> Result<Json<ApiResponse<VideoChunkResponse>>, AppError> {
     let quality = params.get("quality")
         .and_then(|q| parse_video_quality(q))
         .unwrap_or(VideoQuality::Medium);
@@ -307,14 +329,7 @@ pub async fn get_video_chunk(
                 errors: None,
             }))
         },
-        Err(e) => {
-            Ok(Json(ApiResponse {
-                success: false,
-                data: None,
-                message: Some("Failed to get video chunk".to_string()),
-                errors: Some(vec![e.to_string()]),
-            }))
-        }
+        Err(e) => Err(chunk_error(e)),
     }
 }
 
@@ -322,7 +337,7 @@ pub async fn get_video_chunk(
 pub async fn get_video_metadata(
     State((_, controller)): State<(Arc<AudioUploadController>, Arc<VideoUploadController>)>,
     Path(video_id): Path<Uuid>,
-) -> Result<Json<ApiResponse<serde_json::Value>>, StatusCode> {
+) -> Result<Json<ApiResponse<serde_json::Value>>, AppError> {
     // TODO: Get IPFS hash from database using video_id
     let ipfs_hash = format!("QmVideoHash{}", video_id);
     
@@ -350,22 +365,73 @@ pub async fn get_video_metadata(
                 errors: None,
             }))
         },
-        Err(e) => {
-            Ok(Json(ApiResponse {
-                success: false,
-                data: None,
-                message: Some("Failed to get video metadata".to_string()),
-                errors: Some(vec![e.to_string()]),
-            }))
-        }
+        Err(e) => Err(AppError::InternalError(format!("Failed to get video metadata: {}", e))),
     }
 }
 
+/// Get the transcode jobs of a video, one per quality
+pub async fn get_video_transcode_jobs(
+    State((_, controller)): State<(Arc<AudioUploadController>, Arc<VideoUploadController>)>,
+    Path(video_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<VideoTranscodeJobsResponse>>, AppError> {
+    // TODO: Get IPFS hash from database using video_id
+    let ipfs_hash = format!("QmVideoHash{}", video_id);
+    
+    let jobs = controller.video_storage.transcode_jobs(&ipfs_hash).await
+        .map_err(|e| AppError::InternalError(format!("Failed to get transcode jobs: {}", e)))?;
+    
+    let jobs = jobs.into_iter()
+        .map(|job| TranscodeJobResponse {
+            job_id: job.id,
+            quality: format!("{:?}", job.quality),
+            status: job.status,
+            progress_percentage: job.progress * 100.0,
+            attempts: job.attempts,
+            error: job.error,
+            chunk_count: job.rendition.map(|rendition| rendition.chunk_count()),
+            created_at: job.created_at,
+            updated_at: job.updated_at,
+            completed_at: job.completed_at,
+        })
+        .collect();
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(VideoTranscodeJobsResponse { video_id, jobs }),
+        message: None,
+        errors: None,
+    }))
+}
+
+/// Get the peers providing a video and its availability score
+pub async fn get_video_peers(
+    State((_, controller)): State<(Arc<AudioUploadController>, Arc<VideoUploadController>)>,
+    Path(video_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<VideoPeersResponse>>, AppError> {
+    // TODO: Get IPFS hash from database using video_id
+    let ipfs_hash = format!("QmVideoHash{}", video_id);
+    
+    let availability = controller.video_storage.peer_discovery().availability(&ipfs_hash).await;
+    let peers = controller.video_storage.discovered_peers(&ipfs_hash).await;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(VideoPeersResponse {
+            video_id,
+            peer_count: availability.provider_count,
+            availability_score: availability.availability_score,
+            peers,
+        }),
+        message: None,
+        errors: None,
+    }))
+}
+
 /// Get upload progress
 pub async fn get_video_upload_progress(
     State((_, _controller)): State<(Arc<AudioUploadController>, Arc<VideoUploadController>)>,
     Path(upload_id): Path<String>,
-) -> Result<Json<ApiResponse<UploadProgressResponse>>, StatusCode> {
+) -> Result<Json<ApiResponse<UploadProgressResponse>>, AppError> {
     // TODO: Implement actual progress tracking
     let progress = UploadProgressResponse {
         upload_id: upload_id.clone(),
@@ -388,7 +454,7 @@ pub async fn get_video_upload_progress(
 pub async fn delete_video(
     State((_, controller)): State<(Arc<AudioUploadController>, Arc<VideoUploadController>)>,
     Path(video_id): Path<Uuid>,
-) -> Result<Json<ApiResponse<()>>, StatusCode> {
+) -> Result<Json<ApiResponse<()>>, AppError> {
     // TODO: Get IPFS hash from database using video_id
     let ipfs_hash = format!("QmVideoHash{}", video_id);
     
@@ -401,14 +467,7 @@ pub async fn delete_video(
                 errors: None,
             }))
         },
-        Err(e) => {
-            Ok(Json(ApiResponse {
-                success: false,
-                data: None,
-                message: Some("Failed to delete video".to_string()),
-                errors: Some(vec![e.to_string()]),
-            }))
-        }
+        Err(e) => Err(AppError::InternalError(format!("Failed to delete video: {}", e))),
     }
 }
 
@@ -421,10 +480,10 @@ fn validate_video_upload(
     file_size: u64,
     filename: &str,
     max_size: u64,
-) -> Result<(), StatusCode> {
+) -> Result<(), AppError> {
     // Check file size
     if file_size > max_size {
-        return Err(StatusCode::PAYLOAD_TOO_LARGE);
+        return Err(AppError::ValidationError(format!("File size {} exceeds maximum {}", file_size, max_size)));
     }
 
     // Check file extension
@@ -435,12 +494,21 @@ fn validate_video_upload(
 
     let valid_extensions = ["mp4", "avi", "mov", "mkv", "webm", "flv"];
     if !valid_extensions.contains(&extension.to_lowercase().as_str()) {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(AppError::ValidationError(format!("Unsupported video format: {}", extension)));
     }
 
     Ok(())
 }
 
+/// Índice fuera de rango o calidad no disponible son errores del cliente
+fn chunk_error(error: std::io::Error) -> AppError {
+    match error.kind() {
+        std::io::ErrorKind::InvalidInput => AppError::ValidationError(error.to_string()),
+        std::io::ErrorKind::NotFound => AppError::NotFound(error.to_string()),
+        _ => AppError::InternalError(format!("Failed to get video chunk: {}", error)),
+    }
+}
+
 /// Parse video quality from string
 fn parse_video_quality(quality_str: &str) -> Option<VideoQuality> {
     match quality_str.to_lowercase().as_str() {
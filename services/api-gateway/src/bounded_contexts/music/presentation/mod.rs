@@ -3,6 +3,7 @@
 
 pub mod controllers;
 pub mod routes;
+pub mod video_signaling_ws;
 
 pub use controllers::*;
 pub use routes::*; 
\ No newline at end of file
@@ -14,9 +14,9 @@ use super::controllers::upload_controller::{
 };
 use super::controllers::video_upload_controller::{
     VideoUploadController, upload_video, get_video_streaming, get_video_chunk,
-    get_video_metadata, get_video_upload_progress, delete_video
+    get_video_metadata, get_video_upload_progress, get_video_transcode_jobs, get_video_peers, delete_video
 };
-use crate::bounded_contexts::music::infrastructure::storage::StorageConfig;
+use crate::bounded_contexts::music::infrastructure::storage::{LocalStorageConfig, StorageConfig};
 
 /// Create all Music Context REST routes
 /// 
@@ -29,10 +29,10 @@ use crate::bounded_contexts::music::infrastructure::storage::StorageConfig;
 /// - Audio Upload: File upload, processing, streaming
 pub fn create_music_routes() -> Router {
     // Create upload controllers
-    let storage_config = StorageConfig::Local {
-        base_path: "./storage/audio".to_string(),
-        max_file_size: 100 * 1024 * 1024, // 100MB
-    };
+    let storage_config = StorageConfig::Local(
+        LocalStorageConfig::new("./storage/audio", 100 * 1024 * 1024) // 100MB
+            .with_env(),
+    );
     let upload_controller = Arc::new(AudioUploadController::new(storage_config));
     let video_upload_controller = Arc::new(VideoUploadController::new());
 
@@ -57,6 +57,8 @@ pub fn create_music_routes() -> Router {
         .route("/videos/:video_id/stream", get(get_video_streaming))
         .route("/videos/:video_id/chunks/:chunk_index", get(get_video_chunk))
         .route("/videos/:video_id/metadata", get(get_video_metadata))
+        .route("/videos/:video_id/transcode-jobs", get(get_video_transcode_jobs))
+        .route("/videos/:video_id/peers", get(get_video_peers))
         .route("/videos/:video_id", delete(delete_video))
         
         // Album endpoints  
@@ -0,0 +1,258 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Path, State,
+    },
+    http::StatusCode,
+    response::{Json as ResponseJson, Response},
+    routing::get,
+    Router,
+};
+use uuid::Uuid;
+
+use crate::auth::Claims;
+use crate::bounded_contexts::music::application::video_signaling::{
+    SignalingCloseReason, SignalingCommand, SignalingHub, SignalingMessage,
+};
+
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+/// Sin tráfico (ni pongs) durante este tiempo se da la conexión por muerta
+const IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+/// 1013 "Try Again Later": sala llena o el cliente no consumía a tiempo
+const TRY_AGAIN_LATER_CLOSE_CODE: u16 = 1013;
+const GOING_AWAY_CLOSE_CODE: u16 = 1001;
+
+/// Rutas de señalización (`/videos/:content_id/signal`), con su propio estado
+pub fn routes(hub: Arc<SignalingHub>) -> Router {
+    Router::new()
+        .route("/videos/:content_id/signal", get(video_signaling_ws))
+        .with_state(hub)
+}
+
+/// GET /api/v1/music/videos/:content_id/signal - WebRTC signaling over WebSocket
+///
+/// The first message is a `welcome` with the peer id and which peers hold
+/// which chunk ranges. Clients send `have`, `offer`, `answer` and
+/// `ice_candidate` messages tagged by `type`; signals reach only their `to` peer.
+pub async fn video_signaling_ws(
+    ws: WebSocketUpgrade,
+    State(hub): State<Arc<SignalingHub>>,
+    Path(content_id): Path<String>,
+    claims: Claims,
+) -> Result<Response, (StatusCode, ResponseJson<serde_json::Value>)> {
+    let user_id = Uuid::parse_str(&claims.sub).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            ResponseJson(serde_json::json!({"error": "Invalid user ID"})),
+        )
+    })?;
+
+    Ok(ws.on_upgrade(move |socket| run_connection(socket, hub, content_id, user_id)))
+}
+
+async fn send(socket: &mut WebSocket, message: &SignalingMessage) -> bool {
+    match serde_json::to_string(message) {
+        Ok(text) => socket.send(Message::Text(text)).await.is_ok(),
+        Err(e) => {
+            tracing::error!("Failed to serialize signaling message: {}", e);
+            true
+        }
+    }
+}
+
+async fn close(socket: &mut WebSocket, code: u16, reason: &'static str) {
+    let _ = socket
+        .send(Message::Close(Some(CloseFrame { code, reason: reason.into() })))
+        .await;
+}
+
+async fn run_connection(mut socket: WebSocket, hub: Arc<SignalingHub>, content_id: String, user_id: Uuid) {
+    let mut connection = match hub.join(&content_id, user_id).await {
+        Ok(connection) => connection,
+        Err(e) => {
+            let _ = send(&mut socket, &SignalingMessage::Error { message: e.to_string() }).await;
+            close(&mut socket, TRY_AGAIN_LATER_CLOSE_CODE, "room full").await;
+            return;
+        }
+    };
+    let mut ping = tokio::time::interval_at(tokio::time::Instant::now() + PING_INTERVAL, PING_INTERVAL);
+    let mut last_seen = Instant::now();
+
+    loop {
+        tokio::select! {
+            incoming = socket.recv() => {
+                let message = match incoming {
+                    Some(Ok(message)) => message,
+                    _ => break,
+                };
+                last_seen = Instant::now();
+                match message {
+                    Message::Text(text) => {
+                        let error = match serde_json::from_str::<SignalingCommand>(&text) {
+                            Ok(command) => hub.apply(&content_id, connection.peer_id, command).await.err().map(|e| e.to_string()),
+                            Err(e) => Some(format!("Invalid message: {}", e)),
+                        };
+                        if let Some(message) = error {
+                            if !send(&mut socket, &SignalingMessage::Error { message }).await {
+                                break;
+                            }
+                        }
+                    }
+                    Message::Close(_) => break,
+                    _ => {}
+                }
+            }
+            Some(message) = connection.messages.recv() => {
+                if !send(&mut socket, &message).await {
+                    break;
+                }
+            }
+            reason = &mut connection.closed => {
+                if let Ok(SignalingCloseReason::SlowConsumer) = reason {
+                    close(&mut socket, TRY_AGAIN_LATER_CLOSE_CODE, "slow consumer").await;
+                }
+                break;
+            }
+            _ = ping.tick() => {
+                if last_seen.elapsed() > IDLE_TIMEOUT {
+                    close(&mut socket, GOING_AWAY_CLOSE_CODE, "idle timeout").await;
+                    break;
+                }
+                if socket.send(Message::Ping(Vec::new())).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    hub.leave(&content_id, connection.peer_id).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio_tungstenite::tungstenite::{self, client::IntoClientRequest};
+
+    async fn spawn_server(hub: Arc<SignalingHub>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, routes(hub)).await.unwrap();
+        });
+        format!("ws://{}/videos/QmVideo/signal", addr)
+    }
+
+    async fn connect(url: &str) -> tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>> {
+        let user_id = Uuid::new_v4();
+        let token = Claims::new(user_id, "viewer".to_string(), "viewer@vibestream.test".to_string(), "user".to_string(), "access".to_string())
+            .to_jwt()
+            .unwrap();
+        let mut request = url.into_client_request().unwrap();
+        request
+            .headers_mut()
+            .insert("Authorization", format!("Bearer {}", token).parse().unwrap());
+        let (socket, _) = tokio_tungstenite::connect_async(request).await.unwrap();
+        socket
+    }
+
+    async fn next_signal<S>(socket: &mut S) -> SignalingMessage
+    where
+        S: StreamExt<Item = Result<tungstenite::Message, tungstenite::Error>> + Unpin,
+    {
+        loop {
+            let message = tokio::time::timeout(Duration::from_secs(5), socket.next())
+                .await
+                .expect("timed out waiting for signaling message")
+                .unwrap()
+                .unwrap();
+            if let tungstenite::Message::Text(text) = message {
+                return serde_json::from_str(&text).unwrap();
+            }
+        }
+    }
+
+    async fn send_command<S>(socket: &mut S, command: serde_json::Value)
+    where
+        S: SinkExt<tungstenite::Message> + Unpin,
+        S::Error: std::fmt::Debug,
+    {
+        socket.send(tungstenite::Message::Text(command.to_string())).await.unwrap();
+    }
+
+    fn welcome_peer_id(message: SignalingMessage) -> Uuid {
+        match message {
+            SignalingMessage::Welcome { peer_id, .. } => peer_id,
+            other => panic!("expected welcome, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn two_peers_complete_an_offer_answer_exchange() {
+        let hub = Arc::new(SignalingHub::new());
+        let url = spawn_server(Arc::clone(&hub)).await;
+
+        let mut seeder = connect(&url).await;
+        let seeder_id = welcome_peer_id(next_signal(&mut seeder).await);
+        send_command(&mut seeder, serde_json::json!({
+            "type": "have",
+            "ranges": [{"quality": "high", "start": 0, "end": 120}]
+        }))
+        .await;
+
+        // Wait until the announcement is in the table before the viewer joins
+        while hub.holders("QmVideo", "high", 0).await.is_empty() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let mut viewer = connect(&url).await;
+        let (viewer_id, peers) = match next_signal(&mut viewer).await {
+            SignalingMessage::Welcome { peer_id, peers, .. } => (peer_id, peers),
+            other => panic!("expected welcome, got {:?}", other),
+        };
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].peer_id, seeder_id);
+        assert_eq!(peers[0].ranges[0].end, 120);
+        assert_eq!(next_signal(&mut seeder).await, SignalingMessage::PeerJoined { peer_id: viewer_id });
+
+        send_command(&mut viewer, serde_json::json!({"type": "offer", "to": seeder_id, "sdp": "v=0 offer"})).await;
+        assert_eq!(next_signal(&mut seeder).await, SignalingMessage::Offer { from: viewer_id, sdp: "v=0 offer".to_string() });
+
+        send_command(&mut seeder, serde_json::json!({"type": "answer", "to": viewer_id, "sdp": "v=0 answer"})).await;
+        assert_eq!(next_signal(&mut viewer).await, SignalingMessage::Answer { from: seeder_id, sdp: "v=0 answer".to_string() });
+
+        send_command(&mut viewer, serde_json::json!({
+            "type": "ice_candidate", "to": seeder_id, "candidate": "candidate:1 1 udp 2122260223 10.0.0.2 54400 typ host",
+            "sdp_mid": "0", "sdp_mline_index": 0
+        }))
+        .await;
+        send_command(&mut seeder, serde_json::json!({
+            "type": "ice_candidate", "to": viewer_id, "candidate": "candidate:1 1 udp 2122260223 10.0.0.3 54401 typ host"
+        }))
+        .await;
+        assert!(matches!(
+            next_signal(&mut seeder).await,
+            SignalingMessage::IceCandidate { from, ref sdp_mid, .. } if from == viewer_id && sdp_mid.as_deref() == Some("0")
+        ));
+        assert!(matches!(
+            next_signal(&mut viewer).await,
+            SignalingMessage::IceCandidate { from, sdp_mid: None, .. } if from == seeder_id
+        ));
+
+        viewer.close(None).await.unwrap();
+        assert_eq!(next_signal(&mut seeder).await, SignalingMessage::PeerLeft { peer_id: viewer_id });
+    }
+
+    #[tokio::test]
+    async fn malformed_messages_get_an_error_reply() {
+        let url = spawn_server(Arc::new(SignalingHub::new())).await;
+        let mut peer = connect(&url).await;
+        welcome_peer_id(next_signal(&mut peer).await);
+
+        send_command(&mut peer, serde_json::json!({"type": "offer", "sdp": "v=0"})).await;
+        assert!(matches!(next_signal(&mut peer).await, SignalingMessage::Error { ref message } if message.starts_with("Invalid message")));
+    }
+}
@@ -0,0 +1,289 @@
+//! Notification Dispatcher
+//!
+//! Punto único de entrega: consulta las preferencias del destinatario, guarda
+//! la copia in-app solo si la quiere (y la empuja a sus WebSockets abiertos) y
+//! pasa la notificación a los canales externos (email, push) que tenga activos
+//! para esa categoría. El email de las categorías en modo resumen no sale al
+//! momento: se encola para el resumen de su periodo.
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use tracing::error;
+
+use crate::bounded_contexts::notifications::domain::entities::{
+    DigestEntry, Notification, NotificationChannel, NotificationPreferences,
+};
+use crate::bounded_contexts::notifications::domain::repositories::{
+    DigestRepository, NotificationPreferencesRepository, NotificationRepository,
+};
+use super::notification_hub::NotificationHub;
+
+/// Envío por un canal externo
+#[async_trait]
+pub trait NotificationSender: Send + Sync {
+    fn channel(&self) -> NotificationChannel;
+    async fn send(&self, notification: &Notification) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+
+pub struct NotificationDispatcher {
+    notifications: Arc<dyn NotificationRepository>,
+    preferences: Arc<dyn NotificationPreferencesRepository>,
+    senders: Vec<Arc<dyn NotificationSender>>,
+    live: Option<Arc<NotificationHub>>,
+    digests: Option<Arc<dyn DigestRepository>>,
+}
+
+impl NotificationDispatcher {
+    pub fn new(
+        notifications: Arc<dyn NotificationRepository>,
+        preferences: Arc<dyn NotificationPreferencesRepository>,
+    ) -> Self {
+        Self {
+            notifications,
+            preferences,
+            senders: Vec::new(),
+            live: None,
+            digests: None,
+        }
+    }
+
+    pub fn with_sender(mut self, sender: Arc<dyn NotificationSender>) -> Self {
+        self.senders.push(sender);
+        self
+    }
+
+    /// Empujar las notificaciones in-app recién guardadas a las conexiones abiertas
+    pub fn with_live_hub(mut self, hub: Arc<NotificationHub>) -> Self {
+        self.live = Some(hub);
+        self
+    }
+
+    /// Cola de los resúmenes por email; sin ella todo email sale al momento
+    pub fn with_digest_queue(mut self, digests: Arc<dyn DigestRepository>) -> Self {
+        self.digests = Some(digests);
+        self
+    }
+
+    /// Entregar `notification` por los canales que permite su destinatario.
+    /// Devuelve los canales usados; un fallo de email o push se registra sin
+    /// impedir el resto de entregas.
+    pub async fn dispatch(
+        &self,
+        notification: &Notification,
+    ) -> Result<Vec<NotificationChannel>, Box<dyn std::error::Error + Send + Sync>> {
+        let preferences = self.preferences
+            .get_by_user_id(notification.user_id)
+            .await?
+            .unwrap_or_else(|| NotificationPreferences::new(notification.user_id));
+        let channels = preferences.channels_for(&notification.notification_type);
+
+        let mut delivered = Vec::new();
+        if channels.in_app {
+            self.notifications.create(notification).await?;
+            delivered.push(NotificationChannel::InApp);
+            if let Some(hub) = &self.live {
+                hub.push(notification).await;
+            }
+        }
+
+        for sender in &self.senders {
+            let channel = sender.channel();
+            if channel == NotificationChannel::InApp || !channels.allows(channel) {
+                continue;
+            }
+            if channel == NotificationChannel::Email && preferences.digests(&notification.notification_type) {
+                if let Some(digests) = &self.digests {
+                    let Some(entry) = DigestEntry::for_notification(notification, preferences.digest_frequency) else {
+                        continue;
+                    };
+                    match digests.enqueue(&entry).await {
+                        Ok(()) => delivered.push(channel),
+                        Err(e) => error!(
+                            "Failed to queue notification {} for the digest of user {}: {}",
+                            notification.id, notification.user_id, e
+                        ),
+                    }
+                    continue;
+                }
+            }
+            match sender.send(notification).await {
+                Ok(()) => delivered.push(channel),
+                Err(e) => error!(
+                    "Failed to deliver notification {} to user {} via {:?}: {}",
+                    notification.id, notification.user_id, channel, e
+                ),
+            }
+        }
+
+        Ok(delivered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+    use crate::bounded_contexts::notifications::domain::entities::{
+        ChannelPreferences, DigestFrequency, NotificationCategory, NotificationFilters, NotificationPriority,
+        NotificationType,
+    };
+    use crate::bounded_contexts::notifications::infrastructure::MockNotificationPreferencesRepository;
+
+    type RepoResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+    #[derive(Default)]
+    struct RecordingRepository {
+        created: Mutex<Vec<Notification>>,
+    }
+
+    #[async_trait]
+    impl NotificationRepository for RecordingRepository {
+        async fn create(&self, notification: &Notification) -> RepoResult<()> {
+            self.created.lock().unwrap().push(notification.clone());
+            Ok(())
+        }
+        async fn get_by_id(&self, _id: Uuid) -> RepoResult<Option<Notification>> { Ok(None) }
+        async fn get_by_user_id(&self, _user_id: Uuid, _page: u32, _page_size: u32) -> RepoResult<(Vec<Notification>, u32, u32)> { Ok((Vec::new(), 0, 0)) }
+        async fn get_unread_count(&self, _user_id: Uuid) -> RepoResult<u32> { Ok(0) }
+        async fn update(&self, _notification: &Notification) -> RepoResult<()> { Ok(()) }
+        async fn delete(&self, _id: Uuid) -> RepoResult<()> { Ok(()) }
+        async fn mark_as_read(&self, _id: Uuid) -> RepoResult<()> { Ok(()) }
+        async fn mark_as_archived(&self, _id: Uuid) -> RepoResult<()> { Ok(()) }
+        async fn mark_all_as_read(&self, _user_id: Uuid) -> RepoResult<()> { Ok(()) }
+        async fn search(&self, _filters: &NotificationFilters, _page: u32, _page_size: u32) -> RepoResult<Vec<Notification>> { Ok(Vec::new()) }
+        async fn get_summary(&self, _user_id: Uuid) -> RepoResult<(u32, u32, u32, u32)> { Ok((0, 0, 0, 0)) }
+    }
+
+    struct FixedPreferences(NotificationPreferences);
+
+    #[async_trait]
+    impl NotificationPreferencesRepository for FixedPreferences {
+        async fn get_by_user_id(&self, _user_id: Uuid) -> RepoResult<Option<NotificationPreferences>> { Ok(Some(self.0.clone())) }
+        async fn create(&self, _preferences: &NotificationPreferences) -> RepoResult<()> { Ok(()) }
+        async fn update(&self, _preferences: &NotificationPreferences) -> RepoResult<()> { Ok(()) }
+        async fn delete(&self, _user_id: Uuid) -> RepoResult<()> { Ok(()) }
+    }
+
+    struct RecordingSender {
+        channel: NotificationChannel,
+        sent: Mutex<Vec<Uuid>>,
+    }
+
+    impl RecordingSender {
+        fn new(channel: NotificationChannel) -> Arc<Self> {
+            Arc::new(Self { channel, sent: Mutex::new(Vec::new()) })
+        }
+    }
+
+    #[async_trait]
+    impl NotificationSender for RecordingSender {
+        fn channel(&self) -> NotificationChannel { self.channel }
+        async fn send(&self, notification: &Notification) -> RepoResult<()> {
+            self.sent.lock().unwrap().push(notification.id);
+            Ok(())
+        }
+    }
+
+    fn notification(user_id: Uuid, notification_type: NotificationType) -> Notification {
+        Notification::new(user_id, "Title".to_string(), "Message".to_string(), notification_type, NotificationPriority::Normal, None)
+    }
+
+    #[tokio::test]
+    async fn category_preferences_filter_channels() {
+        let user_id = Uuid::new_v4();
+        let mut preferences = NotificationPreferences::new(user_id);
+        preferences.categories = HashMap::from([
+            (NotificationCategory::Campaigns, ChannelPreferences { in_app: false, email: true, push: false }),
+            (NotificationCategory::Rewards, ChannelPreferences::ALL),
+        ]);
+        let repository = Arc::new(RecordingRepository::default());
+        let (email, push) = (RecordingSender::new(NotificationChannel::Email), RecordingSender::new(NotificationChannel::Push));
+        let dispatcher = NotificationDispatcher::new(repository.clone(), Arc::new(FixedPreferences(preferences)))
+            .with_sender(email.clone())
+            .with_sender(push.clone());
+
+        let campaign = notification(user_id, NotificationType::CampaignEnded);
+        assert_eq!(dispatcher.dispatch(&campaign).await.unwrap(), vec![NotificationChannel::Email]);
+
+        let reward = notification(user_id, NotificationType::RewardEarned);
+        assert_eq!(
+            dispatcher.dispatch(&reward).await.unwrap(),
+            vec![NotificationChannel::InApp, NotificationChannel::Email, NotificationChannel::Push]
+        );
+
+        // Sin entrada en el documento: solo in-app
+        let custom = notification(user_id, NotificationType::Custom("artist_shoutout".to_string()));
+        assert_eq!(dispatcher.dispatch(&custom).await.unwrap(), vec![NotificationChannel::InApp]);
+
+        let stored: Vec<Uuid> = repository.created.lock().unwrap().iter().map(|n| n.id).collect();
+        assert_eq!(stored, vec![reward.id, custom.id]);
+        assert_eq!(*email.sent.lock().unwrap(), vec![campaign.id, reward.id]);
+        assert_eq!(*push.sent.lock().unwrap(), vec![reward.id]);
+    }
+
+    #[derive(Default)]
+    struct RecordingDigests {
+        queued: Mutex<Vec<DigestEntry>>,
+    }
+
+    #[async_trait]
+    impl DigestRepository for RecordingDigests {
+        async fn enqueue(&self, entry: &DigestEntry) -> RepoResult<()> {
+            self.queued.lock().unwrap().push(entry.clone());
+            Ok(())
+        }
+        async fn claim_due(&self, _now: chrono::DateTime<chrono::Utc>) -> RepoResult<Vec<DigestEntry>> { Ok(Vec::new()) }
+        async fn complete(&self, _entry_ids: &[Uuid]) -> RepoResult<()> { Ok(()) }
+        async fn release(&self, _entry_ids: &[Uuid]) -> RepoResult<()> { Ok(()) }
+    }
+
+    #[tokio::test]
+    async fn digest_categories_queue_email_instead_of_sending() {
+        let user_id = Uuid::new_v4();
+        let mut preferences = NotificationPreferences::new(user_id);
+        preferences.categories = HashMap::from([
+            (NotificationCategory::Rewards, ChannelPreferences::ALL),
+            (NotificationCategory::Ventures, ChannelPreferences::ALL),
+        ]);
+        preferences.digest_categories.insert(NotificationCategory::Rewards);
+        preferences.digest_frequency = DigestFrequency::Daily;
+        let digests = Arc::new(RecordingDigests::default());
+        let (email, push) = (RecordingSender::new(NotificationChannel::Email), RecordingSender::new(NotificationChannel::Push));
+        let dispatcher = NotificationDispatcher::new(Arc::new(RecordingRepository::default()), Arc::new(FixedPreferences(preferences)))
+            .with_sender(email.clone())
+            .with_sender(push.clone())
+            .with_digest_queue(digests.clone());
+
+        let reward = notification(user_id, NotificationType::RewardEarned);
+        assert_eq!(
+            dispatcher.dispatch(&reward).await.unwrap(),
+            vec![NotificationChannel::InApp, NotificationChannel::Email, NotificationChannel::Push]
+        );
+        let venture = notification(user_id, NotificationType::VentureCreated);
+        dispatcher.dispatch(&venture).await.unwrap();
+
+        // El push no espera al resumen
+        assert_eq!(*push.sent.lock().unwrap(), vec![reward.id, venture.id]);
+        assert_eq!(*email.sent.lock().unwrap(), vec![venture.id]);
+        let queued = digests.queued.lock().unwrap().clone();
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].notification_id, reward.id);
+        assert_eq!(queued[0].period_end - queued[0].period_start, chrono::Duration::days(1));
+    }
+
+    #[tokio::test]
+    async fn users_without_preferences_get_in_app_only() {
+        let repository = Arc::new(RecordingRepository::default());
+        let email = RecordingSender::new(NotificationChannel::Email);
+        let dispatcher = NotificationDispatcher::new(repository.clone(), Arc::new(MockNotificationPreferencesRepository::new()))
+            .with_sender(email.clone());
+
+        let delivered = dispatcher.dispatch(&notification(Uuid::new_v4(), NotificationType::SecurityAlert)).await.unwrap();
+
+        assert_eq!(delivered, vec![NotificationChannel::InApp]);
+        assert_eq!(repository.created.lock().unwrap().len(), 1);
+        assert!(email.sent.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,520 @@
+//! Email Delivery
+//!
+//! Canal email del dispatcher. Las categorías normales se envían al momento;
+//! las que el usuario tiene en modo resumen se encolan y `EmailDigestService`
+//! envía un único email por usuario y periodo cuando este se cierra.
+//!
+//! Cada email lleva un enlace de baja firmado (`SignedLinkService`) que
+//! actualiza las preferencias sin iniciar sesión.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tracing::{debug, error};
+use uuid::Uuid;
+
+use crate::bounded_contexts::notifications::domain::entities::{
+    DigestEntry, DigestFrequency, Notification, NotificationCategory, NotificationChannel, NotificationPreferences,
+};
+use crate::bounded_contexts::notifications::domain::repositories::{
+    DigestRepository, NotificationPreferencesRepository,
+};
+use crate::shared::infrastructure::auth::{LinkSignature, SignedLinkService};
+use super::dispatcher::NotificationSender;
+use super::email_templates::{category_label, DigestEmailContext, EmailTemplates, NotificationEmailContext};
+
+/// Los enlaces de baja siguen funcionando en emails de hace meses
+pub const UNSUBSCRIBE_LINK_TTL_DAYS: i64 = 365;
+
+/// Dirección a la que se envían los emails de un usuario
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailRecipient {
+    pub user_id: Uuid,
+    pub email: String,
+    pub name: String,
+}
+
+#[async_trait]
+pub trait EmailRecipientDirectory: Send + Sync {
+    /// `None` si el usuario no existe o está desactivado
+    async fn email_recipient(&self, user_id: Uuid) -> Result<Option<EmailRecipient>, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailMessage {
+    pub to_email: String,
+    pub to_name: String,
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+    /// También va en la cabecera `List-Unsubscribe`
+    pub unsubscribe_url: String,
+}
+
+/// Transporte de email (SMTP en producción)
+#[async_trait]
+pub trait EmailSender: Send + Sync {
+    async fn send(&self, message: &EmailMessage) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// Ruta firmada del enlace de baja, relativa a la API de notificaciones
+pub fn unsubscribe_path(user_id: Uuid, category: Option<NotificationCategory>) -> String {
+    format!("/unsubscribe/{}/{}", user_id, category.map(|c| c.as_str()).unwrap_or("all"))
+}
+
+pub struct EmailNotificationSender {
+    sender: Arc<dyn EmailSender>,
+    recipients: Arc<dyn EmailRecipientDirectory>,
+    templates: EmailTemplates,
+    links: Arc<SignedLinkService>,
+    /// URL pública de la API de notificaciones, sin barra final
+    public_url: String,
+}
+
+impl EmailNotificationSender {
+    pub fn new(
+        sender: Arc<dyn EmailSender>,
+        recipients: Arc<dyn EmailRecipientDirectory>,
+        links: Arc<SignedLinkService>,
+        public_url: String,
+    ) -> Self {
+        Self {
+            sender,
+            recipients,
+            templates: EmailTemplates::new(),
+            links,
+            public_url: public_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    fn unsubscribe_url(&self, user_id: Uuid, category: Option<NotificationCategory>) -> String {
+        let expires_at = Utc::now() + chrono::Duration::days(UNSUBSCRIBE_LINK_TTL_DAYS);
+        format!("{}{}", self.public_url, self.links.sign(&unsubscribe_path(user_id, category), expires_at))
+    }
+
+    /// Resumen de las entradas de `[period_start, period_end)`; la baja del
+    /// resumen es de todos los emails
+    pub async fn send_digest(
+        &self,
+        user_id: Uuid,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        entries: &[DigestEntry],
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let Some(recipient) = self.recipients.email_recipient(user_id).await? else {
+            debug!("User {} has no email address, dropping digest", user_id);
+            return Ok(());
+        };
+        let frequency = if period_end - period_start <= chrono::Duration::days(1) {
+            DigestFrequency::Daily
+        } else {
+            DigestFrequency::Weekly
+        };
+        let unsubscribe_url = self.unsubscribe_url(user_id, None);
+        let context = DigestEmailContext::new(
+            &recipient.name,
+            frequency,
+            period_start,
+            period_end,
+            entries,
+            unsubscribe_url.clone(),
+        );
+        let rendered = self.templates.render_digest(&context)?;
+
+        self.sender.send(&EmailMessage {
+            to_email: recipient.email,
+            to_name: recipient.name,
+            subject: rendered.subject,
+            html: rendered.html,
+            text: rendered.text,
+            unsubscribe_url,
+        }).await
+    }
+}
+
+#[async_trait]
+impl NotificationSender for EmailNotificationSender {
+    fn channel(&self) -> NotificationChannel {
+        NotificationChannel::Email
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let Some(recipient) = self.recipients.email_recipient(notification.user_id).await? else {
+            debug!("User {} has no email address, skipping notification {}", notification.user_id, notification.id);
+            return Ok(());
+        };
+        let category = NotificationCategory::of(&notification.notification_type);
+        let unsubscribe_url = self.unsubscribe_url(notification.user_id, category);
+        let rendered = self.templates.render_notification(&NotificationEmailContext {
+            recipient_name: recipient.name.clone(),
+            title: notification.title.clone(),
+            message: notification.message.clone(),
+            category: category.map(category_label).unwrap_or("VibeStream").to_string(),
+            unsubscribe_url: unsubscribe_url.clone(),
+        })?;
+
+        self.sender.send(&EmailMessage {
+            to_email: recipient.email,
+            to_name: recipient.name,
+            subject: rendered.subject,
+            html: rendered.html,
+            text: rendered.text,
+            unsubscribe_url,
+        }).await
+    }
+}
+
+#[derive(Debug)]
+pub enum UnsubscribeError {
+    /// Firma incorrecta o enlace caducado
+    InvalidLink,
+    UnknownCategory(String),
+    Repository(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl std::fmt::Display for UnsubscribeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnsubscribeError::InvalidLink => write!(f, "Invalid or expired unsubscribe link"),
+            UnsubscribeError::UnknownCategory(scope) => write!(f, "Unknown notification category: {}", scope),
+            UnsubscribeError::Repository(e) => write!(f, "Preferences error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for UnsubscribeError {}
+
+/// Baja por enlace firmado, sin sesión
+pub struct EmailUnsubscribeService {
+    links: SignedLinkService,
+    preferences: Arc<dyn NotificationPreferencesRepository>,
+}
+
+impl EmailUnsubscribeService {
+    pub fn new(links: SignedLinkService, preferences: Arc<dyn NotificationPreferencesRepository>) -> Self {
+        Self { links, preferences }
+    }
+
+    /// `scope` es una categoría o `all`
+    pub async fn unsubscribe(
+        &self,
+        user_id: Uuid,
+        scope: &str,
+        link: &LinkSignature,
+        now: DateTime<Utc>,
+    ) -> Result<NotificationPreferences, UnsubscribeError> {
+        let category = match scope {
+            "all" => None,
+            other => Some(other.parse::<NotificationCategory>().map_err(|_| UnsubscribeError::UnknownCategory(other.to_string()))?),
+        };
+        if !self.links.verify(&unsubscribe_path(user_id, category), link, now) {
+            return Err(UnsubscribeError::InvalidLink);
+        }
+
+        let mut preferences = self.preferences
+            .get_by_user_id(user_id)
+            .await
+            .map_err(UnsubscribeError::Repository)?
+            .unwrap_or_else(|| NotificationPreferences::new(user_id));
+        preferences.unsubscribe_email(category);
+        self.preferences.update(&preferences).await.map_err(UnsubscribeError::Repository)?;
+        Ok(preferences)
+    }
+}
+
+/// Envío de los resúmenes de periodos ya cerrados
+pub struct EmailDigestService {
+    digests: Arc<dyn DigestRepository>,
+    preferences: Arc<dyn NotificationPreferencesRepository>,
+    email: Arc<EmailNotificationSender>,
+}
+
+impl EmailDigestService {
+    pub fn new(
+        digests: Arc<dyn DigestRepository>,
+        preferences: Arc<dyn NotificationPreferencesRepository>,
+        email: Arc<EmailNotificationSender>,
+    ) -> Self {
+        Self { digests, preferences, email }
+    }
+
+    /// Un email por usuario y periodo. Las categorías cuyo email se ha
+    /// desactivado desde que se encolaron no salen; un envío fallido devuelve
+    /// sus entradas a la cola para la siguiente ejecución. Devuelve los
+    /// resúmenes enviados.
+    pub async fn send_due_digests(&self, now: DateTime<Utc>) -> Result<u32, Box<dyn std::error::Error + Send + Sync>> {
+        let mut batches: BTreeMap<(Uuid, DateTime<Utc>, DateTime<Utc>), Vec<DigestEntry>> = BTreeMap::new();
+        for entry in self.digests.claim_due(now).await? {
+            batches.entry((entry.user_id, entry.period_start, entry.period_end)).or_default().push(entry);
+        }
+
+        let mut sent = 0;
+        for ((user_id, period_start, period_end), entries) in batches {
+            let entry_ids: Vec<Uuid> = entries.iter().map(|entry| entry.id).collect();
+            let preferences = match self.preferences.get_by_user_id(user_id).await {
+                Ok(preferences) => preferences.unwrap_or_else(|| NotificationPreferences::new(user_id)),
+                Err(e) => {
+                    error!("Failed to load preferences for digest of user {}: {}", user_id, e);
+                    self.digests.release(&entry_ids).await?;
+                    continue;
+                }
+            };
+            let wanted: Vec<DigestEntry> = entries
+                .into_iter()
+                .filter(|entry| preferences.channels_for_category(entry.category).email)
+                .collect();
+            if wanted.is_empty() {
+                self.digests.complete(&entry_ids).await?;
+                continue;
+            }
+
+            match self.email.send_digest(user_id, period_start, period_end, &wanted).await {
+                Ok(()) => {
+                    self.digests.complete(&entry_ids).await?;
+                    sent += 1;
+                }
+                Err(e) => {
+                    error!("Failed to send digest to user {}: {}", user_id, e);
+                    self.digests.release(&entry_ids).await?;
+                }
+            }
+        }
+        Ok(sent)
+    }
+}
+
+pub struct EmailDigestJob {
+    service: Arc<EmailDigestService>,
+    interval: Duration,
+}
+
+impl EmailDigestJob {
+    pub fn new(service: Arc<EmailDigestService>, interval: Duration) -> Self {
+        Self { service, interval }
+    }
+
+    pub fn start(&self) -> tokio::task::JoinHandle<()> {
+        let service = Arc::clone(&self.service);
+        let interval = self.interval;
+
+        tokio::spawn(async move {
+            tracing::info!("🚀 Email digest job started");
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match service.send_due_digests(Utc::now()).await {
+                    Ok(count) if count > 0 => tracing::info!("✅ Sent {} email digests", count),
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Email digest job failed: {:?}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use chrono::TimeZone;
+    use crate::bounded_contexts::notifications::application::NotificationDispatcher;
+    use crate::bounded_contexts::notifications::domain::entities::{
+        ChannelPreferences, NotificationPriority, NotificationType,
+    };
+    use crate::bounded_contexts::notifications::infrastructure::MockNotificationRepository;
+
+    type RepoResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+    #[derive(Default)]
+    struct RecordingEmailSender {
+        sent: Mutex<Vec<EmailMessage>>,
+    }
+
+    #[async_trait]
+    impl EmailSender for RecordingEmailSender {
+        async fn send(&self, message: &EmailMessage) -> RepoResult<()> {
+            self.sent.lock().unwrap().push(message.clone());
+            Ok(())
+        }
+    }
+
+    struct Directory;
+
+    #[async_trait]
+    impl EmailRecipientDirectory for Directory {
+        async fn email_recipient(&self, user_id: Uuid) -> RepoResult<Option<EmailRecipient>> {
+            Ok(Some(EmailRecipient { user_id, email: format!("{}@example.com", user_id), name: "fan".to_string() }))
+        }
+    }
+
+    #[derive(Default)]
+    struct InMemoryPreferences(Mutex<HashMap<Uuid, NotificationPreferences>>);
+
+    #[async_trait]
+    impl NotificationPreferencesRepository for InMemoryPreferences {
+        async fn get_by_user_id(&self, user_id: Uuid) -> RepoResult<Option<NotificationPreferences>> {
+            Ok(self.0.lock().unwrap().get(&user_id).cloned())
+        }
+        async fn create(&self, preferences: &NotificationPreferences) -> RepoResult<()> { self.update(preferences).await }
+        async fn update(&self, preferences: &NotificationPreferences) -> RepoResult<()> {
+            self.0.lock().unwrap().insert(preferences.user_id, preferences.clone());
+            Ok(())
+        }
+        async fn delete(&self, user_id: Uuid) -> RepoResult<()> {
+            self.0.lock().unwrap().remove(&user_id);
+            Ok(())
+        }
+    }
+
+    /// Entradas con su marca de reservada
+    #[derive(Default)]
+    struct InMemoryDigests(Mutex<Vec<(DigestEntry, bool)>>);
+
+    #[async_trait]
+    impl DigestRepository for InMemoryDigests {
+        async fn enqueue(&self, entry: &DigestEntry) -> RepoResult<()> {
+            self.0.lock().unwrap().push((entry.clone(), false));
+            Ok(())
+        }
+        async fn claim_due(&self, now: DateTime<Utc>) -> RepoResult<Vec<DigestEntry>> {
+            let mut claimed = Vec::new();
+            for (entry, is_claimed) in self.0.lock().unwrap().iter_mut() {
+                if !*is_claimed && entry.is_due(now) {
+                    *is_claimed = true;
+                    claimed.push(entry.clone());
+                }
+            }
+            Ok(claimed)
+        }
+        async fn complete(&self, entry_ids: &[Uuid]) -> RepoResult<()> {
+            self.0.lock().unwrap().retain(|(entry, _)| !entry_ids.contains(&entry.id));
+            Ok(())
+        }
+        async fn release(&self, entry_ids: &[Uuid]) -> RepoResult<()> {
+            for (entry, is_claimed) in self.0.lock().unwrap().iter_mut() {
+                if entry_ids.contains(&entry.id) {
+                    *is_claimed = false;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn links() -> Arc<SignedLinkService> {
+        Arc::new(SignedLinkService::new("test-secret"))
+    }
+
+    fn email_sender(transport: Arc<RecordingEmailSender>) -> Arc<EmailNotificationSender> {
+        Arc::new(EmailNotificationSender::new(transport, Arc::new(Directory), links(), "https://api.vibestream.test/".to_string()))
+    }
+
+    fn reward_at(user_id: Uuid, at: DateTime<Utc>) -> Notification {
+        let mut notification = Notification::new(
+            user_id,
+            "Recompensa".to_string(),
+            format!("Recompensa del {}", at.format("%Y-%m-%d %H:%M:%S")),
+            NotificationType::RewardEarned,
+            NotificationPriority::Normal,
+            None,
+        );
+        notification.created_at = at;
+        notification
+    }
+
+    /// Separa la ruta del enlace de sus parámetros de firma
+    fn parse_unsubscribe_url(url: &str) -> (String, LinkSignature) {
+        let (path, query) = url.trim_start_matches("https://api.vibestream.test").split_once('?').unwrap();
+        let params: HashMap<&str, &str> = query.split('&').filter_map(|pair| pair.split_once('=')).collect();
+        (
+            path.to_string(),
+            LinkSignature { expires: params["expires"].parse().unwrap(), signature: params["signature"].to_string() },
+        )
+    }
+
+    #[tokio::test]
+    async fn digest_covers_only_closed_periods_one_email_per_user() {
+        let (ana, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        let digests = Arc::new(InMemoryDigests::default());
+        let preferences = Arc::new(InMemoryPreferences::default());
+        for user_id in [ana, bob] {
+            let mut prefs = NotificationPreferences::new(user_id);
+            prefs.categories.insert(NotificationCategory::Rewards, ChannelPreferences::ALL);
+            preferences.update(&prefs).await.unwrap();
+        }
+
+        // Semana del lunes 6 al domingo 12 de abril de 2026, y el arranque de la siguiente
+        let last_second = Utc.with_ymd_and_hms(2026, 4, 12, 23, 59, 59).unwrap();
+        let next_week = Utc.with_ymd_and_hms(2026, 4, 13, 0, 0, 0).unwrap();
+        for notification in [
+            reward_at(ana, Utc.with_ymd_and_hms(2026, 4, 6, 0, 0, 0).unwrap()),
+            reward_at(ana, last_second),
+            reward_at(ana, next_week),
+            reward_at(bob, Utc.with_ymd_and_hms(2026, 4, 8, 12, 0, 0).unwrap()),
+        ] {
+            let entry = DigestEntry::for_notification(&notification, DigestFrequency::Weekly).unwrap();
+            digests.enqueue(&entry).await.unwrap();
+        }
+
+        let transport = Arc::new(RecordingEmailSender::default());
+        let service = EmailDigestService::new(digests.clone(), preferences, email_sender(transport.clone()));
+
+        // La semana sigue abierta hasta su último segundo
+        assert_eq!(service.send_due_digests(last_second).await.unwrap(), 0);
+
+        assert_eq!(service.send_due_digests(next_week).await.unwrap(), 2);
+        let sent = transport.sent.lock().unwrap().clone();
+        let ana_digest = sent.iter().find(|m| m.to_email == format!("{}@example.com", ana)).unwrap();
+        assert_eq!(ana_digest.subject, "Tu resumen semanal de VibeStream");
+        assert!(ana_digest.text.contains("entre el 2026-04-06 y el 2026-04-12"));
+        assert!(ana_digest.text.contains("Recompensas (2)"));
+        assert!(ana_digest.text.contains("Recompensa del 2026-04-12 23:59:59"));
+        assert!(!ana_digest.text.contains("Recompensa del 2026-04-13"));
+        assert!(ana_digest.unsubscribe_url.contains(&format!("/unsubscribe/{}/all?", ana)));
+
+        // Solo queda la entrada de la semana nueva, sin reservar
+        let remaining = digests.0.lock().unwrap().clone();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].0.created_at, next_week);
+        assert!(!remaining[0].1);
+        assert_eq!(service.send_due_digests(next_week).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn unsubscribe_link_suppresses_future_emails() {
+        let user_id = Uuid::new_v4();
+        let preferences = Arc::new(InMemoryPreferences::default());
+        let mut prefs = NotificationPreferences::new(user_id);
+        prefs.categories.insert(NotificationCategory::Rewards, ChannelPreferences::ALL);
+        preferences.update(&prefs).await.unwrap();
+
+        let transport = Arc::new(RecordingEmailSender::default());
+        let dispatcher = NotificationDispatcher::new(Arc::new(MockNotificationRepository::new()), preferences.clone())
+            .with_sender(email_sender(transport.clone()));
+
+        let delivered = dispatcher.dispatch(&reward_at(user_id, Utc::now())).await.unwrap();
+        assert_eq!(delivered, vec![NotificationChannel::InApp, NotificationChannel::Email]);
+        let (path, link) = parse_unsubscribe_url(&transport.sent.lock().unwrap()[0].unsubscribe_url);
+        assert_eq!(path, unsubscribe_path(user_id, Some(NotificationCategory::Rewards)));
+
+        let unsubscribe = EmailUnsubscribeService::new(SignedLinkService::new("test-secret"), preferences.clone());
+        // El enlace no sirve para otra categoría ni con la firma alterada
+        assert!(matches!(
+            unsubscribe.unsubscribe(user_id, "all", &link, Utc::now()).await,
+            Err(UnsubscribeError::InvalidLink)
+        ));
+        let tampered = LinkSignature { signature: "00".repeat(32), ..link.clone() };
+        assert!(matches!(
+            unsubscribe.unsubscribe(user_id, "rewards", &tampered, Utc::now()).await,
+            Err(UnsubscribeError::InvalidLink)
+        ));
+
+        unsubscribe.unsubscribe(user_id, "rewards", &link, Utc::now()).await.unwrap();
+
+        let delivered = dispatcher.dispatch(&reward_at(user_id, Utc::now())).await.unwrap();
+        assert_eq!(delivered, vec![NotificationChannel::InApp]);
+        assert_eq!(transport.sent.lock().unwrap().len(), 1);
+    }
+}
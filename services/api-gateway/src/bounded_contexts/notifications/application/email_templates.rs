@@ -0,0 +1,232 @@
+//! Email Templates
+//!
+//! Versiones HTML y texto plano de cada email, renderizadas con minijinja a
+//! partir de `templates/email`. El HTML escapa todas las variables; el texto
+//! plano se envía tal cual.
+
+use std::collections::BTreeMap;
+use chrono::{DateTime, Duration, Utc};
+use minijinja::Environment;
+use serde::Serialize;
+
+use crate::bounded_contexts::notifications::domain::entities::{
+    DigestEntry, DigestFrequency, NotificationCategory,
+};
+
+const NOTIFICATION_HTML: &str = "notification.html";
+const NOTIFICATION_TEXT: &str = "notification.txt";
+const DIGEST_HTML: &str = "digest.html";
+const DIGEST_TEXT: &str = "digest.txt";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedEmail {
+    pub subject: String,
+    pub html: String,
+    pub text: String,
+}
+
+/// Nombre de la categoría tal como aparece en los emails
+pub fn category_label(category: NotificationCategory) -> &'static str {
+    match category {
+        NotificationCategory::Ventures => "Ventures",
+        NotificationCategory::Rewards => "Recompensas",
+        NotificationCategory::Campaigns => "Campañas",
+        NotificationCategory::Account => "Cuenta",
+        NotificationCategory::Security => "Seguridad",
+        NotificationCategory::System => "Sistema",
+        NotificationCategory::Marketing => "Novedades",
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NotificationEmailContext {
+    pub recipient_name: String,
+    pub title: String,
+    pub message: String,
+    /// Etiqueta de la categoría (`category_label`)
+    pub category: String,
+    pub unsubscribe_url: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DigestItem {
+    pub date: String,
+    pub title: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DigestSection {
+    pub label: String,
+    pub items: Vec<DigestItem>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DigestEmailContext {
+    pub recipient_name: String,
+    pub subject: String,
+    pub period_from: String,
+    pub period_to: String,
+    pub frequency_label: String,
+    pub sections: Vec<DigestSection>,
+    pub unsubscribe_url: String,
+}
+
+impl DigestEmailContext {
+    /// Una sección por categoría (en el orden de `NotificationCategory`) con
+    /// sus notificaciones de más antigua a más reciente
+    pub fn new(
+        recipient_name: &str,
+        frequency: DigestFrequency,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        entries: &[DigestEntry],
+        unsubscribe_url: String,
+    ) -> Self {
+        let mut by_category: BTreeMap<NotificationCategory, Vec<&DigestEntry>> = BTreeMap::new();
+        for entry in entries {
+            by_category.entry(entry.category).or_default().push(entry);
+        }
+        let sections = by_category
+            .into_iter()
+            .map(|(category, mut entries)| {
+                entries.sort_by_key(|entry| entry.created_at);
+                DigestSection {
+                    label: category_label(category).to_string(),
+                    items: entries
+                        .into_iter()
+                        .map(|entry| DigestItem {
+                            date: entry.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                            title: entry.title.clone(),
+                            message: entry.message.clone(),
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+
+        let frequency_label = match frequency {
+            DigestFrequency::Daily => "diario",
+            DigestFrequency::Weekly => "semanal",
+        };
+        Self {
+            recipient_name: recipient_name.to_string(),
+            subject: format!("Tu resumen {} de VibeStream", frequency_label),
+            period_from: period_start.format("%Y-%m-%d").to_string(),
+            // El fin del periodo es exclusivo: se muestra su último día
+            period_to: (period_end - Duration::days(1)).format("%Y-%m-%d").to_string(),
+            frequency_label: frequency_label.to_string(),
+            sections,
+            unsubscribe_url,
+        }
+    }
+}
+
+pub struct EmailTemplates {
+    env: Environment<'static>,
+}
+
+impl EmailTemplates {
+    pub fn new() -> Self {
+        let mut env = Environment::new();
+        env.set_trim_blocks(true);
+        env.set_lstrip_blocks(true);
+        // Plantillas compiladas en el binario: un error aquí es un bug, no configuración
+        for (name, source) in [
+            (NOTIFICATION_HTML, include_str!("../templates/email/notification.html")),
+            (NOTIFICATION_TEXT, include_str!("../templates/email/notification.txt")),
+            (DIGEST_HTML, include_str!("../templates/email/digest.html")),
+            (DIGEST_TEXT, include_str!("../templates/email/digest.txt")),
+        ] {
+            env.add_template(name, source).expect("email templates are valid");
+        }
+        Self { env }
+    }
+
+    pub fn render_notification(&self, context: &NotificationEmailContext) -> Result<RenderedEmail, minijinja::Error> {
+        Ok(RenderedEmail {
+            subject: context.title.clone(),
+            html: self.env.get_template(NOTIFICATION_HTML)?.render(context)?,
+            text: self.env.get_template(NOTIFICATION_TEXT)?.render(context)?,
+        })
+    }
+
+    pub fn render_digest(&self, context: &DigestEmailContext) -> Result<RenderedEmail, minijinja::Error> {
+        Ok(RenderedEmail {
+            subject: context.subject.clone(),
+            html: self.env.get_template(DIGEST_HTML)?.render(context)?,
+            text: self.env.get_template(DIGEST_TEXT)?.render(context)?,
+        })
+    }
+}
+
+impl Default for EmailTemplates {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+    use uuid::Uuid;
+
+    fn entry(category: NotificationCategory, at: DateTime<Utc>, title: &str, message: &str) -> DigestEntry {
+        let (period_start, period_end) = DigestFrequency::Weekly.period_containing(at);
+        DigestEntry {
+            id: Uuid::new_v4(),
+            user_id: Uuid::nil(),
+            notification_id: Uuid::new_v4(),
+            category,
+            title: title.to_string(),
+            message: message.to_string(),
+            created_at: at,
+            period_start,
+            period_end,
+        }
+    }
+
+    #[test]
+    fn notification_email_matches_snapshot() {
+        let rendered = EmailTemplates::new()
+            .render_notification(&NotificationEmailContext {
+                recipient_name: "ana".to_string(),
+                title: "Tienes un nuevo seguidor".to_string(),
+                message: "Un fan ha empezado a seguirte <3".to_string(),
+                category: category_label(NotificationCategory::Account).to_string(),
+                unsubscribe_url: "https://vibestream.test/unsubscribe/account?expires=1&signature=abc".to_string(),
+            })
+            .unwrap();
+
+        assert_eq!(rendered.subject, "Tienes un nuevo seguidor");
+        assert_eq!(rendered.html.trim_end(), include_str!("../templates/email/snapshots/notification.html").trim_end());
+        assert_eq!(rendered.text.trim_end(), include_str!("../templates/email/snapshots/notification.txt").trim_end());
+    }
+
+    #[test]
+    fn digest_email_matches_snapshot() {
+        let at = |day, hour, minute| Utc.with_ymd_and_hms(2026, 4, day, hour, minute, 0).unwrap();
+        let entries = vec![
+            entry(NotificationCategory::Rewards, at(9, 18, 30), "Recompensa recibida", "Has recibido 12 tokens por tus escuchas."),
+            entry(NotificationCategory::Rewards, at(7, 10, 0), "Recompensa recibida", "Has recibido 5 tokens por tus escuchas."),
+            entry(NotificationCategory::Ventures, at(8, 9, 15), "Nueva inversión en tu venture", "Un fan ha invertido 50 en tu venture Gira 2026."),
+        ];
+        let (period_start, period_end) = DigestFrequency::Weekly.period_containing(at(7, 10, 0));
+        let context = DigestEmailContext::new(
+            "ana",
+            DigestFrequency::Weekly,
+            period_start,
+            period_end,
+            &entries,
+            "https://vibestream.test/unsubscribe/all?expires=1&signature=abc".to_string(),
+        );
+
+        let rendered = EmailTemplates::new().render_digest(&context).unwrap();
+
+        assert_eq!(rendered.subject, "Tu resumen semanal de VibeStream");
+        assert_eq!(rendered.html.trim_end(), include_str!("../templates/email/snapshots/digest.html").trim_end());
+        assert_eq!(rendered.text.trim_end(), include_str!("../templates/email/snapshots/digest.txt").trim_end());
+    }
+}
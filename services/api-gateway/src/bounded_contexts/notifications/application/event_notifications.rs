@@ -0,0 +1,506 @@
+//! Event Notifications
+//!
+//! Traduce los eventos de integración del stream de Redis en notificaciones:
+//! cada tipo de evento tiene en el `EventTemplateRegistry` una plantilla por
+//! destinatario. Las plantillas usan `{{campo}}` (o `{{campo.anidado}}`) sobre
+//! el payload del evento.
+//!
+//! Un evento que no se puede convertir (plantilla que no renderiza, payload sin
+//! el destinatario, destinatario que no existe) no se reintenta: va a la cola
+//! de dead letters con el motivo. Se renderiza todo antes de entregar nada, así
+//! que un evento rechazado no deja notificaciones a medias.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use async_trait::async_trait;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::bounded_contexts::notifications::domain::entities::{
+    Notification, NotificationPriority, NotificationType,
+};
+use super::dispatcher::NotificationDispatcher;
+
+type ServiceResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+// =============================================================================
+// PLANTILLAS
+// =============================================================================
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum TemplateError {
+    #[error("missing variable `{0}`")]
+    MissingVariable(String),
+    #[error("variable `{0}` is not a scalar value")]
+    NotScalar(String),
+    #[error("unclosed placeholder at byte {0}")]
+    UnclosedPlaceholder(usize),
+}
+
+/// Sustituye cada `{{ruta}}` por el valor de `ruta` en `variables`
+pub fn render_template(template: &str, variables: &Value) -> Result<String, TemplateError> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find("}}")
+            .ok_or_else(|| TemplateError::UnclosedPlaceholder(template.len() - rest.len() + start))?;
+        let path = after[..end].trim();
+
+        let value = path
+            .split('.')
+            .try_fold(variables, |value, key| value.get(key))
+            .ok_or_else(|| TemplateError::MissingVariable(path.to_string()))?;
+        match value {
+            Value::String(s) => rendered.push_str(s),
+            Value::Number(n) => rendered.push_str(&n.to_string()),
+            Value::Bool(b) => rendered.push_str(&b.to_string()),
+            Value::Null => return Err(TemplateError::MissingVariable(path.to_string())),
+            Value::Array(_) | Value::Object(_) => return Err(TemplateError::NotScalar(path.to_string())),
+        }
+
+        rest = &after[end + 2..];
+    }
+    rendered.push_str(rest);
+
+    Ok(rendered)
+}
+
+/// Quién recibe la notificación, a partir de un campo del payload
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecipientRule {
+    /// El campo es el id del usuario
+    User(&'static str),
+    /// El campo es el id de un artista (`artists.id`); recibe su usuario
+    ArtistUser(&'static str),
+    /// El campo es el id de una venture; recibe el artista que la creó
+    VentureOwner(&'static str),
+}
+
+impl RecipientRule {
+    fn field(&self) -> &'static str {
+        match self {
+            RecipientRule::User(field) | RecipientRule::ArtistUser(field) | RecipientRule::VentureOwner(field) => field,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EventTemplate {
+    pub recipient: RecipientRule,
+    pub notification_type: NotificationType,
+    pub priority: NotificationPriority,
+    pub title: &'static str,
+    pub message: &'static str,
+}
+
+/// Plantillas de un tipo de evento, una por destinatario
+#[derive(Debug, Clone)]
+pub struct EventNotificationRule {
+    pub event_type: &'static str,
+    pub templates: &'static [EventTemplate],
+}
+
+/// Eventos del stream de integración que generan notificaciones. El tipo es
+/// el campo `type` del entry: el nombre de integración para los eventos
+/// traducidos por el `MapperRegistry` y el de dominio para el resto.
+/// `CampaignBudgetDepleted` no pasa por aquí: viaja por el event bus del
+/// orquestador y lo atiende `CampaignBudgetNotificationListener`.
+pub const DEFAULT_EVENT_NOTIFICATIONS: &[EventNotificationRule] = &[
+    EventNotificationRule {
+        event_type: "SharesPurchased",
+        templates: &[
+            EventTemplate {
+                recipient: RecipientRule::User("fan_id"),
+                notification_type: NotificationType::InvestmentMade,
+                priority: NotificationPriority::Normal,
+                title: "Inversión confirmada",
+                message: "Has invertido {{amount}} en la venture {{venture_id}}.",
+            },
+            EventTemplate {
+                recipient: RecipientRule::VentureOwner("venture_id"),
+                notification_type: NotificationType::InvestmentMade,
+                priority: NotificationPriority::Normal,
+                title: "Nueva inversión en tu venture",
+                message: "Un fan ha invertido {{amount}} en tu venture {{venture_id}}.",
+            },
+        ],
+    },
+    EventNotificationRule {
+        event_type: "RewardDistributed",
+        templates: &[EventTemplate {
+            recipient: RecipientRule::User("user_id"),
+            notification_type: NotificationType::RewardEarned,
+            priority: NotificationPriority::Low,
+            title: "Recompensa recibida",
+            message: "Has recibido {{amount.tokens}} tokens por tus escuchas.",
+        }],
+    },
+    EventNotificationRule {
+        event_type: "music.artist.followed",
+        templates: &[EventTemplate {
+            recipient: RecipientRule::ArtistUser("artist_id"),
+            notification_type: NotificationType::NewFollower,
+            priority: NotificationPriority::Low,
+            title: "Tienes un nuevo seguidor",
+            message: "Un fan ha empezado a seguirte.",
+        }],
+    },
+];
+
+/// Plantillas indexadas por tipo de evento
+#[derive(Debug, Clone, Default)]
+pub struct EventTemplateRegistry {
+    rules: HashMap<String, Vec<EventTemplate>>,
+}
+
+impl EventTemplateRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registro con todas las reglas de `DEFAULT_EVENT_NOTIFICATIONS`
+    pub fn with_default_templates() -> Self {
+        let mut registry = Self::new();
+        for rule in DEFAULT_EVENT_NOTIFICATIONS {
+            registry.register(rule);
+        }
+        registry
+    }
+
+    /// Añadir las plantillas de `rule` a las que ya tuviera su tipo de evento
+    pub fn register(&mut self, rule: &EventNotificationRule) {
+        self.rules
+            .entry(rule.event_type.to_string())
+            .or_default()
+            .extend(rule.templates.iter().cloned());
+    }
+
+    /// Vacío para los eventos que no notifican a nadie
+    pub fn templates_for(&self, event_type: &str) -> &[EventTemplate] {
+        self.rules.get(event_type).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+// =============================================================================
+// PUERTOS
+// =============================================================================
+
+/// Resolución de destinatarios que no viajan en el payload
+#[async_trait]
+pub trait RecipientDirectory: Send + Sync {
+    async fn artist_user(&self, artist_id: Uuid) -> ServiceResult<Option<Uuid>>;
+    async fn venture_owner(&self, venture_id: Uuid) -> ServiceResult<Option<Uuid>>;
+}
+
+/// Entry del stream tal como llegó
+#[derive(Debug, Clone, PartialEq)]
+pub struct StreamEvent {
+    pub id: String,
+    pub event_type: String,
+    pub data: String,
+}
+
+/// Destino de los eventos que no se pueden convertir en notificaciones
+#[async_trait]
+pub trait DeadLetterQueue: Send + Sync {
+    async fn dead_letter(&self, event: &StreamEvent, reason: &str) -> ServiceResult<()>;
+}
+
+// =============================================================================
+// SERVICIO
+// =============================================================================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EventOutcome {
+    /// Ninguna plantilla para este tipo de evento
+    Ignored,
+    /// Notificaciones entregadas (una por destinatario)
+    Delivered(usize),
+    /// Rechazado y enviado a dead letters
+    DeadLettered,
+}
+
+pub struct EventNotificationService {
+    registry: EventTemplateRegistry,
+    directory: Arc<dyn RecipientDirectory>,
+    dispatcher: Arc<NotificationDispatcher>,
+    dead_letters: Arc<dyn DeadLetterQueue>,
+}
+
+impl EventNotificationService {
+    pub fn new(
+        registry: EventTemplateRegistry,
+        directory: Arc<dyn RecipientDirectory>,
+        dispatcher: Arc<NotificationDispatcher>,
+        dead_letters: Arc<dyn DeadLetterQueue>,
+    ) -> Self {
+        Self { registry, directory, dispatcher, dead_letters }
+    }
+
+    /// Convertir `event` en notificaciones. Un error indica un fallo
+    /// transitorio (base de datos, Redis): el consumidor no debe confirmar el
+    /// entry para que se reintente.
+    pub async fn handle(&self, event: &StreamEvent) -> ServiceResult<EventOutcome> {
+        let templates = self.registry.templates_for(&event.event_type);
+        if templates.is_empty() {
+            return Ok(EventOutcome::Ignored);
+        }
+
+        let notifications = match self.build_notifications(event, templates).await? {
+            Ok(notifications) => notifications,
+            Err(reason) => {
+                tracing::warn!("Dead-lettering {} event {}: {}", event.event_type, event.id, reason);
+                self.dead_letters.dead_letter(event, &reason).await?;
+                return Ok(EventOutcome::DeadLettered);
+            }
+        };
+
+        for notification in &notifications {
+            self.dispatcher.dispatch(notification).await?;
+        }
+
+        Ok(EventOutcome::Delivered(notifications.len()))
+    }
+
+    /// El resultado interno es el motivo de rechazo de un evento que nunca
+    /// podrá convertirse; el externo, los fallos transitorios.
+    async fn build_notifications(
+        &self,
+        event: &StreamEvent,
+        templates: &[EventTemplate],
+    ) -> ServiceResult<Result<Vec<Notification>, String>> {
+        let data: Value = match serde_json::from_str(&event.data) {
+            Ok(data) => data,
+            Err(e) => return Ok(Err(format!("invalid event data: {}", e))),
+        };
+        let payload = event_payload(&data);
+
+        let mut notifications = Vec::with_capacity(templates.len());
+        for template in templates {
+            let Some(user_id) = self.resolve_recipient(template.recipient, payload).await? else {
+                return Ok(Err(format!("no recipient for {:?}", template.recipient)));
+            };
+            let rendered = render_template(template.title, payload)
+                .and_then(|title| Ok((title, render_template(template.message, payload)?)));
+            let (title, message) = match rendered {
+                Ok(rendered) => rendered,
+                Err(e) => return Ok(Err(format!("template for {:?} failed to render: {}", template.recipient, e))),
+            };
+
+            notifications.push(Notification::new(
+                user_id,
+                title,
+                message,
+                template.notification_type.clone(),
+                template.priority.clone(),
+                Some(serde_json::json!({
+                    "event_type": event.event_type,
+                    "stream_id": event.id,
+                    "payload": payload,
+                })),
+            ));
+        }
+
+        Ok(Ok(notifications))
+    }
+
+    async fn resolve_recipient(&self, rule: RecipientRule, payload: &Value) -> ServiceResult<Option<Uuid>> {
+        let Some(id) = payload
+            .get(rule.field())
+            .and_then(Value::as_str)
+            .and_then(|id| Uuid::parse_str(id).ok())
+        else {
+            return Ok(None);
+        };
+
+        match rule {
+            RecipientRule::User(_) => Ok(Some(id)),
+            RecipientRule::ArtistUser(_) => self.directory.artist_user(id).await,
+            RecipientRule::VentureOwner(_) => self.directory.venture_owner(id).await,
+        }
+    }
+}
+
+/// Los eventos traducidos viajan dentro del sobre de integración
+/// (`metadata.payload`); el resto se publica tal cual.
+fn event_payload(data: &Value) -> &Value {
+    match data.get("metadata").and_then(|metadata| metadata.get("payload")) {
+        Some(payload) if data.get("event_id").is_some() => payload,
+        _ => data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use crate::bounded_contexts::notifications::infrastructure::MockNotificationPreferencesRepository;
+    use crate::bounded_contexts::notifications::domain::entities::NotificationFilters;
+    use crate::bounded_contexts::notifications::domain::repositories::NotificationRepository;
+
+    #[derive(Default)]
+    struct RecordingRepository {
+        created: Mutex<Vec<Notification>>,
+    }
+
+    #[async_trait]
+    impl NotificationRepository for RecordingRepository {
+        async fn create(&self, notification: &Notification) -> ServiceResult<()> {
+            self.created.lock().unwrap().push(notification.clone());
+            Ok(())
+        }
+        async fn get_by_id(&self, _id: Uuid) -> ServiceResult<Option<Notification>> { Ok(None) }
+        async fn get_by_user_id(&self, _user_id: Uuid, _page: u32, _page_size: u32) -> ServiceResult<(Vec<Notification>, u32, u32)> { Ok((Vec::new(), 0, 0)) }
+        async fn get_unread_count(&self, _user_id: Uuid) -> ServiceResult<u32> { Ok(0) }
+        async fn update(&self, _notification: &Notification) -> ServiceResult<()> { Ok(()) }
+        async fn delete(&self, _id: Uuid) -> ServiceResult<()> { Ok(()) }
+        async fn mark_as_read(&self, _id: Uuid) -> ServiceResult<()> { Ok(()) }
+        async fn mark_as_archived(&self, _id: Uuid) -> ServiceResult<()> { Ok(()) }
+        async fn mark_all_as_read(&self, _user_id: Uuid) -> ServiceResult<()> { Ok(()) }
+        async fn search(&self, _filters: &NotificationFilters, _page: u32, _page_size: u32) -> ServiceResult<Vec<Notification>> { Ok(Vec::new()) }
+        async fn get_summary(&self, _user_id: Uuid) -> ServiceResult<(u32, u32, u32, u32)> { Ok((0, 0, 0, 0)) }
+    }
+
+    struct FixedDirectory {
+        venture_id: Uuid,
+        artist_user_id: Uuid,
+    }
+
+    #[async_trait]
+    impl RecipientDirectory for FixedDirectory {
+        async fn artist_user(&self, _artist_id: Uuid) -> ServiceResult<Option<Uuid>> { Ok(None) }
+        async fn venture_owner(&self, venture_id: Uuid) -> ServiceResult<Option<Uuid>> {
+            Ok((venture_id == self.venture_id).then_some(self.artist_user_id))
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingDeadLetters {
+        events: Mutex<Vec<(StreamEvent, String)>>,
+    }
+
+    #[async_trait]
+    impl DeadLetterQueue for RecordingDeadLetters {
+        async fn dead_letter(&self, event: &StreamEvent, reason: &str) -> ServiceResult<()> {
+            self.events.lock().unwrap().push((event.clone(), reason.to_string()));
+            Ok(())
+        }
+    }
+
+    struct Fixture {
+        service: EventNotificationService,
+        repository: Arc<RecordingRepository>,
+        dead_letters: Arc<RecordingDeadLetters>,
+        venture_id: Uuid,
+        artist_user_id: Uuid,
+    }
+
+    fn fixture(registry: EventTemplateRegistry) -> Fixture {
+        let (venture_id, artist_user_id) = (Uuid::new_v4(), Uuid::new_v4());
+        let repository = Arc::new(RecordingRepository::default());
+        let dead_letters = Arc::new(RecordingDeadLetters::default());
+        let dispatcher = Arc::new(NotificationDispatcher::new(
+            repository.clone(),
+            Arc::new(MockNotificationPreferencesRepository::new()),
+        ));
+        let service = EventNotificationService::new(
+            registry,
+            Arc::new(FixedDirectory { venture_id, artist_user_id }),
+            dispatcher,
+            dead_letters.clone(),
+        );
+        Fixture { service, repository, dead_letters, venture_id, artist_user_id }
+    }
+
+    /// Entry tal como lo deja `RedisStreamEventPublisher` para un `FanInvested`
+    fn shares_purchased(venture_id: Uuid, fan_id: Uuid) -> StreamEvent {
+        let envelope = serde_json::json!({
+            "event_id": Uuid::new_v4(),
+            "event_type": "SharesPurchased",
+            "aggregate_id": venture_id,
+            "metadata": {
+                "source_event_type": "FanInvested",
+                "payload": {
+                    "investment_id": Uuid::new_v4(),
+                    "venture_id": venture_id,
+                    "fan_id": fan_id,
+                    "amount": 250.0,
+                    "investment_type": "RevenueShare",
+                },
+            },
+        });
+        StreamEvent { id: "1700000000000-0".to_string(), event_type: "SharesPurchased".to_string(), data: envelope.to_string() }
+    }
+
+    #[test]
+    fn renders_nested_variables_and_rejects_missing_ones() {
+        let variables = serde_json::json!({ "amount": { "tokens": 1.5 }, "name": "Ana" });
+
+        assert_eq!(render_template("{{ name }} ganó {{amount.tokens}}", &variables).unwrap(), "Ana ganó 1.5");
+        assert_eq!(render_template("{{amount}}", &variables), Err(TemplateError::NotScalar("amount".to_string())));
+        assert_eq!(render_template("{{venture}}", &variables), Err(TemplateError::MissingVariable("venture".to_string())));
+        assert_eq!(render_template("Hola {{name", &variables), Err(TemplateError::UnclosedPlaceholder(5)));
+    }
+
+    #[tokio::test]
+    async fn shares_purchased_notifies_buyer_and_artist() {
+        let fixture = fixture(EventTemplateRegistry::with_default_templates());
+        let fan_id = Uuid::new_v4();
+
+        let outcome = fixture.service.handle(&shares_purchased(fixture.venture_id, fan_id)).await.unwrap();
+
+        assert_eq!(outcome, EventOutcome::Delivered(2));
+        let created = fixture.repository.created.lock().unwrap();
+        let buyer = created.iter().find(|n| n.user_id == fan_id).expect("Buyer notified");
+        assert_eq!(buyer.title, "Inversión confirmada");
+        assert_eq!(buyer.message, format!("Has invertido 250.0 en la venture {}.", fixture.venture_id));
+        let artist = created.iter().find(|n| n.user_id == fixture.artist_user_id).expect("Artist notified");
+        assert_eq!(artist.title, "Nueva inversión en tu venture");
+        assert_eq!(artist.message, format!("Un fan ha invertido 250.0 en tu venture {}.", fixture.venture_id));
+        assert_eq!(artist.notification_type, NotificationType::InvestmentMade);
+        assert_eq!(artist.metadata.as_ref().unwrap()["event_type"], "SharesPurchased");
+        assert!(fixture.dead_letters.events.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn render_failure_dead_letters_without_partial_delivery() {
+        const NEEDS_TITLE: EventNotificationRule = EventNotificationRule {
+            event_type: "SharesPurchased",
+            templates: &[EventTemplate {
+                recipient: RecipientRule::VentureOwner("venture_id"),
+                notification_type: NotificationType::InvestmentMade,
+                priority: NotificationPriority::Normal,
+                title: "Venture {{venture_title}}",
+                message: "Nueva inversión.",
+            }],
+        };
+        let mut registry = EventTemplateRegistry::with_default_templates();
+        registry.register(&NEEDS_TITLE);
+        let fixture = fixture(registry);
+        let event = shares_purchased(fixture.venture_id, Uuid::new_v4());
+
+        assert_eq!(fixture.service.handle(&event).await.unwrap(), EventOutcome::DeadLettered);
+
+        assert!(fixture.repository.created.lock().unwrap().is_empty());
+        let dead = fixture.dead_letters.events.lock().unwrap();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].0, event);
+        assert!(dead[0].1.contains("missing variable `venture_title`"));
+    }
+
+    #[tokio::test]
+    async fn unknown_recipient_is_dead_lettered_and_unmapped_events_ignored() {
+        let fixture = fixture(EventTemplateRegistry::with_default_templates());
+
+        let orphan = shares_purchased(Uuid::new_v4(), Uuid::new_v4());
+        assert_eq!(fixture.service.handle(&orphan).await.unwrap(), EventOutcome::DeadLettered);
+
+        let unmapped = StreamEvent { id: "1-0".to_string(), event_type: "SongUpdated".to_string(), data: "not json".to_string() };
+        assert_eq!(fixture.service.handle(&unmapped).await.unwrap(), EventOutcome::Ignored);
+        assert_eq!(fixture.dead_letters.events.lock().unwrap().len(), 1);
+    }
+}
@@ -1,5 +1,20 @@
 pub mod services;
 pub mod use_cases;
+pub mod dispatcher;
+pub mod event_notifications;
+pub mod notification_hub;
+pub mod push;
+pub mod email;
+pub mod email_templates;
 
 pub use services::*;
-pub use use_cases::*; 
\ No newline at end of file
+pub use use_cases::*;
+pub use dispatcher::{NotificationDispatcher, NotificationSender};
+pub use event_notifications::{EventNotificationService, EventTemplateRegistry};
+pub use notification_hub::{LiveMessage, NotificationHub};
+pub use push::{PushError, PushMessage, PushNotificationSender, PushSender};
+pub use email::{
+    EmailDigestJob, EmailDigestService, EmailMessage, EmailNotificationSender, EmailRecipient,
+    EmailRecipientDirectory, EmailSender, EmailUnsubscribeService, UnsubscribeError,
+};
+pub use email_templates::{EmailTemplates, RenderedEmail};
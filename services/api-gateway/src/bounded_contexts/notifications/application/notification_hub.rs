@@ -0,0 +1,199 @@
+//! Notification Hub
+//!
+//! Conexiones WebSocket abiertas, agrupadas por usuario: cada dispositivo es
+//! una conexión y todas reciben las notificaciones nuevas. Sin conexiones la
+//! notificación solo queda en la lista in-app. Como en el feed de mercado, una
+//! conexión que no consume a tiempo se cierra en lugar de acumular mensajes.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{mpsc, oneshot, RwLock};
+use uuid::Uuid;
+
+use crate::bounded_contexts::notifications::domain::entities::Notification;
+
+/// Mensajes pendientes por conexión antes de considerarla lenta
+pub const OUTBOUND_BUFFER_SIZE: usize = 32;
+
+/// Mensajes enviados al cliente
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LiveMessage {
+    /// Primer mensaje tras autenticarse
+    UnreadCount { count: u32 },
+    Notification { notification: LiveNotification },
+    Error { message: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiveNotification {
+    pub id: Uuid,
+    pub title: String,
+    pub message: String,
+    pub notification_type: String,
+    pub priority: String,
+    pub metadata: Option<serde_json::Value>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<&Notification> for LiveNotification {
+    fn from(notification: &Notification) -> Self {
+        Self {
+            id: notification.id,
+            title: notification.title.clone(),
+            message: notification.message.clone(),
+            notification_type: notification.notification_type.as_str().to_string(),
+            priority: notification.priority.as_str().to_string(),
+            metadata: notification.metadata.clone(),
+            created_at: notification.created_at,
+        }
+    }
+}
+
+/// Extremo de la conexión que usa el socket; `closed` se dispara si el hub la
+/// descarta por lenta
+pub struct LiveConnection {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub messages: mpsc::Receiver<LiveMessage>,
+    pub closed: oneshot::Receiver<()>,
+}
+
+struct ConnectionEntry {
+    sender: mpsc::Sender<LiveMessage>,
+    close: Option<oneshot::Sender<()>>,
+}
+
+#[derive(Default)]
+pub struct NotificationHub {
+    connections: RwLock<HashMap<Uuid, HashMap<Uuid, ConnectionEntry>>>,
+}
+
+impl NotificationHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn register(&self, user_id: Uuid) -> LiveConnection {
+        let id = Uuid::new_v4();
+        let (sender, messages) = mpsc::channel(OUTBOUND_BUFFER_SIZE);
+        let (close, closed) = oneshot::channel();
+
+        self.connections
+            .write()
+            .await
+            .entry(user_id)
+            .or_default()
+            .insert(id, ConnectionEntry { sender, close: Some(close) });
+
+        LiveConnection { id, user_id, messages, closed }
+    }
+
+    pub async fn unregister(&self, user_id: Uuid, connection_id: Uuid) {
+        let mut connections = self.connections.write().await;
+        if let Some(devices) = connections.get_mut(&user_id) {
+            devices.remove(&connection_id);
+            if devices.is_empty() {
+                connections.remove(&user_id);
+            }
+        }
+    }
+
+    pub async fn connection_count(&self, user_id: Uuid) -> usize {
+        self.connections.read().await.get(&user_id).map_or(0, HashMap::len)
+    }
+
+    /// Enviar `notification` a todas las conexiones de su destinatario sin
+    /// bloquear. Devuelve a cuántas llegó; 0 si no tiene ninguna abierta.
+    pub async fn push(&self, notification: &Notification) -> usize {
+        let message = LiveMessage::Notification { notification: LiveNotification::from(notification) };
+        let user_id = notification.user_id;
+
+        let mut delivered = 0;
+        let mut dropped = Vec::new();
+        {
+            let connections = self.connections.read().await;
+            let Some(devices) = connections.get(&user_id) else {
+                return 0;
+            };
+            for (id, entry) in devices {
+                match entry.sender.try_send(message.clone()) {
+                    Ok(()) => delivered += 1,
+                    Err(mpsc::error::TrySendError::Full(_)) => dropped.push((*id, true)),
+                    Err(mpsc::error::TrySendError::Closed(_)) => dropped.push((*id, false)),
+                }
+            }
+        }
+
+        if !dropped.is_empty() {
+            let mut connections = self.connections.write().await;
+            if let Some(devices) = connections.get_mut(&user_id) {
+                for (id, slow) in dropped {
+                    if let Some(mut entry) = devices.remove(&id) {
+                        if let (true, Some(close)) = (slow, entry.close.take()) {
+                            tracing::warn!("Dropping slow notification consumer {} (user {})", id, user_id);
+                            let _ = close.send(());
+                        }
+                    }
+                }
+                if devices.is_empty() {
+                    connections.remove(&user_id);
+                }
+            }
+        }
+
+        delivered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::notifications::domain::entities::{NotificationPriority, NotificationType};
+
+    fn notification(user_id: Uuid) -> Notification {
+        Notification::new(user_id, "Title".to_string(), "Message".to_string(), NotificationType::RewardEarned, NotificationPriority::Normal, None)
+    }
+
+    #[tokio::test]
+    async fn pushes_to_every_device_of_the_recipient_only() {
+        let hub = NotificationHub::new();
+        let (user_id, other_user) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut phone = hub.register(user_id).await;
+        let mut laptop = hub.register(user_id).await;
+        let mut other = hub.register(other_user).await;
+
+        let sent = notification(user_id);
+        assert_eq!(hub.push(&sent).await, 2);
+
+        for connection in [&mut phone, &mut laptop] {
+            match connection.messages.try_recv().unwrap() {
+                LiveMessage::Notification { notification } => assert_eq!(notification.id, sent.id),
+                message => panic!("unexpected message: {:?}", message),
+            }
+        }
+        assert!(other.messages.try_recv().is_err());
+
+        hub.unregister(user_id, phone.id).await;
+        assert_eq!(hub.connection_count(user_id).await, 1);
+        assert_eq!(hub.push(&notification(Uuid::new_v4())).await, 0);
+    }
+
+    #[tokio::test]
+    async fn slow_and_closed_connections_are_pruned() {
+        let hub = NotificationHub::new();
+        let user_id = Uuid::new_v4();
+        let mut slow = hub.register(user_id).await;
+        let gone = hub.register(user_id).await;
+        drop(gone.messages);
+
+        for _ in 0..OUTBOUND_BUFFER_SIZE {
+            assert_eq!(hub.push(&notification(user_id)).await, 1);
+        }
+        assert_eq!(hub.push(&notification(user_id)).await, 0);
+
+        assert_eq!(hub.connection_count(user_id).await, 0);
+        assert!(slow.closed.try_recv().is_ok());
+    }
+}
@@ -0,0 +1,311 @@
+//! Push Delivery
+//!
+//! Canal push del dispatcher: la notificación se envía a todos los
+//! dispositivos registrados del usuario por el proveedor de su plataforma
+//! (FCM para Android, APNs para iOS). Los tokens que el proveedor rechaza como
+//! inválidos se borran; los 5xx se reintentan con backoff exponencial.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use tracing::{debug, error, warn};
+
+use crate::bounded_contexts::notifications::domain::entities::{
+    DevicePlatform, Notification, NotificationCategory, NotificationChannel,
+};
+use crate::bounded_contexts::notifications::domain::repositories::DeviceTokenRepository;
+use super::dispatcher::NotificationSender;
+
+pub const MAX_PUSH_ATTEMPTS: u32 = 3;
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Mensaje para un dispositivo concreto
+#[derive(Debug, Clone, PartialEq)]
+pub struct PushMessage {
+    pub token: String,
+    pub title: String,
+    pub body: String,
+    /// Las notificaciones con la misma clave se sustituyen en el dispositivo
+    pub collapse_key: Option<String>,
+    pub data: HashMap<String, String>,
+}
+
+impl PushMessage {
+    pub fn for_notification(notification: &Notification, token: &str) -> Self {
+        let collapse_key = NotificationCategory::of(&notification.notification_type)
+            .and_then(|category| category.collapse_key())
+            .map(str::to_string);
+
+        Self {
+            token: token.to_string(),
+            title: notification.title.clone(),
+            body: notification.message.clone(),
+            collapse_key,
+            data: HashMap::from([
+                ("notification_id".to_string(), notification.id.to_string()),
+                ("notification_type".to_string(), notification.notification_type.as_str().to_string()),
+            ]),
+        }
+    }
+}
+
+/// Respuesta negativa de un proveedor de push
+#[derive(Debug, Clone, PartialEq)]
+pub enum PushError {
+    /// El token ya no sirve (app desinstalada, token caducado o mal formado)
+    InvalidToken,
+    /// Error del proveedor (5xx, 429) o de red: se puede reintentar
+    Retryable(String),
+    /// Rechazo definitivo que no invalida el token (credenciales, payload)
+    Rejected(String),
+}
+
+impl std::fmt::Display for PushError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PushError::InvalidToken => write!(f, "Device token rejected as invalid"),
+            PushError::Retryable(msg) => write!(f, "Push provider unavailable: {}", msg),
+            PushError::Rejected(msg) => write!(f, "Push rejected: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for PushError {}
+
+/// Proveedor de push de una plataforma
+#[async_trait]
+pub trait PushSender: Send + Sync {
+    fn platform(&self) -> DevicePlatform;
+    async fn send(&self, message: &PushMessage) -> Result<(), PushError>;
+}
+
+pub struct PushNotificationSender {
+    devices: Arc<dyn DeviceTokenRepository>,
+    providers: HashMap<DevicePlatform, Arc<dyn PushSender>>,
+    /// Espera antes del 2º intento; se duplica en cada uno de los siguientes
+    retry_backoff: Duration,
+}
+
+impl PushNotificationSender {
+    pub fn new(devices: Arc<dyn DeviceTokenRepository>) -> Self {
+        Self {
+            devices,
+            providers: HashMap::new(),
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+        }
+    }
+
+    pub fn with_provider(mut self, provider: Arc<dyn PushSender>) -> Self {
+        self.providers.insert(provider.platform(), provider);
+        self
+    }
+
+    pub fn with_retry_backoff(mut self, retry_backoff: Duration) -> Self {
+        self.retry_backoff = retry_backoff;
+        self
+    }
+
+    /// Sin proveedores configurados no tiene sentido registrarlo en el dispatcher
+    pub fn has_providers(&self) -> bool {
+        !self.providers.is_empty()
+    }
+
+    async fn deliver(&self, provider: &dyn PushSender, message: &PushMessage) -> Result<(), PushError> {
+        let mut attempt = 1;
+        loop {
+            match provider.send(message).await {
+                Err(PushError::Retryable(reason)) if attempt < MAX_PUSH_ATTEMPTS => {
+                    debug!("Push attempt {} via {:?} failed: {}", attempt, provider.platform(), reason);
+                    tokio::time::sleep(self.retry_backoff * 2u32.pow(attempt - 1)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationSender for PushNotificationSender {
+    fn channel(&self) -> NotificationChannel {
+        NotificationChannel::Push
+    }
+
+    /// Falla solo si ningún dispositivo del usuario recibió la notificación
+    async fn send(&self, notification: &Notification) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let devices = self.devices.find_by_user(notification.user_id).await?;
+
+        let mut delivered = 0;
+        let mut invalid = Vec::new();
+        let mut last_error = None;
+        for device in &devices {
+            let Some(provider) = self.providers.get(&device.platform) else {
+                debug!("No push provider configured for {:?}", device.platform);
+                continue;
+            };
+            let message = PushMessage::for_notification(notification, &device.token);
+            match self.deliver(provider.as_ref(), &message).await {
+                Ok(()) => delivered += 1,
+                Err(PushError::InvalidToken) => invalid.push(device.token.clone()),
+                Err(e) => {
+                    error!("Failed to push notification {} to device {}: {}", notification.id, device.id, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        if !invalid.is_empty() {
+            let removed = self.devices.remove_tokens(&invalid).await?;
+            warn!("Pruned {} invalid push tokens of user {}", removed, notification.user_id);
+        }
+
+        match last_error {
+            Some(e) if delivered == 0 => Err(Box::new(e)),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+    use crate::bounded_contexts::notifications::domain::entities::{DeviceToken, NotificationPriority, NotificationType};
+
+    type RepoResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+    #[derive(Default)]
+    struct InMemoryDevices {
+        devices: Mutex<Vec<DeviceToken>>,
+    }
+
+    #[async_trait]
+    impl DeviceTokenRepository for InMemoryDevices {
+        async fn register(&self, device: &DeviceToken) -> RepoResult<DeviceToken> {
+            let mut devices = self.devices.lock().unwrap();
+            devices.retain(|d| d.token != device.token);
+            devices.push(device.clone());
+            Ok(device.clone())
+        }
+        async fn find_by_user(&self, user_id: Uuid) -> RepoResult<Vec<DeviceToken>> {
+            Ok(self.devices.lock().unwrap().iter().filter(|d| d.user_id == user_id).cloned().collect())
+        }
+        async fn remove_tokens(&self, tokens: &[String]) -> RepoResult<u64> {
+            let mut devices = self.devices.lock().unwrap();
+            let before = devices.len();
+            devices.retain(|d| !tokens.contains(&d.token));
+            Ok((before - devices.len()) as u64)
+        }
+    }
+
+    /// Responde según el token: `invalid-*` inválido, `flaky-*` un 503 la primera vez
+    struct ScriptedProvider {
+        platform: DevicePlatform,
+        sent: Mutex<Vec<PushMessage>>,
+    }
+
+    impl ScriptedProvider {
+        fn new(platform: DevicePlatform) -> Arc<Self> {
+            Arc::new(Self { platform, sent: Mutex::new(Vec::new()) })
+        }
+
+        fn attempts(&self, token: &str) -> usize {
+            self.sent.lock().unwrap().iter().filter(|m| m.token == token).count()
+        }
+    }
+
+    #[async_trait]
+    impl PushSender for ScriptedProvider {
+        fn platform(&self) -> DevicePlatform { self.platform }
+        async fn send(&self, message: &PushMessage) -> Result<(), PushError> {
+            let previous = self.attempts(&message.token);
+            self.sent.lock().unwrap().push(message.clone());
+            if message.token.starts_with("invalid") {
+                Err(PushError::InvalidToken)
+            } else if message.token.starts_with("down") {
+                Err(PushError::Retryable("503 Service Unavailable".to_string()))
+            } else if message.token.starts_with("flaky") && previous == 0 {
+                Err(PushError::Retryable("503 Service Unavailable".to_string()))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn notification(user_id: Uuid, notification_type: NotificationType) -> Notification {
+        Notification::new(user_id, "Title".to_string(), "Message".to_string(), notification_type, NotificationPriority::Normal, None)
+    }
+
+    async fn register(devices: &InMemoryDevices, user_id: Uuid, platform: DevicePlatform, token: &str) {
+        devices.register(&DeviceToken::new(user_id, platform, token.to_string())).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn invalid_tokens_are_pruned_and_other_devices_still_receive() {
+        let devices = Arc::new(InMemoryDevices::default());
+        let user_id = Uuid::new_v4();
+        register(&devices, user_id, DevicePlatform::Android, "android-ok").await;
+        register(&devices, user_id, DevicePlatform::Android, "invalid-android").await;
+        register(&devices, user_id, DevicePlatform::Ios, "invalid-ios").await;
+        let (fcm, apns) = (ScriptedProvider::new(DevicePlatform::Android), ScriptedProvider::new(DevicePlatform::Ios));
+        let sender = PushNotificationSender::new(devices.clone())
+            .with_provider(fcm.clone())
+            .with_provider(apns.clone())
+            .with_retry_backoff(Duration::ZERO);
+
+        sender.send(&notification(user_id, NotificationType::SecurityAlert)).await.unwrap();
+
+        let remaining: Vec<String> = devices.find_by_user(user_id).await.unwrap().into_iter().map(|d| d.token).collect();
+        assert_eq!(remaining, vec!["android-ok"]);
+        // Un token inválido no se reintenta
+        assert_eq!(fcm.attempts("invalid-android"), 1);
+        assert_eq!(apns.attempts("invalid-ios"), 1);
+
+        // Solo quedaban tokens inválidos: nada que entregar y nada que falle
+        let other = Uuid::new_v4();
+        register(&devices, other, DevicePlatform::Ios, "invalid-again").await;
+        sender.send(&notification(other, NotificationType::SecurityAlert)).await.unwrap();
+        assert!(devices.find_by_user(other).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn provider_errors_are_retried_with_backoff() {
+        let devices = Arc::new(InMemoryDevices::default());
+        let user_id = Uuid::new_v4();
+        register(&devices, user_id, DevicePlatform::Android, "flaky-phone").await;
+        let fcm = ScriptedProvider::new(DevicePlatform::Android);
+        let sender = PushNotificationSender::new(devices.clone())
+            .with_provider(fcm.clone())
+            .with_retry_backoff(Duration::ZERO);
+
+        sender.send(&notification(user_id, NotificationType::SecurityAlert)).await.unwrap();
+        assert_eq!(fcm.attempts("flaky-phone"), 2);
+
+        // Caído en todos los intentos: el canal falla pero el token se conserva
+        register(&devices, user_id, DevicePlatform::Android, "down-tablet").await;
+        devices.remove_tokens(&["flaky-phone".to_string()]).await.unwrap();
+        assert!(sender.send(&notification(user_id, NotificationType::SecurityAlert)).await.is_err());
+        assert_eq!(fcm.attempts("down-tablet"), MAX_PUSH_ATTEMPTS as usize);
+        assert_eq!(devices.find_by_user(user_id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn noisy_categories_carry_a_collapse_key() {
+        let devices = Arc::new(InMemoryDevices::default());
+        let user_id = Uuid::new_v4();
+        register(&devices, user_id, DevicePlatform::Ios, "iphone").await;
+        let apns = ScriptedProvider::new(DevicePlatform::Ios);
+        let sender = PushNotificationSender::new(devices).with_provider(apns.clone());
+
+        let reward = notification(user_id, NotificationType::RewardEarned);
+        sender.send(&reward).await.unwrap();
+        sender.send(&notification(user_id, NotificationType::SecurityAlert)).await.unwrap();
+
+        let sent = apns.sent.lock().unwrap();
+        assert_eq!(sent[0].collapse_key.as_deref(), Some("rewards"));
+        assert_eq!(sent[0].data["notification_id"], reward.id.to_string());
+        assert_eq!(sent[1].collapse_key, None);
+    }
+}
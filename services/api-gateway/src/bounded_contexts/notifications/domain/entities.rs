@@ -1,5 +1,6 @@
-use chrono::{DateTime, Utc, Timelike};
+use chrono::{DateTime, Datelike, Duration, Utc, Timelike};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -60,15 +61,233 @@ pub enum NotificationType {
     CampaignLaunched,
     CampaignEnded,
     CampaignMilestoneReached,
+    CampaignBudgetDepleted,
     AccountCreated,
     ProfileUpdated,
     WalletLinked,
     SystemMaintenance,
     SecurityAlert,
     WelcomeMessage,
+    NewFollower,
+    SongUnderReview,
     Custom(String),
 }
 
+impl NotificationType {
+    /// Nombre que se guarda en `notifications.notification_type`
+    pub fn as_str(&self) -> &str {
+        match self {
+            NotificationType::VentureCreated => "venture_created",
+            NotificationType::InvestmentMade => "investment_made",
+            NotificationType::BenefitDelivered => "benefit_delivered",
+            NotificationType::VentureFunded => "venture_funded",
+            NotificationType::VentureExpired => "venture_expired",
+            NotificationType::SystemAlert => "system_alert",
+            NotificationType::Marketing => "marketing",
+            NotificationType::RevenueDistributed => "revenue_distributed",
+            NotificationType::ListenSessionCompleted => "listen_session_completed",
+            NotificationType::RewardEarned => "reward_earned",
+            NotificationType::ZKProofVerified => "zk_proof_verified",
+            NotificationType::CampaignLaunched => "campaign_launched",
+            NotificationType::CampaignEnded => "campaign_ended",
+            NotificationType::CampaignMilestoneReached => "campaign_milestone_reached",
+            NotificationType::CampaignBudgetDepleted => "campaign_budget_depleted",
+            NotificationType::AccountCreated => "account_created",
+            NotificationType::ProfileUpdated => "profile_updated",
+            NotificationType::WalletLinked => "wallet_linked",
+            NotificationType::SystemMaintenance => "system_maintenance",
+            NotificationType::SecurityAlert => "security_alert",
+            NotificationType::WelcomeMessage => "welcome_message",
+            NotificationType::NewFollower => "new_follower",
+            NotificationType::SongUnderReview => "song_under_review",
+            NotificationType::Custom(s) => s,
+        }
+    }
+}
+
+/// Categorías sobre las que el usuario elige canales
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationCategory {
+    Ventures,
+    Rewards,
+    Campaigns,
+    Account,
+    Security,
+    System,
+    Marketing,
+}
+
+impl NotificationCategory {
+    pub const ALL: [NotificationCategory; 7] = [
+        NotificationCategory::Ventures,
+        NotificationCategory::Rewards,
+        NotificationCategory::Campaigns,
+        NotificationCategory::Account,
+        NotificationCategory::Security,
+        NotificationCategory::System,
+        NotificationCategory::Marketing,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationCategory::Ventures => "ventures",
+            NotificationCategory::Rewards => "rewards",
+            NotificationCategory::Campaigns => "campaigns",
+            NotificationCategory::Account => "account",
+            NotificationCategory::Security => "security",
+            NotificationCategory::System => "system",
+            NotificationCategory::Marketing => "marketing",
+        }
+    }
+
+    /// `None` para los tipos personalizados, que no pertenecen a ninguna categoría
+    pub fn of(notification_type: &NotificationType) -> Option<Self> {
+        match notification_type {
+            NotificationType::VentureCreated |
+            NotificationType::VentureFunded |
+            NotificationType::VentureExpired |
+            NotificationType::InvestmentMade |
+            NotificationType::BenefitDelivered |
+            NotificationType::RevenueDistributed => Some(NotificationCategory::Ventures),
+
+            NotificationType::ListenSessionCompleted |
+            NotificationType::RewardEarned |
+            NotificationType::ZKProofVerified => Some(NotificationCategory::Rewards),
+
+            NotificationType::CampaignLaunched |
+            NotificationType::CampaignEnded |
+            NotificationType::CampaignMilestoneReached |
+            NotificationType::CampaignBudgetDepleted => Some(NotificationCategory::Campaigns),
+
+            NotificationType::AccountCreated |
+            NotificationType::ProfileUpdated |
+            NotificationType::WalletLinked |
+            NotificationType::WelcomeMessage |
+            NotificationType::NewFollower => Some(NotificationCategory::Account),
+
+            NotificationType::SecurityAlert => Some(NotificationCategory::Security),
+
+            NotificationType::SystemAlert |
+            NotificationType::SystemMaintenance |
+            NotificationType::SongUnderReview => Some(NotificationCategory::System),
+
+            NotificationType::Marketing => Some(NotificationCategory::Marketing),
+
+            NotificationType::Custom(_) => None,
+        }
+    }
+
+    /// Collapse key de push para las categorías ruidosas: el dispositivo solo
+    /// muestra la última notificación de la categoría en vez de acumularlas
+    pub fn collapse_key(&self) -> Option<&'static str> {
+        match self {
+            NotificationCategory::Rewards => Some("rewards"),
+            NotificationCategory::Campaigns => Some("campaigns"),
+            NotificationCategory::Marketing => Some("marketing"),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for NotificationCategory {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NotificationCategory::ALL
+            .into_iter()
+            .find(|category| category.as_str() == s)
+            .ok_or_else(|| format!("Unknown notification category: {}", s))
+    }
+}
+
+/// Canales por los que se entrega una notificación
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationChannel {
+    InApp,
+    Email,
+    Push,
+}
+
+/// Canales activos para una categoría
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChannelPreferences {
+    pub in_app: bool,
+    pub email: bool,
+    pub push: bool,
+}
+
+impl ChannelPreferences {
+    pub const ALL: ChannelPreferences = ChannelPreferences { in_app: true, email: true, push: true };
+    pub const IN_APP_ONLY: ChannelPreferences = ChannelPreferences { in_app: true, email: false, push: false };
+
+    pub fn allows(&self, channel: NotificationChannel) -> bool {
+        match channel {
+            NotificationChannel::InApp => self.in_app,
+            NotificationChannel::Email => self.email,
+            NotificationChannel::Push => self.push,
+        }
+    }
+}
+
+/// Plataforma de un dispositivo registrado para push
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum DevicePlatform {
+    /// Firebase Cloud Messaging
+    Android,
+    /// Apple Push Notification service
+    Ios,
+}
+
+impl DevicePlatform {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DevicePlatform::Android => "android",
+            DevicePlatform::Ios => "ios",
+        }
+    }
+}
+
+impl std::str::FromStr for DevicePlatform {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "android" => Ok(DevicePlatform::Android),
+            "ios" => Ok(DevicePlatform::Ios),
+            other => Err(format!("Unknown device platform: {}", other)),
+        }
+    }
+}
+
+/// Token de push de un dispositivo. El token es único: si otro usuario lo
+/// registra (mismo móvil, otra cuenta) pasa a ser suyo.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeviceToken {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub platform: DevicePlatform,
+    pub token: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl DeviceToken {
+    pub fn new(user_id: Uuid, platform: DevicePlatform, token: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            platform,
+            token,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum NotificationPriority {
     Low,
@@ -79,6 +298,18 @@ pub enum NotificationPriority {
     Normal,
 }
 
+impl NotificationPriority {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationPriority::Low => "low",
+            NotificationPriority::Medium => "medium",
+            NotificationPriority::High => "high",
+            NotificationPriority::Urgent => "urgent",
+            NotificationPriority::Normal => "normal",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum NotificationStatus {
     Pending,
@@ -104,6 +335,14 @@ pub struct NotificationPreferences {
     pub benefit_notifications: bool,
     pub marketing_notifications: bool,
     pub system_notifications: bool,
+    /// Canales elegidos por categoría; las que faltan solo llegan in-app
+    #[serde(default)]
+    pub categories: HashMap<NotificationCategory, ChannelPreferences>,
+    /// Categorías cuyo email se agrupa en un resumen periódico en vez de enviarse al momento
+    #[serde(default)]
+    pub digest_categories: BTreeSet<NotificationCategory>,
+    #[serde(default)]
+    pub digest_frequency: DigestFrequency,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -122,6 +361,9 @@ impl NotificationPreferences {
             benefit_notifications: true,
             marketing_notifications: true,
             system_notifications: true,
+            categories: HashMap::new(),
+            digest_categories: BTreeSet::new(),
+            digest_frequency: DigestFrequency::default(),
             created_at: Utc::now(),
             updated_at: Utc::now(),
         }
@@ -139,6 +381,121 @@ impl NotificationPreferences {
             false
         }
     }
+
+    /// Canales por los que debe llegar una notificación de este tipo. Los tipos
+    /// sin categoría o sin entrada en `categories` solo se guardan in-app, y los
+    /// interruptores globales de email y push mandan sobre la categoría.
+    pub fn channels_for(&self, notification_type: &NotificationType) -> ChannelPreferences {
+        NotificationCategory::of(notification_type)
+            .map(|category| self.channels_for_category(category))
+            .unwrap_or(ChannelPreferences::IN_APP_ONLY)
+    }
+
+    pub fn channels_for_category(&self, category: NotificationCategory) -> ChannelPreferences {
+        let mut channels = self.categories.get(&category).copied().unwrap_or(ChannelPreferences::IN_APP_ONLY);
+        channels.email &= self.email_enabled;
+        channels.push &= self.push_enabled;
+        channels
+    }
+
+    /// Si el email de este tipo va al resumen periódico
+    pub fn digests(&self, notification_type: &NotificationType) -> bool {
+        NotificationCategory::of(notification_type)
+            .is_some_and(|category| self.digest_categories.contains(&category))
+    }
+
+    /// Baja de los emails de una categoría, o de todos con `None`
+    pub fn unsubscribe_email(&mut self, category: Option<NotificationCategory>) {
+        match category {
+            Some(category) => {
+                let channels = self.categories.entry(category).or_insert(ChannelPreferences::IN_APP_ONLY);
+                channels.email = false;
+            }
+            None => self.email_enabled = false,
+        }
+        self.updated_at = Utc::now();
+    }
+}
+
+/// Periodicidad de los resúmenes por email. Los periodos son días naturales o
+/// semanas de lunes a domingo, en UTC.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestFrequency {
+    Daily,
+    #[default]
+    Weekly,
+}
+
+impl DigestFrequency {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DigestFrequency::Daily => "daily",
+            DigestFrequency::Weekly => "weekly",
+        }
+    }
+
+    /// `[inicio, fin)` del periodo que contiene `at`
+    pub fn period_containing(&self, at: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let day = at.date_naive();
+        let (first_day, length) = match self {
+            DigestFrequency::Daily => (day, Duration::days(1)),
+            DigestFrequency::Weekly => {
+                (day - Duration::days(day.weekday().num_days_from_monday() as i64), Duration::days(7))
+            }
+        };
+        let start = first_day.and_hms_opt(0, 0, 0).expect("midnight is a valid time").and_utc();
+        (start, start + length)
+    }
+}
+
+impl std::str::FromStr for DigestFrequency {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "daily" => Ok(DigestFrequency::Daily),
+            "weekly" => Ok(DigestFrequency::Weekly),
+            other => Err(format!("Unknown digest frequency: {}", other)),
+        }
+    }
+}
+
+/// Notificación pendiente de salir en el resumen de su periodo
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DigestEntry {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub notification_id: Uuid,
+    pub category: NotificationCategory,
+    pub title: String,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+}
+
+impl DigestEntry {
+    /// `None` para los tipos sin categoría, que nunca van a resumen
+    pub fn for_notification(notification: &Notification, frequency: DigestFrequency) -> Option<Self> {
+        let category = NotificationCategory::of(&notification.notification_type)?;
+        let (period_start, period_end) = frequency.period_containing(notification.created_at);
+        Some(Self {
+            id: Uuid::new_v4(),
+            user_id: notification.user_id,
+            notification_id: notification.id,
+            category,
+            title: notification.title.clone(),
+            message: notification.message.clone(),
+            created_at: notification.created_at,
+            period_start,
+            period_end,
+        })
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.period_end <= now
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -272,4 +629,72 @@ impl From<Notification> for NotificationResponse {
             created_at: notification.created_at,
         }
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn categories_choose_channels() {
+        let mut preferences = NotificationPreferences::new(Uuid::new_v4());
+        preferences.categories.insert(
+            NotificationCategory::Campaigns,
+            ChannelPreferences { in_app: false, email: true, push: true },
+        );
+
+        let channels = preferences.channels_for(&NotificationType::CampaignEnded);
+        assert_eq!(channels, ChannelPreferences { in_app: false, email: true, push: true });
+
+        preferences.push_enabled = false;
+        assert!(!preferences.channels_for(&NotificationType::CampaignEnded).push);
+    }
+
+    #[test]
+    fn unknown_types_are_in_app_only() {
+        let mut preferences = NotificationPreferences::new(Uuid::new_v4());
+        for category in NotificationCategory::ALL {
+            preferences.categories.insert(category, ChannelPreferences::ALL);
+        }
+
+        let custom = NotificationType::Custom("artist_shoutout".to_string());
+        assert_eq!(preferences.channels_for(&custom), ChannelPreferences::IN_APP_ONLY);
+
+        // Una categoría sin entrada en el documento tampoco sale por email ni push
+        preferences.categories.remove(&NotificationCategory::Rewards);
+        assert_eq!(preferences.channels_for(&NotificationType::RewardEarned), ChannelPreferences::IN_APP_ONLY);
+    }
+
+    #[test]
+    fn digest_periods_are_utc_days_and_monday_weeks() {
+        use chrono::TimeZone;
+        // Domingo 12 de abril de 2026, último segundo de la semana
+        let sunday_night = Utc.with_ymd_and_hms(2026, 4, 12, 23, 59, 59).unwrap();
+        let monday = Utc.with_ymd_and_hms(2026, 4, 13, 0, 0, 0).unwrap();
+
+        let (start, end) = DigestFrequency::Weekly.period_containing(sunday_night);
+        assert_eq!(start, Utc.with_ymd_and_hms(2026, 4, 6, 0, 0, 0).unwrap());
+        assert_eq!(end, monday);
+        // El inicio de un periodo pertenece a ese periodo, no al anterior
+        assert_eq!(DigestFrequency::Weekly.period_containing(monday).0, monday);
+
+        let (start, end) = DigestFrequency::Daily.period_containing(sunday_night);
+        assert_eq!(start, Utc.with_ymd_and_hms(2026, 4, 12, 0, 0, 0).unwrap());
+        assert_eq!(end, monday);
+    }
+
+    #[test]
+    fn unsubscribing_turns_off_email_only() {
+        let mut preferences = NotificationPreferences::new(Uuid::new_v4());
+        preferences.categories.insert(NotificationCategory::Rewards, ChannelPreferences::ALL);
+
+        preferences.unsubscribe_email(Some(NotificationCategory::Rewards));
+        assert_eq!(
+            preferences.channels_for(&NotificationType::RewardEarned),
+            ChannelPreferences { in_app: true, email: false, push: true }
+        );
+
+        preferences.categories.insert(NotificationCategory::Campaigns, ChannelPreferences::ALL);
+        preferences.unsubscribe_email(None);
+        assert!(!preferences.channels_for(&NotificationType::CampaignEnded).email);
+    }
+}
@@ -6,10 +6,15 @@ pub mod services;
 pub use entities::{
     Notification, NotificationType, NotificationPriority, NotificationStatus, 
     NotificationPreferences, NotificationTemplate,
+    NotificationCategory, NotificationChannel, ChannelPreferences,
+    DevicePlatform, DeviceToken, DigestEntry, DigestFrequency,
     // DTOs y estructuras adicionales
     CreateNotificationRequest, NotificationResponse, UpdateNotificationStatusRequest,
     NotificationFilters, NotificationSummary, NotificationTypeCount, UpdatePreferencesRequest,
     UpdateNotificationRequest, NotificationListResponse
 };
-pub use repositories::{NotificationRepository, NotificationPreferencesRepository, NotificationTemplateRepository};
+pub use repositories::{
+    NotificationRepository, NotificationPreferencesRepository, NotificationTemplateRepository, DeviceTokenRepository,
+    DigestRepository,
+};
 pub use services::NotificationDomainService; 
\ No newline at end of file
@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 use crate::bounded_contexts::notifications::domain::{
-    Notification, NotificationPreferences, NotificationTemplate, NotificationFilters,
+    DeviceToken, DigestEntry, Notification, NotificationPreferences, NotificationTemplate, NotificationFilters,
 };
 
 #[async_trait]
@@ -35,4 +35,26 @@ pub trait NotificationTemplateRepository: Send + Sync {
     async fn get_all_active(&self) -> Result<Vec<NotificationTemplate>, Box<dyn std::error::Error + Send + Sync>>;
     async fn update(&self, template: &NotificationTemplate) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
     async fn delete(&self, id: Uuid) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
-} 
\ No newline at end of file
+}
+
+#[async_trait]
+pub trait DeviceTokenRepository: Send + Sync {
+    /// Alta o refresco del token; si ya estaba registrado por otro usuario pasa a `device.user_id`
+    async fn register(&self, device: &DeviceToken) -> Result<DeviceToken, Box<dyn std::error::Error + Send + Sync>>;
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<DeviceToken>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Borrar tokens que el proveedor ha dado por inválidos
+    async fn remove_tokens(&self, tokens: &[String]) -> Result<u64, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+/// Notificaciones acumuladas para los resúmenes por email
+#[async_trait]
+pub trait DigestRepository: Send + Sync {
+    async fn enqueue(&self, entry: &DigestEntry) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    /// Reservar las entradas de periodos cerrados (`period_end <= now`) que
+    /// nadie más esté enviando
+    async fn claim_due(&self, now: chrono::DateTime<chrono::Utc>) -> Result<Vec<DigestEntry>, Box<dyn std::error::Error + Send + Sync>>;
+    /// Entradas ya enviadas (o descartadas): no vuelven a salir
+    async fn complete(&self, entry_ids: &[Uuid]) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+    /// Devolver a la cola entradas reservadas que no se pudieron enviar
+    async fn release(&self, entry_ids: &[Uuid]) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
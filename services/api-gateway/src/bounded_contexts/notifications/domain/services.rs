@@ -107,6 +107,10 @@ where
             NotificationType::CampaignLaunched => preferences.marketing_notifications,
             NotificationType::CampaignEnded => preferences.marketing_notifications,
             NotificationType::CampaignMilestoneReached => preferences.marketing_notifications,
+            // Aviso operativo al artista: su campaña se ha pausado
+            NotificationType::CampaignBudgetDepleted => true,
+            // Aviso operativo al artista: su canción no se publica hasta revisarla
+            NotificationType::SongUnderReview => true,
 
             NotificationType::SystemMaintenance |
             NotificationType::SecurityAlert |
@@ -117,7 +121,8 @@ where
 
             NotificationType::AccountCreated |
             NotificationType::ProfileUpdated |
-            NotificationType::WalletLinked => preferences.system_notifications,
+            NotificationType::WalletLinked |
+            NotificationType::NewFollower => preferences.system_notifications,
 
             NotificationType::RevenueDistributed => preferences.venture_notifications,
 
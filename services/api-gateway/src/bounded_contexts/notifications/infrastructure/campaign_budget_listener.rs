@@ -0,0 +1,120 @@
+//! Campaign Budget Listener
+//!
+//! Escucha `CampaignBudgetDepleted` y avisa al artista de que su campaña se ha
+//! pausado, para que recargue el presupuesto o la deje terminar.
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use tracing::error;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+use crate::bounded_contexts::notifications::domain::entities::{
+    Notification, NotificationPriority, NotificationType,
+};
+use crate::bounded_contexts::notifications::application::NotificationDispatcher;
+
+pub struct CampaignBudgetNotificationListener {
+    dispatcher: Arc<NotificationDispatcher>,
+}
+
+impl CampaignBudgetNotificationListener {
+    pub fn new(dispatcher: Arc<NotificationDispatcher>) -> Self {
+        Self { dispatcher }
+    }
+}
+
+#[async_trait]
+impl EventHandler for CampaignBudgetNotificationListener {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let DomainEvent::CampaignBudgetDepleted { campaign_id, artist_id, budget, spent, reward_per_action, occurred_at } = event else {
+            return Ok(());
+        };
+
+        let notification = Notification::new(
+            *artist_id,
+            "Campaña pausada: presupuesto agotado".to_string(),
+            format!(
+                "Tu campaña ha gastado {:.2} de {:.2} y no cubre otra recompensa de {:.2}. Recarga el presupuesto para reanudarla.",
+                spent, budget, reward_per_action
+            ),
+            NotificationType::CampaignBudgetDepleted,
+            NotificationPriority::High,
+            Some(serde_json::json!({
+                "campaign_id": campaign_id,
+                "budget": budget,
+                "spent": spent,
+                "reward_per_action": reward_per_action,
+                "occurred_at": occurred_at,
+            })),
+        );
+        if let Err(e) = self.dispatcher.dispatch(&notification).await {
+            error!("Failed to notify artist {} of depleted campaign {}: {}", artist_id, campaign_id, e);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+    use crate::bounded_contexts::notifications::domain::entities::NotificationFilters;
+    use crate::bounded_contexts::notifications::domain::repositories::NotificationRepository;
+    use crate::bounded_contexts::notifications::infrastructure::MockNotificationPreferencesRepository;
+
+    #[derive(Default)]
+    struct RecordingRepository {
+        created: Mutex<Vec<Notification>>,
+    }
+
+    type RepoResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+    #[async_trait]
+    impl NotificationRepository for RecordingRepository {
+        async fn create(&self, notification: &Notification) -> RepoResult<()> {
+            self.created.lock().unwrap().push(notification.clone());
+            Ok(())
+        }
+        async fn get_by_id(&self, _id: Uuid) -> RepoResult<Option<Notification>> { Ok(None) }
+        async fn get_by_user_id(&self, _user_id: Uuid, _page: u32, _page_size: u32) -> RepoResult<(Vec<Notification>, u32, u32)> { Ok((Vec::new(), 0, 0)) }
+        async fn get_unread_count(&self, _user_id: Uuid) -> RepoResult<u32> { Ok(0) }
+        async fn update(&self, _notification: &Notification) -> RepoResult<()> { Ok(()) }
+        async fn delete(&self, _id: Uuid) -> RepoResult<()> { Ok(()) }
+        async fn mark_as_read(&self, _id: Uuid) -> RepoResult<()> { Ok(()) }
+        async fn mark_as_archived(&self, _id: Uuid) -> RepoResult<()> { Ok(()) }
+        async fn mark_all_as_read(&self, _user_id: Uuid) -> RepoResult<()> { Ok(()) }
+        async fn search(&self, _filters: &NotificationFilters, _page: u32, _page_size: u32) -> RepoResult<Vec<Notification>> { Ok(Vec::new()) }
+        async fn get_summary(&self, _user_id: Uuid) -> RepoResult<(u32, u32, u32, u32)> { Ok((0, 0, 0, 0)) }
+    }
+
+    fn dispatcher(repository: Arc<RecordingRepository>) -> Arc<NotificationDispatcher> {
+        Arc::new(NotificationDispatcher::new(repository, Arc::new(MockNotificationPreferencesRepository::new())))
+    }
+
+    #[tokio::test]
+    async fn depleted_campaign_notifies_its_artist() {
+        let repository = Arc::new(RecordingRepository::default());
+        let listener = CampaignBudgetNotificationListener::new(dispatcher(repository.clone()));
+        let artist_id = Uuid::new_v4();
+        let campaign_id = Uuid::new_v4();
+
+        listener.handle(&DomainEvent::CampaignBudgetDepleted {
+            campaign_id,
+            artist_id,
+            budget: 55.0,
+            spent: 50.0,
+            reward_per_action: 10.0,
+            occurred_at: Utc::now(),
+        }).await.unwrap();
+
+        let created = repository.created.lock().unwrap();
+        assert_eq!(created.len(), 1);
+        assert_eq!(created[0].user_id, artist_id);
+        assert_eq!(created[0].notification_type, NotificationType::CampaignBudgetDepleted);
+        assert_eq!(created[0].metadata.as_ref().unwrap()["campaign_id"], serde_json::json!(campaign_id));
+    }
+}
@@ -0,0 +1,162 @@
+//! Fraud Alert Listener
+//!
+//! Escucha los eventos `FraudDetected` publicados por payment y listen_reward y
+//! crea una alerta de seguridad urgente para cada usuario de operaciones.
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use uuid::Uuid;
+use tracing::{error, warn};
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+use crate::bounded_contexts::notifications::domain::entities::{
+    Notification, NotificationPriority, NotificationType,
+};
+use crate::bounded_contexts::notifications::application::NotificationDispatcher;
+
+pub struct FraudAlertNotificationListener {
+    dispatcher: Arc<NotificationDispatcher>,
+    ops_user_ids: Vec<Uuid>,
+}
+
+impl FraudAlertNotificationListener {
+    pub fn new(dispatcher: Arc<NotificationDispatcher>, ops_user_ids: Vec<Uuid>) -> Self {
+        Self {
+            dispatcher,
+            ops_user_ids,
+        }
+    }
+
+    /// Lista de UUIDs separados por comas en `FRAUD_ALERT_OPS_USER_IDS`
+    pub fn ops_user_ids_from_env() -> Vec<Uuid> {
+        let raw = std::env::var("FRAUD_ALERT_OPS_USER_IDS").unwrap_or_default();
+        let ids = Self::parse_ops_user_ids(&raw);
+        if ids.is_empty() {
+            warn!("FRAUD_ALERT_OPS_USER_IDS is not set; fraud alerts will only be logged");
+        }
+        ids
+    }
+
+    fn parse_ops_user_ids(raw: &str) -> Vec<Uuid> {
+        raw.split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .filter_map(|id| match Uuid::parse_str(id) {
+                Ok(uuid) => Some(uuid),
+                Err(_) => {
+                    warn!("Ignoring invalid ops user id in FRAUD_ALERT_OPS_USER_IDS: {}", id);
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl EventHandler for FraudAlertNotificationListener {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let DomainEvent::FraudDetected { context, subject_id, user_id, reason_code, risk_score, occurred_at } = event else {
+            return Ok(());
+        };
+
+        warn!(
+            "🚨 Fraud detected in {} (subject {}, reason {}, risk {:.2})",
+            context, subject_id, reason_code, risk_score
+        );
+
+        let metadata = serde_json::json!({
+            "context": context,
+            "subject_id": subject_id,
+            "user_id": user_id,
+            "reason_code": reason_code,
+            "risk_score": risk_score,
+            "occurred_at": occurred_at,
+        });
+
+        for ops_user_id in &self.ops_user_ids {
+            let notification = Notification::new(
+                *ops_user_id,
+                format!("Fraud detected in {}", context),
+                format!("{} {} was blocked ({}, risk score {:.2})", context, subject_id, reason_code, risk_score),
+                NotificationType::SecurityAlert,
+                NotificationPriority::Urgent,
+                Some(metadata.clone()),
+            );
+            if let Err(e) = self.dispatcher.dispatch(&notification).await {
+                error!("Failed to create fraud alert for ops user {}: {}", ops_user_id, e);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::sync::Mutex;
+    use crate::bounded_contexts::notifications::domain::entities::NotificationFilters;
+    use crate::bounded_contexts::notifications::domain::repositories::NotificationRepository;
+    use crate::bounded_contexts::notifications::infrastructure::MockNotificationPreferencesRepository;
+    use crate::shared::domain::errors::FraudReasonCode;
+
+    #[derive(Default)]
+    struct RecordingRepository {
+        created: Mutex<Vec<Notification>>,
+    }
+
+    type RepoResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+    #[async_trait]
+    impl NotificationRepository for RecordingRepository {
+        async fn create(&self, notification: &Notification) -> RepoResult<()> {
+            self.created.lock().unwrap().push(notification.clone());
+            Ok(())
+        }
+        async fn get_by_id(&self, _id: Uuid) -> RepoResult<Option<Notification>> { Ok(None) }
+        async fn get_by_user_id(&self, _user_id: Uuid, _page: u32, _page_size: u32) -> RepoResult<(Vec<Notification>, u32, u32)> { Ok((Vec::new(), 0, 0)) }
+        async fn get_unread_count(&self, _user_id: Uuid) -> RepoResult<u32> { Ok(0) }
+        async fn update(&self, _notification: &Notification) -> RepoResult<()> { Ok(()) }
+        async fn delete(&self, _id: Uuid) -> RepoResult<()> { Ok(()) }
+        async fn mark_as_read(&self, _id: Uuid) -> RepoResult<()> { Ok(()) }
+        async fn mark_as_archived(&self, _id: Uuid) -> RepoResult<()> { Ok(()) }
+        async fn mark_all_as_read(&self, _user_id: Uuid) -> RepoResult<()> { Ok(()) }
+        async fn search(&self, _filters: &NotificationFilters, _page: u32, _page_size: u32) -> RepoResult<Vec<Notification>> { Ok(Vec::new()) }
+        async fn get_summary(&self, _user_id: Uuid) -> RepoResult<(u32, u32, u32, u32)> { Ok((0, 0, 0, 0)) }
+    }
+
+    fn dispatcher(repository: Arc<RecordingRepository>) -> Arc<NotificationDispatcher> {
+        Arc::new(NotificationDispatcher::new(repository, Arc::new(MockNotificationPreferencesRepository::new())))
+    }
+
+    #[tokio::test]
+    async fn fraud_event_alerts_every_ops_user() {
+        let repository = Arc::new(RecordingRepository::default());
+        let ops = vec![Uuid::new_v4(), Uuid::new_v4()];
+        let listener = FraudAlertNotificationListener::new(dispatcher(repository.clone()), ops.clone());
+
+        listener.handle(&DomainEvent::FraudDetected {
+            context: "payment".to_string(),
+            subject_id: Uuid::new_v4(),
+            user_id: Some(Uuid::new_v4()),
+            reason_code: FraudReasonCode::VelocityExceeded,
+            risk_score: 0.91,
+            occurred_at: Utc::now(),
+        }).await.unwrap();
+
+        let created = repository.created.lock().unwrap();
+        assert_eq!(created.iter().map(|n| n.user_id).collect::<Vec<_>>(), ops);
+        assert!(created.iter().all(|n| n.notification_type == NotificationType::SecurityAlert
+            && n.priority == NotificationPriority::Urgent));
+        assert_eq!(created[0].metadata.as_ref().unwrap()["reason_code"], "velocity_exceeded");
+    }
+
+    #[test]
+    fn invalid_ops_user_ids_are_skipped() {
+        let id = Uuid::new_v4();
+        let parsed = FraudAlertNotificationListener::parse_ops_user_ids(&format!(" {} , not-a-uuid,,", id));
+        assert_eq!(parsed, vec![id]);
+    }
+}
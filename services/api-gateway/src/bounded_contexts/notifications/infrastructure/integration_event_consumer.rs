@@ -0,0 +1,182 @@
+use std::sync::Arc;
+use std::time::Duration;
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::streams::{StreamId, StreamReadReply};
+
+use crate::bounded_contexts::notifications::application::event_notifications::{
+    DeadLetterQueue, EventNotificationService, EventOutcome, StreamEvent,
+};
+
+// =============================================================================
+// NOTIFICATIONS - CONSUMIDOR DEL STREAM DE EVENTOS DE INTEGRACIÓN
+// =============================================================================
+
+/// Stream donde `RedisStreamEventPublisher` deja los eventos de integración
+pub const INTEGRATION_EVENTS_STREAM: &str = "listen_reward:events";
+
+/// Consumer group propio: el resto de consumidores del stream no se ven afectados
+pub const NOTIFICATIONS_CONSUMER_GROUP: &str = "notifications";
+
+/// Eventos que no se pudieron convertir en notificaciones
+pub const NOTIFICATIONS_DEAD_LETTER_STREAM: &str = "notifications:dead-letter";
+
+/// Guarda el entry original (`source_id`, `type`, `data`) con el motivo del
+/// rechazo, para inspeccionarlo o reinyectarlo a mano.
+pub struct RedisDeadLetterQueue {
+    connection: ConnectionManager,
+    stream_name: String,
+}
+
+impl RedisDeadLetterQueue {
+    pub fn new(connection: ConnectionManager) -> Self {
+        Self {
+            connection,
+            stream_name: NOTIFICATIONS_DEAD_LETTER_STREAM.to_string(),
+        }
+    }
+}
+
+#[async_trait]
+impl DeadLetterQueue for RedisDeadLetterQueue {
+    async fn dead_letter(&self, event: &StreamEvent, reason: &str) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.connection.clone();
+        let _: String = redis::cmd("XADD")
+            .arg(&self.stream_name)
+            .arg("*")
+            .arg("source_id")
+            .arg(&event.id)
+            .arg("type")
+            .arg(&event.event_type)
+            .arg("data")
+            .arg(&event.data)
+            .arg("reason")
+            .arg(reason)
+            .arg("failed_at")
+            .arg(chrono::Utc::now().to_rfc3339())
+            .query_async(&mut conn)
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Lee el stream con su consumer group y confirma (`XACK`) cada entry una vez
+/// entregado o enviado a dead letters. Los fallos transitorios dejan el entry
+/// pendiente y se reintenta en la siguiente vuelta. Usa conexiones propias:
+/// el `XREADGROUP ... BLOCK` bloquearía una conexión compartida.
+pub struct IntegrationEventConsumer {
+    client: redis::Client,
+    service: Arc<EventNotificationService>,
+    stream_name: String,
+    consumer_group: String,
+    consumer_name: String,
+}
+
+impl IntegrationEventConsumer {
+    pub fn new(client: redis::Client, service: Arc<EventNotificationService>) -> Self {
+        Self {
+            client,
+            service,
+            stream_name: INTEGRATION_EVENTS_STREAM.to_string(),
+            consumer_group: NOTIFICATIONS_CONSUMER_GROUP.to_string(),
+            consumer_name: "notifications-worker".to_string(),
+        }
+    }
+
+    pub fn with_stream(mut self, stream_name: impl Into<String>) -> Self {
+        self.stream_name = stream_name.into();
+        self
+    }
+
+    async fn ensure_consumer_group(&self) -> Result<(), redis::RedisError> {
+        let mut conn = self.client.get_async_connection().await?;
+        let result: Result<String, redis::RedisError> = redis::cmd("XGROUP")
+            .arg("CREATE")
+            .arg(&self.stream_name)
+            .arg(&self.consumer_group)
+            .arg("0")
+            .arg("MKSTREAM")
+            .query_async(&mut conn)
+            .await;
+
+        match result {
+            Err(e) if !e.to_string().contains("BUSYGROUP") => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Procesar un lote: `"0"` relee los pendientes de este consumidor, `">"`
+    /// trae entries nuevos. Devuelve cuántos se confirmaron.
+    async fn poll(&self, start_id: &str) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+        let mut conn = self.client.get_async_connection().await?;
+        // `nil` cuando vence el BLOCK sin entries nuevos
+        let reply: Option<StreamReadReply> = redis::cmd("XREADGROUP")
+            .arg("GROUP")
+            .arg(&self.consumer_group)
+            .arg(&self.consumer_name)
+            .arg("COUNT")
+            .arg(10)
+            .arg("BLOCK")
+            .arg(1000)
+            .arg("STREAMS")
+            .arg(&self.stream_name)
+            .arg(start_id)
+            .query_async(&mut conn)
+            .await?;
+
+        let mut acknowledged = 0;
+        for entry in reply.into_iter().flat_map(|reply| reply.keys).flat_map(|key| key.ids) {
+            let event = stream_event(&entry);
+            match self.service.handle(&event).await {
+                Ok(outcome) => {
+                    if let EventOutcome::Delivered(count) = outcome {
+                        tracing::debug!("Delivered {} notifications for {} {}", count, event.event_type, event.id);
+                    }
+                    let _: i64 = redis::cmd("XACK")
+                        .arg(&self.stream_name)
+                        .arg(&self.consumer_group)
+                        .arg(&entry.id)
+                        .query_async(&mut conn)
+                        .await?;
+                    acknowledged += 1;
+                }
+                Err(e) => tracing::error!("Failed to notify {} event {}: {}", event.event_type, event.id, e),
+            }
+        }
+
+        Ok(acknowledged)
+    }
+
+    pub fn start(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            if let Err(e) = self.ensure_consumer_group().await {
+                tracing::error!("Failed to create consumer group on {}: {}", self.stream_name, e);
+                return;
+            }
+            tracing::info!("🚀 Notification event consumer started on {}", self.stream_name);
+
+            loop {
+                for start_id in ["0", ">"] {
+                    match self.poll(start_id).await {
+                        Ok(count) if count > 0 => tracing::info!("✅ Processed {} integration events", count),
+                        Ok(_) => {}
+                        Err(e) => {
+                            tracing::error!("Notification event consumer failed: {}", e);
+                            tokio::time::sleep(Duration::from_secs(1)).await;
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+fn stream_event(entry: &StreamId) -> StreamEvent {
+    let field = |name: &str| entry.get::<String>(name).unwrap_or_default();
+    StreamEvent {
+        id: entry.id.clone(),
+        event_type: field("type"),
+        data: field("data"),
+    }
+}
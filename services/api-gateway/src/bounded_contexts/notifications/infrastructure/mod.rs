@@ -1,5 +1,21 @@
 pub mod postgres_repository;
 pub mod mock_repository;
+pub mod fraud_alert_listener;
+pub mod campaign_budget_listener;
+pub mod song_review_listener;
+pub mod profile_change_listener;
+pub mod user_deletion_listener;
+pub mod integration_event_consumer;
+pub mod push_providers;
+pub mod smtp_email;
 
 pub use postgres_repository::*;
-pub use mock_repository::*;
\ No newline at end of file
+pub use mock_repository::*;
+pub use fraud_alert_listener::FraudAlertNotificationListener;
+pub use campaign_budget_listener::CampaignBudgetNotificationListener;
+pub use song_review_listener::SongReviewNotificationListener;
+pub use profile_change_listener::ProfileChangeNotificationListener;
+pub use user_deletion_listener::NotificationUserDeletionListener;
+pub use integration_event_consumer::{IntegrationEventConsumer, RedisDeadLetterQueue};
+pub use push_providers::{push_sender_from_env, ApnsConfig, ApnsPushSender, FcmConfig, FcmPushSender};
+pub use smtp_email::{email_sender_from_env, SmtpConfig, SmtpEmailSender, SmtpTls};
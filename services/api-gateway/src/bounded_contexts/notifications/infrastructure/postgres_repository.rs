@@ -1,10 +1,16 @@
 use crate::bounded_contexts::notifications::domain::{
     Notification, NotificationType, NotificationPriority, NotificationStatus,
-    NotificationFilters
+    NotificationFilters, NotificationPreferences, DeviceToken, DigestEntry,
 };
-use crate::bounded_contexts::notifications::domain::repositories::NotificationRepository;
+use crate::bounded_contexts::notifications::domain::repositories::{
+    NotificationRepository, NotificationPreferencesRepository, DeviceTokenRepository, DigestRepository,
+};
+use crate::bounded_contexts::notifications::application::email::{EmailRecipient, EmailRecipientDirectory};
+use crate::bounded_contexts::notifications::application::event_notifications::RecipientDirectory;
+use chrono::{DateTime, Utc};
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
 use uuid::Uuid;
 use serde_json::Value;
 
@@ -192,8 +198,8 @@ impl NotificationRepository for PostgresNotificationRepository {
     async fn mark_as_read(&self, id: Uuid) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         sqlx::query!(
             r#"UPDATE notifications 
-               SET read_at = NOW(), status = 'Read', updated_at = NOW() 
-               WHERE id = $1"#,
+               SET read_at = NOW(), status = 'read', updated_at = NOW() 
+               WHERE id = $1 AND read_at IS NULL"#,
             id
         )
         .execute(&self.pool)
@@ -219,7 +225,7 @@ impl NotificationRepository for PostgresNotificationRepository {
     async fn mark_all_as_read(&self, user_id: Uuid) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         sqlx::query!(
             r#"UPDATE notifications 
-               SET read_at = NOW(), status = 'Read', updated_at = NOW() 
+               SET read_at = NOW(), status = 'read', updated_at = NOW() 
                WHERE user_id = $1 AND read_at IS NULL"#,
             user_id
         )
@@ -230,9 +236,31 @@ impl NotificationRepository for PostgresNotificationRepository {
         Ok(())
     }
 
-    async fn search(&self, _filters: &NotificationFilters, _page: u32, _page_size: u32) -> Result<Vec<Notification>, Box<dyn std::error::Error + Send + Sync>> {
-        // TODO: Implementar cuando la base de datos esté disponible
-        Ok(vec![])
+    async fn search(&self, filters: &NotificationFilters, page: u32, page_size: u32) -> Result<Vec<Notification>, Box<dyn std::error::Error + Send + Sync>> {
+        let rows = sqlx::query(
+            r#"SELECT id, user_id, title, message, notification_type, priority, status,
+                      metadata, read_at, created_at, updated_at
+               FROM notifications
+               WHERE ($1::uuid IS NULL OR user_id = $1)
+                 AND ($2::varchar IS NULL OR notification_type = $2)
+                 AND ($3::varchar IS NULL OR LOWER(status) = $3)
+                 AND ($4::boolean IS NULL OR (read_at IS NOT NULL) = $4)
+               ORDER BY created_at DESC
+               LIMIT $5 OFFSET $6"#,
+        )
+        .bind(filters.user_id)
+        .bind(filters.notification_type.as_ref().map(serialize_notification_type))
+        .bind(filters.status.as_ref().map(serialize_notification_status))
+        .bind(filters.read)
+        .bind(page_size as i64)
+        .bind(page as i64 * page_size as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        rows.iter()
+            .map(|row| notification_from_row(row).map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>))
+            .collect()
     }
 
     async fn get_summary(&self, user_id: Uuid) -> Result<(u32, u32, u32, u32), Box<dyn std::error::Error + Send + Sync>> {
@@ -261,41 +289,349 @@ impl NotificationRepository for PostgresNotificationRepository {
     }
 }
 
+fn notification_from_row(row: &PgRow) -> Result<Notification, sqlx::Error> {
+    Ok(Notification {
+        id: row.try_get("id")?,
+        user_id: row.try_get("user_id")?,
+        title: row.try_get("title")?,
+        message: row.try_get("message")?,
+        notification_type: parse_notification_type(&row.try_get::<String, _>("notification_type")?),
+        priority: parse_notification_priority(&row.try_get::<String, _>("priority")?),
+        status: parse_notification_status(&row.try_get::<String, _>("status")?),
+        read_at: row.try_get("read_at")?,
+        metadata: row.try_get("metadata")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+/// Preferencias por usuario: interruptores globales, horas silenciosas y el
+/// documento `categories` con los canales de cada categoría.
+pub struct PostgresNotificationPreferencesRepository {
+    pool: PgPool,
+}
+
+impl PostgresNotificationPreferencesRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    async fn upsert(&self, preferences: &NotificationPreferences) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        sqlx::query(
+            r#"INSERT INTO notification_preferences (
+                user_id, email_enabled, push_enabled, quiet_hours_start, quiet_hours_end, categories,
+                digest_categories, digest_frequency
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (user_id) DO UPDATE SET
+                email_enabled = EXCLUDED.email_enabled,
+                push_enabled = EXCLUDED.push_enabled,
+                quiet_hours_start = EXCLUDED.quiet_hours_start,
+                quiet_hours_end = EXCLUDED.quiet_hours_end,
+                categories = EXCLUDED.categories,
+                digest_categories = EXCLUDED.digest_categories,
+                digest_frequency = EXCLUDED.digest_frequency,
+                updated_at = NOW()"#,
+        )
+        .bind(preferences.user_id)
+        .bind(preferences.email_enabled)
+        .bind(preferences.push_enabled)
+        .bind(preferences.quiet_hours_start.map(i16::from))
+        .bind(preferences.quiet_hours_end.map(i16::from))
+        .bind(serde_json::to_value(&preferences.categories)?)
+        .bind(serde_json::to_value(&preferences.digest_categories)?)
+        .bind(preferences.digest_frequency.as_str())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl NotificationPreferencesRepository for PostgresNotificationPreferencesRepository {
+    async fn get_by_user_id(&self, user_id: Uuid) -> Result<Option<NotificationPreferences>, Box<dyn std::error::Error + Send + Sync>> {
+        let row = sqlx::query(
+            r#"SELECT email_enabled, push_enabled, quiet_hours_start, quiet_hours_end,
+                      categories, digest_categories, digest_frequency, created_at, updated_at
+               FROM notification_preferences WHERE user_id = $1"#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let mut preferences = NotificationPreferences::new(user_id);
+        preferences.email_enabled = row.try_get("email_enabled")?;
+        preferences.push_enabled = row.try_get("push_enabled")?;
+        preferences.quiet_hours_start = row.try_get::<Option<i16>, _>("quiet_hours_start")?.map(|h| h as u8);
+        preferences.quiet_hours_end = row.try_get::<Option<i16>, _>("quiet_hours_end")?.map(|h| h as u8);
+        preferences.categories = serde_json::from_value(row.try_get::<Value, _>("categories")?)?;
+        preferences.digest_categories = serde_json::from_value(row.try_get::<Value, _>("digest_categories")?)?;
+        preferences.digest_frequency = row.try_get::<String, _>("digest_frequency")?.parse()?;
+        preferences.created_at = row.try_get("created_at")?;
+        preferences.updated_at = row.try_get("updated_at")?;
+
+        Ok(Some(preferences))
+    }
+
+    async fn create(&self, preferences: &NotificationPreferences) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.upsert(preferences).await
+    }
+
+    async fn update(&self, preferences: &NotificationPreferences) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.upsert(preferences).await
+    }
+
+    async fn delete(&self, user_id: Uuid) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        sqlx::query("DELETE FROM notification_preferences WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(())
+    }
+}
+
+/// Tokens de push por dispositivo; `token` es único en toda la tabla.
+pub struct PostgresDeviceTokenRepository {
+    pool: PgPool,
+}
+
+impl PostgresDeviceTokenRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn row_to_device_token(row: &PgRow) -> Result<DeviceToken, Box<dyn std::error::Error + Send + Sync>> {
+    let platform: String = row.try_get("platform")?;
+    Ok(DeviceToken {
+        id: row.try_get("id")?,
+        user_id: row.try_get("user_id")?,
+        platform: platform.parse()?,
+        token: row.try_get("token")?,
+        created_at: row.try_get("created_at")?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
+
+#[async_trait]
+impl DeviceTokenRepository for PostgresDeviceTokenRepository {
+    async fn register(&self, device: &DeviceToken) -> Result<DeviceToken, Box<dyn std::error::Error + Send + Sync>> {
+        let row = sqlx::query(
+            r#"INSERT INTO notification_device_tokens (id, user_id, platform, token, created_at, updated_at)
+               VALUES ($1, $2, $3, $4, $5, $6)
+               ON CONFLICT (token) DO UPDATE SET
+                   user_id = EXCLUDED.user_id,
+                   platform = EXCLUDED.platform,
+                   updated_at = EXCLUDED.updated_at
+               RETURNING id, user_id, platform, token, created_at, updated_at"#,
+        )
+        .bind(device.id)
+        .bind(device.user_id)
+        .bind(device.platform.as_str())
+        .bind(&device.token)
+        .bind(device.created_at)
+        .bind(device.updated_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        row_to_device_token(&row)
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<DeviceToken>, Box<dyn std::error::Error + Send + Sync>> {
+        let rows = sqlx::query(
+            r#"SELECT id, user_id, platform, token, created_at, updated_at
+               FROM notification_device_tokens WHERE user_id = $1 ORDER BY updated_at DESC"#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        rows.iter().map(row_to_device_token).collect()
+    }
+
+    async fn remove_tokens(&self, tokens: &[String]) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+        let result = sqlx::query("DELETE FROM notification_device_tokens WHERE token = ANY($1)")
+            .bind(tokens)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+/// Reserva de una entrada que se da por abandonada (el proceso cayó a mitad de envío)
+const DIGEST_CLAIM_TIMEOUT_MINUTES: i32 = 30;
+
+/// Cola de los resúmenes por email; las entradas enviadas se borran.
+pub struct PostgresDigestRepository {
+    pool: PgPool,
+}
+
+impl PostgresDigestRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn row_to_digest_entry(row: &PgRow) -> Result<DigestEntry, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(DigestEntry {
+        id: row.try_get("id")?,
+        user_id: row.try_get("user_id")?,
+        notification_id: row.try_get("notification_id")?,
+        category: row.try_get::<String, _>("category")?.parse()?,
+        title: row.try_get("title")?,
+        message: row.try_get("message")?,
+        created_at: row.try_get("created_at")?,
+        period_start: row.try_get("period_start")?,
+        period_end: row.try_get("period_end")?,
+    })
+}
+
+#[async_trait]
+impl DigestRepository for PostgresDigestRepository {
+    async fn enqueue(&self, entry: &DigestEntry) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        sqlx::query(
+            r#"INSERT INTO notification_digest_entries (
+                id, user_id, notification_id, category, title, message, created_at, period_start, period_end
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (notification_id) DO NOTHING"#,
+        )
+        .bind(entry.id)
+        .bind(entry.user_id)
+        .bind(entry.notification_id)
+        .bind(entry.category.as_str())
+        .bind(&entry.title)
+        .bind(&entry.message)
+        .bind(entry.created_at)
+        .bind(entry.period_start)
+        .bind(entry.period_end)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(())
+    }
+
+    async fn claim_due(&self, now: DateTime<Utc>) -> Result<Vec<DigestEntry>, Box<dyn std::error::Error + Send + Sync>> {
+        let rows = sqlx::query(
+            r#"UPDATE notification_digest_entries SET claimed_at = $1
+               WHERE id IN (
+                   SELECT id FROM notification_digest_entries
+                   WHERE period_end <= $1
+                     AND (claimed_at IS NULL OR claimed_at < $1 - make_interval(mins => $2))
+                   FOR UPDATE SKIP LOCKED
+               )
+               RETURNING id, user_id, notification_id, category, title, message, created_at, period_start, period_end"#,
+        )
+        .bind(now)
+        .bind(DIGEST_CLAIM_TIMEOUT_MINUTES)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        rows.iter().map(row_to_digest_entry).collect()
+    }
+
+    async fn complete(&self, entry_ids: &[Uuid]) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        sqlx::query("DELETE FROM notification_digest_entries WHERE id = ANY($1)")
+            .bind(entry_ids)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(())
+    }
+
+    async fn release(&self, entry_ids: &[Uuid]) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        sqlx::query("UPDATE notification_digest_entries SET claimed_at = NULL WHERE id = ANY($1)")
+            .bind(entry_ids)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        Ok(())
+    }
+}
+
+/// Dirección de email de las cuentas activas
+pub struct PostgresEmailRecipientDirectory {
+    pool: PgPool,
+}
+
+impl PostgresEmailRecipientDirectory {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl EmailRecipientDirectory for PostgresEmailRecipientDirectory {
+    async fn email_recipient(&self, user_id: Uuid) -> Result<Option<EmailRecipient>, Box<dyn std::error::Error + Send + Sync>> {
+        let row = sqlx::query("SELECT email, username FROM users WHERE id = $1 AND is_active")
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+        row.map(|row| {
+            Ok(EmailRecipient {
+                user_id,
+                email: row.try_get("email")?,
+                name: row.try_get("username")?,
+            })
+        })
+        .transpose()
+    }
+}
+
+/// Destinatarios que no viajan en los eventos: el usuario de un artista y el
+/// creador de una venture (`artist_ventures.artist_id` ya es un usuario).
+pub struct PostgresRecipientDirectory {
+    pool: PgPool,
+}
+
+impl PostgresRecipientDirectory {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RecipientDirectory for PostgresRecipientDirectory {
+    async fn artist_user(&self, artist_id: Uuid) -> Result<Option<Uuid>, Box<dyn std::error::Error + Send + Sync>> {
+        sqlx::query_scalar("SELECT user_id FROM artists WHERE id = $1")
+            .bind(artist_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+
+    async fn venture_owner(&self, venture_id: Uuid) -> Result<Option<Uuid>, Box<dyn std::error::Error + Send + Sync>> {
+        sqlx::query_scalar("SELECT artist_id FROM artist_ventures WHERE id = $1")
+            .bind(venture_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)
+    }
+}
+
 // Helper functions to serialize enums to database strings
 fn serialize_notification_type(nt: &NotificationType) -> String {
-    match nt {
-        NotificationType::VentureCreated => "venture_created",
-        NotificationType::InvestmentMade => "investment_made",
-        NotificationType::BenefitDelivered => "benefit_delivered",
-        NotificationType::VentureFunded => "venture_funded",
-        NotificationType::VentureExpired => "venture_expired",
-        NotificationType::SystemAlert => "system_alert",
-        NotificationType::Marketing => "marketing",
-        NotificationType::RevenueDistributed => "revenue_distributed",
-        NotificationType::ListenSessionCompleted => "listen_session_completed",
-        NotificationType::RewardEarned => "reward_earned",
-        NotificationType::ZKProofVerified => "zk_proof_verified",
-        NotificationType::CampaignLaunched => "campaign_launched",
-        NotificationType::CampaignEnded => "campaign_ended",
-        NotificationType::CampaignMilestoneReached => "campaign_milestone_reached",
-        NotificationType::AccountCreated => "account_created",
-        NotificationType::ProfileUpdated => "profile_updated",
-        NotificationType::WalletLinked => "wallet_linked",
-        NotificationType::SystemMaintenance => "system_maintenance",
-        NotificationType::SecurityAlert => "security_alert",
-        NotificationType::WelcomeMessage => "welcome_message",
-        NotificationType::Custom(s) => s,
-    }.to_string()
+    nt.as_str().to_string()
 }
 
 fn serialize_notification_priority(np: &NotificationPriority) -> String {
-    match np {
-        NotificationPriority::Low => "low",
-        NotificationPriority::Medium => "medium",
-        NotificationPriority::High => "high",
-        NotificationPriority::Urgent => "urgent",
-        NotificationPriority::Normal => "normal",
-    }.to_string()
+    np.as_str().to_string()
 }
 
 fn serialize_notification_status(ns: &NotificationStatus) -> String {
@@ -327,12 +663,15 @@ fn parse_notification_type(s: &str) -> NotificationType {
         "campaign_launched" | "campaignlaunched" => NotificationType::CampaignLaunched,
         "campaign_ended" | "campaignended" => NotificationType::CampaignEnded,
         "campaign_milestone_reached" | "campaignmilestonereached" => NotificationType::CampaignMilestoneReached,
+        "campaign_budget_depleted" | "campaignbudgetdepleted" => NotificationType::CampaignBudgetDepleted,
         "account_created" | "accountcreated" => NotificationType::AccountCreated,
         "profile_updated" | "profileupdated" => NotificationType::ProfileUpdated,
         "wallet_linked" | "walletlinked" => NotificationType::WalletLinked,
         "system_maintenance" | "systemmaintenance" => NotificationType::SystemMaintenance,
         "security_alert" | "securityalert" => NotificationType::SecurityAlert,
         "welcome_message" | "welcomemessage" => NotificationType::WelcomeMessage,
+        "new_follower" | "newfollower" => NotificationType::NewFollower,
+        "song_under_review" | "songunderreview" => NotificationType::SongUnderReview,
         _ => NotificationType::Custom(s.to_string()),
     }
 }
@@ -0,0 +1,149 @@
+//! Profile Change Listener
+//!
+//! Escucha `UserProfileUpdated` y avisa al propio usuario cuando cambian su
+//! nombre de usuario o su wallet, para que detecte cambios que no hizo él.
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use tracing::error;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+use crate::bounded_contexts::notifications::domain::entities::{
+    Notification, NotificationPriority, NotificationType,
+};
+use crate::bounded_contexts::notifications::application::NotificationDispatcher;
+
+pub struct ProfileChangeNotificationListener {
+    dispatcher: Arc<NotificationDispatcher>,
+}
+
+impl ProfileChangeNotificationListener {
+    pub fn new(dispatcher: Arc<NotificationDispatcher>) -> Self {
+        Self { dispatcher }
+    }
+}
+
+#[async_trait]
+impl EventHandler for ProfileChangeNotificationListener {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let DomainEvent::UserProfileUpdated { user_id, username, changed_fields, previous_username, occurred_at } = event else {
+            return Ok(());
+        };
+        let changed = |field: &str| changed_fields.iter().any(|f| f == field);
+
+        let mut notifications = Vec::new();
+        if changed("username") {
+            notifications.push(Notification::new(
+                *user_id,
+                "Nombre de usuario actualizado".to_string(),
+                format!(
+                    "Tu nombre de usuario ha cambiado de {} a {}",
+                    previous_username.as_deref().unwrap_or("-"),
+                    username
+                ),
+                NotificationType::ProfileUpdated,
+                NotificationPriority::High,
+                Some(serde_json::json!({
+                    "previous_username": previous_username,
+                    "username": username,
+                    "occurred_at": occurred_at,
+                })),
+            ));
+        }
+        if changed("wallet_address") {
+            notifications.push(Notification::new(
+                *user_id,
+                "Wallet actualizada".to_string(),
+                "La wallet de tu perfil ha cambiado".to_string(),
+                NotificationType::WalletLinked,
+                NotificationPriority::High,
+                Some(serde_json::json!({ "occurred_at": occurred_at })),
+            ));
+        }
+
+        for notification in notifications {
+            if let Err(e) = self.dispatcher.dispatch(&notification).await {
+                error!("Failed to notify user {} of a profile change: {}", user_id, e);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use std::sync::Mutex;
+    use uuid::Uuid;
+    use crate::bounded_contexts::notifications::domain::entities::NotificationFilters;
+    use crate::bounded_contexts::notifications::domain::repositories::NotificationRepository;
+    use crate::bounded_contexts::notifications::infrastructure::MockNotificationPreferencesRepository;
+
+    #[derive(Default)]
+    struct RecordingRepository {
+        created: Mutex<Vec<Notification>>,
+    }
+
+    type RepoResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+    #[async_trait]
+    impl NotificationRepository for RecordingRepository {
+        async fn create(&self, notification: &Notification) -> RepoResult<()> {
+            self.created.lock().unwrap().push(notification.clone());
+            Ok(())
+        }
+        async fn get_by_id(&self, _id: Uuid) -> RepoResult<Option<Notification>> { Ok(None) }
+        async fn get_by_user_id(&self, _user_id: Uuid, _page: u32, _page_size: u32) -> RepoResult<(Vec<Notification>, u32, u32)> { Ok((Vec::new(), 0, 0)) }
+        async fn get_unread_count(&self, _user_id: Uuid) -> RepoResult<u32> { Ok(0) }
+        async fn update(&self, _notification: &Notification) -> RepoResult<()> { Ok(()) }
+        async fn delete(&self, _id: Uuid) -> RepoResult<()> { Ok(()) }
+        async fn mark_as_read(&self, _id: Uuid) -> RepoResult<()> { Ok(()) }
+        async fn mark_as_archived(&self, _id: Uuid) -> RepoResult<()> { Ok(()) }
+        async fn mark_all_as_read(&self, _user_id: Uuid) -> RepoResult<()> { Ok(()) }
+        async fn search(&self, _filters: &NotificationFilters, _page: u32, _page_size: u32) -> RepoResult<Vec<Notification>> { Ok(Vec::new()) }
+        async fn get_summary(&self, _user_id: Uuid) -> RepoResult<(u32, u32, u32, u32)> { Ok((0, 0, 0, 0)) }
+    }
+
+    fn dispatcher(repository: Arc<RecordingRepository>) -> Arc<NotificationDispatcher> {
+        Arc::new(NotificationDispatcher::new(repository, Arc::new(MockNotificationPreferencesRepository::new())))
+    }
+
+    fn profile_updated(user_id: Uuid, changed_fields: &[&str]) -> DomainEvent {
+        DomainEvent::UserProfileUpdated {
+            user_id,
+            username: "new_name".to_string(),
+            changed_fields: changed_fields.iter().map(|f| f.to_string()).collect(),
+            previous_username: Some("old_name".to_string()),
+            occurred_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn username_and_wallet_changes_are_notified() {
+        let repository = Arc::new(RecordingRepository::default());
+        let listener = ProfileChangeNotificationListener::new(dispatcher(repository.clone()));
+        let user_id = Uuid::new_v4();
+
+        listener.handle(&profile_updated(user_id, &["username", "bio", "wallet_address"])).await.unwrap();
+
+        let created = repository.created.lock().unwrap();
+        assert_eq!(created.len(), 2);
+        assert!(created.iter().all(|n| n.user_id == user_id && n.priority == NotificationPriority::High));
+        assert_eq!(created[0].notification_type, NotificationType::ProfileUpdated);
+        assert!(created[0].message.contains("old_name") && created[0].message.contains("new_name"));
+        assert_eq!(created[1].notification_type, NotificationType::WalletLinked);
+    }
+
+    #[tokio::test]
+    async fn cosmetic_changes_are_not_notified() {
+        let repository = Arc::new(RecordingRepository::default());
+        let listener = ProfileChangeNotificationListener::new(dispatcher(repository.clone()));
+
+        listener.handle(&profile_updated(Uuid::new_v4(), &["display_name", "bio"])).await.unwrap();
+
+        assert!(repository.created.lock().unwrap().is_empty());
+    }
+}
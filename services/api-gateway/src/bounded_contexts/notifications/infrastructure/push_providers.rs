@@ -0,0 +1,432 @@
+//! Push Providers
+//!
+//! Clientes de FCM (HTTP v1, OAuth con la cuenta de servicio) y APNs
+//! (autenticación por token `.p8`). Las credenciales vienen del entorno y cada
+//! proveedor se desactiva si faltan.
+
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use async_trait::async_trait;
+use chrono::Utc;
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::Mutex;
+
+use crate::bounded_contexts::notifications::application::push::{
+    PushError, PushMessage, PushNotificationSender, PushSender,
+};
+use crate::bounded_contexts::notifications::domain::entities::DevicePlatform;
+use crate::bounded_contexts::notifications::domain::repositories::DeviceTokenRepository;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const FCM_SCOPE: &str = "https://www.googleapis.com/auth/firebase.messaging";
+/// Margen antes de la caducidad del access token de Google
+const FCM_TOKEN_MARGIN: Duration = Duration::from_secs(60);
+/// Apple rechaza tokens de más de una hora y su renovación más de una vez cada 20 minutos
+const APNS_TOKEN_LIFETIME: Duration = Duration::from_secs(50 * 60);
+
+struct CachedToken {
+    value: String,
+    expires_at: Instant,
+}
+
+/// Las claves PEM suelen llegar en una sola línea con `\n` escapados
+fn pem_from_env(name: &str) -> Option<String> {
+    std::env::var(name).ok().map(|value| value.replace("\\n", "\n"))
+}
+
+fn transport_error(e: reqwest::Error) -> PushError {
+    PushError::Retryable(e.to_string())
+}
+
+// =============================================================================
+// FCM
+// =============================================================================
+
+#[derive(Debug, Clone)]
+pub struct FcmConfig {
+    pub project_id: String,
+    /// `client_email` de la cuenta de servicio
+    pub client_email: String,
+    /// `private_key` (PEM RSA) de la cuenta de servicio
+    pub private_key: String,
+    pub token_uri: String,
+    pub api_url: String,
+}
+
+impl FcmConfig {
+    pub fn new(project_id: String, client_email: String, private_key: String) -> Self {
+        Self {
+            project_id,
+            client_email,
+            private_key,
+            token_uri: "https://oauth2.googleapis.com/token".to_string(),
+            api_url: "https://fcm.googleapis.com".to_string(),
+        }
+    }
+
+    /// `None` si falta alguna credencial (push Android deshabilitado)
+    pub fn from_env() -> Option<Self> {
+        let project_id = std::env::var("FCM_PROJECT_ID").ok()?;
+        let client_email = std::env::var("FCM_CLIENT_EMAIL").ok()?;
+        let private_key = pem_from_env("FCM_PRIVATE_KEY")?;
+        Some(Self::new(project_id, client_email, private_key))
+    }
+}
+
+#[derive(Serialize)]
+struct ServiceAccountClaims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+#[derive(Deserialize)]
+struct GoogleTokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+pub struct FcmPushSender {
+    config: FcmConfig,
+    key: EncodingKey,
+    client: Client,
+    access_token: Mutex<Option<CachedToken>>,
+}
+
+impl FcmPushSender {
+    pub fn new(config: FcmConfig) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let key = EncodingKey::from_rsa_pem(config.private_key.as_bytes())?;
+        let client = Client::builder().timeout(REQUEST_TIMEOUT).build()?;
+        Ok(Self {
+            config,
+            key,
+            client,
+            access_token: Mutex::new(None),
+        })
+    }
+
+    async fn access_token(&self) -> Result<String, PushError> {
+        let mut cached = self.access_token.lock().await;
+        if let Some(token) = cached.as_ref().filter(|t| t.expires_at > Instant::now()) {
+            return Ok(token.value.clone());
+        }
+
+        let now = Utc::now().timestamp();
+        let claims = ServiceAccountClaims {
+            iss: &self.config.client_email,
+            scope: FCM_SCOPE,
+            aud: &self.config.token_uri,
+            iat: now,
+            exp: now + 3600,
+        };
+        let assertion = jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &self.key)
+            .map_err(|e| PushError::Rejected(format!("Failed to sign FCM assertion: {}", e)))?;
+
+        let response = self
+            .client
+            .post(&self.config.token_uri)
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                ("assertion", assertion.as_str()),
+            ])
+            .send()
+            .await
+            .map_err(transport_error)?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(if status.is_server_error() {
+                PushError::Retryable(format!("FCM token endpoint answered {}", status))
+            } else {
+                PushError::Rejected(format!("FCM token endpoint answered {}: {}", status, body))
+            });
+        }
+
+        let token: GoogleTokenResponse = response.json().await.map_err(transport_error)?;
+        let lifetime = Duration::from_secs(token.expires_in).saturating_sub(FCM_TOKEN_MARGIN);
+        *cached = Some(CachedToken {
+            value: token.access_token.clone(),
+            expires_at: Instant::now() + lifetime,
+        });
+        Ok(token.access_token)
+    }
+
+    fn payload(message: &PushMessage) -> Value {
+        let mut android = serde_json::json!({ "priority": "high" });
+        if let Some(collapse_key) = &message.collapse_key {
+            android["collapse_key"] = Value::String(collapse_key.clone());
+        }
+        serde_json::json!({
+            "message": {
+                "token": message.token,
+                "notification": { "title": message.title, "body": message.body },
+                "data": message.data,
+                "android": android,
+            }
+        })
+    }
+}
+
+/// Clasificar la respuesta de `messages:send`
+pub fn classify_fcm_response(status: StatusCode, body: &Value) -> Result<(), PushError> {
+    if status.is_success() {
+        return Ok(());
+    }
+    let error = &body["error"];
+    let error_code = error["details"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find_map(|detail| detail["errorCode"].as_str())
+        .unwrap_or_else(|| error["status"].as_str().unwrap_or_default());
+    let message = error["message"].as_str().unwrap_or_default();
+
+    match (status, error_code) {
+        (StatusCode::NOT_FOUND, _) | (_, "UNREGISTERED") => Err(PushError::InvalidToken),
+        // Token mal formado; el resto de INVALID_ARGUMENT son fallos del payload
+        (StatusCode::BAD_REQUEST, "INVALID_ARGUMENT") if message.contains("registration token") => {
+            Err(PushError::InvalidToken)
+        }
+        (StatusCode::TOO_MANY_REQUESTS, _) => Err(PushError::Retryable(format!("FCM answered {}", status))),
+        (status, _) if status.is_server_error() => Err(PushError::Retryable(format!("FCM answered {}", status))),
+        (status, code) => Err(PushError::Rejected(format!("FCM answered {} {}: {}", status, code, message))),
+    }
+}
+
+#[async_trait]
+impl PushSender for FcmPushSender {
+    fn platform(&self) -> DevicePlatform {
+        DevicePlatform::Android
+    }
+
+    async fn send(&self, message: &PushMessage) -> Result<(), PushError> {
+        let access_token = self.access_token().await?;
+        let url = format!("{}/v1/projects/{}/messages:send", self.config.api_url, self.config.project_id);
+        let response = self
+            .client
+            .post(url)
+            .bearer_auth(access_token)
+            .json(&Self::payload(message))
+            .send()
+            .await
+            .map_err(transport_error)?;
+
+        let status = response.status();
+        if status == StatusCode::UNAUTHORIZED {
+            // Access token revocado: el siguiente intento pide otro
+            *self.access_token.lock().await = None;
+            return Err(PushError::Retryable("FCM access token rejected".to_string()));
+        }
+        let body: Value = response.json().await.unwrap_or(Value::Null);
+        classify_fcm_response(status, &body)
+    }
+}
+
+// =============================================================================
+// APNs
+// =============================================================================
+
+#[derive(Debug, Clone)]
+pub struct ApnsConfig {
+    pub key_id: String,
+    pub team_id: String,
+    /// Clave `.p8` (PEM EC) de la cuenta de desarrollador
+    pub private_key: String,
+    /// Bundle id de la app (`apns-topic`)
+    pub topic: String,
+    pub api_url: String,
+}
+
+impl ApnsConfig {
+    pub fn new(key_id: String, team_id: String, private_key: String, topic: String, sandbox: bool) -> Self {
+        let api_url = if sandbox {
+            "https://api.sandbox.push.apple.com"
+        } else {
+            "https://api.push.apple.com"
+        };
+        Self {
+            key_id,
+            team_id,
+            private_key,
+            topic,
+            api_url: api_url.to_string(),
+        }
+    }
+
+    /// `None` si falta alguna credencial (push iOS deshabilitado)
+    pub fn from_env() -> Option<Self> {
+        let key_id = std::env::var("APNS_KEY_ID").ok()?;
+        let team_id = std::env::var("APNS_TEAM_ID").ok()?;
+        let private_key = pem_from_env("APNS_PRIVATE_KEY")?;
+        let topic = std::env::var("APNS_TOPIC").ok()?;
+        let sandbox = std::env::var("APNS_SANDBOX").map(|v| v == "true").unwrap_or(false);
+        Some(Self::new(key_id, team_id, private_key, topic, sandbox))
+    }
+}
+
+#[derive(Serialize)]
+struct ProviderTokenClaims<'a> {
+    iss: &'a str,
+    iat: i64,
+}
+
+pub struct ApnsPushSender {
+    config: ApnsConfig,
+    key: EncodingKey,
+    client: Client,
+    provider_token: Mutex<Option<CachedToken>>,
+}
+
+impl ApnsPushSender {
+    pub fn new(config: ApnsConfig) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let key = EncodingKey::from_ec_pem(config.private_key.as_bytes())?;
+        // APNs solo habla HTTP/2, negociado por ALPN
+        let client = Client::builder().use_rustls_tls().timeout(REQUEST_TIMEOUT).build()?;
+        Ok(Self {
+            config,
+            key,
+            client,
+            provider_token: Mutex::new(None),
+        })
+    }
+
+    async fn provider_token(&self) -> Result<String, PushError> {
+        let mut cached = self.provider_token.lock().await;
+        if let Some(token) = cached.as_ref().filter(|t| t.expires_at > Instant::now()) {
+            return Ok(token.value.clone());
+        }
+
+        let mut header = Header::new(Algorithm::ES256);
+        header.kid = Some(self.config.key_id.clone());
+        let claims = ProviderTokenClaims { iss: &self.config.team_id, iat: Utc::now().timestamp() };
+        let token = jsonwebtoken::encode(&header, &claims, &self.key)
+            .map_err(|e| PushError::Rejected(format!("Failed to sign APNs provider token: {}", e)))?;
+
+        *cached = Some(CachedToken {
+            value: token.clone(),
+            expires_at: Instant::now() + APNS_TOKEN_LIFETIME,
+        });
+        Ok(token)
+    }
+
+    fn payload(message: &PushMessage) -> Value {
+        let mut payload = serde_json::json!({
+            "aps": {
+                "alert": { "title": message.title, "body": message.body },
+                "sound": "default",
+            }
+        });
+        for (key, value) in &message.data {
+            payload[key] = Value::String(value.clone());
+        }
+        payload
+    }
+}
+
+/// Clasificar la respuesta de `/3/device/{token}`
+pub fn classify_apns_response(status: StatusCode, body: &Value) -> Result<(), PushError> {
+    if status.is_success() {
+        return Ok(());
+    }
+    let reason = body["reason"].as_str().unwrap_or_default();
+
+    match (status, reason) {
+        (StatusCode::GONE, _) | (_, "BadDeviceToken") | (_, "DeviceTokenNotForTopic") | (_, "Unregistered") => {
+            Err(PushError::InvalidToken)
+        }
+        (StatusCode::TOO_MANY_REQUESTS, _) => Err(PushError::Retryable(format!("APNs answered {}", status))),
+        (status, _) if status.is_server_error() => Err(PushError::Retryable(format!("APNs answered {}", status))),
+        (status, reason) => Err(PushError::Rejected(format!("APNs answered {}: {}", status, reason))),
+    }
+}
+
+#[async_trait]
+impl PushSender for ApnsPushSender {
+    fn platform(&self) -> DevicePlatform {
+        DevicePlatform::Ios
+    }
+
+    async fn send(&self, message: &PushMessage) -> Result<(), PushError> {
+        let provider_token = self.provider_token().await?;
+        let mut request = self
+            .client
+            .post(format!("{}/3/device/{}", self.config.api_url, message.token))
+            .header("authorization", format!("bearer {}", provider_token))
+            .header("apns-topic", &self.config.topic)
+            .header("apns-push-type", "alert")
+            .header("apns-priority", "10")
+            .json(&Self::payload(message));
+        if let Some(collapse_key) = &message.collapse_key {
+            request = request.header("apns-collapse-id", collapse_key);
+        }
+
+        let response = request.send().await.map_err(transport_error)?;
+        let status = response.status();
+        let body: Value = response.json().await.unwrap_or(Value::Null);
+        if status == StatusCode::FORBIDDEN && body["reason"] == "ExpiredProviderToken" {
+            *self.provider_token.lock().await = None;
+            return Err(PushError::Retryable("APNs provider token expired".to_string()));
+        }
+        classify_apns_response(status, &body)
+    }
+}
+
+/// Canal push con los proveedores configurados en el entorno; `None` si no hay ninguno
+pub fn push_sender_from_env(devices: Arc<dyn DeviceTokenRepository>) -> Option<Arc<PushNotificationSender>> {
+    let mut sender = PushNotificationSender::new(devices);
+    if let Some(config) = FcmConfig::from_env() {
+        match FcmPushSender::new(config) {
+            Ok(fcm) => sender = sender.with_provider(Arc::new(fcm)),
+            Err(e) => tracing::error!("FCM push disabled, invalid credentials: {}", e),
+        }
+    }
+    if let Some(config) = ApnsConfig::from_env() {
+        match ApnsPushSender::new(config) {
+            Ok(apns) => sender = sender.with_provider(Arc::new(apns)),
+            Err(e) => tracing::error!("APNs push disabled, invalid credentials: {}", e),
+        }
+    }
+    sender.has_providers().then(|| Arc::new(sender))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn fcm_unregistered_and_malformed_tokens_are_invalid() {
+        let unregistered = json!({"error": {"status": "NOT_FOUND", "details": [{"errorCode": "UNREGISTERED"}]}});
+        assert_eq!(classify_fcm_response(StatusCode::NOT_FOUND, &unregistered), Err(PushError::InvalidToken));
+
+        let malformed = json!({"error": {"status": "INVALID_ARGUMENT", "message": "The registration token is not a valid FCM registration token"}});
+        assert_eq!(classify_fcm_response(StatusCode::BAD_REQUEST, &malformed), Err(PushError::InvalidToken));
+
+        // Un payload incorrecto no es culpa del token
+        let bad_payload = json!({"error": {"status": "INVALID_ARGUMENT", "message": "Invalid value at 'message.data'"}});
+        assert!(matches!(classify_fcm_response(StatusCode::BAD_REQUEST, &bad_payload), Err(PushError::Rejected(_))));
+    }
+
+    #[test]
+    fn provider_outages_are_retryable() {
+        assert!(matches!(classify_fcm_response(StatusCode::SERVICE_UNAVAILABLE, &Value::Null), Err(PushError::Retryable(_))));
+        assert!(matches!(classify_fcm_response(StatusCode::TOO_MANY_REQUESTS, &Value::Null), Err(PushError::Retryable(_))));
+        assert!(matches!(classify_apns_response(StatusCode::INTERNAL_SERVER_ERROR, &Value::Null), Err(PushError::Retryable(_))));
+        assert_eq!(classify_apns_response(StatusCode::OK, &Value::Null), Ok(()));
+    }
+
+    #[test]
+    fn apns_gone_and_bad_tokens_are_invalid() {
+        assert_eq!(classify_apns_response(StatusCode::GONE, &json!({"reason": "Unregistered"})), Err(PushError::InvalidToken));
+        assert_eq!(classify_apns_response(StatusCode::BAD_REQUEST, &json!({"reason": "BadDeviceToken"})), Err(PushError::InvalidToken));
+        assert!(matches!(
+            classify_apns_response(StatusCode::BAD_REQUEST, &json!({"reason": "PayloadTooLarge"})),
+            Err(PushError::Rejected(_))
+        ));
+    }
+}
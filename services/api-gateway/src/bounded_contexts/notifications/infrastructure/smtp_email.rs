@@ -0,0 +1,167 @@
+//! SMTP Email
+//!
+//! Transporte SMTP (lettre) del canal email. La configuración viene del
+//! entorno; sin `SMTP_HOST` el canal email queda deshabilitado.
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use lettre::message::header::{HeaderName, HeaderValue};
+use lettre::message::{Mailbox, MultiPart};
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use sqlx::PgPool;
+
+use crate::bounded_contexts::notifications::application::email::{
+    EmailMessage, EmailNotificationSender, EmailSender,
+};
+use crate::shared::infrastructure::auth::SignedLinkService;
+use super::postgres_repository::PostgresEmailRecipientDirectory;
+
+const DEFAULT_PUBLIC_URL: &str = "http://localhost:3000/api/v1/notifications";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmtpTls {
+    /// STARTTLS obligatorio (puerto 587)
+    StartTls,
+    /// TLS implícito (puerto 465)
+    Tls,
+    /// Sin cifrar, solo para servidores locales de pruebas
+    None,
+}
+
+#[derive(Debug, Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    /// Remitente, p. ej. `VibeStream <no-reply@vibestream.io>`
+    pub from: String,
+    pub tls: SmtpTls,
+}
+
+impl SmtpConfig {
+    /// `None` si falta `SMTP_HOST` o `EMAIL_FROM` (email deshabilitado)
+    pub fn from_env() -> Option<Self> {
+        let host = std::env::var("SMTP_HOST").ok()?;
+        let from = std::env::var("EMAIL_FROM").ok()?;
+        let port = std::env::var("SMTP_PORT").ok().and_then(|port| port.parse().ok()).unwrap_or(587);
+        let tls = match std::env::var("SMTP_TLS").as_deref() {
+            Ok("tls") => SmtpTls::Tls,
+            Ok("none") => SmtpTls::None,
+            _ => SmtpTls::StartTls,
+        };
+        Some(Self {
+            host,
+            port,
+            username: std::env::var("SMTP_USERNAME").ok(),
+            password: std::env::var("SMTP_PASSWORD").ok(),
+            from,
+            tls,
+        })
+    }
+}
+
+pub struct SmtpEmailSender {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpEmailSender {
+    pub fn new(config: SmtpConfig) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let mut builder = match config.tls {
+            SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)?,
+            SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)?,
+            SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host),
+        }
+        .port(config.port);
+        if let (Some(username), Some(password)) = (config.username, config.password) {
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from: config.from.parse()?,
+        })
+    }
+}
+
+/// Email multiparte (texto + HTML) con las cabeceras de baja en un clic (RFC 8058)
+fn build_message(from: &Mailbox, email: &EmailMessage) -> Result<Message, Box<dyn std::error::Error + Send + Sync>> {
+    let to = Mailbox::new(Some(email.to_name.clone()), email.to_email.parse()?);
+    let message = Message::builder()
+        .from(from.clone())
+        .to(to)
+        .subject(email.subject.clone())
+        .raw_header(HeaderValue::new(
+            HeaderName::new_from_ascii_str("List-Unsubscribe"),
+            format!("<{}>", email.unsubscribe_url),
+        ))
+        .raw_header(HeaderValue::new(
+            HeaderName::new_from_ascii_str("List-Unsubscribe-Post"),
+            "List-Unsubscribe=One-Click".to_string(),
+        ))
+        .multipart(MultiPart::alternative_plain_html(email.text.clone(), email.html.clone()))?;
+    Ok(message)
+}
+
+#[async_trait]
+impl EmailSender for SmtpEmailSender {
+    async fn send(&self, email: &EmailMessage) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let message = build_message(&self.from, email)?;
+        self.transport.send(message).await?;
+        Ok(())
+    }
+}
+
+/// Canal email con SMTP y el directorio de usuarios de Postgres; `None` si no
+/// hay SMTP configurado. Los enlaces de baja apuntan a `NOTIFICATIONS_PUBLIC_URL`.
+pub fn email_sender_from_env(pool: PgPool) -> Option<Arc<EmailNotificationSender>> {
+    let config = SmtpConfig::from_env()?;
+    let smtp = match SmtpEmailSender::new(config) {
+        Ok(smtp) => smtp,
+        Err(e) => {
+            tracing::error!("Email notifications disabled, invalid SMTP configuration: {}", e);
+            return None;
+        }
+    };
+    let links = match SignedLinkService::from_env() {
+        Ok(links) => links,
+        Err(e) => {
+            tracing::error!("Email notifications disabled, cannot sign unsubscribe links: {}", e);
+            return None;
+        }
+    };
+    let public_url = std::env::var("NOTIFICATIONS_PUBLIC_URL").unwrap_or_else(|_| DEFAULT_PUBLIC_URL.to_string());
+
+    Some(Arc::new(EmailNotificationSender::new(
+        Arc::new(smtp),
+        Arc::new(PostgresEmailRecipientDirectory::new(pool)),
+        Arc::new(links),
+        public_url,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_carry_one_click_unsubscribe_headers() {
+        let from: Mailbox = "VibeStream <no-reply@vibestream.test>".parse().unwrap();
+        let email = EmailMessage {
+            to_email: "fan@example.com".to_string(),
+            to_name: "fan".to_string(),
+            subject: "Tu resumen semanal de VibeStream".to_string(),
+            html: "<p>Hola</p>".to_string(),
+            text: "Hola".to_string(),
+            unsubscribe_url: "https://api.vibestream.test/unsubscribe/u/all?expires=1&signature=ab".to_string(),
+        };
+
+        let raw = String::from_utf8(build_message(&from, &email).unwrap().formatted()).unwrap();
+
+        assert!(raw.contains("List-Unsubscribe: <https://api.vibestream.test/unsubscribe/u/all?expires=1&signature=ab>"));
+        assert!(raw.contains("List-Unsubscribe-Post: List-Unsubscribe=One-Click"));
+        assert!(raw.contains("multipart/alternative"));
+    }
+}
@@ -0,0 +1,58 @@
+//! Song Review Listener
+//!
+//! Escucha `SongFlaggedForReview` y avisa al artista de que su canción no se
+//! publicará hasta que moderación revise los metadatos.
+
+use std::sync::Arc;
+use async_trait::async_trait;
+use tracing::error;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+use crate::bounded_contexts::notifications::domain::entities::{
+    Notification, NotificationPriority, NotificationType,
+};
+use crate::bounded_contexts::notifications::application::NotificationDispatcher;
+
+pub struct SongReviewNotificationListener {
+    dispatcher: Arc<NotificationDispatcher>,
+}
+
+impl SongReviewNotificationListener {
+    pub fn new(dispatcher: Arc<NotificationDispatcher>) -> Self {
+        Self { dispatcher }
+    }
+}
+
+#[async_trait]
+impl EventHandler for SongReviewNotificationListener {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let DomainEvent::SongFlaggedForReview { song_id, artist_id, uploaded_by, title, categories, confidence, occurred_at } = event else {
+            return Ok(());
+        };
+
+        let notification = Notification::new(
+            *uploaded_by,
+            "Canción pendiente de revisión".to_string(),
+            format!(
+                "Los metadatos de \"{}\" se han marcado para revisión ({}). La canción no será pública hasta que el equipo de moderación la revise.",
+                title,
+                categories.join(", ")
+            ),
+            NotificationType::SongUnderReview,
+            NotificationPriority::High,
+            Some(serde_json::json!({
+                "song_id": song_id,
+                "artist_id": artist_id,
+                "categories": categories,
+                "confidence": confidence,
+                "occurred_at": occurred_at,
+            })),
+        );
+        if let Err(e) = self.dispatcher.dispatch(&notification).await {
+            error!("Failed to notify artist {} of song {} under review: {}", artist_id, song_id, e);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+//! Notification User Deletion Listener
+//!
+//! Borra las notificaciones, preferencias de aviso, dispositivos push y
+//! resúmenes pendientes de una cuenta eliminada.
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+
+pub struct NotificationUserDeletionListener {
+    pool: PgPool,
+}
+
+impl NotificationUserDeletionListener {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_error(e: sqlx::Error) -> AppError {
+    AppError::DatabaseError(format!("Failed to delete notifications: {}", e))
+}
+
+#[async_trait]
+impl EventHandler for NotificationUserDeletionListener {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let DomainEvent::UserDeletionRequested { user_id, .. } = event else {
+            return Ok(());
+        };
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        sqlx::query("DELETE FROM notifications WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        sqlx::query("DELETE FROM notification_preferences WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        sqlx::query("DELETE FROM notification_device_tokens WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        sqlx::query("DELETE FROM notification_digest_entries WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        tx.commit().await.map_err(db_error)?;
+
+        Ok(())
+    }
+}
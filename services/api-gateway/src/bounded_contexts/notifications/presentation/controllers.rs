@@ -1,13 +1,25 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
     response::Json as ResponseJson,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
+use crate::bounded_contexts::notifications::domain::entities::{
+    ChannelPreferences, DevicePlatform, DeviceToken, DigestFrequency, Notification, NotificationCategory,
+    NotificationFilters, NotificationPreferences, NotificationStatus,
+};
+use crate::bounded_contexts::notifications::application::{EmailUnsubscribeService, UnsubscribeError};
 use crate::shared::infrastructure::app_state::NotificationAppState;
+use crate::shared::infrastructure::auth::{AuthenticatedUser, LinkSignature, SignedLinkService};
+
+const DEFAULT_PAGE_SIZE: u32 = 20;
+const MAX_PAGE_SIZE: u32 = 100;
+/// Los tokens de FCM rondan los 160 caracteres y los de APNs son 64 hex
+const MAX_DEVICE_TOKEN_LENGTH: usize = 4096;
 
 // =============================================================================
 // REQUEST/RESPONSE DTOs
@@ -30,12 +42,155 @@ pub struct NotificationResponse {
     pub message: String,
     pub notification_type: String,
     pub priority: String,
+    pub payload: Option<serde_json::Value>,
     pub is_read: bool,
     pub is_archived: bool,
+    pub read_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl From<Notification> for NotificationResponse {
+    fn from(notification: Notification) -> Self {
+        Self {
+            notification_id: notification.id,
+            user_id: notification.user_id,
+            notification_type: notification.notification_type.as_str().to_string(),
+            priority: notification.priority.as_str().to_string(),
+            title: notification.title,
+            message: notification.message,
+            payload: notification.metadata,
+            is_read: notification.read_at.is_some(),
+            is_archived: notification.status == NotificationStatus::Archived,
+            read_at: notification.read_at,
+            created_at: notification.created_at,
+            updated_at: notification.updated_at,
+        }
+    }
+}
+
+/// `page` empieza en 1
+#[derive(Debug, Deserialize)]
+pub struct ListNotificationsQuery {
+    #[serde(default)]
+    pub unread_only: bool,
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationPageResponse {
+    pub notifications: Vec<NotificationResponse>,
+    pub page: u32,
+    pub page_size: u32,
+    pub total: u32,
+    pub unread_count: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MarkAllReadResponse {
+    pub user_id: Uuid,
+    pub unread_count: u32,
+}
+
+/// Preferencias efectivas: todas las categorías, con las ausentes en in-app
+#[derive(Debug, Serialize)]
+pub struct NotificationPreferencesResponse {
+    pub user_id: Uuid,
+    pub email_enabled: bool,
+    pub push_enabled: bool,
+    pub categories: BTreeMap<NotificationCategory, ChannelPreferences>,
+    pub digest_categories: BTreeSet<NotificationCategory>,
+    pub digest_frequency: DigestFrequency,
+}
+
+impl From<&NotificationPreferences> for NotificationPreferencesResponse {
+    fn from(preferences: &NotificationPreferences) -> Self {
+        Self {
+            user_id: preferences.user_id,
+            email_enabled: preferences.email_enabled,
+            push_enabled: preferences.push_enabled,
+            categories: NotificationCategory::ALL
+                .into_iter()
+                .map(|category| {
+                    let channels = preferences.categories.get(&category).copied().unwrap_or(ChannelPreferences::IN_APP_ONLY);
+                    (category, channels)
+                })
+                .collect(),
+            digest_categories: preferences.digest_categories.clone(),
+            digest_frequency: preferences.digest_frequency,
+        }
+    }
+}
+
+/// Las categorías enviadas sustituyen a las guardadas; el resto no cambia
+#[derive(Debug, Deserialize)]
+pub struct UpdateNotificationPreferencesRequest {
+    pub email_enabled: Option<bool>,
+    pub push_enabled: Option<bool>,
+    #[serde(default)]
+    pub categories: HashMap<NotificationCategory, ChannelPreferences>,
+    /// Sustituye al conjunto completo de categorías en resumen
+    pub digest_categories: Option<BTreeSet<NotificationCategory>>,
+    pub digest_frequency: Option<DigestFrequency>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterDeviceRequest {
+    pub token: String,
+    pub platform: DevicePlatform,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeviceResponse {
+    pub device_id: Uuid,
+    pub platform: DevicePlatform,
+    pub registered_at: DateTime<Utc>,
+}
+
+impl From<DeviceToken> for DeviceResponse {
+    fn from(device: DeviceToken) -> Self {
+        Self {
+            device_id: device.id,
+            platform: device.platform,
+            registered_at: device.created_at,
+        }
+    }
+}
+
+type HandlerError = (StatusCode, ResponseJson<serde_json::Value>);
+
+fn error_response(status: StatusCode, message: &str) -> HandlerError {
+    (status, ResponseJson(serde_json::json!({ "error": message })))
+}
+
+fn internal_error(context: &str, e: Box<dyn std::error::Error + Send + Sync>) -> HandlerError {
+    tracing::error!("{}: {}", context, e);
+    error_response(StatusCode::INTERNAL_SERVER_ERROR, context)
+}
+
+/// Notificación del usuario; las de otros usuarios se tratan como inexistentes
+async fn load_own_notification(
+    state: &NotificationAppState,
+    user: &AuthenticatedUser,
+    notification_id: Uuid,
+) -> Result<Notification, HandlerError> {
+    state.notification_repository
+        .get_by_id(notification_id)
+        .await
+        .map_err(|e| internal_error("Failed to load notification", e))?
+        .filter(|notification| notification.user_id == user.user_id)
+        .ok_or_else(|| error_response(StatusCode::NOT_FOUND, "Notification not found"))
+}
+
+async fn load_preferences(state: &NotificationAppState, user_id: Uuid) -> Result<NotificationPreferences, HandlerError> {
+    Ok(state.preferences_repository
+        .get_by_user_id(user_id)
+        .await
+        .map_err(|e| internal_error("Failed to load notification preferences", e))?
+        .unwrap_or_else(|| NotificationPreferences::new(user_id)))
+}
+
 // =============================================================================
 // NOTIFICATION CONTROLLER
 // =============================================================================
@@ -56,8 +211,10 @@ impl NotificationController {
             message: "This is a demo notification".to_string(),
             notification_type: "info".to_string(),
             priority: "normal".to_string(),
+            payload: None,
             is_read: false,
             is_archived: false,
+            read_at: None,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -65,71 +222,66 @@ impl NotificationController {
         Ok(ResponseJson(response))
     }
     
-    /// GET /api/v1/notifications/user/:user_id - Get user notifications
-    pub async fn get_user_notifications(
-        State(_state): State<NotificationAppState>,
-        Path(user_id): Path<Uuid>,
-    ) -> Result<ResponseJson<serde_json::Value>, (StatusCode, ResponseJson<serde_json::Value>)> {
-        // TODO: Implement actual user notifications logic
-        let notifications = vec![
-            serde_json::json!({
-                "notification_id": Uuid::new_v4(),
-                "title": "Demo Notification 1",
-                "message": "This is a demo notification",
-                "notification_type": "info",
-                "is_read": false,
-                "created_at": Utc::now()
-            }),
-            serde_json::json!({
-                "notification_id": Uuid::new_v4(),
-                "title": "Demo Notification 2",
-                "message": "This is another demo notification",
-                "notification_type": "warning",
-                "is_read": true,
-                "created_at": Utc::now()
-            })
-        ];
-        
-        Ok(ResponseJson(serde_json::json!({
-            "user_id": user_id,
-            "notifications": notifications,
-            "total": notifications.len(),
-            "unread_count": 1
-        })))
+    /// GET /api/v1/notifications?unread_only=&page= - Notifications of the authenticated user
+    pub async fn list_notifications(
+        State(state): State<NotificationAppState>,
+        user: AuthenticatedUser,
+        Query(query): Query<ListNotificationsQuery>,
+    ) -> Result<ResponseJson<NotificationPageResponse>, HandlerError> {
+        let page = query.page.unwrap_or(1).max(1);
+        let page_size = query.page_size.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+        let filters = NotificationFilters {
+            user_id: Some(user.user_id),
+            notification_type: None,
+            status: None,
+            read: query.unread_only.then_some(false),
+            limit: None,
+            offset: None,
+        };
+
+        let notifications = state.notification_repository
+            .search(&filters, page - 1, page_size)
+            .await
+            .map_err(|e| internal_error("Failed to list notifications", e))?;
+        let (total, unread_count, _, _) = state.notification_repository
+            .get_summary(user.user_id)
+            .await
+            .map_err(|e| internal_error("Failed to count notifications", e))?;
+
+        Ok(ResponseJson(NotificationPageResponse {
+            notifications: notifications.into_iter().map(NotificationResponse::from).collect(),
+            page,
+            page_size,
+            total: if query.unread_only { unread_count } else { total },
+            unread_count,
+        }))
     }
     
     /// GET /api/v1/notifications/:id - Get notification by ID
     pub async fn get_notification(
-        State(_state): State<NotificationAppState>,
+        State(state): State<NotificationAppState>,
+        user: AuthenticatedUser,
         Path(notification_id): Path<Uuid>,
-    ) -> Result<ResponseJson<NotificationResponse>, (StatusCode, ResponseJson<serde_json::Value>)> {
-        // TODO: Implement actual notification retrieval logic
-        let response = NotificationResponse {
-            notification_id,
-            user_id: Uuid::new_v4(),
-            title: "Demo Notification".to_string(),
-            message: "This is a demo notification".to_string(),
-            notification_type: "info".to_string(),
-            priority: "normal".to_string(),
-            is_read: false,
-            is_archived: false,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
-        };
-        
-        Ok(ResponseJson(response))
+    ) -> Result<ResponseJson<NotificationResponse>, HandlerError> {
+        let notification = load_own_notification(&state, &user, notification_id).await?;
+        Ok(ResponseJson(NotificationResponse::from(notification)))
     }
     
-    /// PUT /api/v1/notifications/:id/read - Mark notification as read
+    /// PUT /api/v1/notifications/:id/read - Mark notification as read.
+    /// Marcar una ya leída no cambia su `read_at`.
     pub async fn mark_as_read(
-        State(_state): State<NotificationAppState>,
+        State(state): State<NotificationAppState>,
+        user: AuthenticatedUser,
         Path(notification_id): Path<Uuid>,
-    ) -> Result<ResponseJson<serde_json::Value>, (StatusCode, ResponseJson<serde_json::Value>)> {
-        // TODO: Implement actual mark as read logic
-        Ok(ResponseJson(serde_json::json!({
-            "message": "Notification marked as read",
-            "notification_id": notification_id
-        })))
+    ) -> Result<ResponseJson<NotificationResponse>, HandlerError> {
+        load_own_notification(&state, &user, notification_id).await?;
+        state.notification_repository
+            .mark_as_read(notification_id)
+            .await
+            .map_err(|e| internal_error("Failed to mark notification as read", e))?;
+
+        let notification = load_own_notification(&state, &user, notification_id).await?;
+        Ok(ResponseJson(NotificationResponse::from(notification)))
     }
     
     /// PUT /api/v1/notifications/:id/archive - Mark notification as archived
@@ -156,51 +308,102 @@ impl NotificationController {
         })))
     }
     
-    /// PUT /api/v1/notifications/user/:user_id/read-all - Mark all notifications as read
+    /// PUT /api/v1/notifications/read-all - Mark every notification of the authenticated user as read
     pub async fn mark_all_as_read(
-        State(_state): State<NotificationAppState>,
-        Path(user_id): Path<Uuid>,
-    ) -> Result<ResponseJson<serde_json::Value>, (StatusCode, ResponseJson<serde_json::Value>)> {
-        // TODO: Implement actual mark all as read logic
-        Ok(ResponseJson(serde_json::json!({
-            "message": "All notifications marked as read",
-            "user_id": user_id
-        })))
+        State(state): State<NotificationAppState>,
+        user: AuthenticatedUser,
+    ) -> Result<ResponseJson<MarkAllReadResponse>, HandlerError> {
+        state.notification_repository
+            .mark_all_as_read(user.user_id)
+            .await
+            .map_err(|e| internal_error("Failed to mark notifications as read", e))?;
+        let unread_count = state.notification_repository
+            .get_unread_count(user.user_id)
+            .await
+            .map_err(|e| internal_error("Failed to count notifications", e))?;
+
+        Ok(ResponseJson(MarkAllReadResponse { user_id: user.user_id, unread_count }))
     }
     
-    /// GET /api/v1/notifications/user/:user_id/preferences - Get user preferences
+    /// POST /api/v1/notifications/devices - Register a push token of the authenticated user.
+    /// Registrar de nuevo el mismo token no lo duplica: lo reasigna a este usuario.
+    pub async fn register_device(
+        State(state): State<NotificationAppState>,
+        user: AuthenticatedUser,
+        axum::extract::Json(request): axum::extract::Json<RegisterDeviceRequest>,
+    ) -> Result<(StatusCode, ResponseJson<DeviceResponse>), HandlerError> {
+        let token = request.token.trim();
+        if token.is_empty() || token.len() > MAX_DEVICE_TOKEN_LENGTH {
+            return Err(error_response(StatusCode::BAD_REQUEST, "Invalid device token"));
+        }
+
+        let device = state.device_repository
+            .register(&DeviceToken::new(user.user_id, request.platform, token.to_string()))
+            .await
+            .map_err(|e| internal_error("Failed to register device", e))?;
+
+        Ok((StatusCode::CREATED, ResponseJson(DeviceResponse::from(device))))
+    }
+    
+    /// GET /api/v1/notifications/preferences - Preferences of the authenticated user
     pub async fn get_preferences(
-        State(_state): State<NotificationAppState>,
-        Path(user_id): Path<Uuid>,
-    ) -> Result<ResponseJson<serde_json::Value>, (StatusCode, ResponseJson<serde_json::Value>)> {
-        // TODO: Implement actual preferences logic
-        let preferences = serde_json::json!({
-            "user_id": user_id,
-            "email_notifications": true,
-            "push_notifications": true,
-            "sms_notifications": false,
-            "notification_types": {
-                "campaign_updates": true,
-                "new_songs": true,
-                "investment_opportunities": true,
-                "system_announcements": false
-            }
-        });
-        
-        Ok(ResponseJson(preferences))
+        State(state): State<NotificationAppState>,
+        user: AuthenticatedUser,
+    ) -> Result<ResponseJson<NotificationPreferencesResponse>, HandlerError> {
+        let preferences = load_preferences(&state, user.user_id).await?;
+        Ok(ResponseJson(NotificationPreferencesResponse::from(&preferences)))
     }
     
-    /// PUT /api/v1/notifications/user/:user_id/preferences - Update user preferences
+    /// PUT /api/v1/notifications/preferences - Update preferences of the authenticated user
     pub async fn update_preferences(
-        State(_state): State<NotificationAppState>,
-        Path(user_id): Path<Uuid>,
-        axum::extract::Json(_request): axum::extract::Json<serde_json::Value>,
-    ) -> Result<ResponseJson<serde_json::Value>, (StatusCode, ResponseJson<serde_json::Value>)> {
-        // TODO: Implement actual preferences update logic
-        Ok(ResponseJson(serde_json::json!({
-            "message": "Preferences updated successfully",
-            "user_id": user_id
-        })))
+        State(state): State<NotificationAppState>,
+        user: AuthenticatedUser,
+        axum::extract::Json(request): axum::extract::Json<UpdateNotificationPreferencesRequest>,
+    ) -> Result<ResponseJson<NotificationPreferencesResponse>, HandlerError> {
+        let mut preferences = load_preferences(&state, user.user_id).await?;
+        if let Some(email_enabled) = request.email_enabled {
+            preferences.email_enabled = email_enabled;
+        }
+        if let Some(push_enabled) = request.push_enabled {
+            preferences.push_enabled = push_enabled;
+        }
+        preferences.categories.extend(request.categories);
+        if let Some(digest_categories) = request.digest_categories {
+            preferences.digest_categories = digest_categories;
+        }
+        if let Some(digest_frequency) = request.digest_frequency {
+            preferences.digest_frequency = digest_frequency;
+        }
+        preferences.updated_at = Utc::now();
+
+        state.preferences_repository
+            .update(&preferences)
+            .await
+            .map_err(|e| internal_error("Failed to update notification preferences", e))?;
+
+        Ok(ResponseJson(NotificationPreferencesResponse::from(&preferences)))
+    }
+    
+    /// GET|POST /api/v1/notifications/unsubscribe/:user_id/:scope - Unsubscribe link of the emails.
+    /// Sin sesión: la firma del enlace autoriza la baja de `scope` (una categoría o `all`).
+    /// El POST es la baja en un clic de los clientes de correo (RFC 8058).
+    pub async fn unsubscribe(
+        State(state): State<NotificationAppState>,
+        Path((user_id, scope)): Path<(Uuid, String)>,
+        Query(link): Query<LinkSignature>,
+    ) -> Result<ResponseJson<NotificationPreferencesResponse>, HandlerError> {
+        let links = SignedLinkService::from_env().map_err(|e| {
+            tracing::error!("Cannot verify unsubscribe links: {}", e);
+            error_response(StatusCode::INTERNAL_SERVER_ERROR, "Cannot verify unsubscribe link")
+        })?;
+        let service = EmailUnsubscribeService::new(links, state.preferences_repository.clone());
+
+        match service.unsubscribe(user_id, &scope, &link, Utc::now()).await {
+            Ok(preferences) => Ok(ResponseJson(NotificationPreferencesResponse::from(&preferences))),
+            Err(UnsubscribeError::InvalidLink) => Err(error_response(StatusCode::FORBIDDEN, "Invalid or expired unsubscribe link")),
+            Err(UnsubscribeError::UnknownCategory(_)) => Err(error_response(StatusCode::NOT_FOUND, "Unknown notification category")),
+            Err(UnsubscribeError::Repository(e)) => Err(internal_error("Failed to update notification preferences", e)),
+        }
     }
     
     /// GET /api/v1/notifications/user/:user_id/summary - Get notification summary
@@ -0,0 +1,183 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{
+        ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Query, State,
+    },
+    http::StatusCode,
+    response::{Json as ResponseJson, Response},
+    routing::get,
+    Router,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::bounded_contexts::notifications::application::notification_hub::{LiveMessage, NotificationHub};
+use crate::bounded_contexts::notifications::domain::repositories::NotificationRepository;
+use crate::shared::infrastructure::auth::JwtService;
+
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+/// Sin tráfico (ni pongs) durante este tiempo se da la conexión por muerta
+const IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+/// Plazo para el mensaje `authenticate` cuando el token no viene en la URL
+const AUTH_TIMEOUT: Duration = Duration::from_secs(10);
+/// 1013 "Try Again Later": el cliente no consumía los mensajes a tiempo
+const SLOW_CONSUMER_CLOSE_CODE: u16 = 1013;
+const GOING_AWAY_CLOSE_CODE: u16 = 1001;
+const POLICY_VIOLATION_CLOSE_CODE: u16 = 1008;
+
+/// Estado propio del canal en tiempo real
+#[derive(Clone)]
+pub struct LiveNotificationState {
+    pub hub: Arc<NotificationHub>,
+    pub notifications: Arc<dyn NotificationRepository>,
+    pub jwt_service: Arc<JwtService>,
+}
+
+/// Rutas del canal en tiempo real (`/ws/notifications`)
+pub fn routes(state: LiveNotificationState) -> Router {
+    Router::new()
+        .route("/ws/notifications", get(notifications_ws))
+        .with_state(state)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LiveAuthQuery {
+    pub token: Option<String>,
+}
+
+/// Mensajes del cliente
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+enum LiveCommand {
+    Authenticate { token: String },
+}
+
+/// GET /api/v1/notifications/ws/notifications - New notifications over WebSocket
+///
+/// The access token goes in `?token=` or in a first message
+/// `{"action":"authenticate","token":"..."}`. The server then sends
+/// `{"type":"unread_count",...}` followed by `{"type":"notification",...}` for
+/// every notification stored for the user.
+pub async fn notifications_ws(
+    ws: WebSocketUpgrade,
+    State(state): State<LiveNotificationState>,
+    Query(query): Query<LiveAuthQuery>,
+) -> Result<Response, (StatusCode, ResponseJson<serde_json::Value>)> {
+    let user_id = match query.token {
+        Some(token) => Some(authenticate(&state.jwt_service, &token).ok_or_else(|| {
+            (
+                StatusCode::UNAUTHORIZED,
+                ResponseJson(serde_json::json!({"error": "Invalid or expired token"})),
+            )
+        })?),
+        None => None,
+    };
+
+    Ok(ws.on_upgrade(move |socket| run_connection(socket, state, user_id)))
+}
+
+fn authenticate(jwt_service: &JwtService, token: &str) -> Option<Uuid> {
+    let claims = jwt_service.validate_access_token(token).ok()?;
+    Uuid::parse_str(&claims.sub).ok()
+}
+
+async fn send(socket: &mut WebSocket, message: &LiveMessage) -> bool {
+    match serde_json::to_string(message) {
+        Ok(text) => socket.send(Message::Text(text)).await.is_ok(),
+        Err(e) => {
+            tracing::error!("Failed to serialize live notification message: {}", e);
+            true
+        }
+    }
+}
+
+async fn close(socket: &mut WebSocket, code: u16, reason: &'static str) {
+    let _ = socket
+        .send(Message::Close(Some(CloseFrame { code, reason: reason.into() })))
+        .await;
+}
+
+/// Esperar el mensaje `authenticate`; `None` si no llega a tiempo o no es válido
+async fn wait_for_authentication(socket: &mut WebSocket, jwt_service: &JwtService) -> Option<Uuid> {
+    let deadline = tokio::time::Instant::now() + AUTH_TIMEOUT;
+    loop {
+        let message = match tokio::time::timeout_at(deadline, socket.recv()).await {
+            Ok(Some(Ok(message))) => message,
+            _ => return None,
+        };
+        match message {
+            Message::Text(text) => {
+                return match serde_json::from_str::<LiveCommand>(&text) {
+                    Ok(LiveCommand::Authenticate { token }) => authenticate(jwt_service, &token),
+                    Err(_) => None,
+                };
+            }
+            Message::Close(_) => return None,
+            _ => {}
+        }
+    }
+}
+
+async fn run_connection(mut socket: WebSocket, state: LiveNotificationState, user_id: Option<Uuid>) {
+    let user_id = match user_id {
+        Some(user_id) => user_id,
+        None => match wait_for_authentication(&mut socket, &state.jwt_service).await {
+            Some(user_id) => user_id,
+            None => {
+                let error = LiveMessage::Error { message: "Authentication required".to_string() };
+                send(&mut socket, &error).await;
+                close(&mut socket, POLICY_VIOLATION_CLOSE_CODE, "authentication required").await;
+                return;
+            }
+        },
+    };
+
+    // Registrar antes de contar: lo que llegue entre medias también se empuja
+    let mut connection = state.hub.register(user_id).await;
+    match state.notifications.get_unread_count(user_id).await {
+        Ok(count) => {
+            if !send(&mut socket, &LiveMessage::UnreadCount { count }).await {
+                state.hub.unregister(user_id, connection.id).await;
+                return;
+            }
+        }
+        Err(e) => tracing::error!("Failed to load unread count for user {}: {}", user_id, e),
+    }
+
+    let mut ping = tokio::time::interval_at(tokio::time::Instant::now() + PING_INTERVAL, PING_INTERVAL);
+    let mut last_seen = Instant::now();
+
+    loop {
+        tokio::select! {
+            incoming = socket.recv() => {
+                match incoming {
+                    Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                    Some(Ok(_)) => last_seen = Instant::now(),
+                }
+            }
+            Some(message) = connection.messages.recv() => {
+                if !send(&mut socket, &message).await {
+                    break;
+                }
+            }
+            _ = &mut connection.closed => {
+                close(&mut socket, SLOW_CONSUMER_CLOSE_CODE, "slow consumer").await;
+                break;
+            }
+            _ = ping.tick() => {
+                if last_seen.elapsed() > IDLE_TIMEOUT {
+                    close(&mut socket, GOING_AWAY_CLOSE_CODE, "idle timeout").await;
+                    break;
+                }
+                if socket.send(Message::Ping(Vec::new())).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+
+    state.hub.unregister(user_id, connection.id).await;
+}
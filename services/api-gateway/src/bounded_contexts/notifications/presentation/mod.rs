@@ -1,3 +1,4 @@
 pub mod controllers;
+pub mod live_ws;
 
 pub use controllers::*; 
\ No newline at end of file
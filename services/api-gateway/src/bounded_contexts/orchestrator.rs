@@ -10,7 +10,11 @@ use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::shared::domain::errors::AppError;
+use crate::shared::domain::errors::{AppError, FraudReasonCode};
+
+/// Cuenta compartida por todos los usuarios eliminados: los registros que solo
+/// se conservan para estadísticas se reasignan a ella
+pub const DELETED_USER_ID: Uuid = Uuid::nil();
 
 // =============================================================================
 // DOMAIN EVENTS
@@ -31,6 +35,21 @@ pub enum DomainEvent {
     },
     UserProfileUpdated {
         user_id: Uuid,
+        #[serde(default)]
+        username: String,
+        /// "username", "display_name", "bio", "avatar_url" y/o "wallet_address"
+        #[serde(default)]
+        changed_fields: Vec<String>,
+        /// Nombre anterior cuando cambió el username
+        #[serde(default)]
+        previous_username: Option<String>,
+        occurred_at: DateTime<Utc>,
+    },
+    /// Cuenta eliminada: cada contexto borra o anonimiza sus datos del usuario
+    UserDeletionRequested {
+        user_id: Uuid,
+        /// Sustituye al usuario en los registros financieros que se conservan
+        pseudonym_id: Uuid,
         occurred_at: DateTime<Utc>,
     },
 
@@ -53,6 +72,40 @@ pub enum DomainEvent {
         platform: String,
         occurred_at: DateTime<Utc>,
     },
+    SongUploaded {
+        song_id: Uuid,
+        artist_id: Uuid,
+        genre: String,
+        occurred_at: DateTime<Utc>,
+    },
+    /// La moderación marcó los metadatos; la canción queda pendiente de revisión
+    SongFlaggedForReview {
+        song_id: Uuid,
+        artist_id: Uuid,
+        /// Usuario del artista que subió la canción
+        uploaded_by: Uuid,
+        title: String,
+        categories: Vec<String>,
+        confidence: f64,
+        occurred_at: DateTime<Utc>,
+    },
+    RemixLicensePurchased {
+        license_id: Uuid,
+        song_id: Uuid,
+        artist_id: Uuid,
+        licensee_id: Uuid,
+        license_type: String,
+        price: f64,
+        occurred_at: DateTime<Utc>,
+    },
+    AlbumCreated {
+        album_id: Uuid,
+        artist_id: Uuid,
+        title: String,
+        /// Canciones en orden de pista
+        song_ids: Vec<Uuid>,
+        occurred_at: DateTime<Utc>,
+    },
 
     // Campaign Events
     CampaignCreated {
@@ -73,6 +126,25 @@ pub enum DomainEvent {
         amount: f64,
         occurred_at: DateTime<Utc>,
     },
+    /// The campaign reached its end date; counts come from the final analytics
+    CampaignCompleted {
+        campaign_id: Uuid,
+        artist_id: Uuid,
+        participants: u64,
+        rewarded: u64,
+        nfts_claimed: u64,
+        total_spend: f64,
+        occurred_at: DateTime<Utc>,
+    },
+    /// The campaign was paused because its budget cannot cover another reward
+    CampaignBudgetDepleted {
+        campaign_id: Uuid,
+        artist_id: Uuid,
+        budget: f64,
+        spent: f64,
+        reward_per_action: f64,
+        occurred_at: DateTime<Utc>,
+    },
 
     // Listen Reward Events
     ListenSessionStarted {
@@ -108,6 +180,146 @@ pub enum DomainEvent {
         benefit_type: String,
         occurred_at: DateTime<Utc>,
     },
+    /// El venture alcanzó su objetivo de financiación y dejó de aceptar inversiones
+    VentureFunded {
+        venture_id: Uuid,
+        artist_id: Uuid,
+        total_funding: f64,
+        occurred_at: DateTime<Utc>,
+    },
+    SharePriceUpdated {
+        venture_id: Uuid,
+        price: f64,
+        previous_price: f64,
+        occurred_at: DateTime<Utc>,
+    },
+    SharePurchased {
+        venture_id: Uuid,
+        investment_id: Uuid,
+        investor_id: Uuid,
+        amount: f64,
+        funding_before: f64,
+        funding_after: f64,
+        occurred_at: DateTime<Utc>,
+    },
+    ShareTransferred {
+        /// Escrow de la venta; identifica la operación en el mercado secundario
+        escrow_id: Uuid,
+        venture_id: Uuid,
+        investment_id: Uuid,
+        from_user_id: Uuid,
+        to_user_id: Uuid,
+        amount: f64,
+        /// Precio total pagado por el comprador
+        price: f64,
+        occurred_at: DateTime<Utc>,
+    },
+    /// Participaciones del vendedor retenidas mientras el comprador paga
+    SharesEscrowed {
+        escrow_id: Uuid,
+        venture_id: Uuid,
+        seller_id: Uuid,
+        buyer_id: Uuid,
+        quantity: f64,
+        price: f64,
+        expires_at: DateTime<Utc>,
+        occurred_at: DateTime<Utc>,
+    },
+    /// El pago no se cobró: las participaciones vuelven al vendedor
+    EscrowCancelled {
+        escrow_id: Uuid,
+        venture_id: Uuid,
+        seller_id: Uuid,
+        buyer_id: Uuid,
+        quantity: f64,
+        reason: String,
+        occurred_at: DateTime<Utc>,
+    },
+    RevenueDistributed {
+        venture_id: Uuid,
+        distribution_id: Uuid,
+        distributed_by: Option<Uuid>,
+        amount: f64,
+        occurred_at: DateTime<Utc>,
+    },
+    ProposalFinalized {
+        proposal_id: Uuid,
+        venture_id: Uuid,
+        outcome: String,
+        winning_option_id: Option<Uuid>,
+        turnout_percentage: f64,
+        occurred_at: DateTime<Utc>,
+    },
+    InvestmentReservationExpired {
+        investment_id: Uuid,
+        venture_id: Uuid,
+        investor_id: Uuid,
+        shares: f64,
+        occurred_at: DateTime<Utc>,
+    },
+    /// Participaciones reservadas para una compra mientras se cobra el pago;
+    /// inicia la saga de compra
+    SharesReserved {
+        investment_id: Uuid,
+        venture_id: Uuid,
+        fan_id: Uuid,
+        shares: f64,
+        payment_id: Option<Uuid>,
+        expires_at: DateTime<Utc>,
+        occurred_at: DateTime<Utc>,
+    },
+
+    // Payment Events
+    /// Pago de una compra de participaciones liquidado. Distinto del
+    /// `PaymentCompleted` del contexto de pagos, que lo origina.
+    SharePaymentCompleted {
+        investment_id: Uuid,
+        payment_id: Uuid,
+        occurred_at: DateTime<Utc>,
+    },
+    SharePaymentFailed {
+        investment_id: Uuid,
+        payment_id: Uuid,
+        reason: String,
+        occurred_at: DateTime<Utc>,
+    },
+    /// Las royalties del artista terminaron de devolver su adelanto
+    RoyaltyAdvanceRepaid {
+        advance_id: Uuid,
+        artist_id: Uuid,
+        advance_amount: f64,
+        currency: String,
+        occurred_at: DateTime<Utc>,
+    },
+
+    // Fan Loyalty Events
+    /// Un beneficio se canjeó en el recinto (para analítica)
+    FanBenefitRedeemed {
+        redemption_id: Uuid,
+        benefit_id: Uuid,
+        fan_id: Uuid,
+        venue_id: String,
+        staff_id: Uuid,
+        occurred_at: DateTime<Utc>,
+    },
+
+    // Fraud Events (payment, listen_reward → notifications)
+    FraudDetected {
+        context: String,
+        subject_id: Uuid,
+        user_id: Option<Uuid>,
+        reason_code: FraudReasonCode,
+        risk_score: f64,
+        occurred_at: DateTime<Utc>,
+    },
+
+    // Blockchain Events
+    /// Compra de participaciones registrada on-chain
+    SettlementConfirmed {
+        investment_id: Uuid,
+        transaction_hash: String,
+        occurred_at: DateTime<Utc>,
+    },
 }
 
 impl DomainEvent {
@@ -116,17 +328,40 @@ impl DomainEvent {
             DomainEvent::UserRegistered { .. } => "UserRegistered",
             DomainEvent::UserAuthenticated { .. } => "UserAuthenticated",
             DomainEvent::UserProfileUpdated { .. } => "UserProfileUpdated",
+            DomainEvent::UserDeletionRequested { .. } => "UserDeletionRequested",
             DomainEvent::SongListened { .. } => "SongListened",
             DomainEvent::SongLiked { .. } => "SongLiked",
             DomainEvent::SongShared { .. } => "SongShared",
+            DomainEvent::SongUploaded { .. } => "SongUploaded",
+            DomainEvent::SongFlaggedForReview { .. } => "SongFlaggedForReview",
+            DomainEvent::RemixLicensePurchased { .. } => "RemixLicensePurchased",
+            DomainEvent::AlbumCreated { .. } => "AlbumCreated",
             DomainEvent::CampaignCreated { .. } => "CampaignCreated",
             DomainEvent::CampaignActivated { .. } => "CampaignActivated",
             DomainEvent::NFTPurchased { .. } => "NFTPurchased",
+            DomainEvent::CampaignCompleted { .. } => "CampaignCompleted",
+            DomainEvent::CampaignBudgetDepleted { .. } => "CampaignBudgetDepleted",
             DomainEvent::ListenSessionStarted { .. } => "ListenSessionStarted",
             DomainEvent::ListenSessionCompleted { .. } => "ListenSessionCompleted",
             DomainEvent::VentureCreated { .. } => "VentureCreated",
             DomainEvent::InvestmentMade { .. } => "InvestmentMade",
             DomainEvent::BenefitDelivered { .. } => "BenefitDelivered",
+            DomainEvent::VentureFunded { .. } => "VentureFunded",
+            DomainEvent::SharePriceUpdated { .. } => "SharePriceUpdated",
+            DomainEvent::SharePurchased { .. } => "SharePurchased",
+            DomainEvent::ShareTransferred { .. } => "ShareTransferred",
+            DomainEvent::SharesEscrowed { .. } => "SharesEscrowed",
+            DomainEvent::EscrowCancelled { .. } => "EscrowCancelled",
+            DomainEvent::RevenueDistributed { .. } => "RevenueDistributed",
+            DomainEvent::ProposalFinalized { .. } => "ProposalFinalized",
+            DomainEvent::InvestmentReservationExpired { .. } => "InvestmentReservationExpired",
+            DomainEvent::SharesReserved { .. } => "SharesReserved",
+            DomainEvent::SharePaymentCompleted { .. } => "SharePaymentCompleted",
+            DomainEvent::SharePaymentFailed { .. } => "SharePaymentFailed",
+            DomainEvent::SettlementConfirmed { .. } => "SettlementConfirmed",
+            DomainEvent::RoyaltyAdvanceRepaid { .. } => "RoyaltyAdvanceRepaid",
+            DomainEvent::FanBenefitRedeemed { .. } => "FanBenefitRedeemed",
+            DomainEvent::FraudDetected { .. } => "FraudDetected",
         }
     }
 
@@ -135,17 +370,40 @@ impl DomainEvent {
             DomainEvent::UserRegistered { occurred_at, .. } => *occurred_at,
             DomainEvent::UserAuthenticated { occurred_at, .. } => *occurred_at,
             DomainEvent::UserProfileUpdated { occurred_at, .. } => *occurred_at,
+            DomainEvent::UserDeletionRequested { occurred_at, .. } => *occurred_at,
             DomainEvent::SongListened { occurred_at, .. } => *occurred_at,
             DomainEvent::SongLiked { occurred_at, .. } => *occurred_at,
             DomainEvent::SongShared { occurred_at, .. } => *occurred_at,
+            DomainEvent::SongUploaded { occurred_at, .. } => *occurred_at,
+            DomainEvent::SongFlaggedForReview { occurred_at, .. } => *occurred_at,
+            DomainEvent::RemixLicensePurchased { occurred_at, .. } => *occurred_at,
+            DomainEvent::AlbumCreated { occurred_at, .. } => *occurred_at,
             DomainEvent::CampaignCreated { occurred_at, .. } => *occurred_at,
             DomainEvent::CampaignActivated { occurred_at, .. } => *occurred_at,
             DomainEvent::NFTPurchased { occurred_at, .. } => *occurred_at,
+            DomainEvent::CampaignCompleted { occurred_at, .. } => *occurred_at,
+            DomainEvent::CampaignBudgetDepleted { occurred_at, .. } => *occurred_at,
             DomainEvent::ListenSessionStarted { occurred_at, .. } => *occurred_at,
             DomainEvent::ListenSessionCompleted { occurred_at, .. } => *occurred_at,
             DomainEvent::VentureCreated { occurred_at, .. } => *occurred_at,
             DomainEvent::InvestmentMade { occurred_at, .. } => *occurred_at,
             DomainEvent::BenefitDelivered { occurred_at, .. } => *occurred_at,
+            DomainEvent::VentureFunded { occurred_at, .. } => *occurred_at,
+            DomainEvent::SharePriceUpdated { occurred_at, .. } => *occurred_at,
+            DomainEvent::SharePurchased { occurred_at, .. } => *occurred_at,
+            DomainEvent::ShareTransferred { occurred_at, .. } => *occurred_at,
+            DomainEvent::SharesEscrowed { occurred_at, .. } => *occurred_at,
+            DomainEvent::EscrowCancelled { occurred_at, .. } => *occurred_at,
+            DomainEvent::RevenueDistributed { occurred_at, .. } => *occurred_at,
+            DomainEvent::ProposalFinalized { occurred_at, .. } => *occurred_at,
+            DomainEvent::InvestmentReservationExpired { occurred_at, .. } => *occurred_at,
+            DomainEvent::SharesReserved { occurred_at, .. } => *occurred_at,
+            DomainEvent::SharePaymentCompleted { occurred_at, .. } => *occurred_at,
+            DomainEvent::SharePaymentFailed { occurred_at, .. } => *occurred_at,
+            DomainEvent::SettlementConfirmed { occurred_at, .. } => *occurred_at,
+            DomainEvent::RoyaltyAdvanceRepaid { occurred_at, .. } => *occurred_at,
+            DomainEvent::FanBenefitRedeemed { occurred_at, .. } => *occurred_at,
+            DomainEvent::FraudDetected { occurred_at, .. } => *occurred_at,
         }
     }
 }
@@ -242,9 +500,12 @@ impl EventHandler for UserEventHandlers {
                 tracing::info!("User authenticated: {}", user_id);
                 // TODO: Update last login time, track login analytics
             },
-            DomainEvent::UserProfileUpdated { user_id, .. } => {
-                tracing::info!("User profile updated: {}", user_id);
-                // TODO: Update search index, notify followers
+            DomainEvent::UserProfileUpdated { user_id, changed_fields, .. } => {
+                // La búsqueda de usuarios lee de `users`, que ya está actualizada
+                tracing::info!("User profile updated: {} ({})", user_id, changed_fields.join(", "));
+            },
+            DomainEvent::UserDeletionRequested { user_id, .. } => {
+                tracing::info!("User account deleted: {}", user_id);
             },
             _ => {}
         }
@@ -271,6 +532,9 @@ impl EventHandler for MusicEventHandlers {
                 tracing::info!("Song shared: user={}, song={}, platform={}", user_id, song_id, platform);
                 // TODO: Track social sharing, update viral coefficient
             },
+            DomainEvent::SongUploaded { song_id, artist_id, genre, .. } => {
+                tracing::info!("Song uploaded: song={}, artist={}, genre={}", song_id, artist_id, genre);
+            },
             _ => {}
         }
         Ok(())
@@ -399,6 +663,26 @@ impl EventHandler for FanVenturesEventHandlers {
                 tracing::info!("Benefit delivered: venture={}, investor={}, type={}", venture_id, investor_id, benefit_type);
                 // TODO: Update delivery status, notify investor
             },
+            DomainEvent::VentureFunded { venture_id, artist_id, total_funding, .. } => {
+                tracing::info!("Venture funded: venture={}, artist={}, total=${}", venture_id, artist_id, total_funding);
+                // TODO: Notify artist and investors
+            },
+            DomainEvent::SharePriceUpdated { venture_id, price, previous_price, .. } => {
+                tracing::info!("Share price updated: venture={}, price={} (was {})", venture_id, price, previous_price);
+            },
+            DomainEvent::RevenueDistributed { venture_id, amount, .. } => {
+                tracing::info!("Revenue distributed: venture={}, amount=${}", venture_id, amount);
+            },
+            DomainEvent::ProposalFinalized { proposal_id, venture_id, outcome, winning_option_id, turnout_percentage, .. } => {
+                tracing::info!("Proposal finalized: proposal={}, venture={}, outcome={}, winner={:?}, turnout={:.1}%",
+                    proposal_id, venture_id, outcome, winning_option_id, turnout_percentage);
+                // TODO: Notify shareholders of the result
+            },
+            DomainEvent::InvestmentReservationExpired { investment_id, venture_id, investor_id, shares, .. } => {
+                tracing::info!("Investment reservation expired: investment={}, venture={}, investor={}, shares={}",
+                    investment_id, venture_id, investor_id, shares);
+                // TODO: Notify the investor that the purchase was released
+            },
             _ => {}
         }
         Ok(())
@@ -456,18 +740,21 @@ impl EventBusFactory {
         db_pool: sqlx::PgPool,
         blockchain_client: Arc<BlockchainClient>,
         zk_client: Arc<ZkServiceClient>,
+        notification_hub: Arc<crate::bounded_contexts::notifications::application::NotificationHub>,
     ) -> Result<(), AppError> {
         // User Context Handlers (Stateless for now)
         let user_handlers = Arc::new(UserEventHandlers);
         event_bus.subscribe("UserRegistered", Arc::clone(&user_handlers) as Arc<dyn EventHandler>).await?;
         event_bus.subscribe("UserAuthenticated", Arc::clone(&user_handlers) as Arc<dyn EventHandler>).await?;
         event_bus.subscribe("UserProfileUpdated", Arc::clone(&user_handlers) as Arc<dyn EventHandler>).await?;
+        event_bus.subscribe("UserDeletionRequested", Arc::clone(&user_handlers) as Arc<dyn EventHandler>).await?;
 
         // Music Context Handlers (Stateless for now)
         let music_handlers = Arc::new(MusicEventHandlers);
         event_bus.subscribe("SongListened", Arc::clone(&music_handlers) as Arc<dyn EventHandler>).await?;
         event_bus.subscribe("SongLiked", Arc::clone(&music_handlers) as Arc<dyn EventHandler>).await?;
         event_bus.subscribe("SongShared", Arc::clone(&music_handlers) as Arc<dyn EventHandler>).await?;
+        event_bus.subscribe("SongUploaded", Arc::clone(&music_handlers) as Arc<dyn EventHandler>).await?;
 
         // Campaign Context Handlers (Needs Repo & Blockchain)
         let campaign_repo = Arc::new(PostgresCampaignRepository::new(db_pool.clone()));
@@ -489,6 +776,43 @@ impl EventBusFactory {
         event_bus.subscribe("VentureCreated", Arc::clone(&fan_ventures_handlers) as Arc<dyn EventHandler>).await?;
         event_bus.subscribe("InvestmentMade", Arc::clone(&fan_ventures_handlers) as Arc<dyn EventHandler>).await?;
         event_bus.subscribe("BenefitDelivered", Arc::clone(&fan_ventures_handlers) as Arc<dyn EventHandler>).await?;
+        event_bus.subscribe("VentureFunded", Arc::clone(&fan_ventures_handlers) as Arc<dyn EventHandler>).await?;
+        event_bus.subscribe("SharePriceUpdated", Arc::clone(&fan_ventures_handlers) as Arc<dyn EventHandler>).await?;
+        event_bus.subscribe("RevenueDistributed", Arc::clone(&fan_ventures_handlers) as Arc<dyn EventHandler>).await?;
+        event_bus.subscribe("ProposalFinalized", Arc::clone(&fan_ventures_handlers) as Arc<dyn EventHandler>).await?;
+        event_bus.subscribe("InvestmentReservationExpired", Arc::clone(&fan_ventures_handlers) as Arc<dyn EventHandler>).await?;
+
+        // Read model de géneros por artista (para estadísticas de mercado)
+        let genre_projection = Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::ArtistGenreProjection::new(
+            Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::PostgresArtistGenreReadModel::new(db_pool.clone())),
+        ));
+        event_bus.subscribe("SongUploaded", genre_projection as Arc<dyn EventHandler>).await?;
+
+        // Cada álbum nuevo recibe su playlist con las pistas en orden
+        let album_playlists = Arc::new(crate::bounded_contexts::music::infrastructure::messaging::AlbumCreatedEventHandler::new(
+            Arc::new(crate::bounded_contexts::music::infrastructure::repositories::PostgresPlaylistRepository::new(db_pool.clone())),
+        ));
+        event_bus.subscribe("AlbumCreated", album_playlists as Arc<dyn EventHandler>).await?;
+
+        // Canciones similares: se recalculan en segundo plano con cada subida
+        let song_similarities = Arc::new(crate::bounded_contexts::music::infrastructure::messaging::SongSimilarityRefreshHandler::new(
+            Arc::new(crate::bounded_contexts::music::infrastructure::repositories::PostgresSongSimilarityReadModel::new(db_pool.clone())),
+        ));
+        event_bus.subscribe("SongUploaded", song_similarities as Arc<dyn EventHandler>).await?;
+
+        // Registro de auditoría append-only para compras, traspasos y repartos
+        let audit_trail = Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::AuditTrailListener::new(
+            Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::PostgresAuditLogRepository::new(db_pool.clone())),
+        ));
+        for event_type in ["SharePurchased", "ShareTransferred", "RevenueDistributed"] {
+            event_bus.subscribe(event_type, Arc::clone(&audit_trail) as Arc<dyn EventHandler>).await?;
+        }
+
+        // Historial de precios del mercado secundario: una operación por traspaso cobrado
+        let trade_recorder = Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::SecondaryMarketTradeRecorder::new(
+            Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::PostgresSecondaryMarketTradeRepository::new(db_pool.clone())),
+        ));
+        event_bus.subscribe("ShareTransferred", trade_recorder as Arc<dyn EventHandler>).await?;
 
         // Fan Ventures Payment Integration Handlers
         // These handlers update venture funding when payments are confirmed
@@ -504,9 +828,19 @@ impl EventBusFactory {
         let payment_integration = Arc::new(FanVenturesPaymentIntegration::new(
             payment_handler,
             venture_repo.clone(),
+        ).with_currency_converter(Arc::new(
+            crate::bounded_contexts::payment::infrastructure::exchange_rates::currency_converter_from_env(),
+        )));
+        let escrow_service = Arc::new(crate::bounded_contexts::fan_ventures::application::InvestmentEscrowService::new(
+            Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::PostgresInvestmentReservationRepository::new(db_pool.clone())),
+            venture_repo.clone(),
+            Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::PaymentContextEscrow::new(db_pool.clone(), venture_repo.clone())),
+            event_bus.clone(),
+            crate::bounded_contexts::fan_ventures::application::InvestmentEscrowService::reservation_timeout_from_env(),
         ));
         let fan_ventures_payment_listener = Arc::new(FanVenturesPaymentEventListener::new(
-            payment_integration.clone()
+            payment_integration.clone(),
+            escrow_service,
         ));
         
         // Register payment event listeners
@@ -514,10 +848,72 @@ impl EventBusFactory {
         event_bus.subscribe("PaymentFailed", Arc::clone(&fan_ventures_payment_listener) as Arc<dyn EventHandler>).await?;
         event_bus.subscribe("SharePurchasePaymentCompleted", Arc::clone(&fan_ventures_payment_listener) as Arc<dyn EventHandler>).await?;
 
+        // Notifications Context: todas las entregas pasan por las preferencias del destinatario,
+        // las in-app se empujan a los WebSockets abiertos y, si hay proveedores, a los móviles
+        // y al email (al momento o en el resumen periódico)
+        let mut notification_dispatcher = crate::bounded_contexts::notifications::application::NotificationDispatcher::new(
+            Arc::new(crate::bounded_contexts::notifications::infrastructure::PostgresNotificationRepository::new(db_pool.clone())),
+            Arc::new(crate::bounded_contexts::notifications::infrastructure::PostgresNotificationPreferencesRepository::new(db_pool.clone())),
+        ).with_live_hub(notification_hub);
+        if let Some(push_sender) = crate::bounded_contexts::notifications::infrastructure::push_sender_from_env(
+            Arc::new(crate::bounded_contexts::notifications::infrastructure::PostgresDeviceTokenRepository::new(db_pool.clone())),
+        ) {
+            notification_dispatcher = notification_dispatcher.with_sender(push_sender);
+        }
+        if let Some(email_sender) = crate::bounded_contexts::notifications::infrastructure::email_sender_from_env(db_pool.clone()) {
+            notification_dispatcher = notification_dispatcher
+                .with_sender(email_sender)
+                .with_digest_queue(Arc::new(crate::bounded_contexts::notifications::infrastructure::PostgresDigestRepository::new(db_pool.clone())));
+        }
+        let notification_dispatcher = Arc::new(notification_dispatcher);
+
+        // Notifications Context: alertas de fraude para el equipo de operaciones
+        let fraud_alert_listener = Arc::new(crate::bounded_contexts::notifications::infrastructure::FraudAlertNotificationListener::new(
+            notification_dispatcher.clone(),
+            crate::bounded_contexts::notifications::infrastructure::FraudAlertNotificationListener::ops_user_ids_from_env(),
+        ));
+        event_bus.subscribe("FraudDetected", fraud_alert_listener as Arc<dyn EventHandler>).await?;
+
+        // Notifications Context: aviso al usuario cuando cambian su username o su wallet
+        let profile_change_listener = Arc::new(crate::bounded_contexts::notifications::infrastructure::ProfileChangeNotificationListener::new(
+            notification_dispatcher.clone(),
+        ));
+        event_bus.subscribe("UserProfileUpdated", profile_change_listener as Arc<dyn EventHandler>).await?;
+
+        // Notifications Context: aviso al artista cuando su campaña se queda sin presupuesto
+        let campaign_budget_listener = Arc::new(crate::bounded_contexts::notifications::infrastructure::CampaignBudgetNotificationListener::new(
+            notification_dispatcher.clone(),
+        ));
+        event_bus.subscribe("CampaignBudgetDepleted", campaign_budget_listener as Arc<dyn EventHandler>).await?;
+
+        // Notifications Context: aviso al artista cuando su canción queda pendiente de revisión
+        let song_review_listener = Arc::new(crate::bounded_contexts::notifications::infrastructure::SongReviewNotificationListener::new(
+            notification_dispatcher.clone(),
+        ));
+        event_bus.subscribe("SongFlaggedForReview", song_review_listener as Arc<dyn EventHandler>).await?;
+
+        Self::register_user_deletion_handlers(event_bus.as_ref(), db_pool.clone()).await?;
+
         tracing::info!("✅ Registered event handlers WITH DEPENDENCIES for all bounded contexts");
         
         Ok(())
     }
+
+    /// Borrado de cuentas: cada contexto con datos del usuario los borra o los anonimiza
+    pub async fn register_user_deletion_handlers(event_bus: &dyn EventBus, db_pool: sqlx::PgPool) -> Result<(), AppError> {
+        let handlers: [Arc<dyn EventHandler>; 6] = [
+            Arc::new(crate::bounded_contexts::listen_reward::infrastructure::ListenSessionUserDeletionListener::new(db_pool.clone())),
+            Arc::new(crate::bounded_contexts::payment::infrastructure::PaymentUserDeletionListener::new(db_pool.clone())),
+            Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::HoldingsUserDeletionListener::new(db_pool.clone())),
+            Arc::new(crate::bounded_contexts::music::infrastructure::PlaylistUserDeletionListener::new(db_pool.clone())),
+            Arc::new(crate::bounded_contexts::user::infrastructure::FollowsUserDeletionListener::new(db_pool.clone())),
+            Arc::new(crate::bounded_contexts::notifications::infrastructure::NotificationUserDeletionListener::new(db_pool)),
+        ];
+        for handler in handlers {
+            event_bus.subscribe("UserDeletionRequested", handler).await?;
+        }
+        Ok(())
+    }
 }
 
 // =============================================================================
@@ -10,9 +10,11 @@ use std::collections::HashMap;
 use async_trait::async_trait;
 use redis::Client as RedisClient;
 use tokio::sync::RwLock;
-use tracing::{info, error, warn};
+use tracing::{info, error, warn, Instrument};
+use uuid::Uuid;
 
 use crate::shared::domain::errors::AppError;
+use crate::shared::domain::events::{correlation_id_or_new, with_correlation_id};
 use super::{EventBus, EventHandler, DomainEvent};
 
 /// Stream name para eventos de dominio en Redis
@@ -264,13 +266,25 @@ impl RedisStreamsEventBus {
             handlers_guard.get(event_type).cloned().unwrap_or_default()
         };
 
-        // Procesar con todos los handlers registrados
-        for handler in handlers {
-            if let Err(e) = handler.handle(&event).await {
-                error!("Error in handler for event {}: {:?}", event_type, e);
-                // Continuamos con otros handlers aunque uno falle
-            }
+        // Los handlers corren en el worker: restaurar el correlation ID de la petición original
+        let correlation_id = match entry.map.get("correlation_id") {
+            Some(redis::Value::Data(bytes)) => std::str::from_utf8(bytes).ok().and_then(|s| Uuid::parse_str(s).ok()),
+            _ => None,
         }
+        .unwrap_or_else(Uuid::new_v4);
+
+        let span = tracing::info_span!("domain_event", event_type, correlation_id = %correlation_id);
+        with_correlation_id(correlation_id, async {
+            // Procesar con todos los handlers registrados
+            for handler in handlers {
+                if let Err(e) = handler.handle(&event).await {
+                    error!("Error in handler for event {}: {:?}", event_type, e);
+                    // Continuamos con otros handlers aunque uno falle
+                }
+            }
+        })
+        .instrument(span)
+        .await;
 
         Ok(())
     }
@@ -296,6 +310,8 @@ impl EventBus for RedisStreamsEventBus {
             .arg(&event_json)
             .arg("occurred_at")
             .arg(event.occurred_at().to_rfc3339())
+            .arg("correlation_id")
+            .arg(correlation_id_or_new().to_string())
             .query_async(&mut conn)
             .await
             .map_err(|e| AppError::InternalError(format!("Failed to publish event to Redis Stream: {}", e)))?;
@@ -0,0 +1,661 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::application::royalty_distribution_service::RoyaltyPayoutScheduler;
+use crate::bounded_contexts::payment::application::royalty_advance_service::RoyaltyAdvanceService;
+use crate::bounded_contexts::payment::domain::{
+    artist_payouts::{ArtistBalance, ArtistPayout, ArtistPayoutStatus, BalanceLedgerEntry, PayoutMethod, PayoutPolicy},
+    repository::ArtistPayoutRepository,
+    royalty_runs::RoyaltyPayout,
+    value_objects::Currency,
+};
+
+/// One transfer of a payout batch
+#[derive(Debug, Clone, PartialEq)]
+pub struct WalletTransfer {
+    /// Written as memo; lets the payout be found on-chain after a crash
+    pub reference: String,
+    pub recipient: String,
+    pub amount: f64,
+    pub currency: Currency,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferReceipt {
+    pub reference: String,
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransferStatus {
+    Pending,
+    Confirmed,
+    Failed(String),
+}
+
+/// Port to the platform's payout wallet on Solana
+#[async_trait]
+pub trait WalletClient: Send + Sync {
+    /// Send the transfers. May stop half way: transfers without a receipt
+    /// may or may not have been sent, and are looked up by reference later.
+    async fn transfer_batch(&self, transfers: &[WalletTransfer]) -> Result<Vec<TransferReceipt>, AppError>;
+
+    /// Signature of the transfer sent with `reference`, if it reached the chain
+    async fn find_transfer(&self, reference: &str) -> Result<Option<String>, AppError>;
+
+    async fn transfer_status(&self, signature: &str) -> Result<TransferStatus, AppError>;
+}
+
+/// What one scheduler cycle did
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PayoutCycleReport {
+    pub opened: usize,
+    pub submitted: usize,
+    /// Interrupted submissions found on-chain (recorded) or expired (re-queued)
+    pub reconciled: usize,
+    pub confirmed: usize,
+    pub failed: usize,
+}
+
+/// Artist balances and payouts: ledger credits, payout scheduling and execution
+pub struct ArtistPayoutService {
+    repository: Arc<dyn ArtistPayoutRepository>,
+    wallet_client: Option<Arc<dyn WalletClient>>,
+    royalty_advances: Option<Arc<RoyaltyAdvanceService>>,
+    policy: PayoutPolicy,
+}
+
+impl ArtistPayoutService {
+    pub fn new(repository: Arc<dyn ArtistPayoutRepository>, policy: PayoutPolicy) -> Self {
+        Self {
+            repository,
+            wallet_client: None,
+            royalty_advances: None,
+            policy,
+        }
+    }
+
+    /// Sin wallet los payouts cripto se abren (reservan saldo) pero no se envían
+    pub fn with_wallet_client(mut self, wallet_client: Arc<dyn WalletClient>) -> Self {
+        self.wallet_client = Some(wallet_client);
+        self
+    }
+
+    /// Las royalties acreditadas amortizan primero el adelanto activo del artista
+    pub fn with_royalty_advances(mut self, royalty_advances: Arc<RoyaltyAdvanceService>) -> Self {
+        self.royalty_advances = Some(royalty_advances);
+        self
+    }
+
+    pub async fn register_payout_method(&self, artist_id: Uuid, method: PayoutMethod) -> Result<PayoutMethod, AppError> {
+        self.repository.save_payout_method(artist_id, &method).await?;
+        tracing::info!("Artist {} registered a {} payout method", artist_id, method.kind());
+        Ok(method)
+    }
+
+    pub async fn payout_method(&self, artist_id: Uuid) -> Result<Option<PayoutMethod>, AppError> {
+        self.repository.find_payout_method(artist_id).await
+    }
+
+    pub async fn balance(&self, artist_id: Uuid) -> Result<Vec<ArtistBalance>, AppError> {
+        let entries = self.repository.find_entries(artist_id).await?;
+        Ok(ArtistBalance::from_entries(artist_id, &entries))
+    }
+
+    pub async fn payouts(&self, artist_id: Uuid) -> Result<Vec<ArtistPayout>, AppError> {
+        self.repository.find_payouts_by_artist(artist_id).await
+    }
+
+    /// One pass of the scheduler. Order matters: interrupted submissions are
+    /// resolved before anything new is sent.
+    pub async fn run_cycle(&self, now: DateTime<Utc>) -> Result<PayoutCycleReport, AppError> {
+        let mut report = PayoutCycleReport::default();
+
+        if let Some(wallet) = &self.wallet_client {
+            self.reconcile_submissions(wallet.as_ref(), now, &mut report).await?;
+        }
+        self.open_due_payouts(&mut report).await?;
+        if let Some(wallet) = &self.wallet_client {
+            self.submit_pending(wallet.as_ref(), now, &mut report).await?;
+            self.check_submitted(wallet.as_ref(), now, &mut report).await?;
+        }
+
+        Ok(report)
+    }
+
+    async fn reconcile_submissions(
+        &self,
+        wallet: &dyn WalletClient,
+        now: DateTime<Utc>,
+        report: &mut PayoutCycleReport,
+    ) -> Result<(), AppError> {
+        for mut payout in self.repository.find_payouts_by_status(ArtistPayoutStatus::Submitting).await? {
+            match wallet.find_transfer(&payout.reference()).await? {
+                Some(signature) => {
+                    payout.record_submission(signature, now)?;
+                }
+                // Puede seguir en vuelo: sólo se reenvía cuando ya no puede aterrizar
+                None if payout.submission_expired(now, &self.policy) => {
+                    payout.reset_unsent()?;
+                }
+                None => continue,
+            }
+            self.repository.transition_payout(&payout, ArtistPayoutStatus::Submitting).await?;
+            report.reconciled += 1;
+        }
+        Ok(())
+    }
+
+    async fn open_due_payouts(&self, report: &mut PayoutCycleReport) -> Result<(), AppError> {
+        for (artist_id, currency, available) in self.repository.find_due_balances(self.policy.minimum_amount).await? {
+            if !self.policy.is_due(available) {
+                continue;
+            }
+            let Some(method) = self.repository.find_payout_method(artist_id).await? else {
+                continue;
+            };
+            if !method.supports(&currency) {
+                continue;
+            }
+
+            let payout = ArtistPayout::new(artist_id, method, available, currency);
+            if let Some(opened) = self.repository.open_payout(&payout, self.policy.minimum_amount).await? {
+                tracing::info!(
+                    "Opened payout {} of {} {:?} for artist {}",
+                    opened.id, opened.amount, opened.currency, artist_id
+                );
+                report.opened += 1;
+            }
+        }
+        Ok(())
+    }
+
+    async fn submit_pending(
+        &self,
+        wallet: &dyn WalletClient,
+        now: DateTime<Utc>,
+        report: &mut PayoutCycleReport,
+    ) -> Result<(), AppError> {
+        let mut batch: Vec<ArtistPayout> = Vec::new();
+        for mut payout in self.repository.find_payouts_by_status(ArtistPayoutStatus::Pending).await? {
+            if !matches!(payout.method, PayoutMethod::SolanaWallet { .. }) {
+                continue;
+            }
+            // Persistir `Submitting` antes de enviar: tras un crash el payout se
+            // busca en la cadena en vez de reenviarse a ciegas
+            payout.begin_submission(now)?;
+            match self.repository.transition_payout(&payout, ArtistPayoutStatus::Pending).await {
+                Ok(()) => batch.push(payout),
+                Err(AppError::ConcurrencyConflict(_)) => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        let transfers: Vec<WalletTransfer> = batch
+            .iter()
+            .filter_map(|payout| match &payout.method {
+                PayoutMethod::SolanaWallet { address } => Some(WalletTransfer {
+                    reference: payout.reference(),
+                    recipient: address.value().to_string(),
+                    amount: payout.amount,
+                    currency: payout.currency.clone(),
+                }),
+                PayoutMethod::StripeConnect { .. } => None,
+            })
+            .collect();
+
+        let receipts = match wallet.transfer_batch(&transfers).await {
+            Ok(receipts) => receipts,
+            Err(e) => {
+                // Los que no tienen recibo se reconcilian en el siguiente ciclo
+                tracing::warn!("Payout batch of {} transfer(s) interrupted: {}", transfers.len(), e);
+                return Ok(());
+            }
+        };
+
+        for receipt in receipts {
+            let Some(payout) = batch.iter_mut().find(|p| p.reference() == receipt.reference) else {
+                continue;
+            };
+            payout.record_submission(receipt.signature, now)?;
+            self.repository.transition_payout(payout, ArtistPayoutStatus::Submitting).await?;
+            report.submitted += 1;
+        }
+        Ok(())
+    }
+
+    async fn check_submitted(
+        &self,
+        wallet: &dyn WalletClient,
+        now: DateTime<Utc>,
+        report: &mut PayoutCycleReport,
+    ) -> Result<(), AppError> {
+        for mut payout in self.repository.find_payouts_by_status(ArtistPayoutStatus::Submitted).await? {
+            let Some(signature) = payout.tx_signature.clone() else {
+                continue;
+            };
+            match wallet.transfer_status(&signature).await? {
+                TransferStatus::Pending => {}
+                TransferStatus::Confirmed => {
+                    payout.confirm(now)?;
+                    self.repository.settle_payout(&payout).await?;
+                    report.confirmed += 1;
+                }
+                TransferStatus::Failed(reason) => {
+                    tracing::warn!("Payout {} transfer {} failed: {}", payout.id, signature, reason);
+                    payout.fail(reason)?;
+                    self.repository.release_payout(&payout).await?;
+                    report.failed += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Royalty runs credit creators' shares to their balance, minus what repays
+/// an active royalty advance
+#[async_trait]
+impl RoyaltyPayoutScheduler for ArtistPayoutService {
+    async fn schedule(&self, payouts: &[RoyaltyPayout]) -> Result<(), AppError> {
+        let now = Utc::now();
+        let mut entries = Vec::new();
+        for payout in payouts.iter().filter(|p| p.is_creator_payout() && p.amount.value() > 0.0) {
+            let currency = payout.amount.currency().clone();
+            let amount = match &self.royalty_advances {
+                Some(advances) => {
+                    advances
+                        .withhold_repayment(payout.recipient_id, payout.id, payout.amount.value(), &currency, now)
+                        .await?
+                }
+                None => payout.amount.value(),
+            };
+            if amount > 0.0 {
+                entries.push(BalanceLedgerEntry::credit(payout.recipient_id, payout.id, amount, currency)?);
+            }
+        }
+        self.repository.credit(&entries).await
+    }
+
+    async fn cancel(&self, payouts: &[RoyaltyPayout]) -> Result<(), AppError> {
+        let source_ids: Vec<Uuid> = payouts.iter().map(|p| p.id).collect();
+        self.repository.revoke_credits(&source_ids).await?;
+        if let Some(advances) = &self.royalty_advances {
+            advances.revert_repayments(&source_ids).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Worker que ejecuta el scheduler de payouts periódicamente
+pub struct ArtistPayoutJob {
+    service: Arc<ArtistPayoutService>,
+    interval: Duration,
+}
+
+impl ArtistPayoutJob {
+    pub fn new(service: Arc<ArtistPayoutService>, interval: Duration) -> Self {
+        Self { service, interval }
+    }
+
+    pub fn start(&self) -> tokio::task::JoinHandle<()> {
+        let service = Arc::clone(&self.service);
+        let interval = self.interval;
+
+        tokio::spawn(async move {
+            tracing::info!("Artist payout job started");
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match service.run_cycle(Utc::now()).await {
+                    Ok(report) if report != PayoutCycleReport::default() => {
+                        tracing::info!("Artist payout cycle: {:?}", report)
+                    }
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Artist payout cycle failed: {:?}", e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::payment::domain::artist_payouts::LedgerEntryStatus;
+    use crate::bounded_contexts::payment::domain::repository::PaymentRepositoryResult;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    const WALLETS: [&str; 3] = [
+        "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM",
+        "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+        "11111111111111111111111111111111",
+    ];
+
+    #[derive(Default)]
+    struct LedgerState {
+        methods: HashMap<Uuid, PayoutMethod>,
+        entries: Vec<BalanceLedgerEntry>,
+        payouts: Vec<ArtistPayout>,
+    }
+
+    /// Mirrors the conditional updates of the Postgres repository
+    #[derive(Default)]
+    struct InMemoryPayoutRepository {
+        state: Mutex<LedgerState>,
+    }
+
+    #[async_trait]
+    impl ArtistPayoutRepository for InMemoryPayoutRepository {
+        async fn save_payout_method(&self, artist_id: Uuid, method: &PayoutMethod) -> PaymentRepositoryResult<()> {
+            self.state.lock().unwrap().methods.insert(artist_id, method.clone());
+            Ok(())
+        }
+
+        async fn find_payout_method(&self, artist_id: Uuid) -> PaymentRepositoryResult<Option<PayoutMethod>> {
+            Ok(self.state.lock().unwrap().methods.get(&artist_id).cloned())
+        }
+
+        async fn credit(&self, entries: &[BalanceLedgerEntry]) -> PaymentRepositoryResult<()> {
+            let mut state = self.state.lock().unwrap();
+            for entry in entries {
+                if !state.entries.iter().any(|e| e.source_id == entry.source_id) {
+                    state.entries.push(entry.clone());
+                }
+            }
+            Ok(())
+        }
+
+        async fn revoke_credits(&self, source_ids: &[Uuid]) -> PaymentRepositoryResult<()> {
+            let mut state = self.state.lock().unwrap();
+            if state
+                .entries
+                .iter()
+                .any(|e| source_ids.contains(&e.source_id) && e.status != LedgerEntryStatus::Available)
+            {
+                return Err(AppError::InvalidState("Credits already paid out".to_string()));
+            }
+            state.entries.retain(|e| !source_ids.contains(&e.source_id));
+            Ok(())
+        }
+
+        async fn find_entries(&self, artist_id: Uuid) -> PaymentRepositoryResult<Vec<BalanceLedgerEntry>> {
+            Ok(self.state.lock().unwrap().entries.iter().filter(|e| e.artist_id == artist_id).cloned().collect())
+        }
+
+        async fn find_due_balances(&self, minimum: f64) -> PaymentRepositoryResult<Vec<(Uuid, Currency, f64)>> {
+            let state = self.state.lock().unwrap();
+            let mut totals: Vec<(Uuid, Currency, f64)> = Vec::new();
+            for entry in state.entries.iter().filter(|e| e.status == LedgerEntryStatus::Available) {
+                match totals.iter_mut().find(|(a, c, _)| *a == entry.artist_id && *c == entry.currency) {
+                    Some((_, _, total)) => *total += entry.amount,
+                    None => totals.push((entry.artist_id, entry.currency.clone(), entry.amount)),
+                }
+            }
+            Ok(totals.into_iter().filter(|(_, _, total)| *total >= minimum).collect())
+        }
+
+        async fn open_payout(&self, payout: &ArtistPayout, minimum: f64) -> PaymentRepositoryResult<Option<ArtistPayout>> {
+            let mut state = self.state.lock().unwrap();
+            if state
+                .payouts
+                .iter()
+                .any(|p| p.artist_id == payout.artist_id && p.currency == payout.currency && p.status.is_open())
+            {
+                return Ok(None);
+            }
+            let available: f64 = state
+                .entries
+                .iter()
+                .filter(|e| e.artist_id == payout.artist_id && e.currency == payout.currency && e.status == LedgerEntryStatus::Available)
+                .map(|e| e.amount)
+                .sum();
+            if available < minimum {
+                return Ok(None);
+            }
+
+            for entry in state.entries.iter_mut().filter(|e| {
+                e.artist_id == payout.artist_id && e.currency == payout.currency && e.status == LedgerEntryStatus::Available
+            }) {
+                entry.status = LedgerEntryStatus::Reserved;
+                entry.payout_id = Some(payout.id);
+            }
+            let opened = ArtistPayout { amount: available, ..payout.clone() };
+            state.payouts.push(opened.clone());
+            Ok(Some(opened))
+        }
+
+        async fn transition_payout(&self, payout: &ArtistPayout, expected: ArtistPayoutStatus) -> PaymentRepositoryResult<()> {
+            let mut state = self.state.lock().unwrap();
+            match state.payouts.iter_mut().find(|p| p.id == payout.id && p.status == expected) {
+                Some(stored) => {
+                    *stored = payout.clone();
+                    Ok(())
+                }
+                None => Err(AppError::ConcurrencyConflict(format!("Payout {} moved concurrently", payout.id))),
+            }
+        }
+
+        async fn settle_payout(&self, payout: &ArtistPayout) -> PaymentRepositoryResult<()> {
+            self.transition_payout(payout, ArtistPayoutStatus::Submitted).await?;
+            let mut state = self.state.lock().unwrap();
+            for entry in state.entries.iter_mut().filter(|e| e.payout_id == Some(payout.id)) {
+                entry.status = LedgerEntryStatus::Settled;
+                entry.settled_at = payout.confirmed_at;
+            }
+            Ok(())
+        }
+
+        async fn release_payout(&self, payout: &ArtistPayout) -> PaymentRepositoryResult<()> {
+            self.transition_payout(payout, ArtistPayoutStatus::Submitted).await?;
+            let mut state = self.state.lock().unwrap();
+            for entry in state.entries.iter_mut().filter(|e| e.payout_id == Some(payout.id)) {
+                entry.status = LedgerEntryStatus::Available;
+                entry.payout_id = None;
+            }
+            Ok(())
+        }
+
+        async fn find_payouts_by_status(&self, status: ArtistPayoutStatus) -> PaymentRepositoryResult<Vec<ArtistPayout>> {
+            Ok(self.state.lock().unwrap().payouts.iter().filter(|p| p.status == status).cloned().collect())
+        }
+
+        async fn find_payouts_by_artist(&self, artist_id: Uuid) -> PaymentRepositoryResult<Vec<ArtistPayout>> {
+            Ok(self.state.lock().unwrap().payouts.iter().filter(|p| p.artist_id == artist_id).cloned().collect())
+        }
+    }
+
+    /// Fake chain: records every transfer it sends. `crash_after` makes the
+    /// next batch stop after that many transfers, as if the process died.
+    #[derive(Default)]
+    struct FakeChainWallet {
+        sent: Mutex<Vec<WalletTransfer>>,
+        crash_after: Mutex<Option<usize>>,
+        status: Mutex<Option<TransferStatus>>,
+    }
+
+    impl FakeChainWallet {
+        fn sent_to(&self, recipient: &str) -> usize {
+            self.sent.lock().unwrap().iter().filter(|t| t.recipient == recipient).count()
+        }
+
+        fn signature(reference: &str) -> String {
+            format!("sig-{}", reference)
+        }
+    }
+
+    #[async_trait]
+    impl WalletClient for FakeChainWallet {
+        async fn transfer_batch(&self, transfers: &[WalletTransfer]) -> Result<Vec<TransferReceipt>, AppError> {
+            let crash_after = self.crash_after.lock().unwrap().take();
+            let mut receipts = Vec::new();
+            for (index, transfer) in transfers.iter().enumerate() {
+                if Some(index) == crash_after {
+                    return Err(AppError::ServiceUnavailable("connection reset".to_string()));
+                }
+                self.sent.lock().unwrap().push(transfer.clone());
+                receipts.push(TransferReceipt {
+                    reference: transfer.reference.clone(),
+                    signature: Self::signature(&transfer.reference),
+                });
+            }
+            Ok(receipts)
+        }
+
+        async fn find_transfer(&self, reference: &str) -> Result<Option<String>, AppError> {
+            Ok(self
+                .sent
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|t| t.reference == reference)
+                .map(|t| Self::signature(&t.reference)))
+        }
+
+        async fn transfer_status(&self, _signature: &str) -> Result<TransferStatus, AppError> {
+            Ok(self.status.lock().unwrap().clone().unwrap_or(TransferStatus::Confirmed))
+        }
+    }
+
+    fn policy() -> PayoutPolicy {
+        PayoutPolicy { minimum_amount: 10.0, submission_expiry: chrono::Duration::seconds(180) }
+    }
+
+    async fn service_with_artists(amounts: &[f64]) -> (ArtistPayoutService, Arc<InMemoryPayoutRepository>, Arc<FakeChainWallet>, Vec<Uuid>) {
+        let repository = Arc::new(InMemoryPayoutRepository::default());
+        let wallet = Arc::new(FakeChainWallet::default());
+        let service = ArtistPayoutService::new(repository.clone(), policy()).with_wallet_client(wallet.clone());
+
+        let mut artists = Vec::new();
+        for (index, amount) in amounts.iter().enumerate() {
+            let artist = Uuid::new_v4();
+            service
+                .register_payout_method(artist, PayoutMethod::solana_wallet(WALLETS[index]).unwrap())
+                .await
+                .unwrap();
+            repository
+                .credit(&[BalanceLedgerEntry::credit(artist, Uuid::new_v4(), *amount, Currency::USDC).unwrap()])
+                .await
+                .unwrap();
+            artists.push(artist);
+        }
+        (service, repository, wallet, artists)
+    }
+
+    #[tokio::test]
+    async fn crash_mid_batch_does_not_double_pay_on_retry() {
+        let (service, _repository, wallet, artists) = service_with_artists(&[20.0, 30.0, 40.0]).await;
+        let start = Utc::now();
+
+        // El proceso muere tras enviar dos de las tres transferencias
+        *wallet.crash_after.lock().unwrap() = Some(2);
+        let first = service.run_cycle(start).await.unwrap();
+        assert_eq!(first.opened, 3);
+        assert_eq!(first.submitted, 0);
+        assert_eq!(wallet.sent.lock().unwrap().len(), 2);
+
+        // Reintento inmediato: las dos enviadas se encuentran en la cadena, la
+        // tercera puede seguir en vuelo y no se reenvía todavía
+        let retry = service.run_cycle(start + chrono::Duration::seconds(30)).await.unwrap();
+        assert_eq!(retry.reconciled, 2);
+        assert_eq!(retry.submitted, 0);
+        assert_eq!(wallet.sent.lock().unwrap().len(), 2);
+
+        // Pasado el plazo de expiración la tercera se reenvía, una sola vez
+        let late = service.run_cycle(start + chrono::Duration::seconds(240)).await.unwrap();
+        assert_eq!(late.reconciled, 1);
+        assert_eq!(late.submitted, 1);
+
+        for wallet_address in WALLETS {
+            assert_eq!(wallet.sent_to(wallet_address), 1);
+        }
+        for (artist, amount) in artists.iter().zip([20.0, 30.0, 40.0]) {
+            let balance = &service.balance(*artist).await.unwrap()[0];
+            assert_eq!((balance.available, balance.in_payout, balance.paid_out), (0.0, 0.0, amount));
+        }
+    }
+
+    #[tokio::test]
+    async fn ledger_settles_only_after_confirmation() {
+        let (service, _repository, wallet, artists) = service_with_artists(&[25.0]).await;
+        *wallet.status.lock().unwrap() = Some(TransferStatus::Pending);
+
+        let report = service.run_cycle(Utc::now()).await.unwrap();
+        assert_eq!((report.submitted, report.confirmed), (1, 0));
+        let balance = &service.balance(artists[0]).await.unwrap()[0];
+        assert_eq!((balance.in_payout, balance.paid_out), (25.0, 0.0));
+
+        *wallet.status.lock().unwrap() = Some(TransferStatus::Confirmed);
+        let report = service.run_cycle(Utc::now()).await.unwrap();
+        assert_eq!(report.confirmed, 1);
+        assert_eq!(report.opened, 0);
+        let balance = &service.balance(artists[0]).await.unwrap()[0];
+        assert_eq!((balance.in_payout, balance.paid_out), (0.0, 25.0));
+        assert_eq!(wallet.sent.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn failed_transfer_returns_balance() {
+        let (service, _repository, wallet, artists) = service_with_artists(&[25.0]).await;
+        *wallet.status.lock().unwrap() = Some(TransferStatus::Failed("insufficient funds".to_string()));
+
+        let report = service.run_cycle(Utc::now()).await.unwrap();
+        assert_eq!(report.failed, 1);
+        let balance = &service.balance(artists[0]).await.unwrap()[0];
+        assert_eq!((balance.available, balance.in_payout, balance.paid_out), (25.0, 0.0, 0.0));
+
+        let payouts = service.payouts(artists[0]).await.unwrap();
+        assert_eq!(payouts[0].status, ArtistPayoutStatus::Failed);
+    }
+
+    #[tokio::test]
+    async fn balances_below_minimum_or_without_method_are_not_paid() {
+        let (service, repository, wallet, _artists) = service_with_artists(&[9.5]).await;
+        let no_method = Uuid::new_v4();
+        repository
+            .credit(&[BalanceLedgerEntry::credit(no_method, Uuid::new_v4(), 50.0, Currency::USDC).unwrap()])
+            .await
+            .unwrap();
+
+        let report = service.run_cycle(Utc::now()).await.unwrap();
+        assert_eq!(report.opened, 0);
+        assert!(wallet.sent.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn royalty_credits_are_idempotent_per_source() {
+        use crate::bounded_contexts::payment::domain::royalty_runs::{RoyaltyPayoutStatus, RoyaltyRecipientType};
+        use crate::bounded_contexts::payment::domain::value_objects::Amount;
+
+        let repository = Arc::new(InMemoryPayoutRepository::default());
+        let service = ArtistPayoutService::new(repository.clone(), policy());
+        let artist = Uuid::new_v4();
+        let royalty = |recipient_type| RoyaltyPayout {
+            id: Uuid::new_v4(),
+            run_id: Uuid::new_v4(),
+            song_id: Uuid::new_v4(),
+            recipient_id: artist,
+            recipient_type,
+            percentage: 80.0,
+            amount: Amount::new(8.0, Currency::USD).unwrap(),
+            status: RoyaltyPayoutStatus::Pending,
+            created_at: Utc::now(),
+        };
+        let payouts = vec![royalty(RoyaltyRecipientType::Artist), royalty(RoyaltyRecipientType::Platform)];
+
+        service.schedule(&payouts).await.unwrap();
+        service.schedule(&payouts).await.unwrap();
+        assert_eq!(service.balance(artist).await.unwrap()[0].available, 8.0);
+
+        service.cancel(&payouts).await.unwrap();
+        assert!(service.balance(artist).await.unwrap().is_empty());
+    }
+}
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
 use crate::bounded_contexts::payment::domain::value_objects::*;
@@ -212,6 +213,21 @@ pub struct PaymentPurposeDto {
     pub reason: Option<String>,
 }
 
+impl PaymentPurposeDto {
+    /// What the payment is for: the song, campaign, contract... it refers to
+    pub fn target_id(&self) -> Option<Uuid> {
+        self.song_id
+            .or(self.campaign_id)
+            .or(self.contract_id)
+            .or(self.share_id)
+            .or(self.distribution_id)
+            .or(self.original_payment_id)
+            .or(self.session_id)
+            .or(self.artist_id)
+            .or(self.to_user)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaymentMetadataDto {
     pub user_ip: Option<String>,
@@ -244,6 +260,8 @@ pub struct InitiatePaymentResult {
     pub net_amount: f64,
     pub platform_fee: f64,
     pub created_at: DateTime<Utc>,
+    /// The request repeated an earlier one: this is the payment it created
+    pub replayed: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -318,14 +336,43 @@ impl InitiatePaymentCommand {
             errors.push("Payer and payee cannot be the same".to_string());
         }
         
+        if self.idempotency_key.as_ref().map_or(false, |key| key.len() > MAX_IDEMPOTENCY_KEY_LENGTH) {
+            errors.push(format!("Idempotency key cannot exceed {} characters", MAX_IDEMPOTENCY_KEY_LENGTH));
+        }
+        
         if errors.is_empty() {
             Ok(())
         } else {
             Err(errors)
         }
     }
+    
+    /// Idempotency key of the request: the client's, or one derived from it
+    pub fn idempotency_key_at(&self, now: DateTime<Utc>) -> String {
+        match self.idempotency_key.as_deref().map(str::trim) {
+            Some(key) if !key.is_empty() => key.to_string(),
+            _ => self.default_idempotency_key(now),
+        }
+    }
+    
+    /// sha256(payer + payee + amount + purpose + target + minute): the same payer
+    /// paying the same payee the same amount for the same thing within a minute
+    /// is taken as a retry
+    pub fn default_idempotency_key(&self, now: DateTime<Utc>) -> String {
+        let minute = now.timestamp().div_euclid(60) * 60;
+        let target = self.purpose.target_id().map(|id| id.to_string()).unwrap_or_default();
+        let material = format!(
+            "{}:{}:{}:{}:{}:{}:{}",
+            self.payer_id, self.payee_id, self.amount_value, self.amount_currency,
+            self.purpose.purpose_type, target, minute
+        );
+        hex::encode(Sha256::digest(material.as_bytes()))
+    }
 }
 
+/// Same limit as Stripe's `Idempotency-Key`
+pub const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
 impl CreateRoyaltyDistributionCommand {
     pub fn validate(&self) -> Result<(), Vec<String>> {
         let mut errors = Vec::new();
@@ -454,6 +501,74 @@ mod tests {
         assert!(command.validate().is_err());
     }
     
+    #[test]
+    fn test_default_idempotency_key_groups_requests_by_minute() {
+        use chrono::TimeZone;
+        
+        let command = InitiatePaymentCommand {
+            payer_id: Uuid::new_v4(),
+            payee_id: Uuid::new_v4(),
+            amount_value: 9.99,
+            amount_currency: Currency::USD,
+            payment_method: PaymentMethodDto {
+                method_type: "PlatformBalance".to_string(),
+                card_details: None,
+                crypto_details: None,
+                bank_details: None,
+            },
+            purpose: PaymentPurposeDto {
+                purpose_type: "SongPurchase".to_string(),
+                campaign_id: None,
+                nft_quantity: None,
+                contract_id: None,
+                ownership_percentage: None,
+                share_id: None,
+                from_user: None,
+                to_user: None,
+                song_id: Some(Uuid::new_v4()),
+                artist_id: None,
+                session_id: None,
+                listen_duration: None,
+                distribution_id: None,
+                original_payment_id: None,
+                reason: None,
+            },
+            metadata: PaymentMetadataDto {
+                user_ip: None,
+                user_agent: None,
+                platform_version: "1.0.0".to_string(),
+                reference_id: None,
+                additional_data: serde_json::Value::Null,
+            },
+            idempotency_key: None,
+        };
+        let at = |minute, second| Utc.with_ymd_and_hms(2026, 10, 15, 12, minute, second).unwrap();
+        
+        // Dos peticiones idénticas en el mismo minuto son la misma
+        assert_eq!(command.idempotency_key_at(at(0, 5)), command.idempotency_key_at(at(0, 55)));
+        assert_eq!(command.idempotency_key_at(at(0, 5)).len(), 64);
+        assert_ne!(command.idempotency_key_at(at(0, 55)), command.idempotency_key_at(at(1, 5)));
+        
+        let mut other_amount = command.clone();
+        other_amount.amount_value = 19.99;
+        assert_ne!(command.idempotency_key_at(at(0, 5)), other_amount.idempotency_key_at(at(0, 5)));
+        
+        // Otra canción del mismo artista al mismo precio es otra compra
+        let mut other_song = command.clone();
+        other_song.purpose.song_id = Some(Uuid::new_v4());
+        assert_ne!(command.idempotency_key_at(at(0, 5)), other_song.idempotency_key_at(at(0, 5)));
+        let mut other_purpose = command.clone();
+        other_purpose.purpose.purpose_type = "Tip".to_string();
+        assert_ne!(command.idempotency_key_at(at(0, 5)), other_purpose.idempotency_key_at(at(0, 5)));
+        
+        // La clave del cliente manda
+        let mut with_key = command.clone();
+        with_key.idempotency_key = Some(" order-42 ".to_string());
+        assert_eq!(with_key.idempotency_key_at(at(0, 5)), "order-42");
+        with_key.idempotency_key = Some("x".repeat(MAX_IDEMPOTENCY_KEY_LENGTH + 1));
+        assert!(with_key.validate().is_err());
+    }
+    
     #[test]
     fn test_royalty_distribution_command_validation() {
         let command = CreateRoyaltyDistributionCommand {
@@ -0,0 +1,82 @@
+use chrono::Utc;
+use std::sync::Arc;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::domain::{
+    exchange::{ExchangeRateProvider, NormalizedAmount, DEFAULT_BASE_CURRENCY},
+    value_objects::{Amount, Currency},
+};
+
+/// Converts amounts into the base currency used for internal accounting
+pub struct CurrencyConverter {
+    provider: Arc<dyn ExchangeRateProvider>,
+    base_currency: Currency,
+}
+
+impl CurrencyConverter {
+    pub fn new(provider: Arc<dyn ExchangeRateProvider>, base_currency: Currency) -> Self {
+        Self { provider, base_currency }
+    }
+
+    /// Moneda base leída de `BASE_CURRENCY` (por defecto USD)
+    pub fn base_currency_from_env() -> Currency {
+        match std::env::var("BASE_CURRENCY") {
+            Ok(code) => code.parse().unwrap_or_else(|e| {
+                tracing::warn!("Ignoring BASE_CURRENCY={}: {}", code, e);
+                DEFAULT_BASE_CURRENCY
+            }),
+            Err(_) => DEFAULT_BASE_CURRENCY,
+        }
+    }
+
+    pub fn base_currency(&self) -> &Currency {
+        &self.base_currency
+    }
+
+    /// The amount with its base-currency equivalent at the current rate.
+    /// Amounts already in the base currency never reach the provider.
+    pub async fn normalize(&self, amount: &Amount) -> Result<NormalizedAmount, AppError> {
+        if amount.currency() == &self.base_currency {
+            return Ok(NormalizedAmount::in_base(amount.clone(), Utc::now()));
+        }
+        let rate = self.provider.rate(amount.currency(), &self.base_currency).await?;
+        NormalizedAmount::new(amount.clone(), rate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::payment::infrastructure::exchange_rates::FixedExchangeRateProvider;
+
+    fn converter() -> CurrencyConverter {
+        let provider = FixedExchangeRateProvider::new()
+            .with_rate(Currency::EUR, Currency::USD, 1.0837)
+            .with_rate(Currency::USD, Currency::GBP, 0.7862);
+        CurrencyConverter::new(Arc::new(provider), Currency::USD)
+    }
+
+    #[tokio::test]
+    async fn amounts_are_normalized_to_the_base_currency() {
+        let converter = converter();
+
+        let eur = converter.normalize(&Amount::new(250.0, Currency::EUR).unwrap()).await.unwrap();
+        assert_eq!(eur.base, Amount::new(270.93, Currency::USD).unwrap());
+        assert_eq!(eur.original.value(), 250.0);
+
+        // Solo existe USD→GBP: se usa el inverso (1 / 0.7862 = 1.271941…)
+        let gbp = converter.normalize(&Amount::new(15.75, Currency::GBP).unwrap()).await.unwrap();
+        assert_eq!(gbp.base.value(), 20.03);
+    }
+
+    #[tokio::test]
+    async fn base_currency_skips_the_provider() {
+        let converter = CurrencyConverter::new(Arc::new(FixedExchangeRateProvider::new()), Currency::USD);
+
+        let usd = converter.normalize(&Amount::new(12.34, Currency::USD).unwrap()).await.unwrap();
+        assert!(!usd.is_converted());
+
+        // Sin tipo configurado para EUR la conversión falla en lugar de asumir 1:1
+        assert!(converter.normalize(&Amount::new(12.34, Currency::EUR).unwrap()).await.is_err());
+    }
+}
@@ -3,6 +3,12 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::bounded_contexts::payment::domain::value_objects::Currency;
+use crate::bounded_contexts::payment::domain::refunds::RefundTransaction;
+use crate::bounded_contexts::payment::domain::royalty_runs::RoyaltyPayout;
+use crate::bounded_contexts::payment::domain::artist_payouts::{ArtistBalance, ArtistPayout, PayoutMethod};
+use crate::bounded_contexts::payment::domain::royalty_advances::RoyaltyAdvance;
+use crate::bounded_contexts::payment::domain::webhooks::{Webhook, WebhookDelivery};
+use crate::bounded_contexts::payment::application::royalty_distribution_service::RoyaltyDistributionOutcome;
 use utoipa::ToSchema;
 
 /// Payment DTO for API responses
@@ -24,6 +30,24 @@ pub struct PaymentDTO {
     pub updated_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
     pub failure_reason: Option<String>,
+    /// Instrucciones y estado del depósito (solo pagos USDC en Solana)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crypto_deposit: Option<CryptoDepositDTO>,
+}
+
+/// Crypto deposit DTO: where to send funds and what has arrived so far
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CryptoDepositDTO {
+    pub deposit_address: String,
+    /// Memo que debe acompañar la transferencia
+    pub memo: String,
+    pub expected_amount: AmountDTO,
+    pub received_amount: AmountDTO,
+    pub shortfall: AmountDTO,
+    /// awaiting_deposit | partially_paid | completed | expired
+    pub status: String,
+    pub expires_at: DateTime<Utc>,
+    pub signatures: Vec<String>,
 }
 
 /// Amount DTO
@@ -268,6 +292,244 @@ pub struct RefundResponse {
     pub created_at: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RefundPaymentRequest {
+    /// Amount to refund; omit to refund the whole remaining balance
+    pub amount: Option<f64>,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConfirmRefundPayoutRequest {
+    /// Hash of the on-chain transfer sent to the payer
+    pub tx_hash: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RefundTransactionDTO {
+    pub refund_id: Uuid,
+    pub payment_id: Uuid,
+    pub amount: f64,
+    pub currency: String,
+    /// pending, processing, awaiting_payout, completed o failed
+    pub status: String,
+    pub gateway: String,
+    pub gateway_refund_id: Option<String>,
+    pub reason: String,
+    pub failure_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl From<RefundTransaction> for RefundTransactionDTO {
+    fn from(refund: RefundTransaction) -> Self {
+        Self {
+            refund_id: refund.id,
+            payment_id: refund.payment_id,
+            amount: refund.amount.value(),
+            currency: format!("{:?}", refund.amount.currency()),
+            status: refund.status.to_string(),
+            gateway: refund.gateway,
+            gateway_refund_id: refund.gateway_refund_id,
+            reason: refund.reason,
+            failure_reason: refund.failure_reason,
+            created_at: refund.created_at,
+            completed_at: refund.completed_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct VoidRoyaltyRunRequest {
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RoyaltyPayoutDTO {
+    pub payout_id: Uuid,
+    pub recipient_id: Uuid,
+    /// artist, contributor o platform
+    pub recipient_type: String,
+    pub percentage: f64,
+    pub amount: f64,
+    pub currency: String,
+    /// pending, scheduled, retained o cancelled
+    pub status: String,
+}
+
+impl From<RoyaltyPayout> for RoyaltyPayoutDTO {
+    fn from(payout: RoyaltyPayout) -> Self {
+        Self {
+            payout_id: payout.id,
+            recipient_id: payout.recipient_id,
+            recipient_type: payout.recipient_type.to_string(),
+            percentage: payout.percentage,
+            amount: payout.amount.value(),
+            currency: format!("{:?}", payout.amount.currency()),
+            status: payout.status.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RoyaltyRunDTO {
+    pub run_id: Uuid,
+    pub song_id: Uuid,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub total_revenue: f64,
+    pub currency: String,
+    /// active o voided
+    pub status: String,
+    /// The same distribution had already been recorded; nothing new was paid
+    pub already_distributed: bool,
+    pub payouts: Vec<RoyaltyPayoutDTO>,
+    pub created_at: DateTime<Utc>,
+    pub voided_at: Option<DateTime<Utc>>,
+    pub void_reason: Option<String>,
+}
+
+impl From<RoyaltyDistributionOutcome> for RoyaltyRunDTO {
+    fn from(outcome: RoyaltyDistributionOutcome) -> Self {
+        let run = outcome.run;
+        Self {
+            run_id: run.id,
+            song_id: run.song_id,
+            period_start: run.period_start,
+            period_end: run.period_end,
+            total_revenue: run.total_revenue.value(),
+            currency: format!("{:?}", run.total_revenue.currency()),
+            status: run.status.to_string(),
+            already_distributed: !outcome.created,
+            payouts: outcome.payouts.into_iter().map(RoyaltyPayoutDTO::from).collect(),
+            created_at: run.created_at,
+            voided_at: run.voided_at,
+            void_reason: run.void_reason,
+        }
+    }
+}
+
+/// Payout method to register; replaces the current one
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RegisterPayoutMethodRequest {
+    /// Bank payouts through Stripe Connect (`acct_...`)
+    StripeConnect { account_id: String },
+    /// USDC to a Solana wallet
+    SolanaWallet { address: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PayoutMethodDTO {
+    /// stripe_connect o solana_wallet
+    pub method_type: String,
+    /// Stripe account id o dirección Solana
+    pub destination: String,
+}
+
+impl From<PayoutMethod> for PayoutMethodDTO {
+    fn from(method: PayoutMethod) -> Self {
+        let method_type = method.kind().to_string();
+        let destination = match method {
+            PayoutMethod::StripeConnect { account_id } => account_id,
+            PayoutMethod::SolanaWallet { address } => address.value().to_string(),
+        };
+        Self { method_type, destination }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ArtistBalanceDTO {
+    pub currency: String,
+    pub available: f64,
+    /// Reserved by payouts not confirmed yet
+    pub in_payout: f64,
+    pub paid_out: f64,
+}
+
+impl From<ArtistBalance> for ArtistBalanceDTO {
+    fn from(balance: ArtistBalance) -> Self {
+        Self {
+            currency: format!("{:?}", balance.currency),
+            available: balance.available,
+            in_payout: balance.in_payout,
+            paid_out: balance.paid_out,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ArtistPayoutDTO {
+    pub payout_id: Uuid,
+    pub method: PayoutMethodDTO,
+    pub amount: f64,
+    pub currency: String,
+    /// pending, submitting, submitted, confirmed o failed
+    pub status: String,
+    pub tx_signature: Option<String>,
+    pub failure_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+}
+
+impl From<ArtistPayout> for ArtistPayoutDTO {
+    fn from(payout: ArtistPayout) -> Self {
+        Self {
+            payout_id: payout.id,
+            method: payout.method.into(),
+            amount: payout.amount,
+            currency: format!("{:?}", payout.currency),
+            status: payout.status.to_string(),
+            tx_signature: payout.tx_signature,
+            failure_reason: payout.failure_reason,
+            created_at: payout.created_at,
+            confirmed_at: payout.confirmed_at,
+        }
+    }
+}
+
+/// Advance against future royalties
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RequestRoyaltyAdvanceRequest {
+    pub advance_amount: f64,
+    /// Share of each royalty credit withheld until the advance is repaid (default 0.2)
+    pub repayment_rate: Option<f64>,
+    /// Currency of the advance and of the royalties that repay it (default USD)
+    pub currency: Option<Currency>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RoyaltyAdvanceDTO {
+    pub advance_id: Uuid,
+    pub artist_id: Uuid,
+    pub advance_amount: f64,
+    pub repayment_rate: f64,
+    pub outstanding_balance: f64,
+    pub currency: String,
+    /// active, repaid o defaulted
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub repaid_at: Option<DateTime<Utc>>,
+}
+
+impl From<RoyaltyAdvance> for RoyaltyAdvanceDTO {
+    fn from(advance: RoyaltyAdvance) -> Self {
+        use rust_decimal::prelude::ToPrimitive;
+
+        Self {
+            advance_id: advance.id,
+            artist_id: advance.artist_id,
+            advance_amount: advance.advance_amount.to_f64().unwrap_or_default(),
+            repayment_rate: advance.repayment_rate,
+            outstanding_balance: advance.outstanding_balance.to_f64().unwrap_or_default(),
+            currency: format!("{:?}", advance.currency),
+            status: advance.status.to_string(),
+            created_at: advance.created_at,
+            repaid_at: advance.repaid_at,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct InitiatePaymentRequest {
     pub payer_id: Uuid,
@@ -278,6 +540,10 @@ pub struct InitiatePaymentRequest {
     pub related_entity_id: Option<Uuid>,
     pub payment_method: String,
     pub metadata: Option<serde_json::Value>,
+    /// Reintentos con la misma clave devuelven el pago original. Si falta se
+    /// usa también la cabecera `Idempotency-Key`.
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -329,6 +595,76 @@ pub struct FraudDetectionStats {
     pub prevented_fraud_amount: f64,
 }
 
+/// Register a webhook for third-party integrations
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateWebhookRequest {
+    pub endpoint_url: String,
+    /// payment.completed, payment.failed, payment.cancelled and/or payment.refunded
+    pub subscribed_events: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookDTO {
+    pub webhook_id: Uuid,
+    pub endpoint_url: String,
+    pub subscribed_events: Vec<String>,
+    pub is_active: bool,
+    pub consecutive_failures: u32,
+    /// Signing secret, only returned when the webhook is created
+    pub secret: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookDTO {
+    /// Creation response: the only time the secret is shown
+    pub fn with_secret(webhook: Webhook) -> Self {
+        let secret = webhook.secret.clone();
+        Self { secret: Some(secret), ..webhook.into() }
+    }
+}
+
+impl From<Webhook> for WebhookDTO {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            webhook_id: webhook.id,
+            endpoint_url: webhook.endpoint_url,
+            subscribed_events: webhook.subscribed_events,
+            is_active: webhook.is_active,
+            consecutive_failures: webhook.consecutive_failures,
+            secret: None,
+            created_at: webhook.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookDeliveryDTO {
+    pub delivery_id: Uuid,
+    /// Shared by the retries of the same event
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub attempt: u32,
+    pub status_code: Option<u16>,
+    pub succeeded: bool,
+    pub error: Option<String>,
+    pub attempted_at: DateTime<Utc>,
+}
+
+impl From<WebhookDelivery> for WebhookDeliveryDTO {
+    fn from(delivery: WebhookDelivery) -> Self {
+        Self {
+            delivery_id: delivery.id,
+            event_id: delivery.event_id,
+            event_type: delivery.event_type,
+            attempt: delivery.attempt,
+            status_code: delivery.status_code,
+            succeeded: delivery.succeeded,
+            error: delivery.error,
+            attempted_at: delivery.attempted_at,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
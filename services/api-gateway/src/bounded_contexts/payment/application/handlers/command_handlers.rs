@@ -1,8 +1,11 @@
 use async_trait::async_trait;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
 use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::orchestrator::{DomainEvent as IntegrationEvent, EventBus};
 use crate::bounded_contexts::payment::{
     domain::{
         aggregates::*,
@@ -10,13 +13,61 @@ use crate::bounded_contexts::payment::{
         value_objects::*,
         repository::*,
         services::*,
+        exchange::CapturedExchangeRate,
+        webhooks::PaymentWebhookNotifier,
     },
     application::{
         commands::*,
+        currency_conversion::CurrencyConverter,
         services::PaymentApplicationService,
     },
 };
 
+/// Platform fee read from `PLATFORM_FEE_PERCENTAGE` (20% by default)
+pub fn platform_fee_percentage_from_env() -> Decimal {
+    match std::env::var("PLATFORM_FEE_PERCENTAGE") {
+        Ok(value) => match value.trim().parse::<Decimal>() {
+            Ok(pct) if pct >= Decimal::ZERO && pct <= Decimal::ONE_HUNDRED => pct,
+            _ => {
+                tracing::warn!("Ignoring PLATFORM_FEE_PERCENTAGE={}: expected a percentage between 0 and 100", value);
+                DEFAULT_PLATFORM_FEE_PERCENTAGE
+            }
+        },
+        Err(_) => DEFAULT_PLATFORM_FEE_PERCENTAGE,
+    }
+}
+
+/// Result of a request whose idempotency key already has a payment. Only a retry
+/// of the same payment by the same payer gets it back; reusing the key for a
+/// different payment is a conflict.
+fn replayed_payment(
+    payer_id: Uuid,
+    payee_id: Uuid,
+    amount: &Amount,
+    existing: &PaymentAggregate,
+) -> Result<InitiatePaymentResult, AppError> {
+    let payment = existing.payment();
+    if payment.payer_id() != payer_id {
+        // No se revela el pago de otro usuario
+        return Err(AppError::ConflictError("Idempotency key already used".to_string()));
+    }
+    let same_request = payment.payee_id() == payee_id
+        && payment.amount().currency() == amount.currency()
+        && (payment.amount().value() - amount.value()).abs() < f64::EPSILON;
+    if !same_request {
+        return Err(AppError::DuplicatePayment { existing_id: *payment.id().value() });
+    }
+
+    Ok(InitiatePaymentResult {
+        payment_id: *payment.id().value(),
+        status: format!("{:?}", payment.status()),
+        net_amount: payment.net_amount().value(),
+        platform_fee: payment.platform_fee().map(|f| f.value()).unwrap_or(0.0),
+        created_at: payment.created_at(),
+        replayed: true,
+    })
+}
+
 /// Command Handler for Payment Operations
 #[async_trait]
 pub trait PaymentCommandHandler: Send + Sync {
@@ -57,6 +108,9 @@ pub struct PaymentCommandHandlerImpl {
     fraud_detection_service: Arc<dyn FraudDetectionService>,
     notification_service: Arc<dyn PaymentNotificationService>,
     application_service: Arc<PaymentApplicationService>,
+    event_bus: Option<Arc<dyn EventBus>>,
+    exchange_rate_capture: Option<(Arc<CurrencyConverter>, Arc<dyn PaymentExchangeRateRepository>)>,
+    webhooks: Option<Arc<dyn PaymentWebhookNotifier>>,
 }
 
 impl PaymentCommandHandlerImpl {
@@ -73,6 +127,74 @@ impl PaymentCommandHandlerImpl {
             fraud_detection_service,
             notification_service,
             application_service,
+            event_bus: None,
+            exchange_rate_capture: None,
+            webhooks: None,
+        }
+    }
+
+    /// Bus de integración por el que se notifica el fraude a otros contextos
+    pub fn with_event_bus(mut self, event_bus: Arc<dyn EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Guardar el tipo de cambio de los pagos completados en una moneda distinta
+    /// de la base, para reembolsos y reversiones posteriores
+    pub fn with_exchange_rate_capture(
+        mut self,
+        converter: Arc<CurrencyConverter>,
+        exchange_rates: Arc<dyn PaymentExchangeRateRepository>,
+    ) -> Self {
+        self.exchange_rate_capture = Some((converter, exchange_rates));
+        self
+    }
+
+    /// Webhooks de integraciones externas: pago completado, fallido, cancelado o reembolsado
+    pub fn with_webhooks(mut self, webhooks: Arc<dyn PaymentWebhookNotifier>) -> Self {
+        self.webhooks = Some(webhooks);
+        self
+    }
+
+    fn notify_webhooks(&self, event_type: &'static str, payment_aggregate: &PaymentAggregate) {
+        if let Some(webhooks) = &self.webhooks {
+            webhooks.notify(event_type, payment_aggregate);
+        }
+    }
+
+    /// El pago ya está completado: un fallo aquí se registra pero no lo revierte
+    async fn capture_exchange_rate(&self, payment_aggregate: &PaymentAggregate) {
+        let Some((converter, exchange_rates)) = &self.exchange_rate_capture else { return };
+        let payment = payment_aggregate.payment();
+        if payment.amount().currency() == converter.base_currency() {
+            return;
+        }
+
+        let payment_id = *payment.id().value();
+        let captured = match converter.normalize(payment.amount()).await {
+            Ok(amount) => CapturedExchangeRate { payment_id, amount, captured_at: chrono::Utc::now() },
+            Err(e) => {
+                tracing::warn!("Could not convert payment {} to {}: {:?}", payment_id, converter.base_currency(), e);
+                return;
+            }
+        };
+        if let Err(e) = exchange_rates.record(&captured).await {
+            tracing::warn!("Failed to record exchange rate of payment {}: {:?}", payment_id, e);
+        }
+    }
+
+    async fn publish_fraud_detected(&self, payment_aggregate: &PaymentAggregate, fraud_result: &FraudCheckResult) {
+        let Some(event_bus) = &self.event_bus else { return };
+        let event = IntegrationEvent::FraudDetected {
+            context: "payment".to_string(),
+            subject_id: *payment_aggregate.payment().id().value(),
+            user_id: Some(payment_aggregate.payment().payer_id()),
+            reason_code: fraud_result.reason_code(),
+            risk_score: fraud_result.risk_score,
+            occurred_at: chrono::Utc::now(),
+        };
+        if let Err(e) = event_bus.publish(event).await {
+            tracing::error!("Failed to publish FraudDetected event: {:?}", e);
         }
     }
 }
@@ -82,25 +204,20 @@ impl PaymentCommandHandler for PaymentCommandHandlerImpl {
     async fn handle_initiate_payment(&self, command: InitiatePaymentCommand) -> Result<InitiatePaymentResult, AppError> {
         // 1. Validate command
         command.validate()?;
+        let idempotency_key = command.idempotency_key_at(chrono::Utc::now());
         
         // 2. Convert command to domain objects
         let amount = Amount::new(command.amount_value, command.amount_currency)?;
         let payment_method = self.convert_payment_method_dto(command.payment_method)?;
         let purpose = self.convert_payment_purpose_dto(command.purpose)?;
         let metadata = self.convert_payment_metadata_dto(command.metadata)?;
-        let platform_fee_percentage = FeePercentage::new(5.0)?; // TODO: Get from config
-        
-        // 3. Check for idempotency
-        if let Some(idempotency_key) = &command.idempotency_key {
-            if let Some(existing_payment) = self.application_service.find_by_idempotency_key(idempotency_key).await? {
-                return Ok(InitiatePaymentResult {
-                    payment_id: *existing_payment.payment().id().value(),
-                    status: format!("{:?}", existing_payment.payment().status()),
-                    net_amount: existing_payment.payment().net_amount().value(),
-                    platform_fee: existing_payment.payment().platform_fee().map(|f| f.value()).unwrap_or(0.0),
-                    created_at: existing_payment.payment().created_at(),
-                });
-            }
+        let platform_fee_percentage = FeePercentage::new(
+            platform_fee_percentage_from_env().to_f64().unwrap_or_default(),
+        )?;
+        
+        // 3. Check for idempotency: a retry gets the payment the first request created
+        if let Some(existing_payment) = self.application_service.find_by_idempotency_key(&idempotency_key).await? {
+            return replayed_payment(command.payer_id, command.payee_id, &amount, &existing_payment);
         }
         
         // 4. Create payment aggregate
@@ -112,13 +229,15 @@ impl PaymentCommandHandler for PaymentCommandHandlerImpl {
             purpose,
             platform_fee_percentage,
             metadata,
-        )?;
+        )?
+        .with_idempotency_key(idempotency_key);
         
         // 5. Perform fraud check
         let fraud_result = self.fraud_detection_service.analyze_payment(&payment_aggregate).await?;
         match fraud_result.action_required {
             FraudAction::Block => {
-                return Err(AppError::FraudDetected("Payment blocked due to fraud detection".to_string()));
+                self.publish_fraud_detected(&payment_aggregate, &fraud_result).await;
+                return Err(fraud_result.fraud_error());
             }
             FraudAction::RequireAdditionalVerification => {
                 return Err(AppError::AdditionalVerificationRequired);
@@ -126,8 +245,19 @@ impl PaymentCommandHandler for PaymentCommandHandlerImpl {
             _ => {}
         }
         
-        // 6. Save payment
-        self.payment_repository.save(&payment_aggregate).await?;
+        // 6. Save payment. Otra petición con la misma clave pudo guardarse entre
+        // la comprobación y el insert: el índice único lo detecta
+        match self.payment_repository.save(&payment_aggregate).await {
+            Ok(()) => {}
+            Err(AppError::DuplicatePayment { existing_id }) => {
+                let existing_payment = self.payment_repository
+                    .find_by_id(&PaymentId::from_uuid(existing_id))
+                    .await?
+                    .ok_or(AppError::DuplicatePayment { existing_id })?;
+                return replayed_payment(command.payer_id, command.payee_id, &amount, &existing_payment);
+            }
+            Err(e) => return Err(e),
+        }
         
         // 7. Return result
         Ok(InitiatePaymentResult {
@@ -136,6 +266,7 @@ impl PaymentCommandHandler for PaymentCommandHandlerImpl {
             net_amount: payment_aggregate.payment().net_amount().value(),
             platform_fee: payment_aggregate.payment().platform_fee().map(|f| f.value()).unwrap_or(0.0),
             created_at: payment_aggregate.payment().created_at(),
+            replayed: false,
         })
     }
     
@@ -159,8 +290,10 @@ impl PaymentCommandHandler for PaymentCommandHandlerImpl {
         let processing_time_ms = start_time.elapsed().as_millis() as u64;
         
         // 4. Update payment status based on result
-        if processing_result.success {
-            payment_aggregate.complete_payment(processing_result.blockchain_hash)?;
+        if processing_result.awaiting_confirmation {
+            // Depósito pendiente: el gateway completará o expirará el pago
+        } else if processing_result.success {
+            payment_aggregate.complete_payment(processing_result.blockchain_hash.clone())?;
         } else {
             payment_aggregate.fail_payment(
                 "PROCESSING_FAILED".to_string(),
@@ -172,7 +305,9 @@ impl PaymentCommandHandler for PaymentCommandHandlerImpl {
         self.payment_repository.save(&payment_aggregate).await?;
         
         // 6. Send notifications
-        if processing_result.success {
+        if processing_result.awaiting_confirmation {
+            // Se notificará al confirmarse el depósito
+        } else if processing_result.success {
             self.notification_service.send_payment_completed_notification(&payment_aggregate).await?;
         } else {
             self.notification_service.send_payment_failed_notification(&payment_aggregate, "Processing failed").await?;
@@ -205,8 +340,10 @@ impl PaymentCommandHandler for PaymentCommandHandlerImpl {
         // 3. Complete payment
         payment_aggregate.complete_payment(blockchain_hash)?;
         
-        // 4. Save and notify
+        // 4. Save, capture the exchange rate and notify
         self.payment_repository.save(&payment_aggregate).await?;
+        self.capture_exchange_rate(&payment_aggregate).await;
+        self.notify_webhooks("payment.completed", &payment_aggregate);
         self.notification_service.send_payment_completed_notification(&payment_aggregate).await?;
         
         Ok(ProcessPaymentResult {
@@ -231,6 +368,7 @@ impl PaymentCommandHandler for PaymentCommandHandlerImpl {
         
         // 3. Save and notify
         self.payment_repository.save(&payment_aggregate).await?;
+        self.notify_webhooks("payment.failed", &payment_aggregate);
         self.notification_service.send_payment_failed_notification(&payment_aggregate, &command.error_message).await?;
         
         Ok(ProcessPaymentResult {
@@ -253,8 +391,9 @@ impl PaymentCommandHandler for PaymentCommandHandlerImpl {
         // 2. Cancel payment
         payment_aggregate.cancel_payment(command.reason)?;
         
-        // 3. Save
+        // 3. Save and notify integrations
         self.payment_repository.save(&payment_aggregate).await?;
+        self.notify_webhooks("payment.cancelled", &payment_aggregate);
         
         Ok(ProcessPaymentResult {
             payment_id: command.payment_id,
@@ -320,7 +459,8 @@ impl PaymentCommandHandler for PaymentCommandHandlerImpl {
         self.payment_repository.save(&original_payment).await?;
         self.payment_repository.save(&refund_payment).await?;
         
-        // 6. Send notification
+        // 6. Send notifications
+        self.notify_webhooks("payment.refunded", &original_payment);
         self.notification_service.send_refund_notification(&original_payment, &refund_amount).await?;
         
         Ok(crate::bounded_contexts::payment::application::commands::RefundResult {
@@ -402,6 +542,13 @@ impl PaymentCommandHandlerImpl {
                     Err(AppError::InvalidInput("NFT purchase details required".to_string()))
                 }
             }
+            "SongPurchase" => {
+                if let Some(song_id) = dto.song_id {
+                    Ok(PaymentPurpose::SongPurchase { song_id })
+                } else {
+                    Err(AppError::InvalidInput("Song purchase details required".to_string()))
+                }
+            }
             "SharePurchase" => {
                 if let (Some(contract_id), Some(ownership_percentage)) = (dto.contract_id, dto.ownership_percentage) {
                     Ok(PaymentPurpose::SharePurchase { contract_id, ownership_percentage })
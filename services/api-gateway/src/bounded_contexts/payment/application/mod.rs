@@ -3,9 +3,29 @@ pub mod queries;
 pub mod handlers;
 pub mod services;
 pub mod dto;
+pub mod refund_service;
+pub mod royalty_distribution_service;
+pub mod artist_payout_service;
+pub mod royalty_advance_service;
+pub mod payment_statistics_service;
+pub mod currency_conversion;
 
 pub use commands::*;
 pub use queries::*;
 pub use handlers::*;
 pub use services::*;
-pub use dto::*; 
\ No newline at end of file
+pub use dto::*;
+pub use refund_service::{RefundService, RefundGatewayExecutor, RefundExecution};
+pub use royalty_distribution_service::{
+    RoyaltyDistributionService, SongRoyaltySettingsProvider, RoyaltyPayoutScheduler,
+    DistributeSongRoyalties, RoyaltyDistributionOutcome,
+};
+pub use artist_payout_service::{
+    ArtistPayoutService, ArtistPayoutJob, WalletClient, WalletTransfer, TransferReceipt,
+    TransferStatus, PayoutCycleReport,
+};
+pub use royalty_advance_service::RoyaltyAdvanceService;
+pub use payment_statistics_service::{
+    PaymentStatisticsService, PaymentStatisticsQuery, PaymentStatisticsCache, DEFAULT_STATISTICS_DAYS,
+};
+pub use currency_conversion::CurrencyConverter;
@@ -0,0 +1,169 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::domain::{
+    repository::PaymentStatisticsRepository,
+    statistics::{PaymentStatisticsReport, StatisticsPeriod},
+};
+
+/// Window used when no dates are given; the only one that is cached
+pub const DEFAULT_STATISTICS_DAYS: i64 = 30;
+
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// Statistics for a date range. Without dates it covers the last 30 days.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PaymentStatisticsQuery {
+    pub start_date: Option<DateTime<Utc>>,
+    pub end_date: Option<DateTime<Utc>>,
+    /// Restrict to the payments received by this artist
+    pub artist_id: Option<Uuid>,
+}
+
+impl PaymentStatisticsQuery {
+    fn is_default_window(&self) -> bool {
+        self.start_date.is_none() && self.end_date.is_none()
+    }
+
+    /// Validated period; a missing start defaults to 30 days before the end
+    pub fn period(&self, now: DateTime<Utc>) -> Result<StatisticsPeriod, AppError> {
+        let end = self.end_date.unwrap_or(now);
+        let start = self.start_date.unwrap_or(end - chrono::Duration::days(DEFAULT_STATISTICS_DAYS));
+        StatisticsPeriod::new(start, end)
+    }
+
+    fn cache_key(&self) -> String {
+        match self.artist_id {
+            Some(artist_id) => format!("payments:statistics:{}d:artist:{}", DEFAULT_STATISTICS_DAYS, artist_id),
+            None => format!("payments:statistics:{}d:platform", DEFAULT_STATISTICS_DAYS),
+        }
+    }
+}
+
+/// Short-lived cache for statistics reports. Failures are logged by the
+/// implementation and treated as a miss; they never fail the request.
+#[async_trait]
+pub trait PaymentStatisticsCache: Send + Sync {
+    async fn get(&self, key: &str) -> Option<PaymentStatisticsReport>;
+    async fn put(&self, key: &str, report: &PaymentStatisticsReport, ttl: Duration);
+}
+
+pub struct PaymentStatisticsService {
+    repository: Arc<dyn PaymentStatisticsRepository>,
+    cache: Option<Arc<dyn PaymentStatisticsCache>>,
+}
+
+impl PaymentStatisticsService {
+    pub fn new(repository: Arc<dyn PaymentStatisticsRepository>) -> Self {
+        Self { repository, cache: None }
+    }
+
+    /// Cachear la consulta por defecto (últimos 30 días)
+    pub fn with_cache(mut self, cache: Arc<dyn PaymentStatisticsCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub async fn statistics(&self, query: &PaymentStatisticsQuery) -> Result<PaymentStatisticsReport, AppError> {
+        let period = query.period(Utc::now())?;
+
+        let cache = self.cache.as_ref().filter(|_| query.is_default_window());
+        if let Some(cache) = cache {
+            if let Some(report) = cache.get(&query.cache_key()).await {
+                return Ok(report);
+            }
+        }
+
+        let rows = self.repository.aggregate(&period, query.artist_id).await?;
+        let report = PaymentStatisticsReport::from_rows(&period, query.artist_id, &rows);
+
+        if let Some(cache) = cache {
+            cache.put(&query.cache_key(), &report, CACHE_TTL).await;
+        }
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use crate::bounded_contexts::payment::domain::{
+        repository::PaymentRepositoryResult,
+        statistics::PaymentStatisticsRow,
+    };
+
+    #[derive(Default)]
+    struct CountingRepository {
+        calls: Mutex<Vec<(StatisticsPeriod, Option<Uuid>)>>,
+    }
+
+    #[async_trait]
+    impl PaymentStatisticsRepository for CountingRepository {
+        async fn aggregate(&self, period: &StatisticsPeriod, payee_id: Option<Uuid>) -> PaymentRepositoryResult<Vec<PaymentStatisticsRow>> {
+            self.calls.lock().unwrap().push((period.clone(), payee_id));
+            Ok(vec![])
+        }
+    }
+
+    #[derive(Default)]
+    struct MemoryCache {
+        entries: Mutex<HashMap<String, PaymentStatisticsReport>>,
+    }
+
+    #[async_trait]
+    impl PaymentStatisticsCache for MemoryCache {
+        async fn get(&self, key: &str) -> Option<PaymentStatisticsReport> {
+            self.entries.lock().unwrap().get(key).cloned()
+        }
+        async fn put(&self, key: &str, report: &PaymentStatisticsReport, _ttl: Duration) {
+            self.entries.lock().unwrap().insert(key.to_string(), report.clone());
+        }
+    }
+
+    #[tokio::test]
+    async fn only_the_default_window_is_cached() {
+        let repository = Arc::new(CountingRepository::default());
+        let service = PaymentStatisticsService::new(repository.clone()).with_cache(Arc::new(MemoryCache::default()));
+
+        let default_query = PaymentStatisticsQuery::default();
+        service.statistics(&default_query).await.unwrap();
+        service.statistics(&default_query).await.unwrap();
+        assert_eq!(repository.calls.lock().unwrap().len(), 1);
+
+        let explicit = PaymentStatisticsQuery {
+            start_date: Some(Utc::now() - chrono::Duration::days(7)),
+            ..Default::default()
+        };
+        service.statistics(&explicit).await.unwrap();
+        service.statistics(&explicit).await.unwrap();
+        assert_eq!(repository.calls.lock().unwrap().len(), 3);
+
+        // Cada artista tiene su propia entrada
+        let artist_id = Uuid::new_v4();
+        service.statistics(&PaymentStatisticsQuery { artist_id: Some(artist_id), ..Default::default() }).await.unwrap();
+        assert_eq!(repository.calls.lock().unwrap().last().unwrap().1, Some(artist_id));
+    }
+
+    #[tokio::test]
+    async fn invalid_range_is_rejected_before_querying() {
+        let repository = Arc::new(CountingRepository::default());
+        let service = PaymentStatisticsService::new(repository.clone());
+        let now = Utc::now();
+
+        let result = service.statistics(&PaymentStatisticsQuery {
+            start_date: Some(now),
+            end_date: Some(now - chrono::Duration::days(1)),
+            artist_id: None,
+        }).await;
+
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+        assert!(repository.calls.lock().unwrap().is_empty());
+    }
+}
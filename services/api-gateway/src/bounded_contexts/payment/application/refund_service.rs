@@ -0,0 +1,243 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::domain::{
+    aggregates::PaymentAggregate,
+    refunds::{RefundLedger, RefundStatus, RefundTransaction, RoyaltyReversal},
+    repository::{
+        PaymentExchangeRateRepository, PaymentRepository, RefundTransactionRepository, RoyaltyReversalRepository,
+    },
+    value_objects::{PaymentId, PaymentPurpose},
+    webhooks::PaymentWebhookNotifier,
+};
+
+/// Outcome of handing a refund to the gateway that charged the payment
+#[derive(Debug, Clone, PartialEq)]
+pub enum RefundExecution {
+    /// Money is back with the payer
+    Completed { gateway_refund_id: Option<String> },
+    /// Accepted by the gateway, final result arrives later (webhook)
+    Submitted { gateway_refund_id: String },
+    /// Crypto refund waiting for an operator to send the on-chain transfer
+    QueuedForPayout { payout_id: String },
+}
+
+/// Port to the payment gateways for executing refunds
+#[async_trait]
+pub trait RefundGatewayExecutor: Send + Sync {
+    /// Name of the gateway that will refund `payment`
+    fn gateway_for(&self, payment: &PaymentAggregate) -> Result<&'static str, AppError>;
+
+    async fn execute(&self, payment: &PaymentAggregate, refund: &RefundTransaction) -> Result<RefundExecution, AppError>;
+
+    /// Record that the manual payout of a queued refund was sent (`tx_hash` on-chain)
+    async fn confirm_payout(&self, refund: &RefundTransaction, tx_hash: &str) -> Result<(), AppError>;
+}
+
+/// Refund workflow: validation, gateway execution, accounting and royalty reversal
+pub struct RefundService {
+    payment_repository: Arc<dyn PaymentRepository>,
+    refund_repository: Arc<dyn RefundTransactionRepository>,
+    reversal_repository: Arc<dyn RoyaltyReversalRepository>,
+    executor: Arc<dyn RefundGatewayExecutor>,
+    exchange_rates: Option<Arc<dyn PaymentExchangeRateRepository>>,
+    webhooks: Option<Arc<dyn PaymentWebhookNotifier>>,
+}
+
+impl RefundService {
+    pub fn new(
+        payment_repository: Arc<dyn PaymentRepository>,
+        refund_repository: Arc<dyn RefundTransactionRepository>,
+        reversal_repository: Arc<dyn RoyaltyReversalRepository>,
+        executor: Arc<dyn RefundGatewayExecutor>,
+    ) -> Self {
+        Self {
+            payment_repository,
+            refund_repository,
+            reversal_repository,
+            executor,
+            exchange_rates: None,
+            webhooks: None,
+        }
+    }
+
+    /// Tipos de cambio capturados al completar los pagos. El reembolso siempre
+    /// se hace en la moneda del pago; el tipo capturado solo sirve para llevar
+    /// el importe a la moneda base en la que se repartieron los royalties.
+    pub fn with_exchange_rates(mut self, exchange_rates: Arc<dyn PaymentExchangeRateRepository>) -> Self {
+        self.exchange_rates = Some(exchange_rates);
+        self
+    }
+
+    /// Avisar a los webhooks suscritos a `payment.refunded` de cada reembolso completado
+    pub fn with_webhooks(mut self, webhooks: Arc<dyn PaymentWebhookNotifier>) -> Self {
+        self.webhooks = Some(webhooks);
+        self
+    }
+
+    /// Refund `amount` of a completed payment (`None` = whatever is left)
+    pub async fn refund_payment(
+        &self,
+        payment_id: Uuid,
+        amount: Option<f64>,
+        reason: String,
+        requested_by: Uuid,
+    ) -> Result<RefundTransaction, AppError> {
+        let mut payment = self.load_payment(payment_id).await?;
+        let refunds = self.refund_repository.find_by_payment(payment_id).await?;
+        let ledger = RefundLedger::new(payment.payment().amount().clone(), refunds);
+
+        let refund_amount = ledger.authorize(payment.payment().status(), amount)?;
+        let gateway = self.executor.gateway_for(&payment)?;
+        let mut refund = RefundTransaction::new(payment_id, refund_amount, reason, gateway.to_string(), requested_by);
+
+        // Reservar el importe antes de llamar al gateway: una segunda petición
+        // concurrente ya no cabe en el saldo reembolsable
+        self.refund_repository
+            .create(&refund, payment.payment().amount().value())
+            .await?;
+
+        match self.executor.execute(&payment, &refund).await {
+            Ok(RefundExecution::Completed { gateway_refund_id }) => {
+                refund.complete(gateway_refund_id)?;
+                self.refund_repository.update(&refund).await?;
+                self.apply_completed_refund(&mut payment, &ledger, &refund).await?;
+            }
+            Ok(RefundExecution::Submitted { gateway_refund_id }) => {
+                refund.mark_processing(Some(gateway_refund_id))?;
+                self.refund_repository.update(&refund).await?;
+            }
+            Ok(RefundExecution::QueuedForPayout { payout_id }) => {
+                refund.await_payout(payout_id)?;
+                self.refund_repository.update(&refund).await?;
+            }
+            Err(error) => {
+                tracing::warn!("Refund {} for payment {} failed at {}: {}", refund.id, payment_id, gateway, error);
+                refund.fail(error.to_string())?;
+                self.refund_repository.update(&refund).await?;
+                return Err(error);
+            }
+        }
+
+        tracing::info!(
+            "Refund {} of {} for payment {} is {}",
+            refund.id,
+            refund.amount.value(),
+            payment_id,
+            refund.status
+        );
+        Ok(refund)
+    }
+
+    /// Settle a refund that finished asynchronously (gateway webhook or manual payout sent)
+    pub async fn complete_refund(&self, refund_id: Uuid, gateway_refund_id: Option<String>) -> Result<RefundTransaction, AppError> {
+        let mut refund = self.load_refund(refund_id).await?;
+        let mut payment = self.load_payment(refund.payment_id).await?;
+        let ledger = RefundLedger::new(
+            payment.payment().amount().clone(),
+            self.refund_repository.find_by_payment(refund.payment_id).await?,
+        );
+
+        refund.complete(gateway_refund_id)?;
+        self.refund_repository.update(&refund).await?;
+        self.apply_completed_refund(&mut payment, &ledger, &refund).await?;
+        Ok(refund)
+    }
+
+    /// An operator sent the on-chain transfer of a queued crypto refund
+    pub async fn confirm_payout(&self, refund_id: Uuid, tx_hash: String) -> Result<RefundTransaction, AppError> {
+        let refund = self.load_refund(refund_id).await?;
+        if refund.status != RefundStatus::AwaitingPayout {
+            return Err(AppError::InvalidState(format!(
+                "Refund {} is not awaiting a payout (status: {})",
+                refund_id, refund.status
+            )));
+        }
+        self.executor.confirm_payout(&refund, &tx_hash).await?;
+        self.complete_refund(refund_id, Some(tx_hash)).await
+    }
+
+    /// Mark an in-flight refund as failed, releasing its amount
+    pub async fn fail_refund(&self, refund_id: Uuid, reason: String) -> Result<RefundTransaction, AppError> {
+        let mut refund = self.load_refund(refund_id).await?;
+        if refund.status == RefundStatus::Completed {
+            return Err(AppError::InvalidState(format!("Refund {} already completed", refund_id)));
+        }
+        refund.fail(reason)?;
+        self.refund_repository.update(&refund).await?;
+        Ok(refund)
+    }
+
+    pub async fn refunds_for_payment(&self, payment_id: Uuid) -> Result<Vec<RefundTransaction>, AppError> {
+        self.refund_repository.find_by_payment(payment_id).await
+    }
+
+    /// Accounting once the money is back: payment status and royalty claw-back
+    async fn apply_completed_refund(
+        &self,
+        payment: &mut PaymentAggregate,
+        ledger: &RefundLedger,
+        refund: &RefundTransaction,
+    ) -> Result<(), AppError> {
+        let total_refunded = ledger.completed_after(refund);
+        payment.apply_refund(refund.amount.clone(), total_refunded)?;
+        self.payment_repository.update(payment).await?;
+        if let Some(webhooks) = &self.webhooks {
+            webhooks.notify("payment.refunded", payment);
+        }
+
+        // Solo las compras de canciones entran en los ingresos que reparten las ejecuciones
+        let PaymentPurpose::SongPurchase { song_id } = payment.payment().purpose() else {
+            return Ok(());
+        };
+        let earned_at = payment.payment().completed_at().unwrap_or_else(|| payment.payment().created_at());
+
+        let refunded_base_value = self.base_value_of(refund).await?;
+        let reversals: Vec<RoyaltyReversal> = self
+            .reversal_repository
+            .find_payouts_for_revenue(*song_id, earned_at)
+            .await?
+            .iter()
+            .filter_map(|basis| RoyaltyReversal::compute(basis, refund.id, refunded_base_value))
+            .collect();
+
+        if !reversals.is_empty() {
+            self.reversal_repository.save_all(&reversals).await?;
+            tracing::info!(
+                "Reversed {} royalty payout(s) for refund {}",
+                reversals.len(),
+                refund.id
+            );
+        }
+        Ok(())
+    }
+
+    /// Refunded amount in the base currency, at the rate captured when the
+    /// payment completed. Payments without a captured rate were made in the
+    /// base currency.
+    async fn base_value_of(&self, refund: &RefundTransaction) -> Result<f64, AppError> {
+        let Some(exchange_rates) = &self.exchange_rates else {
+            return Ok(refund.amount.value());
+        };
+        match exchange_rates.find_by_payment(refund.payment_id).await? {
+            Some(captured) => Ok(captured.amount.base_value_of(&refund.amount)?.value()),
+            None => Ok(refund.amount.value()),
+        }
+    }
+
+    async fn load_payment(&self, payment_id: Uuid) -> Result<PaymentAggregate, AppError> {
+        self.payment_repository
+            .find_by_id(&PaymentId::from_uuid(payment_id))
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Payment {} not found", payment_id)))
+    }
+
+    async fn load_refund(&self, refund_id: Uuid) -> Result<RefundTransaction, AppError> {
+        self.refund_repository
+            .find_by_id(refund_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Refund {} not found", refund_id)))
+    }
+}
@@ -0,0 +1,268 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventBus};
+use crate::bounded_contexts::payment::domain::{
+    repository::RoyaltyAdvanceRepository,
+    royalty_advances::{AdvancePolicy, RoyaltyAdvance},
+    value_objects::Currency,
+};
+use crate::shared::domain::errors::AppError;
+
+/// Royalty advances: eligibility from the royalty history, and repayment from
+/// the royalties credited to the artist afterwards
+pub struct RoyaltyAdvanceService {
+    repository: Arc<dyn RoyaltyAdvanceRepository>,
+    event_bus: Option<Arc<dyn EventBus>>,
+    policy: AdvancePolicy,
+}
+
+impl RoyaltyAdvanceService {
+    pub fn new(repository: Arc<dyn RoyaltyAdvanceRepository>, policy: AdvancePolicy) -> Self {
+        Self { repository, event_bus: None, policy }
+    }
+
+    /// Publicar `RoyaltyAdvanceRepaid` cuando un adelanto queda devuelto
+    pub fn with_event_bus(mut self, event_bus: Arc<dyn EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Grant an advance if the projected monthly royalties (average of the
+    /// last complete months) exceed the policy's coverage of the monthly
+    /// repayment. The amount is credited to the artist balance.
+    pub async fn request_advance(
+        &self,
+        artist_id: Uuid,
+        advance_amount: Decimal,
+        repayment_rate: f64,
+        currency: Currency,
+        now: DateTime<Utc>,
+    ) -> Result<RoyaltyAdvance, AppError> {
+        let advance = RoyaltyAdvance::new(artist_id, advance_amount, repayment_rate, currency)?;
+        if self.repository.find_active(artist_id).await?.is_some() {
+            return Err(AppError::ConflictError(format!("Artist {} is still repaying an advance", artist_id)));
+        }
+
+        let (since, until) = self.policy.history_window(now);
+        let monthly: Vec<f64> = self
+            .repository
+            .monthly_royalties(artist_id, &advance.currency, since, until)
+            .await?
+            .into_iter()
+            .map(|(_, total)| total)
+            .collect();
+        let projected = self.policy.check_eligibility(advance.advance_amount, &monthly)?;
+
+        self.repository.create(&advance).await?;
+        tracing::info!(
+            "Granted royalty advance {} of {} {:?} to artist {} (projected {} per month)",
+            advance.id, advance.advance_amount, advance.currency, artist_id, projected
+        );
+        Ok(advance)
+    }
+
+    pub async fn advances(&self, artist_id: Uuid) -> Result<Vec<RoyaltyAdvance>, AppError> {
+        self.repository.find_by_artist(artist_id).await
+    }
+
+    /// Withhold the repayment of the artist's active advance from a royalty
+    /// credit of `amount` coming from `source_id`. Returns what is left to
+    /// credit to the balance.
+    pub async fn withhold_repayment(
+        &self,
+        artist_id: Uuid,
+        source_id: Uuid,
+        amount: f64,
+        currency: &Currency,
+        now: DateTime<Utc>,
+    ) -> Result<f64, AppError> {
+        // Reintento de un crédito ya retenido (incluso si el adelanto ya se devolvió)
+        if let Some(recorded) = self.repository.find_repayment(source_id).await? {
+            return Ok(recorded.remainder_of(amount));
+        }
+        let Some(mut advance) = self.repository.find_active(artist_id).await? else {
+            return Ok(amount);
+        };
+        let Some(repayment) = advance.withhold(source_id, amount, currency, now)? else {
+            return Ok(amount);
+        };
+
+        let recorded = self.repository.record_repayment(&advance, &repayment).await?;
+        // Un intento concurrente retuvo esta fuente antes: vale lo que guardó
+        if recorded != repayment {
+            return Ok(recorded.remainder_of(amount));
+        }
+        if !advance.is_active() {
+            self.publish_repaid(&advance).await;
+        }
+        Ok(repayment.remainder_of(amount))
+    }
+
+    /// Royalty payouts taken back (voided run): their repayments are undone
+    pub async fn revert_repayments(&self, source_ids: &[Uuid]) -> Result<(), AppError> {
+        self.repository.revert_repayments(source_ids).await
+    }
+
+    async fn publish_repaid(&self, advance: &RoyaltyAdvance) {
+        tracing::info!("Royalty advance {} of artist {} repaid", advance.id, advance.artist_id);
+        let Some(event_bus) = &self.event_bus else {
+            return;
+        };
+        let event = DomainEvent::RoyaltyAdvanceRepaid {
+            advance_id: advance.id,
+            artist_id: advance.artist_id,
+            advance_amount: advance.advance_amount.to_f64().unwrap_or_default(),
+            currency: format!("{:?}", advance.currency),
+            occurred_at: advance.repaid_at.unwrap_or_else(Utc::now),
+        };
+        // La devolución ya está guardada: un fallo del bus no la deshace
+        if let Err(e) = event_bus.publish(event).await {
+            tracing::error!("Failed to publish RoyaltyAdvanceRepaid for {}: {}", advance.id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use chrono::TimeZone;
+    use std::sync::Mutex;
+    use crate::bounded_contexts::orchestrator::EventHandler;
+    use crate::bounded_contexts::payment::domain::repository::PaymentRepositoryResult;
+    use crate::bounded_contexts::payment::domain::royalty_advances::{AdvanceRepayment, RoyaltyAdvanceStatus};
+
+    #[derive(Default)]
+    struct InMemoryAdvances {
+        advances: Mutex<Vec<RoyaltyAdvance>>,
+        repayments: Mutex<Vec<AdvanceRepayment>>,
+        monthly: Mutex<Vec<(DateTime<Utc>, f64)>>,
+    }
+
+    #[async_trait]
+    impl RoyaltyAdvanceRepository for InMemoryAdvances {
+        async fn create(&self, advance: &RoyaltyAdvance) -> PaymentRepositoryResult<()> {
+            self.advances.lock().unwrap().push(advance.clone());
+            Ok(())
+        }
+        async fn find_active(&self, artist_id: Uuid) -> PaymentRepositoryResult<Option<RoyaltyAdvance>> {
+            Ok(self.advances.lock().unwrap().iter().find(|a| a.artist_id == artist_id && a.is_active()).cloned())
+        }
+        async fn find_by_artist(&self, artist_id: Uuid) -> PaymentRepositoryResult<Vec<RoyaltyAdvance>> {
+            Ok(self.advances.lock().unwrap().iter().filter(|a| a.artist_id == artist_id).cloned().collect())
+        }
+        async fn monthly_royalties(
+            &self,
+            _artist_id: Uuid,
+            _currency: &Currency,
+            since: DateTime<Utc>,
+            until: DateTime<Utc>,
+        ) -> PaymentRepositoryResult<Vec<(DateTime<Utc>, f64)>> {
+            Ok(self.monthly.lock().unwrap().iter().filter(|(month, _)| *month >= since && *month < until).cloned().collect())
+        }
+        async fn find_repayment(&self, source_id: Uuid) -> PaymentRepositoryResult<Option<AdvanceRepayment>> {
+            Ok(self.repayments.lock().unwrap().iter().find(|r| r.source_id == source_id).cloned())
+        }
+        async fn record_repayment(
+            &self,
+            advance: &RoyaltyAdvance,
+            repayment: &AdvanceRepayment,
+        ) -> PaymentRepositoryResult<AdvanceRepayment> {
+            let mut repayments = self.repayments.lock().unwrap();
+            if let Some(existing) = repayments.iter().find(|r| r.source_id == repayment.source_id) {
+                return Ok(existing.clone());
+            }
+            repayments.push(repayment.clone());
+            let mut advances = self.advances.lock().unwrap();
+            if let Some(stored) = advances.iter_mut().find(|a| a.id == advance.id) {
+                *stored = advance.clone();
+            }
+            Ok(repayment.clone())
+        }
+        async fn revert_repayments(&self, _source_ids: &[Uuid]) -> PaymentRepositoryResult<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingEventBus {
+        published: Mutex<Vec<DomainEvent>>,
+    }
+
+    #[async_trait]
+    impl EventBus for RecordingEventBus {
+        async fn publish(&self, event: DomainEvent) -> Result<(), AppError> {
+            self.published.lock().unwrap().push(event);
+            Ok(())
+        }
+        async fn subscribe(&self, _event_type: &str, _handler: Arc<dyn EventHandler>) -> Result<(), AppError> {
+            Ok(())
+        }
+    }
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 5, 20, 12, 0, 0).unwrap()
+    }
+
+    fn month(m: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, m, 1, 0, 0, 0).unwrap()
+    }
+
+    #[tokio::test]
+    async fn advance_requires_three_months_of_history() {
+        let repository = Arc::new(InMemoryAdvances::default());
+        // Mayo es el mes en curso y no cuenta
+        *repository.monthly.lock().unwrap() = vec![(month(3), 500.0), (month(4), 500.0), (month(5), 500.0)];
+        let service = RoyaltyAdvanceService::new(repository.clone(), AdvancePolicy::default());
+        let artist_id = Uuid::new_v4();
+
+        let result = service.request_advance(artist_id, Decimal::new(1200, 0), 0.2, Currency::USD, now()).await;
+        assert!(matches!(result, Err(AppError::InsufficientRoyaltyHistory(_))));
+
+        repository.monthly.lock().unwrap().push((month(2), 500.0));
+        let advance = service.request_advance(artist_id, Decimal::new(1200, 0), 0.2, Currency::USD, now()).await.unwrap();
+        assert_eq!(advance.outstanding_balance, Decimal::new(1200, 0));
+
+        let second = service.request_advance(artist_id, Decimal::new(100, 0), 0.2, Currency::USD, now()).await;
+        assert!(matches!(second, Err(AppError::ConflictError(_))));
+    }
+
+    #[tokio::test]
+    async fn royalties_repay_the_advance_once_per_source_and_publish_when_repaid() {
+        let repository = Arc::new(InMemoryAdvances::default());
+        let event_bus = Arc::new(RecordingEventBus::default());
+        let service = RoyaltyAdvanceService::new(repository.clone(), AdvancePolicy::default())
+            .with_event_bus(event_bus.clone());
+        let artist_id = Uuid::new_v4();
+        let advance = RoyaltyAdvance::new(artist_id, Decimal::new(30, 0), 0.2, Currency::USD).unwrap();
+        repository.create(&advance).await.unwrap();
+
+        let first = Uuid::new_v4();
+        assert_eq!(service.withhold_repayment(artist_id, first, 100.0, &Currency::USD, now()).await.unwrap(), 80.0);
+        // El mismo crédito otra vez no vuelve a retener
+        assert_eq!(service.withhold_repayment(artist_id, first, 100.0, &Currency::USD, now()).await.unwrap(), 80.0);
+        // Otra divisa no amortiza
+        assert_eq!(service.withhold_repayment(artist_id, Uuid::new_v4(), 100.0, &Currency::USDC, now()).await.unwrap(), 100.0);
+        assert!(event_bus.published.lock().unwrap().is_empty());
+
+        let last = Uuid::new_v4();
+        assert_eq!(service.withhold_repayment(artist_id, last, 100.0, &Currency::USD, now()).await.unwrap(), 90.0);
+        let stored = service.advances(artist_id).await.unwrap().remove(0);
+        assert_eq!(stored.outstanding_balance, Decimal::ZERO);
+        assert_eq!(stored.status, RoyaltyAdvanceStatus::Repaid);
+
+        assert_eq!(service.withhold_repayment(artist_id, Uuid::new_v4(), 100.0, &Currency::USD, now()).await.unwrap(), 100.0);
+        assert_eq!(service.withhold_repayment(artist_id, last, 100.0, &Currency::USD, now()).await.unwrap(), 90.0);
+
+        let published = event_bus.published.lock().unwrap();
+        assert_eq!(published.len(), 1);
+        assert!(matches!(
+            &published[0],
+            DomainEvent::RoyaltyAdvanceRepaid { advance_id, advance_amount, .. } if *advance_id == advance.id && *advance_amount == 30.0
+        ));
+    }
+}
@@ -0,0 +1,234 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::application::currency_conversion::CurrencyConverter;
+use crate::bounded_contexts::payment::domain::{
+    exchange::NormalizedAmount,
+    repository::RoyaltyRunRepository,
+    royalty_runs::{
+        RoyaltyDistributionConfig, RoyaltyDistributionRun, RoyaltyPayout, RoyaltyPayoutStatus,
+        RoyaltySplit, RoyaltySplitPlan, RunResolution, SongRoyaltySettings,
+    },
+    value_objects::{Amount, Currency},
+};
+
+/// Port to the music context: royalty percentage and credits of a song
+#[async_trait]
+pub trait SongRoyaltySettingsProvider: Send + Sync {
+    async fn royalty_settings(&self, song_id: Uuid) -> Result<Option<SongRoyaltySettings>, AppError>;
+}
+
+/// Port to the artist payout scheduler, which actually pays creators
+#[async_trait]
+pub trait RoyaltyPayoutScheduler: Send + Sync {
+    /// Take over the creator payouts of a run. Must be idempotent per payout
+    /// id: a retry can hand over a payout that was already accepted.
+    async fn schedule(&self, payouts: &[RoyaltyPayout]) -> Result<(), AppError>;
+
+    /// The run of these payouts was voided
+    async fn cancel(&self, payouts: &[RoyaltyPayout]) -> Result<(), AppError>;
+}
+
+/// Request to distribute a song's revenue for a period
+#[derive(Debug, Clone)]
+pub struct DistributeSongRoyalties {
+    pub song_id: Uuid,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub total_revenue: f64,
+    pub currency: Currency,
+    /// Split proposed by the caller; must agree with the song's settings
+    pub rules: Option<Vec<RoyaltySplit>>,
+    pub initiated_by: Uuid,
+}
+
+#[derive(Debug, Clone)]
+pub struct RoyaltyDistributionOutcome {
+    pub run: RoyaltyDistributionRun,
+    pub payouts: Vec<RoyaltyPayout>,
+    /// `false` when the same distribution had already been recorded
+    pub created: bool,
+}
+
+/// Royalty distribution: split derivation, (song, period) idempotency and payout hand-off
+pub struct RoyaltyDistributionService {
+    run_repository: Arc<dyn RoyaltyRunRepository>,
+    settings_provider: Arc<dyn SongRoyaltySettingsProvider>,
+    config: RoyaltyDistributionConfig,
+    payout_scheduler: Option<Arc<dyn RoyaltyPayoutScheduler>>,
+    currency_converter: Option<Arc<CurrencyConverter>>,
+}
+
+impl RoyaltyDistributionService {
+    pub fn new(
+        run_repository: Arc<dyn RoyaltyRunRepository>,
+        settings_provider: Arc<dyn SongRoyaltySettingsProvider>,
+        config: RoyaltyDistributionConfig,
+    ) -> Self {
+        Self {
+            run_repository,
+            settings_provider,
+            config,
+            payout_scheduler: None,
+            currency_converter: None,
+        }
+    }
+
+    /// Sin scheduler los pagos a artistas quedan `pending` hasta que se configure uno
+    pub fn with_payout_scheduler(mut self, payout_scheduler: Arc<dyn RoyaltyPayoutScheduler>) -> Self {
+        self.payout_scheduler = Some(payout_scheduler);
+        self
+    }
+
+    /// Revenue reported in another currency is distributed in the base
+    /// currency; without a converter it is distributed as reported
+    pub fn with_currency_converter(mut self, currency_converter: Arc<CurrencyConverter>) -> Self {
+        self.currency_converter = Some(currency_converter);
+        self
+    }
+
+    pub async fn distribute(&self, request: DistributeSongRoyalties) -> Result<RoyaltyDistributionOutcome, AppError> {
+        let settings = self
+            .settings_provider
+            .royalty_settings(request.song_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Song {} not found", request.song_id)))?;
+        let plan = RoyaltySplitPlan::derive(&settings, &self.config)?;
+
+        if let Some(rules) = request.rules {
+            let requested = RoyaltySplitPlan::new(rules)?;
+            if !requested.matches(&plan) {
+                return Err(AppError::ValidationError(format!(
+                    "Distribution rules do not match the royalty settings of song {}",
+                    request.song_id
+                )));
+            }
+        }
+
+        let reported = Amount::new(request.total_revenue, request.currency)?;
+        let revenue = match &self.currency_converter {
+            Some(converter) => converter.normalize(&reported).await?,
+            None => NormalizedAmount::in_base(reported, Utc::now()),
+        };
+        let run = RoyaltyDistributionRun::new(
+            request.song_id,
+            request.period_start,
+            request.period_end,
+            revenue.base.clone(),
+            plan,
+            request.initiated_by,
+        )?
+        .with_conversion(&revenue);
+
+        let existing = self
+            .run_repository
+            .find_overlapping(run.song_id, run.period_start, run.period_end)
+            .await?;
+        if let RunResolution::AlreadyDistributed(previous) = run.resolve_against(&existing)? {
+            let payouts = self.run_repository.find_payouts(previous.id).await?;
+            return Ok(RoyaltyDistributionOutcome { run: previous, payouts, created: false });
+        }
+
+        let mut payouts = run.payouts()?;
+        self.run_repository.create(&run, &payouts).await?;
+        self.schedule_pending(&mut payouts).await;
+
+        tracing::info!(
+            "Royalty run {} distributed {} {:?} of song {} to {} recipient(s)",
+            run.id,
+            run.total_revenue.value(),
+            run.total_revenue.currency(),
+            run.song_id,
+            payouts.len()
+        );
+        Ok(RoyaltyDistributionOutcome { run, payouts, created: true })
+    }
+
+    /// Void a run (admin only, checked by the caller) so its period can be distributed again
+    pub async fn void_run(&self, run_id: Uuid, voided_by: Uuid, reason: String) -> Result<RoyaltyDistributionRun, AppError> {
+        let mut run = self.load_run(run_id).await?;
+        run.void(voided_by, reason)?;
+
+        let scheduled: Vec<RoyaltyPayout> = self
+            .run_repository
+            .find_payouts(run_id)
+            .await?
+            .into_iter()
+            .filter(|p| p.status == RoyaltyPayoutStatus::Scheduled)
+            .collect();
+        if let (Some(scheduler), false) = (&self.payout_scheduler, scheduled.is_empty()) {
+            scheduler.cancel(&scheduled).await?;
+        }
+
+        self.run_repository.void(&run).await?;
+        tracing::info!("Royalty run {} of song {} voided by {}", run.id, run.song_id, voided_by);
+        Ok(run)
+    }
+
+    /// Retry the hand-off of payouts that are still pending (scheduler was down)
+    pub async fn reschedule_pending(&self, run_id: Uuid) -> Result<Vec<RoyaltyPayout>, AppError> {
+        let run = self.load_run(run_id).await?;
+        if !run.is_active() {
+            return Err(AppError::InvalidState(format!("Royalty run {} is voided", run_id)));
+        }
+        let mut payouts = self.run_repository.find_payouts(run_id).await?;
+        self.schedule_pending(&mut payouts).await;
+        Ok(payouts)
+    }
+
+    pub async fn get_run(&self, run_id: Uuid) -> Result<RoyaltyDistributionOutcome, AppError> {
+        let run = self.load_run(run_id).await?;
+        let payouts = self.run_repository.find_payouts(run_id).await?;
+        Ok(RoyaltyDistributionOutcome { run, payouts, created: false })
+    }
+
+    /// Hand pending creator payouts to the scheduler. The run is already
+    /// recorded, so a scheduler failure only leaves them pending for a retry.
+    async fn schedule_pending(&self, payouts: &mut [RoyaltyPayout]) {
+        let Some(scheduler) = &self.payout_scheduler else {
+            return;
+        };
+
+        let pending: Vec<RoyaltyPayout> = payouts
+            .iter()
+            .filter(|p| p.is_creator_payout() && p.status == RoyaltyPayoutStatus::Pending)
+            .cloned()
+            .collect();
+        if pending.is_empty() {
+            return;
+        }
+
+        let result = match scheduler.schedule(&pending).await {
+            Ok(()) => {
+                let ids: Vec<Uuid> = pending.iter().map(|p| p.id).collect();
+                self.run_repository
+                    .update_payout_status(&ids, RoyaltyPayoutStatus::Scheduled)
+                    .await
+            }
+            Err(error) => Err(error),
+        };
+
+        match result {
+            Ok(()) => {
+                for payout in payouts.iter_mut().filter(|p| pending.iter().any(|q| q.id == p.id)) {
+                    payout.status = RoyaltyPayoutStatus::Scheduled;
+                }
+            }
+            Err(error) => tracing::warn!(
+                "Royalty payouts of run {} left pending: {}",
+                pending[0].run_id,
+                error
+            ),
+        }
+    }
+
+    async fn load_run(&self, run_id: Uuid) -> Result<RoyaltyDistributionRun, AppError> {
+        self.run_repository
+            .find_by_id(run_id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Royalty run {} not found", run_id)))
+    }
+}
@@ -79,7 +79,9 @@ impl PaymentApplicationService {
         let processing_result = self.payment_processing_service.process_payment(&mut payment_aggregate).await?;
         
         // 6. Update status based on result
-        if processing_result.success {
+        if processing_result.awaiting_confirmation {
+            // Sigue en Processing: el gateway confirmará de forma asíncrona
+        } else if processing_result.success {
             payment_aggregate.complete_payment(processing_result.blockchain_hash)?;
             
             // 7. Send success notification
@@ -514,6 +516,7 @@ impl PaymentProcessingService for MockPaymentProcessingService {
             gateway_response: Some("Mock Gateway Success".to_string()),
             processing_time_ms: 50,
             fees_charged: Amount::new(0.0, _payment.amount().currency().clone())?,
+            awaiting_confirmation: false,
         })
     }
     
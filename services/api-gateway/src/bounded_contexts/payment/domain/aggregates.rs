@@ -126,6 +126,14 @@ impl PaymentAggregate {
         Ok(())
     }
     
+    /// Record an underpayment; the payment stays open for the shortfall
+    pub fn record_partial_payment(&mut self, received_amount: Amount) -> Result<(), AppError> {
+        let event = self.payment.record_partial_payment(received_amount)?;
+        self.add_event(Box::new(event));
+        self.version += 1;
+        Ok(())
+    }
+    
     /// Fail the payment
     pub fn fail_payment(&mut self, error_code: String, error_message: String) -> Result<(), AppError> {
         let event = self.payment.fail(error_code, error_message)?;
@@ -163,6 +171,14 @@ impl PaymentAggregate {
         self.version += 1;
         Ok(())
     }
+
+    /// Apply a settled (possibly partial) refund transaction
+    pub fn apply_refund(&mut self, refund_amount: Amount, total_refunded: f64) -> Result<(), AppError> {
+        let event = self.payment.apply_refund(refund_amount, total_refunded)?;
+        self.add_event(Box::new(event));
+        self.version += 1;
+        Ok(())
+    }
     
     /// Validate payment against business rules
     pub fn validate(&self) -> Result<Vec<String>, AppError> {
@@ -258,6 +274,12 @@ impl PaymentAggregate {
         self.cancel_payment("Cancelled by gateway".to_string())
     }
 
+    /// Key under which retries of the request that created this payment find it again
+    pub fn with_idempotency_key(mut self, idempotency_key: String) -> Self {
+        self.payment.set_idempotency_key(idempotency_key);
+        self
+    }
+
     /// Add domain event
     fn add_event(&mut self, event: Box<dyn DomainEvent>) {
         self.uncommitted_events.push(event);
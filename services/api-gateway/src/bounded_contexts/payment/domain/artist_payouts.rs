@@ -0,0 +1,444 @@
+//! Artist payouts
+//!
+//! Royalties credited to an artist land in a balance ledger, one entry per
+//! royalty payout. When the available balance reaches the configured minimum a
+//! payout reserves those entries; they are only marked settled once the
+//! transfer is confirmed, so a failed or lost transfer gives the money back to
+//! the balance instead of losing it.
+//!
+//! Crypto payouts go through `Submitting` before the transfer is sent: a
+//! payout found in that state after a crash may or may not be on-chain, and is
+//! reconciled by looking up its reference before anything is sent again.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use super::value_objects::Currency;
+
+const AMOUNT_SCALE: f64 = 1_000_000.0;
+
+fn round_amount(value: f64) -> f64 {
+    (value * AMOUNT_SCALE).round() / AMOUNT_SCALE
+}
+
+/// Solana account address: base58 encoding of a 32-byte public key
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SolanaAddress(String);
+
+impl SolanaAddress {
+    pub fn parse(address: &str) -> Result<Self, AppError> {
+        let address = address.trim();
+        match bs58::decode(address).into_vec() {
+            Ok(bytes) if bytes.len() == 32 => Ok(Self(address.to_string())),
+            _ => Err(AppError::ValidationError(format!("Invalid Solana address: {}", address))),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Where an artist's payouts are sent
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PayoutMethod {
+    /// Stripe Connect account (`acct_...`). Execution is not wired yet: payouts
+    /// to this method are opened and wait in `Pending`.
+    StripeConnect { account_id: String },
+    /// USDC on Solana
+    SolanaWallet { address: SolanaAddress },
+}
+
+impl PayoutMethod {
+    pub fn stripe_connect(account_id: &str) -> Result<Self, AppError> {
+        let account_id = account_id.trim();
+        if !account_id.starts_with("acct_") || account_id.len() <= "acct_".len() {
+            return Err(AppError::ValidationError(format!("Invalid Stripe Connect account: {}", account_id)));
+        }
+        Ok(PayoutMethod::StripeConnect { account_id: account_id.to_string() })
+    }
+
+    pub fn solana_wallet(address: &str) -> Result<Self, AppError> {
+        Ok(PayoutMethod::SolanaWallet { address: SolanaAddress::parse(address)? })
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            PayoutMethod::StripeConnect { .. } => "stripe_connect",
+            PayoutMethod::SolanaWallet { .. } => "solana_wallet",
+        }
+    }
+
+    /// Currencies this method can pay out. USD balances go out as USDC 1:1.
+    pub fn supports(&self, currency: &Currency) -> bool {
+        match self {
+            PayoutMethod::StripeConnect { .. } => matches!(currency, Currency::USD | Currency::EUR | Currency::GBP),
+            PayoutMethod::SolanaWallet { .. } => matches!(currency, Currency::USD | Currency::USDC),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LedgerEntryStatus {
+    Available,
+    /// Held by an open payout
+    Reserved,
+    /// Paid out, transfer confirmed
+    Settled,
+}
+
+impl fmt::Display for LedgerEntryStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            LedgerEntryStatus::Available => "available",
+            LedgerEntryStatus::Reserved => "reserved",
+            LedgerEntryStatus::Settled => "settled",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for LedgerEntryStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "available" => Ok(LedgerEntryStatus::Available),
+            "reserved" => Ok(LedgerEntryStatus::Reserved),
+            "settled" => Ok(LedgerEntryStatus::Settled),
+            other => Err(format!("Unknown ledger entry status: {}", other)),
+        }
+    }
+}
+
+/// Earnings credited to an artist
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BalanceLedgerEntry {
+    pub id: Uuid,
+    pub artist_id: Uuid,
+    /// What generated the entry (royalty payout or royalty reversal id);
+    /// recording it twice is a no-op
+    pub source_id: Uuid,
+    pub amount: f64,
+    pub currency: Currency,
+    pub status: LedgerEntryStatus,
+    pub payout_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub settled_at: Option<DateTime<Utc>>,
+}
+
+impl BalanceLedgerEntry {
+    pub fn credit(artist_id: Uuid, source_id: Uuid, amount: f64, currency: Currency) -> Result<Self, AppError> {
+        if amount <= 0.0 {
+            return Err(AppError::ValidationError("Ledger credits must be positive".to_string()));
+        }
+        Ok(Self {
+            id: Uuid::new_v4(),
+            artist_id,
+            source_id,
+            amount: round_amount(amount),
+            currency,
+            status: LedgerEntryStatus::Available,
+            payout_id: None,
+            created_at: Utc::now(),
+            settled_at: None,
+        })
+    }
+
+    /// Negative entry taking `amount` back from the balance (a refund reversed
+    /// royalties that were already credited); it nets out of the next payout
+    pub fn debit(artist_id: Uuid, source_id: Uuid, amount: f64, currency: Currency) -> Result<Self, AppError> {
+        let mut entry = Self::credit(artist_id, source_id, amount, currency)?;
+        entry.amount = -entry.amount;
+        Ok(entry)
+    }
+}
+
+/// Balance of an artist in one currency
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArtistBalance {
+    pub artist_id: Uuid,
+    pub currency: Currency,
+    /// Not yet in any payout
+    pub available: f64,
+    /// Reserved by payouts whose transfer is not confirmed yet
+    pub in_payout: f64,
+    pub paid_out: f64,
+}
+
+impl ArtistBalance {
+    /// Group ledger entries into one balance per currency
+    pub fn from_entries(artist_id: Uuid, entries: &[BalanceLedgerEntry]) -> Vec<ArtistBalance> {
+        let mut balances: Vec<ArtistBalance> = Vec::new();
+        for entry in entries.iter().filter(|e| e.artist_id == artist_id) {
+            let index = match balances.iter().position(|b| b.currency == entry.currency) {
+                Some(index) => index,
+                None => {
+                    balances.push(ArtistBalance {
+                        artist_id,
+                        currency: entry.currency.clone(),
+                        available: 0.0,
+                        in_payout: 0.0,
+                        paid_out: 0.0,
+                    });
+                    balances.len() - 1
+                }
+            };
+            let balance = &mut balances[index];
+            match entry.status {
+                LedgerEntryStatus::Available => balance.available = round_amount(balance.available + entry.amount),
+                LedgerEntryStatus::Reserved => balance.in_payout = round_amount(balance.in_payout + entry.amount),
+                LedgerEntryStatus::Settled => balance.paid_out = round_amount(balance.paid_out + entry.amount),
+            }
+        }
+        balances
+    }
+}
+
+/// When payouts are opened
+#[derive(Debug, Clone)]
+pub struct PayoutPolicy {
+    /// Minimum available balance (in the balance's currency) to open a payout
+    pub minimum_amount: f64,
+    /// A transfer not seen on-chain this long after it was handed to the
+    /// wallet can no longer land (its blockhash expired) and is safe to resend
+    pub submission_expiry: Duration,
+}
+
+impl PayoutPolicy {
+    pub fn from_env() -> Self {
+        let minimum_amount = std::env::var("ARTIST_PAYOUT_MINIMUM")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(10.0);
+        let expiry_secs = std::env::var("ARTIST_PAYOUT_SUBMISSION_EXPIRY_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(180);
+
+        Self {
+            minimum_amount,
+            submission_expiry: Duration::seconds(expiry_secs),
+        }
+    }
+
+    pub fn is_due(&self, available: f64) -> bool {
+        available + 1e-9 >= self.minimum_amount && available > 0.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ArtistPayoutStatus {
+    /// Balance reserved, transfer not sent
+    Pending,
+    /// Handed to the wallet; may or may not be on-chain
+    Submitting,
+    /// On-chain with a known signature, waiting for confirmation
+    Submitted,
+    Confirmed,
+    /// Transfer failed; the reserved balance went back to available
+    Failed,
+}
+
+impl ArtistPayoutStatus {
+    pub fn is_open(&self) -> bool {
+        matches!(self, ArtistPayoutStatus::Pending | ArtistPayoutStatus::Submitting | ArtistPayoutStatus::Submitted)
+    }
+}
+
+impl fmt::Display for ArtistPayoutStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            ArtistPayoutStatus::Pending => "pending",
+            ArtistPayoutStatus::Submitting => "submitting",
+            ArtistPayoutStatus::Submitted => "submitted",
+            ArtistPayoutStatus::Confirmed => "confirmed",
+            ArtistPayoutStatus::Failed => "failed",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for ArtistPayoutStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "pending" => Ok(ArtistPayoutStatus::Pending),
+            "submitting" => Ok(ArtistPayoutStatus::Submitting),
+            "submitted" => Ok(ArtistPayoutStatus::Submitted),
+            "confirmed" => Ok(ArtistPayoutStatus::Confirmed),
+            "failed" => Ok(ArtistPayoutStatus::Failed),
+            other => Err(format!("Unknown artist payout status: {}", other)),
+        }
+    }
+}
+
+/// Transfer of an artist's reserved balance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArtistPayout {
+    pub id: Uuid,
+    pub artist_id: Uuid,
+    pub method: PayoutMethod,
+    pub amount: f64,
+    pub currency: Currency,
+    pub status: ArtistPayoutStatus,
+    pub tx_signature: Option<String>,
+    pub failure_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub submitting_at: Option<DateTime<Utc>>,
+    pub submitted_at: Option<DateTime<Utc>>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+}
+
+impl ArtistPayout {
+    pub fn new(artist_id: Uuid, method: PayoutMethod, amount: f64, currency: Currency) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            artist_id,
+            method,
+            amount: round_amount(amount),
+            currency,
+            status: ArtistPayoutStatus::Pending,
+            tx_signature: None,
+            failure_reason: None,
+            created_at: Utc::now(),
+            submitting_at: None,
+            submitted_at: None,
+            confirmed_at: None,
+        }
+    }
+
+    /// Memo/reference attached to the transfer, used to find it on-chain
+    pub fn reference(&self) -> String {
+        format!("vibestream-payout:{}", self.id)
+    }
+
+    fn transition(&mut self, expected: ArtistPayoutStatus, next: ArtistPayoutStatus) -> Result<(), AppError> {
+        if self.status != expected {
+            return Err(AppError::InvalidState(format!(
+                "Payout {} is {}, cannot move to {}",
+                self.id, self.status, next
+            )));
+        }
+        self.status = next;
+        Ok(())
+    }
+
+    pub fn begin_submission(&mut self, now: DateTime<Utc>) -> Result<(), AppError> {
+        self.transition(ArtistPayoutStatus::Pending, ArtistPayoutStatus::Submitting)?;
+        self.submitting_at = Some(now);
+        Ok(())
+    }
+
+    pub fn record_submission(&mut self, signature: String, now: DateTime<Utc>) -> Result<(), AppError> {
+        self.transition(ArtistPayoutStatus::Submitting, ArtistPayoutStatus::Submitted)?;
+        self.tx_signature = Some(signature);
+        self.submitted_at = Some(now);
+        Ok(())
+    }
+
+    /// The transfer never reached the chain and can no longer: send it again
+    pub fn reset_unsent(&mut self) -> Result<(), AppError> {
+        self.transition(ArtistPayoutStatus::Submitting, ArtistPayoutStatus::Pending)?;
+        self.submitting_at = None;
+        Ok(())
+    }
+
+    pub fn confirm(&mut self, now: DateTime<Utc>) -> Result<(), AppError> {
+        self.transition(ArtistPayoutStatus::Submitted, ArtistPayoutStatus::Confirmed)?;
+        self.confirmed_at = Some(now);
+        Ok(())
+    }
+
+    pub fn fail(&mut self, reason: String) -> Result<(), AppError> {
+        if !self.status.is_open() {
+            return Err(AppError::InvalidState(format!("Payout {} is already {}", self.id, self.status)));
+        }
+        self.status = ArtistPayoutStatus::Failed;
+        self.failure_reason = Some(reason);
+        Ok(())
+    }
+
+    /// Handed to the wallet long enough ago that an unseen transfer cannot land anymore
+    pub fn submission_expired(&self, now: DateTime<Utc>, policy: &PayoutPolicy) -> bool {
+        self.status == ArtistPayoutStatus::Submitting
+            && self.submitting_at.map_or(true, |at| now - at >= policy.submission_expiry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID_ADDRESS: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+
+    fn policy() -> PayoutPolicy {
+        PayoutPolicy { minimum_amount: 10.0, submission_expiry: Duration::seconds(180) }
+    }
+
+    #[test]
+    fn solana_addresses_must_decode_to_32_bytes() {
+        assert!(SolanaAddress::parse(VALID_ADDRESS).is_ok());
+        assert!(SolanaAddress::parse("11111111111111111111111111111111").is_ok());
+        // '0', 'O', 'I' y 'l' no existen en base58
+        assert!(SolanaAddress::parse("0WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM").is_err());
+        assert!(SolanaAddress::parse("9WzDXwBbmkg8ZTbNMqUxvQ").is_err());
+        assert!(SolanaAddress::parse("0x742d35Cc6634C0532925a3b844Bc454e4438f44e").is_err());
+    }
+
+    #[test]
+    fn payout_methods_are_validated() {
+        assert!(PayoutMethod::stripe_connect("acct_1Nv0FGQ9RKHgCVdK").is_ok());
+        assert!(PayoutMethod::stripe_connect("cus_123").is_err());
+        assert!(PayoutMethod::solana_wallet(VALID_ADDRESS).unwrap().supports(&Currency::USDC));
+        assert!(!PayoutMethod::solana_wallet(VALID_ADDRESS).unwrap().supports(&Currency::ETH));
+    }
+
+    #[test]
+    fn balance_groups_entries_by_status_and_currency() {
+        let artist = Uuid::new_v4();
+        let mut reserved = BalanceLedgerEntry::credit(artist, Uuid::new_v4(), 4.0, Currency::USD).unwrap();
+        reserved.status = LedgerEntryStatus::Reserved;
+        let entries = vec![
+            BalanceLedgerEntry::credit(artist, Uuid::new_v4(), 2.5, Currency::USD).unwrap(),
+            BalanceLedgerEntry::credit(artist, Uuid::new_v4(), 1.5, Currency::USD).unwrap(),
+            reserved,
+            BalanceLedgerEntry::credit(artist, Uuid::new_v4(), 3.0, Currency::EUR).unwrap(),
+            BalanceLedgerEntry::credit(Uuid::new_v4(), Uuid::new_v4(), 99.0, Currency::USD).unwrap(),
+        ];
+
+        let balances = ArtistBalance::from_entries(artist, &entries);
+        assert_eq!(balances.len(), 2);
+        let usd = balances.iter().find(|b| b.currency == Currency::USD).unwrap();
+        assert_eq!((usd.available, usd.in_payout, usd.paid_out), (4.0, 4.0, 0.0));
+    }
+
+    #[test]
+    fn minimum_threshold_gates_payouts() {
+        assert!(!policy().is_due(9.99));
+        assert!(policy().is_due(10.0));
+    }
+
+    #[test]
+    fn payout_lifecycle() {
+        let method = PayoutMethod::solana_wallet(VALID_ADDRESS).unwrap();
+        let mut payout = ArtistPayout::new(Uuid::new_v4(), method, 12.0, Currency::USDC);
+        let start = Utc::now();
+
+        assert!(payout.confirm(start).is_err());
+        payout.begin_submission(start).unwrap();
+        assert!(!payout.submission_expired(start + Duration::seconds(60), &policy()));
+        assert!(payout.submission_expired(start + Duration::seconds(180), &policy()));
+
+        payout.record_submission("sig".to_string(), start).unwrap();
+        assert!(payout.reset_unsent().is_err());
+        payout.confirm(start).unwrap();
+        assert!(payout.fail("late".to_string()).is_err());
+    }
+}
@@ -1,4 +1,6 @@
 use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -25,11 +27,51 @@ pub struct Payment {
     completed_at: Option<DateTime<Utc>>,
     failure_reason: Option<String>,
     metadata: PaymentMetadata,
+    /// Client key that makes retries of the same request return this payment
+    #[serde(default)]
+    idempotency_key: Option<String>,
 }
 
 
+/// Platform fee taken from a payment before the artist is paid, when
+/// `PLATFORM_FEE_PERCENTAGE` is not set
+pub const DEFAULT_PLATFORM_FEE_PERCENTAGE: Decimal = Decimal::from_parts(20, 0, 0, false, 0);
+
+/// Decimals the split is rounded to when the currency is not known (fiat minor unit)
+const SPLIT_DECIMALS: u32 = 2;
+
+/// How a payment is shared between the artist and the platform.
+/// `artist_share + platform_share == total_amount` always holds.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PaymentSplit {
+    pub total_amount: Decimal,
+    pub platform_fee_pct: Decimal,
+    pub artist_share: Decimal,
+    pub platform_share: Decimal,
+}
+
+impl PaymentSplit {
+    /// The platform share is rounded half away from zero and the artist gets
+    /// the remainder, so no fraction of a cent is lost or created.
+    fn with_precision(total_amount: Decimal, platform_fee_pct: Decimal, decimals: u32) -> Self {
+        let platform_share = (total_amount * platform_fee_pct / Decimal::ONE_HUNDRED)
+            .round_dp_with_strategy(decimals, RoundingStrategy::MidpointAwayFromZero);
+
+        Self {
+            total_amount,
+            platform_fee_pct,
+            artist_share: total_amount - platform_share,
+            platform_share,
+        }
+    }
+}
 
 impl Payment {
+    /// Split `amount` into the platform fee and what is left for the artist
+    pub fn compute_split(amount: Decimal, platform_fee_pct: Decimal) -> PaymentSplit {
+        PaymentSplit::with_precision(amount, platform_fee_pct, SPLIT_DECIMALS)
+    }
+
     /// Create a new payment
     pub fn new(
         payer_id: Uuid,
@@ -42,9 +84,14 @@ impl Payment {
     ) -> Result<(Self, PaymentInitiated), AppError> {
         let payment_id = PaymentId::new();
         
-        // Calculate platform fee and net amount
-        let platform_fee = platform_fee_percentage.calculate_fee(&amount)?;
-        let net_amount = amount.subtract(&platform_fee)?;
+        // Calculate platform fee and net amount in Decimal, rounded to the currency's minor unit
+        let split = PaymentSplit::with_precision(
+            to_decimal(amount.value())?,
+            to_decimal(platform_fee_percentage.value())?,
+            amount.currency().minor_units(),
+        );
+        let platform_fee = Amount::new(from_decimal(split.platform_share)?, amount.currency().clone())?;
+        let net_amount = Amount::new(from_decimal(split.artist_share)?, amount.currency().clone())?;
         
         let payment = Self {
             id: payment_id.clone(),
@@ -63,6 +110,7 @@ impl Payment {
             completed_at: None,
             failure_reason: None,
             metadata,
+            idempotency_key: None,
         };
         
         let event = PaymentInitiated::new(
@@ -99,7 +147,8 @@ impl Payment {
     
     /// Complete the payment successfully
     pub fn complete(&mut self, blockchain_hash: Option<TransactionHash>) -> Result<PaymentCompleted, AppError> {
-        if self.status != PaymentStatus::Processing {
+        // Un depósito parcial se completa cuando llega el resto
+        if !matches!(self.status, PaymentStatus::Processing | PaymentStatus::PartiallyPaid { .. }) {
             return Err(AppError::InvalidState(
                 format!("Cannot complete payment in status: {:?}", self.status)
             ));
@@ -122,6 +171,45 @@ impl Payment {
         ))
     }
     
+    /// Record that only part of the expected amount was received
+    pub fn record_partial_payment(&mut self, received_amount: Amount) -> Result<PaymentPartiallyPaid, AppError> {
+        if !matches!(self.status, PaymentStatus::Processing | PaymentStatus::PartiallyPaid { .. }) {
+            return Err(AppError::InvalidState(
+                format!("Cannot record partial payment in status: {:?}", self.status)
+            ));
+        }
+
+        if received_amount.currency() != self.amount.currency() {
+            return Err(AppError::InvalidInput(
+                "Received amount currency does not match payment currency".to_string()
+            ));
+        }
+
+        if received_amount.value() >= self.amount.value() {
+            return Err(AppError::InvalidInput(
+                "Received amount covers the payment; complete it instead".to_string()
+            ));
+        }
+
+        let shortfall = Amount::new(
+            self.amount.value() - received_amount.value(),
+            self.amount.currency().clone(),
+        )?;
+        self.status = PaymentStatus::PartiallyPaid {
+            received_amount: received_amount.value(),
+            shortfall: shortfall.value(),
+        };
+        self.updated_at = Utc::now();
+
+        Ok(PaymentPartiallyPaid::new(
+            self.id.clone(),
+            self.payer_id,
+            self.amount.clone(),
+            received_amount,
+            shortfall,
+        ))
+    }
+
     /// Mark payment as failed
     pub fn fail(&mut self, error_code: String, error_message: String) -> Result<PaymentFailed, AppError> {
         if self.status.is_final() {
@@ -212,7 +300,40 @@ impl Payment {
             refund_date,
         ))
     }
-    
+
+    /// Record a settled refund transaction; the payment only becomes `Refunded`
+    /// once `total_refunded` covers the whole amount, partial refunds leave it
+    /// `Completed` so the remainder can still be refunded.
+    pub fn apply_refund(&mut self, refund_amount: Amount, total_refunded: f64) -> Result<PaymentRefunded, AppError> {
+        if !self.status.can_be_refunded() {
+            return Err(AppError::InvalidState(
+                "Payment cannot be refunded in current status".to_string()
+            ));
+        }
+
+        if total_refunded > self.amount.value() + 1e-6 {
+            return Err(AppError::InvalidInput(
+                "Refunded total cannot exceed original payment amount".to_string()
+            ));
+        }
+
+        let refund_date = Utc::now();
+        if total_refunded + 1e-6 >= self.amount.value() {
+            self.status = PaymentStatus::Refunded {
+                refund_amount: total_refunded,
+                refund_date,
+            };
+        }
+        self.updated_at = refund_date;
+
+        Ok(PaymentRefunded::new(
+            self.id.clone(),
+            self.amount.clone(),
+            refund_amount,
+            refund_date,
+        ))
+    }
+
     // Getters
     pub fn id(&self) -> &PaymentId { &self.id }
     pub fn transaction_id(&self) -> Option<&TransactionId> { self.transaction_id.as_ref() }
@@ -230,6 +351,19 @@ impl Payment {
     pub fn completed_at(&self) -> Option<DateTime<Utc>> { self.completed_at }
     pub fn failure_reason(&self) -> Option<&String> { self.failure_reason.as_ref() }
     pub fn metadata(&self) -> &PaymentMetadata { &self.metadata }
+    pub fn idempotency_key(&self) -> Option<&str> { self.idempotency_key.as_deref() }
+
+    pub fn set_idempotency_key(&mut self, idempotency_key: String) {
+        self.idempotency_key = Some(idempotency_key);
+    }
+}
+
+fn to_decimal(value: f64) -> Result<Decimal, AppError> {
+    Decimal::try_from(value).map_err(|e| AppError::InvalidInput(format!("Invalid amount {}: {}", value, e)))
+}
+
+fn from_decimal(value: Decimal) -> Result<f64, AppError> {
+    value.to_f64().ok_or_else(|| AppError::InvalidInput(format!("Amount out of range: {}", value)))
 }
 
 /// Royalty Distribution Entity
@@ -559,4 +693,67 @@ mod tests {
         assert_eq!(payment.status(), &PaymentStatus::Completed);
         assert!(payment.completed_at().is_some());
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_partial_payment_then_completion() {
+        let metadata = PaymentMetadata {
+            user_ip: None,
+            user_agent: None,
+            platform_version: "1.0.0".to_string(),
+            reference_id: None,
+            additional_data: serde_json::Value::Null,
+        };
+        let (mut payment, _) = Payment::new(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Amount::new(20.0, Currency::USDC).unwrap(),
+            PaymentMethod::PlatformBalance,
+            PaymentPurpose::SongPurchase { song_id: Uuid::new_v4() },
+            FeePercentage::new(2.5).unwrap(),
+            metadata,
+        ).unwrap();
+
+        // Solo desde Processing
+        assert!(payment.record_partial_payment(Amount::new(5.0, Currency::USDC).unwrap()).is_err());
+
+        payment.start_processing(TransactionId::new()).unwrap();
+        let event = payment.record_partial_payment(Amount::new(15.0, Currency::USDC).unwrap()).unwrap();
+        assert_eq!(event.shortfall.value(), 5.0);
+        assert_eq!(payment.status(), &PaymentStatus::PartiallyPaid { received_amount: 15.0, shortfall: 5.0 });
+        assert!(!payment.status().is_final());
+
+        payment.complete(None).unwrap();
+        assert_eq!(payment.status(), &PaymentStatus::Completed);
+    }
+
+    #[test]
+    fn test_split_sums_to_total_amount() {
+        let split = Payment::compute_split(Decimal::new(10000, 2), DEFAULT_PLATFORM_FEE_PERCENTAGE);
+
+        assert_eq!(split.platform_share, Decimal::new(2000, 2));
+        assert_eq!(split.artist_share, Decimal::new(8000, 2));
+        assert_eq!(split.artist_share + split.platform_share, split.total_amount);
+    }
+
+    #[test]
+    fn test_split_rounds_in_decimal() {
+        // 20% of 0.10 + 0.20 is 0.06 exactly; in f64 it would be 0.060000000000000005
+        let total = Decimal::new(10, 2) + Decimal::new(20, 2);
+        let split = Payment::compute_split(total, Decimal::new(20, 0));
+        assert_eq!(split.platform_share, Decimal::new(6, 2));
+
+        // 20% of 10.01 = 2.002 -> 2.00, artist keeps the remainder
+        let split = Payment::compute_split(Decimal::new(1001, 2), Decimal::new(20, 0));
+        assert_eq!(split.platform_share, Decimal::new(200, 2));
+        assert_eq!(split.artist_share, Decimal::new(801, 2));
+
+        // Half a cent rounds away from zero: 15% of 0.10 = 0.015 -> 0.02
+        let split = Payment::compute_split(Decimal::new(10, 2), Decimal::new(15, 0));
+        assert_eq!(split.platform_share, Decimal::new(2, 2));
+
+        for cents in [1, 3, 7, 33, 999, 123457] {
+            let split = Payment::compute_split(Decimal::new(cents, 2), Decimal::new(175, 1));
+            assert_eq!(split.artist_share + split.platform_share, split.total_amount);
+        }
+    }
+}
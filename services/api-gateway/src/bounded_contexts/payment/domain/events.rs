@@ -275,6 +275,52 @@ impl DomainEvent for PaymentCancelled {
     fn event_data(&self) -> serde_json::Value { serde_json::to_value(self).unwrap_or_default() }
 }
 
+/// Payment Partially Paid Event
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PaymentPartiallyPaid {
+    pub payment_id: PaymentId,
+    pub payer_id: Uuid,
+    pub expected_amount: Amount,
+    pub received_amount: Amount,
+    pub shortfall: Amount,
+    pub occurred_at: DateTime<Utc>,
+    pub metadata: EventMetadata,
+}
+
+impl PaymentPartiallyPaid {
+    pub fn new(
+        payment_id: PaymentId,
+        payer_id: Uuid,
+        expected_amount: Amount,
+        received_amount: Amount,
+        shortfall: Amount,
+    ) -> Self {
+        let metadata = EventMetadata::with_type_and_aggregate(
+            "PaymentPartiallyPaid",
+            payment_id.value(),
+            "Payment"
+        );
+        Self {
+            payment_id,
+            payer_id,
+            expected_amount,
+            received_amount,
+            shortfall,
+            occurred_at: Utc::now(),
+            metadata,
+        }
+    }
+}
+
+impl DomainEvent for PaymentPartiallyPaid {
+    fn metadata(&self) -> &EventMetadata { &self.metadata }
+    fn event_type(&self) -> &str { "PaymentPartiallyPaid" }
+    fn aggregate_id(&self) -> Uuid { self.payment_id.value() }
+    fn aggregate_type(&self) -> &str { "Payment" }
+    fn occurred_at(&self) -> DateTime<Utc> { self.occurred_at }
+    fn event_data(&self) -> serde_json::Value { serde_json::to_value(self).unwrap_or_default() }
+}
+
 /// Payment Refund Started Event
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PaymentRefundStarted {
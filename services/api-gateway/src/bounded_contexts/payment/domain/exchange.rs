@@ -0,0 +1,186 @@
+//! Currency conversion
+//!
+//! Internal accounting (royalty runs, reversals, venture funding) is kept in a
+//! single base currency. A payment in another currency is converted once, when
+//! it is captured, and the rate used is stored next to it: the original amount
+//! stays the one shown to the payer and refunded, and any later base-currency
+//! figure derived from it (a refund's reversal, for instance) reuses that rate
+//! instead of the market rate of the day.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use super::value_objects::{Amount, Currency};
+
+/// Currency used when `BASE_CURRENCY` is not set
+pub const DEFAULT_BASE_CURRENCY: Currency = Currency::USD;
+
+/// Units of `to` per unit of `from`, as quoted at `quoted_at`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExchangeRate {
+    pub from: Currency,
+    pub to: Currency,
+    pub rate: f64,
+    pub quoted_at: DateTime<Utc>,
+}
+
+impl ExchangeRate {
+    pub fn new(from: Currency, to: Currency, rate: f64, quoted_at: DateTime<Utc>) -> Result<Self, AppError> {
+        if !rate.is_finite() || rate <= 0.0 {
+            return Err(AppError::InvalidInput(format!("Invalid exchange rate {} for {}/{}", rate, from, to)));
+        }
+        if from == to && rate != 1.0 {
+            return Err(AppError::InvalidInput(format!("Exchange rate of {} to itself must be 1", from)));
+        }
+        Ok(Self { from, to, rate, quoted_at })
+    }
+
+    pub fn identity(currency: Currency, quoted_at: DateTime<Utc>) -> Self {
+        Self { from: currency.clone(), to: currency, rate: 1.0, quoted_at }
+    }
+
+    pub fn inverse(&self) -> Self {
+        Self {
+            from: self.to.clone(),
+            to: self.from.clone(),
+            rate: 1.0 / self.rate,
+            quoted_at: self.quoted_at,
+        }
+    }
+
+    /// Convert an amount in `from` into `to`, rounded to `to`'s minor unit
+    pub fn convert(&self, amount: &Amount) -> Result<Amount, AppError> {
+        if amount.currency() != &self.from {
+            return Err(AppError::InvalidInput(format!(
+                "Cannot convert {} with a {}/{} rate",
+                amount.currency(), self.from, self.to
+            )));
+        }
+        Amount::new(self.to.round(amount.value() * self.rate), self.to.clone())
+    }
+}
+
+/// Source of exchange rates
+#[async_trait]
+pub trait ExchangeRateProvider: Send + Sync {
+    async fn rate(&self, from: &Currency, to: &Currency) -> Result<ExchangeRate, AppError>;
+}
+
+/// An amount together with its base-currency equivalent and the rate used
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NormalizedAmount {
+    /// As paid: shown to the payer and used for refunds
+    pub original: Amount,
+    /// Used for internal accounting
+    pub base: Amount,
+    /// `original` → `base`
+    pub rate: ExchangeRate,
+}
+
+impl NormalizedAmount {
+    pub fn new(original: Amount, rate: ExchangeRate) -> Result<Self, AppError> {
+        let base = rate.convert(&original)?;
+        Ok(Self { original, base, rate })
+    }
+
+    /// Already in the base currency
+    pub fn in_base(amount: Amount, now: DateTime<Utc>) -> Self {
+        Self {
+            rate: ExchangeRate::identity(amount.currency().clone(), now),
+            base: amount.clone(),
+            original: amount,
+        }
+    }
+
+    pub fn is_converted(&self) -> bool {
+        self.original.currency() != self.base.currency()
+    }
+
+    /// Base-currency value of part of the original amount (e.g. a partial
+    /// refund), at the captured rate
+    pub fn base_value_of(&self, original_part: &Amount) -> Result<Amount, AppError> {
+        self.rate.convert(original_part)
+    }
+
+    /// Original-currency amount equivalent to `base_part` at the captured rate,
+    /// never more than the original amount
+    pub fn original_value_of(&self, base_part: &Amount) -> Result<Amount, AppError> {
+        let converted = self.rate.inverse().convert(base_part)?;
+        if converted.value() > self.original.value() {
+            return Ok(self.original.clone());
+        }
+        Ok(converted)
+    }
+}
+
+/// Conversion captured when a payment was completed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapturedExchangeRate {
+    pub payment_id: Uuid,
+    pub amount: NormalizedAmount,
+    pub captured_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn amount(value: f64, currency: Currency) -> Amount {
+        Amount::new(value, currency).unwrap()
+    }
+
+    fn eur_usd() -> ExchangeRate {
+        ExchangeRate::new(Currency::EUR, Currency::USD, 1.0837, Utc::now()).unwrap()
+    }
+
+    #[test]
+    fn conversion_rounds_to_the_target_minor_unit() {
+        // 19.99 * 1.0837 = 21.663163
+        let converted = eur_usd().convert(&amount(19.99, Currency::EUR)).unwrap();
+        assert_eq!(converted, amount(21.66, Currency::USD));
+
+        // 1234.567891 * 0.9231 = 1139.629620…
+        let usdc_eur = ExchangeRate::new(Currency::USDC, Currency::EUR, 0.9231, Utc::now()).unwrap();
+        assert_eq!(usdc_eur.convert(&amount(1234.567891, Currency::USDC)).unwrap().value(), 1139.63);
+
+        // Hacia una moneda con 6 decimales se conserva la precisión
+        let gbp_usdc = ExchangeRate::new(Currency::GBP, Currency::USDC, 1.2719, Utc::now()).unwrap();
+        assert_eq!(gbp_usdc.convert(&amount(33.33, Currency::GBP)).unwrap().value(), 42.392427);
+    }
+
+    #[test]
+    fn rate_must_match_the_amount_currency() {
+        assert!(eur_usd().convert(&amount(10.0, Currency::GBP)).is_err());
+        assert!(ExchangeRate::new(Currency::EUR, Currency::USD, 0.0, Utc::now()).is_err());
+        assert!(ExchangeRate::new(Currency::EUR, Currency::USD, f64::NAN, Utc::now()).is_err());
+        assert!(ExchangeRate::new(Currency::USD, Currency::USD, 1.1, Utc::now()).is_err());
+    }
+
+    #[test]
+    fn partial_refunds_use_the_captured_rate() {
+        let captured = NormalizedAmount::new(amount(49.90, Currency::EUR), eur_usd()).unwrap();
+        // 49.90 * 1.0837 = 54.07663
+        assert_eq!(captured.base.value(), 54.08);
+        assert!(captured.is_converted());
+
+        // Reembolso parcial de 12.35 EUR: 13.383695 → 13.38 USD
+        assert_eq!(captured.base_value_of(&amount(12.35, Currency::EUR)).unwrap().value(), 13.38);
+
+        // De vuelta a EUR al mismo tipo: 13.38 / 1.0837 = 12.3466…
+        assert_eq!(captured.original_value_of(&amount(13.38, Currency::USD)).unwrap().value(), 12.35);
+
+        // Nunca más que lo cobrado: 54.09 USD serían 49.91 EUR
+        assert_eq!(captured.original_value_of(&amount(54.09, Currency::USD)).unwrap(), captured.original);
+    }
+
+    #[test]
+    fn base_currency_amounts_are_not_converted() {
+        let normalized = NormalizedAmount::in_base(amount(10.0, Currency::USD), Utc::now());
+        assert!(!normalized.is_converted());
+        assert_eq!(normalized.base, normalized.original);
+        assert_eq!(normalized.rate.rate, 1.0);
+    }
+}
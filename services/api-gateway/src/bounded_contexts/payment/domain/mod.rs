@@ -4,10 +4,24 @@ pub mod value_objects;
 pub mod events;
 pub mod repository;
 pub mod services;
+pub mod refunds;
+pub mod royalty_runs;
+pub mod artist_payouts;
+pub mod royalty_advances;
+pub mod statistics;
+pub mod exchange;
+pub mod webhooks;
 
 pub use aggregates::*;
 pub use entities::*;
 pub use value_objects::*;
 pub use events::*;
 pub use repository::*;
-pub use services::*; 
\ No newline at end of file
+pub use services::*;
+pub use refunds::*;
+pub use royalty_runs::*;
+pub use artist_payouts::*;
+pub use royalty_advances::*;
+pub use statistics::*;
+pub use exchange::*;
+pub use webhooks::*;
\ No newline at end of file
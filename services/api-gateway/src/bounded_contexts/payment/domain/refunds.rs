@@ -0,0 +1,476 @@
+//! Refund workflow
+//!
+//! A refund is its own transaction linked to the original payment. The
+//! `RefundLedger` decides how much of a payment is still refundable (refunds in
+//! flight count against it, so a second request cannot refund the same money
+//! twice) and `RoyaltyReversal` claws back the royalty payouts that were
+//! computed from the refunded revenue.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use super::artist_payouts::BalanceLedgerEntry;
+use super::royalty_runs::RoyaltyRecipientType;
+use super::value_objects::{Amount, Currency, PaymentStatus};
+
+/// Las cantidades de royalties se guardan con 6 decimales (`DECIMAL(15,6)`)
+const AMOUNT_SCALE: f64 = 1_000_000.0;
+/// Tolerancia para comparar importes en coma flotante
+const AMOUNT_EPSILON: f64 = 1e-6;
+
+fn round_amount(value: f64) -> f64 {
+    (value * AMOUNT_SCALE).round() / AMOUNT_SCALE
+}
+
+/// Lifecycle of a refund transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RefundStatus {
+    /// Recorded, not yet sent to the gateway
+    Pending,
+    /// Submitted to the gateway, waiting for its answer
+    Processing,
+    /// Crypto refund queued for a manual on-chain payout
+    AwaitingPayout,
+    Completed,
+    Failed,
+}
+
+impl RefundStatus {
+    pub fn is_final(&self) -> bool {
+        matches!(self, RefundStatus::Completed | RefundStatus::Failed)
+    }
+
+    /// Todo reembolso no fallido reserva su importe frente al pago original
+    pub fn reserves_amount(&self) -> bool {
+        !matches!(self, RefundStatus::Failed)
+    }
+
+    fn can_transition_to(&self, next: RefundStatus) -> bool {
+        use RefundStatus::*;
+        matches!(
+            (self, next),
+            (Pending, Processing)
+                | (Pending, AwaitingPayout)
+                | (Pending, Completed)
+                | (Pending, Failed)
+                | (Processing, Completed)
+                | (Processing, Failed)
+                | (AwaitingPayout, Completed)
+                | (AwaitingPayout, Failed)
+        )
+    }
+}
+
+impl fmt::Display for RefundStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            RefundStatus::Pending => "pending",
+            RefundStatus::Processing => "processing",
+            RefundStatus::AwaitingPayout => "awaiting_payout",
+            RefundStatus::Completed => "completed",
+            RefundStatus::Failed => "failed",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for RefundStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "pending" => Ok(RefundStatus::Pending),
+            "processing" => Ok(RefundStatus::Processing),
+            "awaiting_payout" => Ok(RefundStatus::AwaitingPayout),
+            "completed" => Ok(RefundStatus::Completed),
+            "failed" => Ok(RefundStatus::Failed),
+            other => Err(format!("Unknown refund status: {}", other)),
+        }
+    }
+}
+
+/// Refund transaction linked to the payment it refunds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefundTransaction {
+    pub id: Uuid,
+    pub payment_id: Uuid,
+    pub amount: Amount,
+    pub reason: String,
+    pub status: RefundStatus,
+    /// Gateway that executes the refund (the one that charged the payment)
+    pub gateway: String,
+    pub gateway_refund_id: Option<String>,
+    pub initiated_by: Uuid,
+    pub failure_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl RefundTransaction {
+    pub fn new(payment_id: Uuid, amount: Amount, reason: String, gateway: String, initiated_by: Uuid) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            payment_id,
+            amount,
+            reason,
+            status: RefundStatus::Pending,
+            gateway,
+            gateway_refund_id: None,
+            initiated_by,
+            failure_reason: None,
+            created_at: now,
+            updated_at: now,
+            completed_at: None,
+        }
+    }
+
+    fn transition(&mut self, next: RefundStatus) -> Result<(), AppError> {
+        if !self.status.can_transition_to(next) {
+            return Err(AppError::InvalidState(format!(
+                "Refund {} cannot move from {} to {}",
+                self.id, self.status, next
+            )));
+        }
+        self.status = next;
+        self.updated_at = Utc::now();
+        Ok(())
+    }
+
+    pub fn mark_processing(&mut self, gateway_refund_id: Option<String>) -> Result<(), AppError> {
+        self.transition(RefundStatus::Processing)?;
+        self.gateway_refund_id = gateway_refund_id;
+        Ok(())
+    }
+
+    /// `payout_id` identifica la entrada en la cola de pagos manuales
+    pub fn await_payout(&mut self, payout_id: String) -> Result<(), AppError> {
+        self.transition(RefundStatus::AwaitingPayout)?;
+        self.gateway_refund_id = Some(payout_id);
+        Ok(())
+    }
+
+    pub fn complete(&mut self, gateway_refund_id: Option<String>) -> Result<(), AppError> {
+        self.transition(RefundStatus::Completed)?;
+        if gateway_refund_id.is_some() {
+            self.gateway_refund_id = gateway_refund_id;
+        }
+        self.completed_at = Some(self.updated_at);
+        Ok(())
+    }
+
+    pub fn fail(&mut self, reason: String) -> Result<(), AppError> {
+        self.transition(RefundStatus::Failed)?;
+        self.failure_reason = Some(reason);
+        Ok(())
+    }
+}
+
+/// Refund accounting for a single payment
+#[derive(Debug, Clone)]
+pub struct RefundLedger {
+    payment_amount: Amount,
+    refunds: Vec<RefundTransaction>,
+}
+
+impl RefundLedger {
+    pub fn new(payment_amount: Amount, refunds: Vec<RefundTransaction>) -> Self {
+        Self { payment_amount, refunds }
+    }
+
+    /// Completed plus in-flight refunds
+    pub fn reserved(&self) -> f64 {
+        self.refunds
+            .iter()
+            .filter(|refund| refund.status.reserves_amount())
+            .map(|refund| refund.amount.value())
+            .sum()
+    }
+
+    pub fn completed(&self) -> f64 {
+        self.refunds
+            .iter()
+            .filter(|refund| refund.status == RefundStatus::Completed)
+            .map(|refund| refund.amount.value())
+            .sum()
+    }
+
+    pub fn remaining(&self) -> f64 {
+        round_amount((self.payment_amount.value() - self.reserved()).max(0.0))
+    }
+
+    pub fn is_fully_refunded(&self) -> bool {
+        self.remaining() <= AMOUNT_EPSILON
+    }
+
+    /// Valida una petición de reembolso y devuelve el importe a reembolsar.
+    ///
+    /// `requested = None` reembolsa todo lo que queda.
+    pub fn authorize(&self, status: &PaymentStatus, requested: Option<f64>) -> Result<Amount, AppError> {
+        let fully_refunded = matches!(status, PaymentStatus::Refunded { .. }) || self.is_fully_refunded();
+        if fully_refunded {
+            return Err(AppError::ConflictError("Payment has already been fully refunded".to_string()));
+        }
+        if !status.can_be_refunded() {
+            return Err(AppError::InvalidState(format!(
+                "Only completed payments can be refunded (status: {})",
+                status.name()
+            )));
+        }
+
+        let remaining = self.remaining();
+        let amount = match requested {
+            Some(amount) if amount <= 0.0 => {
+                return Err(AppError::InvalidInput("Refund amount must be positive".to_string()));
+            }
+            Some(amount) if amount > remaining + AMOUNT_EPSILON => {
+                return Err(AppError::InvalidInput(format!(
+                    "Refund amount {} exceeds refundable balance {}",
+                    amount, remaining
+                )));
+            }
+            Some(amount) => round_amount(amount.min(remaining)),
+            None => remaining,
+        };
+
+        Amount::new(amount, self.payment_amount.currency().clone())
+    }
+
+    /// Total refunded once `refund` completes; drives the payment's final status
+    pub fn completed_after(&self, refund: &RefundTransaction) -> f64 {
+        let already_counted = self
+            .refunds
+            .iter()
+            .any(|existing| existing.id == refund.id && existing.status == RefundStatus::Completed);
+        if already_counted {
+            self.completed()
+        } else {
+            round_amount(self.completed() + refund.amount.value())
+        }
+    }
+
+    pub fn covers_payment(&self, total_refunded: f64) -> bool {
+        total_refunded + AMOUNT_EPSILON >= self.payment_amount.value()
+    }
+}
+
+/// Royalty payout of a distribution run whose revenue includes a refunded
+/// payment, with what earlier refunds already clawed back
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayoutRefundBasis {
+    pub royalty_payout_id: Uuid,
+    pub recipient_id: Uuid,
+    pub recipient_type: RoyaltyRecipientType,
+    /// Revenue of the run the payout was computed from
+    pub run_revenue: f64,
+    pub amount: f64,
+    pub currency: Currency,
+    pub reversed_amount: f64,
+}
+
+/// Claw-back of a royalty payout caused by a refund
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoyaltyReversal {
+    pub id: Uuid,
+    pub refund_id: Uuid,
+    pub royalty_payout_id: Uuid,
+    pub recipient_id: Uuid,
+    pub recipient_type: RoyaltyRecipientType,
+    pub amount: f64,
+    pub currency: Currency,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RoyaltyReversal {
+    /// Reverses the share of the payout funded by `refund_amount`.
+    ///
+    /// Refunding X of a run's revenue R removes X/R of each of its payouts,
+    /// capped at what is still unreversed so a partial-then-full sequence adds
+    /// up to exactly the original payout.
+    pub fn compute(basis: &PayoutRefundBasis, refund_id: Uuid, refund_amount: f64) -> Option<Self> {
+        if basis.run_revenue <= 0.0 || refund_amount <= 0.0 {
+            return None;
+        }
+        let ratio = (refund_amount / basis.run_revenue).min(1.0);
+
+        let outstanding = (basis.amount - basis.reversed_amount).max(0.0);
+        let amount = round_amount((basis.amount * ratio).min(outstanding));
+        if amount <= 0.0 {
+            return None;
+        }
+
+        Some(Self {
+            id: Uuid::new_v4(),
+            refund_id,
+            royalty_payout_id: basis.royalty_payout_id,
+            recipient_id: basis.recipient_id,
+            recipient_type: basis.recipient_type,
+            amount,
+            currency: basis.currency.clone(),
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Debit on the creator's balance; the platform's share has no balance
+    pub fn balance_debit(&self) -> Option<BalanceLedgerEntry> {
+        if self.recipient_type == RoyaltyRecipientType::Platform {
+            return None;
+        }
+        BalanceLedgerEntry::debit(self.recipient_id, self.id, self.amount, self.currency.clone()).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usd(value: f64) -> Amount {
+        Amount::new(value, Currency::USD).unwrap()
+    }
+
+    fn refund(payment_id: Uuid, value: f64, status: RefundStatus) -> RefundTransaction {
+        let mut refund = RefundTransaction::new(payment_id, usd(value), "test".to_string(), "stripe".to_string(), Uuid::new_v4());
+        refund.status = status;
+        refund
+    }
+
+    fn basis(run_revenue: f64, amount: f64, recipient_type: RoyaltyRecipientType) -> PayoutRefundBasis {
+        PayoutRefundBasis {
+            royalty_payout_id: Uuid::new_v4(),
+            recipient_id: Uuid::new_v4(),
+            recipient_type,
+            run_revenue,
+            amount,
+            currency: Currency::USD,
+            reversed_amount: 0.0,
+        }
+    }
+
+    #[test]
+    fn full_refund_defaults_to_remaining_balance() {
+        let ledger = RefundLedger::new(usd(100.0), vec![]);
+        let amount = ledger.authorize(&PaymentStatus::Completed, None).unwrap();
+        assert_eq!(amount.value(), 100.0);
+    }
+
+    #[test]
+    fn double_refund_is_rejected() {
+        let payment_id = Uuid::new_v4();
+        let completed = RefundLedger::new(usd(100.0), vec![refund(payment_id, 100.0, RefundStatus::Completed)]);
+        assert!(matches!(
+            completed.authorize(&PaymentStatus::Completed, None),
+            Err(AppError::ConflictError(_))
+        ));
+
+        // Un reembolso en curso también bloquea el segundo
+        let in_flight = RefundLedger::new(usd(100.0), vec![refund(payment_id, 100.0, RefundStatus::AwaitingPayout)]);
+        assert!(matches!(
+            in_flight.authorize(&PaymentStatus::Completed, Some(1.0)),
+            Err(AppError::ConflictError(_))
+        ));
+
+        let refunded = PaymentStatus::Refunded { refund_amount: 100.0, refund_date: Utc::now() };
+        assert!(matches!(
+            RefundLedger::new(usd(100.0), vec![]).authorize(&refunded, None),
+            Err(AppError::ConflictError(_))
+        ));
+    }
+
+    #[test]
+    fn only_completed_payments_can_be_refunded() {
+        let ledger = RefundLedger::new(usd(100.0), vec![]);
+        assert!(matches!(
+            ledger.authorize(&PaymentStatus::Pending, None),
+            Err(AppError::InvalidState(_))
+        ));
+    }
+
+    #[test]
+    fn failed_refunds_release_their_amount() {
+        let payment_id = Uuid::new_v4();
+        let ledger = RefundLedger::new(usd(100.0), vec![refund(payment_id, 60.0, RefundStatus::Failed)]);
+        assert_eq!(ledger.authorize(&PaymentStatus::Completed, None).unwrap().value(), 100.0);
+    }
+
+    #[test]
+    fn partial_then_full_refund_sequence() {
+        let payment_id = Uuid::new_v4();
+        let mut ledger = RefundLedger::new(usd(100.0), vec![]);
+
+        let first = ledger.authorize(&PaymentStatus::Completed, Some(30.0)).unwrap();
+        assert_eq!(first.value(), 30.0);
+        let mut partial = refund(payment_id, first.value(), RefundStatus::Pending);
+        partial.complete(Some("re_1".to_string())).unwrap();
+        assert!(!ledger.covers_payment(ledger.completed_after(&partial)));
+        ledger = RefundLedger::new(usd(100.0), vec![partial]);
+
+        assert!(matches!(
+            ledger.authorize(&PaymentStatus::Completed, Some(70.01)),
+            Err(AppError::InvalidInput(_))
+        ));
+
+        let rest = ledger.authorize(&PaymentStatus::Completed, None).unwrap();
+        assert_eq!(rest.value(), 70.0);
+        let full = refund(payment_id, rest.value(), RefundStatus::Pending);
+        assert!(ledger.covers_payment(ledger.completed_after(&full)));
+    }
+
+    #[test]
+    fn refund_lifecycle_rejects_invalid_transitions() {
+        let mut refund = refund(Uuid::new_v4(), 10.0, RefundStatus::Pending);
+        refund.await_payout("payout-1".to_string()).unwrap();
+        assert!(refund.mark_processing(None).is_err());
+        refund.complete(Some("0xabc".to_string())).unwrap();
+        assert!(refund.completed_at.is_some());
+        assert!(refund.fail("late failure".to_string()).is_err());
+    }
+
+    #[test]
+    fn royalty_reversal_is_proportional_to_refund() {
+        // 100 de ingresos repartidos 70/30; se reembolsan 25
+        let artist = RoyaltyReversal::compute(&basis(100.0, 70.0, RoyaltyRecipientType::Artist), Uuid::new_v4(), 25.0).unwrap();
+        let platform = RoyaltyReversal::compute(&basis(100.0, 30.0, RoyaltyRecipientType::Platform), Uuid::new_v4(), 25.0).unwrap();
+        assert_eq!(artist.amount, 17.5);
+        assert_eq!(platform.amount, 7.5);
+    }
+
+    #[test]
+    fn royalty_reversal_only_touches_the_refunded_payments_share() {
+        // La ejecución agrega dos pagos de 100; reembolsar uno entero revierte la mitad
+        let reversal = RoyaltyReversal::compute(&basis(200.0, 140.0, RoyaltyRecipientType::Artist), Uuid::new_v4(), 100.0).unwrap();
+        assert_eq!(reversal.amount, 70.0);
+    }
+
+    #[test]
+    fn partial_then_full_reversals_add_up_to_original_payout() {
+        let mut basis = basis(90.0, 63.0, RoyaltyRecipientType::Contributor);
+
+        let first = RoyaltyReversal::compute(&basis, Uuid::new_v4(), 30.0).unwrap();
+        assert_eq!(first.amount, 21.0);
+        basis.reversed_amount += first.amount;
+
+        let second = RoyaltyReversal::compute(&basis, Uuid::new_v4(), 60.0).unwrap();
+        assert!((first.amount + second.amount - 63.0).abs() < AMOUNT_EPSILON);
+        basis.reversed_amount += second.amount;
+
+        // Nada queda por revertir
+        assert!(RoyaltyReversal::compute(&basis, Uuid::new_v4(), 10.0).is_none());
+    }
+
+    #[test]
+    fn only_creator_reversals_debit_a_balance() {
+        let artist = RoyaltyReversal::compute(&basis(100.0, 70.0, RoyaltyRecipientType::Artist), Uuid::new_v4(), 25.0).unwrap();
+        let debit = artist.balance_debit().unwrap();
+        assert_eq!(debit.artist_id, artist.recipient_id);
+        assert_eq!(debit.source_id, artist.id);
+        assert_eq!(debit.amount, -17.5);
+
+        let platform = RoyaltyReversal::compute(&basis(100.0, 30.0, RoyaltyRecipientType::Platform), Uuid::new_v4(), 25.0).unwrap();
+        assert!(platform.balance_debit().is_none());
+    }
+}
@@ -9,6 +9,13 @@ use super::aggregates::*;
 use super::entities::*;
 use super::value_objects::*;
 use super::events::*;
+use super::refunds::*;
+use super::royalty_runs::*;
+use super::artist_payouts::*;
+use super::royalty_advances::*;
+use super::statistics::*;
+use super::exchange::*;
+use super::webhooks::*;
 use crate::bounded_contexts::payment::application::commands::Wallet;
 
 pub type PaymentRepositoryResult<T> = Result<T, AppError>;
@@ -117,6 +124,189 @@ pub trait RoyaltyDistributionRepository: Send + Sync {
     async fn get_artist_total_distributions(&self, artist_id: Uuid, start: DateTime<Utc>, end: DateTime<Utc>) -> PaymentRepositoryResult<HashMap<Currency, f64>>;
 }
 
+/// Repository for refund transactions
+#[async_trait]
+pub trait RefundTransactionRepository: Send + Sync {
+    /// Insert a new refund, reserving its amount against the payment.
+    ///
+    /// Fails with `ConcurrencyConflict` if, together with the refunds already
+    /// reserved, it would exceed `payment_amount` (two concurrent requests).
+    async fn create(&self, refund: &RefundTransaction, payment_amount: f64) -> PaymentRepositoryResult<()>;
+
+    /// Persist a status transition
+    async fn update(&self, refund: &RefundTransaction) -> PaymentRepositoryResult<()>;
+
+    /// Find refund by ID
+    async fn find_by_id(&self, id: Uuid) -> PaymentRepositoryResult<Option<RefundTransaction>>;
+
+    /// All refunds of a payment, oldest first
+    async fn find_by_payment(&self, payment_id: Uuid) -> PaymentRepositoryResult<Vec<RefundTransaction>>;
+}
+
+/// Repository for royalty reversals caused by refunds
+#[async_trait]
+pub trait RoyaltyReversalRepository: Send + Sync {
+    /// Payouts of the active run that distributed the song's revenue earned at
+    /// `earned_at`, with the amounts already reversed
+    async fn find_payouts_for_revenue(&self, song_id: Uuid, earned_at: DateTime<Utc>) -> PaymentRepositoryResult<Vec<PayoutRefundBasis>>;
+
+    /// Save the reversals of one refund together with the debits on the
+    /// creators' balances, atomically
+    async fn save_all(&self, reversals: &[RoyaltyReversal]) -> PaymentRepositoryResult<()>;
+}
+
+/// Repository for royalty distribution runs and their payouts
+#[async_trait]
+pub trait RoyaltyRunRepository: Send + Sync {
+    /// Insert a run with its payouts.
+    ///
+    /// Fails with `ConcurrencyConflict` if another active run of the song
+    /// overlapping the period was recorded concurrently.
+    async fn create(&self, run: &RoyaltyDistributionRun, payouts: &[RoyaltyPayout]) -> PaymentRepositoryResult<()>;
+
+    /// Find run by ID
+    async fn find_by_id(&self, id: Uuid) -> PaymentRepositoryResult<Option<RoyaltyDistributionRun>>;
+
+    /// Runs of the song (active or voided) whose period overlaps `[start, end)`
+    async fn find_overlapping(&self, song_id: Uuid, start: DateTime<Utc>, end: DateTime<Utc>) -> PaymentRepositoryResult<Vec<RoyaltyDistributionRun>>;
+
+    /// Payouts of a run
+    async fn find_payouts(&self, run_id: Uuid) -> PaymentRepositoryResult<Vec<RoyaltyPayout>>;
+
+    /// Set the status of the given payouts
+    async fn update_payout_status(&self, payout_ids: &[Uuid], status: RoyaltyPayoutStatus) -> PaymentRepositoryResult<()>;
+
+    /// Persist a voided run and cancel its payouts
+    async fn void(&self, run: &RoyaltyDistributionRun) -> PaymentRepositoryResult<()>;
+}
+
+/// Aggregation queries over the payments table
+#[async_trait]
+pub trait PaymentStatisticsRepository: Send + Sync {
+    /// Count and volume per (day, status, payment type, currency) of the
+    /// payments created in the period. `payee_id` restricts it to the
+    /// payments received by that user.
+    async fn aggregate(&self, period: &StatisticsPeriod, payee_id: Option<Uuid>) -> PaymentRepositoryResult<Vec<PaymentStatisticsRow>>;
+}
+
+/// Exchange rates captured when payments in a non-base currency completed
+#[async_trait]
+pub trait PaymentExchangeRateRepository: Send + Sync {
+    /// Record the conversion of a payment. The first capture wins: recording
+    /// the same payment again keeps the original rate.
+    async fn record(&self, captured: &CapturedExchangeRate) -> PaymentRepositoryResult<()>;
+
+    async fn find_by_payment(&self, payment_id: Uuid) -> PaymentRepositoryResult<Option<CapturedExchangeRate>>;
+}
+
+/// Repository for outbound webhooks and their delivery log
+#[async_trait]
+pub trait WebhookRepository: Send + Sync {
+    async fn create(&self, webhook: &Webhook) -> PaymentRepositoryResult<()>;
+
+    async fn find_by_id(&self, id: Uuid) -> PaymentRepositoryResult<Option<Webhook>>;
+
+    /// Active webhooks subscribed to `event_type`
+    async fn find_subscribed(&self, event_type: &str) -> PaymentRepositoryResult<Vec<Webhook>>;
+
+    async fn record_attempt(&self, delivery: &WebhookDelivery) -> PaymentRepositoryResult<()>;
+
+    /// Count a delivery outcome against the stored failure streak and return
+    /// the updated webhook (deactivated once the streak reaches the limit)
+    async fn record_outcome(&self, webhook_id: Uuid, succeeded: bool) -> PaymentRepositoryResult<Webhook>;
+
+    /// Latest delivery attempts of a webhook, newest first
+    async fn find_deliveries(&self, webhook_id: Uuid, limit: u32) -> PaymentRepositoryResult<Vec<WebhookDelivery>>;
+}
+
+/// Repository for artist balances (ledger) and payouts
+#[async_trait]
+pub trait ArtistPayoutRepository: Send + Sync {
+    /// Register or replace the payout method of an artist
+    async fn save_payout_method(&self, artist_id: Uuid, method: &PayoutMethod) -> PaymentRepositoryResult<()>;
+
+    async fn find_payout_method(&self, artist_id: Uuid) -> PaymentRepositoryResult<Option<PayoutMethod>>;
+
+    /// Add earnings to the ledger. Entries whose `source_id` is already
+    /// credited are skipped, so crediting the same source twice is a no-op.
+    async fn credit(&self, entries: &[BalanceLedgerEntry]) -> PaymentRepositoryResult<()>;
+
+    /// Remove credits that were never paid out, along with the refund debits
+    /// reversing them. Fails with `InvalidState` if any of them is already
+    /// reserved by or settled in a payout.
+    async fn revoke_credits(&self, source_ids: &[Uuid]) -> PaymentRepositoryResult<()>;
+
+    /// Ledger entries of an artist
+    async fn find_entries(&self, artist_id: Uuid) -> PaymentRepositoryResult<Vec<BalanceLedgerEntry>>;
+
+    /// (artist, currency, available amount) with at least `minimum` available
+    async fn find_due_balances(&self, minimum: f64) -> PaymentRepositoryResult<Vec<(Uuid, Currency, f64)>>;
+
+    /// Create `payout` reserving all available entries of its artist and
+    /// currency; its amount is set to what was actually reserved. Returns
+    /// `None` if the artist already has an open payout in that currency or the
+    /// reserved amount is below `minimum`.
+    async fn open_payout(&self, payout: &ArtistPayout, minimum: f64) -> PaymentRepositoryResult<Option<ArtistPayout>>;
+
+    /// Persist a status change of a payout that was `expected` before.
+    /// Fails with `ConcurrencyConflict` if someone else moved it first.
+    async fn transition_payout(&self, payout: &ArtistPayout, expected: ArtistPayoutStatus) -> PaymentRepositoryResult<()>;
+
+    /// Confirmed payout: its entries become settled
+    async fn settle_payout(&self, payout: &ArtistPayout) -> PaymentRepositoryResult<()>;
+
+    /// Failed payout: its entries go back to available
+    async fn release_payout(&self, payout: &ArtistPayout) -> PaymentRepositoryResult<()>;
+
+    async fn find_payouts_by_status(&self, status: ArtistPayoutStatus) -> PaymentRepositoryResult<Vec<ArtistPayout>>;
+
+    /// Payouts of an artist, newest first
+    async fn find_payouts_by_artist(&self, artist_id: Uuid) -> PaymentRepositoryResult<Vec<ArtistPayout>>;
+}
+
+/// Repository for royalty advances and their repayments
+#[async_trait]
+pub trait RoyaltyAdvanceRepository: Send + Sync {
+    /// Store a new advance and credit its amount to the artist balance (the
+    /// ledger entry's source is the advance id). Fails with `ConflictError`
+    /// if the artist already has an active advance.
+    async fn create(&self, advance: &RoyaltyAdvance) -> PaymentRepositoryResult<()>;
+
+    async fn find_active(&self, artist_id: Uuid) -> PaymentRepositoryResult<Option<RoyaltyAdvance>>;
+
+    /// Advances of an artist, newest first
+    async fn find_by_artist(&self, artist_id: Uuid) -> PaymentRepositoryResult<Vec<RoyaltyAdvance>>;
+
+    /// Royalties credited to the artist in `currency` per calendar month in
+    /// [since, until), before repayments and without advances. Months without
+    /// royalties are absent.
+    async fn monthly_royalties(
+        &self,
+        artist_id: Uuid,
+        currency: &Currency,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> PaymentRepositoryResult<Vec<(DateTime<Utc>, f64)>>;
+
+    /// Repayment already withheld from a royalty payout, if any
+    async fn find_repayment(&self, source_id: Uuid) -> PaymentRepositoryResult<Option<AdvanceRepayment>>;
+
+    /// Store `repayment` together with the resulting state of `advance`.
+    /// Returns the repayment recorded for its source: if an earlier attempt
+    /// already recorded one, that one is returned and the advance is left
+    /// untouched. Fails with `ConcurrencyConflict` if the advance changed
+    /// since it was loaded.
+    async fn record_repayment(
+        &self,
+        advance: &RoyaltyAdvance,
+        repayment: &AdvanceRepayment,
+    ) -> PaymentRepositoryResult<AdvanceRepayment>;
+
+    /// Undo the repayments taken from these royalty payouts; a repaid advance
+    /// becomes active again
+    async fn revert_repayments(&self, source_ids: &[Uuid]) -> PaymentRepositoryResult<()>;
+}
+
 /// Repository for Revenue Sharing Aggregates
 #[async_trait]
 pub trait RevenueSharingRepository: Send + Sync {
@@ -0,0 +1,329 @@
+//! Royalty advances
+//!
+//! An artist can borrow against future royalties. The advance is paid through
+//! the artist balance and repaid from the royalties credited afterwards: every
+//! creator payout gives up `repayment_rate` of its amount until the
+//! outstanding balance reaches zero. Each repayment is recorded against the
+//! royalty payout it came from, so crediting the same payout twice does not
+//! repay twice and voiding the run gives the repayment back.
+
+use chrono::{DateTime, Datelike, Months, TimeZone, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use super::value_objects::Currency;
+
+/// Decimales con los que se guardan saldo y cuotas (los del ledger)
+const AMOUNT_DECIMALS: u32 = 6;
+
+/// Share of each royalty credit withheld when the artist does not choose one
+pub const DEFAULT_REPAYMENT_RATE: f64 = 0.2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoyaltyAdvanceStatus {
+    /// Being repaid from royalties
+    Active,
+    Repaid,
+    /// Written off; no longer repaid from royalties
+    Defaulted,
+}
+
+impl fmt::Display for RoyaltyAdvanceStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            RoyaltyAdvanceStatus::Active => "active",
+            RoyaltyAdvanceStatus::Repaid => "repaid",
+            RoyaltyAdvanceStatus::Defaulted => "defaulted",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for RoyaltyAdvanceStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "active" => Ok(RoyaltyAdvanceStatus::Active),
+            "repaid" => Ok(RoyaltyAdvanceStatus::Repaid),
+            "defaulted" => Ok(RoyaltyAdvanceStatus::Defaulted),
+            other => Err(format!("Unknown royalty advance status: {}", other)),
+        }
+    }
+}
+
+/// Money lent to an artist against future royalties
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoyaltyAdvance {
+    pub id: Uuid,
+    pub artist_id: Uuid,
+    pub advance_amount: Decimal,
+    /// Share of each royalty credit withheld, in (0, 1)
+    pub repayment_rate: f64,
+    pub outstanding_balance: Decimal,
+    /// Only royalties in this currency repay the advance
+    pub currency: Currency,
+    pub status: RoyaltyAdvanceStatus,
+    pub created_at: DateTime<Utc>,
+    pub repaid_at: Option<DateTime<Utc>>,
+}
+
+/// Part of one royalty credit withheld to repay an advance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdvanceRepayment {
+    pub advance_id: Uuid,
+    /// Royalty payout the amount was withheld from
+    pub source_id: Uuid,
+    pub amount: Decimal,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RoyaltyAdvance {
+    pub fn new(artist_id: Uuid, advance_amount: Decimal, repayment_rate: f64, currency: Currency) -> Result<Self, AppError> {
+        let advance_amount = advance_amount.round_dp(AMOUNT_DECIMALS);
+        if advance_amount <= Decimal::ZERO {
+            return Err(AppError::ValidationError("Advance amount must be positive".to_string()));
+        }
+        // Con un 100% el crédito desaparecería entero y el ledger no admite importes a cero
+        if !(repayment_rate > 0.0 && repayment_rate < 1.0) {
+            return Err(AppError::ValidationError(format!(
+                "Repayment rate must be between 0 and 1 (exclusive), got {}",
+                repayment_rate
+            )));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            artist_id,
+            advance_amount,
+            repayment_rate,
+            outstanding_balance: advance_amount,
+            currency,
+            status: RoyaltyAdvanceStatus::Active,
+            created_at: Utc::now(),
+            repaid_at: None,
+        })
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status == RoyaltyAdvanceStatus::Active
+    }
+
+    /// Withhold the repayment from a royalty credit of `royalty`: the repayment
+    /// rate of it, capped at what is still owed. Reaching zero marks the
+    /// advance repaid. `None` when nothing is withheld (advance not active,
+    /// other currency or nothing to withhold).
+    pub fn withhold(
+        &mut self,
+        source_id: Uuid,
+        royalty: f64,
+        currency: &Currency,
+        now: DateTime<Utc>,
+    ) -> Result<Option<AdvanceRepayment>, AppError> {
+        if !self.is_active() || *currency != self.currency || royalty <= 0.0 {
+            return Ok(None);
+        }
+
+        let royalty = to_decimal(royalty)?;
+        let rate = to_decimal(self.repayment_rate)?;
+        let amount = (royalty * rate).round_dp(AMOUNT_DECIMALS).min(self.outstanding_balance);
+        if amount <= Decimal::ZERO {
+            return Ok(None);
+        }
+
+        self.outstanding_balance -= amount;
+        if self.outstanding_balance.is_zero() {
+            self.status = RoyaltyAdvanceStatus::Repaid;
+            self.repaid_at = Some(now);
+        }
+
+        Ok(Some(AdvanceRepayment { advance_id: self.id, source_id, amount, created_at: now }))
+    }
+
+    pub fn mark_defaulted(&mut self) -> Result<(), AppError> {
+        if !self.is_active() {
+            return Err(AppError::InvalidState(format!("Advance {} is {} and cannot default", self.id, self.status)));
+        }
+        self.status = RoyaltyAdvanceStatus::Defaulted;
+        Ok(())
+    }
+}
+
+impl AdvanceRepayment {
+    /// What is left of a royalty credit of `royalty` after this repayment
+    pub fn remainder_of(&self, royalty: f64) -> f64 {
+        (to_decimal(royalty).unwrap_or_default() - self.amount)
+            .round_dp(AMOUNT_DECIMALS)
+            .to_f64()
+            .unwrap_or_default()
+            .max(0.0)
+    }
+}
+
+fn to_decimal(value: f64) -> Result<Decimal, AppError> {
+    Decimal::try_from(value).map_err(|e| AppError::InvalidInput(format!("Invalid amount {}: {}", value, e)))
+}
+
+/// When an artist qualifies for an advance
+#[derive(Debug, Clone)]
+pub struct AdvancePolicy {
+    /// Complete months of royalties the projection is based on
+    pub history_months: u32,
+    /// Projected monthly royalties must exceed this many monthly repayments
+    pub coverage_ratio: Decimal,
+    /// Months the advance is expected to be repaid in; the monthly repayment
+    /// is the advance amount over this term
+    pub term_months: u32,
+}
+
+impl Default for AdvancePolicy {
+    fn default() -> Self {
+        Self { history_months: 3, coverage_ratio: Decimal::new(3, 0), term_months: 12 }
+    }
+}
+
+impl AdvancePolicy {
+    pub fn from_env() -> Self {
+        let term_months = std::env::var("ROYALTY_ADVANCE_TERM_MONTHS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|months| *months > 0)
+            .unwrap_or(12);
+
+        Self { term_months, ..Self::default() }
+    }
+
+    /// Start and end of the complete months the projection looks at, ending
+    /// at the start of the current month
+    pub fn history_window(&self, now: DateTime<Utc>) -> (DateTime<Utc>, DateTime<Utc>) {
+        let month_start = Utc
+            .with_ymd_and_hms(now.year(), now.month(), 1, 0, 0, 0)
+            .single()
+            .unwrap_or(now);
+        let since = month_start
+            .checked_sub_months(Months::new(self.history_months))
+            .unwrap_or(month_start);
+        (since, month_start)
+    }
+
+    pub fn monthly_repayment(&self, advance_amount: Decimal) -> Decimal {
+        advance_amount / Decimal::from(self.term_months.max(1))
+    }
+
+    /// Projected monthly royalties from the totals of the months in the
+    /// history window that had royalties. Fails with
+    /// `InsufficientRoyaltyHistory` if any month of the window had none, and
+    /// with `DomainRuleViolation` if the projection does not exceed
+    /// `coverage_ratio` monthly repayments.
+    pub fn check_eligibility(&self, advance_amount: Decimal, monthly_royalties: &[f64]) -> Result<Decimal, AppError> {
+        let months_with_royalties = monthly_royalties.iter().filter(|total| **total > 0.0).count();
+        if months_with_royalties < self.history_months as usize {
+            return Err(AppError::InsufficientRoyaltyHistory(format!(
+                "{} month(s) of royalties in the last {}, {} required",
+                months_with_royalties, self.history_months, self.history_months
+            )));
+        }
+
+        let total = monthly_royalties
+            .iter()
+            .try_fold(Decimal::ZERO, |sum, month| to_decimal(*month).map(|month| sum + month))?;
+        let projected = (total / Decimal::from(self.history_months)).round_dp(AMOUNT_DECIMALS);
+        let required = self.monthly_repayment(advance_amount) * self.coverage_ratio;
+        if projected <= required {
+            return Err(AppError::DomainRuleViolation(format!(
+                "Projected monthly royalties of {} do not exceed {} times the monthly repayment of {}",
+                projected,
+                self.coverage_ratio,
+                self.monthly_repayment(advance_amount).round_dp(AMOUNT_DECIMALS)
+            )));
+        }
+        Ok(projected)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn advance(amount: i64, rate: f64) -> RoyaltyAdvance {
+        RoyaltyAdvance::new(Uuid::new_v4(), Decimal::new(amount, 0), rate, Currency::USD).unwrap()
+    }
+
+    #[test]
+    fn withholds_the_repayment_rate_until_repaid() {
+        let mut advance = advance(50, 0.2);
+        let now = Utc::now();
+
+        let repayment = advance.withhold(Uuid::new_v4(), 100.0, &Currency::USD, now).unwrap().unwrap();
+        assert_eq!(repayment.amount, Decimal::new(20, 0));
+        assert_eq!(repayment.remainder_of(100.0), 80.0);
+        assert_eq!(advance.outstanding_balance, Decimal::new(30, 0));
+        assert!(advance.is_active());
+
+        let repayment = advance.withhold(Uuid::new_v4(), 12.5, &Currency::USD, now).unwrap().unwrap();
+        assert_eq!(repayment.amount, Decimal::new(25, 1));
+        assert_eq!(advance.outstanding_balance, Decimal::new(275, 1));
+
+        // La última cuota sólo retiene lo que falta
+        let repayment = advance.withhold(Uuid::new_v4(), 1000.0, &Currency::USD, now).unwrap().unwrap();
+        assert_eq!(repayment.amount, Decimal::new(275, 1));
+        assert_eq!(repayment.remainder_of(1000.0), 972.5);
+        assert_eq!(advance.outstanding_balance, Decimal::ZERO);
+        assert_eq!(advance.status, RoyaltyAdvanceStatus::Repaid);
+        assert_eq!(advance.repaid_at, Some(now));
+
+        assert!(advance.withhold(Uuid::new_v4(), 100.0, &Currency::USD, now).unwrap().is_none());
+    }
+
+    #[test]
+    fn only_active_advances_in_the_same_currency_withhold() {
+        let mut advance = advance(50, 0.2);
+        assert!(advance.withhold(Uuid::new_v4(), 100.0, &Currency::USDC, Utc::now()).unwrap().is_none());
+
+        advance.mark_defaulted().unwrap();
+        assert!(advance.withhold(Uuid::new_v4(), 100.0, &Currency::USD, Utc::now()).unwrap().is_none());
+        assert_eq!(advance.outstanding_balance, Decimal::new(50, 0));
+        assert!(advance.mark_defaulted().is_err());
+    }
+
+    #[test]
+    fn advances_are_validated() {
+        assert!(RoyaltyAdvance::new(Uuid::new_v4(), Decimal::ZERO, 0.2, Currency::USD).is_err());
+        assert!(RoyaltyAdvance::new(Uuid::new_v4(), Decimal::new(100, 0), 0.0, Currency::USD).is_err());
+        assert!(RoyaltyAdvance::new(Uuid::new_v4(), Decimal::new(100, 0), 1.0, Currency::USD).is_err());
+    }
+
+    #[test]
+    fn eligibility_needs_three_months_covering_three_repayments() {
+        let policy = AdvancePolicy::default();
+        // 1200 a 12 meses: 100 al mes, hacen falta más de 300 al mes
+        let amount = Decimal::new(1200, 0);
+
+        assert!(matches!(
+            policy.check_eligibility(amount, &[400.0, 400.0]),
+            Err(AppError::InsufficientRoyaltyHistory(_))
+        ));
+        assert!(matches!(
+            policy.check_eligibility(amount, &[400.0, 0.0, 400.0]),
+            Err(AppError::InsufficientRoyaltyHistory(_))
+        ));
+        assert!(matches!(
+            policy.check_eligibility(amount, &[300.0, 300.0, 300.0]),
+            Err(AppError::DomainRuleViolation(_))
+        ));
+        assert_eq!(policy.check_eligibility(amount, &[200.0, 300.0, 500.0]).unwrap(), Decimal::new(333_333_333, 6));
+    }
+
+    #[test]
+    fn history_window_covers_the_complete_months_before_now() {
+        let now = Utc.with_ymd_and_hms(2026, 2, 14, 10, 30, 0).unwrap();
+        let (since, until) = AdvancePolicy::default().history_window(now);
+        assert_eq!(since, Utc.with_ymd_and_hms(2025, 11, 1, 0, 0, 0).unwrap());
+        assert_eq!(until, Utc.with_ymd_and_hms(2026, 2, 1, 0, 0, 0).unwrap());
+    }
+}
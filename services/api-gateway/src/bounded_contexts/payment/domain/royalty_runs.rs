@@ -0,0 +1,737 @@
+//! Royalty distribution runs
+//!
+//! A run splits the revenue of one song over one period between the song's
+//! creators and the platform. The split is never taken from the caller: it is
+//! derived from the song's `RoyaltyPercentage` and credits
+//! (`RoyaltySplitPlan::derive`) and caller-supplied rules are only accepted
+//! when they agree with it. Each (song, period) is distributed once; a run
+//! with different terms needs the previous one voided by an admin first.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use super::exchange::NormalizedAmount;
+use super::value_objects::Amount;
+
+const AMOUNT_SCALE: f64 = 1_000_000.0;
+/// Tolerancia para la suma de porcentajes (coma flotante)
+const PERCENTAGE_EPSILON: f64 = 1e-6;
+/// Diferencia admitida entre las reglas enviadas y las derivadas de la canción
+const RULE_MATCH_TOLERANCE: f64 = 0.01;
+
+fn round_amount(value: f64) -> f64 {
+    (value * AMOUNT_SCALE).round() / AMOUNT_SCALE
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RoyaltyRecipientType {
+    /// Main artist of the song (uncredited remainder of the creators' pool)
+    Artist,
+    /// Credited contributor: featured artist, producer, songwriter...
+    Contributor,
+    Platform,
+}
+
+impl fmt::Display for RoyaltyRecipientType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            RoyaltyRecipientType::Artist => "artist",
+            RoyaltyRecipientType::Contributor => "contributor",
+            RoyaltyRecipientType::Platform => "platform",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for RoyaltyRecipientType {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "artist" => Ok(RoyaltyRecipientType::Artist),
+            "contributor" => Ok(RoyaltyRecipientType::Contributor),
+            "platform" => Ok(RoyaltyRecipientType::Platform),
+            other => Err(format!("Unknown royalty recipient type: {}", other)),
+        }
+    }
+}
+
+/// Share of a run's revenue that goes to one recipient
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoyaltySplit {
+    pub recipient_id: Uuid,
+    pub recipient_type: RoyaltyRecipientType,
+    /// 0-100
+    pub percentage: f64,
+}
+
+/// Royalty settings of a song, as stored by the music context
+#[derive(Debug, Clone)]
+pub struct SongRoyaltySettings {
+    pub song_id: Uuid,
+    pub artist_id: Uuid,
+    /// Creators' share of the song's revenue (0-100)
+    pub royalty_percentage: f64,
+    pub credits: Vec<SongRoyaltyCredit>,
+}
+
+/// A credit's share of the creators' pool (0-100)
+#[derive(Debug, Clone)]
+pub struct SongRoyaltyCredit {
+    pub contributor_id: Uuid,
+    pub royalty_share: f64,
+}
+
+/// Platform side of every royalty split
+#[derive(Debug, Clone)]
+pub struct RoyaltyDistributionConfig {
+    /// Account that receives the platform's share
+    pub platform_recipient_id: Uuid,
+    /// Songs cannot give creators so much that the platform keeps less than this (0-100)
+    pub min_platform_fee_percentage: f64,
+}
+
+impl RoyaltyDistributionConfig {
+    pub fn from_env() -> Self {
+        let platform_recipient_id = std::env::var("ROYALTY_PLATFORM_RECIPIENT_ID")
+            .ok()
+            .and_then(|v| Uuid::parse_str(&v).ok())
+            .unwrap_or_else(Uuid::nil);
+        let min_platform_fee_percentage = std::env::var("ROYALTY_MIN_PLATFORM_FEE_PERCENTAGE")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(10.0);
+
+        Self { platform_recipient_id, min_platform_fee_percentage }
+    }
+}
+
+/// Validated set of splits: non-negative, one entry per recipient, summing to 100%
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoyaltySplitPlan {
+    splits: Vec<RoyaltySplit>,
+}
+
+impl RoyaltySplitPlan {
+    pub fn new(splits: Vec<RoyaltySplit>) -> Result<Self, AppError> {
+        if splits.is_empty() {
+            return Err(AppError::ValidationError("Royalty split has no recipients".to_string()));
+        }
+
+        for (index, split) in splits.iter().enumerate() {
+            if !(0.0..=100.0).contains(&split.percentage) || split.percentage.is_nan() {
+                return Err(AppError::ValidationError(format!(
+                    "Royalty percentage for {} must be between 0 and 100, got {}",
+                    split.recipient_id, split.percentage
+                )));
+            }
+            if splits[..index]
+                .iter()
+                .any(|s| s.recipient_id == split.recipient_id && s.recipient_type == split.recipient_type)
+            {
+                return Err(AppError::ValidationError(format!(
+                    "Recipient {} appears more than once in the royalty split",
+                    split.recipient_id
+                )));
+            }
+        }
+        if splits.iter().filter(|s| s.recipient_type == RoyaltyRecipientType::Platform).count() > 1 {
+            return Err(AppError::ValidationError("Royalty split has more than one platform share".to_string()));
+        }
+
+        let total: f64 = splits.iter().map(|s| s.percentage).sum();
+        if (total - 100.0).abs() > PERCENTAGE_EPSILON {
+            return Err(AppError::ValidationError(format!(
+                "Royalty split percentages sum to {}%, expected 100%",
+                total
+            )));
+        }
+
+        Ok(Self { splits })
+    }
+
+    /// Split of a song: the creators' pool (`royalty_percentage`) is shared
+    /// between credits, the uncredited part of it goes to the main artist, and
+    /// the rest of the revenue is the platform's.
+    pub fn derive(settings: &SongRoyaltySettings, config: &RoyaltyDistributionConfig) -> Result<Self, AppError> {
+        let pool = settings.royalty_percentage;
+        if !(0.0..=100.0).contains(&pool) {
+            return Err(AppError::ValidationError(format!(
+                "Song {} has an invalid royalty percentage: {}",
+                settings.song_id, pool
+            )));
+        }
+
+        let platform_share = 100.0 - pool;
+        if platform_share + PERCENTAGE_EPSILON < config.min_platform_fee_percentage {
+            return Err(AppError::ValidationError(format!(
+                "Song {} gives creators {}% of revenue; the platform fee is at least {}%",
+                settings.song_id, pool, config.min_platform_fee_percentage
+            )));
+        }
+
+        let credited: f64 = settings.credits.iter().map(|c| c.royalty_share).sum();
+        if credited > 100.0 + PERCENTAGE_EPSILON {
+            return Err(AppError::ValidationError(format!(
+                "Song {} credits add up to {}% of the creators' pool",
+                settings.song_id, credited
+            )));
+        }
+
+        let mut splits: Vec<RoyaltySplit> = Vec::new();
+        let mut add = |recipient_id: Uuid, recipient_type: RoyaltyRecipientType, percentage: f64| {
+            if percentage <= 0.0 {
+                return;
+            }
+            match splits.iter_mut().find(|s| s.recipient_id == recipient_id && s.recipient_type != RoyaltyRecipientType::Platform) {
+                Some(existing) => existing.percentage += percentage,
+                None => splits.push(RoyaltySplit { recipient_id, recipient_type, percentage }),
+            }
+        };
+
+        // El artista principal va primero: si además tiene créditos, se suman a su parte
+        add(settings.artist_id, RoyaltyRecipientType::Artist, pool * (1.0 - credited / 100.0).max(0.0));
+        for credit in &settings.credits {
+            let recipient_type = if credit.contributor_id == settings.artist_id {
+                RoyaltyRecipientType::Artist
+            } else {
+                RoyaltyRecipientType::Contributor
+            };
+            add(credit.contributor_id, recipient_type, pool * credit.royalty_share / 100.0);
+        }
+        add(config.platform_recipient_id, RoyaltyRecipientType::Platform, platform_share);
+
+        Self::new(splits)
+    }
+
+    pub fn splits(&self) -> &[RoyaltySplit] {
+        &self.splits
+    }
+
+    /// Same recipients with the same percentages. The platform share is
+    /// matched by type: callers do not know the treasury account id.
+    pub fn matches(&self, other: &RoyaltySplitPlan) -> bool {
+        self.splits.len() == other.splits.len()
+            && self.splits.iter().all(|split| {
+                other.splits.iter().any(|candidate| {
+                    let same_recipient = match split.recipient_type {
+                        RoyaltyRecipientType::Platform => candidate.recipient_type == RoyaltyRecipientType::Platform,
+                        _ => candidate.recipient_type != RoyaltyRecipientType::Platform
+                            && candidate.recipient_id == split.recipient_id,
+                    };
+                    same_recipient && (candidate.percentage - split.percentage).abs() <= RULE_MATCH_TOLERANCE
+                })
+            })
+    }
+
+    /// Amount of `total` for each split, rounded to 6 decimals. The rounding
+    /// residue goes to the platform so the amounts always add up to `total`.
+    pub fn allocate(&self, total: f64) -> Vec<(RoyaltySplit, f64)> {
+        let mut allocations: Vec<(RoyaltySplit, f64)> = self
+            .splits
+            .iter()
+            .map(|split| (split.clone(), round_amount(total * split.percentage / 100.0)))
+            .collect();
+
+        let allocated: f64 = allocations.iter().map(|(_, amount)| amount).sum();
+        let residue = round_amount(total - allocated);
+        if residue != 0.0 {
+            let index = allocations
+                .iter()
+                .position(|(split, _)| split.recipient_type == RoyaltyRecipientType::Platform)
+                .unwrap_or(allocations.len() - 1);
+            allocations[index].1 = round_amount(allocations[index].1 + residue);
+        }
+        allocations
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoyaltyRunStatus {
+    Active,
+    /// Voided by an admin: no longer blocks a new run for its period
+    Voided,
+}
+
+impl fmt::Display for RoyaltyRunStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RoyaltyRunStatus::Active => write!(f, "active"),
+            RoyaltyRunStatus::Voided => write!(f, "voided"),
+        }
+    }
+}
+
+impl FromStr for RoyaltyRunStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "active" => Ok(RoyaltyRunStatus::Active),
+            "voided" => Ok(RoyaltyRunStatus::Voided),
+            other => Err(format!("Unknown royalty run status: {}", other)),
+        }
+    }
+}
+
+/// What to do with a new run given the runs already recorded for the song
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunResolution {
+    Create,
+    /// Same song, period and terms: the distribution already happened
+    AlreadyDistributed(RoyaltyDistributionRun),
+}
+
+/// One distribution of a song's revenue for a period
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoyaltyDistributionRun {
+    pub id: Uuid,
+    pub song_id: Uuid,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    /// Revenue in the base currency; payouts are computed from it
+    pub total_revenue: Amount,
+    /// Revenue as reported, when it was converted to the base currency
+    pub original_revenue: Option<Amount>,
+    /// Rate applied to `original_revenue` (units of base currency per unit)
+    pub exchange_rate: Option<f64>,
+    pub plan: RoyaltySplitPlan,
+    pub status: RoyaltyRunStatus,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub voided_by: Option<Uuid>,
+    pub voided_at: Option<DateTime<Utc>>,
+    pub void_reason: Option<String>,
+}
+
+impl RoyaltyDistributionRun {
+    pub fn new(
+        song_id: Uuid,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        total_revenue: Amount,
+        plan: RoyaltySplitPlan,
+        created_by: Uuid,
+    ) -> Result<Self, AppError> {
+        if period_end <= period_start {
+            return Err(AppError::ValidationError("Royalty period must end after it starts".to_string()));
+        }
+        if total_revenue.value() <= 0.0 {
+            return Err(AppError::ValidationError("Royalty revenue must be positive".to_string()));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            song_id,
+            period_start,
+            period_end,
+            total_revenue,
+            original_revenue: None,
+            exchange_rate: None,
+            plan,
+            status: RoyaltyRunStatus::Active,
+            created_by,
+            created_at: Utc::now(),
+            voided_by: None,
+            voided_at: None,
+            void_reason: None,
+        })
+    }
+
+    /// Keep the revenue as reported when `total_revenue` is its conversion
+    /// to the base currency
+    pub fn with_conversion(mut self, revenue: &NormalizedAmount) -> Self {
+        if revenue.is_converted() {
+            self.original_revenue = Some(revenue.original.clone());
+            self.exchange_rate = Some(revenue.rate.rate);
+        }
+        self
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status == RoyaltyRunStatus::Active
+    }
+
+    /// Periods are half-open: a run ending when another starts does not overlap it
+    pub fn overlaps(&self, other: &RoyaltyDistributionRun) -> bool {
+        self.song_id == other.song_id
+            && self.period_start < other.period_end
+            && other.period_start < self.period_end
+    }
+
+    pub fn same_terms(&self, other: &RoyaltyDistributionRun) -> bool {
+        self.song_id == other.song_id
+            && self.period_start == other.period_start
+            && self.period_end == other.period_end
+            && Self::same_amount(self.reported_revenue(), other.reported_revenue())
+            && self.plan.matches(&other.plan)
+    }
+
+    /// Revenue as the caller reported it. A retry of a converted run is
+    /// recognised by it even if the exchange rate moved in between.
+    pub fn reported_revenue(&self) -> &Amount {
+        self.original_revenue.as_ref().unwrap_or(&self.total_revenue)
+    }
+
+    fn same_amount(a: &Amount, b: &Amount) -> bool {
+        a.currency() == b.currency() && (a.value() - b.value()).abs() < 1e-6
+    }
+
+    /// Check this (new) run against the song's recorded runs. Voided runs are
+    /// ignored; any active run overlapping the period blocks it unless it is
+    /// the very same distribution.
+    pub fn resolve_against(&self, existing: &[RoyaltyDistributionRun]) -> Result<RunResolution, AppError> {
+        let blocking: Vec<&RoyaltyDistributionRun> = existing
+            .iter()
+            .filter(|run| run.is_active() && run.overlaps(self))
+            .collect();
+
+        match blocking.as_slice() {
+            [] => Ok(RunResolution::Create),
+            [previous] if previous.same_terms(self) => Ok(RunResolution::AlreadyDistributed((*previous).clone())),
+            [previous] if previous.period_start == self.period_start && previous.period_end == self.period_end => {
+                Err(AppError::ConflictError(format!(
+                    "Song {} was already distributed for this period by run {} with different terms; an admin must void it first",
+                    self.song_id, previous.id
+                )))
+            }
+            [previous, ..] => Err(AppError::ConflictError(format!(
+                "Period {} - {} overlaps royalty run {} ({} - {}) of song {}",
+                self.period_start, self.period_end, previous.id, previous.period_start, previous.period_end, self.song_id
+            ))),
+        }
+    }
+
+    pub fn void(&mut self, voided_by: Uuid, reason: String) -> Result<(), AppError> {
+        if !self.is_active() {
+            return Err(AppError::InvalidState(format!("Royalty run {} is already voided", self.id)));
+        }
+        self.status = RoyaltyRunStatus::Voided;
+        self.voided_by = Some(voided_by);
+        self.voided_at = Some(Utc::now());
+        self.void_reason = Some(reason);
+        Ok(())
+    }
+
+    /// One payout record per recipient. The platform's share is retained, not paid out.
+    pub fn payouts(&self) -> Result<Vec<RoyaltyPayout>, AppError> {
+        self.plan
+            .allocate(self.total_revenue.value())
+            .into_iter()
+            .map(|(split, amount)| {
+                let status = if split.recipient_type == RoyaltyRecipientType::Platform {
+                    RoyaltyPayoutStatus::Retained
+                } else {
+                    RoyaltyPayoutStatus::Pending
+                };
+                Ok(RoyaltyPayout {
+                    id: Uuid::new_v4(),
+                    run_id: self.id,
+                    song_id: self.song_id,
+                    recipient_id: split.recipient_id,
+                    recipient_type: split.recipient_type,
+                    percentage: split.percentage,
+                    amount: Amount::new(amount, self.total_revenue.currency().clone())?,
+                    status,
+                    created_at: self.created_at,
+                })
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RoyaltyPayoutStatus {
+    /// Waiting to be handed to the payout scheduler
+    Pending,
+    /// Accepted by the payout scheduler
+    Scheduled,
+    /// Platform share, stays with the platform
+    Retained,
+    /// The run was voided
+    Cancelled,
+}
+
+impl fmt::Display for RoyaltyPayoutStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            RoyaltyPayoutStatus::Pending => "pending",
+            RoyaltyPayoutStatus::Scheduled => "scheduled",
+            RoyaltyPayoutStatus::Retained => "retained",
+            RoyaltyPayoutStatus::Cancelled => "cancelled",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+impl FromStr for RoyaltyPayoutStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "pending" => Ok(RoyaltyPayoutStatus::Pending),
+            "scheduled" => Ok(RoyaltyPayoutStatus::Scheduled),
+            "retained" => Ok(RoyaltyPayoutStatus::Retained),
+            "cancelled" => Ok(RoyaltyPayoutStatus::Cancelled),
+            other => Err(format!("Unknown royalty payout status: {}", other)),
+        }
+    }
+}
+
+/// Amount owed to one recipient by a run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoyaltyPayout {
+    pub id: Uuid,
+    pub run_id: Uuid,
+    pub song_id: Uuid,
+    pub recipient_id: Uuid,
+    pub recipient_type: RoyaltyRecipientType,
+    pub percentage: f64,
+    pub amount: Amount,
+    pub status: RoyaltyPayoutStatus,
+    pub created_at: DateTime<Utc>,
+}
+
+impl RoyaltyPayout {
+    /// Payouts that go to an artist or contributor (not the platform)
+    pub fn is_creator_payout(&self) -> bool {
+        self.recipient_type != RoyaltyRecipientType::Platform
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::exchange::ExchangeRate;
+    use super::super::value_objects::Currency;
+    use chrono::TimeZone;
+
+    fn config() -> RoyaltyDistributionConfig {
+        RoyaltyDistributionConfig {
+            platform_recipient_id: Uuid::nil(),
+            min_platform_fee_percentage: 10.0,
+        }
+    }
+
+    fn settings(royalty_percentage: f64, credits: Vec<(Uuid, f64)>) -> SongRoyaltySettings {
+        SongRoyaltySettings {
+            song_id: Uuid::new_v4(),
+            artist_id: Uuid::new_v4(),
+            royalty_percentage,
+            credits: credits
+                .into_iter()
+                .map(|(contributor_id, royalty_share)| SongRoyaltyCredit { contributor_id, royalty_share })
+                .collect(),
+        }
+    }
+
+    fn split(recipient_id: Uuid, recipient_type: RoyaltyRecipientType, percentage: f64) -> RoyaltySplit {
+        RoyaltySplit { recipient_id, recipient_type, percentage }
+    }
+
+    fn day(d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, d, 0, 0, 0).unwrap()
+    }
+
+    fn run(song_id: Uuid, start: u32, end: u32, revenue: f64, plan: &RoyaltySplitPlan) -> RoyaltyDistributionRun {
+        RoyaltyDistributionRun::new(
+            song_id,
+            day(start),
+            day(end),
+            Amount::new(revenue, Currency::USD).unwrap(),
+            plan.clone(),
+            Uuid::new_v4(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn split_percentages_must_sum_to_100() {
+        let artist = Uuid::new_v4();
+        let short = RoyaltySplitPlan::new(vec![
+            split(artist, RoyaltyRecipientType::Artist, 70.0),
+            split(Uuid::nil(), RoyaltyRecipientType::Platform, 20.0),
+        ]);
+        assert!(matches!(short, Err(AppError::ValidationError(_))));
+
+        let over = RoyaltySplitPlan::new(vec![
+            split(artist, RoyaltyRecipientType::Artist, 85.0),
+            split(Uuid::nil(), RoyaltyRecipientType::Platform, 20.0),
+        ]);
+        assert!(matches!(over, Err(AppError::ValidationError(_))));
+
+        let exact = RoyaltySplitPlan::new(vec![
+            split(artist, RoyaltyRecipientType::Artist, 80.0),
+            split(Uuid::nil(), RoyaltyRecipientType::Platform, 20.0),
+        ]);
+        assert!(exact.is_ok());
+    }
+
+    #[test]
+    fn split_rejects_negative_and_duplicate_recipients() {
+        let artist = Uuid::new_v4();
+        let negative = RoyaltySplitPlan::new(vec![
+            split(artist, RoyaltyRecipientType::Artist, 110.0),
+            split(Uuid::nil(), RoyaltyRecipientType::Platform, -10.0),
+        ]);
+        assert!(matches!(negative, Err(AppError::ValidationError(_))));
+
+        let duplicate = RoyaltySplitPlan::new(vec![
+            split(artist, RoyaltyRecipientType::Artist, 40.0),
+            split(artist, RoyaltyRecipientType::Artist, 40.0),
+            split(Uuid::nil(), RoyaltyRecipientType::Platform, 20.0),
+        ]);
+        assert!(matches!(duplicate, Err(AppError::ValidationError(_))));
+    }
+
+    #[test]
+    fn derived_split_honors_song_percentage_and_credits() {
+        let producer = Uuid::new_v4();
+        let song = settings(80.0, vec![(producer, 25.0)]);
+        let plan = RoyaltySplitPlan::derive(&song, &config()).unwrap();
+
+        let expected = RoyaltySplitPlan::new(vec![
+            split(song.artist_id, RoyaltyRecipientType::Artist, 60.0),
+            split(producer, RoyaltyRecipientType::Contributor, 20.0),
+            split(Uuid::nil(), RoyaltyRecipientType::Platform, 20.0),
+        ])
+        .unwrap();
+        assert!(plan.matches(&expected));
+    }
+
+    #[test]
+    fn song_percentage_cannot_undercut_platform_fee() {
+        let song = settings(95.0, vec![]);
+        assert!(matches!(
+            RoyaltySplitPlan::derive(&song, &config()),
+            Err(AppError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn caller_rules_must_match_song_settings() {
+        let song = settings(80.0, vec![]);
+        let derived = RoyaltySplitPlan::derive(&song, &config()).unwrap();
+
+        // El id de la plataforma que manda el cliente no importa, sólo el tipo
+        let agreeing = RoyaltySplitPlan::new(vec![
+            split(song.artist_id, RoyaltyRecipientType::Artist, 80.0),
+            split(Uuid::new_v4(), RoyaltyRecipientType::Platform, 20.0),
+        ])
+        .unwrap();
+        assert!(agreeing.matches(&derived));
+
+        let greedy = RoyaltySplitPlan::new(vec![
+            split(song.artist_id, RoyaltyRecipientType::Artist, 90.0),
+            split(Uuid::nil(), RoyaltyRecipientType::Platform, 10.0),
+        ])
+        .unwrap();
+        assert!(!greedy.matches(&derived));
+    }
+
+    #[test]
+    fn allocation_adds_up_to_revenue() {
+        let plan = RoyaltySplitPlan::new(vec![
+            split(Uuid::new_v4(), RoyaltyRecipientType::Artist, 33.333333),
+            split(Uuid::new_v4(), RoyaltyRecipientType::Contributor, 33.333333),
+            split(Uuid::nil(), RoyaltyRecipientType::Platform, 33.333334),
+        ])
+        .unwrap();
+
+        let allocations = plan.allocate(1.99);
+        let total: f64 = allocations.iter().map(|(_, amount)| amount).sum();
+        assert!((total - 1.99).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rerun_with_same_terms_is_idempotent() {
+        let song = settings(80.0, vec![]);
+        let plan = RoyaltySplitPlan::derive(&song, &config()).unwrap();
+        let first = run(song.song_id, 1, 31, 100.0, &plan);
+        let again = run(song.song_id, 1, 31, 100.0, &plan);
+
+        assert_eq!(again.resolve_against(&[first.clone()]).unwrap(), RunResolution::AlreadyDistributed(first));
+    }
+
+    #[test]
+    fn converted_rerun_matches_on_the_reported_revenue() {
+        let song = settings(80.0, vec![]);
+        let plan = RoyaltySplitPlan::derive(&song, &config()).unwrap();
+        let eur = Amount::new(1000.0, Currency::EUR).unwrap();
+        let converted_run = |rate: f64| {
+            let rate = ExchangeRate::new(Currency::EUR, Currency::USD, rate, Utc::now()).unwrap();
+            let revenue = NormalizedAmount::new(eur.clone(), rate).unwrap();
+            RoyaltyDistributionRun::new(song.song_id, day(1), day(31), revenue.base.clone(), plan.clone(), Uuid::new_v4())
+                .unwrap()
+                .with_conversion(&revenue)
+        };
+
+        let first = converted_run(1.0837);
+        assert_eq!(first.total_revenue, Amount::new(1083.7, Currency::USD).unwrap());
+        assert_eq!(first.original_revenue, Some(eur.clone()));
+        // Los pagos se calculan en la moneda base
+        assert!(first.payouts().unwrap().iter().all(|p| p.amount.currency() == &Currency::USD));
+
+        // El tipo cambió entre el primer intento y el reintento: sigue siendo el mismo reparto
+        let retry = converted_run(1.0912);
+        assert_eq!(retry.resolve_against(&[first.clone()]).unwrap(), RunResolution::AlreadyDistributed(first));
+    }
+
+    #[test]
+    fn rerun_with_changed_terms_requires_void() {
+        let song = settings(80.0, vec![]);
+        let plan = RoyaltySplitPlan::derive(&song, &config()).unwrap();
+        let mut first = run(song.song_id, 1, 31, 100.0, &plan);
+
+        let more_revenue = run(song.song_id, 1, 31, 150.0, &plan);
+        assert!(matches!(more_revenue.resolve_against(&[first.clone()]), Err(AppError::ConflictError(_))));
+
+        let changed = SongRoyaltySettings { royalty_percentage: 70.0, ..song.clone() };
+        let new_plan = RoyaltySplitPlan::derive(&changed, &config()).unwrap();
+        let new_rules = run(song.song_id, 1, 31, 100.0, &new_plan);
+        assert!(matches!(new_rules.resolve_against(&[first.clone()]), Err(AppError::ConflictError(_))));
+
+        first.void(Uuid::new_v4(), "wrong split".to_string()).unwrap();
+        assert_eq!(new_rules.resolve_against(&[first.clone()]).unwrap(), RunResolution::Create);
+        assert!(matches!(first.void(Uuid::new_v4(), "again".to_string()), Err(AppError::InvalidState(_))));
+    }
+
+    #[test]
+    fn overlapping_periods_are_rejected() {
+        let song = settings(80.0, vec![]);
+        let plan = RoyaltySplitPlan::derive(&song, &config()).unwrap();
+        let january = run(song.song_id, 1, 31, 100.0, &plan);
+
+        let mid_month = run(song.song_id, 15, 20, 10.0, &plan);
+        assert!(matches!(mid_month.resolve_against(&[january.clone()]), Err(AppError::ConflictError(_))));
+
+        let straddling = run(song.song_id, 30, 31, 10.0, &plan);
+        let first_half = run(song.song_id, 1, 16, 50.0, &plan);
+        let second_half = run(song.song_id, 16, 31, 50.0, &plan);
+        assert!(matches!(straddling.resolve_against(&[second_half.clone()]), Err(AppError::ConflictError(_))));
+
+        // Periodos contiguos (el fin es exclusivo) y otras canciones no se pisan
+        assert_eq!(second_half.resolve_against(&[first_half]).unwrap(), RunResolution::Create);
+        let other_song = run(Uuid::new_v4(), 1, 31, 100.0, &plan);
+        assert_eq!(other_song.resolve_against(&[january]).unwrap(), RunResolution::Create);
+    }
+
+    #[test]
+    fn payouts_retain_platform_share() {
+        let song = settings(80.0, vec![]);
+        let plan = RoyaltySplitPlan::derive(&song, &config()).unwrap();
+        let payouts = run(song.song_id, 1, 31, 1.99, &plan).payouts().unwrap();
+
+        assert_eq!(payouts.len(), 2);
+        let artist = payouts.iter().find(|p| p.is_creator_payout()).unwrap();
+        assert_eq!(artist.status, RoyaltyPayoutStatus::Pending);
+        assert!((artist.amount.value() - 1.592).abs() < 1e-9);
+        let platform = payouts.iter().find(|p| !p.is_creator_payout()).unwrap();
+        assert_eq!(platform.status, RoyaltyPayoutStatus::Retained);
+    }
+}
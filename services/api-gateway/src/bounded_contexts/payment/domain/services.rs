@@ -3,7 +3,7 @@ use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 use uuid::Uuid;
 
-use crate::shared::domain::errors::AppError;
+use crate::shared::domain::errors::{AppError, FraudDetails, FraudReasonCode};
 use super::aggregates::*;
 use super::entities::*;
 use super::value_objects::*;
@@ -344,6 +344,8 @@ pub struct PaymentProcessingResult {
     pub gateway_response: Option<String>,
     pub processing_time_ms: u64,
     pub fees_charged: Amount,
+    /// El gateway aceptó el pago pero la confirmación llega después (p.ej. depósito USDC)
+    pub awaiting_confirmation: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -362,6 +364,34 @@ pub struct FraudCheckResult {
     pub confidence_level: f64,
 }
 
+impl FraudCheckResult {
+    /// Motivo principal del bloqueo, derivado del primer indicador reconocido
+    pub fn reason_code(&self) -> FraudReasonCode {
+        self.fraud_indicators
+            .iter()
+            .find_map(|indicator| {
+                let indicator = indicator.to_lowercase();
+                if indicator.contains("velocity") || indicator.contains("frequency") {
+                    Some(FraudReasonCode::VelocityExceeded)
+                } else if indicator.contains("amount") {
+                    Some(FraudReasonCode::UnusualAmount)
+                } else if indicator.contains("payment_method") || indicator.contains("card") {
+                    Some(FraudReasonCode::BlockedPaymentMethod)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(FraudReasonCode::HighRiskScore)
+    }
+
+    pub fn fraud_error(&self) -> AppError {
+        AppError::FraudDetected(
+            FraudDetails::new(self.reason_code(), "Payment blocked due to fraud detection")
+                .with_risk_score(self.risk_score),
+        )
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum FraudAction {
     Allow,
@@ -622,7 +652,7 @@ where
                     &payment_aggregate,
                     &fraud_result.fraud_indicators,
                 ).await?;
-                return Err(AppError::FraudDetected("Payment blocked due to fraud detection".to_string()));
+                return Err(fraud_result.fraud_error());
             }
             FraudAction::Review => {
                 // Mark for manual review but don't block
@@ -719,4 +749,32 @@ mod tests {
             _ => assert!(false),
         }
     }
+
+    fn fraud_result(indicators: &[&str]) -> FraudCheckResult {
+        FraudCheckResult {
+            risk_score: 0.95,
+            fraud_indicators: indicators.iter().map(|i| i.to_string()).collect(),
+            action_required: FraudAction::Block,
+            confidence_level: 0.9,
+        }
+    }
+
+    #[test]
+    fn test_fraud_reason_code_from_indicators() {
+        assert_eq!(fraud_result(&["High payment velocity"]).reason_code(), FraudReasonCode::VelocityExceeded);
+        assert_eq!(fraud_result(&["Unusual amount for user"]).reason_code(), FraudReasonCode::UnusualAmount);
+        assert_eq!(fraud_result(&["Card reported stolen"]).reason_code(), FraudReasonCode::BlockedPaymentMethod);
+        assert_eq!(fraud_result(&[]).reason_code(), FraudReasonCode::HighRiskScore);
+    }
+
+    #[test]
+    fn test_fraud_error_carries_reason_and_score() {
+        match fraud_result(&["velocity"]).fraud_error() {
+            AppError::FraudDetected(details) => {
+                assert_eq!(details.reason_code, FraudReasonCode::VelocityExceeded);
+                assert_eq!(details.risk_score, Some(0.95));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
 } 
\ No newline at end of file
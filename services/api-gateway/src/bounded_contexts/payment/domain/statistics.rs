@@ -0,0 +1,222 @@
+//! Payment statistics
+//!
+//! The repository returns one row per (day, status, payment type, currency)
+//! with its count and volume; every aggregate of the report is folded from
+//! those rows. Amounts in different currencies are never added together, so
+//! averages and daily volumes are reported per currency.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+
+/// Longest period a statistics query may cover
+pub const MAX_STATISTICS_PERIOD_DAYS: i64 = 366;
+
+const STATUS_COMPLETED: &str = "Completed";
+const STATUS_REFUNDED: &str = "Refunded";
+
+/// Period and scope of a statistics report
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StatisticsPeriod {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl StatisticsPeriod {
+    pub fn new(start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Self, AppError> {
+        if start >= end {
+            return Err(AppError::ValidationError("start_date must be before end_date".to_string()));
+        }
+        if end - start > Duration::days(MAX_STATISTICS_PERIOD_DAYS) {
+            return Err(AppError::ValidationError(format!(
+                "Statistics period cannot exceed {} days",
+                MAX_STATISTICS_PERIOD_DAYS
+            )));
+        }
+        Ok(Self { start, end })
+    }
+
+    /// The `days` days up to `now`
+    pub fn last_days(days: i64, now: DateTime<Utc>) -> Self {
+        Self { start: now - Duration::days(days), end: now }
+    }
+}
+
+/// Count and volume of one (day, status, payment type, currency) group
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentStatisticsRow {
+    pub day: NaiveDate,
+    pub status: String,
+    pub payment_type: String,
+    pub currency: String,
+    pub count: u64,
+    pub volume: f64,
+}
+
+/// Payment count and volume per currency
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StatisticsBucket {
+    pub count: u64,
+    pub volume: BTreeMap<String, f64>,
+}
+
+impl StatisticsBucket {
+    fn add(&mut self, row: &PaymentStatisticsRow) {
+        self.count += row.count;
+        *self.volume.entry(row.currency.clone()).or_insert(0.0) += row.volume;
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DailyPaymentStatistics {
+    pub date: NaiveDate,
+    pub payment_count: u64,
+    pub completed_count: u64,
+    pub refunded_count: u64,
+    /// Volume of completed payments per currency
+    pub completed_volume: BTreeMap<String, f64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PaymentStatisticsReport {
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    /// Artist the report is restricted to (`None` = platform-wide)
+    pub artist_id: Option<Uuid>,
+    pub total_payments: u64,
+    pub by_status: BTreeMap<String, StatisticsBucket>,
+    pub by_payment_type: BTreeMap<String, StatisticsBucket>,
+    pub by_currency: BTreeMap<String, StatisticsBucket>,
+    /// Average completed payment amount per currency
+    pub average_transaction_value: BTreeMap<String, f64>,
+    /// Refunded payments over payments that were completed or refunded
+    pub refund_rate: f64,
+    pub daily: Vec<DailyPaymentStatistics>,
+}
+
+impl PaymentStatisticsReport {
+    pub fn from_rows(period: &StatisticsPeriod, artist_id: Option<Uuid>, rows: &[PaymentStatisticsRow]) -> Self {
+        let mut by_status: BTreeMap<String, StatisticsBucket> = BTreeMap::new();
+        let mut by_payment_type: BTreeMap<String, StatisticsBucket> = BTreeMap::new();
+        let mut by_currency: BTreeMap<String, StatisticsBucket> = BTreeMap::new();
+        let mut daily: BTreeMap<NaiveDate, DailyPaymentStatistics> = BTreeMap::new();
+
+        for row in rows {
+            by_status.entry(row.status.clone()).or_default().add(row);
+            by_payment_type.entry(row.payment_type.clone()).or_default().add(row);
+            by_currency.entry(row.currency.clone()).or_default().add(row);
+
+            let day = daily.entry(row.day).or_insert_with(|| DailyPaymentStatistics {
+                date: row.day,
+                payment_count: 0,
+                completed_count: 0,
+                refunded_count: 0,
+                completed_volume: BTreeMap::new(),
+            });
+            day.payment_count += row.count;
+            match row.status.as_str() {
+                STATUS_COMPLETED => {
+                    day.completed_count += row.count;
+                    *day.completed_volume.entry(row.currency.clone()).or_insert(0.0) += row.volume;
+                }
+                STATUS_REFUNDED => day.refunded_count += row.count,
+                _ => {}
+            }
+        }
+
+        let completed = by_status.get(STATUS_COMPLETED).cloned().unwrap_or_default();
+        let refunded_count = by_status.get(STATUS_REFUNDED).map(|b| b.count).unwrap_or(0);
+
+        // Para el promedio por divisa hace falta el conteo por divisa, no el total
+        let mut completed_counts: BTreeMap<&str, u64> = BTreeMap::new();
+        for row in rows.iter().filter(|r| r.status == STATUS_COMPLETED) {
+            *completed_counts.entry(row.currency.as_str()).or_insert(0) += row.count;
+        }
+        let average_transaction_value = completed
+            .volume
+            .iter()
+            .filter_map(|(currency, volume)| {
+                let count = *completed_counts.get(currency.as_str())?;
+                (count > 0).then(|| (currency.clone(), volume / count as f64))
+            })
+            .collect();
+
+        let settled = completed.count + refunded_count;
+        let refund_rate = if settled > 0 { refunded_count as f64 / settled as f64 } else { 0.0 };
+
+        Self {
+            period_start: period.start,
+            period_end: period.end,
+            artist_id,
+            total_payments: rows.iter().map(|r| r.count).sum(),
+            by_status,
+            by_payment_type,
+            by_currency,
+            average_transaction_value,
+            refund_rate,
+            daily: daily.into_values().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(day: u32, status: &str, payment_type: &str, currency: &str, count: u64, volume: f64) -> PaymentStatisticsRow {
+        PaymentStatisticsRow {
+            day: NaiveDate::from_ymd_opt(2026, 10, day).unwrap(),
+            status: status.to_string(),
+            payment_type: payment_type.to_string(),
+            currency: currency.to_string(),
+            count,
+            volume,
+        }
+    }
+
+    #[test]
+    fn period_must_be_ordered_and_bounded() {
+        let now = Utc::now();
+        assert!(StatisticsPeriod::new(now, now).is_err());
+        assert!(StatisticsPeriod::new(now - Duration::days(400), now).is_err());
+        assert!(StatisticsPeriod::new(now - Duration::days(30), now).is_ok());
+    }
+
+    #[test]
+    fn report_folds_rows_into_each_aggregate() {
+        let period = StatisticsPeriod::last_days(30, Utc::now());
+        let rows = vec![
+            row(1, "Completed", "SongPurchase", "USD", 3, 30.0),
+            row(1, "Failed", "SongPurchase", "USD", 1, 10.0),
+            row(2, "Completed", "NFTPurchase", "USDC", 1, 50.0),
+            row(2, "Refunded", "SongPurchase", "USD", 1, 10.0),
+        ];
+
+        let report = PaymentStatisticsReport::from_rows(&period, None, &rows);
+
+        assert_eq!(report.total_payments, 6);
+        assert_eq!(report.by_status["Completed"].count, 4);
+        assert_eq!(report.by_status["Completed"].volume["USD"], 30.0);
+        assert_eq!(report.by_payment_type["SongPurchase"].count, 5);
+        assert_eq!(report.by_currency["USDC"].volume["USDC"], 50.0);
+        assert_eq!(report.average_transaction_value["USD"], 10.0);
+        assert_eq!(report.average_transaction_value["USDC"], 50.0);
+        assert_eq!(report.refund_rate, 0.2);
+        assert_eq!(report.daily.len(), 2);
+        assert_eq!(report.daily[0].payment_count, 4);
+        assert_eq!(report.daily[1].refunded_count, 1);
+        assert_eq!(report.daily[1].completed_volume["USDC"], 50.0);
+    }
+
+    #[test]
+    fn empty_period_has_zero_refund_rate() {
+        let report = PaymentStatisticsReport::from_rows(&StatisticsPeriod::last_days(30, Utc::now()), None, &[]);
+        assert_eq!(report.total_payments, 0);
+        assert_eq!(report.refund_rate, 0.0);
+        assert!(report.daily.is_empty());
+    }
+}
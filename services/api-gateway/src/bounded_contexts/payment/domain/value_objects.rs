@@ -132,6 +132,69 @@ impl Currency {
     pub fn is_cryptocurrency(&self) -> bool {
         matches!(self, Currency::ETH | Currency::SOL | Currency::USDC | Currency::VIBES)
     }
+
+    /// ISO 4217 code (ticker for cryptocurrencies)
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::USD => "USD",
+            Currency::EUR => "EUR",
+            Currency::GBP => "GBP",
+            Currency::ETH => "ETH",
+            Currency::SOL => "SOL",
+            Currency::USDC => "USDC",
+            Currency::VIBES => "VIBES",
+        }
+    }
+
+    /// Decimals of the currency's minor unit. Crypto amounts are `f64`, so
+    /// they are capped at 9 decimals instead of the chain's native precision.
+    pub fn minor_units(&self) -> u32 {
+        match self {
+            Currency::USD | Currency::EUR | Currency::GBP | Currency::VIBES => 2,
+            Currency::USDC => 6,
+            Currency::ETH | Currency::SOL => 9,
+        }
+    }
+
+    /// Round half away from zero to the currency's minor unit
+    pub fn round(&self, value: f64) -> f64 {
+        let scale = 10f64.powi(self.minor_units() as i32);
+        // 10.005 es 10.00499999… en binario: se limpia el ruido antes de redondear
+        let scaled = ((value * scale) * 1e6).round() / 1e6;
+        scaled.round() / scale
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+impl std::str::FromStr for Currency {
+    type Err = AppError;
+
+    /// Fiat codes must be ISO 4217 (three letters); crypto tickers are the
+    /// platform's own extension. Valid codes we do not settle are rejected too.
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        let code = code.trim().to_ascii_uppercase();
+        let currency = match code.as_str() {
+            "USD" => Currency::USD,
+            "EUR" => Currency::EUR,
+            "GBP" => Currency::GBP,
+            "ETH" => Currency::ETH,
+            "SOL" => Currency::SOL,
+            "USDC" => Currency::USDC,
+            "VIBES" => Currency::VIBES,
+            other if other.len() == 3 && other.chars().all(|c| c.is_ascii_uppercase()) => {
+                return Err(AppError::InvalidInput(format!("Unsupported currency: {}", other)));
+            }
+            other => {
+                return Err(AppError::InvalidInput(format!("Invalid ISO 4217 currency code: {}", other)));
+            }
+        };
+        Ok(currency)
+    }
 }
 
 /// Payment Method Value Object
@@ -282,6 +345,10 @@ pub enum PaymentPurpose {
         campaign_id: Uuid,
         nft_quantity: u32,
     },
+    /// One-off purchase of a song
+    SongPurchase {
+        song_id: Uuid,
+    },
     /// Purchase of fractional shares
     SharePurchase {
         contract_id: Uuid,
@@ -329,6 +396,9 @@ impl PaymentPurpose {
             PaymentPurpose::NFTPurchase { campaign_id, nft_quantity } => {
                 format!("Purchase of {} NFT(s) from campaign {}", nft_quantity, campaign_id)
             }
+            PaymentPurpose::SongPurchase { song_id } => {
+                format!("Purchase of song {}", song_id)
+            }
             PaymentPurpose::SharePurchase { contract_id, ownership_percentage } => {
                 format!("Purchase of {:.2}% ownership in contract {}", ownership_percentage, contract_id)
             }
@@ -356,6 +426,7 @@ impl PaymentPurpose {
     pub fn category(&self) -> PaymentCategory {
         match self {
             PaymentPurpose::NFTPurchase { .. } => PaymentCategory::Purchase,
+            PaymentPurpose::SongPurchase { .. } => PaymentCategory::Purchase,
             PaymentPurpose::SharePurchase { .. } => PaymentCategory::Investment,
             PaymentPurpose::ShareTrade { .. } => PaymentCategory::Trade,
             PaymentPurpose::RoyaltyDistribution { .. } => PaymentCategory::Payout,
@@ -406,6 +477,11 @@ pub enum PaymentStatus {
     },
     /// Payment is on hold
     OnHold,
+    /// Less than the expected amount arrived (crypto deposits)
+    PartiallyPaid {
+        received_amount: f64,
+        shortfall: f64,
+    },
 }
 
 impl PaymentStatus {
@@ -425,6 +501,21 @@ impl PaymentStatus {
     pub fn can_be_refunded(&self) -> bool {
         matches!(self, PaymentStatus::Completed)
     }
+
+    /// Nombre persistido en `payments.status` (sin los detalles de la variante)
+    pub fn name(&self) -> &'static str {
+        match self {
+            PaymentStatus::Pending => "Pending",
+            PaymentStatus::Processing => "Processing",
+            PaymentStatus::Completed => "Completed",
+            PaymentStatus::Failed { .. } => "Failed",
+            PaymentStatus::Cancelled { .. } => "Cancelled",
+            PaymentStatus::Refunding => "Refunding",
+            PaymentStatus::Refunded { .. } => "Refunded",
+            PaymentStatus::OnHold => "OnHold",
+            PaymentStatus::PartiallyPaid { .. } => "PartiallyPaid",
+        }
+    }
 }
 
 /// Payment Filter for queries
@@ -766,6 +857,24 @@ mod tests {
         assert!(amount1.add(&amount2).is_err());
     }
     
+    #[test]
+    fn test_currency_code_validation() {
+        assert_eq!(" eur ".parse::<Currency>().unwrap(), Currency::EUR);
+        assert_eq!("USDC".parse::<Currency>().unwrap(), Currency::USDC);
+        assert!(matches!("JPY".parse::<Currency>(), Err(AppError::InvalidInput(msg)) if msg.contains("Unsupported")));
+        assert!(matches!("US1".parse::<Currency>(), Err(AppError::InvalidInput(msg)) if msg.contains("ISO 4217")));
+        assert!("".parse::<Currency>().is_err());
+        assert_eq!(Currency::GBP.to_string(), "GBP");
+    }
+
+    #[test]
+    fn test_currency_rounds_to_minor_unit() {
+        assert_eq!(Currency::EUR.round(10.005), 10.01);
+        assert_eq!(Currency::USD.round(92.274_999), 92.27);
+        assert_eq!(Currency::USDC.round(1.234_567_8), 1.234_568);
+        assert_eq!(Currency::ETH.round(0.000_000_000_4), 0.0);
+    }
+
     #[test]
     fn test_fee_calculation() {
         let fee = FeePercentage::new(2.5).unwrap();
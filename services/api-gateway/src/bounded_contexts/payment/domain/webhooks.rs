@@ -0,0 +1,239 @@
+//! Outbound webhooks
+//!
+//! Platforms built on VibeStream register an endpoint and the payment events
+//! they want. Each event is POSTed as JSON with an `X-VibeStream-Signature`
+//! header: `t=<unix timestamp>,v1=<hex HMAC-SHA256 of "<timestamp>.<body>">`
+//! keyed with the webhook secret. Signing the timestamp lets receivers reject
+//! old deliveries replayed by someone who captured them.
+//!
+//! A delivery is retried a few times; a webhook whose deliveries keep failing
+//! is deactivated so a dead endpoint does not get every event forever.
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use super::aggregates::PaymentAggregate;
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-VibeStream-Signature";
+
+/// Attempts per event before the delivery counts as failed
+pub const MAX_DELIVERY_ATTEMPTS: u32 = 3;
+
+/// Failed deliveries in a row after which the webhook is deactivated
+pub const MAX_CONSECUTIVE_FAILURES: u32 = 5;
+
+/// Payment events a webhook can subscribe to
+pub const WEBHOOK_EVENT_TYPES: &[&str] = &[
+    "payment.completed",
+    "payment.failed",
+    "payment.cancelled",
+    "payment.refunded",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub endpoint_url: String,
+    pub secret: String,
+    pub subscribed_events: Vec<String>,
+    pub is_active: bool,
+    pub consecutive_failures: u32,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    /// New active webhook with a random secret
+    pub fn new(endpoint_url: &str, subscribed_events: Vec<String>, created_by: Uuid) -> Result<Self, AppError> {
+        let endpoint_url = endpoint_url.trim();
+        let is_http = endpoint_url.starts_with("https://") || endpoint_url.starts_with("http://");
+        if !is_http || endpoint_url.len() > 2048 {
+            return Err(AppError::ValidationError("endpoint_url must be an http(s) URL".to_string()));
+        }
+        if subscribed_events.is_empty() {
+            return Err(AppError::ValidationError("Subscribe to at least one event".to_string()));
+        }
+        if let Some(unknown) = subscribed_events.iter().find(|e| !WEBHOOK_EVENT_TYPES.contains(&e.as_str())) {
+            return Err(AppError::ValidationError(format!(
+                "Unknown event '{}', expected one of: {}",
+                unknown,
+                WEBHOOK_EVENT_TYPES.join(", ")
+            )));
+        }
+
+        let mut subscribed_events = subscribed_events;
+        subscribed_events.sort();
+        subscribed_events.dedup();
+
+        let mut secret = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut secret);
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            endpoint_url: endpoint_url.to_string(),
+            secret: format!("whsec_{}", hex::encode(secret)),
+            subscribed_events,
+            is_active: true,
+            consecutive_failures: 0,
+            created_by,
+            created_at: Utc::now(),
+        })
+    }
+
+    pub fn subscribes_to(&self, event_type: &str) -> bool {
+        self.is_active && self.subscribed_events.iter().any(|e| e == event_type)
+    }
+
+    /// Outcome of a delivery (after its retries). Returns true when this
+    /// failure deactivated the webhook.
+    pub fn record_delivery(&mut self, succeeded: bool) -> bool {
+        if succeeded {
+            self.consecutive_failures = 0;
+            return false;
+        }
+        self.consecutive_failures += 1;
+        if self.is_active && self.consecutive_failures >= MAX_CONSECUTIVE_FAILURES {
+            self.is_active = false;
+            return true;
+        }
+        false
+    }
+}
+
+/// Body POSTed to the endpoint
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookPayload {
+    /// Same id on every retry, so receivers can drop duplicates
+    pub id: Uuid,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub occurred_at: DateTime<Utc>,
+    pub data: serde_json::Value,
+}
+
+impl WebhookPayload {
+    pub fn new(event_type: &str, data: serde_json::Value) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            event_type: event_type.to_string(),
+            occurred_at: Utc::now(),
+            data,
+        }
+    }
+}
+
+/// One HTTP attempt to deliver an event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebhookDelivery {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub attempt: u32,
+    /// HTTP status answered by the endpoint; `None` if it could not be reached
+    pub status_code: Option<u16>,
+    pub succeeded: bool,
+    pub error: Option<String>,
+    pub attempted_at: DateTime<Utc>,
+}
+
+/// Port through which the payment flow announces its events to webhooks
+pub trait PaymentWebhookNotifier: Send + Sync {
+    /// Must return at once: delivery (and its retries) happens in the background
+    fn notify(&self, event_type: &'static str, payment: &PaymentAggregate);
+}
+
+/// `X-VibeStream-Signature` value for `body` sent at `timestamp`
+pub fn sign_webhook_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    format!("t={},v1={}", timestamp, hex::encode(signature_mac(secret, timestamp, body).finalize().into_bytes()))
+}
+
+/// Check a signature header the way a receiver should: the HMAC matches and
+/// the timestamp is no older than `tolerance_secs`
+pub fn verify_webhook_signature(secret: &str, header: &str, body: &str, now: DateTime<Utc>, tolerance_secs: i64) -> bool {
+    let mut timestamp = None;
+    let mut signature = None;
+    for part in header.split(',') {
+        match part.trim().split_once('=') {
+            Some(("t", value)) => timestamp = value.parse::<i64>().ok(),
+            Some(("v1", value)) => signature = hex::decode(value).ok(),
+            _ => {}
+        }
+    }
+    let (Some(timestamp), Some(signature)) = (timestamp, signature) else {
+        return false;
+    };
+    if (now.timestamp() - timestamp).abs() > tolerance_secs {
+        return false;
+    }
+    signature_mac(secret, timestamp, body).verify_slice(&signature).is_ok()
+}
+
+fn signature_mac(secret: &str, timestamp: i64, body: &str) -> HmacSha256 {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    mac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn webhook() -> Webhook {
+        Webhook::new("https://partner.example.com/hooks", vec!["payment.completed".to_string()], Uuid::new_v4()).unwrap()
+    }
+
+    #[test]
+    fn webhooks_need_an_http_url_and_known_events() {
+        let admin = Uuid::new_v4();
+        assert!(Webhook::new("ftp://partner.example.com", vec!["payment.completed".to_string()], admin).is_err());
+        assert!(Webhook::new("https://partner.example.com", vec![], admin).is_err());
+        assert!(Webhook::new("https://partner.example.com", vec!["user.created".to_string()], admin).is_err());
+
+        let hook = webhook();
+        assert!(hook.secret.starts_with("whsec_"));
+        assert_ne!(hook.secret, webhook().secret);
+        assert!(hook.subscribes_to("payment.completed"));
+        assert!(!hook.subscribes_to("payment.failed"));
+    }
+
+    #[test]
+    fn fifth_failed_delivery_in_a_row_deactivates_the_webhook() {
+        let mut hook = webhook();
+        for _ in 0..4 {
+            assert!(!hook.record_delivery(false));
+        }
+        // Un éxito reinicia la cuenta
+        hook.record_delivery(true);
+        for _ in 0..4 {
+            assert!(!hook.record_delivery(false));
+        }
+        assert!(hook.is_active);
+
+        assert!(hook.record_delivery(false));
+        assert!(!hook.is_active);
+        assert!(!hook.subscribes_to("payment.completed"));
+    }
+
+    #[test]
+    fn receivers_can_verify_the_signature() {
+        let now = Utc::now();
+        let body = r#"{"type":"payment.completed"}"#;
+        let header = sign_webhook_payload("whsec_test", now.timestamp(), body);
+
+        assert!(verify_webhook_signature("whsec_test", &header, body, now, 300));
+        assert!(!verify_webhook_signature("whsec_other", &header, body, now, 300));
+        assert!(!verify_webhook_signature("whsec_test", &header, r#"{"type":"payment.refunded"}"#, now, 300));
+        // Replayed too late
+        assert!(!verify_webhook_signature("whsec_test", &header, body, now + chrono::Duration::minutes(10), 300));
+    }
+}
@@ -0,0 +1,215 @@
+//! Exchange rate providers
+//!
+//! `HttpExchangeRateProvider` queries a Frankfurter-compatible API
+//! (`GET {base}/latest?from=EUR&to=USD`), `CachedExchangeRateProvider` keeps
+//! each pair for a TTL in front of it, and `FixedExchangeRateProvider` serves
+//! configured rates for tests and local development.
+
+use async_trait::async_trait;
+use chrono::{NaiveDate, TimeZone, Utc};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::application::currency_conversion::CurrencyConverter;
+use crate::bounded_contexts::payment::domain::{
+    exchange::{ExchangeRate, ExchangeRateProvider},
+    value_objects::Currency,
+};
+
+const DEFAULT_EXCHANGE_RATE_API_URL: &str = "https://api.frankfurter.app";
+const DEFAULT_CACHE_TTL_SECS: u64 = 3600;
+
+/// Rates configured up front. A pair without a rate falls back to the
+/// inverse of the opposite pair.
+#[derive(Default)]
+pub struct FixedExchangeRateProvider {
+    rates: HashMap<(Currency, Currency), f64>,
+}
+
+impl FixedExchangeRateProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_rate(mut self, from: Currency, to: Currency, rate: f64) -> Self {
+        self.rates.insert((from, to), rate);
+        self
+    }
+}
+
+#[async_trait]
+impl ExchangeRateProvider for FixedExchangeRateProvider {
+    async fn rate(&self, from: &Currency, to: &Currency) -> Result<ExchangeRate, AppError> {
+        if from == to {
+            return Ok(ExchangeRate::identity(from.clone(), Utc::now()));
+        }
+        if let Some(rate) = self.rates.get(&(from.clone(), to.clone())) {
+            return ExchangeRate::new(from.clone(), to.clone(), *rate, Utc::now());
+        }
+        if let Some(rate) = self.rates.get(&(to.clone(), from.clone())) {
+            return Ok(ExchangeRate::new(to.clone(), from.clone(), *rate, Utc::now())?.inverse());
+        }
+        Err(AppError::NotFound(format!("No exchange rate configured for {}/{}", from, to)))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LatestRatesResponse {
+    date: Option<String>,
+    rates: HashMap<String, f64>,
+}
+
+/// Rates from a Frankfurter-compatible HTTP API
+pub struct HttpExchangeRateProvider {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+impl HttpExchangeRateProvider {
+    pub fn new(base_url: String) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// URL leída de `EXCHANGE_RATE_API_URL`
+    pub fn from_env() -> Self {
+        Self::new(std::env::var("EXCHANGE_RATE_API_URL").unwrap_or_else(|_| DEFAULT_EXCHANGE_RATE_API_URL.to_string()))
+    }
+}
+
+#[async_trait]
+impl ExchangeRateProvider for HttpExchangeRateProvider {
+    async fn rate(&self, from: &Currency, to: &Currency) -> Result<ExchangeRate, AppError> {
+        if from == to {
+            return Ok(ExchangeRate::identity(from.clone(), Utc::now()));
+        }
+
+        let response = self
+            .client
+            .get(format!("{}/latest", self.base_url))
+            .query(&[("from", from.code()), ("to", to.code())])
+            .send()
+            .await
+            .map_err(|e| AppError::ExternalServiceError(format!("Exchange rate request failed: {}", e)))?;
+        if !response.status().is_success() {
+            return Err(AppError::ExternalServiceError(format!(
+                "Exchange rate API returned {} for {}/{}",
+                response.status(), from, to
+            )));
+        }
+
+        let body: LatestRatesResponse = response
+            .json()
+            .await
+            .map_err(|e| AppError::ExternalServiceError(format!("Invalid exchange rate response: {}", e)))?;
+        let rate = body.rates.get(to.code()).copied().ok_or_else(|| {
+            AppError::ExternalServiceError(format!("Exchange rate API has no rate for {}/{}", from, to))
+        })?;
+        let quoted_at = body
+            .date
+            .and_then(|d| NaiveDate::parse_from_str(&d, "%Y-%m-%d").ok())
+            .and_then(|d| d.and_hms_opt(0, 0, 0))
+            .map(|d| Utc.from_utc_datetime(&d))
+            .unwrap_or_else(Utc::now);
+
+        ExchangeRate::new(from.clone(), to.clone(), rate, quoted_at)
+    }
+}
+
+/// Keeps each pair's rate for `ttl` in front of another provider
+pub struct CachedExchangeRateProvider {
+    inner: Arc<dyn ExchangeRateProvider>,
+    ttl: Duration,
+    cache: RwLock<HashMap<(Currency, Currency), (ExchangeRate, Instant)>>,
+}
+
+impl CachedExchangeRateProvider {
+    pub fn new(inner: Arc<dyn ExchangeRateProvider>, ttl: Duration) -> Self {
+        Self { inner, ttl, cache: RwLock::new(HashMap::new()) }
+    }
+}
+
+#[async_trait]
+impl ExchangeRateProvider for CachedExchangeRateProvider {
+    async fn rate(&self, from: &Currency, to: &Currency) -> Result<ExchangeRate, AppError> {
+        let key = (from.clone(), to.clone());
+        if let Some((rate, fetched_at)) = self.cache.read().await.get(&key) {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(rate.clone());
+            }
+        }
+
+        let rate = self.inner.rate(from, to).await?;
+        self.cache.write().await.insert(key, (rate.clone(), Instant::now()));
+        Ok(rate)
+    }
+}
+
+/// Converter backed by the HTTP provider with a cache
+/// (`EXCHANGE_RATE_API_URL`, `EXCHANGE_RATE_CACHE_TTL_SECS`, `BASE_CURRENCY`)
+pub fn currency_converter_from_env() -> CurrencyConverter {
+    let ttl = std::env::var("EXCHANGE_RATE_CACHE_TTL_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_CACHE_TTL_SECS);
+    let provider = CachedExchangeRateProvider::new(
+        Arc::new(HttpExchangeRateProvider::from_env()),
+        Duration::from_secs(ttl),
+    );
+    CurrencyConverter::new(Arc::new(provider), CurrencyConverter::base_currency_from_env())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingProvider {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl ExchangeRateProvider for CountingProvider {
+        async fn rate(&self, from: &Currency, to: &Currency) -> Result<ExchangeRate, AppError> {
+            let calls = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            ExchangeRate::new(from.clone(), to.clone(), 1.0 + calls as f64 / 100.0, Utc::now())
+        }
+    }
+
+    #[tokio::test]
+    async fn rates_are_cached_per_pair_until_the_ttl() {
+        let inner = Arc::new(CountingProvider { calls: AtomicUsize::new(0) });
+        let cached = CachedExchangeRateProvider::new(inner.clone(), Duration::from_secs(60));
+
+        let first = cached.rate(&Currency::EUR, &Currency::USD).await.unwrap();
+        let second = cached.rate(&Currency::EUR, &Currency::USD).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 1);
+
+        cached.rate(&Currency::GBP, &Currency::USD).await.unwrap();
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+
+        let expired = CachedExchangeRateProvider::new(inner.clone(), Duration::ZERO);
+        let a = expired.rate(&Currency::EUR, &Currency::USD).await.unwrap();
+        let b = expired.rate(&Currency::EUR, &Currency::USD).await.unwrap();
+        assert_ne!(a.rate, b.rate);
+    }
+
+    #[tokio::test]
+    async fn fixed_provider_inverts_the_opposite_pair() {
+        let provider = FixedExchangeRateProvider::new().with_rate(Currency::USD, Currency::EUR, 0.8);
+
+        assert_eq!(provider.rate(&Currency::EUR, &Currency::USD).await.unwrap().rate, 1.25);
+        assert_eq!(provider.rate(&Currency::USD, &Currency::USD).await.unwrap().rate, 1.0);
+        assert!(matches!(provider.rate(&Currency::GBP, &Currency::USD).await, Err(AppError::NotFound(_))));
+    }
+}
@@ -0,0 +1,866 @@
+// =============================================================================
+// CRYPTO PAYMENT GATEWAY - USDC ON SOLANA
+// =============================================================================
+//
+// Cada pago recibe una referencia de depósito única (memo `VS-<payment_id>`).
+// El fan envía USDC a la cuenta de token (ATA) de la plataforma incluyendo ese
+// memo; un watcher suscrito a la ATA casa las transferencias con los depósitos
+// pendientes y completa, marca como parcialmente pagado o expira cada pago.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, RwLock};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::application::dto::{AmountDTO, CryptoDepositDTO};
+use crate::bounded_contexts::payment::domain::{
+    aggregates::PaymentAggregate,
+    repository::PaymentRepository,
+    value_objects::{Amount, Blockchain, Currency, PaymentId, PaymentMethod, TransactionHash, TransactionId},
+};
+
+use super::{GatewayHealth, GatewayResult, PaymentGateway, RefundResult, WebhookEvent};
+
+/// Código de respuesta con el que el gateway indica que el pago espera el depósito
+pub const AWAITING_DEPOSIT_CODE: &str = "awaiting_deposit";
+/// Prefijo de la referencia de depósito incluida en el memo
+pub const DEPOSIT_MEMO_PREFIX: &str = "VS-";
+/// USDC usa 6 decimales en Solana
+pub const USDC_DECIMALS: i32 = 6;
+
+const DEFAULT_DEPOSIT_WINDOW_MINUTES: i64 = 30;
+/// Tolerancia por redondeo: 0.01 USDC
+const DEFAULT_AMOUNT_TOLERANCE: u64 = 10_000;
+const EXPIRY_SWEEP_INTERVAL: Duration = Duration::from_secs(15);
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+const TRANSFER_CHANNEL_CAPACITY: usize = 256;
+/// Firmas consultadas por cada notificación de la cuenta
+const SIGNATURES_PAGE_SIZE: u64 = 25;
+
+/// Convertir USDC a unidades mínimas (micro-USDC) redondeando
+pub fn to_usdc_units(value: f64) -> u64 {
+    (value * 10f64.powi(USDC_DECIMALS)).round() as u64
+}
+
+/// Convertir micro-USDC a importe decimal
+pub fn from_usdc_units(units: u64) -> f64 {
+    units as f64 / 10f64.powi(USDC_DECIMALS)
+}
+
+/// Referencia de depósito de un pago
+pub fn deposit_memo(payment_id: Uuid) -> String {
+    format!("{}{}", DEPOSIT_MEMO_PREFIX, payment_id.simple())
+}
+
+/// Extraer el pago referenciado por un memo.
+///
+/// El RPC entrega los memos como `"[len] texto"` (varios separados por `"; "`),
+/// por eso se busca la referencia en cualquier posición.
+pub fn parse_deposit_memo(memo: &str) -> Option<Uuid> {
+    memo.match_indices(DEPOSIT_MEMO_PREFIX).find_map(|(index, _)| {
+        let start = index + DEPOSIT_MEMO_PREFIX.len();
+        memo.get(start..start + 32).and_then(|reference| Uuid::try_parse(reference).ok())
+    })
+}
+
+// =============================================================================
+// TRANSFER STREAM
+// =============================================================================
+
+/// Transferencia USDC entrante a la cuenta de depósito
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UsdcTransfer {
+    pub signature: String,
+    pub memo: Option<String>,
+    /// Importe en micro-USDC
+    pub amount: u64,
+    pub slot: u64,
+}
+
+/// Fuente de transferencias entrantes a la ATA de la plataforma
+#[async_trait]
+pub trait UsdcTransferStream: Send + Sync {
+    /// Abrir la suscripción; el canal se cierra si la fuente deja de emitir
+    async fn subscribe(&self) -> Result<mpsc::Receiver<UsdcTransfer>, AppError>;
+}
+
+/// Suscripción real vía PubSub de Solana.
+///
+/// `accountSubscribe` sobre la ATA avisa de cada cambio de saldo; entonces se leen
+/// las firmas nuevas (`getSignaturesForAddress`, que incluye el memo) y el importe
+/// recibido a partir de los balances de token pre/post de cada transacción.
+#[derive(Clone)]
+pub struct SolanaUsdcTransferStream {
+    rpc_url: String,
+    ws_url: String,
+    token_account: String,
+    client: reqwest::Client,
+}
+
+impl SolanaUsdcTransferStream {
+    pub fn new(rpc_url: String, ws_url: String, token_account: String) -> Self {
+        Self {
+            rpc_url,
+            ws_url,
+            token_account,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    async fn rpc(&self, method: &str, params: Value) -> Result<Value, AppError> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response: Value = self.client
+            .post(&self.rpc_url)
+            .json(&request)
+            .send()
+            .await
+            .map_err(|e| AppError::ExternalServiceError(format!("Solana RPC {} failed: {}", method, e)))?
+            .json()
+            .await
+            .map_err(|e| AppError::ExternalServiceError(format!("Invalid Solana RPC response: {}", e)))?;
+
+        if let Some(error) = response.get("error") {
+            return Err(AppError::ExternalServiceError(format!("Solana RPC {} error: {}", method, error)));
+        }
+        Ok(response["result"].clone())
+    }
+
+    /// Transferencias posteriores a `cursor`, de la más antigua a la más reciente
+    async fn fetch_new_transfers(&self, cursor: &mut Option<String>) -> Result<Vec<UsdcTransfer>, AppError> {
+        let mut options = json!({ "limit": SIGNATURES_PAGE_SIZE, "commitment": "confirmed" });
+        if let Some(until) = cursor.as_ref() {
+            options["until"] = json!(until);
+        }
+        let signatures = self.rpc("getSignaturesForAddress", json!([self.token_account, options])).await?;
+        let signatures = signatures.as_array().cloned().unwrap_or_default();
+
+        if let Some(newest) = signatures.first().and_then(|s| s["signature"].as_str()) {
+            *cursor = Some(newest.to_string());
+        }
+
+        let mut transfers = Vec::new();
+        for entry in signatures.iter().rev() {
+            // Transacciones fallidas no mueven fondos
+            if !entry["err"].is_null() {
+                continue;
+            }
+            let Some(signature) = entry["signature"].as_str() else { continue };
+            let transaction = self.rpc(
+                "getTransaction",
+                json!([signature, { "encoding": "jsonParsed", "commitment": "confirmed", "maxSupportedTransactionVersion": 0 }]),
+            ).await?;
+
+            if let Some(amount) = received_amount(&transaction, &self.token_account).filter(|amount| *amount > 0) {
+                transfers.push(UsdcTransfer {
+                    signature: signature.to_string(),
+                    memo: entry["memo"].as_str().map(str::to_string),
+                    amount,
+                    slot: entry["slot"].as_u64().unwrap_or_default(),
+                });
+            }
+        }
+        Ok(transfers)
+    }
+
+    /// Una sesión de suscripción; termina si se cierra el socket o el receptor
+    async fn run_subscription(&self, sender: &mpsc::Sender<UsdcTransfer>, cursor: &mut Option<String>) -> Result<(), AppError> {
+        let (mut socket, _) = tokio_tungstenite::connect_async(self.ws_url.as_str())
+            .await
+            .map_err(|e| AppError::ExternalServiceError(format!("Solana WebSocket connect failed: {}", e)))?;
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "accountSubscribe",
+            "params": [self.token_account, { "encoding": "jsonParsed", "commitment": "confirmed" }]
+        });
+        socket
+            .send(Message::Text(request.to_string()))
+            .await
+            .map_err(|e| AppError::ExternalServiceError(e.to_string()))?;
+
+        while let Some(message) = socket.next().await {
+            match message.map_err(|e| AppError::ExternalServiceError(e.to_string()))? {
+                Message::Text(text) => {
+                    let value: Value = serde_json::from_str(&text)
+                        .map_err(|e| AppError::ExternalServiceError(format!("Invalid PubSub message: {}", e)))?;
+                    if value["method"] != "accountNotification" {
+                        continue;
+                    }
+                    for transfer in self.fetch_new_transfers(cursor).await? {
+                        if sender.send(transfer).await.is_err() {
+                            return Ok(());
+                        }
+                    }
+                }
+                Message::Ping(payload) => {
+                    socket
+                        .send(Message::Pong(payload))
+                        .await
+                        .map_err(|e| AppError::ExternalServiceError(e.to_string()))?;
+                }
+                Message::Close(_) => break,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl UsdcTransferStream for SolanaUsdcTransferStream {
+    async fn subscribe(&self) -> Result<mpsc::Receiver<UsdcTransfer>, AppError> {
+        if !(self.ws_url.starts_with("ws://") || self.ws_url.starts_with("wss://")) {
+            return Err(AppError::ConfigurationError(format!("Invalid Solana WebSocket URL: {}", self.ws_url)));
+        }
+
+        let (sender, receiver) = mpsc::channel(TRANSFER_CHANNEL_CAPACITY);
+        let stream = self.clone();
+        tokio::spawn(async move {
+            let mut cursor = None;
+            let mut delay = INITIAL_RECONNECT_DELAY;
+            while !sender.is_closed() {
+                match stream.run_subscription(&sender, &mut cursor).await {
+                    Ok(()) => delay = INITIAL_RECONNECT_DELAY,
+                    Err(e) => tracing::warn!("USDC deposit subscription for {} failed: {}", stream.token_account, e),
+                }
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+            }
+        });
+        Ok(receiver)
+    }
+}
+
+/// Incremento del saldo de `token_account` en una transacción `jsonParsed`
+fn received_amount(transaction: &Value, token_account: &str) -> Option<u64> {
+    let account_keys = transaction.pointer("/transaction/message/accountKeys")?.as_array()?;
+    let account_index = account_keys
+        .iter()
+        .position(|key| key["pubkey"].as_str().or_else(|| key.as_str()) == Some(token_account))?;
+
+    let balance = |field: &str| -> u64 {
+        transaction["meta"][field]
+            .as_array()
+            .and_then(|balances| {
+                balances
+                    .iter()
+                    .find(|balance| balance["accountIndex"].as_u64() == Some(account_index as u64))
+            })
+            .and_then(|balance| balance.pointer("/uiTokenAmount/amount")?.as_str()?.parse().ok())
+            .unwrap_or(0)
+    };
+
+    balance("postTokenBalances").checked_sub(balance("preTokenBalances"))
+}
+
+// =============================================================================
+// DEPOSITS
+// =============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepositState {
+    AwaitingDeposit,
+    PartiallyPaid,
+    Completed,
+    Expired,
+}
+
+impl DepositState {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DepositState::AwaitingDeposit => "awaiting_deposit",
+            DepositState::PartiallyPaid => "partially_paid",
+            DepositState::Completed => "completed",
+            DepositState::Expired => "expired",
+        }
+    }
+}
+
+/// Depósito esperado para un pago (importes en micro-USDC)
+#[derive(Debug, Clone)]
+pub struct CryptoDeposit {
+    pub payment_id: Uuid,
+    pub deposit_address: String,
+    pub memo: String,
+    pub expected_amount: u64,
+    pub received_amount: u64,
+    pub signatures: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+    pub state: DepositState,
+}
+
+impl CryptoDeposit {
+    pub fn shortfall(&self) -> u64 {
+        self.expected_amount.saturating_sub(self.received_amount)
+    }
+
+    /// Acepta transferencias mientras no esté completado y siga dentro de la ventana
+    pub fn is_open(&self, now: DateTime<Utc>) -> bool {
+        matches!(self.state, DepositState::AwaitingDeposit | DepositState::PartiallyPaid) && now < self.expires_at
+    }
+}
+
+impl From<&CryptoDeposit> for CryptoDepositDTO {
+    fn from(deposit: &CryptoDeposit) -> Self {
+        Self {
+            deposit_address: deposit.deposit_address.clone(),
+            memo: deposit.memo.clone(),
+            expected_amount: AmountDTO::new(from_usdc_units(deposit.expected_amount), Currency::USDC),
+            received_amount: AmountDTO::new(from_usdc_units(deposit.received_amount), Currency::USDC),
+            shortfall: AmountDTO::new(from_usdc_units(deposit.shortfall()), Currency::USDC),
+            status: deposit.state.as_str().to_string(),
+            expires_at: deposit.expires_at,
+            signatures: deposit.signatures.clone(),
+        }
+    }
+}
+
+/// Cambio de estado que debe aplicarse al pago
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DepositOutcome {
+    Completed { signature: String, received_amount: u64 },
+    PartiallyPaid { received_amount: u64, shortfall: u64 },
+    Expired,
+}
+
+/// Aplica los resultados de los depósitos a los pagos
+#[async_trait]
+pub trait DepositSettlement: Send + Sync {
+    async fn settle(&self, payment_id: Uuid, outcome: &DepositOutcome) -> Result<(), AppError>;
+}
+
+/// Settlement sobre el repositorio de pagos
+pub struct RepositoryDepositSettlement {
+    repository: Arc<dyn PaymentRepository>,
+}
+
+impl RepositoryDepositSettlement {
+    pub fn new(repository: Arc<dyn PaymentRepository>) -> Self {
+        Self { repository }
+    }
+}
+
+#[async_trait]
+impl DepositSettlement for RepositoryDepositSettlement {
+    async fn settle(&self, payment_id: Uuid, outcome: &DepositOutcome) -> Result<(), AppError> {
+        let mut payment = self.repository
+            .find_by_id(&PaymentId::from_uuid(payment_id))
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Payment {} not found", payment_id)))?;
+
+        match outcome {
+            DepositOutcome::Completed { signature, .. } => {
+                payment.complete_payment(Some(TransactionHash::new(signature.clone())?))?;
+            }
+            DepositOutcome::PartiallyPaid { received_amount, .. } => {
+                payment.record_partial_payment(Amount::new(from_usdc_units(*received_amount), Currency::USDC)?)?;
+            }
+            DepositOutcome::Expired => {
+                payment.cancel_payment("USDC deposit window expired".to_string())?;
+            }
+        }
+
+        self.repository.update(&payment).await
+    }
+}
+
+// =============================================================================
+// GATEWAY
+// =============================================================================
+
+#[derive(Debug, Clone)]
+pub struct CryptoGatewayConfig {
+    /// ATA de USDC de la plataforma que recibe los depósitos
+    pub deposit_address: String,
+    pub rpc_url: String,
+    pub ws_url: String,
+    pub deposit_window: ChronoDuration,
+    /// Diferencia máxima (micro-USDC) aceptada como pago completo
+    pub amount_tolerance: u64,
+}
+
+impl CryptoGatewayConfig {
+    pub fn new(deposit_address: String, rpc_url: String) -> Self {
+        let ws_url = rpc_url.replacen("https://", "wss://", 1).replacen("http://", "ws://", 1);
+        Self {
+            deposit_address,
+            rpc_url,
+            ws_url,
+            deposit_window: ChronoDuration::minutes(DEFAULT_DEPOSIT_WINDOW_MINUTES),
+            amount_tolerance: DEFAULT_AMOUNT_TOLERANCE,
+        }
+    }
+
+    /// `None` si no hay cuenta de depósito configurada (gateway deshabilitado)
+    pub fn from_env() -> Option<Self> {
+        let deposit_address = std::env::var("SOLANA_USDC_DEPOSIT_ACCOUNT").ok()?;
+        let rpc_url = std::env::var("SOLANA_RPC_URL").unwrap_or_else(|_| "https://api.devnet.solana.com".to_string());
+        let mut config = Self::new(deposit_address, rpc_url);
+
+        if let Ok(ws_url) = std::env::var("SOLANA_WS_URL") {
+            config.ws_url = ws_url;
+        }
+        if let Some(minutes) = std::env::var("USDC_DEPOSIT_WINDOW_MINUTES").ok().and_then(|v| v.parse().ok()) {
+            config.deposit_window = ChronoDuration::minutes(minutes);
+        }
+        Some(config)
+    }
+}
+
+/// Gateway de pagos en USDC sobre Solana mediante depósitos con memo.
+///
+/// El estado de los depósitos vive en memoria del proceso; el pago persistido
+/// refleja cada transición a través de `DepositSettlement`.
+pub struct CryptoPaymentGateway {
+    config: CryptoGatewayConfig,
+    deposits: RwLock<HashMap<Uuid, CryptoDeposit>>,
+    watching: AtomicBool,
+}
+
+impl CryptoPaymentGateway {
+    pub fn new(config: CryptoGatewayConfig) -> Self {
+        Self {
+            config,
+            deposits: RwLock::new(HashMap::new()),
+            watching: AtomicBool::new(false),
+        }
+    }
+
+    pub fn deposit_address(&self) -> &str {
+        &self.config.deposit_address
+    }
+
+    fn is_solana_usdc(payment: &PaymentAggregate) -> bool {
+        matches!(
+            payment.payment().payment_method(),
+            PaymentMethod::Cryptocurrency { blockchain: Blockchain::Solana, .. }
+        ) && *payment.payment().amount().currency() == Currency::USDC
+    }
+
+    /// Registrar (o devolver, si ya existe) el depósito esperado de un pago
+    pub async fn register_deposit(&self, payment: &PaymentAggregate) -> Result<CryptoDeposit, AppError> {
+        if !Self::is_solana_usdc(payment) {
+            return Err(AppError::InvalidInput(
+                "Crypto gateway only supports USDC payments on Solana".to_string()
+            ));
+        }
+
+        let payment_id = payment.payment().id().value();
+        let mut deposits = self.deposits.write().await;
+        let deposit = deposits.entry(payment_id).or_insert_with(|| CryptoDeposit {
+            payment_id,
+            deposit_address: self.config.deposit_address.clone(),
+            memo: deposit_memo(payment_id),
+            expected_amount: to_usdc_units(payment.payment().amount().value()),
+            received_amount: 0,
+            signatures: Vec::new(),
+            expires_at: Utc::now() + self.config.deposit_window,
+            state: DepositState::AwaitingDeposit,
+        });
+        Ok(deposit.clone())
+    }
+
+    pub async fn deposit_status(&self, payment_id: Uuid) -> Option<CryptoDeposit> {
+        self.deposits.read().await.get(&payment_id).cloned()
+    }
+
+    /// Casar una transferencia con su depósito; `None` si no cambia ningún pago
+    pub async fn apply_transfer(&self, transfer: &UsdcTransfer, now: DateTime<Utc>) -> Option<(Uuid, DepositOutcome)> {
+        let payment_id = transfer.memo.as_deref().and_then(parse_deposit_memo)?;
+        let mut deposits = self.deposits.write().await;
+        let Some(deposit) = deposits.get_mut(&payment_id) else {
+            tracing::warn!("USDC transfer {} references unknown payment {}", transfer.signature, payment_id);
+            return None;
+        };
+
+        // Reentregas tras reconexión
+        if deposit.signatures.contains(&transfer.signature) {
+            return None;
+        }
+        if !deposit.is_open(now) {
+            tracing::warn!(
+                "USDC transfer {} for payment {} arrived after the deposit closed ({:?})",
+                transfer.signature,
+                payment_id,
+                deposit.state
+            );
+            return None;
+        }
+
+        deposit.received_amount += transfer.amount;
+        deposit.signatures.push(transfer.signature.clone());
+
+        if deposit.received_amount + self.config.amount_tolerance >= deposit.expected_amount {
+            deposit.state = DepositState::Completed;
+            Some((payment_id, DepositOutcome::Completed {
+                signature: transfer.signature.clone(),
+                received_amount: deposit.received_amount,
+            }))
+        } else {
+            deposit.state = DepositState::PartiallyPaid;
+            Some((payment_id, DepositOutcome::PartiallyPaid {
+                received_amount: deposit.received_amount,
+                shortfall: deposit.shortfall(),
+            }))
+        }
+    }
+
+    /// Expirar los depósitos sin fondos cuya ventana terminó.
+    ///
+    /// Los parcialmente pagados conservan ese estado (con su shortfall) para
+    /// resolverlos manualmente; solo dejan de aceptar transferencias.
+    pub async fn expire_deposits(&self, now: DateTime<Utc>) -> Vec<(Uuid, DepositOutcome)> {
+        let mut deposits = self.deposits.write().await;
+        deposits
+            .values_mut()
+            .filter(|deposit| deposit.state == DepositState::AwaitingDeposit && now >= deposit.expires_at)
+            .map(|deposit| {
+                deposit.state = DepositState::Expired;
+                (deposit.payment_id, DepositOutcome::Expired)
+            })
+            .collect()
+    }
+
+    async fn settle(settlement: &dyn DepositSettlement, payment_id: Uuid, outcome: DepositOutcome) {
+        if let Err(e) = settlement.settle(payment_id, &outcome).await {
+            tracing::error!("Failed to settle USDC deposit for payment {} ({:?}): {}", payment_id, outcome, e);
+        }
+    }
+
+    /// Lanzar el watcher: casa transferencias y barre expiraciones
+    pub fn spawn_watcher(
+        self: &Arc<Self>,
+        stream: Arc<dyn UsdcTransferStream>,
+        settlement: Arc<dyn DepositSettlement>,
+    ) -> JoinHandle<()> {
+        let gateway = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut sweep = tokio::time::interval(EXPIRY_SWEEP_INTERVAL);
+            let mut delay = INITIAL_RECONNECT_DELAY;
+
+            loop {
+                let mut transfers = match stream.subscribe().await {
+                    Ok(receiver) => receiver,
+                    Err(e) => {
+                        tracing::warn!("USDC deposit stream unavailable: {}", e);
+                        tokio::time::sleep(delay).await;
+                        delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+                        continue;
+                    }
+                };
+                gateway.watching.store(true, Ordering::Relaxed);
+                delay = INITIAL_RECONNECT_DELAY;
+
+                loop {
+                    tokio::select! {
+                        transfer = transfers.recv() => {
+                            let Some(transfer) = transfer else { break };
+                            if let Some((payment_id, outcome)) = gateway.apply_transfer(&transfer, Utc::now()).await {
+                                Self::settle(settlement.as_ref(), payment_id, outcome).await;
+                            }
+                        }
+                        _ = sweep.tick() => {
+                            for (payment_id, outcome) in gateway.expire_deposits(Utc::now()).await {
+                                Self::settle(settlement.as_ref(), payment_id, outcome).await;
+                            }
+                        }
+                    }
+                }
+
+                gateway.watching.store(false, Ordering::Relaxed);
+                tracing::warn!("USDC deposit stream closed, resubscribing");
+                tokio::time::sleep(delay).await;
+            }
+        })
+    }
+}
+
+#[async_trait]
+impl PaymentGateway for CryptoPaymentGateway {
+    async fn process_payment(&self, payment: &PaymentAggregate) -> Result<GatewayResult, AppError> {
+        let start_time = Instant::now();
+        let deposit = self.register_deposit(payment).await?;
+
+        Ok(GatewayResult {
+            success: true,
+            transaction_id: deposit.memo.clone(),
+            blockchain_hash: None,
+            gateway_response_code: AWAITING_DEPOSIT_CODE.to_string(),
+            gateway_message: format!(
+                "Send {:.6} USDC to {} with memo {} before {}",
+                from_usdc_units(deposit.expected_amount),
+                deposit.deposit_address,
+                deposit.memo,
+                deposit.expires_at.to_rfc3339()
+            ),
+            processing_time_ms: start_time.elapsed().as_millis() as u64,
+            fees_charged: Amount::new(0.0, Currency::USDC)?,
+            client_secret: None,
+        })
+    }
+
+    async fn process_refund(
+        &self,
+        _original_transaction_id: &TransactionId,
+        _refund_amount: &Amount,
+        _reason: &str,
+    ) -> Result<RefundResult, AppError> {
+        Err(AppError::InvalidInput(
+            "USDC refunds require a separate on-chain transfer".to_string()
+        ))
+    }
+
+    async fn verify_webhook(&self, _payload: &str, _signature: &str) -> Result<WebhookEvent, AppError> {
+        Err(AppError::InvalidInput(
+            "USDC deposits are confirmed on-chain, not via webhooks".to_string()
+        ))
+    }
+
+    async fn health_check(&self) -> Result<GatewayHealth, AppError> {
+        let is_healthy = self.watching.load(Ordering::Relaxed);
+        Ok(GatewayHealth {
+            is_healthy,
+            response_time_ms: 0,
+            last_check: Utc::now(),
+            error_message: (!is_healthy).then(|| "USDC deposit stream not connected".to_string()),
+        })
+    }
+
+    fn gateway_name(&self) -> &'static str {
+        "solana_usdc"
+    }
+
+    fn supports_payment_method(&self, payment: &PaymentAggregate) -> bool {
+        Self::is_solana_usdc(payment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::payment::domain::value_objects::{
+        FeePercentage, PaymentMetadata, PaymentPurpose, WalletAddress,
+    };
+    use tokio::sync::Mutex;
+
+    /// Stream simulado: el test empuja transferencias por el canal
+    struct MockTransferStream {
+        receiver: Mutex<Option<mpsc::Receiver<UsdcTransfer>>>,
+    }
+
+    #[async_trait]
+    impl UsdcTransferStream for MockTransferStream {
+        async fn subscribe(&self) -> Result<mpsc::Receiver<UsdcTransfer>, AppError> {
+            self.receiver
+                .lock()
+                .await
+                .take()
+                .ok_or_else(|| AppError::ExternalServiceError("stream already consumed".to_string()))
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingSettlement {
+        outcomes: Mutex<Vec<(Uuid, DepositOutcome)>>,
+    }
+
+    #[async_trait]
+    impl DepositSettlement for RecordingSettlement {
+        async fn settle(&self, payment_id: Uuid, outcome: &DepositOutcome) -> Result<(), AppError> {
+            self.outcomes.lock().await.push((payment_id, outcome.clone()));
+            Ok(())
+        }
+    }
+
+    fn usdc_payment(amount: f64) -> PaymentAggregate {
+        PaymentAggregate::create_payment(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Amount::new(amount, Currency::USDC).unwrap(),
+            PaymentMethod::Cryptocurrency {
+                blockchain: Blockchain::Solana,
+                wallet_address: WalletAddress::new("7EcDhSYGxXyscszYEp35KHN8vvw3svAuLKTzXwCFLtV".to_string()).unwrap(),
+            },
+            PaymentPurpose::SongPurchase { song_id: Uuid::new_v4() },
+            FeePercentage::new(2.5).unwrap(),
+            PaymentMetadata {
+                user_ip: None,
+                user_agent: None,
+                platform_version: "1.0.0".to_string(),
+                reference_id: None,
+                additional_data: serde_json::Value::Null,
+            },
+        )
+        .unwrap()
+    }
+
+    fn gateway() -> Arc<CryptoPaymentGateway> {
+        Arc::new(CryptoPaymentGateway::new(CryptoGatewayConfig::new(
+            "PlatformUsdcAta1111111111111111111111111111".to_string(),
+            "http://127.0.0.1:8899".to_string(),
+        )))
+    }
+
+    fn transfer(signature: &str, payment_id: Uuid, amount: f64) -> UsdcTransfer {
+        UsdcTransfer {
+            signature: signature.to_string(),
+            memo: Some(format!("[35] {}", deposit_memo(payment_id))),
+            amount: to_usdc_units(amount),
+            slot: 1,
+        }
+    }
+
+    async fn wait_for_outcomes(settlement: &RecordingSettlement, count: usize) -> Vec<(Uuid, DepositOutcome)> {
+        for _ in 0..100 {
+            let outcomes = settlement.outcomes.lock().await.clone();
+            if outcomes.len() >= count {
+                return outcomes;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("timed out waiting for {} deposit outcomes", count);
+    }
+
+    #[test]
+    fn parses_deposit_memo_from_rpc_format() {
+        let payment_id = Uuid::new_v4();
+        assert_eq!(parse_deposit_memo(&format!("[35] {}", deposit_memo(payment_id))), Some(payment_id));
+        assert_eq!(parse_deposit_memo(&format!("[4] test; [35] {}", deposit_memo(payment_id))), Some(payment_id));
+        assert_eq!(parse_deposit_memo("[7] VS-nope"), None);
+    }
+
+    #[tokio::test]
+    async fn process_payment_returns_deposit_instructions() {
+        let gateway = gateway();
+        let payment = usdc_payment(12.5);
+
+        let result = gateway.process_payment(&payment).await.unwrap();
+        let deposit = gateway.deposit_status(payment.payment().id().value()).await.unwrap();
+
+        assert_eq!(result.gateway_response_code, AWAITING_DEPOSIT_CODE);
+        assert_eq!(result.transaction_id, deposit.memo);
+        assert_eq!(deposit.expected_amount, 12_500_000);
+        assert_eq!(deposit.state, DepositState::AwaitingDeposit);
+
+        // Reprocesar no genera otra referencia
+        let again = gateway.register_deposit(&payment).await.unwrap();
+        assert_eq!(again.memo, deposit.memo);
+        assert_eq!(again.expires_at, deposit.expires_at);
+    }
+
+    #[tokio::test]
+    async fn watcher_completes_payment_within_tolerance() {
+        let gateway = gateway();
+        let payment = usdc_payment(10.0);
+        let payment_id = payment.payment().id().value();
+        gateway.register_deposit(&payment).await.unwrap();
+
+        let (sender, receiver) = mpsc::channel(8);
+        let stream = Arc::new(MockTransferStream { receiver: Mutex::new(Some(receiver)) });
+        let settlement = Arc::new(RecordingSettlement::default());
+        let watcher = gateway.spawn_watcher(stream, settlement.clone());
+
+        // Transferencia sin memo o de otro pago: ignoradas
+        sender.send(UsdcTransfer { signature: "noise".to_string(), memo: None, amount: 1, slot: 1 }).await.unwrap();
+        sender.send(transfer("other", Uuid::new_v4(), 10.0)).await.unwrap();
+        sender.send(transfer("sig-1", payment_id, 9.995)).await.unwrap();
+        // Reentrega tras reconexión
+        sender.send(transfer("sig-1", payment_id, 9.995)).await.unwrap();
+
+        let outcomes = wait_for_outcomes(&settlement, 1).await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(settlement.outcomes.lock().await.len(), 1);
+        assert_eq!(
+            outcomes[0],
+            (payment_id, DepositOutcome::Completed { signature: "sig-1".to_string(), received_amount: 9_995_000 })
+        );
+        assert_eq!(gateway.deposit_status(payment_id).await.unwrap().state, DepositState::Completed);
+        assert!(gateway.health_check().await.unwrap().is_healthy);
+
+        watcher.abort();
+    }
+
+    #[tokio::test]
+    async fn underpayment_reports_shortfall_until_topped_up() {
+        let gateway = gateway();
+        let payment = usdc_payment(20.0);
+        let payment_id = payment.payment().id().value();
+        gateway.register_deposit(&payment).await.unwrap();
+
+        let (sender, receiver) = mpsc::channel(8);
+        let stream = Arc::new(MockTransferStream { receiver: Mutex::new(Some(receiver)) });
+        let settlement = Arc::new(RecordingSettlement::default());
+        let watcher = gateway.spawn_watcher(stream, settlement.clone());
+
+        sender.send(transfer("sig-1", payment_id, 15.0)).await.unwrap();
+        let outcomes = wait_for_outcomes(&settlement, 1).await;
+        assert_eq!(
+            outcomes[0].1,
+            DepositOutcome::PartiallyPaid { received_amount: 15_000_000, shortfall: 5_000_000 }
+        );
+        let dto = CryptoDepositDTO::from(&gateway.deposit_status(payment_id).await.unwrap());
+        assert_eq!(dto.status, "partially_paid");
+        assert_eq!(dto.shortfall.value, 5.0);
+
+        sender.send(transfer("sig-2", payment_id, 5.0)).await.unwrap();
+        let outcomes = wait_for_outcomes(&settlement, 2).await;
+        assert_eq!(
+            outcomes[1].1,
+            DepositOutcome::Completed { signature: "sig-2".to_string(), received_amount: 20_000_000 }
+        );
+
+        watcher.abort();
+    }
+
+    #[tokio::test]
+    async fn deposits_expire_after_window() {
+        let gateway = gateway();
+        let empty = usdc_payment(5.0);
+        let partial = usdc_payment(5.0);
+        let empty_id = empty.payment().id().value();
+        let partial_id = partial.payment().id().value();
+        gateway.register_deposit(&empty).await.unwrap();
+        gateway.register_deposit(&partial).await.unwrap();
+        gateway.apply_transfer(&transfer("sig-1", partial_id, 1.0), Utc::now()).await.unwrap();
+
+        let after_window = Utc::now() + ChronoDuration::minutes(DEFAULT_DEPOSIT_WINDOW_MINUTES + 1);
+        assert_eq!(gateway.expire_deposits(after_window).await, vec![(empty_id, DepositOutcome::Expired)]);
+        assert_eq!(gateway.deposit_status(empty_id).await.unwrap().state, DepositState::Expired);
+
+        // Fuera de plazo no se aceptan más fondos; el parcial conserva su shortfall
+        assert_eq!(gateway.apply_transfer(&transfer("sig-2", partial_id, 4.0), after_window).await, None);
+        let deposit = gateway.deposit_status(partial_id).await.unwrap();
+        assert_eq!(deposit.state, DepositState::PartiallyPaid);
+        assert_eq!(deposit.shortfall(), 4_000_000);
+    }
+
+    #[test]
+    fn received_amount_reads_token_balance_delta() {
+        let account = "PlatformUsdcAta1111111111111111111111111111";
+        let transaction = json!({
+            "transaction": { "message": { "accountKeys": [
+                { "pubkey": "Payer111111111111111111111111111111111111111" },
+                { "pubkey": account }
+            ] } },
+            "meta": {
+                "preTokenBalances": [{ "accountIndex": 1, "uiTokenAmount": { "amount": "2000000" } }],
+                "postTokenBalances": [{ "accountIndex": 1, "uiTokenAmount": { "amount": "12500000" } }]
+            }
+        });
+        assert_eq!(received_amount(&transaction, account), Some(10_500_000));
+        assert_eq!(received_amount(&transaction, "Elsewhere"), None);
+    }
+}
@@ -67,7 +67,7 @@ impl MultiGatewayRouter {
     async fn select_by_payment_method(&self, payment: &PaymentAggregate) -> Result<Arc<dyn PaymentGateway>, AppError> {
         let preferred_gateways = match payment.payment().payment_method() {
             PaymentMethod::CreditCard { .. } => vec!["stripe", "paypal"],
-            PaymentMethod::Cryptocurrency { .. } => vec!["coinbase"],
+            PaymentMethod::Cryptocurrency { .. } => vec!["solana_usdc", "coinbase"],
             PaymentMethod::BankTransfer { .. } => vec!["stripe", "paypal"],
             PaymentMethod::PlatformBalance => vec![], // Internal processing
         };
@@ -1,12 +1,18 @@
 pub mod stripe_gateway;
 pub mod coinbase_gateway;
 pub mod paypal_gateway;
+pub mod crypto_gateway;
 pub mod gateway_router;
+pub mod refund_executor;
 
 pub use stripe_gateway::StripeGateway;
 pub use coinbase_gateway::CoinbaseGateway;
 pub use paypal_gateway::PayPalGateway;
+pub use crypto_gateway::{
+    CryptoPaymentGateway, CryptoGatewayConfig, SolanaUsdcTransferStream, RepositoryDepositSettlement,
+};
 pub use gateway_router::{PaymentGatewayRouter, MultiGatewayRouter};
+pub use refund_executor::GatewayRefundExecutor;
 
 // Re-export types defined in this module
 // Re-export types defined in this module
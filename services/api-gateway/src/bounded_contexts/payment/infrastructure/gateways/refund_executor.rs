@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::application::refund_service::{RefundExecution, RefundGatewayExecutor};
+use crate::bounded_contexts::payment::domain::{
+    aggregates::PaymentAggregate,
+    refunds::RefundTransaction,
+    value_objects::PaymentMethod,
+};
+use crate::bounded_contexts::payment::infrastructure::repositories::PostgresCryptoPayoutQueue;
+
+use super::PaymentGateway;
+
+const CRYPTO_PAYOUT_GATEWAY: &str = "crypto_manual_payout";
+
+/// Sends each refund back through the rail the payment came in on: card and
+/// bank payments through the card gateway's refund API, crypto payments to the
+/// manual payout queue.
+pub struct GatewayRefundExecutor {
+    card_gateway: Option<Arc<dyn PaymentGateway>>,
+    payout_queue: Arc<PostgresCryptoPayoutQueue>,
+}
+
+impl GatewayRefundExecutor {
+    pub fn new(card_gateway: Option<Arc<dyn PaymentGateway>>, payout_queue: Arc<PostgresCryptoPayoutQueue>) -> Self {
+        Self { card_gateway, payout_queue }
+    }
+
+    fn card_gateway(&self) -> Result<&Arc<dyn PaymentGateway>, AppError> {
+        self.card_gateway
+            .as_ref()
+            .ok_or_else(|| AppError::ServiceUnavailable("No card gateway configured for refunds".to_string()))
+    }
+}
+
+#[async_trait]
+impl RefundGatewayExecutor for GatewayRefundExecutor {
+    fn gateway_for(&self, payment: &PaymentAggregate) -> Result<&'static str, AppError> {
+        match payment.payment().payment_method() {
+            PaymentMethod::CreditCard { .. } | PaymentMethod::BankTransfer { .. } => {
+                Ok(self.card_gateway()?.gateway_name())
+            }
+            PaymentMethod::Cryptocurrency { .. } => Ok(CRYPTO_PAYOUT_GATEWAY),
+            PaymentMethod::PlatformBalance => Err(AppError::InvalidInput(
+                "Platform balance payments are not refundable through a gateway".to_string(),
+            )),
+        }
+    }
+
+    async fn execute(&self, payment: &PaymentAggregate, refund: &RefundTransaction) -> Result<RefundExecution, AppError> {
+        match payment.payment().payment_method() {
+            PaymentMethod::Cryptocurrency { blockchain, wallet_address } => {
+                let payout_id = self.payout_queue.enqueue(refund, blockchain, wallet_address).await?;
+                Ok(RefundExecution::QueuedForPayout { payout_id: payout_id.to_string() })
+            }
+            PaymentMethod::CreditCard { .. } | PaymentMethod::BankTransfer { .. } => {
+                let transaction_id = payment.payment().transaction_id().ok_or_else(|| {
+                    AppError::InvalidState("Payment has no gateway transaction to refund".to_string())
+                })?;
+
+                let result = self.card_gateway()?
+                    .process_refund(transaction_id, &refund.amount, &refund.reason)
+                    .await?;
+
+                if result.success {
+                    Ok(RefundExecution::Completed { gateway_refund_id: Some(result.refund_id) })
+                } else if result.gateway_response_code == "pending" {
+                    Ok(RefundExecution::Submitted { gateway_refund_id: result.refund_id })
+                } else {
+                    Err(AppError::PaymentGatewayError(result.gateway_message))
+                }
+            }
+            PaymentMethod::PlatformBalance => Err(AppError::InvalidInput(
+                "Platform balance payments are not refundable through a gateway".to_string(),
+            )),
+        }
+    }
+
+    async fn confirm_payout(&self, refund: &RefundTransaction, tx_hash: &str) -> Result<(), AppError> {
+        self.payout_queue.mark_sent(refund.id, tx_hash).await
+    }
+}
@@ -1,16 +1,16 @@
 use async_trait::async_trait;
 use chrono::Utc;
 use reqwest;
-use serde::{Deserialize, Serialize};
-use serde_json::{json, Value};
+use serde::Deserialize;
+use serde_json::Value;
 use std::time::Instant;
 use hmac_sha256::HMAC;
-use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use sha2::{Digest, Sha256};
 
 use crate::shared::domain::errors::AppError;
 use crate::bounded_contexts::payment::domain::{
     aggregates::PaymentAggregate,
-    value_objects::{Amount, Currency, TransactionId, TransactionHash, PaymentMethod},
+    value_objects::{Amount, Currency, TransactionId, PaymentMethod, PaymentPurpose},
 };
 
 use super::{
@@ -18,6 +18,38 @@ use super::{
     WebhookEvent, GatewayHealth,
 };
 
+const STRIPE_API_BASE_URL: &str = "https://api.stripe.com/v1";
+/// Antigüedad máxima aceptada para la firma de un webhook (igual que las librerías oficiales)
+const WEBHOOK_TOLERANCE_SECONDS: i64 = 300;
+/// Divisas sin decimales en Stripe (el importe ya está en unidades)
+const ZERO_DECIMAL_CURRENCIES: &[&str] = &[
+    "bif", "clp", "djf", "gnf", "jpy", "kmf", "krw", "mga", "pyg", "rwf", "ugx", "vnd", "vuv", "xaf", "xof", "xpf",
+];
+/// Divisas con tres decimales en Stripe
+const THREE_DECIMAL_CURRENCIES: &[&str] = &["bhd", "jod", "kwd", "omr", "tnd"];
+
+/// Decimales de la unidad mínima de una divisa (código ISO en minúsculas)
+pub fn minor_unit_exponent(currency_code: &str) -> i32 {
+    let code = currency_code.to_lowercase();
+    if ZERO_DECIMAL_CURRENCIES.contains(&code.as_str()) {
+        0
+    } else if THREE_DECIMAL_CURRENCIES.contains(&code.as_str()) {
+        3
+    } else {
+        2
+    }
+}
+
+/// Convertir un importe a unidades mínimas (p.ej. céntimos) redondeando, no truncando
+pub fn to_minor_units(value: f64, currency_code: &str) -> u64 {
+    (value * 10f64.powi(minor_unit_exponent(currency_code))).round() as u64
+}
+
+/// Convertir unidades mínimas de Stripe al importe decimal
+pub fn from_minor_units(amount: u64, currency_code: &str) -> f64 {
+    amount as f64 / 10f64.powi(minor_unit_exponent(currency_code))
+}
+
 /// Stripe payment gateway implementation
 pub struct StripeGateway {
     config: GatewayConfig,
@@ -25,19 +57,6 @@ pub struct StripeGateway {
     base_url: String,
 }
 
-#[derive(Debug, Serialize)]
-struct StripePaymentIntentRequest {
-    amount: u64,
-    currency: String,
-    payment_method: String,
-    confirm: bool,
-    metadata: Value,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    customer: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    description: Option<String>,
-}
-
 #[derive(Debug, Deserialize)]
 struct StripePaymentIntentResponse {
     id: String,
@@ -63,15 +82,6 @@ struct StripeCharge {
     fee: u64,
 }
 
-#[derive(Debug, Serialize)]
-struct StripeRefundRequest {
-    charge: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    amount: Option<u64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    reason: Option<String>,
-}
-
 #[derive(Debug, Deserialize)]
 struct StripeRefundResponse {
     id: String,
@@ -97,11 +107,9 @@ struct StripeWebhookData {
 
 impl StripeGateway {
     pub async fn new(config: GatewayConfig) -> Result<Self, AppError> {
-        let base_url = if config.environment == "test" {
-            "https://api.stripe.com/v1".to_string()
-        } else {
-            "https://api.stripe.com/v1".to_string()
-        };
+        // STRIPE_API_BASE_URL permite apuntar a stripe-mock (p.ej. http://localhost:12111/v1)
+        let base_url = std::env::var("STRIPE_API_BASE_URL")
+            .unwrap_or_else(|_| STRIPE_API_BASE_URL.to_string());
 
         let client = reqwest::Client::builder()
             .timeout(std::time::Duration::from_secs(30))
@@ -115,19 +123,85 @@ impl StripeGateway {
         })
     }
 
-    /// Convert amount to Stripe's format (cents)
-    fn amount_to_stripe_cents(&self, amount: &Amount) -> u64 {
-        (amount.value() * 100.0) as u64
+    /// Usar otra URL base para la API (stripe-mock en tests)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Convert amount to Stripe's minor units for its currency
+    fn amount_to_stripe_minor_units(&self, amount: &Amount) -> Result<u64, AppError> {
+        let currency = self.currency_to_stripe(amount.currency())?;
+        Ok(to_minor_units(amount.value(), currency))
     }
 
     /// Convert currency to Stripe format
-    fn currency_to_stripe(&self, currency: &Currency) -> String {
+    fn currency_to_stripe(&self, currency: &Currency) -> Result<&'static str, AppError> {
         match currency {
-            Currency::USD => "usd".to_string(),
-            Currency::EUR => "eur".to_string(),
-            Currency::GBP => "gbp".to_string(),
-            _ => "usd".to_string(), // Default fallback
+            Currency::USD => Ok("usd"),
+            Currency::EUR => Ok("eur"),
+            Currency::GBP => Ok("gbp"),
+            other => Err(AppError::InvalidInput(format!("Stripe doesn't support {:?} payments", other))),
+        }
+    }
+
+    /// Tipo de compra y entidad asociada, para los metadatos del PaymentIntent
+    fn purpose_metadata(purpose: &PaymentPurpose) -> (&'static str, Option<(&'static str, uuid::Uuid)>) {
+        match purpose {
+            PaymentPurpose::SongPurchase { song_id } => ("song_purchase", Some(("song_id", *song_id))),
+            PaymentPurpose::SharePurchase { contract_id, .. } => ("share_purchase", Some(("contract_id", *contract_id))),
+            PaymentPurpose::NFTPurchase { campaign_id, .. } => ("nft_purchase", Some(("campaign_id", *campaign_id))),
+            _ => ("other", None),
+        }
+    }
+
+    /// Parámetros (form-encoded) para crear el PaymentIntent de un pago
+    fn payment_intent_params(&self, payment: &PaymentAggregate) -> Result<Vec<(String, String)>, AppError> {
+        let payment_data = payment.payment();
+        let (purpose, entity) = Self::purpose_metadata(payment_data.purpose());
+
+        let mut params = vec![
+            ("amount".to_string(), self.amount_to_stripe_minor_units(payment_data.amount())?.to_string()),
+            ("currency".to_string(), self.currency_to_stripe(payment_data.amount().currency())?.to_string()),
+            ("payment_method".to_string(), self.get_payment_method_id(payment)?),
+            ("confirm".to_string(), "false".to_string()),
+            ("description".to_string(), payment_data.purpose().description()),
+            ("metadata[payment_id]".to_string(), payment_data.id().value().to_string()),
+            ("metadata[purpose]".to_string(), purpose.to_string()),
+            ("metadata[user_id]".to_string(), payment_data.payer_id().to_string()),
+        ];
+        if let Some((key, id)) = entity {
+            params.push((format!("metadata[{}]", key), id.to_string()));
         }
+        Ok(params)
+    }
+
+    /// Clave de idempotencia del PaymentIntent: la de la petición que creó el
+    /// pago, o el id del pago. Stripe admite hasta 255 caracteres, así que las
+    /// claves largas se envían como hash.
+    fn payment_intent_idempotency_key(payment: &PaymentAggregate) -> String {
+        match payment.payment().idempotency_key() {
+            Some(key) if key.len() <= 240 => format!("payment-intent-{}", key),
+            Some(key) => format!("payment-intent-{}", hex::encode(Sha256::digest(key.as_bytes()))),
+            None => format!("payment-intent-{}", payment.payment().id().value()),
+        }
+    }
+
+    /// Crear el PaymentIntent (sin confirmar). La clave de idempotencia evita
+    /// intents duplicados si se reintenta el procesamiento del mismo pago.
+    async fn create_payment_intent(&self, payment: &PaymentAggregate) -> Result<StripePaymentIntentResponse, AppError> {
+        let params = self.payment_intent_params(payment)?;
+        let idempotency_key = Self::payment_intent_idempotency_key(payment);
+
+        self.make_stripe_request("POST", "/payment_intents", Some(&params), Some(&idempotency_key)).await
+    }
+
+    /// Confirmar un PaymentIntent creado previamente
+    async fn confirm_payment_intent(&self, intent_id: &str) -> Result<StripePaymentIntentResponse, AppError> {
+        let endpoint = format!("/payment_intents/{}/confirm", intent_id);
+        let idempotency_key = format!("payment-intent-confirm-{}", intent_id);
+
+        self.make_stripe_request("POST", &endpoint, None, Some(&idempotency_key)).await
     }
 
     /// Get payment method ID for Stripe
@@ -155,33 +229,43 @@ impl StripeGateway {
     }
 
     /// Verify Stripe webhook signature using HMAC-SHA256
-    fn verify_webhook_signature(&self, payload: &str, signature: &str) -> Result<bool, AppError> {
-        // Parse the signature header: "t=timestamp,v1=signature"
-        let parts: Vec<&str> = signature.split(',').collect();
-        if parts.len() != 2 {
-            return Err(AppError::AuthenticationError("Invalid signature format".to_string()));
-        }
+    ///
+    /// The `Stripe-Signature` header is `t=<timestamp>,v1=<hex>[,v1=<hex>...]`;
+    /// any `v1` entry may match (secret rotation) and old timestamps are rejected.
+    fn verify_webhook_signature(&self, payload: &str, signature: &str) -> Result<(), AppError> {
+        self.verify_webhook_signature_at(payload, signature, Utc::now().timestamp())
+    }
 
-        let timestamp_part = parts[0];
-        let signature_part = parts[1];
+    fn verify_webhook_signature_at(&self, payload: &str, signature: &str, now: i64) -> Result<(), AppError> {
+        let mut timestamp = None;
+        let mut signatures = Vec::new();
+        for part in signature.split(',') {
+            match part.trim().split_once('=') {
+                Some(("t", value)) => timestamp = Some(value),
+                Some(("v1", value)) => signatures.push(value),
+                _ => {}
+            }
+        }
 
-        if !timestamp_part.starts_with("t=") || !signature_part.starts_with("v1=") {
+        let timestamp = timestamp
+            .ok_or_else(|| AppError::AuthenticationError("Invalid signature format".to_string()))?;
+        if signatures.is_empty() {
             return Err(AppError::AuthenticationError("Invalid signature format".to_string()));
         }
 
-        let timestamp = &timestamp_part[2..];
-        let signature = &signature_part[3..];
+        let timestamp_secs: i64 = timestamp
+            .parse()
+            .map_err(|_| AppError::AuthenticationError("Invalid signature timestamp".to_string()))?;
+        if (now - timestamp_secs).abs() > WEBHOOK_TOLERANCE_SECONDS {
+            return Err(AppError::AuthenticationError("Webhook signature timestamp outside tolerance".to_string()));
+        }
 
-        // Create the signed payload
+        // Create the signed payload and calculate HMAC-SHA256
         let signed_payload = format!("{}.{}", timestamp, payload);
+        let expected_signature = hex::encode(HMAC::mac(signed_payload.as_bytes(), self.config.webhook_secret.as_bytes()));
 
-        // Calculate HMAC-SHA256
-        let hmac = HMAC::mac(signed_payload.as_bytes(), self.config.webhook_secret.as_bytes());
-        let expected_signature = BASE64.encode(hmac);
-
-        // Compare signatures
-        if signature == expected_signature {
-            Ok(true)
+        if signatures.iter().any(|candidate| constant_time_eq(candidate.as_bytes(), expected_signature.as_bytes())) {
+            Ok(())
         } else {
             Err(AppError::AuthenticationError("Invalid webhook signature".to_string()))
         }
@@ -192,7 +276,8 @@ impl StripeGateway {
         &self,
         method: &str,
         endpoint: &str,
-        body: Option<Value>,
+        params: Option<&[(String, String)]>,
+        idempotency_key: Option<&str>,
     ) -> Result<T, AppError> {
         let url = format!("{}{}", self.base_url, endpoint);
         
@@ -205,9 +290,13 @@ impl StripeGateway {
         // Add authentication header
         request = request.header("Authorization", format!("Bearer {}", self.config.api_key));
 
-        // Add body if provided
-        if let Some(body_data) = body {
-            request = request.json(&body_data);
+        if let Some(key) = idempotency_key {
+            request = request.header("Idempotency-Key", key);
+        }
+
+        // Stripe espera application/x-www-form-urlencoded, no JSON
+        if let Some(params) = params {
+            request = request.form(params);
         }
 
         let response = request.send().await
@@ -252,30 +341,9 @@ impl PaymentGateway for StripeGateway {
         // Real Stripe API implementation
         tracing::info!("Processing payment through Stripe: {}", payment.payment().id().value());
 
-        // Get payment method ID
-        let payment_method_id = self.get_payment_method_id(payment)?;
-
-        // Create payment intent request
-        let payment_intent_request = StripePaymentIntentRequest {
-            amount: self.amount_to_stripe_cents(payment.payment().amount()),
-            currency: self.currency_to_stripe(payment.payment().amount().currency()),
-            payment_method: payment_method_id,
-            confirm: true,
-            metadata: json!({
-                "payment_id": payment.payment().id().value(),
-                "purpose": format!("{:?}", payment.payment().purpose()),
-                "user_id": payment.payment().payer_id(),
-            }),
-            customer: None, // Would be set if customer exists
-            description: Some(format!("VibeStream payment for {:?}", payment.payment().purpose())),
-        };
-
-        // Make API call to Stripe
-        let response: StripePaymentIntentResponse = self.make_stripe_request(
-            "POST",
-            "/payment_intents",
-            Some(serde_json::to_value(payment_intent_request)?),
-        ).await?;
+        // El intent se crea al procesar (idempotente) y se confirma en el mismo paso
+        let intent = self.create_payment_intent(payment).await?;
+        let response = self.confirm_payment_intent(&intent.id).await?;
 
         let processing_time = start_time.elapsed().as_millis() as u64;
 
@@ -287,9 +355,9 @@ impl PaymentGateway for StripeGateway {
             format!("Payment status: {}", response.status)
         };
 
-        // Calculate fees (Stripe charges 2.9% + 30 cents)
+        // Calculate fees (Stripe charges 2.9% + 30 minor units)
         let fee_amount = (response.amount as f64 * 0.029) + 30.0;
-        let fees_charged = Amount::new(fee_amount / 100.0, payment.payment().amount().currency().clone())
+        let fees_charged = Amount::new(from_minor_units(fee_amount.round() as u64, &response.currency), payment.payment().amount().currency().clone())
             .map_err(|e| AppError::DomainError(e.to_string()))?;
 
         Ok(GatewayResult {
@@ -321,18 +389,20 @@ impl PaymentGateway for StripeGateway {
             });
         }
 
-        // Create refund request
-        let refund_request = StripeRefundRequest {
-            charge: original_transaction_id.value().to_string(),
-            amount: Some(self.amount_to_stripe_cents(refund_amount)),
-            reason: Some(reason.to_string()),
-        };
+        // Create refund request. Stripe solo acepta motivos predefinidos, así que
+        // el motivo libre viaja en los metadatos.
+        let params = vec![
+            ("charge".to_string(), original_transaction_id.value().to_string()),
+            ("amount".to_string(), self.amount_to_stripe_minor_units(refund_amount)?.to_string()),
+            ("metadata[reason]".to_string(), reason.to_string()),
+        ];
 
         // Make API call to Stripe
         let response: StripeRefundResponse = self.make_stripe_request(
             "POST",
             "/refunds",
-            Some(serde_json::to_value(refund_request)?),
+            Some(&params),
+            None,
         ).await?;
 
         let success = response.status == "succeeded";
@@ -382,7 +452,7 @@ impl PaymentGateway for StripeGateway {
         }
 
         // Make a simple API call to check health
-        let _: Value = self.make_stripe_request("GET", "/account", None).await?;
+        let _: Value = self.make_stripe_request("GET", "/account", None, None).await?;
 
         let response_time = start_time.elapsed().as_millis() as u64;
 
@@ -408,6 +478,11 @@ impl PaymentGateway for StripeGateway {
     }
 }
 
+/// Comparación sin cortocircuito para no filtrar la firma por tiempos
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -425,21 +500,142 @@ mod tests {
         assert!(gateway.is_ok());
     }
 
-    #[test]
-    fn test_amount_conversion() {
+    fn test_gateway(base_url: &str) -> StripeGateway {
         let config = GatewayConfig {
-            api_key: "sk_test_fake".to_string(),
+            api_key: "sk_test_123".to_string(),
             webhook_secret: "whsec_fake".to_string(),
             environment: "test".to_string(),
         };
 
-        let gateway = StripeGateway {
+        StripeGateway {
             config,
             client: reqwest::Client::new(),
-            base_url: "https://api.stripe.com/v1".to_string(),
-        };
+            base_url: base_url.to_string(),
+        }
+    }
+
+    fn sign(secret: &str, timestamp: i64, payload: &str) -> String {
+        let signed_payload = format!("{}.{}", timestamp, payload);
+        format!("t={},v1={}", timestamp, hex::encode(HMAC::mac(signed_payload.as_bytes(), secret.as_bytes())))
+    }
+
+    fn song_purchase(song_id: Uuid) -> PaymentAggregate {
+        PaymentAggregate::create_payment(
+            Uuid::new_v4(),
+            Uuid::new_v4(),
+            Amount::new(1.99, Currency::USD).unwrap(),
+            PaymentMethod::CreditCard {
+                last_four_digits: "4242".to_string(),
+                card_type: crate::bounded_contexts::payment::domain::value_objects::CardType::Visa,
+            },
+            PaymentPurpose::SongPurchase { song_id },
+            crate::bounded_contexts::payment::domain::value_objects::FeePercentage::new(10.0).unwrap(),
+            crate::bounded_contexts::payment::domain::value_objects::PaymentMetadata {
+                user_ip: None,
+                user_agent: None,
+                platform_version: "1.0.0".to_string(),
+                reference_id: None,
+                additional_data: serde_json::json!({}),
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_amount_conversion() {
+        let gateway = test_gateway(STRIPE_API_BASE_URL);
 
         let amount = Amount::new(100.50, Currency::USD).unwrap();
-        assert_eq!(gateway.amount_to_stripe_cents(&amount), 10050);
+        assert_eq!(gateway.amount_to_stripe_minor_units(&amount).unwrap(), 10050);
+
+        // 19.99 * 100 = 1998.9999... en f64; truncar perdería un céntimo
+        let amount = Amount::new(19.99, Currency::EUR).unwrap();
+        assert_eq!(gateway.amount_to_stripe_minor_units(&amount).unwrap(), 1999);
+
+        let amount = Amount::new(10.0, Currency::ETH).unwrap();
+        assert!(gateway.amount_to_stripe_minor_units(&amount).is_err());
+    }
+
+    #[test]
+    fn test_minor_units_by_currency() {
+        assert_eq!(to_minor_units(500.0, "jpy"), 500);
+        assert_eq!(to_minor_units(1.234, "KWD"), 1234);
+        assert_eq!(to_minor_units(0.29, "gbp"), 29);
+        assert_eq!(from_minor_units(1999, "usd"), 19.99);
+        assert_eq!(from_minor_units(500, "jpy"), 500.0);
+    }
+
+    #[test]
+    fn test_payment_intent_params_include_purchase_metadata() {
+        let gateway = test_gateway(STRIPE_API_BASE_URL);
+        let song_id = Uuid::new_v4();
+        let payment = song_purchase(song_id);
+
+        let params = gateway.payment_intent_params(&payment).unwrap();
+        let get = |key: &str| params.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+
+        assert_eq!(get("amount"), Some("199"));
+        assert_eq!(get("currency"), Some("usd"));
+        assert_eq!(get("confirm"), Some("false"));
+        assert_eq!(get("metadata[purpose]"), Some("song_purchase"));
+        assert_eq!(get("metadata[song_id]"), Some(song_id.to_string().as_str()));
+        assert_eq!(get("metadata[payment_id]"), Some(payment.payment().id().value().to_string().as_str()));
+    }
+
+    #[test]
+    fn test_payment_intent_idempotency_key_follows_the_request() {
+        let payment = song_purchase(Uuid::new_v4());
+        assert_eq!(
+            StripeGateway::payment_intent_idempotency_key(&payment),
+            format!("payment-intent-{}", payment.payment().id().value())
+        );
+
+        let payment = song_purchase(Uuid::new_v4()).with_idempotency_key("order-42".to_string());
+        assert_eq!(StripeGateway::payment_intent_idempotency_key(&payment), "payment-intent-order-42");
+
+        let payment = song_purchase(Uuid::new_v4()).with_idempotency_key("k".repeat(255));
+        assert!(StripeGateway::payment_intent_idempotency_key(&payment).len() <= 255);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_webhook_signature_verification() {
+        let gateway = test_gateway(STRIPE_API_BASE_URL);
+        let payload = r#"{"id":"evt_1","type":"payment_intent.succeeded"}"#;
+        let now = 1_700_000_000;
+
+        let header = sign("whsec_fake", now, payload);
+        assert!(gateway.verify_webhook_signature_at(payload, &header, now + 10).is_ok());
+
+        // Secreto rotado: basta con que una de las firmas v1 sea válida
+        let rotated = format!("{},v1=deadbeef", header);
+        assert!(gateway.verify_webhook_signature_at(payload, &rotated, now).is_ok());
+
+        let tampered = payload.replace("evt_1", "evt_2");
+        assert!(gateway.verify_webhook_signature_at(&tampered, &header, now).is_err());
+
+        let wrong_secret = sign("whsec_other", now, payload);
+        assert!(gateway.verify_webhook_signature_at(payload, &wrong_secret, now).is_err());
+
+        // Repetición de un evento antiguo
+        assert!(gateway
+            .verify_webhook_signature_at(payload, &header, now + WEBHOOK_TOLERANCE_SECONDS + 1)
+            .is_err());
+
+        assert!(gateway.verify_webhook_signature_at(payload, "v1=abc", now).is_err());
+    }
+
+    /// Requiere stripe-mock: `docker run -p 12111:12111 stripe/stripe-mock`
+    #[tokio::test]
+    #[ignore]
+    async fn test_process_payment_against_stripe_mock() {
+        let base_url = std::env::var("STRIPE_MOCK_URL")
+            .unwrap_or_else(|_| "http://localhost:12111/v1".to_string());
+        let gateway = test_gateway(&base_url);
+        let payment = song_purchase(Uuid::new_v4());
+
+        let result = gateway.process_payment(&payment).await.unwrap();
+
+        assert!(result.transaction_id.starts_with("pi_"));
+        assert!(!result.gateway_response_code.is_empty());
+    }
+}
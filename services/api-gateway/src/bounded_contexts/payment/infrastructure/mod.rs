@@ -4,10 +4,20 @@ pub mod gateways;
 pub mod messaging;
 pub mod database;
 pub mod webhooks;
+pub mod statistics_cache;
+pub mod exchange_rates;
+pub mod user_deletion_listener;
+pub mod payout_wallet;
 
 pub use repositories::*;
 pub use services::*;
 pub use gateways::*;
 pub use messaging::*;
 pub use database::*; 
-pub use webhooks::*; 
\ No newline at end of file
+pub use webhooks::*;
+pub use statistics_cache::RedisPaymentStatisticsCache;
+pub use user_deletion_listener::PaymentUserDeletionListener;
+pub use payout_wallet::{PayoutTransferResultWorker, QueuePayoutWalletClient};
+pub use exchange_rates::{
+    FixedExchangeRateProvider, HttpExchangeRateProvider, CachedExchangeRateProvider, currency_converter_from_env,
+};
\ No newline at end of file
@@ -0,0 +1,212 @@
+//! Wallet de payouts sobre la cola Redis hacia solana-integration
+//!
+//! Las transferencias se encolan y solana-integration publica firma y estado
+//! en la cola de respuesta; el resultado se guarda por referencia para que el
+//! scheduler de payouts lo consulte en el siguiente ciclo.
+
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use sqlx::{PgPool, Row};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use vibestream_types::{PayoutTransferOutcome, PayoutTransferRequest, PayoutTransferResult, QueueNames};
+
+use crate::bounded_contexts::payment::application::artist_payout_service::{
+    TransferReceipt, TransferStatus, WalletClient, WalletTransfer,
+};
+use crate::services::MessageQueue;
+use crate::shared::domain::errors::AppError;
+
+pub struct QueuePayoutWalletClient {
+    pool: PgPool,
+    message_queue: MessageQueue,
+    request_queue: String,
+    reply_queue: String,
+    /// Igual que `PayoutPolicy::submission_expiry`: una petición que sigue en
+    /// la cola pasado este plazo ya no se envía
+    submission_expiry: Duration,
+}
+
+impl QueuePayoutWalletClient {
+    pub fn new(pool: PgPool, message_queue: MessageQueue, submission_expiry: Duration) -> Self {
+        Self {
+            pool,
+            message_queue,
+            request_queue: QueueNames::SOLANA_PAYOUT_TRANSFER.to_string(),
+            reply_queue: QueueNames::ARTIST_PAYOUT_TRANSFER_RESULTS.to_string(),
+            submission_expiry,
+        }
+    }
+
+    pub fn reply_queue(&self) -> &str {
+        &self.reply_queue
+    }
+
+    /// Registrar lo que informa solana-integration. Un resultado repetido o
+    /// atrasado no deshace una confirmación.
+    pub async fn apply_result(&self, result: &PayoutTransferResult) -> Result<(), AppError> {
+        let (status, signature, reason) = match &result.outcome {
+            PayoutTransferOutcome::Sent { signature } => ("sent", Some(signature.as_str()), None),
+            PayoutTransferOutcome::Confirmed { signature } => ("confirmed", Some(signature.as_str()), None),
+            PayoutTransferOutcome::Failed { signature, reason } => ("failed", signature.as_deref(), Some(reason.as_str())),
+        };
+
+        sqlx::query(
+            r#"UPDATE payout_wallet_transfers
+               SET status = $2, signature = COALESCE($3, signature), failure_reason = $4, updated_at = NOW()
+               WHERE reference = $1 AND status <> 'confirmed'"#,
+        )
+        .bind(&result.reference)
+        .bind(status)
+        .bind(signature)
+        .bind(reason)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+}
+
+fn transfer_status(status: &str, failure_reason: Option<String>) -> TransferStatus {
+    match status {
+        "confirmed" => TransferStatus::Confirmed,
+        "failed" => TransferStatus::Failed(failure_reason.unwrap_or_else(|| "Transfer failed".to_string())),
+        _ => TransferStatus::Pending,
+    }
+}
+
+#[async_trait]
+impl WalletClient for QueuePayoutWalletClient {
+    /// Encola las transferencias; las firmas llegan después por la cola de
+    /// respuesta, así que no hay recibos inmediatos
+    async fn transfer_batch(&self, transfers: &[WalletTransfer]) -> Result<Vec<TransferReceipt>, AppError> {
+        let valid_until = Utc::now() + self.submission_expiry;
+        for transfer in transfers {
+            let currency = format!("{:?}", transfer.currency);
+            // Un reenvío tras expirar vuelve a 'queued'; una transferencia ya firmada no se toca
+            sqlx::query(
+                r#"INSERT INTO payout_wallet_transfers (reference, recipient_wallet, amount, currency)
+                   VALUES ($1, $2, $3::float8, $4)
+                   ON CONFLICT (reference) DO UPDATE
+                   SET status = 'queued', failure_reason = NULL, queued_at = NOW(), updated_at = NOW()
+                   WHERE payout_wallet_transfers.signature IS NULL"#,
+            )
+            .bind(&transfer.reference)
+            .bind(&transfer.recipient)
+            .bind(transfer.amount)
+            .bind(&currency)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+            let request = PayoutTransferRequest {
+                reference: transfer.reference.clone(),
+                recipient_wallet: transfer.recipient.clone(),
+                amount: transfer.amount,
+                currency,
+                valid_until,
+                reply_queue: self.reply_queue.clone(),
+            };
+            let message = serde_json::to_string(&request)
+                .map_err(|e| AppError::SerializationError(e.to_string()))?;
+            self.message_queue
+                .send_message(&self.request_queue, &message)
+                .await
+                .map_err(|e| AppError::ExternalServiceError(format!("Failed to enqueue payout transfer: {}", e)))?;
+        }
+        Ok(Vec::new())
+    }
+
+    async fn find_transfer(&self, reference: &str) -> Result<Option<String>, AppError> {
+        sqlx::query_scalar(
+            "SELECT signature FROM payout_wallet_transfers WHERE reference = $1 AND signature IS NOT NULL",
+        )
+        .bind(reference)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))
+    }
+
+    async fn transfer_status(&self, signature: &str) -> Result<TransferStatus, AppError> {
+        let row = sqlx::query("SELECT status, failure_reason FROM payout_wallet_transfers WHERE signature = $1")
+            .bind(signature)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(match row {
+            Some(row) => {
+                let status: String = row.get("status");
+                transfer_status(&status, row.get("failure_reason"))
+            }
+            None => TransferStatus::Pending,
+        })
+    }
+}
+
+/// Consume los resultados de transferencias de payouts que publica solana-integration
+pub struct PayoutTransferResultWorker {
+    wallet: Arc<QueuePayoutWalletClient>,
+    message_queue: MessageQueue,
+    running: Arc<AtomicBool>,
+}
+
+impl PayoutTransferResultWorker {
+    pub fn new(wallet: Arc<QueuePayoutWalletClient>, message_queue: MessageQueue) -> Self {
+        Self {
+            wallet,
+            message_queue,
+            running: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub async fn start(&self) -> Result<(), AppError> {
+        self.running.store(true, Ordering::Relaxed);
+        let queue_name = self.wallet.reply_queue().to_string();
+
+        tracing::info!("Starting payout transfer result worker on {}", queue_name);
+
+        while self.running.load(Ordering::Relaxed) {
+            match self.message_queue.receive_message(&queue_name, 5).await {
+                Ok(Some(message_json)) => match serde_json::from_str::<PayoutTransferResult>(&message_json) {
+                    Ok(result) => {
+                        if let Err(e) = self.wallet.apply_result(&result).await {
+                            tracing::error!("Failed to apply payout transfer result {}: {}", result.reference, e);
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("Failed to deserialize payout transfer result: {}", e);
+                    }
+                },
+                Ok(None) => continue,
+                Err(e) => {
+                    tracing::error!("Error receiving message from queue {}: {}", queue_name, e);
+                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                }
+            }
+        }
+
+        tracing::info!("Payout transfer result worker stopped");
+        Ok(())
+    }
+
+    pub fn stop(&self) {
+        self.running.store(false, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_confirmed_and_failed_transfers_leave_pending() {
+        assert_eq!(transfer_status("queued", None), TransferStatus::Pending);
+        assert_eq!(transfer_status("sent", None), TransferStatus::Pending);
+        assert_eq!(transfer_status("confirmed", None), TransferStatus::Confirmed);
+        assert_eq!(
+            transfer_status("failed", Some("Insufficient funds".to_string())),
+            TransferStatus::Failed("Insufficient funds".to_string())
+        );
+    }
+}
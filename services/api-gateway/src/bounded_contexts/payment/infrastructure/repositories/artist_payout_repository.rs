@@ -0,0 +1,384 @@
+//! PostgreSQL implementation of ArtistPayoutRepository
+
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::domain::{
+    artist_payouts::{ArtistPayout, ArtistPayoutStatus, BalanceLedgerEntry, PayoutMethod},
+    repository::{ArtistPayoutRepository, PaymentRepositoryResult},
+    value_objects::Currency,
+};
+
+use super::PostgresRefundRepository;
+
+/// Código SQLSTATE de `unique_violation` (índice `idx_artist_payouts_one_open`)
+const UNIQUE_VIOLATION: &str = "23505";
+
+const ENTRY_COLUMNS: &str = r#"id, artist_id, source_id, amount::float8 AS amount, currency, status,
+       payout_id, created_at, settled_at"#;
+
+const PAYOUT_COLUMNS: &str = r#"id, artist_id, method, amount::float8 AS amount, currency, status,
+       tx_signature, failure_reason, created_at, submitting_at, submitted_at, confirmed_at"#;
+
+pub struct PostgresArtistPayoutRepository {
+    pool: PgPool,
+}
+
+impl PostgresArtistPayoutRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_entry(row: PgRow) -> Result<BalanceLedgerEntry, AppError> {
+        let currency: String = row.get("currency");
+        let status: String = row.get("status");
+
+        Ok(BalanceLedgerEntry {
+            id: row.get("id"),
+            artist_id: row.get("artist_id"),
+            source_id: row.get("source_id"),
+            amount: row.get("amount"),
+            currency: PostgresRefundRepository::parse_currency(&currency),
+            status: status.parse().map_err(AppError::SerializationError)?,
+            payout_id: row.get("payout_id"),
+            created_at: row.get("created_at"),
+            settled_at: row.get("settled_at"),
+        })
+    }
+
+    fn row_to_payout(row: PgRow) -> Result<ArtistPayout, AppError> {
+        let currency: String = row.get("currency");
+        let status: String = row.get("status");
+        let method: PayoutMethod = serde_json::from_value(row.get("method"))
+            .map_err(|e| AppError::SerializationError(e.to_string()))?;
+
+        Ok(ArtistPayout {
+            id: row.get("id"),
+            artist_id: row.get("artist_id"),
+            method,
+            amount: row.get("amount"),
+            currency: PostgresRefundRepository::parse_currency(&currency),
+            status: status.parse().map_err(AppError::SerializationError)?,
+            tx_signature: row.get("tx_signature"),
+            failure_reason: row.get("failure_reason"),
+            created_at: row.get("created_at"),
+            submitting_at: row.get("submitting_at"),
+            submitted_at: row.get("submitted_at"),
+            confirmed_at: row.get("confirmed_at"),
+        })
+    }
+
+    /// Close a submitted payout and update its entries with `entry_sql`
+    async fn close_payout(&self, payout: &ArtistPayout, entry_sql: &str) -> PaymentRepositoryResult<()> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let result = sqlx::query(
+            r#"UPDATE artist_payouts
+               SET status = $2, failure_reason = $3, confirmed_at = $4
+               WHERE id = $1 AND status = 'submitted'"#,
+        )
+        .bind(payout.id)
+        .bind(payout.status.to_string())
+        .bind(&payout.failure_reason)
+        .bind(payout.confirmed_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::ConcurrencyConflict(format!(
+                "Payout {} was closed concurrently",
+                payout.id
+            )));
+        }
+
+        sqlx::query(entry_sql)
+            .bind(payout.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ArtistPayoutRepository for PostgresArtistPayoutRepository {
+    async fn save_payout_method(&self, artist_id: Uuid, method: &PayoutMethod) -> PaymentRepositoryResult<()> {
+        let details = serde_json::to_value(method)
+            .map_err(|e| AppError::SerializationError(e.to_string()))?;
+
+        sqlx::query(
+            r#"INSERT INTO artist_payout_methods (artist_id, method_type, details, created_at, updated_at)
+               VALUES ($1, $2, $3, NOW(), NOW())
+               ON CONFLICT (artist_id) DO UPDATE
+               SET method_type = EXCLUDED.method_type, details = EXCLUDED.details, updated_at = NOW()"#,
+        )
+        .bind(artist_id)
+        .bind(method.kind())
+        .bind(details)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to save payout method: {}", e)))?;
+        Ok(())
+    }
+
+    async fn find_payout_method(&self, artist_id: Uuid) -> PaymentRepositoryResult<Option<PayoutMethod>> {
+        let row = sqlx::query("SELECT details FROM artist_payout_methods WHERE artist_id = $1")
+            .bind(artist_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        row.map(|row| {
+            serde_json::from_value(row.get("details"))
+                .map_err(|e| AppError::SerializationError(e.to_string()))
+        })
+        .transpose()
+    }
+
+    async fn credit(&self, entries: &[BalanceLedgerEntry]) -> PaymentRepositoryResult<()> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        for entry in entries {
+            sqlx::query(
+                r#"INSERT INTO artist_balance_entries (id, artist_id, source_id, amount, currency, status, created_at)
+                   VALUES ($1, $2, $3, $4::float8, $5, $6, $7)
+                   ON CONFLICT (source_id) DO NOTHING"#,
+            )
+            .bind(entry.id)
+            .bind(entry.artist_id)
+            .bind(entry.source_id)
+            .bind(entry.amount)
+            .bind(format!("{:?}", entry.currency))
+            .bind(entry.status.to_string())
+            .bind(entry.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to credit artist balance: {}", e)))?;
+        }
+
+        tx.commit().await.map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn revoke_credits(&self, source_ids: &[Uuid]) -> PaymentRepositoryResult<()> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // Los débitos de reembolsos sobre esos pagos se van con ellos: el
+        // crédito que compensaban desaparece
+        let source_ids: Vec<Uuid> = sqlx::query(
+            r#"SELECT id FROM royalty_reversals WHERE royalty_payout_id = ANY($1)
+               UNION SELECT unnest($1::uuid[])"#,
+        )
+        .bind(source_ids)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .map(|row| row.get("id"))
+        .collect();
+
+        // Bloquea las filas para que ningún payout las reserve mientras tanto
+        let paid: i64 = sqlx::query(
+            r#"SELECT COUNT(*) AS paid FROM (
+                   SELECT status FROM artist_balance_entries WHERE source_id = ANY($1) FOR UPDATE
+               ) locked WHERE status <> 'available'"#,
+        )
+        .bind(&source_ids)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .get("paid");
+
+        if paid > 0 {
+            return Err(AppError::InvalidState(format!(
+                "{} credit(s) are already in a payout and cannot be revoked",
+                paid
+            )));
+        }
+
+        sqlx::query("DELETE FROM artist_balance_entries WHERE source_id = ANY($1)")
+            .bind(&source_ids)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn find_entries(&self, artist_id: Uuid) -> PaymentRepositoryResult<Vec<BalanceLedgerEntry>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM artist_balance_entries WHERE artist_id = $1 ORDER BY created_at",
+            ENTRY_COLUMNS
+        ))
+        .bind(artist_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(Self::row_to_entry).collect()
+    }
+
+    async fn find_due_balances(&self, minimum: f64) -> PaymentRepositoryResult<Vec<(Uuid, Currency, f64)>> {
+        let rows = sqlx::query(
+            r#"SELECT artist_id, currency, SUM(amount)::float8 AS available
+               FROM artist_balance_entries
+               WHERE status = 'available'
+               GROUP BY artist_id, currency
+               HAVING SUM(amount) >= $1::float8"#,
+        )
+        .bind(minimum)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let currency: String = row.get("currency");
+                (row.get("artist_id"), PostgresRefundRepository::parse_currency(&currency), row.get("available"))
+            })
+            .collect())
+    }
+
+    async fn open_payout(&self, payout: &ArtistPayout, minimum: f64) -> PaymentRepositoryResult<Option<ArtistPayout>> {
+        let currency = format!("{:?}", payout.currency);
+        let method = serde_json::to_value(&payout.method)
+            .map_err(|e| AppError::SerializationError(e.to_string()))?;
+
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let entry_ids: Vec<(Uuid, f64)> = sqlx::query(
+            r#"SELECT id, amount::float8 AS amount FROM artist_balance_entries
+               WHERE artist_id = $1 AND currency = $2 AND status = 'available'
+               FOR UPDATE SKIP LOCKED"#,
+        )
+        .bind(payout.artist_id)
+        .bind(&currency)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .into_iter()
+        .map(|row| (row.get("id"), row.get("amount")))
+        .collect();
+
+        let reserved: f64 = entry_ids.iter().map(|(_, amount)| amount).sum();
+        if reserved < minimum {
+            return Ok(None);
+        }
+        let opened = ArtistPayout { amount: reserved, ..payout.clone() };
+
+        let inserted = sqlx::query(
+            r#"INSERT INTO artist_payouts (id, artist_id, method, amount, currency, status, created_at)
+               VALUES ($1, $2, $3, $4::float8, $5, $6, $7)"#,
+        )
+        .bind(opened.id)
+        .bind(opened.artist_id)
+        .bind(method)
+        .bind(opened.amount)
+        .bind(&currency)
+        .bind(opened.status.to_string())
+        .bind(opened.created_at)
+        .execute(&mut *tx)
+        .await;
+
+        match inserted {
+            Ok(_) => {}
+            // Ya hay un payout abierto para este artista y moneda
+            Err(e) if e.as_database_error().and_then(|d| d.code()).as_deref() == Some(UNIQUE_VIOLATION) => {
+                return Ok(None)
+            }
+            Err(e) => return Err(AppError::DatabaseError(format!("Failed to open payout: {}", e))),
+        }
+
+        let ids: Vec<Uuid> = entry_ids.iter().map(|(id, _)| *id).collect();
+        sqlx::query("UPDATE artist_balance_entries SET status = 'reserved', payout_id = $2 WHERE id = ANY($1)")
+            .bind(&ids)
+            .bind(opened.id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(Some(opened))
+    }
+
+    async fn transition_payout(&self, payout: &ArtistPayout, expected: ArtistPayoutStatus) -> PaymentRepositoryResult<()> {
+        let result = sqlx::query(
+            r#"UPDATE artist_payouts
+               SET status = $2, tx_signature = $3, failure_reason = $4,
+                   submitting_at = $5, submitted_at = $6, confirmed_at = $7
+               WHERE id = $1 AND status = $8"#,
+        )
+        .bind(payout.id)
+        .bind(payout.status.to_string())
+        .bind(&payout.tx_signature)
+        .bind(&payout.failure_reason)
+        .bind(payout.submitting_at)
+        .bind(payout.submitted_at)
+        .bind(payout.confirmed_at)
+        .bind(expected.to_string())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::ConcurrencyConflict(format!(
+                "Payout {} is no longer {}",
+                payout.id, expected
+            )));
+        }
+        Ok(())
+    }
+
+    async fn settle_payout(&self, payout: &ArtistPayout) -> PaymentRepositoryResult<()> {
+        self.close_payout(
+            payout,
+            "UPDATE artist_balance_entries SET status = 'settled', settled_at = NOW() WHERE payout_id = $1",
+        )
+        .await
+    }
+
+    async fn release_payout(&self, payout: &ArtistPayout) -> PaymentRepositoryResult<()> {
+        self.close_payout(
+            payout,
+            "UPDATE artist_balance_entries SET status = 'available', payout_id = NULL WHERE payout_id = $1",
+        )
+        .await
+    }
+
+    async fn find_payouts_by_status(&self, status: ArtistPayoutStatus) -> PaymentRepositoryResult<Vec<ArtistPayout>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM artist_payouts WHERE status = $1 ORDER BY created_at",
+            PAYOUT_COLUMNS
+        ))
+        .bind(status.to_string())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(Self::row_to_payout).collect()
+    }
+
+    async fn find_payouts_by_artist(&self, artist_id: Uuid) -> PaymentRepositoryResult<Vec<ArtistPayout>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM artist_payouts WHERE artist_id = $1 ORDER BY created_at DESC",
+            PAYOUT_COLUMNS
+        ))
+        .bind(artist_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(Self::row_to_payout).collect()
+    }
+}
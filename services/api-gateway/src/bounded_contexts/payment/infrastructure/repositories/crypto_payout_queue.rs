@@ -0,0 +1,75 @@
+//! Manual payout queue for crypto refunds
+//!
+//! On-chain payments cannot be reversed through a gateway API: the refund is
+//! queued here and an operator sends the transfer, then confirms it with the
+//! transaction hash.
+
+use sqlx::PgPool;
+use uuid::Uuid;
+use chrono::Utc;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::domain::{
+    refunds::RefundTransaction,
+    value_objects::{Blockchain, WalletAddress},
+};
+
+pub struct PostgresCryptoPayoutQueue {
+    pool: PgPool,
+}
+
+impl PostgresCryptoPayoutQueue {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Queue the payout of `refund` to the wallet that paid; returns the payout id
+    pub async fn enqueue(
+        &self,
+        refund: &RefundTransaction,
+        blockchain: &Blockchain,
+        recipient: &WalletAddress,
+    ) -> Result<Uuid, AppError> {
+        let payout_id = Uuid::new_v4();
+        sqlx::query(
+            r#"INSERT INTO crypto_refund_payouts (
+                   id, refund_id, payment_id, blockchain, recipient_address, amount, currency, status, created_at
+               ) VALUES ($1, $2, $3, $4, $5, $6::float8, $7, 'queued', $8)"#,
+        )
+        .bind(payout_id)
+        .bind(refund.id)
+        .bind(refund.payment_id)
+        .bind(format!("{:?}", blockchain))
+        .bind(recipient.value())
+        .bind(refund.amount.value())
+        .bind(format!("{:?}", refund.amount.currency()))
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to queue crypto payout: {}", e)))?;
+
+        Ok(payout_id)
+    }
+
+    /// Mark the payout of a refund as sent on-chain
+    pub async fn mark_sent(&self, refund_id: Uuid, tx_hash: &str) -> Result<(), AppError> {
+        let result = sqlx::query(
+            r#"UPDATE crypto_refund_payouts
+               SET status = 'sent', tx_hash = $2, sent_at = NOW()
+               WHERE refund_id = $1 AND status = 'queued'"#,
+        )
+        .bind(refund_id)
+        .bind(tx_hash)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::InvalidState(format!(
+                "No queued payout for refund {}",
+                refund_id
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+//! PostgreSQL implementation of PaymentExchangeRateRepository
+
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::domain::{
+    exchange::{CapturedExchangeRate, ExchangeRate, NormalizedAmount},
+    repository::{PaymentExchangeRateRepository, PaymentRepositoryResult},
+    value_objects::Amount,
+};
+
+use super::PostgresRefundRepository;
+
+pub struct PostgresPaymentExchangeRateRepository {
+    pool: PgPool,
+}
+
+impl PostgresPaymentExchangeRateRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PaymentExchangeRateRepository for PostgresPaymentExchangeRateRepository {
+    async fn record(&self, captured: &CapturedExchangeRate) -> PaymentRepositoryResult<()> {
+        let amount = &captured.amount;
+        sqlx::query(
+            r#"INSERT INTO payment_exchange_rates (
+                   payment_id, original_value, original_currency, base_value, base_currency,
+                   rate, quoted_at, captured_at
+               ) VALUES ($1, $2::float8, $3, $4::float8, $5, $6, $7, $8)
+               ON CONFLICT (payment_id) DO NOTHING"#,
+        )
+        .bind(captured.payment_id)
+        .bind(amount.original.value())
+        .bind(format!("{:?}", amount.original.currency()))
+        .bind(amount.base.value())
+        .bind(format!("{:?}", amount.base.currency()))
+        .bind(amount.rate.rate)
+        .bind(amount.rate.quoted_at)
+        .bind(captured.captured_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to record exchange rate: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn find_by_payment(&self, payment_id: Uuid) -> PaymentRepositoryResult<Option<CapturedExchangeRate>> {
+        let row = sqlx::query(
+            r#"SELECT payment_id, original_value::float8 AS original_value, original_currency,
+                      base_value::float8 AS base_value, base_currency, rate, quoted_at, captured_at
+               FROM payment_exchange_rates
+               WHERE payment_id = $1"#,
+        )
+        .bind(payment_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let Some(row) = row else { return Ok(None) };
+
+        let original_currency = PostgresRefundRepository::parse_currency(&row.get::<String, _>("original_currency"));
+        let base_currency = PostgresRefundRepository::parse_currency(&row.get::<String, _>("base_currency"));
+        // Se conserva el importe base guardado: no se recalcula con el tipo
+        let amount = NormalizedAmount {
+            original: Amount::new(row.get("original_value"), original_currency.clone())?,
+            base: Amount::new(row.get("base_value"), base_currency.clone())?,
+            rate: ExchangeRate::new(original_currency, base_currency, row.get("rate"), row.get("quoted_at"))?,
+        };
+
+        Ok(Some(CapturedExchangeRate {
+            payment_id: row.get("payment_id"),
+            amount,
+            captured_at: row.get("captured_at"),
+        }))
+    }
+}
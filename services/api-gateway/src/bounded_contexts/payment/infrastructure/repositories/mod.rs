@@ -2,12 +2,31 @@ pub mod payment_repository;
 pub mod royalty_repository;
 pub mod revenue_sharing_repository;
 pub mod refund_repository_impl; // Added
+pub mod royalty_reversal_repository;
+pub mod crypto_payout_queue;
+pub mod royalty_run_repository;
+pub mod song_royalty_settings;
+pub mod artist_payout_repository;
+pub mod royalty_advance_repository;
+pub mod payment_statistics_repository;
+pub mod exchange_rate_repository;
+pub mod webhook_repository;
 // pub mod fraud_repository;
 // pub mod payment_analytics_repository;
 
 pub use payment_repository::*;
 pub use royalty_repository::*;
 pub use revenue_sharing_repository::*;
+pub use refund_repository_impl::PostgresRefundRepository;
+pub use royalty_reversal_repository::PostgresRoyaltyReversalRepository;
+pub use crypto_payout_queue::PostgresCryptoPayoutQueue;
+pub use royalty_run_repository::PostgresRoyaltyRunRepository;
+pub use song_royalty_settings::PostgresSongRoyaltySettings;
+pub use artist_payout_repository::PostgresArtistPayoutRepository;
+pub use royalty_advance_repository::PostgresRoyaltyAdvanceRepository;
+pub use payment_statistics_repository::PostgresPaymentStatisticsRepository;
+pub use exchange_rate_repository::PostgresPaymentExchangeRateRepository;
+pub use webhook_repository::PostgresWebhookRepository;
 // pub use fraud_repository::*;
 // pub use payment_analytics_repository::*;
 
@@ -23,8 +23,20 @@ impl PostgreSQLPaymentRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    /// Payment that already holds an idempotency key
+    pub async fn existing_payment_id(&self, idempotency_key: &str) -> Result<Option<Uuid>, AppError> {
+        let row = sqlx::query("SELECT id FROM payments WHERE idempotency_key = $1")
+            .bind(idempotency_key)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(row.map(|r| r.get("id")))
+    }
 }
 
+const UNIQUE_VIOLATION: &str = "23505";
+
 pub type PostgresPaymentRepository = PostgreSQLPaymentRepository;
 
 #[async_trait]
@@ -37,12 +49,13 @@ impl PaymentRepository for PostgreSQLPaymentRepository {
             "INSERT INTO payments (
                 id, payer_id, payee_id, amount_value, amount_currency, net_amount_value, net_amount_currency, 
                 platform_fee_value, platform_fee_currency, payment_method_details, payment_method_type, 
-                purpose_details, purpose_type, status, 
+                purpose_details, purpose_type, status, status_details,
                 blockchain_hash, created_at, updated_at, completed_at,
                 failure_reason, metadata
-            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20)
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21)
             ON CONFLICT (id) DO UPDATE SET
                 status = EXCLUDED.status,
+                status_details = EXCLUDED.status_details,
                 blockchain_hash = EXCLUDED.blockchain_hash,
                 updated_at = EXCLUDED.updated_at,
                 completed_at = EXCLUDED.completed_at,
@@ -66,6 +79,7 @@ impl PaymentRepository for PostgreSQLPaymentRepository {
             serde_json::to_value(payment.payment().purpose()).unwrap(),
             match payment.payment().purpose() {
                 PaymentPurpose::NFTPurchase{..} => "NFTPurchase",
+                PaymentPurpose::SongPurchase{..} => "SongPurchase",
                 PaymentPurpose::SharePurchase{..} => "SharePurchase",
                 PaymentPurpose::ShareTrade{..} => "ShareTrade", // Make sure this is in CHECK constraint! Schema said 'etc.'? NO, schema listing was incomplete in comment but CHECK might be stricter.
                 // 008 migration: CHECK (purpose_type VARCHAR(50) NOT NULL) -- Wait, no CHECK for purpose_type values list in 008?
@@ -78,7 +92,8 @@ impl PaymentRepository for PostgreSQLPaymentRepository {
                 PaymentPurpose::PlatformFee{..} => "PlatformFee",
                 PaymentPurpose::Refund{..} => "Refund",
             },
-            format!("{:?}", payment.payment().status()),
+            payment.payment().status().name(),
+            serde_json::to_value(payment.payment().status()).unwrap(),
             payment.payment().blockchain_hash().map(|h| h.value().to_string()),
             payment.payment().created_at(),
             payment.payment().updated_at(),
@@ -88,7 +103,34 @@ impl PaymentRepository for PostgreSQLPaymentRepository {
         )
         .execute(&mut *tx)
         .await
-        .map_err(AppError::DatabaseError)?;
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // Artist/platform split as exact NUMERIC, for payouts and platform revenue,
+        // and the idempotency key, unique across payments
+        let platform_share = payment.payment().platform_fee().map(|f| f.value()).unwrap_or(0.0);
+        let idempotency_key = payment.payment().idempotency_key();
+        let updated = sqlx::query(
+            "UPDATE payments SET artist_share = $2, platform_share = $3,
+                idempotency_key = COALESCE($4, idempotency_key)
+             WHERE id = $1",
+        )
+        .bind(payment.payment().id().value())
+        .bind(rust_decimal::Decimal::try_from(payment.payment().net_amount().value()).unwrap_or_default())
+        .bind(rust_decimal::Decimal::try_from(platform_share).unwrap_or_default())
+        .bind(idempotency_key)
+        .execute(&mut *tx)
+        .await;
+        match (updated, idempotency_key) {
+            (Ok(_), _) => {}
+            (Err(e), Some(key)) if e.as_database_error().and_then(|d| d.code()).as_deref() == Some(UNIQUE_VIOLATION) => {
+                drop(tx);
+                return match self.existing_payment_id(key).await? {
+                    Some(existing_id) => Err(AppError::DuplicatePayment { existing_id }),
+                    None => Err(AppError::DatabaseError(e.to_string())),
+                };
+            }
+            (Err(e), _) => return Err(AppError::DatabaseError(e.to_string())),
+        }
         
         // Save payment events
         for event in payment.uncommitted_events() {
@@ -157,20 +199,8 @@ impl PaymentRepository for PostgreSQLPaymentRepository {
     }
     
     async fn find_by_idempotency_key(&self, key: &str) -> Result<Option<PaymentAggregate>, AppError> {
-        let payment_row = sqlx::query!(
-            "SELECT * FROM payments WHERE metadata->>'idempotency_key' = $1",
-            key
-        )
-        .fetch_optional(&self.pool)
-        .await
-        .map_err(AppError::DatabaseError)?;
-        
-        match payment_row {
-            Some(row) => {
-                let payment = self.row_to_payment(&row)?;
-                let payment_id = payment.id();
-                self.find_by_id(payment_id).await
-            }
+        match self.existing_payment_id(key).await? {
+            Some(payment_id) => self.find_by_id(&PaymentId::from_uuid(payment_id)).await,
             None => Ok(None),
         }
     }
@@ -330,10 +360,7 @@ impl PaymentRepository for PostgreSQLPaymentRepository {
     async fn find_by_status(&self, status: &PaymentStatus, pagination: &Pagination) -> PaymentRepositoryResult<Vec<PaymentAggregate>> {
         let offset = pagination.page * pagination.limit;
         let limit = pagination.limit;
-        let status_str = format!("{:?}", status); // Use debug repr as stored
-        
-        // Note: status stored might be "Pending", "Completed" etc. 
-        // We need to match what save() does. save() uses format!("{:?}", status).
+        let status_str = status.name();
         
         let rows = sqlx::query(
             "SELECT * FROM payments WHERE status = $1 ORDER BY created_at DESC LIMIT $2 OFFSET $3")
@@ -434,7 +461,7 @@ impl PaymentRepository for PostgreSQLPaymentRepository {
     }
 
     async fn count_by_status(&self, status: &PaymentStatus) -> PaymentRepositoryResult<u64> {
-        let status_str = format!("{:?}", status);
+        let status_str = status.name();
         let row = sqlx::query!(
             "SELECT COUNT(*) as count FROM payments WHERE status = $1",
             status_str
@@ -676,16 +703,22 @@ impl PostgreSQLPaymentRepository {
         ).map_err(|e| AppError::SerializationError(e.to_string()))?;
         
         let status_str: String = row.try_get("status").map_err(AppError::DatabaseError)?;
-        let status = match status_str.as_str() {
-            "Pending" => PaymentStatus::Pending,
-            "Processing" => PaymentStatus::Processing,
-            "Completed" => PaymentStatus::Completed,
-            "Failed" => PaymentStatus::Failed { error_code: "DB".into(), error_message: "From DB".into() }, // Need to parse from status_details?
-            "Cancelled" => PaymentStatus::Cancelled { reason: "From DB".into() },
-            "OnHold" => PaymentStatus::OnHold,
-            "Refunding" => PaymentStatus::Refunding,
-            "Refunded" => PaymentStatus::Refunded { refund_amount: 0.0, refund_date: Utc::now() },
-            _ => PaymentStatus::Pending,
+        // status_details conserva los campos de la variante (importes de PartiallyPaid, etc.)
+        let status_details: Option<serde_json::Value> = row.try_get("status_details").map_err(AppError::DatabaseError)?;
+        let status = match status_details.and_then(|details| serde_json::from_value::<PaymentStatus>(details).ok()) {
+            Some(status) if status.name() == status_str => status,
+            _ => match status_str.as_str() {
+                "Pending" => PaymentStatus::Pending,
+                "Processing" => PaymentStatus::Processing,
+                "Completed" => PaymentStatus::Completed,
+                "Failed" => PaymentStatus::Failed { error_code: "DB".into(), error_message: "From DB".into() },
+                "Cancelled" => PaymentStatus::Cancelled { reason: "From DB".into() },
+                "OnHold" => PaymentStatus::OnHold,
+                "Refunding" => PaymentStatus::Refunding,
+                "Refunded" => PaymentStatus::Refunded { refund_amount: 0.0, refund_date: Utc::now() },
+                "PartiallyPaid" => PaymentStatus::PartiallyPaid { received_amount: 0.0, shortfall: 0.0 },
+                _ => PaymentStatus::Pending,
+            },
         };
         
         let blockchain_hash = row.try_get::<Option<String>, _>("blockchain_hash").map_err(AppError::DatabaseError)?
@@ -0,0 +1,58 @@
+//! PostgreSQL implementation of PaymentStatisticsRepository
+
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::domain::{
+    repository::{PaymentRepositoryResult, PaymentStatisticsRepository},
+    statistics::{PaymentStatisticsRow, StatisticsPeriod},
+};
+
+pub struct PostgresPaymentStatisticsRepository {
+    pool: PgPool,
+}
+
+impl PostgresPaymentStatisticsRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PaymentStatisticsRepository for PostgresPaymentStatisticsRepository {
+    async fn aggregate(&self, period: &StatisticsPeriod, payee_id: Option<Uuid>) -> PaymentRepositoryResult<Vec<PaymentStatisticsRow>> {
+        let rows = sqlx::query(
+            r#"SELECT (created_at AT TIME ZONE 'UTC')::date AS day,
+                      status,
+                      purpose_type,
+                      amount_currency,
+                      COUNT(*) AS payment_count,
+                      COALESCE(SUM(amount_value), 0)::float8 AS volume
+               FROM payments
+               WHERE created_at >= $1 AND created_at < $2
+                 AND ($3::uuid IS NULL OR payee_id = $3)
+               GROUP BY day, status, purpose_type, amount_currency
+               ORDER BY day"#,
+        )
+        .bind(period.start)
+        .bind(period.end)
+        .bind(payee_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to aggregate payment statistics: {}", e)))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PaymentStatisticsRow {
+                day: row.get("day"),
+                status: row.get("status"),
+                payment_type: row.get("purpose_type"),
+                currency: row.get("amount_currency"),
+                count: row.get::<i64, _>("payment_count") as u64,
+                volume: row.get("volume"),
+            })
+            .collect())
+    }
+}
@@ -1,9 +1,16 @@
 use async_trait::async_trait;
-use sqlx::PgPool;
+use sqlx::{postgres::PgRow, PgPool, Row};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use crate::shared::domain::errors::AppError;
-use crate::bounded_contexts::payment::domain::value_objects::Amount;
+use crate::bounded_contexts::payment::domain::{
+    refunds::RefundTransaction,
+    repository::{PaymentRepositoryResult, RefundTransactionRepository},
+    value_objects::{Amount, Currency},
+};
+
+const REFUND_COLUMNS: &str = r#"id, payment_id, amount::float8 AS amount, currency, reason, status, gateway,
+       gateway_refund_id, initiated_by, failure_reason, created_at, updated_at, completed_at"#;
 
 /// Refund entity (simplified for repository usage)
 pub struct Refund {
@@ -32,6 +39,38 @@ impl PostgresRefundRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+
+    pub(crate) fn parse_currency(code: &str) -> Currency {
+        match code {
+            "EUR" => Currency::EUR,
+            "GBP" => Currency::GBP,
+            "ETH" => Currency::ETH,
+            "SOL" => Currency::SOL,
+            "USDC" => Currency::USDC,
+            "VIBES" => Currency::VIBES,
+            _ => Currency::USD,
+        }
+    }
+
+    fn row_to_transaction(row: PgRow) -> Result<RefundTransaction, AppError> {
+        let currency: String = row.get("currency");
+        let status: String = row.get("status");
+
+        Ok(RefundTransaction {
+            id: row.get("id"),
+            payment_id: row.get("payment_id"),
+            amount: Amount::new(row.get("amount"), Self::parse_currency(&currency))?,
+            reason: row.get("reason"),
+            status: status.parse().map_err(AppError::SerializationError)?,
+            gateway: row.get::<Option<String>, _>("gateway").unwrap_or_default(),
+            gateway_refund_id: row.get("gateway_refund_id"),
+            initiated_by: row.get::<Option<Uuid>, _>("initiated_by").unwrap_or_default(),
+            failure_reason: row.get("failure_reason"),
+            created_at: row.get::<Option<DateTime<Utc>>, _>("created_at").unwrap_or_else(Utc::now),
+            updated_at: row.get::<Option<DateTime<Utc>>, _>("updated_at").unwrap_or_else(Utc::now),
+            completed_at: row.get("completed_at"),
+        })
+    }
 }
 
 #[async_trait]
@@ -98,3 +137,99 @@ impl RefundRepository for PostgresRefundRepository {
         Ok(refunds)
     }
 }
+
+#[async_trait]
+impl RefundTransactionRepository for PostgresRefundRepository {
+    async fn create(&self, refund: &RefundTransaction, payment_amount: f64) -> PaymentRepositoryResult<()> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        // Serializa los reembolsos del mismo pago: el segundo espera al primero
+        // y ve su importe ya reservado
+        sqlx::query("SELECT id FROM payments WHERE id = $1 FOR UPDATE")
+            .bind(refund.payment_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let result = sqlx::query(
+            r#"INSERT INTO refunds (
+                   id, payment_id, amount, currency, reason, status, gateway,
+                   gateway_refund_id, initiated_by, created_at, updated_at
+               )
+               SELECT $1, $2, $3::float8, $4, $5, $6, $7, $8, $9, $10, $11
+               WHERE (
+                   SELECT COALESCE(SUM(amount), 0)::float8 FROM refunds
+                   WHERE payment_id = $2 AND status <> 'failed'
+               ) + $3::float8 <= $12::float8 + 0.000001"#,
+        )
+        .bind(refund.id)
+        .bind(refund.payment_id)
+        .bind(refund.amount.value())
+        .bind(format!("{:?}", refund.amount.currency()))
+        .bind(&refund.reason)
+        .bind(refund.status.to_string())
+        .bind(&refund.gateway)
+        .bind(&refund.gateway_refund_id)
+        .bind(refund.initiated_by)
+        .bind(refund.created_at)
+        .bind(refund.updated_at)
+        .bind(payment_amount)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to create refund: {}", e)))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::ConcurrencyConflict(format!(
+                "Payment {} was refunded concurrently",
+                refund.payment_id
+            )));
+        }
+
+        tx.commit().await.map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn update(&self, refund: &RefundTransaction) -> PaymentRepositoryResult<()> {
+        sqlx::query(
+            r#"UPDATE refunds
+               SET status = $2, gateway_refund_id = $3, failure_reason = $4,
+                   completed_at = $5, updated_at = $6
+               WHERE id = $1"#,
+        )
+        .bind(refund.id)
+        .bind(refund.status.to_string())
+        .bind(&refund.gateway_refund_id)
+        .bind(&refund.failure_reason)
+        .bind(refund.completed_at)
+        .bind(refund.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to update refund: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> PaymentRepositoryResult<Option<RefundTransaction>> {
+        let row = sqlx::query(&format!("SELECT {} FROM refunds WHERE id = $1", REFUND_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        row.map(Self::row_to_transaction).transpose()
+    }
+
+    async fn find_by_payment(&self, payment_id: Uuid) -> PaymentRepositoryResult<Vec<RefundTransaction>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM refunds WHERE payment_id = $1 ORDER BY created_at",
+            REFUND_COLUMNS
+        ))
+        .bind(payment_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(Self::row_to_transaction).collect()
+    }
+}
@@ -0,0 +1,251 @@
+//! PostgreSQL implementation of RoyaltyAdvanceRepository
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::domain::{
+    repository::{PaymentRepositoryResult, RoyaltyAdvanceRepository},
+    royalty_advances::{AdvanceRepayment, RoyaltyAdvance},
+    value_objects::Currency,
+};
+
+use super::PostgresRefundRepository;
+
+/// Código SQLSTATE de `unique_violation` (índice `idx_royalty_advances_one_active`)
+const UNIQUE_VIOLATION: &str = "23505";
+
+const ADVANCE_COLUMNS: &str = r#"id, artist_id, advance_amount, repayment_rate, outstanding_balance, currency,
+       status, created_at, repaid_at"#;
+
+pub struct PostgresRoyaltyAdvanceRepository {
+    pool: PgPool,
+}
+
+impl PostgresRoyaltyAdvanceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_advance(row: PgRow) -> Result<RoyaltyAdvance, AppError> {
+        let currency: String = row.get("currency");
+        let status: String = row.get("status");
+
+        Ok(RoyaltyAdvance {
+            id: row.get("id"),
+            artist_id: row.get("artist_id"),
+            advance_amount: row.get("advance_amount"),
+            repayment_rate: row.get("repayment_rate"),
+            outstanding_balance: row.get("outstanding_balance"),
+            currency: PostgresRefundRepository::parse_currency(&currency),
+            status: status.parse().map_err(AppError::SerializationError)?,
+            created_at: row.get("created_at"),
+            repaid_at: row.get("repaid_at"),
+        })
+    }
+
+    fn row_to_repayment(row: PgRow) -> AdvanceRepayment {
+        AdvanceRepayment {
+            advance_id: row.get("advance_id"),
+            source_id: row.get("source_id"),
+            amount: row.get("amount"),
+            created_at: row.get("created_at"),
+        }
+    }
+}
+
+#[async_trait]
+impl RoyaltyAdvanceRepository for PostgresRoyaltyAdvanceRepository {
+    async fn create(&self, advance: &RoyaltyAdvance) -> PaymentRepositoryResult<()> {
+        let currency = format!("{:?}", advance.currency);
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        sqlx::query(
+            r#"INSERT INTO royalty_advances
+                   (id, artist_id, advance_amount, repayment_rate, outstanding_balance, currency, status, created_at)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"#,
+        )
+        .bind(advance.id)
+        .bind(advance.artist_id)
+        .bind(advance.advance_amount)
+        .bind(advance.repayment_rate)
+        .bind(advance.outstanding_balance)
+        .bind(&currency)
+        .bind(advance.status.to_string())
+        .bind(advance.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.code().as_deref() == Some(UNIQUE_VIOLATION) => AppError::ConflictError(
+                format!("Artist {} is still repaying an advance", advance.artist_id),
+            ),
+            other => AppError::DatabaseError(format!("Failed to create royalty advance: {}", other)),
+        })?;
+
+        // El adelanto se cobra como cualquier otro saldo del artista
+        sqlx::query(
+            r#"INSERT INTO artist_balance_entries (id, artist_id, source_id, amount, currency, status, created_at)
+               VALUES ($1, $2, $3, $4, $5, 'available', $6)"#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(advance.artist_id)
+        .bind(advance.id)
+        .bind(advance.advance_amount)
+        .bind(&currency)
+        .bind(advance.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to credit royalty advance: {}", e)))?;
+
+        tx.commit().await.map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn find_active(&self, artist_id: Uuid) -> PaymentRepositoryResult<Option<RoyaltyAdvance>> {
+        let row = sqlx::query(&format!(
+            "SELECT {} FROM royalty_advances WHERE artist_id = $1 AND status = 'active'",
+            ADVANCE_COLUMNS
+        ))
+        .bind(artist_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        row.map(Self::row_to_advance).transpose()
+    }
+
+    async fn find_by_artist(&self, artist_id: Uuid) -> PaymentRepositoryResult<Vec<RoyaltyAdvance>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM royalty_advances WHERE artist_id = $1 ORDER BY created_at DESC",
+            ADVANCE_COLUMNS
+        ))
+        .bind(artist_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(Self::row_to_advance).collect()
+    }
+
+    async fn monthly_royalties(
+        &self,
+        artist_id: Uuid,
+        currency: &Currency,
+        since: DateTime<Utc>,
+        until: DateTime<Utc>,
+    ) -> PaymentRepositoryResult<Vec<(DateTime<Utc>, f64)>> {
+        // Lo retenido para amortizar también son royalties del artista
+        let rows = sqlx::query(
+            r#"SELECT DATE_TRUNC('month', e.created_at) AS month,
+                      SUM(e.amount + COALESCE(r.amount, 0))::float8 AS total
+               FROM artist_balance_entries e
+               LEFT JOIN royalty_advance_repayments r ON r.source_id = e.source_id
+               WHERE e.artist_id = $1 AND e.currency = $2
+                 AND e.created_at >= $3 AND e.created_at < $4
+                 AND NOT EXISTS (SELECT 1 FROM royalty_advances a WHERE a.id = e.source_id)
+               GROUP BY 1
+               ORDER BY 1"#,
+        )
+        .bind(artist_id)
+        .bind(format!("{:?}", currency))
+        .bind(since)
+        .bind(until)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load royalty history: {}", e)))?;
+
+        Ok(rows.into_iter().map(|row| (row.get("month"), row.get("total"))).collect())
+    }
+
+    async fn find_repayment(&self, source_id: Uuid) -> PaymentRepositoryResult<Option<AdvanceRepayment>> {
+        let row = sqlx::query(
+            "SELECT advance_id, source_id, amount, created_at FROM royalty_advance_repayments WHERE source_id = $1",
+        )
+        .bind(source_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        Ok(row.map(Self::row_to_repayment))
+    }
+
+    async fn record_repayment(
+        &self,
+        advance: &RoyaltyAdvance,
+        repayment: &AdvanceRepayment,
+    ) -> PaymentRepositoryResult<AdvanceRepayment> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let inserted = sqlx::query(
+            r#"INSERT INTO royalty_advance_repayments (source_id, advance_id, amount, created_at)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT (source_id) DO NOTHING"#,
+        )
+        .bind(repayment.source_id)
+        .bind(repayment.advance_id)
+        .bind(repayment.amount)
+        .bind(repayment.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to record advance repayment: {}", e)))?;
+
+        if inserted.rows_affected() == 0 {
+            drop(tx);
+            return self.find_repayment(repayment.source_id).await?.ok_or_else(|| {
+                AppError::ConcurrencyConflict(format!("Repayment from {} was reverted concurrently", repayment.source_id))
+            });
+        }
+
+        let previous_balance: Decimal = advance.outstanding_balance + repayment.amount;
+        let updated = sqlx::query(
+            r#"UPDATE royalty_advances
+               SET outstanding_balance = $2, status = $3, repaid_at = $4
+               WHERE id = $1 AND status = 'active' AND outstanding_balance = $5"#,
+        )
+        .bind(advance.id)
+        .bind(advance.outstanding_balance)
+        .bind(advance.status.to_string())
+        .bind(advance.repaid_at)
+        .bind(previous_balance)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if updated.rows_affected() == 0 {
+            return Err(AppError::ConcurrencyConflict(format!(
+                "Royalty advance {} changed while withholding a repayment",
+                advance.id
+            )));
+        }
+
+        tx.commit().await.map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(repayment.clone())
+    }
+
+    async fn revert_repayments(&self, source_ids: &[Uuid]) -> PaymentRepositoryResult<()> {
+        sqlx::query(
+            r#"WITH removed AS (
+                   DELETE FROM royalty_advance_repayments WHERE source_id = ANY($1)
+                   RETURNING advance_id, amount
+               ), totals AS (
+                   SELECT advance_id, SUM(amount) AS amount FROM removed GROUP BY advance_id
+               )
+               UPDATE royalty_advances a
+               SET outstanding_balance = a.outstanding_balance + t.amount,
+                   status = CASE WHEN a.status = 'repaid' THEN 'active' ELSE a.status END,
+                   repaid_at = CASE WHEN a.status = 'repaid' THEN NULL ELSE a.repaid_at END
+               FROM totals t
+               WHERE a.id = t.advance_id"#,
+        )
+        .bind(source_ids)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to revert advance repayments: {}", e)))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,117 @@
+//! PostgreSQL implementation of RoyaltyReversalRepository
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::domain::{
+    refunds::{PayoutRefundBasis, RoyaltyReversal},
+    repository::{PaymentRepositoryResult, RoyaltyReversalRepository},
+};
+
+use super::PostgresRefundRepository;
+
+pub struct PostgresRoyaltyReversalRepository {
+    pool: PgPool,
+}
+
+impl PostgresRoyaltyReversalRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RoyaltyReversalRepository for PostgresRoyaltyReversalRepository {
+    async fn find_payouts_for_revenue(&self, song_id: Uuid, earned_at: DateTime<Utc>) -> PaymentRepositoryResult<Vec<PayoutRefundBasis>> {
+        // El ingreso cae en la ejecución activa cuyo periodo [inicio, fin) lo contiene
+        let rows = sqlx::query(
+            r#"SELECT p.id, p.recipient_id, p.recipient_type, p.currency,
+                      r.total_revenue::float8 AS run_revenue,
+                      p.amount::float8 AS amount,
+                      COALESCE(SUM(v.amount), 0)::float8 AS reversed_amount
+               FROM royalty_payouts p
+               JOIN royalty_distribution_runs r ON r.id = p.run_id
+               LEFT JOIN royalty_reversals v ON v.royalty_payout_id = p.id
+               WHERE r.song_id = $1 AND r.status = 'active'
+                 AND r.period_start <= $2 AND r.period_end > $2
+                 AND p.status <> 'cancelled'
+               GROUP BY p.id, r.total_revenue"#,
+        )
+        .bind(song_id)
+        .bind(earned_at)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter()
+            .map(|row| {
+                let recipient_type: String = row.get("recipient_type");
+                let currency: String = row.get("currency");
+                Ok(PayoutRefundBasis {
+                    royalty_payout_id: row.get("id"),
+                    recipient_id: row.get("recipient_id"),
+                    recipient_type: recipient_type.parse().map_err(AppError::SerializationError)?,
+                    run_revenue: row.get("run_revenue"),
+                    amount: row.get("amount"),
+                    currency: PostgresRefundRepository::parse_currency(&currency),
+                    reversed_amount: row.get("reversed_amount"),
+                })
+            })
+            .collect()
+    }
+
+    async fn save_all(&self, reversals: &[RoyaltyReversal]) -> PaymentRepositoryResult<()> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        for reversal in reversals {
+            let inserted = sqlx::query(
+                r#"INSERT INTO royalty_reversals (
+                       id, refund_id, royalty_payout_id, recipient_id, recipient_type,
+                       amount, currency, created_at
+                   ) VALUES ($1, $2, $3, $4, $5, $6::float8, $7, $8)
+                   ON CONFLICT (refund_id, royalty_payout_id) DO NOTHING"#,
+            )
+            .bind(reversal.id)
+            .bind(reversal.refund_id)
+            .bind(reversal.royalty_payout_id)
+            .bind(reversal.recipient_id)
+            .bind(reversal.recipient_type.to_string())
+            .bind(reversal.amount)
+            .bind(format!("{:?}", reversal.currency))
+            .bind(reversal.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to save royalty reversal: {}", e)))?;
+
+            // Ya revertido por este reembolso: el débito también existe
+            if inserted.rows_affected() == 0 {
+                continue;
+            }
+
+            let Some(debit) = reversal.balance_debit() else {
+                continue;
+            };
+            sqlx::query(
+                r#"INSERT INTO artist_balance_entries (id, artist_id, source_id, amount, currency, status, created_at)
+                   VALUES ($1, $2, $3, $4::float8, $5, $6, $7)"#,
+            )
+            .bind(debit.id)
+            .bind(debit.artist_id)
+            .bind(debit.source_id)
+            .bind(debit.amount)
+            .bind(format!("{:?}", debit.currency))
+            .bind(debit.status.to_string())
+            .bind(debit.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to debit artist balance: {}", e)))?;
+        }
+
+        tx.commit().await.map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,242 @@
+//! PostgreSQL implementation of RoyaltyRunRepository
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, PgPool, Row};
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::domain::{
+    repository::{PaymentRepositoryResult, RoyaltyRunRepository},
+    royalty_runs::{RoyaltyDistributionRun, RoyaltyPayout, RoyaltyPayoutStatus, RoyaltySplitPlan},
+    value_objects::Amount,
+};
+
+use super::PostgresRefundRepository;
+
+/// Código SQLSTATE de `exclusion_violation` (constraint `royalty_runs_no_overlap`)
+const EXCLUSION_VIOLATION: &str = "23P01";
+
+const RUN_COLUMNS: &str = r#"id, song_id, period_start, period_end, total_revenue::float8 AS total_revenue,
+       currency, original_revenue::float8 AS original_revenue, original_currency, exchange_rate,
+       splits, status, created_by, created_at, voided_by, voided_at, void_reason"#;
+
+const PAYOUT_COLUMNS: &str = r#"id, run_id, song_id, recipient_id, recipient_type, percentage::float8 AS percentage,
+       amount::float8 AS amount, currency, status, created_at"#;
+
+pub struct PostgresRoyaltyRunRepository {
+    pool: PgPool,
+}
+
+impl PostgresRoyaltyRunRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_run(row: PgRow) -> Result<RoyaltyDistributionRun, AppError> {
+        let currency: String = row.get("currency");
+        let status: String = row.get("status");
+        let plan: RoyaltySplitPlan = serde_json::from_value(row.get("splits"))
+            .map_err(|e| AppError::SerializationError(e.to_string()))?;
+        let original_revenue = match (
+            row.get::<Option<f64>, _>("original_revenue"),
+            row.get::<Option<String>, _>("original_currency"),
+        ) {
+            (Some(value), Some(currency)) => Some(Amount::new(value, PostgresRefundRepository::parse_currency(&currency))?),
+            _ => None,
+        };
+
+        Ok(RoyaltyDistributionRun {
+            id: row.get("id"),
+            song_id: row.get("song_id"),
+            period_start: row.get("period_start"),
+            period_end: row.get("period_end"),
+            total_revenue: Amount::new(row.get("total_revenue"), PostgresRefundRepository::parse_currency(&currency))?,
+            original_revenue,
+            exchange_rate: row.get("exchange_rate"),
+            plan,
+            status: status.parse().map_err(AppError::SerializationError)?,
+            created_by: row.get("created_by"),
+            created_at: row.get("created_at"),
+            voided_by: row.get("voided_by"),
+            voided_at: row.get("voided_at"),
+            void_reason: row.get("void_reason"),
+        })
+    }
+
+    fn row_to_payout(row: PgRow) -> Result<RoyaltyPayout, AppError> {
+        let currency: String = row.get("currency");
+        let recipient_type: String = row.get("recipient_type");
+        let status: String = row.get("status");
+
+        Ok(RoyaltyPayout {
+            id: row.get("id"),
+            run_id: row.get("run_id"),
+            song_id: row.get("song_id"),
+            recipient_id: row.get("recipient_id"),
+            recipient_type: recipient_type.parse().map_err(AppError::SerializationError)?,
+            percentage: row.get("percentage"),
+            amount: Amount::new(row.get("amount"), PostgresRefundRepository::parse_currency(&currency))?,
+            status: status.parse().map_err(AppError::SerializationError)?,
+            created_at: row.get("created_at"),
+        })
+    }
+}
+
+#[async_trait]
+impl RoyaltyRunRepository for PostgresRoyaltyRunRepository {
+    async fn create(&self, run: &RoyaltyDistributionRun, payouts: &[RoyaltyPayout]) -> PaymentRepositoryResult<()> {
+        let splits = serde_json::to_value(&run.plan)
+            .map_err(|e| AppError::SerializationError(e.to_string()))?;
+
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        sqlx::query(
+            r#"INSERT INTO royalty_distribution_runs (
+                   id, song_id, period_start, period_end, total_revenue, currency,
+                   original_revenue, original_currency, exchange_rate,
+                   splits, status, created_by, created_at
+               ) VALUES ($1, $2, $3, $4, $5::float8, $6, $7::float8, $8, $9, $10, $11, $12, $13)"#,
+        )
+        .bind(run.id)
+        .bind(run.song_id)
+        .bind(run.period_start)
+        .bind(run.period_end)
+        .bind(run.total_revenue.value())
+        .bind(format!("{:?}", run.total_revenue.currency()))
+        .bind(run.original_revenue.as_ref().map(|a| a.value()))
+        .bind(run.original_revenue.as_ref().map(|a| format!("{:?}", a.currency())))
+        .bind(run.exchange_rate)
+        .bind(splits)
+        .bind(run.status.to_string())
+        .bind(run.created_by)
+        .bind(run.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            // Otra ejecución solapada entró entre la comprobación y el insert
+            if e.as_database_error().and_then(|d| d.code()).as_deref() == Some(EXCLUSION_VIOLATION) {
+                AppError::ConcurrencyConflict(format!(
+                    "Song {} was distributed concurrently for an overlapping period",
+                    run.song_id
+                ))
+            } else {
+                AppError::DatabaseError(format!("Failed to create royalty run: {}", e))
+            }
+        })?;
+
+        for payout in payouts {
+            sqlx::query(
+                r#"INSERT INTO royalty_payouts (
+                       id, run_id, song_id, recipient_id, recipient_type, percentage,
+                       amount, currency, status, created_at, updated_at
+                   ) VALUES ($1, $2, $3, $4, $5, $6::float8, $7::float8, $8, $9, $10, $10)"#,
+            )
+            .bind(payout.id)
+            .bind(payout.run_id)
+            .bind(payout.song_id)
+            .bind(payout.recipient_id)
+            .bind(payout.recipient_type.to_string())
+            .bind(payout.percentage)
+            .bind(payout.amount.value())
+            .bind(format!("{:?}", payout.amount.currency()))
+            .bind(payout.status.to_string())
+            .bind(payout.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to create royalty payout: {}", e)))?;
+        }
+
+        tx.commit().await.map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> PaymentRepositoryResult<Option<RoyaltyDistributionRun>> {
+        let row = sqlx::query(&format!("SELECT {} FROM royalty_distribution_runs WHERE id = $1", RUN_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        row.map(Self::row_to_run).transpose()
+    }
+
+    async fn find_overlapping(&self, song_id: Uuid, start: DateTime<Utc>, end: DateTime<Utc>) -> PaymentRepositoryResult<Vec<RoyaltyDistributionRun>> {
+        let rows = sqlx::query(&format!(
+            r#"SELECT {} FROM royalty_distribution_runs
+               WHERE song_id = $1 AND period_start < $3 AND period_end > $2
+               ORDER BY period_start"#,
+            RUN_COLUMNS
+        ))
+        .bind(song_id)
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(Self::row_to_run).collect()
+    }
+
+    async fn find_payouts(&self, run_id: Uuid) -> PaymentRepositoryResult<Vec<RoyaltyPayout>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM royalty_payouts WHERE run_id = $1 ORDER BY percentage DESC",
+            PAYOUT_COLUMNS
+        ))
+        .bind(run_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        rows.into_iter().map(Self::row_to_payout).collect()
+    }
+
+    async fn update_payout_status(&self, payout_ids: &[Uuid], status: RoyaltyPayoutStatus) -> PaymentRepositoryResult<()> {
+        sqlx::query("UPDATE royalty_payouts SET status = $2, updated_at = NOW() WHERE id = ANY($1)")
+            .bind(payout_ids)
+            .bind(status.to_string())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn void(&self, run: &RoyaltyDistributionRun) -> PaymentRepositoryResult<()> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let result = sqlx::query(
+            r#"UPDATE royalty_distribution_runs
+               SET status = $2, voided_by = $3, voided_at = $4, void_reason = $5
+               WHERE id = $1 AND status = 'active'"#,
+        )
+        .bind(run.id)
+        .bind(run.status.to_string())
+        .bind(run.voided_by)
+        .bind(run.voided_at)
+        .bind(&run.void_reason)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::ConcurrencyConflict(format!(
+                "Royalty run {} was voided concurrently",
+                run.id
+            )));
+        }
+
+        sqlx::query(
+            r#"UPDATE royalty_payouts SET status = 'cancelled', updated_at = NOW()
+               WHERE run_id = $1 AND status IN ('pending', 'scheduled')"#,
+        )
+        .bind(run.id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        tx.commit().await.map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+}
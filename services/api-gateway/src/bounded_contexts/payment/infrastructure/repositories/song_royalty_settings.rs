@@ -0,0 +1,57 @@
+//! Royalty settings of songs, read from the music context's tables
+
+use async_trait::async_trait;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::application::royalty_distribution_service::SongRoyaltySettingsProvider;
+use crate::bounded_contexts::payment::domain::royalty_runs::{SongRoyaltyCredit, SongRoyaltySettings};
+
+pub struct PostgresSongRoyaltySettings {
+    pool: PgPool,
+}
+
+impl PostgresSongRoyaltySettings {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl SongRoyaltySettingsProvider for PostgresSongRoyaltySettings {
+    async fn royalty_settings(&self, song_id: Uuid) -> Result<Option<SongRoyaltySettings>, AppError> {
+        // NULL = default de la columna (10%)
+        let song = sqlx::query(
+            r#"SELECT artist_id, COALESCE(royalty_percentage, 10.00)::float8 AS royalty_percentage
+               FROM songs WHERE id = $1"#,
+        )
+        .bind(song_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let Some(song) = song else {
+            return Ok(None);
+        };
+
+        let credits = sqlx::query("SELECT contributor_id, royalty_share FROM song_credits WHERE song_id = $1")
+            .bind(song_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?
+            .into_iter()
+            .map(|row| SongRoyaltyCredit {
+                contributor_id: row.get("contributor_id"),
+                royalty_share: row.get("royalty_share"),
+            })
+            .collect();
+
+        Ok(Some(SongRoyaltySettings {
+            song_id,
+            artist_id: song.get("artist_id"),
+            royalty_percentage: song.get("royalty_percentage"),
+            credits,
+        }))
+    }
+}
@@ -0,0 +1,155 @@
+//! PostgreSQL implementation of WebhookRepository
+
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::domain::{
+    repository::{PaymentRepositoryResult, WebhookRepository},
+    webhooks::{Webhook, WebhookDelivery, MAX_CONSECUTIVE_FAILURES},
+};
+
+const WEBHOOK_COLUMNS: &str = r#"id, endpoint_url, secret, subscribed_events, is_active,
+       consecutive_failures, created_by, created_at"#;
+
+pub struct PostgresWebhookRepository {
+    pool: PgPool,
+}
+
+impl PostgresWebhookRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_webhook(row: PgRow) -> Webhook {
+        Webhook {
+            id: row.get("id"),
+            endpoint_url: row.get("endpoint_url"),
+            secret: row.get("secret"),
+            subscribed_events: row.get("subscribed_events"),
+            is_active: row.get("is_active"),
+            consecutive_failures: row.get::<i32, _>("consecutive_failures").max(0) as u32,
+            created_by: row.get("created_by"),
+            created_at: row.get("created_at"),
+        }
+    }
+
+    fn row_to_delivery(row: PgRow) -> WebhookDelivery {
+        WebhookDelivery {
+            id: row.get("id"),
+            webhook_id: row.get("webhook_id"),
+            event_id: row.get("event_id"),
+            event_type: row.get("event_type"),
+            attempt: row.get::<i32, _>("attempt").max(1) as u32,
+            status_code: row.get::<Option<i32>, _>("status_code").map(|code| code as u16),
+            succeeded: row.get("succeeded"),
+            error: row.get("error"),
+            attempted_at: row.get("attempted_at"),
+        }
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for PostgresWebhookRepository {
+    async fn create(&self, webhook: &Webhook) -> PaymentRepositoryResult<()> {
+        sqlx::query(
+            r#"INSERT INTO webhooks (
+                   id, endpoint_url, secret, subscribed_events, is_active, consecutive_failures,
+                   created_by, created_at, updated_at
+               ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $8)"#,
+        )
+        .bind(webhook.id)
+        .bind(&webhook.endpoint_url)
+        .bind(&webhook.secret)
+        .bind(&webhook.subscribed_events)
+        .bind(webhook.is_active)
+        .bind(webhook.consecutive_failures as i32)
+        .bind(webhook.created_by)
+        .bind(webhook.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to create webhook: {}", e)))?;
+        Ok(())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> PaymentRepositoryResult<Option<Webhook>> {
+        let row = sqlx::query(&format!("SELECT {} FROM webhooks WHERE id = $1", WEBHOOK_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(row.map(Self::row_to_webhook))
+    }
+
+    async fn find_subscribed(&self, event_type: &str) -> PaymentRepositoryResult<Vec<Webhook>> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM webhooks WHERE is_active AND $1 = ANY(subscribed_events)",
+            WEBHOOK_COLUMNS
+        ))
+        .bind(event_type)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(rows.into_iter().map(Self::row_to_webhook).collect())
+    }
+
+    async fn record_attempt(&self, delivery: &WebhookDelivery) -> PaymentRepositoryResult<()> {
+        sqlx::query(
+            r#"INSERT INTO webhook_deliveries (
+                   id, webhook_id, event_id, event_type, attempt, status_code, succeeded, error, attempted_at
+               ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#,
+        )
+        .bind(delivery.id)
+        .bind(delivery.webhook_id)
+        .bind(delivery.event_id)
+        .bind(&delivery.event_type)
+        .bind(delivery.attempt as i32)
+        .bind(delivery.status_code.map(i32::from))
+        .bind(delivery.succeeded)
+        .bind(&delivery.error)
+        .bind(delivery.attempted_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to record webhook delivery: {}", e)))?;
+        Ok(())
+    }
+
+    async fn record_outcome(&self, webhook_id: Uuid, succeeded: bool) -> PaymentRepositoryResult<Webhook> {
+        // En una sola sentencia: entregas concurrentes no pierden fallos de la racha
+        let row = sqlx::query(&format!(
+            r#"UPDATE webhooks
+               SET consecutive_failures = CASE WHEN $2 THEN 0 ELSE consecutive_failures + 1 END,
+                   is_active = is_active AND ($2 OR consecutive_failures + 1 < $3),
+                   updated_at = NOW()
+               WHERE id = $1
+               RETURNING {}"#,
+            WEBHOOK_COLUMNS
+        ))
+        .bind(webhook_id)
+        .bind(succeeded)
+        .bind(MAX_CONSECUTIVE_FAILURES as i32)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        row.map(Self::row_to_webhook)
+            .ok_or_else(|| AppError::NotFound(format!("Webhook {} not found", webhook_id)))
+    }
+
+    async fn find_deliveries(&self, webhook_id: Uuid, limit: u32) -> PaymentRepositoryResult<Vec<WebhookDelivery>> {
+        let rows = sqlx::query(
+            r#"SELECT id, webhook_id, event_id, event_type, attempt, status_code, succeeded, error, attempted_at
+               FROM webhook_deliveries
+               WHERE webhook_id = $1
+               ORDER BY attempted_at DESC, attempt DESC
+               LIMIT $2"#,
+        )
+        .bind(webhook_id)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(rows.into_iter().map(Self::row_to_delivery).collect())
+    }
+}
@@ -1,3 +1,5 @@
 pub mod payment_processing_service;
+pub mod webhook_delivery_service;
 
 pub use payment_processing_service::PaymentProcessingServiceImpl;
+pub use webhook_delivery_service::WebhookDeliveryService;
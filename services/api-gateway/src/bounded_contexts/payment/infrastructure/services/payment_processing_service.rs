@@ -10,6 +10,7 @@ use crate::bounded_contexts::payment::domain::{
 };
 use crate::bounded_contexts::payment::infrastructure::gateways::{
     gateway_router::{MultiGatewayRouter, GatewayRoutingResult},
+    crypto_gateway::AWAITING_DEPOSIT_CODE,
 };
 use crate::shared::domain::errors::AppError;
 use crate::bounded_contexts::payment::infrastructure::repositories::{
@@ -46,6 +47,9 @@ impl PaymentProcessingService for PaymentProcessingServiceImpl {
             gateway_response: Some(gateway_result.routing_reason),
             processing_time_ms: gateway_result.gateway_result.as_ref().map(|r| r.processing_time_ms).unwrap_or(0),
             fees_charged: gateway_result.gateway_result.as_ref().map(|r| r.fees_charged.clone()).unwrap_or_else(|| Amount::new(0.0, payment.payment().amount().currency().clone()).unwrap()),
+            awaiting_confirmation: gateway_result.gateway_result.as_ref()
+                .map(|r| r.gateway_response_code == AWAITING_DEPOSIT_CODE)
+                .unwrap_or(false),
         })
     }
     
@@ -0,0 +1,316 @@
+//! Delivery of payment events to the webhooks of third-party integrations
+//!
+//! Every HTTP attempt is stored in `webhook_deliveries`. A delivery that still
+//! fails after its retries counts against the webhook's failure streak.
+
+use chrono::Utc;
+use reqwest::{redirect, Client};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::payment::domain::{
+    aggregates::PaymentAggregate,
+    repository::WebhookRepository,
+    webhooks::{
+        sign_webhook_payload, PaymentWebhookNotifier, Webhook, WebhookDelivery, WebhookPayload,
+        MAX_DELIVERY_ATTEMPTS, WEBHOOK_SIGNATURE_HEADER,
+    },
+};
+
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_secs(2);
+
+#[derive(Clone)]
+pub struct WebhookDeliveryService {
+    repository: Arc<dyn WebhookRepository>,
+    client: Client,
+    /// Wait before the 2nd attempt; it doubles for each following one
+    retry_backoff: Duration,
+}
+
+impl WebhookDeliveryService {
+    pub fn new(repository: Arc<dyn WebhookRepository>) -> Self {
+        // Sin redirecciones: solo se llama a la URL que registró el admin
+        let client = Client::builder()
+            .timeout(DELIVERY_TIMEOUT)
+            .redirect(redirect::Policy::none())
+            .user_agent("VibeStream-Webhooks/1.0")
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            repository,
+            client,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+        }
+    }
+
+    pub fn with_retry_backoff(mut self, retry_backoff: Duration) -> Self {
+        self.retry_backoff = retry_backoff;
+        self
+    }
+
+    pub async fn register(&self, endpoint_url: &str, events: Vec<String>, created_by: Uuid) -> Result<Webhook, AppError> {
+        let webhook = Webhook::new(endpoint_url, events, created_by)?;
+        self.repository.create(&webhook).await?;
+        tracing::info!("Webhook {} registered for {:?} by {}", webhook.id, webhook.subscribed_events, created_by);
+        Ok(webhook)
+    }
+
+    /// Latest delivery attempts of a webhook, newest first
+    pub async fn deliveries(&self, webhook_id: Uuid, limit: u32) -> Result<Vec<WebhookDelivery>, AppError> {
+        if self.repository.find_by_id(webhook_id).await?.is_none() {
+            return Err(AppError::NotFound(format!("Webhook {} not found", webhook_id)));
+        }
+        self.repository.find_deliveries(webhook_id, limit).await
+    }
+
+    /// Deliver an event to every active webhook subscribed to it
+    pub async fn dispatch(&self, event_type: &str, data: serde_json::Value) -> Result<(), AppError> {
+        let payload = WebhookPayload::new(event_type, data);
+        for webhook in self.repository.find_subscribed(event_type).await? {
+            self.deliver(&webhook, &payload).await?;
+        }
+        Ok(())
+    }
+
+    /// POST the payload with retries. Returns whether the endpoint accepted it.
+    pub async fn deliver(&self, webhook: &Webhook, payload: &WebhookPayload) -> Result<bool, AppError> {
+        let body = serde_json::to_string(payload).map_err(|e| AppError::SerializationError(e.to_string()))?;
+
+        let mut delivered = false;
+        for attempt in 1..=MAX_DELIVERY_ATTEMPTS {
+            if attempt > 1 {
+                tokio::time::sleep(self.retry_backoff * 2u32.pow(attempt - 2)).await;
+            }
+
+            let signature = sign_webhook_payload(&webhook.secret, Utc::now().timestamp(), &body);
+            let response = self
+                .client
+                .post(&webhook.endpoint_url)
+                .header("Content-Type", "application/json")
+                .header(WEBHOOK_SIGNATURE_HEADER, signature)
+                .header("X-VibeStream-Event", &payload.event_type)
+                .header("X-VibeStream-Delivery", payload.id.to_string())
+                .body(body.clone())
+                .send()
+                .await;
+
+            let (status_code, error) = match response {
+                Ok(response) if response.status().is_success() => (Some(response.status().as_u16()), None),
+                Ok(response) => (Some(response.status().as_u16()), Some(format!("Endpoint answered {}", response.status()))),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            delivered = error.is_none();
+
+            self.repository
+                .record_attempt(&WebhookDelivery {
+                    id: Uuid::new_v4(),
+                    webhook_id: webhook.id,
+                    event_id: payload.id,
+                    event_type: payload.event_type.clone(),
+                    attempt,
+                    status_code,
+                    succeeded: delivered,
+                    error,
+                    attempted_at: Utc::now(),
+                })
+                .await?;
+
+            if delivered {
+                break;
+            }
+        }
+
+        let updated = self.repository.record_outcome(webhook.id, delivered).await?;
+        if webhook.is_active && !updated.is_active {
+            tracing::warn!(
+                "Webhook {} deactivated after {} failed deliveries in a row",
+                webhook.id,
+                updated.consecutive_failures
+            );
+        }
+        Ok(delivered)
+    }
+}
+
+impl PaymentWebhookNotifier for WebhookDeliveryService {
+    fn notify(&self, event_type: &'static str, payment: &PaymentAggregate) {
+        let service = self.clone();
+        let data = payment_event_data(payment);
+        tokio::spawn(async move {
+            if let Err(e) = service.dispatch(event_type, data).await {
+                tracing::error!("Failed to dispatch {} webhooks: {}", event_type, e);
+            }
+        });
+    }
+}
+
+/// `data` of payment events: the payment as integrators see it
+pub fn payment_event_data(payment: &PaymentAggregate) -> serde_json::Value {
+    let payment = payment.payment();
+    serde_json::json!({
+        "payment_id": payment.id().value(),
+        "payer_id": payment.payer_id(),
+        "payee_id": payment.payee_id(),
+        "amount": payment.amount().value(),
+        "currency": format!("{:?}", payment.amount().currency()),
+        "status": format!("{:?}", payment.status()),
+        "purpose": serde_json::to_value(payment.purpose()).ok(),
+        "transaction_id": payment.transaction_id().map(|t| *t.value()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::payment::domain::repository::PaymentRepositoryResult;
+    use crate::bounded_contexts::payment::domain::webhooks::{verify_webhook_signature, MAX_CONSECUTIVE_FAILURES};
+    use std::sync::Mutex;
+    use wiremock::matchers::{header_exists, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[derive(Default)]
+    struct InMemoryWebhookRepository {
+        webhooks: Mutex<Vec<Webhook>>,
+        deliveries: Mutex<Vec<WebhookDelivery>>,
+    }
+
+    #[async_trait::async_trait]
+    impl WebhookRepository for InMemoryWebhookRepository {
+        async fn create(&self, webhook: &Webhook) -> PaymentRepositoryResult<()> {
+            self.webhooks.lock().unwrap().push(webhook.clone());
+            Ok(())
+        }
+
+        async fn find_by_id(&self, id: Uuid) -> PaymentRepositoryResult<Option<Webhook>> {
+            Ok(self.webhooks.lock().unwrap().iter().find(|w| w.id == id).cloned())
+        }
+
+        async fn find_subscribed(&self, event_type: &str) -> PaymentRepositoryResult<Vec<Webhook>> {
+            Ok(self.webhooks.lock().unwrap().iter().filter(|w| w.subscribes_to(event_type)).cloned().collect())
+        }
+
+        async fn record_attempt(&self, delivery: &WebhookDelivery) -> PaymentRepositoryResult<()> {
+            self.deliveries.lock().unwrap().push(delivery.clone());
+            Ok(())
+        }
+
+        async fn record_outcome(&self, webhook_id: Uuid, succeeded: bool) -> PaymentRepositoryResult<Webhook> {
+            let mut webhooks = self.webhooks.lock().unwrap();
+            let webhook = webhooks.iter_mut().find(|w| w.id == webhook_id).expect("webhook");
+            webhook.record_delivery(succeeded);
+            Ok(webhook.clone())
+        }
+
+        async fn find_deliveries(&self, webhook_id: Uuid, limit: u32) -> PaymentRepositoryResult<Vec<WebhookDelivery>> {
+            let deliveries = self.deliveries.lock().unwrap();
+            Ok(deliveries.iter().rev().filter(|d| d.webhook_id == webhook_id).take(limit as usize).cloned().collect())
+        }
+    }
+
+    async fn endpoint(status: u16) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hooks"))
+            .and(header_exists("X-VibeStream-Signature"))
+            .respond_with(ResponseTemplate::new(status))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    async fn service_with_webhook(server: &MockServer) -> (WebhookDeliveryService, Arc<InMemoryWebhookRepository>, Webhook) {
+        let repository = Arc::new(InMemoryWebhookRepository::default());
+        let service = WebhookDeliveryService::new(repository.clone()).with_retry_backoff(Duration::ZERO);
+        let webhook = service
+            .register(&format!("{}/hooks", server.uri()), vec!["payment.completed".to_string()], Uuid::new_v4())
+            .await
+            .unwrap();
+        (service, repository, webhook)
+    }
+
+    #[tokio::test]
+    async fn delivered_payload_carries_a_valid_signature() {
+        let server = endpoint(200).await;
+        let (service, repository, webhook) = service_with_webhook(&server).await;
+
+        service.dispatch("payment.completed", serde_json::json!({ "payment_id": "p-1" })).await.unwrap();
+        // Sin suscripción a este evento no se envía nada
+        service.dispatch("payment.failed", serde_json::json!({})).await.unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 1);
+        let body = String::from_utf8(requests[0].body.clone()).unwrap();
+        let signature = requests[0]
+            .headers
+            .iter()
+            .find(|(name, _)| name.as_str().eq_ignore_ascii_case(WEBHOOK_SIGNATURE_HEADER))
+            .map(|(_, values)| values.last().as_str().to_string())
+            .unwrap();
+        assert!(verify_webhook_signature(&webhook.secret, &signature, &body, Utc::now(), 300));
+        assert!(!verify_webhook_signature("whsec_someone_else", &signature, &body, Utc::now(), 300));
+
+        let payload: WebhookPayload = serde_json::from_str(&body).unwrap();
+        assert_eq!(payload.event_type, "payment.completed");
+        assert_eq!(payload.data["payment_id"], "p-1");
+
+        let deliveries = service.deliveries(webhook.id, 10).await.unwrap();
+        assert_eq!(deliveries.len(), 1);
+        assert!(deliveries[0].succeeded);
+        assert_eq!(deliveries[0].status_code, Some(200));
+        assert_eq!(repository.find_by_id(webhook.id).await.unwrap().unwrap().consecutive_failures, 0);
+    }
+
+    #[tokio::test]
+    async fn failed_delivery_is_retried_and_every_attempt_stored() {
+        let server = endpoint(500).await;
+        let (service, repository, webhook) = service_with_webhook(&server).await;
+
+        let delivered = service.deliver(&webhook, &WebhookPayload::new("payment.completed", serde_json::json!({}))).await.unwrap();
+
+        assert!(!delivered);
+        assert_eq!(server.received_requests().await.unwrap().len(), MAX_DELIVERY_ATTEMPTS as usize);
+        let deliveries = repository.deliveries.lock().unwrap().clone();
+        assert_eq!(deliveries.iter().map(|d| d.attempt).collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert!(deliveries.iter().all(|d| !d.succeeded && d.status_code == Some(500)));
+        // Mismo evento en todos los reintentos
+        assert!(deliveries.iter().all(|d| d.event_id == deliveries[0].event_id));
+        assert_eq!(repository.find_by_id(webhook.id).await.unwrap().unwrap().consecutive_failures, 1);
+    }
+
+    #[tokio::test]
+    async fn webhook_is_deactivated_after_consecutive_failed_deliveries() {
+        let server = endpoint(503).await;
+        let (service, repository, webhook) = service_with_webhook(&server).await;
+
+        for _ in 0..MAX_CONSECUTIVE_FAILURES {
+            service.dispatch("payment.completed", serde_json::json!({})).await.unwrap();
+        }
+        let stored = repository.find_by_id(webhook.id).await.unwrap().unwrap();
+        assert!(!stored.is_active);
+
+        // Inactivo: ya no recibe eventos
+        let requests_before = server.received_requests().await.unwrap().len();
+        service.dispatch("payment.completed", serde_json::json!({})).await.unwrap();
+        assert_eq!(server.received_requests().await.unwrap().len(), requests_before);
+    }
+
+    #[tokio::test]
+    async fn unreachable_endpoint_counts_as_a_failed_attempt() {
+        let repository = Arc::new(InMemoryWebhookRepository::default());
+        let service = WebhookDeliveryService::new(repository.clone()).with_retry_backoff(Duration::ZERO);
+        let webhook = service
+            .register("http://127.0.0.1:9/hooks", vec!["payment.refunded".to_string()], Uuid::new_v4())
+            .await
+            .unwrap();
+
+        service.dispatch("payment.refunded", serde_json::json!({})).await.unwrap();
+
+        let deliveries = service.deliveries(webhook.id, 10).await.unwrap();
+        assert_eq!(deliveries.len(), MAX_DELIVERY_ATTEMPTS as usize);
+        assert!(deliveries.iter().all(|d| d.status_code.is_none() && d.error.is_some()));
+    }
+}
@@ -0,0 +1,43 @@
+//! Redis cache for payment statistics reports
+
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use std::time::Duration;
+
+use crate::bounded_contexts::payment::application::PaymentStatisticsCache;
+use crate::bounded_contexts::payment::domain::statistics::PaymentStatisticsReport;
+
+pub struct RedisPaymentStatisticsCache {
+    connection: ConnectionManager,
+}
+
+impl RedisPaymentStatisticsCache {
+    pub fn new(connection: ConnectionManager) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl PaymentStatisticsCache for RedisPaymentStatisticsCache {
+    async fn get(&self, key: &str) -> Option<PaymentStatisticsReport> {
+        let mut conn = self.connection.clone();
+        let cached: Option<String> = match conn.get(key).await {
+            Ok(cached) => cached,
+            Err(e) => {
+                tracing::warn!("Payment statistics cache read failed: {}", e);
+                return None;
+            }
+        };
+        cached.and_then(|json| serde_json::from_str(&json).ok())
+    }
+
+    async fn put(&self, key: &str, report: &PaymentStatisticsReport, ttl: Duration) {
+        let Ok(json) = serde_json::to_string(report) else { return };
+        let mut conn = self.connection.clone();
+        let result: redis::RedisResult<()> = conn.set_ex(key, json, ttl.as_secs() as usize).await;
+        if let Err(e) = result {
+            tracing::warn!("Payment statistics cache write failed: {}", e);
+        }
+    }
+}
@@ -0,0 +1,67 @@
+//! Payment User Deletion Listener
+//!
+//! Payments are financial records and are kept, but the deleted user is
+//! replaced by the pseudonym of the event and the request metadata that
+//! identified them (IP, user agent) is dropped.
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+use crate::shared::domain::errors::AppError;
+
+pub struct PaymentUserDeletionListener {
+    pool: PgPool,
+}
+
+impl PaymentUserDeletionListener {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_error(e: sqlx::Error) -> AppError {
+    AppError::DatabaseError(format!("Failed to pseudonymize payments: {}", e))
+}
+
+#[async_trait]
+impl EventHandler for PaymentUserDeletionListener {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let DomainEvent::UserDeletionRequested { user_id, pseudonym_id, .. } = event else {
+            return Ok(());
+        };
+
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        sqlx::query(
+            r#"
+            UPDATE payments
+            SET metadata = metadata - 'user_ip' - 'user_agent', updated_at = NOW()
+            WHERE payer_id = $1 AND metadata IS NOT NULL
+            "#,
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        let as_payer = sqlx::query("UPDATE payments SET payer_id = $2, updated_at = NOW() WHERE payer_id = $1")
+            .bind(user_id)
+            .bind(pseudonym_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        let as_payee = sqlx::query("UPDATE payments SET payee_id = $2, updated_at = NOW() WHERE payee_id = $1")
+            .bind(user_id)
+            .bind(pseudonym_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        tx.commit().await.map_err(db_error)?;
+
+        tracing::info!(
+            "Pseudonymized {} payments of deleted user {}",
+            as_payer.rows_affected() + as_payee.rows_affected(),
+            user_id
+        );
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 use chrono::Utc;
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
 use serde_json::Value;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -8,11 +8,10 @@ use uuid::Uuid;
 use crate::shared::domain::errors::AppError;
 use crate::bounded_contexts::payment::{
     domain::{
-        aggregates::PaymentAggregate,
-        value_objects::{PaymentStatus, TransactionId},
+        value_objects::{PaymentId, PaymentStatus, TransactionId},
         repository::PaymentRepository,
     },
-    infrastructure::gateways::StripeGateway,
+    infrastructure::gateways::{stripe_gateway::from_minor_units, PaymentGateway, StripeGateway},
 };
 
 use super::{WebhookHandler, WebhookEventType, WebhookEventData, WebhookProcessingResult};
@@ -41,6 +40,36 @@ struct StripePaymentIntent {
     metadata: Value,
 }
 
+/// Qué hacer con un evento según el estado actual del pago.
+///
+/// Stripe reintenta los webhooks hasta recibir un 2xx, así que el mismo evento
+/// puede llegar varias veces: si el pago ya está en el estado destino se trata
+/// como ya aplicado y se responde con éxito sin tocar nada.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WebhookTransition {
+    Apply,
+    AlreadyApplied,
+    Invalid,
+}
+
+pub fn transition_for(current: &PaymentStatus, event_type: &WebhookEventType) -> WebhookTransition {
+    use WebhookTransition::*;
+
+    match (event_type, current) {
+        (WebhookEventType::PaymentSucceeded, PaymentStatus::Completed) => AlreadyApplied,
+        (WebhookEventType::PaymentSucceeded, PaymentStatus::Pending | PaymentStatus::Processing) => Apply,
+        (WebhookEventType::PaymentFailed, PaymentStatus::Failed { .. }) => AlreadyApplied,
+        (WebhookEventType::PaymentFailed, PaymentStatus::Pending | PaymentStatus::Processing) => Apply,
+        (WebhookEventType::PaymentRefunded, PaymentStatus::Refunded { .. }) => AlreadyApplied,
+        (WebhookEventType::PaymentRefunded, PaymentStatus::Refunding) => Apply,
+        (WebhookEventType::PaymentDisputed, PaymentStatus::OnHold) => AlreadyApplied,
+        (WebhookEventType::PaymentDisputed, PaymentStatus::Completed) => Apply,
+        (WebhookEventType::PaymentExpired, PaymentStatus::Cancelled { .. }) => AlreadyApplied,
+        (WebhookEventType::PaymentExpired, PaymentStatus::Pending | PaymentStatus::Processing) => Apply,
+        _ => Invalid,
+    }
+}
+
 /// Stripe webhook handler
 pub struct StripeWebhookHandler {
     gateway: Arc<StripeGateway>,
@@ -78,8 +107,11 @@ impl StripeWebhookHandler {
             "requires_payment_method" => PaymentStatus::Pending,
             "requires_confirmation" => PaymentStatus::Pending,
             "requires_action" => PaymentStatus::Pending,
-            "canceled" => PaymentStatus::Cancelled,
-            _ => PaymentStatus::Failed,
+            "canceled" => PaymentStatus::Cancelled { reason: "Cancelled by gateway".to_string() },
+            other => PaymentStatus::Failed {
+                error_code: "GATEWAY_ERROR".to_string(),
+                error_message: format!("Stripe status: {}", other),
+            },
         }
     }
 
@@ -100,32 +132,56 @@ impl StripeWebhookHandler {
         let payment_id = event_data.payment_id;
         
         // Find the payment in our repository
-        let payment = self.payment_repository
-            .find_by_id(&TransactionId::new(payment_id.to_string())?)
+        let mut payment = self.payment_repository
+            .find_by_id(&PaymentId::from_uuid(payment_id))
             .await?
             .ok_or_else(|| AppError::NotFound(format!("Payment not found: {}", payment_id)))?;
 
+        match transition_for(payment.payment().status(), &event_data.event_type) {
+            WebhookTransition::Apply => {}
+            WebhookTransition::AlreadyApplied => {
+                tracing::info!(
+                    "Stripe webhook {:?} for payment {} already applied, skipping",
+                    event_data.event_type,
+                    payment_id
+                );
+                return Ok(());
+            }
+            WebhookTransition::Invalid => {
+                return Err(AppError::InvalidState(format!(
+                    "Cannot apply {:?} to payment {} in status {:?}",
+                    event_data.event_type,
+                    payment_id,
+                    payment.payment().status()
+                )));
+            }
+        }
+
         // Update payment status based on webhook event
-        let updated_payment = match event_data.event_type {
+        match event_data.event_type {
             WebhookEventType::PaymentSucceeded => {
-                payment.mark_as_completed(event_data.transaction_id.clone())?
+                if *payment.payment().status() == PaymentStatus::Pending {
+                    payment.start_processing(TransactionId::new())?;
+                }
+                // Los pagos con tarjeta no tienen hash on-chain
+                payment.complete_payment(None)?;
             }
             WebhookEventType::PaymentFailed => {
-                payment.mark_as_failed(event_data.transaction_id.clone(), "Payment failed via Stripe".to_string())?
+                payment.mark_as_failed(event_data.transaction_id.clone(), "Payment failed via Stripe".to_string())?;
             }
             WebhookEventType::PaymentRefunded => {
-                payment.mark_as_refunded(event_data.transaction_id.clone())?
+                payment.mark_as_refunded(event_data.transaction_id.clone())?;
             }
             WebhookEventType::PaymentDisputed => {
-                payment.mark_as_disputed(event_data.transaction_id.clone())?
+                payment.mark_as_disputed(event_data.transaction_id.clone())?;
             }
             WebhookEventType::PaymentExpired => {
-                payment.mark_as_cancelled(event_data.transaction_id.clone())?
+                payment.mark_as_cancelled(event_data.transaction_id.clone())?;
             }
-        };
+        }
 
         // Save the updated payment
-        self.payment_repository.save(&updated_payment).await?;
+        self.payment_repository.update(&payment).await?;
 
         tracing::info!(
             "Updated payment {} status to {:?} via Stripe webhook",
@@ -165,7 +221,7 @@ impl WebhookHandler for StripeWebhookHandler {
             event_type: event_type.clone(),
             payment_id,
             transaction_id: stripe_event.data.object.id.clone(),
-            amount: (stripe_event.data.object.amount as f64) / 100.0, // Convert from cents
+            amount: from_minor_units(stripe_event.data.object.amount, &stripe_event.data.object.currency),
             currency: stripe_event.data.object.currency.clone(),
             status: payment_status,
             occurred_at: Utc::now(),
@@ -203,19 +259,22 @@ impl WebhookHandler for StripeWebhookHandler {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::bounded_contexts::payment::infrastructure::repositories::InMemoryPaymentRepository;
 
-    #[tokio::test]
-    async fn test_stripe_webhook_handler_creation() {
+    #[test]
+    fn test_stripe_event_type_mapping() {
         let config = crate::bounded_contexts::payment::infrastructure::gateways::GatewayConfig {
             api_key: "sk_test_fake".to_string(),
             webhook_secret: "whsec_fake".to_string(),
             environment: "test".to_string(),
         };
 
-        let gateway = StripeGateway::new(config).await.unwrap();
-        let repository = Arc::new(InMemoryPaymentRepository::new());
-        
+        let gateway = tokio_test::block_on(StripeGateway::new(config)).unwrap();
+        let repository: Arc<dyn PaymentRepository> = Arc::new(
+            crate::bounded_contexts::payment::infrastructure::repositories::PostgreSQLPaymentRepository::new(
+                sqlx::PgPool::connect_lazy("postgres://localhost/vibestream_test").unwrap(),
+            ),
+        );
+
         let handler = StripeWebhookHandler::new(
             Arc::new(gateway),
             repository,
@@ -224,23 +283,6 @@ mod tests {
         assert_eq!(handler.handler_name(), "stripe_webhook_handler");
         assert!(handler.can_handle("stripe"));
         assert!(!handler.can_handle("paypal"));
-    }
-
-    #[test]
-    fn test_stripe_event_type_mapping() {
-        let config = crate::bounded_contexts::payment::infrastructure::gateways::GatewayConfig {
-            api_key: "sk_test_fake".to_string(),
-            webhook_secret: "whsec_fake".to_string(),
-            environment: "test".to_string(),
-        };
-
-        let gateway = StripeGateway::new(config).await.unwrap();
-        let repository = Arc::new(InMemoryPaymentRepository::new());
-        
-        let handler = StripeWebhookHandler::new(
-            Arc::new(gateway),
-            repository,
-        );
 
         assert!(matches!(
             handler.map_stripe_event_type("payment_intent.succeeded"),
@@ -252,4 +294,36 @@ mod tests {
             WebhookEventType::PaymentFailed
         ));
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_duplicate_deliveries_are_noops() {
+        assert_eq!(
+            transition_for(&PaymentStatus::Processing, &WebhookEventType::PaymentSucceeded),
+            WebhookTransition::Apply
+        );
+        assert_eq!(
+            transition_for(&PaymentStatus::Completed, &WebhookEventType::PaymentSucceeded),
+            WebhookTransition::AlreadyApplied
+        );
+        assert_eq!(
+            transition_for(
+                &PaymentStatus::Cancelled { reason: "Cancelled by gateway".to_string() },
+                &WebhookEventType::PaymentExpired
+            ),
+            WebhookTransition::AlreadyApplied
+        );
+    }
+
+    #[test]
+    fn test_out_of_order_events_are_rejected() {
+        // Un fallo tardío no puede deshacer un pago ya completado
+        assert_eq!(
+            transition_for(&PaymentStatus::Completed, &WebhookEventType::PaymentFailed),
+            WebhookTransition::Invalid
+        );
+        assert_eq!(
+            transition_for(&PaymentStatus::Pending, &WebhookEventType::PaymentRefunded),
+            WebhookTransition::Invalid
+        );
+    }
+}
@@ -1,12 +1,13 @@
 use axum::{
     extract::{Query, Path, State},
-    http::StatusCode,
-    response::Json,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Json, Response},
+    middleware,
     routing::{get, post, put, delete},
-    Router, Extension,
+    Router,
 };
 use serde::{Deserialize, Serialize};
-use utoipa::ToSchema;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 use std::sync::Arc;
 use chrono::{DateTime, Utc};
@@ -17,7 +18,7 @@ use crate::bounded_contexts::payment::application::{
         CancelPaymentCommand, InitiateRefundCommand, 
         CreateRoyaltyDistributionCommand, ProcessRoyaltyDistributionCommand,
         StartPaymentProcessingCommand, PaymentPurposeDto, PaymentMetadataDto,
-        CreateWalletCommand, CreateWalletResult, DistributeRoyaltiesCommand, Wallet,
+        CreateWalletCommand, CreateWalletResult, Wallet,
     },
     handlers::{
         command_handlers::{
@@ -34,7 +35,12 @@ use crate::bounded_contexts::payment::application::{
 
     },
     dto::*, 
-    dto::{PaymentStatistics, PaymentAnalytics, FraudDetectionStats},
+    dto::{PaymentAnalytics, FraudDetectionStats},
+    refund_service::RefundService,
+    royalty_distribution_service::{RoyaltyDistributionService, DistributeSongRoyalties},
+    artist_payout_service::ArtistPayoutService,
+    royalty_advance_service::RoyaltyAdvanceService,
+    payment_statistics_service::{PaymentStatisticsService, PaymentStatisticsQuery},
     services::{
         PaymentApplicationService, RoyaltyDistributionApplicationService,
         MockPaymentProcessingService, MockFraudDetectionService, MockNotificationService,
@@ -48,14 +54,23 @@ use crate::bounded_contexts::payment::infrastructure::webhooks::{
     WebhookRouter, StripeWebhookHandler, PayPalWebhookHandler, CoinbaseWebhookHandler,
     WebhookQueueProcessor, ReconciliationResult,
 };
+use crate::bounded_contexts::payment::infrastructure::services::WebhookDeliveryService;
 use crate::services::MessageQueue;
 use crate::bounded_contexts::payment::infrastructure::gateways::{
-    PaymentGateway, StripeGateway, PayPalGateway, CoinbaseGateway,
+    PaymentGateway, StripeGateway, PayPalGateway, CoinbaseGateway, CryptoPaymentGateway,
 };
 use crate::bounded_contexts::payment::domain::value_objects::Currency;
+use crate::bounded_contexts::payment::domain::royalty_runs::RoyaltySplit;
+use crate::bounded_contexts::payment::domain::artist_payouts::PayoutMethod;
+use crate::bounded_contexts::payment::domain::royalty_advances::DEFAULT_REPAYMENT_RATE;
+use crate::bounded_contexts::payment::domain::statistics::PaymentStatisticsReport;
+use crate::auth::Claims;
+use crate::bounded_contexts::user::domain::UserRole;
 use crate::bounded_contexts::payment::application::handlers::command_handlers::CreateWalletCommandHandler;
 
 use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::auth::{authorize_owner, authorize_role, OwnedResource, Principal};
+use crate::shared::infrastructure::idempotency::{IdempotencyStore, idempotency_middleware, IDEMPOTENCY_KEY_HEADER};
 
 // =============================================================================
 // REQUEST/RESPONSE DTOs
@@ -111,6 +126,13 @@ pub struct PaymentController {
     royalty_command_handler: Arc<RoyaltyCommandHandlerImpl>,
     wallet_command_handler: Arc<CreateWalletCommandHandler>,
     payment_query_handler: Arc<GetPaymentQueryHandler>,
+    crypto_gateway: Option<Arc<CryptoPaymentGateway>>,
+    refund_service: Option<Arc<RefundService>>,
+    royalty_distribution_service: Option<Arc<RoyaltyDistributionService>>,
+    artist_payout_service: Option<Arc<ArtistPayoutService>>,
+    royalty_advance_service: Option<Arc<RoyaltyAdvanceService>>,
+    statistics_service: Option<Arc<PaymentStatisticsService>>,
+    webhook_service: Option<Arc<WebhookDeliveryService>>,
 }
 
 impl PaymentController {
@@ -135,17 +157,81 @@ impl PaymentController {
             royalty_command_handler,
             wallet_command_handler,
             payment_query_handler,
+            crypto_gateway: None,
+            refund_service: None,
+            royalty_distribution_service: None,
+            artist_payout_service: None,
+            royalty_advance_service: None,
+            statistics_service: None,
+            webhook_service: None,
         }
     }
 
-    pub fn routes(controller: Arc<Self>) -> Router {
-        Router::new()
-            // Payment operations
+    /// Exponer dirección/memo y estado de los depósitos USDC en las respuestas de pago
+    pub fn with_crypto_gateway(mut self, crypto_gateway: Arc<CryptoPaymentGateway>) -> Self {
+        self.crypto_gateway = Some(crypto_gateway);
+        self
+    }
+
+    /// Reembolsos totales/parciales ejecutados por el gateway de origen
+    pub fn with_refund_service(mut self, refund_service: Arc<RefundService>) -> Self {
+        self.refund_service = Some(refund_service);
+        self
+    }
+
+    /// Reparto de royalties derivado de los ajustes de cada canción
+    pub fn with_royalty_distribution_service(mut self, service: Arc<RoyaltyDistributionService>) -> Self {
+        self.royalty_distribution_service = Some(service);
+        self
+    }
+
+    /// Saldos, métodos de cobro y payouts de artistas
+    pub fn with_artist_payout_service(mut self, service: Arc<ArtistPayoutService>) -> Self {
+        self.artist_payout_service = Some(service);
+        self
+    }
+
+    /// Adelantos sobre royalties futuras
+    pub fn with_royalty_advance_service(mut self, service: Arc<RoyaltyAdvanceService>) -> Self {
+        self.royalty_advance_service = Some(service);
+        self
+    }
+
+    /// Estadísticas agregadas sobre la tabla de pagos
+    pub fn with_statistics_service(mut self, service: Arc<PaymentStatisticsService>) -> Self {
+        self.statistics_service = Some(service);
+        self
+    }
+
+    /// Webhooks salientes para integraciones externas
+    pub fn with_webhook_service(mut self, service: Arc<WebhookDeliveryService>) -> Self {
+        self.webhook_service = Some(service);
+        self
+    }
+
+    async fn with_crypto_deposit(&self, mut payment: PaymentDTO) -> PaymentDTO {
+        if let Some(gateway) = &self.crypto_gateway {
+            payment.crypto_deposit = gateway.deposit_status(payment.id).await.as_ref().map(CryptoDepositDTO::from);
+        }
+        payment
+    }
+
+    pub fn routes(controller: Arc<Self>, idempotency_store: Arc<dyn IdempotencyStore>) -> Router {
+        // Creación y procesamiento generan cargos: aceptan Idempotency-Key
+        let idempotent_routes = Router::new()
             .route("/payments", post(initiate_payment))
             .route("/payments/:payment_id/process", post(process_payment))
+            .route_layer(middleware::from_fn_with_state(idempotency_store, idempotency_middleware));
+
+        Router::new()
+            .merge(idempotent_routes)
+            // Payment operations
             .route("/payments/:payment_id/complete", post(complete_payment))
             .route("/payments/:payment_id/cancel", post(cancel_payment))
             .route("/payments/refund", post(initiate_refund))
+            .route("/payments/:payment_id/refund", post(refund_payment))
+            .route("/payments/:payment_id/refunds", get(list_payment_refunds))
+            .route("/refunds/:refund_id/payout", post(confirm_refund_payout))
             
             // Payment queries
             .route("/payments/:payment_id", get(get_payment))
@@ -161,10 +247,18 @@ impl PaymentController {
             // Royalty operations
             .route("/royalties/distribute", post(distribute_royalties))
             .route("/royalties/:distribution_id/process", post(process_royalty_distribution))
+            .route("/royalties/runs/:run_id", get(get_royalty_run))
+            .route("/royalties/runs/:run_id/void", post(void_royalty_run))
             
             // Royalty queries
             .route("/royalties", get(get_royalty_distributions))
             .route("/royalties/artist/:artist_id/summary", get(get_artist_revenue_summary))
+
+            // Artist balances and payouts
+            .route("/artists/:artist_id/balance", get(get_artist_balance))
+            .route("/artists/:artist_id/payouts", get(get_artist_payouts))
+            .route("/artists/:artist_id/payout-method", put(register_payout_method))
+            .route("/artists/:artist_id/royalty-advance", post(request_royalty_advance))
             
             // Wallet operations
             .route("/wallets", get(list_wallets).post(create_wallet))
@@ -181,6 +275,10 @@ impl PaymentController {
             .route("/webhooks/coinbase", post(coinbase_webhook))
             .route("/webhooks/:gateway", post(generic_webhook))
             
+            // Outbound webhooks (admin). /webhooks/* recibe los callbacks de los gateways
+            .route("/admin/webhooks", post(create_webhook))
+            .route("/admin/webhooks/:webhook_id/deliveries", get(list_webhook_deliveries))
+            
             // Reconciliation endpoints
             .route("/reconcile/:payment_id", post(reconcile_payment))
             
@@ -197,24 +295,30 @@ impl PaymentController {
     path = "/api/v1/payments",
     request_body = InitiatePaymentRequest,
     responses(
-        (status = 200, description = "Payment initiated successfully", body = ApiResponse<InitiatePaymentResponse>),
+        (status = 200, description = "Payment initiated successfully. A repeated request returns the payment it already created, with its URL in Location", body = ApiResponse<InitiatePaymentResponse>,
+            headers(("Location" = String, description = "Only on repeated requests: URL of the existing payment"))),
         (status = 400, description = "Invalid input"),
         (status = 402, description = "Insufficient funds"),
-        (status = 403, description = "Fraud detected")
+        (status = 403, description = "Fraud detected (body carries a machine-readable reason_code)"),
+        (status = 409, description = "Idempotency key already used for a different payment (DUPLICATE_PAYMENT)")
     ),
     tag = "payments"
 )]
 pub async fn initiate_payment(
     State(controller): State<Arc<PaymentController>>,
-    Extension(_current_user_id): Extension<Uuid>,
+    claims: Claims,
+    headers: HeaderMap,
     Json(request): Json<InitiatePaymentRequest>,
-) -> Result<Json<ApiResponse<InitiatePaymentResponse>>, StatusCode> {
+) -> Result<Response, AppError> {
+    // Sólo se puede pagar en nombre propio
+    authorize_owner(&claims, OwnedResource::Payment, request.payer_id)?;
+
     // Construct purpose DTO (simplified mapping)
     let purpose = PaymentPurposeDto {
         purpose_type: request.payment_type.clone(),
         campaign_id: if request.payment_type == "NFTPurchase" { Some(request.related_entity_id) } else { None },
         contract_id: if request.payment_type == "SharePurchase" { Some(request.related_entity_id) } else { None },
-        song_id: if request.payment_type == "RoyaltyDistribution" { Some(request.related_entity_id) } else { None },
+        song_id: if matches!(request.payment_type.as_str(), "RoyaltyDistribution" | "SongPurchase") { Some(request.related_entity_id) } else { None },
         nft_quantity: None,
         ownership_percentage: None,
         share_id: None,
@@ -236,7 +340,12 @@ pub async fn initiate_payment(
         payment_method: request.payment_method,
         purpose,
         metadata: request.metadata,
-        idempotency_key: None, // Could come from headers
+        idempotency_key: request.idempotency_key.clone().or_else(|| {
+            headers
+                .get(IDEMPOTENCY_KEY_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string)
+        }),
     };
 
     match controller.payment_command_handler.handle_initiate_payment(command).await {
@@ -250,16 +359,20 @@ pub async fn initiate_payment(
                 expires_at: None, // Default expiry
                 created_at: result.created_at,
             };
-            Ok(Json(ApiResponse::success(response)))
+            if result.replayed {
+                // Reintento: el pago ya existe, no se ha vuelto a cobrar
+                let location = format!("/api/v1/payments/{}", result.payment_id);
+                return Ok((
+                    StatusCode::OK,
+                    [(header::LOCATION, location)],
+                    Json(ApiResponse::success(response)),
+                ).into_response());
+            }
+            Ok(Json(ApiResponse::success(response)).into_response())
         }
         Err(err) => {
-            eprintln!("Initiate payment error: {:?}", err);
-            match err {
-                AppError::ValidationError(_) => Err(StatusCode::BAD_REQUEST),
-                AppError::InsufficientFundsError(_) => Err(StatusCode::PAYMENT_REQUIRED), // Fixed enum variant name
-                AppError::FraudDetected(_) => Err(StatusCode::FORBIDDEN),
-                _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
-            }
+            tracing::warn!("Initiate payment error: {:?}", err);
+            Err(err)
         }
     }
 }
@@ -281,9 +394,11 @@ pub async fn initiate_payment(
 pub async fn process_payment(
     State(controller): State<Arc<PaymentController>>,
     Path(payment_id): Path<Uuid>,
-    Extension(_current_user_id): Extension<Uuid>,
+    claims: Claims,
     Json(request): Json<ProcessPaymentRequest>,
-) -> Result<Json<ApiResponse<PaymentDTO>>, StatusCode> { // Start returning proper result type or DTO? 
+) -> Result<Json<ApiResponse<PaymentDTO>>, AppError> { // Start returning proper result type or DTO? 
+    authorize_payer(&controller, &claims, payment_id).await?;
+
     // Note: Controller returns PaymentDTO but Command returns ProcessPaymentResult. 
     // We should map ProcessPaymentResult to PaymentDTO if possible, or return ProcessPaymentResult directly if API allows.
     // Assuming we return ProcessPaymentResult for now as it contains status etc.
@@ -304,18 +419,14 @@ pub async fn process_payment(
             let query = GetPaymentQuery { payment_id };
             let query_handler = GetPaymentQueryHandler::new(controller.payment_repository.clone());
                  match query_handler.handle(query).await {
-                    Ok(Some(payment)) => Ok(Json(ApiResponse::success(payment))),
-                    _ => Err(StatusCode::INTERNAL_SERVER_ERROR)
+                    Ok(Some(payment)) => Ok(Json(ApiResponse::success(controller.with_crypto_deposit(payment).await))),
+                    Ok(None) => Err(AppError::NotFound(format!("Payment {} not found", payment_id))),
+                    Err(err) => Err(err),
                  }
         }, 
         Err(err) => {
-            eprintln!("Process payment error: {:?}", err);
-            match err {
-                AppError::NotFound(_) => Err(StatusCode::NOT_FOUND),
-                AppError::ValidationError(_) => Err(StatusCode::BAD_REQUEST),
-                // AppError::PaymentGatewayError(_) => Err(StatusCode::BAD_GATEWAY), // If exists
-                _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
-            }
+            tracing::warn!("Process payment error: {:?}", err);
+            Err(err)
         }
     }
 }
@@ -336,8 +447,10 @@ pub async fn process_payment(
 pub async fn complete_payment(
     State(controller): State<Arc<PaymentController>>,
     Path(payment_id): Path<Uuid>,
-    Extension(_current_user_id): Extension<Uuid>,
-) -> Result<Json<ApiResponse<PaymentDTO>>, StatusCode> {
+    claims: Claims,
+) -> Result<Json<ApiResponse<PaymentDTO>>, AppError> {
+    authorize_payer(&controller, &claims, payment_id).await?;
+
     let command = CompletePaymentCommand {
         payment_id,
         blockchain_hash: None, // Need to get from request if manual completion
@@ -352,16 +465,13 @@ pub async fn complete_payment(
             let query = GetPaymentQuery { payment_id, include_events: false };
             match controller.payment_query_handler.handle(query).await {
                     Ok(Some(payment)) => Ok(Json(ApiResponse::success(payment))),
-                    _ => Err(StatusCode::INTERNAL_SERVER_ERROR)
+                    Ok(None) => Err(AppError::NotFound(format!("Payment {} not found", payment_id))),
+                    Err(err) => Err(err),
                  }
              }
         Err(err) => {
-            eprintln!("Complete payment error: {:?}", err);
-            match err {
-                AppError::NotFound(_) => Err(StatusCode::NOT_FOUND),
-                AppError::ValidationError(_) => Err(StatusCode::BAD_REQUEST),
-                _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
-            }
+            tracing::warn!("Complete payment error: {:?}", err);
+            Err(err)
         }
     }
 }
@@ -382,12 +492,14 @@ pub async fn complete_payment(
 pub async fn cancel_payment(
     State(controller): State<Arc<PaymentController>>,
     Path(payment_id): Path<Uuid>,
-    Extension(current_user_id): Extension<Uuid>,
-) -> Result<Json<ApiResponse<PaymentDTO>>, StatusCode> {
+    claims: Claims,
+) -> Result<Json<ApiResponse<PaymentDTO>>, AppError> {
+    authorize_payer(&controller, &claims, payment_id).await?;
+
     let command = CancelPaymentCommand {
         payment_id,
         reason: "User requested cancellation".to_string(),
-        cancelled_by: current_user_id,
+        cancelled_by: claims.subject_id()?,
     };
 
     match controller.payment_command_handler.handle_cancel_payment(command).await {
@@ -396,16 +508,13 @@ pub async fn cancel_payment(
             let query = GetPaymentQuery { payment_id, include_events: false };
             match controller.payment_query_handler.handle(query).await {
                     Ok(Some(payment)) => Ok(Json(ApiResponse::success(payment))),
-                    _ => Err(StatusCode::INTERNAL_SERVER_ERROR)
+                    Ok(None) => Err(AppError::NotFound(format!("Payment {} not found", payment_id))),
+                    Err(err) => Err(err),
                  }
              }
         Err(err) => {
-            eprintln!("Cancel payment error: {:?}", err);
-            match err {
-                AppError::NotFound(_) => Err(StatusCode::NOT_FOUND),
-                AppError::ValidationError(_) => Err(StatusCode::BAD_REQUEST),
-                _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
-            }
+            tracing::warn!("Cancel payment error: {:?}", err);
+            Err(err)
         }
     }
 }
@@ -423,29 +532,135 @@ pub async fn cancel_payment(
 )]
 pub async fn initiate_refund(
     State(controller): State<Arc<PaymentController>>,
-    Extension(current_user_id): Extension<Uuid>,
+    claims: Claims,
     Json(request): Json<InitiateRefundRequest>,
-) -> Result<Json<ApiResponse<crate::bounded_contexts::payment::application::commands::RefundResult>>, StatusCode> {
-    let original_payment_id = request.original_payment_id.ok_or(StatusCode::BAD_REQUEST)?;
+) -> Result<Json<ApiResponse<crate::bounded_contexts::payment::application::commands::RefundResult>>, AppError> {
+    let original_payment_id = request.original_payment_id
+        .ok_or_else(|| AppError::ValidationError("original_payment_id is required".to_string()))?;
+    authorize_payer(&controller, &claims, original_payment_id).await?;
     
     let command = InitiateRefundCommand {
         original_payment_id,
         refund_amount: request.refund_amount.unwrap_or(0.0),
         refund_currency: Currency::USD, // TODO: Get from request or original payment
         reason: request.reason.unwrap_or_else(|| "User requested refund".to_string()),
-        initiated_by: current_user_id,
+        initiated_by: claims.subject_id()?,
     };
 
     match controller.payment_command_handler.handle_initiate_refund(command).await {
         Ok(result) => Ok(Json(ApiResponse::success(result))),
         Err(err) => {
-            eprintln!("Initiate refund error: {:?}", err);
-            match err {
-               AppError::NotFound(_) => Err(StatusCode::NOT_FOUND),
-               AppError::InvalidInput(_) => Err(StatusCode::BAD_REQUEST),
-               AppError::InvalidState(_) => Err(StatusCode::CONFLICT),
-               _ => Err(StatusCode::INTERNAL_SERVER_ERROR),
-            }
+            tracing::warn!("Initiate refund error: {:?}", err);
+            Err(payment_error(err))
+        }
+    }
+}
+
+/// En pagos, una transición de estado inválida (pago no completado, run ya
+/// anulado...) es un conflicto con el estado actual del recurso: 409
+fn payment_error(err: AppError) -> AppError {
+    match err {
+        AppError::InvalidState(msg) => AppError::ConflictError(msg),
+        other => other,
+    }
+}
+
+fn not_configured(feature: &str) -> AppError {
+    AppError::NotImplemented(format!("{} are not configured", feature))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/payments/{payment_id}/refund",
+    request_body = RefundPaymentRequest,
+    params(
+        ("payment_id" = Uuid, Path, description = "Payment ID to refund")
+    ),
+    responses(
+        (status = 200, description = "Refund executed or queued", body = ApiResponse<RefundTransactionDTO>),
+        (status = 400, description = "Invalid refund amount"),
+        (status = 404, description = "Payment not found"),
+        (status = 409, description = "Payment not completed or already fully refunded"),
+        (status = 502, description = "Payment gateway rejected the refund")
+    ),
+    tag = "payments"
+)]
+pub async fn refund_payment(
+    State(controller): State<Arc<PaymentController>>,
+    Path(payment_id): Path<Uuid>,
+    claims: Claims,
+    Json(request): Json<RefundPaymentRequest>,
+) -> Result<Json<ApiResponse<RefundTransactionDTO>>, AppError> {
+    authorize_payer(&controller, &claims, payment_id).await?;
+    let refund_service = controller.refund_service.as_ref().ok_or_else(|| not_configured("Refunds"))?;
+    let reason = request.reason.unwrap_or_else(|| "User requested refund".to_string());
+
+    match refund_service.refund_payment(payment_id, request.amount, reason, claims.subject_id()?).await {
+        Ok(refund) => Ok(Json(ApiResponse::success(refund.into()))),
+        Err(err) => {
+            tracing::error!("Refund of payment {} failed: {:?}", payment_id, err);
+            Err(payment_error(err))
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/payments/{payment_id}/refunds",
+    params(
+        ("payment_id" = Uuid, Path, description = "Payment ID")
+    ),
+    responses(
+        (status = 200, description = "Refunds of the payment", body = ApiResponse<Vec<RefundTransactionDTO>>)
+    ),
+    tag = "payments"
+)]
+pub async fn list_payment_refunds(
+    State(controller): State<Arc<PaymentController>>,
+    Path(payment_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<RefundTransactionDTO>>>, AppError> {
+    let refund_service = controller.refund_service.as_ref().ok_or_else(|| not_configured("Refunds"))?;
+
+    match refund_service.refunds_for_payment(payment_id).await {
+        Ok(refunds) => Ok(Json(ApiResponse::success(refunds.into_iter().map(Into::into).collect()))),
+        Err(err) => {
+            tracing::error!("Listing refunds of payment {} failed: {:?}", payment_id, err);
+            Err(payment_error(err))
+        }
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/refunds/{refund_id}/payout",
+    request_body = ConfirmRefundPayoutRequest,
+    params(
+        ("refund_id" = Uuid, Path, description = "Crypto refund awaiting payout")
+    ),
+    responses(
+        (status = 200, description = "Payout recorded, refund completed", body = ApiResponse<RefundTransactionDTO>),
+        (status = 404, description = "Refund not found"),
+        (status = 409, description = "Refund is not awaiting a payout")
+    ),
+    tag = "payments"
+)]
+pub async fn confirm_refund_payout(
+    State(controller): State<Arc<PaymentController>>,
+    Path(refund_id): Path<Uuid>,
+    claims: Claims,
+    Json(request): Json<ConfirmRefundPayoutRequest>,
+) -> Result<Json<ApiResponse<RefundTransactionDTO>>, AppError> {
+    authorize_role(&claims, &[UserRole::Admin])?;
+    let refund_service = controller.refund_service.as_ref().ok_or_else(|| not_configured("Refunds"))?;
+    if request.tx_hash.trim().is_empty() {
+        return Err(AppError::ValidationError("tx_hash is required".to_string()));
+    }
+
+    match refund_service.confirm_payout(refund_id, request.tx_hash).await {
+        Ok(refund) => Ok(Json(ApiResponse::success(refund.into()))),
+        Err(err) => {
+            tracing::error!("Confirming payout of refund {} failed: {:?}", refund_id, err);
+            Err(payment_error(err))
         }
     }
 }
@@ -469,34 +684,31 @@ pub async fn initiate_refund(
 pub async fn get_payment(
     State(controller): State<Arc<PaymentController>>,
     Path(payment_id): Path<Uuid>,
-) -> Result<Json<ApiResponse<PaymentDTO>>, StatusCode> {
+) -> Result<Json<ApiResponse<PaymentDTO>>, AppError> {
     let query = GetPaymentQuery { payment_id, include_events: true };
     
     match controller.payment_query_handler.handle(query).await {
-        Ok(Some(payment)) => Ok(Json(ApiResponse::success(payment))),
-        Ok(None) => Err(StatusCode::NOT_FOUND),
-        Err(err) => {
-            eprintln!("Get payment error: {:?}", err);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
-        }
+        Ok(Some(payment)) => Ok(Json(ApiResponse::success(controller.with_crypto_deposit(payment).await))),
+        Ok(None) => Err(AppError::NotFound(format!("Payment {} not found", payment_id))),
+        Err(err) => Err(err),
     }
 }
 
 pub async fn get_payment_by_transaction(
     State(_controller): State<Arc<PaymentController>>,
     Path(_transaction_id): Path<String>,
-) -> Result<Json<ApiResponse<PaymentDTO>>, StatusCode> {
+) -> Result<Json<ApiResponse<PaymentDTO>>, AppError> {
     let _query = GetPaymentByTransactionQuery { transaction_id: _transaction_id };
     
     // Handler would be implemented
     // For now, return not found
-    Err(StatusCode::NOT_FOUND)
+    Err(AppError::NotFound("Payment not found for transaction".to_string()))
 }
 
 pub async fn search_payments(
     State(_controller): State<Arc<PaymentController>>,
     Query(params): Query<SearchPaymentsRequest>,
-) -> Result<Json<ApiResponse<SearchPaymentsResult>>, StatusCode> {
+) -> Result<Json<ApiResponse<SearchPaymentsResult>>, AppError> {
     let _query = SearchPaymentsQuery {
         user_id: params.user_id,
         payment_type: params.payment_type,
@@ -523,13 +735,10 @@ pub async fn search_payments(
 pub async fn get_user_payment_history(
     State(_controller): State<Arc<PaymentController>>,
     Path(user_id): Path<Uuid>,
-    Extension(current_user_id): Extension<Uuid>,
+    claims: Claims,
     Query(_params): Query<std::collections::HashMap<String, String>>,
-) -> Result<Json<ApiResponse<SearchPaymentsResult>>, StatusCode> {
-    // Check authorization
-    if user_id != current_user_id {
-        return Err(StatusCode::FORBIDDEN);
-    }
+) -> Result<Json<ApiResponse<SearchPaymentsResult>>, AppError> {
+    authorize_owner(&claims, OwnedResource::Payment, user_id)?;
 
     let result = SearchPaymentsResult {
         payments: vec![],
@@ -543,12 +752,9 @@ pub async fn get_user_payment_history(
 pub async fn get_user_payment_summary(
     State(_controller): State<Arc<PaymentController>>,
     Path(user_id): Path<Uuid>,
-    Extension(current_user_id): Extension<Uuid>,
-) -> Result<Json<ApiResponse<()>>, StatusCode> {
-    // Check authorization
-    if user_id != current_user_id {
-        return Err(StatusCode::FORBIDDEN);
-    }
+    claims: Claims,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    authorize_owner(&claims, OwnedResource::Payment, user_id)?;
 
     Ok(Json(ApiResponse::success(())))
 }
@@ -557,28 +763,65 @@ pub async fn get_user_payment_summary(
 // PAYMENT ANALYTICS
 // =============================================================================
 
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct PaymentStatisticsParams {
+    /// Start of the period (default: 30 days before `end_date`)
+    pub start_date: Option<DateTime<Utc>>,
+    /// End of the period (default: now)
+    pub end_date: Option<DateTime<Utc>>,
+    /// Admins only: restrict to the revenue of this artist
+    pub artist_id: Option<Uuid>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/payments/statistics",
+    params(PaymentStatisticsParams),
+    responses(
+        (status = 200, description = "Payment statistics for the period", body = ApiResponse<PaymentStatisticsReport>),
+        (status = 400, description = "Invalid date range"),
+        (status = 403, description = "Forbidden - Admins see platform-wide numbers, artists only their own")
+    ),
+    tag = "payments"
+)]
 pub async fn get_payment_statistics(
-    State(_controller): State<Arc<PaymentController>>,
-    Query(_params): Query<std::collections::HashMap<String, String>>,
-) -> Result<Json<ApiResponse<PaymentStatistics>>, StatusCode> {
-    let stats = PaymentStatistics {
-        total_payments: 1000,
-        total_volume: 50000.0,
-        successful_payments: 950,
-        failed_payments: 30,
-        refunded_payments: 20,
-        average_amount: 50.0,
-        currencies: std::collections::HashMap::new(),
-        payment_methods: std::collections::HashMap::new(),
+    State(controller): State<Arc<PaymentController>>,
+    Query(params): Query<PaymentStatisticsParams>,
+    claims: Claims,
+) -> Result<Json<ApiResponse<PaymentStatisticsReport>>, AppError> {
+    let service = controller.statistics_service.as_ref().ok_or_else(|| not_configured("Payment statistics"))?;
+
+    let query = PaymentStatisticsQuery {
+        start_date: params.start_date,
+        end_date: params.end_date,
+        artist_id: statistics_scope(&claims, params.artist_id)?,
     };
+    let report = service.statistics(&query).await?;
+
+    Ok(Json(ApiResponse::success(report)))
+}
 
-    Ok(Json(ApiResponse::success(stats)))
+/// Admins see platform-wide numbers (or any artist's); artists only their own revenue
+fn statistics_scope(claims: &Claims, requested_artist_id: Option<Uuid>) -> Result<Option<Uuid>, AppError> {
+    match claims.role.as_str() {
+        "admin" => Ok(requested_artist_id),
+        "artist" => {
+            let own_id = claims.subject_id()?;
+            match requested_artist_id {
+                Some(artist_id) if artist_id != own_id => {
+                    Err(AppError::Forbidden("Cannot access another artist's statistics".to_string()))
+                }
+                _ => Ok(Some(own_id)),
+            }
+        }
+        _ => Err(AppError::Forbidden("Payment statistics are restricted to admins and artists".to_string())),
+    }
 }
 
 pub async fn get_payment_analytics(
     State(_controller): State<Arc<PaymentController>>,
     Query(params): Query<std::collections::HashMap<String, String>>,
-) -> Result<Json<ApiResponse<PaymentAnalytics>>, StatusCode> {
+) -> Result<Json<ApiResponse<PaymentAnalytics>>, AppError> {
     let analytics = PaymentAnalytics {
         time_range: params.get("time_range").cloned().unwrap_or("7d".to_string()),
         daily_volume: vec![],
@@ -599,49 +842,320 @@ pub async fn get_payment_analytics(
 // ROYALTY OPERATIONS
 // =============================================================================
 
+#[utoipa::path(
+    post,
+    path = "/api/v1/royalties/distribute",
+    request_body = DistributeRoyaltiesRequest,
+    responses(
+        (status = 200, description = "Royalties distributed (or already distributed with the same terms)", body = ApiResponse<RoyaltyRunDTO>),
+        (status = 400, description = "Rules do not sum to 100% or do not match the song's royalty settings"),
+        (status = 404, description = "Song not found"),
+        (status = 409, description = "Period overlaps a distribution with different terms")
+    ),
+    tag = "payments"
+)]
 pub async fn distribute_royalties(
     State(controller): State<Arc<PaymentController>>,
-    Extension(current_user_id): Extension<Uuid>,
+    claims: Claims,
     Json(request): Json<DistributeRoyaltiesRequest>,
-) -> Result<Json<ApiResponse<DistributeRoyaltiesResponse>>, StatusCode> {
-    let command = DistributeRoyaltiesCommand {
+) -> Result<Json<ApiResponse<RoyaltyRunDTO>>, AppError> {
+    authorize_role(&claims, &[UserRole::Artist])?;
+    let service = controller.royalty_distribution_service.as_ref().ok_or_else(|| not_configured("Royalty distributions"))?;
+
+    let rules = match request.distribution_rules {
+        Some(rules) => Some(serde_json::from_value::<Vec<RoyaltySplit>>(rules)
+            .map_err(|e| AppError::ValidationError(format!("Invalid distribution_rules: {}", e)))?),
+        None => None,
+    };
+
+    let command = DistributeSongRoyalties {
         song_id: request.song_id,
-        artist_id: request.artist_id,
-        album_id: request.album_id,
         period_start: request.period_start,
         period_end: request.period_end,
         total_revenue: request.total_revenue,
         currency: request.currency,
-        distribution_rules: request.distribution_rules,
-        initiated_by: current_user_id,
+        rules,
+        initiated_by: claims.subject_id()?,
     };
 
-    let handler = &controller.royalty_command_handler;
-
-    match handler.handle_create_distribution(command).await {
-        Ok(result) => {
-            let response = DistributeRoyaltiesResponse {
-                distribution_id: result.distribution_id,
-                total_amount: result.total_amount,
-                recipient_count: result.recipient_count,
-                distributions: result.distributions,
-                status: result.status,
-                created_at: result.created_at,
-            };
-            Ok(Json(ApiResponse::success(response)))
+    match service.distribute(command).await {
+        Ok(outcome) => Ok(Json(ApiResponse::success(outcome.into()))),
+        Err(err) => {
+            tracing::error!("Royalty distribution of song {} failed: {:?}", request.song_id, err);
+            Err(payment_error(err))
         }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/royalties/runs/{run_id}",
+    params(
+        ("run_id" = Uuid, Path, description = "Royalty distribution run ID")
+    ),
+    responses(
+        (status = 200, description = "Run with its payouts", body = ApiResponse<RoyaltyRunDTO>),
+        (status = 404, description = "Run not found")
+    ),
+    tag = "payments"
+)]
+pub async fn get_royalty_run(
+    State(controller): State<Arc<PaymentController>>,
+    Path(run_id): Path<Uuid>,
+) -> Result<Json<ApiResponse<RoyaltyRunDTO>>, AppError> {
+    let service = controller.royalty_distribution_service.as_ref().ok_or_else(|| not_configured("Royalty distributions"))?;
+
+    match service.get_run(run_id).await {
+        Ok(outcome) => Ok(Json(ApiResponse::success(outcome.into()))),
+        Err(err) => Err(payment_error(err)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/royalties/runs/{run_id}/void",
+    request_body = VoidRoyaltyRunRequest,
+    params(
+        ("run_id" = Uuid, Path, description = "Royalty distribution run ID")
+    ),
+    responses(
+        (status = 200, description = "Run voided, its period can be distributed again", body = ApiResponse<RoyaltyRunDTO>),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Run not found"),
+        (status = 409, description = "Run already voided")
+    ),
+    tag = "payments"
+)]
+pub async fn void_royalty_run(
+    State(controller): State<Arc<PaymentController>>,
+    Path(run_id): Path<Uuid>,
+    claims: Claims,
+    Json(request): Json<VoidRoyaltyRunRequest>,
+) -> Result<Json<ApiResponse<RoyaltyRunDTO>>, AppError> {
+    authorize_role(&claims, &[UserRole::Admin])?;
+    let admin_id = claims.subject_id()?;
+    if request.reason.trim().is_empty() {
+        return Err(AppError::ValidationError("reason is required".to_string()));
+    }
+    let service = controller.royalty_distribution_service.as_ref().ok_or_else(|| not_configured("Royalty distributions"))?;
+
+    match service.void_run(run_id, admin_id, request.reason).await {
+        Ok(_) => match service.get_run(run_id).await {
+            Ok(outcome) => Ok(Json(ApiResponse::success(outcome.into()))),
+            Err(err) => Err(payment_error(err)),
+        },
         Err(err) => {
-            eprintln!("Distribute royalties error: {:?}", err);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            tracing::error!("Voiding royalty run {} failed: {:?}", run_id, err);
+            Err(payment_error(err))
         }
     }
 }
 
+/// Sólo el pagador (o un admin) opera sobre un pago ya creado
+async fn authorize_payer(controller: &PaymentController, claims: &Claims, payment_id: Uuid) -> Result<(), AppError> {
+    let query = GetPaymentQuery { payment_id, include_events: false };
+    let payment = controller.payment_query_handler.handle(query).await?
+        .ok_or_else(|| AppError::NotFound(format!("Payment {} not found", payment_id)))?;
+    authorize_owner(claims, OwnedResource::Payment, payment.payer_id)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/artists/{artist_id}/balance",
+    params(
+        ("artist_id" = Uuid, Path, description = "Artist ID")
+    ),
+    responses(
+        (status = 200, description = "Balance per currency: available, in payout and paid out", body = ApiResponse<Vec<ArtistBalanceDTO>>),
+        (status = 403, description = "Forbidden - Own balance or admin only")
+    ),
+    tag = "payments"
+)]
+pub async fn get_artist_balance(
+    State(controller): State<Arc<PaymentController>>,
+    Path(artist_id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<ApiResponse<Vec<ArtistBalanceDTO>>>, AppError> {
+    authorize_owner(&claims, OwnedResource::Payout, artist_id)?;
+    let service = controller.artist_payout_service.as_ref().ok_or_else(|| not_configured("Artist payouts"))?;
+
+    match service.balance(artist_id).await {
+        Ok(balances) => Ok(Json(ApiResponse::success(balances.into_iter().map(ArtistBalanceDTO::from).collect()))),
+        Err(err) => Err(payment_error(err)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/artists/{artist_id}/payouts",
+    params(
+        ("artist_id" = Uuid, Path, description = "Artist ID")
+    ),
+    responses(
+        (status = 200, description = "Payouts of the artist, newest first", body = ApiResponse<Vec<ArtistPayoutDTO>>),
+        (status = 403, description = "Forbidden - Own payouts or admin only")
+    ),
+    tag = "payments"
+)]
+pub async fn get_artist_payouts(
+    State(controller): State<Arc<PaymentController>>,
+    Path(artist_id): Path<Uuid>,
+    claims: Claims,
+) -> Result<Json<ApiResponse<Vec<ArtistPayoutDTO>>>, AppError> {
+    authorize_owner(&claims, OwnedResource::Payout, artist_id)?;
+    let service = controller.artist_payout_service.as_ref().ok_or_else(|| not_configured("Artist payouts"))?;
+
+    match service.payouts(artist_id).await {
+        Ok(payouts) => Ok(Json(ApiResponse::success(payouts.into_iter().map(ArtistPayoutDTO::from).collect()))),
+        Err(err) => Err(payment_error(err)),
+    }
+}
+
+#[utoipa::path(
+    put,
+    path = "/api/v1/artists/{artist_id}/payout-method",
+    request_body = RegisterPayoutMethodRequest,
+    params(
+        ("artist_id" = Uuid, Path, description = "Artist ID")
+    ),
+    responses(
+        (status = 200, description = "Payout method registered", body = ApiResponse<PayoutMethodDTO>),
+        (status = 400, description = "Invalid Stripe Connect account or Solana address"),
+        (status = 403, description = "Forbidden - Own payout method or admin only")
+    ),
+    tag = "payments"
+)]
+pub async fn register_payout_method(
+    State(controller): State<Arc<PaymentController>>,
+    Path(artist_id): Path<Uuid>,
+    claims: Claims,
+    Json(request): Json<RegisterPayoutMethodRequest>,
+) -> Result<Json<ApiResponse<PayoutMethodDTO>>, AppError> {
+    authorize_owner(&claims, OwnedResource::Payout, artist_id)?;
+    let service = controller.artist_payout_service.as_ref().ok_or_else(|| not_configured("Artist payouts"))?;
+
+    let method = match request {
+        RegisterPayoutMethodRequest::StripeConnect { account_id } => PayoutMethod::stripe_connect(&account_id),
+        RegisterPayoutMethodRequest::SolanaWallet { address } => PayoutMethod::solana_wallet(&address),
+    }
+    .map_err(payment_error)?;
+
+    match service.register_payout_method(artist_id, method).await {
+        Ok(method) => Ok(Json(ApiResponse::success(method.into()))),
+        Err(err) => Err(payment_error(err)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/artists/{artist_id}/royalty-advance",
+    request_body = RequestRoyaltyAdvanceRequest,
+    params(
+        ("artist_id" = Uuid, Path, description = "Artist ID")
+    ),
+    responses(
+        (status = 201, description = "Advance granted and credited to the artist balance", body = ApiResponse<RoyaltyAdvanceDTO>),
+        (status = 400, description = "Invalid amount or repayment rate, or projected royalties do not cover 3x the monthly repayment"),
+        (status = 403, description = "Forbidden - Own advance or admin only"),
+        (status = 409, description = "The artist is still repaying an advance"),
+        (status = 422, description = "Fewer than 3 months of royalty history (INSUFFICIENT_ROYALTY_HISTORY)")
+    ),
+    tag = "payments"
+)]
+pub async fn request_royalty_advance(
+    State(controller): State<Arc<PaymentController>>,
+    Path(artist_id): Path<Uuid>,
+    claims: Claims,
+    Json(request): Json<RequestRoyaltyAdvanceRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<RoyaltyAdvanceDTO>>), AppError> {
+    authorize_owner(&claims, OwnedResource::Payout, artist_id)?;
+    let service = controller.royalty_advance_service.as_ref().ok_or_else(|| not_configured("Royalty advances"))?;
+
+    let advance_amount = rust_decimal::Decimal::try_from(request.advance_amount)
+        .map_err(|e| AppError::ValidationError(format!("Invalid advance amount: {}", e)))?;
+    let advance = service
+        .request_advance(
+            artist_id,
+            advance_amount,
+            request.repayment_rate.unwrap_or(DEFAULT_REPAYMENT_RATE),
+            request.currency.unwrap_or(Currency::USD),
+            Utc::now(),
+        )
+        .await
+        .map_err(payment_error)?;
+
+    Ok((StatusCode::CREATED, Json(ApiResponse::success(advance.into()))))
+}
+
+// =============================================================================
+// OUTBOUND WEBHOOKS (admin)
+// =============================================================================
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct WebhookDeliveriesParams {
+    /// Attempts to return, newest first (default 50, max 200)
+    pub limit: Option<u32>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/v1/admin/webhooks",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Webhook registered; the signing secret is only shown here", body = ApiResponse<WebhookDTO>),
+        (status = 400, description = "Invalid URL or unknown event"),
+        (status = 403, description = "Forbidden - Admin only")
+    ),
+    tag = "payments"
+)]
+pub async fn create_webhook(
+    State(controller): State<Arc<PaymentController>>,
+    claims: Claims,
+    Json(request): Json<CreateWebhookRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<WebhookDTO>>), AppError> {
+    authorize_role(&claims, &[UserRole::Admin])?;
+    let service = controller.webhook_service.as_ref().ok_or_else(|| not_configured("Webhooks"))?;
+
+    let webhook = service
+        .register(&request.endpoint_url, request.subscribed_events, claims.subject_id()?)
+        .await?;
+    Ok((StatusCode::CREATED, Json(ApiResponse::success(WebhookDTO::with_secret(webhook)))))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/v1/admin/webhooks/{webhook_id}/deliveries",
+    params(
+        ("webhook_id" = Uuid, Path, description = "Webhook ID"),
+        WebhookDeliveriesParams
+    ),
+    responses(
+        (status = 200, description = "Delivery attempts, newest first", body = ApiResponse<Vec<WebhookDeliveryDTO>>),
+        (status = 403, description = "Forbidden - Admin only"),
+        (status = 404, description = "Webhook not found")
+    ),
+    tag = "payments"
+)]
+pub async fn list_webhook_deliveries(
+    State(controller): State<Arc<PaymentController>>,
+    Path(webhook_id): Path<Uuid>,
+    Query(params): Query<WebhookDeliveriesParams>,
+    claims: Claims,
+) -> Result<Json<ApiResponse<Vec<WebhookDeliveryDTO>>>, AppError> {
+    authorize_role(&claims, &[UserRole::Admin])?;
+    let service = controller.webhook_service.as_ref().ok_or_else(|| not_configured("Webhooks"))?;
+
+    let limit = params.limit.unwrap_or(50).clamp(1, 200);
+    let deliveries = service.deliveries(webhook_id, limit).await?;
+    Ok(Json(ApiResponse::success(deliveries.into_iter().map(WebhookDeliveryDTO::from).collect())))
+}
+
 pub async fn process_royalty_distribution(
     State(_controller): State<Arc<PaymentController>>,
     Path(_distribution_id): Path<Uuid>,
-    Extension(_current_user_id): Extension<Uuid>,
-) -> Result<Json<ApiResponse<()>>, StatusCode> {
+    claims: Claims,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    authorize_role(&claims, &[UserRole::Admin])?;
     // Process royalty distribution logic
     Ok(Json(ApiResponse::success(())))
 }
@@ -649,7 +1163,7 @@ pub async fn process_royalty_distribution(
 pub async fn get_royalty_distributions(
     State(_controller): State<Arc<PaymentController>>,
     Query(_params): Query<std::collections::HashMap<String, String>>,
-) -> Result<Json<ApiResponse<()>>, StatusCode> {
+) -> Result<Json<ApiResponse<()>>, AppError> {
     Ok(Json(ApiResponse::success(())))
 }
 
@@ -657,7 +1171,7 @@ pub async fn get_artist_revenue_summary(
     State(_controller): State<Arc<PaymentController>>,
     Path(_artist_id): Path<Uuid>,
     Query(_params): Query<std::collections::HashMap<String, String>>,
-) -> Result<Json<ApiResponse<()>>, StatusCode> {
+) -> Result<Json<ApiResponse<()>>, AppError> {
     Ok(Json(ApiResponse::success(())))
 }
 
@@ -667,15 +1181,16 @@ pub async fn get_artist_revenue_summary(
 
 pub async fn create_wallet(
     State(controller): State<Arc<PaymentController>>,
-    Extension(current_user_id): Extension<Uuid>,
+    claims: Claims,
     Json(request): Json<CreateWalletRequest>,
-) -> Result<Json<ApiResponse<CreateWalletResponse>>, StatusCode> {
+) -> Result<Json<ApiResponse<CreateWalletResponse>>, AppError> {
+    authorize_owner(&claims, OwnedResource::Wallet, request.user_id)?;
     let command = CreateWalletCommand {
         user_id: request.user_id,
         wallet_type: request.wallet_type,
         currency: request.currency,
         is_primary: request.is_primary,
-        created_by: current_user_id,
+        created_by: claims.subject_id()?,
     };
 
     let handler = &controller.wallet_command_handler;
@@ -694,8 +1209,8 @@ pub async fn create_wallet(
             Ok(Json(ApiResponse::success(response)))
         }
         Err(err) => {
-            eprintln!("Create wallet error: {:?}", err);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            tracing::error!("Create wallet error: {:?}", err);
+            Err(err)
         }
     }
 }
@@ -703,8 +1218,8 @@ pub async fn create_wallet(
 pub async fn list_wallets(
     State(_controller): State<Arc<PaymentController>>,
     Query(_params): Query<std::collections::HashMap<String, String>>,
-    Extension(_current_user_id): Extension<Uuid>,
-) -> Result<Json<ApiResponse<Vec<CreateWalletResponse>>>, StatusCode> {
+    _claims: Claims,
+) -> Result<Json<ApiResponse<Vec<CreateWalletResponse>>>, AppError> {
     // List user's wallets
     Ok(Json(ApiResponse::success(vec![])))
 }
@@ -712,26 +1227,26 @@ pub async fn list_wallets(
 pub async fn get_wallet(
     State(_controller): State<Arc<PaymentController>>,
     Path(_wallet_id): Path<Uuid>,
-    Extension(_current_user_id): Extension<Uuid>,
-) -> Result<Json<ApiResponse<CreateWalletResponse>>, StatusCode> {
+    _claims: Claims,
+) -> Result<Json<ApiResponse<CreateWalletResponse>>, AppError> {
     // Get wallet details
-    Err(StatusCode::NOT_FOUND)
+    Err(AppError::NotFound("Wallet not found".to_string()))
 }
 
 pub async fn update_wallet(
     State(_controller): State<Arc<PaymentController>>,
     Path(_wallet_id): Path<Uuid>,
-    Extension(_current_user_id): Extension<Uuid>,
-) -> Result<Json<ApiResponse<CreateWalletResponse>>, StatusCode> {
+    _claims: Claims,
+) -> Result<Json<ApiResponse<CreateWalletResponse>>, AppError> {
     // Update wallet
-    Err(StatusCode::NOT_FOUND)
+    Err(AppError::NotFound("Wallet not found".to_string()))
 }
 
 pub async fn get_wallet_balance(
     State(_controller): State<Arc<PaymentController>>,
     Path(wallet_id): Path<Uuid>,
-    Extension(_current_user_id): Extension<Uuid>,
-) -> Result<Json<ApiResponse<WalletBalanceResponse>>, StatusCode> {
+    _claims: Claims,
+) -> Result<Json<ApiResponse<WalletBalanceResponse>>, AppError> {
     let response = WalletBalanceResponse {
         wallet_id,
         balance: 100.0,
@@ -750,7 +1265,7 @@ pub async fn get_wallet_balance(
 
 pub async fn list_payment_gateways(
     State(_controller): State<Arc<PaymentController>>,
-) -> Result<Json<ApiResponse<Vec<String>>>, StatusCode> {
+) -> Result<Json<ApiResponse<Vec<String>>>, AppError> {
     let gateways = vec![
         "stripe".to_string(),
         "paypal".to_string(),
@@ -764,8 +1279,8 @@ pub async fn list_payment_gateways(
 pub async fn process_gateway_payment(
     State(_controller): State<Arc<PaymentController>>,
     Path(_gateway_id): Path<String>,
-    Extension(_current_user_id): Extension<Uuid>,
-) -> Result<Json<ApiResponse<()>>, StatusCode> {
+    _claims: Claims,
+) -> Result<Json<ApiResponse<()>>, AppError> {
     // Process payment through specific gateway
     Ok(Json(ApiResponse::success(())))
 }
@@ -778,11 +1293,11 @@ pub async fn stripe_webhook(
     State(controller): State<Arc<PaymentController>>,
     headers: axum::http::HeaderMap,
     body: String,
-) -> Result<Json<ApiResponse<()>>, StatusCode> {
+) -> Result<Json<ApiResponse<()>>, AppError> {
     let signature = headers
         .get("stripe-signature")
         .and_then(|h| h.to_str().ok())
-        .ok_or(StatusCode::BAD_REQUEST)?;
+        .ok_or_else(|| AppError::ValidationError("Missing stripe-signature header".to_string()))?;
 
     // If queue processor is available, enqueue for async processing
     if let Some(ref queue_processor) = controller.webhook_queue_processor {
@@ -809,7 +1324,7 @@ pub async fn stripe_webhook(
         }
         Err(e) => {
             tracing::error!("Stripe webhook error: {:?}", e);
-            Err(StatusCode::BAD_REQUEST)
+            Err(AppError::ValidationError(format!("Stripe webhook rejected: {}", e)))
         }
     }
 }
@@ -818,11 +1333,11 @@ pub async fn paypal_webhook(
     State(controller): State<Arc<PaymentController>>,
     headers: axum::http::HeaderMap,
     body: String,
-) -> Result<Json<ApiResponse<()>>, StatusCode> {
+) -> Result<Json<ApiResponse<()>>, AppError> {
     let signature = headers
         .get("paypal-transmission-sig")
         .and_then(|h| h.to_str().ok())
-        .ok_or(StatusCode::BAD_REQUEST)?;
+        .ok_or_else(|| AppError::ValidationError("Missing paypal-transmission-sig header".to_string()))?;
 
     // If queue processor is available, enqueue for async processing
     if let Some(ref queue_processor) = controller.webhook_queue_processor {
@@ -849,7 +1364,7 @@ pub async fn paypal_webhook(
         }
         Err(e) => {
             tracing::error!("PayPal webhook error: {:?}", e);
-            Err(StatusCode::BAD_REQUEST)
+            Err(AppError::ValidationError(format!("PayPal webhook rejected: {}", e)))
         }
     }
 }
@@ -858,11 +1373,11 @@ pub async fn coinbase_webhook(
     State(controller): State<Arc<PaymentController>>,
     headers: axum::http::HeaderMap,
     body: String,
-) -> Result<Json<ApiResponse<()>>, StatusCode> {
+) -> Result<Json<ApiResponse<()>>, AppError> {
     let signature = headers
         .get("x-cc-webhook-signature")
         .and_then(|h| h.to_str().ok())
-        .ok_or(StatusCode::BAD_REQUEST)?;
+        .ok_or_else(|| AppError::ValidationError("Missing x-cc-webhook-signature header".to_string()))?;
 
     // If queue processor is available, enqueue for async processing
     if let Some(ref queue_processor) = controller.webhook_queue_processor {
@@ -888,7 +1403,7 @@ pub async fn coinbase_webhook(
         }
         Err(e) => {
             tracing::error!("Coinbase webhook error: {:?}", e);
-            Err(StatusCode::BAD_REQUEST)
+            Err(AppError::ValidationError(format!("Coinbase webhook rejected: {}", e)))
         }
     }
 }
@@ -902,18 +1417,18 @@ pub async fn coinbase_webhook(
 pub async fn reconcile_payment(
     State(controller): State<Arc<PaymentController>>,
     Path(payment_id): Path<Uuid>,
-) -> Result<Json<ApiResponse<ReconciliationResult>>, StatusCode> {
+) -> Result<Json<ApiResponse<ReconciliationResult>>, AppError> {
     if let Some(ref queue_processor) = controller.webhook_queue_processor {
         match queue_processor.reconcile_payment(payment_id).await {
             Ok(result) => Ok(Json(ApiResponse::success(result))),
             Err(e) => {
                 tracing::error!("Reconciliation error: {:?}", e);
-                Err(StatusCode::INTERNAL_SERVER_ERROR)
+                Err(e)
             }
         }
     } else {
         // Fallback or not implemented
-        Err(StatusCode::NOT_IMPLEMENTED)
+        Err(not_configured("Webhook queues"))
     }
 }
 
@@ -922,14 +1437,14 @@ pub async fn generic_webhook(
     Path(gateway): Path<String>,
     headers: axum::http::HeaderMap,
     body: String,
-) -> Result<Json<ApiResponse<()>>, StatusCode> {
+) -> Result<Json<ApiResponse<()>>, AppError> {
     // Try to find signature in common header names
     let signature = headers
         .get("x-webhook-signature")
         .or_else(|| headers.get("signature"))
         .or_else(|| headers.get("x-signature"))
         .and_then(|h| h.to_str().ok())
-        .ok_or(StatusCode::BAD_REQUEST)?;
+        .ok_or_else(|| AppError::ValidationError("Missing webhook signature header".to_string()))?;
 
     match controller.webhook_router.route_webhook(&gateway, &body, signature).await {
         Ok(result) => {
@@ -941,7 +1456,7 @@ pub async fn generic_webhook(
         }
         Err(e) => {
             tracing::error!("Generic webhook error for gateway {}: {:?}", gateway, e);
-            Err(StatusCode::BAD_REQUEST)
+            Err(AppError::ValidationError(format!("{} webhook rejected: {}", gateway, e)))
         }
     }
 }
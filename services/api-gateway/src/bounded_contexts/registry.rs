@@ -0,0 +1,242 @@
+// =============================================================================
+// BOUNDED CONTEXT REGISTRY - ESTADO UNIFICADO DE LOS CONTEXTOS
+// =============================================================================
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures_util::future::join_all;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::shared::domain::errors::AppError;
+
+/// Tiempo máximo que se espera al health check de cada contexto
+pub const DEFAULT_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum HealthStatus {
+    Healthy,
+    Degraded,
+    Unhealthy,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BoundedContextHealth {
+    pub name: String,
+    pub status: HealthStatus,
+    pub message: Option<String>,
+    pub latency_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BoundedContextHealthReport {
+    pub checked_at: DateTime<Utc>,
+    /// `Healthy` solo si todos los contextos lo están
+    pub status: HealthStatus,
+    pub contexts: HashMap<String, BoundedContextHealth>,
+}
+
+impl BoundedContextHealthReport {
+    pub fn new(contexts: Vec<BoundedContextHealth>) -> Self {
+        let healthy = contexts.iter().filter(|c| c.status == HealthStatus::Healthy).count();
+        let unhealthy = contexts.iter().filter(|c| c.status == HealthStatus::Unhealthy).count();
+        let status = if healthy == contexts.len() {
+            HealthStatus::Healthy
+        } else if unhealthy == contexts.len() {
+            HealthStatus::Unhealthy
+        } else {
+            HealthStatus::Degraded
+        };
+
+        Self {
+            checked_at: Utc::now(),
+            status,
+            contexts: contexts.into_iter().map(|c| (c.name.clone(), c)).collect(),
+        }
+    }
+}
+
+/// Health check propio de un bounded context
+#[async_trait]
+pub trait BoundedContextHealthCheck: Send + Sync {
+    fn name(&self) -> &str;
+    /// `Err` marca el contexto como `Unhealthy` con el mensaje del error
+    async fn health_check(&self) -> Result<HealthStatus, AppError>;
+}
+
+/// Contexto respaldado por Postgres: sano si su tabla principal responde
+pub struct PostgresContextHealthCheck {
+    name: String,
+    pool: PgPool,
+    table: &'static str,
+}
+
+impl PostgresContextHealthCheck {
+    pub fn new(name: impl Into<String>, pool: PgPool, table: &'static str) -> Self {
+        Self { name: name.into(), pool, table }
+    }
+}
+
+#[async_trait]
+impl BoundedContextHealthCheck for PostgresContextHealthCheck {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn health_check(&self) -> Result<HealthStatus, AppError> {
+        sqlx::query(&format!("SELECT 1 FROM {} LIMIT 1", self.table))
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("{} is not reachable: {}", self.table, e)))?;
+        Ok(HealthStatus::Healthy)
+    }
+}
+
+pub struct BoundedContextRegistry {
+    contexts: Vec<Arc<dyn BoundedContextHealthCheck>>,
+    timeout: Duration,
+}
+
+impl BoundedContextRegistry {
+    pub fn new() -> Self {
+        Self {
+            contexts: Vec::new(),
+            timeout: DEFAULT_HEALTH_CHECK_TIMEOUT,
+        }
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn register(mut self, context: Arc<dyn BoundedContextHealthCheck>) -> Self {
+        self.contexts.push(context);
+        self
+    }
+
+    /// Registro con los contextos que persisten en Postgres
+    pub fn for_database(pool: PgPool) -> Self {
+        [
+            ("user", "users"),
+            ("music", "songs"),
+            ("payment", "payments"),
+            ("campaign", "campaigns"),
+            ("listen_reward", "listen_sessions"),
+            ("fan_ventures", "artist_ventures"),
+            ("notifications", "notifications"),
+            ("fan_loyalty", "fan_loyalty_events"),
+        ]
+        .into_iter()
+        .fold(Self::new(), |registry, (name, table)| {
+            registry.register(Arc::new(PostgresContextHealthCheck::new(name, pool.clone(), table)))
+        })
+    }
+
+    /// Ejecutar todos los health checks a la vez, cada uno con su timeout
+    pub async fn health_check_all(&self) -> BoundedContextHealthReport {
+        let checks = self.contexts.iter().map(|context| self.check(context.as_ref()));
+        BoundedContextHealthReport::new(join_all(checks).await)
+    }
+
+    async fn check(&self, context: &dyn BoundedContextHealthCheck) -> BoundedContextHealth {
+        let started = Instant::now();
+        let (status, message) = match tokio::time::timeout(self.timeout, context.health_check()).await {
+            Ok(Ok(status)) => (status, None),
+            Ok(Err(e)) => (HealthStatus::Unhealthy, Some(e.to_string())),
+            Err(_) => (
+                HealthStatus::Unhealthy,
+                Some(format!("Health check timed out after {} ms", self.timeout.as_millis())),
+            ),
+        };
+
+        BoundedContextHealth {
+            name: context.name().to_string(),
+            status,
+            message,
+            latency_ms: started.elapsed().as_millis() as u64,
+        }
+    }
+}
+
+impl Default for BoundedContextRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct StubContext {
+        name: &'static str,
+        result: Result<HealthStatus, &'static str>,
+        delay: Duration,
+    }
+
+    impl StubContext {
+        fn new(name: &'static str, result: Result<HealthStatus, &'static str>) -> Arc<Self> {
+            Arc::new(Self { name, result, delay: Duration::ZERO })
+        }
+    }
+
+    #[async_trait]
+    impl BoundedContextHealthCheck for StubContext {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn health_check(&self) -> Result<HealthStatus, AppError> {
+            tokio::time::sleep(self.delay).await;
+            self.result.map_err(|e| AppError::DatabaseError(e.to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_all_healthy_contexts_report_healthy() {
+        let registry = BoundedContextRegistry::new()
+            .register(StubContext::new("user", Ok(HealthStatus::Healthy)))
+            .register(StubContext::new("music", Ok(HealthStatus::Healthy)));
+
+        let report = registry.health_check_all().await;
+
+        assert_eq!(report.status, HealthStatus::Healthy);
+        assert_eq!(report.contexts.len(), 2);
+        assert!(report.contexts.values().all(|c| c.message.is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_one_unhealthy_context_degrades_overall_status() {
+        let registry = BoundedContextRegistry::new()
+            .register(StubContext::new("user", Ok(HealthStatus::Healthy)))
+            .register(StubContext::new("payment", Err("connection refused")));
+
+        let report = registry.health_check_all().await;
+
+        assert_eq!(report.status, HealthStatus::Degraded);
+        let payment = &report.contexts["payment"];
+        assert_eq!(payment.status, HealthStatus::Unhealthy);
+        assert!(payment.message.as_deref().unwrap().contains("connection refused"));
+        assert_eq!(report.contexts["user"].status, HealthStatus::Healthy);
+    }
+
+    #[tokio::test]
+    async fn test_slow_context_times_out_as_unhealthy() {
+        let slow = Arc::new(StubContext { name: "campaign", result: Ok(HealthStatus::Healthy), delay: Duration::from_secs(5) });
+        let registry = BoundedContextRegistry::new()
+            .with_timeout(Duration::from_millis(50))
+            .register(slow);
+
+        let report = registry.health_check_all().await;
+
+        assert_eq!(report.status, HealthStatus::Unhealthy);
+        let campaign = &report.contexts["campaign"];
+        assert_eq!(campaign.status, HealthStatus::Unhealthy);
+        assert!(campaign.message.as_deref().unwrap().contains("timed out"));
+        assert!(campaign.latency_ms < 5000);
+    }
+}
@@ -16,12 +16,16 @@ pub struct CreateUserCommand {
     pub bio: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+/// Partial profile update with JSON merge semantics: `None` leaves the field
+/// as it is, `Some(None)` clears it and `Some(Some(value))` sets it
+#[derive(Debug, Default, Deserialize)]
 pub struct UpdateUserCommand {
     pub user_id: Uuid,
-    pub display_name: Option<String>,
-    pub bio: Option<String>,
-    pub profile_image_url: Option<String>,
+    pub username: Option<String>,
+    pub display_name: Option<Option<String>>,
+    pub bio: Option<Option<String>>,
+    pub profile_image_url: Option<Option<String>>,
+    pub wallet_address: Option<Option<String>>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -56,6 +60,7 @@ pub struct UserResponse {
     pub role: String,
     pub is_verified: bool,
     pub is_active: bool,
+    pub wallet_address: Option<String>,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
 
@@ -0,0 +1,42 @@
+// Listening Preferences Refresh
+//
+// Folds new listen events into the preference vector of every user who has
+// accumulated `PREFERENCES_REFRESH_EVERY_LISTENS` of them since the last refresh.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::bounded_contexts::user::infrastructure::listening_preferences::PostgresListeningPreferences;
+
+/// Users refreshed per run; the rest wait for the next tick
+pub const PREFERENCES_REFRESH_BATCH: i64 = 500;
+
+/// Worker que recalcula los vectores de preferencias pendientes
+pub struct ListeningPreferencesRefreshJob {
+    preferences: Arc<PostgresListeningPreferences>,
+    interval: Duration,
+}
+
+impl ListeningPreferencesRefreshJob {
+    pub fn new(preferences: Arc<PostgresListeningPreferences>, interval: Duration) -> Self {
+        Self { preferences, interval }
+    }
+
+    pub fn start(&self) -> tokio::task::JoinHandle<()> {
+        let preferences = Arc::clone(&self.preferences);
+        let interval = self.interval;
+
+        tokio::spawn(async move {
+            tracing::info!("🚀 Listening preferences refresh job started");
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match preferences.refresh_due(PREFERENCES_REFRESH_BATCH).await {
+                    Ok(count) if count > 0 => tracing::info!("✅ Refreshed listening preferences of {} users", count),
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Listening preferences refresh failed: {}", e),
+                }
+            }
+        })
+    }
+}
@@ -4,6 +4,7 @@ pub mod queries;
 pub mod handlers;
 pub mod services;
 pub mod events;
+pub mod listening_preferences;
 
 // Re-export main types
 pub use dtos::{
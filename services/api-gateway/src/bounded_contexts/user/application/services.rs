@@ -1,10 +1,11 @@
 // User Application Services
 // This module contains the main application services for user operations
 
+use crate::bounded_contexts::orchestrator::{DomainEvent as IntegrationEvent, EventBus};
 use crate::bounded_contexts::user::domain::{
-    entities::User,
+    entities::{User, USERNAME_CHANGE_COOLDOWN_DAYS},
     aggregates::UserAggregate,
-    value_objects::{Email, Username, PasswordHash, ProfileUrl, UserId},
+    value_objects::{Email, Username, PasswordHash, ProfileUrl, UserId, WalletAddress},
     repository::UserRepository,
     services::{UserDomainService, DefaultUserDomainService},
 };
@@ -14,8 +15,15 @@ use crate::bounded_contexts::user::application::handlers::{
     UserCommandHandler, UserQueryHandler,
 };
 use crate::shared::domain::errors::AppError;
+use crate::bounded_contexts::user::infrastructure::data_export::{PostgresUserDataExports, UserDataExport};
+use crate::bounded_contexts::user::infrastructure::listening_preferences::{PostgresListeningPreferences, StoredListeningPreferences};
+use crate::shared::infrastructure::auth::{
+    PasswordService, RefreshTokenService, TwoFactorService, VerifiedWallet, WalletAuthService, WalletChain,
+};
+use crate::shared::infrastructure::auth::refresh_tokens::REVOKED_ON_ACCOUNT_DELETION;
 use crate::shared::infrastructure::clients::facial_recognition_client::FacialRecognitionClient;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use serde_json::Value;
 use uuid::Uuid;
@@ -43,8 +51,24 @@ pub struct UserApplicationService<R: UserRepository> {
     pub(crate) repository: Arc<R>,
     domain_service: Arc<dyn UserDomainService + Send + Sync>,
     pub facial_client: Option<Arc<FacialRecognitionClient>>,
+    /// Refresh-token sessions; without it login only hands out access tokens
+    pub sessions: Option<Arc<RefreshTokenService>>,
+    /// Sign-in with Solana/Ethereum; without it wallet login is disabled
+    pub wallet_auth: Option<Arc<WalletAuthService>>,
+    /// TOTP second factor; without it 2FA cannot be set up and login never asks for it
+    pub two_factor: Option<Arc<TwoFactorService>>,
+    /// Personal data exports; without it exports cannot be requested
+    pub data_exports: Option<Arc<PostgresUserDataExports>>,
+    /// Listening preference vectors; without it they cannot be read
+    pub listening_preferences: Option<Arc<PostgresListeningPreferences>>,
+    /// Integration bus for `UserProfileUpdated` and `UserDeletionRequested`
+    event_bus: Option<Arc<dyn EventBus>>,
 }
 
+/// Password hash of accounts created by wallet login: it never verifies, so
+/// they can only sign in with the wallet until a password is set
+const WALLET_ONLY_PASSWORD_HASH: &str = "!wallet-only";
+
 impl<R: UserRepository + 'static> UserApplicationService<R> {
     pub fn new(repository: Arc<R>, facial_client: Option<Arc<FacialRecognitionClient>>) -> Self {
         let domain_service = Arc::new(DefaultUserDomainService::new(repository.clone()));
@@ -52,6 +76,280 @@ impl<R: UserRepository + 'static> UserApplicationService<R> {
             repository,
             domain_service,
             facial_client,
+            sessions: None,
+            wallet_auth: None,
+            two_factor: None,
+            data_exports: None,
+            listening_preferences: None,
+            event_bus: None,
+        }
+    }
+
+    pub fn with_sessions(mut self, sessions: Arc<RefreshTokenService>) -> Self {
+        self.sessions = Some(sessions);
+        self
+    }
+
+    pub fn with_wallet_auth(mut self, wallet_auth: Arc<WalletAuthService>) -> Self {
+        self.wallet_auth = Some(wallet_auth);
+        self
+    }
+
+    pub fn with_two_factor(mut self, two_factor: Arc<TwoFactorService>) -> Self {
+        self.two_factor = Some(two_factor);
+        self
+    }
+
+    pub fn with_data_exports(mut self, data_exports: Arc<PostgresUserDataExports>) -> Self {
+        self.data_exports = Some(data_exports);
+        self
+    }
+
+    pub fn with_listening_preferences(mut self, listening_preferences: Arc<PostgresListeningPreferences>) -> Self {
+        self.listening_preferences = Some(listening_preferences);
+        self
+    }
+
+    pub fn with_event_bus(mut self, event_bus: Arc<dyn EventBus>) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Account of a verified wallet. With `link_to` the wallet is linked to that
+    /// account; otherwise the account it is already linked to is used, or a new
+    /// one is created for it.
+    pub async fn sign_in_with_wallet(&self, wallet: &VerifiedWallet, link_to: Option<Uuid>) -> Result<User, AppError> {
+        let address = WalletAddress::new(wallet.address.clone()).map_err(AppError::ValidationError)?;
+        let linked = self.repository.find_by_wallet_address(&address).await?;
+
+        if let Some(user_id) = link_to {
+            if let Some(other) = linked.as_ref().filter(|aggregate| aggregate.user.id.value() != user_id) {
+                tracing::warn!("Wallet {} already linked to user {}", address.value(), other.user.id);
+                return Err(AppError::ValidationError("Wallet already linked to another account".to_string()));
+            }
+            let mut aggregate = self.repository.find_by_id(&UserId::from_uuid(user_id)).await?
+                .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+            aggregate.link_wallet(address).map_err(AppError::ValidationError)?;
+            self.repository.update(&aggregate).await?;
+            return Ok(aggregate.user);
+        }
+
+        if let Some(aggregate) = linked {
+            return Ok(aggregate.user);
+        }
+
+        // Cuenta nueva: username derivado de la dirección y email de marcador
+        // hasta que el usuario registre uno propio
+        let prefix = match wallet.chain {
+            WalletChain::Solana => "sol",
+            WalletChain::Ethereum => "eth",
+        };
+        let bare_address = address.value().trim_start_matches("0x");
+        let username_for = |len: usize| {
+            Username::new(format!("{}_{}", prefix, bare_address.chars().take(len).collect::<String>()))
+                .map_err(AppError::ValidationError)
+        };
+        let mut username = username_for(12)?;
+        if self.repository.username_exists(&username).await? {
+            username = username_for(26)?;
+        }
+        let email = Email::new(format!("wallet-{}@wallet.vibestream.app", Uuid::new_v4().simple()))
+            .map_err(AppError::ValidationError)?;
+        let mut aggregate = UserAggregate::create(email, username, PasswordHash::new(WALLET_ONLY_PASSWORD_HASH.to_string()))
+            .map_err(AppError::ValidationError)?;
+        aggregate.link_wallet(address).map_err(AppError::ValidationError)?;
+        self.repository.save(&aggregate).await?;
+        Ok(aggregate.user)
+    }
+
+    /// Apply a partial profile update. Only the fields in the command change;
+    /// a username change needs a free name and respects the cooldown, and a
+    /// wallet must have a valid checksum and not belong to another account.
+    pub async fn update_profile(&self, command: UpdateUserCommand, now: DateTime<Utc>) -> Result<UserAggregate, AppError> {
+        let mut aggregate = self.repository.find_by_id(&UserId::from_uuid(command.user_id)).await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+        let previous_username = aggregate.user.username.value().to_string();
+        let mut changed_fields = Vec::new();
+
+        if let Some(username) = command.username {
+            let username = Username::new(username).map_err(AppError::ValidationError)?;
+            if username != aggregate.user.username {
+                if let Some(allowed_at) = aggregate.user.next_username_change_at(now) {
+                    return Err(AppError::DomainRuleViolation(format!(
+                        "Username can only be changed once every {} days (next change: {})",
+                        USERNAME_CHANGE_COOLDOWN_DAYS,
+                        allowed_at.format("%Y-%m-%d")
+                    )));
+                }
+                if self.repository.username_exists(&username).await? {
+                    return Err(AppError::ConflictError("Username already taken".to_string()));
+                }
+                aggregate.change_username(username, now).map_err(AppError::DomainRuleViolation)?;
+                changed_fields.push("username");
+            }
+        }
+
+        let display_name = match command.display_name {
+            Some(display_name) => {
+                changed_fields.push("display_name");
+                display_name.map(|name| name.trim().to_string()).filter(|name| !name.is_empty())
+            }
+            None => aggregate.profile.display_name.clone(),
+        };
+        let bio = match command.bio {
+            Some(bio) => {
+                changed_fields.push("bio");
+                bio
+            }
+            None => aggregate.profile.bio.clone(),
+        };
+        let avatar_url = match command.profile_image_url {
+            Some(url) => {
+                changed_fields.push("avatar_url");
+                url.map(ProfileUrl::new).transpose().map_err(AppError::ValidationError)?
+            }
+            None => aggregate.profile.avatar_url.clone(),
+        };
+        let location = aggregate.profile.location.clone();
+        let website = aggregate.profile.website.clone();
+        aggregate
+            .update_profile(display_name, bio, avatar_url, location, website)
+            .map_err(AppError::ValidationError)?;
+
+        match command.wallet_address {
+            Some(Some(address)) => {
+                let address = address.trim().to_string();
+                WalletChain::validate(&address)
+                    .map_err(|_| AppError::ValidationError("Invalid wallet address or checksum".to_string()))?;
+                let address = WalletAddress::new(address).map_err(AppError::ValidationError)?;
+                if let Some(other) = self.repository.find_by_wallet_address(&address).await? {
+                    if other.user.id != aggregate.user.id {
+                        return Err(AppError::ConflictError("Wallet already linked to another account".to_string()));
+                    }
+                }
+                if aggregate.user.wallet_address.as_ref() != Some(&address) {
+                    aggregate.link_wallet(address).map_err(AppError::ValidationError)?;
+                    changed_fields.push("wallet_address");
+                }
+            }
+            Some(None) if aggregate.user.wallet_address.is_some() => {
+                aggregate.unlink_wallet().map_err(AppError::ValidationError)?;
+                changed_fields.push("wallet_address");
+            }
+            _ => {}
+        }
+
+        self.repository.update(&aggregate).await?;
+
+        if !changed_fields.is_empty() {
+            let username = aggregate.user.username.value().to_string();
+            self.publish(IntegrationEvent::UserProfileUpdated {
+                user_id: command.user_id,
+                previous_username: Some(previous_username).filter(|previous| *previous != username),
+                username,
+                changed_fields: changed_fields.into_iter().map(str::to_string).collect(),
+                occurred_at: now,
+            })
+            .await;
+        }
+
+        Ok(aggregate)
+    }
+
+    /// Sensitive operations (account deletion, data export) need the account's
+    /// factors again, not just a session: the password and, with 2FA, a code.
+    /// Wallet-only accounts without 2FA have nothing to re-authenticate with.
+    pub async fn reauthenticate(
+        &self,
+        user_id: Uuid,
+        password: Option<&str>,
+        two_factor_code: Option<&str>,
+        now: DateTime<Utc>,
+    ) -> Result<(), AppError> {
+        let aggregate = self.repository.find_by_id(&UserId::from_uuid(user_id)).await?
+            .filter(|aggregate| aggregate.user.is_active)
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+        let password_hash = aggregate.user.password_hash.value();
+        let has_password = password_hash != WALLET_ONLY_PASSWORD_HASH;
+        let two_factor = match &self.two_factor {
+            Some(two_factor) if two_factor.is_enabled(user_id).await? => Some(two_factor),
+            _ => None,
+        };
+
+        if !has_password && two_factor.is_none() {
+            return Err(AppError::Forbidden(
+                "This operation needs a password or two-factor authentication on the account".to_string(),
+            ));
+        }
+        if has_password && !password.map_or(false, |password| PasswordService::verify_credentials(password, Some(password_hash))) {
+            return Err(AppError::AuthenticationError("Invalid password".to_string()));
+        }
+        if let Some(two_factor) = two_factor {
+            let code = two_factor_code
+                .ok_or_else(|| AppError::AuthenticationError("Two-factor code required".to_string()))?;
+            two_factor.verify(user_id, code, now).await?;
+        }
+        Ok(())
+    }
+
+    /// Delete the account: anonymize it, end its sessions and announce
+    /// `UserDeletionRequested` so every context drops or anonymizes its data.
+    /// Login is blocked from here on: the account is inactive and its
+    /// email, username and password are gone.
+    pub async fn delete_account(&self, user_id: Uuid, now: DateTime<Utc>) -> Result<(), AppError> {
+        let mut aggregate = self.repository.find_by_id(&UserId::from_uuid(user_id)).await?
+            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+        aggregate.delete_account(now).map_err(AppError::ConflictError)?;
+
+        // Los pagos e inversiones que se conservan pasan a este pseudónimo
+        let pseudonym = UserAggregate::deleted_user_pseudonym(now).map_err(AppError::InternalError)?;
+        self.repository.save(&pseudonym).await?;
+        self.repository.update(&aggregate).await?;
+
+        if let Some(sessions) = &self.sessions {
+            sessions.revoke_all(user_id, REVOKED_ON_ACCOUNT_DELETION).await?;
+        }
+        if let Some(data_exports) = &self.data_exports {
+            data_exports.delete_for_user(user_id).await?;
+        }
+
+        self.publish(IntegrationEvent::UserDeletionRequested {
+            user_id,
+            pseudonym_id: pseudonym.user.id.to_uuid(),
+            occurred_at: now,
+        })
+        .await;
+        Ok(())
+    }
+
+    /// Start a personal data export; the archive is assembled in the background
+    pub async fn request_data_export(&self, user_id: Uuid, now: DateTime<Utc>) -> Result<UserDataExport, AppError> {
+        let data_exports = self.data_exports.as_ref()
+            .ok_or_else(|| AppError::ConfigurationError("Data exports are not configured".to_string()))?;
+        let export = data_exports.create(user_id, now).await?;
+
+        let (data_exports, pending) = (Arc::clone(data_exports), export.clone());
+        tokio::spawn(async move {
+            if let Err(e) = data_exports.generate(&pending).await {
+                tracing::error!("Data export {} of user {} failed: {}", pending.id, pending.user_id, e);
+            }
+        });
+        Ok(export)
+    }
+
+    /// Listening preference vector of a user, as of its last refresh
+    pub async fn get_listening_preferences(&self, user_id: Uuid) -> Result<StoredListeningPreferences, AppError> {
+        let listening_preferences = self.listening_preferences.as_ref()
+            .ok_or_else(|| AppError::ConfigurationError("Listening preferences are not configured".to_string()))?;
+        listening_preferences.find(user_id).await?
+            .ok_or_else(|| AppError::NotFound(format!("User {} not found", user_id)))
+    }
+
+    /// El perfil ya está guardado: un fallo al publicar se registra pero no lo revierte
+    async fn publish(&self, event: IntegrationEvent) {
+        let Some(event_bus) = &self.event_bus else { return };
+        if let Err(e) = event_bus.publish(event).await {
+            tracing::error!("Failed to publish user event: {:?}", e);
         }
     }
 
@@ -113,31 +411,13 @@ impl<R: UserRepository + Send + Sync> UserCommandHandler for UserApplicationServ
             role: user_aggregate.user.role.to_string(),
             is_verified: user_aggregate.user.is_verified,
             is_active: user_aggregate.user.is_active,
+            wallet_address: user_aggregate.user.wallet_address.as_ref().map(|w| w.value().to_string()),
             created_at: user_aggregate.user.created_at,
         })
     }
     
     async fn handle_update_user(&self, command: UpdateUserCommand) -> Result<UserResponse, AppError> {
-        let user_id = UserId::from_uuid(command.user_id);
-        let mut user_aggregate = self.repository.find_by_id(&user_id).await?
-            .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
-        
-        // Update profile fields
-        if let Some(display_name) = command.display_name {
-            user_aggregate.profile.update_display_name(Some(display_name));
-        }
-        
-        if let Some(bio) = command.bio {
-            user_aggregate.profile.update_bio(Some(bio));
-        }
-        
-        if let Some(profile_image_url) = command.profile_image_url {
-            let profile_url = ProfileUrl::new(profile_image_url).map_err(|e| AppError::ValidationError(e))?;
-            user_aggregate.profile.update_avatar(Some(profile_url));
-        }
-        
-        // Save updated user
-        self.repository.update(&user_aggregate).await?;
+        let user_aggregate = self.update_profile(command, Utc::now()).await?;
         
         Ok(UserResponse {
             id: user_aggregate.user.id.to_uuid(),
@@ -150,6 +430,7 @@ impl<R: UserRepository + Send + Sync> UserCommandHandler for UserApplicationServ
             role: user_aggregate.user.role.to_string(),
             is_verified: user_aggregate.user.is_verified,
             is_active: user_aggregate.user.is_active,
+            wallet_address: user_aggregate.user.wallet_address.as_ref().map(|w| w.value().to_string()),
             created_at: user_aggregate.user.created_at,
         })
     }
@@ -169,10 +450,11 @@ impl<R: UserRepository + Send + Sync> UserCommandHandler for UserApplicationServ
         self.repository.find_by_id(&followee_id).await?
             .ok_or_else(|| AppError::NotFound("User to follow not found".to_string()))?;
         
+        // Dejar de seguir a quien no sigues no cambia nada y no es un error
         if command.follow {
-            self.repository.add_follower(&followee_id, &follower_id).await?;
+            self.repository.add_follower(&follower_id, &followee_id).await?;
         } else {
-            self.repository.remove_follower(&followee_id, &follower_id).await?;
+            self.repository.remove_follower(&follower_id, &followee_id).await?;
         }
         
         Ok(())
@@ -184,6 +466,7 @@ impl<R: UserRepository + Send + Sync> UserQueryHandler for UserApplicationServic
     async fn handle_get_user(&self, query: GetUserQuery) -> Result<UserResponse, AppError> {
         let user_id = UserId::from_uuid(query.user_id);
         let user_aggregate = self.repository.find_by_id(&user_id).await?
+            .filter(|aggregate| !aggregate.user.is_deleted())
             .ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
             
         Ok(UserResponse {
@@ -197,6 +480,7 @@ impl<R: UserRepository + Send + Sync> UserQueryHandler for UserApplicationServic
             role: user_aggregate.user.role.to_string(),
             is_verified: user_aggregate.user.is_verified,
             is_active: user_aggregate.user.is_active,
+            wallet_address: user_aggregate.user.wallet_address.as_ref().map(|w| w.value().to_string()),
             created_at: user_aggregate.user.created_at,
         })
     }
@@ -219,9 +503,176 @@ impl<R: UserRepository + Send + Sync> UserQueryHandler for UserApplicationServic
             role: user_aggregate.user.role.to_string(),
             is_verified: user_aggregate.user.is_verified,
             is_active: user_aggregate.user.is_active,
+            wallet_address: user_aggregate.user.wallet_address.as_ref().map(|w| w.value().to_string()),
             created_at: user_aggregate.user.created_at,
         }).collect();
         
         Ok(responses)
     }
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::user::infrastructure::in_memory_repository::InMemoryUserRepository;
+    use chrono::Duration;
+
+    async fn service_with_users(names: &[&str]) -> (UserApplicationService<InMemoryUserRepository>, Vec<Uuid>) {
+        let repository = Arc::new(InMemoryUserRepository::new());
+        let mut ids = Vec::new();
+        for name in names {
+            let aggregate = UserAggregate::create(
+                Email::new(format!("{}@example.com", name)).unwrap(),
+                Username::new(name.to_string()).unwrap(),
+                PasswordHash::new("hashed_password".to_string()),
+            )
+            .unwrap();
+            ids.push(aggregate.user.id.to_uuid());
+            repository.save(&aggregate).await.unwrap();
+        }
+        (UserApplicationService::new(repository, None), ids)
+    }
+
+    fn rename(user_id: Uuid, username: &str) -> UpdateUserCommand {
+        UpdateUserCommand { user_id, username: Some(username.to_string()), ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn username_change_respects_cooldown() {
+        let (service, ids) = service_with_users(&["first_name"]).await;
+        let now = Utc::now();
+
+        service.update_profile(rename(ids[0], "second_name"), now).await.unwrap();
+        let again = service.update_profile(rename(ids[0], "third_name"), now + Duration::days(29)).await;
+        assert!(matches!(again, Err(AppError::DomainRuleViolation(_))));
+
+        let later = service.update_profile(rename(ids[0], "third_name"), now + Duration::days(31)).await.unwrap();
+        assert_eq!(later.user.username.value(), "third_name");
+    }
+
+    #[tokio::test]
+    async fn taken_username_is_a_conflict() {
+        let (service, ids) = service_with_users(&["alice_music", "bob_music"]).await;
+
+        let result = service.update_profile(rename(ids[0], "bob_music"), Utc::now()).await;
+        assert!(matches!(result, Err(AppError::ConflictError(_))));
+    }
+
+    #[tokio::test]
+    async fn wallet_with_bad_checksum_is_rejected() {
+        let (service, ids) = service_with_users(&["wallet_user"]).await;
+        let command = |address: &str| UpdateUserCommand {
+            user_id: ids[0],
+            wallet_address: Some(Some(address.to_string())),
+            ..Default::default()
+        };
+
+        let tampered = service.update_profile(command("0xf39fd6e51aad88F6F4ce6aB8827279cffFb92266"), Utc::now()).await;
+        assert!(matches!(tampered, Err(AppError::ValidationError(_))));
+
+        let linked = service.update_profile(command("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266"), Utc::now()).await.unwrap();
+        assert!(linked.user.wallet_address.is_some());
+    }
+
+    #[tokio::test]
+    async fn absent_fields_are_kept_and_null_clears() {
+        let (service, ids) = service_with_users(&["merge_user"]).await;
+        let set = UpdateUserCommand {
+            user_id: ids[0],
+            display_name: Some(Some("Merge".to_string())),
+            bio: Some(Some("Bio".to_string())),
+            ..Default::default()
+        };
+        service.update_profile(set, Utc::now()).await.unwrap();
+
+        let clear_bio = UpdateUserCommand { user_id: ids[0], bio: Some(None), ..Default::default() };
+        let updated = service.update_profile(clear_bio, Utc::now()).await.unwrap();
+        assert_eq!(updated.profile.display_name.as_deref(), Some("Merge"));
+        assert_eq!(updated.profile.bio, None);
+    }
+
+    struct RecordingHandler(std::sync::Mutex<Vec<IntegrationEvent>>);
+
+    #[async_trait]
+    impl crate::bounded_contexts::orchestrator::EventHandler for RecordingHandler {
+        async fn handle(&self, event: &IntegrationEvent) -> Result<(), AppError> {
+            self.0.lock().unwrap().push(event.clone());
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn deleted_account_is_anonymized_and_announced() {
+        let (service, ids) = service_with_users(&["leaving_user"]).await;
+        let event_bus = Arc::new(crate::bounded_contexts::orchestrator::InMemoryEventBus::new());
+        let recorder = Arc::new(RecordingHandler(Default::default()));
+        event_bus.subscribe("UserDeletionRequested", recorder.clone()).await.unwrap();
+        let service = service.with_event_bus(event_bus);
+
+        service.delete_account(ids[0], Utc::now()).await.unwrap();
+
+        let events = recorder.0.lock().unwrap().clone();
+        assert_eq!(events.len(), 1);
+        let IntegrationEvent::UserDeletionRequested { user_id, pseudonym_id, .. } = &events[0] else {
+            panic!("Expected UserDeletionRequested, got {:?}", events[0]);
+        };
+        assert_eq!(*user_id, ids[0]);
+        assert_ne!(pseudonym_id, user_id);
+        let pseudonym = service.repository.find_by_id(&UserId::from_uuid(*pseudonym_id)).await.unwrap().unwrap();
+        assert!(pseudonym.user.is_deleted());
+
+        let deleted = service.repository.find_by_id(&UserId::from_uuid(ids[0])).await.unwrap().unwrap();
+        assert!(deleted.user.is_deleted() && !deleted.user.is_active);
+        assert!(service.find_user_by_email("leaving_user@example.com").await.unwrap().is_none());
+        assert!(matches!(service.delete_account(ids[0], Utc::now()).await, Err(AppError::ConflictError(_))));
+    }
+
+    #[tokio::test]
+    async fn reauthentication_needs_the_password() {
+        let repository = Arc::new(InMemoryUserRepository::new());
+        let password_hash = PasswordService::hash_password("Correct-Horse-9").unwrap();
+        let aggregate = UserAggregate::create(
+            Email::new("careful@example.com".to_string()).unwrap(),
+            Username::new("careful_user".to_string()).unwrap(),
+            PasswordHash::new(password_hash),
+        )
+        .unwrap();
+        let user_id = aggregate.user.id.to_uuid();
+        repository.save(&aggregate).await.unwrap();
+        let service = UserApplicationService::new(repository, None);
+
+        let missing = service.reauthenticate(user_id, None, None, Utc::now()).await;
+        assert!(matches!(missing, Err(AppError::AuthenticationError(_))));
+        let wrong = service.reauthenticate(user_id, Some("wrong-password"), None, Utc::now()).await;
+        assert!(matches!(wrong, Err(AppError::AuthenticationError(_))));
+        assert!(service.reauthenticate(user_id, Some("Correct-Horse-9"), None, Utc::now()).await.is_ok());
+    }
+
+    fn follow(follower_id: Uuid, followee_id: Uuid, follow: bool) -> FollowUserCommand {
+        FollowUserCommand { follower_id, followee_id, follow }
+    }
+
+    #[tokio::test]
+    async fn follow_graph_is_directed_and_idempotent() {
+        let (service, ids) = service_with_users(&["fan_one", "band_one"]).await;
+        let (fan, band) = (UserId::from_uuid(ids[0]), UserId::from_uuid(ids[1]));
+
+        service.handle_follow_user(follow(ids[0], ids[1], true)).await.unwrap();
+        service.handle_follow_user(follow(ids[0], ids[1], true)).await.unwrap();
+        assert!(service.repository.is_following(&fan, &band).await.unwrap());
+        assert!(!service.repository.is_following(&band, &fan).await.unwrap());
+        assert_eq!(service.repository.count_followers(&band).await.unwrap(), 1);
+        assert_eq!(service.repository.count_following(&fan).await.unwrap(), 1);
+
+        service.handle_follow_user(follow(ids[0], ids[1], false)).await.unwrap();
+        // Unfollow de alguien a quien ya no sigues: no-op
+        service.handle_follow_user(follow(ids[0], ids[1], false)).await.unwrap();
+        assert_eq!(service.repository.count_followers(&band).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn following_yourself_is_rejected() {
+        let (service, ids) = service_with_users(&["lonely_fan"]).await;
+        let result = service.handle_follow_user(follow(ids[0], ids[0], true)).await;
+        assert!(matches!(result, Err(AppError::ValidationError(_))));
+    }
+}
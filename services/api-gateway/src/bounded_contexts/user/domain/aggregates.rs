@@ -3,7 +3,7 @@ use chrono::{DateTime, Utc};
 use std::collections::VecDeque;
 
 use super::{
-    entities::{User, UserProfile, UserPreferences, UserStats},
+    entities::{User, UserProfile, UserPreferences, UserStats, MAX_BIO_LENGTH},
     value_objects::{
         UserId, Email, Username, PasswordHash, WalletAddress, 
         UserTier, UserRole, ProfileUrl
@@ -240,6 +240,10 @@ impl UserAggregate {
             return Err("Usuario desactivado".to_string());
         }
 
+        if bio.as_ref().map_or(false, |bio| bio.chars().count() > MAX_BIO_LENGTH) {
+            return Err(format!("La bio no puede tener más de {} caracteres", MAX_BIO_LENGTH));
+        }
+
         // Update profile fields
         self.profile.update_display_name(display_name.clone());
         self.profile.update_bio(bio.clone());
@@ -262,6 +266,64 @@ impl UserAggregate {
         Ok(())
     }
 
+    /// Change the username; the uniqueness check belongs to the repository
+    pub fn change_username(&mut self, username: Username, now: DateTime<Utc>) -> Result<(), String> {
+        if !self.user.is_active {
+            return Err("Usuario desactivado".to_string());
+        }
+
+        if username == self.user.username {
+            return Ok(());
+        }
+        self.user.change_username(username, now)?;
+        self.increment_version();
+
+        Ok(())
+    }
+
+    /// Account that stands in for a deleted user in the records that are kept
+    /// (payments, holdings). It is born deleted, with no link to the person.
+    pub fn deleted_user_pseudonym(now: DateTime<Utc>) -> Result<Self, String> {
+        let id = UserId::new();
+        let tag = id.value().simple().to_string();
+        let mut user = User::with_id(
+            id.clone(),
+            Email::new(format!("deleted-{}@deleted.vibestream.app", tag))?,
+            Username::new(format!("deleted_{}", &tag[..22]))?,
+            PasswordHash::new(String::new()),
+        );
+        user.anonymize(now)?;
+        user.created_at = now;
+
+        Ok(Self::load(
+            user,
+            UserProfile::new(id.clone()),
+            UserPreferences::new(id.clone()),
+            UserStats::new(id),
+            1,
+        ))
+    }
+
+    /// Delete the account: anonymize the user and clear the profile.
+    /// The other contexts are told through `UserDeletionRequested`.
+    pub fn delete_account(&mut self, now: DateTime<Utc>) -> Result<(), String> {
+        if self.user.is_deleted() {
+            return Err("La cuenta ya está eliminada".to_string());
+        }
+
+        self.user.anonymize(now)?;
+        self.profile.update_display_name(None);
+        self.profile.update_bio(None);
+        self.profile.update_avatar(None);
+        self.profile.update_cover(None);
+        self.profile.update_location(None);
+        self.profile.update_website(None);
+        self.profile.social_links.clear();
+        self.increment_version();
+
+        Ok(())
+    }
+
     /// Add listening session to stats
     pub fn add_listening_session(&mut self, duration_minutes: u64) -> Result<(), String> {
         if !self.user.is_active {
@@ -623,4 +685,28 @@ mod tests {
         assert!(result.is_ok());
         assert!(!user_aggregate.user.is_active);
     }
+
+    #[test]
+    fn test_delete_account_removes_personal_data() {
+        // Arrange
+        let email = Email::new("test@example.com".to_string()).unwrap();
+        let username = Username::new("testuser".to_string()).unwrap();
+        let password_hash = PasswordHash::new("hashed_password".to_string());
+        let mut user_aggregate = UserAggregate::create(email, username, password_hash).unwrap();
+        user_aggregate.link_wallet(WalletAddress::new("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".to_string()).unwrap()).unwrap();
+        user_aggregate.update_profile(Some("Test".to_string()), Some("Bio".to_string()), None, Some("Madrid".to_string()), None).unwrap();
+
+        // Act
+        let result = user_aggregate.delete_account(Utc::now());
+
+        // Assert
+        assert!(result.is_ok());
+        assert!(user_aggregate.user.is_deleted());
+        assert!(!user_aggregate.user.is_active);
+        assert!(!user_aggregate.user.email.value().contains("test@example.com"));
+        assert!(user_aggregate.user.username.value().starts_with("deleted_"));
+        assert!(user_aggregate.user.wallet_address.is_none());
+        assert!(user_aggregate.profile.display_name.is_none() && user_aggregate.profile.location.is_none());
+        assert!(user_aggregate.delete_account(Utc::now()).is_err());
+    }
 } 
\ No newline at end of file
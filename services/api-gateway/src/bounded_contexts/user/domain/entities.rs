@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
 
+use super::listening_preferences::ListeningPreferences;
 use super::value_objects::{
     UserId, Email, Username, PasswordHash, WalletAddress, 
     UserTier, UserRole, ProfileUrl
@@ -22,8 +23,23 @@ pub struct User {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub last_login_at: Option<DateTime<Utc>>,
+    /// Last username change; `None` if the user kept the original one
+    #[serde(default)]
+    pub username_changed_at: Option<DateTime<Utc>>,
+    /// Account deleted by its owner; the row stays, without personal data
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
+/// Days a user has to wait between username changes
+pub const USERNAME_CHANGE_COOLDOWN_DAYS: i64 = 30;
+
+/// Longest bio a profile can have, in characters
+pub const MAX_BIO_LENGTH: usize = 500;
+
+/// Password hash of deleted accounts: it never verifies
+const DELETED_ACCOUNT_PASSWORD_HASH: &str = "!deleted";
+
 impl User {
     pub fn new(
         email: Email,
@@ -44,6 +60,8 @@ impl User {
             created_at: now,
             updated_at: now,
             last_login_at: None,
+            username_changed_at: None,
+            deleted_at: None,
         }
     }
 
@@ -67,6 +85,8 @@ impl User {
             created_at: now,
             updated_at: now,
             last_login_at: None,
+            username_changed_at: None,
+            deleted_at: None,
         }
     }
 
@@ -76,6 +96,31 @@ impl User {
         self.updated_at = Utc::now();
     }
 
+    /// When the username can be changed again, if it is still in its cooldown
+    pub fn next_username_change_at(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        self.username_changed_at
+            .map(|changed_at| changed_at + chrono::Duration::days(USERNAME_CHANGE_COOLDOWN_DAYS))
+            .filter(|allowed_at| *allowed_at > now)
+    }
+
+    /// Change the username, at most once every `USERNAME_CHANGE_COOLDOWN_DAYS`
+    pub fn change_username(&mut self, username: Username, now: DateTime<Utc>) -> Result<(), String> {
+        if username == self.username {
+            return Ok(());
+        }
+        if let Some(allowed_at) = self.next_username_change_at(now) {
+            return Err(format!(
+                "El nombre de usuario solo se puede cambiar una vez cada {} días (próximo cambio: {})",
+                USERNAME_CHANGE_COOLDOWN_DAYS,
+                allowed_at.format("%Y-%m-%d")
+            ));
+        }
+        self.username = username;
+        self.username_changed_at = Some(now);
+        self.updated_at = now;
+        Ok(())
+    }
+
     /// Link wallet address
     pub fn link_wallet(&mut self, wallet_address: WalletAddress) {
         self.wallet_address = Some(wallet_address);
@@ -106,6 +151,25 @@ impl User {
         self.updated_at = Utc::now();
     }
 
+    /// Soft delete: keep the id for the records that reference it, drop the
+    /// personal data and any way to sign in
+    pub fn anonymize(&mut self, now: DateTime<Utc>) -> Result<(), String> {
+        let tag = self.id.value().simple().to_string();
+        self.email = Email::new(format!("deleted-{}@deleted.vibestream.app", tag))?;
+        self.username = Username::new(format!("deleted_{}", &tag[..22]))?;
+        self.password_hash = PasswordHash::new(DELETED_ACCOUNT_PASSWORD_HASH.to_string());
+        self.wallet_address = None;
+        self.is_verified = false;
+        self.is_active = false;
+        self.deleted_at = Some(now);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    pub fn is_deleted(&self) -> bool {
+        self.deleted_at.is_some()
+    }
+
     /// Deactivate user
     pub fn deactivate(&mut self) {
         self.is_active = false;
@@ -147,6 +211,9 @@ pub struct UserProfile {
     pub website: Option<ProfileUrl>,
     pub social_links: HashMap<String, String>,
     pub is_public: bool,
+    /// Feature vector for personalisation, refreshed from the listen history
+    #[serde(default)]
+    pub listening_preferences: Option<ListeningPreferences>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -164,6 +231,7 @@ impl UserProfile {
             website: None,
             social_links: HashMap::new(),
             is_public: true,
+            listening_preferences: None,
             created_at: now,
             updated_at: now,
         }
@@ -502,6 +570,30 @@ mod tests {
         assert!(!user.has_permission("moderate_content"));
     }
 
+    #[test]
+    fn test_username_change_cooldown() {
+        let email = Email::new("fan@example.com".to_string()).unwrap();
+        let username = Username::new("fan_one".to_string()).unwrap();
+        let mut user = User::new(email, username, PasswordHash::new("hashed_password".to_string()));
+        let now = Utc::now();
+
+        // El primer cambio no espera
+        user.change_username(Username::new("fan_two".to_string()).unwrap(), now).unwrap();
+        assert_eq!(user.username.value(), "fan_two");
+        assert_eq!(user.username_changed_at, Some(now));
+
+        let too_soon = now + chrono::Duration::days(USERNAME_CHANGE_COOLDOWN_DAYS - 1);
+        assert!(user.change_username(Username::new("fan_three".to_string()).unwrap(), too_soon).is_err());
+        assert_eq!(user.username.value(), "fan_two");
+        // Repetir el mismo nombre no es un cambio
+        assert!(user.change_username(Username::new("fan_two".to_string()).unwrap(), too_soon).is_ok());
+
+        let later = now + chrono::Duration::days(USERNAME_CHANGE_COOLDOWN_DAYS);
+        assert_eq!(user.next_username_change_at(later), None);
+        user.change_username(Username::new("fan_three".to_string()).unwrap(), later).unwrap();
+        assert_eq!(user.username.value(), "fan_three");
+    }
+
     #[test]
     fn test_user_profile_updates() {
         let user_id = UserId::new();
@@ -0,0 +1,244 @@
+//! Listening preferences of a user, the feature vector used for personalisation
+//!
+//! Every preference is an exponential moving average over the listen history:
+//! each listen moves the vector a fraction `alpha` towards what was just heard,
+//! so recent taste weighs more than old taste and nothing has to be recomputed
+//! from scratch.
+
+use chrono::{DateTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use vibestream_types::CreateListenEvent;
+
+use crate::bounded_contexts::music::domain::value_objects::{AudioQuality, Genre, SongMood};
+
+/// Smoothing factor of the moving averages
+pub const DEFAULT_EMA_ALPHA: f64 = 0.1;
+/// The stored vector is refreshed every this many new listens
+pub const PREFERENCES_REFRESH_EVERY_LISTENS: i64 = 10;
+/// Shorter listens are skips and say nothing about taste
+pub const MIN_PREFERENCE_LISTEN_SECONDS: i32 = 30;
+// Por debajo de este peso la categoría se olvida
+const MIN_PREFERENCE_WEIGHT: f64 = 0.001;
+const HOURS_PER_DAY: usize = 24;
+
+/// What was heard in a listen: features of the song and when it was played
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListenContext {
+    pub genre: Option<Genre>,
+    pub mood: Option<SongMood>,
+    pub tempo_bpm: Option<u16>,
+    pub quality: Option<AudioQuality>,
+    pub listened_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ListeningPreferences {
+    /// Share of recent listens per genre, highest first
+    pub favorite_genres: Vec<(Genre, f64)>,
+    /// Share of recent listens per mood, highest first
+    pub favorite_moods: Vec<(SongMood, f64)>,
+    pub preferred_tempo_bpm: Option<u16>,
+    pub preferred_quality: Option<AudioQuality>,
+    /// Hours of the day (UTC) with more than an even share of listens
+    pub active_hours: Vec<u8>,
+    // Estado de las medias que no se ve en los campos publicados
+    #[serde(default)]
+    tempo_average: Option<f64>,
+    #[serde(default)]
+    quality_weights: Vec<(AudioQuality, f64)>,
+    #[serde(default)]
+    hour_weights: Vec<f64>,
+}
+
+impl ListeningPreferences {
+    /// Preferences after one more listen. Skips (shorter than
+    /// `MIN_PREFERENCE_LISTEN_SECONDS`) leave them unchanged, and a feature the
+    /// song lacks leaves its preference unchanged instead of decaying it.
+    pub fn update_from_listen(&self, event: &CreateListenEvent, context: &ListenContext, alpha: f64) -> Self {
+        let mut updated = self.clone();
+        if event.listen_duration_seconds < MIN_PREFERENCE_LISTEN_SECONDS {
+            return updated;
+        }
+        let alpha = alpha.clamp(0.0, 1.0);
+
+        if let Some(genre) = &context.genre {
+            update_weights(&mut updated.favorite_genres, genre, alpha);
+        }
+        if let Some(mood) = &context.mood {
+            update_weights(&mut updated.favorite_moods, mood, alpha);
+        }
+        if let Some(tempo_bpm) = context.tempo_bpm {
+            let average = match updated.tempo_average {
+                Some(average) => average + alpha * (tempo_bpm as f64 - average),
+                None => tempo_bpm as f64,
+            };
+            updated.tempo_average = Some(average);
+            updated.preferred_tempo_bpm = Some(average.round() as u16);
+        }
+        if let Some(quality) = &context.quality {
+            update_weights(&mut updated.quality_weights, quality, alpha);
+            updated.preferred_quality = updated.quality_weights.first().map(|(quality, _)| quality.clone());
+        }
+
+        updated.hour_weights.resize(HOURS_PER_DAY, 0.0);
+        let hour = context.listened_at.hour() as usize;
+        for (h, weight) in updated.hour_weights.iter_mut().enumerate() {
+            *weight = (1.0 - alpha) * *weight + if h == hour { alpha } else { 0.0 };
+        }
+        updated.active_hours = updated
+            .hour_weights
+            .iter()
+            .enumerate()
+            .filter(|(_, weight)| **weight > 1.0 / HOURS_PER_DAY as f64)
+            .map(|(h, _)| h as u8)
+            .collect();
+
+        updated
+    }
+
+    pub fn genre_weight(&self, genre: &Genre) -> f64 {
+        weight_of(&self.favorite_genres, genre)
+    }
+
+    pub fn mood_weight(&self, mood: &SongMood) -> f64 {
+        weight_of(&self.favorite_moods, mood)
+    }
+}
+
+/// EMA of a categorical distribution: every weight decays and the observed
+/// category gains `alpha`, so the weights track the share of recent listens
+fn update_weights<T: Clone + PartialEq>(weights: &mut Vec<(T, f64)>, observed: &T, alpha: f64) {
+    for (_, weight) in weights.iter_mut() {
+        *weight *= 1.0 - alpha;
+    }
+    match weights.iter_mut().find(|(value, _)| value == observed) {
+        Some((_, weight)) => *weight += alpha,
+        None => weights.push((observed.clone(), alpha)),
+    }
+    weights.retain(|(_, weight)| *weight >= MIN_PREFERENCE_WEIGHT);
+    weights.sort_by(|a, b| b.1.total_cmp(&a.1));
+}
+
+fn weight_of<T: PartialEq>(weights: &[(T, f64)], value: &T) -> f64 {
+    weights.iter().find(|(v, _)| v == value).map_or(0.0, |(_, weight)| *weight)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    fn listen(seconds: i32) -> CreateListenEvent {
+        CreateListenEvent {
+            song_id: Uuid::new_v4(),
+            listen_duration_seconds: seconds,
+            user_agent: None,
+            zk_proof_hash: None,
+        }
+    }
+
+    fn context(genre: &str, mood: SongMood, hour: u32) -> ListenContext {
+        ListenContext {
+            genre: Some(Genre::new(genre.to_string()).unwrap()),
+            mood: Some(mood),
+            tempo_bpm: None,
+            quality: None,
+            listened_at: Utc.with_ymd_and_hms(2026, 10, 15, hour, 0, 0).unwrap(),
+        }
+    }
+
+    /// Generador determinista para que el test no dependa del azar
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next_percent(&mut self) -> u64 {
+            self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (self.0 >> 33) % 100
+        }
+    }
+
+    #[test]
+    fn test_ema_converges_to_the_true_listening_distribution() {
+        // 60% rock, 30% jazz, 10% pop; 70% calm, 30% energetic
+        let mut rng = Lcg(42);
+        let mut preferences = ListeningPreferences::default();
+        for _ in 0..20_000 {
+            let genre = match rng.next_percent() {
+                0..=59 => "rock",
+                60..=89 => "jazz",
+                _ => "pop",
+            };
+            let mood = if rng.next_percent() < 70 { SongMood::Calm } else { SongMood::Energetic };
+            preferences = preferences.update_from_listen(&listen(180), &context(genre, mood, 21), 0.01);
+        }
+
+        let rock = Genre::new("rock".to_string()).unwrap();
+        let jazz = Genre::new("jazz".to_string()).unwrap();
+        let pop = Genre::new("pop".to_string()).unwrap();
+        assert!((preferences.genre_weight(&rock) - 0.6).abs() < 0.05);
+        assert!((preferences.genre_weight(&jazz) - 0.3).abs() < 0.05);
+        assert!((preferences.genre_weight(&pop) - 0.1).abs() < 0.05);
+        assert_eq!(preferences.favorite_genres[0].0, rock);
+        assert!((preferences.mood_weight(&SongMood::Calm) - 0.7).abs() < 0.05);
+        assert!((preferences.mood_weight(&SongMood::Energetic) - 0.3).abs() < 0.05);
+
+        // Los pesos siguen siendo una distribución
+        let total: f64 = preferences.favorite_genres.iter().map(|(_, weight)| weight).sum();
+        assert!((total - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_preferences_follow_a_change_of_taste_with_the_default_alpha() {
+        let mut preferences = ListeningPreferences::default();
+        for _ in 0..50 {
+            let mut ctx = context("metal", SongMood::Aggressive, 23);
+            ctx.tempo_bpm = Some(170);
+            ctx.quality = Some(AudioQuality::Medium);
+            preferences = preferences.update_from_listen(&listen(200), &ctx, DEFAULT_EMA_ALPHA);
+        }
+        for _ in 0..100 {
+            let mut ctx = context("classical", SongMood::Calm, 8);
+            ctx.tempo_bpm = Some(80);
+            ctx.quality = Some(AudioQuality::Lossless);
+            preferences = preferences.update_from_listen(&listen(200), &ctx, DEFAULT_EMA_ALPHA);
+        }
+
+        assert_eq!(preferences.favorite_genres[0].0, Genre::new("classical".to_string()).unwrap());
+        assert!(preferences.genre_weight(&Genre::new("classical".to_string()).unwrap()) > 0.99);
+        assert_eq!(preferences.favorite_moods[0].0, SongMood::Calm);
+        assert_eq!(preferences.preferred_tempo_bpm, Some(80));
+        assert_eq!(preferences.preferred_quality, Some(AudioQuality::Lossless));
+        assert_eq!(preferences.active_hours, vec![8]);
+    }
+
+    #[test]
+    fn test_skips_and_missing_features_leave_preferences_unchanged() {
+        let first = ListeningPreferences::default().update_from_listen(&listen(180), &context("rock", SongMood::Happy, 10), DEFAULT_EMA_ALPHA);
+
+        let after_skip = first.update_from_listen(&listen(5), &context("pop", SongMood::Sad, 11), DEFAULT_EMA_ALPHA);
+        assert_eq!(after_skip, first);
+
+        let mut without_genre = context("pop", SongMood::Happy, 10);
+        without_genre.genre = None;
+        let updated = first.update_from_listen(&listen(180), &without_genre, DEFAULT_EMA_ALPHA);
+        assert_eq!(updated.favorite_genres, first.favorite_genres);
+        assert!(updated.mood_weight(&SongMood::Happy) > first.mood_weight(&SongMood::Happy));
+    }
+
+    #[test]
+    fn test_preferences_round_trip_through_json() {
+        let mut ctx = context("jazz", SongMood::Romantic, 22);
+        ctx.tempo_bpm = Some(96);
+        ctx.quality = Some(AudioQuality::High);
+        let preferences = ListeningPreferences::default().update_from_listen(&listen(240), &ctx, DEFAULT_EMA_ALPHA);
+
+        let json = serde_json::to_value(&preferences).unwrap();
+        let restored: ListeningPreferences = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, preferences);
+        assert_eq!(
+            restored.update_from_listen(&listen(240), &ctx, DEFAULT_EMA_ALPHA),
+            preferences.update_from_listen(&listen(240), &ctx, DEFAULT_EMA_ALPHA)
+        );
+    }
+}
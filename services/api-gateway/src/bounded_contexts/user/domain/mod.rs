@@ -5,6 +5,7 @@ pub mod events;
 pub mod services;
 pub mod repository;
 pub mod specifications;
+pub mod listening_preferences;
 
 // Re-export key types
 pub use value_objects::{
@@ -24,6 +25,7 @@ pub use services::{
     PasswordDomainService, UserValidationService
 };
 pub use repository::UserRepository;
+pub use listening_preferences::{ListenContext, ListeningPreferences};
 pub use specifications::{
     EmailSpecification, UsernameSpecification, 
     PasswordSpecification, UserActiveSpecification
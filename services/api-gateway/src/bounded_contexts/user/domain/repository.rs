@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use super::{
     aggregates::{UserAggregate, UserSummary},
     entities::UserStats,
-    value_objects::{UserId, Email, Username},
+    value_objects::{UserId, Email, Username, WalletAddress},
 };
 use crate::shared::domain::errors::AppError;
 
@@ -26,6 +26,9 @@ pub trait UserRepository: Send + Sync {
     /// Find user by username
     async fn find_by_username(&self, username: &Username) -> Result<Option<UserAggregate>, AppError>;
 
+    /// Find the user a wallet address is linked to
+    async fn find_by_wallet_address(&self, address: &WalletAddress) -> Result<Option<UserAggregate>, AppError>;
+
     /// Check if email exists
     async fn email_exists(&self, email: &Email) -> Result<bool, AppError>;
 
@@ -35,11 +38,12 @@ pub trait UserRepository: Send + Sync {
     /// Search users by text with pagination (simple helper)
     async fn search_users(&self, search_text: Option<&str>, limit: u32, offset: u32) -> Result<Vec<UserAggregate>, AppError>;
 
-    /// Add follower relationship
-    async fn add_follower(&self, follower_id: &UserId, followee_id: &UserId) -> Result<(), AppError>;
+    /// Add follower relationship. Returns false if it already existed; the
+    /// followee's artist follower count changes in the same transaction
+    async fn add_follower(&self, follower_id: &UserId, followee_id: &UserId) -> Result<bool, AppError>;
 
-    /// Remove follower relationship
-    async fn remove_follower(&self, follower_id: &UserId, followee_id: &UserId) -> Result<(), AppError>;
+    /// Remove follower relationship. Returns false if there was none
+    async fn remove_follower(&self, follower_id: &UserId, followee_id: &UserId) -> Result<bool, AppError>;
 
     /// Find users by criteria
     async fn find_users(&self, criteria: UserSearchCriteria) -> Result<Vec<UserSummary>, AppError>;
@@ -65,6 +69,12 @@ pub trait UserRepository: Send + Sync {
     /// Get users that a user is following
     async fn get_following(&self, user_id: &UserId, page: u32, page_size: u32) -> Result<Vec<UserSummary>, AppError>;
 
+    /// Number of users following this user
+    async fn count_followers(&self, user_id: &UserId) -> Result<u64, AppError>;
+
+    /// Number of users this user follows
+    async fn count_following(&self, user_id: &UserId) -> Result<u64, AppError>;
+
     /// Check if user A follows user B
     async fn is_following(&self, follower_id: &UserId, followee_id: &UserId) -> Result<bool, AppError>;
 
@@ -1,5 +1,4 @@
 use async_trait::async_trait;
-use bcrypt::{hash, verify, DEFAULT_COST};
 use chrono::{DateTime, Utc, Duration};
 use std::sync::Arc;
 
@@ -10,6 +9,7 @@ use super::{
     specifications::{EmailSpecification, UsernameSpecification, PasswordSpecification, Specification},
 };
 use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::auth::PasswordService;
 
 /// Authentication domain service
 #[async_trait]
@@ -87,15 +87,13 @@ impl PasswordDomainService {
         Self
     }
 
+    /// argon2id
     pub fn hash_password(&self, password: &str) -> Result<PasswordHash, AppError> {
-        let hash_result = hash(password, DEFAULT_COST)
-            .map_err(|e| AppError::Internal(format!("Error hashing password: {}", e)))?;
-        Ok(PasswordHash::new(hash_result))
+        Ok(PasswordHash::new(PasswordService::hash_password(password)?))
     }
 
     pub fn verify_password(&self, password: &str, hash: &PasswordHash) -> Result<bool, AppError> {
-        verify(password, hash.value())
-            .map_err(|e| AppError::Internal(format!("Error verifying password: {}", e)))
+        PasswordService::verify_password(password, hash.value())
     }
 
     pub fn validate_password_strength(&self, password: &str) -> Result<(), AppError> {
@@ -275,10 +273,15 @@ impl AuthenticationDomainService for DefaultAuthenticationService {
             self.user_repository.find_by_username(&username).await?
         };
 
-        let mut user_aggregate = user_aggregate.ok_or(AppError::NotFound("User not found".to_string()))?;
-
-        // Verify password
-        let password_hash = self.hash_password(&password)?;
+        // Verify password: sin usuario se verifica contra un hash ficticio y el
+        // error es el mismo, para no revelar qué cuentas existen
+        let stored_hash = user_aggregate.as_ref().map(|aggregate| aggregate.user.password_hash.value());
+        if !PasswordService::verify_credentials(&password, stored_hash) {
+            return Err(AppError::AuthenticationError("Invalid credentials".to_string()));
+        }
+        let mut user_aggregate = user_aggregate
+            .ok_or_else(|| AppError::AuthenticationError("Invalid credentials".to_string()))?;
+        let password_hash = user_aggregate.user.password_hash.clone();
         user_aggregate.authenticate(&password_hash)?;
 
         Ok(user_aggregate)
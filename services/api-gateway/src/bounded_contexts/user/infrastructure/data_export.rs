@@ -0,0 +1,270 @@
+// Personal data exports (GDPR access requests)
+//
+// The archive is assembled from the tables of every context that holds data
+// about the user. Only the user's own data goes in: payments show the amount
+// and purpose but not the other party, so an export never leaks someone else.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+
+/// Days a finished archive stays available for download
+pub const EXPORT_RETENTION_DAYS: i64 = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataExportStatus {
+    Pending,
+    Ready,
+    Failed,
+}
+
+impl DataExportStatus {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Ready => "ready",
+            Self::Failed => "failed",
+        }
+    }
+
+    fn parse(status: &str) -> Self {
+        match status {
+            "ready" => Self::Ready,
+            "failed" => Self::Failed,
+            _ => Self::Pending,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UserDataExport {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub status: DataExportStatus,
+    pub requested_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl UserDataExport {
+    /// Ready and not yet past its retention
+    pub fn is_downloadable(&self, now: DateTime<Utc>) -> bool {
+        self.status == DataExportStatus::Ready && self.expires_at.map_or(false, |expires_at| expires_at > now)
+    }
+}
+
+pub struct PostgresUserDataExports {
+    pool: PgPool,
+}
+
+fn db_error(e: sqlx::Error) -> AppError {
+    AppError::DatabaseError(format!("User data export error: {}", e))
+}
+
+impl PostgresUserDataExports {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create(&self, user_id: Uuid, now: DateTime<Utc>) -> Result<UserDataExport, AppError> {
+        // Los archivos caducados se borran al pedir uno nuevo
+        sqlx::query("UPDATE user_data_exports SET archive = NULL WHERE expires_at < $1 AND archive IS NOT NULL")
+            .bind(now)
+            .execute(&self.pool)
+            .await
+            .map_err(db_error)?;
+
+        let export = UserDataExport {
+            id: Uuid::new_v4(),
+            user_id,
+            status: DataExportStatus::Pending,
+            requested_at: now,
+            completed_at: None,
+            expires_at: None,
+        };
+        sqlx::query("INSERT INTO user_data_exports (id, user_id, status, requested_at) VALUES ($1, $2, $3, $4)")
+            .bind(export.id)
+            .bind(user_id)
+            .bind(export.status.as_str())
+            .bind(now)
+            .execute(&self.pool)
+            .await
+            .map_err(db_error)?;
+        Ok(export)
+    }
+
+    pub async fn find(&self, export_id: Uuid) -> Result<Option<UserDataExport>, AppError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, user_id, status, requested_at, completed_at, expires_at
+            FROM user_data_exports
+            WHERE id = $1
+            "#,
+        )
+        .bind(export_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        row.map(|row| {
+            Ok(UserDataExport {
+                id: row.try_get("id").map_err(db_error)?,
+                user_id: row.try_get("user_id").map_err(db_error)?,
+                status: DataExportStatus::parse(row.try_get::<String, _>("status").map_err(db_error)?.as_str()),
+                requested_at: row.try_get("requested_at").map_err(db_error)?,
+                completed_at: row.try_get("completed_at").map_err(db_error)?,
+                expires_at: row.try_get("expires_at").map_err(db_error)?,
+            })
+        })
+        .transpose()
+    }
+
+    pub async fn archive(&self, export_id: Uuid) -> Result<Option<serde_json::Value>, AppError> {
+        sqlx::query_scalar::<_, Option<serde_json::Value>>("SELECT archive FROM user_data_exports WHERE id = $1")
+            .bind(export_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map(Option::flatten)
+            .map_err(db_error)
+    }
+
+    /// Assemble the archive and store it; a failure is recorded on the export
+    pub async fn generate(&self, export: &UserDataExport) -> Result<(), AppError> {
+        let result = match self.assemble(export.user_id).await {
+            Ok(archive) => {
+                let now = Utc::now();
+                sqlx::query(
+                    r#"
+                    UPDATE user_data_exports
+                    SET status = 'ready', archive = $2, completed_at = $3, expires_at = $4
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(export.id)
+                .bind(archive)
+                .bind(now)
+                .bind(now + chrono::Duration::days(EXPORT_RETENTION_DAYS))
+                .execute(&self.pool)
+                .await
+                .map(|_| ())
+                .map_err(db_error)
+            }
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = &result {
+            sqlx::query("UPDATE user_data_exports SET status = 'failed', error = $2, completed_at = NOW() WHERE id = $1")
+                .bind(export.id)
+                .bind(e.to_string())
+                .execute(&self.pool)
+                .await
+                .map_err(db_error)?;
+        }
+        result
+    }
+
+    /// The exports hold personal data too, so they go with the account
+    pub async fn delete_for_user(&self, user_id: Uuid) -> Result<(), AppError> {
+        sqlx::query("DELETE FROM user_data_exports WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(db_error)?;
+        Ok(())
+    }
+
+    /// Profile, playlists, listen history, payments and fan venture holdings
+    pub async fn assemble(&self, user_id: Uuid) -> Result<serde_json::Value, AppError> {
+        let profile: Option<serde_json::Value> = sqlx::query_scalar(
+            r#"
+            SELECT jsonb_build_object(
+                'id', id, 'email', email, 'username', username, 'display_name', display_name,
+                'bio', bio, 'avatar_url', avatar_url, 'wallet_address', wallet_address,
+                'role', role, 'tier', tier, 'is_verified', is_verified,
+                'created_at', created_at, 'updated_at', updated_at
+            )
+            FROM users WHERE id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)?;
+        let profile = profile.ok_or_else(|| AppError::NotFound("User not found".to_string()))?;
+
+        let playlists = self.section(
+            r#"
+            SELECT COALESCE(jsonb_agg(jsonb_build_object(
+                'id', p.id, 'title', p.title, 'description', p.description, 'is_public', p.is_public,
+                'created_at', p.created_at,
+                'song_ids', (SELECT COALESCE(jsonb_agg(ps.song_id ORDER BY ps.position), '[]'::jsonb)
+                             FROM playlist_songs ps WHERE ps.playlist_id = p.id)
+            ) ORDER BY p.created_at), '[]'::jsonb)
+            FROM playlists p WHERE p.user_id = $1
+            "#,
+            user_id,
+        ).await?;
+
+        let listen_history = self.section(
+            r#"
+            SELECT COALESCE(jsonb_agg(jsonb_build_object(
+                'session_id', id, 'song_id', song_id, 'status', status,
+                'listen_duration_seconds', listen_duration_seconds, 'reward_tokens', final_reward_tokens,
+                'started_at', started_at, 'completed_at', completed_at
+            ) ORDER BY started_at), '[]'::jsonb)
+            FROM listen_sessions WHERE user_id = $1
+            "#,
+            user_id,
+        ).await?;
+
+        // Sin la otra parte del pago ni sus datos (método de pago, IP)
+        let payments = self.section(
+            r#"
+            SELECT COALESCE(jsonb_agg(jsonb_build_object(
+                'payment_id', id,
+                'direction', CASE WHEN payer_id = $1 THEN 'sent' ELSE 'received' END,
+                'amount', amount_value, 'currency', amount_currency,
+                'payment_method_type', CASE WHEN payer_id = $1 THEN payment_method_type END,
+                'purpose_type', purpose_type, 'status', status, 'created_at', created_at
+            ) ORDER BY created_at), '[]'::jsonb)
+            FROM payments WHERE payer_id = $1 OR payee_id = $1
+            "#,
+            user_id,
+        ).await?;
+
+        let holdings = self.section(
+            r#"
+            SELECT COALESCE(jsonb_agg(jsonb_build_object(
+                'investment_id', fi.id, 'venture_id', fi.venture_id, 'venture_title', v.title,
+                'amount', fi.investment_amount, 'investment_type', fi.investment_type,
+                'status', fi.status, 'created_at', fi.created_at
+            ) ORDER BY fi.created_at), '[]'::jsonb)
+            FROM fan_investments fi
+            JOIN artist_ventures v ON v.id = fi.venture_id
+            WHERE fi.fan_id = $1
+            "#,
+            user_id,
+        ).await?;
+
+        Ok(serde_json::json!({
+            "generated_at": Utc::now(),
+            "profile": profile,
+            "playlists": playlists,
+            "listen_history": listen_history,
+            "payments": payments,
+            "fan_venture_holdings": holdings,
+        }))
+    }
+
+    async fn section(&self, query: &str, user_id: Uuid) -> Result<serde_json::Value, AppError> {
+        sqlx::query_scalar::<_, serde_json::Value>(query)
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(db_error)
+    }
+}
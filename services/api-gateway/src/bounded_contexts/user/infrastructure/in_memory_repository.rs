@@ -6,7 +6,7 @@ use crate::bounded_contexts::user::domain::{
     aggregates::{UserAggregate, UserSummary},
     entities::{UserStats},
     repository::{UserRepository, UserSearchCriteria},
-    value_objects::{UserId, Email, Username},
+    value_objects::{UserId, Email, Username, WalletAddress},
 };
 use crate::shared::domain::errors::AppError;
 
@@ -52,6 +52,14 @@ impl UserRepository for InMemoryUserRepository {
         Ok(users.values().find(|user| &user.user.username == username).cloned())
     }
 
+    async fn find_by_wallet_address(&self, address: &WalletAddress) -> Result<Option<UserAggregate>, AppError> {
+        let users = self.users.read().unwrap();
+        Ok(users.values()
+            .find(|user| user.user.wallet_address.as_ref()
+                .map_or(false, |linked| linked.value().eq_ignore_ascii_case(address.value())))
+            .cloned())
+    }
+
     async fn email_exists(&self, email: &Email) -> Result<bool, AppError> {
         Ok(self.find_by_email(email).await?.is_some())
     }
@@ -93,6 +101,16 @@ impl UserRepository for InMemoryUserRepository {
         Ok(summaries)
     }
 
+    async fn count_followers(&self, user_id: &UserId) -> Result<u64, AppError> {
+        let followers_map = self.followers.read().unwrap();
+        Ok(followers_map.get(user_id).map_or(0, |ids| ids.len() as u64))
+    }
+
+    async fn count_following(&self, user_id: &UserId) -> Result<u64, AppError> {
+        let followers_map = self.followers.read().unwrap();
+        Ok(followers_map.values().filter(|ids| ids.contains(user_id)).count() as u64)
+    }
+
     async fn is_following(&self, follower_id: &UserId, followee_id: &UserId) -> Result<bool, AppError> {
         let followers_map = self.followers.read().unwrap();
         if let Some(follower_ids) = followers_map.get(followee_id) {
@@ -343,20 +361,27 @@ impl UserRepository for InMemoryUserRepository {
         Ok(results)
     }
 
-    async fn add_follower(&self, follower_id: &UserId, followee_id: &UserId) -> Result<(), AppError> {
+    async fn add_follower(&self, follower_id: &UserId, followee_id: &UserId) -> Result<bool, AppError> {
         let mut followers = self.followers.write().unwrap();
-        followers.entry(followee_id.clone()).or_insert_with(Vec::new).push(follower_id.clone());
-        Ok(())
+        let follower_list = followers.entry(followee_id.clone()).or_insert_with(Vec::new);
+        if follower_list.contains(follower_id) {
+            return Ok(false);
+        }
+        follower_list.push(follower_id.clone());
+        Ok(true)
     }
 
-    async fn remove_follower(&self, follower_id: &UserId, followee_id: &UserId) -> Result<(), AppError> {
+    async fn remove_follower(&self, follower_id: &UserId, followee_id: &UserId) -> Result<bool, AppError> {
         let mut followers = self.followers.write().unwrap();
-        if let Some(follower_list) = followers.get_mut(followee_id) {
-            follower_list.retain(|id| id != follower_id);
-            if follower_list.is_empty() {
-                followers.remove(followee_id);
-            }
+        let Some(follower_list) = followers.get_mut(followee_id) else {
+            return Ok(false);
+        };
+        let before = follower_list.len();
+        follower_list.retain(|id| id != follower_id);
+        let removed = follower_list.len() < before;
+        if follower_list.is_empty() {
+            followers.remove(followee_id);
         }
-        Ok(())
+        Ok(removed)
     }
 } 
\ No newline at end of file
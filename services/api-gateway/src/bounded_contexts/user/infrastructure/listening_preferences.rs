@@ -0,0 +1,175 @@
+// Listening preference vectors (users.listening_preferences)
+//
+// The vector is folded incrementally: `listening_preferences_listens` says how
+// many of the user's listen_events are already in it, and a refresh only
+// applies the ones after that, oldest first, so the moving averages see the
+// history in the order it happened.
+
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+use vibestream_types::CreateListenEvent;
+
+use crate::bounded_contexts::music::domain::value_objects::{AudioQuality, Genre, SongMood};
+use crate::bounded_contexts::user::domain::listening_preferences::{
+    ListenContext, ListeningPreferences, DEFAULT_EMA_ALPHA, PREFERENCES_REFRESH_EVERY_LISTENS,
+};
+use crate::shared::domain::errors::AppError;
+
+/// Preferences of a user as stored, with how many listens they include
+#[derive(Debug, Clone)]
+pub struct StoredListeningPreferences {
+    pub preferences: ListeningPreferences,
+    pub listens: i64,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+pub struct PostgresListeningPreferences {
+    pool: PgPool,
+    alpha: f64,
+}
+
+fn db_error(e: sqlx::Error) -> AppError {
+    AppError::DatabaseError(format!("Listening preferences error: {}", e))
+}
+
+impl PostgresListeningPreferences {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool, alpha: DEFAULT_EMA_ALPHA }
+    }
+
+    pub fn with_alpha(mut self, alpha: f64) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// `None` when the user does not exist; a user without listens yet has empty preferences
+    pub async fn find(&self, user_id: Uuid) -> Result<Option<StoredListeningPreferences>, AppError> {
+        let row = sqlx::query(
+            r#"
+            SELECT listening_preferences, listening_preferences_listens, listening_preferences_updated_at
+            FROM users
+            WHERE id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        row.map(|row| {
+            let stored: Option<serde_json::Value> = row.try_get("listening_preferences").map_err(db_error)?;
+            let updated_at = row.try_get("listening_preferences_updated_at").map_err(db_error)?;
+            let (preferences, listens) = match stored.map(serde_json::from_value::<ListeningPreferences>) {
+                Some(Ok(preferences)) => (preferences, row.try_get::<i32, _>("listening_preferences_listens").map_err(db_error)? as i64),
+                Some(Err(e)) => {
+                    // Se reconstruye desde el primer listen en el próximo refresco
+                    tracing::warn!("Unreadable listening preferences of user {}: {}", user_id, e);
+                    (ListeningPreferences::default(), 0)
+                }
+                None => (ListeningPreferences::default(), 0),
+            };
+            Ok(StoredListeningPreferences { preferences, listens, updated_at })
+        })
+        .transpose()
+    }
+
+    /// Users with at least `PREFERENCES_REFRESH_EVERY_LISTENS` listens not yet in their vector
+    pub async fn users_due(&self, limit: i64) -> Result<Vec<Uuid>, AppError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT u.id
+            FROM users u
+            JOIN listen_events le ON le.user_id = u.id
+            GROUP BY u.id, u.listening_preferences_listens
+            HAVING COUNT(*) >= u.listening_preferences_listens + $1
+            LIMIT $2
+            "#,
+        )
+        .bind(PREFERENCES_REFRESH_EVERY_LISTENS)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)
+    }
+
+    /// Folds the user's new listens into the stored vector. Returns `false`
+    /// when there was nothing new or another refresh got there first.
+    pub async fn refresh(&self, user_id: Uuid) -> Result<bool, AppError> {
+        let Some(stored) = self.find(user_id).await? else {
+            return Ok(false);
+        };
+
+        let rows = sqlx::query(
+            r#"
+            SELECT le.song_id, le.listen_duration_seconds, le.created_at,
+                   s.genre, s.mood, s.tempo_bpm, s.audio_quality
+            FROM listen_events le
+            JOIN songs s ON s.id = le.song_id
+            WHERE le.user_id = $1
+            ORDER BY le.created_at, le.id
+            OFFSET $2
+            "#,
+        )
+        .bind(user_id)
+        .bind(stored.listens)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+        if rows.is_empty() {
+            return Ok(false);
+        }
+
+        let mut preferences = stored.preferences;
+        for row in &rows {
+            let event = CreateListenEvent {
+                song_id: row.try_get("song_id").map_err(db_error)?,
+                listen_duration_seconds: row.try_get("listen_duration_seconds").map_err(db_error)?,
+                user_agent: None,
+                zk_proof_hash: None,
+            };
+            let context = ListenContext {
+                genre: row.try_get::<Option<String>, _>("genre").map_err(db_error)?.and_then(|genre| Genre::new(genre).ok()),
+                mood: row.try_get::<Option<String>, _>("mood").map_err(db_error)?.and_then(|mood| SongMood::from_string(&mood).ok()),
+                tempo_bpm: row.try_get::<Option<i16>, _>("tempo_bpm").map_err(db_error)?.map(|bpm| bpm.max(0) as u16),
+                quality: row.try_get::<Option<String>, _>("audio_quality").map_err(db_error)?.and_then(|quality| AudioQuality::from_string(&quality).ok()),
+                listened_at: row.try_get::<Option<DateTime<Utc>>, _>("created_at").map_err(db_error)?.unwrap_or_else(Utc::now),
+            };
+            preferences = preferences.update_from_listen(&event, &context, self.alpha);
+        }
+
+        let json = serde_json::to_value(&preferences)
+            .map_err(|e| AppError::SerializationError(format!("Failed to serialize listening preferences: {}", e)))?;
+        let updated = sqlx::query(
+            r#"
+            UPDATE users
+            SET listening_preferences = $2,
+                listening_preferences_listens = $3,
+                listening_preferences_updated_at = NOW()
+            WHERE id = $1 AND listening_preferences_updated_at IS NOT DISTINCT FROM $4
+            "#,
+        )
+        .bind(user_id)
+        .bind(json)
+        .bind((stored.listens + rows.len() as i64) as i32)
+        .bind(stored.updated_at)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        Ok(updated.rows_affected() == 1)
+    }
+
+    /// Refreshes up to `limit` users that are due and returns how many were updated
+    pub async fn refresh_due(&self, limit: i64) -> Result<usize, AppError> {
+        let mut refreshed = 0;
+        for user_id in self.users_due(limit).await? {
+            match self.refresh(user_id).await {
+                Ok(true) => refreshed += 1,
+                Ok(false) => {}
+                Err(e) => tracing::warn!("Could not refresh listening preferences of user {}: {}", user_id, e),
+            }
+        }
+        Ok(refreshed)
+    }
+}
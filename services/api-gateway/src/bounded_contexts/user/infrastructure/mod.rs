@@ -1,2 +1,7 @@
 pub mod in_memory_repository;
-pub mod postgres_repository; 
\ No newline at end of file
+pub mod postgres_repository;
+pub mod data_export;
+pub mod listening_preferences;
+pub mod user_deletion_listener;
+
+pub use user_deletion_listener::FollowsUserDeletionListener;
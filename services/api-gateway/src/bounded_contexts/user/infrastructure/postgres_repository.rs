@@ -76,6 +76,7 @@ impl UserPostgresRepository {
             website: None,
             social_links: std::collections::HashMap::new(),
             is_public: true,
+            listening_preferences: None,
             created_at,
             updated_at,
         };
@@ -222,6 +223,27 @@ impl UserRepository for UserPostgresRepository {
         }
     }
 
+    async fn find_by_wallet_address(&self, address: &WalletAddress) -> Result<Option<UserAggregate>, AppError> {
+        let rec = sqlx::query(
+            r#"SELECT id, email, username, password_hash, display_name, bio, avatar_url,
+                      wallet_address, tier, role, tier_points, total_rewards_earned, total_listening_time_minutes,
+                      is_verified, is_active, last_login_at, created_at, updated_at
+               FROM users
+               WHERE CASE WHEN $2 THEN LOWER(wallet_address) = LOWER($1) ELSE wallet_address = $1 END
+               ORDER BY created_at LIMIT 1"#
+        )
+        .bind(address.value())
+        .bind(address.is_ethereum())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        match rec {
+            Some(row) => Ok(Some(self.row_to_aggregate(row)?)),
+            None => Ok(None),
+        }
+    }
+
     async fn email_exists(&self, email: &Email) -> Result<bool, AppError> {
         Ok(self.find_by_email(email).await?.is_some())
     }
@@ -559,34 +581,60 @@ impl UserRepository for UserPostgresRepository {
         self.find_users(criteria).await
     }
 
-    async fn add_follower(&self, follower_id: &UserId, followee_id: &UserId) -> Result<(), AppError> {
-        sqlx::query(
-            r#"INSERT INTO user_followers (follower_id, followee_id, created_at)
+    async fn add_follower(&self, follower_id: &UserId, followee_id: &UserId) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let inserted = sqlx::query(
+            r#"INSERT INTO follows (follower_id, followee_id, created_at)
                VALUES ($1, $2, $3)
                ON CONFLICT (follower_id, followee_id) DO NOTHING"#
         )
         .bind(follower_id.to_uuid())
         .bind(followee_id.to_uuid())
         .bind(chrono::Utc::now())
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .rows_affected() == 1;
+
+        if inserted {
+            sqlx::query("UPDATE artists SET follower_count = follower_count + 1 WHERE user_id = $1")
+                .bind(followee_id.to_uuid())
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
 
-        Ok(())
+        tx.commit().await.map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(inserted)
     }
 
-    async fn remove_follower(&self, follower_id: &UserId, followee_id: &UserId) -> Result<(), AppError> {
-        sqlx::query(
-            r#"DELETE FROM user_followers 
+    async fn remove_follower(&self, follower_id: &UserId, followee_id: &UserId) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+
+        let deleted = sqlx::query(
+            r#"DELETE FROM follows 
                WHERE follower_id = $1 AND followee_id = $2"#
         )
         .bind(follower_id.to_uuid())
         .bind(followee_id.to_uuid())
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await
-        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?
+        .rows_affected() == 1;
+
+        if deleted {
+            sqlx::query("UPDATE artists SET follower_count = follower_count - 1 WHERE user_id = $1")
+                .bind(followee_id.to_uuid())
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        }
 
-        Ok(())
+        tx.commit().await.map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(deleted)
     }
 
     async fn get_followers(&self, user_id: &UserId, page: u32, page_size: u32) -> Result<Vec<crate::bounded_contexts::user::domain::aggregates::UserSummary>, AppError> {
@@ -602,9 +650,9 @@ impl UserRepository for UserPostgresRepository {
                 u.total_rewards_earned, u.tier_points,
                 u.created_at
             FROM users u
-            INNER JOIN user_followers uf ON u.id = uf.follower_id
+            INNER JOIN follows uf ON u.id = uf.follower_id
             WHERE uf.followee_id = $1
-            ORDER BY uf.created_at DESC
+            ORDER BY uf.created_at DESC, u.id
             LIMIT $2 OFFSET $3
             "#
         )
@@ -653,9 +701,9 @@ impl UserRepository for UserPostgresRepository {
                 u.total_rewards_earned, u.tier_points,
                 u.created_at
             FROM users u
-            INNER JOIN user_followers uf ON u.id = uf.followee_id
+            INNER JOIN follows uf ON u.id = uf.followee_id
             WHERE uf.follower_id = $1
-            ORDER BY uf.created_at DESC
+            ORDER BY uf.created_at DESC, u.id
             LIMIT $2 OFFSET $3
             "#
         )
@@ -691,6 +739,24 @@ impl UserRepository for UserPostgresRepository {
         Ok(following)
     }
 
+    async fn count_followers(&self, user_id: &UserId) -> Result<u64, AppError> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM follows WHERE followee_id = $1")
+            .bind(user_id.to_uuid())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(count as u64)
+    }
+
+    async fn count_following(&self, user_id: &UserId) -> Result<u64, AppError> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM follows WHERE follower_id = $1")
+            .bind(user_id.to_uuid())
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(count as u64)
+    }
+
     async fn is_following(&self, follower_id: &UserId, followee_id: &UserId) -> Result<bool, AppError> {
         let follower_uuid = follower_id.to_uuid();
         let followee_uuid = followee_id.to_uuid();
@@ -698,7 +764,7 @@ impl UserRepository for UserPostgresRepository {
         let result = sqlx::query(
             r#"
             SELECT EXISTS(
-                SELECT 1 FROM user_followers 
+                SELECT 1 FROM follows 
                 WHERE follower_id = $1 AND followee_id = $2
             ) as is_following
             "#
@@ -0,0 +1,58 @@
+//! Follows User Deletion Listener
+//!
+//! Borra las relaciones de seguimiento de una cuenta eliminada, en ambos
+//! sentidos, y descuenta de `artists.follower_count` en la misma transacción
+//! los seguidores que desaparecen.
+
+use async_trait::async_trait;
+use sqlx::PgPool;
+
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+use crate::shared::domain::errors::AppError;
+
+pub struct FollowsUserDeletionListener {
+    pool: PgPool,
+}
+
+impl FollowsUserDeletionListener {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_error(e: sqlx::Error) -> AppError {
+    AppError::DatabaseError(format!("Failed to delete follows: {}", e))
+}
+
+#[async_trait]
+impl EventHandler for FollowsUserDeletionListener {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        let DomainEvent::UserDeletionRequested { user_id, .. } = event else {
+            return Ok(());
+        };
+
+        // Solo se descuenta lo que este DELETE borró: un unfollow concurrente
+        // de la misma fila no lo descuenta otra vez
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let removed: i64 = sqlx::query_scalar(
+            r#"WITH removed AS (
+                   DELETE FROM follows WHERE follower_id = $1 OR followee_id = $1
+                   RETURNING followee_id
+               ), decremented AS (
+                   UPDATE artists a
+                   SET follower_count = a.follower_count - r.followers
+                   FROM (SELECT followee_id, COUNT(*) AS followers FROM removed GROUP BY followee_id) r
+                   WHERE a.user_id = r.followee_id
+               )
+               SELECT COUNT(*) FROM removed"#,
+        )
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(db_error)?;
+        tx.commit().await.map_err(db_error)?;
+
+        tracing::info!("Deleted {} follows of deleted user {}", removed, user_id);
+        Ok(())
+    }
+}
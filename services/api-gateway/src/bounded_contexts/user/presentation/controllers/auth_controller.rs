@@ -0,0 +1,446 @@
+// Auth Session Controller
+// Refresh-token rotation, logout, per-device session management and wallet login
+
+use axum::{
+    extract::{Json, Path, State},
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::two_factor_controller::{two_factor_step_up, TwoFactorPendingResponse};
+use super::user_controller::{ApiResponse, LoginResponse, RefreshTokenRequest, RefreshTokenResponse};
+use crate::bounded_contexts::user::application::services::UserApplicationService;
+use crate::bounded_contexts::user::domain::{entities::User, repository::UserRepository, value_objects::UserId};
+use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::auth::{
+    AuthenticatedUser, DeviceMetadata, JwtService, RefreshSession, RefreshTokenError, RefreshTokenService, TokenPair,
+    WalletAuthError, WalletAuthService,
+};
+use crate::shared::infrastructure::database::postgres::PostgresUserRepository;
+
+type UserAppService = UserApplicationService<PostgresUserRepository>;
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LogoutRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SessionResponse {
+    pub session_id: Uuid,
+    pub device_name: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub started_at: chrono::DateTime<chrono::Utc>,
+    pub last_refreshed_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+impl From<RefreshSession> for SessionResponse {
+    fn from(session: RefreshSession) -> Self {
+        Self {
+            session_id: session.session_id,
+            device_name: session.device.device_name,
+            user_agent: session.device.user_agent,
+            ip_address: session.device.ip_address,
+            started_at: session.started_at,
+            last_refreshed_at: session.last_refreshed_at,
+            expires_at: session.expires_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct WalletChallengeRequest {
+    /// Solana (base58) or Ethereum (0x) address
+    pub address: String,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WalletChallengeResponse {
+    pub address: String,
+    pub chain: String,
+    pub nonce: String,
+    /// Exact text the wallet has to sign
+    pub message: String,
+    pub issued_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct WalletVerifyRequest {
+    pub message: String,
+    /// base58 for Solana, 0x-prefixed hex (65 bytes) for Ethereum
+    pub signature: String,
+    pub device_name: Option<String>,
+}
+
+// =============================================================================
+// TOKEN ISSUANCE (compartido con login)
+// =============================================================================
+
+/// Access JWT carrying the user's current role and tier
+pub(crate) fn access_token_for(user: &User) -> Result<(String, u64), AppError> {
+    let jwt_service = JwtService::from_env()?;
+    let access_token = jwt_service.generate_access_token(
+        user.id.value(),
+        user.username.value(),
+        user.email.value(),
+        &user.role.to_string(),
+        &user.tier.to_string(),
+    )?;
+    Ok((access_token, jwt_service.access_token_expiry_secs()))
+}
+
+/// Tokens handed out on login: an access JWT and, when sessions are enabled,
+/// the refresh token of a new session for this device
+pub(crate) async fn start_session(
+    user_service: &UserAppService,
+    user: &User,
+    device: DeviceMetadata,
+) -> Result<TokenPair, AppError> {
+    let (access_token, expires_in) = access_token_for(user)?;
+    let refresh_token = match &user_service.sessions {
+        Some(sessions) => Some(sessions.issue(user.id.value(), device).await?.token),
+        None => None,
+    };
+    Ok(TokenPair { access_token, refresh_token, expires_in })
+}
+
+fn sessions_of(user_service: &UserAppService) -> Result<&Arc<RefreshTokenService>, StatusCode> {
+    user_service.sessions.as_ref().ok_or_else(|| {
+        tracing::error!("Refresh token sessions are not configured");
+        StatusCode::SERVICE_UNAVAILABLE
+    })
+}
+
+fn wallet_auth_of(user_service: &UserAppService) -> Result<&Arc<WalletAuthService>, StatusCode> {
+    user_service.wallet_auth.as_ref().ok_or_else(|| {
+        tracing::error!("Wallet login is not configured");
+        StatusCode::SERVICE_UNAVAILABLE
+    })
+}
+
+fn unauthorized<T>(message: impl Into<String>) -> (StatusCode, Json<ApiResponse<T>>) {
+    (StatusCode::UNAUTHORIZED, Json(ApiResponse {
+        success: false,
+        data: None,
+        message: Some(message.into()),
+        errors: None,
+    }))
+}
+
+// =============================================================================
+// ENDPOINTS
+// =============================================================================
+
+/// Exchange a refresh token for a new access token and a new refresh token.
+/// The presented token stops working; presenting it again revokes the session.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/refresh",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 200, description = "Token refreshed successfully", body = ApiResponse<RefreshTokenResponse>),
+        (status = 401, description = "Invalid, expired, revoked or reused refresh token", body = ApiResponse<serde_json::Value>)
+    ),
+    tag = "auth"
+)]
+pub async fn refresh_session(
+    State(user_service): State<UserAppService>,
+    headers: HeaderMap,
+    Json(request): Json<RefreshTokenRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<RefreshTokenResponse>>), StatusCode> {
+    let sessions = sessions_of(&user_service)?;
+
+    let rotated = match sessions.rotate(&request.refresh_token, DeviceMetadata::from_headers(&headers, None)).await {
+        Ok(rotated) => rotated,
+        Err(RefreshTokenError::Store(e)) => {
+            tracing::error!("Error rotating refresh token: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Err(e) => return Ok(unauthorized(e.to_string())),
+    };
+
+    // El access token refleja el rol y el tier actuales; una cuenta desactivada
+    // pierde también la sesión
+    let user_id = rotated.record.user_id;
+    let user = user_service
+        .repository
+        .find_by_id(&UserId::from_uuid(user_id))
+        .await
+        .map_err(|e| {
+            tracing::error!("Error loading user {} for refresh: {}", user_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .map(|aggregate| aggregate.user)
+        .filter(|user| user.is_active);
+    let Some(user) = user else {
+        if let Err(e) = sessions.revoke_session(user_id, rotated.record.family_id).await {
+            tracing::warn!("Failed to revoke session of unavailable user {}: {}", user_id, e);
+        }
+        return Ok(unauthorized("Usuario no disponible"));
+    };
+
+    let (access_token, expires_in) = access_token_for(&user).map_err(|e| {
+        tracing::error!("Error generating access token: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok((StatusCode::OK, Json(ApiResponse {
+        success: true,
+        data: Some(RefreshTokenResponse {
+            access_token,
+            refresh_token: rotated.token,
+            expires_in,
+        }),
+        message: Some("Token renovado exitosamente".to_string()),
+        errors: None,
+    })))
+}
+
+/// End the session the refresh token belongs to
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/logout",
+    request_body = LogoutRequest,
+    responses(
+        (status = 200, description = "Session closed", body = ApiResponse<serde_json::Value>)
+    ),
+    tag = "auth"
+)]
+pub async fn logout(
+    State(user_service): State<UserAppService>,
+    Json(request): Json<LogoutRequest>,
+) -> Result<Json<ApiResponse<serde_json::Value>>, StatusCode> {
+    sessions_of(&user_service)?
+        .revoke(&request.refresh_token)
+        .await
+        .map_err(|e| {
+            tracing::error!("Error revoking session on logout: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: None,
+        message: Some("Sesión cerrada".to_string()),
+        errors: None,
+    }))
+}
+
+/// Active sessions (one per device) of the authenticated user
+#[utoipa::path(
+    get,
+    path = "/api/v1/auth/sessions",
+    responses(
+        (status = 200, description = "Active sessions", body = ApiResponse<Vec<SessionResponse>>),
+        (status = 401, description = "Not authenticated")
+    ),
+    tag = "auth"
+)]
+pub async fn list_sessions(
+    AuthenticatedUser { user_id, .. }: AuthenticatedUser,
+    State(user_service): State<UserAppService>,
+) -> Result<Json<ApiResponse<Vec<SessionResponse>>>, StatusCode> {
+    let sessions = sessions_of(&user_service)?
+        .sessions(user_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Error listing sessions of user {}: {}", user_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(sessions.into_iter().map(SessionResponse::from).collect()),
+        message: None,
+        errors: None,
+    }))
+}
+
+/// Revoke one of the authenticated user's sessions, e.g. a lost device
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/sessions/{session_id}/revoke",
+    params(
+        ("session_id" = Uuid, Path, description = "Session ID")
+    ),
+    responses(
+        (status = 200, description = "Session revoked", body = ApiResponse<serde_json::Value>),
+        (status = 404, description = "No such active session", body = ApiResponse<serde_json::Value>)
+    ),
+    tag = "auth"
+)]
+pub async fn revoke_session(
+    AuthenticatedUser { user_id, .. }: AuthenticatedUser,
+    State(user_service): State<UserAppService>,
+    Path(session_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<ApiResponse<serde_json::Value>>), StatusCode> {
+    let revoked = sessions_of(&user_service)?
+        .revoke_session(user_id, session_id)
+        .await
+        .map_err(|e| {
+            tracing::error!("Error revoking session {}: {}", session_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    if !revoked {
+        return Ok((StatusCode::NOT_FOUND, Json(ApiResponse {
+            success: false,
+            data: None,
+            message: Some("Sesión no encontrada".to_string()),
+            errors: None,
+        })));
+    }
+
+    Ok((StatusCode::OK, Json(ApiResponse {
+        success: true,
+        data: Some(serde_json::json!({ "session_id": session_id })),
+        message: Some("Sesión revocada".to_string()),
+        errors: None,
+    })))
+}
+
+/// Message with a single-use nonce for the wallet to sign (SIWS / SIWE)
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/wallet/challenge",
+    request_body = WalletChallengeRequest,
+    responses(
+        (status = 200, description = "Message to sign", body = ApiResponse<WalletChallengeResponse>),
+        (status = 400, description = "Not a Solana or Ethereum address", body = ApiResponse<serde_json::Value>)
+    ),
+    tag = "auth"
+)]
+pub async fn wallet_challenge(
+    State(user_service): State<UserAppService>,
+    Json(request): Json<WalletChallengeRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<WalletChallengeResponse>>), StatusCode> {
+    let challenge = match wallet_auth_of(&user_service)?.challenge(&request.address).await {
+        Ok(challenge) => challenge,
+        Err(WalletAuthError::Store(e)) => {
+            tracing::error!("Error storing wallet challenge: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Err(e) => {
+            return Ok((StatusCode::BAD_REQUEST, Json(ApiResponse {
+                success: false,
+                data: None,
+                message: Some(e.to_string()),
+                errors: None,
+            })));
+        }
+    };
+
+    Ok((StatusCode::OK, Json(ApiResponse {
+        success: true,
+        data: Some(WalletChallengeResponse {
+            address: challenge.address,
+            chain: challenge.chain.as_str().to_string(),
+            nonce: challenge.nonce,
+            message: challenge.message,
+            issued_at: challenge.issued_at,
+            expires_at: challenge.expires_at,
+        }),
+        message: None,
+        errors: None,
+    })))
+}
+
+/// Log in with a signed challenge. The wallet's account is used (or created);
+/// with a valid access token the wallet is linked to that account instead.
+/// Returns the same tokens as password login, including the 2FA step.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/wallet/verify",
+    request_body = WalletVerifyRequest,
+    responses(
+        (status = 200, description = "Login successful", body = ApiResponse<LoginResponse>),
+        (status = 202, description = "Signature accepted, 2FA code required at /api/v1/auth/2fa/verify", body = ApiResponse<TwoFactorPendingResponse>),
+        (status = 401, description = "Invalid signature, or unknown, used or expired nonce", body = ApiResponse<serde_json::Value>),
+        (status = 409, description = "Wallet linked to another account", body = ApiResponse<serde_json::Value>)
+    ),
+    tag = "auth"
+)]
+pub async fn wallet_verify(
+    State(user_service): State<UserAppService>,
+    caller: Option<AuthenticatedUser>,
+    headers: HeaderMap,
+    Json(request): Json<WalletVerifyRequest>,
+) -> Result<Response, StatusCode> {
+    let wallet = match wallet_auth_of(&user_service)?
+        .verify(&request.message, &request.signature, chrono::Utc::now())
+        .await
+    {
+        Ok(wallet) => wallet,
+        Err(WalletAuthError::Store(e)) => {
+            tracing::error!("Error consuming wallet nonce: {}", e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+        Err(e) => return Ok(unauthorized::<LoginResponse>(e.to_string()).into_response()),
+    };
+
+    let link_to = caller.map(|caller| caller.user_id);
+    let user = match user_service.sign_in_with_wallet(&wallet, link_to).await {
+        Ok(user) => user,
+        Err(AppError::ValidationError(message)) => {
+            return Ok((StatusCode::CONFLICT, Json(ApiResponse::<LoginResponse> {
+                success: false,
+                data: None,
+                message: Some(message),
+                errors: None,
+            })).into_response());
+        }
+        Err(e) => {
+            tracing::error!("Error resolving account of wallet {}: {}", wallet.address, e);
+            return Err(StatusCode::INTERNAL_SERVER_ERROR);
+        }
+    };
+    if !user.is_active {
+        return Ok(unauthorized::<LoginResponse>("Usuario no disponible").into_response());
+    }
+
+    // Enlazar una wallet a la sesión actual no es un login: solo este último pide el segundo factor
+    if link_to.is_none() {
+        let pending = two_factor_step_up(&user_service, &user).await.map_err(|e| {
+            tracing::error!("Error checking 2FA of user {}: {}", user.id.value(), e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        if let Some(pending) = pending {
+            return Ok((StatusCode::ACCEPTED, Json(ApiResponse {
+                success: true,
+                data: Some(pending),
+                message: Some("Introduce el código de verificación".to_string()),
+                errors: None,
+            })).into_response());
+        }
+    }
+
+    let device = DeviceMetadata::from_headers(&headers, request.device_name.clone());
+    let token_pair = start_session(&user_service, &user, device).await.map_err(|e| {
+        tracing::error!("Error issuing tokens for user {}: {}", user.id.value(), e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok((StatusCode::OK, Json(ApiResponse {
+        success: true,
+        data: Some(LoginResponse {
+            user_id: user.id.value(),
+            username: user.username.value().to_string(),
+            email: user.email.value().to_string(),
+            display_name: None,
+            token: token_pair.access_token,
+            refresh_token: token_pair.refresh_token,
+            expires_in: token_pair.expires_in,
+            user_role: user.role.to_string(),
+            tier: user.tier.to_string(),
+        }),
+        message: Some("Login exitoso".to_string()),
+        errors: None,
+    })).into_response())
+}
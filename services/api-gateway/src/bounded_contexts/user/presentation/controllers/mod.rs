@@ -2,5 +2,8 @@
 // This module exports all user-related REST controllers
 
 pub mod user_controller;
+pub mod auth_controller;
+pub mod two_factor_controller;
+pub mod privacy_controller;
 
 pub use user_controller::*; 
\ No newline at end of file
@@ -0,0 +1,235 @@
+// Privacy Controller
+// Account deletion and personal data exports (GDPR)
+
+use axum::{
+    extract::{Json, Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::user_controller::ApiResponse;
+use crate::bounded_contexts::user::application::services::UserApplicationService;
+use crate::bounded_contexts::user::infrastructure::data_export::UserDataExport;
+use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::auth::{AuthenticatedUser, LinkSignature, SignedLinkService};
+use crate::shared::infrastructure::database::postgres::PostgresUserRepository;
+
+type UserAppService = UserApplicationService<PostgresUserRepository>;
+
+/// How long a download link is valid (never beyond the archive's own expiry)
+const DOWNLOAD_LINK_TTL_MINUTES: i64 = 60;
+
+/// Proof that the person at the keyboard is the account owner
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct ReauthenticationRequest {
+    pub password: Option<String>,
+    /// Required when 2FA is enabled; for wallet-only accounts it replaces the password
+    pub two_factor_code: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DataExportResponse {
+    pub export_id: Uuid,
+    /// pending, ready or failed
+    pub status: String,
+    pub requested_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub expires_at: Option<DateTime<Utc>>,
+    /// Signed link, present once the archive is ready
+    pub download_url: Option<String>,
+}
+
+impl DataExportResponse {
+    fn new(export: &UserDataExport, download_url: Option<String>) -> Self {
+        Self {
+            export_id: export.id,
+            status: serde_json::to_value(export.status)
+                .ok()
+                .and_then(|status| status.as_str().map(str::to_string))
+                .unwrap_or_default(),
+            requested_at: export.requested_at,
+            completed_at: export.completed_at,
+            expires_at: export.expires_at,
+            download_url,
+        }
+    }
+}
+
+fn download_path(export_id: Uuid) -> String {
+    format!("/api/v1/users/exports/{}/download", export_id)
+}
+
+fn failure<T>(status: StatusCode, message: impl Into<String>) -> (StatusCode, Json<ApiResponse<T>>) {
+    (status, Json(ApiResponse {
+        success: false,
+        data: None,
+        message: Some(message.into()),
+        errors: None,
+    }))
+}
+
+/// Error de la aplicación como respuesta; los internos solo van al log
+pub(crate) fn app_failure<T>(error: AppError) -> Result<(StatusCode, Json<ApiResponse<T>>), StatusCode> {
+    let status = StatusCode::from(error.clone());
+    if status.is_server_error() {
+        tracing::error!("Privacy request failed: {}", error);
+        return Err(status);
+    }
+    Ok(failure(status, error.to_string()))
+}
+
+// =============================================================================
+// ENDPOINTS
+// =============================================================================
+
+/// Request an archive of all the user's personal data. It is built in the
+/// background; poll the export to get the download link.
+#[utoipa::path(
+    post,
+    path = "/api/v1/users/{user_id}/export",
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    request_body = ReauthenticationRequest,
+    responses(
+        (status = 202, description = "Export started", body = ApiResponse<DataExportResponse>),
+        (status = 401, description = "Re-authentication failed", body = ApiResponse<serde_json::Value>),
+        (status = 403, description = "Not your account", body = ApiResponse<serde_json::Value>)
+    ),
+    tag = "users"
+)]
+pub async fn request_data_export(
+    AuthenticatedUser { user_id, .. }: AuthenticatedUser,
+    State(user_service): State<UserAppService>,
+    Path(requested_user_id): Path<Uuid>,
+    Json(request): Json<ReauthenticationRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<DataExportResponse>>), StatusCode> {
+    if user_id != requested_user_id {
+        return Ok(failure(StatusCode::FORBIDDEN, "Solo puedes exportar los datos de tu propia cuenta"));
+    }
+    let now = Utc::now();
+    if let Err(e) = user_service
+        .reauthenticate(user_id, request.password.as_deref(), request.two_factor_code.as_deref(), now)
+        .await
+    {
+        return app_failure(e);
+    }
+
+    let export = match user_service.request_data_export(user_id, now).await {
+        Ok(export) => export,
+        Err(e) => return app_failure(e),
+    };
+
+    Ok((StatusCode::ACCEPTED, Json(ApiResponse {
+        success: true,
+        data: Some(DataExportResponse::new(&export, None)),
+        message: Some("Estamos preparando tus datos".to_string()),
+        errors: None,
+    })))
+}
+
+/// Status of an export, with a signed download link once it is ready
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{user_id}/exports/{export_id}",
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("export_id" = Uuid, Path, description = "Export ID")
+    ),
+    responses(
+        (status = 200, description = "Export status", body = ApiResponse<DataExportResponse>),
+        (status = 403, description = "Not your account", body = ApiResponse<serde_json::Value>),
+        (status = 404, description = "Export not found", body = ApiResponse<serde_json::Value>)
+    ),
+    tag = "users"
+)]
+pub async fn get_data_export(
+    AuthenticatedUser { user_id, .. }: AuthenticatedUser,
+    State(user_service): State<UserAppService>,
+    Path((requested_user_id, export_id)): Path<(Uuid, Uuid)>,
+) -> Result<(StatusCode, Json<ApiResponse<DataExportResponse>>), StatusCode> {
+    if user_id != requested_user_id {
+        return Ok(failure(StatusCode::FORBIDDEN, "Solo puedes consultar los datos de tu propia cuenta"));
+    }
+    let Some(data_exports) = &user_service.data_exports else {
+        return Err(StatusCode::SERVICE_UNAVAILABLE);
+    };
+    // La exportación de otro usuario se trata como inexistente
+    let export = match data_exports.find(export_id).await {
+        Ok(Some(export)) if export.user_id == user_id => export,
+        Ok(_) => return Ok(failure(StatusCode::NOT_FOUND, "Exportación no encontrada")),
+        Err(e) => return app_failure(e),
+    };
+
+    let now = Utc::now();
+    let download_url = match export.expires_at {
+        Some(expires_at) if export.is_downloadable(now) => {
+            let links = SignedLinkService::from_env().map_err(|e| {
+                tracing::error!("Cannot sign download links: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+            let link_expires_at = expires_at.min(now + Duration::minutes(DOWNLOAD_LINK_TTL_MINUTES));
+            Some(links.sign(&download_path(export.id), link_expires_at))
+        }
+        _ => None,
+    };
+
+    Ok((StatusCode::OK, Json(ApiResponse {
+        success: true,
+        data: Some(DataExportResponse::new(&export, download_url)),
+        message: None,
+        errors: None,
+    })))
+}
+
+/// Download an export archive. Public: the signed link is the credential.
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/exports/{export_id}/download",
+    params(
+        ("export_id" = Uuid, Path, description = "Export ID"),
+        ("expires" = i64, Query, description = "Link expiry (unix timestamp)"),
+        ("signature" = String, Query, description = "Link signature")
+    ),
+    responses(
+        (status = 200, description = "JSON archive", body = serde_json::Value),
+        (status = 403, description = "Invalid or expired link"),
+        (status = 410, description = "Archive no longer available")
+    ),
+    tag = "users"
+)]
+pub async fn download_data_export(
+    State(user_service): State<UserAppService>,
+    Path(export_id): Path<Uuid>,
+    Query(link): Query<LinkSignature>,
+) -> Result<Response, StatusCode> {
+    let now = Utc::now();
+    let links = SignedLinkService::from_env().map_err(|e| {
+        tracing::error!("Cannot verify download links: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    if !links.verify(&download_path(export_id), &link, now) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let data_exports = user_service.data_exports.as_ref().ok_or(StatusCode::SERVICE_UNAVAILABLE)?;
+    let internal_error = |e: AppError| {
+        tracing::error!("Error reading data export {}: {}", export_id, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    };
+    let export = data_exports.find(export_id).await.map_err(internal_error)?.ok_or(StatusCode::NOT_FOUND)?;
+    if !export.is_downloadable(now) {
+        return Err(StatusCode::GONE);
+    }
+    let archive = data_exports.archive(export_id).await.map_err(internal_error)?.ok_or(StatusCode::GONE)?;
+
+    let disposition = format!("attachment; filename=\"vibestream-data-{}.json\"", export_id);
+    Ok((
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "application/json".to_string()), (header::CONTENT_DISPOSITION, disposition)],
+        Json(archive),
+    )
+        .into_response())
+}
@@ -0,0 +1,274 @@
+// Two-Factor Authentication Controller
+// TOTP setup, confirmation and removal, and the second step of login
+
+use axum::{
+    extract::{Json, State},
+    http::{HeaderMap, StatusCode},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::auth_controller::start_session;
+use super::user_controller::{ApiResponse, LoginResponse};
+use crate::bounded_contexts::user::application::services::UserApplicationService;
+use crate::bounded_contexts::user::domain::{entities::User, repository::UserRepository, value_objects::UserId};
+use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::auth::{
+    jwt_service::TWO_FACTOR_PENDING_EXPIRY_SECS, AuthenticatedUser, DeviceMetadata, JwtService, TwoFactorError,
+    TwoFactorService,
+};
+use crate::shared::infrastructure::database::postgres::PostgresUserRepository;
+
+type UserAppService = UserApplicationService<PostgresUserRepository>;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TwoFactorSetupResponse {
+    /// Base32 secret, for apps that cannot scan the QR code
+    pub secret: String,
+    /// `otpauth://` URI to show as a QR code
+    pub otpauth_uri: String,
+    /// Single-use codes for when the device is lost. Shown only once.
+    pub recovery_codes: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TwoFactorCodeRequest {
+    /// 6-digit code from the authenticator app (or a recovery code to disable)
+    pub code: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TwoFactorVerifyRequest {
+    /// Token returned by login when the account has 2FA enabled
+    pub two_factor_token: String,
+    /// 6-digit code from the authenticator app or a recovery code
+    pub code: String,
+    pub device_name: Option<String>,
+}
+
+/// Login answer for accounts with 2FA: the password was right, the code is still missing
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TwoFactorPendingResponse {
+    pub two_factor_required: bool,
+    /// Exchange it at /api/v1/auth/2fa/verify together with a code
+    pub two_factor_token: String,
+    pub expires_in: u64,
+}
+
+// =============================================================================
+// LOGIN STEP-UP (compartido con login)
+// =============================================================================
+
+/// When the user has 2FA enabled, the "2FA pending" token to hand out instead of the session
+pub(crate) async fn two_factor_step_up(
+    user_service: &UserAppService,
+    user: &User,
+) -> Result<Option<TwoFactorPendingResponse>, AppError> {
+    let Some(two_factor) = &user_service.two_factor else {
+        return Ok(None);
+    };
+    if !two_factor.is_enabled(user.id.value()).await? {
+        return Ok(None);
+    }
+    let two_factor_token = JwtService::from_env()?.generate_two_factor_pending_token(user.id.value())?;
+    Ok(Some(TwoFactorPendingResponse {
+        two_factor_required: true,
+        two_factor_token,
+        expires_in: TWO_FACTOR_PENDING_EXPIRY_SECS,
+    }))
+}
+
+fn two_factor_of(user_service: &UserAppService) -> Result<&Arc<TwoFactorService>, StatusCode> {
+    user_service.two_factor.as_ref().ok_or_else(|| {
+        tracing::error!("Two-factor authentication is not configured");
+        StatusCode::SERVICE_UNAVAILABLE
+    })
+}
+
+fn failure<T>(status: StatusCode, message: impl Into<String>) -> (StatusCode, Json<ApiResponse<T>>) {
+    (status, Json(ApiResponse {
+        success: false,
+        data: None,
+        message: Some(message.into()),
+        errors: None,
+    }))
+}
+
+/// Error de 2FA como respuesta: código inválido es 401, estado incorrecto 409
+fn two_factor_failure<T>(error: TwoFactorError) -> Result<(StatusCode, Json<ApiResponse<T>>), StatusCode> {
+    match error {
+        TwoFactorError::Store(e) => {
+            tracing::error!("Two-factor store error: {}", e);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+        TwoFactorError::InvalidCode => Ok(failure(StatusCode::UNAUTHORIZED, "Código de verificación inválido")),
+        other => Ok(failure(StatusCode::CONFLICT, other.to_string())),
+    }
+}
+
+// =============================================================================
+// ENDPOINTS
+// =============================================================================
+
+/// Start the 2FA setup: a new TOTP secret and recovery codes.
+/// Login does not ask for codes until the setup is confirmed.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/2fa/setup",
+    responses(
+        (status = 200, description = "Secret and recovery codes", body = ApiResponse<TwoFactorSetupResponse>),
+        (status = 401, description = "Not authenticated"),
+        (status = 409, description = "2FA already enabled", body = ApiResponse<serde_json::Value>)
+    ),
+    tag = "auth"
+)]
+pub async fn setup_two_factor(
+    AuthenticatedUser { user_id, email, .. }: AuthenticatedUser,
+    State(user_service): State<UserAppService>,
+) -> Result<(StatusCode, Json<ApiResponse<TwoFactorSetupResponse>>), StatusCode> {
+    let setup = match two_factor_of(&user_service)?.begin_setup(user_id, &email, chrono::Utc::now()).await {
+        Ok(setup) => setup,
+        Err(e) => return two_factor_failure(e),
+    };
+
+    Ok((StatusCode::OK, Json(ApiResponse {
+        success: true,
+        data: Some(TwoFactorSetupResponse {
+            secret: setup.secret,
+            otpauth_uri: setup.otpauth_uri,
+            recovery_codes: setup.recovery_codes,
+        }),
+        message: Some("Escanea el código y confirma con el primer código de la app".to_string()),
+        errors: None,
+    })))
+}
+
+/// Enable 2FA with a first code from the authenticator app
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/2fa/confirm",
+    request_body = TwoFactorCodeRequest,
+    responses(
+        (status = 200, description = "2FA enabled", body = ApiResponse<serde_json::Value>),
+        (status = 401, description = "Invalid code", body = ApiResponse<serde_json::Value>),
+        (status = 409, description = "No pending setup, or 2FA already enabled", body = ApiResponse<serde_json::Value>)
+    ),
+    tag = "auth"
+)]
+pub async fn confirm_two_factor(
+    AuthenticatedUser { user_id, .. }: AuthenticatedUser,
+    State(user_service): State<UserAppService>,
+    Json(request): Json<TwoFactorCodeRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<serde_json::Value>>), StatusCode> {
+    if let Err(e) = two_factor_of(&user_service)?.confirm(user_id, &request.code, chrono::Utc::now()).await {
+        return two_factor_failure(e);
+    }
+
+    Ok((StatusCode::OK, Json(ApiResponse {
+        success: true,
+        data: Some(serde_json::json!({ "two_factor_enabled": true })),
+        message: Some("Verificación en dos pasos activada".to_string()),
+        errors: None,
+    })))
+}
+
+/// Disable 2FA. Needs a current code (or a recovery code), not just a session.
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/2fa/disable",
+    request_body = TwoFactorCodeRequest,
+    responses(
+        (status = 200, description = "2FA disabled", body = ApiResponse<serde_json::Value>),
+        (status = 401, description = "Invalid code", body = ApiResponse<serde_json::Value>),
+        (status = 409, description = "2FA not enabled", body = ApiResponse<serde_json::Value>)
+    ),
+    tag = "auth"
+)]
+pub async fn disable_two_factor(
+    AuthenticatedUser { user_id, .. }: AuthenticatedUser,
+    State(user_service): State<UserAppService>,
+    Json(request): Json<TwoFactorCodeRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<serde_json::Value>>), StatusCode> {
+    if let Err(e) = two_factor_of(&user_service)?.disable(user_id, &request.code, chrono::Utc::now()).await {
+        return two_factor_failure(e);
+    }
+
+    Ok((StatusCode::OK, Json(ApiResponse {
+        success: true,
+        data: Some(serde_json::json!({ "two_factor_enabled": false })),
+        message: Some("Verificación en dos pasos desactivada".to_string()),
+        errors: None,
+    })))
+}
+
+/// Second step of login: exchange the "2FA pending" token and a code for the session tokens
+#[utoipa::path(
+    post,
+    path = "/api/v1/auth/2fa/verify",
+    request_body = TwoFactorVerifyRequest,
+    responses(
+        (status = 200, description = "Login successful", body = ApiResponse<LoginResponse>),
+        (status = 401, description = "Invalid or expired 2FA token, or invalid code", body = ApiResponse<serde_json::Value>)
+    ),
+    tag = "auth"
+)]
+pub async fn verify_two_factor(
+    State(user_service): State<UserAppService>,
+    headers: HeaderMap,
+    Json(request): Json<TwoFactorVerifyRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<LoginResponse>>), StatusCode> {
+    let two_factor = two_factor_of(&user_service)?;
+    let jwt_service = JwtService::from_env().map_err(|e| {
+        tracing::error!("JWT configuration error: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let Ok(user_id) = jwt_service.validate_two_factor_pending_token(&request.two_factor_token) else {
+        return Ok(failure(StatusCode::UNAUTHORIZED, "Token de verificación inválido o caducado"));
+    };
+
+    if let Err(e) = two_factor.verify(user_id, &request.code, chrono::Utc::now()).await {
+        return two_factor_failure(e);
+    }
+
+    let user = load_active_user(&user_service, user_id).await?;
+    let Some(user) = user else {
+        return Ok(failure(StatusCode::UNAUTHORIZED, "Usuario no disponible"));
+    };
+
+    let device = DeviceMetadata::from_headers(&headers, request.device_name.clone());
+    let token_pair = start_session(&user_service, &user, device).await.map_err(|e| {
+        tracing::error!("Error issuing tokens for user {}: {}", user_id, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok((StatusCode::OK, Json(ApiResponse {
+        success: true,
+        data: Some(LoginResponse {
+            user_id: user.id.value(),
+            username: user.username.value().to_string(),
+            email: user.email.value().to_string(),
+            display_name: None,
+            token: token_pair.access_token,
+            refresh_token: token_pair.refresh_token,
+            expires_in: token_pair.expires_in,
+            user_role: user.role.to_string(),
+            tier: user.tier.to_string(),
+        }),
+        message: Some("Login exitoso".to_string()),
+        errors: None,
+    })))
+}
+
+async fn load_active_user(user_service: &UserAppService, user_id: Uuid) -> Result<Option<User>, StatusCode> {
+    let user = user_service
+        .repository
+        .find_by_id(&UserId::from_uuid(user_id))
+        .await
+        .map_err(|e| {
+            tracing::error!("Error loading user {} for 2FA login: {}", user_id, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    Ok(user.map(|aggregate| aggregate.user).filter(|user| user.is_active))
+}
@@ -3,8 +3,8 @@
 
 use axum::{
     extract::{Path, Query, State, Json},
-    http::StatusCode,
-    response::Json as ResponseJson,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Json as ResponseJson, Response},
     routing::{get, post, put, delete},
     Router,
 };
@@ -17,15 +17,19 @@ use std::collections::HashMap;
 use crate::bounded_contexts::user::application::{
     handlers::{
         CreateUserCommand, UpdateUserCommand, FollowUserCommand,
-        GetUserQuery,
+        GetUserQuery, UserResponse,
         UserCommandHandler, UserQueryHandler,
     },
     services::UserApplicationService,
 };
 use crate::shared::infrastructure::database::postgres::PostgresUserRepository;
-use crate::shared::infrastructure::auth::{JwtService, PasswordService, AuthenticatedUser};
+use crate::shared::infrastructure::auth::{JwtService, PasswordService, AuthenticatedUser, DeviceMetadata};
+use super::auth_controller::start_session;
+use super::two_factor_controller::{two_factor_step_up, TwoFactorPendingResponse};
+use super::privacy_controller::{app_failure, ReauthenticationRequest};
 use crate::shared::domain::errors::AppError;
 use crate::bounded_contexts::user::domain::repository::UserRepository;
+use crate::bounded_contexts::user::domain::entities::User;
 use crate::shared::infrastructure::clients::facial_recognition_client::VerifyFaceResponse;
 
 // Type alias para simplificar el estado
@@ -59,6 +63,9 @@ pub struct LoginRequest {
     pub credential: String, // email or username
     pub password: String,
     pub remember_me: Option<bool>,
+    /// Shown in the session list, e.g. "iPhone de Ana"
+    #[serde(default)]
+    pub device_name: Option<String>,
 }
 
 #[derive(Debug, Deserialize, ToSchema)]
@@ -86,11 +93,25 @@ pub struct LoginResponse {
     pub tier: String,
 }
 
+/// JSON merge patch: an absent field is left as is, `null` clears it
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct UpdateUserRequest {
-    pub display_name: Option<String>,
-    pub bio: Option<String>,
-    pub profile_image_url: Option<String>,
+    /// Can change once every 30 days
+    pub username: Option<String>,
+    #[serde(default, deserialize_with = "patch_field")]
+    #[schema(value_type = Option<String>)]
+    pub display_name: Option<Option<String>>,
+    /// Up to 500 characters
+    #[serde(default, deserialize_with = "patch_field")]
+    #[schema(value_type = Option<String>)]
+    pub bio: Option<Option<String>>,
+    #[serde(default, alias = "profile_image_url", deserialize_with = "patch_field")]
+    #[schema(value_type = Option<String>)]
+    pub avatar_url: Option<Option<String>>,
+    /// EIP-55 checksummed if mixed case
+    #[serde(default, deserialize_with = "patch_field")]
+    #[schema(value_type = Option<String>)]
+    pub wallet_address: Option<Option<String>>,
     pub location: Option<String>,
     pub website: Option<String>,
     pub social_links: Option<HashMap<String, String>>,
@@ -139,6 +160,28 @@ pub struct UserStatsResponse {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Weight of one genre or mood in the user's recent listens
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct WeightedPreference {
+    pub value: String,
+    pub weight: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ListeningPreferencesResponse {
+    pub user_id: Uuid,
+    pub favorite_genres: Vec<WeightedPreference>,
+    pub favorite_moods: Vec<WeightedPreference>,
+    pub preferred_tempo_bpm: Option<u16>,
+    /// low, medium, high or lossless
+    pub preferred_quality: Option<String>,
+    /// Hours of the day (UTC)
+    pub active_hours: Vec<u8>,
+    /// Listens included in the vector
+    pub listens: i64,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
 #[derive(Debug, Deserialize, ToSchema)]
 pub struct FollowUserRequest {
     pub follow: bool, // true = follow, false = unfollow
@@ -277,14 +320,14 @@ pub async fn register_user(
                     StatusCode::INTERNAL_SERVER_ERROR
                 })?;
             
-            let token_pair = jwt_service.generate_token_pair(
+            let access_token = jwt_service.generate_access_token(
                 result.id,
                 &result.username,
                 &result.email,
                 "user", // Default role
                 "free", // Default tier
             ).map_err(|e| {
-                eprintln!("Error generating token pair: {}", e);
+                eprintln!("Error generating access token: {}", e);
                 StatusCode::INTERNAL_SERVER_ERROR
             })?;
 
@@ -293,7 +336,7 @@ pub async fn register_user(
                 username: result.username,
                 email: result.email,
                 display_name: result.display_name,
-                token: token_pair.access_token,
+                token: access_token,
                 created_at: result.created_at,
             };
 
@@ -320,6 +363,7 @@ pub async fn register_user(
     request_body = LoginRequest,
     responses(
         (status = 200, description = "Login successful", body = ApiResponse<LoginResponse>),
+        (status = 202, description = "Password accepted, 2FA code required at /api/v1/auth/2fa/verify", body = ApiResponse<TwoFactorPendingResponse>),
         (status = 401, description = "Invalid credentials", body = ApiResponse<serde_json::Value>)
     ),
     tag = "users"
@@ -327,8 +371,9 @@ pub async fn register_user(
 #[axum::debug_handler]
 pub async fn login_user(
     State(user_service): State<UserAppService>,
+    headers: HeaderMap,
     Json(request): Json<LoginRequest>,
-) -> Result<Json<ApiResponse<LoginResponse>>, StatusCode> {
+) -> Result<Response, StatusCode> {
     // Find user by email or username
     let user = if request.credential.contains('@') {
         // Search by email
@@ -340,49 +385,49 @@ pub async fn login_user(
             .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
     };
 
-    let user = user.ok_or(StatusCode::UNAUTHORIZED)?;
-    
-    // Verify password
-    let is_valid = PasswordService::verify_password(&request.password, user.password_hash.value())
-        .map_err(|e| {
-            eprintln!("Error verifying password: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-    
-    if !is_valid {
-        return Ok(Json(ApiResponse {
-            success: false,
-            data: None,
-            message: Some("Credenciales inválidas".to_string()),
+    // Verify password. Un usuario inexistente cuesta lo mismo que una contraseña
+    // incorrecta y recibe la misma respuesta
+    let user = match authenticate(user.as_ref(), &request.password, PasswordService::verify_credentials) {
+        Some(user) => user,
+        None => {
+            return Ok((StatusCode::UNAUTHORIZED, Json(ApiResponse::<LoginResponse> {
+                success: false,
+                data: None,
+                message: Some("Credenciales inválidas".to_string()),
+                errors: None,
+            })).into_response());
+        }
+    };
+
+    // Hashes bcrypt antiguos se migran a argon2id con la contraseña ya verificada
+    if PasswordService::needs_rehash(user.password_hash.value()) {
+        if let Err(e) = rehash_password(&user_service, user.id.value(), &request.password).await {
+            tracing::warn!("Failed to upgrade password hash for user {}: {}", user.id.value(), e);
+        }
+    }
+
+    // Con 2FA activado la contraseña solo da un token pendiente del segundo factor
+    let pending = two_factor_step_up(&user_service, user).await.map_err(|e| {
+        tracing::error!("Error checking 2FA of user {}: {}", user.id.value(), e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    if let Some(pending) = pending {
+        return Ok((StatusCode::ACCEPTED, Json(ApiResponse {
+            success: true,
+            data: Some(pending),
+            message: Some("Introduce el código de verificación".to_string()),
             errors: None,
-        }));
+        })).into_response());
     }
 
-    // Generate real JWT tokens
-    let jwt_secret = crate::shared::infrastructure::auth::get_jwt_secret()
-        .map_err(|e| {
-            eprintln!("JWT configuration error: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-    let jwt_service = JwtService::new(&jwt_secret)
-        .map_err(|e| {
-            eprintln!("Error creating JWT service: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-    
-    let role_str = format!("{}", user.role);
-    let tier_str = format!("{}", user.tier);
-    
-    let token_pair = jwt_service.generate_token_pair(
-        user.id.value(),
-        user.username.value(),
-        user.email.value(),
-        &role_str,
-        &tier_str,
-    ).map_err(|e| {
-        eprintln!("Error generating token pair: {}", e);
+    // Access token + refresh token de una sesión nueva para este dispositivo
+    let device = DeviceMetadata::from_headers(&headers, request.device_name.clone());
+    let token_pair = start_session(&user_service, user, device).await.map_err(|e| {
+        tracing::error!("Error issuing tokens for user {}: {}", user.id.value(), e);
         StatusCode::INTERNAL_SERVER_ERROR
     })?;
+    let role_str = format!("{}", user.role);
+    let tier_str = format!("{}", user.tier);
 
     let response = LoginResponse {
         user_id: user.id.value(),
@@ -390,81 +435,42 @@ pub async fn login_user(
         email: user.email.value().to_string(),
         display_name: None, // TODO: Add display_name field to User entity
         token: token_pair.access_token,
-        refresh_token: Some(token_pair.refresh_token),
+        refresh_token: token_pair.refresh_token,
         expires_in: token_pair.expires_in,
         user_role: role_str,
         tier: tier_str,
     };
 
-    Ok(Json(ApiResponse {
+    Ok((StatusCode::OK, Json(ApiResponse {
         success: true,
         data: Some(response),
         message: Some("Login exitoso".to_string()),
         errors: None,
-    }))
+    })).into_response())
 }
 
-/// Refresh access token using refresh token
-#[utoipa::path(
-    post,
-    path = "/api/v1/users/refresh",
-    request_body = RefreshTokenRequest,
-    responses(
-        (status = 200, description = "Token refreshed successfully", body = ApiResponse<RefreshTokenResponse>),
-        (status = 401, description = "Invalid refresh token", body = ApiResponse<serde_json::Value>)
-    ),
-    tag = "users"
-)]
-#[axum::debug_handler]
-pub async fn refresh_token(
-    Json(request): Json<RefreshTokenRequest>,
-) -> Result<Json<ApiResponse<RefreshTokenResponse>>, StatusCode> {
-    let jwt_secret = crate::shared::infrastructure::auth::get_jwt_secret()
-        .map_err(|e| {
-            eprintln!("JWT configuration error: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-    let jwt_service = JwtService::new(&jwt_secret)
-        .map_err(|e| {
-            eprintln!("Error creating JWT service: {}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-    
-    // Validate refresh token
-    let claims = jwt_service.validate_refresh_token(&request.refresh_token)
-        .map_err(|e| {
-            eprintln!("Error validating refresh token: {}", e);
-            StatusCode::UNAUTHORIZED
-        })?;
-    
-    // Parse user ID from claims
-    let user_id = Uuid::parse_str(&claims.sub)
-        .map_err(|_| StatusCode::UNAUTHORIZED)?;
-    
-    // Generate new token pair
-    let token_pair = jwt_service.generate_token_pair(
-        user_id,
-        &claims.username,
-        &claims.email,
-        &claims.role,
-        &claims.tier,
-    ).map_err(|e| {
-        eprintln!("Error generating token pair: {}", e);
-        StatusCode::INTERNAL_SERVER_ERROR
-    })?;
-    
-    let response = RefreshTokenResponse {
-        access_token: token_pair.access_token,
-        refresh_token: token_pair.refresh_token,
-        expires_in: token_pair.expires_in,
+/// Usuario al que corresponde `password`. La contraseña se verifica siempre
+/// (contra el hash ficticio si no hay cuenta) antes de decidir nada, para que
+/// el tiempo de respuesta no revele si el usuario existe
+fn authenticate<'a>(
+    user: Option<&'a User>,
+    password: &str,
+    verify_credentials: impl Fn(&str, Option<&str>) -> bool,
+) -> Option<&'a User> {
+    let valid = verify_credentials(password, user.map(|user| user.password_hash.value()));
+    user.filter(|user| valid && user.is_active)
+}
+
+async fn rehash_password(user_service: &UserAppService, user_id: Uuid, password: &str) -> Result<(), AppError> {
+    let user_id_vo = crate::bounded_contexts::user::domain::value_objects::UserId::from_uuid(user_id);
+    let Some(mut user_aggregate) = user_service.repository.find_by_id(&user_id_vo).await? else {
+        return Ok(());
     };
-    
-    Ok(Json(ApiResponse {
-        success: true,
-        data: Some(response),
-        message: Some("Token renovado exitosamente".to_string()),
-        errors: None,
-    }))
+    let new_hash = PasswordService::hash_password(password)?;
+    user_aggregate.user.update_password(
+        crate::bounded_contexts::user::domain::value_objects::PasswordHash::new(new_hash)
+    );
+    user_service.repository.update(&user_aggregate).await
 }
 
 /// Get user profile by ID
@@ -495,31 +501,9 @@ pub async fn get_user_profile(
     
     match user_service.handle_get_user(query).await {
         Ok(user_response) => {
-            let profile = UserProfileResponse {
-                id: user_response.id,
-                username: user_response.username,
-                email: user_response.email,
-                display_name: user_response.display_name,
-                bio: user_response.bio,
-                avatar_url: user_response.profile_image_url,
-                cover_url: None, // TODO: Add cover_url to UserResponse
-                location: None,  // TODO: Add location to UserResponse
-                website: None,   // TODO: Add website to UserResponse
-                social_links: HashMap::new(), // TODO: Add social_links to UserResponse
-                is_public: true, // TODO: Add is_public to UserResponse
-                tier: user_response.tier,
-                role: user_response.role, 
-                is_verified: user_response.is_verified,
-                is_active: user_response.is_active,
-                wallet_address: None,       // TODO: Add wallet_address to UserResponse
-                created_at: user_response.created_at,
-                updated_at: user_response.created_at, // TODO: Add updated_at to UserResponse
-                last_login_at: Some(Utc::now()),      // TODO: Add last_login_at to UserResponse
-            };
-
             Ok(Json(ApiResponse {
                 success: true,
-                data: Some(profile),
+                data: Some(profile_response(user_response)),
                 message: None,
                 errors: None,
             }))
@@ -533,48 +517,100 @@ pub async fn get_user_profile(
     }
 }
 
-/// PUT /api/v1/users/{user_id}
+fn profile_response(user_response: UserResponse) -> UserProfileResponse {
+    UserProfileResponse {
+        id: user_response.id,
+        username: user_response.username,
+        email: user_response.email,
+        display_name: user_response.display_name,
+        bio: user_response.bio,
+        avatar_url: user_response.profile_image_url,
+        cover_url: None, // TODO: Add cover_url to UserResponse
+        location: None,  // TODO: Add location to UserResponse
+        website: None,   // TODO: Add website to UserResponse
+        social_links: HashMap::new(), // TODO: Add social_links to UserResponse
+        is_public: true, // TODO: Add is_public to UserResponse
+        tier: user_response.tier,
+        role: user_response.role,
+        is_verified: user_response.is_verified,
+        is_active: user_response.is_active,
+        wallet_address: user_response.wallet_address,
+        created_at: user_response.created_at,
+        updated_at: user_response.created_at, // TODO: Add updated_at to UserResponse
+        last_login_at: Some(Utc::now()),      // TODO: Add last_login_at to UserResponse
+    }
+}
+
 /// Update user profile
+///
+/// Partial update: only the fields sent change, and `null` clears a field.
+#[utoipa::path(
+    put,
+    path = "/api/v1/users/{user_id}",
+    params(
+        ("user_id" = Uuid, Path, description = "User ID")
+    ),
+    request_body = UpdateUserRequest,
+    responses(
+        (status = 200, description = "Updated profile", body = ApiResponse<UserProfileResponse>),
+        (status = 400, description = "Invalid field, or username changed too recently", body = ApiResponse<serde_json::Value>),
+        (status = 403, description = "Not your profile", body = ApiResponse<serde_json::Value>),
+        (status = 404, description = "User not found", body = ApiResponse<serde_json::Value>),
+        (status = 409, description = "Username taken or wallet linked to another account", body = ApiResponse<serde_json::Value>)
+    ),
+    tag = "users"
+)]
 #[axum::debug_handler]
 pub async fn update_user_profile(
     AuthenticatedUser { user_id: authenticated_user_id, .. }: AuthenticatedUser,
     State(user_service): State<UserAppService>,
     Path(user_id): Path<Uuid>,
     Json(request): Json<UpdateUserRequest>,
-) -> Result<Json<ApiResponse<()>>, StatusCode> {
+) -> Result<(StatusCode, Json<ApiResponse<UserProfileResponse>>), StatusCode> {
     // Validar que el usuario solo puede editar su propio perfil
     if authenticated_user_id != user_id {
-        return Ok(Json(ApiResponse {
+        return Ok((StatusCode::FORBIDDEN, Json(ApiResponse {
             success: false,
             data: None,
             message: Some("Solo puedes editar tu propio perfil".to_string()),
             errors: None,
-        }));
+        })));
     }
     
     let command = UpdateUserCommand {
         user_id,
-        display_name: request.display_name.clone(),
-        bio: request.bio.clone(),
-        profile_image_url: request.profile_image_url.clone(),
+        username: request.username,
+        display_name: request.display_name,
+        bio: request.bio,
+        profile_image_url: request.avatar_url,
+        wallet_address: request.wallet_address,
     };
 
     match user_service.handle_update_user(command).await {
-        Ok(_) => Ok(Json(ApiResponse {
+        Ok(user_response) => Ok((StatusCode::OK, Json(ApiResponse {
             success: true,
-            data: None,
+            data: Some(profile_response(user_response)),
             message: Some("Perfil actualizado exitosamente".to_string()),
             errors: None,
-        })),
-        Err(e) => Ok(Json(ApiResponse {
+        }))),
+        Err(e) => Ok((StatusCode::from(e.clone()), Json(ApiResponse {
             success: false,
             data: None,
             message: Some(e.to_string()),
             errors: None,
-        })),
+        }))),
     }
 }
 
+/// Distingue un campo ausente (`None`) de un `null` explícito (`Some(None)`)
+fn patch_field<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
 /// GET /api/v1/users/{user_id}/stats
 /// Get user statistics
 /// 
@@ -625,6 +661,58 @@ pub async fn get_user_stats(
     }))
 }
 
+/// GET /api/v1/users/{user_id}/preferences
+/// Listening preference vector used for personalisation, as of its last
+/// refresh (every 10 listens)
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{user_id}/preferences",
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Listening preferences", body = ApiResponse<ListeningPreferencesResponse>),
+        (status = 403, description = "Not your account", body = ApiResponse<serde_json::Value>),
+        (status = 404, description = "User not found", body = ApiResponse<serde_json::Value>)
+    ),
+    tag = "users"
+)]
+#[axum::debug_handler]
+pub async fn get_listening_preferences(
+    AuthenticatedUser { user_id: authenticated_user_id, role, .. }: AuthenticatedUser,
+    State(user_service): State<UserAppService>,
+    Path(user_id): Path<Uuid>,
+) -> Result<(StatusCode, Json<ApiResponse<ListeningPreferencesResponse>>), StatusCode> {
+    if authenticated_user_id != user_id && role != "admin" {
+        return app_failure(AppError::Forbidden("Solo puedes ver tus propias preferencias".to_string()));
+    }
+    let stored = match user_service.get_listening_preferences(user_id).await {
+        Ok(stored) => stored,
+        Err(e) => return app_failure(e),
+    };
+
+    let preferences = stored.preferences;
+    let response = ListeningPreferencesResponse {
+        user_id,
+        favorite_genres: preferences.favorite_genres.iter()
+            .map(|(genre, weight)| WeightedPreference { value: genre.to_string(), weight: *weight })
+            .collect(),
+        favorite_moods: preferences.favorite_moods.iter()
+            .map(|(mood, weight)| WeightedPreference { value: mood.to_string().to_lowercase(), weight: *weight })
+            .collect(),
+        preferred_tempo_bpm: preferences.preferred_tempo_bpm,
+        preferred_quality: preferences.preferred_quality.map(|quality| quality.as_str().to_string()),
+        active_hours: preferences.active_hours,
+        listens: stored.listens,
+        updated_at: stored.updated_at,
+    };
+
+    Ok((StatusCode::OK, Json(ApiResponse {
+        success: true,
+        data: Some(response),
+        message: None,
+        errors: None,
+    })))
+}
+
 /// GET /api/v1/users/search
 /// Search users
 #[axum::debug_handler]
@@ -685,47 +773,32 @@ pub async fn search_users(
 }
 
 /// POST /api/v1/users/{user_id}/follow
-/// Follow/unfollow user
+/// Follow/unfollow user. Unfollowing someone you don't follow is a no-op.
 #[axum::debug_handler]
 pub async fn follow_user(
     AuthenticatedUser { user_id: follower_id, .. }: AuthenticatedUser,
     State(user_service): State<UserAppService>,
     Path(followee_id): Path<Uuid>,
     Json(request): Json<FollowUserRequest>,
-) -> Result<Json<ApiResponse<()>>, StatusCode> {
-    // Validar que no se puede seguir a sí mismo
-    if follower_id == followee_id {
-        return Ok(Json(ApiResponse {
-            success: false,
-            data: None,
-            message: Some("No puedes seguirte a ti mismo".to_string()),
-            errors: None,
-        }));
-    }
-    
+) -> Result<(StatusCode, Json<ApiResponse<()>>), StatusCode> {
     let command = FollowUserCommand {
         follower_id,
         followee_id,
         follow: request.follow,
     };
 
-    match user_service.handle_follow_user(command).await {
-        Ok(_) => {
-            let action = if request.follow { "seguido" } else { "dejado de seguir" };
-            Ok(Json(ApiResponse {
-                success: true,
-                data: None,
-                message: Some(format!("Usuario {} exitosamente", action)),
-                errors: None,
-            }))
-        }
-        Err(e) => Ok(Json(ApiResponse {
-            success: false,
-            data: None,
-            message: Some(e.to_string()),
-            errors: None,
-        })),
+    // Seguirse a sí mismo lo rechaza el servicio con un 400
+    if let Err(e) = user_service.handle_follow_user(command).await {
+        return app_failure(e);
     }
+
+    let action = if request.follow { "seguido" } else { "dejado de seguir" };
+    Ok((StatusCode::OK, Json(ApiResponse {
+        success: true,
+        data: None,
+        message: Some(format!("Usuario {} exitosamente", action)),
+        errors: None,
+    })))
 }
 
 /// POST /api/v1/users/{user_id}/change-password
@@ -902,64 +975,78 @@ pub async fn link_wallet(
 }
 
 /// DELETE /api/v1/users/{user_id}
-/// Delete user account (soft delete)
-#[axum::debug_handler]
+/// Delete user account: personal data is anonymized and the other contexts are
+/// told to erase or pseudonymize theirs. Needs the password (and 2FA code) again.
+#[utoipa::path(
+    delete,
+    path = "/api/v1/users/{user_id}",
+    params(("user_id" = Uuid, Path, description = "User ID")),
+    request_body = ReauthenticationRequest,
+    responses(
+        (status = 200, description = "Account deleted", body = ApiResponse<serde_json::Value>),
+        (status = 401, description = "Re-authentication failed", body = ApiResponse<serde_json::Value>),
+        (status = 403, description = "Not your account", body = ApiResponse<serde_json::Value>),
+        (status = 404, description = "User not found", body = ApiResponse<serde_json::Value>),
+        (status = 409, description = "Account already deleted", body = ApiResponse<serde_json::Value>)
+    ),
+    tag = "users"
+)]
 pub async fn delete_user(
     AuthenticatedUser { user_id, role, .. }: AuthenticatedUser,
     State(user_service): State<UserAppService>,
     Path(requested_user_id): Path<Uuid>,
-) -> Result<Json<ApiResponse<()>>, StatusCode> {
+    Json(request): Json<ReauthenticationRequest>,
+) -> Result<(StatusCode, Json<ApiResponse<serde_json::Value>>), StatusCode> {
     // Validar que el usuario solo puede eliminar su propia cuenta (o admin)
     if user_id != requested_user_id && role != "admin" {
-        return Ok(Json(ApiResponse {
+        return Ok((StatusCode::FORBIDDEN, Json(ApiResponse {
             success: false,
             data: None,
             message: Some("Solo puedes eliminar tu propia cuenta".to_string()),
             errors: None,
-        }));
+        })));
     }
 
-    // Buscar usuario
-    let user_id_vo = crate::bounded_contexts::user::domain::value_objects::UserId::from_uuid(requested_user_id);
-    let mut user_aggregate = user_service.repository.find_by_id(&user_id_vo).await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
-        .ok_or(StatusCode::NOT_FOUND)?;
-
-    // Soft delete: desactivar usuario en lugar de eliminar
-    user_aggregate.user.deactivate();
+    // Quien borra (el propio usuario o el admin) confirma su identidad
+    let now = Utc::now();
+    if let Err(e) = user_service
+        .reauthenticate(user_id, request.password.as_deref(), request.two_factor_code.as_deref(), now)
+        .await
+    {
+        return app_failure(e);
+    }
 
-    // Guardar cambios
-    user_service.repository.update(&user_aggregate).await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    if let Err(e) = user_service.delete_account(requested_user_id, now).await {
+        return app_failure(e);
+    }
 
-    Ok(Json(ApiResponse {
+    Ok((StatusCode::OK, Json(ApiResponse {
         success: true,
-        data: None,
+        data: Some(serde_json::json!({ "user_id": requested_user_id, "deleted_at": now })),
         message: Some("Cuenta eliminada exitosamente".to_string()),
         errors: None,
-    }))
+    })))
 }
 
-/// GET /api/v1/users/{user_id}/followers
-/// Get user followers
-#[axum::debug_handler]
-pub async fn get_user_followers(
-    State(user_service): State<UserAppService>,
-    Path(user_id): Path<Uuid>,
-    Query(query): Query<HashMap<String, String>>,
-) -> Result<Json<ApiResponse<UserListResponse>>, StatusCode> {
+/// Page and page size of the follower lists (`page` starts at 0)
+fn follow_list_paging(query: &HashMap<String, String>) -> (u32, u32) {
     let page = query.get("page")
         .and_then(|p| p.parse::<u32>().ok())
         .unwrap_or(0);
     let page_size = query.get("page_size")
         .and_then(|p| p.parse::<u32>().ok())
-        .unwrap_or(20);
-
-    let user_id_vo = crate::bounded_contexts::user::domain::value_objects::UserId::from_uuid(user_id);
-    let followers_summaries = user_service.repository.get_followers(&user_id_vo, page, page_size).await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        .unwrap_or(20)
+        .clamp(1, 100);
+    (page, page_size)
+}
 
-    let users: Vec<UserSummaryResponse> = followers_summaries.into_iter().map(|summary| {
+fn user_list_page(
+    summaries: Vec<crate::bounded_contexts::user::domain::aggregates::UserSummary>,
+    page: u32,
+    page_size: u32,
+    total_count: u64,
+) -> UserListResponse {
+    let users: Vec<UserSummaryResponse> = summaries.into_iter().map(|summary| {
         UserSummaryResponse {
             id: summary.id.value(),
             username: summary.username.value().to_string(),
@@ -975,7 +1062,6 @@ pub async fn get_user_followers(
         }
     }).collect();
 
-    let total_count = users.len() as u64; // TODO: Get actual count from repository
     let total_pages = (total_count as f64 / page_size as f64).ceil() as u32;
 
     let pagination = PaginationResponse {
@@ -987,74 +1073,81 @@ pub async fn get_user_followers(
         has_previous_page: page > 0,
     };
 
-    let response = UserListResponse {
+    UserListResponse {
         users,
         pagination,
-    };
+    }
+}
+
+/// GET /api/v1/users/{user_id}/followers
+/// Get user followers, most recent first
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{user_id}/followers",
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("page" = Option<u32>, Query, description = "Page, starting at 0"),
+        ("page_size" = Option<u32>, Query, description = "Page size (1-100, default 20)")
+    ),
+    responses(
+        (status = 200, description = "Followers", body = ApiResponse<UserListResponse>)
+    ),
+    tag = "users"
+)]
+#[axum::debug_handler]
+pub async fn get_user_followers(
+    State(user_service): State<UserAppService>,
+    Path(user_id): Path<Uuid>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Result<Json<ApiResponse<UserListResponse>>, StatusCode> {
+    let (page, page_size) = follow_list_paging(&query);
+
+    let user_id_vo = crate::bounded_contexts::user::domain::value_objects::UserId::from_uuid(user_id);
+    let followers_summaries = user_service.repository.get_followers(&user_id_vo, page, page_size).await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let total_count = user_service.repository.count_followers(&user_id_vo).await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     Ok(Json(ApiResponse {
         success: true,
-        data: Some(response),
+        data: Some(user_list_page(followers_summaries, page, page_size, total_count)),
         message: None,
         errors: None,
     }))
 }
 
 /// GET /api/v1/users/{user_id}/following
-/// Get users that the user is following
+/// Get users that the user is following, most recent first
+#[utoipa::path(
+    get,
+    path = "/api/v1/users/{user_id}/following",
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        ("page" = Option<u32>, Query, description = "Page, starting at 0"),
+        ("page_size" = Option<u32>, Query, description = "Page size (1-100, default 20)")
+    ),
+    responses(
+        (status = 200, description = "Users being followed", body = ApiResponse<UserListResponse>)
+    ),
+    tag = "users"
+)]
 #[axum::debug_handler]
 pub async fn get_user_following(
     State(user_service): State<UserAppService>,
     Path(user_id): Path<Uuid>,
     Query(query): Query<HashMap<String, String>>,
 ) -> Result<Json<ApiResponse<UserListResponse>>, StatusCode> {
-    let page = query.get("page")
-        .and_then(|p| p.parse::<u32>().ok())
-        .unwrap_or(0);
-    let page_size = query.get("page_size")
-        .and_then(|p| p.parse::<u32>().ok())
-        .unwrap_or(20);
+    let (page, page_size) = follow_list_paging(&query);
 
     let user_id_vo = crate::bounded_contexts::user::domain::value_objects::UserId::from_uuid(user_id);
     let following_summaries = user_service.repository.get_following(&user_id_vo, page, page_size).await
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    let users: Vec<UserSummaryResponse> = following_summaries.into_iter().map(|summary| {
-        UserSummaryResponse {
-            id: summary.id.value(),
-            username: summary.username.value().to_string(),
-            display_name: summary.display_name.clone(),
-            avatar_url: summary.avatar_url.clone(),
-            tier: format!("{}", summary.tier),
-            role: format!("{}", summary.role),
-            is_verified: summary.is_verified,
-            is_active: summary.is_active,
-            tier_points: summary.tier_points.max(0) as u32,
-            total_rewards: summary.total_rewards,
-            created_at: summary.created_at,
-        }
-    }).collect();
-
-    let total_count = users.len() as u64; // TODO: Get actual count from repository
-    let total_pages = (total_count as f64 / page_size as f64).ceil() as u32;
-
-    let pagination = PaginationResponse {
-        page,
-        page_size,
-        total_count,
-        total_pages,
-        has_next_page: page < total_pages.saturating_sub(1),
-        has_previous_page: page > 0,
-    };
-
-    let response = UserListResponse {
-        users,
-        pagination,
-    };
+    let total_count = user_service.repository.count_following(&user_id_vo).await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
     Ok(Json(ApiResponse {
         success: true,
-        data: Some(response),
+        data: Some(user_list_page(following_summaries, page, page_size, total_count)),
         message: None,
         errors: None,
     }))
@@ -1109,7 +1202,6 @@ pub fn create_user_routes() -> Router<UserAppService> {
         // Authentication & Registration
         .route("/register", post(register_user))
         .route("/login", post(login_user))
-        .route("/refresh", post(refresh_token))
         
         // User Profile Management
         .route("/:user_id", get(get_user_profile))
@@ -1118,6 +1210,7 @@ pub fn create_user_routes() -> Router<UserAppService> {
         
         // User Statistics & Analytics
         .route("/:user_id/stats", get(get_user_stats))
+        .route("/:user_id/preferences", get(get_listening_preferences))
         
         // User Search & Discovery
         .route("/search", get(search_users))
@@ -1187,4 +1280,23 @@ pub async fn unfollow_user(
         "message": "User unfollowed successfully",
         "user_id": user_id
     })))
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn unknown_users_still_run_the_password_check() {
+        let checked: RefCell<Vec<Option<String>>> = RefCell::new(Vec::new());
+        let verify = |_password: &str, stored_hash: Option<&str>| {
+            checked.borrow_mut().push(stored_hash.map(str::to_string));
+            false
+        };
+
+        assert!(authenticate(None, "secret-password", verify).is_none());
+        // Sin cuenta se verifica igualmente, contra el hash ficticio
+        assert_eq!(*checked.borrow(), vec![None]);
+    }
+}
@@ -9,6 +9,15 @@ use axum::{
 use std::sync::Arc;
 
 use super::controllers::user_controller::*;
+use super::controllers::auth_controller::{
+    refresh_session, logout, list_sessions, revoke_session, wallet_challenge, wallet_verify,
+};
+use super::controllers::two_factor_controller::{
+    setup_two_factor, confirm_two_factor, disable_two_factor, verify_two_factor,
+};
+use super::controllers::privacy_controller::{
+    request_data_export, get_data_export, download_data_export,
+};
 use crate::bounded_contexts::user::application::services::UserApplicationService;
 use crate::shared::infrastructure::database::postgres::PostgresUserRepository;
 use crate::shared::infrastructure::auth::middleware::jwt_auth_middleware;
@@ -23,7 +32,9 @@ pub fn configure_user_routes(
         .route("/register", post(register_user))
         .route("/login", post(login_user))
         // Search básico puede ser público (con limitaciones)
-        .route("/search", get(search_users));
+        .route("/search", get(search_users))
+        // Descarga de exportaciones: el enlace firmado es la credencial
+        .route("/exports/:export_id/download", get(download_data_export));
     
     // Rutas protegidas (requieren JWT)
     let protected_routes = Router::new()
@@ -34,6 +45,7 @@ pub fn configure_user_routes(
         
         // User Statistics & Analytics
         .route("/:user_id/stats", get(get_user_stats))
+        .route("/:user_id/preferences", get(get_listening_preferences))
         
         // Social Features
         .route("/:user_id/follow", post(follow_user))
@@ -44,6 +56,10 @@ pub fn configure_user_routes(
         .route("/:user_id/change-password", post(change_password))
         .route("/:user_id/link-wallet", post(link_wallet))
 
+        // Privacy (GDPR)
+        .route("/:user_id/export", post(request_data_export))
+        .route("/:user_id/exports/:export_id", get(get_data_export))
+
         // Biometrics
         .route("/biometrics/verify", post(verify_biometrics))
         
@@ -61,6 +77,34 @@ pub fn configure_user_routes(
         .with_state((*user_service).clone())
 }
 
+/// Configure refresh-token session routes (served under /api/v1/auth)
+pub fn configure_auth_routes(
+    user_service: Arc<UserApplicationService<PostgresUserRepository>>,
+) -> Router {
+    // El refresh token es la credencial: no hace falta access token
+    let public_routes = Router::new()
+        .route("/refresh", post(refresh_session))
+        .route("/logout", post(logout))
+        // Login con wallet: la firma del reto es la credencial
+        .route("/wallet/challenge", post(wallet_challenge))
+        .route("/wallet/verify", post(wallet_verify))
+        // Segundo paso del login: el token pendiente de 2FA es la credencial
+        .route("/2fa/verify", post(verify_two_factor));
+
+    let protected_routes = Router::new()
+        .route("/sessions", get(list_sessions))
+        .route("/sessions/:session_id/revoke", post(revoke_session))
+        .route("/2fa/setup", post(setup_two_factor))
+        .route("/2fa/confirm", post(confirm_two_factor))
+        .route("/2fa/disable", post(disable_two_factor))
+        .layer(middleware::from_fn(jwt_auth_middleware));
+
+    Router::new()
+        .merge(public_routes)
+        .merge(protected_routes)
+        .with_state((*user_service).clone())
+}
+
 /// Create User Context Routes with API version prefix
 pub fn create_user_routes(
     user_service: Arc<UserApplicationService<PostgresUserRepository>>,
@@ -76,10 +120,29 @@ pub fn create_user_routes(
 POST   /api/v1/users/register         - Register new user
 POST   /api/v1/users/login           - User login
 
+🔑 Sessions:
+POST   /api/v1/auth/refresh                       - Rotate refresh token, new access token
+POST   /api/v1/auth/logout                        - Revoke the refresh token's session
+GET    /api/v1/auth/sessions                      - Active sessions of the current user
+POST   /api/v1/auth/sessions/{session_id}/revoke  - Revoke another device's session
+POST   /api/v1/auth/wallet/challenge              - Sign-in message with a single-use nonce
+POST   /api/v1/auth/wallet/verify                 - Log in (or link) with the signed message
+
+🛡️ Two-Factor Authentication (TOTP):
+POST   /api/v1/auth/2fa/setup                     - New secret (otpauth:// URI) and recovery codes
+POST   /api/v1/auth/2fa/confirm                   - Enable 2FA with a first code from the app
+POST   /api/v1/auth/2fa/disable                   - Disable 2FA (requires a current code)
+POST   /api/v1/auth/2fa/verify                    - Exchange the 2FA pending token + code for the session
+
 👤 User Profile Management:
 GET    /api/v1/users/{user_id}       - Get user profile
 PUT    /api/v1/users/{user_id}       - Update user profile  
-DELETE /api/v1/users/{user_id}       - Delete user account
+DELETE /api/v1/users/{user_id}       - Delete user account (re-authentication required)
+
+🔒 Privacy (GDPR):
+POST   /api/v1/users/{user_id}/export                   - Start a personal data export (re-authentication required)
+GET    /api/v1/users/{user_id}/exports/{export_id}      - Export status and signed download link
+GET    /api/v1/users/exports/{export_id}/download       - Download the archive (signed link)
 
 📊 User Statistics:
 GET    /api/v1/users/{user_id}/stats - Get user statistics
@@ -89,8 +152,8 @@ GET    /api/v1/users/search?q=...    - Search users by criteria
 
 👥 Social Features:
 POST   /api/v1/users/{user_id}/follow   - Follow/unfollow user
-GET    /api/v1/users/{user_id}/followers - Get user followers
-GET    /api/v1/users/{user_id}/following - Get users being followed
+GET    /api/v1/users/{user_id}/followers - Get user followers (?page=0&page_size=20)
+GET    /api/v1/users/{user_id}/following - Get users being followed (?page=0&page_size=20)
 
 🔧 Account Management:
 POST   /api/v1/users/{user_id}/change-password - Change password
@@ -122,6 +185,14 @@ POST /api/v1/users/login
   "remember_me": true
 }
 
+Login with 2FA enabled (202, then verify):
+POST /api/v1/users/login  ->  { "two_factor_required": true, "two_factor_token": "...", "expires_in": 300 }
+POST /api/v1/auth/2fa/verify
+{
+  "two_factor_token": "...",
+  "code": "287082"
+}
+
 Update Profile:
 PUT /api/v1/users/{user_id}
 {
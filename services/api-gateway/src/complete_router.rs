@@ -104,6 +104,7 @@ async fn create_music_routes(app_state: AppState) -> Result<Router, Box<dyn std:
         .route("/songs", get(SongController::get_songs))
         .route("/songs", axum::routing::post(SongController::create_song))
         .route("/songs/:id", get(SongController::get_song))
+        .route("/songs/:id/similar", get(SongController::get_similar_songs))
         .route("/songs/:id", axum::routing::put(SongController::update_song))
         .route("/songs/:id", axum::routing::delete(SongController::delete_song))
         .route("/songs/discover", get(SongController::discover_songs))
@@ -111,18 +112,25 @@ async fn create_music_routes(app_state: AppState) -> Result<Router, Box<dyn std:
         .route("/songs/:id/like", axum::routing::post(SongController::like_song))
         .route("/songs/:id/unlike", axum::routing::post(SongController::unlike_song))
         .route("/songs/:id/share", axum::routing::post(SongController::share_song))
+        .route("/songs/:id/remix-license", axum::routing::post(SongController::purchase_remix_license))
+        .route("/songs/:id/stems", get(SongController::download_stems))
+        .route("/songs/:id/analytics", get(SongController::get_song_analytics))
         .route("/albums", get(AlbumController::get_albums))
         .route("/albums", axum::routing::post(AlbumController::create_album))
         .route("/albums/:id", get(AlbumController::get_album))
         .route("/playlists", get(PlaylistController::get_playlists))
         .route("/playlists", axum::routing::post(PlaylistController::create_playlist))
+        .route("/playlists/generate", axum::routing::post(PlaylistController::generate_playlist))
+        .route("/playlists/recommended", get(PlaylistController::get_recommended_playlists))
         .route("/playlists/:id", get(PlaylistController::get_playlist))
         .route("/playlists/:id/songs", axum::routing::post(PlaylistController::add_song_to_playlist))
         .route("/playlists/:id/songs/:song_id", axum::routing::delete(PlaylistController::remove_song_from_playlist))
+        .route("/playlists/:id/follow", axum::routing::post(PlaylistController::follow_playlist).delete(PlaylistController::unfollow_playlist))
         .route("/artists", get(ArtistController::get_artists))
         .route("/artists/:id", get(ArtistController::get_artist))
         .route("/artists/:id/songs", get(ArtistController::get_artist_songs))
         .route("/artists/:id/albums", get(ArtistController::get_artist_albums))
+        .route("/artists/:id/followers", get(ArtistController::get_artist_followers))
         .with_state(music_state);
     
     Ok(router)
@@ -203,14 +211,14 @@ async fn create_notification_routes(app_state: AppState) -> Result<Router, Box<d
     
     let router = Router::new()
         .route("/", axum::routing::post(crate::bounded_contexts::notifications::presentation::controllers::NotificationController::create_notification))
-        .route("/user/:user_id", get(crate::bounded_contexts::notifications::presentation::controllers::NotificationController::get_user_notifications))
+        .route("/", get(crate::bounded_contexts::notifications::presentation::controllers::NotificationController::list_notifications))
         .route("/:id", get(crate::bounded_contexts::notifications::presentation::controllers::NotificationController::get_notification))
         .route("/:id/read", axum::routing::put(crate::bounded_contexts::notifications::presentation::controllers::NotificationController::mark_as_read))
         .route("/:id/archive", axum::routing::put(crate::bounded_contexts::notifications::presentation::controllers::NotificationController::mark_as_archived))
         .route("/:id", axum::routing::delete(crate::bounded_contexts::notifications::presentation::controllers::NotificationController::delete_notification))
-        .route("/user/:user_id/read-all", axum::routing::put(crate::bounded_contexts::notifications::presentation::controllers::NotificationController::mark_all_as_read))
-        .route("/user/:user_id/preferences", get(crate::bounded_contexts::notifications::presentation::controllers::NotificationController::get_preferences))
-        .route("/user/:user_id/preferences", axum::routing::put(crate::bounded_contexts::notifications::presentation::controllers::NotificationController::update_preferences))
+        .route("/read-all", axum::routing::put(crate::bounded_contexts::notifications::presentation::controllers::NotificationController::mark_all_as_read))
+        .route("/preferences", get(crate::bounded_contexts::notifications::presentation::controllers::NotificationController::get_preferences))
+        .route("/preferences", axum::routing::put(crate::bounded_contexts::notifications::presentation::controllers::NotificationController::update_preferences))
         .route("/user/:user_id/summary", get(crate::bounded_contexts::notifications::presentation::controllers::NotificationController::get_notification_summary))
         .with_state(notification_state);
     
@@ -0,0 +1,276 @@
+// =============================================================================
+// SALUD AGREGADA DE LA PLATAFORMA (GET /health/aggregate)
+// =============================================================================
+//
+// Comprueba en paralelo la base de datos, Redis y los workers (zk-service,
+// ethereum-service y el worker de Solana). Cada comprobación tiene su tiempo
+// máximo y el conjunto un plazo total: lo que no haya terminado al vencer el
+// plazo cuenta como caído. El resultado se cachea unos segundos para que los
+// balanceadores y monitores no multipliquen las llamadas a los workers.
+
+use axum::{extract::State, http::StatusCode, response::Json, routing::get, Router};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use crate::gateways::dependencies::{platform_checks, run_check, DependencyCheck, ServiceStatus, CRITICAL_SERVICES};
+use crate::shared::infrastructure::app_state::AppState;
+
+pub const AGGREGATE_HEALTH_PATH: &str = "/health/aggregate";
+
+/// Tiempo máximo de cada comprobación
+pub const AGGREGATE_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Plazo total para responder, pase lo que pase con las comprobaciones
+pub const AGGREGATE_HEALTH_DEADLINE: Duration = Duration::from_secs(3);
+
+/// Tiempo durante el que se reutiliza el último informe
+pub const AGGREGATE_HEALTH_CACHE_TTL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlatformStatus {
+    Healthy,
+    /// Falla alguna dependencia no crítica
+    Degraded,
+    /// Falla alguna de `CRITICAL_SERVICES`
+    Unhealthy,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AggregateHealthReport {
+    pub status: PlatformStatus,
+    pub checked_at: DateTime<Utc>,
+    pub services: BTreeMap<String, ServiceStatus>,
+}
+
+impl AggregateHealthReport {
+    fn from_services(services: BTreeMap<String, ServiceStatus>) -> Self {
+        let status = if services.values().any(|status| status.critical && !status.healthy) {
+            PlatformStatus::Unhealthy
+        } else if services.values().any(|status| !status.healthy) {
+            PlatformStatus::Degraded
+        } else {
+            PlatformStatus::Healthy
+        };
+        Self { status, checked_at: Utc::now(), services }
+    }
+}
+
+pub struct AggregateHealth {
+    checks: Vec<Arc<dyn DependencyCheck>>,
+    check_timeout: Duration,
+    deadline: Duration,
+    cache_ttl: Duration,
+    /// Último informe; el lock también hace que peticiones simultáneas
+    /// esperen a una sola ronda de comprobaciones
+    cached: Mutex<Option<(Instant, AggregateHealthReport)>>,
+}
+
+impl AggregateHealth {
+    pub fn new(checks: Vec<Arc<dyn DependencyCheck>>) -> Self {
+        Self {
+            checks,
+            check_timeout: AGGREGATE_CHECK_TIMEOUT,
+            deadline: AGGREGATE_HEALTH_DEADLINE,
+            cache_ttl: AGGREGATE_HEALTH_CACHE_TTL,
+            cached: Mutex::new(None),
+        }
+    }
+
+    pub fn for_app_state(app_state: &AppState) -> Self {
+        Self::new(platform_checks(app_state))
+    }
+
+    pub fn with_timeouts(mut self, check_timeout: Duration, deadline: Duration) -> Self {
+        self.check_timeout = check_timeout;
+        self.deadline = deadline;
+        self
+    }
+
+    pub fn with_cache_ttl(mut self, cache_ttl: Duration) -> Self {
+        self.cache_ttl = cache_ttl;
+        self
+    }
+
+    /// Informe cacheado si es reciente; si no, una ronda nueva de comprobaciones
+    pub async fn report(&self) -> AggregateHealthReport {
+        let mut cached = self.cached.lock().await;
+        if let Some((checked, report)) = cached.as_ref() {
+            if checked.elapsed() < self.cache_ttl {
+                return report.clone();
+            }
+        }
+
+        let report = self.check_all().await;
+        *cached = Some((Instant::now(), report.clone()));
+        report
+    }
+
+    async fn check_all(&self) -> AggregateHealthReport {
+        let deadline = tokio::time::Instant::now() + self.deadline;
+        let handles: Vec<_> = self
+            .checks
+            .iter()
+            .map(|check| {
+                let name = check.name().to_string();
+                (name, tokio::spawn(run_check(check.clone(), self.check_timeout)))
+            })
+            .collect();
+
+        let mut services = BTreeMap::new();
+        for (name, mut handle) in handles {
+            let status = match tokio::time::timeout_at(deadline, &mut handle).await {
+                Ok(Ok((_, status))) => status,
+                Ok(Err(e)) => self.failed(&name, format!("Health check panicked: {}", e), 0),
+                Err(_) => {
+                    handle.abort();
+                    let message = format!("Deadline of {}ms exceeded", self.deadline.as_millis());
+                    self.failed(&name, message, self.deadline.as_millis() as u64)
+                }
+            };
+            services.insert(name, status);
+        }
+
+        AggregateHealthReport::from_services(services)
+    }
+
+    fn failed(&self, name: &str, error: String, latency_ms: u64) -> ServiceStatus {
+        ServiceStatus {
+            healthy: false,
+            critical: CRITICAL_SERVICES.contains(&name),
+            latency_ms,
+            error: Some(error),
+        }
+    }
+}
+
+/// `GET /health/aggregate`: 503 solo si cae una dependencia crítica
+pub fn aggregate_health_router(health: Arc<AggregateHealth>) -> Router {
+    Router::new()
+        .route(AGGREGATE_HEALTH_PATH, get(aggregate_health_handler))
+        .with_state(health)
+}
+
+async fn aggregate_health_handler(
+    State(health): State<Arc<AggregateHealth>>,
+) -> (StatusCode, Json<AggregateHealthReport>) {
+    let report = health.report().await;
+    let status = match report.status {
+        PlatformStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+        PlatformStatus::Healthy | PlatformStatus::Degraded => StatusCode::OK,
+    };
+    (status, Json(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use axum::body::Body;
+    use axum::http::Request;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::ServiceExt;
+
+    struct StubCheck {
+        name: &'static str,
+        result: Result<(), String>,
+        delay: Duration,
+        calls: AtomicUsize,
+    }
+
+    fn stub_check(name: &'static str, result: Result<(), &str>, delay: Duration) -> Arc<StubCheck> {
+        Arc::new(StubCheck { name, result: result.map_err(str::to_string), delay, calls: AtomicUsize::new(0) })
+    }
+
+    fn stub(name: &'static str, result: Result<(), &str>) -> Arc<dyn DependencyCheck> {
+        stub_check(name, result, Duration::ZERO)
+    }
+
+    #[async_trait]
+    impl DependencyCheck for StubCheck {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn check(&self) -> Result<(), String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            self.result.clone()
+        }
+    }
+
+    fn platform(down: &'static str) -> Vec<Arc<dyn DependencyCheck>> {
+        ["database", "redis", "zk_service", "ethereum_service", "solana_worker"]
+            .into_iter()
+            .map(|name| stub(name, if name == down { Err("connection refused") } else { Ok(()) }))
+            .collect()
+    }
+
+    async fn get_aggregate(health: AggregateHealth) -> (StatusCode, serde_json::Value) {
+        let response = aggregate_health_router(Arc::new(health))
+            .oneshot(Request::get(AGGREGATE_HEALTH_PATH).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn a_worker_down_degrades_the_platform() {
+        let (status, body) = get_aggregate(AggregateHealth::new(platform("ethereum_service"))).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "degraded");
+        assert_eq!(body["services"]["ethereum_service"]["healthy"], false);
+        assert_eq!(body["services"]["ethereum_service"]["error"], "connection refused");
+        assert_eq!(body["services"]["database"]["healthy"], true);
+        assert!(body["services"]["solana_worker"]["latency_ms"].is_u64());
+    }
+
+    #[tokio::test]
+    async fn a_critical_dependency_down_makes_it_unhealthy() {
+        let (status, body) = get_aggregate(AggregateHealth::new(platform("redis"))).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "unhealthy");
+
+        let (status, body) = get_aggregate(AggregateHealth::new(platform("none"))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "healthy");
+    }
+
+    #[tokio::test]
+    async fn hung_checks_do_not_outlast_the_deadline() {
+        let hung: Arc<dyn DependencyCheck> = stub_check("zk_service", Ok(()), Duration::from_secs(60));
+        let health = AggregateHealth::new(vec![stub("database", Ok(())), stub("redis", Ok(())), hung])
+            .with_timeouts(Duration::from_secs(30), Duration::from_millis(50));
+
+        let started = Instant::now();
+        let report = health.report().await;
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(report.status, PlatformStatus::Degraded);
+        assert_eq!(report.services["zk_service"].error.as_deref(), Some("Deadline of 50ms exceeded"));
+        assert!(report.services["database"].healthy);
+    }
+
+    #[tokio::test]
+    async fn reports_are_cached_for_the_ttl() {
+        let database = stub_check("database", Ok(()), Duration::ZERO);
+        let health = AggregateHealth::new(vec![database.clone() as Arc<dyn DependencyCheck>, stub("redis", Ok(()))]);
+
+        let first = health.report().await;
+        let second = health.report().await;
+        assert_eq!(database.calls.load(Ordering::SeqCst), 1);
+        assert_eq!(first.checked_at, second.checked_at);
+
+        let health = AggregateHealth::new(vec![database.clone() as Arc<dyn DependencyCheck>]).with_cache_ttl(Duration::ZERO);
+        health.report().await;
+        health.report().await;
+        assert_eq!(database.calls.load(Ordering::SeqCst), 3);
+    }
+}
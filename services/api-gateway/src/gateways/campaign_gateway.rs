@@ -7,8 +7,16 @@ use axum::response::Json as ResponseJson;
 use serde_json::json;
 use std::sync::Arc;
 use crate::shared::infrastructure::app_state::AppState;
+use crate::bounded_contexts::campaign::application::analytics_snapshots::{
+    CampaignAnalyticsSnapshotJob, CampaignAnalyticsSnapshotService,
+};
+use crate::bounded_contexts::campaign::application::budget::CampaignBudgetService;
+use crate::bounded_contexts::campaign::application::nft_minting::CampaignNftService;
+use crate::bounded_contexts::campaign::application::scheduler::{CampaignSchedulerJob, CampaignSchedulerService};
+use crate::bounded_contexts::campaign::infrastructure::analytics_repository::PostgresCampaignAnalyticsRepository;
+use crate::bounded_contexts::campaign::infrastructure::nft_mint_queue::{CampaignNftMintResultWorker, RedisNftMintQueue};
 use crate::bounded_contexts::campaign::infrastructure::postgres_repository::{
-    PostgresCampaignRepository, PostgresCampaignParticipationRepository
+    PostgresAudienceRepository, PostgresCampaignRepository, PostgresCampaignParticipationRepository
 };
 
 use crate::bounded_contexts::campaign::presentation::controllers::campaign_controller::create_campaign_routes;
@@ -21,12 +29,61 @@ pub async fn create_campaign_gateway(app_state: AppState) -> Result<Router, Box<
     // Nota: PostgresCampaignRepository::new requiere PgPool
     let campaign_repository = Arc::new(PostgresCampaignRepository::new(pool.clone()));
     let participation_repository = Arc::new(PostgresCampaignParticipationRepository::new(pool.clone()));
+    let audience_repository = Arc::new(PostgresAudienceRepository::new(pool.clone()));
+    let analytics_repository = Arc::new(PostgresCampaignAnalyticsRepository::new(pool.clone()));
+    let budget_service = Arc::new(CampaignBudgetService::new(
+        participation_repository.clone(),
+        app_state.event_bus.clone(),
+    ));
+
+    // Minteo de NFTs vía solana-integration: petición por cola, resultado por cola
+    let nft_service = Arc::new(
+        CampaignNftService::new(
+            participation_repository.clone(),
+            Arc::new(RedisNftMintQueue::new(app_state.message_queue.clone())),
+        )
+        .with_analytics(analytics_repository.clone()),
+    );
+    let mint_result_worker = CampaignNftMintResultWorker::new(nft_service.clone(), app_state.message_queue.clone());
+    app_state.background_tasks.spawn("campaign_nft_mint_results", async move {
+        if let Err(e) = mint_result_worker.start().await {
+            tracing::error!("Campaign NFT mint result worker stopped: {}", e);
+        }
+    });
+
+    // Activa (con auto_start) y completa campañas según sus fechas
+    let scheduler_service = Arc::new(
+        CampaignSchedulerService::new(
+            campaign_repository.clone(),
+            analytics_repository.clone(),
+            app_state.event_bus.clone(),
+        )
+        .with_nft_distribution(nft_service.clone()),
+    );
+    let scheduler_job = CampaignSchedulerJob::new(scheduler_service.clone(), std::time::Duration::from_secs(60));
+    app_state.background_tasks.register("campaign_scheduler", scheduler_job.start());
+
+    // Foto diaria de analytics: histórico y respuesta rápida de campañas completadas
+    let snapshot_service = Arc::new(CampaignAnalyticsSnapshotService::new(
+        analytics_repository.clone(),
+        analytics_repository.clone(),
+        campaign_repository.clone(),
+    ));
+    app_state.background_tasks.register(
+        "campaign_analytics_snapshots",
+        CampaignAnalyticsSnapshotJob::new(snapshot_service.clone(), std::time::Duration::from_secs(3600)).start(),
+    );
     
     // Crear rutas usando el controlador existente
     // El controlador maneja su propio estado (Arc<CampaignController>)
     let router = create_campaign_routes(
         campaign_repository,
-        participation_repository
+        budget_service,
+        scheduler_service,
+        audience_repository,
+        analytics_repository,
+        snapshot_service,
+        nft_service,
     );
     
     // Agregar ruta de health check y info que podrían no estar en el controlador
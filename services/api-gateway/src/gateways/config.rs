@@ -0,0 +1,431 @@
+// =============================================================================
+// GATEWAY CONFIGURATION LOADING
+// =============================================================================
+//
+// Cada gateway parte de su `GatewayConfig` por defecto. Encima se aplica, si
+// existe, el TOML de `GATEWAY_CONFIG` y después las variables de entorno
+// `VIBESTREAM__<SECCIÓN>__<CAMPO>`:
+//
+// ```toml
+// [user_gateway]
+// port = 4001
+// host = "0.0.0.0"
+// cors_enabled = false
+//
+// [docs_gateway]
+// health_check_enabled = false
+// ```
+//
+// `VIBESTREAM__USER_GATEWAY__PORT=5001` gana al fichero. Una sección o un campo
+// desconocidos, puertos repetidos o el puerto 0 en producción hacen fallar el
+// arranque en lugar de levantar los gateways con una configuración a medias.
+
+use axum::{
+    extract::Request,
+    http::StatusCode,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::net::SocketAddr;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use tower_http::cors::CorsLayer;
+
+use super::GatewayConfig;
+use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::correlation::TracingLayer;
+use crate::shared::infrastructure::metrics::{metrics_router, Metrics, MetricsLayer};
+use crate::shared::infrastructure::shutdown::serve_with_graceful_shutdown;
+
+/// Ruta del TOML con la configuración de los gateways
+pub const GATEWAY_CONFIG_ENV: &str = "GATEWAY_CONFIG";
+/// Prefijo de las variables que sobrescriben el fichero
+pub const GATEWAY_ENV_PREFIX: &str = "VIBESTREAM__";
+
+/// Campos que el fichero o el entorno pueden cambiar; el resto se queda con
+/// el valor por defecto del gateway
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GatewayOverrides {
+    port: Option<u16>,
+    host: Option<String>,
+    cors_enabled: Option<bool>,
+    rate_limiting_enabled: Option<bool>,
+    health_check_enabled: Option<bool>,
+}
+
+impl GatewayOverrides {
+    fn set(&mut self, field: &str, value: &str) -> Result<(), String> {
+        let flag = |value: &str| {
+            value
+                .parse::<bool>()
+                .map_err(|_| format!("expected true or false, got '{}'", value))
+        };
+        match field {
+            "port" => self.port = Some(value.parse().map_err(|_| format!("invalid port '{}'", value))?),
+            "host" => self.host = Some(value.to_string()),
+            "cors_enabled" => self.cors_enabled = Some(flag(value)?),
+            "rate_limiting_enabled" => self.rate_limiting_enabled = Some(flag(value)?),
+            "health_check_enabled" => self.health_check_enabled = Some(flag(value)?),
+            other => return Err(format!("unknown field '{}'", other)),
+        }
+        Ok(())
+    }
+
+    fn apply_to(self, config: &mut GatewayConfig) {
+        if let Some(port) = self.port {
+            config.port = port;
+        }
+        if let Some(host) = self.host {
+            config.host = host;
+        }
+        if let Some(enabled) = self.cors_enabled {
+            config.cors_enabled = enabled;
+        }
+        if let Some(enabled) = self.rate_limiting_enabled {
+            config.rate_limiting_enabled = enabled;
+        }
+        if let Some(enabled) = self.health_check_enabled {
+            config.health_check_enabled = enabled;
+        }
+    }
+}
+
+/// La configuración de todos los gateways que arranca el binario multipuerto
+#[derive(Debug, Clone)]
+pub struct GatewaysConfig {
+    pub gateways: Vec<GatewayConfig>,
+}
+
+impl Default for GatewaysConfig {
+    fn default() -> Self {
+        Self {
+            gateways: vec![
+                GatewayConfig::docs_gateway(),
+                GatewayConfig::user_gateway(),
+                GatewayConfig::music_gateway(),
+                GatewayConfig::payment_gateway(),
+                GatewayConfig::campaign_gateway(),
+                GatewayConfig::listen_reward_gateway(),
+                GatewayConfig::fan_ventures_gateway(),
+                GatewayConfig::notification_gateway(),
+                GatewayConfig::fan_loyalty_gateway(),
+            ],
+        }
+    }
+}
+
+impl GatewaysConfig {
+    /// Aplicar sobre los valores por defecto el TOML (si lo hay) y después
+    /// las variables `VIBESTREAM__*` de `env`. No valida: ver `validate`.
+    pub fn load<I>(toml: Option<&str>, env: I) -> Result<Self, AppError>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut overrides: BTreeMap<String, GatewayOverrides> = match toml {
+            Some(contents) => ::config::Config::builder()
+                .add_source(::config::File::from_str(contents, ::config::FileFormat::Toml))
+                .build()
+                .and_then(|config| config.try_deserialize())
+                .map_err(|e| AppError::ConfigurationError(format!("Invalid gateway config: {}", e)))?,
+            None => BTreeMap::new(),
+        };
+
+        for (key, value) in env {
+            let Some(path) = key.strip_prefix(GATEWAY_ENV_PREFIX) else {
+                continue;
+            };
+            let path = path.to_lowercase();
+            let (section, field) = path.split_once("__").ok_or_else(|| {
+                AppError::ConfigurationError(format!("{} must look like {}<GATEWAY>__<FIELD>", key, GATEWAY_ENV_PREFIX))
+            })?;
+            overrides
+                .entry(section.to_string())
+                .or_default()
+                .set(field, &value)
+                .map_err(|e| AppError::ConfigurationError(format!("Invalid {}: {}", key, e)))?;
+        }
+
+        let mut config = Self::default();
+        for (section, section_overrides) in overrides {
+            let gateway = config
+                .gateways
+                .iter_mut()
+                .find(|gateway| gateway.section() == section)
+                .ok_or_else(|| AppError::ConfigurationError(format!("Unknown gateway section '{}'", section)))?;
+            section_overrides.apply_to(gateway);
+        }
+        Ok(config)
+    }
+
+    /// Puertos repetidos y hosts vacíos nunca son válidos; el puerto 0 (uno
+    /// libre cualquiera) solo fuera de producción
+    pub fn validate(&self, production: bool) -> Result<(), AppError> {
+        let mut ports: BTreeMap<u16, &str> = BTreeMap::new();
+        for gateway in &self.gateways {
+            if gateway.host.trim().is_empty() {
+                return Err(AppError::ConfigurationError(format!("Gateway '{}' has an empty host", gateway.name)));
+            }
+            if gateway.port == 0 {
+                if production {
+                    return Err(AppError::ConfigurationError(format!(
+                        "Gateway '{}' has port 0, which is not allowed in production",
+                        gateway.name
+                    )));
+                }
+                continue;
+            }
+            if let Some(other) = ports.insert(gateway.port, &gateway.name) {
+                return Err(AppError::ConfigurationError(format!(
+                    "Gateways '{}' and '{}' both use port {}",
+                    other, gateway.name, gateway.port
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Fichero de `GATEWAY_CONFIG` más el entorno del proceso, ya validado;
+    /// producción cuando `APP_ENV=production`
+    pub fn from_env() -> Result<Self, AppError> {
+        let contents = match std::env::var(GATEWAY_CONFIG_ENV) {
+            Ok(path) => Some(std::fs::read_to_string(&path).map_err(|e| {
+                AppError::ConfigurationError(format!("Cannot read gateway config {}: {}", path, e))
+            })?),
+            Err(_) => None,
+        };
+        let config = Self::load(contents.as_deref(), std::env::vars())?;
+        let production = std::env::var("APP_ENV").map(|env| env == "production").unwrap_or(false);
+        config.validate(production)?;
+        Ok(config)
+    }
+
+    /// `from_env` leído una sola vez, para los gateways que se construyen
+    /// fuera del arranque. Si no es válida, los valores por defecto.
+    pub fn shared() -> &'static Self {
+        static LOADED: OnceLock<GatewaysConfig> = OnceLock::new();
+        LOADED.get_or_init(|| {
+            Self::from_env().unwrap_or_else(|e| {
+                tracing::error!("Using default gateway config: {}", e);
+                Self::default()
+            })
+        })
+    }
+
+    pub fn get(&self, name: &str) -> Option<&GatewayConfig> {
+        self.gateways.iter().find(|gateway| gateway.name == name)
+    }
+}
+
+impl GatewayConfig {
+    /// Sección del TOML y segmento de las variables de entorno
+    pub fn section(&self) -> String {
+        format!("{}_gateway", self.name)
+    }
+
+    /// Métricas de sus peticiones en `/metrics`, span de traza por petición,
+    /// CORS permisivo si `cors_enabled` y, sin `health_check_enabled`, las
+    /// rutas `/health` y `/health/*` del gateway responden 404
+    pub fn apply(&self, router: Router) -> Router {
+        // Los scrapes de `/metrics` no cuentan como peticiones del gateway
+        let router = router
+            .layer(MetricsLayer::new(&self.name))
+            .merge(metrics_router(Metrics::global(), Some(&self.name)));
+        let router = if self.health_check_enabled {
+            router
+        } else {
+            router.layer(middleware::from_fn(hide_health_check))
+        };
+        let router = if self.cors_enabled {
+            router.layer(CorsLayer::permissive())
+        } else {
+            router
+        };
+        router.layer(TracingLayer::new())
+    }
+
+    pub async fn bind(&self) -> Result<TcpListener, AppError> {
+        TcpListener::bind((self.host.as_str(), self.port)).await.map_err(|e| {
+            AppError::ConfigurationError(format!(
+                "Gateway '{}' cannot listen on {}:{}: {}",
+                self.name, self.host, self.port, e
+            ))
+        })
+    }
+}
+
+async fn hide_health_check(request: Request, next: Next) -> Response {
+    let path = request.uri().path();
+    if path == "/health" || path.starts_with("/health/") {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+    next.run(request).await
+}
+
+// =============================================================================
+// STARTUP
+// =============================================================================
+
+/// Gateway ya escuchando en su puerto, listo para servir
+pub struct BoundGateway {
+    pub name: String,
+    pub local_addr: SocketAddr,
+    listener: TcpListener,
+    router: Router,
+}
+
+/// Abrir el puerto de cada gateway con su configuración. Falla sin servir
+/// nada si algún router no tiene configuración o algún puerto está ocupado.
+pub async fn bind_gateways(
+    config: &GatewaysConfig,
+    routers: Vec<(String, Router)>,
+) -> Result<Vec<BoundGateway>, AppError> {
+    let mut bound = Vec::with_capacity(routers.len());
+    for (name, router) in routers {
+        let gateway = config
+            .get(&name)
+            .ok_or_else(|| AppError::ConfigurationError(format!("No configuration for gateway '{}'", name)))?;
+        let listener = gateway.bind().await?;
+        let local_addr = listener
+            .local_addr()
+            .map_err(|e| AppError::InternalError(format!("Gateway '{}' has no local address: {}", name, e)))?;
+        bound.push(BoundGateway { name, local_addr, listener, router: gateway.apply(router) });
+    }
+    Ok(bound)
+}
+
+/// Servir todos los gateways hasta que se cancele `token`. Si uno cae, se
+/// cancela el resto y se devuelve su error.
+pub async fn serve_gateways(
+    gateways: Vec<BoundGateway>,
+    token: CancellationToken,
+    drain_timeout: Duration,
+) -> std::io::Result<()> {
+    let mut servers = JoinSet::new();
+    for gateway in gateways {
+        let token = token.clone();
+        servers.spawn(async move {
+            let result = serve_with_graceful_shutdown(gateway.listener, gateway.router, token, drain_timeout).await;
+            (gateway.name, result)
+        });
+    }
+
+    let mut first_error = None;
+    while let Some(joined) = servers.join_next().await {
+        let (name, result) = joined.map_err(std::io::Error::other)?;
+        if let Err(e) = result {
+            tracing::error!("Gateway {} stopped: {}", name, e);
+            token.cancel();
+            first_error.get_or_insert(e);
+        }
+    }
+    first_error.map_or(Ok(()), Err)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn error_message(result: Result<impl std::fmt::Debug, AppError>) -> String {
+        match result.unwrap_err() {
+            AppError::ConfigurationError(message) => message,
+            other => panic!("expected a configuration error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn defaults_match_the_gateway_constructors() {
+        let config = GatewaysConfig::load(None, Vec::new()).unwrap();
+        let ports: Vec<(&str, u16)> = config.gateways.iter().map(|g| (g.name.as_str(), g.port)).collect();
+        assert_eq!(ports[0], ("docs", 3000));
+        assert_eq!(ports[1], ("user", 3001));
+        assert_eq!(ports[8], ("fan_loyalty", 3008));
+        assert!(config.validate(true).is_ok());
+    }
+
+    #[test]
+    fn toml_overrides_only_the_fields_it_sets() {
+        let toml = r#"
+            [user_gateway]
+            port = 4001
+            host = "0.0.0.0"
+            cors_enabled = false
+
+            [music_gateway]
+            health_check_enabled = false
+            rate_limiting_enabled = true
+        "#;
+        let config = GatewaysConfig::load(Some(toml), Vec::new()).unwrap();
+
+        let user = config.get("user").unwrap();
+        assert_eq!((user.host.as_str(), user.port), ("0.0.0.0", 4001));
+        assert!(!user.cors_enabled);
+        assert!(user.health_check_enabled);
+        let music = config.get("music").unwrap();
+        assert_eq!(music.port, 3002);
+        assert!(!music.health_check_enabled);
+        assert!(music.rate_limiting_enabled);
+    }
+
+    #[test]
+    fn environment_wins_over_the_file() {
+        let toml = "[user_gateway]\nport = 4001\n";
+        let config = GatewaysConfig::load(
+            Some(toml),
+            env(&[
+                ("VIBESTREAM__USER_GATEWAY__PORT", "5001"),
+                ("VIBESTREAM__PAYMENT_GATEWAY__CORS_ENABLED", "false"),
+                ("UNRELATED", "1"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(config.get("user").unwrap().port, 5001);
+        assert!(!config.get("payment").unwrap().cors_enabled);
+    }
+
+    #[test]
+    fn unknown_sections_fields_and_bad_values_are_rejected() {
+        assert!(error_message(GatewaysConfig::load(Some("[users_gateway]\nport = 1\n"), Vec::new()))
+            .contains("users_gateway"));
+        assert!(GatewaysConfig::load(Some("[user_gateway]\nprot = 1\n"), Vec::new()).is_err());
+
+        let message = error_message(GatewaysConfig::load(None, env(&[("VIBESTREAM__USER_GATEWAY__PORT", "abc")])));
+        assert!(message.contains("VIBESTREAM__USER_GATEWAY__PORT"), "{}", message);
+        assert!(GatewaysConfig::load(None, env(&[("VIBESTREAM__USER_GATEWAY__CORS", "true")])).is_err());
+        assert!(GatewaysConfig::load(None, env(&[("VIBESTREAM__USER_GATEWAY__CORS_ENABLED", "yes")])).is_err());
+        assert!(GatewaysConfig::load(None, env(&[("VIBESTREAM__PORT", "1")])).is_err());
+    }
+
+    #[test]
+    fn duplicate_ports_fail_validation() {
+        let config = GatewaysConfig::load(Some("[music_gateway]\nport = 3001\n"), Vec::new()).unwrap();
+        let message = error_message(config.validate(false));
+        assert!(message.contains("'user' and 'music'"), "{}", message);
+        assert!(message.contains("3001"), "{}", message);
+    }
+
+    #[test]
+    fn port_zero_is_only_allowed_outside_production() {
+        let toml = "[user_gateway]\nport = 0\n[music_gateway]\nport = 0\n";
+        let config = GatewaysConfig::load(Some(toml), Vec::new()).unwrap();
+        assert!(config.validate(false).is_ok());
+        assert!(error_message(config.validate(true)).contains("port 0"));
+    }
+
+    #[test]
+    fn empty_host_fails_validation() {
+        let config = GatewaysConfig::load(None, env(&[("VIBESTREAM__USER_GATEWAY__HOST", " ")])).unwrap();
+        assert!(error_message(config.validate(false)).contains("empty host"));
+    }
+}
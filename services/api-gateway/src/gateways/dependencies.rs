@@ -0,0 +1,398 @@
+// =============================================================================
+// VALIDACIÓN DE DEPENDENCIAS ANTES DE CREAR LOS GATEWAYS
+// =============================================================================
+//
+// Cada dependencia externa se comprueba con un `DependencyCheck`. Si falla una
+// de `CRITICAL_SERVICES` no se crean los gateways; las demás solo degradan el
+// informe.
+
+use async_trait::async_trait;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use vibestream_types::QueueNames;
+
+use crate::shared::infrastructure::app_state::AppState;
+
+/// Dependencias sin las que no se sirve tráfico
+pub const CRITICAL_SERVICES: &[&str] = &["database", "redis"];
+
+/// Tiempo máximo por defecto de cada comprobación
+pub const DEPENDENCY_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ServiceStatus {
+    pub healthy: bool,
+    pub critical: bool,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyReport {
+    pub all_healthy: bool,
+    pub services: HashMap<String, ServiceStatus>,
+}
+
+impl DependencyReport {
+    /// Servicios no críticos que fallaron
+    pub fn degraded_services(&self) -> Vec<&str> {
+        let mut degraded: Vec<&str> = self
+            .services
+            .iter()
+            .filter(|(_, status)| !status.healthy)
+            .map(|(name, _)| name.as_str())
+            .collect();
+        degraded.sort_unstable();
+        degraded
+    }
+}
+
+/// Comprobación de una dependencia externa
+#[async_trait]
+pub trait DependencyCheck: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn check(&self) -> Result<(), String>;
+}
+
+struct DatabaseCheck(AppState);
+
+#[async_trait]
+impl DependencyCheck for DatabaseCheck {
+    fn name(&self) -> &str {
+        "database"
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.0.database_pool.health_check().await.map_err(|e| e.to_string())
+    }
+}
+
+struct RedisCheck(AppState);
+
+#[async_trait]
+impl DependencyCheck for RedisCheck {
+    fn name(&self) -> &str {
+        "redis"
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.0.message_queue.ping().await.map_err(|e| e.to_string())
+    }
+}
+
+struct ZkServiceCheck(AppState);
+
+#[async_trait]
+impl DependencyCheck for ZkServiceCheck {
+    fn name(&self) -> &str {
+        "zk_service"
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        let health = self.0.zk_client.health_check().await.map_err(|e| e.to_string())?;
+        if health.status != "healthy" {
+            return Err(format!("ZK service reports status '{}'", health.status));
+        }
+        Ok(())
+    }
+}
+
+struct EthereumRpcCheck(AppState);
+
+#[async_trait]
+impl DependencyCheck for EthereumRpcCheck {
+    fn name(&self) -> &str {
+        "ethereum_rpc"
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        self.0.blockchain_client.get_block_number().await.map(|_| ()).map_err(|e| e.to_string())
+    }
+}
+
+/// `getHealth` del JSON-RPC de Solana: responde "ok" si el nodo está al día
+struct SolanaRpcCheck {
+    rpc_url: String,
+    client: reqwest::Client,
+}
+
+impl SolanaRpcCheck {
+    fn from_env() -> Self {
+        Self {
+            rpc_url: std::env::var("SOLANA_RPC_URL").unwrap_or_else(|_| "https://api.devnet.solana.com".to_string()),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl DependencyCheck for SolanaRpcCheck {
+    fn name(&self) -> &str {
+        "solana_rpc"
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        let response: serde_json::Value = self
+            .client
+            .post(&self.rpc_url)
+            .json(&serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "getHealth"}))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach Solana RPC: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid Solana RPC response: {}", e))?;
+        match response.get("result").and_then(|result| result.as_str()) {
+            Some("ok") => Ok(()),
+            _ => Err(format!("Solana RPC unhealthy: {}", response.get("error").unwrap_or(&response))),
+        }
+    }
+}
+
+/// `GET {base_url}/health` de otro servicio; si el cuerpo trae `status`
+/// tiene que ser "healthy"
+struct HttpHealthCheck {
+    name: &'static str,
+    url: String,
+    client: reqwest::Client,
+}
+
+impl HttpHealthCheck {
+    fn new(name: &'static str, base_url: &str) -> Self {
+        Self {
+            name,
+            url: format!("{}/health", base_url.trim_end_matches('/')),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl DependencyCheck for HttpHealthCheck {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        let response = self
+            .client
+            .get(&self.url)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach {}: {}", self.url, e))?;
+        if !response.status().is_success() {
+            return Err(format!("{} responded {}", self.url, response.status()));
+        }
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        match body.get("status").and_then(|status| status.as_str()) {
+            Some(status) if status != "healthy" => Err(format!("{} reports status '{}'", self.name, status)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Mensajes pendientes a partir de los que un worker se considera atascado
+pub const MAX_QUEUE_BACKLOG: usize = 1000;
+
+/// El worker de Solana no expone HTTP: se comprueba que su cola responde y
+/// que no acumula más de `max_backlog` mensajes sin consumir
+struct QueueBacklogCheck {
+    name: &'static str,
+    queue: &'static str,
+    max_backlog: usize,
+    app_state: AppState,
+}
+
+#[async_trait]
+impl DependencyCheck for QueueBacklogCheck {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        let backlog = self.app_state.message_queue.queue_length(self.queue).await.map_err(|e| e.to_string())?;
+        if backlog > self.max_backlog {
+            return Err(format!("{} messages waiting in {}", backlog, self.queue));
+        }
+        Ok(())
+    }
+}
+
+/// Las comprobaciones de las dependencias del `AppState`
+pub fn app_state_checks(app_state: &AppState) -> Vec<Arc<dyn DependencyCheck>> {
+    vec![
+        Arc::new(DatabaseCheck(app_state.clone())),
+        Arc::new(RedisCheck(app_state.clone())),
+        Arc::new(ZkServiceCheck(app_state.clone())),
+        Arc::new(SolanaRpcCheck::from_env()),
+        Arc::new(EthereumRpcCheck(app_state.clone())),
+    ]
+}
+
+/// Base de datos, Redis y los workers de los que dependen los gateways
+pub fn platform_checks(app_state: &AppState) -> Vec<Arc<dyn DependencyCheck>> {
+    let ethereum_service_url =
+        std::env::var("ETHEREUM_SERVICE_URL").unwrap_or_else(|_| "http://localhost:3001".to_string());
+    vec![
+        Arc::new(DatabaseCheck(app_state.clone())),
+        Arc::new(RedisCheck(app_state.clone())),
+        Arc::new(ZkServiceCheck(app_state.clone())),
+        Arc::new(HttpHealthCheck::new("ethereum_service", &ethereum_service_url)),
+        Arc::new(QueueBacklogCheck {
+            name: "solana_worker",
+            queue: QueueNames::SOLANA,
+            max_backlog: MAX_QUEUE_BACKLOG,
+            app_state: app_state.clone(),
+        }),
+    ]
+}
+
+/// Ejecutar una comprobación con su propio tiempo máximo
+pub(crate) async fn run_check(check: Arc<dyn DependencyCheck>, timeout: Duration) -> (String, ServiceStatus) {
+    let started = Instant::now();
+    let result = match tokio::time::timeout(timeout, check.check()).await {
+        Ok(result) => result,
+        Err(_) => Err(format!("Timed out after {}ms", timeout.as_millis())),
+    };
+    let status = ServiceStatus {
+        healthy: result.is_ok(),
+        critical: CRITICAL_SERVICES.contains(&check.name()),
+        latency_ms: started.elapsed().as_millis() as u64,
+        error: result.err(),
+    };
+    (check.name().to_string(), status)
+}
+
+/// Ejecutar las comprobaciones en paralelo. Devuelve los fallos de las
+/// críticas como error; si solo fallan otras, el informe sale con
+/// `all_healthy = false`.
+pub async fn check_dependencies(
+    checks: Vec<Arc<dyn DependencyCheck>>,
+    timeout: Duration,
+) -> Result<DependencyReport, Vec<String>> {
+    let handles: Vec<_> = checks
+        .into_iter()
+        .map(|check| tokio::spawn(run_check(check, timeout)))
+        .collect();
+
+    let mut services = HashMap::new();
+    for handle in handles {
+        let (name, status) = handle.await.map_err(|e| vec![format!("Dependency check panicked: {}", e)])?;
+        services.insert(name, status);
+    }
+
+    let mut critical_failures: Vec<String> = services
+        .iter()
+        .filter(|(_, status)| status.critical && !status.healthy)
+        .map(|(name, status)| format!("{}: {}", name, status.error.as_deref().unwrap_or("unhealthy")))
+        .collect();
+    // Una crítica que ni se comprobó cuenta como caída
+    for name in CRITICAL_SERVICES {
+        if !services.contains_key(*name) {
+            critical_failures.push(format!("{}: not checked", name));
+        }
+    }
+    if !critical_failures.is_empty() {
+        critical_failures.sort();
+        return Err(critical_failures);
+    }
+
+    Ok(DependencyReport {
+        all_healthy: services.values().all(|status| status.healthy),
+        services,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeCheck {
+        name: &'static str,
+        result: Result<(), String>,
+        delay: Duration,
+    }
+
+    fn fake(name: &'static str, result: Result<(), &str>) -> Arc<dyn DependencyCheck> {
+        Arc::new(FakeCheck { name, result: result.map_err(str::to_string), delay: Duration::ZERO })
+    }
+
+    async fn check(checks: Vec<Arc<dyn DependencyCheck>>) -> Result<DependencyReport, Vec<String>> {
+        check_dependencies(checks, DEPENDENCY_CHECK_TIMEOUT).await
+    }
+
+    #[async_trait]
+    impl DependencyCheck for FakeCheck {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn check(&self) -> Result<(), String> {
+            tokio::time::sleep(self.delay).await;
+            self.result.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn all_dependencies_healthy() {
+        let report = check(vec![
+            fake("database", Ok(())),
+            fake("redis", Ok(())),
+            fake("zk_service", Ok(())),
+        ])
+        .await
+        .unwrap();
+
+        assert!(report.all_healthy);
+        assert_eq!(report.services.len(), 3);
+        assert!(report.services["database"].critical);
+        assert!(!report.services["zk_service"].critical);
+        assert!(report.degraded_services().is_empty());
+    }
+
+    #[tokio::test]
+    async fn non_critical_failures_only_degrade_the_report() {
+        let report = check(vec![
+            fake("database", Ok(())),
+            fake("redis", Ok(())),
+            fake("solana_rpc", Err("connection refused")),
+            fake("ethereum_rpc", Err("503")),
+        ])
+        .await
+        .unwrap();
+
+        assert!(!report.all_healthy);
+        assert_eq!(report.degraded_services(), vec!["ethereum_rpc", "solana_rpc"]);
+        assert_eq!(report.services["solana_rpc"].error.as_deref(), Some("connection refused"));
+    }
+
+    #[tokio::test]
+    async fn critical_failures_are_returned_as_errors() {
+        let errors = check(vec![
+            fake("database", Err("password authentication failed")),
+            fake("redis", Ok(())),
+            fake("zk_service", Err("unreachable")),
+        ])
+        .await
+        .unwrap_err();
+        assert_eq!(errors, vec!["database: password authentication failed".to_string()]);
+
+        // Sin comprobación de Redis tampoco se arranca
+        let errors = check(vec![fake("database", Ok(()))]).await.unwrap_err();
+        assert_eq!(errors, vec!["redis: not checked".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn hanging_dependencies_time_out() {
+        let hanging: Arc<dyn DependencyCheck> =
+            Arc::new(FakeCheck { name: "redis", result: Ok(()), delay: Duration::from_secs(60) });
+
+        let errors = check_dependencies(vec![fake("database", Ok(())), hanging], Duration::from_millis(50))
+            .await
+            .unwrap_err();
+        assert_eq!(errors, vec!["redis: Timed out after 50ms".to_string()]);
+    }
+}
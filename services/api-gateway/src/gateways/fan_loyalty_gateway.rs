@@ -17,6 +17,10 @@ use crate::bounded_contexts::fan_loyalty::application::real_dependency_injection
 // Alias para simplificar
 type FanLoyaltyContainer = RealFanLoyaltyContainer;
 use crate::bounded_contexts::fan_loyalty::infrastructure::api_handlers::create_fan_loyalty_router;
+use crate::bounded_contexts::fan_loyalty::application::wristband_minting::WristbandMintService;
+use crate::bounded_contexts::fan_loyalty::infrastructure::nft_mint_queue::{RedisWristbandMintQueue, WristbandNftMintResultWorker};
+use crate::bounded_contexts::fan_loyalty::application::benefit_redemption::BenefitRedemptionService;
+use crate::bounded_contexts::fan_loyalty::infrastructure::postgres_repositories::{PostgresBenefitRepository, PostgresWristbandMintRepository};
 
 /// Crear el gateway para Fan Loyalty System
 pub async fn create_fan_loyalty_gateway(app_state: AppState) -> Result<Router, Box<dyn std::error::Error>> {
@@ -31,6 +35,39 @@ pub async fn create_fan_loyalty_gateway(app_state: AppState) -> Result<Router, B
         app_state.blockchain_client.clone(),
     );
 
+    // Minteo de pulseras vía solana-integration, a la wallet de custodia de la plataforma
+    let fan_loyalty_container = match std::env::var("WRISTBAND_CUSTODY_WALLET") {
+        Ok(custody_wallet) => {
+            let mut minting = WristbandMintService::new(
+                Arc::new(PostgresWristbandMintRepository::new(app_state.database_pool.get_pool().clone())),
+                Arc::new(RedisWristbandMintQueue::new(app_state.message_queue.clone())),
+                custody_wallet,
+            );
+            if let Ok(metadata_base_url) = std::env::var("WRISTBAND_METADATA_BASE_URL") {
+                minting = minting.with_metadata_base_url(metadata_base_url);
+            }
+            let minting = Arc::new(minting);
+            let mint_result_worker = WristbandNftMintResultWorker::new(minting.clone(), app_state.message_queue.clone());
+            app_state.background_tasks.spawn("wristband_nft_mint_results", async move {
+                if let Err(e) = mint_result_worker.start().await {
+                    tracing::error!("Wristband NFT mint result worker stopped: {}", e);
+                }
+            });
+            Arc::new((*fan_loyalty_container).clone().with_wristband_minting(minting))
+        }
+        Err(_) => {
+            tracing::warn!("WRISTBAND_CUSTODY_WALLET not set; wristbands will not be minted");
+            fan_loyalty_container
+        }
+    };
+
+    // Catálogo de beneficios y canjes; cada canje se publica para analítica
+    let benefits = BenefitRedemptionService::new(Arc::new(PostgresBenefitRepository::new(
+        app_state.database_pool.get_pool().clone(),
+    )))
+    .with_event_bus(app_state.event_bus.clone());
+    let fan_loyalty_container = Arc::new((*fan_loyalty_container).clone().with_benefits(Arc::new(benefits)));
+
     // Crear router principal con API handlers
     let api_router = create_fan_loyalty_router(fan_loyalty_container.clone());
     
@@ -54,6 +91,7 @@ async fn health_check() -> Json<serde_json::Value> {
             "biometric_verification",
             "nft_wristbands", 
             "qr_codes",
+            "benefit_redemption",
             "event_driven_architecture",
             "loose_coupling",
             "tdd_implementation"
@@ -72,7 +110,11 @@ async fn info() -> Json<serde_json::Value> {
             "create_wristband": "POST /api/v1/create-wristband", 
             "get_wristband": "GET /api/v1/wristband/:id",
             "activate_wristband": "POST /api/v1/activate-wristband/:id",
-            "validate_qr": "GET /api/v1/validate-qr/:code"
+            "generate_qr": "POST /api/v1/wristbands/:id/qr",
+            "validate_qr": "GET /api/v1/validate-qr/:code",
+            "benefits": "GET/POST /api/v1/benefits",
+            "redeem_benefit": "POST /api/v1/benefits/:id/redeem",
+            "fan_benefits": "GET /api/v1/fans/:fan_id/benefits"
         },
         "features": {
             "biometric_verification": "Audio, behavioral, device, location biometrics",
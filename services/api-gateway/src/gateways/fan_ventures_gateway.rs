@@ -6,6 +6,11 @@ use axum::{Router, routing::{get, post, put, delete}, response::Json as Response
 use serde_json::json;
 use crate::shared::infrastructure::app_state::{AppState, AppStateFactory};
 use crate::bounded_contexts::fan_ventures::presentation::controllers::FanVenturesController;
+use crate::bounded_contexts::fan_ventures::presentation::{proposal_handlers, purchase_handlers, market_handlers, market_ws, audit_handlers, trade_handlers};
+use crate::bounded_contexts::fan_ventures::application::{ProposalFinalizationJob, MarketStatsRefreshJob, ReservationExpiryJob, SharePurchaseSaga, SharePurchaseSagaConfig};
+use crate::bounded_contexts::fan_ventures::infrastructure::{RedisVentureEventStream, FAN_VENTURES_OUTBOX_CONTEXT, FAN_VENTURES_STREAM_EVENTS};
+use crate::shared::infrastructure::outbox::{EventBusOutboxPublisher, OutboxRelay, OutboxRelayJob};
+use crate::shared::infrastructure::saga::{PostgresSagaStore, SagaTimeoutJob};
 
 /// Crear el gateway de fan ventures básico
 pub async fn create_fan_ventures_gateway(app_state: AppState) -> Result<Router, Box<dyn std::error::Error>> {
@@ -14,6 +19,76 @@ pub async fn create_fan_ventures_gateway(app_state: AppState) -> Result<Router,
         .await
         .map_err(|e| Box::<dyn std::error::Error>::from(e.to_string()))?;
 
+    // Cierra las votaciones vencidas aunque nadie consulte la propuesta
+    let finalization_job = ProposalFinalizationJob::new(
+        fan_ventures_state.proposal_service.clone(),
+        std::time::Duration::from_secs(60),
+    );
+    let background_tasks = fan_ventures_state.app_state.background_tasks.clone();
+    background_tasks.register("proposal_finalization", finalization_job.start());
+
+    // Mantiene caliente la caché de estadísticas de mercado (ventana de 30 días)
+    let market_stats_job = MarketStatsRefreshJob::new(
+        fan_ventures_state.market_stats_service.clone(),
+        std::time::Duration::from_secs(3600),
+    );
+    background_tasks.register("market_stats_refresh", market_stats_job.start());
+
+    // Libera las compras pendientes cuya reserva venció
+    let reservation_expiry_job = ReservationExpiryJob::new(
+        fan_ventures_state.escrow_service.clone(),
+        std::time::Duration::from_secs(30),
+    );
+    background_tasks.register("reservation_expiry", reservation_expiry_job.start());
+
+    // Saga de compra: reserva → cobro → liquidación, con compensación si falla o vence
+    let purchase_saga = std::sync::Arc::new(SharePurchaseSaga::with_config(
+        std::sync::Arc::new(PostgresSagaStore::new(fan_ventures_state.app_state.get_db_pool().clone())),
+        fan_ventures_state.escrow_service.clone(),
+        SharePurchaseSagaConfig::from_env(),
+    ));
+    for event_type in ["SharesReserved", "SharePaymentCompleted", "SharePaymentFailed", "SettlementConfirmed"] {
+        fan_ventures_state.app_state.event_bus
+            .subscribe(event_type, purchase_saga.clone())
+            .await
+            .map_err(|e| Box::<dyn std::error::Error>::from(e.to_string()))?;
+    }
+    background_tasks.register(
+        "share_purchase_saga_timeouts",
+        SagaTimeoutJob::new(purchase_saga, std::time::Duration::from_secs(30)).start(),
+    );
+
+    // Publica en el event bus los eventos que las ventas entre fans dejan en el outbox
+    let outbox_relay = std::sync::Arc::new(OutboxRelay::new(
+        fan_ventures_state.app_state.get_db_pool().clone(),
+        FAN_VENTURES_OUTBOX_CONTEXT,
+        std::sync::Arc::new(EventBusOutboxPublisher::new(fan_ventures_state.app_state.event_bus.clone())),
+    ));
+    background_tasks.register(
+        "fan_ventures_outbox_relay",
+        OutboxRelayJob::new(outbox_relay, std::time::Duration::from_secs(1)).start(),
+    );
+
+    // Feed de precios/portfolio en tiempo real alimentado por el event bus
+    let market_feed = fan_ventures_state.market_feed.clone();
+    for event_type in ["SharePriceUpdated", "InvestmentMade", "RevenueDistributed"] {
+        fan_ventures_state.app_state.event_bus
+            .subscribe(event_type, market_feed.clone())
+            .await
+            .map_err(|e| Box::<dyn std::error::Error>::from(e.to_string()))?;
+    }
+
+    // Réplica de los eventos del contexto en el stream `fan_ventures_events`
+    let venture_stream = std::sync::Arc::new(RedisVentureEventStream::new(
+        fan_ventures_state.app_state.message_queue.connection_manager(),
+    ));
+    for event_type in FAN_VENTURES_STREAM_EVENTS {
+        fan_ventures_state.app_state.event_bus
+            .subscribe(event_type, venture_stream.clone())
+            .await
+            .map_err(|e| Box::<dyn std::error::Error>::from(e.to_string()))?;
+    }
+
     let router = Router::new()
         .route("/health", get(health_check))
         .route("/info", get(gateway_info))
@@ -33,8 +108,11 @@ pub async fn create_fan_ventures_gateway(app_state: AppState) -> Result<Router,
         // INVESTMENT MANAGEMENT
         // =============================================================================
         //.route("/investments", get(FanVenturesController::get_investments))
+        .route("/investments", post(FanVenturesController::create_investment))
         .route("/ventures/:id/invest", post(FanVenturesController::invest_in_venture))
         //.route("/investments/:id", get(FanVenturesController::get_investment))
+        .route("/purchases/:id/confirm", post(purchase_handlers::confirm_purchase))
+        .route("/purchases/:id/cancel", post(purchase_handlers::cancel_purchase))
         
         // =============================================================================
         // BENEFIT DELIVERY
@@ -46,13 +124,34 @@ pub async fn create_fan_ventures_gateway(app_state: AppState) -> Result<Router,
         // ANALYTICS & REPORTING
         // =============================================================================
         .route("/analytics/ventures/:id", get(FanVenturesController::get_venture_analytics))
+        .route("/market/stats", get(market_handlers::get_market_stats))
+        .route("/ventures/:id/trade-history", get(trade_handlers::get_trade_history))
         
         // =============================================================================
         // USER INVESTMENTS
         // =============================================================================
         .route("/investments/user/:user_id", get(FanVenturesController::get_user_investments))
+        .route("/portfolios/:user_id", get(FanVenturesController::get_portfolio))
+        
+        // =============================================================================
+        // SHAREHOLDER VOTING
+        // =============================================================================
+        .route("/ventures/:id/proposals", post(proposal_handlers::create_proposal))
+        .route("/ventures/:id/proposals", get(proposal_handlers::list_venture_proposals))
+        .route("/proposals/:id", get(proposal_handlers::get_proposal))
+        .route("/proposals/:id/votes", post(proposal_handlers::cast_vote))
+        
+        // =============================================================================
+        // ADMIN
+        // =============================================================================
+        .route("/admin/audit/:aggregate_id", get(audit_handlers::get_audit_trail))
+        
+        .with_state(fan_ventures_state)
         
-        .with_state(fan_ventures_state);
+        // =============================================================================
+        // REAL-TIME MARKET FEED
+        // =============================================================================
+        .merge(market_ws::routes(market_feed));
     
     Ok(router)
 }
@@ -74,9 +173,13 @@ async fn gateway_info() -> ResponseJson<serde_json::Value> {
             "health": "/health",
             "ventures": "/ventures",
             "investments": "/investments",
+            "purchases": "/purchases/:id/{confirm,cancel}",
             "portfolios": "/portfolios",
             "benefits": "/benefits",
             "analytics": "/analytics/*",
+            "market": "/market/stats",
+            "trade_history": "/ventures/:id/trade-history",
+            "websocket": "/ws",
             "admin": "/admin/*"
         }
     }))
@@ -5,10 +5,49 @@
 use axum::{Router, routing::{get, post, put, delete}, response::Json as ResponseJson, extract::{State, Json, Path}};
 use serde_json::json;
 use crate::shared::infrastructure::app_state::AppState;
+use crate::shared::infrastructure::auth::RequireRole;
+use crate::bounded_contexts::user::domain::UserRole;
 use crate::shared::infrastructure::clients::zk_service_client::{ZkProof, VerifyProofResponse};
 
 /// Crear el gateway de listen rewards básico
 pub async fn create_listen_reward_gateway(app_state: AppState) -> Result<Router, Box<dyn std::error::Error>> {
+    // Las sesiones abandonadas (activas más allá del timeout) pasan a Failed
+    let session_timeout_minutes = std::env::var("LISTEN_SESSION_TIMEOUT_MINUTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(crate::bounded_contexts::listen_reward::application::DEFAULT_SESSION_TIMEOUT_MINUTES);
+    let session_cleanup_job = crate::bounded_contexts::listen_reward::application::ExpiredSessionCleanupJob::new(
+        std::sync::Arc::new(crate::bounded_contexts::listen_reward::infrastructure::repositories::PostgresListenSessionRepository::new(app_state.get_db_pool().clone())),
+        session_timeout_minutes,
+        std::time::Duration::from_secs(300),
+    );
+    app_state.background_tasks.register("listen_session_cleanup", session_cleanup_job.start());
+
+    // Publica los eventos que las distribuciones dejan en el outbox con el
+    // publicador configurado (LISTEN_REWARD_EVENT_PUBLISHER = postgres | redis_stream)
+    let publisher_factory = crate::bounded_contexts::listen_reward::infrastructure::event_publishers::EventPublisherFactory::new(
+        app_state.get_db_pool().clone(),
+    )
+    .await?;
+    let event_publisher = match std::env::var("LISTEN_REWARD_EVENT_PUBLISHER").as_deref() {
+        Ok("redis_stream") => {
+            let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
+            publisher_factory.create_redis_stream_publisher(&redis_url, "listen_reward:events").await?
+        }
+        _ => publisher_factory.create_postgres_publisher().await?,
+    };
+    let outbox_relay = std::sync::Arc::new(crate::shared::infrastructure::outbox::OutboxRelay::new(
+        app_state.get_db_pool().clone(),
+        crate::bounded_contexts::listen_reward::infrastructure::repositories::LISTEN_REWARD_OUTBOX_CONTEXT,
+        std::sync::Arc::new(crate::bounded_contexts::listen_reward::infrastructure::EventPublisherOutboxAdapter::new(
+            std::sync::Arc::from(event_publisher),
+        )),
+    ));
+    app_state.background_tasks.register(
+        "listen_reward_outbox_relay",
+        crate::shared::infrastructure::outbox::OutboxRelayJob::new(outbox_relay, std::time::Duration::from_secs(1)).start(),
+    );
+
     let router = Router::new()
         .route("/health", get(health_check))
         .route("/info", get(gateway_info))
@@ -49,8 +88,14 @@ pub async fn create_listen_reward_gateway(app_state: AppState) -> Result<Router,
         // =============================================================================
         // ADMIN ENDPOINTS
         // =============================================================================
-        .route("/admin/sessions", get(get_all_sessions_admin))
-        .route("/admin/rewards", get(get_all_rewards_admin));
+        .merge(
+            Router::new()
+                .route("/admin/sessions", get(get_all_sessions_admin))
+                .route("/admin/rewards", get(get_all_rewards_admin))
+                .route("/admin/fraud-scores", get(crate::bounded_contexts::listen_reward::presentation::handlers::get_fraud_scores_for_review))
+                .route("/admin/fraud-scores/:id/review", post(crate::bounded_contexts::listen_reward::presentation::handlers::review_fraud_score))
+                .route_layer(RequireRole(UserRole::Admin)),
+        );
     
     Ok(router.with_state(app_state))
 }
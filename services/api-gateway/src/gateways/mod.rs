@@ -13,9 +13,12 @@ pub mod listen_reward_gateway;
 pub mod fan_ventures_gateway;
 pub mod notification_gateway;
 pub mod fan_loyalty_gateway;
+pub mod dependencies;
+pub mod aggregate_health;
+pub mod config;
 
 // Re-export para facilitar el uso
-pub use user_gateway::create_user_gateway;
+pub use user_gateway::{create_user_gateway, create_auth_gateway};
 pub use music_gateway::create_music_gateway;
 pub use payment_gateway::create_payment_gateway;
 pub use campaign_gateway::create_campaign_gateway;
@@ -23,6 +26,9 @@ pub use listen_reward_gateway::create_listen_reward_gateway;
 pub use fan_ventures_gateway::create_fan_ventures_gateway;
 pub use notification_gateway::create_notification_gateway;
 pub use fan_loyalty_gateway::create_fan_loyalty_gateway;
+pub use dependencies::{DependencyReport, ServiceStatus, CRITICAL_SERVICES};
+pub use aggregate_health::{aggregate_health_router, AggregateHealth, AggregateHealthReport, PlatformStatus};
+pub use config::{bind_gateways, serve_gateways, BoundGateway, GatewaysConfig, GATEWAY_CONFIG_ENV};
 
 // =============================================================================
 // GATEWAY FACTORY
@@ -30,14 +36,25 @@ pub use fan_loyalty_gateway::create_fan_loyalty_gateway;
 
 use axum::Router;
 use std::sync::Arc;
+use crate::shared::domain::errors::AppError;
 use crate::shared::infrastructure::app_state::AppState;
+use crate::shared::infrastructure::rate_limit::{
+    RateLimitConfig, RateLimitLayer, RateLimiter, RedisRateLimitStore,
+};
 
 /// Factory para crear todos los gateways con configuración consistente
 pub struct GatewayFactory;
 
 impl GatewayFactory {
-    /// Crear todos los gateways independientes
+    /// Crear todos los gateways independientes. Si falla una dependencia de
+    /// `CRITICAL_SERVICES` no se crea ninguno.
     pub async fn create_all_gateways(app_state: AppState) -> Result<Vec<(String, Router)>, Box<dyn std::error::Error>> {
+        let report = Self::validate_dependencies(&app_state).await
+            .map_err(|failures| format!("Critical dependencies unavailable: {}", failures.join("; ")))?;
+        if !report.all_healthy {
+            tracing::warn!("Starting gateways with degraded dependencies: {}", report.degraded_services().join(", "));
+        }
+        
         let gateways = vec![
             ("user".to_string(), create_user_gateway(app_state.clone()).await?),
             ("music".to_string(), create_music_gateway(app_state.clone()).await?),
@@ -52,6 +69,15 @@ impl GatewayFactory {
         Ok(gateways)
     }
     
+    /// Comprobar base de datos, Redis, servicio ZK y RPC de Solana y Ethereum.
+    /// Devuelve los fallos de las dependencias críticas como error.
+    pub async fn validate_dependencies(app_state: &AppState) -> Result<DependencyReport, Vec<String>> {
+        dependencies::check_dependencies(
+            dependencies::app_state_checks(app_state),
+            dependencies::DEPENDENCY_CHECK_TIMEOUT,
+        ).await
+    }
+    
     /// Crear gateway específico por nombre
     pub async fn create_gateway_by_name(
         name: &str, 
@@ -71,6 +97,21 @@ impl GatewayFactory {
     }
 }
 
+/// Aplicar el rate limiting del gateway con los cubos en Redis: por clase de
+/// ruta si lo tiene activado (en `GATEWAY_CONFIG` o, si no aparece, según
+/// `config`) y, siempre, las reglas por endpoint de `RATE_LIMIT_CONFIG`.
+/// Falla si `RATE_LIMIT_CONFIG` no es válida.
+pub(crate) fn with_rate_limiting(router: Router, config: &GatewayConfig, app_state: &AppState) -> Result<Router, AppError> {
+    let mut config = GatewaysConfig::shared().get(&config.name).unwrap_or(config).clone();
+    // Los límites por clase son los del gateway; del fichero solo se suman las reglas por endpoint
+    config.rate_limits.rules.extend(RateLimitConfig::from_env()?.rules);
+    let store = Arc::new(RedisRateLimitStore::new(app_state.message_queue.connection_manager()));
+    Ok(match RateLimiter::for_gateway(&config, store) {
+        Some(limiter) => router.layer(RateLimitLayer::new(limiter)),
+        None => router,
+    })
+}
+
 // =============================================================================
 // GATEWAY CONFIGURATION
 // =============================================================================
@@ -83,6 +124,8 @@ pub struct GatewayConfig {
     pub host: String,
     pub cors_enabled: bool,
     pub rate_limiting_enabled: bool,
+    /// Límites por clase de ruta cuando `rate_limiting_enabled`
+    pub rate_limits: RateLimitConfig,
     pub health_check_enabled: bool,
 }
 
@@ -94,12 +137,26 @@ impl Default for GatewayConfig {
             host: "127.0.0.1".to_string(),
             cors_enabled: true,
             rate_limiting_enabled: false,
+            rate_limits: RateLimitConfig::default(),
             health_check_enabled: true,
         }
     }
 }
 
 impl GatewayConfig {
+    /// Documentación OpenAPI centralizada
+    pub fn docs_gateway() -> Self {
+        Self {
+            name: "docs".to_string(),
+            port: 3000,
+            host: "127.0.0.1".to_string(),
+            cors_enabled: true,
+            rate_limiting_enabled: false,
+            rate_limits: RateLimitConfig::default(),
+            health_check_enabled: true,
+        }
+    }
+
     /// Crear configuración específica para cada gateway
     pub fn user_gateway() -> Self {
         Self {
@@ -108,6 +165,7 @@ impl GatewayConfig {
             host: "127.0.0.1".to_string(),
             cors_enabled: true,
             rate_limiting_enabled: true,
+            rate_limits: RateLimitConfig::default(),
             health_check_enabled: true,
         }
     }
@@ -119,6 +177,7 @@ impl GatewayConfig {
             host: "127.0.0.1".to_string(),
             cors_enabled: true,
             rate_limiting_enabled: false,
+            rate_limits: RateLimitConfig::default(),
             health_check_enabled: true,
         }
     }
@@ -130,6 +189,7 @@ impl GatewayConfig {
             host: "127.0.0.1".to_string(),
             cors_enabled: true,
             rate_limiting_enabled: true,
+            rate_limits: RateLimitConfig::default(),
             health_check_enabled: true,
         }
     }
@@ -141,6 +201,7 @@ impl GatewayConfig {
             host: "127.0.0.1".to_string(),
             cors_enabled: true,
             rate_limiting_enabled: false,
+            rate_limits: RateLimitConfig::default(),
             health_check_enabled: true,
         }
     }
@@ -152,6 +213,7 @@ impl GatewayConfig {
             host: "127.0.0.1".to_string(),
             cors_enabled: true,
             rate_limiting_enabled: false,
+            rate_limits: RateLimitConfig::default(),
             health_check_enabled: true,
         }
     }
@@ -163,6 +225,7 @@ impl GatewayConfig {
             host: "127.0.0.1".to_string(),
             cors_enabled: true,
             rate_limiting_enabled: false,
+            rate_limits: RateLimitConfig::default(),
             health_check_enabled: true,
         }
     }
@@ -174,6 +237,7 @@ impl GatewayConfig {
             host: "127.0.0.1".to_string(),
             cors_enabled: true,
             rate_limiting_enabled: false,
+            rate_limits: RateLimitConfig::default(),
             health_check_enabled: true,
         }
     }
@@ -185,6 +249,7 @@ impl GatewayConfig {
             host: "127.0.0.1".to_string(),
             cors_enabled: true,
             rate_limiting_enabled: true, // High security for biometric data
+            rate_limits: RateLimitConfig::default(),
             health_check_enabled: true,
         }
     }
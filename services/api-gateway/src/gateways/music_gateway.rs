@@ -8,6 +8,7 @@
 // - Gestión de álbumes y playlists
 // - Búsqueda y descubrimiento musical
 
+use std::sync::Arc;
 use axum::{
     Router,
     routing::{get, post, put, delete},
@@ -16,10 +17,15 @@ use axum::{
 };
 use serde_json::json;
 use crate::shared::infrastructure::app_state::{AppState, AppStateFactory};
+use crate::gateways::{with_rate_limiting, GatewayConfig};
 use crate::shared::infrastructure::auth::middleware::jwt_auth_middleware;
+use crate::shared::infrastructure::auth::RequireRole;
+use crate::bounded_contexts::user::domain::UserRole;
 use crate::bounded_contexts::music::presentation::controllers::{
     SongController, AlbumController, PlaylistController, ArtistController
 };
+use crate::bounded_contexts::music::application::{SignalingHub, UserSimilarityRefreshJob, SIMILARITY_REFRESH_HOUR_UTC};
+use crate::bounded_contexts::music::presentation::video_signaling_ws;
 
 // =============================================================================
 // GATEWAY CREATION
@@ -30,10 +36,17 @@ use crate::bounded_contexts::music::presentation::controllers::{
 /// Conecta a controllers reales que usan repositorios PostgreSQL
 pub async fn create_music_gateway(app_state: AppState) -> Result<Router, Box<dyn std::error::Error>> {
     // Crear MusicAppState desde AppState usando el factory
-    let music_app_state = AppStateFactory::create_music_state(app_state).await
+    let music_app_state = AppStateFactory::create_music_state(app_state.clone()).await
         .map_err(|e| -> Box<dyn std::error::Error> {
             Box::new(std::io::Error::new(std::io::ErrorKind::Other, format!("{}", e)))
         })?;
+
+    // Recalcula cada noche los usuarios similares que usa el recomendador de playlists
+    let similarity_job = UserSimilarityRefreshJob::new(
+        music_app_state.recommendation_read_model.clone(),
+        SIMILARITY_REFRESH_HOUR_UTC,
+    );
+    app_state.background_tasks.register("user_similarity_refresh", similarity_job.start());
     
     // =============================================================================
     // RUTAS PÚBLICAS (No requieren autenticación)
@@ -46,6 +59,9 @@ pub async fn create_music_gateway(app_state: AppState) -> Result<Router, Box<dyn
         // Songs - Lectura pública
         .route("/songs", get(SongController::get_songs))
         .route("/songs/:id", get(SongController::get_song))
+        .route("/songs/:id/credits", get(SongController::get_song_credits))
+        .route("/songs/:id/chapters", get(SongController::get_song_chapters))
+        .route("/songs/:id/similar", get(SongController::get_similar_songs))
         
         // Albums - Lectura pública
         .route("/albums", get(AlbumController::get_albums))
@@ -58,6 +74,7 @@ pub async fn create_music_gateway(app_state: AppState) -> Result<Router, Box<dyn
         // Artists - Lectura pública
         .route("/artists/:id", get(ArtistController::get_artist))
         .route("/artists/:id/albums", get(ArtistController::get_artist_albums))
+        .route("/artists/:id/followers", get(ArtistController::get_artist_followers))
         
         // Endpoints temporales (públicos por ahora)
         .route("/songs/discover", get(SongController::discover_songs))
@@ -80,11 +97,25 @@ pub async fn create_music_gateway(app_state: AppState) -> Result<Router, Box<dyn
     // =============================================================================
     // RUTAS PROTEGIDAS (Requieren autenticación JWT)
     // =============================================================================
+    let admin_routes = Router::new()
+        .route("/admin/songs", get(get_all_songs_admin))
+        .route("/admin/songs/:id", put(update_song_admin))
+        .route("/admin/songs/:id", delete(delete_song_admin))
+        .route("/admin/albums", get(get_all_albums_admin))
+        .route("/admin/artists", get(get_all_artists_admin))
+        .route_layer(RequireRole(UserRole::Admin));
+    
     let protected_routes = Router::new()
         // Songs - Escritura (requiere auth)
         .route("/songs", post(SongController::create_song))
         .route("/songs/:id", put(SongController::update_song))
         .route("/songs/:id", delete(SongController::delete_song))
+        .route("/songs/:id/remix-license", post(SongController::purchase_remix_license))
+        .route("/songs/:id/stems", get(SongController::download_stems))
+        .route("/songs/:id/analytics", get(SongController::get_song_analytics))
+
+        // Artists - Dashboard del artista (requiere auth)
+        .route("/artists/:id/dashboard", get(ArtistController::get_artist_dashboard))
         
         // Albums - Escritura (requiere auth)
         .route("/albums", post(AlbumController::create_album))
@@ -93,8 +124,13 @@ pub async fn create_music_gateway(app_state: AppState) -> Result<Router, Box<dyn
         
         // Playlists - Escritura (requiere auth)
         .route("/playlists", post(PlaylistController::create_playlist))
+        .route("/playlists/generate", post(PlaylistController::generate_playlist))
+        .route("/playlists/recommended", get(PlaylistController::get_recommended_playlists))
+        .route("/playlists/:id/follow", post(PlaylistController::follow_playlist))
+        .route("/playlists/:id/follow", delete(PlaylistController::unfollow_playlist))
         .route("/playlists/:id/songs", post(PlaylistController::add_song_to_playlist))
         .route("/playlists/:id/songs/:song_id", delete(PlaylistController::remove_song_from_playlist))
+        .route("/playlists/:id/history", get(PlaylistController::get_playlist_history))
         
         // Artists - Escritura (requiere auth)
         // TODO: Implementar ArtistController::update_artist
@@ -104,11 +140,9 @@ pub async fn create_music_gateway(app_state: AppState) -> Result<Router, Box<dyn
         .route("/songs/:id/like", post(like_song))
         .route("/songs/:id/unlike", post(unlike_song))
         .route("/songs/:id/share", post(share_song))
-        .route("/admin/songs", get(get_all_songs_admin))
-        .route("/admin/songs/:id", put(update_song_admin))
-        .route("/admin/songs/:id", delete(delete_song_admin))
-        .route("/admin/albums", get(get_all_albums_admin))
-        .route("/admin/artists", get(get_all_artists_admin))
+        
+        // Admin - solo rol admin
+        .merge(admin_routes)
         
         // Aplicar middleware de autenticación a todas las rutas protegidas
         .layer(middleware::from_fn(jwt_auth_middleware));
@@ -119,9 +153,12 @@ pub async fn create_music_gateway(app_state: AppState) -> Result<Router, Box<dyn
     let router = Router::new()
         .merge(public_routes)
         .merge(protected_routes)
-        .with_state(music_app_state);
+        .with_state(music_app_state)
+        // Señalización WebRTC para el intercambio P2P de segmentos de vídeo
+        .merge(video_signaling_ws::routes(Arc::new(SignalingHub::new())));
 
-    Ok(router)
+    // Sin límites por clase de ruta; solo las reglas por endpoint (subidas, listados...)
+    Ok(with_rate_limiting(router, &GatewayConfig::music_gateway(), &app_state)?)
 }
 
 // =============================================================================
@@ -186,7 +223,7 @@ async fn share_song() -> ResponseJson<serde_json::Value> {
 
 // NOTE: Album and Playlist CRUD handlers removed - these endpoints now use real controllers:
 // - AlbumController::get_albums, create_album, get_album, update_album, delete_album
-// - PlaylistController::get_playlists, create_playlist, get_playlist, add_song_to_playlist, remove_song_from_playlist
+// - PlaylistController::get_playlists, create_playlist, generate_playlist, get_playlist, add_song_to_playlist, remove_song_from_playlist
 
 // =============================================================================
 // ARTIST MANAGEMENT HANDLERS
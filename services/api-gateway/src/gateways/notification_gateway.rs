@@ -2,12 +2,70 @@
 // NOTIFICATION GATEWAY - GESTIÓN DE NOTIFICACIONES INDEPENDIENTE
 // =============================================================================
 
+use std::sync::Arc;
 use axum::{Router, routing::{get, post, put, delete}, response::Json as ResponseJson};
 use serde_json::json;
-use crate::shared::infrastructure::app_state::AppState;
+use crate::shared::infrastructure::app_state::{AppState, AppStateFactory};
+use crate::shared::infrastructure::auth::{JwtService, RequireRole};
+use crate::bounded_contexts::notifications::presentation::controllers::NotificationController;
+use crate::bounded_contexts::notifications::presentation::live_ws::{self, LiveNotificationState};
+use crate::bounded_contexts::notifications::application::{
+    EmailDigestJob, EmailDigestService, EventNotificationService, EventTemplateRegistry, NotificationDispatcher,
+};
+use crate::bounded_contexts::notifications::infrastructure::{
+    email_sender_from_env, push_sender_from_env, IntegrationEventConsumer, PostgresDigestRepository,
+    PostgresRecipientDirectory, RedisDeadLetterQueue,
+};
+use crate::bounded_contexts::user::domain::UserRole;
 
 /// Crear el gateway de notificaciones básico
-pub async fn create_notification_gateway(_app_state: AppState) -> Result<Router, Box<dyn std::error::Error>> {
+pub async fn create_notification_gateway(app_state: AppState) -> Result<Router, Box<dyn std::error::Error>> {
+    let notification_state = AppStateFactory::create_notification_state(app_state)
+        .await
+        .map_err(|e| Box::<dyn std::error::Error>::from(e.to_string()))?;
+
+    // Notificaciones generadas por los eventos de integración (Redis Streams)
+    let redis_url = std::env::var("REDIS_URL").unwrap_or_else(|_| "redis://localhost:6379".to_string());
+    let redis_client = redis::Client::open(redis_url).map_err(|e| Box::new(e) as Box<dyn std::error::Error>)?;
+    let mut dispatcher = NotificationDispatcher::new(
+        notification_state.notification_repository.clone(),
+        notification_state.preferences_repository.clone(),
+    ).with_live_hub(notification_state.app_state.notification_hub.clone());
+    if let Some(push_sender) = push_sender_from_env(notification_state.device_repository.clone()) {
+        dispatcher = dispatcher.with_sender(push_sender);
+    }
+    if let Some(email_sender) = email_sender_from_env(notification_state.app_state.get_db_pool().clone()) {
+        let digests = Arc::new(PostgresDigestRepository::new(notification_state.app_state.get_db_pool().clone()));
+        dispatcher = dispatcher.with_sender(email_sender.clone()).with_digest_queue(digests.clone());
+
+        // Resúmenes de los periodos cerrados
+        let digest_service = Arc::new(EmailDigestService::new(
+            digests,
+            notification_state.preferences_repository.clone(),
+            email_sender,
+        ));
+        notification_state.app_state.background_tasks.register(
+            "email_digests",
+            EmailDigestJob::new(digest_service, std::time::Duration::from_secs(600)).start(),
+        );
+    }
+    let event_notifications = Arc::new(EventNotificationService::new(
+        EventTemplateRegistry::with_default_templates(),
+        Arc::new(PostgresRecipientDirectory::new(notification_state.app_state.get_db_pool().clone())),
+        Arc::new(dispatcher),
+        Arc::new(RedisDeadLetterQueue::new(notification_state.app_state.message_queue.connection_manager())),
+    ));
+    notification_state.app_state.background_tasks.register(
+        "integration_event_consumer",
+        Arc::new(IntegrationEventConsumer::new(redis_client, event_notifications)).start(),
+    );
+
+    let live_state = LiveNotificationState {
+        hub: notification_state.app_state.notification_hub.clone(),
+        notifications: notification_state.notification_repository.clone(),
+        jwt_service: Arc::new(JwtService::from_env().map_err(|e| Box::<dyn std::error::Error>::from(e.to_string()))?),
+    };
+
     let router = Router::new()
         .route("/health", get(health_check))
         .route("/info", get(gateway_info))
@@ -15,13 +73,15 @@ pub async fn create_notification_gateway(_app_state: AppState) -> Result<Router,
         // =============================================================================
         // NOTIFICATION MANAGEMENT
         // =============================================================================
-        .route("/notifications", get(get_notifications))
+        .route("/notifications", get(NotificationController::list_notifications))
         .route("/notifications", post(create_notification))
-        .route("/notifications/:id", get(get_notification))
+        .route("/notifications/read-all", put(NotificationController::mark_all_as_read))
+        .route("/notifications/devices", post(NotificationController::register_device))
+        .route("/notifications/:id", get(NotificationController::get_notification))
         .route("/notifications/:id", put(update_notification))
         .route("/notifications/:id", delete(delete_notification))
         .route("/notifications/:id/send", post(send_notification))
-        .route("/notifications/:id/mark-read", post(mark_notification_read))
+        .route("/notifications/:id/read", put(NotificationController::mark_as_read))
         
         // =============================================================================
         // PUSH NOTIFICATIONS
@@ -52,12 +112,15 @@ pub async fn create_notification_gateway(_app_state: AppState) -> Result<Router,
         // =============================================================================
         // NOTIFICATION PREFERENCES
         // =============================================================================
-        .route("/preferences", get(get_preferences))
+        .route("/preferences", get(NotificationController::get_preferences))
+        .route("/preferences", put(NotificationController::update_preferences))
         .route("/preferences", post(create_preferences))
         .route("/preferences/:id", get(get_preference))
         .route("/preferences/:id", put(update_preferences))
         .route("/preferences/:id/disable", post(disable_preferences))
         .route("/preferences/:id/enable", post(enable_preferences))
+        .route("/unsubscribe/:user_id/:scope", get(NotificationController::unsubscribe))
+        .route("/unsubscribe/:user_id/:scope", post(NotificationController::unsubscribe))
         
         // =============================================================================
         // TEMPLATES
@@ -79,9 +142,19 @@ pub async fn create_notification_gateway(_app_state: AppState) -> Result<Router,
         // =============================================================================
         // ADMIN ENDPOINTS
         // =============================================================================
-        .route("/admin/notifications", get(get_all_notifications_admin))
-        .route("/admin/templates", get(get_all_templates_admin))
-        .route("/admin/preferences", get(get_all_preferences_admin));
+        .merge(
+            Router::new()
+                .route("/admin/notifications", get(get_all_notifications_admin))
+                .route("/admin/templates", get(get_all_templates_admin))
+                .route("/admin/preferences", get(get_all_preferences_admin))
+                .route_layer(RequireRole(UserRole::Admin)),
+        )
+        .with_state(notification_state)
+        
+        // =============================================================================
+        // REAL-TIME DELIVERY
+        // =============================================================================
+        .merge(live_ws::routes(live_state));
     
     Ok(router)
 }
@@ -102,12 +175,15 @@ async fn gateway_info() -> ResponseJson<serde_json::Value> {
         "endpoints": {
             "health": "/health",
             "notifications": "/notifications",
+            "devices": "/notifications/devices",
             "push": "/push",
             "email": "/email",
             "messages": "/messages",
             "preferences": "/preferences",
+            "unsubscribe": "/unsubscribe/:user_id/:scope",
             "templates": "/templates",
             "analytics": "/analytics/*",
+            "live": "/ws/notifications",
             "admin": "/admin/*"
         }
     }))
@@ -117,26 +193,12 @@ async fn gateway_info() -> ResponseJson<serde_json::Value> {
 // NOTIFICATION MANAGEMENT HANDLERS
 // =============================================================================
 
-async fn get_notifications() -> ResponseJson<serde_json::Value> {
-    ResponseJson(json!({
-        "notifications": [],
-        "total": 0,
-        "message": "Get notifications endpoint - TODO: Implement with real service"
-    }))
-}
-
 async fn create_notification() -> ResponseJson<serde_json::Value> {
     ResponseJson(json!({
         "message": "Create notification endpoint - TODO: Implement with real service"
     }))
 }
 
-async fn get_notification() -> ResponseJson<serde_json::Value> {
-    ResponseJson(json!({
-        "message": "Get notification endpoint - TODO: Implement with real service"
-    }))
-}
-
 async fn update_notification() -> ResponseJson<serde_json::Value> {
     ResponseJson(json!({
         "message": "Update notification endpoint - TODO: Implement with real service"
@@ -155,12 +217,6 @@ async fn send_notification() -> ResponseJson<serde_json::Value> {
     }))
 }
 
-async fn mark_notification_read() -> ResponseJson<serde_json::Value> {
-    ResponseJson(json!({
-        "message": "Mark notification read endpoint - TODO: Implement with real service"
-    }))
-}
-
 // =============================================================================
 // PUSH NOTIFICATION HANDLERS
 // =============================================================================
@@ -261,12 +317,6 @@ async fn mark_message_read() -> ResponseJson<serde_json::Value> {
 // NOTIFICATION PREFERENCES HANDLERS
 // =============================================================================
 
-async fn get_preferences() -> ResponseJson<serde_json::Value> {
-    ResponseJson(json!({
-        "message": "Get preferences endpoint - TODO: Implement with real service"
-    }))
-}
-
 async fn create_preferences() -> ResponseJson<serde_json::Value> {
     ResponseJson(json!({
         "message": "Create preferences endpoint - TODO: Implement with real service"
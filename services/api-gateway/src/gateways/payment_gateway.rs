@@ -6,6 +6,8 @@ use axum::{Router, routing::get, response::Json as ResponseJson};
 use serde_json::json;
 use std::sync::Arc;
 use crate::shared::infrastructure::app_state::AppState;
+use crate::shared::infrastructure::idempotency::{IdempotencyStore, PostgresIdempotencyStore};
+use crate::gateways::{with_rate_limiting, GatewayConfig};
 use crate::bounded_contexts::payment::infrastructure::repositories::{
     PostgreSQLPaymentRepository as PostgresPaymentRepository,
     PostgresRoyaltyRepository,
@@ -41,6 +43,7 @@ pub async fn create_payment_gateway(app_state: AppState) -> Result<Router, Box<d
     };
 
     // Initialize Router
+    let mut card_gateway: Option<Arc<dyn crate::bounded_contexts::payment::infrastructure::gateways::PaymentGateway>> = None;
     let mut gateway_router = crate::bounded_contexts::payment::infrastructure::gateways::MultiGatewayRouter::new();
 
     // Register Stripe Gateway
@@ -49,6 +52,7 @@ pub async fn create_payment_gateway(app_state: AppState) -> Result<Router, Box<d
         
         // Register in router
         gateway_router.register_gateway(stripe_gateway.clone());
+        card_gateway = Some(stripe_gateway.clone());
 
         // Register in webhook router
         let stripe_handler = crate::bounded_contexts::payment::infrastructure::webhooks::StripeWebhookHandler::new(
@@ -60,6 +64,22 @@ pub async fn create_payment_gateway(app_state: AppState) -> Result<Router, Box<d
         tracing::error!("Failed to initialize Stripe Gateway");
     }
 
+    // Register USDC-on-Solana gateway (solo si hay cuenta de depósito configurada)
+    let crypto_gateway = crate::bounded_contexts::payment::infrastructure::gateways::CryptoGatewayConfig::from_env().map(|config| {
+        let stream = Arc::new(crate::bounded_contexts::payment::infrastructure::gateways::SolanaUsdcTransferStream::new(
+            config.rpc_url.clone(),
+            config.ws_url.clone(),
+            config.deposit_address.clone(),
+        ));
+        let settlement = Arc::new(crate::bounded_contexts::payment::infrastructure::gateways::RepositoryDepositSettlement::new(
+            payment_repository.clone(),
+        ));
+        let crypto_gateway = Arc::new(crate::bounded_contexts::payment::infrastructure::gateways::CryptoPaymentGateway::new(config));
+        gateway_router.add_gateway(crypto_gateway.clone());
+        crypto_gateway.spawn_watcher(stream, settlement);
+        crypto_gateway
+    });
+
     let webhook_router = Arc::new(router);
     let gateway_router = Arc::new(gateway_router);
 
@@ -81,14 +101,26 @@ pub async fn create_payment_gateway(app_state: AppState) -> Result<Router, Box<d
         notification_service.clone(),
     ));
 
-    // 6. Initialize Command Handler
+    // Webhooks salientes: los pagos completados, fallidos, cancelados y
+    // reembolsados se envían firmados a las integraciones registradas
+    let webhook_service = Arc::new(crate::bounded_contexts::payment::infrastructure::services::WebhookDeliveryService::new(
+        Arc::new(crate::bounded_contexts::payment::infrastructure::repositories::PostgresWebhookRepository::new(pool.clone())),
+    ));
+
+    // 6. Initialize Command Handler. Los pagos en otra moneda guardan el tipo de
+    // cambio a la moneda base con el que se completaron
+    let currency_converter = Arc::new(crate::bounded_contexts::payment::infrastructure::exchange_rates::currency_converter_from_env());
+    let exchange_rate_repository = Arc::new(crate::bounded_contexts::payment::infrastructure::repositories::PostgresPaymentExchangeRateRepository::new(pool.clone()));
     let command_handler = Arc::new(crate::bounded_contexts::payment::application::handlers::command_handlers::PaymentCommandHandlerImpl::new(
         payment_repository.clone(),
         payment_processing_service,
         fraud_detection_service,
         notification_service,
         payment_application_service,
-    ));
+    )
+    .with_event_bus(app_state.event_bus.clone())
+    .with_exchange_rate_capture(currency_converter.clone(), exchange_rate_repository.clone())
+    .with_webhooks(webhook_service.clone()));
 
     // 7. Initialize Query Handlers
     let analytics_repository = Arc::new(MockPaymentAnalyticsRepository);
@@ -117,8 +149,8 @@ pub async fn create_payment_gateway(app_state: AppState) -> Result<Router, Box<d
     ));
     
     // Create controller with injected handler
-    let payment_controller = Arc::new(PaymentController::new(
-        payment_repository,
+    let mut payment_controller = PaymentController::new(
+        payment_repository.clone(),
         royalty_repository,
         wallet_repository,
         webhook_router,
@@ -127,10 +159,86 @@ pub async fn create_payment_gateway(app_state: AppState) -> Result<Router, Box<d
         royalty_command_handler,
         wallet_command_handler,
         payment_query_handler,
+    );
+    if let Some(crypto_gateway) = crypto_gateway {
+        payment_controller = payment_controller.with_crypto_gateway(crypto_gateway);
+    }
+
+    // Refunds: tarjeta por la API de Stripe, cripto a la cola de pagos manuales
+    let refund_executor = Arc::new(crate::bounded_contexts::payment::infrastructure::gateways::GatewayRefundExecutor::new(
+        card_gateway,
+        Arc::new(crate::bounded_contexts::payment::infrastructure::repositories::PostgresCryptoPayoutQueue::new(pool.clone())),
     ));
+    let refund_service = Arc::new(crate::bounded_contexts::payment::application::refund_service::RefundService::new(
+        payment_repository.clone(),
+        Arc::new(crate::bounded_contexts::payment::infrastructure::repositories::PostgresRefundRepository::new(pool.clone())),
+        Arc::new(crate::bounded_contexts::payment::infrastructure::repositories::PostgresRoyaltyReversalRepository::new(pool.clone())),
+        refund_executor,
+    ).with_exchange_rates(exchange_rate_repository)
+    .with_webhooks(webhook_service.clone()));
+    payment_controller = payment_controller.with_refund_service(refund_service);
+
+    // Adelantos sobre royalties: se amortizan con las royalties acreditadas
+    let royalty_advance_service = Arc::new(crate::bounded_contexts::payment::application::royalty_advance_service::RoyaltyAdvanceService::new(
+        Arc::new(crate::bounded_contexts::payment::infrastructure::repositories::PostgresRoyaltyAdvanceRepository::new(pool.clone())),
+        crate::bounded_contexts::payment::domain::royalty_advances::AdvancePolicy::from_env(),
+    ).with_event_bus(app_state.event_bus.clone()));
+    payment_controller = payment_controller.with_royalty_advance_service(royalty_advance_service.clone());
+
+    // Saldos y payouts de artistas. Las transferencias USDC van por cola a
+    // solana-integration, que devuelve firma y estado por la cola de respuesta
+    let payout_policy = crate::bounded_contexts::payment::domain::artist_payouts::PayoutPolicy::from_env();
+    let payout_wallet = Arc::new(crate::bounded_contexts::payment::infrastructure::QueuePayoutWalletClient::new(
+        pool.clone(),
+        app_state.message_queue.clone(),
+        payout_policy.submission_expiry,
+    ));
+    let payout_transfer_worker = crate::bounded_contexts::payment::infrastructure::PayoutTransferResultWorker::new(
+        payout_wallet.clone(),
+        app_state.message_queue.clone(),
+    );
+    app_state.background_tasks.spawn("payout_transfer_results", async move {
+        if let Err(e) = payout_transfer_worker.start().await {
+            tracing::error!("Payout transfer result worker stopped: {}", e);
+        }
+    });
+    let artist_payout_service = Arc::new(crate::bounded_contexts::payment::application::artist_payout_service::ArtistPayoutService::new(
+        Arc::new(crate::bounded_contexts::payment::infrastructure::repositories::PostgresArtistPayoutRepository::new(pool.clone())),
+        payout_policy,
+    )
+    .with_wallet_client(payout_wallet)
+    .with_royalty_advances(royalty_advance_service));
+    let artist_payout_job = crate::bounded_contexts::payment::application::artist_payout_service::ArtistPayoutJob::new(
+        artist_payout_service.clone(),
+        std::time::Duration::from_secs(60),
+    );
+    app_state.background_tasks.register("artist_payouts", artist_payout_job.start());
+    payment_controller = payment_controller.with_artist_payout_service(artist_payout_service.clone());
+
+    // Royalties: split derivado de royalty_percentage + créditos de cada canción;
+    // la parte de los creadores se acredita en su saldo
+    let royalty_distribution_service = Arc::new(crate::bounded_contexts::payment::application::royalty_distribution_service::RoyaltyDistributionService::new(
+        Arc::new(crate::bounded_contexts::payment::infrastructure::repositories::PostgresRoyaltyRunRepository::new(pool.clone())),
+        Arc::new(crate::bounded_contexts::payment::infrastructure::repositories::PostgresSongRoyaltySettings::new(pool.clone())),
+        crate::bounded_contexts::payment::domain::royalty_runs::RoyaltyDistributionConfig::from_env(),
+    )
+    .with_payout_scheduler(artist_payout_service)
+    .with_currency_converter(currency_converter));
+    payment_controller = payment_controller.with_royalty_distribution_service(royalty_distribution_service);
+
+    // Estadísticas reales sobre la tabla de pagos; la ventana de 30 días se cachea en Redis
+    let statistics_service = crate::bounded_contexts::payment::application::PaymentStatisticsService::new(
+        Arc::new(crate::bounded_contexts::payment::infrastructure::repositories::PostgresPaymentStatisticsRepository::new(pool.clone())),
+    ).with_cache(Arc::new(crate::bounded_contexts::payment::infrastructure::RedisPaymentStatisticsCache::new(
+        app_state.message_queue.connection_manager(),
+    )));
+    payment_controller = payment_controller.with_statistics_service(Arc::new(statistics_service));
+    payment_controller = payment_controller.with_webhook_service(webhook_service);
+    let payment_controller = Arc::new(payment_controller);
     
     // Obtener rutas del controller
-    let payment_routes = PaymentController::routes(payment_controller);
+    let idempotency_store: Arc<dyn IdempotencyStore> = Arc::new(PostgresIdempotencyStore::new(pool.clone()));
+    let payment_routes = PaymentController::routes(payment_controller, idempotency_store);
     
     // Crear router principal con health/info + rutas reales
     let router = Router::new()
@@ -145,7 +253,7 @@ pub async fn create_payment_gateway(app_state: AppState) -> Result<Router, Box<d
         // =============================================================================
         .merge(payment_routes);
     
-    Ok(router)
+    Ok(with_rate_limiting(router, &GatewayConfig::payment_gateway(), &app_state)?)
 }
 
 async fn health_check() -> ResponseJson<serde_json::Value> {
@@ -17,8 +17,14 @@ use serde_json::json;
 use std::sync::Arc;
 use crate::shared::infrastructure::app_state::{AppState, AppStateFactory};
 use crate::bounded_contexts::user::application::services::UserApplicationService;
+use crate::bounded_contexts::user::infrastructure::data_export::PostgresUserDataExports;
+use crate::bounded_contexts::user::infrastructure::listening_preferences::PostgresListeningPreferences;
+use crate::bounded_contexts::user::application::listening_preferences::ListeningPreferencesRefreshJob;
 use crate::shared::infrastructure::database::postgres::PostgresUserRepository;
-use crate::bounded_contexts::user::presentation::routes::configure_user_routes;
+use crate::bounded_contexts::user::presentation::routes::{configure_user_routes, configure_auth_routes};
+use crate::shared::infrastructure::app_state::UserAppState;
+use crate::shared::infrastructure::auth::{RefreshTokenService, TwoFactorService, WalletAuthService};
+use crate::gateways::{with_rate_limiting, GatewayConfig};
 
 // =============================================================================
 // GATEWAY CREATION
@@ -27,15 +33,19 @@ use crate::bounded_contexts::user::presentation::routes::configure_user_routes;
 /// Crear el gateway de usuario con todas las rutas y middleware
 pub async fn create_user_gateway(app_state: AppState) -> Result<Router, Box<dyn std::error::Error>> {
     // Crear UserAppState desde AppState usando el factory
-    let user_state = AppStateFactory::create_user_state(app_state)
+    let user_state = AppStateFactory::create_user_state(app_state.clone())
         .await
         .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
     
-    // Crear UserApplicationService con el repositorio
-    let user_service = Arc::new(UserApplicationService::new(
-        user_state.user_repository.clone(),
-        Some(app_state.facial_client.clone())
-    ));
+    let user_service = create_user_service(&app_state, &user_state);
+
+    // Cada 10 escuchas nuevas se recalcula el vector de preferencias del usuario
+    if let Some(listening_preferences) = &user_service.listening_preferences {
+        app_state.background_tasks.register(
+            "listening_preferences_refresh",
+            ListeningPreferencesRefreshJob::new(Arc::clone(listening_preferences), std::time::Duration::from_secs(60)).start(),
+        );
+    }
     
     // Configurar rutas reales usando los controllers
     let user_routes = configure_user_routes(user_service);
@@ -53,7 +63,41 @@ pub async fn create_user_gateway(app_state: AppState) -> Result<Router, Box<dyn
         // =============================================================================
         .nest("/", user_routes);
 
-    Ok(router)
+    Ok(with_rate_limiting(router, &GatewayConfig::user_gateway(), &app_state)?)
+}
+
+/// Crear el gateway de sesiones (/api/v1/auth): refresh, logout y dispositivos
+pub async fn create_auth_gateway(app_state: AppState) -> Result<Router, Box<dyn std::error::Error>> {
+    let user_state = AppStateFactory::create_user_state(app_state.clone())
+        .await
+        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+
+    let router = configure_auth_routes(create_user_service(&app_state, &user_state));
+    Ok(with_rate_limiting(router, &GatewayConfig::user_gateway(), &app_state)?)
+}
+
+/// UserApplicationService con el repositorio, las sesiones de refresh token, el login con wallet, el 2FA, las exportaciones de datos, las preferencias de escucha y el bus de eventos
+fn create_user_service(
+    app_state: &AppState,
+    user_state: &UserAppState,
+) -> Arc<UserApplicationService<PostgresUserRepository>> {
+    let sessions = Arc::new(RefreshTokenService::postgres(app_state.get_db_pool().clone()));
+    let wallet_auth = Arc::new(WalletAuthService::redis(app_state.message_queue.connection_manager()));
+    let two_factor = Arc::new(TwoFactorService::postgres(app_state.get_db_pool().clone()));
+    let data_exports = Arc::new(PostgresUserDataExports::new(app_state.get_db_pool().clone()));
+    let listening_preferences = Arc::new(PostgresListeningPreferences::new(app_state.get_db_pool().clone()));
+    Arc::new(
+        UserApplicationService::new(
+            user_state.user_repository.clone(),
+            Some(app_state.facial_client.clone()),
+        )
+        .with_sessions(sessions)
+        .with_wallet_auth(wallet_auth)
+        .with_two_factor(two_factor)
+        .with_data_exports(data_exports)
+        .with_listening_preferences(listening_preferences)
+        .with_event_bus(app_state.event_bus.clone()),
+    )
 }
 
 // =============================================================================
@@ -78,7 +122,10 @@ async fn gateway_info() -> ResponseJson<serde_json::Value> {
             "users": "/",
             "register": "/register",
             "login": "/login",
+            "sessions": "/api/v1/auth/refresh, /api/v1/auth/logout, /api/v1/auth/sessions",
+            "wallet_login": "/api/v1/auth/wallet/challenge, /api/v1/auth/wallet/verify",
             "profiles": "/:id/profile",
+            "preferences": "/:id/preferences",
             "social": "/:id/follow, /:id/followers",
             "search": "/search, /discover",
             "admin": "/admin/users"
@@ -6,6 +6,8 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use vibestream_types::{ApiMessage, Blockchain, WalletAddress, ServiceMessage, MessageBroker};
 use crate::shared::infrastructure::app_state::AppState;
+use crate::shared::domain::events::current_request_id;
+use crate::shared::infrastructure::telemetry::current_trace_context;
 use crate::auth::{Claims, LoginRequest, LoginResponse, UserInfo, hash_password, verify_password};
 use sqlx::Row;
 use uuid::Uuid;
@@ -16,6 +18,7 @@ pub struct HealthResponse {
     service: String,
     timestamp: String,
     redis: String,
+    zk_service: String,
 }
 
 #[derive(Serialize)]
@@ -120,11 +123,23 @@ pub async fn health_check(State(state): State<AppState>) -> Result<Json<HealthRe
         Err(_) => "disconnected",
     };
 
+    let zk_status = match state.zk_client.health_check().await {
+        Ok(health) => health.status,
+        Err(e) => {
+            tracing::warn!("ZK service health check failed: {}", e);
+            "unreachable".to_string()
+        }
+    };
+
+    // El gateway sigue sirviendo sin pruebas ZK reales, pero lo refleja
+    let status = if zk_status == "healthy" { "healthy" } else { "degraded" };
+
     Ok(Json(HealthResponse {
-        status: "healthy".to_string(),
+        status: status.to_string(),
         service: "api-gateway".to_string(),
         timestamp: chrono::Utc::now().to_rfc3339(),
         redis: redis_status.to_string(),
+        zk_service: zk_status,
     }))
 }
 
@@ -141,7 +156,10 @@ pub async fn process_transaction(
         amount: request.amount,
     };
 
-    let service_message = ServiceMessage::new(api_message);
+    // El worker continúa la traza de esta petición y registra su request ID
+    let service_message = ServiceMessage::new(api_message)
+        .with_trace_context(current_trace_context())
+        .with_request_id(current_request_id());
     let request_id = service_message.id.0.to_string();
 
     // Determinar la cola correcta según la blockchain
@@ -190,7 +208,10 @@ pub async fn get_balance(
 
     // Crear mensaje para obtener balance
     let api_message = ApiMessage::GetBalance { wallet };
-    let service_message = ServiceMessage::new(api_message);
+    // El worker continúa la traza de esta petición y registra su request ID
+    let service_message = ServiceMessage::new(api_message)
+        .with_trace_context(current_trace_context())
+        .with_request_id(current_request_id());
 
     // Determinar la cola correcta
     let queue_name = match blockchain {
@@ -26,8 +26,11 @@ pub mod simple {
     };
     use serde::{Deserialize, Serialize};
     use serde_json::json;
+    use sqlx::Row;
+    use uuid::Uuid;
     
     use crate::shared::infrastructure::app_state::AppState;
+    use crate::shared::infrastructure::auth::{JwtService, PasswordService};
     
     pub async fn create_router() -> Result<Router, Box<dyn std::error::Error>> {
         // Initialize unified AppState
@@ -86,20 +89,63 @@ pub mod simple {
     }
     
     async fn login(
-        State(_state): State<AppState>,
+        State(state): State<AppState>,
         Json(payload): Json<LoginRequest>,
     ) -> Result<ResponseJson<LoginResponse>, StatusCode> {
-        // Demo implementation - in real app would validate credentials
-        if payload.username == "demo" && payload.password == "password" {
-            let token = "eyJhbGciOiJIUzI1NiIsInR5cCI6IkpXVCJ9.eyJzdWIiOiIxMjM0NTY3ODkwIiwibmFtZSI6IkpvaG4gRG9lIiwiaWF0IjoxNTE2MjM5MDIyfQ";
-            let response = LoginResponse {
-                token: token.to_string(),
-                user_id: "user_123".to_string(),
-                expires_at: (chrono::Utc::now() + chrono::Duration::hours(24)).to_rfc3339(),
-            };
-            Ok(ResponseJson(response))
-        } else {
-            Err(StatusCode::UNAUTHORIZED)
+        let row = sqlx::query(
+            "SELECT id, username, email, password_hash, role, COALESCE(tier, 'free') AS tier \
+             FROM users WHERE username = $1 OR email = $1"
+        )
+        .bind(&payload.username)
+        .fetch_optional(state.database_pool.get_pool())
+        .await
+        .map_err(|e| {
+            tracing::error!("Login lookup failed: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        
+        // Misma verificación (y mismo coste) tanto si el usuario existe como si no
+        let stored_hash: Option<String> = row.as_ref().map(|row| row.get("password_hash"));
+        if !PasswordService::verify_credentials(&payload.password, stored_hash.as_deref()) {
+            return Err(StatusCode::UNAUTHORIZED);
+        }
+        let row = row.ok_or(StatusCode::UNAUTHORIZED)?;
+        let user_id: Uuid = row.get("id");
+        
+        if stored_hash.as_deref().map_or(false, PasswordService::needs_rehash) {
+            if let Ok(upgraded) = PasswordService::hash_password(&payload.password) {
+                if let Err(e) = sqlx::query("UPDATE users SET password_hash = $1, updated_at = NOW() WHERE id = $2")
+                    .bind(upgraded)
+                    .bind(user_id)
+                    .execute(state.database_pool.get_pool())
+                    .await
+                {
+                    tracing::warn!("Failed to upgrade password hash for user {}: {}", user_id, e);
+                }
+            }
         }
+        
+        let jwt_service = JwtService::from_env().map_err(|e| {
+            tracing::error!("JWT configuration error: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        let username: String = row.get("username");
+        let email: String = row.get("email");
+        let role: String = row.get("role");
+        let tier: String = row.get("tier");
+        let token = jwt_service
+            .generate_access_token(user_id, &username, &email, &role, &tier)
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        let claims = jwt_service
+            .validate_access_token(&token)
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        let expires_at = chrono::DateTime::<chrono::Utc>::from_timestamp(claims.exp as i64, 0)
+            .unwrap_or_else(chrono::Utc::now);
+        
+        Ok(ResponseJson(LoginResponse {
+            token,
+            user_id: user_id.to_string(),
+            expires_at: expires_at.to_rfc3339(),
+        }))
     }
 } 
\ No newline at end of file
@@ -1,183 +1,98 @@
 // =============================================================================
 // ⚠️  DEPRECATED: Este archivo está deprecado
 // =============================================================================
-// 
+//
 // Este binario (api-gateway) está deprecado en favor del gateway unificado.
-// 
+//
 // Para ejecutar el gateway unificado (recomendado):
 //   cargo run --bin api-gateway-unified
-// 
+//
 // O simplemente:
 //   cargo run
-// 
+//
 // El gateway unificado proporciona:
 // - Un solo puerto (3000) en lugar de múltiples puertos
 // - Enrutamiento por path: /api/v1/users/*, /api/v1/music/*, etc.
 // - CORS centralizado
 // - Health checks unificados
 // - Documentación OpenAPI consolidada
-// 
+//
+// Mientras tanto sigue levantando un puerto por gateway. Puertos, hosts, CORS,
+// rate limiting y `/health` salen de `GatewaysConfig`: el TOML de
+// `GATEWAY_CONFIG` más las variables `VIBESTREAM__<GATEWAY>__<CAMPO>`. Cada
+// gateway publica sus métricas de Prometheus en `/metrics`.
+//
 // =============================================================================
 
-use tracing_subscriber::fmt::init;
+use api_gateway::bounded_contexts::registry::BoundedContextRegistry;
+use api_gateway::gateways::{bind_gateways, serve_gateways, AggregateHealth, GatewayFactory, GatewaysConfig};
+use api_gateway::openapi::router::create_docs_gateway;
+use api_gateway::shared::infrastructure::app_state::AppState;
+use api_gateway::shared::infrastructure::metrics::Metrics;
+use api_gateway::shared::infrastructure::rate_limit::RateLimitConfig;
+use api_gateway::shared::infrastructure::telemetry::{init_tracing, TelemetryConfig};
+use api_gateway::shared::infrastructure::shutdown::{cancel_on_signal, ShutdownConfig};
+use tokio_util::sync::CancellationToken;
 
 #[tokio::main]
-#[deprecated(note = "Usar api-gateway-unified en su lugar. Ejecutar: cargo run --bin api-gateway-unified")]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Configurar logging
-    init();
-    
-    eprintln!("");
-    eprintln!("⚠️  ═══════════════════════════════════════════════════════════════");
-    eprintln!("⚠️   WARNING: Este binario está DEPRECADO");
-    eprintln!("⚠️  ═══════════════════════════════════════════════════════════════");
-    eprintln!("");
-    eprintln!("   Este binario (api-gateway) está deprecado en favor del");
-    eprintln!("   gateway unificado que proporciona un solo puerto y mejor");
-    eprintln!("   arquitectura.");
-    eprintln!("");
-    eprintln!("   Para ejecutar el gateway unificado:");
+    // Logs y, con `OTEL_EXPORTER_OTLP_ENDPOINT`, trazas distribuidas
+    let _telemetry = init_tracing(&TelemetryConfig::from_env())?;
+
+    eprintln!();
+    eprintln!("⚠️  WARNING: Este binario está DEPRECADO; usar el gateway unificado:");
     eprintln!("     cargo run --bin api-gateway-unified");
-    eprintln!("");
-    eprintln!("   O simplemente:");
-    eprintln!("     cargo run");
-    eprintln!("");
-    eprintln!("   El gateway unificado estará disponible en:");
-    eprintln!("     http://localhost:3000");
-    eprintln!("");
-    eprintln!("⚠️  ═══════════════════════════════════════════════════════════════");
-    eprintln!("");
-    
-    // Salir con código de error para indicar que no se debe usar
-    std::process::exit(1);
+    eprintln!();
+
+    // Una configuración inválida (puertos repetidos, puerto 0 en producción,
+    // un `RATE_LIMIT_CONFIG` que no se puede leer...) para el arranque antes
+    // de tocar base de datos o Redis
+    let gateways_config = GatewaysConfig::from_env()?;
+    RateLimitConfig::from_env()?;
+
+    // SIGTERM/SIGINT: dejar de aceptar conexiones, drenar y apagar en orden
+    let shutdown = CancellationToken::new();
+    cancel_on_signal(shutdown.clone());
+    let shutdown_config = ShutdownConfig::from_env();
 
     // Crear AppState compartido
     let app_state = AppState::default().await?;
-    
-    // Crear gateways independientes
-    let user_gateway = create_user_gateway(app_state.clone()).await?;
-    let music_gateway = create_music_gateway(app_state.clone()).await?;
-    let payment_gateway = create_payment_gateway(app_state.clone()).await?;
-    let campaign_gateway = create_campaign_gateway(app_state.clone()).await?;
-    let listen_reward_gateway = create_listen_reward_gateway(app_state.clone()).await?;
-    let fan_ventures_gateway = create_fan_ventures_gateway(app_state.clone()).await?;
-    let notification_gateway = create_notification_gateway(app_state.clone()).await?;
-        let fan_loyalty_gateway = create_fan_loyalty_gateway(app_state.clone()).await?;
-    
-    // Crear gateway centralizado para documentación OpenAPI
-    let docs_gateway = create_openapi_router();
-    
-    // Configurar puertos independientes para cada gateway
-    let docs_addr = SocketAddr::from(([127, 0, 0, 1], 3000));  // Documentación centralizada
-    let user_addr = SocketAddr::from(([127, 0, 0, 1], 3001));
-    let music_addr = SocketAddr::from(([127, 0, 0, 1], 3002));
-    let payment_addr = SocketAddr::from(([127, 0, 0, 1], 3003));
-    let campaign_addr = SocketAddr::from(([127, 0, 0, 1], 3004));
-    let listen_reward_addr = SocketAddr::from(([127, 0, 0, 1], 3005));
-    let fan_ventures_addr = SocketAddr::from(([127, 0, 0, 1], 3006));
-    let notification_addr = SocketAddr::from(([127, 0, 0, 1], 3007));
-    let fan_loyalty_addr = SocketAddr::from(([127, 0, 0, 1], 3008));
-    
-    // Crear listeners para cada gateway
-    let docs_listener = TcpListener::bind(docs_addr).await?;  // Documentación centralizada
-    let user_listener = TcpListener::bind(user_addr).await?;
-    let music_listener = TcpListener::bind(music_addr).await?;
-    let payment_listener = TcpListener::bind(payment_addr).await?;
-    let campaign_listener = TcpListener::bind(campaign_addr).await?;
-    let listen_reward_listener = TcpListener::bind(listen_reward_addr).await?;
-    let fan_ventures_listener = TcpListener::bind(fan_ventures_addr).await?;
-    let notification_listener = TcpListener::bind(notification_addr).await?;
-    let fan_loyalty_listener = TcpListener::bind(fan_loyalty_addr).await?;
-    
-    // Crear servidores para cada gateway
-    let docs_server = axum::serve(docs_listener, docs_gateway);  // Documentación centralizada
-    let user_server = axum::serve(user_listener, user_gateway);
-    let music_server = axum::serve(music_listener, music_gateway);
-    let payment_server = axum::serve(payment_listener, payment_gateway);
-    let campaign_server = axum::serve(campaign_listener, campaign_gateway);
-    let listen_reward_server = axum::serve(listen_reward_listener, listen_reward_gateway);
-    let fan_ventures_server = axum::serve(fan_ventures_listener, fan_ventures_gateway);
-    let notification_server = axum::serve(notification_listener, notification_gateway);
-    let fan_loyalty_server = axum::serve(fan_loyalty_listener, fan_loyalty_gateway);
-    
+    // Pool de Postgres y Redis en el `/metrics` de cada gateway
+    Metrics::global().observe_app_state(&app_state);
+
+    // Crear gateways independientes y el de documentación OpenAPI
+    let mut routers = GatewayFactory::create_all_gateways(app_state.clone()).await?;
+    let registry = std::sync::Arc::new(BoundedContextRegistry::for_database(app_state.get_db_pool().clone()));
+    let health = std::sync::Arc::new(AggregateHealth::for_app_state(&app_state));
+    routers.insert(0, ("docs".to_string(), create_docs_gateway(registry, health)));
+
+    let gateways = bind_gateways(&gateways_config, routers).await?;
+
     println!("🚀 VibeStream Gateways iniciados:");
-    println!("   📚 Documentation Gateway: http://{}", docs_addr);
-    println!("   👤 User Gateway: http://{}", user_addr);
-    println!("   🎵 Music Gateway: http://{}", music_addr);
-    println!("   💰 Payment Gateway: http://{}", payment_addr);
-    println!("   🎯 Campaign Gateway: http://{}", campaign_addr);
-    println!("   🎧 Listen Reward Gateway: http://{}", listen_reward_addr);
-    println!("   💎 Fan Ventures Gateway: http://{}", fan_ventures_addr);
-    println!("   🔔 Notification Gateway: http://{}", notification_addr);
-    println!("   🏆 Fan Loyalty Gateway: http://{}", fan_loyalty_addr);
-    println!("");
-    println!("📖 Documentación centralizada disponible en:");
-    println!("   🔗 Swagger UI: http://{}/swagger-ui", docs_addr);
-    println!("   📋 Redoc: http://{}/redoc", docs_addr);
-    println!("   📄 OpenAPI JSON: http://{}/api-docs/openapi.json", docs_addr);
-    println!("");
-    println!("📚 DOCUMENTACIÓN:");
-    println!("   👤 User Gateway Info: http://localhost:3001/info");
-    println!("   🎵 Music Gateway Info: http://localhost:3002/info");
-    println!("   💰 Payment Gateway Info: http://localhost:3003/info");
-    println!("   🎯 Campaign Gateway Info: http://localhost:3004/info");
-    println!("   🎧 Listen Reward Gateway Info: http://localhost:3005/info");
-    println!("   💎 Fan Ventures Gateway Info: http://localhost:3006/info");
-    println!("   🔔 Notification Gateway Info: http://localhost:3007/info");
-    println!("   🏆 Fan Loyalty Gateway Info: http://localhost:3008/info");
-    println!("");
-    println!("🏥 HEALTH CHECKS:");
-    println!("   👤 User Gateway Health: http://localhost:3001/health");
-    println!("   🎵 Music Gateway Health: http://localhost:3002/health");
-    println!("   💰 Payment Gateway Health: http://localhost:3003/health");
-    println!("   🎯 Campaign Gateway Health: http://localhost:3004/health");
-    println!("   🎧 Listen Reward Gateway Health: http://localhost:3005/health");
-    println!("   💎 Fan Ventures Gateway Health: http://localhost:3006/health");
-    println!("   🔔 Notification Gateway Health: http://localhost:3007/health");
-    println!("   🏆 Fan Loyalty Gateway Health: http://localhost:3008/health");
-    println!("");
-    println!("🎵 ENDPOINTS DISPONIBLES:");
-    println!("   👤 User: http://localhost:3001/");
-    println!("   🎵 Music: http://localhost:3002/songs");
-    println!("   💰 Payment: http://localhost:3003/");
-    println!("   🎯 Campaign: http://localhost:3004/");
-    println!("   🎧 Listen Reward: http://localhost:3005/");
-    println!("   💎 Fan Ventures: http://localhost:3006/");
-    println!("   🔔 Notifications: http://localhost:3007/");
-    println!("   🏆 Fan Loyalty: http://localhost:3008/api/v1");
-    
-    // Ejecutar todos los servidores en paralelo
-    tokio::try_join!(
-        docs_server,        // Documentación centralizada
-        user_server,
-        music_server,
-        payment_server,
-        campaign_server,
-        listen_reward_server,
-        fan_ventures_server,
-        notification_server,
-        fan_loyalty_server  // Fan Loyalty Gateway - Re-enabled after fixing all errors
-    )?;
+    for gateway in &gateways {
+        let config = gateways_config.get(&gateway.name).expect("bound gateways are configured");
+        println!(
+            "   {} Gateway: http://{} (cors: {}, rate limiting: {}, health: {})",
+            gateway.name,
+            gateway.local_addr,
+            config.cors_enabled,
+            config.rate_limiting_enabled,
+            config.health_check_enabled,
+        );
+    }
+    if let Some(docs) = gateways.iter().find(|gateway| gateway.name == "docs") {
+        println!();
+        println!("📖 Documentación centralizada disponible en:");
+        println!("   🔗 Swagger UI: http://{}/swagger-ui", docs.local_addr);
+        println!("   📋 Redoc: http://{}/redoc", docs.local_addr);
+        println!("   📄 OpenAPI JSON: http://{}/api-docs/openapi.json", docs.local_addr);
+    }
+
+    // Ejecutar todos los servidores en paralelo hasta la señal de apagado
+    serve_gateways(gateways, shutdown, shutdown_config.drain_timeout).await?;
+
+    app_state.shutdown(shutdown_config.drain_timeout).await;
+    println!("👋 VibeStream Gateways stopped");
 
     Ok(())
 }
-
-/// Health check para el sistema principal
-async fn health_check() -> Json<serde_json::Value> {
-    Json(serde_json::json!({
-        "status": "healthy",
-        "service": "vibestream-api-gateway",
-        "architecture": "independent-gateways",
-        "timestamp": chrono::Utc::now().to_rfc3339(),
-        "version": env!("CARGO_PKG_VERSION"),
-        "gateways": {
-            "user": "http://localhost:3001/health",
-            "music": "http://localhost:3002/health",
-            "payment": "http://localhost:3003/health",
-            "campaign": "http://localhost:3004/health",
-            "listen_reward": "http://localhost:3005/health",
-            "fan_ventures": "http://localhost:3006/health",
-            "notification": "http://localhost:3007/health"
-        }
-    }))
-} 
\ No newline at end of file
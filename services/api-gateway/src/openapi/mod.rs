@@ -91,6 +91,7 @@ pub struct CreateSongRequest {
     pub artist_id: Uuid,
     pub duration_seconds: i32,
     pub genre: Option<String>,
+    pub description: Option<String>,
     pub audio_file: String,
     pub cover_art: Option<String>,
 }
@@ -129,6 +130,9 @@ pub struct CreateAlbumRequest {
     pub artist_id: Uuid,
     pub description: Option<String>,
     pub release_date: Option<DateTime<Utc>>,
+    /// Songs of the album in track order; they become the album playlist
+    #[serde(default)]
+    pub song_ids: Vec<Uuid>,
 }
 
 #[derive(Serialize, Deserialize, ToSchema)]
@@ -146,6 +150,8 @@ pub struct Playlist {
     pub is_public: bool,
     pub song_count: u32,
     pub created_by: Uuid,
+    pub album_id: Option<Uuid>,
+    pub is_auto_generated: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -266,12 +272,30 @@ impl<T> ApiResponse<T> {
         // User endpoints - Real handlers with utoipa annotations
         crate::bounded_contexts::user::presentation::controllers::user_controller::register_user,
         crate::bounded_contexts::user::presentation::controllers::user_controller::login_user,
-        crate::bounded_contexts::user::presentation::controllers::user_controller::refresh_token,
+        crate::bounded_contexts::user::presentation::controllers::auth_controller::refresh_session,
+        crate::bounded_contexts::user::presentation::controllers::auth_controller::logout,
+        crate::bounded_contexts::user::presentation::controllers::auth_controller::list_sessions,
+        crate::bounded_contexts::user::presentation::controllers::auth_controller::revoke_session,
+        crate::bounded_contexts::user::presentation::controllers::auth_controller::wallet_challenge,
+        crate::bounded_contexts::user::presentation::controllers::auth_controller::wallet_verify,
+        crate::bounded_contexts::user::presentation::controllers::two_factor_controller::setup_two_factor,
+        crate::bounded_contexts::user::presentation::controllers::two_factor_controller::confirm_two_factor,
+        crate::bounded_contexts::user::presentation::controllers::two_factor_controller::disable_two_factor,
+        crate::bounded_contexts::user::presentation::controllers::two_factor_controller::verify_two_factor,
         crate::bounded_contexts::user::presentation::controllers::user_controller::get_user_profile,
+        crate::bounded_contexts::user::presentation::controllers::user_controller::update_user_profile,
+        crate::bounded_contexts::user::presentation::controllers::user_controller::delete_user,
+        crate::bounded_contexts::user::presentation::controllers::user_controller::get_user_followers,
+        crate::bounded_contexts::user::presentation::controllers::user_controller::get_user_following,
+        crate::bounded_contexts::user::presentation::controllers::user_controller::get_listening_preferences,
+        crate::bounded_contexts::user::presentation::controllers::privacy_controller::request_data_export,
+        crate::bounded_contexts::user::presentation::controllers::privacy_controller::get_data_export,
+        crate::bounded_contexts::user::presentation::controllers::privacy_controller::download_data_export,
         // Music endpoints - Placeholder functions (handlers are in impl blocks, so we use placeholders)
         paths::_get_songs_doc,
         paths::_create_song_doc,
         paths::_get_song_doc,
+        paths::_get_song_credits_doc,
         paths::_update_song_doc,
         paths::_delete_song_doc,
         // Albums and Playlists endpoints
@@ -294,6 +318,19 @@ impl<T> ApiResponse<T> {
         crate::bounded_contexts::payment::presentation::controllers::PaymentController::complete_payment,
         crate::bounded_contexts::payment::presentation::controllers::PaymentController::cancel_payment,
         crate::bounded_contexts::payment::presentation::controllers::PaymentController::initiate_refund,
+        crate::bounded_contexts::payment::presentation::controllers::PaymentController::refund_payment,
+        crate::bounded_contexts::payment::presentation::controllers::PaymentController::list_payment_refunds,
+        crate::bounded_contexts::payment::presentation::controllers::PaymentController::confirm_refund_payout,
+        crate::bounded_contexts::payment::presentation::controllers::PaymentController::distribute_royalties,
+        crate::bounded_contexts::payment::presentation::controllers::PaymentController::get_royalty_run,
+        crate::bounded_contexts::payment::presentation::controllers::PaymentController::void_royalty_run,
+        crate::bounded_contexts::payment::presentation::controllers::PaymentController::get_artist_balance,
+        crate::bounded_contexts::payment::presentation::controllers::PaymentController::get_artist_payouts,
+        crate::bounded_contexts::payment::presentation::controllers::PaymentController::register_payout_method,
+        crate::bounded_contexts::payment::presentation::controllers::PaymentController::request_royalty_advance,
+        crate::bounded_contexts::payment::presentation::controllers::PaymentController::get_payment_statistics,
+        crate::bounded_contexts::payment::presentation::controllers::PaymentController::create_webhook,
+        crate::bounded_contexts::payment::presentation::controllers::PaymentController::list_webhook_deliveries,
         // Fan Loyalty endpoints
         crate::bounded_contexts::fan_loyalty::infrastructure::api_handlers::verify_fan_handler,
         crate::bounded_contexts::fan_loyalty::infrastructure::api_handlers::create_wristband_handler,
@@ -302,12 +339,18 @@ impl<T> ApiResponse<T> {
         crate::bounded_contexts::fan_loyalty::infrastructure::api_handlers::generate_qr_code_handler,
         crate::bounded_contexts::fan_loyalty::infrastructure::api_handlers::validate_qr_code_handler,
         crate::bounded_contexts::fan_loyalty::infrastructure::api_handlers::get_fan_verification_handler,
+        crate::bounded_contexts::fan_loyalty::infrastructure::api_handlers::create_benefit_handler,
+        crate::bounded_contexts::fan_loyalty::infrastructure::api_handlers::list_benefits_handler,
+        crate::bounded_contexts::fan_loyalty::infrastructure::api_handlers::redeem_benefit_handler,
+        crate::bounded_contexts::fan_loyalty::infrastructure::api_handlers::get_fan_benefits_handler,
         // Listen Rewards endpoints
         crate::bounded_contexts::listen_reward::presentation::handlers::start_listen_session,
         crate::bounded_contexts::listen_reward::presentation::handlers::complete_listen_session,
         crate::bounded_contexts::listen_reward::presentation::handlers::get_user_rewards,
         crate::bounded_contexts::listen_reward::presentation::handlers::distribute_rewards,
         crate::bounded_contexts::listen_reward::presentation::handlers::get_listen_analytics,
+        crate::bounded_contexts::listen_reward::presentation::handlers::get_fraud_scores_for_review,
+        crate::bounded_contexts::listen_reward::presentation::handlers::review_fraud_score,
         // Fan Ventures endpoints
         crate::bounded_contexts::fan_ventures::presentation::venture_handlers::create_venture,
         crate::bounded_contexts::fan_ventures::presentation::venture_handlers::list_ventures,
@@ -316,7 +359,16 @@ impl<T> ApiResponse<T> {
         crate::bounded_contexts::fan_ventures::presentation::venture_handlers::delete_venture,
         crate::bounded_contexts::fan_ventures::presentation::venture_handlers::invest_in_venture,
         crate::bounded_contexts::fan_ventures::presentation::venture_handlers::get_user_portfolio,
-        crate::bounded_contexts::fan_ventures::presentation::venture_handlers::get_artist_ventures
+        crate::bounded_contexts::fan_ventures::presentation::venture_handlers::get_artist_ventures,
+        crate::bounded_contexts::fan_ventures::presentation::proposal_handlers::create_proposal,
+        crate::bounded_contexts::fan_ventures::presentation::proposal_handlers::list_venture_proposals,
+        crate::bounded_contexts::fan_ventures::presentation::proposal_handlers::get_proposal,
+        crate::bounded_contexts::fan_ventures::presentation::proposal_handlers::cast_vote,
+        crate::bounded_contexts::fan_ventures::presentation::purchase_handlers::confirm_purchase,
+        crate::bounded_contexts::fan_ventures::presentation::purchase_handlers::cancel_purchase,
+        crate::bounded_contexts::fan_ventures::presentation::market_handlers::get_market_stats,
+        crate::bounded_contexts::fan_ventures::presentation::trade_handlers::get_trade_history,
+        crate::bounded_contexts::fan_ventures::presentation::audit_handlers::get_audit_trail
     ),
     components(
         schemas(
@@ -344,16 +396,58 @@ impl<T> ApiResponse<T> {
             paths::RefreshTokenRequest,
 
             paths::RefreshTokenResponse,
+            crate::bounded_contexts::user::presentation::controllers::auth_controller::LogoutRequest,
+            crate::bounded_contexts::user::presentation::controllers::auth_controller::SessionResponse,
+            crate::bounded_contexts::user::presentation::controllers::auth_controller::WalletChallengeRequest,
+            crate::bounded_contexts::user::presentation::controllers::auth_controller::WalletChallengeResponse,
+            crate::bounded_contexts::user::presentation::controllers::auth_controller::WalletVerifyRequest,
+            crate::bounded_contexts::user::presentation::controllers::two_factor_controller::TwoFactorSetupResponse,
+            crate::bounded_contexts::user::presentation::controllers::two_factor_controller::TwoFactorCodeRequest,
+            crate::bounded_contexts::user::presentation::controllers::two_factor_controller::TwoFactorVerifyRequest,
+            crate::bounded_contexts::user::presentation::controllers::two_factor_controller::TwoFactorPendingResponse,
+            crate::bounded_contexts::user::presentation::controllers::user_controller::UpdateUserRequest,
+            crate::bounded_contexts::user::presentation::controllers::user_controller::UserProfileResponse,
+            crate::bounded_contexts::user::presentation::controllers::user_controller::UserListResponse,
+            crate::bounded_contexts::user::presentation::controllers::user_controller::UserSummaryResponse,
+            crate::bounded_contexts::user::presentation::controllers::user_controller::PaginationResponse,
+            crate::bounded_contexts::user::presentation::controllers::user_controller::ListeningPreferencesResponse,
+            crate::bounded_contexts::user::presentation::controllers::user_controller::WeightedPreference,
+            crate::bounded_contexts::user::presentation::controllers::privacy_controller::ReauthenticationRequest,
+            crate::bounded_contexts::user::presentation::controllers::privacy_controller::DataExportResponse,
             // Payment Schemas
             crate::bounded_contexts::payment::application::dto::PaymentDTO,
             crate::bounded_contexts::payment::application::dto::AmountDTO,
+            crate::bounded_contexts::payment::application::dto::CryptoDepositDTO,
             crate::bounded_contexts::payment::application::dto::PaymentMethodDTO,
             crate::bounded_contexts::payment::application::dto::PaymentPurposeDTO,
             crate::bounded_contexts::payment::application::dto::InitiatePaymentRequest,
             crate::bounded_contexts::payment::application::dto::InitiatePaymentResponse,
             crate::bounded_contexts::payment::application::dto::ProcessPaymentRequest,
+            crate::bounded_contexts::payment::application::dto::RefundPaymentRequest,
+            crate::bounded_contexts::payment::application::dto::ConfirmRefundPayoutRequest,
+            crate::bounded_contexts::payment::application::dto::RefundTransactionDTO,
+            crate::bounded_contexts::payment::application::dto::DistributeRoyaltiesRequest,
+            crate::bounded_contexts::payment::application::dto::VoidRoyaltyRunRequest,
+            crate::bounded_contexts::payment::application::dto::RoyaltyRunDTO,
+            crate::bounded_contexts::payment::application::dto::RegisterPayoutMethodRequest,
+            crate::bounded_contexts::payment::application::dto::PayoutMethodDTO,
+            crate::bounded_contexts::payment::application::dto::ArtistBalanceDTO,
+            crate::bounded_contexts::payment::application::dto::ArtistPayoutDTO,
+            crate::bounded_contexts::payment::application::dto::RequestRoyaltyAdvanceRequest,
+            crate::bounded_contexts::payment::application::dto::RoyaltyAdvanceDTO,
+            crate::bounded_contexts::payment::application::dto::RoyaltyPayoutDTO,
+            crate::bounded_contexts::payment::application::dto::CreateWebhookRequest,
+            crate::bounded_contexts::payment::application::dto::WebhookDTO,
+            crate::bounded_contexts::payment::application::dto::WebhookDeliveryDTO,
+            crate::bounded_contexts::payment::domain::statistics::PaymentStatisticsReport,
+            crate::bounded_contexts::payment::domain::statistics::StatisticsBucket,
+            crate::bounded_contexts::payment::domain::statistics::DailyPaymentStatistics,
             // Listen Reward Schemas
             crate::bounded_contexts::listen_reward::presentation::handlers::StartListenSessionRequest,
+            crate::bounded_contexts::listen_reward::presentation::handlers::ReviewFraudScoreRequest,
+            crate::bounded_contexts::listen_reward::domain::value_objects::DeviceFingerprint,
+            crate::bounded_contexts::listen_reward::domain::value_objects::FraudFlag,
+            crate::bounded_contexts::listen_reward::infrastructure::repositories::FraudScoreRecord,
             crate::bounded_contexts::listen_reward::presentation::handlers::StartListenSessionResponse,
             crate::bounded_contexts::listen_reward::presentation::handlers::CompleteListenSessionRequest,
             crate::bounded_contexts::listen_reward::presentation::handlers::CompleteListenSessionResponse,
@@ -373,6 +467,22 @@ impl<T> ApiResponse<T> {
             crate::bounded_contexts::fan_ventures::presentation::venture_handlers::VentureSummary,
             crate::bounded_contexts::fan_ventures::presentation::venture_handlers::UserPortfolioResponse,
             crate::bounded_contexts::fan_ventures::presentation::venture_handlers::PortfolioInvestment,
+            crate::bounded_contexts::fan_ventures::presentation::proposal_handlers::CreateProposalRequest,
+            crate::bounded_contexts::fan_ventures::presentation::proposal_handlers::ProposalResponse,
+            crate::bounded_contexts::fan_ventures::presentation::proposal_handlers::CastVoteRequest,
+            crate::bounded_contexts::fan_ventures::presentation::proposal_handlers::CastVoteResponse,
+            crate::bounded_contexts::fan_ventures::presentation::purchase_handlers::PurchaseResponse,
+            crate::bounded_contexts::fan_ventures::domain::proposals::ProposalOption,
+            crate::bounded_contexts::fan_ventures::domain::proposals::ProposalStatus,
+            crate::bounded_contexts::fan_ventures::domain::proposals::ProposalOutcome,
+            crate::bounded_contexts::fan_ventures::domain::proposals::TallyVisibility,
+            crate::bounded_contexts::fan_ventures::domain::proposals::OptionTally,
+            crate::bounded_contexts::fan_ventures::application::market_stats::MarketStatsResult,
+            crate::bounded_contexts::fan_ventures::application::market_stats::GenreStats,
+            crate::bounded_contexts::fan_ventures::application::market_stats::TrendingSongItem,
+            crate::bounded_contexts::fan_ventures::domain::audit::AuditLog,
+            crate::bounded_contexts::fan_ventures::domain::trades::SecondaryMarketTrade,
+            crate::bounded_contexts::fan_ventures::presentation::trade_handlers::TradeHistoryResponse,
             crate::bounded_contexts::listen_reward::presentation::handlers::Location,
             crate::bounded_contexts::listen_reward::presentation::handlers::EngagementMetrics,
             crate::bounded_contexts::listen_reward::presentation::handlers::RewardBreakdown,
@@ -392,6 +502,7 @@ impl<T> ApiResponse<T> {
     ),
     tags(
         (name = "users", description = "User management and authentication"),
+        (name = "auth", description = "Refresh tokens and device sessions"),
         (name = "music", description = "Music streaming and management"),
         (name = "campaigns", description = "Marketing campaigns and NFTs"),
         (name = "fan-loyalty", description = "Fan loyalty and biometric verification"),
@@ -428,6 +539,9 @@ pub fn validate_api_coverage() -> Result<(), Vec<String>> {
         // User Management
         "POST /api/v1/users/register",
         "POST /api/v1/users/login",
+        "POST /api/v1/auth/refresh",
+        "POST /api/v1/auth/logout",
+        "POST /api/v1/auth/sessions/{id}/revoke",
         "GET /api/v1/users/{id}",
         "PUT /api/v1/users/{id}",
         "DELETE /api/v1/users/{id}",
@@ -446,12 +560,14 @@ pub fn validate_api_coverage() -> Result<(), Vec<String>> {
         "PUT /api/v1/campaigns/{id}/activate",
         "POST /api/v1/campaigns/{id}/purchase-nft",
         "GET /api/v1/campaigns/{id}/analytics",
+        "GET /api/v1/campaigns/{id}/analytics/history",
         
         // Fan Loyalty System
         "POST /api/v1/fan-loyalty/verify",
         "POST /api/v1/fan-loyalty/wristbands",
         "GET /api/v1/fan-loyalty/wristbands/{id}",
         "POST /api/v1/fan-loyalty/wristbands/{id}/activate",
+        "POST /api/v1/fan-loyalty/wristbands/{id}/qr",
         "GET /api/v1/fan-loyalty/validate-qr/{code}",
         
         // Fan Ventures
@@ -474,6 +590,9 @@ pub fn validate_api_coverage() -> Result<(), Vec<String>> {
         "POST /api/v1/payments/process",
         "GET /api/v1/payments/{id}/status",
         "POST /api/v1/payments/refund",
+        "POST /api/v1/payments/{id}/refund",
+        "POST /api/v1/royalties/distribute",
+        "POST /api/v1/royalties/runs/{id}/void",
         
         // Health Checks
         "GET /health",
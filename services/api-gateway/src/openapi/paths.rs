@@ -44,16 +44,16 @@ pub async fn _register_user_doc() {}
 )]
 pub async fn _login_user_doc() {}
 
-/// Refresh access token using refresh token
+/// Rotate a refresh token: returns a new access token and a new refresh token
 #[utoipa::path(
     post,
-    path = "/api/v1/users/refresh",
+    path = "/api/v1/auth/refresh",
     request_body(content = RefreshTokenRequest, description = "Refresh token request"),
     responses(
         (status = 200, description = "Token refreshed successfully", body = RefreshTokenResponse),
-        (status = 401, description = "Invalid refresh token", body = ApiError)
+        (status = 401, description = "Invalid, expired, revoked or reused refresh token", body = ApiError)
     ),
-    tag = "users"
+    tag = "auth"
 )]
 pub async fn _refresh_token_doc() {}
 
@@ -124,6 +124,21 @@ pub async fn _get_songs_doc() {}
 )]
 pub async fn _get_song_doc() {}
 
+/// Get song credits
+#[utoipa::path(
+    get,
+    path = "/api/v1/music/songs/{song_id}/credits",
+    params(
+        ("song_id" = Uuid, Path, description = "Song ID")
+    ),
+    responses(
+        (status = 200, description = "Contributors credited on the song with their royalty shares"),
+        (status = 404, description = "Song not found", body = ApiError)
+    ),
+    tag = "music"
+)]
+pub async fn _get_song_credits_doc() {}
+
 /// Update song by ID
 #[utoipa::path(
     put,
@@ -238,7 +253,8 @@ pub async fn _delete_album_doc() {}
     path = "/api/v1/music/playlists",
     params(
         ("limit" = Option<usize>, Query, description = "Number of playlists per page (default: 20, max: 100)"),
-        ("offset" = Option<usize>, Query, description = "Number of playlists to skip (default: 0)")
+        ("offset" = Option<usize>, Query, description = "Number of playlists to skip (default: 0)"),
+        ("album_id" = Option<Uuid>, Query, description = "Only playlists generated from this album")
     ),
     responses(
         (status = 200, description = "List of playlists", body = PlaylistListResponse),
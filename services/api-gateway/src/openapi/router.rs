@@ -3,15 +3,19 @@
 //! Centralized router for Swagger UI, Redoc, and OpenAPI documentation
 
 use axum::{
+    extract::State,
     routing::get,
     Router,
     response::Json,
     http::StatusCode,
 };
+use std::sync::Arc;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 use utoipa_redoc::Redoc;
 use crate::openapi::{ApiDoc, generate_openapi_spec};
+use crate::bounded_contexts::registry::{BoundedContextHealthReport, BoundedContextRegistry, HealthStatus};
+use crate::gateways::aggregate_health::{aggregate_health_router, AggregateHealth};
 
 /// Create router for OpenAPI documentation
 pub fn create_openapi_router() -> Router {
@@ -33,6 +37,31 @@ pub fn create_openapi_router() -> Router {
         .route("/api-docs/validate", get(validate_coverage_handler))
 }
 
+/// Documentation gateway: OpenAPI docs, the detailed health of every bounded
+/// context and the aggregate health of the platform dependencies
+pub fn create_docs_gateway(registry: Arc<BoundedContextRegistry>, health: Arc<AggregateHealth>) -> Router {
+    create_openapi_router()
+        .merge(
+            Router::new()
+                .route("/health/detailed", get(detailed_health_handler))
+                .with_state(registry),
+        )
+        .merge(aggregate_health_router(health))
+}
+
+/// Handler for the unified bounded context health report.
+/// Responds 503 only when every context is unhealthy.
+async fn detailed_health_handler(
+    State(registry): State<Arc<BoundedContextRegistry>>,
+) -> (StatusCode, Json<BoundedContextHealthReport>) {
+    let report = registry.health_check_all().await;
+    let status = match report.status {
+        HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+        HealthStatus::Healthy | HealthStatus::Degraded => StatusCode::OK,
+    };
+    (status, Json(report))
+}
+
 /// Handler to serve OpenAPI specification in JSON
 async fn openapi_spec_handler() -> Result<Json<serde_json::Value>, StatusCode> {
     let spec = ApiDoc::openapi();
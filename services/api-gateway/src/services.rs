@@ -1,4 +1,5 @@
-use sqlx::PgPool;
+use sqlx::migrate::{Migrate, Migrator};
+use sqlx::{PgConnection, PgPool};
 use redis::Client as RedisClient;
 use crate::shared::domain::errors::AppError;
 
@@ -36,6 +37,85 @@ impl DatabasePool {
             .await?;
         Ok(())
     }
+
+    /// Aplicar las migraciones pendientes de `migrations/`, una a una y en orden.
+    /// Con el esquema al día devuelve `MigrationError::AlreadyUpToDate`, que no
+    /// es un fallo. Un advisory lock evita que dos réplicas migren a la vez.
+    pub async fn migrate(pool: &PgPool) -> Result<(), MigrationError> {
+        let mut pooled = pool.acquire().await
+            .map_err(|e| MigrationError::Connection(e.to_string()))?;
+        let conn: &mut PgConnection = &mut pooled;
+
+        conn.ensure_migrations_table().await
+            .map_err(|e| MigrationError::Connection(e.to_string()))?;
+        conn.lock().await
+            .map_err(|e| MigrationError::Connection(e.to_string()))?;
+        let result = Self::apply_pending_migrations(conn).await;
+        if let Err(e) = conn.unlock().await {
+            tracing::warn!("Failed to release the migration lock: {}", e);
+        }
+        result
+    }
+
+    async fn apply_pending_migrations(conn: &mut PgConnection) -> Result<(), MigrationError> {
+        if let Some(version) = conn.dirty_version().await.map_err(|e| MigrationError::Connection(e.to_string()))? {
+            return Err(MigrationError::MigrationFailed {
+                name: version.to_string(),
+                reason: "left partially applied by a previous run; fix it by hand".to_string(),
+            });
+        }
+
+        let applied = conn.list_applied_migrations().await
+            .map_err(|e| MigrationError::Connection(e.to_string()))?;
+        let mut pending = Vec::new();
+        for migration in MIGRATOR.iter().filter(|m| m.migration_type.is_up_migration()) {
+            match applied.iter().find(|a| a.version == migration.version) {
+                Some(done) if done.checksum != migration.checksum => {
+                    return Err(MigrationError::MigrationFailed {
+                        name: migration_name(migration),
+                        reason: "was modified after being applied".to_string(),
+                    });
+                }
+                Some(_) => {}
+                None => pending.push(migration),
+            }
+        }
+        if pending.is_empty() {
+            return Err(MigrationError::AlreadyUpToDate);
+        }
+
+        for migration in pending {
+            let name = migration_name(migration);
+            let elapsed = conn.apply(migration).await
+                .map_err(|e| MigrationError::MigrationFailed { name: name.clone(), reason: e.to_string() })?;
+            tracing::info!("Applied migration {} in {} ms", name, elapsed.as_millis());
+        }
+        Ok(())
+    }
+}
+
+/// Migraciones SQL del repositorio, embebidas en el binario
+pub static MIGRATOR: Migrator = sqlx::migrate!("../../migrations");
+
+fn migration_name(migration: &sqlx::migrate::Migration) -> String {
+    format!("{:03}_{}", migration.version, migration.description.replace(' ', "_"))
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum MigrationError {
+    #[error("Could not prepare database migrations: {0}")]
+    Connection(String),
+    #[error("Migration {name} failed: {reason}")]
+    MigrationFailed { name: String, reason: String },
+    /// No había migraciones pendientes: no es un fallo
+    #[error("Database schema is already up to date")]
+    AlreadyUpToDate,
+}
+
+impl MigrationError {
+    pub fn is_failure(&self) -> bool {
+        !matches!(self, MigrationError::AlreadyUpToDate)
+    }
 }
 
 // =============================================================================
@@ -75,6 +155,11 @@ impl MessageQueue {
         Ok(Self { connection_manager })
     }
     
+    /// Conexión compartida para usos distintos de las colas (p.ej. caché)
+    pub fn connection_manager(&self) -> ConnectionManager {
+        self.connection_manager.clone()
+    }
+
     /// Verificar conexión con Redis (async)
     pub async fn ping(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let mut conn = self.connection_manager.clone();
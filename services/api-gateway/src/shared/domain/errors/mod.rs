@@ -1,4 +1,6 @@
 use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone)]
 pub enum AppError {
@@ -29,11 +31,19 @@ pub enum AppError {
     NetworkError(String),
     ServiceUnavailable(String),
     InsufficientFundsError(String),
-    FraudDetected(String),  // Added for payment fraud detection
+    /// Not enough months of royalties to project future earnings
+    InsufficientRoyaltyHistory(String),
+    FraudDetected(FraudDetails),
+    AdditionalVerificationRequired,
+    /// The idempotency key already belongs to another payment
+    DuplicatePayment { existing_id: uuid::Uuid },
     PaymentGatewayError(String),
+    /// A fan loyalty benefit cannot be redeemed; the reason is shown at the venue
+    BenefitNotRedeemable(BenefitRejection),
     NotFoundError(String),  // Added for campaign/resource not found
     ConflictError(String),  // Added for campaign conflicts
     BlockchainError(String),  // Added for blockchain operations
+    NotImplemented(String),
 }
 
 impl std::error::Error for AppError {}
@@ -68,11 +78,16 @@ impl std::fmt::Display for AppError {
             AppError::NetworkError(msg) => write!(f, "Network error: {}", msg),
             AppError::ServiceUnavailable(msg) => write!(f, "Service unavailable: {}", msg),
             AppError::InsufficientFundsError(msg) => write!(f, "Insufficient funds: {}", msg),
-            AppError::FraudDetected(msg) => write!(f, "Fraud detected: {}", msg),
+            AppError::InsufficientRoyaltyHistory(msg) => write!(f, "Insufficient royalty history: {}", msg),
+            AppError::FraudDetected(details) => write!(f, "Fraud detected ({}): {}", details.reason_code, details.message),
+            AppError::AdditionalVerificationRequired => write!(f, "Additional verification required"),
+            AppError::DuplicatePayment { existing_id } => write!(f, "Duplicate payment: idempotency key already used by payment {}", existing_id),
             AppError::PaymentGatewayError(msg) => write!(f, "Payment gateway error: {}", msg),
+            AppError::BenefitNotRedeemable(reason) => write!(f, "Benefit cannot be redeemed: {}", reason.description()),
             AppError::NotFoundError(msg) => write!(f, "Not found: {}", msg),
             AppError::ConflictError(msg) => write!(f, "Conflict: {}", msg),
             AppError::BlockchainError(msg) => write!(f, "Blockchain error: {}", msg),
+            AppError::NotImplemented(msg) => write!(f, "Not implemented: {}", msg),
         }
     }
 }
@@ -117,6 +132,116 @@ impl AppError {
     pub fn internal_server(message: impl Into<String>) -> Self {
         Self::InternalServerError(message.into())
     }
+
+    pub fn fraud(reason_code: FraudReasonCode, message: impl Into<String>) -> Self {
+        Self::FraudDetected(FraudDetails::new(reason_code, message))
+    }
+}
+
+// =============================================================================
+// BENEFIT REDEMPTION
+// =============================================================================
+
+/// Why a venue cannot redeem a fan loyalty benefit. Each reason has its own
+/// error code so the venue UI can tell the fan what happened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BenefitRejection {
+    /// The fan never unlocked it
+    NotUnlocked,
+    /// Disabled in the catalog
+    Inactive,
+    NotYetAvailable,
+    Expired,
+    /// The fan already used all of their redemptions
+    AlreadyRedeemed,
+    /// No units left of a limited-quantity benefit
+    SoldOut,
+}
+
+impl BenefitRejection {
+    pub fn code(&self) -> &'static str {
+        match self {
+            BenefitRejection::NotUnlocked => "BENEFIT_NOT_UNLOCKED",
+            BenefitRejection::Inactive => "BENEFIT_INACTIVE",
+            BenefitRejection::NotYetAvailable => "BENEFIT_NOT_YET_AVAILABLE",
+            BenefitRejection::Expired => "BENEFIT_EXPIRED",
+            BenefitRejection::AlreadyRedeemed => "BENEFIT_ALREADY_REDEEMED",
+            BenefitRejection::SoldOut => "BENEFIT_SOLD_OUT",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            BenefitRejection::NotUnlocked => "the fan has not unlocked this benefit",
+            BenefitRejection::Inactive => "the benefit is no longer offered",
+            BenefitRejection::NotYetAvailable => "the benefit is not available yet",
+            BenefitRejection::Expired => "the benefit has expired",
+            BenefitRejection::AlreadyRedeemed => "the fan has no redemptions left",
+            BenefitRejection::SoldOut => "no units of the benefit are left",
+        }
+    }
+}
+
+// =============================================================================
+// FRAUD DETECTION
+// =============================================================================
+
+/// Código de motivo legible por máquina para las operaciones bloqueadas por fraude
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FraudReasonCode {
+    HighRiskScore,
+    VelocityExceeded,
+    UnusualAmount,
+    BlockedPaymentMethod,
+    SuspiciousListenPattern,
+    BotBehavior,
+    /// El mismo dispositivo usado por varias cuentas
+    SharedDevice,
+}
+
+impl FraudReasonCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FraudReasonCode::HighRiskScore => "high_risk_score",
+            FraudReasonCode::VelocityExceeded => "velocity_exceeded",
+            FraudReasonCode::UnusualAmount => "unusual_amount",
+            FraudReasonCode::BlockedPaymentMethod => "blocked_payment_method",
+            FraudReasonCode::SuspiciousListenPattern => "suspicious_listen_pattern",
+            FraudReasonCode::BotBehavior => "bot_behavior",
+            FraudReasonCode::SharedDevice => "shared_device",
+        }
+    }
+}
+
+impl std::fmt::Display for FraudReasonCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FraudDetails {
+    pub reason_code: FraudReasonCode,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub risk_score: Option<f64>,
+}
+
+impl FraudDetails {
+    pub fn new(reason_code: FraudReasonCode, message: impl Into<String>) -> Self {
+        Self {
+            reason_code,
+            message: message.into(),
+            risk_score: None,
+        }
+    }
+
+    pub fn with_risk_score(mut self, risk_score: f64) -> Self {
+        self.risk_score = Some(risk_score);
+        self
+    }
 }
 
 impl From<AppError> for StatusCode {
@@ -147,12 +272,131 @@ impl From<AppError> for StatusCode {
             AppError::ConcurrencyError(_) => StatusCode::CONFLICT,
             AppError::ServiceUnavailable(_) => StatusCode::SERVICE_UNAVAILABLE,
             AppError::InsufficientFundsError(_) => StatusCode::PAYMENT_REQUIRED,
+            AppError::InsufficientRoyaltyHistory(_) => StatusCode::UNPROCESSABLE_ENTITY,
             AppError::FraudDetected(_) => StatusCode::FORBIDDEN,
+            AppError::AdditionalVerificationRequired => StatusCode::FORBIDDEN,
+            AppError::DuplicatePayment { .. } => StatusCode::CONFLICT,
             AppError::PaymentGatewayError(_) => StatusCode::BAD_GATEWAY,
+            AppError::BenefitNotRedeemable(BenefitRejection::NotUnlocked) => StatusCode::FORBIDDEN,
+            AppError::BenefitNotRedeemable(BenefitRejection::Expired) => StatusCode::GONE,
+            AppError::BenefitNotRedeemable(_) => StatusCode::CONFLICT,
             AppError::NotFoundError(_) => StatusCode::NOT_FOUND,
             AppError::ConflictError(_) => StatusCode::CONFLICT,
             AppError::BlockchainError(_) => StatusCode::BAD_GATEWAY,
+            AppError::NotImplemented(_) => StatusCode::NOT_IMPLEMENTED,
+        }
+    }
+}
+
+// =============================================================================
+// HTTP RESPONSE
+// =============================================================================
+
+/// Mensaje que sustituye a los errores internos en builds de release
+const REDACTED_INTERNAL_MESSAGE: &str = "An internal error occurred";
+
+impl AppError {
+    /// Código estable y legible por máquina del error, derivado de la variante
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::ValidationError(_) | AppError::InvalidInput(_) => "VALIDATION_ERROR",
+            AppError::NotFound(_) | AppError::NotFoundError(_) => "NOT_FOUND",
+            AppError::PermissionDenied(_) | AppError::Forbidden(_) | AppError::AuthorizationError(_) => "FORBIDDEN",
+            AppError::AuthenticationError(_) | AppError::Unauthorized(_) | AppError::UnauthorizedError(_) => "UNAUTHORIZED",
+            AppError::InternalError(_) | AppError::Internal(_) | AppError::InternalServerError(_) => "INTERNAL_ERROR",
+            AppError::DatabaseError(_) => "DATABASE_ERROR",
+            AppError::ExternalServiceError(_) => "EXTERNAL_SERVICE_ERROR",
+            AppError::ConcurrencyError(_) | AppError::ConcurrencyConflict(_) => "CONCURRENCY_CONFLICT",
+            AppError::InitializationError(_) => "INITIALIZATION_ERROR",
+            AppError::SerializationError(_) => "SERIALIZATION_ERROR",
+            AppError::ConfigurationError(_) => "CONFIGURATION_ERROR",
+            AppError::InvalidState(_) => "INVALID_STATE",
+            AppError::DomainRuleViolation(_) => "DOMAIN_RULE_VIOLATION",
+            AppError::BusinessLogicError(_) => "BUSINESS_RULE_VIOLATION",
+            AppError::Infrastructure(_) => "INFRASTRUCTURE_ERROR",
+            AppError::RateLimitError(_) => "RATE_LIMITED",
+            AppError::NetworkError(_) => "NETWORK_ERROR",
+            AppError::ServiceUnavailable(_) => "SERVICE_UNAVAILABLE",
+            AppError::InsufficientFundsError(_) => "INSUFFICIENT_FUNDS",
+            AppError::InsufficientRoyaltyHistory(_) => "INSUFFICIENT_ROYALTY_HISTORY",
+            AppError::FraudDetected(_) => "FRAUD_DETECTED",
+            AppError::AdditionalVerificationRequired => "ADDITIONAL_VERIFICATION_REQUIRED",
+            AppError::DuplicatePayment { .. } => "DUPLICATE_PAYMENT",
+            AppError::PaymentGatewayError(_) => "PAYMENT_GATEWAY_ERROR",
+            AppError::BenefitNotRedeemable(reason) => reason.code(),
+            AppError::ConflictError(_) => "CONFLICT",
+            AppError::BlockchainError(_) => "BLOCKCHAIN_ERROR",
+            AppError::NotImplemented(_) => "NOT_IMPLEMENTED",
+        }
+    }
+
+    /// Errores cuyo mensaje puede exponer detalles de infraestructura
+    fn is_internal(&self) -> bool {
+        matches!(
+            self,
+            AppError::InternalError(_)
+                | AppError::Internal(_)
+                | AppError::InternalServerError(_)
+                | AppError::DatabaseError(_)
+                | AppError::SerializationError(_)
+                | AppError::ConfigurationError(_)
+                | AppError::InitializationError(_)
+                | AppError::Infrastructure(_)
+        )
+    }
+
+    fn message(&self) -> String {
+        match self {
+            AppError::FraudDetected(details) => details.message.clone(),
+            AppError::AdditionalVerificationRequired => "Additional verification is required to continue".to_string(),
+            _ if self.is_internal() && !cfg!(debug_assertions) => REDACTED_INTERNAL_MESSAGE.to_string(),
+            _ => self.to_string(),
+        }
+    }
+
+    fn details(&self) -> Vec<serde_json::Value> {
+        match self {
+            AppError::FraudDetected(details) => vec![serde_json::json!({
+                "reason_code": details.reason_code,
+                "risk_score": details.risk_score,
+            })],
+            AppError::DuplicatePayment { existing_id } => vec![serde_json::json!({
+                "existing_payment_id": existing_id,
+            })],
+            AppError::BenefitNotRedeemable(reason) => vec![serde_json::json!({
+                "reason": reason,
+            })],
+            _ => Vec::new(),
+        }
+    }
+
+    /// Envoltorio de error común a todas las APIs. Dentro de una petición
+    /// incluye su `request_id` para que el cliente pueda citarlo.
+    pub fn response_body(&self) -> serde_json::Value {
+        let mut body = serde_json::json!({
+            "success": false,
+            "error": {
+                "code": self.code(),
+                "message": self.message(),
+                "details": self.details(),
+            },
+            "timestamp": chrono::Utc::now(),
+        });
+        if let Some(request_id) = crate::shared::domain::events::current_request_id() {
+            body["request_id"] = serde_json::json!(request_id);
         }
+        body
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let body = self.response_body();
+        let status = StatusCode::from(self);
+        if status.is_server_error() {
+            tracing::error!("Request failed: {}", body["error"]["message"]);
+        }
+        (status, axum::Json(body)).into_response()
     }
 }
 
@@ -224,12 +468,118 @@ impl AppError {
 // Add Music Context repository error conversions
 impl From<crate::bounded_contexts::music::domain::repositories::song_repository::RepositoryError> for AppError {
     fn from(err: crate::bounded_contexts::music::domain::repositories::song_repository::RepositoryError) -> Self {
+        use crate::bounded_contexts::music::domain::repositories::song_repository::RepositoryError;
         match err {
-            crate::bounded_contexts::music::domain::repositories::song_repository::RepositoryError::NotFound =>
-                AppError::NotFound("Song not found".to_string()),
-            // Adaptamos los errores que no existen en el enum RepositoryError
-            // usando los errores disponibles en ese enum
-            _ => AppError::InternalError("Repository error".to_string())
+            RepositoryError::NotFound => AppError::NotFound("Song not found".to_string()),
+            RepositoryError::DatabaseError(msg) => AppError::DatabaseError(msg),
+            RepositoryError::SerializationError(msg) => AppError::SerializationError(msg),
+            RepositoryError::ValidationError(msg) => AppError::ValidationError(msg),
         }
     }
-} 
\ No newline at end of file
+}
+
+impl From<crate::bounded_contexts::music::domain::entities::remix_license::RemixLicenseError> for AppError {
+    fn from(err: crate::bounded_contexts::music::domain::entities::remix_license::RemixLicenseError) -> Self {
+        use crate::bounded_contexts::music::domain::entities::remix_license::RemixLicenseError;
+        match err {
+            RemixLicenseError::InvalidPrice { .. } | RemixLicenseError::InvalidExpiry { .. } => AppError::ValidationError(err.to_string()),
+            RemixLicenseError::ExclusivelyLicensed { .. } | RemixLicenseError::AlreadyLicensed { .. } => AppError::ConflictError(err.to_string()),
+            RemixLicenseError::Expired { .. } | RemixLicenseError::WrongSong { .. } => AppError::Forbidden(err.to_string()),
+            RemixLicenseError::NoStems { .. } => AppError::NotFound(err.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fraud_detected_maps_to_forbidden() {
+        let error = AppError::fraud(FraudReasonCode::VelocityExceeded, "Too many payments");
+        assert_eq!(StatusCode::from(error), StatusCode::FORBIDDEN);
+        assert_eq!(StatusCode::from(AppError::AdditionalVerificationRequired), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn duplicate_payment_is_a_conflict_carrying_the_existing_id() {
+        let existing_id = uuid::Uuid::new_v4();
+        let error = AppError::DuplicatePayment { existing_id };
+
+        assert_eq!(error.code(), "DUPLICATE_PAYMENT");
+        let body = error.response_body();
+        assert_eq!(body["error"]["details"][0]["existing_payment_id"], existing_id.to_string());
+        assert_eq!(StatusCode::from(error), StatusCode::CONFLICT);
+    }
+
+    #[test]
+    fn fraud_display_includes_reason_code() {
+        let error = AppError::fraud(FraudReasonCode::BotBehavior, "Scripted listening");
+        assert_eq!(error.to_string(), "Fraud detected (bot_behavior): Scripted listening");
+    }
+
+    #[test]
+    fn fraud_reason_code_survives_serialization() {
+        let details = FraudDetails::new(FraudReasonCode::SuspiciousListenPattern, "Replayed session")
+            .with_risk_score(0.93);
+
+        let json = serde_json::to_value(&details).unwrap();
+        assert_eq!(json["reason_code"], "suspicious_listen_pattern");
+
+        let restored: FraudDetails = serde_json::from_value(json).unwrap();
+        assert_eq!(restored, details);
+    }
+
+    async fn body_of(error: AppError) -> (StatusCode, serde_json::Value) {
+        let response = error.into_response();
+        let status = response.status();
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn error_response_uses_common_envelope() {
+        let (status, body) = body_of(AppError::ValidationError("title is required".to_string())).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["success"], false);
+        assert_eq!(body["error"]["code"], "VALIDATION_ERROR");
+        assert_eq!(body["error"]["message"], "Validation error: title is required");
+        assert!(body["error"]["details"].as_array().unwrap().is_empty());
+        assert!(body["timestamp"].is_string());
+        // Fuera de una petición no hay request_id que citar
+        assert!(body.get("request_id").is_none());
+    }
+
+    #[tokio::test]
+    async fn fraud_response_carries_reason_code() {
+        let (status, body) = body_of(AppError::fraud(FraudReasonCode::BlockedPaymentMethod, "Card is blocked")).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["code"], "FRAUD_DETECTED");
+        assert_eq!(body["error"]["message"], "Card is blocked");
+        assert_eq!(body["error"]["details"][0]["reason_code"], "blocked_payment_method");
+    }
+
+    #[tokio::test]
+    async fn benefit_rejection_has_its_own_code() {
+        let (status, body) = body_of(AppError::BenefitNotRedeemable(BenefitRejection::SoldOut)).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["error"]["code"], "BENEFIT_SOLD_OUT");
+        assert_eq!(body["error"]["details"][0]["reason"], "sold_out");
+
+        let (status, body) = body_of(AppError::BenefitNotRedeemable(BenefitRejection::Expired)).await;
+        assert_eq!(status, StatusCode::GONE);
+        assert_eq!(body["error"]["code"], "BENEFIT_EXPIRED");
+    }
+
+    #[tokio::test]
+    async fn internal_messages_are_redacted_outside_debug_builds() {
+        let (status, body) = body_of(AppError::DatabaseError("relation \"payments\" does not exist".to_string())).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["error"]["code"], "DATABASE_ERROR");
+        if cfg!(debug_assertions) {
+            assert!(body["error"]["message"].as_str().unwrap().contains("payments"));
+        } else {
+            assert_eq!(body["error"]["message"], REDACTED_INTERNAL_MESSAGE);
+        }
+    }
+}
@@ -2,7 +2,43 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::fmt::Debug;
+use std::future::Future;
 use uuid::Uuid;
+use vibestream_types::RequestId;
+
+tokio::task_local! {
+    /// Correlation ID de la petición en curso (lo fija `TracingLayer`)
+    static CORRELATION_ID: Uuid;
+    /// `X-Request-Id` de la petición en curso (lo fija `TracingLayer`)
+    static REQUEST_ID: RequestId;
+}
+
+/// Correlation ID del contexto actual, si lo hay
+pub fn current_correlation_id() -> Option<Uuid> {
+    CORRELATION_ID.try_with(|id| *id).ok()
+}
+
+/// Correlation ID del contexto actual o uno nuevo si el evento no nace de una petición
+pub fn correlation_id_or_new() -> Uuid {
+    current_correlation_id().unwrap_or_else(Uuid::new_v4)
+}
+
+/// Ejecutar `future` con `correlation_id` como contexto: los eventos creados
+/// dentro (y los handlers del event bus en memoria) heredan el ID.
+pub async fn with_correlation_id<F: Future>(correlation_id: Uuid, future: F) -> F::Output {
+    CORRELATION_ID.scope(correlation_id, future).await
+}
+
+/// Request ID de la petición en curso, si lo hay
+pub fn current_request_id() -> Option<RequestId> {
+    REQUEST_ID.try_with(|id| *id).ok()
+}
+
+/// Ejecutar `future` con `request_id` como contexto: errores, mensajes a las
+/// colas y registros de auditoría creados dentro lo incluyen.
+pub async fn with_request_id<F: Future>(request_id: RequestId, future: F) -> F::Output {
+    REQUEST_ID.scope(request_id, future).await
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EventMetadata {
@@ -11,25 +47,38 @@ pub struct EventMetadata {
     pub aggregate_id: Uuid,
     pub aggregate_type: String,
     pub occurred_at: DateTime<Utc>,
-    pub correlation_id: Option<Uuid>,
+    pub correlation_id: Uuid,
     pub user_id: Option<Uuid>,
     pub version: i32,
 }
 
 impl EventMetadata {
+    /// Usa el correlation ID de la petición en curso (o genera uno)
     pub fn new() -> Self {
+        Self::new_with_correlation(correlation_id_or_new())
+    }
+
+    pub fn new_with_correlation(correlation_id: Uuid) -> Self {
         Self {
             event_id: Uuid::new_v4(),
             event_type: String::new(),
             aggregate_id: Uuid::new_v4(),
             aggregate_type: String::new(),
             occurred_at: Utc::now(),
-            correlation_id: None,
+            correlation_id,
             user_id: None,
             version: 1,
         }
     }
 
+    /// Metadatos para un evento derivado de `parent`: mismo correlation ID y usuario
+    pub fn inherit(parent: &EventMetadata) -> Self {
+        Self {
+            user_id: parent.user_id,
+            ..Self::new_with_correlation(parent.correlation_id)
+        }
+    }
+
     pub fn with_type_and_aggregate(event_type: &str, aggregate_id: Uuid, aggregate_type: &str) -> Self {
         Self {
             event_id: Uuid::new_v4(),
@@ -37,7 +86,7 @@ impl EventMetadata {
             aggregate_id,
             aggregate_type: aggregate_type.to_string(),
             occurred_at: Utc::now(),
-            correlation_id: None,
+            correlation_id: correlation_id_or_new(),
             user_id: None,
             version: 1,
         }
@@ -64,4 +113,31 @@ pub trait IntegrationEvent: std::fmt::Debug + Send + Sync {
     fn event_type(&self) -> &str;
     fn target_contexts(&self) -> Vec<String>;
     fn event_data(&self) -> serde_json::Value;
-} 
\ No newline at end of file
+} 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn new_metadata_uses_scoped_correlation_id() {
+        let correlation_id = Uuid::new_v4();
+
+        let metadata = with_correlation_id(correlation_id, async { EventMetadata::new() }).await;
+        assert_eq!(metadata.correlation_id, correlation_id);
+
+        // Fuera del scope cada evento recibe uno propio
+        assert_ne!(EventMetadata::new().correlation_id, correlation_id);
+    }
+
+    #[test]
+    fn inherit_keeps_correlation_and_user() {
+        let mut parent = EventMetadata::with_type_and_aggregate("PaymentCompleted", Uuid::new_v4(), "Payment");
+        parent.user_id = Some(Uuid::new_v4());
+
+        let child = EventMetadata::inherit(&parent);
+
+        assert_eq!(child.correlation_id, parent.correlation_id);
+        assert_eq!(child.user_id, parent.user_id);
+        assert_ne!(child.event_id, parent.event_id);
+    }
+}
@@ -0,0 +1,250 @@
+//! Traducción de eventos de dominio a eventos de integración
+//!
+//! Los eventos de dominio son internos de cada bounded context; lo que sale a
+//! la cola de mensajes son los eventos de integración de `vibestream_types`.
+//! Los publicadores pasan cada evento por el `MapperRegistry` antes de
+//! publicarlo.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use vibestream_types::{IntegrationEvent as IntegrationEventEnvelope, IntegrationEventType};
+
+use super::events::{DomainEvent, EventMetadata, IntegrationEvent};
+
+/// Traduce un tipo de evento de dominio a su evento de integración
+pub trait IntegrationEventMapper: Send + Sync {
+    /// `event_type()` de los eventos de dominio que traduce
+    fn domain_event_type(&self) -> &str;
+    fn map(&self, domain_event: &dyn DomainEvent) -> Box<dyn IntegrationEvent>;
+}
+
+/// Evento de integración listo para publicar
+#[derive(Debug, Clone)]
+pub struct MappedIntegrationEvent {
+    metadata: EventMetadata,
+    envelope: IntegrationEventEnvelope,
+    target_contexts: Vec<String>,
+}
+
+impl MappedIntegrationEvent {
+    pub fn envelope(&self) -> &IntegrationEventEnvelope {
+        &self.envelope
+    }
+}
+
+impl IntegrationEvent for MappedIntegrationEvent {
+    fn metadata(&self) -> &EventMetadata {
+        &self.metadata
+    }
+
+    fn event_type(&self) -> &str {
+        &self.envelope.event_type
+    }
+
+    fn target_contexts(&self) -> Vec<String> {
+        self.target_contexts.clone()
+    }
+
+    fn event_data(&self) -> serde_json::Value {
+        serde_json::to_value(&self.envelope).unwrap_or_default()
+    }
+}
+
+/// Traducción uno a uno: mismo agregado e instante, el payload del evento de
+/// dominio viaja dentro del evento de integración.
+#[derive(Debug, Clone)]
+pub struct DomainEventMapping {
+    domain_event_type: &'static str,
+    integration_type: IntegrationEventType,
+    target_contexts: &'static [&'static str],
+}
+
+impl DomainEventMapping {
+    pub const fn new(
+        domain_event_type: &'static str,
+        integration_type: IntegrationEventType,
+        target_contexts: &'static [&'static str],
+    ) -> Self {
+        Self { domain_event_type, integration_type, target_contexts }
+    }
+
+    pub fn integration_type(&self) -> IntegrationEventType {
+        self.integration_type
+    }
+
+    fn build(&self, aggregate_id: Uuid, aggregate_type: &str, occurred_at: DateTime<Utc>, payload: serde_json::Value) -> MappedIntegrationEvent {
+        // `DomainEvent::metadata` no está implementado en todos los contextos:
+        // el correlation ID sale del contexto de la petición
+        let mut metadata = EventMetadata::with_type_and_aggregate(self.integration_type.as_str(), aggregate_id, aggregate_type);
+        metadata.occurred_at = occurred_at;
+        let envelope = IntegrationEventEnvelope::from_domain_event(
+            self.integration_type,
+            self.domain_event_type,
+            aggregate_id,
+            occurred_at,
+            Some(metadata.correlation_id),
+            payload,
+        );
+
+        MappedIntegrationEvent {
+            metadata,
+            envelope,
+            target_contexts: self.target_contexts.iter().map(|context| context.to_string()).collect(),
+        }
+    }
+}
+
+impl IntegrationEventMapper for DomainEventMapping {
+    fn domain_event_type(&self) -> &str {
+        self.domain_event_type
+    }
+
+    fn map(&self, domain_event: &dyn DomainEvent) -> Box<dyn IntegrationEvent> {
+        Box::new(self.build(
+            domain_event.aggregate_id(),
+            domain_event.aggregate_type(),
+            domain_event.occurred_at(),
+            domain_event.event_data(),
+        ))
+    }
+}
+
+/// Eventos de dominio que salen de su contexto y el evento de integración que
+/// los representa. Fan Ventures sustituye a Fractional Ownership, por eso sus
+/// eventos conservan los nombres de integración de contratos y acciones.
+pub const DEFAULT_MAPPINGS: &[DomainEventMapping] = &[
+    DomainEventMapping::new("music.song.uploaded", IntegrationEventType::SongCreated, &["campaign", "listen_reward", "fan_ventures"]),
+    DomainEventMapping::new("music.artist.profile_created", IntegrationEventType::ArtistCreated, &["campaign", "fan_ventures"]),
+    DomainEventMapping::new("UserRegistered", IntegrationEventType::UserRegistered, &["music", "campaign", "listen_reward", "fan_ventures", "notifications"]),
+    DomainEventMapping::new("UserProfileUpdated", IntegrationEventType::UserUpdated, &["music", "campaign", "fan_ventures"]),
+    DomainEventMapping::new("CampaignCreated", IntegrationEventType::CampaignCreated, &["music", "notifications"]),
+    DomainEventMapping::new("CampaignActivated", IntegrationEventType::CampaignActivated, &["music", "notifications"]),
+    DomainEventMapping::new("NFTPurchased", IntegrationEventType::NftPurchased, &["payment", "notifications"]),
+    DomainEventMapping::new("ListenSessionStarted", IntegrationEventType::ListenSessionStarted, &["music"]),
+    DomainEventMapping::new("ListenSessionCompleted", IntegrationEventType::ListenSessionCompleted, &["music", "campaign"]),
+    DomainEventMapping::new("RewardDistributed", IntegrationEventType::RewardDistributed, &["payment", "notifications"]),
+    DomainEventMapping::new("VentureCreated", IntegrationEventType::OwnershipContractCreated, &["payment", "notifications"]),
+    DomainEventMapping::new("FanInvested", IntegrationEventType::SharesPurchased, &["payment", "notifications"]),
+    DomainEventMapping::new("PaymentCompleted", IntegrationEventType::PaymentProcessed, &["fan_ventures", "notifications"]),
+    DomainEventMapping::new("PaymentFailed", IntegrationEventType::PaymentFailed, &["notifications"]),
+];
+
+/// Eventos de integración sin evento de dominio detrás: ni `SongUpdated` ni
+/// `NotificationSent` tienen todavía equivalente en su contexto.
+pub const UNMAPPED_INTEGRATION_TYPES: &[IntegrationEventType] = &[
+    IntegrationEventType::SongUpdated,
+    IntegrationEventType::NotificationSent,
+];
+
+/// Mappers indexados por tipo de evento de dominio
+#[derive(Clone, Default)]
+pub struct MapperRegistry {
+    mappers: HashMap<String, Arc<dyn IntegrationEventMapper>>,
+}
+
+impl MapperRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registro con todas las traducciones de `DEFAULT_MAPPINGS`
+    pub fn with_default_mappings() -> Self {
+        let mut registry = Self::new();
+        for mapping in DEFAULT_MAPPINGS {
+            registry.register(Arc::new(mapping.clone()));
+        }
+        registry
+    }
+
+    /// Registrar `mapper`; sustituye al que hubiera para el mismo tipo de evento
+    pub fn register(&mut self, mapper: Arc<dyn IntegrationEventMapper>) {
+        self.mappers.insert(mapper.domain_event_type().to_string(), mapper);
+    }
+
+    pub fn has_mapper(&self, domain_event_type: &str) -> bool {
+        self.mappers.contains_key(domain_event_type)
+    }
+
+    /// `None` para eventos de dominio que no salen de su contexto
+    pub fn map(&self, domain_event: &dyn DomainEvent) -> Option<Box<dyn IntegrationEvent>> {
+        self.mappers
+            .get(domain_event.event_type())
+            .map(|mapper| mapper.map(domain_event))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::fan_ventures::domain::entities::InvestmentType;
+    use crate::bounded_contexts::fan_ventures::domain::events::FanInvested;
+    use crate::shared::domain::events::with_correlation_id;
+    use std::collections::HashSet;
+
+    #[test]
+    fn every_integration_event_has_a_domain_event() {
+        let mapped: HashSet<_> = DEFAULT_MAPPINGS.iter().map(|mapping| mapping.integration_type()).collect();
+
+        for event_type in IntegrationEventType::ALL {
+            assert!(
+                mapped.contains(&event_type) != UNMAPPED_INTEGRATION_TYPES.contains(&event_type),
+                "{} must be mapped from exactly one domain event or listed as unmapped",
+                event_type.as_str()
+            );
+        }
+        assert_eq!(mapped.len(), DEFAULT_MAPPINGS.len(), "two domain events map to the same integration event");
+    }
+
+    #[test]
+    fn every_default_mapping_is_registered() {
+        let registry = MapperRegistry::with_default_mappings();
+        for mapping in DEFAULT_MAPPINGS {
+            assert!(registry.has_mapper(mapping.domain_event_type), "{} has no mapper", mapping.domain_event_type);
+        }
+    }
+
+    #[tokio::test]
+    async fn maps_domain_event_into_integration_event() {
+        let investment = FanInvested {
+            investment_id: Uuid::new_v4(),
+            venture_id: Uuid::new_v4(),
+            fan_id: Uuid::new_v4(),
+            amount: 250.0,
+            investment_type: InvestmentType::RevenueShare,
+            invested_at: Utc::now(),
+        };
+        let correlation_id = Uuid::new_v4();
+        let registry = MapperRegistry::with_default_mappings();
+
+        let event = with_correlation_id(correlation_id, async { registry.map(&investment) })
+            .await
+            .expect("FanInvested is published");
+
+        assert_eq!(event.event_type(), "SharesPurchased");
+        assert_eq!(event.metadata().aggregate_id, investment.venture_id);
+        assert_eq!(event.metadata().correlation_id, correlation_id);
+        assert!(event.target_contexts().contains(&"payment".to_string()));
+        let data = event.event_data();
+        assert_eq!(data["aggregate_id"], investment.venture_id.to_string());
+        assert_eq!(data["metadata"]["source_event_type"], "FanInvested");
+        assert_eq!(data["metadata"]["payload"]["amount"], 250.0);
+    }
+
+    #[test]
+    fn internal_domain_events_are_not_mapped() {
+        let registry = MapperRegistry::new();
+        let investment = FanInvested {
+            investment_id: Uuid::new_v4(),
+            venture_id: Uuid::new_v4(),
+            fan_id: Uuid::new_v4(),
+            amount: 1.0,
+            investment_type: InvestmentType::RevenueShare,
+            invested_at: Utc::now(),
+        };
+
+        assert!(registry.map(&investment).is_none());
+    }
+}
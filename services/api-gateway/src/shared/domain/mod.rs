@@ -1,7 +1,9 @@
 //! Capacidades de dominio compartidas (eventos, errores, repositorios)
 
 pub mod events;
+pub mod integration_events;
 pub mod errors;
 pub mod repositories; 
 
-pub use events::{DomainEvent, EventMetadata}; 
\ No newline at end of file
+pub use events::{DomainEvent, EventMetadata};
+pub use integration_events::{IntegrationEventMapper, MapperRegistry}; 
\ No newline at end of file
@@ -1,10 +1,12 @@
 use std::sync::Arc;
-use crate::services::{MessageQueue, DatabasePool};
+use crate::services::{MessageQueue, DatabasePool, MigrationError};
 use crate::bounded_contexts::orchestrator::{EventBus, DomainEvent, RedisStreamsEventBus, RedisStreamsEventWorker};
 use crate::bounded_contexts::music::domain::repositories::{AlbumRepository, PlaylistRepository};
 use crate::shared::infrastructure::clients::facial_recognition_client::FacialRecognitionClient;
 use crate::shared::infrastructure::clients::zk_service_client::ZkServiceClient;
 use crate::shared::infrastructure::clients::blockchain_client::{BlockchainClient, BlockchainConfig};
+use crate::bounded_contexts::notifications::application::NotificationHub;
+use crate::shared::infrastructure::shutdown::BackgroundTasks;
 
 // =============================================================================
 // SIMPLIFIED APP STATE - Separado por contexto para reducir acoplamiento
@@ -30,6 +32,10 @@ pub struct AppState {
     pub facial_client: Arc<FacialRecognitionClient>,
     pub zk_client: Arc<ZkServiceClient>,
     pub blockchain_client: Arc<BlockchainClient>,
+    /// Conexiones WebSocket de notificaciones de este proceso
+    pub notification_hub: Arc<NotificationHub>,
+    /// Jobs arrancados por los gateways; se paran en `shutdown`
+    pub background_tasks: BackgroundTasks,
     
     // Config
     pub env: String,
@@ -46,6 +52,8 @@ impl Clone for AppState {
             facial_client: self.facial_client.clone(),
             zk_client: self.zk_client.clone(),
             blockchain_client: self.blockchain_client.clone(),
+            notification_hub: self.notification_hub.clone(),
+            background_tasks: self.background_tasks.clone(),
             env: self.env.clone(),
         }
     }
@@ -68,6 +76,8 @@ impl AppState {
         // Inicializar solo servicios compartidos esenciales
         let message_queue = MessageQueue::new(redis_url).await?;
         let database_pool = DatabasePool::new(database_url).await?;
+        // Antes que nada que use el esquema: handlers, workers y tráfico
+        Self::run_migrations_if_enabled(database_pool.get_pool()).await?;
         
         // Usar Redis Streams Event Bus para producción
         let (event_bus, event_worker_handle) = crate::bounded_contexts::orchestrator::EventBusFactory::create_redis_streams_event_bus(redis_url)
@@ -114,6 +124,8 @@ impl AppState {
             facial_client,
             zk_client,
             blockchain_client,
+            notification_hub: Arc::new(NotificationHub::new()),
+            background_tasks: BackgroundTasks::default(),
             env,
         };
 
@@ -123,12 +135,9 @@ impl AppState {
             Arc::clone(&app_state.event_bus),
             app_state.get_db_pool().clone(),
             Arc::clone(&app_state.blockchain_client),
-            Arc::clone(&app_state.zk_client)
+            Arc::clone(&app_state.zk_client),
+            Arc::clone(&app_state.notification_hub),
         ).await.map_err(|e| format!("Failed to register event handlers: {}", e))?;
-
-        
-        // Ejecutar migraciones automáticamente si está habilitado
-        Self::run_migrations_if_enabled(app_state.get_db_pool()).await?;
         
         Ok(app_state)
     }
@@ -157,7 +166,9 @@ impl AppState {
     /// - La variable de entorno RUN_MIGRATIONS está configurada como "true" o "1"
     /// - O si no está configurada (por defecto en desarrollo)
     /// 
-    /// Para deshabilitar en producción, establecer RUN_MIGRATIONS=false
+    /// Para deshabilitar en producción, establecer RUN_MIGRATIONS=false.
+    /// Una migración fallida aborta el arranque: no se sirve tráfico con el
+    /// esquema a medias.
     async fn run_migrations_if_enabled(pool: &sqlx::PgPool) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let run_migrations = std::env::var("RUN_MIGRATIONS")
             .unwrap_or_else(|_| "true".to_string())
@@ -166,41 +177,10 @@ impl AppState {
         // Ejecutar migraciones si está habilitado (por defecto sí)
         if run_migrations == "true" || run_migrations == "1" || run_migrations.is_empty() {
             println!("🔄 Running database migrations...");
-            
-            // Intentar ejecutar migraciones desde el directorio migrations
-            // Primero intentamos desde la raíz del proyecto
-            let migrations_paths = vec![
-                "../../migrations",
-                "../migrations",
-                "migrations",
-            ];
-            
-            let mut migration_success = false;
-            for path in migrations_paths {
-                if std::path::Path::new(path).exists() {
-                    match sqlx::migrate::Migrator::new(std::path::Path::new(path)).await {
-                        Ok(migrator) => {
-                            match migrator.run(pool).await {
-                                Ok(_) => {
-                                    println!("✅ Database migrations completed successfully");
-                                    migration_success = true;
-                                    break;
-                                }
-                                Err(e) => {
-                                    eprintln!("⚠️  Failed to run migrations from {}: {}", path, e);
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            eprintln!("⚠️  Failed to create migrator from {}: {}", path, e);
-                        }
-                    }
-                }
-            }
-            
-            if !migration_success {
-                println!("⚠️  Could not find migrations directory. Skipping automatic migrations.");
-                println!("   You can run migrations manually with: sqlx migrate run");
+            match DatabasePool::migrate(pool).await {
+                Ok(()) => println!("✅ Database migrations completed successfully"),
+                Err(MigrationError::AlreadyUpToDate) => println!("✅ Database schema already up to date"),
+                Err(e) => return Err(Box::new(e)),
             }
         } else {
             println!("⏭️  Skipping automatic migrations (RUN_MIGRATIONS={})", run_migrations);
@@ -209,6 +189,20 @@ impl AppState {
         Ok(())
     }
     
+    /// Apagado ordenado, una vez que los servidores dejaron de atender: para
+    /// los jobs en background y el worker del event bus, y cierra el pool
+    /// (espera a que se devuelvan las conexiones en uso). Las conexiones a
+    /// Redis se cierran al soltar el último clon del estado.
+    pub async fn shutdown(&self, timeout: std::time::Duration) {
+        self.background_tasks.shutdown(timeout).await;
+        if let Some(event_worker) = &self._event_worker_handle {
+            event_worker.abort();
+        }
+        if tokio::time::timeout(timeout, self.get_db_pool().close()).await.is_err() {
+            tracing::warn!("Database pool still closing after {:?}", timeout);
+        }
+    }
+    
     /// Publicar un evento de dominio
     pub async fn publish_event(&self, event: DomainEvent) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         self.event_bus.publish(event).await
@@ -276,6 +270,17 @@ pub struct MusicAppState {
     pub song_repository: Arc<crate::bounded_contexts::music::infrastructure::repositories::PostgresSongRepository>,
     pub album_repository: Arc<crate::bounded_contexts::music::infrastructure::repositories::PostgresAlbumRepository>,
     pub playlist_repository: Arc<crate::bounded_contexts::music::infrastructure::repositories::PostgresPlaylistRepository>,
+    pub remix_license_repository: Arc<crate::bounded_contexts::music::infrastructure::repositories::PostgresRemixLicenseRepository>,
+    pub remix_license_pricing: crate::bounded_contexts::music::domain::RemixLicensePricing,
+    pub remix_license_payments: Arc<dyn crate::bounded_contexts::music::domain::services::RemixLicensePayments>,
+    pub stems_url_signer: Arc<crate::bounded_contexts::music::infrastructure::storage::StemsUrlSigner>,
+    pub recommendation_read_model: Arc<crate::bounded_contexts::music::infrastructure::repositories::PostgresPlaylistRecommendationReadModel>,
+    pub playlist_recommender: Arc<dyn crate::bounded_contexts::music::domain::services::PlaylistRecommendationEngine>,
+    pub artist_followers: Arc<crate::bounded_contexts::music::infrastructure::repositories::PostgresArtistFollowersReadModel>,
+    pub artist_dashboard: Arc<crate::bounded_contexts::music::infrastructure::repositories::PostgresArtistDashboardReadModel>,
+    pub song_analytics: Arc<crate::bounded_contexts::music::infrastructure::repositories::PostgresSongAnalyticsReadModel>,
+    pub song_similarities: Arc<crate::bounded_contexts::music::infrastructure::repositories::PostgresSongSimilarityReadModel>,
+    pub content_moderation: Arc<dyn crate::bounded_contexts::music::domain::services::ContentModerationService>,
 }
 
 impl MusicAppState {
@@ -284,12 +289,45 @@ impl MusicAppState {
         song_repository: Arc<crate::bounded_contexts::music::infrastructure::repositories::PostgresSongRepository>,
         album_repository: Arc<crate::bounded_contexts::music::infrastructure::repositories::PostgresAlbumRepository>,
         playlist_repository: Arc<crate::bounded_contexts::music::infrastructure::repositories::PostgresPlaylistRepository>,
+        remix_license_repository: Arc<crate::bounded_contexts::music::infrastructure::repositories::PostgresRemixLicenseRepository>,
     ) -> Self {
+        let recommendation_read_model = Arc::new(
+            crate::bounded_contexts::music::infrastructure::repositories::PostgresPlaylistRecommendationReadModel::new(app_state.get_db_pool().clone()),
+        );
+        let playlist_recommender = Arc::new(
+            crate::bounded_contexts::music::domain::services::CollaborativeFilterRecommender::new(recommendation_read_model.clone()),
+        );
+        let artist_followers = Arc::new(
+            crate::bounded_contexts::music::infrastructure::repositories::PostgresArtistFollowersReadModel::new(app_state.get_db_pool().clone()),
+        );
+        let artist_dashboard = Arc::new(
+            crate::bounded_contexts::music::infrastructure::repositories::PostgresArtistDashboardReadModel::new(app_state.get_db_pool().clone()),
+        );
+        let song_analytics = Arc::new(
+            crate::bounded_contexts::music::infrastructure::repositories::PostgresSongAnalyticsReadModel::new(app_state.get_db_pool().clone()),
+        );
+        let song_similarities = Arc::new(
+            crate::bounded_contexts::music::infrastructure::repositories::PostgresSongSimilarityReadModel::new(app_state.get_db_pool().clone()),
+        );
+        let remix_license_payments = Arc::new(
+            crate::bounded_contexts::music::infrastructure::PaymentContextRemixLicensePayments::new(app_state.get_db_pool().clone()),
+        );
         Self {
             app_state,
             song_repository,
             album_repository,
             playlist_repository,
+            remix_license_repository,
+            remix_license_pricing: crate::bounded_contexts::music::domain::RemixLicensePricing::from_env(),
+            remix_license_payments,
+            stems_url_signer: Arc::new(crate::bounded_contexts::music::infrastructure::storage::StemsUrlSigner::from_env()),
+            recommendation_read_model,
+            playlist_recommender,
+            artist_followers,
+            artist_dashboard,
+            song_analytics,
+            song_similarities,
+            content_moderation: crate::bounded_contexts::music::infrastructure::content_moderation_from_env(),
         }
     }
 }
@@ -362,16 +400,52 @@ impl ListenRewardAppState {
 pub struct FanVenturesAppState {
     pub app_state: AppState,
     pub venture_repository: Arc<crate::bounded_contexts::fan_ventures::infrastructure::PostgresFanVenturesRepository>,
+    pub proposal_service: Arc<crate::bounded_contexts::fan_ventures::application::ProposalService>,
+    pub market_stats_service: Arc<crate::bounded_contexts::fan_ventures::application::MarketStatsService>,
+    pub market_feed: Arc<crate::bounded_contexts::fan_ventures::application::MarketFeed>,
+    pub escrow_service: Arc<crate::bounded_contexts::fan_ventures::application::InvestmentEscrowService>,
+    /// Compras de participaciones serializadas por venture con un lock en Redis
+    pub purchase_shares_handler: Arc<crate::bounded_contexts::fan_ventures::application::PurchaseSharesCommandHandler>,
+    /// Ventas entre fans: participaciones en escrow hasta cobrar al comprador
+    pub transfer_shares_handler: Arc<crate::bounded_contexts::fan_ventures::application::TransferSharesCommandHandler>,
+    pub audit_log_repository: Arc<dyn crate::bounded_contexts::fan_ventures::domain::repositories::AuditLogRepository>,
+    /// Historial de operaciones del mercado secundario
+    pub trade_repository: Arc<dyn crate::bounded_contexts::fan_ventures::domain::repositories::SecondaryMarketTradeRepository>,
 }
 
 impl FanVenturesAppState {
     pub fn new(
         app_state: AppState,
         venture_repository: Arc<crate::bounded_contexts::fan_ventures::infrastructure::PostgresFanVenturesRepository>,
+        proposal_service: Arc<crate::bounded_contexts::fan_ventures::application::ProposalService>,
+        market_stats_service: Arc<crate::bounded_contexts::fan_ventures::application::MarketStatsService>,
+        market_feed: Arc<crate::bounded_contexts::fan_ventures::application::MarketFeed>,
+        escrow_service: Arc<crate::bounded_contexts::fan_ventures::application::InvestmentEscrowService>,
+        audit_log_repository: Arc<dyn crate::bounded_contexts::fan_ventures::domain::repositories::AuditLogRepository>,
+        trade_repository: Arc<dyn crate::bounded_contexts::fan_ventures::domain::repositories::SecondaryMarketTradeRepository>,
     ) -> Self {
+        let purchase_shares_handler = Arc::new(crate::bounded_contexts::fan_ventures::application::PurchaseSharesCommandHandler::new(
+            escrow_service.clone(),
+            Arc::new(crate::shared::infrastructure::distributed_lock::RedisDistributedLock::new(
+                app_state.message_queue.connection_manager(),
+            )),
+        ));
+        let pool = app_state.get_db_pool().clone();
+        let transfer_shares_handler = Arc::new(crate::bounded_contexts::fan_ventures::application::TransferSharesCommandHandler::new(
+            Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::PostgresShareEscrowRepository::new(pool.clone())),
+            Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::PaymentContextEscrow::new(pool, venture_repository.clone())),
+        ));
         Self {
             app_state,
             venture_repository,
+            proposal_service,
+            market_stats_service,
+            market_feed,
+            escrow_service,
+            purchase_shares_handler,
+            transfer_shares_handler,
+            audit_log_repository,
+            trade_repository,
         }
     }
 }
@@ -383,6 +457,7 @@ pub struct NotificationAppState {
     pub notification_repository: Arc<dyn crate::bounded_contexts::notifications::domain::repositories::NotificationRepository + Send + Sync>,
     pub preferences_repository: Arc<dyn crate::bounded_contexts::notifications::domain::repositories::NotificationPreferencesRepository + Send + Sync>,
     pub template_repository: Arc<dyn crate::bounded_contexts::notifications::domain::repositories::NotificationTemplateRepository + Send + Sync>,
+    pub device_repository: Arc<dyn crate::bounded_contexts::notifications::domain::repositories::DeviceTokenRepository + Send + Sync>,
 }
 
 impl NotificationAppState {
@@ -391,12 +466,14 @@ impl NotificationAppState {
         notification_repository: Arc<dyn crate::bounded_contexts::notifications::domain::repositories::NotificationRepository + Send + Sync>,
         preferences_repository: Arc<dyn crate::bounded_contexts::notifications::domain::repositories::NotificationPreferencesRepository + Send + Sync>,
         template_repository: Arc<dyn crate::bounded_contexts::notifications::domain::repositories::NotificationTemplateRepository + Send + Sync>,
+        device_repository: Arc<dyn crate::bounded_contexts::notifications::domain::repositories::DeviceTokenRepository + Send + Sync>,
     ) -> Self {
         Self {
             app_state,
             notification_repository,
             preferences_repository,
             template_repository,
+            device_repository,
         }
     }
 }
@@ -416,12 +493,14 @@ impl AppStateFactory {
         let song_repository = Arc::new(crate::bounded_contexts::music::infrastructure::repositories::PostgresSongRepository::new(pool.clone()));
         let album_repository = Arc::new(crate::bounded_contexts::music::infrastructure::repositories::PostgresAlbumRepository::new(pool.clone()));
         let playlist_repository = Arc::new(crate::bounded_contexts::music::infrastructure::repositories::PostgresPlaylistRepository::new(pool.clone()));
+        let remix_license_repository = Arc::new(crate::bounded_contexts::music::infrastructure::repositories::PostgresRemixLicenseRepository::new(pool.clone()));
         
         Ok(MusicAppState::new(
             app_state,
             song_repository,
             album_repository,
             playlist_repository,
+            remix_license_repository,
         ))
     }
     
@@ -470,10 +549,34 @@ impl AppStateFactory {
         let pool = app_state.get_db_pool();
         
         let venture_repository = Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::PostgresFanVenturesRepository::new(pool.clone()));
+        let proposal_repository = Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::PostgresProposalRepository::new(pool.clone()));
+        let proposal_service = Arc::new(crate::bounded_contexts::fan_ventures::application::ProposalService::new(
+            proposal_repository,
+            venture_repository.clone(),
+            app_state.event_bus.clone(),
+        ));
+        let genre_read_model = Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::PostgresArtistGenreReadModel::new(pool.clone()));
+        let market_stats_service = Arc::new(crate::bounded_contexts::fan_ventures::application::MarketStatsService::new(
+            pool.clone(),
+            genre_read_model,
+        ));
+        let escrow_service = Arc::new(crate::bounded_contexts::fan_ventures::application::InvestmentEscrowService::new(
+            Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::PostgresInvestmentReservationRepository::new(pool.clone())),
+            venture_repository.clone(),
+            Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::PaymentContextEscrow::new(pool.clone(), venture_repository.clone())),
+            app_state.event_bus.clone(),
+            crate::bounded_contexts::fan_ventures::application::InvestmentEscrowService::reservation_timeout_from_env(),
+        ));
         
         Ok(FanVenturesAppState::new(
             app_state,
             venture_repository,
+            proposal_service,
+            market_stats_service,
+            Arc::new(crate::bounded_contexts::fan_ventures::application::MarketFeed::new()),
+            escrow_service,
+            Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::PostgresAuditLogRepository::new(pool.clone())),
+            Arc::new(crate::bounded_contexts::fan_ventures::infrastructure::PostgresSecondaryMarketTradeRepository::new(pool.clone())),
         ))
     }
     
@@ -482,14 +585,16 @@ impl AppStateFactory {
         let pool = app_state.get_db_pool();
         
         let notification_repository = Arc::new(crate::bounded_contexts::notifications::infrastructure::PostgresNotificationRepository::new(pool.clone()));
-        let preferences_repository = Arc::new(crate::bounded_contexts::notifications::infrastructure::MockNotificationPreferencesRepository::new());
+        let preferences_repository = Arc::new(crate::bounded_contexts::notifications::infrastructure::PostgresNotificationPreferencesRepository::new(pool.clone()));
         let template_repository = Arc::new(crate::bounded_contexts::notifications::infrastructure::MockNotificationTemplateRepository::new());
+        let device_repository = Arc::new(crate::bounded_contexts::notifications::infrastructure::PostgresDeviceTokenRepository::new(pool.clone()));
         
         Ok(NotificationAppState::new(
             app_state,
             notification_repository,
             preferences_repository,
             template_repository,
+            device_repository,
         ))
     }
 } 
\ No newline at end of file
@@ -0,0 +1,273 @@
+// =============================================================================
+// AUTHORIZATION - ROLES Y PROPIEDAD DE RECURSOS
+// =============================================================================
+//
+// Dos niveles de control por encima de la autenticación:
+// - `RequireRole`: layer de Axum para grupos de rutas enteros (p.ej. `/admin/*`).
+// - `authorize_owner`: en cada caso de uso, compara al usuario autenticado con el
+//   dueño del recurso (artista de la canción, creador de la playlist, pagador del
+//   pago...). El id del dueño sale siempre del recurso guardado o se compara con
+//   el que manda el cliente, nunca se da por bueno.
+// Los admins pasan ambos controles.
+
+use axum::{
+    http::Request,
+    response::{IntoResponse, Response},
+};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use uuid::Uuid;
+
+use crate::bounded_contexts::user::domain::UserRole;
+use crate::shared::domain::errors::AppError;
+use super::middleware::{extract_token, AuthenticatedUser};
+use super::{Claims, JwtService};
+
+// =============================================================================
+// PRINCIPAL
+// =============================================================================
+
+/// Quien hace la petición, venga del extractor `AuthenticatedUser` o de las claims
+pub trait Principal {
+    fn subject_id(&self) -> Result<Uuid, AppError>;
+    fn role_name(&self) -> &str;
+
+    fn role(&self) -> Option<UserRole> {
+        UserRole::from_str(self.role_name()).ok()
+    }
+
+    fn is_admin(&self) -> bool {
+        self.role() == Some(UserRole::Admin)
+    }
+}
+
+impl Principal for AuthenticatedUser {
+    fn subject_id(&self) -> Result<Uuid, AppError> {
+        Ok(self.user_id)
+    }
+
+    fn role_name(&self) -> &str {
+        &self.role
+    }
+}
+
+impl Principal for Claims {
+    fn subject_id(&self) -> Result<Uuid, AppError> {
+        parse_subject(&self.sub)
+    }
+
+    fn role_name(&self) -> &str {
+        &self.role
+    }
+}
+
+impl Principal for crate::auth::Claims {
+    fn subject_id(&self) -> Result<Uuid, AppError> {
+        parse_subject(&self.sub)
+    }
+
+    fn role_name(&self) -> &str {
+        &self.role
+    }
+}
+
+fn parse_subject(sub: &str) -> Result<Uuid, AppError> {
+    Uuid::parse_str(sub).map_err(|_| AppError::Unauthorized("Invalid subject in token".to_string()))
+}
+
+// =============================================================================
+// ROLE & OWNERSHIP CHECKS
+// =============================================================================
+
+/// Recurso con dueño, para los mensajes de `authorize_owner`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OwnedResource {
+    Artist,
+    Song,
+    Album,
+    Playlist,
+    Campaign,
+    Payment,
+    Payout,
+    Wallet,
+    Venture,
+}
+
+impl OwnedResource {
+    fn owner(&self) -> &'static str {
+        match self {
+            OwnedResource::Artist => "artist",
+            OwnedResource::Song => "song's artist",
+            OwnedResource::Album => "album's artist",
+            OwnedResource::Playlist => "playlist's creator",
+            OwnedResource::Campaign => "campaign's artist",
+            OwnedResource::Payment => "payment's payer",
+            OwnedResource::Payout => "payout's artist",
+            OwnedResource::Wallet => "wallet's owner",
+            OwnedResource::Venture => "venture's artist",
+        }
+    }
+}
+
+fn role_allowed(role_name: &str, allowed: &[UserRole]) -> bool {
+    match UserRole::from_str(role_name) {
+        Ok(UserRole::Admin) => true,
+        Ok(role) => allowed.contains(&role),
+        Err(_) => false,
+    }
+}
+
+/// `Forbidden` salvo que el usuario tenga uno de los roles (o sea admin)
+pub fn authorize_role<P: Principal + ?Sized>(principal: &P, allowed: &[UserRole]) -> Result<(), AppError> {
+    if role_allowed(principal.role_name(), allowed) {
+        return Ok(());
+    }
+    let allowed = allowed.iter().map(ToString::to_string).collect::<Vec<_>>().join(" or ");
+    Err(AppError::Forbidden(format!("Requires the {} role", allowed)))
+}
+
+/// `Forbidden` salvo que el usuario sea el dueño del recurso (o sea admin)
+pub fn authorize_owner<P: Principal + ?Sized>(
+    principal: &P,
+    resource: OwnedResource,
+    owner_id: Uuid,
+) -> Result<(), AppError> {
+    if principal.is_admin() || principal.subject_id()? == owner_id {
+        return Ok(());
+    }
+    Err(AppError::Forbidden(format!("Only the {} can do this", resource.owner())))
+}
+
+// =============================================================================
+// REQUIRE ROLE LAYER
+// =============================================================================
+
+/// Layer que exige un rol a todas las rutas que envuelve:
+/// `.route_layer(RequireRole(UserRole::Admin))`.
+///
+/// Usa las claims que haya dejado el middleware JWT y, si no las hay, valida el
+/// header Authorization. Sin usuario responde 401; con otro rol, 403.
+#[derive(Debug, Clone)]
+pub struct RequireRole(pub UserRole);
+
+impl<S> Layer<S> for RequireRole {
+    type Service = RequireRoleService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequireRoleService { inner, role: self.0.clone() }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RequireRoleService<S> {
+    inner: S,
+    role: UserRole,
+}
+
+fn request_role<B>(request: &Request<B>) -> Result<String, AppError> {
+    if let Some(claims) = request.extensions().get::<Claims>() {
+        return Ok(claims.role.clone());
+    }
+    if let Some(claims) = request.extensions().get::<crate::auth::Claims>() {
+        return Ok(claims.role.clone());
+    }
+    let token = extract_token(request.headers())
+        .ok_or_else(|| AppError::Unauthorized("Missing or invalid authorization header".to_string()))?;
+    let claims = JwtService::from_env()?.validate_access_token(&token)?;
+    Ok(claims.role)
+}
+
+impl<S, ReqBody> Service<Request<ReqBody>> for RequireRoleService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response> + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let rejection = match request_role(&request) {
+            Ok(role) if role_allowed(&role, std::slice::from_ref(&self.role)) => None,
+            Ok(_) => Some(AppError::Forbidden(format!("Requires the {} role", self.role))),
+            Err(e) => Some(e),
+        };
+
+        match rejection {
+            Some(error) => Box::pin(async move { Ok(error.into_response()) }),
+            None => Box::pin(self.inner.call(request)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn user(role: &str) -> AuthenticatedUser {
+        AuthenticatedUser {
+            user_id: Uuid::new_v4(),
+            username: "someone".to_string(),
+            email: "someone@example.com".to_string(),
+            role: role.to_string(),
+            tier: "free".to_string(),
+        }
+    }
+
+    #[test]
+    fn only_the_owner_or_an_admin_passes_the_ownership_check() {
+        let artist = user("artist");
+        assert!(authorize_owner(&artist, OwnedResource::Song, artist.user_id).is_ok());
+        assert!(authorize_owner(&user("admin"), OwnedResource::Song, artist.user_id).is_ok());
+
+        let err = authorize_owner(&user("artist"), OwnedResource::Song, artist.user_id).unwrap_err();
+        assert!(matches!(err, AppError::Forbidden(_)));
+    }
+
+    #[test]
+    fn admins_have_every_role() {
+        assert!(authorize_role(&user("artist"), &[UserRole::Artist]).is_ok());
+        assert!(authorize_role(&user("admin"), &[UserRole::Artist]).is_ok());
+        assert!(authorize_role(&user("user"), &[UserRole::Artist]).is_err());
+        assert!(authorize_role(&user("unknown"), &[UserRole::User]).is_err());
+    }
+
+    async fn status_for(claims: Option<Claims>) -> StatusCode {
+        let app = Router::new()
+            .route("/admin/songs", get(|| async { "ok" }))
+            .route_layer(RequireRole(UserRole::Admin));
+        let mut request = Request::builder().uri("/admin/songs").body(Body::empty()).unwrap();
+        if let Some(claims) = claims {
+            request.extensions_mut().insert(claims);
+        }
+        app.oneshot(request).await.unwrap().status()
+    }
+
+    fn claims(role: &str) -> Claims {
+        Claims {
+            sub: Uuid::new_v4().to_string(),
+            username: "someone".to_string(),
+            email: "someone@example.com".to_string(),
+            role: role.to_string(),
+            tier: "free".to_string(),
+            exp: u64::MAX,
+            iat: 0,
+        }
+    }
+
+    #[tokio::test]
+    async fn require_role_layer_rejects_other_roles() {
+        assert_eq!(status_for(Some(claims("admin"))).await, StatusCode::OK);
+        assert_eq!(status_for(Some(claims("artist"))).await, StatusCode::FORBIDDEN);
+        assert_eq!(status_for(None).await, StatusCode::UNAUTHORIZED);
+    }
+}
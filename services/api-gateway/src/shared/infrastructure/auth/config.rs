@@ -22,12 +22,12 @@ pub fn get_jwt_secret() -> Result<String, AppError> {
         ))
 }
 
-/// Get JWT access token expiry from environment (optional, defaults to 3600 seconds)
+/// Get JWT access token expiry from environment (optional, defaults to 900 seconds)
 pub fn get_jwt_access_token_expiry() -> u64 {
     std::env::var("JWT_ACCESS_TOKEN_EXPIRY")
         .ok()
         .and_then(|v| v.parse().ok())
-        .unwrap_or(3600) // Default: 1 hour
+        .unwrap_or(900) // Default: 15 minutes
 }
 
 /// Get JWT refresh token expiry from environment (optional, defaults to 2592000 seconds = 30 days)
@@ -67,7 +67,7 @@ mod tests {
     #[test]
     fn test_get_jwt_access_token_expiry_default() {
         std::env::remove_var("JWT_ACCESS_TOKEN_EXPIRY");
-        assert_eq!(get_jwt_access_token_expiry(), 3600);
+        assert_eq!(get_jwt_access_token_expiry(), 900);
     }
 
     #[test]
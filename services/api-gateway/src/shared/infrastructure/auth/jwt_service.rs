@@ -1,8 +1,13 @@
-use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
 use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::auth::config::{get_jwt_secret, get_jwt_access_token_expiry};
+
+/// Access tokens are short-lived: 15 minutes unless configured otherwise
+pub const DEFAULT_ACCESS_TOKEN_EXPIRY_SECS: u64 = 900;
 
 // =============================================================================
 // JWT SERVICE - Implementación real de autenticación
@@ -19,10 +24,26 @@ pub struct Claims {
     pub iat: u64,           // Issued at time
 }
 
+/// Token between a correct password and the second factor. It has none of the
+/// access-token claims, so it cannot be used as one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoFactorPendingClaims {
+    pub sub: String,        // User ID
+    pub purpose: String,    // Always TWO_FACTOR_PENDING_PURPOSE
+    pub exp: u64,
+    pub iat: u64,
+}
+
+pub const TWO_FACTOR_PENDING_PURPOSE: &str = "2fa_pending";
+
+/// The user has 5 minutes to enter the TOTP code after the password
+pub const TWO_FACTOR_PENDING_EXPIRY_SECS: u64 = 300;
+
+/// Access JWT plus the opaque refresh token of its session, if sessions are enabled
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TokenPair {
     pub access_token: String,
-    pub refresh_token: String,
+    pub refresh_token: Option<String>,
     pub expires_in: u64,
 }
 
@@ -30,7 +51,6 @@ pub struct JwtService {
     encoding_key: EncodingKey,
     decoding_key: DecodingKey,
     access_token_expiry: Duration,
-    refresh_token_expiry: Duration,
 }
 
 impl JwtService {
@@ -41,11 +61,35 @@ impl JwtService {
         Ok(Self {
             encoding_key,
             decoding_key,
-            access_token_expiry: Duration::from_secs(3600), // 1 hour
-            refresh_token_expiry: Duration::from_secs(2592000), // 30 days
+            access_token_expiry: Duration::from_secs(DEFAULT_ACCESS_TOKEN_EXPIRY_SECS),
         })
     }
     
+    /// Service configured from `JWT_SECRET` and `JWT_ACCESS_TOKEN_EXPIRY`.
+    /// Refresh tokens are opaque and live in `refresh_tokens`, not here.
+    pub fn from_env() -> Result<Self, AppError> {
+        let secret = get_jwt_secret()?;
+        Ok(Self::new(&secret)?
+            .with_access_token_expiry(Duration::from_secs(get_jwt_access_token_expiry())))
+    }
+    
+    pub fn with_access_token_expiry(mut self, expiry: Duration) -> Self {
+        self.access_token_expiry = expiry;
+        self
+    }
+    
+    pub fn access_token_expiry_secs(&self) -> u64 {
+        self.access_token_expiry.as_secs()
+    }
+    
+    /// Solo HS256 y sin margen sobre `exp`: un token caducado se rechaza en el acto
+    fn validation() -> Validation {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.leeway = 0;
+        validation.set_required_spec_claims(&["exp", "sub"]);
+        validation
+    }
+    
     /// Generate access token for user
     pub fn generate_access_token(
         &self,
@@ -72,99 +116,51 @@ impl JwtService {
             iat: now,
         };
         
-        encode(&Header::default(), &claims, &self.encoding_key)
+        encode(&Header::new(Algorithm::HS256), &claims, &self.encoding_key)
             .map_err(|e| AppError::InternalError(format!("Failed to encode JWT: {}", e)))
     }
     
-    /// Generate refresh token for user
-    pub fn generate_refresh_token(
-        &self,
-        user_id: Uuid,
-        username: &str,
-        email: &str,
-        role: &str,
-        tier: &str,
-    ) -> Result<String, AppError> {
-        let now = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .map_err(|_| AppError::InternalError("Failed to get current time".to_string()))?
-            .as_secs();
-        
-        let expires_at = now + self.refresh_token_expiry.as_secs();
-        
-        let claims = Claims {
-            sub: user_id.to_string(),
-            username: username.to_string(),
-            email: email.to_string(),
-            role: role.to_string(),
-            tier: tier.to_string(),
-            exp: expires_at,
-            iat: now,
-        };
-        
-        encode(&Header::default(), &claims, &self.encoding_key)
-            .map_err(|e| AppError::InternalError(format!("Failed to encode refresh JWT: {}", e)))
-    }
-    
-    /// Generate both access and refresh tokens
-    pub fn generate_token_pair(
-        &self,
-        user_id: Uuid,
-        username: &str,
-        email: &str,
-        role: &str,
-        tier: &str,
-    ) -> Result<TokenPair, AppError> {
-        let access_token = self.generate_access_token(user_id, username, email, role, tier)?;
-        let refresh_token = self.generate_refresh_token(user_id, username, email, role, tier)?;
-        
-        Ok(TokenPair {
-            access_token,
-            refresh_token,
-            expires_in: self.access_token_expiry.as_secs(),
-        })
-    }
-    
     /// Validate and decode access token
     pub fn validate_access_token(&self, token: &str) -> Result<Claims, AppError> {
         let token_data = decode::<Claims>(
             token,
             &self.decoding_key,
-            &Validation::default(),
+            &Self::validation(),
         )
         .map_err(|e| AppError::AuthenticationError(format!("Invalid token: {}", e)))?;
         
         Ok(token_data.claims)
     }
     
-    /// Validate and decode refresh token
-    pub fn validate_refresh_token(&self, token: &str) -> Result<Claims, AppError> {
-        let token_data = decode::<Claims>(
-            token,
-            &self.decoding_key,
-            &Validation::default(),
-        )
-        .map_err(|e| AppError::AuthenticationError(format!("Invalid refresh token: {}", e)))?;
+    /// "2FA pending" token for a user whose password was correct
+    pub fn generate_two_factor_pending_token(&self, user_id: Uuid) -> Result<String, AppError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| AppError::InternalError("Failed to get current time".to_string()))?
+            .as_secs();
         
-        Ok(token_data.claims)
+        let claims = TwoFactorPendingClaims {
+            sub: user_id.to_string(),
+            purpose: TWO_FACTOR_PENDING_PURPOSE.to_string(),
+            exp: now + TWO_FACTOR_PENDING_EXPIRY_SECS,
+            iat: now,
+        };
+        
+        encode(&Header::new(Algorithm::HS256), &claims, &self.encoding_key)
+            .map_err(|e| AppError::InternalError(format!("Failed to encode JWT: {}", e)))
     }
     
-    /// Refresh access token using refresh token
-    pub fn refresh_access_token(&self, refresh_token: &str) -> Result<String, AppError> {
-        let claims = self.validate_refresh_token(refresh_token)?;
+    /// User ID of a valid, unexpired "2FA pending" token
+    pub fn validate_two_factor_pending_token(&self, token: &str) -> Result<Uuid, AppError> {
+        let claims = decode::<TwoFactorPendingClaims>(token, &self.decoding_key, &Self::validation())
+            .map_err(|e| AppError::AuthenticationError(format!("Invalid token: {}", e)))?
+            .claims;
         
-        // Parse user ID from claims
-        let user_id = Uuid::parse_str(&claims.sub)
-            .map_err(|_| AppError::AuthenticationError("Invalid user ID in token".to_string()))?;
-        
-        // Generate new access token
-        self.generate_access_token(
-            user_id,
-            &claims.username,
-            &claims.email,
-            &claims.role,
-            &claims.tier,
-        )
+        if claims.purpose != TWO_FACTOR_PENDING_PURPOSE {
+            return Err(AppError::AuthenticationError("Invalid token: not a 2FA token".to_string()));
+        }
+        Uuid::parse_str(&claims.sub)
+            .map_err(|_| AppError::AuthenticationError("Invalid token: bad subject".to_string()))
     }
     
     /// Check if token is expired
@@ -183,24 +179,66 @@ impl JwtService {
 // PASSWORD HASHING SERVICE
 // =============================================================================
 
-use bcrypt::{hash, verify, DEFAULT_COST};
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
 
 pub struct PasswordService;
 
 impl PasswordService {
-    /// Hash a password using bcrypt
+    /// Hash a password using argon2id (PHC string format)
     pub fn hash_password(password: &str) -> Result<String, AppError> {
-        hash(password, DEFAULT_COST)
+        let salt = SaltString::generate(&mut OsRng);
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
             .map_err(|e| AppError::InternalError(format!("Failed to hash password: {}", e)))
     }
     
-    /// Verify a password against its hash
+    /// Verify a password against its hash. Bcrypt hashes created before the
+    /// switch to argon2id are still accepted (see `needs_rehash`).
     pub fn verify_password(password: &str, hash: &str) -> Result<bool, AppError> {
-        verify(password, hash)
-            .map_err(|e| AppError::InternalError(format!("Failed to verify password: {}", e)))
+        if Self::is_bcrypt_hash(hash) {
+            return bcrypt::verify(password, hash)
+                .map_err(|e| AppError::InternalError(format!("Failed to verify password: {}", e)));
+        }
+        let parsed = PasswordHash::new(hash)
+            .map_err(|e| AppError::InternalError(format!("Failed to verify password: {}", e)))?;
+        Ok(Argon2::default().verify_password(password.as_bytes(), &parsed).is_ok())
+    }
+    
+    /// Check a login attempt against the account's stored hash, if there is one.
+    /// Without an account the password is still verified against a dummy hash,
+    /// so a failed login takes the same time whether or not the user exists.
+    pub fn verify_credentials(password: &str, stored_hash: Option<&str>) -> bool {
+        match stored_hash {
+            Some(hash) => Self::verify_password(password, hash).unwrap_or(false),
+            None => {
+                let _ = Self::verify_password(password, dummy_hash());
+                false
+            }
+        }
+    }
+    
+    /// The hash predates argon2id and should be replaced after a successful login
+    pub fn needs_rehash(hash: &str) -> bool {
+        !hash.starts_with("$argon2id$")
+    }
+    
+    fn is_bcrypt_hash(hash: &str) -> bool {
+        hash.starts_with("$2a$") || hash.starts_with("$2b$") || hash.starts_with("$2y$")
     }
 }
 
+fn dummy_hash() -> &'static str {
+    static DUMMY_HASH: OnceLock<String> = OnceLock::new();
+    DUMMY_HASH.get_or_init(|| {
+        PasswordService::hash_password("vibestream-dummy-password")
+            .expect("argon2id with default parameters must hash")
+    })
+}
+
 // =============================================================================
 // TESTS
 // =============================================================================
@@ -216,7 +254,7 @@ mod tests {
         
         // Generate token
         let token = jwt_service
-            .generate_access_token(&user_id, "testuser", "test@example.com", "user", "bronze")
+            .generate_access_token(user_id, "testuser", "test@example.com", "user", "bronze")
             .unwrap();
         
         // Validate token
@@ -224,6 +262,74 @@ mod tests {
         assert_eq!(claims.sub, user_id.to_string());
         assert_eq!(claims.username, "testuser");
         assert_eq!(claims.email, "test@example.com");
+        assert_eq!(claims.role, "user");
+        assert_eq!(claims.tier, "bronze");
+        assert_eq!(claims.exp - claims.iat, DEFAULT_ACCESS_TOKEN_EXPIRY_SECS);
+    }
+    
+    #[test]
+    fn test_expired_token_is_rejected() {
+        let jwt_service = JwtService::new("test_secret").unwrap();
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+        let claims = Claims {
+            sub: Uuid::new_v4().to_string(),
+            username: "testuser".to_string(),
+            email: "test@example.com".to_string(),
+            role: "user".to_string(),
+            tier: "free".to_string(),
+            exp: now - 1,
+            iat: now - DEFAULT_ACCESS_TOKEN_EXPIRY_SECS - 1,
+        };
+        let token = encode(&Header::new(Algorithm::HS256), &claims, &EncodingKey::from_secret(b"test_secret")).unwrap();
+        
+        assert!(matches!(jwt_service.validate_access_token(&token), Err(AppError::AuthenticationError(_))));
+    }
+    
+    #[test]
+    fn test_tampered_token_is_rejected() {
+        let jwt_service = JwtService::new("test_secret").unwrap();
+        let token = jwt_service
+            .generate_access_token(Uuid::new_v4(), "testuser", "test@example.com", "user", "free")
+            .unwrap();
+        let parts: Vec<&str> = token.split('.').collect();
+        
+        // Firma alterada
+        let mut signature = parts[2].to_string();
+        let last = if signature.ends_with('A') { "B" } else { "A" };
+        signature.replace_range(signature.len() - 1.., last);
+        let tampered = format!("{}.{}.{}", parts[0], parts[1], signature);
+        assert!(jwt_service.validate_access_token(&tampered).is_err());
+        
+        // Claims cambiadas con la firma original (elevación de rol)
+        let mut claims = jwt_service.validate_access_token(&token).unwrap();
+        claims.role = "admin".to_string();
+        let forged = encode(&Header::new(Algorithm::HS256), &claims, &EncodingKey::from_secret(b"other_secret")).unwrap();
+        let forged_payload = forged.split('.').nth(1).unwrap();
+        let escalated = format!("{}.{}.{}", parts[0], forged_payload, parts[2]);
+        assert!(jwt_service.validate_access_token(&escalated).is_err());
+        
+        // Firmado con otra clave
+        assert!(jwt_service.validate_access_token(&forged).is_err());
+        
+        // Algoritmo distinto de HS256
+        let hs512 = encode(&Header::new(Algorithm::HS512), &claims, &EncodingKey::from_secret(b"test_secret")).unwrap();
+        assert!(jwt_service.validate_access_token(&hs512).is_err());
+    }
+    
+    #[test]
+    fn test_two_factor_pending_token_is_not_an_access_token() {
+        let jwt_service = JwtService::new("test_secret").unwrap();
+        let user_id = Uuid::new_v4();
+        let pending = jwt_service.generate_two_factor_pending_token(user_id).unwrap();
+        
+        assert_eq!(jwt_service.validate_two_factor_pending_token(&pending).unwrap(), user_id);
+        assert!(jwt_service.validate_access_token(&pending).is_err());
+        
+        // Y un access token no sirve para completar el segundo factor
+        let access = jwt_service
+            .generate_access_token(user_id, "testuser", "test@example.com", "user", "free")
+            .unwrap();
+        assert!(jwt_service.validate_two_factor_pending_token(&access).is_err());
     }
     
     #[test]
@@ -240,5 +346,28 @@ mod tests {
         // Verify wrong password
         let is_valid = PasswordService::verify_password("wrongpassword", &hash).unwrap();
         assert!(!is_valid);
+        
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(!PasswordService::needs_rehash(&hash));
+    }
+    
+    #[test]
+    fn test_legacy_bcrypt_hashes_still_verify() {
+        let legacy = bcrypt::hash("testpassword123", 4).unwrap();
+        
+        assert!(PasswordService::verify_password("testpassword123", &legacy).unwrap());
+        assert!(!PasswordService::verify_password("wrongpassword", &legacy).unwrap());
+        assert!(PasswordService::needs_rehash(&legacy));
+    }
+    
+    #[test]
+    fn test_unknown_user_never_authenticates() {
+        let hash = PasswordService::hash_password("testpassword123").unwrap();
+        
+        assert!(PasswordService::verify_credentials("testpassword123", Some(&hash)));
+        assert!(!PasswordService::verify_credentials("wrongpassword", Some(&hash)));
+        assert!(!PasswordService::verify_credentials("testpassword123", None));
+        assert!(!PasswordService::verify_credentials("vibestream-dummy-password", None));
+        assert!(!PasswordService::verify_credentials("testpassword123", Some("not-a-hash")));
     }
 }
@@ -12,7 +12,7 @@ use crate::shared::infrastructure::auth::{JwtService, Claims, config::get_jwt_se
 use std::sync::Arc;
 
 /// Extract JWT token from Authorization header
-fn extract_token(headers: &HeaderMap) -> Option<String> {
+pub(crate) fn extract_token(headers: &HeaderMap) -> Option<String> {
     headers
         .get("authorization")
         .and_then(|header| header.to_str().ok())
@@ -96,8 +96,10 @@ pub fn extract_claims(request: &Request) -> Option<Claims> {
 // AUTHENTICATED USER EXTRACTOR
 // =============================================================================
 // 
-// Extractor de Axum para obtener el usuario autenticado directamente en handlers
-// Usa las claims insertadas por jwt_auth_middleware
+// Extractor de Axum para obtener el usuario autenticado directamente en handlers.
+// Usa las claims insertadas por jwt_auth_middleware y, si la ruta no pasa por el
+// middleware, valida él mismo el header Authorization. Los handlers protegidos
+// deben tomar el user id de aquí y nunca del body.
 
 use axum::{
     extract::FromRequestParts,
@@ -117,18 +119,60 @@ pub struct AuthenticatedUser {
     pub tier: String,
 }
 
-impl From<Claims> for AuthenticatedUser {
-    fn from(claims: Claims) -> Self {
+impl TryFrom<Claims> for AuthenticatedUser {
+    type Error = AppError;
+
+    fn try_from(claims: Claims) -> Result<Self, Self::Error> {
         let user_id = Uuid::parse_str(&claims.sub)
-            .unwrap_or_else(|_| Uuid::nil()); // Fallback si no se puede parsear
+            .map_err(|_| AppError::AuthenticationError("Invalid user ID in token".to_string()))?;
         
-        Self {
+        Ok(Self {
             user_id,
             username: claims.username,
             email: claims.email,
             role: claims.role,
             tier: claims.tier,
-        }
+        })
+    }
+}
+
+type AuthRejection = (StatusCode, axum::response::Json<serde_json::Value>);
+
+fn auth_rejection(status: StatusCode, message: &str, error: &str) -> AuthRejection {
+    (
+        status,
+        axum::response::Json(serde_json::json!({
+            "success": false,
+            "message": message,
+            "error": error
+        })),
+    )
+}
+
+impl AuthenticatedUser {
+    /// Resolve the user of a request with the given JWT service
+    pub fn from_parts_with(parts: &Parts, jwt_service: &JwtService) -> Result<Self, AuthRejection> {
+        let claims = match parts.extensions.get::<Claims>() {
+            Some(claims) => claims.clone(),
+            None => {
+                let token = extract_token(&parts.headers).ok_or_else(|| auth_rejection(
+                    StatusCode::UNAUTHORIZED,
+                    "Falta el header Authorization: Bearer <token>",
+                    "Missing or invalid authorization header",
+                ))?;
+                jwt_service.validate_access_token(&token).map_err(|_| auth_rejection(
+                    StatusCode::UNAUTHORIZED,
+                    "Token inválido o caducado",
+                    "Invalid or expired token",
+                ))?
+            }
+        };
+        
+        Self::try_from(claims).map_err(|_| auth_rejection(
+            StatusCode::UNAUTHORIZED,
+            "ID de usuario inválido en el token",
+            "Invalid user ID in token",
+        ))
     }
 }
 
@@ -137,41 +181,23 @@ impl<S> FromRequestParts<S> for AuthenticatedUser
 where
     S: Send + Sync,
 {
-    type Rejection = (axum::http::StatusCode, axum::response::Json<serde_json::Value>);
+    type Rejection = AuthRejection;
 
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
-        // Extraer Claims de las extensions (insertadas por jwt_auth_middleware)
-        let claims = parts
-            .extensions
-            .get::<Claims>()
-            .cloned()
-            .ok_or((
-                axum::http::StatusCode::UNAUTHORIZED,
-                axum::response::Json(serde_json::json!({
-                    "success": false,
-                    "message": "No se encontraron claims de autenticación. Asegúrate de usar jwt_auth_middleware.",
-                    "error": "Missing authentication claims"
-                })),
-            ))?;
-        
-        // Convertir Claims a AuthenticatedUser
-        let user_id = Uuid::parse_str(&claims.sub)
-            .map_err(|_| (
-                axum::http::StatusCode::UNAUTHORIZED,
-                axum::response::Json(serde_json::json!({
-                    "success": false,
-                    "message": "ID de usuario inválido en el token",
-                    "error": "Invalid user ID in token"
-                })),
-            ))?;
+        // Sin claims ni header no hace falta la configuración JWT para responder 401
+        if parts.extensions.get::<Claims>().is_none() && extract_token(&parts.headers).is_none() {
+            return Err(auth_rejection(
+                StatusCode::UNAUTHORIZED,
+                "Falta el header Authorization: Bearer <token>",
+                "Missing or invalid authorization header",
+            ));
+        }
         
-        Ok(AuthenticatedUser {
-            user_id,
-            username: claims.username,
-            email: claims.email,
-            role: claims.role,
-            tier: claims.tier,
-        })
+        let jwt_service = JwtService::from_env().map_err(|e| {
+            tracing::error!("JWT configuration error: {}", e);
+            auth_rejection(StatusCode::INTERNAL_SERVER_ERROR, "Error de configuración JWT", "JWT configuration error")
+        })?;
+        Self::from_parts_with(parts, &jwt_service)
     }
 }
 
@@ -204,3 +230,59 @@ pub async fn optional_jwt_auth_middleware(
     next.run(request).await
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request as HttpRequest;
+
+    fn parts(authorization: Option<&str>) -> Parts {
+        let mut builder = HttpRequest::builder().uri("/api/v1/music/songs");
+        if let Some(value) = authorization {
+            builder = builder.header("authorization", value);
+        }
+        builder.body(()).unwrap().into_parts().0
+    }
+
+    #[tokio::test]
+    async fn missing_authorization_header_is_rejected_with_401() {
+        let (status, body) = AuthenticatedUser::from_request_parts(&mut parts(None), &()).await.unwrap_err();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body.0["success"], false);
+
+        // Un esquema distinto de Bearer cuenta como ausente
+        let (status, _) = AuthenticatedUser::from_request_parts(&mut parts(Some("Basic ZGVtbzpwYXNzd29yZA==")), &())
+            .await
+            .unwrap_err();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn bearer_token_resolves_the_user() {
+        let jwt_service = JwtService::new("test_secret").unwrap();
+        let user_id = Uuid::new_v4();
+        let token = jwt_service
+            .generate_access_token(user_id, "producer", "producer@example.com", "artist", "premium")
+            .unwrap();
+
+        let user = AuthenticatedUser::from_parts_with(&parts(Some(&format!("Bearer {}", token))), &jwt_service).unwrap();
+        assert_eq!(user.user_id, user_id);
+        assert_eq!(user.role, "artist");
+        assert_eq!(user.tier, "premium");
+    }
+
+    #[test]
+    fn invalid_tokens_are_rejected_with_401() {
+        let jwt_service = JwtService::new("test_secret").unwrap();
+        let other = JwtService::new("other_secret").unwrap();
+        let token = other
+            .generate_access_token(Uuid::new_v4(), "producer", "producer@example.com", "admin", "vip")
+            .unwrap();
+
+        let (status, _) = AuthenticatedUser::from_parts_with(&parts(Some(&format!("Bearer {}", token))), &jwt_service).unwrap_err();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+        let (status, _) = AuthenticatedUser::from_parts_with(&parts(Some("Bearer not.a.jwt")), &jwt_service).unwrap_err();
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+}
@@ -1,12 +1,52 @@
 pub mod jwt_service;
 pub mod middleware;
+pub mod authorization;
 pub mod config;
+pub mod refresh_tokens;
+pub mod wallet_auth;
+pub mod two_factor;
+pub mod signed_links;
 
-pub use jwt_service::{JwtService, PasswordService, Claims, TokenPair};
+pub use jwt_service::{JwtService, PasswordService, Claims, TokenPair, TwoFactorPendingClaims};
 pub use middleware::{
     jwt_auth_middleware, 
     optional_jwt_auth_middleware, 
     extract_claims,
     AuthenticatedUser,
 };
+pub use authorization::{
+    authorize_owner,
+    authorize_role,
+    OwnedResource,
+    Principal,
+    RequireRole,
+};
+pub use refresh_tokens::{
+    DeviceMetadata,
+    RefreshSession,
+    RefreshTokenError,
+    RefreshTokenService,
+    RefreshTokenStore,
+    PostgresRefreshTokenStore,
+};
+pub use wallet_auth::{
+    VerifiedWallet,
+    WalletAuthError,
+    WalletAuthService,
+    WalletChain,
+    WalletChallenge,
+    WalletNonceStore,
+    RedisWalletNonceStore,
+};
+pub use two_factor::{
+    TotpSecret,
+    TwoFactorError,
+    TwoFactorMethod,
+    TwoFactorRecord,
+    TwoFactorService,
+    TwoFactorSetup,
+    TwoFactorStore,
+    PostgresTwoFactorStore,
+};
+pub use signed_links::{LinkSignature, SignedLinkService};
 pub use config::{get_jwt_secret, get_jwt_access_token_expiry, get_jwt_refresh_token_expiry};
@@ -0,0 +1,600 @@
+//! Opaque refresh tokens with rotation
+//!
+//! A refresh token is 32 random bytes handed to the client once; only its
+//! SHA-256 is stored. Every login starts a family (one per device session) and
+//! every refresh replaces the presented token with a new one of the same
+//! family. A token that was already replaced can only be presented again if it
+//! was copied, so that revokes the whole family: both the thief and the
+//! legitimate device have to log in again.
+
+use async_trait::async_trait;
+use axum::http::HeaderMap;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::auth::config::get_jwt_refresh_token_expiry;
+
+pub const REVOKED_ON_LOGOUT: &str = "logout";
+pub const REVOKED_BY_USER: &str = "revoked_by_user";
+pub const REVOKED_ON_REUSE: &str = "reuse_detected";
+pub const REVOKED_ON_ACCOUNT_DELETION: &str = "account_deleted";
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum RefreshTokenError {
+    #[error("Invalid refresh token")]
+    Invalid,
+    #[error("Refresh token expired")]
+    Expired,
+    #[error("Refresh token revoked")]
+    Revoked,
+    #[error("Refresh token reused, session revoked")]
+    Reused,
+    #[error(transparent)]
+    Store(#[from] AppError),
+}
+
+impl From<RefreshTokenError> for AppError {
+    fn from(error: RefreshTokenError) -> Self {
+        match error {
+            RefreshTokenError::Store(e) => e,
+            other => AppError::AuthenticationError(other.to_string()),
+        }
+    }
+}
+
+/// Device a session was started (or last refreshed) from
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct DeviceMetadata {
+    pub device_name: Option<String>,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+}
+
+impl DeviceMetadata {
+    /// `User-Agent` and the client address (`X-Forwarded-For`, then `X-Real-IP`)
+    pub fn from_headers(headers: &HeaderMap, device_name: Option<String>) -> Self {
+        let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok()).map(str::trim);
+        let ip_address = header("x-forwarded-for")
+            .and_then(|forwarded| forwarded.split(',').next())
+            .or_else(|| header("x-real-ip"))
+            .map(|ip| ip.trim().chars().take(45).collect())
+            .filter(|ip: &String| !ip.is_empty());
+        Self {
+            device_name: device_name
+                .map(|name| name.trim().chars().take(100).collect::<String>())
+                .filter(|name| !name.is_empty()),
+            user_agent: header("user-agent").map(str::to_string),
+            ip_address,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RefreshTokenRecord {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub family_id: Uuid,
+    pub token_hash: String,
+    pub device: DeviceMetadata,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub replaced_by: Option<Uuid>,
+    pub revoked_at: Option<DateTime<Utc>>,
+    pub revoked_reason: Option<String>,
+}
+
+/// An active device session: the current token of a family
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RefreshSession {
+    pub session_id: Uuid,
+    #[serde(flatten)]
+    pub device: DeviceMetadata,
+    pub started_at: DateTime<Utc>,
+    pub last_refreshed_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait RefreshTokenStore: Send + Sync {
+    async fn insert(&self, record: &RefreshTokenRecord) -> Result<(), AppError>;
+
+    async fn find_by_hash(&self, token_hash: &str) -> Result<Option<RefreshTokenRecord>, AppError>;
+
+    /// Store `next` as the replacement of `current`. Returns false (and stores
+    /// nothing) when `current` was already replaced or revoked meanwhile.
+    async fn rotate(&self, current: Uuid, next: &RefreshTokenRecord) -> Result<bool, AppError>;
+
+    /// Revoke every live token of one of the user's families; returns how many
+    async fn revoke_family(&self, user_id: Uuid, family_id: Uuid, reason: &str, at: DateTime<Utc>) -> Result<u64, AppError>;
+
+    async fn active_sessions(&self, user_id: Uuid, now: DateTime<Utc>) -> Result<Vec<RefreshSession>, AppError>;
+}
+
+/// A freshly issued token: `token` is only ever known to the client
+#[derive(Debug, Clone)]
+pub struct IssuedRefreshToken {
+    pub token: String,
+    pub record: RefreshTokenRecord,
+}
+
+pub struct RefreshTokenService {
+    store: Arc<dyn RefreshTokenStore>,
+    ttl: Duration,
+}
+
+impl RefreshTokenService {
+    pub fn new(store: Arc<dyn RefreshTokenStore>, ttl: Duration) -> Self {
+        Self { store, ttl }
+    }
+
+    /// Postgres-backed, with the TTL from `JWT_REFRESH_TOKEN_EXPIRY`
+    pub fn postgres(pool: PgPool) -> Self {
+        Self::new(
+            Arc::new(PostgresRefreshTokenStore::new(pool)),
+            Duration::seconds(get_jwt_refresh_token_expiry() as i64),
+        )
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Start a new session (token family) for the user
+    pub async fn issue(&self, user_id: Uuid, device: DeviceMetadata) -> Result<IssuedRefreshToken, AppError> {
+        let issued = self.new_token(user_id, Uuid::new_v4(), device, Utc::now());
+        self.store.insert(&issued.record).await?;
+        Ok(issued)
+    }
+
+    /// Exchange `token` for a new one of the same family
+    pub async fn rotate(&self, token: &str, device: DeviceMetadata) -> Result<IssuedRefreshToken, RefreshTokenError> {
+        let now = Utc::now();
+        let current = self
+            .store
+            .find_by_hash(&hash_token(token))
+            .await?
+            .ok_or(RefreshTokenError::Invalid)?;
+
+        if current.revoked_at.is_some() {
+            return Err(RefreshTokenError::Revoked);
+        }
+        if current.replaced_by.is_some() {
+            return Err(self.revoke_reused(&current, now).await);
+        }
+        if current.expires_at <= now {
+            return Err(RefreshTokenError::Expired);
+        }
+
+        let device = DeviceMetadata {
+            device_name: device.device_name.or(current.device.device_name.clone()),
+            ..device
+        };
+        let next = self.new_token(current.user_id, current.family_id, device, now);
+        // Otra petición rotó el mismo token a la vez: uno de los dos es una copia
+        if !self.store.rotate(current.id, &next.record).await? {
+            return Err(self.revoke_reused(&current, now).await);
+        }
+        Ok(next)
+    }
+
+    /// Logout: revoke the session the token belongs to. Unknown tokens are ignored.
+    pub async fn revoke(&self, token: &str) -> Result<(), AppError> {
+        if let Some(record) = self.store.find_by_hash(&hash_token(token)).await? {
+            self.store
+                .revoke_family(record.user_id, record.family_id, REVOKED_ON_LOGOUT, Utc::now())
+                .await?;
+        }
+        Ok(())
+    }
+
+    pub async fn sessions(&self, user_id: Uuid) -> Result<Vec<RefreshSession>, AppError> {
+        self.store.active_sessions(user_id, Utc::now()).await
+    }
+
+    /// Revoke one of the user's sessions; false if the user has no such live session
+    pub async fn revoke_session(&self, user_id: Uuid, session_id: Uuid) -> Result<bool, AppError> {
+        let revoked = self
+            .store
+            .revoke_family(user_id, session_id, REVOKED_BY_USER, Utc::now())
+            .await?;
+        Ok(revoked > 0)
+    }
+
+    /// Revoke every session of the user, e.g. when the account is deleted
+    pub async fn revoke_all(&self, user_id: Uuid, reason: &str) -> Result<u64, AppError> {
+        let now = Utc::now();
+        let mut revoked = 0;
+        for session in self.store.active_sessions(user_id, now).await? {
+            revoked += self.store.revoke_family(user_id, session.session_id, reason, now).await?;
+        }
+        Ok(revoked)
+    }
+
+    async fn revoke_reused(&self, record: &RefreshTokenRecord, now: DateTime<Utc>) -> RefreshTokenError {
+        tracing::warn!(
+            "Refresh token reuse for user {} (session {}), revoking the session",
+            record.user_id,
+            record.family_id
+        );
+        match self
+            .store
+            .revoke_family(record.user_id, record.family_id, REVOKED_ON_REUSE, now)
+            .await
+        {
+            Ok(_) => RefreshTokenError::Reused,
+            Err(e) => RefreshTokenError::Store(e),
+        }
+    }
+
+    fn new_token(&self, user_id: Uuid, family_id: Uuid, device: DeviceMetadata, now: DateTime<Utc>) -> IssuedRefreshToken {
+        let token = hex::encode(rand::random::<[u8; 32]>());
+        IssuedRefreshToken {
+            record: RefreshTokenRecord {
+                id: Uuid::new_v4(),
+                user_id,
+                family_id,
+                token_hash: hash_token(&token),
+                device,
+                created_at: now,
+                expires_at: now + self.ttl,
+                replaced_by: None,
+                revoked_at: None,
+                revoked_reason: None,
+            },
+            token,
+        }
+    }
+}
+
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+// =============================================================================
+// POSTGRES STORE
+// =============================================================================
+
+pub struct PostgresRefreshTokenStore {
+    pool: PgPool,
+}
+
+impl PostgresRefreshTokenStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_error(e: sqlx::Error) -> AppError {
+    AppError::DatabaseError(format!("Refresh token store error: {}", e))
+}
+
+async fn insert_record<'e, E>(executor: E, record: &RefreshTokenRecord) -> Result<(), AppError>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    sqlx::query(
+        r#"
+        INSERT INTO refresh_tokens (
+            id, user_id, family_id, token_hash, device_name, user_agent, ip_address, created_at, expires_at
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+        "#,
+    )
+    .bind(record.id)
+    .bind(record.user_id)
+    .bind(record.family_id)
+    .bind(&record.token_hash)
+    .bind(&record.device.device_name)
+    .bind(&record.device.user_agent)
+    .bind(&record.device.ip_address)
+    .bind(record.created_at)
+    .bind(record.expires_at)
+    .execute(executor)
+    .await
+    .map_err(db_error)?;
+    Ok(())
+}
+
+#[async_trait]
+impl RefreshTokenStore for PostgresRefreshTokenStore {
+    async fn insert(&self, record: &RefreshTokenRecord) -> Result<(), AppError> {
+        insert_record(&self.pool, record).await
+    }
+
+    async fn find_by_hash(&self, token_hash: &str) -> Result<Option<RefreshTokenRecord>, AppError> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, user_id, family_id, token_hash, device_name, user_agent, ip_address,
+                   created_at, expires_at, replaced_by, revoked_at, revoked_reason
+            FROM refresh_tokens
+            WHERE token_hash = $1
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        row.map(|row| {
+            Ok(RefreshTokenRecord {
+                id: row.try_get("id").map_err(db_error)?,
+                user_id: row.try_get("user_id").map_err(db_error)?,
+                family_id: row.try_get("family_id").map_err(db_error)?,
+                token_hash: row.try_get("token_hash").map_err(db_error)?,
+                device: DeviceMetadata {
+                    device_name: row.try_get("device_name").map_err(db_error)?,
+                    user_agent: row.try_get("user_agent").map_err(db_error)?,
+                    ip_address: row.try_get("ip_address").map_err(db_error)?,
+                },
+                created_at: row.try_get("created_at").map_err(db_error)?,
+                expires_at: row.try_get("expires_at").map_err(db_error)?,
+                replaced_by: row.try_get("replaced_by").map_err(db_error)?,
+                revoked_at: row.try_get("revoked_at").map_err(db_error)?,
+                revoked_reason: row.try_get("revoked_reason").map_err(db_error)?,
+            })
+        })
+        .transpose()
+    }
+
+    async fn rotate(&self, current: Uuid, next: &RefreshTokenRecord) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        insert_record(&mut *tx, next).await?;
+
+        let updated = sqlx::query(
+            r#"
+            UPDATE refresh_tokens
+            SET replaced_by = $2, last_used_at = $3
+            WHERE id = $1 AND replaced_by IS NULL AND revoked_at IS NULL
+            "#,
+        )
+        .bind(current)
+        .bind(next.id)
+        .bind(next.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .rows_affected();
+
+        if updated == 0 {
+            tx.rollback().await.map_err(db_error)?;
+            return Ok(false);
+        }
+        tx.commit().await.map_err(db_error)?;
+        Ok(true)
+    }
+
+    async fn revoke_family(&self, user_id: Uuid, family_id: Uuid, reason: &str, at: DateTime<Utc>) -> Result<u64, AppError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = $3, revoked_reason = $4
+            WHERE user_id = $1 AND family_id = $2 AND revoked_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(family_id)
+        .bind(at)
+        .bind(reason)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?;
+        Ok(result.rows_affected())
+    }
+
+    async fn active_sessions(&self, user_id: Uuid, now: DateTime<Utc>) -> Result<Vec<RefreshSession>, AppError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT current.family_id, current.device_name, current.user_agent, current.ip_address,
+                   current.created_at, current.expires_at,
+                   (SELECT MIN(created_at) FROM refresh_tokens f WHERE f.family_id = current.family_id) AS started_at
+            FROM refresh_tokens current
+            WHERE current.user_id = $1
+              AND current.replaced_by IS NULL
+              AND current.revoked_at IS NULL
+              AND current.expires_at > $2
+            ORDER BY current.created_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        rows.iter()
+            .map(|row| {
+                let last_refreshed_at: DateTime<Utc> = row.try_get("created_at").map_err(db_error)?;
+                Ok(RefreshSession {
+                    session_id: row.try_get("family_id").map_err(db_error)?,
+                    device: DeviceMetadata {
+                        device_name: row.try_get("device_name").map_err(db_error)?,
+                        user_agent: row.try_get("user_agent").map_err(db_error)?,
+                        ip_address: row.try_get("ip_address").map_err(db_error)?,
+                    },
+                    started_at: row
+                        .try_get::<Option<DateTime<Utc>>, _>("started_at")
+                        .map_err(db_error)?
+                        .unwrap_or(last_refreshed_at),
+                    last_refreshed_at,
+                    expires_at: row.try_get("expires_at").map_err(db_error)?,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct InMemoryStore {
+        records: Mutex<Vec<RefreshTokenRecord>>,
+    }
+
+    #[async_trait]
+    impl RefreshTokenStore for InMemoryStore {
+        async fn insert(&self, record: &RefreshTokenRecord) -> Result<(), AppError> {
+            self.records.lock().unwrap().push(record.clone());
+            Ok(())
+        }
+
+        async fn find_by_hash(&self, token_hash: &str) -> Result<Option<RefreshTokenRecord>, AppError> {
+            Ok(self.records.lock().unwrap().iter().find(|r| r.token_hash == token_hash).cloned())
+        }
+
+        async fn rotate(&self, current: Uuid, next: &RefreshTokenRecord) -> Result<bool, AppError> {
+            let mut records = self.records.lock().unwrap();
+            let Some(record) = records
+                .iter_mut()
+                .find(|r| r.id == current && r.replaced_by.is_none() && r.revoked_at.is_none())
+            else {
+                return Ok(false);
+            };
+            record.replaced_by = Some(next.id);
+            records.push(next.clone());
+            Ok(true)
+        }
+
+        async fn revoke_family(&self, user_id: Uuid, family_id: Uuid, reason: &str, at: DateTime<Utc>) -> Result<u64, AppError> {
+            let mut revoked = 0;
+            for record in self.records.lock().unwrap().iter_mut() {
+                if record.user_id == user_id && record.family_id == family_id && record.revoked_at.is_none() {
+                    record.revoked_at = Some(at);
+                    record.revoked_reason = Some(reason.to_string());
+                    revoked += 1;
+                }
+            }
+            Ok(revoked)
+        }
+
+        async fn active_sessions(&self, user_id: Uuid, now: DateTime<Utc>) -> Result<Vec<RefreshSession>, AppError> {
+            Ok(self
+                .records
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|r| r.user_id == user_id && r.replaced_by.is_none() && r.revoked_at.is_none() && r.expires_at > now)
+                .map(|r| RefreshSession {
+                    session_id: r.family_id,
+                    device: r.device.clone(),
+                    started_at: r.created_at,
+                    last_refreshed_at: r.created_at,
+                    expires_at: r.expires_at,
+                })
+                .collect())
+        }
+    }
+
+    fn service() -> (RefreshTokenService, Arc<InMemoryStore>) {
+        let store = Arc::new(InMemoryStore::default());
+        (RefreshTokenService::new(store.clone(), Duration::days(30)), store)
+    }
+
+    fn phone() -> DeviceMetadata {
+        DeviceMetadata { device_name: Some("Pixel 8".to_string()), ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn every_refresh_rotates_the_token() {
+        let (service, store) = service();
+        let user_id = Uuid::new_v4();
+        let login = service.issue(user_id, phone()).await.unwrap();
+
+        let first = service.rotate(&login.token, DeviceMetadata::default()).await.unwrap();
+        let second = service.rotate(&first.token, DeviceMetadata::default()).await.unwrap();
+
+        assert_ne!(first.token, login.token);
+        assert_ne!(second.token, first.token);
+        assert_eq!(second.record.family_id, login.record.family_id);
+        assert_eq!(second.record.user_id, user_id);
+        // El nombre del dispositivo se conserva entre rotaciones
+        assert_eq!(second.record.device.device_name.as_deref(), Some("Pixel 8"));
+        // Solo se guarda el hash
+        assert!(store.records.lock().unwrap().iter().all(|r| r.token_hash != second.token));
+
+        let sessions = service.sessions(user_id).await.unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].session_id, login.record.family_id);
+    }
+
+    #[tokio::test]
+    async fn reusing_a_rotated_token_revokes_the_family() {
+        let (service, _) = service();
+        let user_id = Uuid::new_v4();
+        let login = service.issue(user_id, phone()).await.unwrap();
+        let other_device = service.issue(user_id, DeviceMetadata::default()).await.unwrap();
+
+        let rotated = service.rotate(&login.token, DeviceMetadata::default()).await.unwrap();
+
+        // El token antiguo vuelve a aparecer: alguien lo copió
+        assert!(matches!(
+            service.rotate(&login.token, DeviceMetadata::default()).await,
+            Err(RefreshTokenError::Reused)
+        ));
+        // El token legítimo más reciente también queda invalidado
+        assert!(matches!(
+            service.rotate(&rotated.token, DeviceMetadata::default()).await,
+            Err(RefreshTokenError::Revoked)
+        ));
+        // Las demás sesiones no se ven afectadas
+        assert!(service.rotate(&other_device.token, DeviceMetadata::default()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn revoked_families_are_rejected() {
+        let (service, _) = service();
+        let user_id = Uuid::new_v4();
+        let laptop = service.issue(user_id, DeviceMetadata::default()).await.unwrap();
+        let phone = service.issue(user_id, phone()).await.unwrap();
+
+        // Logout desde el portátil
+        service.revoke(&laptop.token).await.unwrap();
+        assert!(matches!(
+            service.rotate(&laptop.token, DeviceMetadata::default()).await,
+            Err(RefreshTokenError::Revoked)
+        ));
+
+        // Otro usuario no puede revocar la sesión del teléfono
+        assert!(!service.revoke_session(Uuid::new_v4(), phone.record.family_id).await.unwrap());
+        assert!(service.revoke_session(user_id, phone.record.family_id).await.unwrap());
+        assert!(matches!(
+            service.rotate(&phone.token, DeviceMetadata::default()).await,
+            Err(RefreshTokenError::Revoked)
+        ));
+        assert!(service.sessions(user_id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn unknown_and_expired_tokens_are_rejected() {
+        let store = Arc::new(InMemoryStore::default());
+        let service = RefreshTokenService::new(store.clone(), Duration::seconds(-1));
+        let expired = service.issue(Uuid::new_v4(), DeviceMetadata::default()).await.unwrap();
+
+        assert!(matches!(
+            service.rotate(&expired.token, DeviceMetadata::default()).await,
+            Err(RefreshTokenError::Expired)
+        ));
+        assert!(matches!(
+            service.rotate("not-a-token", DeviceMetadata::default()).await,
+            Err(RefreshTokenError::Invalid)
+        ));
+    }
+
+    #[test]
+    fn device_metadata_comes_from_the_request_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("user-agent", "VibeStream/2.1 (iOS 18)".parse().unwrap());
+        headers.insert("x-forwarded-for", "203.0.113.7, 10.0.0.1".parse().unwrap());
+
+        let device = DeviceMetadata::from_headers(&headers, Some("  ".to_string()));
+        assert_eq!(device.user_agent.as_deref(), Some("VibeStream/2.1 (iOS 18)"));
+        assert_eq!(device.ip_address.as_deref(), Some("203.0.113.7"));
+        assert_eq!(device.device_name, None);
+    }
+}
@@ -0,0 +1,670 @@
+//! Two-factor authentication with TOTP (RFC 6238) and recovery codes
+//!
+//! Setup hands the user a secret, as an `otpauth://` URI for the authenticator
+//! app, and a set of recovery codes. Nothing changes at login until the user
+//! confirms the setup with a first valid code. From then on a correct password
+//! is not enough: the user also has to present the current TOTP code (the
+//! previous and next 30 s steps are accepted for clock drift) or one of the
+//! recovery codes. A TOTP step is accepted once and recovery codes are
+//! single-use; only their SHA-256 is stored.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use sqlx::{PgPool, Row};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+
+pub const TOTP_STEP_SECS: i64 = 30;
+pub const TOTP_DIGITS: u32 = 6;
+/// Steps accepted on each side of the current one
+pub const TOTP_ALLOWED_DRIFT_STEPS: i64 = 1;
+pub const RECOVERY_CODE_COUNT: usize = 10;
+/// 160 bits, the HMAC-SHA1 block the RFC recommends
+const SECRET_BYTES: usize = 20;
+const DEFAULT_TOTP_ISSUER: &str = "VibeStream";
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum TwoFactorError {
+    #[error("Two-factor authentication is already enabled")]
+    AlreadyEnabled,
+    #[error("Two-factor authentication is not enabled")]
+    NotEnabled,
+    #[error("Two-factor setup has not been started")]
+    SetupNotStarted,
+    #[error("Invalid two-factor code")]
+    InvalidCode,
+    #[error(transparent)]
+    Store(#[from] AppError),
+}
+
+impl From<TwoFactorError> for AppError {
+    fn from(error: TwoFactorError) -> Self {
+        match error {
+            TwoFactorError::Store(e) => e,
+            TwoFactorError::InvalidCode => AppError::AuthenticationError(error.to_string()),
+            other => AppError::ValidationError(other.to_string()),
+        }
+    }
+}
+
+/// Shared secret between the server and the authenticator app
+#[derive(Clone, PartialEq, Eq)]
+pub struct TotpSecret(Vec<u8>);
+
+impl TotpSecret {
+    pub fn generate() -> Self {
+        Self(rand::random::<[u8; SECRET_BYTES]>().to_vec())
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+
+    /// Base32 without padding, as authenticator apps expect it
+    pub fn from_base32(encoded: &str) -> Option<Self> {
+        let normalized: String = encoded
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != '=')
+            .collect::<String>()
+            .to_ascii_uppercase();
+        BASE32_NOPAD.decode(normalized.as_bytes()).ok().filter(|bytes| !bytes.is_empty()).map(Self)
+    }
+
+    pub fn to_base32(&self) -> String {
+        BASE32_NOPAD.encode(&self.0)
+    }
+
+    /// Code of the 30 s step `now` falls in
+    pub fn code_at(&self, now: DateTime<Utc>) -> String {
+        self.code_for_step(totp_step(now))
+    }
+
+    fn code_for_step(&self, step: i64) -> String {
+        // HOTP (RFC 4226) con el número de paso como contador
+        let mut mac = Hmac::<Sha1>::new_from_slice(&self.0).expect("HMAC accepts keys of any length");
+        mac.update(&(step as u64).to_be_bytes());
+        let digest = mac.finalize().into_bytes();
+        let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([
+            digest[offset] & 0x7f,
+            digest[offset + 1],
+            digest[offset + 2],
+            digest[offset + 3],
+        ]);
+        format!("{:0width$}", binary % 10u32.pow(TOTP_DIGITS), width = TOTP_DIGITS as usize)
+    }
+
+    /// Step whose code is `code`, looking one step back and one ahead of `now`
+    pub fn verify(&self, code: &str, now: DateTime<Utc>) -> Option<i64> {
+        let code: String = code.chars().filter(|c| !c.is_whitespace()).collect();
+        if code.len() != TOTP_DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        let current = totp_step(now);
+        (current - TOTP_ALLOWED_DRIFT_STEPS..=current + TOTP_ALLOWED_DRIFT_STEPS)
+            .find(|step| constant_time_eq(self.code_for_step(*step).as_bytes(), code.as_bytes()))
+    }
+
+    /// `otpauth://` URI for the authenticator app (usually shown as a QR code)
+    pub fn provisioning_uri(&self, issuer: &str, account: &str) -> String {
+        format!(
+            "otpauth://totp/{issuer}:{account}?secret={secret}&issuer={issuer}&algorithm=SHA1&digits={digits}&period={period}",
+            issuer = percent_encode(issuer),
+            account = percent_encode(account),
+            secret = self.to_base32(),
+            digits = TOTP_DIGITS,
+            period = TOTP_STEP_SECS,
+        )
+    }
+}
+
+impl std::fmt::Debug for TotpSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("TotpSecret(..)")
+    }
+}
+
+pub fn totp_step(now: DateTime<Utc>) -> i64 {
+    now.timestamp().div_euclid(TOTP_STEP_SECS)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Fresh recovery codes such as `3f9a1-c07e2`, shown to the user only once
+pub fn generate_recovery_codes() -> Vec<String> {
+    (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let code = hex::encode(rand::random::<[u8; 5]>());
+            format!("{}-{}", &code[..5], &code[5..])
+        })
+        .collect()
+}
+
+/// Recovery codes are compared ignoring case, spaces and the dash
+pub fn hash_recovery_code(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_ascii_lowercase();
+    hex::encode(Sha256::digest(normalized.as_bytes()))
+}
+
+/// Two-factor state of a user. Until `enabled_at` the setup is only pending.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TwoFactorRecord {
+    pub user_id: Uuid,
+    /// Base32 TOTP secret
+    pub secret: String,
+    pub enabled_at: Option<DateTime<Utc>>,
+    pub last_used_step: Option<i64>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl TwoFactorRecord {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled_at.is_some()
+    }
+}
+
+#[async_trait]
+pub trait TwoFactorStore: Send + Sync {
+    async fn find(&self, user_id: Uuid) -> Result<Option<TwoFactorRecord>, AppError>;
+
+    /// Replace an unconfirmed setup (and its recovery codes) with a new one.
+    /// Returns false when 2FA is already enabled for the user.
+    async fn save_pending(
+        &self,
+        user_id: Uuid,
+        secret: &str,
+        recovery_code_hashes: &[String],
+        at: DateTime<Utc>,
+    ) -> Result<bool, AppError>;
+
+    async fn enable(&self, user_id: Uuid, at: DateTime<Utc>) -> Result<(), AppError>;
+
+    /// Mark `step` as used. False if it, or a later step, was already used.
+    async fn use_step(&self, user_id: Uuid, step: i64) -> Result<bool, AppError>;
+
+    /// Spend a recovery code. False if it does not exist or was already used.
+    async fn use_recovery_code(&self, user_id: Uuid, code_hash: &str, at: DateTime<Utc>) -> Result<bool, AppError>;
+
+    /// Remove the secret and every recovery code
+    async fn delete(&self, user_id: Uuid) -> Result<(), AppError>;
+}
+
+/// What a new setup hands the user: shown once, never again
+#[derive(Debug, Clone)]
+pub struct TwoFactorSetup {
+    pub secret: String,
+    pub otpauth_uri: String,
+    pub recovery_codes: Vec<String>,
+}
+
+/// Second factor the user proved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TwoFactorMethod {
+    Totp,
+    RecoveryCode,
+}
+
+pub struct TwoFactorService {
+    store: Arc<dyn TwoFactorStore>,
+    issuer: String,
+}
+
+impl TwoFactorService {
+    pub fn new(store: Arc<dyn TwoFactorStore>, issuer: impl Into<String>) -> Self {
+        Self { store, issuer: issuer.into() }
+    }
+
+    /// Secrets in Postgres; the issuer shown in the app comes from `TOTP_ISSUER`
+    pub fn postgres(pool: PgPool) -> Self {
+        let issuer = std::env::var("TOTP_ISSUER").unwrap_or_else(|_| DEFAULT_TOTP_ISSUER.to_string());
+        Self::new(Arc::new(PostgresTwoFactorStore::new(pool)), issuer)
+    }
+
+    pub async fn is_enabled(&self, user_id: Uuid) -> Result<bool, AppError> {
+        Ok(self.store.find(user_id).await?.map_or(false, |record| record.is_enabled()))
+    }
+
+    /// New secret and recovery codes. Starting over before confirming
+    /// discards the previous ones.
+    pub async fn begin_setup(&self, user_id: Uuid, account: &str, now: DateTime<Utc>) -> Result<TwoFactorSetup, TwoFactorError> {
+        let secret = TotpSecret::generate();
+        let recovery_codes = generate_recovery_codes();
+        let hashes: Vec<String> = recovery_codes.iter().map(|code| hash_recovery_code(code)).collect();
+
+        if !self.store.save_pending(user_id, &secret.to_base32(), &hashes, now).await? {
+            return Err(TwoFactorError::AlreadyEnabled);
+        }
+        Ok(TwoFactorSetup {
+            secret: secret.to_base32(),
+            otpauth_uri: secret.provisioning_uri(&self.issuer, account),
+            recovery_codes,
+        })
+    }
+
+    /// Turn 2FA on with a first code from the app, proving it was set up
+    pub async fn confirm(&self, user_id: Uuid, code: &str, now: DateTime<Utc>) -> Result<(), TwoFactorError> {
+        let record = self.store.find(user_id).await?.ok_or(TwoFactorError::SetupNotStarted)?;
+        if record.is_enabled() {
+            return Err(TwoFactorError::AlreadyEnabled);
+        }
+        self.verify_totp(&record, code, now).await?;
+        self.store.enable(user_id, now).await?;
+        Ok(())
+    }
+
+    /// Check the second factor: a TOTP code or an unused recovery code
+    pub async fn verify(&self, user_id: Uuid, code: &str, now: DateTime<Utc>) -> Result<TwoFactorMethod, TwoFactorError> {
+        let record = self
+            .store
+            .find(user_id)
+            .await?
+            .filter(TwoFactorRecord::is_enabled)
+            .ok_or(TwoFactorError::NotEnabled)?;
+
+        if self.verify_totp(&record, code, now).await.is_ok() {
+            return Ok(TwoFactorMethod::Totp);
+        }
+        if self.store.use_recovery_code(user_id, &hash_recovery_code(code), now).await? {
+            return Ok(TwoFactorMethod::RecoveryCode);
+        }
+        Err(TwoFactorError::InvalidCode)
+    }
+
+    /// Turn 2FA off. Needs a valid code, so a stolen session alone cannot do it;
+    /// a recovery code also works for users who lost their device.
+    pub async fn disable(&self, user_id: Uuid, code: &str, now: DateTime<Utc>) -> Result<(), TwoFactorError> {
+        self.verify(user_id, code, now).await?;
+        self.store.delete(user_id).await?;
+        Ok(())
+    }
+
+    async fn verify_totp(&self, record: &TwoFactorRecord, code: &str, now: DateTime<Utc>) -> Result<(), TwoFactorError> {
+        let secret = TotpSecret::from_base32(&record.secret)
+            .ok_or_else(|| AppError::InternalError(format!("Corrupt TOTP secret for user {}", record.user_id)))?;
+        let step = secret.verify(code, now).ok_or(TwoFactorError::InvalidCode)?;
+        // Un código ya usado (o uno anterior al último aceptado) no vuelve a valer
+        if !self.store.use_step(record.user_id, step).await? {
+            return Err(TwoFactorError::InvalidCode);
+        }
+        Ok(())
+    }
+}
+
+// =============================================================================
+// POSTGRES STORE
+// =============================================================================
+
+pub struct PostgresTwoFactorStore {
+    pool: PgPool,
+}
+
+impl PostgresTwoFactorStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_error(e: sqlx::Error) -> AppError {
+    AppError::DatabaseError(format!("Two-factor store error: {}", e))
+}
+
+#[async_trait]
+impl TwoFactorStore for PostgresTwoFactorStore {
+    async fn find(&self, user_id: Uuid) -> Result<Option<TwoFactorRecord>, AppError> {
+        let row = sqlx::query(
+            r#"
+            SELECT user_id, secret, enabled_at, last_used_step, created_at
+            FROM user_two_factor
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)?;
+
+        row.map(|row| {
+            Ok(TwoFactorRecord {
+                user_id: row.try_get("user_id").map_err(db_error)?,
+                secret: row.try_get("secret").map_err(db_error)?,
+                enabled_at: row.try_get("enabled_at").map_err(db_error)?,
+                last_used_step: row.try_get("last_used_step").map_err(db_error)?,
+                created_at: row.try_get("created_at").map_err(db_error)?,
+            })
+        })
+        .transpose()
+    }
+
+    async fn save_pending(
+        &self,
+        user_id: Uuid,
+        secret: &str,
+        recovery_code_hashes: &[String],
+        at: DateTime<Utc>,
+    ) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        let saved = sqlx::query(
+            r#"
+            INSERT INTO user_two_factor (user_id, secret, enabled_at, last_used_step, created_at)
+            VALUES ($1, $2, NULL, NULL, $3)
+            ON CONFLICT (user_id) DO UPDATE
+            SET secret = EXCLUDED.secret, last_used_step = NULL, created_at = EXCLUDED.created_at
+            WHERE user_two_factor.enabled_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(secret)
+        .bind(at)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .rows_affected();
+
+        if saved == 0 {
+            tx.rollback().await.map_err(db_error)?;
+            return Ok(false);
+        }
+
+        sqlx::query("DELETE FROM user_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        for code_hash in recovery_code_hashes {
+            sqlx::query(
+                r#"
+                INSERT INTO user_recovery_codes (id, user_id, code_hash, created_at)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(Uuid::new_v4())
+            .bind(user_id)
+            .bind(code_hash)
+            .bind(at)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        }
+
+        tx.commit().await.map_err(db_error)?;
+        Ok(true)
+    }
+
+    async fn enable(&self, user_id: Uuid, at: DateTime<Utc>) -> Result<(), AppError> {
+        sqlx::query("UPDATE user_two_factor SET enabled_at = $2 WHERE user_id = $1 AND enabled_at IS NULL")
+            .bind(user_id)
+            .bind(at)
+            .execute(&self.pool)
+            .await
+            .map_err(db_error)?;
+        Ok(())
+    }
+
+    async fn use_step(&self, user_id: Uuid, step: i64) -> Result<bool, AppError> {
+        // Condicional en una sola sentencia: dos logins concurrentes no pueden usar el mismo paso
+        let updated = sqlx::query(
+            r#"
+            UPDATE user_two_factor
+            SET last_used_step = $2
+            WHERE user_id = $1 AND (last_used_step IS NULL OR last_used_step < $2)
+            "#,
+        )
+        .bind(user_id)
+        .bind(step)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?
+        .rows_affected();
+        Ok(updated == 1)
+    }
+
+    async fn use_recovery_code(&self, user_id: Uuid, code_hash: &str, at: DateTime<Utc>) -> Result<bool, AppError> {
+        let updated = sqlx::query(
+            r#"
+            UPDATE user_recovery_codes
+            SET used_at = $3
+            WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(code_hash)
+        .bind(at)
+        .execute(&self.pool)
+        .await
+        .map_err(db_error)?
+        .rows_affected();
+        Ok(updated == 1)
+    }
+
+    async fn delete(&self, user_id: Uuid) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        sqlx::query("DELETE FROM user_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        sqlx::query("DELETE FROM user_two_factor WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        tx.commit().await.map_err(db_error)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    // Secreto de los vectores de prueba de RFC 6238 (SHA-1)
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+    const RFC_SECRET_BASE32: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    fn at(unix_secs: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(unix_secs, 0).unwrap()
+    }
+
+    #[derive(Default)]
+    struct InMemoryTwoFactorStore {
+        records: Mutex<HashMap<Uuid, TwoFactorRecord>>,
+        recovery_codes: Mutex<HashMap<Uuid, Vec<(String, bool)>>>,
+    }
+
+    #[async_trait]
+    impl TwoFactorStore for InMemoryTwoFactorStore {
+        async fn find(&self, user_id: Uuid) -> Result<Option<TwoFactorRecord>, AppError> {
+            Ok(self.records.lock().unwrap().get(&user_id).cloned())
+        }
+
+        async fn save_pending(&self, user_id: Uuid, secret: &str, hashes: &[String], at: DateTime<Utc>) -> Result<bool, AppError> {
+            let mut records = self.records.lock().unwrap();
+            if records.get(&user_id).map_or(false, |record| record.is_enabled()) {
+                return Ok(false);
+            }
+            records.insert(user_id, TwoFactorRecord {
+                user_id,
+                secret: secret.to_string(),
+                enabled_at: None,
+                last_used_step: None,
+                created_at: at,
+            });
+            self.recovery_codes
+                .lock()
+                .unwrap()
+                .insert(user_id, hashes.iter().map(|hash| (hash.clone(), false)).collect());
+            Ok(true)
+        }
+
+        async fn enable(&self, user_id: Uuid, at: DateTime<Utc>) -> Result<(), AppError> {
+            if let Some(record) = self.records.lock().unwrap().get_mut(&user_id) {
+                record.enabled_at.get_or_insert(at);
+            }
+            Ok(())
+        }
+
+        async fn use_step(&self, user_id: Uuid, step: i64) -> Result<bool, AppError> {
+            let mut records = self.records.lock().unwrap();
+            let Some(record) = records.get_mut(&user_id) else { return Ok(false) };
+            if record.last_used_step.map_or(false, |last| last >= step) {
+                return Ok(false);
+            }
+            record.last_used_step = Some(step);
+            Ok(true)
+        }
+
+        async fn use_recovery_code(&self, user_id: Uuid, code_hash: &str, _at: DateTime<Utc>) -> Result<bool, AppError> {
+            let mut codes = self.recovery_codes.lock().unwrap();
+            let unused = codes
+                .get_mut(&user_id)
+                .and_then(|codes| codes.iter_mut().find(|(hash, used)| hash == code_hash && !used));
+            Ok(unused.map(|(_, used)| *used = true).is_some())
+        }
+
+        async fn delete(&self, user_id: Uuid) -> Result<(), AppError> {
+            self.records.lock().unwrap().remove(&user_id);
+            self.recovery_codes.lock().unwrap().remove(&user_id);
+            Ok(())
+        }
+    }
+
+    /// Servicio con 2FA activado para `user_id` con el secreto de la RFC y dos códigos de recuperación
+    async fn enabled_service(user_id: Uuid) -> TwoFactorService {
+        let store = Arc::new(InMemoryTwoFactorStore::default());
+        let hashes = vec![hash_recovery_code("aaaaa-11111"), hash_recovery_code("bbbbb-22222")];
+        store.save_pending(user_id, RFC_SECRET_BASE32, &hashes, at(0)).await.unwrap();
+        store.enable(user_id, at(0)).await.unwrap();
+        TwoFactorService::new(store, "VibeStream")
+    }
+
+    #[test]
+    fn codes_match_rfc_6238_vectors() {
+        let secret = TotpSecret::from_bytes(RFC_SECRET);
+        // Los 6 últimos dígitos de los valores de 8 dígitos de la RFC
+        assert_eq!(secret.code_at(at(59)), "287082");
+        assert_eq!(secret.code_at(at(1111111109)), "081804");
+        assert_eq!(secret.code_at(at(1111111111)), "050471");
+        assert_eq!(secret.code_at(at(1234567890)), "005924");
+        assert_eq!(secret.code_at(at(2000000000)), "279037");
+        assert_eq!(secret.to_base32(), RFC_SECRET_BASE32);
+        assert_eq!(TotpSecret::from_base32("gezd gnbv gy3t qojq gezd gnbv gy3t qojq"), Some(secret));
+    }
+
+    #[test]
+    fn one_step_of_clock_drift_is_tolerated() {
+        let secret = TotpSecret::from_bytes(RFC_SECRET);
+        let step = totp_step(at(1234567890));
+
+        assert_eq!(secret.verify("005924", at(1234567890)), Some(step));
+        assert_eq!(secret.verify("005 924", at(1234567919)), Some(step));
+        // El reloj del móvil va 30 s por detrás o por delante
+        assert_eq!(secret.verify("005924", at(1234567890 + 30)), Some(step));
+        assert_eq!(secret.verify("005924", at(1234567890 - 30)), Some(step));
+        // Dos pasos ya no
+        assert_eq!(secret.verify("005924", at(1234567890 + 60)), None);
+        assert_eq!(secret.verify("005924", at(1234567890 - 31)), None);
+        assert_eq!(secret.verify("5924", at(1234567890)), None);
+    }
+
+    #[test]
+    fn provisioning_uri_follows_key_uri_format() {
+        let secret = TotpSecret::from_bytes(RFC_SECRET);
+        assert_eq!(
+            secret.provisioning_uri("VibeStream", "fan@example.com"),
+            "otpauth://totp/VibeStream:fan%40example.com?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ\
+             &issuer=VibeStream&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+
+    #[tokio::test]
+    async fn setup_is_only_enabled_after_a_valid_code() {
+        let store = Arc::new(InMemoryTwoFactorStore::default());
+        let service = TwoFactorService::new(store.clone(), "VibeStream");
+        let user_id = Uuid::new_v4();
+        let now = at(1234567890);
+
+        let setup = service.begin_setup(user_id, "fan@example.com", now).await.unwrap();
+        assert_eq!(setup.recovery_codes.len(), RECOVERY_CODE_COUNT);
+        assert!(setup.otpauth_uri.starts_with("otpauth://totp/VibeStream:fan%40example.com?"));
+        assert!(setup.otpauth_uri.contains(&format!("secret={}", setup.secret)));
+        assert!(!service.is_enabled(user_id).await.unwrap());
+
+        // Misma configuración pendiente, con el secreto de la RFC
+        store.save_pending(user_id, RFC_SECRET_BASE32, &[], now).await.unwrap();
+        assert!(matches!(service.confirm(user_id, "123456", now).await, Err(TwoFactorError::InvalidCode)));
+        assert!(matches!(service.verify(user_id, "005924", now).await, Err(TwoFactorError::NotEnabled)));
+        service.confirm(user_id, "005924", now).await.unwrap();
+        assert!(service.is_enabled(user_id).await.unwrap());
+
+        // Activado, no se puede generar otro secreto sin desactivarlo antes
+        assert!(matches!(
+            service.begin_setup(user_id, "fan@example.com", now).await,
+            Err(TwoFactorError::AlreadyEnabled)
+        ));
+    }
+
+    #[tokio::test]
+    async fn totp_code_cannot_be_replayed() {
+        let user_id = Uuid::new_v4();
+        let service = enabled_service(user_id).await;
+
+        assert_eq!(service.verify(user_id, "005924", at(1234567890)).await.unwrap(), TwoFactorMethod::Totp);
+        assert!(matches!(service.verify(user_id, "005924", at(1234567900)).await, Err(TwoFactorError::InvalidCode)));
+
+        // El código del paso siguiente sí vale
+        let next = TotpSecret::from_bytes(RFC_SECRET).code_at(at(1234567920));
+        assert_eq!(service.verify(user_id, &next, at(1234567920)).await.unwrap(), TwoFactorMethod::Totp);
+    }
+
+    #[tokio::test]
+    async fn recovery_codes_are_single_use() {
+        let user_id = Uuid::new_v4();
+        let service = enabled_service(user_id).await;
+        let now = at(1234567890);
+
+        assert_eq!(service.verify(user_id, "AAAAA 11111", now).await.unwrap(), TwoFactorMethod::RecoveryCode);
+        assert!(matches!(service.verify(user_id, "aaaaa-11111", now).await, Err(TwoFactorError::InvalidCode)));
+        assert!(matches!(service.verify(user_id, "ccccc-33333", now).await, Err(TwoFactorError::InvalidCode)));
+        assert_eq!(service.verify(user_id, "bbbbb-22222", now).await.unwrap(), TwoFactorMethod::RecoveryCode);
+    }
+
+    #[tokio::test]
+    async fn disabling_requires_a_valid_code() {
+        let user_id = Uuid::new_v4();
+        let service = enabled_service(user_id).await;
+        let now = at(1234567890);
+
+        assert!(matches!(service.disable(user_id, "123456", now).await, Err(TwoFactorError::InvalidCode)));
+        assert!(service.is_enabled(user_id).await.unwrap());
+
+        service.disable(user_id, "005924", now).await.unwrap();
+        assert!(!service.is_enabled(user_id).await.unwrap());
+        assert!(matches!(service.verify(user_id, "bbbbb-22222", now).await, Err(TwoFactorError::NotEnabled)));
+    }
+}
@@ -0,0 +1,468 @@
+//! Wallet login: Sign-In-With-Solana and Sign-In-With-Ethereum (EIP-4361)
+//!
+//! The client asks for a challenge for its wallet address and gets back a
+//! message carrying a random nonce and an expiry. The wallet signs that exact
+//! message: ed25519 over the raw bytes for Solana, an EIP-191 `personal_sign`
+//! for Ethereum. The challenge lives in Redis until it expires or is presented
+//! once, whatever the outcome, so a signature can never be replayed.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use redis::aio::ConnectionManager;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::shared::domain::errors::AppError;
+
+pub const WALLET_CHALLENGE_TTL_SECS: i64 = 300;
+const SIGN_IN_STATEMENT: &str = "Sign in to VibeStream.";
+const DEFAULT_SIGN_IN_DOMAIN: &str = "vibestream.app";
+const NONCE_KEY_PREFIX: &str = "wallet_auth:nonce:";
+
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum WalletAuthError {
+    #[error("Unsupported wallet address: {0}")]
+    InvalidAddress(String),
+    #[error("Malformed sign-in message")]
+    MalformedMessage,
+    #[error("Unknown or already used nonce")]
+    UnknownNonce,
+    #[error("Sign-in challenge expired")]
+    Expired,
+    #[error("Signed message does not match the issued challenge")]
+    MessageMismatch,
+    #[error("Invalid wallet signature")]
+    InvalidSignature,
+    #[error(transparent)]
+    Store(#[from] AppError),
+}
+
+impl From<WalletAuthError> for AppError {
+    fn from(error: WalletAuthError) -> Self {
+        match error {
+            WalletAuthError::Store(e) => e,
+            other => AppError::AuthenticationError(other.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WalletChain {
+    Solana,
+    Ethereum,
+}
+
+impl WalletChain {
+    /// `0x` + 40 hex digits is Ethereum; a base58 ed25519 public key is Solana
+    pub fn detect(address: &str) -> Result<Self, WalletAuthError> {
+        if let Some(hex_part) = address.strip_prefix("0x") {
+            if hex_part.len() == 40 && hex_part.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Ok(WalletChain::Ethereum);
+            }
+        } else if matches!(bs58::decode(address).into_vec(), Ok(bytes) if bytes.len() == 32) {
+            return Ok(WalletChain::Solana);
+        }
+        Err(WalletAuthError::InvalidAddress(address.to_string()))
+    }
+
+    /// Like `detect`, but a mixed-case Ethereum address must also carry a valid
+    /// EIP-55 checksum. All-lowercase and all-uppercase addresses have none.
+    pub fn validate(address: &str) -> Result<Self, WalletAuthError> {
+        let chain = Self::detect(address)?;
+        if chain == WalletChain::Ethereum {
+            let hex_part = &address[2..];
+            let mixed_case = hex_part.chars().any(|c| c.is_ascii_lowercase())
+                && hex_part.chars().any(|c| c.is_ascii_uppercase());
+            if mixed_case {
+                let parsed = ethers::types::Address::from_str(address)
+                    .map_err(|_| WalletAuthError::InvalidAddress(address.to_string()))?;
+                if ethers::utils::to_checksum(&parsed, None) != address {
+                    return Err(WalletAuthError::InvalidAddress(address.to_string()));
+                }
+            }
+        }
+        Ok(chain)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WalletChain::Solana => "solana",
+            WalletChain::Ethereum => "ethereum",
+        }
+    }
+
+    fn display_name(&self) -> &'static str {
+        match self {
+            WalletChain::Solana => "Solana",
+            WalletChain::Ethereum => "Ethereum",
+        }
+    }
+
+    fn chain_id(&self) -> &'static str {
+        match self {
+            WalletChain::Solana => "mainnet",
+            WalletChain::Ethereum => "1",
+        }
+    }
+}
+
+/// A message the wallet has to sign, bound to one address until `expires_at`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalletChallenge {
+    pub address: String,
+    pub chain: WalletChain,
+    pub nonce: String,
+    pub message: String,
+    pub issued_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl WalletChallenge {
+    pub fn new(
+        address: &str,
+        domain: &str,
+        nonce: String,
+        issued_at: DateTime<Utc>,
+        ttl: Duration,
+    ) -> Result<Self, WalletAuthError> {
+        let address = address.trim();
+        let chain = WalletChain::detect(address)?;
+        let expires_at = issued_at + ttl;
+        let timestamp = |at: DateTime<Utc>| at.to_rfc3339_opts(SecondsFormat::Secs, true);
+        let message = format!(
+            "{domain} wants you to sign in with your {chain} account:\n{address}\n\n{statement}\n\n\
+             URI: https://{domain}\nVersion: 1\nChain ID: {chain_id}\nNonce: {nonce}\n\
+             Issued At: {issued}\nExpiration Time: {expires}",
+            domain = domain,
+            chain = chain.display_name(),
+            address = address,
+            statement = SIGN_IN_STATEMENT,
+            chain_id = chain.chain_id(),
+            nonce = nonce,
+            issued = timestamp(issued_at),
+            expires = timestamp(expires_at),
+        );
+        Ok(Self {
+            address: address.to_string(),
+            chain,
+            nonce,
+            message,
+            issued_at,
+            expires_at,
+        })
+    }
+}
+
+/// Nonce of a sign-in message, from its `Nonce:` line
+pub fn message_nonce(message: &str) -> Option<&str> {
+    message
+        .lines()
+        .find_map(|line| line.strip_prefix("Nonce: "))
+        .map(str::trim)
+        .filter(|nonce| !nonce.is_empty())
+}
+
+/// Check that `signature` is `address` signing `message`.
+/// Solana signatures are base58, Ethereum ones 65-byte `0x` hex (r, s, v).
+pub fn verify_wallet_signature(
+    chain: WalletChain,
+    address: &str,
+    message: &str,
+    signature: &str,
+) -> Result<(), WalletAuthError> {
+    match chain {
+        WalletChain::Solana => {
+            let public_key: [u8; 32] = bs58::decode(address)
+                .into_vec()
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or_else(|| WalletAuthError::InvalidAddress(address.to_string()))?;
+            let signature: [u8; 64] = bs58::decode(signature.trim())
+                .into_vec()
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or(WalletAuthError::InvalidSignature)?;
+            let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(&public_key)
+                .map_err(|_| WalletAuthError::InvalidAddress(address.to_string()))?;
+            verifying_key
+                .verify_strict(message.as_bytes(), &ed25519_dalek::Signature::from_bytes(&signature))
+                .map_err(|_| WalletAuthError::InvalidSignature)
+        }
+        WalletChain::Ethereum => {
+            let expected = ethers::types::Address::from_str(address)
+                .map_err(|_| WalletAuthError::InvalidAddress(address.to_string()))?;
+            let signature = ethers::types::Signature::from_str(signature.trim())
+                .map_err(|_| WalletAuthError::InvalidSignature)?;
+            // `verify` aplica el prefijo EIP-191 ("\x19Ethereum Signed Message:\n" + len)
+            signature
+                .verify(message, expected)
+                .map_err(|_| WalletAuthError::InvalidSignature)
+        }
+    }
+}
+
+#[async_trait]
+pub trait WalletNonceStore: Send + Sync {
+    async fn put(&self, challenge: &WalletChallenge, ttl: Duration) -> Result<(), AppError>;
+
+    /// Remove and return the challenge: a nonce can only be presented once
+    async fn take(&self, nonce: &str) -> Result<Option<WalletChallenge>, AppError>;
+}
+
+/// Wallet address proven by a valid signature
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifiedWallet {
+    pub address: String,
+    pub chain: WalletChain,
+}
+
+pub struct WalletAuthService {
+    store: Arc<dyn WalletNonceStore>,
+    domain: String,
+    ttl: Duration,
+}
+
+impl WalletAuthService {
+    pub fn new(store: Arc<dyn WalletNonceStore>, domain: impl Into<String>, ttl: Duration) -> Self {
+        Self { store, domain: domain.into(), ttl }
+    }
+
+    /// Nonces in Redis; the domain in the message comes from `WALLET_AUTH_DOMAIN`
+    pub fn redis(connection: ConnectionManager) -> Self {
+        let domain = std::env::var("WALLET_AUTH_DOMAIN").unwrap_or_else(|_| DEFAULT_SIGN_IN_DOMAIN.to_string());
+        Self::new(
+            Arc::new(RedisWalletNonceStore::new(connection)),
+            domain,
+            Duration::seconds(WALLET_CHALLENGE_TTL_SECS),
+        )
+    }
+
+    pub async fn challenge(&self, address: &str) -> Result<WalletChallenge, WalletAuthError> {
+        let nonce = hex::encode(rand::random::<[u8; 16]>());
+        let challenge = WalletChallenge::new(address, &self.domain, nonce, Utc::now(), self.ttl)?;
+        self.store.put(&challenge, self.ttl).await?;
+        Ok(challenge)
+    }
+
+    /// Consume the message's nonce and check the signature against the
+    /// address the challenge was issued for
+    pub async fn verify(
+        &self,
+        message: &str,
+        signature: &str,
+        now: DateTime<Utc>,
+    ) -> Result<VerifiedWallet, WalletAuthError> {
+        let nonce = message_nonce(message).ok_or(WalletAuthError::MalformedMessage)?;
+        let challenge = self.store.take(nonce).await?.ok_or(WalletAuthError::UnknownNonce)?;
+        if challenge.message != message {
+            return Err(WalletAuthError::MessageMismatch);
+        }
+        if now >= challenge.expires_at {
+            return Err(WalletAuthError::Expired);
+        }
+        verify_wallet_signature(challenge.chain, &challenge.address, message, signature)?;
+        Ok(VerifiedWallet { address: challenge.address, chain: challenge.chain })
+    }
+}
+
+pub struct RedisWalletNonceStore {
+    connection: ConnectionManager,
+}
+
+impl RedisWalletNonceStore {
+    pub fn new(connection: ConnectionManager) -> Self {
+        Self { connection }
+    }
+}
+
+#[async_trait]
+impl WalletNonceStore for RedisWalletNonceStore {
+    async fn put(&self, challenge: &WalletChallenge, ttl: Duration) -> Result<(), AppError> {
+        let data = serde_json::to_string(challenge)
+            .map_err(|e| AppError::SerializationError(e.to_string()))?;
+        let mut conn = self.connection.clone();
+        let _: () = redis::cmd("SET")
+            .arg(format!("{}{}", NONCE_KEY_PREFIX, challenge.nonce))
+            .arg(data)
+            .arg("EX")
+            .arg(ttl.num_seconds().max(1))
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::ExternalServiceError(format!("Redis nonce error: {}", e)))?;
+        Ok(())
+    }
+
+    async fn take(&self, nonce: &str) -> Result<Option<WalletChallenge>, AppError> {
+        let mut conn = self.connection.clone();
+        // GETDEL es atómico: dos verificaciones concurrentes no pueden consumir el mismo nonce
+        let data: Option<String> = redis::cmd("GETDEL")
+            .arg(format!("{}{}", NONCE_KEY_PREFIX, nonce))
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| AppError::ExternalServiceError(format!("Redis nonce error: {}", e)))?;
+        data.map(|data| serde_json::from_str(&data).map_err(|e| AppError::SerializationError(e.to_string())))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    // Claves fijas: el vector 1 de RFC 8032 (ed25519) y la cuenta #0 de Hardhat
+    // (secp256k1, 0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80).
+    // Las firmas se calcularon sobre `fixed_challenge` con esas claves.
+    const SOLANA_ADDRESS: &str = "FVen3X669xLzsi6N2V91DoiyzHzg1uAgqiT8jZ9nS96Z";
+    const SOLANA_SIGNATURE: &str =
+        "3FrfhMTtaJSnDCPbAzN9VHuhWvm14XSFRkUJPRwMTcaQYy5JnHAQXJzhvUQerqw8yC3K6FbqPhm4rGqyHqFyRUw4";
+    const ETHEREUM_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+    const ETHEREUM_SIGNATURE: &str = "0x485429e4c8f70d065f892cc4127951826d07a0f8ac6a158453a44a05be20175169edf28e63e38e425e8a6863686baf826e3ca90f4f5a02f2ba1a353342dc1b401c";
+
+    fn issued_at() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 10, 15, 12, 0, 0).unwrap()
+    }
+
+    fn fixed_challenge(address: &str) -> WalletChallenge {
+        WalletChallenge::new(address, "vibestream.app", "6f1d2b9c8e4a7f30".to_string(), issued_at(), Duration::minutes(5)).unwrap()
+    }
+
+    #[derive(Default)]
+    struct InMemoryNonceStore {
+        challenges: Mutex<HashMap<String, WalletChallenge>>,
+    }
+
+    #[async_trait]
+    impl WalletNonceStore for InMemoryNonceStore {
+        async fn put(&self, challenge: &WalletChallenge, _ttl: Duration) -> Result<(), AppError> {
+            self.challenges.lock().unwrap().insert(challenge.nonce.clone(), challenge.clone());
+            Ok(())
+        }
+
+        async fn take(&self, nonce: &str) -> Result<Option<WalletChallenge>, AppError> {
+            Ok(self.challenges.lock().unwrap().remove(nonce))
+        }
+    }
+
+    async fn service_with(challenge: &WalletChallenge) -> WalletAuthService {
+        let store = Arc::new(InMemoryNonceStore::default());
+        store.put(challenge, Duration::minutes(5)).await.unwrap();
+        WalletAuthService::new(store, "vibestream.app", Duration::minutes(5))
+    }
+
+    #[test]
+    fn challenge_message_follows_sign_in_with_format() {
+        let challenge = fixed_challenge(ETHEREUM_ADDRESS);
+        assert_eq!(challenge.chain, WalletChain::Ethereum);
+        assert_eq!(
+            challenge.message,
+            "vibestream.app wants you to sign in with your Ethereum account:\n\
+             0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266\n\n\
+             Sign in to VibeStream.\n\n\
+             URI: https://vibestream.app\n\
+             Version: 1\n\
+             Chain ID: 1\n\
+             Nonce: 6f1d2b9c8e4a7f30\n\
+             Issued At: 2026-10-15T12:00:00Z\n\
+             Expiration Time: 2026-10-15T12:05:00Z"
+        );
+        assert_eq!(message_nonce(&challenge.message), Some("6f1d2b9c8e4a7f30"));
+        assert_eq!(fixed_challenge(SOLANA_ADDRESS).chain, WalletChain::Solana);
+        assert!(matches!(WalletChain::detect("not-a-wallet"), Err(WalletAuthError::InvalidAddress(_))));
+    }
+
+    #[test]
+    fn solana_signature_is_verified_with_ed25519() {
+        let challenge = fixed_challenge(SOLANA_ADDRESS);
+        assert!(verify_wallet_signature(WalletChain::Solana, SOLANA_ADDRESS, &challenge.message, SOLANA_SIGNATURE).is_ok());
+
+        let tampered = challenge.message.replace("VibeStream", "EvilStream");
+        assert!(matches!(
+            verify_wallet_signature(WalletChain::Solana, SOLANA_ADDRESS, &tampered, SOLANA_SIGNATURE),
+            Err(WalletAuthError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn ethereum_signature_is_verified_with_eip191() {
+        let challenge = fixed_challenge(ETHEREUM_ADDRESS);
+        assert!(verify_wallet_signature(WalletChain::Ethereum, ETHEREUM_ADDRESS, &challenge.message, ETHEREUM_SIGNATURE).is_ok());
+        // El checksum EIP-55 no cambia la dirección
+        let lowercase = ETHEREUM_ADDRESS.to_lowercase();
+        assert!(verify_wallet_signature(WalletChain::Ethereum, &lowercase, &challenge.message, ETHEREUM_SIGNATURE).is_ok());
+
+        let other = "0x70997970C51812dc3A010C7d01b50e0d17dc79C8";
+        assert!(matches!(
+            verify_wallet_signature(WalletChain::Ethereum, other, &challenge.message, ETHEREUM_SIGNATURE),
+            Err(WalletAuthError::InvalidSignature)
+        ));
+    }
+
+    #[test]
+    fn ethereum_checksum_is_validated() {
+        assert_eq!(WalletChain::validate(ETHEREUM_ADDRESS).unwrap(), WalletChain::Ethereum);
+        assert!(WalletChain::validate(&ETHEREUM_ADDRESS.to_lowercase()).is_ok());
+        assert_eq!(WalletChain::validate(SOLANA_ADDRESS).unwrap(), WalletChain::Solana);
+
+        // Un carácter con la mayúscula cambiada rompe el checksum
+        let tampered = ETHEREUM_ADDRESS.replacen("f39Fd", "f39fd", 1);
+        assert!(matches!(WalletChain::validate(&tampered), Err(WalletAuthError::InvalidAddress(_))));
+        assert!(WalletChain::validate("0x1234").is_err());
+    }
+
+    #[tokio::test]
+    async fn nonce_is_single_use() {
+        let challenge = fixed_challenge(SOLANA_ADDRESS);
+        let service = service_with(&challenge).await;
+        let now = issued_at() + Duration::minutes(1);
+
+        let wallet = service.verify(&challenge.message, SOLANA_SIGNATURE, now).await.unwrap();
+        assert_eq!(wallet, VerifiedWallet { address: SOLANA_ADDRESS.to_string(), chain: WalletChain::Solana });
+
+        assert!(matches!(
+            service.verify(&challenge.message, SOLANA_SIGNATURE, now).await,
+            Err(WalletAuthError::UnknownNonce)
+        ));
+    }
+
+    #[tokio::test]
+    async fn failed_attempt_also_burns_the_nonce() {
+        let challenge = fixed_challenge(ETHEREUM_ADDRESS);
+        let service = service_with(&challenge).await;
+        let now = issued_at() + Duration::minutes(1);
+
+        let bad_signature = ETHEREUM_SIGNATURE.replace("4854", "4855");
+        assert!(service.verify(&challenge.message, &bad_signature, now).await.is_err());
+        assert!(matches!(
+            service.verify(&challenge.message, ETHEREUM_SIGNATURE, now).await,
+            Err(WalletAuthError::UnknownNonce)
+        ));
+    }
+
+    #[tokio::test]
+    async fn expired_or_altered_challenges_are_rejected() {
+        let challenge = fixed_challenge(ETHEREUM_ADDRESS);
+        let service = service_with(&challenge).await;
+        assert!(matches!(
+            service.verify(&challenge.message, ETHEREUM_SIGNATURE, challenge.expires_at).await,
+            Err(WalletAuthError::Expired)
+        ));
+
+        // Mismo nonce pero otra dirección: la firma no puede reasignarse a otra cuenta
+        let service = service_with(&challenge).await;
+        let altered = challenge.message.replace(ETHEREUM_ADDRESS, "0x70997970C51812dc3A010C7d01b50e0d17dc79C8");
+        assert!(matches!(
+            service.verify(&altered, ETHEREUM_SIGNATURE, issued_at()).await,
+            Err(WalletAuthError::MessageMismatch)
+        ));
+
+        assert!(matches!(
+            service.verify("hello", ETHEREUM_SIGNATURE, issued_at()).await,
+            Err(WalletAuthError::MalformedMessage)
+        ));
+    }
+}
@@ -4,6 +4,8 @@ use serde::{Deserialize, Serialize};
 use std::time::Duration;
 use vibestream_types::*; // Assuming types are available here
 
+use crate::shared::infrastructure::telemetry::with_trace_headers;
+
 #[derive(Clone)]
 pub struct ZkServiceClient {
     client: Client,
@@ -46,6 +48,16 @@ pub enum ZkProofType {
         user_public_key: [String; 2],
         nonce: String,
     },
+    AgeVerification { birth_year: u32, minimum_age: u32 },
+}
+
+/// Mirror of `ZkServiceHealth` returned by the ZK service `GET /health`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZkServiceHealth {
+    pub status: String,
+    pub circuits_available: Vec<String>,
+    pub last_proof_generation_ms: Option<u64>,
+    pub cache_size: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -70,7 +82,7 @@ impl ZkServiceClient {
         let url = format!("{}/generate", self.base_url);
         let request = GenerateProofRequest { proof_type };
 
-        let response = self.client.post(&url)
+        let response = with_trace_headers(self.client.post(&url))
             .json(&request)
             .send()
             .await
@@ -87,11 +99,28 @@ impl ZkServiceClient {
         Ok(proof)
     }
 
+    /// The ZK service answers 503 with the same body when unhealthy, so the
+    /// body is parsed regardless of the status code.
+    pub async fn health_check(&self) -> Result<ZkServiceHealth> {
+        let url = format!("{}/health", self.base_url);
+
+        let response = self.client.get(&url)
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await
+            .context("Failed to reach ZK service")?;
+
+        let health: ZkServiceHealth = response.json().await
+            .context("Failed to parse ZK service health response")?;
+
+        Ok(health)
+    }
+
     pub async fn verify_proof(&self, proof: ZkProof) -> Result<bool> {
         let url = format!("{}/verify", self.base_url);
         let request = VerifyProofRequest { proof };
 
-        let response = self.client.post(&url)
+        let response = with_trace_headers(self.client.post(&url))
             .json(&request)
             .send()
             .await
@@ -0,0 +1,258 @@
+// =============================================================================
+// CORRELATION ID / TRACING LAYER
+// =============================================================================
+//
+// Cada petición HTTP recibe un correlation ID (cabecera `X-Correlation-ID` o uno
+// nuevo). El layer lo guarda en las extensions, abre un `tracing::Span` con él y
+// ejecuta el handler dentro de `with_correlation_id`, de modo que todos los
+// `EventMetadata` creados durante la petición lo comparten.
+//
+// Igual con el request ID (`X-Request-Id`, un UUID): va en las extensions como
+// `RequestId`, en el span, en la respuesta (también en el envoltorio de error) y
+// en los mensajes que se publican en las colas, para cruzar logs con los workers.
+//
+// El span es también el de la petición en la traza distribuida: continúa la
+// del `traceparent` entrante (o empieza una) y la respuesta devuelve el suyo.
+
+use axum::http::{HeaderName, HeaderValue, Request, Response};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use uuid::Uuid;
+use vibestream_types::RequestId;
+
+use crate::shared::domain::events::{with_correlation_id, with_request_id};
+use crate::shared::infrastructure::telemetry::{extract_context, trace_headers};
+
+pub const CORRELATION_ID_HEADER: &str = "x-correlation-id";
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Correlation ID de la petición, disponible como `Extension<CorrelationId>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CorrelationId(pub Uuid);
+
+/// Tower layer que propaga el correlation ID a logs y eventos
+#[derive(Debug, Clone, Default)]
+pub struct TracingLayer;
+
+impl TracingLayer {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for TracingLayer {
+    type Service = TracingService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TracingService { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TracingService<S> {
+    inner: S,
+}
+
+/// Un ID inválido en la cabecera se sustituye por uno nuevo
+fn id_from_header(value: Option<&HeaderValue>) -> Uuid {
+    value
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| Uuid::parse_str(value.trim()).ok())
+        .unwrap_or_else(Uuid::new_v4)
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for TracingService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        let correlation_id = id_from_header(request.headers().get(CORRELATION_ID_HEADER));
+        let request_id = RequestId(id_from_header(request.headers().get(REQUEST_ID_HEADER)));
+        request.extensions_mut().insert(CorrelationId(correlation_id));
+        request.extensions_mut().insert(request_id);
+
+        let span = tracing::info_span!(
+            "request",
+            "otel.kind" = "server",
+            correlation_id = %correlation_id,
+            request_id = %request_id,
+            method = %request.method(),
+            path = %request.uri().path(),
+        );
+        span.set_parent(extract_context(request.headers()));
+        let trace_headers = trace_headers(&span);
+        let future = self.inner.call(request);
+
+        Box::pin(
+            async move {
+                let mut response = with_request_id(request_id, with_correlation_id(correlation_id, future)).await?;
+                if let Ok(value) = HeaderValue::from_str(&correlation_id.to_string()) {
+                    response.headers_mut().insert(CORRELATION_ID_HEADER, value);
+                }
+                if let Ok(value) = HeaderValue::from_str(&request_id.to_string()) {
+                    response.headers_mut().insert(REQUEST_ID_HEADER, value);
+                }
+                for (name, value) in trace_headers {
+                    if let (Ok(name), Ok(value)) = (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+                        response.headers_mut().insert(name, value);
+                    }
+                }
+                Ok(response)
+            }
+            .instrument(span),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounded_contexts::orchestrator::{DomainEvent, EventBus, EventHandler, InMemoryEventBus};
+    use crate::shared::domain::errors::AppError;
+    use crate::shared::domain::events::{current_request_id, EventMetadata};
+    use axum::{
+        body::Body,
+        extract::State,
+        routing::{get, post},
+        Extension, Router,
+    };
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+    use tower::ServiceExt;
+
+    /// Handler de otro contexto (Fan Ventures) que emite un evento derivado
+    struct RecordingHandler {
+        seen: Arc<Mutex<Vec<Uuid>>>,
+    }
+
+    #[async_trait::async_trait]
+    impl EventHandler for RecordingHandler {
+        async fn handle(&self, _event: &DomainEvent) -> Result<(), AppError> {
+            self.seen.lock().await.push(EventMetadata::new().correlation_id);
+            Ok(())
+        }
+    }
+
+    async fn upload_song(State(bus): State<Arc<InMemoryEventBus>>) -> &'static str {
+        // Contexto Music: el evento se publica dentro de la petición
+        bus.publish(DomainEvent::SongUploaded {
+            song_id: Uuid::new_v4(),
+            artist_id: Uuid::new_v4(),
+            genre: "rock".to_string(),
+            occurred_at: chrono::Utc::now(),
+        })
+        .await
+        .unwrap();
+        "ok"
+    }
+
+    async fn app() -> (Router, Arc<Mutex<Vec<Uuid>>>) {
+        let bus = Arc::new(InMemoryEventBus::new());
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        bus.subscribe("SongUploaded", Arc::new(RecordingHandler { seen: Arc::clone(&seen) }))
+            .await
+            .unwrap();
+
+        let router = Router::new()
+            .route("/songs", post(upload_song))
+            .with_state(bus)
+            .layer(TracingLayer::new());
+        (router, seen)
+    }
+
+    #[tokio::test]
+    async fn correlation_id_propagates_from_header_to_other_context() {
+        let (router, seen) = app().await;
+        let correlation_id = Uuid::new_v4();
+
+        let response = router
+            .oneshot(
+                Request::post("/songs")
+                    .header(CORRELATION_ID_HEADER, correlation_id.to_string())
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            response.headers().get(CORRELATION_ID_HEADER).unwrap(),
+            correlation_id.to_string().as_str()
+        );
+        assert_eq!(*seen.lock().await, vec![correlation_id]);
+    }
+
+    #[tokio::test]
+    async fn missing_header_generates_correlation_id() {
+        let (router, seen) = app().await;
+
+        let response = router
+            .oneshot(Request::post("/songs").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        let header = response.headers().get(CORRELATION_ID_HEADER).unwrap().to_str().unwrap();
+        let generated = Uuid::parse_str(header).unwrap();
+        assert_eq!(*seen.lock().await, vec![generated]);
+    }
+
+    #[tokio::test]
+    async fn request_id_round_trips_and_reaches_handlers() {
+        async fn echo(Extension(request_id): Extension<RequestId>) -> String {
+            assert_eq!(current_request_id(), Some(request_id));
+            request_id.to_string()
+        }
+        let router = Router::new().route("/echo", get(echo)).layer(TracingLayer::new());
+        let request_id = Uuid::new_v4();
+
+        let response = router
+            .clone()
+            .oneshot(Request::get("/echo").header(REQUEST_ID_HEADER, request_id.to_string()).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], request_id.to_string().as_str());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, request_id.to_string().as_bytes());
+
+        // Lo que no es un UUID se sustituye por uno nuevo
+        let response = router
+            .oneshot(Request::get("/echo").header(REQUEST_ID_HEADER, "not-a-uuid").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let header = response.headers()[REQUEST_ID_HEADER].to_str().unwrap();
+        assert!(Uuid::parse_str(header).is_ok());
+    }
+
+    #[tokio::test]
+    async fn error_envelope_carries_the_request_id() {
+        let router = Router::new()
+            .route("/fail", get(|| async { AppError::NotFound("Song not found".to_string()) }))
+            .layer(TracingLayer::new());
+        let request_id = Uuid::new_v4();
+
+        let response = router
+            .oneshot(Request::get("/fail").header(REQUEST_ID_HEADER, request_id.to_string()).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], request_id.to_string().as_str());
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["request_id"], request_id.to_string());
+    }
+}
@@ -7,7 +7,8 @@ use std::sync::Arc;
 use crate::bounded_contexts::user::domain::{
     aggregates::{UserAggregate, UserSummary},
     entities::{User, UserProfile, UserPreferences, UserStats},
-    value_objects::{UserId, Email, Username, PasswordHash},
+    listening_preferences::ListeningPreferences,
+    value_objects::{UserId, Email, Username, PasswordHash, WalletAddress, ProfileUrl, UserRole, UserTier},
     repository::{UserRepository, UserSearchCriteria},
 };
 use crate::shared::domain::errors::AppError;
@@ -63,6 +64,56 @@ impl PostgresUserRepository {
             created_at: created_at.unwrap_or_else(|| chrono::Utc::now()),
         })
     }
+
+    /// Aggregate of a `users` row, keeping its id, wallet, role and tier
+    fn row_to_aggregate(row: &sqlx::postgres::PgRow) -> Result<UserAggregate, AppError> {
+        let user_id = UserId::from_uuid(row.try_get("id")?);
+        let email = Email::new(row.try_get("email")?)
+            .map_err(|e| AppError::ValidationError(format!("Invalid email: {}", e)))?;
+        let username = Username::new(row.try_get("username")?)
+            .map_err(|e| AppError::ValidationError(format!("Invalid username: {}", e)))?;
+        let password_hash = PasswordHash::new(row.try_get("password_hash")?);
+
+        let mut user = User::with_id(user_id.clone(), email, username, password_hash);
+        user.wallet_address = row.try_get::<Option<String>, _>("wallet_address")?
+            .and_then(|address| WalletAddress::new(address).ok());
+        if let Some(role) = row.try_get::<Option<String>, _>("role")? {
+            user.role = UserRole::from_str(&role).unwrap_or(UserRole::User);
+        }
+        if let Some(tier) = row.try_get::<Option<String>, _>("tier")? {
+            user.tier = UserTier::from_str(&tier).unwrap_or(UserTier::Free);
+        }
+        user.is_verified = row.try_get::<Option<bool>, _>("is_verified")?.unwrap_or(false);
+        if let Some(created_at) = row.try_get("created_at")? {
+            user.created_at = created_at;
+        }
+        if let Some(updated_at) = row.try_get("updated_at")? {
+            user.updated_at = updated_at;
+        }
+        user.username_changed_at = row.try_get("username_changed_at")?;
+        user.is_active = row.try_get::<Option<bool>, _>("is_active")?.unwrap_or(true);
+        user.deleted_at = row.try_get("deleted_at")?;
+
+        let mut profile = UserProfile::new(user_id.clone());
+        profile.update_display_name(row.try_get("display_name")?);
+        profile.update_bio(row.try_get("bio")?);
+        profile.update_avatar(row.try_get::<Option<String>, _>("avatar_url")?
+            .and_then(|url| ProfileUrl::new(url).ok()));
+        // Si el JSON guardado no se puede leer se recalcula en el próximo refresco
+        profile.listening_preferences = row
+            .try_get::<Option<sqlx::types::Json<ListeningPreferences>>, _>("listening_preferences")
+            .ok()
+            .flatten()
+            .map(|preferences| preferences.0);
+
+        Ok(UserAggregate::load(
+            user,
+            profile,
+            UserPreferences::new(user_id.clone()),
+            UserStats::new(user_id),
+            1, // version
+        ))
+    }
 }
 
 #[async_trait]
@@ -79,15 +130,21 @@ impl UserRepository for PostgresUserRepository {
         
         sqlx::query(
             r#"
-            INSERT INTO users (id, email, username, password_hash, display_name, bio, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            INSERT INTO users (id, email, username, password_hash, display_name, bio, created_at, updated_at, wallet_address,
+                               avatar_url, username_changed_at, is_active, deleted_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
             ON CONFLICT (id) DO UPDATE SET
                 email = EXCLUDED.email,
                 username = EXCLUDED.username,
                 password_hash = EXCLUDED.password_hash,
                 display_name = EXCLUDED.display_name,
                 bio = EXCLUDED.bio,
-                updated_at = EXCLUDED.updated_at
+                updated_at = EXCLUDED.updated_at,
+                wallet_address = EXCLUDED.wallet_address,
+                avatar_url = EXCLUDED.avatar_url,
+                username_changed_at = EXCLUDED.username_changed_at,
+                is_active = EXCLUDED.is_active,
+                deleted_at = EXCLUDED.deleted_at
             "#
         )
         .bind(user_id)
@@ -98,6 +155,11 @@ impl UserRepository for PostgresUserRepository {
         .bind(bio)
         .bind(created_at)
         .bind(updated_at)
+        .bind(user.user.wallet_address.as_ref().map(|address| address.value()))
+        .bind(user.profile.avatar_url.as_ref().map(|url| url.value()))
+        .bind(user.user.username_changed_at)
+        .bind(user.user.is_active)
+        .bind(user.user.deleted_at)
         .execute(&*self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to save user: {}", e)))?;
@@ -115,7 +177,8 @@ impl UserRepository for PostgresUserRepository {
         
         let row = sqlx::query(
             r#"
-            SELECT id, email, username, password_hash, display_name, bio, created_at, updated_at
+            SELECT id, email, username, password_hash, wallet_address, role, tier, is_verified,
+                   display_name, bio, avatar_url, listening_preferences, username_changed_at, is_active, deleted_at, created_at, updated_at
             FROM users
             WHERE id = $1
             "#
@@ -125,41 +188,7 @@ impl UserRepository for PostgresUserRepository {
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to find user by ID: {}", e)))?;
         
-        if let Some(row) = row {
-            // Create User entity with proper error handling
-            let email = Email::new(row.try_get("email")?)
-                .map_err(|e| AppError::ValidationError(format!("Invalid email: {}", e)))?;
-            let username = Username::new(row.try_get("username")?)
-                .map_err(|e| AppError::ValidationError(format!("Invalid username: {}", e)))?;
-            let password_hash = PasswordHash::new(row.try_get("password_hash")?);
-            
-            let user = User::new(email, username, password_hash);
-            
-            // Create UserProfile entity
-            let profile = UserProfile::new(UserId::from_uuid(row.try_get("id")?));
-            // Update profile with actual values
-            let mut profile = UserProfile::new(UserId::from_uuid(row.try_get("id")?));
-            profile.update_display_name(row.try_get("display_name")?);
-            profile.update_bio(row.try_get("bio")?);
-            
-            // Create default UserPreferences
-            let preferences = UserPreferences::new(UserId::from_uuid(row.try_get("id")?));
-            
-            // Create default UserStats
-            let stats = UserStats::new(UserId::from_uuid(row.try_get("id")?));
-            
-            let user_aggregate = UserAggregate::load(
-                user,
-                profile,
-                preferences,
-                stats,
-                1, // version
-            );
-            
-            Ok(Some(user_aggregate))
-        } else {
-            Ok(None)
-        }
+        row.map(|row| Self::row_to_aggregate(&row)).transpose()
     }
 
     async fn find_by_email(&self, email: &Email) -> Result<Option<UserAggregate>, AppError> {
@@ -167,7 +196,8 @@ impl UserRepository for PostgresUserRepository {
         
         let row = sqlx::query(
             r#"
-            SELECT id, email, username, password_hash, display_name, bio, created_at, updated_at
+            SELECT id, email, username, password_hash, wallet_address, role, tier, is_verified,
+                   display_name, bio, avatar_url, listening_preferences, username_changed_at, is_active, deleted_at, created_at, updated_at
             FROM users
             WHERE email = $1
             "#
@@ -177,41 +207,7 @@ impl UserRepository for PostgresUserRepository {
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to find user by email: {}", e)))?;
         
-        if let Some(row) = row {
-            // Create User entity with proper error handling
-            let email = Email::new(row.try_get("email")?)
-                .map_err(|e| AppError::ValidationError(format!("Invalid email: {}", e)))?;
-            let username = Username::new(row.try_get("username")?)
-                .map_err(|e| AppError::ValidationError(format!("Invalid username: {}", e)))?;
-            let password_hash = PasswordHash::new(row.try_get("password_hash")?);
-            
-            let user = User::new(email, username, password_hash);
-            
-            // Create UserProfile entity
-            let profile = UserProfile::new(UserId::from_uuid(row.try_get("id")?));
-            // Update profile with actual values
-            let mut profile = UserProfile::new(UserId::from_uuid(row.try_get("id")?));
-            profile.update_display_name(row.try_get("display_name")?);
-            profile.update_bio(row.try_get("bio")?);
-            
-            // Create default UserPreferences
-            let preferences = UserPreferences::new(UserId::from_uuid(row.try_get("id")?));
-            
-            // Create default UserStats
-            let stats = UserStats::new(UserId::from_uuid(row.try_get("id")?));
-            
-            let user_aggregate = UserAggregate::load(
-                user,
-                profile,
-                preferences,
-                stats,
-                1, // version
-            );
-            
-            Ok(Some(user_aggregate))
-        } else {
-            Ok(None)
-        }
+        row.map(|row| Self::row_to_aggregate(&row)).transpose()
     }
 
     async fn find_by_username(&self, username: &Username) -> Result<Option<UserAggregate>, AppError> {
@@ -219,7 +215,8 @@ impl UserRepository for PostgresUserRepository {
         
         let row = sqlx::query(
             r#"
-            SELECT id, email, username, password_hash, display_name, bio, created_at, updated_at
+            SELECT id, email, username, password_hash, wallet_address, role, tier, is_verified,
+                   display_name, bio, avatar_url, listening_preferences, username_changed_at, is_active, deleted_at, created_at, updated_at
             FROM users
             WHERE username = $1
             "#
@@ -229,41 +226,28 @@ impl UserRepository for PostgresUserRepository {
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to find user by username: {}", e)))?;
         
-        if let Some(row) = row {
-            // Create User entity with proper error handling
-            let email = Email::new(row.try_get("email")?)
-                .map_err(|e| AppError::ValidationError(format!("Invalid email: {}", e)))?;
-            let username = Username::new(row.try_get("username")?)
-                .map_err(|e| AppError::ValidationError(format!("Invalid username: {}", e)))?;
-            let password_hash = PasswordHash::new(row.try_get("password_hash")?);
-            
-            let user = User::new(email, username, password_hash);
-            
-            // Create UserProfile entity
-            let profile = UserProfile::new(UserId::from_uuid(row.try_get("id")?));
-            // Update profile with actual values
-            let mut profile = UserProfile::new(UserId::from_uuid(row.try_get("id")?));
-            profile.update_display_name(row.try_get("display_name")?);
-            profile.update_bio(row.try_get("bio")?);
-            
-            // Create default UserPreferences
-            let preferences = UserPreferences::new(UserId::from_uuid(row.try_get("id")?));
-            
-            // Create default UserStats
-            let stats = UserStats::new(UserId::from_uuid(row.try_get("id")?));
-            
-            let user_aggregate = UserAggregate::load(
-                user,
-                profile,
-                preferences,
-                stats,
-                1, // version
-            );
-            
-            Ok(Some(user_aggregate))
-        } else {
-            Ok(None)
-        }
+        row.map(|row| Self::row_to_aggregate(&row)).transpose()
+    }
+
+    async fn find_by_wallet_address(&self, address: &WalletAddress) -> Result<Option<UserAggregate>, AppError> {
+        // Las direcciones Ethereum se comparan sin checksum EIP-55; las de Solana son sensibles a mayúsculas
+        let row = sqlx::query(
+            r#"
+            SELECT id, email, username, password_hash, wallet_address, role, tier, is_verified,
+                   display_name, bio, avatar_url, listening_preferences, username_changed_at, is_active, deleted_at, created_at, updated_at
+            FROM users
+            WHERE CASE WHEN $2 THEN LOWER(wallet_address) = LOWER($1) ELSE wallet_address = $1 END
+            ORDER BY created_at
+            LIMIT 1
+            "#
+        )
+        .bind(address.value())
+        .bind(address.is_ethereum())
+        .fetch_optional(&*self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to find user by wallet: {}", e)))?;
+
+        row.map(|row| Self::row_to_aggregate(&row)).transpose()
     }
 
     async fn delete(&self, id: &UserId) -> Result<(), AppError> {
@@ -313,9 +297,10 @@ impl UserRepository for PostgresUserRepository {
         
         let rows = sqlx::query(
             r#"
-            SELECT id, email, username, password_hash, display_name, bio, created_at, updated_at
+            SELECT id, email, username, password_hash, display_name, bio, avatar_url, username_changed_at, is_active, deleted_at, created_at, updated_at
             FROM users
-            WHERE ($1::text IS NULL OR 
+            WHERE deleted_at IS NULL
+              AND ($1::text IS NULL OR 
                    username ILIKE $1 OR 
                    email ILIKE $1 OR 
                    display_name ILIKE $1)
@@ -374,40 +359,63 @@ impl UserRepository for PostgresUserRepository {
         Ok(count.unwrap_or(0) as u64)
     }
 
-    async fn add_follower(&self, follower_id: &UserId, followee_id: &UserId) -> Result<(), AppError> {
-        let follower_uuid = follower_id.value();
-        let followee_uuid = followee_id.value();
-        
-        sqlx::query(
+    async fn add_follower(&self, follower_id: &UserId, followee_id: &UserId) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to begin transaction: {}", e)))?;
+
+        let inserted = sqlx::query(
             r#"
-            INSERT INTO user_followers (follower_id, followee_id, created_at)
+            INSERT INTO follows (follower_id, followee_id, created_at)
             VALUES ($1, $2, NOW())
             ON CONFLICT (follower_id, followee_id) DO NOTHING
             "#
         )
-        .bind(follower_uuid)
-        .bind(followee_uuid)
-        .execute(&*self.pool)
+        .bind(follower_id.value())
+        .bind(followee_id.value())
+        .execute(&mut *tx)
         .await
-        .map_err(|e| AppError::DatabaseError(format!("Failed to add follower: {}", e)))?;
-        
-        Ok(())
+        .map_err(|e| AppError::DatabaseError(format!("Failed to add follower: {}", e)))?
+        .rows_affected() == 1;
+
+        // Solo si la fila es nueva: un follow repetido no infla el contador
+        if inserted {
+            sqlx::query("UPDATE artists SET follower_count = follower_count + 1 WHERE user_id = $1")
+                .bind(followee_id.value())
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to update follower count: {}", e)))?;
+        }
+
+        tx.commit().await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit follow: {}", e)))?;
+        Ok(inserted)
     }
 
-    async fn remove_follower(&self, follower_id: &UserId, followee_id: &UserId) -> Result<(), AppError> {
-        let follower_uuid = follower_id.value();
-        let followee_uuid = followee_id.value();
-        
-        sqlx::query(
-            "DELETE FROM user_followers WHERE follower_id = $1 AND followee_id = $2"
+    async fn remove_follower(&self, follower_id: &UserId, followee_id: &UserId) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to begin transaction: {}", e)))?;
+
+        let deleted = sqlx::query(
+            "DELETE FROM follows WHERE follower_id = $1 AND followee_id = $2"
         )
-        .bind(follower_uuid)
-        .bind(followee_uuid)
-        .execute(&*self.pool)
+        .bind(follower_id.value())
+        .bind(followee_id.value())
+        .execute(&mut *tx)
         .await
-        .map_err(|e| AppError::DatabaseError(format!("Failed to remove follower: {}", e)))?;
-        
-        Ok(())
+        .map_err(|e| AppError::DatabaseError(format!("Failed to remove follower: {}", e)))?
+        .rows_affected() == 1;
+
+        if deleted {
+            sqlx::query("UPDATE artists SET follower_count = follower_count - 1 WHERE user_id = $1")
+                .bind(followee_id.value())
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| AppError::DatabaseError(format!("Failed to update follower count: {}", e)))?;
+        }
+
+        tx.commit().await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to commit unfollow: {}", e)))?;
+        Ok(deleted)
     }
 
     async fn find_users(&self, criteria: UserSearchCriteria) -> Result<Vec<UserSummary>, AppError> {
@@ -554,8 +562,8 @@ impl UserRepository for PostgresUserRepository {
                 COALESCE(utp.current_points, 0)::INTEGER as tier_points
             FROM users u
             LEFT JOIN listen_sessions ls ON u.id = ls.user_id AND ls.status IN ('completed', 'verified', 'rewarded')
-            LEFT JOIN user_followers uf ON u.id = uf.follower_id
-            LEFT JOIN user_followers uf2 ON u.id = uf2.followee_id
+            LEFT JOIN follows uf ON u.id = uf.follower_id
+            LEFT JOIN follows uf2 ON u.id = uf2.followee_id
             LEFT JOIN fan_investments fi ON u.id = fi.fan_id AND fi.status = 'active'
             LEFT JOIN campaign_nfts cn ON u.id = cn.user_id
             LEFT JOIN nft_purchases nft ON u.id = nft.user_id
@@ -628,9 +636,9 @@ impl UserRepository for PostgresUserRepository {
                 u.total_rewards_earned, u.current_balance,
                 u.created_at
             FROM users u
-            INNER JOIN user_followers uf ON u.id = uf.follower_id
+            INNER JOIN follows uf ON u.id = uf.follower_id
             WHERE uf.followee_id = $1
-            ORDER BY uf.created_at DESC
+            ORDER BY uf.created_at DESC, u.id
             LIMIT $2 OFFSET $3
             "#
         )
@@ -674,9 +682,9 @@ impl UserRepository for PostgresUserRepository {
                 u.total_rewards_earned, u.current_balance,
                 u.created_at
             FROM users u
-            INNER JOIN user_followers uf ON u.id = uf.followee_id
+            INNER JOIN follows uf ON u.id = uf.followee_id
             WHERE uf.follower_id = $1
-            ORDER BY uf.created_at DESC
+            ORDER BY uf.created_at DESC, u.id
             LIMIT $2 OFFSET $3
             "#
         )
@@ -708,20 +716,40 @@ impl UserRepository for PostgresUserRepository {
         Ok(following)
     }
 
+    async fn count_followers(&self, user_id: &UserId) -> Result<u64, AppError> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM follows WHERE followee_id = $1")
+            .bind(user_id.value())
+            .fetch_one(&*self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to count followers: {}", e)))?;
+
+        Ok(count as u64)
+    }
+
+    async fn count_following(&self, user_id: &UserId) -> Result<u64, AppError> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM follows WHERE follower_id = $1")
+            .bind(user_id.value())
+            .fetch_one(&*self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to count following: {}", e)))?;
+
+        Ok(count as u64)
+    }
+
     async fn is_following(&self, follower_id: &UserId, followee_id: &UserId) -> Result<bool, AppError> {
         let follower_uuid = follower_id.value();
         let followee_uuid = followee_id.value();
         
-        let count = sqlx::query_scalar!(
-            "SELECT COUNT(*) FROM user_followers WHERE follower_id = $1 AND followee_id = $2",
-            follower_uuid,
-            followee_uuid
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM follows WHERE follower_id = $1 AND followee_id = $2"
         )
+        .bind(follower_uuid)
+        .bind(followee_uuid)
         .fetch_one(&*self.pool)
         .await
         .map_err(|e| AppError::DatabaseError(format!("Failed to check if following: {}", e)))?;
         
-        Ok(count.unwrap_or(0) > 0)
+        Ok(count > 0)
     }
 
     async fn find_users_registered_between(
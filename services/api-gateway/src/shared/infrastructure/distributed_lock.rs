@@ -0,0 +1,158 @@
+// =============================================================================
+// DISTRIBUTED LOCKS
+// =============================================================================
+//
+// Exclusión mutua entre instancias del gateway con Redis:
+// `SET lock:<recurso> <token> NX PX <ttl>`. El token identifica al dueño, de
+// modo que solo quien tomó el lock puede soltarlo; el TTL lo libera solo si el
+// dueño muere, así que debe ser mayor que lo que dura la operación protegida.
+
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::shared::domain::errors::AppError;
+
+/// Pausa entre intentos mientras se espera un lock ocupado
+const RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum LockError {
+    #[error("Lock {0} not acquired in time")]
+    AcquisitionTimeout(String),
+    #[error("Lock {0} is not held by this owner (it expired or was taken over)")]
+    LockNotHeld(String),
+    #[error("Lock store error: {0}")]
+    Store(String),
+}
+
+impl From<LockError> for AppError {
+    fn from(error: LockError) -> Self {
+        match error {
+            LockError::Store(e) => AppError::ExternalServiceError(e),
+            other => AppError::ConcurrencyConflict(other.to_string()),
+        }
+    }
+}
+
+#[async_trait]
+pub trait DistributedLock: Send + Sync {
+    /// Tomar `key` para `token` si está libre. `false` si lo tiene otro.
+    async fn try_acquire(&self, key: &str, token: &str, ttl: Duration) -> Result<bool, LockError>;
+
+    /// Soltar `key` si sigue siendo de `token`
+    async fn release(&self, key: &str, token: &str) -> Result<(), LockError>;
+}
+
+/// Reintentar `try_acquire` hasta `wait`
+pub async fn acquire_lock(
+    lock: Arc<dyn DistributedLock>,
+    key: &str,
+    token: &str,
+    ttl: Duration,
+    wait: Duration,
+) -> Result<LockGuard, LockError> {
+    let deadline = Instant::now() + wait;
+    loop {
+        if lock.try_acquire(key, token, ttl).await? {
+            return Ok(LockGuard {
+                lock,
+                key: key.to_string(),
+                token: token.to_string(),
+                released: false,
+            });
+        }
+        if Instant::now() + RETRY_INTERVAL > deadline {
+            return Err(LockError::AcquisitionTimeout(key.to_string()));
+        }
+        tokio::time::sleep(RETRY_INTERVAL).await;
+    }
+}
+
+/// Lock tomado. Se suelta con `release`; si el futuro que lo tiene se cancela
+/// antes, `Drop` lo suelta en segundo plano para no esperar al TTL.
+pub struct LockGuard {
+    lock: Arc<dyn DistributedLock>,
+    key: String,
+    token: String,
+    released: bool,
+}
+
+impl LockGuard {
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub async fn release(mut self) -> Result<(), LockError> {
+        self.released = true;
+        self.lock.release(&self.key, &self.token).await
+    }
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        if self.released {
+            return;
+        }
+        let (lock, key, token) = (self.lock.clone(), std::mem::take(&mut self.key), std::mem::take(&mut self.token));
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move {
+                if let Err(e) = lock.release(&key, &token).await {
+                    tracing::warn!("Failed to release abandoned lock {}: {}", key, e);
+                }
+            });
+        }
+    }
+}
+
+/// Borra la clave solo si su valor es el token de quien la suelta
+const RELEASE_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
+pub struct RedisDistributedLock {
+    connection: ConnectionManager,
+    release_script: redis::Script,
+}
+
+impl RedisDistributedLock {
+    pub fn new(connection: ConnectionManager) -> Self {
+        Self { connection, release_script: redis::Script::new(RELEASE_SCRIPT) }
+    }
+}
+
+#[async_trait]
+impl DistributedLock for RedisDistributedLock {
+    async fn try_acquire(&self, key: &str, token: &str, ttl: Duration) -> Result<bool, LockError> {
+        let mut conn = self.connection.clone();
+        let reply: Option<String> = redis::cmd("SET")
+            .arg(key)
+            .arg(token)
+            .arg("NX")
+            .arg("PX")
+            .arg(ttl.as_millis() as u64)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| LockError::Store(format!("Redis lock error: {}", e)))?;
+        Ok(reply.is_some())
+    }
+
+    async fn release(&self, key: &str, token: &str) -> Result<(), LockError> {
+        let mut conn = self.connection.clone();
+        let deleted: i64 = self.release_script
+            .key(key)
+            .arg(token)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| LockError::Store(format!("Redis lock error: {}", e)))?;
+        if deleted == 1 {
+            Ok(())
+        } else {
+            Err(LockError::LockNotHeld(key.to_string()))
+        }
+    }
+}
@@ -29,7 +29,8 @@ impl DomainEventWrapper {
                 event_type,
                 aggregate_type,
                 aggregate_id,
-                correlation_id,
+                // Sin ID explícito se usa el de la petición en curso
+                correlation_id: correlation_id.or_else(crate::shared::domain::events::current_correlation_id),
                 causation_id: None,
                 occurred_at: Utc::now(),
                 version: 1,
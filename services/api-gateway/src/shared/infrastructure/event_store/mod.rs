@@ -0,0 +1,342 @@
+// =============================================================================
+// EVENT STORE
+// =============================================================================
+//
+// Registro append-only de eventos de dominio compartido por los contextos. Cada
+// agregado tiene su stream con números de secuencia consecutivos: al añadir se
+// indica la versión que se leyó y, si otro escribió entremedias, se rechaza
+// (concurrencia optimista). Además todos los eventos tienen una posición
+// global, que es lo que recorren las proyecciones al reconstruirse.
+//
+// Se guarda `DomainEvent::event_data()` con el nombre del tipo; el
+// `EventTypeRegistry` usa ese nombre para volver a obtener el evento concreto.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+use crate::shared::domain::events::DomainEvent;
+
+pub mod postgres;
+pub mod projections;
+
+pub use postgres::PostgresEventStore;
+pub use projections::{
+    CheckpointStore, InMemoryCheckpointStore, PostgresCheckpointStore, Projection, ProjectionRunner, RebuildReport,
+};
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum EventStoreError {
+    #[error("Stream {aggregate_id} is at version {actual}, expected {expected}")]
+    ConcurrencyConflict { aggregate_id: Uuid, expected: i64, actual: i64 },
+    #[error("Unknown event type: {0}")]
+    UnknownEventType(String),
+    #[error("Event serialization failed: {0}")]
+    Serialization(String),
+    #[error("Event store error: {0}")]
+    Storage(String),
+}
+
+impl From<EventStoreError> for AppError {
+    fn from(error: EventStoreError) -> Self {
+        match error {
+            EventStoreError::ConcurrencyConflict { .. } => AppError::ConcurrencyConflict(error.to_string()),
+            EventStoreError::UnknownEventType(_) | EventStoreError::Serialization(_) => {
+                AppError::SerializationError(error.to_string())
+            }
+            EventStoreError::Storage(e) => AppError::DatabaseError(e),
+        }
+    }
+}
+
+/// Evento tal como quedó guardado
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredEvent {
+    /// Posición global, creciente en orden de escritura
+    pub position: i64,
+    pub event_id: Uuid,
+    pub aggregate_id: Uuid,
+    pub aggregate_type: String,
+    /// Número del evento dentro de su stream, desde 1
+    pub sequence: i64,
+    pub event_type: String,
+    pub event_data: serde_json::Value,
+    pub correlation_id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl StoredEvent {
+    /// Recuperar el evento concreto a partir de su nombre de tipo
+    pub fn decode(&self, registry: &EventTypeRegistry) -> Result<Box<dyn DomainEvent>, EventStoreError> {
+        registry.decode(&self.event_type, &self.event_data)
+    }
+}
+
+/// Evento listo para guardar en un stream
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewEvent {
+    pub event_id: Uuid,
+    pub aggregate_type: String,
+    pub event_type: String,
+    pub event_data: serde_json::Value,
+    pub correlation_id: Uuid,
+    pub user_id: Option<Uuid>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+impl NewEvent {
+    pub fn from_domain_event(event: &dyn DomainEvent) -> Self {
+        let metadata = event.metadata();
+        Self {
+            event_id: metadata.event_id,
+            aggregate_type: event.aggregate_type().to_string(),
+            event_type: event.event_type().to_string(),
+            event_data: event.event_data(),
+            correlation_id: metadata.correlation_id,
+            user_id: metadata.user_id,
+            occurred_at: event.occurred_at(),
+        }
+    }
+}
+
+#[async_trait]
+pub trait EventStore: Send + Sync {
+    /// Añadir `events` al stream de `aggregate_id`. `expected_version` es la
+    /// secuencia del último evento que se leyó (0 si el stream es nuevo); si el
+    /// stream ya avanzó falla con `ConcurrencyConflict`. Devuelve la nueva versión.
+    async fn append(
+        &self,
+        aggregate_id: Uuid,
+        expected_version: i64,
+        events: &[Box<dyn DomainEvent>],
+    ) -> Result<i64, EventStoreError>;
+
+    /// Stream completo de `aggregate_id` en orden de secuencia
+    async fn load_stream(&self, aggregate_id: Uuid) -> Result<Vec<StoredEvent>, EventStoreError>;
+
+    /// Hasta `limit` eventos de todos los streams posteriores a `position`, en
+    /// orden global. El suscriptor guarda la posición del último que procesó y
+    /// vuelve a llamar desde ella.
+    async fn subscribe_from(&self, position: i64, limit: i64) -> Result<Vec<StoredEvent>, EventStoreError>;
+}
+
+type EventDecoder = Arc<dyn Fn(&serde_json::Value) -> Result<Box<dyn DomainEvent>, String> + Send + Sync>;
+
+/// Tipos de evento que se pueden recuperar del store, por nombre
+#[derive(Clone, Default)]
+pub struct EventTypeRegistry {
+    decoders: HashMap<String, EventDecoder>,
+}
+
+impl EventTypeRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registrar `E` bajo `event_type`, el valor de su `DomainEvent::event_type()`.
+    /// `E` debe deserializarse desde su propio `event_data()`.
+    pub fn register<E>(&mut self, event_type: &str) -> &mut Self
+    where
+        E: DomainEvent + DeserializeOwned + 'static,
+    {
+        self.decoders.insert(
+            event_type.to_string(),
+            Arc::new(|data| {
+                serde_json::from_value::<E>(data.clone())
+                    .map(|event| Box::new(event) as Box<dyn DomainEvent>)
+                    .map_err(|e| e.to_string())
+            }),
+        );
+        self
+    }
+
+    pub fn is_registered(&self, event_type: &str) -> bool {
+        self.decoders.contains_key(event_type)
+    }
+
+    pub fn decode(&self, event_type: &str, data: &serde_json::Value) -> Result<Box<dyn DomainEvent>, EventStoreError> {
+        let decoder = self
+            .decoders
+            .get(event_type)
+            .ok_or_else(|| EventStoreError::UnknownEventType(event_type.to_string()))?;
+        decoder(data).map_err(|e| EventStoreError::Serialization(format!("{}: {}", event_type, e)))
+    }
+}
+
+/// Event store en memoria para tests
+#[derive(Default)]
+pub struct InMemoryEventStore {
+    events: Mutex<Vec<StoredEvent>>,
+}
+
+impl InMemoryEventStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl EventStore for InMemoryEventStore {
+    async fn append(
+        &self,
+        aggregate_id: Uuid,
+        expected_version: i64,
+        events: &[Box<dyn DomainEvent>],
+    ) -> Result<i64, EventStoreError> {
+        let mut stored = self.events.lock().unwrap();
+        let actual = stored.iter().filter(|event| event.aggregate_id == aggregate_id).count() as i64;
+        if actual != expected_version {
+            return Err(EventStoreError::ConcurrencyConflict { aggregate_id, expected: expected_version, actual });
+        }
+
+        for (offset, event) in events.iter().enumerate() {
+            let new_event = NewEvent::from_domain_event(event.as_ref());
+            let position = stored.len() as i64 + 1;
+            stored.push(StoredEvent {
+                position,
+                event_id: new_event.event_id,
+                aggregate_id,
+                aggregate_type: new_event.aggregate_type,
+                sequence: expected_version + offset as i64 + 1,
+                event_type: new_event.event_type,
+                event_data: new_event.event_data,
+                correlation_id: new_event.correlation_id,
+                user_id: new_event.user_id,
+                occurred_at: new_event.occurred_at,
+            });
+        }
+        Ok(expected_version + events.len() as i64)
+    }
+
+    async fn load_stream(&self, aggregate_id: Uuid) -> Result<Vec<StoredEvent>, EventStoreError> {
+        Ok(self.events.lock().unwrap().iter().filter(|event| event.aggregate_id == aggregate_id).cloned().collect())
+    }
+
+    async fn subscribe_from(&self, position: i64, limit: i64) -> Result<Vec<StoredEvent>, EventStoreError> {
+        Ok(self
+            .events
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|event| event.position > position)
+            .take(limit.max(0) as usize)
+            .cloned()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_events {
+    use super::*;
+    use crate::shared::domain::events::EventMetadata;
+    use serde::{Deserialize, Serialize};
+
+    /// Evento mínimo para probar el store
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    pub struct SongPlayed {
+        pub song_id: Uuid,
+        pub seconds: u32,
+        pub metadata: EventMetadata,
+    }
+
+    impl SongPlayed {
+        pub fn new(song_id: Uuid, seconds: u32) -> Self {
+            Self {
+                song_id,
+                seconds,
+                metadata: EventMetadata::with_type_and_aggregate("SongPlayed", song_id, "Song"),
+            }
+        }
+
+        pub fn boxed(song_id: Uuid, seconds: u32) -> Box<dyn DomainEvent> {
+            Box::new(Self::new(song_id, seconds))
+        }
+    }
+
+    impl DomainEvent for SongPlayed {
+        fn metadata(&self) -> &EventMetadata {
+            &self.metadata
+        }
+
+        fn event_type(&self) -> &str {
+            "SongPlayed"
+        }
+
+        fn aggregate_id(&self) -> Uuid {
+            self.song_id
+        }
+
+        fn aggregate_type(&self) -> &str {
+            "Song"
+        }
+
+        fn occurred_at(&self) -> DateTime<Utc> {
+            self.metadata.occurred_at
+        }
+
+        fn event_data(&self) -> serde_json::Value {
+            serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_events::SongPlayed;
+    use super::*;
+
+    #[tokio::test]
+    async fn appends_with_a_stale_version_conflict() {
+        let store = InMemoryEventStore::new();
+        let song = Uuid::new_v4();
+
+        assert_eq!(store.append(song, 0, &[SongPlayed::boxed(song, 30)]).await.unwrap(), 1);
+        // Dos escritores leyeron la versión 1; solo el primero entra
+        assert_eq!(store.append(song, 1, &[SongPlayed::boxed(song, 45)]).await.unwrap(), 2);
+        let conflict = store.append(song, 1, &[SongPlayed::boxed(song, 60)]).await.unwrap_err();
+
+        assert_eq!(conflict, EventStoreError::ConcurrencyConflict { aggregate_id: song, expected: 1, actual: 2 });
+        assert!(matches!(AppError::from(conflict), AppError::ConcurrencyConflict(_)));
+        let stream = store.load_stream(song).await.unwrap();
+        assert_eq!(stream.iter().map(|event| event.sequence).collect::<Vec<_>>(), vec![1, 2]);
+    }
+
+    #[tokio::test]
+    async fn subscription_pages_through_the_global_order() {
+        let store = InMemoryEventStore::new();
+        let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+        store.append(first, 0, &[SongPlayed::boxed(first, 1), SongPlayed::boxed(first, 2)]).await.unwrap();
+        store.append(second, 0, &[SongPlayed::boxed(second, 3)]).await.unwrap();
+
+        let page = store.subscribe_from(0, 2).await.unwrap();
+        assert_eq!(page.iter().map(|event| event.position).collect::<Vec<_>>(), vec![1, 2]);
+        let rest = store.subscribe_from(page.last().unwrap().position, 10).await.unwrap();
+        assert_eq!(rest.len(), 1);
+        assert_eq!((rest[0].aggregate_id, rest[0].sequence), (second, 1));
+        assert!(store.subscribe_from(3, 10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn registry_decodes_stored_events_by_type_name() {
+        let store = InMemoryEventStore::new();
+        let song = Uuid::new_v4();
+        let played = SongPlayed::new(song, 42);
+        store.append(song, 0, &[Box::new(played.clone())]).await.unwrap();
+        let stored = store.load_stream(song).await.unwrap().remove(0);
+
+        let mut registry = EventTypeRegistry::new();
+        assert!(matches!(stored.decode(&registry), Err(EventStoreError::UnknownEventType(_))));
+        registry.register::<SongPlayed>("SongPlayed");
+
+        let decoded = stored.decode(&registry).unwrap();
+        assert_eq!(decoded.event_type(), "SongPlayed");
+        assert_eq!(decoded.metadata().event_id, played.metadata.event_id);
+        assert_eq!(decoded.event_data()["seconds"], 42);
+        assert_eq!(stored.event_id, played.metadata.event_id);
+    }
+}
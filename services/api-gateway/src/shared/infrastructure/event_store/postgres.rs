@@ -0,0 +1,137 @@
+use async_trait::async_trait;
+use sqlx::{postgres::PgRow, PgPool, Row};
+use uuid::Uuid;
+
+use super::{EventStore, EventStoreError, NewEvent, StoredEvent};
+use crate::shared::domain::events::DomainEvent;
+
+const UNIQUE_VIOLATION: &str = "23505";
+/// Lock de transacción que serializa las escrituras: así las posiciones se
+/// asignan en orden de commit y quien lee desde una posición no se salta un
+/// evento que aún no había confirmado
+const APPEND_LOCK_KEY: i64 = 0x6576_656e_7473;
+
+const EVENT_COLUMNS: &str = r#"position, event_id, aggregate_id, aggregate_type, sequence, event_type, event_data,
+       correlation_id, user_id, occurred_at"#;
+
+/// Event store sobre la tabla `events`
+pub struct PostgresEventStore {
+    pool: PgPool,
+}
+
+impl PostgresEventStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn row_to_event(row: PgRow) -> StoredEvent {
+        StoredEvent {
+            position: row.get("position"),
+            event_id: row.get("event_id"),
+            aggregate_id: row.get("aggregate_id"),
+            aggregate_type: row.get("aggregate_type"),
+            sequence: row.get("sequence"),
+            event_type: row.get("event_type"),
+            event_data: row.get("event_data"),
+            correlation_id: row.get("correlation_id"),
+            user_id: row.get("user_id"),
+            occurred_at: row.get("occurred_at"),
+        }
+    }
+}
+
+fn storage_error(e: sqlx::Error) -> EventStoreError {
+    EventStoreError::Storage(e.to_string())
+}
+
+#[async_trait]
+impl EventStore for PostgresEventStore {
+    async fn append(
+        &self,
+        aggregate_id: Uuid,
+        expected_version: i64,
+        events: &[Box<dyn DomainEvent>],
+    ) -> Result<i64, EventStoreError> {
+        let mut tx = self.pool.begin().await.map_err(storage_error)?;
+        sqlx::query("SELECT pg_advisory_xact_lock($1)")
+            .bind(APPEND_LOCK_KEY)
+            .execute(&mut *tx)
+            .await
+            .map_err(storage_error)?;
+
+        let actual: i64 = sqlx::query_scalar("SELECT COALESCE(MAX(sequence), 0) FROM events WHERE aggregate_id = $1")
+            .bind(aggregate_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(storage_error)?;
+        if actual != expected_version {
+            return Err(EventStoreError::ConcurrencyConflict { aggregate_id, expected: expected_version, actual });
+        }
+
+        for (offset, event) in events.iter().enumerate() {
+            let event = NewEvent::from_domain_event(event.as_ref());
+            let result = sqlx::query(
+                r#"INSERT INTO events (
+                       event_id, aggregate_id, aggregate_type, sequence, event_type, event_data,
+                       correlation_id, user_id, occurred_at
+                   ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"#,
+            )
+            .bind(event.event_id)
+            .bind(aggregate_id)
+            .bind(&event.aggregate_type)
+            .bind(expected_version + offset as i64 + 1)
+            .bind(&event.event_type)
+            .bind(&event.event_data)
+            .bind(event.correlation_id)
+            .bind(event.user_id)
+            .bind(event.occurred_at)
+            .execute(&mut *tx)
+            .await;
+
+            match result {
+                Ok(_) => {}
+                // (aggregate_id, sequence) ya ocupado: alguien escribió sin pasar por el lock
+                Err(sqlx::Error::Database(db))
+                    if db.code().as_deref() == Some(UNIQUE_VIOLATION) && db.constraint() == Some("events_aggregate_sequence_key") =>
+                {
+                    return Err(EventStoreError::ConcurrencyConflict {
+                        aggregate_id,
+                        expected: expected_version,
+                        actual: expected_version + offset as i64 + 1,
+                    });
+                }
+                Err(e) => return Err(storage_error(e)),
+            }
+        }
+
+        tx.commit().await.map_err(storage_error)?;
+        Ok(expected_version + events.len() as i64)
+    }
+
+    async fn load_stream(&self, aggregate_id: Uuid) -> Result<Vec<StoredEvent>, EventStoreError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM events WHERE aggregate_id = $1 ORDER BY sequence",
+            EVENT_COLUMNS
+        ))
+        .bind(aggregate_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(storage_error)?;
+
+        Ok(rows.into_iter().map(Self::row_to_event).collect())
+    }
+
+    async fn subscribe_from(&self, position: i64, limit: i64) -> Result<Vec<StoredEvent>, EventStoreError> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM events WHERE position > $1 ORDER BY position LIMIT $2",
+            EVENT_COLUMNS
+        ))
+        .bind(position)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(storage_error)?;
+
+        Ok(rows.into_iter().map(Self::row_to_event).collect())
+    }
+}
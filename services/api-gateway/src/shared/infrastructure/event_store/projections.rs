@@ -0,0 +1,243 @@
+// Proyecciones sobre el event store
+//
+// Una proyección es un modelo de lectura construido aplicando los eventos en
+// orden global. El runner guarda tras cada lote la última posición aplicada
+// (checkpoint), de modo que si se corta continúa desde ahí; `rebuild` vacía la
+// proyección y la reconstruye desde la posición 0.
+use async_trait::async_trait;
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use super::{EventStore, StoredEvent};
+use crate::shared::domain::errors::AppError;
+
+const DEFAULT_BATCH_SIZE: i64 = 500;
+
+#[async_trait]
+pub trait Projection: Send + Sync {
+    /// Nombre con el que se guarda su checkpoint
+    fn name(&self) -> &str;
+
+    /// Borrar el estado de la proyección antes de reconstruirla
+    async fn reset(&self) -> Result<(), AppError>;
+
+    /// Aplicar un evento; los tipos que no le interesan se ignoran
+    async fn apply(&self, event: &StoredEvent) -> Result<(), AppError>;
+}
+
+#[async_trait]
+pub trait CheckpointStore: Send + Sync {
+    /// Última posición aplicada por `projection`, 0 si nunca se ejecutó
+    async fn load(&self, projection: &str) -> Result<i64, AppError>;
+
+    async fn save(&self, projection: &str, position: i64) -> Result<(), AppError>;
+}
+
+/// Checkpoints en la tabla `projection_checkpoints`
+pub struct PostgresCheckpointStore {
+    pool: PgPool,
+}
+
+impl PostgresCheckpointStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for PostgresCheckpointStore {
+    async fn load(&self, projection: &str) -> Result<i64, AppError> {
+        let position: Option<i64> =
+            sqlx::query_scalar("SELECT position FROM projection_checkpoints WHERE projection_name = $1")
+                .bind(projection)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(position.unwrap_or(0))
+    }
+
+    async fn save(&self, projection: &str, position: i64) -> Result<(), AppError> {
+        sqlx::query(
+            r#"INSERT INTO projection_checkpoints (projection_name, position, updated_at)
+               VALUES ($1, $2, NOW())
+               ON CONFLICT (projection_name) DO UPDATE SET position = EXCLUDED.position, updated_at = NOW()"#,
+        )
+        .bind(projection)
+        .bind(position)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(e.to_string()))?;
+        Ok(())
+    }
+}
+
+/// Checkpoints en memoria para tests
+#[derive(Default)]
+pub struct InMemoryCheckpointStore {
+    positions: Mutex<HashMap<String, i64>>,
+}
+
+impl InMemoryCheckpointStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for InMemoryCheckpointStore {
+    async fn load(&self, projection: &str) -> Result<i64, AppError> {
+        Ok(self.positions.lock().unwrap().get(projection).copied().unwrap_or(0))
+    }
+
+    async fn save(&self, projection: &str, position: i64) -> Result<(), AppError> {
+        self.positions.lock().unwrap().insert(projection.to_string(), position);
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RebuildReport {
+    pub events_applied: u64,
+    /// Posición del último evento aplicado (el checkpoint final)
+    pub position: i64,
+}
+
+pub struct ProjectionRunner {
+    store: Arc<dyn EventStore>,
+    checkpoints: Arc<dyn CheckpointStore>,
+    batch_size: i64,
+}
+
+impl ProjectionRunner {
+    pub fn new(store: Arc<dyn EventStore>, checkpoints: Arc<dyn CheckpointStore>) -> Self {
+        Self { store, checkpoints, batch_size: DEFAULT_BATCH_SIZE }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: i64) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Aplicar los eventos posteriores al checkpoint de la proyección
+    pub async fn catch_up(&self, projection: &dyn Projection) -> Result<RebuildReport, AppError> {
+        let mut position = self.checkpoints.load(projection.name()).await?;
+        let mut events_applied = 0;
+
+        loop {
+            let batch = self.store.subscribe_from(position, self.batch_size).await?;
+            for event in &batch {
+                projection.apply(event).await?;
+                events_applied += 1;
+            }
+            let Some(last) = batch.last() else { break };
+            position = last.position;
+            self.checkpoints.save(projection.name(), position).await?;
+
+            if (batch.len() as i64) < self.batch_size {
+                break;
+            }
+        }
+
+        Ok(RebuildReport { events_applied, position })
+    }
+
+    /// Reconstruir la proyección desde la posición 0
+    pub async fn rebuild(&self, projection: &dyn Projection) -> Result<RebuildReport, AppError> {
+        tracing::info!("Rebuilding projection {}", projection.name());
+        projection.reset().await?;
+        self.checkpoints.save(projection.name(), 0).await?;
+
+        let report = self.catch_up(projection).await?;
+        tracing::info!(
+            "Projection {} rebuilt: {} events applied up to position {}",
+            projection.name(),
+            report.events_applied,
+            report.position
+        );
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_events::SongPlayed;
+    use super::super::InMemoryEventStore;
+    use super::*;
+    use uuid::Uuid;
+
+    /// Segundos reproducidos por canción
+    #[derive(Default)]
+    struct SecondsPlayed {
+        totals: Mutex<HashMap<Uuid, u64>>,
+        fail_at: Mutex<Option<i64>>,
+    }
+
+    #[async_trait]
+    impl Projection for SecondsPlayed {
+        fn name(&self) -> &str {
+            "seconds_played"
+        }
+
+        async fn reset(&self) -> Result<(), AppError> {
+            self.totals.lock().unwrap().clear();
+            Ok(())
+        }
+
+        async fn apply(&self, event: &StoredEvent) -> Result<(), AppError> {
+            if *self.fail_at.lock().unwrap() == Some(event.position) {
+                return Err(AppError::InternalError("projection crashed".to_string()));
+            }
+            let seconds = event.event_data["seconds"].as_u64().unwrap_or(0);
+            *self.totals.lock().unwrap().entry(event.aggregate_id).or_default() += seconds;
+            Ok(())
+        }
+    }
+
+    async fn seeded_store(song: Uuid, plays: &[u32]) -> Arc<InMemoryEventStore> {
+        let store = Arc::new(InMemoryEventStore::new());
+        let events: Vec<_> = plays.iter().map(|seconds| SongPlayed::boxed(song, *seconds)).collect();
+        store.append(song, 0, &events).await.unwrap();
+        store
+    }
+
+    #[tokio::test]
+    async fn rebuild_replays_everything_from_position_zero() {
+        let song = Uuid::new_v4();
+        let store = seeded_store(song, &[10, 20, 30, 40, 50]).await;
+        let checkpoints = Arc::new(InMemoryCheckpointStore::new());
+        let runner = ProjectionRunner::new(store, checkpoints.clone()).with_batch_size(2);
+        let projection = SecondsPlayed::default();
+        // Estado corrupto previo que el rebuild debe descartar
+        projection.totals.lock().unwrap().insert(song, 999);
+        checkpoints.save("seconds_played", 5).await.unwrap();
+
+        let report = runner.rebuild(&projection).await.unwrap();
+
+        assert_eq!(report, RebuildReport { events_applied: 5, position: 5 });
+        assert_eq!(projection.totals.lock().unwrap()[&song], 150);
+        assert_eq!(checkpoints.load("seconds_played").await.unwrap(), 5);
+    }
+
+    #[tokio::test]
+    async fn catch_up_resumes_from_the_last_checkpoint() {
+        let song = Uuid::new_v4();
+        let store = seeded_store(song, &[10, 20, 30, 40, 50]).await;
+        let checkpoints = Arc::new(InMemoryCheckpointStore::new());
+        let runner = ProjectionRunner::new(store, checkpoints.clone()).with_batch_size(2);
+        let projection = SecondsPlayed::default();
+        *projection.fail_at.lock().unwrap() = Some(4);
+
+        assert!(runner.catch_up(&projection).await.is_err());
+        // Solo el primer lote completo quedó confirmado
+        assert_eq!(checkpoints.load("seconds_played").await.unwrap(), 2);
+
+        projection.fail_at.lock().unwrap().take();
+        projection.reset().await.unwrap();
+        projection.totals.lock().unwrap().insert(song, 30);
+        let report = runner.catch_up(&projection).await.unwrap();
+
+        assert_eq!(report, RebuildReport { events_applied: 3, position: 5 });
+        assert_eq!(projection.totals.lock().unwrap()[&song], 150);
+    }
+}
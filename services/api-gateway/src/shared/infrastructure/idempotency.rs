@@ -0,0 +1,582 @@
+// =============================================================================
+// IDEMPOTENCY KEYS
+// =============================================================================
+//
+// Middleware reutilizable para endpoints que crean cargos: el cliente envía una
+// cabecera `Idempotency-Key` y, si reintenta la misma petición, recibe la
+// respuesta original en lugar de ejecutar el handler otra vez.
+//
+// Las claves son de cada usuario: se guardan como `{user_id}:{key}`, así que la
+// petición se autentica antes de reservar nada y otro usuario con la misma
+// clave no ve la respuesta guardada.
+
+use async_trait::async_trait;
+use axum::{
+    body::{to_bytes, Body},
+    extract::{FromRequestParts, Request, State},
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use sqlx::{PgPool, Row};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use crate::auth::Claims;
+use crate::shared::domain::errors::AppError;
+use crate::shared::domain::events::current_request_id;
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+pub const IDEMPOTENT_REPLAY_HEADER: &str = "idempotent-replayed";
+/// Las claves caducan a las 24 horas
+pub const IDEMPOTENCY_KEY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+const MAX_KEY_LENGTH: usize = 255;
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Respuesta guardada para devolverla tal cual en los reintentos
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+    /// Id del recurso creado (p.ej. el payment_id), si la respuesta lo incluye
+    pub resource_id: Option<String>,
+}
+
+/// Resultado de intentar reservar una clave
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdempotencyClaim {
+    /// Primera petición con esta clave: se ejecuta el handler
+    Acquired,
+    /// La clave ya se completó con el mismo cuerpo
+    Replay(StoredResponse),
+    /// Otra petición con la misma clave todavía se está procesando
+    InProgress,
+    /// La clave se usó con un cuerpo distinto
+    Mismatch,
+}
+
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// Reservar la clave de forma atómica para el hash de la petición
+    async fn claim(&self, key: &str, request_hash: &str) -> Result<IdempotencyClaim, AppError>;
+
+    /// Guardar la respuesta final de una clave reservada
+    async fn complete(&self, key: &str, response: &StoredResponse) -> Result<(), AppError>;
+
+    /// Liberar una clave reservada sin respuesta (errores 5xx), para permitir reintentos
+    async fn release(&self, key: &str) -> Result<(), AppError>;
+}
+
+/// Store respaldado por la tabla `idempotency_keys`
+pub struct PostgresIdempotencyStore {
+    pool: PgPool,
+    ttl: Duration,
+}
+
+impl PostgresIdempotencyStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool, ttl: IDEMPOTENCY_KEY_TTL }
+    }
+
+    /// Borrar claves caducadas
+    pub async fn purge_expired(&self) -> Result<u64, AppError> {
+        let result = sqlx::query("DELETE FROM idempotency_keys WHERE expires_at < NOW()")
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to purge idempotency keys: {}", e)))?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for PostgresIdempotencyStore {
+    async fn claim(&self, key: &str, request_hash: &str) -> Result<IdempotencyClaim, AppError> {
+        // El INSERT decide qué petición gana si llegan dos a la vez; una clave
+        // caducada se reutiliza como si fuera nueva. Se guarda el request ID de
+        // la petición que la ejecutó.
+        let acquired = sqlx::query(
+            r#"
+            INSERT INTO idempotency_keys (key, request_hash, status, request_id, created_at, expires_at)
+            VALUES ($1, $2, 'in_progress', $4, NOW(), NOW() + make_interval(secs => $3))
+            ON CONFLICT (key) DO UPDATE SET
+                request_hash = EXCLUDED.request_hash,
+                request_id = EXCLUDED.request_id,
+                status = 'in_progress',
+                response_status = NULL,
+                response_content_type = NULL,
+                response_body = NULL,
+                resource_id = NULL,
+                created_at = NOW(),
+                expires_at = EXCLUDED.expires_at
+            WHERE idempotency_keys.expires_at < NOW()
+            RETURNING key
+            "#,
+        )
+        .bind(key)
+        .bind(request_hash)
+        .bind(self.ttl.as_secs_f64())
+        .bind(current_request_id().map(|id| id.0))
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to claim idempotency key: {}", e)))?;
+
+        if acquired.is_some() {
+            return Ok(IdempotencyClaim::Acquired);
+        }
+
+        let row = sqlx::query(
+            r#"
+            SELECT request_hash, status, response_status, response_content_type, response_body, resource_id
+            FROM idempotency_keys
+            WHERE key = $1
+            "#,
+        )
+        .bind(key)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to load idempotency key: {}", e)))?;
+
+        // Liberada entre ambas consultas: el cliente puede reintentar
+        let Some(row) = row else {
+            return Ok(IdempotencyClaim::InProgress);
+        };
+
+        if row.get::<String, _>("request_hash") != request_hash {
+            return Ok(IdempotencyClaim::Mismatch);
+        }
+        if row.get::<String, _>("status") != "completed" {
+            return Ok(IdempotencyClaim::InProgress);
+        }
+
+        Ok(IdempotencyClaim::Replay(StoredResponse {
+            status: row.get::<i32, _>("response_status") as u16,
+            content_type: row.get("response_content_type"),
+            body: row.get("response_body"),
+            resource_id: row.get("resource_id"),
+        }))
+    }
+
+    async fn complete(&self, key: &str, response: &StoredResponse) -> Result<(), AppError> {
+        sqlx::query(
+            r#"
+            UPDATE idempotency_keys
+            SET status = 'completed', response_status = $2, response_content_type = $3,
+                response_body = $4, resource_id = $5
+            WHERE key = $1
+            "#,
+        )
+        .bind(key)
+        .bind(response.status as i32)
+        .bind(&response.content_type)
+        .bind(&response.body)
+        .bind(&response.resource_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| AppError::DatabaseError(format!("Failed to store idempotent response: {}", e)))?;
+
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> Result<(), AppError> {
+        sqlx::query("DELETE FROM idempotency_keys WHERE key = $1 AND status = 'in_progress'")
+            .bind(key)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| AppError::DatabaseError(format!("Failed to release idempotency key: {}", e)))?;
+
+        Ok(())
+    }
+}
+
+struct InMemoryEntry {
+    request_hash: String,
+    response: Option<StoredResponse>,
+    expires_at: Instant,
+}
+
+/// Store en memoria para tests y entornos sin base de datos
+pub struct InMemoryIdempotencyStore {
+    entries: Mutex<HashMap<String, InMemoryEntry>>,
+    ttl: Duration,
+}
+
+impl InMemoryIdempotencyStore {
+    pub fn new() -> Self {
+        Self::with_ttl(IDEMPOTENCY_KEY_TTL)
+    }
+
+    pub fn with_ttl(ttl: Duration) -> Self {
+        Self { entries: Mutex::new(HashMap::new()), ttl }
+    }
+}
+
+impl Default for InMemoryIdempotencyStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for InMemoryIdempotencyStore {
+    async fn claim(&self, key: &str, request_hash: &str) -> Result<IdempotencyClaim, AppError> {
+        let mut entries = self.entries.lock().await;
+        let now = Instant::now();
+
+        if let Some(entry) = entries.get(key).filter(|entry| entry.expires_at > now) {
+            return Ok(if entry.request_hash != request_hash {
+                IdempotencyClaim::Mismatch
+            } else {
+                match &entry.response {
+                    Some(response) => IdempotencyClaim::Replay(response.clone()),
+                    None => IdempotencyClaim::InProgress,
+                }
+            });
+        }
+
+        entries.insert(key.to_string(), InMemoryEntry {
+            request_hash: request_hash.to_string(),
+            response: None,
+            expires_at: now + self.ttl,
+        });
+        Ok(IdempotencyClaim::Acquired)
+    }
+
+    async fn complete(&self, key: &str, response: &StoredResponse) -> Result<(), AppError> {
+        if let Some(entry) = self.entries.lock().await.get_mut(key) {
+            entry.response = Some(response.clone());
+        }
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> Result<(), AppError> {
+        let mut entries = self.entries.lock().await;
+        if entries.get(key).map_or(false, |entry| entry.response.is_none()) {
+            entries.remove(key);
+        }
+        Ok(())
+    }
+}
+
+/// Hash de método, ruta y cuerpo: reutilizar la clave en otro endpoint también es un conflicto
+pub fn request_fingerprint(method: &str, path: &str, body: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(method.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(path.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(body);
+    hex::encode(hasher.finalize())
+}
+
+/// Buscar el id del recurso creado en respuestas `ApiResponse` (`data.payment_id`, `data.id`...)
+fn extract_resource_id(body: &[u8]) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_slice(body).ok()?;
+    let data = value.get("data").unwrap_or(&value);
+    ["payment_id", "id"]
+        .iter()
+        .find_map(|field| data.get(*field))
+        .map(|id| id.as_str().map(String::from).unwrap_or_else(|| id.to_string()))
+}
+
+/// Clave en el store: la del cliente dentro del espacio de su usuario
+pub fn scoped_key(user_id: &str, key: &str) -> String {
+    format!("{}:{}", user_id, key)
+}
+
+/// Respuestas que no se guardan: los 5xx para poder reintentar, y los 401/403
+/// porque dependen de las credenciales y no del cuerpo de la petición
+fn is_retryable(status: StatusCode) -> bool {
+    status.is_server_error() || status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN
+}
+
+fn replay_response(stored: StoredResponse) -> Response {
+    let mut response = Response::new(Body::from(stored.body));
+    *response.status_mut() = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    if let Some(content_type) = stored.content_type.and_then(|ct| HeaderValue::from_str(&ct).ok()) {
+        response.headers_mut().insert(header::CONTENT_TYPE, content_type);
+    }
+    response
+        .headers_mut()
+        .insert(IDEMPOTENT_REPLAY_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+/// Idempotency-Key middleware
+/// Peticiones sin cabecera pasan sin cambios.
+/// Compatible with axum::middleware::from_fn_with_state
+pub async fn idempotency_middleware(
+    State(store): State<Arc<dyn IdempotencyStore>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let key = match request.headers().get(IDEMPOTENCY_KEY_HEADER) {
+        None => return next.run(request).await,
+        Some(value) => match value.to_str() {
+            Ok(key) if !key.trim().is_empty() && key.len() <= MAX_KEY_LENGTH => key.trim().to_string(),
+            _ => return AppError::ValidationError("Invalid Idempotency-Key header".to_string()).into_response(),
+        },
+    };
+
+    // Sin usuario autenticado no se reserva la clave: el handler rechazaría la
+    // petición y ese 401 no debe quedar guardado para el dueño de la clave
+    let (mut parts, body) = request.into_parts();
+    let claims = match Claims::from_request_parts(&mut parts, &()).await {
+        Ok(claims) => claims,
+        Err(_) => return AppError::Unauthorized("Valid bearer token required".to_string()).into_response(),
+    };
+    let key = scoped_key(&claims.sub, &key);
+
+    let body = match to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(_) => return AppError::ValidationError("Request body too large".to_string()).into_response(),
+    };
+    let request_hash = request_fingerprint(parts.method.as_str(), parts.uri.path(), &body);
+
+    match store.claim(&key, &request_hash).await {
+        Ok(IdempotencyClaim::Acquired) => {}
+        Ok(IdempotencyClaim::Replay(stored)) => return replay_response(stored),
+        Ok(IdempotencyClaim::InProgress) => {
+            return AppError::ConflictError("A request with this Idempotency-Key is already in progress".to_string())
+                .into_response();
+        }
+        Ok(IdempotencyClaim::Mismatch) => {
+            return AppError::ConflictError("Idempotency-Key was already used with a different request".to_string())
+                .into_response();
+        }
+        Err(e) => {
+            tracing::error!("Idempotency store unavailable: {}", e);
+            return AppError::ServiceUnavailable("Idempotency store unavailable".to_string()).into_response();
+        }
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+    // El cliente debe poder reintentar con la misma clave
+    if is_retryable(response.status()) {
+        if let Err(e) = store.release(&key).await {
+            tracing::warn!("Failed to release idempotency key {}: {}", key, e);
+        }
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, MAX_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            let _ = store.release(&key).await;
+            tracing::error!("Failed to buffer response for idempotency key {}: {}", key, e);
+            return AppError::InternalError("Failed to read response".to_string()).into_response();
+        }
+    };
+
+    let stored = StoredResponse {
+        status: parts.status.as_u16(),
+        content_type: parts
+            .headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(String::from),
+        body: body.to_vec(),
+        resource_id: extract_resource_id(&body),
+    };
+    if let Err(e) = store.complete(&key, &stored).await {
+        tracing::error!("Failed to store response for idempotency key {}: {}", key, e);
+    }
+
+    Response::from_parts(parts, Body::from(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware, response::Json as ResponseJson, routing::post, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    const PAYER: &str = "7b0c5a4e-2f7e-4c1e-9a55-0c3f1f2d9e01";
+
+    fn app(store: Arc<dyn IdempotencyStore>, calls: Arc<AtomicUsize>) -> Router {
+        Router::new()
+            .route(
+                "/payments",
+                post(move |body: String| {
+                    let calls = Arc::clone(&calls);
+                    async move {
+                        let n = calls.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        (
+                            StatusCode::CREATED,
+                            ResponseJson(serde_json::json!({
+                                "success": true,
+                                "data": { "payment_id": format!("payment-{}", n), "echo": body }
+                            })),
+                        )
+                    }
+                }),
+            )
+            .route_layer(middleware::from_fn_with_state(store, idempotency_middleware))
+    }
+
+    fn bearer(user_id: &str) -> String {
+        let claims = Claims::new(
+            Uuid::parse_str(user_id).unwrap(), "fan".to_string(), "fan@vibestream.test".to_string(),
+            "user".to_string(), "access".to_string(),
+        );
+        format!("Bearer {}", claims.to_jwt().unwrap())
+    }
+
+    fn request_as(user_id: Option<&str>, key: &str, body: &str) -> Request {
+        let mut request = Request::builder()
+            .method("POST")
+            .uri("/payments")
+            .header(IDEMPOTENCY_KEY_HEADER, key)
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(user_id) = user_id {
+            request = request.header(header::AUTHORIZATION, bearer(user_id));
+        }
+        request.body(Body::from(body.to_string())).unwrap()
+    }
+
+    fn payment_request(key: &str, body: &str) -> Request {
+        request_as(Some(PAYER), key, body)
+    }
+
+    async fn body_json(response: Response) -> serde_json::Value {
+        serde_json::from_slice(&to_bytes(response.into_body(), MAX_BODY_BYTES).await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn replay_returns_original_response() {
+        let store: Arc<dyn IdempotencyStore> = Arc::new(InMemoryIdempotencyStore::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(Arc::clone(&store), Arc::clone(&calls));
+
+        let first = app.clone().oneshot(payment_request("key-1", r#"{"amount":10}"#)).await.unwrap();
+        assert_eq!(first.status(), StatusCode::CREATED);
+        let first_body = body_json(first).await;
+
+        let replay = app.oneshot(payment_request("key-1", r#"{"amount":10}"#)).await.unwrap();
+        assert_eq!(replay.status(), StatusCode::CREATED);
+        assert_eq!(replay.headers().get(IDEMPOTENT_REPLAY_HEADER).unwrap(), "true");
+        assert_eq!(body_json(replay).await, first_body);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let hash = request_fingerprint("POST", "/payments", br#"{"amount":10}"#);
+        match store.claim(&scoped_key(PAYER, "key-1"), &hash).await.unwrap() {
+            IdempotencyClaim::Replay(stored) => assert_eq!(stored.resource_id.as_deref(), Some("payment-0")),
+            other => panic!("unexpected claim: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn conflicting_replay_is_rejected() {
+        let store: Arc<dyn IdempotencyStore> = Arc::new(InMemoryIdempotencyStore::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(store, Arc::clone(&calls));
+
+        let first = app.clone().oneshot(payment_request("key-2", r#"{"amount":10}"#)).await.unwrap();
+        assert_eq!(first.status(), StatusCode::CREATED);
+
+        let conflicting = app.oneshot(payment_request("key-2", r#"{"amount":99}"#)).await.unwrap();
+        assert_eq!(conflicting.status(), StatusCode::CONFLICT);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn concurrent_first_requests_run_handler_once() {
+        let store: Arc<dyn IdempotencyStore> = Arc::new(InMemoryIdempotencyStore::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(store, Arc::clone(&calls));
+
+        let (a, b) = tokio::join!(
+            app.clone().oneshot(payment_request("key-3", r#"{"amount":10}"#)),
+            app.clone().oneshot(payment_request("key-3", r#"{"amount":10}"#)),
+        );
+        let mut statuses = vec![a.unwrap().status(), b.unwrap().status()];
+        statuses.sort();
+
+        assert_eq!(statuses, vec![StatusCode::CREATED, StatusCode::CONFLICT]);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn expired_key_runs_handler_again() {
+        let store: Arc<dyn IdempotencyStore> = Arc::new(InMemoryIdempotencyStore::with_ttl(Duration::from_millis(10)));
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(store, Arc::clone(&calls));
+
+        app.clone().oneshot(payment_request("key-4", r#"{"amount":10}"#)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let second = app.oneshot(payment_request("key-4", r#"{"amount":10}"#)).await.unwrap();
+
+        assert!(second.headers().get(IDEMPOTENT_REPLAY_HEADER).is_none());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn keys_are_scoped_to_the_caller() {
+        let store: Arc<dyn IdempotencyStore> = Arc::new(InMemoryIdempotencyStore::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(store, Arc::clone(&calls));
+        let other_user = Uuid::new_v4().to_string();
+
+        let mine = app.clone().oneshot(payment_request("key-5", r#"{"amount":10}"#)).await.unwrap();
+        let theirs = app.oneshot(request_as(Some(&other_user), "key-5", r#"{"amount":10}"#)).await.unwrap();
+
+        // La misma clave y el mismo cuerpo de otro usuario no reciben mi pago
+        assert!(theirs.headers().get(IDEMPOTENT_REPLAY_HEADER).is_none());
+        assert_eq!(body_json(mine).await["data"]["payment_id"], "payment-0");
+        assert_eq!(body_json(theirs).await["data"]["payment_id"], "payment-1");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn unauthenticated_requests_do_not_claim_the_key() {
+        let store: Arc<dyn IdempotencyStore> = Arc::new(InMemoryIdempotencyStore::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let app = app(store, Arc::clone(&calls));
+
+        let anonymous = app.clone().oneshot(request_as(None, "key-6", r#"{"amount":10}"#)).await.unwrap();
+        assert_eq!(anonymous.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(body_json(anonymous).await["error"]["code"], "UNAUTHORIZED");
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        let owner = app.oneshot(payment_request("key-6", r#"{"amount":10}"#)).await.unwrap();
+        assert_eq!(owner.status(), StatusCode::CREATED);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn forbidden_responses_are_not_stored() {
+        let store: Arc<dyn IdempotencyStore> = Arc::new(InMemoryIdempotencyStore::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let handler_calls = Arc::clone(&calls);
+        let app = Router::new()
+            .route(
+                "/payments",
+                post(move || {
+                    let calls = Arc::clone(&handler_calls);
+                    async move {
+                        match calls.fetch_add(1, Ordering::SeqCst) {
+                            0 => StatusCode::FORBIDDEN,
+                            _ => StatusCode::CREATED,
+                        }
+                    }
+                }),
+            )
+            .route_layer(middleware::from_fn_with_state(store, idempotency_middleware));
+
+        let denied = app.clone().oneshot(payment_request("key-7", "{}")).await.unwrap();
+        assert_eq!(denied.status(), StatusCode::FORBIDDEN);
+
+        let retried = app.oneshot(payment_request("key-7", "{}")).await.unwrap();
+        assert_eq!(retried.status(), StatusCode::CREATED);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,387 @@
+// =============================================================================
+// PROMETHEUS METRICS
+// =============================================================================
+//
+// `MetricsLayer` cuenta las peticiones de cada gateway (total, duración y en
+// curso) etiquetadas por gateway, plantilla de ruta, método y clase de estado.
+// La ruta sale de `MatchedPath` (`/songs/:id`, nunca el UUID), así que el
+// número de series está acotado por las rutas declaradas; lo que no casa con
+// ninguna cuenta como `unmatched`.
+//
+// El registro es del proceso: cada gateway publica en `/metrics` sus series más
+// las del proceso, el pool de Postgres y Redis, y el gateway unificado publica
+// todas juntas.
+
+use axum::{
+    extract::{MatchedPath, State},
+    http::{header, Method, Request, Response},
+    response::IntoResponse,
+    routing::get,
+    Router,
+};
+use prometheus::{
+    Encoder, Gauge, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry, TextEncoder,
+};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower::{Layer, Service};
+
+use crate::services::MessageQueue;
+use crate::shared::infrastructure::app_state::AppState;
+
+pub const METRICS_PATH: &str = "/metrics";
+/// Etiqueta `route` de las peticiones que no casan con ninguna ruta
+pub const UNMATCHED_ROUTE: &str = "unmatched";
+/// Un Redis que no responde en este tiempo se publica como caído
+const REDIS_PING_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Registro de métricas y fuentes (pool, Redis) que se leen en cada scrape
+pub struct Metrics {
+    registry: Registry,
+    requests_total: IntCounterVec,
+    request_duration_seconds: HistogramVec,
+    requests_in_flight: IntGaugeVec,
+    db_pool_connections: IntGaugeVec,
+    db_pool_max_connections: IntGauge,
+    redis_up: IntGauge,
+    redis_ping_seconds: Gauge,
+    database_pool: OnceLock<sqlx::PgPool>,
+    message_queue: OnceLock<MessageQueue>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+        let requests_total = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests handled"),
+            &["gateway", "route", "method", "status"],
+        )
+        .expect("valid metric");
+        let request_duration_seconds = HistogramVec::new(
+            HistogramOpts::new("http_request_duration_seconds", "HTTP request latency in seconds"),
+            &["gateway", "route", "method", "status"],
+        )
+        .expect("valid metric");
+        let requests_in_flight = IntGaugeVec::new(
+            Opts::new("http_requests_in_flight", "HTTP requests being handled"),
+            &["gateway", "route", "method"],
+        )
+        .expect("valid metric");
+        let db_pool_connections = IntGaugeVec::new(
+            Opts::new("db_pool_connections", "Postgres pool connections by state"),
+            &["state"],
+        )
+        .expect("valid metric");
+        let db_pool_max_connections =
+            IntGauge::new("db_pool_max_connections", "Postgres pool size limit").expect("valid metric");
+        let redis_up = IntGauge::new("redis_up", "Whether Redis answered PING").expect("valid metric");
+        let redis_ping_seconds =
+            Gauge::new("redis_ping_seconds", "Redis PING round trip in seconds").expect("valid metric");
+
+        let collectors: Vec<Box<dyn prometheus::core::Collector>> = vec![
+            Box::new(requests_total.clone()),
+            Box::new(request_duration_seconds.clone()),
+            Box::new(requests_in_flight.clone()),
+            Box::new(db_pool_connections.clone()),
+            Box::new(db_pool_max_connections.clone()),
+            Box::new(redis_up.clone()),
+            Box::new(redis_ping_seconds.clone()),
+        ];
+        for collector in collectors {
+            registry.register(collector).expect("metric registered once");
+        }
+        #[cfg(target_os = "linux")]
+        registry
+            .register(Box::new(prometheus::process_collector::ProcessCollector::for_self()))
+            .expect("process metrics registered once");
+
+        Self {
+            registry,
+            requests_total,
+            request_duration_seconds,
+            requests_in_flight,
+            db_pool_connections,
+            db_pool_max_connections,
+            redis_up,
+            redis_ping_seconds,
+            database_pool: OnceLock::new(),
+            message_queue: OnceLock::new(),
+        }
+    }
+
+    /// Métricas del proceso, compartidas por todos los gateways
+    pub fn global() -> Arc<Metrics> {
+        static GLOBAL: OnceLock<Arc<Metrics>> = OnceLock::new();
+        GLOBAL.get_or_init(|| Arc::new(Metrics::new())).clone()
+    }
+
+    /// Publicar el estado del pool de Postgres y de Redis del `AppState`
+    pub fn observe_app_state(&self, app_state: &AppState) {
+        self.observe_database_pool(app_state.get_db_pool().clone());
+        let _ = self.message_queue.set(app_state.message_queue.clone());
+    }
+
+    pub fn observe_database_pool(&self, pool: sqlx::PgPool) {
+        let _ = self.database_pool.set(pool);
+    }
+
+    /// Leer pool y Redis; se hace en cada scrape para publicar valores al día
+    pub async fn refresh_resources(&self) {
+        if let Some(pool) = self.database_pool.get() {
+            let size = pool.size() as i64;
+            let idle = pool.num_idle() as i64;
+            self.db_pool_connections.with_label_values(&["idle"]).set(idle);
+            self.db_pool_connections.with_label_values(&["active"]).set(size - idle);
+            self.db_pool_max_connections.set(pool.options().get_max_connections() as i64);
+        }
+        if let Some(message_queue) = self.message_queue.get() {
+            let started = Instant::now();
+            match tokio::time::timeout(REDIS_PING_TIMEOUT, message_queue.ping()).await {
+                Ok(Ok(())) => {
+                    self.redis_up.set(1);
+                    self.redis_ping_seconds.set(started.elapsed().as_secs_f64());
+                }
+                _ => self.redis_up.set(0),
+            }
+        }
+    }
+
+    /// Formato de texto de Prometheus. Con `gateway` solo se incluyen las
+    /// series HTTP de ese gateway (las que no llevan la etiqueta, todas).
+    pub fn render(&self, gateway: Option<&str>) -> String {
+        let families: Vec<_> = self
+            .registry
+            .gather()
+            .into_iter()
+            .filter_map(|mut family| {
+                if let Some(gateway) = gateway {
+                    let metrics: Vec<_> = family
+                        .take_metric()
+                        .into_iter()
+                        .filter(|metric| {
+                            metric
+                                .get_label()
+                                .iter()
+                                .all(|label| label.get_name() != "gateway" || label.get_value() == gateway)
+                        })
+                        .collect();
+                    family.set_metric(metrics.into());
+                }
+                (!family.get_metric().is_empty()).then_some(family)
+            })
+            .collect();
+
+        let mut buffer = Vec::new();
+        TextEncoder::new().encode(&families, &mut buffer).expect("text encoding never fails");
+        String::from_utf8(buffer).expect("text format is UTF-8")
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `GET /metrics`; con `gateway`, solo las series de ese gateway
+pub fn metrics_router(metrics: Arc<Metrics>, gateway: Option<&str>) -> Router {
+    Router::new()
+        .route(METRICS_PATH, get(metrics_handler))
+        .with_state((metrics, gateway.map(str::to_string)))
+}
+
+async fn metrics_handler(State((metrics, gateway)): State<(Arc<Metrics>, Option<String>)>) -> impl IntoResponse {
+    metrics.refresh_resources().await;
+    ([(header::CONTENT_TYPE, prometheus::TEXT_FORMAT)], metrics.render(gateway.as_deref()))
+}
+
+/// Tower layer que registra las peticiones de un gateway. Tiene que aplicarse
+/// con `Router::layer` para ver la `MatchedPath` de cada ruta.
+#[derive(Clone)]
+pub struct MetricsLayer {
+    metrics: Arc<Metrics>,
+    gateway: Arc<str>,
+}
+
+impl MetricsLayer {
+    pub fn new(gateway: &str) -> Self {
+        Self::with_metrics(Metrics::global(), gateway)
+    }
+
+    pub fn with_metrics(metrics: Arc<Metrics>, gateway: &str) -> Self {
+        Self { metrics, gateway: gateway.into() }
+    }
+}
+
+impl<S> Layer<S> for MetricsLayer {
+    type Service = MetricsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MetricsService { inner, metrics: self.metrics.clone(), gateway: self.gateway.clone() }
+    }
+}
+
+#[derive(Clone)]
+pub struct MetricsService<S> {
+    inner: S,
+    metrics: Arc<Metrics>,
+    gateway: Arc<str>,
+}
+
+/// Métodos no estándar se agrupan para no abrir series arbitrarias
+fn method_label(method: &Method) -> &'static str {
+    match *method {
+        Method::GET => "GET",
+        Method::POST => "POST",
+        Method::PUT => "PUT",
+        Method::PATCH => "PATCH",
+        Method::DELETE => "DELETE",
+        Method::HEAD => "HEAD",
+        Method::OPTIONS => "OPTIONS",
+        _ => "OTHER",
+    }
+}
+
+fn status_class(status: u16) -> &'static str {
+    match status {
+        100..=199 => "1xx",
+        200..=299 => "2xx",
+        300..=399 => "3xx",
+        400..=499 => "4xx",
+        _ => "5xx",
+    }
+}
+
+/// Resta la petición de las que están en curso también si se cancela
+struct InFlight(IntGauge);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.dec();
+    }
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for MetricsService<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let route = request
+            .extensions()
+            .get::<MatchedPath>()
+            .map_or(UNMATCHED_ROUTE.to_string(), |path| path.as_str().to_string());
+        let method = method_label(request.method());
+        let metrics = self.metrics.clone();
+        let gateway = self.gateway.clone();
+
+        let in_flight = metrics.requests_in_flight.with_label_values(&[&*gateway, route.as_str(), method]);
+        in_flight.inc();
+        let in_flight = InFlight(in_flight);
+        let started = Instant::now();
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let result = future.await;
+            drop(in_flight);
+            let status = match &result {
+                Ok(response) => status_class(response.status().as_u16()),
+                Err(_) => "5xx",
+            };
+            let labels = [&*gateway, route.as_str(), method, status];
+            metrics.requests_total.with_label_values(&labels).inc();
+            metrics
+                .request_duration_seconds
+                .with_label_values(&labels)
+                .observe(started.elapsed().as_secs_f64());
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode};
+    use tower::ServiceExt;
+
+    fn gateway(metrics: &Arc<Metrics>, name: &str) -> Router {
+        Router::new()
+            .route("/songs/:id", get(|| async { "song" }))
+            .route("/fail", get(|| async { StatusCode::INTERNAL_SERVER_ERROR }))
+            .layer(MetricsLayer::with_metrics(metrics.clone(), name))
+    }
+
+    async fn send(router: &Router, uri: &str) -> StatusCode {
+        let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+        router.clone().oneshot(request).await.unwrap().status()
+    }
+
+    fn sample<'a>(text: &'a str, name: &str, labels: &[&str]) -> Option<&'a str> {
+        text.lines()
+            .filter(|line| line.starts_with(&format!("{}{{", name)))
+            .find(|line| labels.iter().all(|label| line.contains(label)))
+            .and_then(|line| line.rsplit(' ').next())
+    }
+
+    #[tokio::test]
+    async fn requests_are_labeled_by_route_template() {
+        let metrics = Arc::new(Metrics::new());
+        let music = gateway(&metrics, "music");
+        let first = uuid::Uuid::new_v4().to_string();
+
+        assert_eq!(send(&music, &format!("/songs/{}", first)).await, StatusCode::OK);
+        assert_eq!(send(&music, &format!("/songs/{}", uuid::Uuid::new_v4())).await, StatusCode::OK);
+        assert_eq!(send(&music, "/fail").await, StatusCode::INTERNAL_SERVER_ERROR);
+        assert_eq!(send(&music, "/nowhere").await, StatusCode::NOT_FOUND);
+
+        let text = metrics.render(None);
+        let song = ["gateway=\"music\"", "route=\"/songs/:id\"", "method=\"GET\"", "status=\"2xx\""];
+        assert_eq!(sample(&text, "http_requests_total", &song), Some("2"));
+        assert_eq!(sample(&text, "http_request_duration_seconds_count", &song), Some("2"));
+        assert_eq!(sample(&text, "http_requests_total", &["route=\"/fail\"", "status=\"5xx\""]), Some("1"));
+        assert_eq!(sample(&text, "http_requests_total", &["route=\"unmatched\"", "status=\"4xx\""]), Some("1"));
+        assert_eq!(sample(&text, "http_requests_in_flight", &["route=\"/songs/:id\""]), Some("0"));
+        assert!(!text.contains(&first));
+    }
+
+    #[tokio::test]
+    async fn gateway_endpoint_only_shows_its_own_series() {
+        let metrics = Arc::new(Metrics::new());
+        send(&gateway(&metrics, "music"), "/songs/1").await;
+        send(&gateway(&metrics, "user"), "/songs/1").await;
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .max_connections(7)
+            .connect_lazy("postgres://vibestream@localhost/vibestream")
+            .unwrap();
+        metrics.observe_database_pool(pool);
+
+        let router = metrics_router(metrics.clone(), Some("music"));
+        let response = router
+            .oneshot(Request::builder().uri(METRICS_PATH).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], prometheus::TEXT_FORMAT);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+
+        assert!(text.contains("gateway=\"music\""));
+        assert!(!text.contains("gateway=\"user\""));
+        assert_eq!(sample(&text, "db_pool_connections", &["state=\"idle\""]), Some("0"));
+        assert!(text.contains("db_pool_max_connections 7"));
+
+        assert!(metrics.render(None).contains("gateway=\"user\""));
+    }
+}
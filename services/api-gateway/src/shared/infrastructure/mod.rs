@@ -1,4 +1,4 @@
-//! Shared infrastructure components (database, messaging, security, websocket, cdn, discovery).
+//! Shared infrastructure components (database, messaging, security, websocket, cdn, discovery, metrics, tracing).
 
 pub mod event_bus;
 pub mod clients;
@@ -8,6 +8,16 @@ pub mod cdn;
 pub mod discovery;
 pub mod app_state;
 pub mod auth;
+pub mod idempotency;
+pub mod rate_limit;
+pub mod distributed_lock;
+pub mod correlation;
+pub mod outbox;
+pub mod event_store;
+pub mod saga;
+pub mod shutdown;
+pub mod metrics;
+pub mod telemetry;
 
 // Re-export common database types
 pub use database::postgres::PostgresUserRepository;
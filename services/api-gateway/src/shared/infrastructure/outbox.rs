@@ -0,0 +1,427 @@
+// =============================================================================
+// TRANSACTIONAL OUTBOX
+// =============================================================================
+//
+// Los casos de uso escriben sus eventos en `outbox_events` dentro de la misma
+// transacción que el cambio del agregado: o se guardan los dos o ninguno. Un
+// relay lee las filas pendientes con `FOR UPDATE SKIP LOCKED` (varias
+// instancias se reparten el trabajo sin pisarse), las publica y las marca como
+// enviadas al confirmar la transacción.
+//
+// La entrega es al menos una vez: si el relay muere después de publicar y antes
+// de confirmar, las filas siguen pendientes y se vuelven a publicar. Por eso
+// cada evento viaja con su id y los consumidores descartan los repetidos.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{postgres::PgRow, PgPool, Postgres, Row, Transaction};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::bounded_contexts::orchestrator::{DomainEvent, EventBus};
+use crate::shared::domain::errors::AppError;
+use crate::shared::domain::events::DomainEvent as MetadataDomainEvent;
+
+/// Filas que el relay toma por transacción
+pub const DEFAULT_OUTBOX_BATCH_SIZE: i64 = 100;
+/// Intentos antes de dejar un evento en `dead_letter`
+pub const DEFAULT_OUTBOX_MAX_ATTEMPTS: i32 = 10;
+/// Espera antes del primer reintento; se dobla en cada uno
+const DEFAULT_RETRY_BASE_DELAY: Duration = Duration::from_secs(5);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(3600);
+
+/// Estado de un evento en el outbox
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboxStatus {
+    Pending,
+    Dispatched,
+    /// Agotó los reintentos; queda para revisión manual
+    DeadLetter,
+}
+
+impl std::fmt::Display for OutboxStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutboxStatus::Pending => write!(f, "pending"),
+            OutboxStatus::Dispatched => write!(f, "dispatched"),
+            OutboxStatus::DeadLetter => write!(f, "dead_letter"),
+        }
+    }
+}
+
+impl std::str::FromStr for OutboxStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(OutboxStatus::Pending),
+            "dispatched" => Ok(OutboxStatus::Dispatched),
+            "dead_letter" => Ok(OutboxStatus::DeadLetter),
+            _ => Err(format!("Invalid OutboxStatus: {}", s)),
+        }
+    }
+}
+
+/// Evento guardado en el outbox a la espera de publicarse
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutboxMessage {
+    /// Id del evento; el mismo en cada reintento
+    pub id: Uuid,
+    /// Contexto que lo escribió (`fan_ventures`, `listen_reward`...)
+    pub context: String,
+    pub event_type: String,
+    pub aggregate_type: String,
+    pub aggregate_id: Uuid,
+    pub payload: serde_json::Value,
+    pub occurred_at: DateTime<Utc>,
+    /// Publicaciones fallidas hasta ahora
+    pub attempts: i32,
+}
+
+impl OutboxMessage {
+    /// Evento del event bus del orquestador; el payload es el propio evento
+    /// serializado y el id se genera aquí.
+    pub fn from_event(
+        context: &str,
+        aggregate_type: &str,
+        aggregate_id: Uuid,
+        event: &DomainEvent,
+    ) -> Result<Self, AppError> {
+        Ok(Self {
+            id: Uuid::new_v4(),
+            context: context.to_string(),
+            event_type: event.event_type().to_string(),
+            aggregate_type: aggregate_type.to_string(),
+            aggregate_id,
+            payload: serde_json::to_value(event).map_err(|e| AppError::SerializationError(e.to_string()))?,
+            occurred_at: event.occurred_at(),
+            attempts: 0,
+        })
+    }
+
+    /// Evento de dominio con metadatos: conserva su `event_id`
+    pub fn from_domain_event(context: &str, event: &dyn MetadataDomainEvent) -> Self {
+        Self {
+            id: event.metadata().event_id,
+            context: context.to_string(),
+            event_type: event.event_type().to_string(),
+            aggregate_type: event.aggregate_type().to_string(),
+            aggregate_id: event.aggregate_id(),
+            payload: event.event_data(),
+            occurred_at: event.occurred_at(),
+            attempts: 0,
+        }
+    }
+
+    /// Evento del orquestador guardado con `from_event`
+    pub fn to_event(&self) -> Result<DomainEvent, AppError> {
+        serde_json::from_value(self.payload.clone()).map_err(|e| {
+            AppError::SerializationError(format!("Outbox event {} is not a {}: {}", self.id, self.event_type, e))
+        })
+    }
+
+    fn from_row(row: PgRow) -> Self {
+        Self {
+            id: row.get("id"),
+            context: row.get("context"),
+            event_type: row.get("event_type"),
+            aggregate_type: row.get("aggregate_type"),
+            aggregate_id: row.get("aggregate_id"),
+            payload: row.get("payload"),
+            occurred_at: row.get("occurred_at"),
+            attempts: row.get("attempts"),
+        }
+    }
+}
+
+/// Guardar `message` en la transacción del caso de uso. Un id repetido se
+/// ignora, así que reintentar la transacción no duplica el evento.
+pub async fn enqueue(tx: &mut Transaction<'_, Postgres>, message: &OutboxMessage) -> Result<(), AppError> {
+    sqlx::query(
+        r#"INSERT INTO outbox_events (
+               id, context, event_type, aggregate_type, aggregate_id, payload, occurred_at
+           ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+           ON CONFLICT (id) DO NOTHING"#,
+    )
+    .bind(message.id)
+    .bind(&message.context)
+    .bind(&message.event_type)
+    .bind(&message.aggregate_type)
+    .bind(message.aggregate_id)
+    .bind(&message.payload)
+    .bind(message.occurred_at)
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| AppError::DatabaseError(format!("Failed to write outbox event {}: {}", message.event_type, e)))?;
+
+    Ok(())
+}
+
+/// Espera antes del siguiente intento tras `attempts` fallos: base, 2x, 4x...
+/// hasta una hora
+pub fn retry_delay(base: Duration, attempts: i32) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    base.saturating_mul(2u32.pow(exponent)).min(MAX_RETRY_DELAY)
+}
+
+/// Destino de los eventos del outbox
+#[async_trait]
+pub trait OutboxPublisher: Send + Sync {
+    /// Publicar `message`. Un error deja el evento pendiente para reintentarlo.
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), AppError>;
+}
+
+/// Publica en el event bus del orquestador (y con él en sus réplicas en Redis)
+pub struct EventBusOutboxPublisher {
+    event_bus: Arc<dyn EventBus>,
+}
+
+impl EventBusOutboxPublisher {
+    pub fn new(event_bus: Arc<dyn EventBus>) -> Self {
+        Self { event_bus }
+    }
+}
+
+#[async_trait]
+impl OutboxPublisher for EventBusOutboxPublisher {
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), AppError> {
+        self.event_bus.publish(message.to_event()?).await
+    }
+}
+
+/// Resultado de una pasada del relay
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RelayReport {
+    pub dispatched: usize,
+    pub retried: usize,
+    pub dead_lettered: usize,
+}
+
+impl RelayReport {
+    pub fn total(&self) -> usize {
+        self.dispatched + self.retried + self.dead_lettered
+    }
+}
+
+/// Publica los eventos pendientes de un contexto
+pub struct OutboxRelay {
+    pool: PgPool,
+    context: String,
+    publisher: Arc<dyn OutboxPublisher>,
+    batch_size: i64,
+    max_attempts: i32,
+    retry_base_delay: Duration,
+}
+
+impl OutboxRelay {
+    pub fn new(pool: PgPool, context: &str, publisher: Arc<dyn OutboxPublisher>) -> Self {
+        Self {
+            pool,
+            context: context.to_string(),
+            publisher,
+            batch_size: DEFAULT_OUTBOX_BATCH_SIZE,
+            max_attempts: DEFAULT_OUTBOX_MAX_ATTEMPTS,
+            retry_base_delay: DEFAULT_RETRY_BASE_DELAY,
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: i64) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: i32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn with_retry_base_delay(mut self, delay: Duration) -> Self {
+        self.retry_base_delay = delay;
+        self
+    }
+
+    /// Publicar un lote en orden de escritura. Las filas quedan bloqueadas hasta
+    /// el commit: otro relay se salta este lote y, si este muere a medias, se
+    /// liberan sin marcar y se vuelven a publicar.
+    pub async fn relay_batch(&self) -> Result<RelayReport, AppError> {
+        let db_error = |e: sqlx::Error| AppError::DatabaseError(format!("Outbox relay failed: {}", e));
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+
+        let messages: Vec<OutboxMessage> = sqlx::query(
+            r#"SELECT id, context, event_type, aggregate_type, aggregate_id, payload, occurred_at, attempts
+               FROM outbox_events
+               WHERE context = $1 AND status = 'pending' AND next_attempt_at <= NOW()
+               ORDER BY seq
+               LIMIT $2
+               FOR UPDATE SKIP LOCKED"#,
+        )
+        .bind(&self.context)
+        .bind(self.batch_size)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(OutboxMessage::from_row)
+        .collect();
+
+        let mut report = RelayReport::default();
+        let mut dispatched = Vec::with_capacity(messages.len());
+        for message in &messages {
+            let error = match self.publisher.publish(message).await {
+                Ok(()) => {
+                    dispatched.push(message.id);
+                    continue;
+                }
+                Err(e) => e,
+            };
+
+            let attempts = message.attempts + 1;
+            // Un payload ilegible no se arregla reintentando
+            let exhausted = attempts >= self.max_attempts || matches!(error, AppError::SerializationError(_));
+            let status = if exhausted { OutboxStatus::DeadLetter } else { OutboxStatus::Pending };
+            let next_attempt_at = Utc::now()
+                + chrono::Duration::from_std(retry_delay(self.retry_base_delay, attempts)).unwrap_or_else(|_| chrono::Duration::zero());
+            if exhausted {
+                tracing::error!("Outbox event {} ({}) dead-lettered after {} attempts: {}", message.id, message.event_type, attempts, error);
+                report.dead_lettered += 1;
+            } else {
+                tracing::warn!("Outbox event {} ({}) failed, attempt {}: {}", message.id, message.event_type, attempts, error);
+                report.retried += 1;
+            }
+
+            sqlx::query(
+                r#"UPDATE outbox_events
+                   SET status = $2, attempts = $3, last_error = $4, next_attempt_at = $5
+                   WHERE id = $1"#,
+            )
+            .bind(message.id)
+            .bind(status.to_string())
+            .bind(attempts)
+            .bind(error.to_string())
+            .bind(next_attempt_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+        }
+
+        if !dispatched.is_empty() {
+            sqlx::query(
+                r#"UPDATE outbox_events
+                   SET status = 'dispatched', dispatched_at = NOW(), last_error = NULL
+                   WHERE id = ANY($1)"#,
+            )
+            .bind(&dispatched)
+            .execute(&mut *tx)
+            .await
+            .map_err(db_error)?;
+            report.dispatched = dispatched.len();
+        }
+
+        tx.commit().await.map_err(db_error)?;
+        Ok(report)
+    }
+
+    /// Publicar lotes hasta vaciar lo pendiente
+    pub async fn relay_pending(&self) -> Result<RelayReport, AppError> {
+        let mut total = RelayReport::default();
+        loop {
+            let report = self.relay_batch().await?;
+            total.dispatched += report.dispatched;
+            total.retried += report.retried;
+            total.dead_lettered += report.dead_lettered;
+            if (report.total() as i64) < self.batch_size {
+                return Ok(total);
+            }
+        }
+    }
+}
+
+/// Worker que vacía periódicamente el outbox de un contexto
+pub struct OutboxRelayJob {
+    relay: Arc<OutboxRelay>,
+    interval: Duration,
+}
+
+impl OutboxRelayJob {
+    pub fn new(relay: Arc<OutboxRelay>, interval: Duration) -> Self {
+        Self { relay, interval }
+    }
+
+    pub fn start(&self) -> tokio::task::JoinHandle<()> {
+        let relay = Arc::clone(&self.relay);
+        let interval = self.interval;
+
+        tokio::spawn(async move {
+            tracing::info!("🚀 Outbox relay for {} started", relay.context);
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match relay.relay_pending().await {
+                    Ok(report) if report.total() > 0 => tracing::debug!("Outbox relay for {}: {:?}", relay.context, report),
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Outbox relay for {} failed: {:?}", relay.context, e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orchestrator_events_round_trip_through_the_payload() {
+        let event = DomainEvent::EscrowCancelled {
+            escrow_id: Uuid::new_v4(),
+            venture_id: Uuid::new_v4(),
+            seller_id: Uuid::new_v4(),
+            buyer_id: Uuid::new_v4(),
+            quantity: 3.0,
+            reason: "Payment failed".to_string(),
+            occurred_at: Utc::now(),
+        };
+
+        let message = OutboxMessage::from_event("fan_ventures", "ShareEscrow", Uuid::nil(), &event).unwrap();
+
+        assert_eq!(message.event_type, "EscrowCancelled");
+        assert_eq!(message.occurred_at, event.occurred_at());
+        assert_eq!(
+            serde_json::to_value(message.to_event().unwrap()).unwrap(),
+            serde_json::to_value(&event).unwrap()
+        );
+    }
+
+    #[test]
+    fn unreadable_payload_is_a_serialization_error() {
+        let mut message = OutboxMessage::from_event(
+            "fan_ventures",
+            "Venture",
+            Uuid::nil(),
+            &DomainEvent::VentureFunded { venture_id: Uuid::nil(), artist_id: Uuid::nil(), total_funding: 1.0, occurred_at: Utc::now() },
+        )
+        .unwrap();
+        message.payload = serde_json::json!({ "event_count": 1 });
+
+        assert!(matches!(message.to_event(), Err(AppError::SerializationError(_))));
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_an_hour() {
+        let base = Duration::from_secs(5);
+        assert_eq!(retry_delay(base, 1), Duration::from_secs(5));
+        assert_eq!(retry_delay(base, 2), Duration::from_secs(10));
+        assert_eq!(retry_delay(base, 4), Duration::from_secs(40));
+        assert_eq!(retry_delay(base, 30), MAX_RETRY_DELAY);
+        assert_eq!(retry_delay(Duration::ZERO, 3), Duration::ZERO);
+    }
+
+    #[test]
+    fn status_round_trips_through_its_column_value() {
+        for status in [OutboxStatus::Pending, OutboxStatus::Dispatched, OutboxStatus::DeadLetter] {
+            assert_eq!(status.to_string().parse::<OutboxStatus>().unwrap(), status);
+        }
+        assert!("sent".parse::<OutboxStatus>().is_err());
+    }
+}
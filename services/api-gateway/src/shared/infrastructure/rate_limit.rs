@@ -0,0 +1,700 @@
+// =============================================================================
+// RATE LIMITING
+// =============================================================================
+//
+// Token bucket por usuario (o por IP si la petición es anónima) y por clase de
+// ruta: login/registro, lecturas y escrituras tienen cubos distintos. Encima,
+// reglas por endpoint (método + regex de la ruta) con su propio límite y
+// ventana, cargadas de un TOML. Los cubos viven en Redis para que el límite se
+// respete entre réplicas del gateway; el cálculo se hace en un script Lua con
+// la hora del servidor Redis, así que es atómico y no depende del reloj de
+// cada réplica.
+
+use async_trait::async_trait;
+use axum::{
+    extract::{ConnectInfo, OriginalUri, Request},
+    http::{HeaderMap, HeaderValue, Method},
+    response::{IntoResponse, Response},
+};
+use redis::aio::ConnectionManager;
+use regex::Regex;
+use serde::Deserialize;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tower::{Layer, Service};
+
+use crate::gateways::GatewayConfig;
+use crate::shared::domain::errors::AppError;
+use crate::shared::infrastructure::auth::middleware::extract_token;
+use crate::shared::infrastructure::auth::{Claims, JwtService};
+
+pub const RATE_LIMIT_LIMIT_HEADER: &str = "x-ratelimit-limit";
+pub const RATE_LIMIT_REMAINING_HEADER: &str = "x-ratelimit-remaining";
+pub const RATE_LIMIT_RESET_HEADER: &str = "x-ratelimit-reset";
+/// Los mismos valores que `x-ratelimit-remaining` / `x-ratelimit-reset`
+pub const RATE_LIMIT_REMAINING_FIELD_HEADER: &str = "rate_limit_remaining";
+pub const RATE_LIMIT_RESET_FIELD_HEADER: &str = "rate_limit_reset";
+pub const RETRY_AFTER_HEADER: &str = "retry-after";
+/// Ruta del TOML con los límites; sin ella se usan los de por defecto
+pub const RATE_LIMIT_CONFIG_ENV: &str = "RATE_LIMIT_CONFIG";
+const KEY_PREFIX: &str = "ratelimit";
+
+/// `capacity` peticiones por `period`, recargadas de forma continua
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub capacity: u32,
+    pub period: Duration,
+}
+
+impl RateLimit {
+    pub const fn per_minute(capacity: u32) -> Self {
+        Self { capacity, period: Duration::from_secs(60) }
+    }
+}
+
+/// Clase de ruta, cada una con su propio cubo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouteClass {
+    /// Login, registro y emisión de tokens: objetivo de fuerza bruta
+    Auth,
+    Read,
+    Write,
+}
+
+impl RouteClass {
+    const AUTH_SUFFIXES: [&'static str; 8] = [
+        "/login",
+        "/register",
+        "/refresh",
+        "/wallet/challenge",
+        "/wallet/verify",
+        // Códigos TOTP de 6 dígitos: sin límite se adivinan por fuerza bruta
+        "/2fa/verify",
+        "/2fa/confirm",
+        "/2fa/disable",
+    ];
+
+    pub fn of(method: &Method, path: &str) -> Self {
+        let path = path.trim_end_matches('/');
+        if Self::AUTH_SUFFIXES.iter().any(|suffix| path.ends_with(suffix)) {
+            RouteClass::Auth
+        } else if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+            RouteClass::Read
+        } else {
+            RouteClass::Write
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RouteClass::Auth => "auth",
+            RouteClass::Read => "read",
+            RouteClass::Write => "write",
+        }
+    }
+}
+
+/// Límite propio de un endpoint: `limit` peticiones cada `window_seconds`
+#[derive(Debug, Clone)]
+pub struct RateLimitRule {
+    pub method: Method,
+    /// Se compara con la ruta completa (`/api/v1/music/songs`), anclada en los dos extremos
+    pub path_pattern: Regex,
+    pub limit: u32,
+    pub window_seconds: u64,
+}
+
+impl RateLimitRule {
+    pub fn new(method: Method, path_pattern: &str, limit: u32, window_seconds: u64) -> Result<Self, AppError> {
+        if limit == 0 || window_seconds == 0 {
+            return Err(AppError::ConfigurationError(format!(
+                "Rate limit rule {} {} needs a positive limit and window",
+                method, path_pattern
+            )));
+        }
+        let path_pattern = Regex::new(&format!("^(?:{})$", path_pattern)).map_err(|e| {
+            AppError::ConfigurationError(format!("Invalid rate limit path pattern '{}': {}", path_pattern, e))
+        })?;
+        Ok(Self { method, path_pattern, limit, window_seconds })
+    }
+
+    pub fn matches(&self, method: &Method, path: &str) -> bool {
+        let path = match path.trim_end_matches('/') {
+            "" => "/",
+            path => path,
+        };
+        self.method == *method && self.path_pattern.is_match(path)
+    }
+
+    pub fn rate_limit(&self) -> RateLimit {
+        RateLimit { capacity: self.limit, period: Duration::from_secs(self.window_seconds) }
+    }
+
+    // Cada regla tiene su cubo, aparte de los de clase de ruta
+    fn bucket(&self) -> String {
+        format!("rule:{}:{}", self.method, self.path_pattern.as_str())
+    }
+}
+
+impl PartialEq for RateLimitRule {
+    fn eq(&self, other: &Self) -> bool {
+        self.method == other.method
+            && self.path_pattern.as_str() == other.path_pattern.as_str()
+            && self.limit == other.limit
+            && self.window_seconds == other.window_seconds
+    }
+}
+
+impl Eq for RateLimitRule {}
+
+/// Límites de un gateway: por clase de ruta y, por encima, reglas por endpoint
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitConfig {
+    pub auth: RateLimit,
+    pub read: RateLimit,
+    pub write: RateLimit,
+    /// La primera regla que encaja gana sobre la clase de ruta
+    pub rules: Vec<RateLimitRule>,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            auth: RateLimit::per_minute(5),
+            read: RateLimit::per_minute(100),
+            write: RateLimit::per_minute(30),
+            rules: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct WindowLimitToml {
+    limit: u32,
+    window_seconds: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RateLimitRuleToml {
+    method: String,
+    path_pattern: String,
+    limit: u32,
+    window_seconds: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RateLimitConfigToml {
+    auth: Option<WindowLimitToml>,
+    read: Option<WindowLimitToml>,
+    write: Option<WindowLimitToml>,
+    #[serde(default)]
+    rules: Vec<RateLimitRuleToml>,
+}
+
+impl RateLimitConfig {
+    pub fn limit_for(&self, class: RouteClass) -> RateLimit {
+        match class {
+            RouteClass::Auth => self.auth,
+            RouteClass::Read => self.read,
+            RouteClass::Write => self.write,
+        }
+    }
+
+    pub fn rule_for(&self, method: &Method, path: &str) -> Option<&RateLimitRule> {
+        self.rules.iter().find(|rule| rule.matches(method, path))
+    }
+
+    /// Leer los límites de un TOML. Las clases que no aparecen conservan su
+    /// valor por defecto:
+    ///
+    /// ```toml
+    /// [write]
+    /// limit = 30
+    /// window_seconds = 60
+    ///
+    /// [[rules]]
+    /// method = "POST"
+    /// path_pattern = '/api/v1/music/songs'
+    /// limit = 10
+    /// window_seconds = 3600
+    /// ```
+    pub fn from_toml(contents: &str) -> Result<Self, AppError> {
+        let parsed: RateLimitConfigToml = ::config::Config::builder()
+            .add_source(::config::File::from_str(contents, ::config::FileFormat::Toml))
+            .build()
+            .and_then(|config| config.try_deserialize())
+            .map_err(|e| AppError::ConfigurationError(format!("Invalid rate limit config: {}", e)))?;
+
+        let window = |limit: Option<WindowLimitToml>, default: RateLimit| -> Result<RateLimit, AppError> {
+            match limit {
+                Some(WindowLimitToml { limit: 0, .. }) | Some(WindowLimitToml { window_seconds: 0, .. }) => Err(
+                    AppError::ConfigurationError("Route class limits need a positive limit and window".to_string()),
+                ),
+                Some(limit) => Ok(RateLimit { capacity: limit.limit, period: Duration::from_secs(limit.window_seconds) }),
+                None => Ok(default),
+            }
+        };
+        let defaults = Self::default();
+        let rules = parsed
+            .rules
+            .into_iter()
+            .map(|rule| {
+                let method = Method::from_bytes(rule.method.to_uppercase().as_bytes()).map_err(|_| {
+                    AppError::ConfigurationError(format!("Invalid rate limit method '{}'", rule.method))
+                })?;
+                RateLimitRule::new(method, &rule.path_pattern, rule.limit, rule.window_seconds)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            auth: window(parsed.auth, defaults.auth)?,
+            read: window(parsed.read, defaults.read)?,
+            write: window(parsed.write, defaults.write)?,
+            rules,
+        })
+    }
+
+    pub fn from_file(path: &str) -> Result<Self, AppError> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| AppError::ConfigurationError(format!("Cannot read rate limit config {}: {}", path, e)))?;
+        Self::from_toml(&contents)
+    }
+
+    /// Límites del fichero de `RATE_LIMIT_CONFIG`, leído una sola vez; los de
+    /// por defecto si no está definida. Un fichero ilegible o inválido es un
+    /// error: el arranque no sigue con límites distintos de los configurados.
+    pub fn from_env() -> Result<Self, AppError> {
+        static LOADED: OnceLock<RateLimitConfig> = OnceLock::new();
+        if let Some(config) = LOADED.get() {
+            return Ok(config.clone());
+        }
+        let config = match std::env::var(RATE_LIMIT_CONFIG_ENV) {
+            Ok(path) => Self::from_file(&path)?,
+            Err(_) => Self::default(),
+        };
+        Ok(LOADED.get_or_init(|| config).clone())
+    }
+}
+
+/// Estado del cubo tras intentar consumir un token
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// Hasta que el cubo vuelva a estar lleno
+    pub reset_after: Duration,
+    /// Hasta que haya un token disponible (cero si se permitió)
+    pub retry_after: Duration,
+}
+
+#[async_trait]
+pub trait RateLimitStore: Send + Sync {
+    /// Consumir un token del cubo `key` de forma atómica
+    async fn take(&self, key: &str, limit: &RateLimit) -> Result<RateLimitDecision, AppError>;
+}
+
+/// Cubos en Redis: un hash `{tokens, ts}` por clave que caduca si no se usa
+pub struct RedisRateLimitStore {
+    connection: ConnectionManager,
+    script: redis::Script,
+}
+
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local capacity = tonumber(ARGV[1])
+local period_ms = tonumber(ARGV[2])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+local rate = capacity / period_ms
+
+local bucket = redis.call('HMGET', KEYS[1], 'tokens', 'ts')
+local tokens = tonumber(bucket[1]) or capacity
+local ts = tonumber(bucket[2]) or now
+tokens = math.min(capacity, tokens + math.max(0, now - ts) * rate)
+
+local allowed = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+    allowed = 1
+end
+
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'ts', now)
+redis.call('PEXPIRE', KEYS[1], period_ms)
+
+local retry_ms = 0
+if allowed == 0 then
+    retry_ms = math.ceil((1 - tokens) / rate)
+end
+local reset_ms = math.ceil((capacity - tokens) / rate)
+return {allowed, math.floor(tokens), retry_ms, reset_ms}
+"#;
+
+impl RedisRateLimitStore {
+    pub fn new(connection: ConnectionManager) -> Self {
+        Self { connection, script: redis::Script::new(TOKEN_BUCKET_SCRIPT) }
+    }
+}
+
+#[async_trait]
+impl RateLimitStore for RedisRateLimitStore {
+    async fn take(&self, key: &str, limit: &RateLimit) -> Result<RateLimitDecision, AppError> {
+        let mut conn = self.connection.clone();
+        let (allowed, remaining, retry_ms, reset_ms): (i64, i64, i64, i64) = self.script
+            .key(key)
+            .arg(limit.capacity)
+            .arg(limit.period.as_millis() as u64)
+            .invoke_async(&mut conn)
+            .await
+            .map_err(|e| AppError::ExternalServiceError(format!("Redis rate limit error: {}", e)))?;
+
+        Ok(RateLimitDecision {
+            allowed: allowed == 1,
+            limit: limit.capacity,
+            remaining: remaining.max(0) as u32,
+            reset_after: Duration::from_millis(reset_ms.max(0) as u64),
+            retry_after: Duration::from_millis(retry_ms.max(0) as u64),
+        })
+    }
+}
+
+/// Limitador de un gateway: store + límites por clase de ruta y por endpoint
+pub struct RateLimiter {
+    store: Arc<dyn RateLimitStore>,
+    config: RateLimitConfig,
+    /// Sin esto solo se limitan los endpoints con regla
+    limit_route_classes: bool,
+}
+
+impl RateLimiter {
+    pub fn new(store: Arc<dyn RateLimitStore>, config: RateLimitConfig) -> Self {
+        Self { store, config, limit_route_classes: true }
+    }
+
+    /// `None` si el gateway tiene el rate limiting desactivado y ninguna regla
+    /// por endpoint; con reglas pero desactivado, solo se aplican las reglas
+    pub fn for_gateway(gateway: &GatewayConfig, store: Arc<dyn RateLimitStore>) -> Option<Arc<Self>> {
+        if !gateway.rate_limiting_enabled && gateway.rate_limits.rules.is_empty() {
+            return None;
+        }
+        Some(Arc::new(Self {
+            store,
+            config: gateway.rate_limits.clone(),
+            limit_route_classes: gateway.rate_limiting_enabled,
+        }))
+    }
+
+    /// Cubo y límite de una petición, o `None` si no se limita
+    fn bucket_for(&self, method: &Method, path: &str) -> Option<(String, RateLimit)> {
+        if let Some(rule) = self.config.rule_for(method, path) {
+            return Some((rule.bucket(), rule.rate_limit()));
+        }
+        if !self.limit_route_classes {
+            return None;
+        }
+        let class = RouteClass::of(method, path);
+        Some((class.as_str().to_string(), self.config.limit_for(class)))
+    }
+}
+
+/// Usuario del token si lo hay; si no, la IP del cliente
+fn client_identity(request: &Request) -> String {
+    if let Some(claims) = request.extensions().get::<Claims>() {
+        return format!("user:{}", claims.sub);
+    }
+    let token_subject = extract_token(request.headers()).and_then(|token| {
+        JwtService::from_env().ok()?.validate_access_token(&token).ok().map(|claims| claims.sub)
+    });
+    if let Some(sub) = token_subject {
+        return format!("user:{}", sub);
+    }
+    if let Some(ConnectInfo(addr)) = request.extensions().get::<ConnectInfo<SocketAddr>>() {
+        return format!("ip:{}", addr.ip());
+    }
+    let forwarded = request
+        .headers()
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(',').next())
+        .map(str::trim)
+        .filter(|ip| !ip.is_empty());
+    format!("ip:{}", forwarded.unwrap_or("unknown"))
+}
+
+fn seconds_ceil(duration: Duration) -> u64 {
+    (duration.as_millis() as u64 + 999) / 1000
+}
+
+fn set_rate_limit_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    headers.insert(RATE_LIMIT_LIMIT_HEADER, HeaderValue::from(decision.limit));
+    headers.insert(RATE_LIMIT_REMAINING_HEADER, HeaderValue::from(decision.remaining));
+    headers.insert(RATE_LIMIT_RESET_HEADER, HeaderValue::from(seconds_ceil(decision.reset_after)));
+    headers.insert(RATE_LIMIT_REMAINING_FIELD_HEADER, HeaderValue::from(decision.remaining));
+    headers.insert(RATE_LIMIT_RESET_FIELD_HEADER, HeaderValue::from(seconds_ceil(decision.reset_after)));
+}
+
+fn too_many_requests(decision: &RateLimitDecision) -> Response {
+    let mut response = AppError::RateLimitError(format!(
+        "Too many requests, retry in {} seconds",
+        seconds_ceil(decision.retry_after)
+    ))
+    .into_response();
+    set_rate_limit_headers(response.headers_mut(), decision);
+    response.headers_mut().insert(
+        RETRY_AFTER_HEADER,
+        HeaderValue::from(seconds_ceil(decision.retry_after).max(1)),
+    );
+    response
+}
+
+// =============================================================================
+// RATE LIMIT LAYER
+// =============================================================================
+
+/// Layer de rate limiting: `router.layer(RateLimitLayer::new(limiter))`.
+///
+/// Responde 429 con `Retry-After` cuando el cubo está vacío y añade los
+/// headers de límite al resto de respuestas. Si Redis no responde la petición
+/// pasa (fail open) y se registra un warning.
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitLayer {
+    pub fn new(limiter: Arc<RateLimiter>) -> Self {
+        Self { limiter }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService { inner, limiter: Arc::clone(&self.limiter) }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<S> Service<Request> for RateLimitService<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // La petición la atiende el servicio que ya pasó por poll_ready
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        // Dentro de un router anidado la URI ya no lleva el prefijo del gateway
+        let path = request
+            .extensions()
+            .get::<OriginalUri>()
+            .map(|uri| uri.path().to_string())
+            .unwrap_or_else(|| request.uri().path().to_string());
+        // Los webhooks de pasarelas van firmados y no pueden reintentar tras un 429 a nuestro ritmo
+        let bucket = if path.contains("/webhooks/") {
+            None
+        } else {
+            self.limiter.bucket_for(request.method(), &path)
+        };
+        let Some((bucket, limit)) = bucket else {
+            return Box::pin(inner.call(request));
+        };
+        let key = format!("{}:{}:{}", KEY_PREFIX, bucket, client_identity(&request));
+        let limiter = Arc::clone(&self.limiter);
+
+        Box::pin(async move {
+            let decision = match limiter.store.take(&key, &limit).await {
+                Ok(decision) => decision,
+                Err(e) => {
+                    tracing::warn!("Rate limiter unavailable, letting request through: {}", e);
+                    return inner.call(request).await;
+                }
+            };
+
+            if !decision.allowed {
+                tracing::debug!("Rate limit exceeded for {}", key);
+                return Ok(too_many_requests(&decision));
+            }
+
+            let mut response = inner.call(request).await?;
+            set_rate_limit_headers(response.headers_mut(), &decision);
+            Ok(response)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_routes() {
+        assert_eq!(RouteClass::of(&Method::POST, "/api/v1/users/login"), RouteClass::Auth);
+        assert_eq!(RouteClass::of(&Method::POST, "/api/v1/users/register/"), RouteClass::Auth);
+        assert_eq!(RouteClass::of(&Method::POST, "/api/v1/auth/wallet/verify"), RouteClass::Auth);
+        assert_eq!(RouteClass::of(&Method::POST, "/api/v1/auth/2fa/verify"), RouteClass::Auth);
+        assert_eq!(RouteClass::of(&Method::GET, "/api/v1/payments/123"), RouteClass::Read);
+        assert_eq!(RouteClass::of(&Method::POST, "/api/v1/payments"), RouteClass::Write);
+        assert_eq!(RouteClass::of(&Method::DELETE, "/api/v1/users/123/follow"), RouteClass::Write);
+    }
+
+    #[test]
+    fn limiter_only_exists_for_gateways_with_rate_limiting() {
+        struct NoopStore;
+        #[async_trait]
+        impl RateLimitStore for NoopStore {
+            async fn take(&self, _key: &str, limit: &RateLimit) -> Result<RateLimitDecision, AppError> {
+                Ok(RateLimitDecision {
+                    allowed: true,
+                    limit: limit.capacity,
+                    remaining: limit.capacity,
+                    reset_after: Duration::ZERO,
+                    retry_after: Duration::ZERO,
+                })
+            }
+        }
+
+        let store: Arc<dyn RateLimitStore> = Arc::new(NoopStore);
+        assert!(RateLimiter::for_gateway(&GatewayConfig::user_gateway(), Arc::clone(&store)).is_some());
+        assert!(RateLimiter::for_gateway(&GatewayConfig::payment_gateway(), Arc::clone(&store)).is_some());
+        assert!(RateLimiter::for_gateway(&GatewayConfig::music_gateway(), store).is_none());
+    }
+
+    #[test]
+    fn default_limits() {
+        let config = RateLimitConfig::default();
+        assert_eq!(config.limit_for(RouteClass::Auth), RateLimit::per_minute(5));
+        assert_eq!(config.limit_for(RouteClass::Read), RateLimit::per_minute(100));
+        assert!(config.rules.is_empty());
+    }
+
+    const RULES_TOML: &str = r#"
+        [write]
+        limit = 20
+        window_seconds = 60
+
+        [[rules]]
+        method = "POST"
+        path_pattern = '/api/v1/music/songs'
+        limit = 10
+        window_seconds = 3600
+
+        [[rules]]
+        method = "post"
+        path_pattern = '/api/v1/payments/payments(/[^/]+/process)?'
+        limit = 60
+        window_seconds = 60
+
+        [[rules]]
+        method = "GET"
+        path_pattern = '/api/v1/music/songs(/[0-9a-f-]+)?'
+        limit = 1000
+        window_seconds = 60
+    "#;
+
+    #[test]
+    fn rules_match_method_and_whole_path() {
+        let config = RateLimitConfig::from_toml(RULES_TOML).unwrap();
+        assert_eq!(config.rules.len(), 3);
+        assert_eq!(config.write, RateLimit::per_minute(20));
+        assert_eq!(config.read, RateLimit::per_minute(100));
+
+        let upload = config.rule_for(&Method::POST, "/api/v1/music/songs").unwrap();
+        assert_eq!(upload.rate_limit(), RateLimit { capacity: 10, period: Duration::from_secs(3600) });
+        assert_eq!(config.rule_for(&Method::POST, "/api/v1/music/songs/"), Some(upload));
+        // Anclada: ni subrutas ni prefijos
+        assert!(config.rule_for(&Method::POST, "/api/v1/music/songs/123/like").is_none());
+        assert!(config.rule_for(&Method::POST, "/v2/api/v1/music/songs").is_none());
+
+        let listing = config.rule_for(&Method::GET, "/api/v1/music/songs").unwrap();
+        assert_eq!(listing.limit, 1000);
+        assert_eq!(config.rule_for(&Method::GET, "/api/v1/music/songs/4f9c2a1e-0b7d-4e1a-9c3f-2d8e6b5a7c10"), Some(listing));
+        assert!(config.rule_for(&Method::DELETE, "/api/v1/music/songs").is_none());
+
+        let process = config.rule_for(&Method::POST, "/api/v1/payments/payments/abc/process").unwrap();
+        assert_eq!(process.method, Method::POST);
+        assert!(config.rule_for(&Method::POST, "/api/v1/payments/payments/abc/cancel").is_none());
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        assert!(RateLimitRule::new(Method::GET, "/songs(", 10, 60).is_err());
+        assert!(RateLimitRule::new(Method::GET, "/songs", 0, 60).is_err());
+        assert!(RateLimitRule::new(Method::GET, "/songs", 10, 0).is_err());
+        let bad_method = "[[rules]]\nmethod = \"GE T\"\npath_pattern = '/songs'\nlimit = 1\nwindow_seconds = 1";
+        assert!(RateLimitConfig::from_toml(bad_method).is_err());
+    }
+
+    /// Cubo que no se recarga: `capacity` peticiones y después 429
+    #[derive(Default)]
+    struct CountingStore {
+        taken: std::sync::Mutex<std::collections::HashMap<String, u32>>,
+    }
+
+    #[async_trait]
+    impl RateLimitStore for CountingStore {
+        async fn take(&self, key: &str, limit: &RateLimit) -> Result<RateLimitDecision, AppError> {
+            let mut taken = self.taken.lock().unwrap();
+            let count = taken.entry(key.to_string()).or_insert(0);
+            let allowed = *count < limit.capacity;
+            if allowed {
+                *count += 1;
+            }
+            Ok(RateLimitDecision {
+                allowed,
+                limit: limit.capacity,
+                remaining: limit.capacity - *count,
+                reset_after: limit.period,
+                retry_after: if allowed { Duration::ZERO } else { limit.period },
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn exceeding_a_rule_returns_429_with_retry_after() {
+        use axum::{body::Body, http::StatusCode, routing::get, Router};
+        use tower::ServiceExt;
+
+        let mut gateway = GatewayConfig::music_gateway();
+        gateway.rate_limits.rules = vec![RateLimitRule::new(Method::POST, "/songs", 2, 3600).unwrap()];
+        let limiter = RateLimiter::for_gateway(&gateway, Arc::new(CountingStore::default())).unwrap();
+        let app = Router::new()
+            .route("/songs", get(|| async { "songs" }).post(|| async { "created" }))
+            .layer(RateLimitLayer::new(limiter));
+        let request = |method: Method| axum::http::Request::builder().method(method).uri("/songs").body(Body::empty()).unwrap();
+
+        let first = app.clone().oneshot(request(Method::POST)).await.unwrap();
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(first.headers()[RATE_LIMIT_REMAINING_FIELD_HEADER], "1");
+        assert_eq!(first.headers()[RATE_LIMIT_RESET_FIELD_HEADER], "3600");
+        assert_eq!(app.clone().oneshot(request(Method::POST)).await.unwrap().status(), StatusCode::OK);
+
+        let limited = app.clone().oneshot(request(Method::POST)).await.unwrap();
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(limited.headers()[RETRY_AFTER_HEADER], "3600");
+        assert_eq!(limited.headers()[RATE_LIMIT_REMAINING_FIELD_HEADER], "0");
+
+        // El gateway no limita por clase de ruta: GET no tiene regla y pasa sin headers
+        let listing = app.oneshot(request(Method::GET)).await.unwrap();
+        assert_eq!(listing.status(), StatusCode::OK);
+        assert!(listing.headers().get(RATE_LIMIT_REMAINING_FIELD_HEADER).is_none());
+    }
+}
@@ -0,0 +1,510 @@
+// Sagas (process managers)
+//
+// Una saga coordina un proceso que cruza varios bounded contexts: reacciona a
+// sus eventos, avanza paso a paso y, si algo falla o un paso no llega a tiempo,
+// ejecuta comandos de compensación. Cada transición se guarda antes de actuar,
+// de modo que tras un reinicio la instancia continúa desde el último paso
+// persistido; el plazo (`deadline_at`) del paso actual lo vigila `SagaTimeoutJob`.
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sqlx::{postgres::PgRow, PgPool, Row};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::shared::domain::errors::AppError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SagaStatus {
+    Running,
+    /// Deshaciendo los pasos ya hechos
+    Compensating,
+    Completed,
+    Compensated,
+    /// La compensación no pudo completarse: requiere intervención manual
+    Failed,
+}
+
+impl SagaStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SagaStatus::Running => "running",
+            SagaStatus::Compensating => "compensating",
+            SagaStatus::Completed => "completed",
+            SagaStatus::Compensated => "compensated",
+            SagaStatus::Failed => "failed",
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self, SagaStatus::Completed | SagaStatus::Compensated | SagaStatus::Failed)
+    }
+}
+
+impl std::fmt::Display for SagaStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for SagaStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "running" => Ok(SagaStatus::Running),
+            "compensating" => Ok(SagaStatus::Compensating),
+            "completed" => Ok(SagaStatus::Completed),
+            "compensated" => Ok(SagaStatus::Compensated),
+            "failed" => Ok(SagaStatus::Failed),
+            other => Err(format!("Unknown saga status: {}", other)),
+        }
+    }
+}
+
+/// Estado persistido de una saga. `data` es el estado propio de cada tipo de saga.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SagaInstance {
+    pub id: Uuid,
+    pub saga_type: String,
+    /// Identificador del proceso coordinado (p. ej. la inversión)
+    pub correlation_id: Uuid,
+    pub step: String,
+    pub status: SagaStatus,
+    pub data: serde_json::Value,
+    /// Si el paso actual no avanza antes de este instante, vence
+    pub deadline_at: Option<DateTime<Utc>>,
+    /// Intentos fallidos de compensación
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub version: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SagaInstance {
+    pub fn start(
+        saga_type: &str,
+        correlation_id: Uuid,
+        step: &str,
+        data: serde_json::Value,
+        deadline_at: Option<DateTime<Utc>>,
+    ) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            saga_type: saga_type.to_string(),
+            correlation_id,
+            step: step.to_string(),
+            status: SagaStatus::Running,
+            data,
+            deadline_at,
+            attempts: 0,
+            last_error: None,
+            version: 0,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn data<T: DeserializeOwned>(&self) -> Result<T, AppError> {
+        serde_json::from_value(self.data.clone()).map_err(|e| {
+            AppError::SerializationError(format!("Invalid state for saga {}: {}", self.id, e))
+        })
+    }
+
+    pub fn set_data<T: Serialize>(&mut self, data: &T) -> Result<(), AppError> {
+        self.data = serde_json::to_value(data).map_err(|e| AppError::SerializationError(e.to_string()))?;
+        Ok(())
+    }
+
+    fn from_row(row: PgRow) -> Result<Self, AppError> {
+        let status: String = row.get("status");
+        Ok(Self {
+            id: row.get("id"),
+            saga_type: row.get("saga_type"),
+            correlation_id: row.get("correlation_id"),
+            step: row.get("step"),
+            status: status.parse().map_err(AppError::SerializationError)?,
+            data: row.get("data"),
+            deadline_at: row.get("deadline_at"),
+            attempts: row.get("attempts"),
+            last_error: row.get("last_error"),
+            version: row.get("version"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        })
+    }
+}
+
+/// Transición registrada para auditoría
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SagaTransition {
+    pub saga_id: Uuid,
+    /// `None` al crearse la instancia
+    pub from_step: Option<String>,
+    pub to_step: String,
+    pub status: SagaStatus,
+    /// Evento o timeout que provocó la transición
+    pub trigger: String,
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[async_trait]
+pub trait SagaStore: Send + Sync {
+    /// Crear la instancia. Devuelve `false` si ya había una para la misma
+    /// correlación (evento de inicio repetido).
+    async fn create(&self, instance: &SagaInstance, trigger: &str) -> Result<bool, AppError>;
+
+    async fn find(&self, saga_type: &str, correlation_id: Uuid) -> Result<Option<SagaInstance>, AppError>;
+
+    /// Guardar la instancia y su transición. Falla con `ConcurrencyConflict` si
+    /// otro proceso la modificó desde que se leyó; si no, incrementa su versión.
+    async fn save(&self, instance: &mut SagaInstance, trigger: &str) -> Result<(), AppError>;
+
+    /// Instancias sin terminar cuyo plazo venció, las más antiguas primero
+    async fn find_due(&self, saga_type: &str, now: DateTime<Utc>, limit: i64) -> Result<Vec<SagaInstance>, AppError>;
+
+    async fn transitions(&self, saga_id: Uuid) -> Result<Vec<SagaTransition>, AppError>;
+}
+
+/// Sagas en las tablas `saga_instances` y `saga_transitions`
+pub struct PostgresSagaStore {
+    pool: PgPool,
+}
+
+impl PostgresSagaStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+const SAGA_COLUMNS: &str = "id, saga_type, correlation_id, step, status, data, deadline_at, attempts, last_error, version, created_at, updated_at";
+
+fn db_error(e: sqlx::Error) -> AppError {
+    AppError::DatabaseError(format!("Saga store failed: {}", e))
+}
+
+#[async_trait]
+impl SagaStore for PostgresSagaStore {
+    async fn create(&self, instance: &SagaInstance, trigger: &str) -> Result<bool, AppError> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let inserted = sqlx::query(
+            r#"INSERT INTO saga_instances (
+                   id, saga_type, correlation_id, step, status, data, deadline_at,
+                   attempts, last_error, version, created_at, updated_at
+               ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+               ON CONFLICT (saga_type, correlation_id) DO NOTHING"#,
+        )
+        .bind(instance.id)
+        .bind(&instance.saga_type)
+        .bind(instance.correlation_id)
+        .bind(&instance.step)
+        .bind(instance.status.as_str())
+        .bind(&instance.data)
+        .bind(instance.deadline_at)
+        .bind(instance.attempts)
+        .bind(&instance.last_error)
+        .bind(instance.version)
+        .bind(instance.created_at)
+        .bind(instance.updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?
+        .rows_affected()
+            == 1;
+        if !inserted {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"INSERT INTO saga_transitions (saga_id, from_step, to_step, status, trigger, recorded_at)
+               VALUES ($1, NULL, $2, $3, $4, $5)"#,
+        )
+        .bind(instance.id)
+        .bind(&instance.step)
+        .bind(instance.status.as_str())
+        .bind(trigger)
+        .bind(instance.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)?;
+        Ok(true)
+    }
+
+    async fn find(&self, saga_type: &str, correlation_id: Uuid) -> Result<Option<SagaInstance>, AppError> {
+        sqlx::query(&format!(
+            "SELECT {} FROM saga_instances WHERE saga_type = $1 AND correlation_id = $2",
+            SAGA_COLUMNS
+        ))
+        .bind(saga_type)
+        .bind(correlation_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(db_error)?
+        .map(SagaInstance::from_row)
+        .transpose()
+    }
+
+    async fn save(&self, instance: &mut SagaInstance, trigger: &str) -> Result<(), AppError> {
+        let mut tx = self.pool.begin().await.map_err(db_error)?;
+        let from_step: Option<String> =
+            sqlx::query_scalar("SELECT step FROM saga_instances WHERE id = $1 AND version = $2 FOR UPDATE")
+                .bind(instance.id)
+                .bind(instance.version)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(db_error)?;
+        let Some(from_step) = from_step else {
+            return Err(AppError::ConcurrencyConflict(format!(
+                "Saga {} was modified concurrently (expected version {})",
+                instance.id, instance.version
+            )));
+        };
+
+        let updated_at = Utc::now();
+        sqlx::query(
+            r#"UPDATE saga_instances
+               SET step = $2, status = $3, data = $4, deadline_at = $5, attempts = $6,
+                   last_error = $7, version = version + 1, updated_at = $8
+               WHERE id = $1"#,
+        )
+        .bind(instance.id)
+        .bind(&instance.step)
+        .bind(instance.status.as_str())
+        .bind(&instance.data)
+        .bind(instance.deadline_at)
+        .bind(instance.attempts)
+        .bind(&instance.last_error)
+        .bind(updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        sqlx::query(
+            r#"INSERT INTO saga_transitions (saga_id, from_step, to_step, status, trigger, recorded_at)
+               VALUES ($1, $2, $3, $4, $5, $6)"#,
+        )
+        .bind(instance.id)
+        .bind(from_step)
+        .bind(&instance.step)
+        .bind(instance.status.as_str())
+        .bind(trigger)
+        .bind(updated_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(db_error)?;
+
+        tx.commit().await.map_err(db_error)?;
+        instance.version += 1;
+        instance.updated_at = updated_at;
+        Ok(())
+    }
+
+    async fn find_due(&self, saga_type: &str, now: DateTime<Utc>, limit: i64) -> Result<Vec<SagaInstance>, AppError> {
+        sqlx::query(&format!(
+            r#"SELECT {} FROM saga_instances
+               WHERE saga_type = $1 AND status IN ('running', 'compensating') AND deadline_at <= $2
+               ORDER BY deadline_at
+               LIMIT $3"#,
+            SAGA_COLUMNS
+        ))
+        .bind(saga_type)
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(SagaInstance::from_row)
+        .collect()
+    }
+
+    async fn transitions(&self, saga_id: Uuid) -> Result<Vec<SagaTransition>, AppError> {
+        sqlx::query(
+            r#"SELECT saga_id, from_step, to_step, status, trigger, recorded_at
+               FROM saga_transitions WHERE saga_id = $1 ORDER BY id"#,
+        )
+        .bind(saga_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(db_error)?
+        .into_iter()
+        .map(|row| {
+            let status: String = row.get("status");
+            Ok(SagaTransition {
+                saga_id: row.get("saga_id"),
+                from_step: row.get("from_step"),
+                to_step: row.get("to_step"),
+                status: status.parse().map_err(AppError::SerializationError)?,
+                trigger: row.get("trigger"),
+                recorded_at: row.get("recorded_at"),
+            })
+        })
+        .collect()
+    }
+}
+
+/// Sagas en memoria para tests
+#[derive(Default)]
+pub struct InMemorySagaStore {
+    instances: Mutex<HashMap<Uuid, SagaInstance>>,
+    transitions: Mutex<Vec<SagaTransition>>,
+}
+
+impl InMemorySagaStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SagaStore for InMemorySagaStore {
+    async fn create(&self, instance: &SagaInstance, trigger: &str) -> Result<bool, AppError> {
+        let mut instances = self.instances.lock().unwrap();
+        if instances
+            .values()
+            .any(|i| i.saga_type == instance.saga_type && i.correlation_id == instance.correlation_id)
+        {
+            return Ok(false);
+        }
+        instances.insert(instance.id, instance.clone());
+        self.transitions.lock().unwrap().push(SagaTransition {
+            saga_id: instance.id,
+            from_step: None,
+            to_step: instance.step.clone(),
+            status: instance.status,
+            trigger: trigger.to_string(),
+            recorded_at: instance.created_at,
+        });
+        Ok(true)
+    }
+
+    async fn find(&self, saga_type: &str, correlation_id: Uuid) -> Result<Option<SagaInstance>, AppError> {
+        Ok(self
+            .instances
+            .lock()
+            .unwrap()
+            .values()
+            .find(|i| i.saga_type == saga_type && i.correlation_id == correlation_id)
+            .cloned())
+    }
+
+    async fn save(&self, instance: &mut SagaInstance, trigger: &str) -> Result<(), AppError> {
+        let mut instances = self.instances.lock().unwrap();
+        let stored = instances
+            .get_mut(&instance.id)
+            .filter(|stored| stored.version == instance.version)
+            .ok_or_else(|| {
+                AppError::ConcurrencyConflict(format!(
+                    "Saga {} was modified concurrently (expected version {})",
+                    instance.id, instance.version
+                ))
+            })?;
+        let from_step = stored.step.clone();
+
+        instance.version += 1;
+        instance.updated_at = Utc::now();
+        *stored = instance.clone();
+        self.transitions.lock().unwrap().push(SagaTransition {
+            saga_id: instance.id,
+            from_step: Some(from_step),
+            to_step: instance.step.clone(),
+            status: instance.status,
+            trigger: trigger.to_string(),
+            recorded_at: instance.updated_at,
+        });
+        Ok(())
+    }
+
+    async fn find_due(&self, saga_type: &str, now: DateTime<Utc>, limit: i64) -> Result<Vec<SagaInstance>, AppError> {
+        let mut due: Vec<SagaInstance> = self
+            .instances
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|i| i.saga_type == saga_type && !i.status.is_finished())
+            .filter(|i| i.deadline_at.is_some_and(|deadline| deadline <= now))
+            .cloned()
+            .collect();
+        due.sort_by_key(|i| i.deadline_at);
+        due.truncate(limit.max(0) as usize);
+        Ok(due)
+    }
+
+    async fn transitions(&self, saga_id: Uuid) -> Result<Vec<SagaTransition>, AppError> {
+        Ok(self.transitions.lock().unwrap().iter().filter(|t| t.saga_id == saga_id).cloned().collect())
+    }
+}
+
+/// Una saga cuyos plazos se vigilan periódicamente
+#[async_trait]
+pub trait SagaTimeouts: Send + Sync {
+    fn saga_type(&self) -> &str;
+
+    /// Procesar las instancias vencidas. Devuelve cuántas avanzaron.
+    async fn handle_timeouts(&self) -> Result<usize, AppError>;
+}
+
+/// Worker que dispara los timeouts de una saga
+pub struct SagaTimeoutJob {
+    saga: Arc<dyn SagaTimeouts>,
+    interval: Duration,
+}
+
+impl SagaTimeoutJob {
+    pub fn new(saga: Arc<dyn SagaTimeouts>, interval: Duration) -> Self {
+        Self { saga, interval }
+    }
+
+    pub fn start(&self) -> tokio::task::JoinHandle<()> {
+        let saga = Arc::clone(&self.saga);
+        let interval = self.interval;
+
+        tokio::spawn(async move {
+            tracing::info!("🚀 Timeout job for saga {} started", saga.saga_type());
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                match saga.handle_timeouts().await {
+                    Ok(count) if count > 0 => tracing::info!("⏰ {} {} sagas timed out", count, saga.saga_type()),
+                    Ok(_) => {}
+                    Err(e) => tracing::error!("Timeouts for saga {} failed: {:?}", saga.saga_type(), e),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn stale_saves_conflict_and_transitions_are_recorded() {
+        let store = InMemorySagaStore::new();
+        let correlation = Uuid::new_v4();
+        let mut instance = SagaInstance::start("test", correlation, "first", serde_json::json!({}), None);
+        assert!(store.create(&instance, "Started").await.unwrap());
+        // Evento de inicio repetido: la instancia existente se conserva
+        let duplicate = SagaInstance::start("test", correlation, "first", serde_json::json!({}), None);
+        assert!(!store.create(&duplicate, "Started").await.unwrap());
+
+        let mut stale = store.find("test", correlation).await.unwrap().unwrap();
+        instance.step = "second".to_string();
+        store.save(&mut instance, "Advanced").await.unwrap();
+        assert_eq!(instance.version, 1);
+
+        stale.step = "other".to_string();
+        assert!(matches!(store.save(&mut stale, "Advanced").await, Err(AppError::ConcurrencyConflict(_))));
+
+        let transitions = store.transitions(instance.id).await.unwrap();
+        let steps: Vec<_> = transitions.iter().map(|t| (t.from_step.as_deref(), t.to_step.as_str())).collect();
+        assert_eq!(steps, vec![(None, "first"), (Some("first"), "second")]);
+    }
+}
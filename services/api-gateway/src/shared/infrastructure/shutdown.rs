@@ -0,0 +1,236 @@
+// =============================================================================
+// GRACEFUL SHUTDOWN Y READINESS
+// =============================================================================
+//
+// SIGTERM/SIGINT cancelan un `CancellationToken` compartido. A partir de ahí:
+// 1. `/ready` pasa a 503 para que el balanceador deje de enviar tráfico.
+// 2. Cada servidor deja de aceptar conexiones y espera a las peticiones en
+//    curso, como mucho `drain_timeout`.
+// 3. Se paran las tareas en background (outbox relay, schedulers...) y se
+//    cierra el pool de base de datos.
+//
+// `/ready` solo responde 200 cuando las migraciones han corrido y todos los
+// servidores están escuchando; `/health` sigue diciendo si el proceso vive.
+
+use axum::{extract::State, http::StatusCode, response::Json, routing::get, Router};
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+pub const DEFAULT_DRAIN_TIMEOUT_SECS: u64 = 25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShutdownConfig {
+    /// Tiempo que se espera a las peticiones en curso antes de cortarlas
+    pub drain_timeout: Duration,
+}
+
+impl Default for ShutdownConfig {
+    fn default() -> Self {
+        Self {
+            drain_timeout: Duration::from_secs(DEFAULT_DRAIN_TIMEOUT_SECS),
+        }
+    }
+}
+
+impl ShutdownConfig {
+    /// `SHUTDOWN_DRAIN_TIMEOUT_SECS`; por debajo del terminationGracePeriod del pod
+    pub fn from_env() -> Self {
+        let drain_timeout = std::env::var("SHUTDOWN_DRAIN_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .map(Duration::from_secs)
+            .unwrap_or(Self::default().drain_timeout);
+        Self { drain_timeout }
+    }
+}
+
+/// Cancela `token` con la primera SIGTERM o SIGINT
+pub fn cancel_on_signal(token: CancellationToken) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let ctrl_c = async {
+            if let Err(e) = tokio::signal::ctrl_c().await {
+                tracing::error!("Failed to listen for SIGINT: {}", e);
+                std::future::pending::<()>().await;
+            }
+        };
+        #[cfg(unix)]
+        let terminate = async {
+            match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+                Ok(mut signal) => {
+                    signal.recv().await;
+                }
+                Err(e) => {
+                    tracing::error!("Failed to listen for SIGTERM: {}", e);
+                    std::future::pending::<()>().await;
+                }
+            }
+        };
+        #[cfg(not(unix))]
+        let terminate = std::future::pending::<()>();
+
+        tokio::select! {
+            _ = ctrl_c => tracing::info!("SIGINT received, shutting down"),
+            _ = terminate => tracing::info!("SIGTERM received, shutting down"),
+            _ = token.cancelled() => return,
+        }
+        token.cancel();
+    })
+}
+
+/// Sirve `router` hasta que se cancele `token`; entonces deja de aceptar
+/// conexiones y espera a las peticiones en curso hasta `drain_timeout`.
+/// Las que no acaben a tiempo se cortan al terminar el proceso.
+pub async fn serve_with_graceful_shutdown(
+    listener: TcpListener,
+    router: Router,
+    token: CancellationToken,
+    drain_timeout: Duration,
+) -> std::io::Result<()> {
+    let server = axum::serve(listener, router.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(token.clone().cancelled_owned());
+    let mut server = tokio::spawn(async move { server.await });
+
+    tokio::select! {
+        result = &mut server => return join_result(result),
+        _ = token.cancelled() => {}
+    }
+
+    match tokio::time::timeout(drain_timeout, &mut server).await {
+        Ok(result) => join_result(result),
+        Err(_) => {
+            tracing::warn!("In-flight requests still running after {:?}, closing them", drain_timeout);
+            server.abort();
+            Ok(())
+        }
+    }
+}
+
+fn join_result(result: Result<std::io::Result<()>, tokio::task::JoinError>) -> std::io::Result<()> {
+    result.map_err(std::io::Error::other)?
+}
+
+// =============================================================================
+// READINESS
+// =============================================================================
+
+/// Si el proceso debe recibir tráfico
+#[derive(Debug, Clone, Default)]
+pub struct Readiness {
+    ready: Arc<AtomicBool>,
+}
+
+impl Readiness {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Migraciones hechas y todos los servidores escuchando
+    pub fn mark_ready(&self) {
+        self.ready.store(true, Ordering::SeqCst);
+    }
+
+    /// Apagando: que el balanceador deje de enviar peticiones nuevas
+    pub fn mark_not_ready(&self) {
+        self.ready.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::SeqCst)
+    }
+
+    /// `GET /ready`
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/ready", get(readiness_check))
+            .with_state(self.clone())
+    }
+}
+
+async fn readiness_check(State(readiness): State<Readiness>) -> (StatusCode, Json<serde_json::Value>) {
+    if readiness.is_ready() {
+        (StatusCode::OK, Json(serde_json::json!({ "status": "ready" })))
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, Json(serde_json::json!({ "status": "not_ready" })))
+    }
+}
+
+// =============================================================================
+// BACKGROUND TASKS
+// =============================================================================
+
+/// Jobs que los gateways arrancan en background, para pararlos al apagar
+#[derive(Debug, Clone, Default)]
+pub struct BackgroundTasks {
+    tasks: Arc<Mutex<Vec<(&'static str, JoinHandle<()>)>>>,
+}
+
+impl BackgroundTasks {
+    pub fn register(&self, name: &'static str, handle: JoinHandle<()>) {
+        self.tasks.lock().unwrap().push((name, handle));
+    }
+
+    pub fn spawn<F>(&self, name: &'static str, task: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.register(name, tokio::spawn(task));
+    }
+
+    pub fn len(&self) -> usize {
+        self.tasks.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Para todas las tareas y espera a que suelten lo que tienen. Los jobs son
+    /// bucles por intervalo sin estado propio: se cortan entre ticks o en el
+    /// await en curso, y una transacción a medias se deshace y se repite en el
+    /// siguiente arranque.
+    pub async fn shutdown(&self, timeout: Duration) {
+        let tasks: Vec<_> = std::mem::take(&mut *self.tasks.lock().unwrap());
+        for (_, handle) in &tasks {
+            handle.abort();
+        }
+        let stopped = async {
+            for (name, handle) in tasks {
+                match handle.await {
+                    Err(e) if e.is_panic() => tracing::error!("Background task {} panicked: {}", name, e),
+                    _ => tracing::debug!("Background task {} stopped", name),
+                }
+            }
+        };
+        if tokio::time::timeout(timeout, stopped).await.is_err() {
+            tracing::warn!("Background tasks still stopping after {:?}", timeout);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn background_tasks_are_stopped_on_shutdown() {
+        let tasks = BackgroundTasks::default();
+        let (tx, mut rx) = tokio::sync::mpsc::channel::<()>(1);
+        tasks.spawn("ticker", async move {
+            let _tx = tx;
+            std::future::pending::<()>().await;
+        });
+        assert_eq!(tasks.len(), 1);
+
+        tasks.shutdown(Duration::from_secs(1)).await;
+
+        // El sender se soltó al abortar la tarea
+        assert!(rx.recv().await.is_none());
+        assert!(tasks.is_empty());
+    }
+}
@@ -0,0 +1,230 @@
+// =============================================================================
+// DISTRIBUTED TRACING (OpenTelemetry)
+// =============================================================================
+//
+// Los spans de `tracing` (peticiones, casos de uso y repositorios con
+// `#[instrument]`) se exportan por OTLP cuando hay
+// `OTEL_EXPORTER_OTLP_ENDPOINT`; sin él solo se escriben los logs. El contexto
+// viaja en formato W3C: cabecera `traceparent` en HTTP (lo extrae y devuelve
+// `TracingLayer`) y `ServiceMessage::trace_context` en las colas de Redis,
+// para que los workers de Solana y ZK continúen la misma traza.
+
+use axum::http::HeaderMap;
+use opentelemetry::global;
+use opentelemetry::propagation::Extractor;
+use opentelemetry::KeyValue;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{self as sdktrace, Sampler};
+use opentelemetry_sdk::Resource;
+use std::collections::HashMap;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use vibestream_types::TraceContext;
+
+use crate::shared::domain::errors::AppError;
+use crate::shared::domain::events::current_request_id;
+use crate::shared::infrastructure::correlation::REQUEST_ID_HEADER;
+
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+pub const TRACESTATE_HEADER: &str = "tracestate";
+
+const DEFAULT_SERVICE_NAME: &str = "api-gateway";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TelemetryConfig {
+    pub service_name: String,
+    /// Colector OTLP (gRPC); sin él no se exportan spans
+    pub otlp_endpoint: Option<String>,
+    /// Fracción de trazas nuevas que se muestrean (0.0-1.0). Las que llegan con
+    /// `traceparent` siguen la decisión de quien las empezó.
+    pub sampling_ratio: f64,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            service_name: DEFAULT_SERVICE_NAME.to_string(),
+            otlp_endpoint: None,
+            sampling_ratio: 1.0,
+        }
+    }
+}
+
+impl TelemetryConfig {
+    /// `OTEL_SERVICE_NAME`, `OTEL_EXPORTER_OTLP_ENDPOINT` y
+    /// `OTEL_TRACES_SAMPLER_ARG`; un ratio fuera de 0.0-1.0 se ignora
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> Self {
+        let non_empty = |name: &str| var(name).map(|value| value.trim().to_string()).filter(|value| !value.is_empty());
+        let defaults = Self::default();
+        Self {
+            service_name: non_empty("OTEL_SERVICE_NAME").unwrap_or(defaults.service_name),
+            otlp_endpoint: non_empty("OTEL_EXPORTER_OTLP_ENDPOINT"),
+            sampling_ratio: non_empty("OTEL_TRACES_SAMPLER_ARG")
+                .and_then(|ratio| ratio.parse::<f64>().ok())
+                .filter(|ratio| (0.0..=1.0).contains(ratio))
+                .unwrap_or(defaults.sampling_ratio),
+        }
+    }
+
+    pub fn sampler(&self) -> Sampler {
+        Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(self.sampling_ratio)))
+    }
+}
+
+/// Vacía los spans pendientes al apagar
+pub struct TelemetryGuard {
+    exporting: bool,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if self.exporting {
+            global::shutdown_tracer_provider();
+        }
+    }
+}
+
+/// Propagación W3C (`traceparent`/`tracestate`) para HTTP y colas
+pub fn install_propagator() {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+}
+
+/// Logs por consola y, con colector configurado, exportación OTLP de los spans
+pub fn init_tracing(config: &TelemetryConfig) -> Result<TelemetryGuard, AppError> {
+    install_propagator();
+
+    let otel_layer = match &config.otlp_endpoint {
+        Some(endpoint) => {
+            let tracer = opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(endpoint.clone()))
+                .with_trace_config(
+                    sdktrace::config()
+                        .with_sampler(config.sampler())
+                        .with_resource(Resource::new(vec![KeyValue::new("service.name", config.service_name.clone())])),
+                )
+                .install_batch(opentelemetry_sdk::runtime::Tokio)
+                .map_err(|e| AppError::ConfigurationError(format!("Invalid OTLP exporter {}: {}", endpoint, e)))?;
+            Some(tracing_opentelemetry::layer().with_tracer(tracer))
+        }
+        None => None,
+    };
+
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::filter::LevelFilter::INFO)
+        .with(tracing_subscriber::fmt::layer())
+        .with(otel_layer)
+        .try_init()
+        .map_err(|e| AppError::ConfigurationError(format!("Tracing already initialized: {}", e)))?;
+
+    Ok(TelemetryGuard { exporting: config.otlp_endpoint.is_some() })
+}
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|name| name.as_str()).collect()
+    }
+}
+
+/// Contexto remoto de una petición entrante; sin `traceparent` válido está
+/// vacío y el span de la petición empieza una traza nueva
+pub fn extract_context(headers: &HeaderMap) -> opentelemetry::Context {
+    global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)))
+}
+
+/// Cabeceras W3C de `span`; vacías si no se está trazando
+pub fn trace_headers(span: &tracing::Span) -> HashMap<String, String> {
+    let mut carrier = HashMap::new();
+    global::get_text_map_propagator(|propagator| propagator.inject_context(&span.context(), &mut carrier));
+    carrier
+}
+
+/// Contexto del span actual para embeberlo en un `ServiceMessage`
+pub fn current_trace_context() -> Option<TraceContext> {
+    let mut headers = trace_headers(&tracing::Span::current());
+    let traceparent = headers.remove(TRACEPARENT_HEADER)?;
+    Some(TraceContext {
+        traceparent,
+        tracestate: headers.remove(TRACESTATE_HEADER).filter(|state| !state.is_empty()),
+    })
+}
+
+/// Hacer de `span` un hijo del productor del mensaje
+pub fn continue_trace(span: &tracing::Span, trace_context: &TraceContext) {
+    let mut carrier = HashMap::from([(TRACEPARENT_HEADER.to_string(), trace_context.traceparent.clone())]);
+    if let Some(tracestate) = &trace_context.tracestate {
+        carrier.insert(TRACESTATE_HEADER.to_string(), tracestate.clone());
+    }
+    span.set_parent(global::get_text_map_propagator(|propagator| propagator.extract(&carrier)));
+}
+
+/// Propagar la traza y el request ID actuales en una llamada HTTP a otro servicio
+pub fn with_trace_headers(request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    let request = match current_request_id() {
+        Some(request_id) => request.header(REQUEST_ID_HEADER, request_id.to_string()),
+        None => request,
+    };
+    trace_headers(&tracing::Span::current())
+        .into_iter()
+        .fold(request, |request, (name, value)| request.header(name, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_sdk::testing::trace::InMemorySpanExporterBuilder;
+    use tracing::Instrument;
+
+    #[test]
+    fn config_comes_from_otel_variables() {
+        let vars = HashMap::from([
+            ("OTEL_SERVICE_NAME", "gateway-eu"),
+            ("OTEL_EXPORTER_OTLP_ENDPOINT", "http://collector:4317"),
+            ("OTEL_TRACES_SAMPLER_ARG", "0.25"),
+        ]);
+        let config = TelemetryConfig::from_vars(|name| vars.get(name).map(|value| value.to_string()));
+        assert_eq!(config.service_name, "gateway-eu");
+        assert_eq!(config.otlp_endpoint.as_deref(), Some("http://collector:4317"));
+        assert_eq!(config.sampling_ratio, 0.25);
+
+        let config = TelemetryConfig::from_vars(|name| (name == "OTEL_TRACES_SAMPLER_ARG").then(|| "2".to_string()));
+        assert_eq!(config, TelemetryConfig::default());
+    }
+
+    #[tokio::test]
+    async fn queue_messages_carry_the_producer_trace() {
+        install_propagator();
+        let exporter = InMemorySpanExporterBuilder::new().build();
+        let provider = sdktrace::TracerProvider::builder().with_simple_exporter(exporter.clone()).build();
+        let subscriber =
+            tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        let _default = tracing::subscriber::set_default(subscriber);
+
+        let producer = tracing::info_span!("publish");
+        let trace_context = async { current_trace_context() }.instrument(producer.clone()).await.unwrap();
+        let (trace_id, parent_span_id) = trace_context.ids().unwrap();
+        let (trace_id, parent_span_id) = (trace_id.to_string(), parent_span_id.to_string());
+
+        let consumer = tracing::info_span!("consume");
+        continue_trace(&consumer, &trace_context);
+        drop(consumer);
+        drop(producer);
+
+        let spans = exporter.get_finished_spans().unwrap();
+        let consumer = spans.iter().find(|span| span.name == "consume").unwrap();
+        assert_eq!(consumer.span_context.trace_id().to_string(), trace_id);
+        assert_eq!(consumer.parent_span_id.to_string(), parent_span_id);
+    }
+}
@@ -0,0 +1,211 @@
+// =============================================================================
+// ARTIST DASHBOARD INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// Todos los KPIs del dashboard salen de una sola consulta: cada campo refleja
+// los datos sembrados del artista (sin mezclar los de otro) y la consulta se
+// resuelve en menos de 50 ms con una tabla de escuchas poblada.
+
+use api_gateway::bounded_contexts::music::infrastructure::repositories::{
+    PostgresArtistDashboardReadModel, DASHBOARD_TOP_SONGS,
+};
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::{insert_user, setup_pool};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+async fn insert_artist(pool: &PgPool, user_id: Uuid, stage_name: &str) -> Uuid {
+    sqlx::query_scalar("INSERT INTO artists (user_id, stage_name) VALUES ($1, $2) RETURNING id")
+        .bind(user_id)
+        .bind(stage_name)
+        .fetch_one(pool)
+        .await
+        .expect("Artist inserted")
+}
+
+async fn insert_song(pool: &PgPool, artist_id: Uuid, title: &str) -> Uuid {
+    sqlx::query_scalar("INSERT INTO songs (title, artist_id, duration_seconds) VALUES ($1, $2, 200) RETURNING id")
+        .bind(title)
+        .bind(artist_id)
+        .fetch_one(pool)
+        .await
+        .expect("Song inserted")
+}
+
+/// `count` escuchas de la canción repartidas entre `listeners`, `days_ago` días atrás
+async fn insert_listens(pool: &PgPool, song_id: Uuid, listeners: &[Uuid], count: i32, days_ago: i32) {
+    sqlx::query(
+        r#"INSERT INTO listen_events (user_id, song_id, listen_duration_seconds, created_at)
+           SELECT ($2::uuid[])[1 + i % array_length($2::uuid[], 1)], $1, 180,
+                  NOW() - make_interval(days => $4, secs => i)
+           FROM generate_series(1, $3) AS i"#,
+    )
+    .bind(song_id)
+    .bind(listeners)
+    .bind(count)
+    .bind(days_ago)
+    .execute(pool)
+    .await
+    .expect("Listens inserted");
+}
+
+async fn insert_campaign(pool: &PgPool, song_id: Uuid, artist_user: Uuid, name: &str, status: &str) -> Uuid {
+    let campaign_id = Uuid::new_v4();
+    sqlx::query(
+        r#"INSERT INTO campaigns (id, song_id, artist_id, name, description, start_date, end_date,
+                                  boost_multiplier, nft_price, max_nfts, nfts_sold, status)
+           VALUES ($1, $2, $3, $4, 'Dashboard', NOW(), NOW() + INTERVAL '30 days',
+                   2.0, 10.0, 100, 12, $5)"#,
+    )
+    .bind(campaign_id)
+    .bind(song_id)
+    .bind(artist_user)
+    .bind(name)
+    .bind(status)
+    .execute(pool)
+    .await
+    .expect("Campaign inserted");
+    campaign_id
+}
+
+async fn insert_balance_entry(pool: &PgPool, artist_user: Uuid, amount: &str) {
+    sqlx::query("INSERT INTO artist_balance_entries (artist_id, source_id, amount, currency) VALUES ($1, $2, $3::numeric, 'USD')")
+        .bind(artist_user)
+        .bind(Uuid::new_v4())
+        .bind(amount)
+        .execute(pool)
+        .await
+        .expect("Balance entry inserted");
+}
+
+async fn insert_revenue_distribution(pool: &PgPool, artist_user: Uuid, artist_share: &str) {
+    let venture_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO artist_ventures (artist_id, title, funding_goal, min_investment) VALUES ($1, 'Debut EP', 1000, 1) RETURNING id",
+    )
+    .bind(artist_user)
+    .fetch_one(pool)
+    .await
+    .expect("Venture inserted");
+    sqlx::query(
+        r#"INSERT INTO revenue_distributions (id, venture_id, total_revenue, artist_share, fan_share, platform_fee,
+                                              distributed_at, period_start, period_end)
+           VALUES ($1, $2, 1000, $3::numeric, 250, 50, NOW(), NOW() - INTERVAL '30 days', NOW())"#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(venture_id)
+    .bind(artist_share)
+    .execute(pool)
+    .await
+    .expect("Revenue distribution inserted");
+}
+
+#[tokio::test]
+async fn test_dashboard_populates_every_kpi_quickly() {
+    let (_setup, pool) = setup_pool().await;
+
+    let tag = &Uuid::new_v4().simple().to_string()[..8];
+    let artist_user = insert_user(&pool, &format!("dash_artist_{}", tag)).await;
+    let artist_id = insert_artist(&pool, artist_user, "Dashboard Artist").await;
+    let mut listeners = Vec::new();
+    for i in 0..20 {
+        listeners.push(insert_user(&pool, &format!("dash_listener_{}_{}", tag, i)).await);
+    }
+
+    // Siete canciones con 10, 20, ... 70 escuchas recientes: solo las cinco
+    // más escuchadas entran en el top
+    let mut songs = Vec::new();
+    for i in 0..7 {
+        let song_id = insert_song(&pool, artist_id, &format!("Track {}", i)).await;
+        insert_listens(&pool, song_id, &listeners[..10], (i + 1) * 10, 1).await;
+        songs.push(song_id);
+    }
+    // Escuchas antiguas: cuentan como streams pero no como oyentes mensuales
+    insert_listens(&pool, songs[0], &listeners[10..], 40, 60).await;
+
+    // Otro artista con mucho catálogo escuchado, para que la consulta trabaje
+    // sobre una tabla poblada
+    let other_user = insert_user(&pool, &format!("dash_other_{}", tag)).await;
+    let other_artist = insert_artist(&pool, other_user, "Other Artist").await;
+    for i in 0..20 {
+        let song_id = insert_song(&pool, other_artist, &format!("Other {}", i)).await;
+        insert_listens(&pool, song_id, &listeners, 5_000, i % 45).await;
+    }
+    insert_campaign(&pool, songs[6], other_user, "Other Campaign", "Active").await;
+    insert_balance_entry(&pool, other_user, "999.00").await;
+
+    let campaign_id = insert_campaign(&pool, songs[6], artist_user, "Launch Campaign", "Active").await;
+    insert_campaign(&pool, songs[5], artist_user, "Draft Campaign", "Draft").await;
+    insert_balance_entry(&pool, artist_user, "120.50").await;
+    insert_balance_entry(&pool, artist_user, "79.50").await;
+    insert_revenue_distribution(&pool, artist_user, "700.00").await;
+    sqlx::query(
+        "INSERT INTO royalty_advances (artist_id, advance_amount, repayment_rate, outstanding_balance, currency) VALUES ($1, 500, 0.25, 320.25, 'USD')",
+    )
+    .bind(artist_user)
+    .execute(&pool)
+    .await
+    .expect("Advance inserted");
+    sqlx::query("UPDATE artists SET follower_count = 42 WHERE id = $1")
+        .bind(artist_id)
+        .execute(&pool)
+        .await
+        .expect("Followers updated");
+    sqlx::query("ANALYZE").execute(&pool).await.expect("Analyzed");
+
+    let read_model = PostgresArtistDashboardReadModel::new(pool.clone());
+    let dashboard = read_model.dashboard(artist_id).await.unwrap().expect("Dashboard found");
+
+    assert_eq!(dashboard.artist_id, artist_id);
+    assert_eq!(dashboard.total_earnings, Decimal::from_str("200.00").unwrap());
+    assert_eq!(dashboard.monthly_listeners, 10);
+    assert_eq!(dashboard.total_streams, 280 + 40);
+    assert_eq!(dashboard.fractional_ownership_revenue, Decimal::from_str("700.00").unwrap());
+    assert_eq!(dashboard.follower_count, 42);
+    assert_eq!(dashboard.royalty_advances_outstanding, Decimal::from_str("320.25").unwrap());
+
+    assert_eq!(dashboard.top_songs.len(), DASHBOARD_TOP_SONGS as usize);
+    let streams: Vec<u64> = dashboard.top_songs.iter().map(|s| s.total_streams).collect();
+    assert_eq!(streams, vec![70, 60, 50, 50, 40]);
+    assert_eq!(dashboard.top_songs[0].song_id, songs[6]);
+    assert_eq!(dashboard.top_songs[0].title, "Track 6");
+    assert!(dashboard.top_songs.iter().all(|s| s.monthly_listeners == 10));
+
+    assert_eq!(dashboard.active_campaigns.len(), 1);
+    let campaign = &dashboard.active_campaigns[0];
+    assert_eq!(campaign.campaign_id, campaign_id);
+    assert_eq!(campaign.song_id, songs[6]);
+    assert_eq!(campaign.name, "Launch Campaign");
+    assert_eq!((campaign.nfts_sold, campaign.max_nfts), (12, 100));
+
+    // Con la caché caliente, la consulta completa en menos de 50 ms
+    let started = Instant::now();
+    read_model.dashboard(artist_id).await.unwrap().expect("Dashboard found");
+    let elapsed = started.elapsed();
+    assert!(elapsed < Duration::from_millis(50), "dashboard took {:?}", elapsed);
+}
+
+#[tokio::test]
+async fn test_dashboard_of_new_artist_is_zeroed_and_unknown_artist_is_none() {
+    let (_setup, pool) = setup_pool().await;
+
+    let tag = &Uuid::new_v4().simple().to_string()[..8];
+    let user_id = insert_user(&pool, &format!("dash_new_{}", tag)).await;
+    let artist_id = insert_artist(&pool, user_id, "New Artist").await;
+    let read_model = PostgresArtistDashboardReadModel::new(pool);
+
+    let dashboard = read_model.dashboard(artist_id).await.unwrap().expect("Dashboard found");
+    assert_eq!(dashboard.total_earnings, Decimal::ZERO);
+    assert_eq!((dashboard.monthly_listeners, dashboard.total_streams, dashboard.follower_count), (0, 0, 0));
+    assert!(dashboard.top_songs.is_empty());
+    assert!(dashboard.active_campaigns.is_empty());
+    assert_eq!(dashboard.fractional_ownership_revenue, Decimal::ZERO);
+    assert_eq!(dashboard.royalty_advances_outstanding, Decimal::ZERO);
+    assert_eq!(read_model.artist_user_id(artist_id).await.unwrap(), Some(user_id));
+
+    assert!(read_model.dashboard(Uuid::new_v4()).await.unwrap().is_none());
+    assert!(read_model.artist_user_id(Uuid::new_v4()).await.unwrap().is_none());
+}
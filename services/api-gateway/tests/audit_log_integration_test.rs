@@ -0,0 +1,84 @@
+use api_gateway::bounded_contexts::fan_ventures::domain::audit::AuditLog;
+use api_gateway::bounded_contexts::fan_ventures::domain::repositories::AuditLogRepository;
+use api_gateway::bounded_contexts::fan_ventures::infrastructure::PostgresAuditLogRepository;
+use api_gateway::bounded_contexts::orchestrator::DomainEvent;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::setup_pool;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+fn share_purchase(venture_id: Uuid, minutes_ago: i64) -> AuditLog {
+    AuditLog::from_event(&DomainEvent::SharePurchased {
+        venture_id,
+        investment_id: Uuid::new_v4(),
+        investor_id: Uuid::new_v4(),
+        amount: 25.0,
+        funding_before: 100.0,
+        funding_after: 125.0,
+        occurred_at: Utc::now() - Duration::minutes(minutes_ago),
+    })
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_audit_log_is_append_only() {
+    let (_setup, pool) = setup_pool().await;
+    let repo = PostgresAuditLogRepository::new(pool.clone());
+
+    // 1. Inserts are allowed and come back in chronological order
+    let venture_id = Uuid::new_v4();
+    let (older, newer) = (share_purchase(venture_id, 10), share_purchase(venture_id, 1));
+    repo.append(&newer).await.expect("Insert failed");
+    repo.append(&older).await.expect("Insert failed");
+    repo.append(&share_purchase(Uuid::new_v4(), 5)).await.expect("Insert failed");
+
+    let trail = repo.get_for_aggregate(venture_id).await.unwrap();
+    assert_eq!(trail.iter().map(|e| e.event_id).collect::<Vec<_>>(), vec![older.event_id, newer.event_id]);
+    assert_eq!(trail[0].after_state["current_funding"], 125.0);
+
+    // 2. The table owner is stopped by the immutability trigger
+    let update = sqlx::query("UPDATE audit_log SET event_type = 'Tampered' WHERE event_id = $1")
+        .bind(older.event_id)
+        .execute(&pool)
+        .await;
+    assert!(update.unwrap_err().to_string().contains("audit log entries are immutable"));
+
+    let delete = sqlx::query("DELETE FROM audit_log WHERE event_id = $1")
+        .bind(older.event_id)
+        .execute(&pool)
+        .await;
+    assert!(delete.is_err());
+
+    // 3. The application role has no UPDATE/DELETE grants at all
+    let mut tx = pool.begin().await.unwrap();
+    sqlx::query("SET LOCAL ROLE vibestream_api").execute(&mut *tx).await.unwrap();
+    let update = sqlx::query("UPDATE audit_log SET event_type = 'Tampered' WHERE event_id = $1")
+        .bind(older.event_id)
+        .execute(&mut *tx)
+        .await;
+    assert!(update.unwrap_err().to_string().contains("permission denied"));
+    tx.rollback().await.unwrap();
+
+    let mut tx = pool.begin().await.unwrap();
+    sqlx::query("SET LOCAL ROLE vibestream_api").execute(&mut *tx).await.unwrap();
+    let appended = share_purchase(venture_id, 0);
+    sqlx::query(
+        "INSERT INTO audit_log (event_id, aggregate_id, event_type, before_state, after_state, occurred_at) VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(appended.event_id)
+    .bind(appended.aggregate_id)
+    .bind(&appended.event_type)
+    .bind(&appended.before_state)
+    .bind(&appended.after_state)
+    .bind(appended.occurred_at)
+    .execute(&mut *tx)
+    .await
+    .expect("Application role should be able to insert");
+    tx.rollback().await.unwrap();
+
+    // 4. Nothing was modified
+    let trail = repo.get_for_aggregate(venture_id).await.unwrap();
+    assert_eq!(trail.len(), 2);
+    assert!(trail.iter().all(|e| e.event_type == "SharePurchased"));
+}
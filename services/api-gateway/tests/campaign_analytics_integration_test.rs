@@ -0,0 +1,218 @@
+// =============================================================================
+// CAMPAIGN ANALYTICS INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// Los eventos de `campaign_events` alimentan las proyecciones de analytics; el
+// informe debe coincidir con el guion de eventos y sobrevivir a un rebuild.
+// Las fotos diarias calculadas en SQL deben coincidir con el cálculo de dominio.
+
+use api_gateway::bounded_contexts::campaign::domain::analytics::{
+    CampaignActivity, CampaignActivityEvent, CampaignAnalyticsSnapshot, CampaignListen,
+};
+use api_gateway::bounded_contexts::campaign::domain::repository::{CampaignAnalyticsRepository, CampaignSnapshotRepository};
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use api_gateway::bounded_contexts::campaign::infrastructure::analytics_repository::PostgresCampaignAnalyticsRepository;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::{insert_user, setup_pool};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn insert_campaign(pool: &PgPool, budget: f64) -> Uuid {
+    let artist_user = Uuid::new_v4();
+    sqlx::query("INSERT INTO users (id, email, username, password_hash) VALUES ($1, 'analytics@example.com', 'analytics_artist', 'hash')")
+        .bind(artist_user)
+        .execute(pool)
+        .await
+        .expect("User inserted");
+    let artist_id: Uuid = sqlx::query_scalar("INSERT INTO artists (user_id, stage_name) VALUES ($1, 'Analytics Artist') RETURNING id")
+        .bind(artist_user)
+        .fetch_one(pool)
+        .await
+        .expect("Artist inserted");
+    let song_id: Uuid = sqlx::query_scalar("INSERT INTO songs (title, artist_id) VALUES ('Funnel', $1) RETURNING id")
+        .bind(artist_id)
+        .fetch_one(pool)
+        .await
+        .expect("Song inserted");
+
+    let campaign_id = Uuid::new_v4();
+    sqlx::query(
+        r#"INSERT INTO campaigns (id, song_id, artist_id, name, description, start_date, end_date,
+                                  boost_multiplier, nft_price, max_nfts, status, budget)
+           VALUES ($1, $2, $3, 'Funnel Campaign', 'Analytics', NOW(), NOW() + INTERVAL '30 days',
+                   2.0, 10.0, 100, 'Active', $4)"#,
+    )
+    .bind(campaign_id)
+    .bind(song_id)
+    .bind(artist_user)
+    .bind(budget)
+    .execute(pool)
+    .await
+    .expect("Campaign inserted");
+    campaign_id
+}
+
+fn action(action_type: &str, region: &str) -> CampaignActivity {
+    CampaignActivity::ActionPerformed { action_type: action_type.to_string(), region: Some(region.to_string()) }
+}
+
+#[tokio::test]
+async fn test_funnel_from_scripted_events_and_rebuild() {
+    let (_setup, pool) = setup_pool().await;
+    let repository = PostgresCampaignAnalyticsRepository::new(pool.clone());
+
+    let campaign_id = insert_campaign(&pool, 100.0).await;
+    let [ana, ben, cai, dee, eli] = [(); 5].map(|_| Uuid::new_v4());
+    let script = vec![
+        (ana, CampaignActivity::Viewed),
+        (ben, CampaignActivity::Viewed),
+        (cai, CampaignActivity::Viewed),
+        (dee, CampaignActivity::Viewed),
+        (ana, action("listen", "ES")),
+        (ana, action("share", "FR")),
+        (ben, action("listen", "ES")),
+        (eli, action("listen", "MX")),
+        (ana, CampaignActivity::RewardGranted { amount: 10.0 }),
+        (ben, CampaignActivity::RewardGranted { amount: 5.0 }),
+        (ana, CampaignActivity::NftClaimed { mint_address: "Mint1".to_string() }),
+    ];
+    for (user_id, activity) in script {
+        repository.append(&CampaignActivityEvent::new(campaign_id, user_id, activity)).await.expect("Event appended");
+    }
+
+    let report = repository.load_report(campaign_id).await.unwrap().expect("Campaign report");
+    let funnel = &report.conversion_funnel;
+    assert_eq!((funnel.viewed, funnel.participated, funnel.rewarded, funnel.nft_claimed), (5, 3, 2, 1));
+    assert_eq!(report.performance_metrics.total_participants, 3);
+    assert_eq!(report.performance_metrics.total_actions, 4);
+    assert_eq!(report.engagement_data.actions_breakdown.get("listen"), Some(&3));
+    assert_eq!(report.audience_insights.location_distribution.get("ES"), Some(&2));
+    assert_eq!(report.audience_insights.location_distribution.get("MX"), Some(&1));
+    assert_eq!(report.roi_analysis.total_spend, 15.0);
+    assert_eq!(report.roi_analysis.remaining_budget, Some(85.0));
+    assert_eq!(report.time_series_data.last().map(|p| p.total_participants), Some(3));
+
+    // Proyecciones corruptas: el rebuild las rehace desde el event store
+    sqlx::query("UPDATE campaign_analytics_participants SET funnel_stage = 1 WHERE campaign_id = $1")
+        .bind(campaign_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    sqlx::query("DELETE FROM campaign_analytics_actions WHERE campaign_id = $1")
+        .bind(campaign_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    assert_eq!(repository.rebuild(campaign_id).await.unwrap(), 11);
+    let rebuilt = repository.load_report(campaign_id).await.unwrap().expect("Campaign report");
+    assert_eq!(rebuilt, report);
+
+    assert!(repository.load_report(Uuid::new_v4()).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_daily_snapshots_match_the_domain_calculation() {
+    let (_setup, pool) = setup_pool().await;
+    let repository = PostgresCampaignAnalyticsRepository::new(pool.clone());
+
+    let at = |day: u32, hour: u32| Utc.with_ymd_and_hms(2026, 10, day, hour, 0, 0).unwrap();
+    let (starts_at, ends_at) = (at(1, 9), at(4, 0));
+    let campaign_id = insert_campaign(&pool, 100.0).await;
+    let (song_id, artist_id): (Uuid, Uuid) = sqlx::query_as(
+        "UPDATE campaigns c SET start_date = $2, end_date = $3 FROM songs s WHERE c.id = $1 AND s.id = c.song_id RETURNING s.id, s.artist_id",
+    )
+    .bind(campaign_id)
+    .bind(starts_at)
+    .bind(ends_at)
+    .fetch_one(&pool)
+    .await
+    .expect("Campaign window updated");
+
+    let [ana, ben, cai] = [insert_user(&pool, "ana").await, insert_user(&pool, "ben").await, insert_user(&pool, "cai").await];
+    let event = |user_id: Uuid, activity: CampaignActivity, occurred_at: DateTime<Utc>| CampaignActivityEvent {
+        occurred_at,
+        ..CampaignActivityEvent::new(campaign_id, user_id, activity)
+    };
+    let events = vec![
+        event(ana, CampaignActivity::Viewed, at(1, 10)),
+        event(ana, action("listen", "ES"), at(1, 11)),
+        event(ana, CampaignActivity::RewardGranted { amount: 10.0 }, at(1, 11)),
+        event(ben, action("share", "FR"), at(2, 15)),
+        event(ben, CampaignActivity::RewardGranted { amount: 2.5 }, at(2, 15)),
+        event(ana, CampaignActivity::NftClaimed { mint_address: "Mint1".to_string() }, at(3, 8)),
+        event(cai, CampaignActivity::Viewed, at(3, 9)),
+    ];
+    for event in &events {
+        repository.append(event).await.expect("Event appended");
+    }
+
+    let listens = vec![
+        // Antes del inicio y después del final
+        (cai, 500, at(1, 8), "completed"),
+        (ana, 180, at(1, 12), "completed"),
+        (ana, 200, at(2, 23), "rewarded"),
+        (ben, 240, at(2, 16), "verified"),
+        (cai, 60, at(3, 20), "completed"),
+        (ben, 999, at(4, 10), "completed"),
+    ];
+    for (user_id, seconds, started_at, status) in &listens {
+        sqlx::query(
+            r#"INSERT INTO listen_sessions (user_id, song_id, artist_id, user_tier, status, listen_duration_seconds, started_at, completed_at)
+               VALUES ($1, $2, $3, 'basic', $4, $5, $6, $6 + INTERVAL '5 minutes')"#,
+        )
+        .bind(user_id)
+        .bind(song_id)
+        .bind(artist_id)
+        .bind(status)
+        .bind(*seconds as i32)
+        .bind(started_at)
+        .execute(&pool)
+        .await
+        .expect("Listen inserted");
+    }
+    // Sesión sin terminar: no cuenta
+    sqlx::query(
+        "INSERT INTO listen_sessions (user_id, song_id, artist_id, user_tier, status, started_at) VALUES ($1, $2, $3, 'basic', 'active', $4)",
+    )
+    .bind(cai)
+    .bind(song_id)
+    .bind(artist_id)
+    .bind(at(2, 10))
+    .execute(&pool)
+    .await
+    .expect("Active listen inserted");
+
+    let domain_listens: Vec<_> = listens
+        .iter()
+        .map(|(user_id, seconds, started_at, _)| CampaignListen { user_id: *user_id, seconds: *seconds, started_at: *started_at })
+        .collect();
+    let first_day = NaiveDate::from_ymd_opt(2026, 10, 1).unwrap();
+    for offset in 0..5 {
+        let date = first_day + Duration::days(offset);
+        let expected = CampaignAnalyticsSnapshot::calculate(campaign_id, date, starts_at, ends_at, &events, &domain_listens);
+        let snapshot = repository.compute_snapshot(campaign_id, date).await.unwrap().expect("Campaign exists");
+        assert_eq!(snapshot, expected, "snapshot of {}", date);
+        repository.save_snapshot(&snapshot).await.unwrap();
+    }
+
+    let last = repository.find_latest_snapshot(campaign_id).await.unwrap().expect("Snapshot saved");
+    assert_eq!((last.participants_count, last.nfts_minted, last.unique_listeners), (2, 1, 3));
+    assert_eq!((last.total_listen_seconds, last.budget_spent), (680, 12.5));
+
+    let history = repository
+        .find_snapshots(campaign_id, first_day + Duration::days(1), first_day + Duration::days(2))
+        .await
+        .unwrap();
+    assert_eq!(history.iter().map(|s| s.participants_count).collect::<Vec<_>>(), vec![2, 2]);
+
+    // Una foto ya guardada no se sobrescribe y el día ya no está pendiente
+    let mut altered = last.clone();
+    altered.participants_count = 99;
+    repository.save_snapshot(&altered).await.unwrap();
+    assert_eq!(repository.find_latest_snapshot(campaign_id).await.unwrap(), Some(last.clone()));
+    let third_day = first_day + Duration::days(2);
+    assert!(!repository.find_campaigns_to_snapshot(third_day, 100).await.unwrap().contains(&campaign_id));
+    assert!(repository.compute_snapshot(Uuid::new_v4(), last.date).await.unwrap().is_none());
+}
@@ -0,0 +1,133 @@
+// =============================================================================
+// CAMPAIGN AUDIENCE INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// Los perfiles se derivan de `listen_sessions`: país de la última escucha,
+// géneros más escuchados y escuchas completadas para el nivel de fan.
+
+use std::sync::Arc;
+use api_gateway::bounded_contexts::campaign::application::CampaignAudienceService;
+use api_gateway::bounded_contexts::campaign::domain::audience::AudienceCriterion;
+use api_gateway::bounded_contexts::campaign::domain::value_objects::{FanLevel, TargetAudience};
+use api_gateway::bounded_contexts::campaign::infrastructure::postgres_repository::{
+    PostgresAudienceRepository, PostgresCampaignRepository,
+};
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::setup_pool;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+struct Catalog {
+    artist_id: Uuid,
+    jazz_song: Uuid,
+    rock_song: Uuid,
+}
+
+async fn insert_catalog(pool: &PgPool) -> (Uuid, Catalog) {
+    let artist_user = Uuid::new_v4();
+    sqlx::query("INSERT INTO users (id, email, username, password_hash) VALUES ($1, 'audience@example.com', 'audience_artist', 'hash')")
+        .bind(artist_user)
+        .execute(pool)
+        .await
+        .expect("User inserted");
+    let artist_id: Uuid = sqlx::query_scalar("INSERT INTO artists (user_id, stage_name) VALUES ($1, 'Audience Artist') RETURNING id")
+        .bind(artist_user)
+        .fetch_one(pool)
+        .await
+        .expect("Artist inserted");
+    let mut songs = Vec::new();
+    for genre in ["Jazz", "Rock"] {
+        let song_id: Uuid = sqlx::query_scalar("INSERT INTO songs (title, artist_id, genre) VALUES ($1, $2, $1) RETURNING id")
+            .bind(genre)
+            .bind(artist_id)
+            .fetch_one(pool)
+            .await
+            .expect("Song inserted");
+        songs.push(song_id);
+    }
+    (artist_user, Catalog { artist_id, jazz_song: songs[0], rock_song: songs[1] })
+}
+
+async fn insert_listens(pool: &PgPool, catalog: &Catalog, user_id: Uuid, song_id: Uuid, country: &str, listens: i32) {
+    sqlx::query(
+        r#"INSERT INTO listen_sessions (user_id, song_id, artist_id, user_tier, status, location_country, completed_at)
+           SELECT $1, $2, $3, 'basic', 'completed', $4, NOW() FROM generate_series(1, $5)"#,
+    )
+    .bind(user_id)
+    .bind(song_id)
+    .bind(catalog.artist_id)
+    .bind(country)
+    .bind(listens)
+    .execute(pool)
+    .await
+    .expect("Listens inserted");
+}
+
+async fn insert_campaign(pool: &PgPool, artist_user: Uuid, song_id: Uuid) -> Uuid {
+    let campaign_id = Uuid::new_v4();
+    sqlx::query(
+        r#"INSERT INTO campaigns (id, song_id, artist_id, name, description, start_date, end_date,
+                                  boost_multiplier, nft_price, max_nfts, status)
+           VALUES ($1, $2, $3, 'Targeted Campaign', 'Audience', NOW() + INTERVAL '1 day', NOW() + INTERVAL '8 days',
+                   2.0, 10.0, 100, 'Draft')"#,
+    )
+    .bind(campaign_id)
+    .bind(song_id)
+    .bind(artist_user)
+    .execute(pool)
+    .await
+    .expect("Campaign inserted");
+    campaign_id
+}
+
+#[tokio::test]
+async fn test_audience_estimate_and_eligibility() {
+    let (_setup, pool) = setup_pool().await;
+
+    let (artist_user, catalog) = insert_catalog(&pool).await;
+    let campaign_id = insert_campaign(&pool, artist_user, catalog.jazz_song).await;
+
+    // Cumple todo / fuera de país / otro género / poco activo
+    let (target, abroad, rocker, casual) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+    insert_listens(&pool, &catalog, target, catalog.jazz_song, "ES", 60).await;
+    insert_listens(&pool, &catalog, abroad, catalog.jazz_song, "US", 60).await;
+    insert_listens(&pool, &catalog, rocker, catalog.rock_song, "ES", 60).await;
+    insert_listens(&pool, &catalog, casual, catalog.jazz_song, "ES", 10).await;
+
+    let service = CampaignAudienceService::new(
+        Arc::new(PostgresCampaignRepository::new(pool.clone())),
+        Arc::new(PostgresAudienceRepository::new(pool.clone())),
+    );
+
+    let open = service.estimate_reach(campaign_id).await.unwrap();
+    assert_eq!((open.total_fans, open.estimated_reach), (4, 4));
+
+    service
+        .set_target_audience(
+            campaign_id,
+            TargetAudience::new(vec!["es".to_string()], vec!["jazz".to_string()], Some(FanLevel::Regular)),
+        )
+        .await
+        .unwrap();
+
+    let estimate = service.estimate_reach(campaign_id).await.unwrap();
+    assert_eq!(estimate.total_fans, 4);
+    assert_eq!(estimate.location_matches, 3);
+    assert_eq!(estimate.genre_matches, 3);
+    assert_eq!(estimate.fan_level_matches, 3);
+    assert_eq!(estimate.estimated_reach, 1);
+
+    assert!(service.check_eligibility(campaign_id, target).await.unwrap().is_eligible());
+    let failed = |user_id| {
+        let service = &service;
+        async move { service.check_eligibility(campaign_id, user_id).await.unwrap().failed_criteria() }
+    };
+    assert_eq!(failed(abroad).await, vec![AudienceCriterion::Location]);
+    assert_eq!(failed(rocker).await, vec![AudienceCriterion::Genre]);
+    assert_eq!(failed(casual).await, vec![AudienceCriterion::FanLevel]);
+    assert_eq!(
+        failed(Uuid::new_v4()).await,
+        vec![AudienceCriterion::Location, AudienceCriterion::Genre, AudienceCriterion::FanLevel]
+    );
+}
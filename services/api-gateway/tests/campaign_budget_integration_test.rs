@@ -0,0 +1,116 @@
+// =============================================================================
+// CAMPAIGN BUDGET INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// Participaciones en paralelo contra una campaña con presupuesto limitado: el
+// gasto nunca supera el presupuesto, la campaña se pausa una sola vez y el
+// artista recibe el aviso.
+
+use std::sync::Arc;
+use api_gateway::bounded_contexts::campaign::application::budget::CampaignBudgetService;
+use api_gateway::bounded_contexts::campaign::domain::entities::CampaignStatus;
+use api_gateway::bounded_contexts::campaign::infrastructure::postgres_repository::PostgresCampaignParticipationRepository;
+use api_gateway::bounded_contexts::notifications::infrastructure::{
+    CampaignBudgetNotificationListener, PostgresNotificationRepository,
+};
+use api_gateway::bounded_contexts::orchestrator::{EventBus, EventHandler, InMemoryEventBus};
+use api_gateway::shared::domain::errors::AppError;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::{insert_user, setup_pool};
+use futures_util::future::join_all;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Campaña activa del artista con `budget` y `reward_per_action` dados
+async fn insert_campaign(pool: &PgPool, artist_user: Uuid, budget: f64, reward_per_action: f64) -> Uuid {
+    let artist_id: Uuid = sqlx::query_scalar("INSERT INTO artists (user_id, stage_name) VALUES ($1, 'Budget Artist') RETURNING id")
+        .bind(artist_user)
+        .fetch_one(pool)
+        .await
+        .expect("Artist inserted");
+    let song_id: Uuid = sqlx::query_scalar("INSERT INTO songs (title, artist_id) VALUES ('Budget', $1) RETURNING id")
+        .bind(artist_id)
+        .fetch_one(pool)
+        .await
+        .expect("Song inserted");
+
+    let campaign_id = Uuid::new_v4();
+    sqlx::query(
+        r#"INSERT INTO campaigns (id, song_id, artist_id, name, description, start_date, end_date,
+                                  boost_multiplier, nft_price, max_nfts, status, budget, reward_per_action)
+           VALUES ($1, $2, $3, 'Budget Campaign', 'Budget', NOW(), NOW() + INTERVAL '30 days',
+                   2.0, 10.0, 100, 'Active', $4, $5)"#,
+    )
+    .bind(campaign_id)
+    .bind(song_id)
+    .bind(artist_user)
+    .bind(budget)
+    .bind(reward_per_action)
+    .execute(pool)
+    .await
+    .expect("Campaign inserted");
+    campaign_id
+}
+
+#[tokio::test]
+async fn test_parallel_participations_never_overspend_and_pause_once() {
+    let (_setup, pool) = setup_pool().await;
+
+    let artist_user = insert_user(&pool, "budget_artist").await;
+    let campaign_id = insert_campaign(&pool, artist_user, 55.0, 10.0).await;
+    let mut fans = Vec::new();
+    for i in 0..20 {
+        fans.push(insert_user(&pool, &format!("budget_fan_{}", i)).await);
+    }
+
+    let event_bus = Arc::new(InMemoryEventBus::new());
+    let listener = Arc::new(CampaignBudgetNotificationListener::new(Arc::new(PostgresNotificationRepository::new(pool.clone()))));
+    event_bus.subscribe("CampaignBudgetDepleted", listener as Arc<dyn EventHandler>).await.unwrap();
+    let service = Arc::new(CampaignBudgetService::new(
+        Arc::new(PostgresCampaignParticipationRepository::new(pool.clone())),
+        event_bus.clone(),
+    ));
+
+    let attempts = fans.iter().map(|&fan| {
+        let service = service.clone();
+        tokio::spawn(async move { service.reward_participation(campaign_id, fan).await })
+    });
+    let results: Vec<_> = join_all(attempts).await.into_iter().map(|r| r.expect("Task panicked")).collect();
+
+    // 55 cubre cinco recompensas de 10; el resto se rechaza con la campaña pausada
+    let rewarded = results.iter().filter(|r| matches!(r, Ok(reward) if *reward == 10.0)).count();
+    let conflicts = results.iter().filter(|r| matches!(r, Err(AppError::ConflictError(_)))).count();
+    assert_eq!(rewarded, 5);
+    assert_eq!(conflicts, 15);
+
+    let budget = service.find_budget(campaign_id).await.unwrap().expect("Campaign exists");
+    assert_eq!(budget.spent, 50.0);
+    assert_eq!(budget.remaining(), Some(5.0));
+    assert_eq!(budget.status, CampaignStatus::Paused);
+
+    let (participants, rewards_paid): (i64, f64) = sqlx::query_as(
+        "SELECT COUNT(*), COALESCE(SUM(reward_earned), 0)::FLOAT8 FROM campaign_participants WHERE campaign_id = $1",
+    )
+    .bind(campaign_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(participants, 5);
+    assert_eq!(rewards_paid, 50.0);
+
+    let notifications: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM notifications WHERE user_id = $1 AND notification_type = 'campaign_budget_depleted'",
+    )
+    .bind(artist_user)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(notifications, 1);
+
+    // Sin recarga no se puede reanudar; tras recargar vuelve a aceptar participaciones
+    assert!(matches!(service.resume(campaign_id).await, Err(AppError::DomainRuleViolation(_))));
+    service.top_up(campaign_id, 20.0).await.unwrap();
+    assert_eq!(service.resume(campaign_id).await.unwrap().status, CampaignStatus::Active);
+    assert_eq!(service.reward_participation(campaign_id, fans[0]).await.unwrap(), 10.0);
+}
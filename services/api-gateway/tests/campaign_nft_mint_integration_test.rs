@@ -0,0 +1,191 @@
+// =============================================================================
+// CAMPAIGN NFT MINT INTEGRATION TESTS (Postgres + Redis)
+// =============================================================================
+//
+// El minteo viaja por la cola de Solana; aquí un responder simulado ocupa el
+// lugar de solana-integration. La colección no puede superar
+// `nft_collection_size` aunque varios participantes pidan a la vez.
+
+use api_gateway::bounded_contexts::campaign::application::nft_minting::CampaignNftService;
+use api_gateway::bounded_contexts::campaign::domain::nft_mint::NftMintStatus;
+use api_gateway::bounded_contexts::campaign::domain::repository::CampaignNftMintRepository;
+use api_gateway::bounded_contexts::campaign::infrastructure::nft_mint_queue::{
+    CampaignNftMintResultWorker, RedisNftMintQueue,
+};
+use api_gateway::bounded_contexts::campaign::infrastructure::postgres_repository::PostgresCampaignParticipationRepository;
+use api_gateway::services::MessageQueue;
+use api_gateway::shared::domain::errors::AppError;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::{insert_user, setup_pool};
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+use vibestream_types::{NftMintOutcome, NftMintRequest, NftMintResult};
+
+/// Usuario con wallet, sin la cual no se le puede acuñar el NFT
+async fn insert_user_with_wallet(pool: &PgPool, username: &str) -> Uuid {
+    let id = insert_user(pool, username).await;
+    sqlx::query("UPDATE users SET wallet_address = $2 WHERE id = $1")
+        .bind(id)
+        .bind(format!("{}Wallet1111111111111111111111111", username))
+        .execute(pool)
+        .await
+        .expect("Wallet set");
+    id
+}
+
+/// Campaña activa con una colección de `collection_size` NFTs y `participants` participantes
+async fn insert_campaign(pool: &PgPool, collection_size: i32, participants: usize) -> (Uuid, Vec<Uuid>) {
+    let artist_user = insert_user_with_wallet(pool, "nft_artist").await;
+    let artist_id: Uuid = sqlx::query_scalar("INSERT INTO artists (user_id, stage_name) VALUES ($1, 'NFT Artist') RETURNING id")
+        .bind(artist_user)
+        .fetch_one(pool)
+        .await
+        .expect("Artist inserted");
+    let song_id: Uuid = sqlx::query_scalar("INSERT INTO songs (title, artist_id) VALUES ('Collectible', $1) RETURNING id")
+        .bind(artist_id)
+        .fetch_one(pool)
+        .await
+        .expect("Song inserted");
+
+    let campaign_id = Uuid::new_v4();
+    sqlx::query(
+        r#"INSERT INTO campaigns (id, song_id, artist_id, name, description, start_date, end_date,
+                                  boost_multiplier, nft_price, max_nfts, status, nft_collection_size)
+           VALUES ($1, $2, $3, 'Collectors Drop', 'Limited drop', NOW(), NOW() + INTERVAL '30 days',
+                   2.0, 10.0, 100, 'Active', $4)"#,
+    )
+    .bind(campaign_id)
+    .bind(song_id)
+    .bind(artist_user)
+    .bind(collection_size)
+    .execute(pool)
+    .await
+    .expect("Campaign inserted");
+
+    let mut fans = Vec::new();
+    for i in 0..participants {
+        let fan = insert_user_with_wallet(pool, &format!("nft_fan_{}", i)).await;
+        sqlx::query("INSERT INTO campaign_participants (campaign_id, user_id) VALUES ($1, $2)")
+            .bind(campaign_id)
+            .bind(fan)
+            .execute(pool)
+            .await
+            .expect("Participation inserted");
+        fans.push(fan);
+    }
+    (campaign_id, fans)
+}
+
+/// Responder simulado: la dirección del mint se deriva del `request_id`, así que
+/// un mensaje repetido devuelve el mismo mint
+fn spawn_mock_responder(queue: MessageQueue, request_queue: String) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            let Ok(Some(message)) = queue.receive_message(&request_queue, 1).await else { continue };
+            let request: NftMintRequest = serde_json::from_str(&message).expect("Mint request");
+            let result = NftMintResult {
+                request_id: request.request_id,
+                outcome: NftMintOutcome::Minted {
+                    mint_address: format!("Mint{}", request.request_id.simple()),
+                    signature: format!("Sig{}", request.request_id.simple()),
+                },
+            };
+            queue
+                .send_message(&request.reply_queue, &serde_json::to_string(&result).unwrap())
+                .await
+                .expect("Result sent");
+        }
+    })
+}
+
+async fn minted_count(pool: &PgPool, campaign_id: Uuid) -> i32 {
+    sqlx::query_scalar("SELECT nfts_minted FROM campaigns WHERE id = $1")
+        .bind(campaign_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_concurrent_mints_respect_collection_size_and_record_mint_addresses() {
+    let (setup, pool) = setup_pool().await;
+    setup.wait_for_redis().await.expect("Redis failed to start");
+    let queue = MessageQueue::new(&setup.get_redis_url()).await.expect("Redis queue");
+
+    let suffix = Uuid::new_v4();
+    let request_queue = format!("test_nft_mint:{}", suffix);
+    let reply_queue = format!("test_nft_mint_results:{}", suffix);
+    let repository = Arc::new(PostgresCampaignParticipationRepository::new(pool.clone()));
+    let service = Arc::new(
+        CampaignNftService::new(
+            repository.clone(),
+            Arc::new(RedisNftMintQueue::new(queue.clone()).with_queue_name(request_queue.clone())),
+        )
+        .with_reply_queue(reply_queue.clone()),
+    );
+    let responder = spawn_mock_responder(queue.clone(), request_queue);
+    let worker = Arc::new(CampaignNftMintResultWorker::new(service.clone(), queue.clone()));
+    let worker_task = tokio::spawn({
+        let worker = worker.clone();
+        async move { worker.start().await }
+    });
+
+    let (campaign_id, fans) = insert_campaign(&pool, 2, 3).await;
+
+    // Tres participantes a la vez para dos NFTs
+    let mut tasks = Vec::new();
+    for fan in fans.clone() {
+        let service = service.clone();
+        tasks.push(tokio::spawn(async move { service.mint_for_participant(campaign_id, fan, None).await }));
+    }
+    let mut reserved = Vec::new();
+    let mut sold_out = 0;
+    for task in tasks {
+        match task.await.unwrap() {
+            Ok(mint) => reserved.push(mint),
+            Err(AppError::DomainRuleViolation(_)) => sold_out += 1,
+            Err(e) => panic!("unexpected error: {:?}", e),
+        }
+    }
+    assert_eq!(reserved.len(), 2);
+    assert_eq!(sold_out, 1);
+    assert_eq!(minted_count(&pool, campaign_id).await, 2);
+
+    // Esperar a que el worker registre los resultados del responder
+    for _ in 0..50 {
+        let mut all_minted = true;
+        for mint in &reserved {
+            let stored = repository.find_mint(mint.request_id).await.unwrap().unwrap();
+            all_minted &= stored.status == NftMintStatus::Minted;
+        }
+        if all_minted {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    for mint in &reserved {
+        let stored = repository.find_mint(mint.request_id).await.unwrap().unwrap();
+        assert_eq!(stored.status, NftMintStatus::Minted);
+        assert_eq!(stored.mint_address, Some(format!("Mint{}", mint.request_id.simple())));
+        assert_eq!(stored.signature, Some(format!("Sig{}", mint.request_id.simple())));
+    }
+
+    // Un resultado repetido no cambia nada, y pedir otra vez devuelve el mismo NFT
+    let first = &reserved[0];
+    let duplicate = NftMintResult {
+        request_id: first.request_id,
+        outcome: NftMintOutcome::Minted { mint_address: "OtherMint".to_string(), signature: "OtherSig".to_string() },
+    };
+    assert_eq!(service.handle_result(duplicate).await.unwrap(), NftMintStatus::Minted);
+    let again = service.mint_for_participant(campaign_id, first.user_id, None).await.unwrap();
+    assert_eq!(again.request_id, first.request_id);
+    assert_eq!(again.mint_address, Some(format!("Mint{}", first.request_id.simple())));
+    assert_eq!(minted_count(&pool, campaign_id).await, 2);
+
+    worker.stop();
+    let _ = worker_task.await;
+    responder.abort();
+}
@@ -0,0 +1,102 @@
+// =============================================================================
+// CAMPAIGN SCHEDULING INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// El scheduler recibe la hora como parámetro: se mueve el reloj y se comprueban
+// las transiciones que quedan guardadas en `campaigns`.
+
+use std::sync::Arc;
+use api_gateway::bounded_contexts::campaign::application::scheduler::CampaignSchedulerService;
+use api_gateway::bounded_contexts::campaign::domain::entities::CampaignStatus;
+use api_gateway::bounded_contexts::campaign::infrastructure::analytics_repository::PostgresCampaignAnalyticsRepository;
+use api_gateway::bounded_contexts::campaign::infrastructure::postgres_repository::PostgresCampaignRepository;
+use api_gateway::bounded_contexts::orchestrator::InMemoryEventBus;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::setup_pool;
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn insert_artist_song(pool: &PgPool) -> (Uuid, Uuid) {
+    let artist_user = Uuid::new_v4();
+    sqlx::query("INSERT INTO users (id, email, username, password_hash) VALUES ($1, 'scheduled@example.com', 'scheduled_artist', 'hash')")
+        .bind(artist_user)
+        .execute(pool)
+        .await
+        .expect("User inserted");
+    let artist_id: Uuid = sqlx::query_scalar("INSERT INTO artists (user_id, stage_name) VALUES ($1, 'Scheduled Artist') RETURNING id")
+        .bind(artist_user)
+        .fetch_one(pool)
+        .await
+        .expect("Artist inserted");
+    let song_id: Uuid = sqlx::query_scalar("INSERT INTO songs (title, artist_id) VALUES ('Scheduled', $1) RETURNING id")
+        .bind(artist_id)
+        .fetch_one(pool)
+        .await
+        .expect("Song inserted");
+    (artist_user, song_id)
+}
+
+async fn insert_campaign(pool: &PgPool, artist_user: Uuid, song_id: Uuid, start: DateTime<Utc>, auto_start: bool) -> Uuid {
+    let campaign_id = Uuid::new_v4();
+    sqlx::query(
+        r#"INSERT INTO campaigns (id, song_id, artist_id, name, description, start_date, end_date,
+                                  boost_multiplier, nft_price, max_nfts, status, auto_start)
+           VALUES ($1, $2, $3, 'Scheduled Campaign', 'Scheduling', $4, $4 + INTERVAL '7 days',
+                   2.0, 10.0, 100, 'Draft', $5)"#,
+    )
+    .bind(campaign_id)
+    .bind(song_id)
+    .bind(artist_user)
+    .bind(start)
+    .bind(auto_start)
+    .execute(pool)
+    .await
+    .expect("Campaign inserted");
+    campaign_id
+}
+
+#[tokio::test]
+async fn test_scheduler_activates_and_completes_by_date() {
+    let (_setup, pool) = setup_pool().await;
+
+    let start = Utc.with_ymd_and_hms(2030, 3, 1, 9, 0, 0).unwrap();
+    let (artist_user, song_id) = insert_artist_song(&pool).await;
+    let auto = insert_campaign(&pool, artist_user, song_id, start, true).await;
+    let manual = insert_campaign(&pool, artist_user, song_id, start, false).await;
+
+    let service = CampaignSchedulerService::new(
+        Arc::new(PostgresCampaignRepository::new(pool.clone())),
+        Arc::new(PostgresCampaignAnalyticsRepository::new(pool.clone())),
+        Arc::new(InMemoryEventBus::new()),
+    );
+    let status = |campaign_id: Uuid| {
+        let service = &service;
+        async move { service.find_schedule(campaign_id).await.unwrap().unwrap().status }
+    };
+
+    assert_eq!(service.run_due_transitions(start - Duration::minutes(1)).await.unwrap(), 0);
+    assert_eq!(service.run_due_transitions(start).await.unwrap(), 1);
+    assert_eq!(status(auto).await, CampaignStatus::Active);
+    assert_eq!(status(manual).await, CampaignStatus::Draft);
+
+    // Sin force no se adelanta el inicio; dentro de la ventana basta con activarla
+    assert!(service.activate(manual, start - Duration::days(1), false).await.is_err());
+    service.activate(manual, start + Duration::hours(1), false).await.unwrap();
+
+    let end = start + Duration::days(7);
+    assert_eq!(service.run_due_transitions(end - Duration::seconds(1)).await.unwrap(), 0);
+    assert_eq!(service.run_due_transitions(end).await.unwrap(), 2);
+    assert_eq!(status(auto).await, CampaignStatus::Completed);
+    assert_eq!(status(manual).await, CampaignStatus::Completed);
+
+    let (activated_at, completed_at): (Option<DateTime<Utc>>, Option<DateTime<Utc>>) =
+        sqlx::query_as("SELECT activated_at, completed_at FROM campaigns WHERE id = $1")
+            .bind(auto)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(activated_at, Some(start));
+    assert_eq!(completed_at, Some(end));
+}
@@ -0,0 +1,45 @@
+// =============================================================================
+// DATABASE MIGRATION INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// `DatabasePool::migrate` sobre una base vacía aplica todo; una segunda pasada
+// no toca nada y lo indica con `AlreadyUpToDate`.
+
+use api_gateway::services::{DatabasePool, MigrationError, MIGRATOR};
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::TestContainersSetup;
+use sqlx::PgPool;
+
+async fn applied_migrations(pool: &PgPool) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_migrations WHERE success")
+        .fetch_one(pool)
+        .await
+        .expect("Migrations table exists")
+}
+
+#[tokio::test]
+async fn test_running_migrations_twice_is_idempotent() {
+    let setup = TestContainersSetup::new();
+    setup.setup_env();
+    setup.wait_for_postgres().await.expect("Postgres failed to start");
+    let pool = PgPool::connect(&setup.get_postgres_url()).await.expect("Failed to connect to pool");
+
+    DatabasePool::migrate(&pool).await.expect("First run applies every migration");
+    let applied = applied_migrations(&pool).await;
+    assert_eq!(applied, MIGRATOR.iter().filter(|m| m.migration_type.is_up_migration()).count() as i64);
+
+    let second = DatabasePool::migrate(&pool).await;
+    assert!(matches!(second, Err(MigrationError::AlreadyUpToDate)));
+    assert!(!second.unwrap_err().is_failure());
+    assert_eq!(applied_migrations(&pool).await, applied);
+
+    // El esquema quedó completo: tablas de la última migración incluidas
+    let auto_start: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM information_schema.columns WHERE table_name = 'campaigns' AND column_name = 'auto_start')",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert!(auto_start);
+}
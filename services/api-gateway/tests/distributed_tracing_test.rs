@@ -0,0 +1,83 @@
+// =============================================================================
+// DISTRIBUTED TRACING TESTS (Postgres)
+// =============================================================================
+//
+// Una petición que entra con `traceparent` produce el span del gateway dentro
+// de esa traza, y las consultas de los repositorios cuelgan de él.
+
+use api_gateway::bounded_contexts::fan_ventures::domain::repositories::InvestmentReservationRepository;
+use api_gateway::bounded_contexts::fan_ventures::infrastructure::reservation_repository::PostgresInvestmentReservationRepository;
+use api_gateway::shared::infrastructure::correlation::TracingLayer;
+use api_gateway::shared::infrastructure::telemetry::{install_propagator, TRACEPARENT_HEADER};
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{Request, StatusCode},
+    routing::get,
+    Router,
+};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_sdk::testing::trace::InMemorySpanExporterBuilder;
+use opentelemetry_sdk::trace::TracerProvider;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::setup_pool;
+use std::sync::Arc;
+use tower::ServiceExt;
+use tracing_subscriber::layer::SubscriberExt;
+use uuid::Uuid;
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+const CALLER_SPAN_ID: &str = "00f067aa0ba902b7";
+
+async fn get_reservation(
+    State(repository): State<Arc<PostgresInvestmentReservationRepository>>,
+    Path(investment_id): Path<Uuid>,
+) -> StatusCode {
+    match repository.find_by_investment(&investment_id).await {
+        Ok(Some(_)) => StatusCode::OK,
+        Ok(None) => StatusCode::NOT_FOUND,
+        Err(_) => StatusCode::INTERNAL_SERVER_ERROR,
+    }
+}
+
+#[tokio::test]
+async fn request_span_parents_repository_spans() {
+    let (_setup, pool) = setup_pool().await;
+
+    install_propagator();
+    let exporter = InMemorySpanExporterBuilder::new().build();
+    let provider = TracerProvider::builder().with_simple_exporter(exporter.clone()).build();
+    let subscriber =
+        tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(provider.tracer("gateway")));
+    let _default = tracing::subscriber::set_default(subscriber);
+
+    let router = Router::new()
+        .route("/reservations/:investment_id", get(get_reservation))
+        .with_state(Arc::new(PostgresInvestmentReservationRepository::new(pool)))
+        .layer(TracingLayer::new());
+    let request = Request::builder()
+        .uri(format!("/reservations/{}", Uuid::new_v4()))
+        .header(TRACEPARENT_HEADER, format!("00-{}-{}-01", TRACE_ID, CALLER_SPAN_ID))
+        .body(Body::empty())
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let spans = exporter.get_finished_spans().unwrap();
+    let gateway = spans.iter().find(|span| span.name == "request").expect("gateway span");
+    let repository = spans
+        .iter()
+        .find(|span| span.name == "reservation_repository.find_by_investment")
+        .expect("repository span");
+
+    // El gateway continúa la traza del llamante y el repositorio cuelga del gateway
+    assert_eq!(gateway.span_context.trace_id().to_string(), TRACE_ID);
+    assert_eq!(gateway.parent_span_id.to_string(), CALLER_SPAN_ID);
+    assert_eq!(repository.span_context.trace_id().to_string(), TRACE_ID);
+    assert_eq!(repository.parent_span_id, gateway.span_context.span_id());
+
+    // La respuesta devuelve el span del gateway para seguir la traza
+    let traceparent = response.headers()[TRACEPARENT_HEADER].to_str().unwrap();
+    assert_eq!(traceparent, format!("00-{}-{}-01", TRACE_ID, gateway.span_context.span_id()));
+}
@@ -0,0 +1,140 @@
+// =============================================================================
+// FAN BENEFIT REDEMPTION INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// Canjes concurrentes: un beneficio con stock limitado nunca se canjea más
+// veces que su cantidad, y uno de un solo uso solo una vez por fan.
+
+use api_gateway::bounded_contexts::fan_loyalty::domain::benefits::{Benefit, BenefitUsage};
+use api_gateway::bounded_contexts::fan_loyalty::domain::entities::FanId;
+use api_gateway::bounded_contexts::fan_loyalty::domain::repositories::BenefitRepository;
+use api_gateway::bounded_contexts::fan_loyalty::infrastructure::postgres_repositories::PostgresBenefitRepository;
+use api_gateway::shared::domain::errors::{AppError, BenefitRejection};
+use chrono::{Duration, Utc};
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::setup_pool;
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+async fn setup() -> (PgPool, Arc<PostgresBenefitRepository>) {
+    let (_setup, pool) = setup_pool().await;
+    let repository = Arc::new(PostgresBenefitRepository::new(pool.clone()));
+    (pool, repository)
+}
+
+async fn create(repository: &PostgresBenefitRepository, name: &str, usage: BenefitUsage, quantity: Option<u32>) -> Benefit {
+    let benefit = Benefit::new(name.to_string(), None, usage, quantity).unwrap();
+    repository.create_benefit(&benefit).await.expect("Benefit created");
+    benefit
+}
+
+async fn unlock(repository: &PostgresBenefitRepository, name: &str) -> FanId {
+    let fan_id = FanId::new();
+    let unlocked = repository
+        .unlock_benefits(&fan_id, &format!("verification_{}", fan_id.0), &[name.to_string()])
+        .await
+        .expect("Benefits unlocked");
+    assert_eq!(unlocked.len(), 1);
+    fan_id
+}
+
+#[tokio::test]
+async fn test_limited_benefit_is_never_redeemed_beyond_its_quantity() {
+    let (pool, repository) = setup().await;
+    let benefit = create(&repository, "Backstage Tour", BenefitUsage::SingleUse, Some(5)).await;
+
+    let mut fans = Vec::new();
+    for _ in 0..20 {
+        fans.push(unlock(&repository, "backstage tour").await);
+    }
+
+    let staff_id = Uuid::new_v4();
+    let handles: Vec<_> = fans
+        .into_iter()
+        .map(|fan_id| {
+            let repository = repository.clone();
+            tokio::spawn(async move {
+                repository.redeem(benefit.id, &fan_id, "venue_madrid", staff_id, serde_json::json!({"gate": "A"})).await
+            })
+        })
+        .collect();
+
+    let mut redeemed = 0;
+    let mut sold_out = 0;
+    for handle in handles {
+        match handle.await.unwrap() {
+            Ok(_) => redeemed += 1,
+            Err(AppError::BenefitNotRedeemable(BenefitRejection::SoldOut)) => sold_out += 1,
+            Err(e) => panic!("Unexpected redemption error: {}", e),
+        }
+    }
+    assert_eq!(redeemed, 5);
+    assert_eq!(sold_out, 15);
+
+    let stored = repository.find_benefit(benefit.id).await.unwrap().unwrap();
+    assert_eq!(stored.remaining_quantity, Some(0));
+    let recorded: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM fan_benefit_redemptions WHERE benefit_id = $1")
+        .bind(benefit.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(recorded, 5);
+}
+
+#[tokio::test]
+async fn test_single_use_benefit_is_redeemed_once_per_fan() {
+    let (pool, repository) = setup().await;
+    let benefit = create(&repository, "Free Drink", BenefitUsage::SingleUse, None).await;
+    let fan_id = unlock(&repository, "Free Drink").await;
+
+    let staff_id = Uuid::new_v4();
+    let handles: Vec<_> = (0..10)
+        .map(|_| {
+            let repository = repository.clone();
+            let fan_id = fan_id.clone();
+            tokio::spawn(async move { repository.redeem(benefit.id, &fan_id, "venue_madrid", staff_id, serde_json::json!({})).await })
+        })
+        .collect();
+
+    let mut redeemed = 0;
+    for handle in handles {
+        match handle.await.unwrap() {
+            Ok(redemption) => {
+                assert_eq!(redemption.uses_remaining, Some(0));
+                redeemed += 1;
+            }
+            Err(AppError::BenefitNotRedeemable(BenefitRejection::AlreadyRedeemed)) => {}
+            Err(e) => panic!("Unexpected redemption error: {}", e),
+        }
+    }
+    assert_eq!(redeemed, 1);
+
+    let recorded: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM fan_benefit_redemptions WHERE fan_id = $1")
+        .bind(fan_id.0)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(recorded, 1);
+
+    // Sin desbloquear no se canjea
+    let stranger = repository.redeem(benefit.id, &FanId::new(), "venue_madrid", staff_id, serde_json::json!({})).await;
+    assert!(matches!(stranger, Err(AppError::BenefitNotRedeemable(BenefitRejection::NotUnlocked))));
+}
+
+#[tokio::test]
+async fn test_time_windowed_benefit_expires() {
+    let (_pool, repository) = setup().await;
+    let usage = BenefitUsage::TimeWindowed {
+        starts_at: Utc::now() - Duration::hours(3),
+        ends_at: Utc::now() - Duration::hours(1),
+    };
+    let benefit = create(&repository, "Early Entry", usage, None).await;
+    let fan_id = unlock(&repository, "Early Entry").await;
+
+    let result = repository.redeem(benefit.id, &fan_id, "venue_madrid", Uuid::new_v4(), serde_json::json!({})).await;
+    assert!(matches!(result, Err(AppError::BenefitNotRedeemable(BenefitRejection::Expired))));
+    let fan_benefits = repository.get_fan_benefits(&fan_id).await.unwrap();
+    assert_eq!(fan_benefits[0].uses_remaining, None);
+}
@@ -0,0 +1,155 @@
+// =============================================================================
+// FOLLOW GRAPH INTEGRATION TESTS
+// =============================================================================
+//
+// `artists.follower_count` se actualiza en la misma transacción que `follows`:
+// con follows/unfollows concurrentes (y repetidos) nunca se separa del COUNT real.
+
+use api_gateway::bounded_contexts::music::infrastructure::repositories::PostgresArtistFollowersReadModel;
+use api_gateway::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+use api_gateway::bounded_contexts::user::infrastructure::FollowsUserDeletionListener;
+use api_gateway::bounded_contexts::user::domain::{repository::UserRepository, value_objects::UserId};
+use api_gateway::shared::infrastructure::database::postgres::PostgresUserRepository;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::{insert_user, setup_pool};
+use chrono::Utc;
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Artista cuyo usuario es `user_id`; devuelve el id del artista
+async fn insert_artist(pool: &PgPool, user_id: Uuid) -> Uuid {
+    sqlx::query_scalar("INSERT INTO artists (user_id, stage_name) VALUES ($1, 'The Followed') RETURNING id")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .expect("Artist inserted")
+}
+
+async fn counts(pool: &PgPool, artist_id: Uuid, artist_user_id: Uuid) -> (i64, i64) {
+    let denormalized: i64 = sqlx::query_scalar("SELECT follower_count FROM artists WHERE id = $1")
+        .bind(artist_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    let actual: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM follows WHERE followee_id = $1")
+        .bind(artist_user_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+    (denormalized, actual)
+}
+
+#[tokio::test]
+async fn test_follower_count_survives_concurrent_follow_and_unfollow() {
+    let (_setup, pool) = setup_pool().await;
+    let artist_user = insert_user(&pool, "followed_artist").await;
+    let artist_id = insert_artist(&pool, artist_user).await;
+    let mut fans = Vec::new();
+    for i in 0..20 {
+        fans.push(insert_user(&pool, &format!("concurrent_fan_{}", i)).await);
+    }
+    let repository = Arc::new(PostgresUserRepository::new(Arc::new(pool.clone())));
+
+    // Cada fan sigue dos veces a la vez; los pares además dejan de seguir dos veces
+    let mut tasks = Vec::new();
+    for (i, fan) in fans.iter().enumerate() {
+        for _ in 0..2 {
+            let repository = repository.clone();
+            let (fan, artist) = (UserId::from_uuid(*fan), UserId::from_uuid(artist_user));
+            tasks.push(tokio::spawn(async move { repository.add_follower(&fan, &artist).await }));
+        }
+        if i % 2 == 0 {
+            for _ in 0..2 {
+                let repository = repository.clone();
+                let (fan, artist) = (UserId::from_uuid(*fan), UserId::from_uuid(artist_user));
+                tasks.push(tokio::spawn(async move { repository.remove_follower(&fan, &artist).await }));
+            }
+        }
+    }
+    for task in tasks {
+        task.await.unwrap().expect("Follow write failed");
+    }
+
+    let (denormalized, actual) = counts(&pool, artist_id, artist_user).await;
+    assert_eq!(denormalized, actual);
+
+    // Tras las carreras, un unfollow final de todos deja el contador a cero
+    for fan in &fans {
+        repository.remove_follower(&UserId::from_uuid(*fan), &UserId::from_uuid(artist_user)).await.unwrap();
+    }
+    assert_eq!(counts(&pool, artist_id, artist_user).await, (0, 0));
+}
+
+#[tokio::test]
+async fn test_follower_count_feeds_lists_and_search_document() {
+    let (_setup, pool) = setup_pool().await;
+    let artist_user = insert_user(&pool, "listed_artist").await;
+    let artist_id = insert_artist(&pool, artist_user).await;
+    let fan = insert_user(&pool, "listing_fan").await;
+    let repository = PostgresUserRepository::new(Arc::new(pool.clone()));
+    let (fan_id, artist_user_id) = (UserId::from_uuid(fan), UserId::from_uuid(artist_user));
+
+    assert!(repository.add_follower(&fan_id, &artist_user_id).await.unwrap());
+    assert!(!repository.add_follower(&fan_id, &artist_user_id).await.unwrap());
+    assert_eq!(repository.count_following(&fan_id).await.unwrap(), 1);
+    assert_eq!(repository.get_following(&fan_id, 0, 20).await.unwrap().len(), 1);
+
+    let read_model = PostgresArtistFollowersReadModel::new(pool.clone());
+    assert_eq!(read_model.follower_count(artist_id).await.unwrap(), Some(1));
+    let followers = read_model.followers(artist_id, 0, 20).await.unwrap();
+    assert_eq!(followers.len(), 1);
+    assert_eq!(followers[0].user_id, fan);
+    let document = read_model.search_document(artist_id).await.unwrap().expect("Artist document");
+    assert_eq!(document.follower_count, 1);
+
+    // Dejar de seguir a quien no sigues: no-op
+    assert!(repository.remove_follower(&fan_id, &artist_user_id).await.unwrap());
+    assert!(!repository.remove_follower(&fan_id, &artist_user_id).await.unwrap());
+    assert_eq!(read_model.follower_count(artist_id).await.unwrap(), Some(0));
+}
+
+#[tokio::test]
+async fn test_deleted_user_follows_are_removed_and_counts_decremented() {
+    let (_setup, pool) = setup_pool().await;
+    let artist_user = insert_user(&pool, "kept_artist").await;
+    let artist_id = insert_artist(&pool, artist_user).await;
+    let leaving_user = insert_user(&pool, "leaving_artist").await;
+    let leaving_artist_id = insert_artist(&pool, leaving_user).await;
+    let fan = insert_user(&pool, "staying_fan").await;
+    let repository = PostgresUserRepository::new(Arc::new(pool.clone()));
+    let follow = |follower: Uuid, followee: Uuid| {
+        let repository = &repository;
+        async move {
+            repository.add_follower(&UserId::from_uuid(follower), &UserId::from_uuid(followee)).await.unwrap();
+        }
+    };
+
+    // El usuario eliminado sigue a un artista y a la vez tiene seguidores
+    follow(leaving_user, artist_user).await;
+    follow(fan, artist_user).await;
+    follow(fan, leaving_user).await;
+    assert_eq!(counts(&pool, artist_id, artist_user).await, (2, 2));
+    assert_eq!(counts(&pool, leaving_artist_id, leaving_user).await, (1, 1));
+
+    FollowsUserDeletionListener::new(pool.clone())
+        .handle(&DomainEvent::UserDeletionRequested {
+            user_id: leaving_user,
+            pseudonym_id: Uuid::new_v4(),
+            occurred_at: Utc::now(),
+        })
+        .await
+        .expect("Follows deleted");
+
+    assert_eq!(counts(&pool, artist_id, artist_user).await, (1, 1));
+    assert_eq!(counts(&pool, leaving_artist_id, leaving_user).await, (0, 0));
+    let remaining: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM follows WHERE follower_id = $1 OR followee_id = $1")
+        .bind(leaving_user)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining, 0);
+    // El fan sigue siguiendo al artista que queda
+    assert_eq!(repository.count_following(&UserId::from_uuid(fan)).await.unwrap(), 1);
+}
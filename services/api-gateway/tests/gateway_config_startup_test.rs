@@ -0,0 +1,121 @@
+// =============================================================================
+// GATEWAY CONFIG STARTUP TESTS
+// =============================================================================
+//
+// Los gateways se levantan con la configuración del TOML de `GATEWAY_CONFIG`
+// más el entorno: cada uno en su puerto, con o sin CORS y `/health` según su
+// sección. Una configuración inválida o un puerto ocupado paran el arranque.
+
+use api_gateway::gateways::{bind_gateways, serve_gateways, GatewaysConfig, GATEWAY_CONFIG_ENV};
+use api_gateway::shared::domain::errors::AppError;
+use axum::{routing::get, Router};
+use std::io::Write;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+fn gateway_router(name: &'static str) -> Router {
+    Router::new()
+        .route("/health", get(move || async move { format!("{} healthy", name) }))
+        .route("/info", get(move || async move { name }))
+}
+
+fn config_file(contents: &str) -> tempfile::NamedTempFile {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(contents.as_bytes()).unwrap();
+    file
+}
+
+#[tokio::test]
+async fn gateways_start_from_a_config_file_with_environment_overrides() {
+    let file = config_file(
+        r#"
+        [user_gateway]
+        port = 0
+
+        [music_gateway]
+        port = 0
+        cors_enabled = false
+        health_check_enabled = false
+        "#,
+    );
+    std::env::set_var(GATEWAY_CONFIG_ENV, file.path());
+    // El entorno gana al fichero
+    std::env::set_var("VIBESTREAM__USER_GATEWAY__HOST", "127.0.0.1");
+    std::env::set_var("VIBESTREAM__MUSIC_GATEWAY__HOST", "127.0.0.1");
+    let config = GatewaysConfig::from_env().expect("valid config");
+
+    let routers = vec![
+        ("user".to_string(), gateway_router("user")),
+        ("music".to_string(), gateway_router("music")),
+    ];
+    let gateways = bind_gateways(&config, routers).await.expect("gateways bound");
+    let user_addr = gateways[0].local_addr;
+    let music_addr = gateways[1].local_addr;
+    assert_ne!(user_addr.port(), 0);
+    assert_ne!(user_addr, music_addr);
+
+    let token = CancellationToken::new();
+    let server = tokio::spawn(serve_gateways(gateways, token.clone(), Duration::from_secs(1)));
+    let client = reqwest::Client::new();
+
+    // user: CORS permisivo y `/health` activos
+    let response = client
+        .get(format!("http://{}/health", user_addr))
+        .header("Origin", "https://app.vibestream.example")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["access-control-allow-origin"], "*");
+    assert_eq!(response.text().await.unwrap(), "user healthy");
+
+    // music: sin CORS y sin `/health`, pero el resto de rutas responde
+    let response = client.get(format!("http://{}/health", music_addr)).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+    let response = client
+        .get(format!("http://{}/info", music_addr))
+        .header("Origin", "https://app.vibestream.example")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(response.headers().get("access-control-allow-origin").is_none());
+
+    token.cancel();
+    tokio::time::timeout(Duration::from_secs(2), server)
+        .await
+        .expect("gateways stopped")
+        .unwrap()
+        .unwrap();
+
+    // Un fichero con puertos repetidos no llega a arrancar
+    let duplicated = config_file("[user_gateway]\nport = 3002\n");
+    std::env::set_var(GATEWAY_CONFIG_ENV, duplicated.path());
+    match GatewaysConfig::from_env() {
+        Err(AppError::ConfigurationError(message)) => assert!(message.contains("port 3002"), "{}", message),
+        other => panic!("expected a configuration error, got {:?}", other),
+    }
+    std::env::remove_var(GATEWAY_CONFIG_ENV);
+    std::env::remove_var("VIBESTREAM__USER_GATEWAY__HOST");
+    std::env::remove_var("VIBESTREAM__MUSIC_GATEWAY__HOST");
+}
+
+#[tokio::test]
+async fn startup_fails_when_a_gateway_port_is_taken() {
+    let taken = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = taken.local_addr().unwrap().port();
+    let config = GatewaysConfig::load(Some(&format!("[payment_gateway]\nport = {}\n", port)), Vec::new()).unwrap();
+
+    let result = bind_gateways(&config, vec![("payment".to_string(), gateway_router("payment"))]).await;
+    match result {
+        Err(AppError::ConfigurationError(message)) => {
+            assert!(message.contains("'payment'"), "{}", message);
+            assert!(message.contains(&port.to_string()), "{}", message);
+        }
+        Err(other) => panic!("expected a configuration error, got {:?}", other),
+        Ok(_) => panic!("bound a port that was already taken"),
+    }
+
+    // Un gateway sin sección en la configuración tampoco arranca
+    assert!(bind_gateways(&config, vec![("unknown".to_string(), gateway_router("unknown"))]).await.is_err());
+}
@@ -0,0 +1,103 @@
+// =============================================================================
+// GATEWAY METRICS TESTS
+// =============================================================================
+//
+// Cada gateway levantado con `bind_gateways` publica en `/metrics` sus
+// peticiones etiquetadas por plantilla de ruta, método y clase de estado,
+// además de las métricas del proceso.
+
+use api_gateway::gateways::{bind_gateways, serve_gateways, GatewaysConfig};
+use axum::{http::StatusCode, routing::get, Router};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+fn music_router() -> Router {
+    Router::new()
+        .route("/health", get(|| async { "music healthy" }))
+        .route("/songs/:id", get(|| async { "song" }))
+        .route("/songs/:id/lyrics", get(|| async { StatusCode::NOT_FOUND }))
+}
+
+fn user_router() -> Router {
+    Router::new().route("/users/:id", get(|| async { "user" }))
+}
+
+/// Valor de la primera muestra de `name` que lleva todas las etiquetas
+fn sample(text: &str, name: &str, labels: &[&str]) -> Option<f64> {
+    text.lines()
+        .filter(|line| line.starts_with(&format!("{}{{", name)))
+        .find(|line| labels.iter().all(|label| line.contains(label)))
+        .and_then(|line| line.rsplit(' ').next())
+        .and_then(|value| value.parse().ok())
+}
+
+#[tokio::test]
+async fn metrics_endpoint_reports_requests_by_route_template() {
+    let config = GatewaysConfig::load(Some("[music_gateway]\nport = 0\n\n[user_gateway]\nport = 0\n"), Vec::new()).unwrap();
+    let gateways = bind_gateways(
+        &config,
+        vec![("music".to_string(), music_router()), ("user".to_string(), user_router())],
+    )
+    .await
+    .expect("gateways bound");
+    let music_addr = gateways[0].local_addr;
+    let user_addr = gateways[1].local_addr;
+
+    let token = CancellationToken::new();
+    let server = tokio::spawn(serve_gateways(gateways, token.clone(), Duration::from_secs(1)));
+    let client = reqwest::Client::new();
+
+    let song_ids: Vec<String> = (0..3).map(|_| uuid::Uuid::new_v4().to_string()).collect();
+    for id in &song_ids {
+        let response = client.get(format!("http://{}/songs/{}", music_addr, id)).send().await.unwrap();
+        assert_eq!(response.status(), 200);
+    }
+    let response = client.get(format!("http://{}/songs/{}/lyrics", music_addr, song_ids[0])).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+    let response = client.get(format!("http://{}/no-such-route", music_addr)).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+    client.get(format!("http://{}/users/42", user_addr)).send().await.unwrap();
+
+    let response = client.get(format!("http://{}/metrics", music_addr)).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(response.headers()["content-type"].to_str().unwrap().starts_with("text/plain"));
+    let text = response.text().await.unwrap();
+
+    for family in [
+        "# TYPE http_requests_total counter",
+        "# TYPE http_request_duration_seconds histogram",
+        "# TYPE http_requests_in_flight gauge",
+    ] {
+        assert!(text.contains(family), "missing `{}` in:\n{}", family, text);
+    }
+    // El colector del proceso solo existe en Linux
+    if cfg!(target_os = "linux") {
+        assert!(text.contains("# TYPE process_cpu_seconds_total counter"));
+        assert!(text.contains("# TYPE process_resident_memory_bytes gauge"));
+    }
+
+    let songs = ["gateway=\"music\"", "route=\"/songs/:id\"", "method=\"GET\"", "status=\"2xx\""];
+    assert_eq!(sample(&text, "http_requests_total", &songs), Some(3.0));
+    assert_eq!(sample(&text, "http_request_duration_seconds_count", &songs), Some(3.0));
+    assert_eq!(
+        sample(&text, "http_requests_total", &["route=\"/songs/:id/lyrics\"", "status=\"4xx\""]),
+        Some(1.0)
+    );
+    assert_eq!(sample(&text, "http_requests_total", &["route=\"unmatched\"", "status=\"4xx\""]), Some(1.0));
+    assert_eq!(sample(&text, "http_requests_in_flight", &["route=\"/songs/:id\""]), Some(0.0));
+
+    // Sin series por UUID, sin contar los propios scrapes y sin las del otro gateway
+    assert!(song_ids.iter().all(|id| !text.contains(id.as_str())));
+    assert!(!text.contains("route=\"/metrics\""));
+    assert!(!text.contains("gateway=\"user\""));
+
+    let user_text = client.get(format!("http://{}/metrics", user_addr)).send().await.unwrap().text().await.unwrap();
+    assert_eq!(sample(&user_text, "http_requests_total", &["gateway=\"user\"", "route=\"/users/:id\""]), Some(1.0));
+
+    token.cancel();
+    tokio::time::timeout(Duration::from_secs(2), server)
+        .await
+        .expect("gateways stopped")
+        .unwrap()
+        .unwrap();
+}
@@ -0,0 +1,106 @@
+// =============================================================================
+// GRACEFUL SHUTDOWN TESTS
+// =============================================================================
+//
+// Al cancelar el token el servidor deja de aceptar conexiones nuevas, pero la
+// petición que ya estaba en curso termina y recibe su respuesta. Si no acaba
+// dentro del plazo de drenado, el servidor no espera más.
+
+use api_gateway::shared::infrastructure::shutdown::{serve_with_graceful_shutdown, Readiness};
+use axum::{extract::State, routing::get, Router};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
+
+/// Avisa de que la petición empezó y tarda `delay` en responder
+fn slow_router(started: Arc<Notify>, delay: Duration, readiness: &Readiness) -> Router {
+    Router::new()
+        .route(
+            "/slow",
+            get(move |State(started): State<Arc<Notify>>| async move {
+                started.notify_one();
+                tokio::time::sleep(delay).await;
+                "done"
+            }),
+        )
+        .with_state(started)
+        .merge(readiness.router())
+}
+
+async fn start_server(
+    router: Router,
+    drain_timeout: Duration,
+) -> (SocketAddr, CancellationToken, tokio::task::JoinHandle<std::io::Result<()>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let token = CancellationToken::new();
+    let server = tokio::spawn(serve_with_graceful_shutdown(listener, router, token.clone(), drain_timeout));
+    (addr, token, server)
+}
+
+#[tokio::test]
+async fn in_flight_request_completes_while_new_connections_are_refused() {
+    let started = Arc::new(Notify::new());
+    let readiness = Readiness::new();
+    let router = slow_router(started.clone(), Duration::from_millis(500), &readiness);
+    let (addr, token, server) = start_server(router, Duration::from_secs(5)).await;
+
+    let client = reqwest::Client::new();
+    let ready = client.get(format!("http://{}/ready", addr)).send().await.unwrap();
+    assert_eq!(ready.status(), 503);
+    readiness.mark_ready();
+    let ready = client.get(format!("http://{}/ready", addr)).send().await.unwrap();
+    assert_eq!(ready.status(), 200);
+
+    let in_flight = tokio::spawn({
+        let url = format!("http://{}/slow", addr);
+        async move { reqwest::Client::new().get(url).send().await?.text().await }
+    });
+    started.notified().await;
+
+    readiness.mark_not_ready();
+    token.cancel();
+
+    // El listener se cierra enseguida: las conexiones nuevas se rechazan
+    let deadline = Instant::now() + Duration::from_secs(1);
+    loop {
+        match tokio::net::TcpStream::connect(addr).await {
+            Err(e) => {
+                assert_eq!(e.kind(), std::io::ErrorKind::ConnectionRefused);
+                break;
+            }
+            Ok(_) if Instant::now() < deadline => tokio::time::sleep(Duration::from_millis(10)).await,
+            Ok(_) => panic!("server still accepting connections after shutdown"),
+        }
+    }
+
+    // ...mientras la petición en curso termina con normalidad
+    assert_eq!(in_flight.await.unwrap().unwrap(), "done");
+    tokio::time::timeout(Duration::from_secs(2), server)
+        .await
+        .expect("server drained")
+        .unwrap()
+        .unwrap();
+}
+
+#[tokio::test]
+async fn drain_stops_waiting_after_the_deadline() {
+    let started = Arc::new(Notify::new());
+    let router = slow_router(started.clone(), Duration::from_secs(60), &Readiness::new());
+    let (addr, token, server) = start_server(router, Duration::from_millis(200)).await;
+
+    let _stuck = tokio::spawn(reqwest::get(format!("http://{}/slow", addr)));
+    started.notified().await;
+
+    let cancelled_at = Instant::now();
+    token.cancel();
+    tokio::time::timeout(Duration::from_secs(2), server)
+        .await
+        .expect("server gave up draining")
+        .unwrap()
+        .unwrap();
+    assert!(cancelled_at.elapsed() < Duration::from_secs(1));
+}
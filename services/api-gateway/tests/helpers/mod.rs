@@ -11,6 +11,8 @@ use tower::ServiceExt;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 
+use api_gateway::shared::domain::errors::AppError;
+
 // =============================================================================
 // TEST CONFIGURATION
 // =============================================================================
@@ -255,14 +257,12 @@ impl TestResponse {
         }
     }
     
-    fn mock_error(status: StatusCode, message: &str) -> Self {
+    /// Respuesta de error con el mismo envoltorio y código que produce `AppError`
+    fn mock_error(error: AppError) -> Self {
+        let body = error.response_body().to_string();
         Self {
-            status,
-            body: json!({
-                "success": false,
-                "error": message,
-                "timestamp": chrono::Utc::now().to_rfc3339()
-            }).to_string(),
+            status: StatusCode::from(error),
+            body,
         }
     }
     
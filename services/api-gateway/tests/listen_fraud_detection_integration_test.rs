@@ -0,0 +1,76 @@
+// =============================================================================
+// LISTEN FRAUD DETECTION INTEGRATION TESTS
+// =============================================================================
+//
+// Varias cuentas sobre el mismo dispositivo: a partir del límite se marcan y la
+// que supera el umbral se rechaza; todas las puntuaciones quedan para revisión.
+
+use api_gateway::bounded_contexts::listen_reward::application::FraudDetectionService;
+use api_gateway::bounded_contexts::listen_reward::domain::value_objects::{DeviceFingerprint, FraudFlag};
+use api_gateway::bounded_contexts::listen_reward::infrastructure::repositories::{
+    FraudScoreRepository, PostgresFraudScoreRepository,
+};
+use api_gateway::shared::domain::errors::{AppError, FraudReasonCode};
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::setup_pool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+fn fingerprint(device_id: Option<String>) -> DeviceFingerprint {
+    DeviceFingerprint::new(format!("ua-{}", Uuid::new_v4()), format!("ip-{}", Uuid::new_v4()), device_id).unwrap()
+}
+
+#[tokio::test]
+async fn accounts_sharing_a_device_are_flagged_then_rejected() {
+    let (_setup, pool) = setup_pool().await;
+    let repository = Arc::new(PostgresFraudScoreRepository::new(pool));
+    let service = FraudDetectionService::new(repository.clone());
+    let farm = fingerprint(Some(format!("farm-{}", Uuid::new_v4())));
+
+    let mut flagged = Vec::new();
+    for _ in 0..7 {
+        let score = service.screen(&farm, Uuid::new_v4()).await.unwrap();
+        flagged.push(score.has_flag(FraudFlag::DuplicateDeviceAcrossUsers));
+    }
+    assert_eq!(flagged, vec![false, false, false, true, true, true, true]);
+
+    let farmer = Uuid::new_v4();
+    match service.screen(&farm, farmer).await {
+        Err(AppError::FraudDetected(details)) => assert_eq!(details.reason_code, FraudReasonCode::SharedDevice),
+        other => panic!("expected the session to be rejected, got {:?}", other),
+    }
+
+    // La cuenta rechazada encabeza la cola de revisión
+    let pending = repository.find_pending_review(0.85, 100).await.unwrap();
+    let rejected = pending.iter().find(|record| record.user_id == farmer).expect("rejection stored for review");
+    assert!(rejected.rejected);
+    assert_eq!(rejected.device_key, farm.device_key());
+    assert_eq!(rejected.sessions_today, 7);
+
+    let admin = Uuid::new_v4();
+    assert!(repository.mark_reviewed(rejected.id, admin, true).await.unwrap());
+    assert!(!repository.mark_reviewed(rejected.id, admin, true).await.unwrap());
+    let pending = repository.find_pending_review(0.85, 100).await.unwrap();
+    assert!(pending.iter().all(|record| record.id != rejected.id));
+}
+
+#[tokio::test]
+async fn device_is_identified_by_hashes_when_it_has_no_id() {
+    let (_setup, pool) = setup_pool().await;
+    let repository = Arc::new(PostgresFraudScoreRepository::new(pool));
+    let service = FraudDetectionService::new(repository.clone());
+    let shared = fingerprint(None);
+
+    for _ in 0..3 {
+        service.screen(&shared, Uuid::new_v4()).await.unwrap();
+    }
+    let fourth = service.screen(&shared, Uuid::new_v4()).await.unwrap();
+    assert!(fourth.has_flag(FraudFlag::DuplicateDeviceAcrossUsers));
+
+    // Mismo user agent desde otra IP: es otro dispositivo
+    let other_network = DeviceFingerprint::new(shared.user_agent_hash.clone(), format!("ip-{}", Uuid::new_v4()), None)
+        .unwrap();
+    let score = service.screen(&other_network, Uuid::new_v4()).await.unwrap();
+    assert!(score.flags.is_empty());
+}
@@ -0,0 +1,87 @@
+use api_gateway::bounded_contexts::listen_reward::domain::entities::{ListenSession, SessionStatus};
+use api_gateway::bounded_contexts::listen_reward::domain::value_objects::RewardTier;
+use api_gateway::bounded_contexts::listen_reward::infrastructure::repositories::{
+    ListenSessionRepository, PostgresListenSessionRepository,
+};
+use api_gateway::shared::domain::errors::AppError;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::setup_pool;
+use vibestream_types::{ArtistContract, SongContract};
+use uuid::Uuid;
+use sqlx::PgPool;
+
+/// Crea una sesión activa sobre una canción sembrada por las migraciones
+async fn new_session(pool: &PgPool) -> ListenSession {
+    let user_id: Uuid = sqlx::query_scalar("SELECT id FROM users LIMIT 1")
+        .fetch_one(pool).await.expect("Seeded user");
+    let (song_id, artist_id): (Uuid, Uuid) = sqlx::query_as("SELECT id, artist_id FROM songs LIMIT 1")
+        .fetch_one(pool).await.expect("Seeded song");
+
+    let song = SongContract::new(song_id, "Seeded Song".to_string(), artist_id, "Seeded Artist".to_string());
+    let artist = ArtistContract::new(artist_id, Uuid::new_v4(), "Seeded Artist".to_string());
+    ListenSession::new(user_id, song, artist, RewardTier::Basic).0
+}
+
+#[tokio::test]
+async fn test_listen_session_concurrent_update_is_rejected() {
+    let (_setup, pool) = setup_pool().await;
+    let repo = PostgresListenSessionRepository::new(pool.clone());
+
+    // 1. Insert: new sessions start at version 1
+    let session = new_session(&pool).await;
+    assert_eq!(repo.save_versioned(&session).await.expect("Insert failed"), 1);
+
+    // 2. Two writers load the same version
+    let mut first = repo.find_by_id(session.id()).await.unwrap().expect("Session should exist");
+    let mut second = repo.find_by_id(session.id()).await.unwrap().expect("Session should exist");
+    assert_eq!(first.version(), 1);
+
+    // 3. The first write wins and bumps the version
+    first.expire().unwrap();
+    assert_eq!(repo.save_versioned(&first).await.expect("First update failed"), 2);
+
+    // 4. The second write is based on a stale version
+    second.expire().unwrap();
+    match repo.save_versioned(&second).await {
+        Err(AppError::ConcurrencyConflict(msg)) => assert_eq!(msg, "Session was modified concurrently"),
+        other => panic!("Expected concurrency conflict, got {:?}", other),
+    }
+
+    // 5. Re-inserting an already persisted session is also a conflict
+    assert!(matches!(repo.save_versioned(&session).await, Err(AppError::ConcurrencyConflict(_))));
+
+    let stored = repo.find_by_id(session.id()).await.unwrap().unwrap();
+    assert_eq!(stored.version(), 2);
+    assert_eq!(stored.status(), &SessionStatus::Failed);
+}
+
+#[tokio::test]
+async fn test_listen_session_expiry_queries() {
+    let (_setup, pool) = setup_pool().await;
+    let repo = PostgresListenSessionRepository::new(pool.clone());
+
+    let stale = new_session(&pool).await;
+    let fresh = new_session(&pool).await;
+    repo.save(&stale).await.expect("Insert failed");
+    repo.save(&fresh).await.expect("Insert failed");
+    sqlx::query("UPDATE listen_sessions SET started_at = NOW() - INTERVAL '2 hours' WHERE id = $1")
+        .bind(stale.id().value())
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let expired = repo.find_expired_sessions(60).await.unwrap();
+    let expired_ids: Vec<Uuid> = expired.iter().map(|s| s.id().value()).collect();
+    assert!(expired_ids.contains(&stale.id().value()));
+    assert!(!expired_ids.contains(&fresh.id().value()));
+
+    let expired_count = api_gateway::bounded_contexts::listen_reward::application::fail_expired_sessions(&repo, 60)
+        .await
+        .unwrap();
+    assert_eq!(expired_count, expired.len());
+
+    let failed = repo.find_by_user_and_status(stale.user_id(), &SessionStatus::Failed).await.unwrap();
+    assert!(failed.iter().any(|s| s.id().value() == stale.id().value()));
+    assert!(repo.find_expired_sessions(60).await.unwrap().is_empty());
+}
@@ -0,0 +1,75 @@
+// =============================================================================
+// LISTENING PREFERENCES INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// El vector de preferencias se recalcula cada 10 escuchas: con 9 no hay nada
+// que refrescar y con la décima se incorporan todas, con los rasgos de la canción.
+
+use api_gateway::bounded_contexts::music::domain::value_objects::{AudioQuality, Genre, SongMood};
+use api_gateway::bounded_contexts::user::infrastructure::listening_preferences::PostgresListeningPreferences;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::setup_pool;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn insert_listens(pool: &PgPool, user_id: Uuid, song_id: Uuid, count: i32) {
+    sqlx::query(
+        r#"INSERT INTO listen_events (user_id, song_id, listen_duration_seconds, created_at)
+           SELECT $1, $2, 180, date_trunc('day', NOW()) + make_interval(hours => 21, secs => i)
+           FROM generate_series(1, $3) AS i"#,
+    )
+    .bind(user_id)
+    .bind(song_id)
+    .bind(count)
+    .execute(pool)
+    .await
+    .expect("Listens inserted");
+}
+
+#[tokio::test]
+async fn test_preferences_are_refreshed_every_ten_listens() {
+    let (_setup, pool) = setup_pool().await;
+    let preferences = PostgresListeningPreferences::new(pool.clone());
+
+    let user_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO users (id, email, username, password_hash) VALUES ($1, 'prefs@example.com', 'prefs_listener', 'hash')")
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .expect("User inserted");
+    let artist_id: Uuid = sqlx::query_scalar("INSERT INTO artists (user_id, stage_name) VALUES ($1, 'Late Night Trio') RETURNING id")
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await
+        .expect("Artist inserted");
+    let song_id: Uuid = sqlx::query_scalar(
+        r#"INSERT INTO songs (title, artist_id, duration_seconds, genre, mood, tempo_bpm, audio_quality)
+           VALUES ('Blue Hour', $1, 200, 'jazz', 'calm', 96, 'high') RETURNING id"#,
+    )
+    .bind(artist_id)
+    .fetch_one(&pool)
+    .await
+    .expect("Song inserted");
+
+    insert_listens(&pool, user_id, song_id, 9).await;
+    assert_eq!(preferences.refresh_due(100).await.unwrap(), 0);
+    let stored = preferences.find(user_id).await.unwrap().expect("User exists");
+    assert_eq!(stored.listens, 0);
+    assert!(stored.preferences.favorite_genres.is_empty());
+
+    insert_listens(&pool, user_id, song_id, 1).await;
+    assert_eq!(preferences.refresh_due(100).await.unwrap(), 1);
+    let stored = preferences.find(user_id).await.unwrap().expect("User exists");
+    assert_eq!(stored.listens, 10);
+    assert!(stored.updated_at.is_some());
+    assert_eq!(stored.preferences.favorite_genres[0].0, Genre::new("jazz".to_string()).unwrap());
+    assert_eq!(stored.preferences.favorite_moods[0].0, SongMood::Calm);
+    assert_eq!(stored.preferences.preferred_tempo_bpm, Some(96));
+    assert_eq!(stored.preferences.preferred_quality, Some(AudioQuality::High));
+    assert_eq!(stored.preferences.active_hours, vec![21]);
+
+    // Ya incluidas: no se vuelven a aplicar
+    assert_eq!(preferences.refresh_due(100).await.unwrap(), 0);
+    assert!(preferences.find(Uuid::new_v4()).await.unwrap().is_none());
+}
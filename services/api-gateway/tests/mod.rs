@@ -45,6 +45,12 @@ pub mod fan_ventures_integration_test;
 pub mod fan_ventures_handlers_tests;
 
 use helpers::TestClient;
+use api_gateway::bounded_contexts::user::domain::UserRole;
+use api_gateway::shared::domain::errors::AppError;
+use api_gateway::shared::infrastructure::auth::{
+    authorize_owner, authorize_role, AuthenticatedUser, JwtService, OwnedResource,
+};
+use axum::response::IntoResponse;
 use serde_json::{json, Value};
 use uuid::Uuid;
 
@@ -552,15 +558,30 @@ async fn test_api_response_consistency() {
         if json["success"] == true {
             assert!(json["data"].is_object() || json["data"].is_array(), "Successful response should have 'data' field");
         } else {
-            assert!(json["error"].is_string(), "Error response should have 'error' field");
+            assert_error_envelope(&json);
         }
         
         println!("✅ {}: Response structure consistent", endpoint);
     }
     
+    // AppError bodies share the same envelope
+    let not_found = api_gateway::shared::domain::errors::AppError::NotFound("Song not found".to_string());
+    let json = not_found.response_body();
+    assert_eq!(json["success"], false);
+    assert!(json["timestamp"].is_string(), "Error response should have 'timestamp' field");
+    assert_error_envelope(&json);
+    assert_eq!(json["error"]["code"], "NOT_FOUND");
+    
     println!("🔄 API consistency tests passed");
 }
 
+fn assert_error_envelope(json: &Value) {
+    assert!(json["error"].is_object(), "Error response should have an 'error' object");
+    assert!(json["error"]["code"].is_string(), "Error should have a machine-readable 'code'");
+    assert!(json["error"]["message"].is_string(), "Error should have a 'message'");
+    assert!(json["error"]["details"].is_array(), "Error should have a 'details' array");
+}
+
 // =============================================================================
 // SECURITY TESTING
 // =============================================================================
@@ -594,6 +615,38 @@ async fn test_security_measures() {
         println!("✅ {}: Properly protected", endpoint);
     }
     
+    // An authenticated fan still cannot create a song for another artist
+    let jwt_service = JwtService::new("test_secret").unwrap();
+    let fan_id = Uuid::new_v4();
+    let other_artist_id = Uuid::new_v4();
+    let token = jwt_service
+        .generate_access_token(fan_id, "fan", "fan@example.com", "user", "free")
+        .unwrap();
+    let parts = axum::http::Request::builder()
+        .uri("/api/v1/music/songs")
+        .header("authorization", format!("Bearer {}", token))
+        .body(())
+        .unwrap()
+        .into_parts()
+        .0;
+    let fan = AuthenticatedUser::from_parts_with(&parts, &jwt_service).unwrap();
+    assert_eq!(fan.user_id, fan_id);
+    
+    let role_check = authorize_role(&fan, &[UserRole::Artist]).unwrap_err();
+    assert_eq!(role_check.into_response().status(), axum::http::StatusCode::FORBIDDEN);
+    
+    let ownership_check = authorize_owner(&fan, OwnedResource::Song, other_artist_id).unwrap_err();
+    assert!(matches!(ownership_check, AppError::Forbidden(_)));
+    assert_eq!(ownership_check.into_response().status(), axum::http::StatusCode::FORBIDDEN);
+    
+    // Even with the artist role, only for their own artist_id
+    let artist = AuthenticatedUser { role: "artist".to_string(), ..fan };
+    assert!(authorize_role(&artist, &[UserRole::Artist]).is_ok());
+    assert!(authorize_owner(&artist, OwnedResource::Song, other_artist_id).is_err());
+    assert!(authorize_owner(&artist, OwnedResource::Song, fan_id).is_ok());
+    
+    println!("✅ Fan cannot create songs for another artist");
+    
     println!("🔒 Security tests passed");
 }
 
@@ -0,0 +1,171 @@
+// =============================================================================
+// NOTIFICATION EMAIL DIGEST INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// Cola de resúmenes: solo salen los periodos cerrados, un email por usuario y
+// periodo, y la baja por enlace firmado corta los envíos siguientes.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use api_gateway::bounded_contexts::notifications::application::{
+    EmailDigestService, EmailMessage, EmailNotificationSender, EmailSender, EmailUnsubscribeService,
+    NotificationDispatcher,
+};
+use api_gateway::bounded_contexts::notifications::domain::entities::{
+    ChannelPreferences, DigestEntry, DigestFrequency, Notification, NotificationCategory, NotificationChannel,
+    NotificationPreferences, NotificationPriority, NotificationType,
+};
+use api_gateway::bounded_contexts::notifications::domain::repositories::{
+    DigestRepository, NotificationPreferencesRepository,
+};
+use api_gateway::bounded_contexts::notifications::infrastructure::{
+    PostgresDigestRepository, PostgresEmailRecipientDirectory, PostgresNotificationPreferencesRepository,
+    PostgresNotificationRepository,
+};
+use api_gateway::shared::infrastructure::auth::{LinkSignature, SignedLinkService};
+use async_trait::async_trait;
+use chrono::{TimeZone, Utc};
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::{insert_user, setup_pool};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+const PUBLIC_URL: &str = "https://api.vibestream.test";
+
+#[derive(Default)]
+struct RecordingEmailSender {
+    sent: Mutex<Vec<EmailMessage>>,
+}
+
+#[async_trait]
+impl EmailSender for RecordingEmailSender {
+    async fn send(&self, message: &EmailMessage) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self.sent.lock().unwrap().push(message.clone());
+        Ok(())
+    }
+}
+
+fn email_sender(pool: &PgPool, transport: Arc<RecordingEmailSender>) -> Arc<EmailNotificationSender> {
+    Arc::new(EmailNotificationSender::new(
+        transport,
+        Arc::new(PostgresEmailRecipientDirectory::new(pool.clone())),
+        Arc::new(SignedLinkService::new("test-secret")),
+        PUBLIC_URL.to_string(),
+    ))
+}
+
+fn reward(user_id: Uuid, message: &str) -> Notification {
+    Notification::new(
+        user_id,
+        "Recompensa".to_string(),
+        message.to_string(),
+        NotificationType::RewardEarned,
+        NotificationPriority::Normal,
+        None,
+    )
+}
+
+async fn rewards_by_email(pool: &PgPool, user_id: Uuid, digest: bool) -> Arc<PostgresNotificationPreferencesRepository> {
+    let repository = Arc::new(PostgresNotificationPreferencesRepository::new(pool.clone()));
+    let mut preferences = NotificationPreferences::new(user_id);
+    preferences.categories = HashMap::from([(NotificationCategory::Rewards, ChannelPreferences::ALL)]);
+    if digest {
+        preferences.digest_categories.insert(NotificationCategory::Rewards);
+        preferences.digest_frequency = DigestFrequency::Daily;
+    }
+    repository.create(&preferences).await.unwrap();
+    repository
+}
+
+#[tokio::test]
+async fn test_daily_digest_groups_closed_day_and_keeps_the_next() {
+    let (_setup, pool) = setup_pool().await;
+    let user_id = insert_user(&pool, "digest_fan").await;
+    let preferences = rewards_by_email(&pool, user_id, true).await;
+    let stored = preferences.get_by_user_id(user_id).await.unwrap().unwrap();
+    assert!(stored.digest_categories.contains(&NotificationCategory::Rewards));
+    assert_eq!(stored.digest_frequency, DigestFrequency::Daily);
+
+    let digests = Arc::new(PostgresDigestRepository::new(pool.clone()));
+    let transport = Arc::new(RecordingEmailSender::default());
+    let email = email_sender(&pool, transport.clone());
+    let dispatcher = NotificationDispatcher::new(
+        Arc::new(PostgresNotificationRepository::new(pool.clone())),
+        preferences.clone(),
+    )
+    .with_sender(email.clone())
+    .with_digest_queue(digests.clone());
+
+    // 14 de abril de 2026: dos avisos al final del día y uno justo al empezar el 15
+    let mut notifications = Vec::new();
+    for (message, at) in [
+        ("Primera recompensa", Utc.with_ymd_and_hms(2026, 4, 14, 18, 0, 0).unwrap()),
+        ("Segunda recompensa", Utc.with_ymd_and_hms(2026, 4, 14, 23, 59, 59).unwrap()),
+        ("Recompensa del día siguiente", Utc.with_ymd_and_hms(2026, 4, 15, 0, 0, 0).unwrap()),
+    ] {
+        let mut notification = reward(user_id, message);
+        notification.created_at = at;
+        let delivered = dispatcher.dispatch(&notification).await.unwrap();
+        assert_eq!(delivered, vec![NotificationChannel::InApp, NotificationChannel::Email]);
+        notifications.push(notification);
+    }
+    // Nada sale al momento
+    assert!(transport.sent.lock().unwrap().is_empty());
+    // Encolar dos veces la misma notificación no la duplica
+    let duplicate = DigestEntry::for_notification(&notifications[0], DigestFrequency::Daily).unwrap();
+    digests.enqueue(&duplicate).await.unwrap();
+
+    let service = EmailDigestService::new(digests.clone(), preferences, email);
+    let midnight = Utc.with_ymd_and_hms(2026, 4, 15, 0, 0, 0).unwrap();
+    assert_eq!(service.send_due_digests(midnight - chrono::Duration::seconds(1)).await.unwrap(), 0);
+    assert_eq!(service.send_due_digests(midnight).await.unwrap(), 1);
+
+    let sent = transport.sent.lock().unwrap().clone();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].to_email, "digest_fan@example.com");
+    assert_eq!(sent[0].subject, "Tu resumen diario de VibeStream");
+    assert!(sent[0].text.contains("Recompensas (2)"));
+    assert!(sent[0].text.contains("Segunda recompensa"));
+    assert!(!sent[0].text.contains("Recompensa del día siguiente"));
+
+    // Lo enviado no vuelve a salir; el día 15 espera a su cierre
+    assert_eq!(service.send_due_digests(midnight).await.unwrap(), 0);
+    let remaining = digests.claim_due(Utc.with_ymd_and_hms(2026, 4, 16, 0, 0, 0).unwrap()).await.unwrap();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].notification_id, notifications[2].id);
+}
+
+#[tokio::test]
+async fn test_unsubscribe_link_stops_future_emails() {
+    let (_setup, pool) = setup_pool().await;
+    let user_id = insert_user(&pool, "leaving_fan").await;
+    let preferences = rewards_by_email(&pool, user_id, false).await;
+
+    let transport = Arc::new(RecordingEmailSender::default());
+    let dispatcher = NotificationDispatcher::new(
+        Arc::new(PostgresNotificationRepository::new(pool.clone())),
+        preferences.clone(),
+    )
+    .with_sender(email_sender(&pool, transport.clone()));
+
+    dispatcher.dispatch(&reward(user_id, "Has ganado 5 VIBES")).await.unwrap();
+    let url = transport.sent.lock().unwrap()[0].unsubscribe_url.clone();
+    let (path, query) = url.trim_start_matches(PUBLIC_URL).split_once('?').unwrap();
+    assert_eq!(path, format!("/unsubscribe/{}/rewards", user_id));
+    let params: HashMap<&str, &str> = query.split('&').filter_map(|pair| pair.split_once('=')).collect();
+    let link = LinkSignature { expires: params["expires"].parse().unwrap(), signature: params["signature"].to_string() };
+
+    let unsubscribe = EmailUnsubscribeService::new(SignedLinkService::new("test-secret"), preferences.clone());
+    // Firmado con otro secreto no vale
+    let forged = EmailUnsubscribeService::new(SignedLinkService::new("other-secret"), preferences.clone());
+    assert!(forged.unsubscribe(user_id, "rewards", &link, Utc::now()).await.is_err());
+
+    let updated = unsubscribe.unsubscribe(user_id, "rewards", &link, Utc::now()).await.unwrap();
+    assert!(!updated.channels_for(&NotificationType::RewardEarned).email);
+    assert!(updated.channels_for(&NotificationType::RewardEarned).push);
+
+    let delivered = dispatcher.dispatch(&reward(user_id, "Has ganado 10 VIBES")).await.unwrap();
+    assert_eq!(delivered, vec![NotificationChannel::InApp]);
+    assert_eq!(transport.sent.lock().unwrap().len(), 1);
+}
@@ -0,0 +1,123 @@
+// =============================================================================
+// NOTIFICATION PUSH INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// Registro de tokens sin duplicados y entrega push por el dispatcher con un
+// proveedor simulado: los tokens que rechaza como inválidos desaparecen.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use api_gateway::bounded_contexts::notifications::application::{
+    NotificationDispatcher, PushError, PushMessage, PushNotificationSender, PushSender,
+};
+use api_gateway::bounded_contexts::notifications::domain::entities::{
+    ChannelPreferences, DevicePlatform, DeviceToken, Notification, NotificationCategory, NotificationChannel,
+    NotificationPreferences, NotificationPriority, NotificationType,
+};
+use api_gateway::bounded_contexts::notifications::domain::repositories::{
+    DeviceTokenRepository, NotificationPreferencesRepository,
+};
+use api_gateway::bounded_contexts::notifications::infrastructure::{
+    PostgresDeviceTokenRepository, PostgresNotificationPreferencesRepository, PostgresNotificationRepository,
+};
+use async_trait::async_trait;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::{insert_user, setup_pool};
+use uuid::Uuid;
+
+/// FCM simulado: los tokens `stale-*` ya no existen
+#[derive(Default)]
+struct FakeFcm {
+    sent: Mutex<Vec<PushMessage>>,
+}
+
+#[async_trait]
+impl PushSender for FakeFcm {
+    fn platform(&self) -> DevicePlatform {
+        DevicePlatform::Android
+    }
+
+    async fn send(&self, message: &PushMessage) -> Result<(), PushError> {
+        self.sent.lock().unwrap().push(message.clone());
+        if message.token.starts_with("stale") {
+            Err(PushError::InvalidToken)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_same_token_registered_twice_belongs_to_last_user() {
+    let (_setup, pool) = setup_pool().await;
+    let devices = PostgresDeviceTokenRepository::new(pool.clone());
+    let (first, second) = (Uuid::new_v4(), Uuid::new_v4());
+
+    let registered = devices.register(&DeviceToken::new(first, DevicePlatform::Ios, "shared-phone".to_string())).await.unwrap();
+    let again = devices.register(&DeviceToken::new(first, DevicePlatform::Ios, "shared-phone".to_string())).await.unwrap();
+    assert_eq!(again.id, registered.id);
+    assert_eq!(devices.find_by_user(first).await.unwrap().len(), 1);
+
+    // Otra cuenta en el mismo móvil se queda con el token
+    devices.register(&DeviceToken::new(second, DevicePlatform::Ios, "shared-phone".to_string())).await.unwrap();
+    assert!(devices.find_by_user(first).await.unwrap().is_empty());
+    assert_eq!(devices.find_by_user(second).await.unwrap()[0].token, "shared-phone");
+}
+
+#[tokio::test]
+async fn test_push_reaches_every_device_and_prunes_invalid_tokens() {
+    let (_setup, pool) = setup_pool().await;
+    let user_id = insert_user(&pool, "push_fan").await;
+    let devices = Arc::new(PostgresDeviceTokenRepository::new(pool.clone()));
+    for token in ["phone-token", "tablet-token", "stale-token"] {
+        devices.register(&DeviceToken::new(user_id, DevicePlatform::Android, token.to_string())).await.unwrap();
+    }
+
+    let preferences_repository = Arc::new(PostgresNotificationPreferencesRepository::new(pool.clone()));
+    let mut preferences = NotificationPreferences::new(user_id);
+    preferences.categories = HashMap::from([(NotificationCategory::Rewards, ChannelPreferences::ALL)]);
+    preferences_repository.create(&preferences).await.unwrap();
+
+    let fcm = Arc::new(FakeFcm::default());
+    let push = PushNotificationSender::new(devices.clone())
+        .with_provider(fcm.clone())
+        .with_retry_backoff(Duration::ZERO);
+    let dispatcher = NotificationDispatcher::new(
+        Arc::new(PostgresNotificationRepository::new(pool.clone())),
+        preferences_repository,
+    )
+    .with_sender(Arc::new(push));
+
+    let reward = Notification::new(
+        user_id,
+        "Recompensa".to_string(),
+        "Has ganado 5 VIBES".to_string(),
+        NotificationType::RewardEarned,
+        NotificationPriority::Normal,
+        None,
+    );
+    let delivered = dispatcher.dispatch(&reward).await.unwrap();
+    assert_eq!(delivered, vec![NotificationChannel::InApp, NotificationChannel::Push]);
+
+    let sent = fcm.sent.lock().unwrap().clone();
+    assert_eq!(sent.len(), 3);
+    assert!(sent.iter().all(|m| m.collapse_key.as_deref() == Some("rewards")));
+
+    let mut remaining: Vec<String> = devices.find_by_user(user_id).await.unwrap().into_iter().map(|d| d.token).collect();
+    remaining.sort();
+    assert_eq!(remaining, vec!["phone-token", "tablet-token"]);
+
+    // Sin push en la categoría no se envía nada
+    let security = Notification::new(
+        user_id,
+        "Nuevo inicio de sesión".to_string(),
+        "Desde otro dispositivo".to_string(),
+        NotificationType::SecurityAlert,
+        NotificationPriority::High,
+        None,
+    );
+    assert_eq!(dispatcher.dispatch(&security).await.unwrap(), vec![NotificationChannel::InApp]);
+    assert_eq!(fcm.sent.lock().unwrap().len(), 3);
+}
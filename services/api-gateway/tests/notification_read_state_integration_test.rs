@@ -0,0 +1,87 @@
+// =============================================================================
+// NOTIFICATION READ STATE & PREFERENCES INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// Las entregas pasan por el dispatcher con las preferencias guardadas, y marcar
+// como leída una notificación ya leída no toca su `read_at`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use api_gateway::bounded_contexts::notifications::application::NotificationDispatcher;
+use api_gateway::bounded_contexts::notifications::domain::entities::{
+    ChannelPreferences, Notification, NotificationCategory, NotificationChannel, NotificationFilters,
+    NotificationPreferences, NotificationPriority, NotificationType,
+};
+use api_gateway::bounded_contexts::notifications::domain::repositories::{
+    NotificationPreferencesRepository, NotificationRepository,
+};
+use api_gateway::bounded_contexts::notifications::infrastructure::{
+    PostgresNotificationPreferencesRepository, PostgresNotificationRepository,
+};
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::{insert_user, setup_pool};
+use uuid::Uuid;
+
+fn notification(user_id: Uuid, notification_type: NotificationType) -> Notification {
+    Notification::new(
+        user_id,
+        "Aviso".to_string(),
+        "Mensaje".to_string(),
+        notification_type,
+        NotificationPriority::Normal,
+        Some(serde_json::json!({ "source": "integration_test" })),
+    )
+}
+
+fn unread_of(user_id: Uuid) -> NotificationFilters {
+    NotificationFilters {
+        user_id: Some(user_id),
+        notification_type: None,
+        status: None,
+        read: Some(false),
+        limit: None,
+        offset: None,
+    }
+}
+
+#[tokio::test]
+async fn test_preferences_filter_delivery_and_read_marking_is_idempotent() {
+    let (_setup, pool) = setup_pool().await;
+
+    let user_id = insert_user(&pool, "notified_fan").await;
+    let notifications = Arc::new(PostgresNotificationRepository::new(pool.clone()));
+    let preferences = Arc::new(PostgresNotificationPreferencesRepository::new(pool.clone()));
+
+    let mut stored = NotificationPreferences::new(user_id);
+    stored.categories = HashMap::from([
+        (NotificationCategory::Marketing, ChannelPreferences { in_app: false, email: true, push: false }),
+    ]);
+    preferences.create(&stored).await.unwrap();
+    let loaded = preferences.get_by_user_id(user_id).await.unwrap().expect("Preferences stored");
+    assert_eq!(loaded.categories, stored.categories);
+
+    let dispatcher = NotificationDispatcher::new(notifications.clone(), preferences.clone());
+    let marketing = notification(user_id, NotificationType::Marketing);
+    assert!(dispatcher.dispatch(&marketing).await.unwrap().is_empty());
+    let reward = notification(user_id, NotificationType::RewardEarned);
+    assert_eq!(dispatcher.dispatch(&reward).await.unwrap(), vec![NotificationChannel::InApp]);
+    let custom = notification(user_id, NotificationType::Custom("artist_shoutout".to_string()));
+    dispatcher.dispatch(&custom).await.unwrap();
+
+    assert!(notifications.get_by_id(marketing.id).await.unwrap().is_none());
+    let unread = notifications.search(&unread_of(user_id), 0, 20).await.unwrap();
+    assert_eq!(unread.len(), 2);
+    assert_eq!(unread[0].metadata.as_ref().unwrap()["source"], "integration_test");
+
+    notifications.mark_as_read(reward.id).await.unwrap();
+    let first_read_at = notifications.get_by_id(reward.id).await.unwrap().unwrap().read_at.expect("Marked as read");
+    notifications.mark_as_read(reward.id).await.unwrap();
+    assert_eq!(notifications.get_by_id(reward.id).await.unwrap().unwrap().read_at, Some(first_read_at));
+    assert_eq!(notifications.get_unread_count(user_id).await.unwrap(), 1);
+
+    notifications.mark_all_as_read(user_id).await.unwrap();
+    notifications.mark_all_as_read(user_id).await.unwrap();
+    assert!(notifications.search(&unread_of(user_id), 0, 20).await.unwrap().is_empty());
+    assert_eq!(notifications.get_by_id(reward.id).await.unwrap().unwrap().read_at, Some(first_read_at));
+}
@@ -0,0 +1,126 @@
+// =============================================================================
+// NOTIFICATION WEBSOCKET INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// Dos dispositivos del mismo usuario (token en la URL y en el primer mensaje)
+// reciben el contador de no leídas al conectar y la notificación que guarda el
+// dispatcher.
+
+use std::sync::Arc;
+use std::time::Duration;
+use api_gateway::bounded_contexts::notifications::application::{LiveMessage, NotificationDispatcher, NotificationHub};
+use api_gateway::bounded_contexts::notifications::domain::entities::{
+    Notification, NotificationPriority, NotificationType,
+};
+use api_gateway::bounded_contexts::notifications::domain::repositories::NotificationRepository;
+use api_gateway::bounded_contexts::notifications::infrastructure::{
+    PostgresNotificationPreferencesRepository, PostgresNotificationRepository,
+};
+use api_gateway::bounded_contexts::notifications::presentation::live_ws::{self, LiveNotificationState};
+use api_gateway::shared::infrastructure::auth::JwtService;
+use futures_util::{SinkExt, StreamExt};
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::{insert_user, setup_pool};
+use tokio_tungstenite::tungstenite;
+use uuid::Uuid;
+
+fn notification(user_id: Uuid, title: &str) -> Notification {
+    Notification::new(
+        user_id,
+        title.to_string(),
+        "Mensaje".to_string(),
+        NotificationType::RewardEarned,
+        NotificationPriority::Normal,
+        None,
+    )
+}
+
+async fn next_message<S>(socket: &mut S) -> LiveMessage
+where
+    S: StreamExt<Item = Result<tungstenite::Message, tungstenite::Error>> + Unpin,
+{
+    loop {
+        let message = tokio::time::timeout(Duration::from_secs(5), socket.next())
+            .await
+            .expect("timed out waiting for live message")
+            .unwrap()
+            .unwrap();
+        if let tungstenite::Message::Text(text) = message {
+            return serde_json::from_str(&text).unwrap();
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_every_device_receives_dispatched_notification() {
+    let (_setup, pool) = setup_pool().await;
+
+    let user_id = insert_user(&pool, "live_fan").await;
+    let notifications = Arc::new(PostgresNotificationRepository::new(pool.clone()));
+    notifications.create(&notification(user_id, "Pendiente")).await.unwrap();
+
+    let hub = Arc::new(NotificationHub::new());
+    let jwt_service = Arc::new(JwtService::new("live-notifications-test-secret").unwrap());
+    let dispatcher = NotificationDispatcher::new(
+        notifications.clone(),
+        Arc::new(PostgresNotificationPreferencesRepository::new(pool.clone())),
+    )
+    .with_live_hub(hub.clone());
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let router = live_ws::routes(LiveNotificationState {
+        hub: hub.clone(),
+        notifications: notifications.clone(),
+        jwt_service: jwt_service.clone(),
+    });
+    tokio::spawn(async move {
+        axum::serve(listener, router).await.unwrap();
+    });
+
+    let token = jwt_service
+        .generate_access_token(user_id, "live_fan", "live_fan@example.com", "user", "free")
+        .unwrap();
+    let url = format!("ws://{}/ws/notifications", addr);
+
+    let (mut phone, _) = tokio_tungstenite::connect_async(format!("{}?token={}", url, token)).await.unwrap();
+    let (mut laptop, _) = tokio_tungstenite::connect_async(url.as_str()).await.unwrap();
+    let authenticate = serde_json::json!({"action": "authenticate", "token": token});
+    laptop.send(tungstenite::Message::Text(authenticate.to_string())).await.unwrap();
+
+    assert_eq!(next_message(&mut phone).await, LiveMessage::UnreadCount { count: 1 });
+    assert_eq!(next_message(&mut laptop).await, LiveMessage::UnreadCount { count: 1 });
+    assert_eq!(hub.connection_count(user_id).await, 2);
+
+    let sent = notification(user_id, "En directo");
+    dispatcher.dispatch(&sent).await.unwrap();
+
+    for socket in [&mut phone, &mut laptop] {
+        match next_message(socket).await {
+            LiveMessage::Notification { notification } => {
+                assert_eq!(notification.id, sent.id);
+                assert_eq!(notification.title, "En directo");
+                assert_eq!(notification.notification_type, "reward_earned");
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+    }
+    assert_eq!(notifications.get_unread_count(user_id).await.unwrap(), 2);
+
+    // Sin token válido no hay registro
+    let (mut intruder, _) = tokio_tungstenite::connect_async(url.as_str()).await.unwrap();
+    let forged = serde_json::json!({"action": "authenticate", "token": "not-a-jwt"});
+    intruder.send(tungstenite::Message::Text(forged.to_string())).await.unwrap();
+    assert!(matches!(next_message(&mut intruder).await, LiveMessage::Error { .. }));
+    assert_eq!(hub.connection_count(user_id).await, 2);
+
+    phone.close(None).await.unwrap();
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while hub.connection_count(user_id).await != 1 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+    })
+    .await
+    .expect("closed device is unregistered");
+}
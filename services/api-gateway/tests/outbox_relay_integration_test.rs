@@ -0,0 +1,367 @@
+// =============================================================================
+// OUTBOX RELAY INTEGRATION TESTS
+// =============================================================================
+//
+// Los eventos se guardan con el cambio del agregado y el relay los publica al
+// menos una vez: si muere a mitad de lote nada se pierde, lo ya publicado se
+// reenvía con el mismo id y el consumidor lo descarta.
+
+use api_gateway::bounded_contexts::fan_ventures::domain::entities::InvestmentStatus;
+use api_gateway::bounded_contexts::fan_ventures::domain::escrow::EscrowedShare;
+use api_gateway::bounded_contexts::fan_ventures::domain::repositories::ShareEscrowRepository;
+use api_gateway::bounded_contexts::fan_ventures::infrastructure::{PostgresShareEscrowRepository, FAN_VENTURES_OUTBOX_CONTEXT};
+use api_gateway::bounded_contexts::listen_reward::domain::aggregates::{RewardDistribution, RewardPool};
+use api_gateway::bounded_contexts::listen_reward::domain::value_objects::{ListenSessionId, RewardAmount, ValidationPeriod};
+use api_gateway::bounded_contexts::listen_reward::infrastructure::event_publishers::{EventPublishResult, EventPublisher};
+use api_gateway::bounded_contexts::listen_reward::infrastructure::repositories::{
+    PostgresRewardDistributionRepository, LISTEN_REWARD_OUTBOX_CONTEXT,
+};
+use api_gateway::bounded_contexts::listen_reward::infrastructure::EventPublisherOutboxAdapter;
+use api_gateway::bounded_contexts::orchestrator::{DomainEvent, EventBus, EventHandler, InMemoryEventBus};
+use api_gateway::shared::domain::errors::AppError;
+use api_gateway::shared::domain::events::DomainEvent as MetadataDomainEvent;
+use api_gateway::shared::infrastructure::outbox::{
+    enqueue, EventBusOutboxPublisher, OutboxMessage, OutboxPublisher, OutboxRelay,
+};
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::{insert_user, setup_pool};
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::PgPool;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
+use uuid::Uuid;
+
+/// `count` eventos de `context` guardados en una transacción, en orden de escritura
+async fn enqueue_events(pool: &PgPool, context: &str, count: usize) -> Vec<Uuid> {
+    let mut tx = pool.begin().await.unwrap();
+    let mut ids = Vec::with_capacity(count);
+    for i in 0..count {
+        let event = DomainEvent::VentureFunded {
+            venture_id: Uuid::new_v4(),
+            artist_id: Uuid::new_v4(),
+            total_funding: i as f64,
+            occurred_at: Utc::now(),
+        };
+        let message = OutboxMessage::from_event(context, "Venture", Uuid::new_v4(), &event).unwrap();
+        enqueue(&mut tx, &message).await.unwrap();
+        ids.push(message.id);
+    }
+    tx.commit().await.unwrap();
+    ids
+}
+
+async fn count_with_status(pool: &PgPool, context: &str, status: &str) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM outbox_events WHERE context = $1 AND status = $2")
+        .bind(context)
+        .bind(status)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+/// Consumidor idempotente: apunta cada entrega y aplica cada id una sola vez
+#[derive(Default)]
+struct DedupingConsumer {
+    deliveries: Mutex<Vec<Uuid>>,
+    seen: Mutex<HashSet<Uuid>>,
+    applied: Mutex<Vec<Uuid>>,
+}
+
+#[async_trait]
+impl OutboxPublisher for DedupingConsumer {
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), AppError> {
+        self.deliveries.lock().unwrap().push(message.id);
+        if self.seen.lock().unwrap().insert(message.id) {
+            self.applied.lock().unwrap().push(message.id);
+        }
+        Ok(())
+    }
+}
+
+/// Entrega `healthy_publishes` eventos y se queda colgado en el siguiente
+struct HangingPublisher {
+    consumer: Arc<DedupingConsumer>,
+    healthy_publishes: usize,
+    published: AtomicUsize,
+    stuck: Arc<Notify>,
+}
+
+#[async_trait]
+impl OutboxPublisher for HangingPublisher {
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), AppError> {
+        if self.published.fetch_add(1, Ordering::SeqCst) == self.healthy_publishes {
+            self.stuck.notify_one();
+            std::future::pending::<()>().await;
+        }
+        self.consumer.publish(message).await
+    }
+}
+
+/// Vaciar el outbox; las filas del relay muerto quedan bloqueadas hasta que su
+/// conexión hace rollback
+async fn drain(relay: &OutboxRelay, pool: &PgPool, context: &str) {
+    for _ in 0..100 {
+        relay.relay_pending().await.expect("Relay pass");
+        if count_with_status(pool, context, "pending").await == 0 {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("outbox {} was not drained", context);
+}
+
+#[tokio::test]
+async fn test_relay_killed_mid_batch_loses_nothing_and_redeliveries_are_deduplicated() {
+    let (_setup, pool) = setup_pool().await;
+    let context = "outbox_kill_test";
+    let ids = enqueue_events(&pool, context, 20).await;
+    let consumer = Arc::new(DedupingConsumer::default());
+
+    // El primer relay publica 7 eventos del lote y muere antes de confirmarlo
+    let stuck = Arc::new(Notify::new());
+    let hanging = Arc::new(HangingPublisher {
+        consumer: consumer.clone(),
+        healthy_publishes: 7,
+        published: AtomicUsize::new(0),
+        stuck: stuck.clone(),
+    });
+    let doomed = OutboxRelay::new(pool.clone(), context, hanging).with_batch_size(20);
+    let task = tokio::spawn(async move { doomed.relay_batch().await });
+    tokio::time::timeout(Duration::from_secs(10), stuck.notified()).await.expect("Relay reached the 8th event");
+    task.abort();
+    assert!(task.await.unwrap_err().is_cancelled());
+
+    assert_eq!(consumer.deliveries.lock().unwrap().len(), 7);
+    assert_eq!(count_with_status(&pool, context, "dispatched").await, 0);
+    assert_eq!(count_with_status(&pool, context, "pending").await, 20);
+
+    // Otro relay retoma el lote entero
+    let relay = OutboxRelay::new(pool.clone(), context, consumer.clone()).with_batch_size(20);
+    drain(&relay, &pool, context).await;
+
+    // Ninguno se perdió; solo se reenviaron los 7 ya publicados, con su id
+    let deliveries = consumer.deliveries.lock().unwrap().clone();
+    assert_eq!(deliveries.len(), 27);
+    assert_eq!(deliveries.iter().collect::<HashSet<_>>(), ids.iter().collect::<HashSet<_>>());
+    assert_eq!(&deliveries[7..14], &ids[..7]);
+    // El consumidor aplica cada evento una vez y en orden de escritura
+    assert_eq!(*consumer.applied.lock().unwrap(), ids);
+    assert_eq!(count_with_status(&pool, context, "dispatched").await, 20);
+
+    // Lo enviado no se vuelve a publicar
+    assert_eq!(relay.relay_pending().await.unwrap().total(), 0);
+    assert_eq!(consumer.deliveries.lock().unwrap().len(), 27);
+}
+
+#[tokio::test]
+async fn test_concurrent_relays_publish_each_event_once() {
+    let (_setup, pool) = setup_pool().await;
+    let context = "outbox_concurrency_test";
+    let ids = enqueue_events(&pool, context, 60).await;
+    let consumer = Arc::new(DedupingConsumer::default());
+
+    let mut tasks = Vec::new();
+    for _ in 0..4 {
+        let relay = OutboxRelay::new(pool.clone(), context, consumer.clone()).with_batch_size(5);
+        tasks.push(tokio::spawn(async move { relay.relay_pending().await }));
+    }
+    for task in tasks {
+        task.await.unwrap().expect("Relay pass");
+    }
+    let relay = OutboxRelay::new(pool.clone(), context, consumer.clone());
+    drain(&relay, &pool, context).await;
+
+    // SKIP LOCKED reparte las filas: ninguna entrega repetida
+    let deliveries = consumer.deliveries.lock().unwrap().clone();
+    assert_eq!(deliveries.len(), 60);
+    assert_eq!(deliveries.into_iter().collect::<HashSet<_>>(), ids.into_iter().collect::<HashSet<_>>());
+}
+
+/// Rechaza siempre el evento `poisoned`
+struct PoisonedPublisher {
+    poisoned: Uuid,
+    consumer: DedupingConsumer,
+}
+
+#[async_trait]
+impl OutboxPublisher for PoisonedPublisher {
+    async fn publish(&self, message: &OutboxMessage) -> Result<(), AppError> {
+        if message.id == self.poisoned {
+            return Err(AppError::ExternalServiceError("stream unavailable".to_string()));
+        }
+        self.consumer.publish(message).await
+    }
+}
+
+#[tokio::test]
+async fn test_failing_event_is_retried_then_dead_lettered() {
+    let (_setup, pool) = setup_pool().await;
+    let context = "outbox_dead_letter_test";
+    let ids = enqueue_events(&pool, context, 3).await;
+    let publisher = Arc::new(PoisonedPublisher { poisoned: ids[1], consumer: DedupingConsumer::default() });
+    let relay = OutboxRelay::new(pool.clone(), context, publisher.clone())
+        .with_max_attempts(3)
+        .with_retry_base_delay(Duration::ZERO);
+
+    let first = relay.relay_batch().await.unwrap();
+    assert_eq!((first.dispatched, first.retried, first.dead_lettered), (2, 1, 0));
+    relay.relay_batch().await.unwrap();
+    let last = relay.relay_batch().await.unwrap();
+    assert_eq!((last.dispatched, last.retried, last.dead_lettered), (0, 0, 1));
+
+    let (status, attempts, last_error): (String, i32, Option<String>) =
+        sqlx::query_as("SELECT status, attempts, last_error FROM outbox_events WHERE id = $1")
+            .bind(ids[1])
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(status, "dead_letter");
+    assert_eq!(attempts, 3);
+    assert!(last_error.unwrap().contains("stream unavailable"));
+    assert_eq!(*publisher.consumer.applied.lock().unwrap(), vec![ids[0], ids[2]]);
+    // Un evento en dead letter ya no se reintenta
+    assert_eq!(relay.relay_pending().await.unwrap().total(), 0);
+}
+
+async fn outbox_types_for(pool: &PgPool, aggregate_id: Uuid) -> Vec<String> {
+    sqlx::query_scalar("SELECT event_type FROM outbox_events WHERE aggregate_id = $1 ORDER BY seq")
+        .bind(aggregate_id)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+#[derive(Default)]
+struct RecordingHandler {
+    events: Mutex<Vec<DomainEvent>>,
+}
+
+#[async_trait]
+impl EventHandler for RecordingHandler {
+    async fn handle(&self, event: &DomainEvent) -> Result<(), AppError> {
+        self.events.lock().unwrap().push(event.clone());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn test_share_escrow_events_commit_with_the_escrow_and_reach_the_event_bus() {
+    let (_setup, pool) = setup_pool().await;
+    let artist = insert_user(&pool, "outbox_artist").await;
+    let seller = insert_user(&pool, "outbox_seller").await;
+    let buyer = insert_user(&pool, "outbox_buyer").await;
+    let venture_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO artist_ventures (artist_id, title, funding_goal, min_investment) VALUES ($1, 'Debut EP', 1000, 1) RETURNING id",
+    )
+    .bind(artist)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let holding_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO fan_investments (fan_id, venture_id, investment_amount, investment_type, status) VALUES ($1, $2, 5, 'revenue_share', 'active') RETURNING id",
+    )
+    .bind(seller)
+    .bind(venture_id)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    let repository = PostgresShareEscrowRepository::new(pool.clone());
+    let holding = repository.find_holding(&holding_id).await.unwrap().unwrap();
+    assert_eq!(holding.status, InvestmentStatus::Active);
+
+    let mut escrow = EscrowedShare::new(&holding, buyer, 4.0, 40.0, chrono::Duration::minutes(15), Utc::now()).unwrap();
+    repository.open(&escrow).await.unwrap();
+    escrow.cancel(Utc::now()).unwrap();
+    repository.cancel(&escrow, "Payment failed").await.unwrap();
+    assert_eq!(outbox_types_for(&pool, escrow.escrow_id).await, vec!["SharesEscrowed", "EscrowCancelled"]);
+
+    // Si la transacción falla tampoco queda el evento
+    let mut oversold = holding.clone();
+    oversold.investment_amount = 50.0;
+    let rejected = EscrowedShare::new(&oversold, buyer, 20.0, 200.0, chrono::Duration::minutes(15), Utc::now()).unwrap();
+    assert!(matches!(repository.open(&rejected).await, Err(AppError::DomainRuleViolation(_))));
+    assert!(outbox_types_for(&pool, rejected.escrow_id).await.is_empty());
+
+    let event_bus = Arc::new(InMemoryEventBus::new());
+    let handler = Arc::new(RecordingHandler::default());
+    event_bus.subscribe("SharesEscrowed", handler.clone()).await.unwrap();
+    event_bus.subscribe("EscrowCancelled", handler.clone()).await.unwrap();
+    let relay = OutboxRelay::new(pool.clone(), FAN_VENTURES_OUTBOX_CONTEXT, Arc::new(EventBusOutboxPublisher::new(event_bus)));
+    drain(&relay, &pool, FAN_VENTURES_OUTBOX_CONTEXT).await;
+
+    let events = handler.events.lock().unwrap();
+    assert_eq!(events.iter().map(DomainEvent::event_type).collect::<Vec<_>>(), vec!["SharesEscrowed", "EscrowCancelled"]);
+    assert!(matches!(&events[1], DomainEvent::EscrowCancelled { escrow_id, reason, .. }
+        if *escrow_id == escrow.escrow_id && reason == "Payment failed"));
+}
+
+/// Publicador del contexto que apunta los ids recibidos
+#[derive(Default)]
+struct RecordingEventPublisher {
+    received: Mutex<Vec<(Uuid, String)>>,
+}
+
+#[async_trait]
+impl EventPublisher for RecordingEventPublisher {
+    async fn publish_event(&self, event: Box<dyn MetadataDomainEvent>) -> Result<EventPublishResult, String> {
+        let event_id = event.metadata().event_id;
+        self.received.lock().unwrap().push((event_id, event.event_type().to_string()));
+        Ok(EventPublishResult::success(event_id))
+    }
+
+    async fn publish_events(&self, events: Vec<Box<dyn MetadataDomainEvent>>) -> Vec<Result<EventPublishResult, String>> {
+        let mut results = Vec::new();
+        for event in events {
+            results.push(self.publish_event(event).await);
+        }
+        results
+    }
+
+    async fn is_healthy(&self) -> bool {
+        true
+    }
+}
+
+#[tokio::test]
+async fn test_reward_distribution_events_are_relayed_with_their_event_ids() {
+    let (_setup, pool) = setup_pool().await;
+    let repository = PostgresRewardDistributionRepository::new(pool.clone());
+    let distributions: Vec<RewardDistribution> = (0..3)
+        .map(|_| {
+            RewardDistribution::for_recipient(
+                RewardPool::new(RewardAmount::new(10.0).unwrap(), ValidationPeriod::daily()),
+                ListenSessionId::new(),
+                Uuid::new_v4(),
+                Uuid::new_v4(),
+                RewardAmount::new(1.5).unwrap(),
+                RewardAmount::new(0.25).unwrap(),
+                RewardAmount::new(0.1).unwrap(),
+            )
+        })
+        .collect();
+    repository.save_distribution(&distributions[0]).await.unwrap();
+    repository.save_batch(&distributions[1..]).await.unwrap();
+
+    let expected: HashSet<Uuid> = distributions
+        .iter()
+        .flat_map(|distribution| distribution.get_events().iter().map(|event| event.metadata().event_id))
+        .collect();
+    assert!(!expected.is_empty());
+
+    let publisher = Arc::new(RecordingEventPublisher::default());
+    let relay = OutboxRelay::new(
+        pool.clone(),
+        LISTEN_REWARD_OUTBOX_CONTEXT,
+        Arc::new(EventPublisherOutboxAdapter::new(publisher.clone())),
+    );
+    drain(&relay, &pool, LISTEN_REWARD_OUTBOX_CONTEXT).await;
+
+    let received = publisher.received.lock().unwrap();
+    assert_eq!(received.iter().map(|(id, _)| *id).collect::<HashSet<_>>(), expected);
+    assert!(received.iter().all(|(_, event_type)| event_type == "RewardDistributionCreated"));
+}
@@ -0,0 +1,135 @@
+// =============================================================================
+// PAYMENT IDEMPOTENCY INTEGRATION TESTS
+// =============================================================================
+//
+// Dos peticiones de pago idénticas dentro del mismo minuto comparten clave de
+// idempotencia, y el índice único de `payments.idempotency_key` impide que la
+// segunda cree otro pago.
+
+use api_gateway::bounded_contexts::payment::application::commands::{
+    InitiatePaymentCommand, PaymentMetadataDto, PaymentMethodDto, PaymentPurposeDto,
+};
+use api_gateway::bounded_contexts::payment::domain::{
+    aggregates::PaymentAggregate,
+    repository::PaymentRepository,
+    value_objects::{Amount, Currency, FeePercentage, PaymentMetadata, PaymentMethod, PaymentPurpose},
+};
+use api_gateway::bounded_contexts::payment::infrastructure::repositories::PostgresPaymentRepository;
+use api_gateway::shared::domain::errors::AppError;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::setup_pool;
+use chrono::{TimeZone, Utc};
+use uuid::Uuid;
+
+fn song_purchase_command(payer_id: Uuid, payee_id: Uuid, song_id: Uuid) -> InitiatePaymentCommand {
+    InitiatePaymentCommand {
+        payer_id,
+        payee_id,
+        amount_value: 1.99,
+        amount_currency: Currency::USD,
+        payment_method: PaymentMethodDto {
+            method_type: "PlatformBalance".to_string(),
+            card_details: None,
+            crypto_details: None,
+            bank_details: None,
+        },
+        purpose: PaymentPurposeDto {
+            purpose_type: "SongPurchase".to_string(),
+            campaign_id: None,
+            nft_quantity: None,
+            contract_id: None,
+            ownership_percentage: None,
+            share_id: None,
+            from_user: None,
+            to_user: None,
+            song_id: Some(song_id),
+            artist_id: None,
+            session_id: None,
+            listen_duration: None,
+            distribution_id: None,
+            original_payment_id: None,
+            reason: None,
+        },
+        metadata: PaymentMetadataDto {
+            user_ip: None,
+            user_agent: None,
+            platform_version: "1.0.0".to_string(),
+            reference_id: None,
+            additional_data: serde_json::Value::Null,
+        },
+        idempotency_key: None,
+    }
+}
+
+fn song_purchase(payer_id: Uuid, payee_id: Uuid, song_id: Uuid, idempotency_key: &str) -> PaymentAggregate {
+    PaymentAggregate::create_payment(
+        payer_id,
+        payee_id,
+        Amount::new(1.99, Currency::USD).unwrap(),
+        PaymentMethod::PlatformBalance,
+        PaymentPurpose::SongPurchase { song_id },
+        FeePercentage::new(10.0).unwrap(),
+        PaymentMetadata {
+            user_ip: None,
+            user_agent: None,
+            platform_version: "1.0.0".to_string(),
+            reference_id: None,
+            additional_data: serde_json::json!({}),
+        },
+    )
+    .unwrap()
+    .with_idempotency_key(idempotency_key.to_string())
+}
+
+#[tokio::test]
+async fn test_identical_requests_within_a_minute_get_the_same_payment() {
+    let (_setup, pool) = setup_pool().await;
+    let users: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM users LIMIT 2")
+        .fetch_all(&pool).await.expect("Seeded users");
+    let (fan, artist) = (users[0], users[1]);
+    let song_id = Uuid::new_v4();
+    let repository = PostgresPaymentRepository::new(pool.clone());
+
+    // Doble clic: la misma petición a los 10s y a los 40s del mismo minuto
+    let command = song_purchase_command(fan, artist, song_id);
+    let first_key = command.idempotency_key_at(Utc.with_ymd_and_hms(2026, 10, 15, 12, 0, 10).unwrap());
+    let second_key = command.idempotency_key_at(Utc.with_ymd_and_hms(2026, 10, 15, 12, 0, 40).unwrap());
+    assert_eq!(first_key, second_key);
+
+    let first = song_purchase(fan, artist, song_id, &first_key);
+    repository.save(&first).await.expect("First payment saved");
+    let first_id = *first.payment().id().value();
+
+    let second = song_purchase(fan, artist, song_id, &second_key);
+    match repository.save(&second).await {
+        Err(AppError::DuplicatePayment { existing_id }) => assert_eq!(existing_id, first_id),
+        other => panic!("Expected DuplicatePayment, got {:?}", other.map(|_| ())),
+    }
+
+    assert_eq!(repository.existing_payment_id(&first_key).await.unwrap(), Some(first_id));
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM payments WHERE payer_id = $1 AND purpose_details->>'song_id' = $2")
+        .bind(fan)
+        .bind(song_id.to_string())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, 1);
+}
+
+#[tokio::test]
+async fn test_client_keys_are_not_shared_with_other_payments() {
+    let (_setup, pool) = setup_pool().await;
+    let users: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM users LIMIT 2")
+        .fetch_all(&pool).await.expect("Seeded users");
+    let repository = PostgresPaymentRepository::new(pool.clone());
+
+    let key = format!("order-{}", Uuid::new_v4());
+    repository.save(&song_purchase(users[0], users[1], Uuid::new_v4(), &key)).await.unwrap();
+
+    // Otra clave, otro pago
+    let other_key = format!("order-{}", Uuid::new_v4());
+    repository.save(&song_purchase(users[0], users[1], Uuid::new_v4(), &other_key)).await.unwrap();
+    assert!(repository.existing_payment_id(&other_key).await.unwrap().is_some());
+    assert_eq!(repository.existing_payment_id("order-unknown").await.unwrap(), None);
+}
@@ -0,0 +1,108 @@
+use api_gateway::bounded_contexts::payment::application::{PaymentStatisticsQuery, PaymentStatisticsService};
+use api_gateway::bounded_contexts::payment::infrastructure::repositories::PostgresPaymentStatisticsRepository;
+use api_gateway::shared::domain::errors::AppError;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::setup_pool;
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use std::sync::Arc;
+use uuid::Uuid;
+use sqlx::PgPool;
+
+/// Periodo sin datos sembrados por las migraciones
+fn day(d: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2020, 3, d, 12, 0, 0).unwrap()
+}
+
+async fn seed_payment(
+    pool: &PgPool,
+    payer_id: Uuid,
+    payee_id: Uuid,
+    amount: f64,
+    currency: &str,
+    purpose_type: &str,
+    status: &str,
+    created_at: DateTime<Utc>,
+) {
+    sqlx::query(
+        r#"INSERT INTO payments (
+               payer_id, payee_id, amount_value, amount_currency, net_amount_value, net_amount_currency,
+               payment_method_type, payment_method_details, purpose_type, purpose_details, status, created_at, updated_at
+           ) VALUES ($1, $2, $3, $4, $3, $4, 'CreditCard', '{}', $5, '{}', $6, $7, $7)"#,
+    )
+    .bind(payer_id)
+    .bind(payee_id)
+    .bind(amount)
+    .bind(currency)
+    .bind(purpose_type)
+    .bind(status)
+    .bind(created_at)
+    .execute(pool)
+    .await
+    .expect("Failed to seed payment");
+}
+
+#[tokio::test]
+async fn test_payment_statistics_aggregates() {
+    let (_setup, pool) = setup_pool().await;
+    let users: Vec<Uuid> = sqlx::query_scalar("SELECT id FROM users ORDER BY id LIMIT 3")
+        .fetch_all(&pool).await.expect("Seeded users");
+    let (fan, artist, other_artist) = (users[0], users[1], users[2]);
+
+    // artist: 3 completados (2 USD + 1 USDC), 1 fallido, 1 reembolsado
+    seed_payment(&pool, fan, artist, 10.0, "USD", "SongPurchase", "Completed", day(2)).await;
+    seed_payment(&pool, fan, artist, 30.0, "USD", "SongPurchase", "Completed", day(2)).await;
+    seed_payment(&pool, fan, artist, 50.0, "USDC", "NFTPurchase", "Completed", day(3)).await;
+    seed_payment(&pool, fan, artist, 20.0, "USD", "SongPurchase", "Failed", day(3)).await;
+    seed_payment(&pool, fan, artist, 15.0, "USD", "SongPurchase", "Refunded", day(4)).await;
+    // other_artist: 1 completado, 1 reembolsado
+    seed_payment(&pool, fan, other_artist, 100.0, "USD", "SharePurchase", "Completed", day(4)).await;
+    seed_payment(&pool, fan, other_artist, 40.0, "USD", "SharePurchase", "Refunded", day(4)).await;
+    // Fuera del periodo
+    seed_payment(&pool, fan, artist, 999.0, "USD", "SongPurchase", "Completed", day(20)).await;
+
+    let service = PaymentStatisticsService::new(Arc::new(PostgresPaymentStatisticsRepository::new(pool.clone())));
+    let query = PaymentStatisticsQuery {
+        start_date: Some(day(1)),
+        end_date: Some(day(10)),
+        artist_id: None,
+    };
+
+    // Platform-wide
+    let report = service.statistics(&query).await.unwrap();
+    assert_eq!(report.total_payments, 7);
+    assert_eq!(report.by_status["Completed"].count, 4);
+    assert_eq!(report.by_status["Completed"].volume["USD"], 140.0);
+    assert_eq!(report.by_status["Failed"].count, 1);
+    assert_eq!(report.by_status["Refunded"].count, 2);
+    assert_eq!(report.by_payment_type["SongPurchase"].count, 4);
+    assert_eq!(report.by_payment_type["SharePurchase"].volume["USD"], 140.0);
+    assert_eq!(report.by_currency["USD"].count, 6);
+    assert_eq!(report.by_currency["USDC"].volume["USDC"], 50.0);
+    assert!((report.average_transaction_value["USD"] - 140.0 / 3.0).abs() < 1e-9);
+    assert_eq!(report.average_transaction_value["USDC"], 50.0);
+    assert!((report.refund_rate - 2.0 / 6.0).abs() < 1e-9);
+    let days: Vec<(NaiveDate, u64)> = report.daily.iter().map(|d| (d.date, d.payment_count)).collect();
+    assert_eq!(days, vec![
+        (NaiveDate::from_ymd_opt(2020, 3, 2).unwrap(), 2),
+        (NaiveDate::from_ymd_opt(2020, 3, 3).unwrap(), 2),
+        (NaiveDate::from_ymd_opt(2020, 3, 4).unwrap(), 3),
+    ]);
+    assert_eq!(report.daily[2].refunded_count, 2);
+    assert_eq!(report.daily[0].completed_volume["USD"], 40.0);
+
+    // Restricted to one artist's revenue
+    let report = service.statistics(&PaymentStatisticsQuery { artist_id: Some(artist), ..query.clone() }).await.unwrap();
+    assert_eq!(report.artist_id, Some(artist));
+    assert_eq!(report.total_payments, 5);
+    assert_eq!(report.by_status["Completed"].volume["USD"], 40.0);
+    assert!(!report.by_payment_type.contains_key("SharePurchase"));
+    assert_eq!(report.average_transaction_value["USD"], 20.0);
+    assert!((report.refund_rate - 0.25).abs() < 1e-9);
+
+    // Date range validation
+    let inverted = PaymentStatisticsQuery { start_date: Some(day(10)), end_date: Some(day(1)), artist_id: None };
+    assert!(matches!(service.statistics(&inverted).await, Err(AppError::ValidationError(_))));
+    let too_long = PaymentStatisticsQuery { start_date: Some(day(1) - Duration::days(400)), end_date: Some(day(1)), artist_id: None };
+    assert!(matches!(service.statistics(&too_long).await, Err(AppError::ValidationError(_))));
+}
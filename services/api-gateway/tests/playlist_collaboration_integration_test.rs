@@ -0,0 +1,125 @@
+// =============================================================================
+// PLAYLIST COLLABORATIVE EDITING INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// Solo el creador y, si la playlist es colaborativa, sus seguidores pueden
+// editarla; cada canción añadida o quitada queda en el historial con su autor,
+// y las operaciones que no cambian nada no dejan rastro.
+
+use api_gateway::bounded_contexts::music::domain::repositories::{
+    playlist_repository::Playlist, PlaylistEditAction, PlaylistRepository,
+};
+use api_gateway::bounded_contexts::music::infrastructure::repositories::PostgresPlaylistRepository;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::{insert_user, setup_pool};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn insert_songs(pool: &PgPool, artist_user: Uuid, count: usize) -> Vec<Uuid> {
+    let artist_id: Uuid = sqlx::query_scalar("INSERT INTO artists (user_id, stage_name) VALUES ($1, 'Playlist Artist') RETURNING id")
+        .bind(artist_user)
+        .fetch_one(pool)
+        .await
+        .expect("Artist inserted");
+    let mut songs = Vec::with_capacity(count);
+    for i in 0..count {
+        let song_id = sqlx::query_scalar("INSERT INTO songs (title, artist_id, duration_seconds) VALUES ($1, $2, 200) RETURNING id")
+            .bind(format!("Song {}", i))
+            .bind(artist_id)
+            .fetch_one(pool)
+            .await
+            .expect("Song inserted");
+        songs.push(song_id);
+    }
+    songs
+}
+
+#[tokio::test]
+async fn test_collaborative_playlist_is_editable_by_followers_only() {
+    let (_setup, pool) = setup_pool().await;
+    let repository = PostgresPlaylistRepository::new(pool.clone());
+    let tag = &Uuid::new_v4().simple().to_string()[..8];
+    let creator = insert_user(&pool, &format!("creator_{}", tag)).await;
+    let follower = insert_user(&pool, &format!("follower_{}", tag)).await;
+    let stranger = insert_user(&pool, &format!("stranger_{}", tag)).await;
+
+    let mut playlist = Playlist::new(Uuid::new_v4(), "Road Trip".to_string(), None, true, creator);
+    repository.save(&playlist).await.unwrap();
+    repository.follow(&playlist.id, &follower).await.unwrap();
+
+    // Mientras no sea colaborativa, seguirla no da permiso de edición
+    let stored = repository.find_by_id(&playlist.id).await.unwrap().unwrap();
+    assert!(!stored.is_collaborative);
+    assert!(!stored.can_be_edited_by(follower, repository.is_follower(&playlist.id, &follower).await.unwrap()));
+
+    playlist.is_collaborative = true;
+    repository.update(&playlist).await.unwrap();
+    let stored = repository.find_by_id(&playlist.id).await.unwrap().unwrap();
+    assert!(stored.is_collaborative);
+    assert!(stored.can_be_edited_by(creator, false));
+    assert!(stored.can_be_edited_by(follower, repository.is_follower(&playlist.id, &follower).await.unwrap()));
+    assert!(!stored.can_be_edited_by(stranger, repository.is_follower(&playlist.id, &stranger).await.unwrap()));
+
+    // Al dejar de seguirla pierde el permiso
+    repository.unfollow(&playlist.id, &follower).await.unwrap();
+    assert!(!stored.can_be_edited_by(follower, repository.is_follower(&playlist.id, &follower).await.unwrap()));
+}
+
+#[tokio::test]
+async fn test_history_records_every_change_with_its_editor() {
+    let (_setup, pool) = setup_pool().await;
+    let repository = PostgresPlaylistRepository::new(pool.clone());
+    let tag = &Uuid::new_v4().simple().to_string()[..8];
+    let creator = insert_user(&pool, &format!("creator_{}", tag)).await;
+    let follower = insert_user(&pool, &format!("follower_{}", tag)).await;
+    let songs = insert_songs(&pool, creator, 3).await;
+
+    let mut playlist = Playlist::new(Uuid::new_v4(), "Shared Mix".to_string(), None, true, creator);
+    playlist.is_collaborative = true;
+    repository.save(&playlist).await.unwrap();
+
+    repository.add_song(&playlist.id, &songs[0], &creator).await.unwrap();
+    repository.add_song(&playlist.id, &songs[1], &follower).await.unwrap();
+    // Duplicado y borrado de una canción que no está: sin cambios, sin historial
+    repository.add_song(&playlist.id, &songs[1], &creator).await.unwrap();
+    repository.remove_song(&playlist.id, &songs[2], &follower).await.unwrap();
+    repository.remove_song(&playlist.id, &songs[0], &follower).await.unwrap();
+    repository.add_song(&playlist.id, &songs[2], &creator).await.unwrap();
+
+    let history: Vec<(Uuid, PlaylistEditAction, Uuid)> = repository
+        .edit_history(&playlist.id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|edit| {
+            assert_eq!(edit.playlist_id, playlist.id);
+            (edit.editor_id, edit.action, edit.song_id)
+        })
+        .collect();
+    assert_eq!(
+        history,
+        vec![
+            (creator, PlaylistEditAction::AddSong, songs[0]),
+            (follower, PlaylistEditAction::AddSong, songs[1]),
+            (follower, PlaylistEditAction::RemoveSong, songs[0]),
+            (creator, PlaylistEditAction::AddSong, songs[2]),
+        ]
+    );
+
+    // El historial cuadra con el contenido actual y con quién añadió cada canción
+    assert_eq!(repository.get_songs(&playlist.id).await.unwrap(), vec![songs[1], songs[2]]);
+    let added_by: Vec<(Uuid, Option<Uuid>)> =
+        sqlx::query_as("SELECT song_id, added_by FROM playlist_songs WHERE playlist_id = $1 ORDER BY position")
+            .bind(playlist.id)
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(added_by, vec![(songs[1], Some(follower)), (songs[2], Some(creator))]);
+    let song_count: i32 = sqlx::query_scalar("SELECT song_count FROM playlists WHERE id = $1")
+        .bind(playlist.id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(song_count, 2);
+}
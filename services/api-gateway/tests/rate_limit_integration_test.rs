@@ -0,0 +1,131 @@
+// =============================================================================
+// RATE LIMITING INTEGRATION TESTS
+// =============================================================================
+//
+// Token bucket por ruta en Redis: auth 5/min, lecturas 100/min, escrituras 30/min,
+// y reglas por endpoint por encima. Usa testcontainers para levantar Redis automáticamente
+
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Method, Request, StatusCode},
+    routing::{get, post},
+    Router,
+};
+use tower::ServiceExt;
+use api_gateway::shared::infrastructure::rate_limit::{
+    RateLimitConfig, RateLimitLayer, RateLimitRule, RateLimiter, RedisRateLimitStore,
+};
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::TestContainersSetup;
+
+async fn rate_limited_app_with(setup: &TestContainersSetup, config: RateLimitConfig) -> Router {
+    let client = redis::Client::open(setup.get_redis_url()).expect("Invalid Redis URL");
+    let connection = redis::aio::ConnectionManager::new(client)
+        .await
+        .expect("Redis connection failed");
+    let limiter = Arc::new(RateLimiter::new(Arc::new(RedisRateLimitStore::new(connection)), config));
+
+    Router::new()
+        .route("/login", post(|| async { "ok" }))
+        .route("/profile", get(|| async { "ok" }))
+        .route("/songs", get(|| async { "ok" }).post(|| async { "ok" }))
+        .layer(RateLimitLayer::new(limiter))
+}
+
+async fn rate_limited_app(setup: &TestContainersSetup) -> Router {
+    rate_limited_app_with(setup, RateLimitConfig::default()).await
+}
+
+fn request(method: &str, uri: &str, ip: &str) -> Request<Body> {
+    Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("x-forwarded-for", ip)
+        .body(Body::empty())
+        .unwrap()
+}
+
+fn header<'a>(response: &'a axum::response::Response, name: &str) -> &'a str {
+    response.headers().get(name).unwrap().to_str().unwrap()
+}
+
+#[tokio::test]
+async fn test_login_is_limited_to_five_attempts_per_minute() {
+    let setup = TestContainersSetup::new();
+    setup.wait_for_redis().await.expect("Redis debe estar listo");
+    let app = rate_limited_app(&setup).await;
+
+    for remaining in (0..5).rev() {
+        let response = app.clone().oneshot(request("POST", "/login", "203.0.113.7")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(header(&response, "x-ratelimit-limit"), "5");
+        assert_eq!(header(&response, "x-ratelimit-remaining"), remaining.to_string());
+    }
+
+    let response = app.clone().oneshot(request("POST", "/login", "203.0.113.7")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(header(&response, "x-ratelimit-remaining"), "0");
+
+    // Un token cada 12s con 5/min
+    let retry_after: u64 = header(&response, "retry-after").parse().unwrap();
+    assert!((1..=12).contains(&retry_after), "retry-after was {}", retry_after);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(json["error"]["code"], "RATE_LIMITED");
+}
+
+#[tokio::test]
+async fn test_buckets_are_per_route_class_and_per_client() {
+    let setup = TestContainersSetup::new();
+    setup.wait_for_redis().await.expect("Redis debe estar listo");
+    let app = rate_limited_app(&setup).await;
+
+    for _ in 0..6 {
+        app.clone().oneshot(request("POST", "/login", "198.51.100.1")).await.unwrap();
+    }
+
+    // Las lecturas del mismo cliente tienen su propio bucket
+    let read = app.clone().oneshot(request("GET", "/profile", "198.51.100.1")).await.unwrap();
+    assert_eq!(read.status(), StatusCode::OK);
+    assert_eq!(header(&read, "x-ratelimit-limit"), "100");
+
+    // Otro cliente no se ve afectado
+    let other = app.clone().oneshot(request("POST", "/login", "198.51.100.2")).await.unwrap();
+    assert_eq!(other.status(), StatusCode::OK);
+    assert_eq!(header(&other, "x-ratelimit-remaining"), "4");
+}
+
+#[tokio::test]
+async fn test_endpoint_rule_overrides_the_route_class() {
+    let setup = TestContainersSetup::new();
+    setup.wait_for_redis().await.expect("Redis debe estar listo");
+    let config = RateLimitConfig {
+        rules: vec![RateLimitRule::new(Method::POST, "/songs", 2, 3600).unwrap()],
+        ..RateLimitConfig::default()
+    };
+    let app = rate_limited_app_with(&setup, config).await;
+
+    for remaining in (0..2).rev() {
+        let response = app.clone().oneshot(request("POST", "/songs", "192.0.2.10")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(header(&response, "x-ratelimit-limit"), "2");
+        assert_eq!(header(&response, "rate_limit_remaining"), remaining.to_string());
+    }
+
+    let response = app.clone().oneshot(request("POST", "/songs", "192.0.2.10")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    // Un token cada 30 minutos con 2/hora
+    let retry_after: u64 = header(&response, "retry-after").parse().unwrap();
+    assert!((1..=1800).contains(&retry_after), "retry-after was {}", retry_after);
+    let reset: u64 = header(&response, "rate_limit_reset").parse().unwrap();
+    assert!(reset <= 3600, "rate_limit_reset was {}", reset);
+
+    // GET /songs no tiene regla: cubo de lecturas
+    let read = app.clone().oneshot(request("GET", "/songs", "192.0.2.10")).await.unwrap();
+    assert_eq!(read.status(), StatusCode::OK);
+    assert_eq!(header(&read, "x-ratelimit-limit"), "100");
+}
@@ -0,0 +1,202 @@
+// =============================================================================
+// REFUND ROYALTY REVERSAL INTEGRATION TESTS
+// =============================================================================
+//
+// Reembolsar una compra cuyos ingresos ya repartió una ejecución de royalties
+// revierte la parte proporcional de cada pago de la ejecución y resta del saldo
+// de los creadores en la misma transacción.
+
+use api_gateway::bounded_contexts::payment::application::{RefundExecution, RefundGatewayExecutor, RefundService};
+use api_gateway::bounded_contexts::payment::domain::{
+    aggregates::PaymentAggregate,
+    artist_payouts::{ArtistBalance, BalanceLedgerEntry},
+    refunds::RefundTransaction,
+    repository::{ArtistPayoutRepository, PaymentRepository, RoyaltyRunRepository},
+    royalty_runs::{RoyaltyDistributionRun, RoyaltyPayout, RoyaltyRecipientType, RoyaltySplit, RoyaltySplitPlan},
+    value_objects::{Amount, Currency, FeePercentage, PaymentMetadata, PaymentMethod, PaymentPurpose, TransactionId},
+};
+use api_gateway::bounded_contexts::payment::infrastructure::repositories::{
+    PostgresArtistPayoutRepository, PostgresPaymentRepository, PostgresRefundRepository,
+    PostgresRoyaltyReversalRepository, PostgresRoyaltyRunRepository,
+};
+use api_gateway::shared::domain::errors::AppError;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::{insert_user, setup_pool};
+use async_trait::async_trait;
+use chrono::Duration;
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Gateway que devuelve el dinero al instante
+struct InstantRefunds;
+
+#[async_trait]
+impl RefundGatewayExecutor for InstantRefunds {
+    fn gateway_for(&self, _payment: &PaymentAggregate) -> Result<&'static str, AppError> {
+        Ok("platform_balance")
+    }
+
+    async fn execute(&self, _payment: &PaymentAggregate, _refund: &RefundTransaction) -> Result<RefundExecution, AppError> {
+        Ok(RefundExecution::Completed { gateway_refund_id: None })
+    }
+
+    async fn confirm_payout(&self, _refund: &RefundTransaction, _tx_hash: &str) -> Result<(), AppError> {
+        Ok(())
+    }
+}
+
+struct DistributedPurchase {
+    payment_id: Uuid,
+    artist_id: Uuid,
+    contributor_id: Uuid,
+    payouts: Vec<RoyaltyPayout>,
+}
+
+/// Compra de 10 USD completada, repartida 60/30/10 por una ejecución de 100 USD
+/// y con los créditos ya en el saldo de los creadores
+async fn distributed_purchase(pool: &PgPool) -> DistributedPurchase {
+    let fan_id = insert_user(pool, &format!("fan_{}", Uuid::new_v4().simple())).await;
+    let artist_id = insert_user(pool, &format!("artist_{}", Uuid::new_v4().simple())).await;
+    let contributor_id = insert_user(pool, &format!("producer_{}", Uuid::new_v4().simple())).await;
+    let song_id: Uuid = sqlx::query_scalar("INSERT INTO songs (title, artist_id) VALUES ('Refunded', $1) RETURNING id")
+        .bind(artist_id)
+        .fetch_one(pool)
+        .await
+        .expect("Song inserted");
+
+    let mut payment = PaymentAggregate::create_payment(
+        fan_id,
+        artist_id,
+        Amount::new(10.0, Currency::USD).unwrap(),
+        PaymentMethod::PlatformBalance,
+        PaymentPurpose::SongPurchase { song_id },
+        FeePercentage::new(10.0).unwrap(),
+        PaymentMetadata {
+            user_ip: None,
+            user_agent: None,
+            platform_version: "1.0.0".to_string(),
+            reference_id: None,
+            additional_data: serde_json::json!({}),
+        },
+    )
+    .unwrap();
+    payment.start_processing(TransactionId::new()).unwrap();
+    payment.complete_payment(None).unwrap();
+    PostgresPaymentRepository::new(pool.clone()).save(&payment).await.expect("Payment saved");
+    let completed_at = payment.payment().completed_at().unwrap();
+
+    let plan = RoyaltySplitPlan::new(vec![
+        RoyaltySplit { recipient_id: artist_id, recipient_type: RoyaltyRecipientType::Artist, percentage: 60.0 },
+        RoyaltySplit { recipient_id: contributor_id, recipient_type: RoyaltyRecipientType::Contributor, percentage: 30.0 },
+        RoyaltySplit { recipient_id: Uuid::nil(), recipient_type: RoyaltyRecipientType::Platform, percentage: 10.0 },
+    ])
+    .unwrap();
+    let run = RoyaltyDistributionRun::new(
+        song_id,
+        completed_at - Duration::days(1),
+        completed_at + Duration::days(1),
+        Amount::new(100.0, Currency::USD).unwrap(),
+        plan,
+        artist_id,
+    )
+    .unwrap();
+    let payouts = run.payouts().unwrap();
+    PostgresRoyaltyRunRepository::new(pool.clone()).create(&run, &payouts).await.expect("Run saved");
+
+    let credits: Vec<BalanceLedgerEntry> = payouts
+        .iter()
+        .filter(|p| p.is_creator_payout())
+        .map(|p| BalanceLedgerEntry::credit(p.recipient_id, p.id, p.amount.value(), Currency::USD).unwrap())
+        .collect();
+    PostgresArtistPayoutRepository::new(pool.clone()).credit(&credits).await.expect("Balances credited");
+
+    DistributedPurchase {
+        payment_id: payment.payment().id().value(),
+        artist_id,
+        contributor_id,
+        payouts,
+    }
+}
+
+fn refund_service(pool: &PgPool) -> RefundService {
+    RefundService::new(
+        Arc::new(PostgresPaymentRepository::new(pool.clone())),
+        Arc::new(PostgresRefundRepository::new(pool.clone())),
+        Arc::new(PostgresRoyaltyReversalRepository::new(pool.clone())),
+        Arc::new(InstantRefunds),
+    )
+}
+
+async fn available_balance(pool: &PgPool, artist_id: Uuid) -> f64 {
+    let entries = PostgresArtistPayoutRepository::new(pool.clone()).find_entries(artist_id).await.unwrap();
+    ArtistBalance::from_entries(artist_id, &entries)
+        .first()
+        .map(|balance| balance.available)
+        .unwrap_or(0.0)
+}
+
+#[tokio::test]
+async fn test_refunding_a_distributed_purchase_reverses_payouts_and_debits_balances() {
+    let (_setup, pool) = setup_pool().await;
+    let purchase = distributed_purchase(&pool).await;
+    let service = refund_service(&pool);
+    assert_eq!(available_balance(&pool, purchase.artist_id).await, 60.0);
+
+    // 4 de los 100 repartidos: 4% de cada pago
+    service
+        .refund_payment(purchase.payment_id, Some(4.0), "Partial".to_string(), purchase.artist_id)
+        .await
+        .expect("Partial refund");
+    assert_eq!(available_balance(&pool, purchase.artist_id).await, 57.6);
+    assert_eq!(available_balance(&pool, purchase.contributor_id).await, 28.8);
+
+    // El resto del pago
+    service
+        .refund_payment(purchase.payment_id, None, "Rest".to_string(), purchase.artist_id)
+        .await
+        .expect("Full refund");
+    assert_eq!(available_balance(&pool, purchase.artist_id).await, 54.0);
+    assert_eq!(available_balance(&pool, purchase.contributor_id).await, 27.0);
+
+    // Una reversión por pago y reembolso; la plataforma no tiene saldo que debitar
+    let reversed: Vec<(String, f64)> = sqlx::query_as(
+        r#"SELECT recipient_type, SUM(amount)::float8 FROM royalty_reversals
+           WHERE royalty_payout_id = ANY($1) GROUP BY recipient_type ORDER BY recipient_type"#,
+    )
+    .bind(purchase.payouts.iter().map(|p| p.id).collect::<Vec<Uuid>>())
+    .fetch_all(&pool)
+    .await
+    .unwrap();
+    assert_eq!(
+        reversed,
+        vec![("artist".to_string(), 6.0), ("contributor".to_string(), 3.0), ("platform".to_string(), 1.0)]
+    );
+    let debits: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM artist_balance_entries WHERE amount < 0 AND artist_id = ANY($1)")
+        .bind(vec![purchase.artist_id, purchase.contributor_id])
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(debits, 4);
+}
+
+#[tokio::test]
+async fn test_revoking_run_credits_also_drops_refund_debits() {
+    let (_setup, pool) = setup_pool().await;
+    let purchase = distributed_purchase(&pool).await;
+    refund_service(&pool)
+        .refund_payment(purchase.payment_id, None, "Chargeback".to_string(), purchase.artist_id)
+        .await
+        .expect("Refund");
+    assert_eq!(available_balance(&pool, purchase.artist_id).await, 54.0);
+
+    // Anular la ejecución retira los créditos; el débito que los compensaba no puede quedarse
+    let source_ids: Vec<Uuid> = purchase.payouts.iter().map(|p| p.id).collect();
+    PostgresArtistPayoutRepository::new(pool.clone())
+        .revoke_credits(&source_ids)
+        .await
+        .expect("Credits revoked");
+    assert_eq!(available_balance(&pool, purchase.artist_id).await, 0.0);
+    assert_eq!(available_balance(&pool, purchase.contributor_id).await, 0.0);
+}
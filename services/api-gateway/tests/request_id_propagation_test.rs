@@ -0,0 +1,134 @@
+// =============================================================================
+// REQUEST ID PROPAGATION TESTS (Postgres + Redis)
+// =============================================================================
+//
+// El `X-Request-Id` de una petición vuelve en la respuesta y llega a lo que la
+// petición deja atrás: el mensaje en la cola del worker, la clave de
+// idempotencia y el registro de auditoría.
+
+use api_gateway::bounded_contexts::fan_ventures::domain::audit::AuditLog;
+use api_gateway::bounded_contexts::fan_ventures::domain::repositories::AuditLogRepository;
+use api_gateway::bounded_contexts::fan_ventures::infrastructure::PostgresAuditLogRepository;
+use api_gateway::bounded_contexts::orchestrator::DomainEvent;
+use api_gateway::handlers::process_transaction;
+use api_gateway::shared::domain::events::with_request_id;
+use api_gateway::shared::infrastructure::app_state::AppState;
+use api_gateway::shared::infrastructure::correlation::{TracingLayer, REQUEST_ID_HEADER};
+use api_gateway::auth::Claims;
+use api_gateway::shared::infrastructure::idempotency::{
+    idempotency_middleware, scoped_key, IdempotencyStore, PostgresIdempotencyStore, IDEMPOTENCY_KEY_HEADER,
+};
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    middleware,
+    routing::post,
+    Router,
+};
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::TestContainersSetup;
+use chrono::Utc;
+use sqlx::PgPool;
+use std::sync::Arc;
+use tower::ServiceExt;
+use uuid::Uuid;
+use vibestream_types::{ApiMessage, RequestId, ServiceMessage};
+
+async fn setup() -> TestContainersSetup {
+    let setup = TestContainersSetup::new();
+    setup.setup_env();
+    setup.wait_for_postgres().await.expect("Postgres failed to start");
+    setup.wait_for_redis().await.expect("Redis failed to start");
+    setup.run_migrations().await.expect("Migrations failed");
+    setup
+}
+
+#[tokio::test]
+async fn queue_message_carries_the_originating_request_id() {
+    let setup = setup().await;
+    let state = AppState::new(&setup.get_postgres_url(), &setup.get_redis_url()).await.expect("App state");
+    let queue = state.message_queue.clone();
+    let router = Router::new()
+        .route("/transactions", post(process_transaction))
+        .with_state(state)
+        .layer(TracingLayer::new());
+    let request_id = Uuid::new_v4();
+
+    let request = Request::post("/transactions")
+        .header(REQUEST_ID_HEADER, request_id.to_string())
+        .header("content-type", "application/json")
+        .body(Body::from(r#"{"blockchain":"Solana","from":"sender","to":"receiver","amount":5000}"#))
+        .unwrap();
+    let response = router.oneshot(request).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[REQUEST_ID_HEADER], request_id.to_string().as_str());
+
+    let raw = queue.receive_message("solana_queue", 5).await.unwrap().expect("Queued message");
+    let message: ServiceMessage<ApiMessage> = serde_json::from_str(&raw).unwrap();
+    assert_eq!(message.request_id, Some(RequestId(request_id)));
+    // El id del mensaje sigue siendo propio
+    assert_ne!(message.id.0, request_id);
+}
+
+#[tokio::test]
+async fn idempotency_key_records_the_request_that_ran_it() {
+    let setup = setup().await;
+    let pool = PgPool::connect(&setup.get_postgres_url()).await.expect("Failed to connect to pool");
+    let store: Arc<dyn IdempotencyStore> = Arc::new(PostgresIdempotencyStore::new(pool.clone()));
+    let router = Router::new()
+        .route("/payments", post(|| async { (StatusCode::CREATED, "created") }))
+        .route_layer(middleware::from_fn_with_state(store, idempotency_middleware))
+        .layer(TracingLayer::new());
+    let (key, request_id, user_id) = (Uuid::new_v4().to_string(), Uuid::new_v4(), Uuid::new_v4());
+    let claims = Claims::new(user_id, "payer".into(), "payer@vibestream.test".into(), "user".into(), "access".into());
+
+    let response = router
+        .oneshot(
+            Request::post("/payments")
+                .header(IDEMPOTENCY_KEY_HEADER, &key)
+                .header("authorization", format!("Bearer {}", claims.to_jwt().unwrap()))
+                .header(REQUEST_ID_HEADER, request_id.to_string())
+                .body(Body::from("{}"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let recorded: Option<Uuid> = sqlx::query_scalar("SELECT request_id FROM idempotency_keys WHERE key = $1")
+        .bind(scoped_key(&user_id.to_string(), &key))
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(recorded, Some(request_id));
+}
+
+#[tokio::test]
+async fn audit_entries_record_the_request_id() {
+    let setup = setup().await;
+    let pool = PgPool::connect(&setup.get_postgres_url()).await.expect("Failed to connect to pool");
+    let repository = PostgresAuditLogRepository::new(pool);
+    let (venture_id, request_id) = (Uuid::new_v4(), RequestId::new());
+    let purchase = DomainEvent::SharePurchased {
+        venture_id,
+        investment_id: Uuid::new_v4(),
+        investor_id: Uuid::new_v4(),
+        amount: 25.0,
+        funding_before: 100.0,
+        funding_after: 125.0,
+        occurred_at: Utc::now(),
+    };
+
+    let entry = with_request_id(request_id, async { AuditLog::from_event(&purchase) }).await.unwrap();
+    repository.append(&entry).await.unwrap();
+    // Fuera de una petición no hay request ID
+    repository.append(&AuditLog::from_event(&purchase).unwrap()).await.unwrap();
+
+    let trail = repository.get_for_aggregate(venture_id).await.unwrap();
+    let request_ids: Vec<Option<Uuid>> = trail.iter().map(|entry| entry.request_id).collect();
+    assert_eq!(request_ids.len(), 2);
+    assert!(request_ids.contains(&Some(request_id.0)));
+    assert!(request_ids.contains(&None));
+}
@@ -0,0 +1,99 @@
+// =============================================================================
+// REWARD DISTRIBUTION BATCH INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// Repartir entre muchos shareholders usa COPY binario; con 1000 registros debe
+// ser más rápido que insertarlos uno a uno y dejar los mismos totales.
+
+use api_gateway::bounded_contexts::listen_reward::domain::aggregates::{RewardDistribution, RewardPool};
+use api_gateway::bounded_contexts::listen_reward::domain::value_objects::{
+    ListenSessionId, RewardAmount, ValidationPeriod,
+};
+use api_gateway::bounded_contexts::listen_reward::infrastructure::repositories::PostgresRewardDistributionRepository;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::setup_pool;
+use std::time::Instant;
+use uuid::Uuid;
+
+const BATCH_SIZE: usize = 1000;
+
+fn shareholder_distributions(user_ids: &[Uuid], artist_id: Uuid) -> Vec<RewardDistribution> {
+    user_ids
+        .iter()
+        .map(|user_id| {
+            RewardDistribution::for_recipient(
+                RewardPool::new(RewardAmount::new(10.0).unwrap(), ValidationPeriod::daily()),
+                ListenSessionId::new(),
+                *user_id,
+                artist_id,
+                RewardAmount::new(1.5).unwrap(),
+                RewardAmount::new(0.25).unwrap(),
+                RewardAmount::new(0.1).unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_batch_insert_is_faster_than_individual_inserts() {
+    let (_setup, pool) = setup_pool().await;
+    let repository = PostgresRewardDistributionRepository::new(pool.clone());
+    let artist_id = Uuid::new_v4();
+
+    let individual_users: Vec<Uuid> = (0..BATCH_SIZE).map(|_| Uuid::new_v4()).collect();
+    let individual = shareholder_distributions(&individual_users, artist_id);
+    let started = Instant::now();
+    for distribution in &individual {
+        repository.save_distribution(distribution).await.expect("Individual insert");
+    }
+    let individual_elapsed = started.elapsed();
+
+    let batch_users: Vec<Uuid> = (0..BATCH_SIZE).map(|_| Uuid::new_v4()).collect();
+    let batch = shareholder_distributions(&batch_users, artist_id);
+    let started = Instant::now();
+    repository.save_batch(&batch).await.expect("Batch insert");
+    let batch_elapsed = started.elapsed();
+
+    println!(
+        "{} reward distributions: individual {:?}, batch {:?}",
+        BATCH_SIZE, individual_elapsed, batch_elapsed
+    );
+    assert!(batch_elapsed < individual_elapsed);
+
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM reward_distributions WHERE artist_id = $1")
+        .bind(artist_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored, 2 * BATCH_SIZE as i64);
+
+    // Ambos caminos guardan lo mismo
+    let first = batch.first().unwrap();
+    let (user_id, artist_share, status): (Uuid, f64, String) = sqlx::query_as(
+        "SELECT user_id, artist_share::FLOAT8, status FROM reward_distributions WHERE id = $1",
+    )
+    .bind(first.id())
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(user_id, batch_users[0]);
+    assert_eq!(artist_share, 0.25);
+    assert_eq!(status, first.status());
+}
+
+#[tokio::test]
+async fn test_user_total_earned_sums_user_shares() {
+    let (_setup, pool) = setup_pool().await;
+    let repository = PostgresRewardDistributionRepository::new(pool);
+
+    let user_id = Uuid::new_v4();
+    let other_user = Uuid::new_v4();
+    let distributions = shareholder_distributions(&[user_id, user_id, other_user], Uuid::new_v4());
+    repository.save_distribution(&distributions[0]).await.unwrap();
+    repository.save_batch(&distributions[1..]).await.unwrap();
+
+    assert_eq!(repository.get_user_total_earned(user_id).await.unwrap(), 3.0);
+    assert_eq!(repository.get_user_total_earned(other_user).await.unwrap(), 1.5);
+    assert_eq!(repository.get_user_total_earned(Uuid::new_v4()).await.unwrap(), 0.0);
+}
@@ -0,0 +1,90 @@
+// =============================================================================
+// SECONDARY MARKET TRADES INTEGRATION TESTS
+// =============================================================================
+//
+// Cada `ShareTransferred` deja una operación en el historial del venture; un
+// evento repetido no la duplica y el precio de hace 24h sale del índice.
+
+use api_gateway::bounded_contexts::fan_ventures::domain::repositories::SecondaryMarketTradeRepository;
+use api_gateway::bounded_contexts::fan_ventures::domain::trades::price_change_24h;
+use api_gateway::bounded_contexts::fan_ventures::infrastructure::{
+    PostgresSecondaryMarketTradeRepository, SecondaryMarketTradeRecorder,
+};
+use api_gateway::bounded_contexts::orchestrator::{DomainEvent, EventHandler};
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::{insert_user, setup_pool};
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+async fn insert_venture(pool: &PgPool, artist_id: Uuid) -> Uuid {
+    sqlx::query_scalar(
+        "INSERT INTO artist_ventures (artist_id, title, funding_goal, min_investment) VALUES ($1, 'Debut EP', 1000, 1) RETURNING id",
+    )
+    .bind(artist_id)
+    .fetch_one(pool)
+    .await
+    .expect("Venture inserted")
+}
+
+fn transfer(venture_id: Uuid, seller: Uuid, buyer: Uuid, shares: f64, price: f64, at: DateTime<Utc>) -> DomainEvent {
+    DomainEvent::ShareTransferred {
+        escrow_id: Uuid::new_v4(),
+        venture_id,
+        investment_id: Uuid::new_v4(),
+        from_user_id: seller,
+        to_user_id: buyer,
+        amount: shares,
+        price,
+        occurred_at: at,
+    }
+}
+
+#[tokio::test]
+async fn test_transfers_build_a_paginated_trade_history_with_price_change() {
+    let (_setup, pool) = setup_pool().await;
+
+    let artist = insert_user(&pool, "trade_artist").await;
+    let (alice, bob) = (insert_user(&pool, "trade_alice").await, insert_user(&pool, "trade_bob").await);
+    let venture_id = insert_venture(&pool, artist).await;
+    let repo = Arc::new(PostgresSecondaryMarketTradeRepository::new(pool.clone()));
+    let recorder = SecondaryMarketTradeRecorder::new(repo.clone());
+
+    // Postgres guarda microsegundos
+    let now = Utc::now().duration_trunc(Duration::seconds(1)).unwrap();
+    let events = [
+        transfer(venture_id, alice, bob, 10.0, 100.0, now - Duration::hours(30)),
+        transfer(venture_id, bob, alice, 5.0, 40.0, now - Duration::hours(26)),
+        transfer(venture_id, alice, bob, 2.0, 22.0, now - Duration::hours(2)),
+    ];
+    for event in &events {
+        recorder.handle(event).await.expect("Trade recorded");
+    }
+    // Redelivery of the same event is a no-op
+    recorder.handle(&events[2]).await.expect("Duplicate ignored");
+    // Other ventures' trades stay out of the history
+    let other_venture = insert_venture(&pool, artist).await;
+    recorder.handle(&transfer(other_venture, alice, bob, 1.0, 1.0, now)).await.unwrap();
+
+    assert_eq!(repo.count_by_venture(&venture_id).await.unwrap(), 3);
+    let first_page = repo.find_by_venture(&venture_id, 0, 2).await.unwrap();
+    let prices: Vec<f64> = first_page.iter().map(|trade| trade.price_per_share).collect();
+    assert_eq!(prices, vec![11.0, 8.0]);
+    assert_eq!(first_page[0].seller_id, alice);
+    let second_page = repo.find_by_venture(&venture_id, 1, 2).await.unwrap();
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page[0].price_per_share, 10.0);
+    assert_eq!(second_page[0].executed_at, now - Duration::hours(30));
+
+    // 8 hace 24h (la operación de hace 26h), 11 ahora: +37,5%
+    let reference = repo.find_price_reference_trades(&venture_id, now).await.unwrap();
+    assert_eq!(reference.len(), 2);
+    assert_eq!(price_change_24h(&reference, now), Some(37.5));
+
+    // Hace 3h la última operación era la de hace 26h y la de referencia la de hace 30h
+    let earlier = now - Duration::hours(3);
+    let reference = repo.find_price_reference_trades(&venture_id, earlier).await.unwrap();
+    assert_eq!(price_change_24h(&reference, earlier), Some(-20.0));
+}
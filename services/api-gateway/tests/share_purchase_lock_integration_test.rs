@@ -0,0 +1,118 @@
+// =============================================================================
+// SHARE PURCHASE LOCK INTEGRATION TESTS (Redis)
+// =============================================================================
+//
+// Dos compras simultáneas de la última participación de un venture: con el
+// lock en Redis solo una llega a reservar; la otra recibe un conflicto.
+
+use api_gateway::bounded_contexts::fan_ventures::application::{
+    PurchaseSharesCommand, PurchaseSharesCommandHandler, ShareReservations,
+};
+use api_gateway::bounded_contexts::fan_ventures::domain::entities::{FanInvestment, InvestmentStatus, InvestmentType};
+use api_gateway::bounded_contexts::fan_ventures::domain::escrow::InvestmentReservation;
+use api_gateway::shared::application::command::CommandHandler;
+use api_gateway::shared::domain::errors::AppError;
+use api_gateway::shared::infrastructure::distributed_lock::{
+    acquire_lock, DistributedLock, LockError, RedisDistributedLock,
+};
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::TestContainersSetup;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Venture con una sola participación y una comprobación no atómica: sin lock,
+/// dos compras a la vez verían la participación libre
+struct LastShare {
+    available: AtomicU32,
+}
+
+#[async_trait]
+impl ShareReservations for LastShare {
+    async fn reserve(
+        &self,
+        venture_id: Uuid,
+        fan_id: Uuid,
+        shares: f64,
+        auto_confirm: bool,
+    ) -> Result<(FanInvestment, InvestmentReservation), AppError> {
+        let available = self.available.load(Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        if available == 0 {
+            return Err(AppError::DomainRuleViolation("No shares left".to_string()));
+        }
+        self.available.store(available - 1, Ordering::SeqCst);
+
+        let investment = FanInvestment::new(
+            Uuid::new_v4(), fan_id, venture_id, shares, InvestmentType::RevenueShare, InvestmentStatus::Pending,
+        );
+        let reservation = InvestmentReservation::new(&investment, None, auto_confirm, chrono::Duration::minutes(15), Utc::now());
+        Ok((investment, reservation))
+    }
+}
+
+async fn redis_lock() -> (TestContainersSetup, Arc<RedisDistributedLock>) {
+    let setup = TestContainersSetup::new();
+    setup.setup_env();
+    setup.wait_for_redis().await.expect("Redis failed to start");
+    let client = redis::Client::open(setup.get_redis_url()).expect("Redis client");
+    let connection = redis::aio::ConnectionManager::new(client).await.expect("Redis connection");
+    (setup, Arc::new(RedisDistributedLock::new(connection)))
+}
+
+#[tokio::test]
+async fn test_only_one_concurrent_purchase_of_the_last_share_succeeds() {
+    let (_setup, lock) = redis_lock().await;
+    let shares = Arc::new(LastShare { available: AtomicU32::new(1) });
+    let handler = Arc::new(PurchaseSharesCommandHandler::new(shares.clone(), lock));
+    let venture_id = Uuid::new_v4();
+
+    let purchases = (0..2).map(|_| {
+        let handler = handler.clone();
+        tokio::spawn(async move {
+            handler
+                .handle(PurchaseSharesCommand {
+                    request_id: Uuid::new_v4(),
+                    venture_id,
+                    fan_id: Uuid::new_v4(),
+                    shares: 1.0,
+                    auto_confirm: true,
+                })
+                .await
+        })
+    });
+    let mut outcomes = Vec::new();
+    for purchase in purchases.collect::<Vec<_>>() {
+        outcomes.push(purchase.await.unwrap());
+    }
+
+    assert_eq!(outcomes.iter().filter(|outcome| outcome.is_ok()).count(), 1);
+    assert!(outcomes.iter().any(|outcome| matches!(
+        outcome,
+        Err(AppError::ConcurrencyConflict(message)) if message == "Share purchase is locked by another transaction"
+    )));
+    assert_eq!(shares.available.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn test_lock_is_released_only_by_its_owner_and_expires() {
+    let (_setup, lock) = redis_lock().await;
+    let key = format!("lock:purchase:{}", Uuid::new_v4());
+
+    assert!(lock.try_acquire(&key, "owner", Duration::from_millis(300)).await.unwrap());
+    assert!(!lock.try_acquire(&key, "intruder", Duration::from_millis(300)).await.unwrap());
+    assert_eq!(lock.release(&key, "intruder").await, Err(LockError::LockNotHeld(key.clone())));
+
+    // El TTL lo libera aunque el dueño no lo suelte
+    tokio::time::sleep(Duration::from_millis(400)).await;
+    let guard = acquire_lock(lock.clone(), &key, "next", Duration::from_millis(5000), Duration::from_millis(100))
+        .await
+        .expect("Expired lock can be taken");
+    assert_eq!(lock.release(&key, "owner").await, Err(LockError::LockNotHeld(key.clone())));
+    guard.release().await.unwrap();
+    assert!(lock.try_acquire(&key, "owner", Duration::from_millis(300)).await.unwrap());
+}
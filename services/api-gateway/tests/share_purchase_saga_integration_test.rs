@@ -0,0 +1,134 @@
+// =============================================================================
+// SHARE PURCHASE SAGA INTEGRATION TESTS
+// =============================================================================
+//
+// La saga persistida en Postgres: camino feliz, fallo del cobro y compensación
+// por timeout retomada por otra instancia tras un "reinicio".
+
+use api_gateway::bounded_contexts::fan_ventures::application::{
+    SharePurchaseCompensations, SharePurchaseSaga, SharePurchaseSagaConfig, SHARE_PURCHASE_SAGA,
+};
+use api_gateway::shared::domain::errors::AppError;
+use api_gateway::shared::infrastructure::saga::{PostgresSagaStore, SagaStatus, SagaStore};
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::setup_pool;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::{Arc, Mutex};
+use uuid::Uuid;
+
+/// Compensaciones registradas; los reembolsos fallan mientras `refunds_down`
+#[derive(Default)]
+struct FakeCompensations {
+    calls: Mutex<Vec<(&'static str, Uuid)>>,
+    refunds_down: Mutex<bool>,
+}
+
+#[async_trait]
+impl SharePurchaseCompensations for FakeCompensations {
+    async fn release_reservation(&self, investment_id: Uuid, _reason: &str) -> Result<(), AppError> {
+        self.calls.lock().unwrap().push(("release", investment_id));
+        Ok(())
+    }
+
+    async fn refund_payment(&self, payment_id: Uuid, _fan_id: Uuid, _reason: &str) -> Result<(), AppError> {
+        if *self.refunds_down.lock().unwrap() {
+            return Err(AppError::InternalError("payment provider unavailable".to_string()));
+        }
+        self.calls.lock().unwrap().push(("refund", payment_id));
+        Ok(())
+    }
+}
+
+fn config() -> SharePurchaseSagaConfig {
+    SharePurchaseSagaConfig {
+        settlement_timeout: Some(chrono::Duration::minutes(10)),
+        retry_delay: chrono::Duration::zero(),
+        ..SharePurchaseSagaConfig::default()
+    }
+}
+
+async fn start(saga: &SharePurchaseSaga) -> (Uuid, Uuid) {
+    let (investment_id, payment_id) = (Uuid::new_v4(), Uuid::new_v4());
+    saga.on_shares_reserved(investment_id, Uuid::new_v4(), Uuid::new_v4(), 25.0, Some(payment_id))
+        .await
+        .unwrap();
+    (investment_id, payment_id)
+}
+
+#[tokio::test]
+async fn purchase_completes_and_failed_payment_is_compensated() {
+    let (_setup, pool) = setup_pool().await;
+    let store = Arc::new(PostgresSagaStore::new(pool));
+    let compensations = Arc::new(FakeCompensations::default());
+    let saga = SharePurchaseSaga::with_config(store.clone(), compensations.clone(), config());
+
+    let (paid, payment_id) = start(&saga).await;
+    saga.on_payment_completed(paid, payment_id).await.unwrap();
+    let completed = saga.on_settlement_confirmed(paid, "0xfeed").await.unwrap().unwrap();
+    assert_eq!(completed.status, SagaStatus::Completed);
+    let steps: Vec<String> = store.transitions(completed.id).await.unwrap().into_iter().map(|t| t.to_step).collect();
+    assert_eq!(steps, vec!["awaiting_payment", "awaiting_settlement", "completed"]);
+
+    let (declined, _) = start(&saga).await;
+    let compensated = saga.on_payment_failed(declined, "card declined").await.unwrap().unwrap();
+    assert_eq!(compensated.status, SagaStatus::Compensated);
+    assert_eq!(*compensations.calls.lock().unwrap(), vec![("release", declined)]);
+}
+
+#[tokio::test]
+async fn timed_out_purchase_is_compensated_after_a_restart() {
+    let (_setup, pool) = setup_pool().await;
+    let store = Arc::new(PostgresSagaStore::new(pool.clone()));
+    let compensations = Arc::new(FakeCompensations::default());
+    *compensations.refunds_down.lock().unwrap() = true;
+    let saga = SharePurchaseSaga::with_config(store.clone(), compensations.clone(), config());
+    let (investment_id, payment_id) = start(&saga).await;
+    saga.on_payment_completed(investment_id, payment_id).await.unwrap();
+
+    // La liquidación on-chain no llega dentro del plazo
+    sqlx::query("UPDATE saga_instances SET deadline_at = $2 WHERE saga_type = $1 AND correlation_id = $3")
+        .bind(SHARE_PURCHASE_SAGA)
+        .bind(Utc::now() - chrono::Duration::seconds(1))
+        .bind(investment_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    saga.process_timeouts().await.unwrap();
+
+    // La reserva se liberó pero el reembolso falló: queda pendiente de reintento
+    let interrupted = saga.load(investment_id).await.unwrap();
+    assert_eq!(interrupted.status, SagaStatus::Compensating);
+    assert_eq!(interrupted.attempts, 1);
+    assert!(interrupted.last_error.is_some());
+
+    // Proceso nuevo sobre la misma base de datos: solo falta el reembolso
+    *compensations.refunds_down.lock().unwrap() = false;
+    let restarted = SharePurchaseSaga::with_config(
+        Arc::new(PostgresSagaStore::new(pool)),
+        compensations.clone(),
+        config(),
+    );
+    restarted.process_timeouts().await.unwrap();
+
+    let compensated = restarted.load(investment_id).await.unwrap();
+    assert_eq!(compensated.status, SagaStatus::Compensated);
+    assert_eq!(
+        *compensations.calls.lock().unwrap(),
+        vec![("release", investment_id), ("refund", payment_id)]
+    );
+    let triggers: Vec<String> = store.transitions(compensated.id).await.unwrap().into_iter().map(|t| t.trigger).collect();
+    assert_eq!(
+        triggers,
+        vec![
+            "SharesReserved",
+            "SharePaymentCompleted",
+            "Timeout",
+            "ReservationReleased",
+            "CompensationFailed",
+            "PaymentRefunded",
+            "CompensationCompleted",
+        ]
+    );
+}
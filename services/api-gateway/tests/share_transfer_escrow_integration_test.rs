@@ -0,0 +1,202 @@
+// =============================================================================
+// SHARE TRANSFER ESCROW INTEGRATION TESTS
+// =============================================================================
+//
+// Abrir el escrow descuenta las participaciones del vendedor en la misma
+// transacción: no se pueden vender dos veces, y cancelar las devuelve.
+
+use api_gateway::bounded_contexts::fan_ventures::domain::entities::{FanInvestment, InvestmentStatus, InvestmentType};
+use api_gateway::bounded_contexts::fan_ventures::domain::escrow::{EscrowStatus, EscrowedShare};
+use api_gateway::bounded_contexts::fan_ventures::domain::repositories::ShareEscrowRepository;
+use api_gateway::bounded_contexts::fan_ventures::domain::vesting::VestingType;
+use api_gateway::bounded_contexts::fan_ventures::infrastructure::PostgresShareEscrowRepository;
+use api_gateway::shared::domain::errors::AppError;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::{insert_user, setup_pool};
+use chrono::{Duration, Utc};
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Venture de `artist_id` con una inversión activa de `shares` para `seller_id`
+async fn insert_holding(pool: &PgPool, artist_id: Uuid, seller_id: Uuid, shares: f64) -> Uuid {
+    let venture_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO artist_ventures (artist_id, title, funding_goal, min_investment) VALUES ($1, 'Debut EP', 1000, 1) RETURNING id",
+    )
+    .bind(artist_id)
+    .fetch_one(pool)
+    .await
+    .expect("Venture inserted");
+
+    sqlx::query_scalar(
+        "INSERT INTO fan_investments (fan_id, venture_id, investment_amount, investment_type, status) VALUES ($1, $2, $3, 'revenue_share', 'active') RETURNING id",
+    )
+    .bind(seller_id)
+    .bind(venture_id)
+    .bind(shares)
+    .fetch_one(pool)
+    .await
+    .expect("Investment inserted")
+}
+
+async fn balance(pool: &PgPool, investment_id: Uuid) -> f64 {
+    sqlx::query_scalar("SELECT investment_amount FROM fan_investments WHERE id = $1")
+        .bind(investment_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_cancelled_escrow_restores_seller_and_completed_escrow_credits_buyer() {
+    let (_setup, pool) = setup_pool().await;
+    let artist = insert_user(&pool, "escrow_artist").await;
+    let seller = insert_user(&pool, "escrow_seller").await;
+    let buyer = insert_user(&pool, "escrow_buyer").await;
+    let holding_id = insert_holding(&pool, artist, seller, 10.0).await;
+    let repository = PostgresShareEscrowRepository::new(pool.clone());
+
+    let holding = repository.find_holding(&holding_id).await.unwrap().expect("Holding");
+    assert_eq!(holding.status, InvestmentStatus::Active);
+
+    // Pago fallido: las participaciones vuelven al vendedor
+    let mut failed = EscrowedShare::new(&holding, buyer, 4.0, 50.0, Duration::minutes(15), Utc::now()).unwrap();
+    repository.open(&failed).await.unwrap();
+    assert_eq!(balance(&pool, holding_id).await, 6.0);
+    failed.cancel(Utc::now()).unwrap();
+    repository.cancel(&failed, "Payment failed").await.unwrap();
+    assert_eq!(balance(&pool, holding_id).await, 10.0);
+    assert_eq!(repository.find_by_id(&failed.escrow_id).await.unwrap().unwrap().status, EscrowStatus::Cancelled);
+    // Un escrow ya resuelto no devuelve dos veces
+    assert!(matches!(repository.cancel(&failed, "Payment failed").await, Err(AppError::ConcurrencyConflict(_))));
+    assert_eq!(balance(&pool, holding_id).await, 10.0);
+
+    // Pago cobrado: pasan al comprador
+    let mut paid = EscrowedShare::new(&holding, buyer, 4.0, 50.0, Duration::minutes(15), Utc::now()).unwrap();
+    repository.open(&paid).await.unwrap();
+    paid.complete(Uuid::new_v4(), Utc::now()).unwrap();
+    let offered = FanInvestment::new(Uuid::new_v4(), buyer, holding.venture_id, 4.0, InvestmentType::RevenueShare, InvestmentStatus::Active);
+    let credited = repository.complete(&paid, &offered).await.unwrap();
+    assert_eq!(balance(&pool, holding_id).await, 6.0);
+    assert_eq!(balance(&pool, credited.id).await, 4.0);
+    assert_eq!(repository.find_by_id(&paid.escrow_id).await.unwrap().unwrap().status, EscrowStatus::Completed);
+
+    // Una segunda compra se acumula en la inversión que el comprador ya tiene
+    let mut again = EscrowedShare::new(&holding, buyer, 1.0, 12.5, Duration::minutes(15), Utc::now()).unwrap();
+    repository.open(&again).await.unwrap();
+    again.complete(Uuid::new_v4(), Utc::now()).unwrap();
+    let offered = FanInvestment::new(Uuid::new_v4(), buyer, holding.venture_id, 1.0, InvestmentType::RevenueShare, InvestmentStatus::Active);
+    let credited_again = repository.complete(&again, &offered).await.unwrap();
+    assert_eq!(credited_again.id, credited.id);
+    assert_eq!(credited_again.investment_amount, 5.0);
+}
+
+#[tokio::test]
+async fn test_concurrent_escrows_cannot_oversell_a_holding() {
+    let (_setup, pool) = setup_pool().await;
+    let artist = insert_user(&pool, "oversell_artist").await;
+    let seller = insert_user(&pool, "oversell_seller").await;
+    let holding_id = insert_holding(&pool, artist, seller, 10.0).await;
+    let repository = Arc::new(PostgresShareEscrowRepository::new(pool.clone()));
+    let holding = repository.find_holding(&holding_id).await.unwrap().unwrap();
+
+    let mut tasks = Vec::new();
+    for i in 0..5 {
+        let buyer = insert_user(&pool, &format!("oversell_buyer_{}", i)).await;
+        let escrow = EscrowedShare::new(&holding, buyer, 3.0, 30.0, Duration::minutes(15), Utc::now()).unwrap();
+        let repository = repository.clone();
+        tasks.push(tokio::spawn(async move { repository.open(&escrow).await }));
+    }
+    let mut opened = 0;
+    for task in tasks {
+        match task.await.unwrap() {
+            Ok(()) => opened += 1,
+            Err(AppError::DomainRuleViolation(_)) => {}
+            Err(e) => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    // 10 participaciones dan para tres ventas de 3
+    assert_eq!(opened, 3);
+    assert_eq!(balance(&pool, holding_id).await, 1.0);
+}
+
+#[tokio::test]
+async fn test_share_holder_carries_vesting_and_transferred_shares() {
+    let (_setup, pool) = setup_pool().await;
+    let artist = insert_user(&pool, "vesting_artist").await;
+    let seller = insert_user(&pool, "vesting_seller").await;
+    let buyer = insert_user(&pool, "vesting_buyer").await;
+    let holding_id = insert_holding(&pool, artist, seller, 10.0).await;
+    let repository = PostgresShareEscrowRepository::new(pool.clone());
+
+    // Sin calendario todo es transferible
+    let holder = repository.find_share_holder(&holding_id).await.unwrap().expect("Holder");
+    assert_eq!(holder.vesting_schedule, None);
+    assert_eq!(holder.transferable_shares_at(Utc::now()), 10.0);
+
+    let start_date = Utc::now() - Duration::days(180);
+    sqlx::query(
+        "UPDATE artist_ventures SET vesting_start_date = $2, vesting_cliff_days = 90, vesting_total_duration_days = 360, vesting_type = 'linear' WHERE id = $1",
+    )
+    .bind(holder.investment.venture_id)
+    .bind(start_date)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let escrow = EscrowedShare::new(&holder.investment, buyer, 2.0, 25.0, Duration::minutes(15), Utc::now()).unwrap();
+    repository.open(&escrow).await.unwrap();
+
+    // Las 2 en escrow cuentan como transferidas: de las 5 liberadas quedan 3
+    let holder = repository.find_share_holder(&holding_id).await.unwrap().unwrap();
+    let schedule = holder.vesting_schedule.clone().expect("Vesting schedule");
+    assert_eq!(schedule.vesting_type, VestingType::Linear);
+    assert_eq!((schedule.cliff_days, schedule.total_duration_days), (90, 360));
+    assert_eq!(schedule.start_date.timestamp(), start_date.timestamp());
+    assert_eq!(holder.transferred_shares, 2.0);
+    assert_eq!(holder.granted_shares(), 10.0);
+    assert!((holder.transferable_shares_at(start_date + Duration::days(180)) - 3.0).abs() < 1e-9);
+    assert!(holder.ensure_transferable(4.0, start_date + Duration::days(180)).is_err());
+}
+
+#[tokio::test]
+async fn test_concurrent_escrows_cannot_spend_unvested_shares() {
+    let (_setup, pool) = setup_pool().await;
+    let artist = insert_user(&pool, "unvested_artist").await;
+    let seller = insert_user(&pool, "unvested_seller").await;
+    let holding_id = insert_holding(&pool, artist, seller, 10.0).await;
+    let repository = Arc::new(PostgresShareEscrowRepository::new(pool.clone()));
+    let holding = repository.find_holding(&holding_id).await.unwrap().unwrap();
+
+    // A mitad del calendario lineal: 5 de las 10 están liberadas
+    sqlx::query(
+        "UPDATE artist_ventures SET vesting_start_date = $2, vesting_cliff_days = 90, vesting_total_duration_days = 360, vesting_type = 'linear' WHERE id = $1",
+    )
+    .bind(holding.venture_id)
+    .bind(Utc::now() - Duration::days(180))
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let mut tasks = Vec::new();
+    for i in 0..4 {
+        let buyer = insert_user(&pool, &format!("unvested_buyer_{}", i)).await;
+        let escrow = EscrowedShare::new(&holding, buyer, 2.0, 20.0, Duration::minutes(15), Utc::now()).unwrap();
+        let repository = repository.clone();
+        tasks.push(tokio::spawn(async move { repository.open(&escrow).await }));
+    }
+    let mut opened = 0;
+    for task in tasks {
+        match task.await.unwrap() {
+            Ok(()) => opened += 1,
+            Err(AppError::DomainRuleViolation(_)) => {}
+            Err(e) => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    // Hay saldo para las cuatro, pero solo 5 liberadas: dos ventas de 2
+    assert_eq!(opened, 2);
+    assert_eq!(balance(&pool, holding_id).await, 6.0);
+}
@@ -0,0 +1,118 @@
+// =============================================================================
+// SONG ANALYTICS INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// Los puntos se agregan sobre `listen_events` con DATE_TRUNC: la suma de los
+// buckets coincide con las escuchas del periodo, sin contar otras canciones ni
+// escuchas anteriores al periodo.
+
+use api_gateway::bounded_contexts::music::infrastructure::repositories::{
+    AnalyticsPeriod, Granularity, PostgresSongAnalyticsReadModel,
+};
+use api_gateway::shared::domain::errors::AppError;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::setup_pool;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn insert_song(pool: &PgPool, artist_id: Uuid, title: &str) -> Uuid {
+    sqlx::query_scalar("INSERT INTO songs (title, artist_id, duration_seconds) VALUES ($1, $2, 200) RETURNING id")
+        .bind(title)
+        .bind(artist_id)
+        .fetch_one(pool)
+        .await
+        .expect("Song inserted")
+}
+
+async fn insert_listeners(pool: &PgPool, count: usize) -> Vec<Uuid> {
+    let mut listeners = Vec::with_capacity(count);
+    for i in 0..count {
+        let user_id = Uuid::new_v4();
+        sqlx::query("INSERT INTO users (id, email, username, password_hash) VALUES ($1, $2, $3, 'hash')")
+            .bind(user_id)
+            .bind(format!("listener{}@example.com", i))
+            .bind(format!("listener_{}", i))
+            .execute(pool)
+            .await
+            .expect("User inserted");
+        listeners.push(user_id);
+    }
+    listeners
+}
+
+/// `count` escuchas repartidas en los últimos 28 días; las pares completan la
+/// canción (200 s) y las impares se quedan en 60 s
+async fn insert_listens(pool: &PgPool, song_id: Uuid, listeners: &[Uuid], count: i32, days_ago_offset: i32) {
+    sqlx::query(
+        r#"INSERT INTO listen_events (user_id, song_id, listen_duration_seconds, created_at)
+           SELECT ($2::uuid[])[1 + i % array_length($2::uuid[], 1)],
+                  $1,
+                  CASE WHEN i % 2 = 0 THEN 200 ELSE 60 END,
+                  NOW() - make_interval(days => $4 + i % 28, hours => i % 24)
+           FROM generate_series(1, $3) AS i"#,
+    )
+    .bind(song_id)
+    .bind(listeners)
+    .bind(count)
+    .bind(days_ago_offset)
+    .execute(pool)
+    .await
+    .expect("Listens inserted");
+}
+
+#[tokio::test]
+async fn test_daily_play_counts_sum_to_total() {
+    let (_setup, pool) = setup_pool().await;
+
+    let listeners = insert_listeners(&pool, 10).await;
+    let artist_id: Uuid = sqlx::query_scalar("INSERT INTO artists (user_id, stage_name) VALUES ($1, 'Analytics Artist') RETURNING id")
+        .bind(listeners[0])
+        .fetch_one(&pool)
+        .await
+        .expect("Artist inserted");
+    let song_id = insert_song(&pool, artist_id, "Measured").await;
+    let other_song = insert_song(&pool, artist_id, "Other").await;
+
+    insert_listens(&pool, song_id, &listeners, 1000, 0).await;
+    insert_listens(&pool, other_song, &listeners, 50, 0).await;
+    // Hace más de un año: fuera de cualquier periodo
+    insert_listens(&pool, song_id, &listeners, 20, 400).await;
+
+    let analytics = PostgresSongAnalyticsReadModel::new(pool.clone());
+
+    let daily = analytics
+        .get_play_count_by_period(song_id, AnalyticsPeriod::Month, Granularity::Daily)
+        .await
+        .unwrap();
+    assert!((29..=32).contains(&daily.len()), "one point per day, got {}", daily.len());
+    assert!(daily.windows(2).all(|pair| pair[0].period_start < pair[1].period_start));
+    assert_eq!(daily.iter().map(|point| point.play_count).sum::<u64>(), 1000);
+    assert!(daily.iter().all(|point| point.unique_listeners <= 10));
+    let completed: f64 = daily.iter().map(|point| point.completion_rate * point.play_count as f64).sum();
+    assert_eq!(completed.round() as u64, 500);
+    assert!(daily.iter().filter(|point| point.play_count == 0).all(|point| point.completion_rate == 0.0));
+
+    let weekly = analytics
+        .get_play_count_by_period(song_id, AnalyticsPeriod::Month, Granularity::Weekly)
+        .await
+        .unwrap();
+    assert_eq!(weekly.iter().map(|point| point.play_count).sum::<u64>(), 1000);
+
+    let yearly = analytics
+        .get_play_count_by_period(song_id, AnalyticsPeriod::Year, Granularity::Daily)
+        .await
+        .unwrap();
+    assert_eq!(yearly.iter().map(|point| point.play_count).sum::<u64>(), 1000);
+
+    let unknown = analytics
+        .get_play_count_by_period(Uuid::new_v4(), AnalyticsPeriod::Week, Granularity::Hourly)
+        .await
+        .unwrap();
+    assert!(unknown.iter().all(|point| point.play_count == 0));
+
+    assert!(matches!(
+        analytics.get_play_count_by_period(song_id, AnalyticsPeriod::Year, Granularity::Hourly).await,
+        Err(AppError::ValidationError(_))
+    ));
+}
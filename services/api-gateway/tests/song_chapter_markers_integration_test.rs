@@ -0,0 +1,91 @@
+// =============================================================================
+// SONG CHAPTER MARKERS INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// Tras subir el audio, las secciones se detectan en background y se guardan
+// como JSONB en la canción; un audio que no se puede decodificar la deja sin
+// marcadores.
+
+use api_gateway::bounded_contexts::music::domain::value_objects::{ChapterMarker, SongId};
+use api_gateway::bounded_contexts::music::infrastructure::repositories::PostgresSongRepository;
+use api_gateway::bounded_contexts::music::infrastructure::storage::WaveformGenerator;
+use bytes::Bytes;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::setup_pool;
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+const SECTIONS_FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/audio/sections.wav");
+
+async fn stored_markers(pool: &PgPool, song_id: &SongId) -> Option<Vec<ChapterMarker>> {
+    sqlx::query_scalar::<_, Option<sqlx::types::Json<Vec<ChapterMarker>>>>("SELECT chapter_markers FROM songs WHERE id = $1")
+        .bind(song_id.to_uuid())
+        .fetch_one(pool)
+        .await
+        .expect("Song found")
+        .map(|markers| markers.0)
+}
+
+async fn insert_song(pool: &PgPool) -> Uuid {
+    let user_id = Uuid::new_v4();
+    let tag = &user_id.simple().to_string()[..8];
+    sqlx::query("INSERT INTO users (id, email, username, password_hash) VALUES ($1, $2, $3, 'hash')")
+        .bind(user_id)
+        .bind(format!("chapters_{}@example.com", tag))
+        .bind(format!("chapters_{}", tag))
+        .execute(pool)
+        .await
+        .expect("User inserted");
+    let artist_id: Uuid = sqlx::query_scalar("INSERT INTO artists (user_id, stage_name) VALUES ($1, 'Chapters Artist') RETURNING id")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .expect("Artist inserted");
+    sqlx::query_scalar("INSERT INTO songs (title, artist_id, duration_seconds, genre) VALUES ('Sections', $1, 28, 'rock') RETURNING id")
+        .bind(artist_id)
+        .fetch_one(pool)
+        .await
+        .expect("Song inserted")
+}
+
+#[tokio::test]
+async fn test_chapter_markers_are_detected_after_upload_and_stored() {
+    let (_setup, pool) = setup_pool().await;
+    let repository = Arc::new(PostgresSongRepository::new(pool.clone()));
+
+    let song_id = SongId::from_uuid(insert_song(&pool).await);
+    assert!(stored_markers(&pool, &song_id).await.is_none());
+
+    let audio = Bytes::from(std::fs::read(SECTIONS_FIXTURE).unwrap());
+    WaveformGenerator::spawn_chapter_detection(repository.clone(), song_id.clone(), audio, "wav".to_string())
+        .await
+        .unwrap();
+
+    let expected = vec![
+        ChapterMarker::new(0, "intro"),
+        ChapterMarker::new(8_500, "verse"),
+        ChapterMarker::new(16_500, "chorus"),
+        ChapterMarker::new(24_500, "outro"),
+    ];
+    assert_eq!(stored_markers(&pool, &song_id).await, Some(expected.clone()));
+
+    let stored: serde_json::Value = sqlx::query_scalar("SELECT chapter_markers FROM songs WHERE id = $1")
+        .bind(song_id.to_uuid())
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(stored[1], serde_json::json!({ "position_ms": 8500, "label": "verse" }));
+
+    // Un fichero que no es audio no toca los marcadores ya guardados
+    WaveformGenerator::spawn_chapter_detection(
+        repository.clone(),
+        song_id.clone(),
+        Bytes::from_static(b"not audio"),
+        "wav".to_string(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(stored_markers(&pool, &song_id).await, Some(expected));
+}
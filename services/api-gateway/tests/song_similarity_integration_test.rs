@@ -0,0 +1,70 @@
+// =============================================================================
+// SONG SIMILARITY INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// Al subir una canción se guardan sus vecinos en `song_similarities` en ambos
+// sentidos; las del mismo género y mood quedan por delante del resto.
+
+use api_gateway::bounded_contexts::music::infrastructure::repositories::PostgresSongSimilarityReadModel;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::setup_pool;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+async fn insert_song(pool: &PgPool, artist_id: Uuid, title: &str, genre: &str, mood: &str, bpm: i16, quality: &str) -> Uuid {
+    sqlx::query_scalar(
+        r#"INSERT INTO songs (title, artist_id, duration_seconds, genre, mood, tempo_bpm, audio_quality)
+           VALUES ($1, $2, 200, $3, $4, $5, $6) RETURNING id"#,
+    )
+    .bind(title)
+    .bind(artist_id)
+    .bind(genre)
+    .bind(mood)
+    .bind(bpm)
+    .bind(quality)
+    .fetch_one(pool)
+    .await
+    .expect("Song inserted")
+}
+
+#[tokio::test]
+async fn test_same_genre_and_mood_ranks_above_other_genres() {
+    let (_setup, pool) = setup_pool().await;
+
+    let user_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO users (id, email, username, password_hash) VALUES ($1, 'similar@example.com', 'similar_artist', 'hash')")
+        .bind(user_id)
+        .execute(&pool)
+        .await
+        .expect("User inserted");
+    let artist_id: Uuid = sqlx::query_scalar("INSERT INTO artists (user_id, stage_name) VALUES ($1, 'Similar Artist') RETURNING id")
+        .bind(user_id)
+        .fetch_one(&pool)
+        .await
+        .expect("Artist inserted");
+
+    let same_style = insert_song(&pool, artist_id, "Same Style", "rock", "Energetic", 150, "medium").await;
+    let other_genre = insert_song(&pool, artist_id, "Other Genre", "classical", "Energetic", 130, "high").await;
+    let other_everything = insert_song(&pool, artist_id, "Other Everything", "ambient", "Calm", 70, "low").await;
+    let uploaded = insert_song(&pool, artist_id, "Uploaded", "rock", "Energetic", 130, "high").await;
+
+    let read_model = PostgresSongSimilarityReadModel::new(pool.clone());
+    assert_eq!(read_model.refresh_song(uploaded).await.unwrap(), 3);
+
+    let similar = read_model.get_similar_songs(uploaded, 10).await.unwrap();
+    let ids: Vec<Uuid> = similar.iter().map(|song| song.id).collect();
+    assert_eq!(ids, vec![same_style, other_genre, other_everything]);
+    assert!(similar[0].relevance_score > similar[1].relevance_score);
+    assert_eq!(similar[0].artist_name, "Similar Artist");
+    assert_eq!(similar[0].audio_quality.as_deref(), Some("medium"));
+
+    // La canción subida también aparece como similar de las demás
+    let reverse = read_model.get_similar_songs(same_style, 10).await.unwrap();
+    assert_eq!(reverse.len(), 1);
+    assert_eq!(reverse[0].id, uploaded);
+    assert!((reverse[0].relevance_score - similar[0].relevance_score).abs() < 1e-6);
+
+    // El límite se respeta
+    assert_eq!(read_model.get_similar_songs(uploaded, 1).await.unwrap().len(), 1);
+}
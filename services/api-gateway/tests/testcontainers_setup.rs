@@ -6,8 +6,10 @@
 use testcontainers::{clients, Container, RunnableImage};
 use testcontainers_modules::postgres::Postgres;
 use testcontainers_modules::redis::Redis;
+use sqlx::PgPool;
 use std::time::Duration;
 use tokio::time::sleep;
+use uuid::Uuid;
 
 /// Configuración de servicios de test usando testcontainers
 pub struct TestContainersSetup {
@@ -149,6 +151,31 @@ impl Default for TestContainersSetup {
     }
 }
 
+/// Levanta los contenedores, ejecuta las migraciones y devuelve un pool contra
+/// PostgreSQL. Hay que conservar el `TestContainersSetup`: al soltarlo se paran
+/// los contenedores.
+pub async fn setup_pool() -> (TestContainersSetup, PgPool) {
+    let setup = TestContainersSetup::new();
+    setup.setup_env();
+    setup.wait_for_postgres().await.expect("Postgres failed to start");
+    setup.run_migrations().await.expect("Migrations failed");
+    let pool = PgPool::connect(&setup.get_postgres_url()).await.expect("Failed to connect to pool");
+    (setup, pool)
+}
+
+/// Inserta un usuario mínimo con email `<username>@example.com`
+pub async fn insert_user(pool: &PgPool, username: &str) -> Uuid {
+    let user_id = Uuid::new_v4();
+    sqlx::query("INSERT INTO users (id, email, username, password_hash) VALUES ($1, $2, $3, 'hash')")
+        .bind(user_id)
+        .bind(format!("{}@example.com", username))
+        .bind(username)
+        .execute(pool)
+        .await
+        .expect("User inserted");
+    user_id
+}
+
 /// Helper macro para tests con testcontainers
 #[macro_export]
 macro_rules! test_with_containers {
@@ -0,0 +1,225 @@
+// =============================================================================
+// ACCOUNT DELETION & DATA EXPORT INTEGRATION TESTS (GDPR)
+// =============================================================================
+//
+// `UserDeletionRequested` llega a los listeners de cada contexto: los pagos
+// pasan al pseudónimo y las playlists se borran. La exportación de datos de un
+// usuario no incluye nada de la otra parte de sus pagos. En las transferencias
+// de participaciones el usuario eliminado pasa al pseudónimo en ambos lados.
+
+use api_gateway::bounded_contexts::fan_ventures::infrastructure::HoldingsUserDeletionListener;
+use api_gateway::bounded_contexts::orchestrator::{
+    DomainEvent, EventBus, EventBusFactory, EventHandler, InMemoryEventBus, DELETED_USER_ID,
+};
+use api_gateway::bounded_contexts::payment::domain::{
+    aggregates::PaymentAggregate,
+    repository::PaymentRepository,
+    value_objects::{Amount, Currency, FeePercentage, PaymentMetadata, PaymentMethod, PaymentPurpose},
+};
+use api_gateway::bounded_contexts::payment::infrastructure::repositories::PostgresPaymentRepository;
+use api_gateway::bounded_contexts::user::domain::{aggregates::UserAggregate, repository::UserRepository};
+use api_gateway::bounded_contexts::user::infrastructure::data_export::PostgresUserDataExports;
+use api_gateway::shared::infrastructure::database::postgres::PostgresUserRepository;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::{insert_user, setup_pool};
+use chrono::Utc;
+use sqlx::PgPool;
+use std::sync::Arc;
+use uuid::Uuid;
+
+async fn insert_song_purchase(pool: &PgPool, payer_id: Uuid, payee_id: Uuid) -> Uuid {
+    let payment = PaymentAggregate::create_payment(
+        payer_id,
+        payee_id,
+        Amount::new(1.99, Currency::USD).unwrap(),
+        PaymentMethod::PlatformBalance,
+        PaymentPurpose::SongPurchase { song_id: Uuid::new_v4() },
+        FeePercentage::new(10.0).unwrap(),
+        PaymentMetadata {
+            user_ip: Some("203.0.113.7".to_string()),
+            user_agent: Some("Mozilla/5.0".to_string()),
+            platform_version: "1.0.0".to_string(),
+            reference_id: None,
+            additional_data: serde_json::json!({}),
+        },
+    )
+    .unwrap();
+    PostgresPaymentRepository::new(pool.clone()).save(&payment).await.expect("Payment saved");
+    *payment.payment().id().value()
+}
+
+#[tokio::test]
+async fn test_deletion_event_reaches_every_context() {
+    let (_setup, pool) = setup_pool().await;
+    let fan = insert_user(&pool, "leaving_fan").await;
+    let artist = insert_user(&pool, "staying_artist").await;
+    let payment_id = insert_song_purchase(&pool, fan, artist).await;
+    sqlx::query("INSERT INTO playlists (user_id, title) VALUES ($1, 'Favoritas')")
+        .bind(fan)
+        .execute(&pool)
+        .await
+        .unwrap();
+    let distribution_id: Uuid = sqlx::query_scalar(
+        r#"INSERT INTO reward_distributions (pool_id, total_tokens, validation_period_start, validation_period_end, user_id, user_share)
+           VALUES ($1, 100, NOW() - INTERVAL '1 day', NOW(), $2, 5) RETURNING id"#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(fan)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+
+    // El servicio guarda el pseudónimo antes de publicar el evento
+    let now = Utc::now();
+    let pseudonym = UserAggregate::deleted_user_pseudonym(now).unwrap();
+    let pseudonym_id = pseudonym.user.id.value();
+    PostgresUserRepository::new(Arc::new(pool.clone())).save(&pseudonym).await.expect("Pseudonym saved");
+
+    let event_bus = InMemoryEventBus::new();
+    EventBusFactory::register_user_deletion_handlers(&event_bus, pool.clone()).await.unwrap();
+    event_bus
+        .publish(DomainEvent::UserDeletionRequested { user_id: fan, pseudonym_id, occurred_at: now })
+        .await
+        .unwrap();
+
+    // El pago se conserva para la contabilidad del artista, sin el fan ni su IP
+    let (payer_id, payee_id, metadata): (Uuid, Uuid, serde_json::Value) =
+        sqlx::query_as("SELECT payer_id, payee_id, metadata FROM payments WHERE id = $1")
+            .bind(payment_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(payer_id, pseudonym_id);
+    assert_eq!(payee_id, artist);
+    assert!(metadata.get("user_ip").map_or(true, |ip| ip.is_null()));
+
+    let remaining_payments: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM payments WHERE payer_id = $1 OR payee_id = $1")
+        .bind(fan)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(remaining_payments, 0);
+
+    let playlists: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM playlists WHERE user_id = $1")
+        .bind(fan)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(playlists, 0);
+
+    // La recompensa sigue en el reparto del pool, sin ligarse al fan
+    let rewarded: Uuid = sqlx::query_scalar("SELECT user_id FROM reward_distributions WHERE id = $1")
+        .bind(distribution_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(rewarded, DELETED_USER_ID);
+}
+
+/// Escrow completado, con su operación en el mercado secundario, de 2 participaciones de `seller_id` a `buyer_id` en un venture de `artist_id`
+async fn insert_share_escrow(pool: &PgPool, artist_id: Uuid, seller_id: Uuid, buyer_id: Uuid) -> Uuid {
+    let venture_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO artist_ventures (artist_id, title, funding_goal, min_investment) VALUES ($1, 'Debut EP', 1000, 1) RETURNING id",
+    )
+    .bind(artist_id)
+    .fetch_one(pool)
+    .await
+    .expect("Venture inserted");
+    let investment_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO fan_investments (fan_id, venture_id, investment_amount, investment_type, status) VALUES ($1, $2, 10, 'revenue_share', 'active') RETURNING id",
+    )
+    .bind(seller_id)
+    .bind(venture_id)
+    .fetch_one(pool)
+    .await
+    .expect("Investment inserted");
+
+    let escrow_id = Uuid::new_v4();
+    sqlx::query(
+        r#"INSERT INTO share_escrows (escrow_id, venture_id, seller_id, seller_investment_id, buyer_id, quantity, price, status, expires_at)
+           VALUES ($1, $2, $3, $4, $5, 2, 20, 'completed', NOW())"#,
+    )
+    .bind(escrow_id)
+    .bind(venture_id)
+    .bind(seller_id)
+    .bind(investment_id)
+    .bind(buyer_id)
+    .execute(pool)
+    .await
+    .expect("Escrow inserted");
+    sqlx::query(
+        r#"INSERT INTO secondary_market_trades (id, venture_id, seller_id, buyer_id, shares_quantity, price_per_share, executed_at)
+           VALUES ($1, $2, $3, $4, 2, 10, NOW())"#,
+    )
+    .bind(escrow_id)
+    .bind(venture_id)
+    .bind(seller_id)
+    .bind(buyer_id)
+    .execute(pool)
+    .await
+    .expect("Trade inserted");
+    escrow_id
+}
+
+#[tokio::test]
+async fn test_deleted_user_is_pseudonymized_on_both_sides_of_share_transfers() {
+    let (_setup, pool) = setup_pool().await;
+    let fan = insert_user(&pool, "leaving_trader").await;
+    let other = insert_user(&pool, "staying_trader").await;
+    let artist = insert_user(&pool, "traded_artist").await;
+    let sold = insert_share_escrow(&pool, artist, fan, other).await;
+    let bought = insert_share_escrow(&pool, artist, other, fan).await;
+
+    let now = Utc::now();
+    let pseudonym = UserAggregate::deleted_user_pseudonym(now).unwrap();
+    let pseudonym_id = pseudonym.user.id.value();
+    PostgresUserRepository::new(Arc::new(pool.clone())).save(&pseudonym).await.expect("Pseudonym saved");
+
+    HoldingsUserDeletionListener::new(pool.clone())
+        .handle(&DomainEvent::UserDeletionRequested { user_id: fan, pseudonym_id, occurred_at: now })
+        .await
+        .expect("Holdings pseudonymized");
+
+    let parties = |escrow_id: Uuid| {
+        let pool = pool.clone();
+        async move {
+            sqlx::query_as::<_, (Uuid, Uuid)>("SELECT seller_id, buyer_id FROM share_escrows WHERE escrow_id = $1")
+                .bind(escrow_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+        }
+    };
+    let trade_parties = |trade_id: Uuid| {
+        let pool = pool.clone();
+        async move {
+            sqlx::query_as::<_, (Uuid, Uuid)>("SELECT seller_id, buyer_id FROM secondary_market_trades WHERE id = $1")
+                .bind(trade_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+        }
+    };
+    assert_eq!(parties(sold).await, (pseudonym_id, other));
+    assert_eq!(parties(bought).await, (other, pseudonym_id));
+    assert_eq!(trade_parties(sold).await, (pseudonym_id, other));
+    assert_eq!(trade_parties(bought).await, (other, pseudonym_id));
+}
+
+#[tokio::test]
+async fn test_export_contains_no_other_users_data() {
+    let (_setup, pool) = setup_pool().await;
+    let fan = insert_user(&pool, "exporting_fan").await;
+    let artist = insert_user(&pool, "paid_artist").await;
+    insert_song_purchase(&pool, fan, artist).await;
+
+    let archive = PostgresUserDataExports::new(pool.clone()).assemble(fan).await.expect("Archive assembled");
+
+    assert_eq!(archive["profile"]["email"], "exporting_fan@example.com");
+    assert_eq!(archive["payments"].as_array().unwrap().len(), 1);
+    assert_eq!(archive["payments"][0]["direction"], "sent");
+    let serialized = archive.to_string();
+    assert!(!serialized.contains(&artist.to_string()));
+    assert!(!serialized.contains("paid_artist"));
+}
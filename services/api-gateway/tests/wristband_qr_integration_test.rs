@@ -0,0 +1,52 @@
+// =============================================================================
+// WRISTBAND QR INTEGRATION TESTS (Postgres)
+// =============================================================================
+//
+// Un QR firmado solo se acepta en el primer escaneo; el segundo devuelve
+// cuándo se usó y un código que nunca se emitió se rechaza.
+
+use api_gateway::bounded_contexts::fan_loyalty::domain::entities::{QrCodeUse, WristbandId};
+use api_gateway::bounded_contexts::fan_loyalty::domain::repositories::QrCodeRepository;
+use api_gateway::bounded_contexts::fan_loyalty::infrastructure::postgres_repositories::PostgresQrCodeRepository;
+use api_gateway::bounded_contexts::fan_loyalty::infrastructure::qr_service::QrCodeSigner;
+#[path = "testcontainers_setup.rs"]
+mod testcontainers_setup;
+use testcontainers_setup::setup_pool;
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+
+#[tokio::test]
+async fn test_qr_code_is_accepted_only_on_first_scan() {
+    let (_setup, pool) = setup_pool().await;
+
+    let wristband_id: Uuid = sqlx::query_scalar(
+        r#"INSERT INTO nft_wristbands (fan_id, concert_id, artist_id, wristband_type, is_active, activated_at)
+           VALUES ($1, 'concert_qr', 'artist_qr', 'VIP', TRUE, NOW()) RETURNING id"#,
+    )
+    .bind(Uuid::new_v4())
+    .fetch_one(&pool)
+    .await
+    .expect("Wristband inserted");
+    let wristband_id = WristbandId(wristband_id);
+
+    let now = Utc::now();
+    let signer = QrCodeSigner::new("wristband-qr-test-secret", Duration::minutes(5));
+    let (code, claims) = signer.issue(&wristband_id, now);
+    assert_eq!(signer.verify(&code, now).unwrap(), claims);
+
+    let repository = PostgresQrCodeRepository::new(pool.clone());
+    repository.save_qr_code(&wristband_id, &code, claims.expires_at).await.unwrap();
+
+    let first_scan = now + Duration::seconds(10);
+    assert_eq!(repository.use_qr_code(&code, first_scan).await.unwrap(), QrCodeUse::Accepted);
+
+    // El segundo escaneo informa del uso anterior
+    match repository.use_qr_code(&code, first_scan + Duration::seconds(5)).await.unwrap() {
+        QrCodeUse::AlreadyUsed(used_at) => assert!((used_at - first_scan).num_milliseconds().abs() < 1),
+        other => panic!("Expected AlreadyUsed, got {:?}", other),
+    }
+
+    // Un código firmado pero nunca guardado no se acepta
+    let (unsaved, _) = signer.issue(&wristband_id, now);
+    assert_eq!(repository.use_qr_code(&unsaved, first_scan).await.unwrap(), QrCodeUse::Unknown);
+}
@@ -1,10 +1,19 @@
+use async_trait::async_trait;
+use ethers::contract::{parse_log, EthEvent};
 use ethers::prelude::*;
-use ethers::providers::{Provider, Http};
+use ethers::providers::{Provider, Http, Ws};
 use ethers::signers::{LocalWallet, Signer};
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 use vibestream_types::*;
 
+/// Espera antes de volver a suscribirse cuando se cae el WebSocket
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TransactionInfo {
     pub hash: String,
@@ -24,9 +33,70 @@ pub struct TokenInfo {
     pub total_supply: U256,
 }
 
+/// Transferencia ERC-20 recibida en la dirección vigilada
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenTransferEvent {
+    pub from: String,
+    pub to: String,
+    pub amount: U256,
+    pub tx_hash: String,
+    pub block_number: u64,
+}
+
+/// `Transfer(address,address,uint256)` del ABI ERC-20
+#[derive(Debug, Clone, EthEvent)]
+#[ethevent(name = "Transfer", abi = "Transfer(address,address,uint256)")]
+struct Erc20Transfer {
+    #[ethevent(indexed)]
+    from: Address,
+    #[ethevent(indexed)]
+    to: Address,
+    value: U256,
+}
+
+/// Origen de logs de una suscripción `eth_subscribe logs`. Cada llamada abre
+/// una conexión nueva y reenvía los logs hasta que se cae; en tests se
+/// sustituye por logs de fixture.
+#[async_trait]
+pub trait LogSubscription: Send + Sync {
+    async fn forward_logs(&self, filter: Filter, logs: mpsc::UnboundedSender<Log>) -> Result<()>;
+}
+
+/// Suscripción real por WebSocket
+pub struct WsLogSubscription {
+    ws_url: String,
+}
+
+#[async_trait]
+impl LogSubscription for WsLogSubscription {
+    async fn forward_logs(&self, filter: Filter, logs: mpsc::UnboundedSender<Log>) -> Result<()> {
+        let provider = Provider::<Ws>::connect(self.ws_url.as_str())
+            .await
+            .map_err(|e| VibeStreamError::Network {
+                message: format!("Failed to connect to WebSocket: {}", e)
+            })?;
+
+        // Los nodos ignoran `fromBlock` en `eth_subscribe`: lo anterior a la
+        // suscripción (o perdido mientras estaba caída) se pide con `eth_getLogs`
+        let mut stream = provider.subscribe_logs(&filter).await?;
+        for log in provider.get_logs(&filter).await? {
+            if logs.send(log).is_err() {
+                return Ok(());
+            }
+        }
+        while let Some(log) = stream.next().await {
+            if logs.send(log).is_err() {
+                break;
+            }
+        }
+        Ok(())
+    }
+}
+
 pub struct EthereumClient {
     provider: Arc<Provider<Http>>,
     wallet: LocalWallet,
+    ws_url: String,
 }
 
 impl EthereumClient {
@@ -41,11 +111,20 @@ impl EthereumClient {
                 message: format!("Invalid private key: {}", e) 
             })?;
         
+        // Mismo nodo por WebSocket salvo que se indique otro con `with_ws_url`
+        let ws_url = rpc_url_to_ws(&rpc_url);
+
         Ok(Self {
             provider: Arc::new(provider),
             wallet,
+            ws_url,
         })
     }
+
+    pub fn with_ws_url(mut self, ws_url: String) -> Self {
+        self.ws_url = ws_url;
+        self
+    }
     
     pub async fn get_balance(&self, address: &str) -> Result<u64> {
         let address: Address = address.parse()
@@ -53,10 +132,7 @@ impl EthereumClient {
                 message: format!("Invalid address: {}", e) 
             })?;
         
-        let balance = self.provider.get_balance(address, None).await
-            .map_err(|e| VibeStreamError::Network { 
-                message: format!("Failed to get balance: {}", e) 
-            })?;
+        let balance = self.provider.get_balance(address, None).await?;
         
         // Convert to u64 (this might overflow for very large balances)
         Ok(balance.as_u64())
@@ -135,4 +211,254 @@ impl EthereumClient {
             status: "pending".to_string(),
         })
     }
-} 
\ No newline at end of file
+
+    /// Vigila los `Transfer` ERC-20 de `token_address` hacia `to_address` a
+    /// partir de `from_block` y llama a `callback` con cada uno. Si el
+    /// WebSocket se cae vuelve a suscribirse desde el último bloque visto, sin
+    /// repetir eventos. Abortar el `JoinHandle` detiene la vigilancia.
+    pub async fn watch_token_transfer_events(
+        &self,
+        token_address: &str,
+        to_address: &str,
+        from_block: u64,
+        callback: impl Fn(TokenTransferEvent) + Send + 'static,
+    ) -> Result<JoinHandle<()>> {
+        let subscription = Arc::new(WsLogSubscription { ws_url: self.ws_url.clone() });
+        watch_token_transfers(subscription, token_address, to_address, from_block, callback, RECONNECT_DELAY)
+    }
+}
+
+fn rpc_url_to_ws(rpc_url: &str) -> String {
+    if let Some(rest) = rpc_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = rpc_url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        rpc_url.to_string()
+    }
+}
+
+/// Decodifica un log `Transfer`; `None` si no es un `Transfer` ERC-20 con
+/// `from` y `to` indexados (p.ej. un NFT ERC-721) o le falta la transacción
+fn decode_transfer_log(log: Log) -> Option<TokenTransferEvent> {
+    let tx_hash = log.transaction_hash?;
+    let block_number = log.block_number?.as_u64();
+    let transfer: Erc20Transfer = parse_log(log).ok()?;
+    Some(TokenTransferEvent {
+        from: format!("{:?}", transfer.from),
+        to: format!("{:?}", transfer.to),
+        amount: transfer.value,
+        tx_hash: format!("{:?}", tx_hash),
+        block_number,
+    })
+}
+
+fn watch_token_transfers(
+    subscription: Arc<dyn LogSubscription>,
+    token_address: &str,
+    to_address: &str,
+    from_block: u64,
+    callback: impl Fn(TokenTransferEvent) + Send + 'static,
+    reconnect_delay: Duration,
+) -> Result<JoinHandle<()>> {
+    let token: Address = token_address.parse()
+        .map_err(|e| VibeStreamError::Validation {
+            message: format!("Invalid token address: {}", e)
+        })?;
+    let to: Address = to_address.parse()
+        .map_err(|e| VibeStreamError::Validation {
+            message: format!("Invalid to address: {}", e)
+        })?;
+    let filter = Filter::new()
+        .address(token)
+        .event(&Erc20Transfer::abi_signature())
+        .topic2(H256::from(to));
+
+
+    Ok(tokio::spawn(async move {
+        // (bloque, índice) del último log entregado. Se reanuda desde ese
+        // bloque, así que sus logs llegan otra vez y se descartan
+        let mut last_seen: Option<(u64, U256)> = None;
+        loop {
+            let resume_from = last_seen.map_or(from_block, |(block, _)| block);
+            let (sender, mut receiver) = mpsc::unbounded_channel();
+            let forward = subscription.forward_logs(filter.clone().from_block(resume_from), sender);
+            tokio::pin!(forward);
+            let result = loop {
+                tokio::select! {
+                    Some(log) = receiver.recv() => {
+                        if let Some(event) = accept_transfer_log(log, token, to, &mut last_seen) {
+                            callback(event);
+                        }
+                    }
+                    result = &mut forward => break result,
+                }
+            };
+            while let Ok(log) = receiver.try_recv() {
+                if let Some(event) = accept_transfer_log(log, token, to, &mut last_seen) {
+                    callback(event);
+                }
+            }
+            if let Err(e) = result {
+                eprintln!("Transfer subscription for {:?} failed: {}", to, e);
+            }
+            tokio::time::sleep(reconnect_delay).await;
+        }
+    }))
+}
+
+fn accept_transfer_log(
+    log: Log,
+    token: Address,
+    to: Address,
+    last_seen: &mut Option<(u64, U256)>,
+) -> Option<TokenTransferEvent> {
+    // Logs deshechos por una reorganización
+    if log.removed == Some(true) || log.address != token {
+        return None;
+    }
+    let position = (log.block_number?.as_u64(), log.log_index.unwrap_or_default());
+    if last_seen.is_some_and(|seen| position <= seen) {
+        return None;
+    }
+    let event = decode_transfer_log(log)?;
+    if event.to != format!("{:?}", to) {
+        return None;
+    }
+    *last_seen = Some(position);
+    Some(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    const TOKEN: &str = "0xdac17f958d2ee523a2206206994597c13d831ec7";
+    const MERCHANT: &str = "0x742d35cc6634c0532925a3b844bc454e4438f44e";
+    const PAYER: &str = "0x8ba1f109551bd432803012645ac136ddd64dba72";
+    const OTHER: &str = "0x1111111111111111111111111111111111111111";
+
+    /// Log tal y como llega en una notificación `eth_subscription`
+    fn transfer_log(block: u64, log_index: u64, to: &str, amount: u64) -> Log {
+        serde_json::from_value(serde_json::json!({
+            "address": TOKEN,
+            "topics": [
+                "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef",
+                format!("0x000000000000000000000000{}", &PAYER[2..]),
+                format!("0x000000000000000000000000{}", &to[2..]),
+            ],
+            "data": format!("0x{:064x}", amount),
+            "blockNumber": format!("{:#x}", block),
+            "blockHash": format!("0x{:064x}", block),
+            "transactionHash": format!("0x{:062x}{:02x}", block, log_index),
+            "transactionIndex": "0x0",
+            "logIndex": format!("{:#x}", log_index),
+            "removed": false
+        }))
+        .unwrap()
+    }
+
+    /// Cada llamada es una conexión: entrega sus logs y se cae. Sin más
+    /// conexiones queda abierta sin recibir nada.
+    struct FixtureSubscription {
+        connections: Mutex<VecDeque<Vec<Log>>>,
+        from_blocks: Mutex<Vec<u64>>,
+    }
+
+    #[async_trait]
+    impl LogSubscription for FixtureSubscription {
+        async fn forward_logs(&self, filter: Filter, logs: mpsc::UnboundedSender<Log>) -> Result<()> {
+            self.from_blocks.lock().unwrap().push(filter.get_from_block().unwrap().as_u64());
+            let connection = self.connections.lock().unwrap().pop_front();
+            let Some(connection) = connection else {
+                return std::future::pending().await;
+            };
+            for log in connection {
+                let _ = logs.send(log);
+            }
+            Err(VibeStreamError::Network { message: "WebSocket connection reset".to_string() })
+        }
+    }
+
+    #[test]
+    fn decodes_erc20_transfer_logs() {
+        let event = decode_transfer_log(transfer_log(16, 2, MERCHANT, 2_500_000)).unwrap();
+        assert_eq!(event.from, PAYER);
+        assert_eq!(event.to, MERCHANT);
+        assert_eq!(event.amount, U256::from(2_500_000u64));
+        assert_eq!(event.block_number, 16);
+        assert_eq!(event.tx_hash, format!("0x{:062x}02", 16));
+
+        // ERC-721: mismo topic pero con el tokenId indexado y sin datos
+        let mut nft = transfer_log(16, 3, MERCHANT, 0);
+        nft.topics.push(H256::from_low_u64_be(7));
+        nft.data = Bytes::default();
+        assert!(decode_transfer_log(nft).is_none());
+    }
+
+    #[tokio::test]
+    async fn reconnects_after_drops_without_repeating_transfers() {
+        let removed = Log { removed: Some(true), ..transfer_log(18, 1, MERCHANT, 999) };
+        let subscription = Arc::new(FixtureSubscription {
+            connections: Mutex::new(VecDeque::from(vec![
+                vec![
+                    transfer_log(16, 0, MERCHANT, 100),
+                    transfer_log(16, 1, OTHER, 200),
+                    transfer_log(17, 3, MERCHANT, 300),
+                ],
+                // Tras reconectar desde el bloque 17 el nodo repite su log
+                vec![transfer_log(17, 3, MERCHANT, 300), transfer_log(18, 0, MERCHANT, 400), removed],
+            ])),
+            from_blocks: Mutex::new(Vec::new()),
+        });
+
+        let (events, mut received) = mpsc::unbounded_channel();
+        let handle = watch_token_transfers(
+            subscription.clone(),
+            TOKEN,
+            MERCHANT,
+            15,
+            move |event| events.send(event).unwrap(),
+            Duration::from_millis(10),
+        )
+        .unwrap();
+
+        let mut amounts = Vec::new();
+        for _ in 0..3 {
+            let event = tokio::time::timeout(Duration::from_secs(2), received.recv()).await.unwrap().unwrap();
+            assert_eq!(event.to, MERCHANT);
+            amounts.push((event.block_number, event.amount.as_u64()));
+        }
+        assert_eq!(amounts, vec![(16, 100), (17, 300), (18, 400)]);
+
+        tokio::time::timeout(Duration::from_secs(2), async {
+            while subscription.from_blocks.lock().unwrap().len() < 3 {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(*subscription.from_blocks.lock().unwrap(), vec![15, 17, 18]);
+        assert!(received.try_recv().is_err());
+
+        handle.abort();
+    }
+
+    #[test]
+    fn rejects_invalid_addresses() {
+        let subscription = Arc::new(FixtureSubscription {
+            connections: Mutex::new(VecDeque::new()),
+            from_blocks: Mutex::new(Vec::new()),
+        });
+        let result = watch_token_transfers(subscription, "0xnot-a-token", MERCHANT, 0, |_| {}, RECONNECT_DELAY);
+        assert!(matches!(result, Err(VibeStreamError::Validation { .. })));
+    }
+
+    #[test]
+    fn websocket_url_follows_the_rpc_url() {
+        assert_eq!(rpc_url_to_ws("https://mainnet.infura.io/v3/key"), "wss://mainnet.infura.io/v3/key");
+        assert_eq!(rpc_url_to_ws("http://localhost:8545"), "ws://localhost:8545");
+    }
+}
@@ -1,48 +1,697 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use futures_util::{SinkExt, StreamExt};
+use solana_client::client_error::{ClientError, ClientErrorKind};
 use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_request::RpcError;
 use solana_sdk::{
+    instruction::Instruction,
+    program_pack::Pack,
     pubkey::Pubkey,
     signature::{Keypair, Signature, Signer},
+    system_instruction,
     transaction::Transaction,
 };
+use solana_sdk::commitment_config::CommitmentConfig;
+use tokio::sync::{oneshot, RwLock};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::tungstenite::Message;
 use vibestream_types::*;
 
+use crate::confirmation::{ConfirmedTransactionWatcher, SignatureStatus, SignatureStatusSource, TransactionFinalized};
+use crate::error::WalletError;
+
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_millis(500);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+/// Límite de cuentas por llamada a `getMultipleAccounts`
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+/// Código JSON-RPC "Invalid params" con el que el nodo responde si la cuenta no existe
+const INVALID_PARAMS_CODE: i64 = -32602;
+/// La renta apenas cambia: se cachea por tamaño de cuenta
+const RENT_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Tamaño en bytes de una cuenta mint SPL, para consultar su renta
+pub const MINT_ACCOUNT_RENT: usize = spl_token::state::Mint::LEN;
+/// Tamaño en bytes de una token account SPL
+pub const TOKEN_ACCOUNT_RENT: usize = spl_token::state::Account::LEN;
+/// Tamaño máximo de la cuenta de metadata de Metaplex (`MAX_METADATA_LEN`)
+pub const METADATA_ACCOUNT_RENT: usize = 679;
+
+type RentCache = Arc<RwLock<HashMap<usize, (u64, Instant)>>>;
+
 pub struct SolanaClient {
     rpc_client: RpcClient,
+    ws_url: String,
     keypair: Keypair,
+    rent_cache: RentCache,
 }
 
 impl SolanaClient {
     pub fn new(rpc_url: String, private_key_bytes: Vec<u8>) -> Result<Self> {
+        let ws_url = derive_ws_url(&rpc_url);
         let rpc_client = RpcClient::new(rpc_url);
-        
+
         let keypair = Keypair::from_bytes(&private_key_bytes)
-            .map_err(|e| VibeStreamError::Validation { 
-                message: format!("Invalid private key: {}", e) 
+            .map_err(|e| VibeStreamError::Validation {
+                message: format!("Invalid private key: {}", e)
             })?;
-        
+
         Ok(Self {
             rpc_client,
+            ws_url,
             keypair,
+            rent_cache: RentCache::default(),
         })
     }
-    
+
+    /// Usar un endpoint WebSocket distinto al derivado del RPC HTTP
+    pub fn with_ws_url(mut self, ws_url: String) -> Self {
+        self.ws_url = ws_url;
+        self
+    }
+
     pub async fn get_balance(&self, pubkey: &Pubkey) -> Result<u64> {
         self.rpc_client
             .get_balance(pubkey)
-            .map_err(|e| VibeStreamError::Network { 
-                message: format!("Failed to get balance: {}", e) 
-            })
+            .map_err(VibeStreamError::from)
+    }
+
+    /// Balance de un token SPL en unidades base (sin aplicar `decimals`).
+    ///
+    /// Consulta la associated token account de la wallet para `mint_address`;
+    /// si la ATA aún no existe la wallet no tiene tokens y se devuelve `0`.
+    pub async fn get_spl_token_balance(&self, mint_address: &str) -> std::result::Result<u64, WalletError> {
+        let mint = parse_pubkey(mint_address)?;
+        let ata = spl_associated_token_account::get_associated_token_address(&self.keypair.pubkey(), &mint);
+
+        match self.rpc_client.get_token_account_balance(&ata) {
+            Ok(balance) => balance
+                .amount
+                .parse::<u64>()
+                .map_err(|e| WalletError::InvalidResponse(format!("Invalid token amount '{}': {}", balance.amount, e))),
+            Err(e) if is_account_not_found(&e) => Ok(0),
+            Err(e) => Err(WalletError::Rpc(format!("Failed to get token balance: {}", e))),
+        }
+    }
+
+    /// Balances de varios tokens SPL, agrupando las ATAs en llamadas a `getMultipleAccounts`.
+    ///
+    /// El resultado incluye todos los mints pedidos; los que no tienen ATA valen `0`.
+    pub async fn get_spl_token_balances_batch(&self, mint_addresses: &[String]) -> std::result::Result<HashMap<String, u64>, WalletError> {
+        let owner = self.keypair.pubkey();
+        let atas = mint_addresses
+            .iter()
+            .map(|mint| parse_pubkey(mint).map(|mint| spl_associated_token_account::get_associated_token_address(&owner, &mint)))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        let mut balances = HashMap::with_capacity(mint_addresses.len());
+        for (mints, atas) in mint_addresses.chunks(MAX_MULTIPLE_ACCOUNTS).zip(atas.chunks(MAX_MULTIPLE_ACCOUNTS)) {
+            let accounts = self
+                .rpc_client
+                .get_multiple_accounts(atas)
+                .map_err(|e| WalletError::Rpc(format!("Failed to get token accounts: {}", e)))?;
+
+            for (mint, account) in mints.iter().zip(accounts) {
+                let amount = match account {
+                    Some(account) => spl_token::state::Account::unpack(&account.data)
+                        .map_err(|e| WalletError::InvalidResponse(format!("Invalid token account for mint {}: {}", mint, e)))?
+                        .amount,
+                    None => 0,
+                };
+                balances.insert(mint.clone(), amount);
+            }
+        }
+
+        Ok(balances)
+    }
+
+    /// Lamports mínimos para que una cuenta de `data_len` bytes quede exenta de renta.
+    ///
+    /// Cada tamaño se consulta a `getMinimumBalanceForRentExemption` como mucho una
+    /// vez cada 10 minutos.
+    pub async fn get_minimum_balance_for_rent_exemption(&self, data_len: usize) -> std::result::Result<u64, WalletError> {
+        if let Some((lamports, fetched_at)) = self.rent_cache.read().await.get(&data_len) {
+            if fetched_at.elapsed() < RENT_CACHE_TTL {
+                return Ok(*lamports);
+            }
+        }
+
+        let lamports = self
+            .rpc_client
+            .get_minimum_balance_for_rent_exemption(data_len)
+            .map_err(|e| WalletError::Rpc(format!("Failed to get rent exemption for {} bytes: {}", data_len, e)))?;
+        self.rent_cache.write().await.insert(data_len, (lamports, Instant::now()));
+        Ok(lamports)
+    }
+
+    /// Instrucción que crea la cuenta mint de un NFT pagada por la wallet, con
+    /// la renta exacta para `MINT_ACCOUNT_RENT` bytes.
+    pub async fn create_mint_account_instruction(&self, mint: &Pubkey) -> std::result::Result<Instruction, WalletError> {
+        let lamports = self.get_minimum_balance_for_rent_exemption(MINT_ACCOUNT_RENT).await?;
+        Ok(system_instruction::create_account(
+            &self.keypair.pubkey(),
+            mint,
+            lamports,
+            MINT_ACCOUNT_RENT as u64,
+            &spl_token::id(),
+        ))
+    }
+
+    /// Mintear un NFT (supply 1, sin decimales ni autoridad de mint) en la ATA
+    /// de `owner`. No devuelve éxito hasta que la transacción está finalizada:
+    /// antes el NFT podría no verse todavía en el ledger o revertirse.
+    pub async fn mint_nft(&self, owner: &Pubkey) -> std::result::Result<(Pubkey, TransactionFinalized), WalletError> {
+        let mint = Keypair::new();
+        let payer = self.keypair.pubkey();
+        let token_program = spl_token::id();
+        let ata = spl_associated_token_account::get_associated_token_address(owner, &mint.pubkey());
+        let invalid_instruction = |e: solana_sdk::program_error::ProgramError| WalletError::InvalidResponse(format!("Invalid mint instruction: {}", e));
+
+        let instructions = vec![
+            self.create_mint_account_instruction(&mint.pubkey()).await?,
+            spl_token::instruction::initialize_mint(&token_program, &mint.pubkey(), &payer, None, 0).map_err(invalid_instruction)?,
+            spl_associated_token_account::instruction::create_associated_token_account(&payer, owner, &mint.pubkey(), &token_program),
+            spl_token::instruction::mint_to(&token_program, &mint.pubkey(), &ata, &payer, &[], 1).map_err(invalid_instruction)?,
+            spl_token::instruction::set_authority(
+                &token_program,
+                &mint.pubkey(),
+                None,
+                spl_token::instruction::AuthorityType::MintTokens,
+                &payer,
+                &[],
+            )
+            .map_err(invalid_instruction)?,
+        ];
+
+        let blockhash = self
+            .rpc_client
+            .get_latest_blockhash()
+            .map_err(|e| WalletError::Rpc(format!("Failed to get latest blockhash: {}", e)))?;
+        let transaction = Transaction::new_signed_with_payer(&instructions, Some(&payer), &[&self.keypair, &mint], blockhash);
+        let signature = self
+            .rpc_client
+            .send_transaction(&transaction)
+            .map_err(|e| WalletError::Rpc(format!("Failed to send mint transaction: {}", e)))?;
+
+        let (sender, receiver) = oneshot::channel();
+        ConfirmedTransactionWatcher::new(signature).watch(self, sender).await?;
+        let finalized = receiver
+            .await
+            .map_err(|_| WalletError::Rpc(format!("Confirmation of {} was dropped", signature)))?;
+        if let Some(error) = &finalized.err {
+            return Err(WalletError::TransactionFailed { signature: signature.to_string(), error: error.clone() });
+        }
+
+        Ok((mint.pubkey(), finalized))
+    }
+
+    /// Pedir un airdrop de `lamports` a la wallet y esperar a que se confirme.
+    ///
+    /// Solo para devnet/localnet: en mainnet devuelve `AirdropNotAvailableOnMainnet`.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub async fn request_airdrop(&self, lamports: u64) -> std::result::Result<Signature, WalletError> {
+        if self.rpc_client.url().contains("mainnet") {
+            return Err(WalletError::AirdropNotAvailableOnMainnet);
+        }
+
+        let commitment = solana_sdk::commitment_config::CommitmentConfig::confirmed();
+        let signature = self
+            .rpc_client
+            .request_airdrop_with_config(
+                &self.keypair.pubkey(),
+                lamports,
+                solana_client::rpc_config::RpcRequestAirdropConfig {
+                    recent_blockhash: None,
+                    commitment: Some(commitment),
+                },
+            )
+            .map_err(|e| WalletError::Rpc(format!("Airdrop request failed: {}", e)))?;
+
+        self.rpc_client
+            .poll_for_signature_with_commitment(&signature, commitment)
+            .map_err(|e| WalletError::Rpc(format!("Airdrop {} not confirmed: {}", signature, e)))?;
+
+        Ok(signature)
     }
-    
+
+    /// Suscribirse a cambios de balance de la wallet vía `accountSubscribe`.
+    ///
+    /// `callback` recibe el nuevo balance en lamports en cada `accountNotification`.
+    /// Si la conexión WebSocket falla se reintenta con backoff exponencial.
+    pub async fn watch_balance<F>(&self, callback: F) -> std::result::Result<JoinHandle<()>, WalletError>
+    where
+        F: Fn(u64) + Send + 'static,
+    {
+        let ws_url = self.ws_url.clone();
+        if !(ws_url.starts_with("ws://") || ws_url.starts_with("wss://")) {
+            return Err(WalletError::WebSocket(format!("Invalid WebSocket URL: {}", ws_url)));
+        }
+        let pubkey = self.keypair.pubkey().to_string();
+        // El callback solo es `Send`; el Mutex permite compartirlo entre awaits
+        let callback = std::sync::Mutex::new(callback);
+
+        Ok(tokio::spawn(async move {
+            let mut delay = INITIAL_RECONNECT_DELAY;
+            loop {
+                // `subscribe_account` reinicia el backoff en cuanto el nodo confirma la suscripción
+                match subscribe_account(&ws_url, &pubkey, &callback, &mut delay).await {
+                    Ok(()) => tracing::info!("Balance subscription for {} closed", pubkey),
+                    Err(e) => tracing::warn!("Balance subscription for {} failed: {}", pubkey, e),
+                }
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+            }
+        }))
+    }
+
+    /// Detener una suscripción creada con `watch_balance`
+    pub fn unwatch_balance(handle: JoinHandle<()>) {
+        handle.abort();
+    }
+
     pub async fn send_transaction(&self, transaction: &Transaction) -> Result<Signature> {
         self.rpc_client
             .send_and_confirm_transaction(transaction)
-            .map_err(|e| VibeStreamError::Network { 
-                message: format!("Failed to send transaction: {}", e) 
-            })
+            .map_err(VibeStreamError::from)
     }
-    
+
     pub fn get_pubkey(&self) -> Pubkey {
         self.keypair.pubkey()
     }
-} 
\ No newline at end of file
+}
+
+impl SignatureStatusSource for SolanaClient {
+    fn signature_status(&self, signature: &Signature) -> std::result::Result<Option<SignatureStatus>, WalletError> {
+        let statuses = self
+            .rpc_client
+            .get_signature_statuses(&[*signature])
+            .map_err(|e| WalletError::Rpc(format!("Failed to get status of {}: {}", signature, e)))?;
+
+        Ok(statuses.value.into_iter().next().flatten().map(|status| SignatureStatus {
+            slot: status.slot,
+            finalized: status.satisfies_commitment(CommitmentConfig::finalized()),
+            err: status.err.map(|e| e.to_string()),
+        }))
+    }
+}
+
+fn parse_pubkey(address: &str) -> std::result::Result<Pubkey, WalletError> {
+    Pubkey::from_str(address).map_err(|e| WalletError::InvalidAddress(format!("{}: {}", address, e)))
+}
+
+/// `getTokenAccountBalance` sobre una cuenta inexistente responde con error de parámetros
+fn is_account_not_found(error: &ClientError) -> bool {
+    matches!(
+        error.kind(),
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { code, message, .. })
+            if *code == INVALID_PARAMS_CODE && message.contains("could not find account")
+    )
+}
+
+/// Derivar el endpoint PubSub a partir del RPC HTTP (igual que la CLI de Solana:
+/// mismo host, esquema ws/wss y puerto + 1 cuando se especifica)
+fn derive_ws_url(rpc_url: &str) -> String {
+    let (scheme, rest) = if let Some(rest) = rpc_url.strip_prefix("https://") {
+        ("wss://", rest)
+    } else if let Some(rest) = rpc_url.strip_prefix("http://") {
+        ("ws://", rest)
+    } else {
+        return rpc_url.to_string();
+    };
+
+    let (authority, path) = match rest.find('/') {
+        Some(idx) => rest.split_at(idx),
+        None => (rest, ""),
+    };
+    let authority = match authority.rsplit_once(':') {
+        Some((host, port)) => match port.parse::<u16>() {
+            Ok(port) => format!("{}:{}", host, port.saturating_add(1)),
+            Err(_) => authority.to_string(),
+        },
+        None => authority.to_string(),
+    };
+
+    format!("{}{}{}", scheme, authority, path)
+}
+
+/// Una sesión de suscripción: conecta, suscribe y entrega notificaciones hasta que se cierra.
+/// `reconnect_delay` vuelve al inicial cuando el nodo confirma la suscripción.
+async fn subscribe_account<F>(
+    ws_url: &str,
+    pubkey: &str,
+    callback: &std::sync::Mutex<F>,
+    reconnect_delay: &mut Duration,
+) -> std::result::Result<(), WalletError>
+where
+    F: Fn(u64) + Send + 'static,
+{
+    let (mut socket, _) = tokio_tungstenite::connect_async(ws_url)
+        .await
+        .map_err(|e| WalletError::WebSocket(e.to_string()))?;
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "accountSubscribe",
+        "params": [pubkey, { "encoding": "base64", "commitment": "confirmed" }]
+    });
+    socket
+        .send(Message::Text(request.to_string()))
+        .await
+        .map_err(|e| WalletError::WebSocket(e.to_string()))?;
+
+    while let Some(message) = socket.next().await {
+        let message = message.map_err(|e| WalletError::WebSocket(e.to_string()))?;
+        match message {
+            Message::Text(text) => match parse_account_notification(&text) {
+                Ok(Some(lamports)) => {
+                    let callback = callback.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+                    callback(lamports);
+                }
+                Ok(None) => {
+                    if is_subscription_confirmation(&text) {
+                        *reconnect_delay = INITIAL_RECONNECT_DELAY;
+                    }
+                }
+                // Un mensaje ilegible no justifica perder la suscripción
+                Err(WalletError::InvalidResponse(e)) => {
+                    tracing::warn!("Skipping unparseable message on balance subscription for {}: {}", pubkey, e);
+                }
+                Err(e) => return Err(e),
+            },
+            Message::Ping(payload) => {
+                socket
+                    .send(Message::Pong(payload))
+                    .await
+                    .map_err(|e| WalletError::WebSocket(e.to_string()))?;
+            }
+            Message::Close(_) => break,
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Extraer lamports de un `accountNotification`; otros mensajes (p.ej. confirmación
+/// de la suscripción) devuelven `None`
+fn parse_account_notification(text: &str) -> std::result::Result<Option<u64>, WalletError> {
+    let value: serde_json::Value = serde_json::from_str(text)
+        .map_err(|e| WalletError::InvalidResponse(e.to_string()))?;
+
+    if let Some(error) = value.get("error") {
+        return Err(WalletError::Rpc(error.to_string()));
+    }
+    if value.get("method").and_then(|m| m.as_str()) != Some("accountNotification") {
+        return Ok(None);
+    }
+
+    value
+        .pointer("/params/result/value/lamports")
+        .and_then(|l| l.as_u64())
+        .map(Some)
+        .ok_or_else(|| WalletError::InvalidResponse("accountNotification without lamports".to_string()))
+}
+
+/// Respuesta del nodo a nuestro `accountSubscribe` (id 1) con el id de la suscripción
+fn is_subscription_confirmation(text: &str) -> bool {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(text) else {
+        return false;
+    };
+    value.get("id").and_then(|id| id.as_u64()) == Some(1) && value.get("result").is_some_and(|result| result.is_u64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::net::TcpListener;
+
+    fn notification(lamports: u64) -> String {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "accountNotification",
+            "params": {
+                "result": {
+                    "context": { "slot": 1 },
+                    "value": { "lamports": lamports, "owner": "11111111111111111111111111111111", "data": ["", "base64"], "executable": false, "rentEpoch": 0 }
+                },
+                "subscription": 7
+            }
+        })
+        .to_string()
+    }
+
+    /// Servidor WebSocket que confirma la suscripción y envía dos notificaciones,
+    /// con mensajes ilegibles entre medias
+    async fn spawn_mock_pubsub() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(stream).await.unwrap();
+
+            let request = socket.next().await.unwrap().unwrap();
+            let request: serde_json::Value = serde_json::from_str(request.to_text().unwrap()).unwrap();
+            assert_eq!(request["method"], "accountSubscribe");
+
+            let confirmation = serde_json::json!({ "jsonrpc": "2.0", "result": 7, "id": 1 });
+            socket.send(Message::Text(confirmation.to_string())).await.unwrap();
+            socket.send(Message::Text(notification(1_000))).await.unwrap();
+            socket.send(Message::Text("not json".to_string())).await.unwrap();
+            let without_lamports = serde_json::json!({ "jsonrpc": "2.0", "method": "accountNotification", "params": {} });
+            socket.send(Message::Text(without_lamports.to_string())).await.unwrap();
+            socket.send(Message::Text(notification(2_500))).await.unwrap();
+
+            // Mantener la conexión abierta
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        });
+
+        format!("ws://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn watch_balance_invokes_callback_per_notification() {
+        let ws_url = spawn_mock_pubsub().await;
+        let client = SolanaClient::new("http://127.0.0.1:8899".to_string(), Keypair::new().to_bytes().to_vec())
+            .unwrap()
+            .with_ws_url(ws_url);
+
+        let balances = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&balances);
+        let handle = client
+            .watch_balance(move |lamports| recorded.lock().unwrap().push(lamports))
+            .await
+            .unwrap();
+
+        for _ in 0..50 {
+            if balances.lock().unwrap().len() >= 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        SolanaClient::unwatch_balance(handle);
+
+        assert_eq!(*balances.lock().unwrap(), vec![1_000, 2_500]);
+    }
+
+    #[test]
+    fn only_the_subscribe_response_counts_as_confirmation() {
+        assert!(is_subscription_confirmation(r#"{"jsonrpc":"2.0","result":7,"id":1}"#));
+        assert!(!is_subscription_confirmation(&notification(1_000)));
+        assert!(!is_subscription_confirmation(r#"{"jsonrpc":"2.0","error":{"code":-32602},"id":1}"#));
+        assert!(!is_subscription_confirmation("not json"));
+    }
+
+    fn client_with_mocks(mocks: solana_client::rpc_client::Mocks) -> SolanaClient {
+        SolanaClient {
+            rpc_client: RpcClient::new_mock_with_mocks("succeeds".to_string(), mocks),
+            ws_url: "ws://127.0.0.1:8900".to_string(),
+            keypair: Keypair::new(),
+            rent_cache: RentCache::default(),
+        }
+    }
+
+    fn rent_mocks(lamports: u64) -> solana_client::rpc_client::Mocks {
+        let mut mocks = solana_client::rpc_client::Mocks::new();
+        mocks.insert(solana_client::rpc_request::RpcRequest::GetMinimumBalanceForRentExemption, serde_json::json!(lamports));
+        mocks
+    }
+
+    // Cada mock responde una sola vez; después el cliente mock devuelve su valor por defecto (20)
+    #[tokio::test(flavor = "multi_thread")]
+    async fn rent_exemption_is_cached_per_size() {
+        let client = client_with_mocks(rent_mocks(1_461_600));
+
+        assert_eq!(client.get_minimum_balance_for_rent_exemption(MINT_ACCOUNT_RENT).await.unwrap(), 1_461_600);
+        assert_eq!(client.get_minimum_balance_for_rent_exemption(MINT_ACCOUNT_RENT).await.unwrap(), 1_461_600);
+        // Otro tamaño no comparte entrada
+        assert_eq!(client.get_minimum_balance_for_rent_exemption(TOKEN_ACCOUNT_RENT).await.unwrap(), 20);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn expired_rent_is_fetched_again() {
+        let client = client_with_mocks(Default::default());
+        let expired = Instant::now() - RENT_CACHE_TTL - Duration::from_secs(1);
+        client.rent_cache.write().await.insert(METADATA_ACCOUNT_RENT, (5_616_720, expired));
+
+        assert_eq!(client.get_minimum_balance_for_rent_exemption(METADATA_ACCOUNT_RENT).await.unwrap(), 20);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn mint_account_is_funded_with_exact_rent() {
+        let client = client_with_mocks(rent_mocks(1_461_600));
+        let mint = Pubkey::new_unique();
+
+        let instruction = client.create_mint_account_instruction(&mint).await.unwrap();
+
+        let expected = system_instruction::create_account(&client.get_pubkey(), &mint, 1_461_600, 82, &spl_token::id());
+        assert_eq!(instruction, expected);
+    }
+
+    /// Cuenta de token tal como la devuelve `getMultipleAccounts` (base64)
+    fn token_account_fixture(mint: &Pubkey, owner: &Pubkey, amount: u64) -> serde_json::Value {
+        use base64::Engine;
+
+        let account = spl_token::state::Account {
+            mint: *mint,
+            owner: *owner,
+            amount,
+            state: spl_token::state::AccountState::Initialized,
+            ..Default::default()
+        };
+        let mut data = vec![0u8; spl_token::state::Account::LEN];
+        spl_token::state::Account::pack(account, &mut data).unwrap();
+
+        serde_json::json!({
+            "lamports": 2_039_280,
+            "data": [base64::engine::general_purpose::STANDARD.encode(data), "base64"],
+            "owner": spl_token::id().to_string(),
+            "executable": false,
+            "rentEpoch": 0,
+            "space": spl_token::state::Account::LEN
+        })
+    }
+
+    // El RpcClient bloqueante necesita el runtime multi-hilo
+    #[tokio::test(flavor = "multi_thread")]
+    async fn spl_token_balance_reads_ata_amount() {
+        let mut mocks = solana_client::rpc_client::Mocks::new();
+        mocks.insert(
+            solana_client::rpc_request::RpcRequest::GetTokenAccountBalance,
+            serde_json::json!({
+                "context": { "slot": 1 },
+                "value": { "amount": "4200000", "decimals": 6, "uiAmount": 4.2, "uiAmountString": "4.2" }
+            }),
+        );
+        let client = client_with_mocks(mocks);
+
+        let balance = client.get_spl_token_balance(&Pubkey::new_unique().to_string()).await.unwrap();
+
+        assert_eq!(balance, 4_200_000);
+    }
+
+    #[tokio::test]
+    async fn spl_token_balance_rejects_invalid_mint() {
+        let client = client_with_mocks(Default::default());
+
+        let result = client.get_spl_token_balance("not-a-pubkey").await;
+
+        assert!(matches!(result, Err(WalletError::InvalidAddress(_))));
+    }
+
+    #[test]
+    fn signature_status_reports_finalized_commitment() {
+        let status = |confirmation_status: &str, err: serde_json::Value| {
+            let result = if err.is_null() { serde_json::json!({"Ok": null}) } else { serde_json::json!({"Err": err}) };
+            let mut mocks = solana_client::rpc_client::Mocks::new();
+            mocks.insert(
+                solana_client::rpc_request::RpcRequest::GetSignatureStatuses,
+                serde_json::json!({
+                    "context": {"slot": 2_050},
+                    "value": [{"slot": 2_000, "confirmations": null, "status": result, "err": err, "confirmationStatus": confirmation_status}]
+                }),
+            );
+            client_with_mocks(mocks).signature_status(&Signature::new_unique()).unwrap().unwrap()
+        };
+
+        let confirmed = status("confirmed", serde_json::Value::Null);
+        assert_eq!(confirmed, SignatureStatus { slot: 2_000, finalized: false, err: None });
+
+        let failed = status("finalized", serde_json::json!({"InstructionError": [1, {"Custom": 1}]}));
+        assert!(failed.finalized);
+        assert!(failed.err.is_some());
+    }
+
+    #[test]
+    fn missing_token_account_is_detected() {
+        let not_found = ClientError::from(ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            code: INVALID_PARAMS_CODE,
+            message: "Invalid param: could not find account".to_string(),
+            data: solana_client::rpc_request::RpcResponseErrorData::Empty,
+        }));
+        let other = ClientError::from(ClientErrorKind::RpcError(RpcError::RpcRequestError("timeout".to_string())));
+
+        assert!(is_account_not_found(&not_found));
+        assert!(!is_account_not_found(&other));
+    }
+
+    // El RpcClient bloqueante necesita el runtime multi-hilo
+    #[tokio::test(flavor = "multi_thread")]
+    async fn spl_token_balances_batch_maps_missing_atas_to_zero() {
+        let held_mint = Pubkey::new_unique();
+        let missing_mint = Pubkey::new_unique();
+        let keypair = Keypair::new();
+
+        let mut mocks = solana_client::rpc_client::Mocks::new();
+        mocks.insert(
+            solana_client::rpc_request::RpcRequest::GetMultipleAccounts,
+            serde_json::json!({
+                "context": { "slot": 1 },
+                "value": [token_account_fixture(&held_mint, &keypair.pubkey(), 750), null]
+            }),
+        );
+        let mut client = client_with_mocks(mocks);
+        client.keypair = keypair;
+
+        let balances = client
+            .get_spl_token_balances_batch(&[held_mint.to_string(), missing_mint.to_string()])
+            .await
+            .unwrap();
+
+        assert_eq!(balances.len(), 2);
+        assert_eq!(balances[&held_mint.to_string()], 750);
+        assert_eq!(balances[&missing_mint.to_string()], 0);
+    }
+
+    #[tokio::test]
+    async fn airdrop_is_refused_on_mainnet() {
+        let client = SolanaClient::new("https://api.mainnet-beta.solana.com".to_string(), Keypair::new().to_bytes().to_vec()).unwrap();
+
+        let result = client.request_airdrop(1_000_000_000).await;
+
+        assert!(matches!(result, Err(WalletError::AirdropNotAvailableOnMainnet)));
+    }
+
+    /// Requiere `solana-test-validator` escuchando en 127.0.0.1:8899
+    #[tokio::test(flavor = "multi_thread")]
+    #[ignore]
+    async fn airdrop_funds_fresh_keypair_on_local_validator() {
+        let client = SolanaClient::new("http://127.0.0.1:8899".to_string(), Keypair::new().to_bytes().to_vec()).unwrap();
+        let lamports = solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+        client.request_airdrop(lamports).await.unwrap();
+
+        assert_eq!(client.get_balance(&client.get_pubkey()).await.unwrap(), lamports);
+    }
+
+    #[test]
+    fn ws_url_is_derived_from_rpc_url() {
+        assert_eq!(derive_ws_url("http://localhost:8899"), "ws://localhost:8900");
+        assert_eq!(derive_ws_url("https://api.devnet.solana.com"), "wss://api.devnet.solana.com");
+    }
+}
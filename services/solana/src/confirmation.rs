@@ -0,0 +1,188 @@
+use std::time::Duration;
+
+use solana_sdk::signature::Signature;
+use tokio::sync::oneshot;
+
+use crate::error::WalletError;
+
+/// Consultas por defecto antes de dar la transacción por perdida (~60s)
+pub const DEFAULT_MAX_POLLS: u32 = 120;
+/// Un slot dura ~400ms; finalizar lleva ~32 slots
+pub const DEFAULT_POLL_INTERVAL_MS: u64 = 500;
+
+/// Estado de una firma según `getSignatureStatuses`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureStatus {
+    pub slot: u64,
+    /// Alcanzó commitment `finalized`
+    pub finalized: bool,
+    /// Error de ejecución de la transacción, si falló
+    pub err: Option<String>,
+}
+
+/// Fuente del estado de una firma: el RPC del nodo o un doble en los tests.
+/// `Ok(None)` si el nodo aún no conoce la firma.
+pub trait SignatureStatusSource {
+    fn signature_status(&self, signature: &Signature) -> Result<Option<SignatureStatus>, WalletError>;
+}
+
+/// La transacción llegó a `finalized`: ya es visible en el ledger y no se revierte
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransactionFinalized {
+    pub signature: Signature,
+    pub slot: u64,
+    pub err: Option<String>,
+}
+
+/// Consulta `getSignatureStatuses` hasta que la transacción está finalizada y
+/// lo comunica por un `oneshot`. Los errores del RPC y las firmas que el nodo
+/// aún no conoce cuentan como una consulta más.
+#[derive(Debug, Clone)]
+pub struct ConfirmedTransactionWatcher {
+    pub signature: Signature,
+    pub max_polls: u32,
+    pub poll_interval_ms: u64,
+}
+
+impl ConfirmedTransactionWatcher {
+    pub fn new(signature: Signature) -> Self {
+        Self {
+            signature,
+            max_polls: DEFAULT_MAX_POLLS,
+            poll_interval_ms: DEFAULT_POLL_INTERVAL_MS,
+        }
+    }
+
+    pub fn with_max_polls(mut self, max_polls: u32) -> Self {
+        self.max_polls = max_polls;
+        self
+    }
+
+    pub fn with_poll_interval_ms(mut self, poll_interval_ms: u64) -> Self {
+        self.poll_interval_ms = poll_interval_ms;
+        self
+    }
+
+    /// Consultar hasta `finalized` y enviar `TransactionFinalized` por `finalized`.
+    /// Si se agotan las consultas devuelve `ConfirmationTimeout` sin enviar nada.
+    pub async fn watch<S>(self, source: &S, finalized: oneshot::Sender<TransactionFinalized>) -> Result<(), WalletError>
+    where
+        S: SignatureStatusSource + ?Sized,
+    {
+        let interval = Duration::from_millis(self.poll_interval_ms);
+        for poll in 1..=self.max_polls {
+            match source.signature_status(&self.signature) {
+                Ok(Some(status)) if status.finalized => {
+                    let event = TransactionFinalized {
+                        signature: self.signature,
+                        slot: status.slot,
+                        err: status.err,
+                    };
+                    // Si nadie espera el resultado no hay a quién avisar
+                    let _ = finalized.send(event);
+                    return Ok(());
+                }
+                Ok(_) => {
+                    tracing::debug!("Transaction {} not finalized yet (poll {}/{})", self.signature, poll, self.max_polls);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to poll status of {} (poll {}/{}): {}", self.signature, poll, self.max_polls, e);
+                }
+            }
+            if poll < self.max_polls {
+                tokio::time::sleep(interval).await;
+            }
+        }
+
+        Err(WalletError::ConfirmationTimeout {
+            signature: self.signature.to_string(),
+            polls: self.max_polls,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use std::sync::Mutex;
+
+    /// Responde en orden; cuando se acaban las respuestas la firma sigue sin finalizar
+    struct ScriptedStatuses {
+        responses: Mutex<VecDeque<Result<Option<SignatureStatus>, WalletError>>>,
+        polls: Mutex<u32>,
+    }
+
+    impl ScriptedStatuses {
+        fn new(responses: Vec<Result<Option<SignatureStatus>, WalletError>>) -> Self {
+            Self { responses: Mutex::new(responses.into()), polls: Mutex::new(0) }
+        }
+
+        fn polls(&self) -> u32 {
+            *self.polls.lock().unwrap()
+        }
+    }
+
+    impl SignatureStatusSource for ScriptedStatuses {
+        fn signature_status(&self, _signature: &Signature) -> Result<Option<SignatureStatus>, WalletError> {
+            *self.polls.lock().unwrap() += 1;
+            self.responses.lock().unwrap().pop_front().unwrap_or(Ok(None))
+        }
+    }
+
+    fn status(slot: u64, finalized: bool, err: Option<&str>) -> Result<Option<SignatureStatus>, WalletError> {
+        Ok(Some(SignatureStatus { slot, finalized, err: err.map(str::to_string) }))
+    }
+
+    fn watcher(max_polls: u32) -> ConfirmedTransactionWatcher {
+        ConfirmedTransactionWatcher::new(Signature::new_unique())
+            .with_max_polls(max_polls)
+            .with_poll_interval_ms(1)
+    }
+
+    #[tokio::test]
+    async fn finalized_after_failed_and_pending_polls() {
+        let source = ScriptedStatuses::new(vec![
+            Err(WalletError::Rpc("connection reset".to_string())),
+            Ok(None),
+            Err(WalletError::Rpc("429 Too Many Requests".to_string())),
+            status(1_000, false, None),
+            status(1_000, true, None),
+        ]);
+        let watcher = watcher(10);
+        let signature = watcher.signature;
+        let (sender, receiver) = oneshot::channel();
+
+        watcher.watch(&source, sender).await.unwrap();
+
+        assert_eq!(source.polls(), 5);
+        assert_eq!(receiver.await.unwrap(), TransactionFinalized { signature, slot: 1_000, err: None });
+    }
+
+    #[tokio::test]
+    async fn failed_transaction_is_reported_once_finalized() {
+        let source = ScriptedStatuses::new(vec![status(7, true, Some("custom program error: 0x1"))]);
+        let (sender, receiver) = oneshot::channel();
+
+        watcher(3).watch(&source, sender).await.unwrap();
+
+        assert_eq!(receiver.await.unwrap().err.as_deref(), Some("custom program error: 0x1"));
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_polls() {
+        let source = ScriptedStatuses::new(vec![
+            Err(WalletError::Rpc("timeout".to_string())),
+            status(42, false, None),
+            status(43, false, None),
+        ]);
+        let (sender, receiver) = oneshot::channel();
+
+        let result = watcher(4).watch(&source, sender).await;
+
+        assert!(matches!(result, Err(WalletError::ConfirmationTimeout { polls: 4, .. })));
+        assert_eq!(source.polls(), 4);
+        // Nothing is emitted for a transaction that never finalized
+        assert!(receiver.await.is_err());
+    }
+}
@@ -0,0 +1,42 @@
+use thiserror::Error;
+use vibestream_types::VibeStreamError;
+
+/// Errores específicos de operaciones de wallet en Solana
+#[derive(Error, Debug)]
+pub enum WalletError {
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+
+    #[error("RPC error: {0}")]
+    Rpc(String),
+
+    #[error("WebSocket error: {0}")]
+    WebSocket(String),
+
+    #[error("Invalid RPC response: {0}")]
+    InvalidResponse(String),
+
+    #[error("Airdrops are not available on mainnet")]
+    AirdropNotAvailableOnMainnet,
+
+    #[error("Transaction {signature} not finalized after {polls} polls")]
+    ConfirmationTimeout { signature: String, polls: u32 },
+
+    #[error("Transaction {signature} failed: {error}")]
+    TransactionFailed { signature: String, error: String },
+}
+
+impl From<WalletError> for VibeStreamError {
+    fn from(err: WalletError) -> Self {
+        match err {
+            WalletError::InvalidAddress(message) => VibeStreamError::Validation { message },
+            WalletError::Rpc(message) | WalletError::WebSocket(message) => VibeStreamError::Network { message },
+            WalletError::InvalidResponse(message) => VibeStreamError::Serialization { message },
+            WalletError::AirdropNotAvailableOnMainnet => VibeStreamError::Validation {
+                message: WalletError::AirdropNotAvailableOnMainnet.to_string(),
+            },
+            err @ WalletError::ConfirmationTimeout { .. } => VibeStreamError::Network { message: err.to_string() },
+            err @ WalletError::TransactionFailed { .. } => VibeStreamError::Internal { message: err.to_string() },
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use redis::aio::ConnectionManager;
+use thiserror::Error;
+use vibestream_types::VibeStreamError;
+
+/// Ventana en la que un mismo mensaje se considera repetido (24h)
+pub const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_millis(86_400_000);
+
+const KEY_PREFIX: &str = "idempotent:";
+/// Valor de la clave mientras el comando se está ejecutando
+const PROCESSING: &str = "processing";
+
+#[derive(Error, Debug, PartialEq)]
+pub enum IdempotencyError {
+    /// El mensaje ya se procesó; `original_result` es la respuesta que se dio
+    #[error("Message already processed")]
+    AlreadyProcessed { original_result: String },
+
+    #[error("Message {0} is still being processed")]
+    InProgress(String),
+
+    #[error("Idempotency store error: {0}")]
+    Store(String),
+}
+
+impl From<IdempotencyError> for VibeStreamError {
+    fn from(err: IdempotencyError) -> Self {
+        match err {
+            IdempotencyError::Store(message) => VibeStreamError::Network { message },
+            err => VibeStreamError::Internal { message: err.to_string() },
+        }
+    }
+}
+
+/// Claves `idempotent:{message_id}`: Redis en producción, un doble en los tests
+#[async_trait]
+pub trait IdempotencyStore: Send + Sync {
+    /// `SET key value NX PX ttl`; `false` si la clave ya existía
+    async fn set_if_absent(&self, key: &str, value: &str, ttl: Duration) -> Result<bool, IdempotencyError>;
+    async fn set(&self, key: &str, value: &str, ttl: Duration) -> Result<(), IdempotencyError>;
+    async fn get(&self, key: &str) -> Result<Option<String>, IdempotencyError>;
+    async fn delete(&self, key: &str) -> Result<(), IdempotencyError>;
+}
+
+pub struct RedisIdempotencyStore {
+    connection: ConnectionManager,
+}
+
+impl RedisIdempotencyStore {
+    pub fn new(connection: ConnectionManager) -> Self {
+        Self { connection }
+    }
+}
+
+fn store_error(e: redis::RedisError) -> IdempotencyError {
+    IdempotencyError::Store(format!("Redis idempotency error: {}", e))
+}
+
+#[async_trait]
+impl IdempotencyStore for RedisIdempotencyStore {
+    async fn set_if_absent(&self, key: &str, value: &str, ttl: Duration) -> Result<bool, IdempotencyError> {
+        let mut conn = self.connection.clone();
+        let reply: Option<String> = redis::cmd("SET")
+            .arg(key)
+            .arg(value)
+            .arg("NX")
+            .arg("PX")
+            .arg(ttl.as_millis() as u64)
+            .query_async(&mut conn)
+            .await
+            .map_err(store_error)?;
+        Ok(reply.is_some())
+    }
+
+    async fn set(&self, key: &str, value: &str, ttl: Duration) -> Result<(), IdempotencyError> {
+        let mut conn = self.connection.clone();
+        redis::cmd("SET")
+            .arg(key)
+            .arg(value)
+            .arg("PX")
+            .arg(ttl.as_millis() as u64)
+            .query_async(&mut conn)
+            .await
+            .map_err(store_error)
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<String>, IdempotencyError> {
+        let mut conn = self.connection.clone();
+        redis::cmd("GET").arg(key).query_async(&mut conn).await.map_err(store_error)
+    }
+
+    async fn delete(&self, key: &str) -> Result<(), IdempotencyError> {
+        let mut conn = self.connection.clone();
+        redis::cmd("DEL").arg(key).query_async(&mut conn).await.map_err(store_error)
+    }
+}
+
+/// Evita ejecutar dos veces un comando de la cola: un reintento del mismo
+/// mensaje recibe la respuesta guardada de la primera ejecución.
+///
+/// 1. `begin` reserva `idempotent:{message_id}` con `SET NX`.
+/// 2. `complete` sustituye la reserva por el resultado durante la ventana.
+/// 3. Si el comando falla, `abandon` borra la clave para que pueda reintentarse.
+#[derive(Clone)]
+pub struct IdempotencyGuard {
+    store: Arc<dyn IdempotencyStore>,
+    ttl: Duration,
+}
+
+impl IdempotencyGuard {
+    pub fn new(store: Arc<dyn IdempotencyStore>) -> Self {
+        Self { store, ttl: DEFAULT_IDEMPOTENCY_TTL }
+    }
+
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    fn key(message_id: &str) -> String {
+        format!("{}{}", KEY_PREFIX, message_id)
+    }
+
+    /// Reservar el mensaje. Falla con `AlreadyProcessed` si ya tiene resultado
+    /// y con `InProgress` si otro consumidor lo está ejecutando.
+    pub async fn begin(&self, message_id: &str) -> Result<(), IdempotencyError> {
+        let key = Self::key(message_id);
+        if self.store.set_if_absent(&key, PROCESSING, self.ttl).await? {
+            return Ok(());
+        }
+
+        match self.store.get(&key).await? {
+            Some(result) if result != PROCESSING => Err(IdempotencyError::AlreadyProcessed { original_result: result }),
+            Some(_) => Err(IdempotencyError::InProgress(message_id.to_string())),
+            // Caducó entre el SET y el GET: se vuelve a intentar la reserva
+            None if self.store.set_if_absent(&key, PROCESSING, self.ttl).await? => Ok(()),
+            None => Err(IdempotencyError::InProgress(message_id.to_string())),
+        }
+    }
+
+    /// Guardar la respuesta que recibirán las repeticiones del mensaje
+    pub async fn complete(&self, message_id: &str, result: &str) -> Result<(), IdempotencyError> {
+        self.store.set(&Self::key(message_id), result, self.ttl).await
+    }
+
+    /// Liberar la reserva de un comando que falló
+    pub async fn abandon(&self, message_id: &str) {
+        if let Err(e) = self.store.delete(&Self::key(message_id)).await {
+            tracing::warn!("Failed to release idempotency key for message {}: {}", message_id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// Doble en memoria (sin caducidad)
+    #[derive(Default)]
+    pub(crate) struct InMemoryIdempotencyStore {
+        pub(crate) keys: Mutex<HashMap<String, String>>,
+    }
+
+    #[async_trait]
+    impl IdempotencyStore for InMemoryIdempotencyStore {
+        async fn set_if_absent(&self, key: &str, value: &str, _ttl: Duration) -> Result<bool, IdempotencyError> {
+            let mut keys = self.keys.lock().unwrap();
+            if keys.contains_key(key) {
+                return Ok(false);
+            }
+            keys.insert(key.to_string(), value.to_string());
+            Ok(true)
+        }
+
+        async fn set(&self, key: &str, value: &str, _ttl: Duration) -> Result<(), IdempotencyError> {
+            self.keys.lock().unwrap().insert(key.to_string(), value.to_string());
+            Ok(())
+        }
+
+        async fn get(&self, key: &str) -> Result<Option<String>, IdempotencyError> {
+            Ok(self.keys.lock().unwrap().get(key).cloned())
+        }
+
+        async fn delete(&self, key: &str) -> Result<(), IdempotencyError> {
+            self.keys.lock().unwrap().remove(key);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn second_delivery_gets_the_original_result() {
+        let store = Arc::new(InMemoryIdempotencyStore::default());
+        let guard = IdempotencyGuard::new(store.clone());
+
+        guard.begin("msg-1").await.unwrap();
+        // Mientras se ejecuta, una repetición no puede empezar otra vez
+        assert_eq!(guard.begin("msg-1").await, Err(IdempotencyError::InProgress("msg-1".to_string())));
+
+        guard.complete("msg-1", "{\"ok\":true}").await.unwrap();
+        assert_eq!(
+            guard.begin("msg-1").await,
+            Err(IdempotencyError::AlreadyProcessed { original_result: "{\"ok\":true}".to_string() })
+        );
+        assert_eq!(store.keys.lock().unwrap()["idempotent:msg-1"], "{\"ok\":true}");
+    }
+
+    #[tokio::test]
+    async fn failed_commands_can_be_retried() {
+        let guard = IdempotencyGuard::new(Arc::new(InMemoryIdempotencyStore::default()));
+
+        guard.begin("msg-2").await.unwrap();
+        guard.abandon("msg-2").await;
+
+        assert!(guard.begin("msg-2").await.is_ok());
+    }
+}
@@ -1,10 +1,16 @@
 use vibestream_types::*;
 
 pub mod client;
+pub mod confirmation;
+pub mod error;
+pub mod idempotency;
 pub mod service;
 
 pub use service::SolanaService;
-pub use client::SolanaClient;
+pub use client::{SolanaClient, MINT_ACCOUNT_RENT, METADATA_ACCOUNT_RENT, TOKEN_ACCOUNT_RENT};
+pub use error::WalletError;
+pub use idempotency::{IdempotencyError, IdempotencyGuard, IdempotencyStore, RedisIdempotencyStore};
+pub use confirmation::{ConfirmedTransactionWatcher, SignatureStatus, SignatureStatusSource, TransactionFinalized};
 
 // Función principal para procesar mensajes
 pub async fn run_solana_worker() -> Result<()> {
@@ -1,15 +1,78 @@
 use crate::client::SolanaClient;
+use crate::idempotency::{IdempotencyError, IdempotencyGuard};
+use tracing::Instrument;
 use vibestream_types::*;
 
 pub struct SolanaService {
     client: SolanaClient,
+    idempotency: Option<IdempotencyGuard>,
 }
 
 impl SolanaService {
     pub fn new(client: SolanaClient) -> Self {
-        Self { client }
+        Self { client, idempotency: None }
+    }
+
+    /// No repetir comandos de la cola entregados más de una vez
+    pub fn with_idempotency(mut self, guard: IdempotencyGuard) -> Self {
+        self.idempotency = Some(guard);
+        self
     }
     
+    /// Procesar un mensaje de la cola dentro de un span con la traza y el
+    /// request ID del productor, para que los logs del worker se correlacionen
+    /// con la petición del gateway que lo originó
+    pub async fn process_envelope(&self, message: ServiceMessage<SolanaMessage>) -> Result<ServiceResponse> {
+        let (trace_id, parent_span_id) = message
+            .trace_context
+            .as_ref()
+            .and_then(TraceContext::ids)
+            .map(|(trace_id, span_id)| (trace_id.to_string(), span_id.to_string()))
+            .unzip();
+        let request_id = message.request_id.map(|id| id.to_string());
+        let span = tracing::info_span!(
+            "solana.process_message",
+            message_id = %message.id.0,
+            request_id = request_id.as_deref().unwrap_or_default(),
+            trace_id = trace_id.as_deref().unwrap_or_default(),
+            parent_span_id = parent_span_id.as_deref().unwrap_or_default(),
+        );
+        self.process_once(message).instrument(span).await
+    }
+
+    /// Los comandos se ejecutan una sola vez por id de mensaje; una repetición
+    /// recibe la respuesta de la primera ejecución. Las consultas no se protegen.
+    async fn process_once(&self, message: ServiceMessage<SolanaMessage>) -> Result<ServiceResponse> {
+        let guard = match &self.idempotency {
+            Some(guard) if is_command(&message.payload) => guard,
+            _ => return self.process_message(message.payload).await,
+        };
+        let message_id = message.id.0.to_string();
+
+        match guard.begin(&message_id).await {
+            Ok(()) => {}
+            Err(IdempotencyError::AlreadyProcessed { original_result }) => {
+                tracing::info!("Message {} already processed, replaying its result", message_id);
+                return serde_json::from_str(&original_result)
+                    .map_err(|e| VibeStreamError::Serialization { message: e.to_string() });
+            }
+            Err(e) => return Err(e.into()),
+        }
+
+        match self.process_message(message.payload).await {
+            Ok(response) => {
+                let result = serde_json::to_string(&response)
+                    .map_err(|e| VibeStreamError::Serialization { message: e.to_string() })?;
+                guard.complete(&message_id, &result).await?;
+                Ok(response)
+            }
+            Err(e) => {
+                guard.abandon(&message_id).await;
+                Err(e)
+            }
+        }
+    }
+
     pub async fn process_message(&self, message: SolanaMessage) -> Result<ServiceResponse> {
         match message {
             SolanaMessage::GetBalance(wallet) => {
@@ -48,4 +111,67 @@ impl SolanaService {
             }
         }
     }
-} 
\ No newline at end of file
+}
+
+/// Mensajes que cambian estado en la red y no deben ejecutarse dos veces
+fn is_command(message: &SolanaMessage) -> bool {
+    matches!(message, SolanaMessage::SendTransaction { .. } | SolanaMessage::CreateStream(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::idempotency::tests::InMemoryIdempotencyStore;
+    use solana_sdk::signature::Keypair;
+    use std::sync::Arc;
+
+    fn service(store: Arc<InMemoryIdempotencyStore>) -> SolanaService {
+        let client = SolanaClient::new("http://127.0.0.1:8899".to_string(), Keypair::new().to_bytes().to_vec()).unwrap();
+        SolanaService::new(client).with_idempotency(IdempotencyGuard::new(store))
+    }
+
+    fn transfer() -> ServiceMessage<SolanaMessage> {
+        ServiceMessage::new(SolanaMessage::SendTransaction {
+            from: "sender".to_string(),
+            to: "receiver".to_string(),
+            amount: 5_000,
+        })
+    }
+
+    fn transaction_id(response: ServiceResponse) -> Uuid {
+        match response {
+            ServiceResponse::Transaction(transaction) => transaction.id.0,
+            other => panic!("unexpected response: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn redelivered_transfer_is_executed_once() {
+        let store = Arc::new(InMemoryIdempotencyStore::default());
+        let service = service(store.clone());
+        let message = transfer();
+
+        let first = transaction_id(service.process_envelope(message.clone()).await.unwrap());
+        // Misma entrega otra vez: cada ejecución crea una transacción con id nuevo,
+        // así que recibir el mismo id significa que no se volvió a ejecutar
+        let replay = transaction_id(service.process_envelope(message.clone()).await.unwrap());
+
+        assert_eq!(replay, first);
+        assert_eq!(store.keys.lock().unwrap().len(), 1);
+        assert!(store.keys.lock().unwrap().contains_key(&format!("idempotent:{}", message.id.0)));
+
+        let other = transaction_id(service.process_envelope(transfer()).await.unwrap());
+        assert_ne!(other, first);
+    }
+
+    #[tokio::test]
+    async fn queries_are_not_recorded() {
+        let store = Arc::new(InMemoryIdempotencyStore::default());
+        let service = service(store.clone());
+        let wallet = WalletAddress { address: "wallet".to_string(), blockchain: Blockchain::Solana };
+
+        service.process_envelope(ServiceMessage::new(SolanaMessage::GetBalance(wallet))).await.unwrap();
+
+        assert!(store.keys.lock().unwrap().is_empty());
+    }
+}
@@ -6,7 +6,7 @@ pub mod service;
 #[cfg(test)]
 mod test_zk;
 
-pub use service::{ZkService, ZkServiceConfig, ZkProofType};
+pub use service::{ZkService, ZkServiceConfig, ZkProofType, ZkServiceHealth, HealthStatus};
 pub use zkp::{ZkProof, ZkProofGenerator, ZkProofVerifier};
 
 /// Función principal para ejecutar el worker ZK
@@ -1,18 +1,21 @@
-use crate::zkp::{ZkProofGenerator, ZkProofVerifier, ZkProof};
+use crate::zkp::{inspect_circuits, CircuitInventory, ZkProofGenerator, ZkProofVerifier, ZkProof};
 use vibestream_types::*;
 use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, error};
 use anyhow::Result as AnyResult;
+use chrono::Datelike;
 use serde::{Deserialize, Serialize};
 use axum::{
-    extract::State,
+    extract::{Request, State},
     http::StatusCode,
-    response::Json,
+    middleware::{self, Next},
+    response::{Json, Response},
     routing::{get, post},
     Router,
 };
+use tracing::Instrument;
 use tower_http::cors::CorsLayer;
 use tower::ServiceBuilder;
 
@@ -49,6 +52,49 @@ pub struct ZkServiceStats {
     proofs_failed: u64,
     average_generation_time_ms: f64,
     average_verification_time_ms: f64,
+    last_generation_time_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Healthy,
+    Degraded,
+    Unhealthy,
+}
+
+/// Estado del servicio ZK expuesto en `GET /health`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZkServiceHealth {
+    pub status: HealthStatus,
+    pub circuits_available: Vec<String>,
+    pub last_proof_generation_ms: Option<u64>,
+    pub cache_size: usize,
+}
+
+impl ZkServiceHealth {
+    /// Sin directorio de circuitos el servicio no puede generar pruebas reales;
+    /// con artefactos rotos o sin artefactos sigue respondiendo (con mocks).
+    pub fn from_inventory(
+        inventory: Option<&CircuitInventory>,
+        last_proof_generation_ms: Option<u64>,
+        cache_size: usize,
+    ) -> Self {
+        let (status, circuits_available) = match inventory {
+            None => (HealthStatus::Unhealthy, Vec::new()),
+            Some(inventory) if inventory.failed.is_empty() && !inventory.available.is_empty() => {
+                (HealthStatus::Healthy, inventory.available.clone())
+            }
+            Some(inventory) => (HealthStatus::Degraded, inventory.available.clone()),
+        };
+
+        Self {
+            status,
+            circuits_available,
+            last_proof_generation_ms,
+            cache_size,
+        }
+    }
 }
 
 impl ZkService {
@@ -112,6 +158,10 @@ impl ZkService {
                     &nonce
                 ).await
             }
+            ZkProofType::AgeVerification { birth_year, minimum_age } => {
+                let current_year = chrono::Utc::now().year() as u32;
+                self.generator.generate_age_proof(birth_year, minimum_age, current_year).await
+            }
         };
 
         let duration = start_time.elapsed();
@@ -120,6 +170,7 @@ impl ZkService {
         let mut stats = self.stats.write().await;
         if result.is_ok() {
             stats.proofs_generated += 1;
+            stats.last_generation_time_ms = Some(duration.as_millis() as u64);
             stats.average_generation_time_ms = 
                 (stats.average_generation_time_ms * (stats.proofs_generated - 1) as f64 + duration.as_millis() as f64) 
                 / stats.proofs_generated as f64;
@@ -130,6 +181,12 @@ impl ZkService {
         result
     }
     
+    /// Prueba de que el usuario tiene al menos `minimum_age` años este año
+    /// natural; la prueba solo publica el hash del año de nacimiento
+    pub async fn prove_age_without_revealing(&self, birth_year: u32, minimum_age: u32) -> Result<ZkProof> {
+        self.generate_proof(ZkProofType::AgeVerification { birth_year, minimum_age }).await
+    }
+    
     /// Verifica una prueba ZK
     pub async fn verify_proof(&self, proof: &ZkProof) -> Result<bool> {
         let start_time = std::time::Instant::now();
@@ -157,6 +214,20 @@ impl ZkService {
         self.stats.read().await.clone()
     }
     
+    /// Comprueba que los circuitos de `circuits_dir` están presentes y se pueden cargar
+    pub async fn health_check(&self) -> ZkServiceHealth {
+        let inventory = match inspect_circuits(Path::new(&self.config.circuits_dir)).await {
+            Ok(inventory) => Some(inventory),
+            Err(e) => {
+                error!("ZK health check failed: {}", e);
+                None
+            }
+        };
+        let last_proof_generation_ms = self.stats.read().await.last_generation_time_ms;
+
+        ZkServiceHealth::from_inventory(inventory.as_ref(), last_proof_generation_ms, self.generator.cache_size())
+    }
+
     /// Función principal del worker ZK
     pub async fn run_worker(&self) -> Result<()> {
         info!("🚀 Starting ZK service worker...");
@@ -185,11 +256,41 @@ impl ZkService {
             .route("/stats", get(get_stats_handler))
             .route("/generate", post(generate_proof_handler))
             .route("/verify", post(verify_proof_handler))
+            .layer(middleware::from_fn(continue_trace))
             .layer(CorsLayer::permissive())
             .with_state(Arc::new(self.clone()))
     }
 }
 
+/// Cada petición corre en un span con la traza (`traceparent`) y el
+/// `X-Request-Id` del gateway, así sus logs se correlacionan con la petición
+/// que la originó
+async fn continue_trace(request: Request, next: Next) -> Response {
+    let trace_context = request
+        .headers()
+        .get("traceparent")
+        .and_then(|value| value.to_str().ok())
+        .map(|traceparent| TraceContext { traceparent: traceparent.to_string(), tracestate: None });
+    let (trace_id, parent_span_id) = trace_context
+        .as_ref()
+        .and_then(TraceContext::ids)
+        .map(|(trace_id, span_id)| (trace_id.to_string(), span_id.to_string()))
+        .unzip();
+    let request_id = request
+        .headers()
+        .get("x-request-id")
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+    let span = tracing::info_span!(
+        "zk.request",
+        path = %request.uri().path(),
+        request_id = request_id.as_deref().unwrap_or_default(),
+        trace_id = trace_id.as_deref().unwrap_or_default(),
+        parent_span_id = parent_span_id.as_deref().unwrap_or_default(),
+    );
+    next.run(request).instrument(span).await
+}
+
 impl Clone for ZkService {
     fn clone(&self) -> Self {
         Self {
@@ -218,18 +319,21 @@ pub enum ZkProofType {
         user_public_key: [String; 2],
         nonce: String,
     },
+    /// Prueba de edad: demuestra que `año actual - birth_year >= minimum_age` sin revelar `birth_year`
+    AgeVerification { birth_year: u32, minimum_age: u32 },
 }
 
 // HTTP handlers - Todos usan State para consistencia
 async fn health_check(
-    State(_service): State<Arc<ZkService>>,
-) -> std::result::Result<Json<serde_json::Value>, StatusCode> {
-    Ok(Json(serde_json::json!({
-        "status": "healthy",
-        "service": "zk-service",
-        "timestamp": chrono::Utc::now().to_rfc3339(),
-        "version": env!("CARGO_PKG_VERSION")
-    })))
+    State(service): State<Arc<ZkService>>,
+) -> (StatusCode, Json<ZkServiceHealth>) {
+    let health = service.health_check().await;
+    let status_code = if health.status == HealthStatus::Unhealthy {
+        StatusCode::SERVICE_UNAVAILABLE
+    } else {
+        StatusCode::OK
+    };
+    (status_code, Json(health))
 }
 
 async fn get_stats_handler(
@@ -250,6 +354,11 @@ async fn generate_proof_handler(
 ) -> std::result::Result<Json<ZkProof>, StatusCode> {
     match service.generate_proof(request.proof_type).await {
         Ok(proof) => Ok(Json(proof)),
+        // La afirmación es falsa (p. ej. edad insuficiente): no hay prueba posible
+        Err(VibeStreamError::Validation { message }) => {
+            info!("Proof request rejected: {}", message);
+            Err(StatusCode::UNPROCESSABLE_ENTITY)
+        }
         Err(e) => {
             error!("Failed to generate proof: {}", e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
@@ -1,4 +1,7 @@
-use crate::zkp::{ZkProofGenerator, ZkProofVerifier};
+use crate::service::{HealthStatus, ZkServiceHealth};
+use crate::zkp::{age_verification_input, inspect_circuits, ZkProofGenerator, ZkProofVerifier};
+use ark_bn254::Fr;
+use vibestream_types::VibeStreamError;
 use std::path::Path;
 use tempfile::TempDir;
 
@@ -99,3 +102,103 @@ async fn test_mock_proof_generation() {
         }
     }
 }
+
+/// .zkey mínimo de snarkjs: cabecera Groth16 (sección 1) y clave de verificación (sección 2)
+fn zkey_fixture(protocol: u32, with_verifying_key: bool) -> Vec<u8> {
+    let mut bytes = b"zkey".to_vec();
+    bytes.extend_from_slice(&1u32.to_le_bytes());
+    let sections = if with_verifying_key { 2u32 } else { 1u32 };
+    bytes.extend_from_slice(&sections.to_le_bytes());
+
+    bytes.extend_from_slice(&1u32.to_le_bytes());
+    bytes.extend_from_slice(&4u64.to_le_bytes());
+    bytes.extend_from_slice(&protocol.to_le_bytes());
+
+    if with_verifying_key {
+        bytes.extend_from_slice(&2u32.to_le_bytes());
+        bytes.extend_from_slice(&16u64.to_le_bytes());
+        bytes.extend_from_slice(&[7u8; 16]);
+    }
+    bytes
+}
+
+#[tokio::test]
+async fn test_inspect_circuits_loads_fixture_artifacts() {
+    let temp_dir = TempDir::new().unwrap();
+    let circuits_dir = temp_dir.path();
+    tokio::fs::create_dir_all(circuits_dir.join("proof_of_listen_js")).await.unwrap();
+
+    tokio::fs::write(circuits_dir.join("proof_of_listen.zkey"), zkey_fixture(1, true)).await.unwrap();
+    tokio::fs::write(
+        circuits_dir.join("proof_of_listen_js").join("proof_of_listen.wasm"),
+        b"\0asm\x01\0\0\0",
+    ).await.unwrap();
+    tokio::fs::write(circuits_dir.join("proof_of_listen.circom"), "pragma circom 2.0.0;").await.unwrap();
+
+    let inventory = inspect_circuits(circuits_dir).await.unwrap();
+    assert!(inventory.failed.is_empty());
+    assert_eq!(inventory.available.len(), 2);
+    assert!(inventory.available.contains(&"proof_of_listen.zkey".to_string()));
+
+    let health = ZkServiceHealth::from_inventory(Some(&inventory), Some(120), 1);
+    assert_eq!(health.status, HealthStatus::Healthy);
+    assert_eq!(health.last_proof_generation_ms, Some(120));
+}
+
+#[tokio::test]
+async fn test_inspect_circuits_reports_broken_artifacts() {
+    let temp_dir = TempDir::new().unwrap();
+    let circuits_dir = temp_dir.path();
+
+    tokio::fs::write(circuits_dir.join("solvency.zkey"), zkey_fixture(1, true)).await.unwrap();
+    tokio::fs::write(circuits_dir.join("no_vkey.zkey"), zkey_fixture(1, false)).await.unwrap();
+    tokio::fs::write(circuits_dir.join("plonk.zkey"), zkey_fixture(2, true)).await.unwrap();
+    tokio::fs::write(circuits_dir.join("truncated.wasm"), b"\0as").await.unwrap();
+
+    let inventory = inspect_circuits(circuits_dir).await.unwrap();
+    assert_eq!(inventory.available, vec!["solvency.zkey".to_string()]);
+    assert_eq!(inventory.failed.len(), 3);
+
+    let health = ZkServiceHealth::from_inventory(Some(&inventory), None, 0);
+    assert_eq!(health.status, HealthStatus::Degraded);
+}
+
+#[tokio::test]
+async fn test_missing_circuits_dir_is_unhealthy() {
+    let temp_dir = TempDir::new().unwrap();
+
+    assert!(inspect_circuits(&temp_dir.path().join("missing")).await.is_err());
+    assert_eq!(ZkServiceHealth::from_inventory(None, None, 0).status, HealthStatus::Unhealthy);
+}
+
+#[test]
+fn test_age_verification_input_for_adult_and_minor() {
+    let salt = Fr::from(918273645546372819u64);
+
+    // 20 años pasan el control de +18
+    let input = age_verification_input(2006, 18, 2026, salt).unwrap();
+    assert_eq!(input["minimumAge"], 18);
+    assert_eq!(input["currentYear"], 2026);
+    assert!(!input["birthYearHash"].as_str().unwrap().is_empty());
+
+    // 16 años no: ni siquiera se intenta generar la prueba
+    match age_verification_input(2010, 18, 2026, salt) {
+        Err(VibeStreamError::Validation { message }) => assert_eq!(message, "Age requirement not met"),
+        other => panic!("Expected validation error, got {:?}", other),
+    }
+    assert!(age_verification_input(2027, 0, 2026, salt).is_err());
+}
+
+#[test]
+fn test_birth_year_commitment_depends_on_salt() {
+    let hash = |salt: u64| {
+        age_verification_input(2006, 18, 2026, Fr::from(salt)).unwrap()["birthYearHash"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    };
+
+    // Mismo año y sal: mismo compromiso; otra sal no permite relacionarlos
+    assert_eq!(hash(1), hash(1));
+    assert_ne!(hash(1), hash(2));
+}
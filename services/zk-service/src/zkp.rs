@@ -2,7 +2,8 @@ use vibestream_types::*;
 use ark_bn254::{Bn254, Fr};
 use ark_groth16::{Groth16, Proof, ProvingKey, VerifyingKey};
 use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
-use ark_ff::PrimeField;
+use ark_ff::{PrimeField, UniformRand};
+use light_poseidon::{Poseidon, PoseidonHasher};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::collections::HashMap;
@@ -29,6 +30,123 @@ pub struct ZkProof {
     pub generated_at: chrono::DateTime<chrono::Utc>,
 }
 
+const WASM_MAGIC: &[u8; 4] = b"\0asm";
+const ZKEY_MAGIC: &[u8; 4] = b"zkey";
+/// Identificador de protocolo Groth16 en la cabecera de un .zkey de snarkjs
+const ZKEY_PROTOCOL_GROTH16: u32 = 1;
+const ZKEY_SECTION_HEADER: u32 = 1;
+const ZKEY_SECTION_GROTH16_HEADER: u32 = 2;
+
+/// Circuito de verificación de edad (`age_verification.circom`)
+pub const AGE_VERIFICATION_CIRCUIT: &str = "age_verification";
+
+/// Resultado de inspeccionar los artefactos (.zkey / .wasm) de un directorio de circuitos
+#[derive(Debug, Clone, Default)]
+pub struct CircuitInventory {
+    /// Artefactos que se han podido cargar, relativos al directorio
+    pub available: Vec<String>,
+    /// Artefactos que no se han podido cargar, con el motivo
+    pub failed: Vec<(String, String)>,
+}
+
+/// Recorre `circuits_dir` (incluidos subdirectorios como `<circuit>_js/`) y
+/// comprueba que cada `.zkey` contiene la clave de verificación Groth16 y que
+/// cada `.wasm` es un módulo válido.
+pub async fn inspect_circuits(circuits_dir: &Path) -> AnyResult<CircuitInventory> {
+    let mut inventory = CircuitInventory::default();
+    let mut pending = vec![circuits_dir.to_path_buf()];
+
+    while let Some(dir) = pending.pop() {
+        let mut entries = fs::read_dir(&dir).await
+            .with_context(|| format!("Failed to read circuits directory {}", dir.display()))?;
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if entry.file_type().await?.is_dir() {
+                pending.push(path);
+                continue;
+            }
+
+            let extension = path.extension().and_then(|e| e.to_str());
+            if !matches!(extension, Some("zkey") | Some("wasm")) {
+                continue;
+            }
+
+            let name = path.strip_prefix(circuits_dir)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .to_string();
+            let result = match extension {
+                Some("zkey") => load_zkey_verifying_key(&path).await,
+                _ => load_wasm(&path).await,
+            };
+            match result {
+                Ok(()) => inventory.available.push(name),
+                Err(e) => {
+                    warn!("Circuit artifact {} failed to load: {}", name, e);
+                    inventory.failed.push((name, e.to_string()));
+                }
+            }
+        }
+    }
+
+    inventory.available.sort();
+    inventory.failed.sort();
+    Ok(inventory)
+}
+
+async fn load_wasm(path: &Path) -> AnyResult<()> {
+    let bytes = fs::read(path).await?;
+    if bytes.len() < 8 || &bytes[..4] != WASM_MAGIC {
+        return Err(anyhow::anyhow!("not a WebAssembly module"));
+    }
+    Ok(())
+}
+
+/// Formato binario de snarkjs: "zkey", versión (u32), nº de secciones (u32) y
+/// secciones `(id: u32, tamaño: u64, datos)`. La sección 1 indica el protocolo y
+/// la 2 contiene la cabecera Groth16 con la clave de verificación.
+async fn load_zkey_verifying_key(path: &Path) -> AnyResult<()> {
+    let bytes = fs::read(path).await?;
+    if bytes.len() < 12 || &bytes[..4] != ZKEY_MAGIC {
+        return Err(anyhow::anyhow!("not a snarkjs zkey file"));
+    }
+
+    let read_u32 = |at: usize| bytes.get(at..at + 4).map(|b| u32::from_le_bytes(b.try_into().unwrap()));
+    let read_u64 = |at: usize| bytes.get(at..at + 8).map(|b| u64::from_le_bytes(b.try_into().unwrap()));
+
+    let section_count = read_u32(8).unwrap_or(0);
+    let mut offset = 12usize;
+    let mut protocol = None;
+    let mut verifying_key_len = None;
+
+    for _ in 0..section_count {
+        let (id, size) = match (read_u32(offset), read_u64(offset + 4)) {
+            (Some(id), Some(size)) => (id, size as usize),
+            _ => return Err(anyhow::anyhow!("truncated section table")),
+        };
+        let data_start = offset + 12;
+        if data_start + size > bytes.len() {
+            return Err(anyhow::anyhow!("section {} exceeds file size", id));
+        }
+
+        match id {
+            ZKEY_SECTION_HEADER => protocol = read_u32(data_start),
+            ZKEY_SECTION_GROTH16_HEADER => verifying_key_len = Some(size),
+            _ => {}
+        }
+        offset = data_start + size;
+    }
+
+    if protocol != Some(ZKEY_PROTOCOL_GROTH16) {
+        return Err(anyhow::anyhow!("unsupported proving protocol"));
+    }
+    match verifying_key_len {
+        Some(len) if len > 0 => Ok(()),
+        _ => Err(anyhow::anyhow!("missing Groth16 verifying key section")),
+    }
+}
+
 /// Circuit manager para compilar y ejecutar circuitos circom
 pub struct CircuitManager {
     circuits_dir: std::path::PathBuf,
@@ -71,6 +189,7 @@ impl CircuitManager {
 
         // Pre-compile essential circuits
         manager.compile_circuit("proof_of_listen").await?;
+        manager.compile_circuit(AGE_VERIFICATION_CIRCUIT).await?;
         
         info!("✅ CircuitManager initialized with {} circuits", manager.compiled_circuits.len());
         Ok(manager)
@@ -183,6 +302,11 @@ impl CircuitManager {
         Ok(())
     }
 
+    /// Número de circuitos compilados en memoria
+    pub fn compiled_circuit_count(&self) -> usize {
+        self.compiled_circuits.len()
+    }
+
     async fn generate_witness(&self, circuit_name: &str, input: &serde_json::Value) -> AnyResult<Vec<u8>> {
         let compiled = self.compiled_circuits.get(circuit_name)
             .ok_or_else(|| anyhow::anyhow!("Circuit not compiled: {}", circuit_name))?;
@@ -310,6 +434,43 @@ impl CircuitManager {
     }
 }
 
+/// Entrada del circuito de edad. Falla si la edad no llega al mínimo: no
+/// tiene sentido generar una prueba con `ageVerified = 0`.
+///
+/// `birthYear` y `salt` son privados; la prueba solo publica
+/// `birthYearHash = Poseidon(birthYear, salt)`, `minimumAge` y `currentYear`.
+pub fn age_verification_input(
+    birth_year: u32,
+    minimum_age: u32,
+    current_year: u32,
+    salt: Fr,
+) -> Result<serde_json::Value> {
+    if birth_year > current_year {
+        return Err(VibeStreamError::Validation {
+            message: "Birth year cannot be in the future".to_string(),
+        });
+    }
+    if current_year - birth_year < minimum_age {
+        return Err(VibeStreamError::Validation {
+            message: "Age requirement not met".to_string(),
+        });
+    }
+
+    let birth_year_hash = Poseidon::<Fr>::new_circom(2)
+        .and_then(|mut poseidon| poseidon.hash(&[Fr::from(birth_year as u64), salt]))
+        .map_err(|e| VibeStreamError::Internal {
+            message: format!("Failed to hash birth year: {}", e),
+        })?;
+
+    Ok(json!({
+        "birthYear": birth_year,
+        "salt": salt.into_bigint().to_string(),
+        "birthYearHash": birth_year_hash.into_bigint().to_string(),
+        "minimumAge": minimum_age,
+        "currentYear": current_year
+    }))
+}
+
 /// Generador de pruebas ZK
 pub struct ZkProofGenerator {
     circuit_manager: CircuitManager,
@@ -320,6 +481,11 @@ impl ZkProofGenerator {
         let circuit_manager = CircuitManager::new(circuits_dir, cache_dir, redis_url).await?;
         Ok(Self { circuit_manager })
     }
+
+    /// Circuitos compilados disponibles en la caché del generador
+    pub fn cache_size(&self) -> usize {
+        self.circuit_manager.compiled_circuit_count()
+    }
     
     /// Genera una prueba de solvencia sin revelar el balance exacto
     pub async fn generate_solvency_proof(&self, balance: u64, min_threshold: u64) -> Result<ZkProof> {
@@ -422,6 +588,24 @@ impl ZkProofGenerator {
         }
     }
 
+    /// Demuestra `current_year - birth_year >= minimum_age` sin revelar el año
+    /// de nacimiento. A diferencia de la prueba de escucha no hay prueba mock:
+    /// sin circuito no se puede verificar la edad.
+    pub async fn generate_age_proof(&self, birth_year: u32, minimum_age: u32, current_year: u32) -> Result<ZkProof> {
+        let salt = Fr::rand(&mut rand::thread_rng());
+        let input = age_verification_input(birth_year, minimum_age, current_year, salt)?;
+
+        let proof = self.circuit_manager.generate_proof(AGE_VERIFICATION_CIRCUIT, &input).await
+            .map_err(|e| {
+                error!("❌ Age verification circuit failed: {}", e);
+                VibeStreamError::Internal {
+                    message: format!("Age proof generation failed: {}", e),
+                }
+            })?;
+        info!("✅ Generated ZK age proof (minimum age {})", minimum_age);
+        Ok(proof)
+    }
+
     /// Genera una prueba mock de listen para testing
     pub fn generate_mock_listen_proof(
         &self,
@@ -496,6 +680,16 @@ impl ZkProofVerifier {
                     }
                 }
             }
+            AGE_VERIFICATION_CIRCUIT => {
+                let is_valid = self.circuit_manager.verify_proof(proof).await
+                    .map_err(|e| VibeStreamError::Internal {
+                        message: format!("Verification failed: {}", e),
+                    })?;
+                // snarkjs pone primero las salidas: public = [ageVerified, birthYearHash, minimumAge, currentYear]
+                let age_verified = proof.public_inputs.get(0).and_then(|v| v.as_str()) == Some("1");
+                info!("✅ ZK age proof verification result: {} (age verified: {})", is_valid, age_verified);
+                Ok(is_valid && age_verified)
+            }
             "solvency" | "transaction" => {
                 // For now, mock verification for these circuits
                 info!("Mock verification for circuit: {}", proof.circuit_id);
@@ -7,6 +7,24 @@ pub enum Blockchain {
     Solana,
 }
 
+/// Red en la que se produjo un error de blockchain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BlockchainNetwork {
+    Solana,
+    Ethereum,
+    Polygon,
+}
+
+impl std::fmt::Display for BlockchainNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlockchainNetwork::Solana => write!(f, "Solana"),
+            BlockchainNetwork::Ethereum => write!(f, "Ethereum"),
+            BlockchainNetwork::Polygon => write!(f, "Polygon"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
     pub id: RequestId,
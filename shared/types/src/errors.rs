@@ -1,40 +1,294 @@
 use thiserror::Error;
 use serde::{Deserialize, Serialize};
 
+use crate::blockchain::BlockchainNetwork;
+
 #[derive(Error, Debug, Clone, Serialize, Deserialize)]
 pub enum VibeStreamError {
-    #[error("Blockchain error: {message}")]
-    Blockchain { message: String },
-    
+    #[error("Blockchain error on {chain} (code {code}): {message}")]
+    Blockchain { chain: BlockchainNetwork, code: u32, message: String },
+
     #[error("Database error: {message}")]
     Database { message: String },
-    
+
     #[error("Network error: {message}")]
     Network { message: String },
-    
+
     #[error("Validation error: {message}")]
     Validation { message: String },
-    
+
     #[error("Resource not found: {resource} with id {id}")]
     NotFound { resource: String, id: String },
-    
+
     #[error("Serialization error: {message}")]
     Serialization { message: String },
-    
+
     #[error("Insufficient balance: required {required}, available {available}")]
     InsufficientBalance { required: u64, available: u64 },
-    
+
     #[error("Transaction not found: {hash}")]
     TransactionNotFound { hash: String },
-    
+
     #[error("Wallet not found: {address}")]
     WalletNotFound { address: String },
-    
+
     #[error("Service unavailable: {service}")]
     ServiceUnavailable { service: String },
-    
+
     #[error("Internal error: {message}")]
     Internal { message: String },
 }
 
-pub type Result<T> = std::result::Result<T, VibeStreamError>; 
\ No newline at end of file
+pub type Result<T> = std::result::Result<T, VibeStreamError>;
+
+/// Códigos de `VibeStreamError::Blockchain`, comunes a todas las redes. El
+/// código nativo de la cadena (p.ej. `custom program error: 0x1`) se conserva
+/// en el mensaje.
+pub mod blockchain_error_codes {
+    pub const UNKNOWN: u32 = 0;
+    pub const INSUFFICIENT_FUNDS: u32 = 1;
+    /// Nonce ya usado (`NONCE_EXPIRED` en ethers)
+    pub const NONCE_EXPIRED: u32 = 2;
+    /// Blockhash caducado: hay que volver a firmar y enviar
+    pub const TRANSACTION_EXPIRED: u32 = 3;
+    pub const UNDERPRICED: u32 = 4;
+    pub const EXECUTION_REVERTED: u32 = 5;
+    pub const ACCOUNT_NOT_FOUND: u32 = 6;
+    /// Nodo caído, desincronizado o sin el bloque pedido
+    pub const NODE_UNAVAILABLE: u32 = 7;
+    pub const RATE_LIMITED: u32 = 8;
+}
+
+impl VibeStreamError {
+    pub fn blockchain(chain: BlockchainNetwork, code: u32, message: impl Into<String>) -> Self {
+        VibeStreamError::Blockchain { chain, code, message: message.into() }
+    }
+
+    /// Errores transitorios: repetir la misma operación más tarde puede funcionar
+    pub fn is_retryable(&self) -> bool {
+        use blockchain_error_codes::*;
+        match self {
+            VibeStreamError::Network { .. } | VibeStreamError::ServiceUnavailable { .. } => true,
+            VibeStreamError::Blockchain { code, .. } => {
+                matches!(*code, TRANSACTION_EXPIRED | NODE_UNAVAILABLE | RATE_LIMITED)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// `InstructionError::Custom` depende del programa; solo 0x1 significa lo
+/// mismo en System program y SPL Token: fondos insuficientes
+#[cfg(feature = "solana")]
+fn solana_program_error(custom_code: u32) -> (u32, &'static str) {
+    match custom_code {
+        0x1 => (blockchain_error_codes::INSUFFICIENT_FUNDS, "insufficient funds"),
+        _ => (blockchain_error_codes::UNKNOWN, "program error"),
+    }
+}
+
+#[cfg(feature = "solana")]
+impl From<solana_client::client_error::ClientError> for VibeStreamError {
+    fn from(err: solana_client::client_error::ClientError) -> Self {
+        use blockchain_error_codes::*;
+        use solana_client::client_error::ClientErrorKind;
+        use solana_client::rpc_request::RpcError;
+        use solana_sdk::instruction::InstructionError;
+        use solana_sdk::transaction::TransactionError;
+
+        // Incluye los fallos de preflight que llegan como error JSON-RPC
+        if let Some(tx_error) = err.get_transaction_error() {
+            let (code, message) = match &tx_error {
+                TransactionError::InstructionError(index, InstructionError::Custom(custom_code)) => {
+                    let (code, description) = solana_program_error(*custom_code);
+                    (code, format!("{} (custom program error: {:#x} in instruction {})", description, custom_code, index))
+                }
+                TransactionError::InsufficientFundsForFee => (INSUFFICIENT_FUNDS, "insufficient funds for fee".to_string()),
+                TransactionError::InsufficientFundsForRent { account_index } => {
+                    (INSUFFICIENT_FUNDS, format!("insufficient funds for rent (account {})", account_index))
+                }
+                TransactionError::BlockhashNotFound => (TRANSACTION_EXPIRED, "blockhash not found or expired".to_string()),
+                TransactionError::AccountNotFound => (ACCOUNT_NOT_FOUND, "account not found".to_string()),
+                other => (UNKNOWN, other.to_string()),
+            };
+            return VibeStreamError::blockchain(BlockchainNetwork::Solana, code, message);
+        }
+
+        match err.kind() {
+            ClientErrorKind::Io(_) => VibeStreamError::Network { message: err.to_string() },
+            ClientErrorKind::Reqwest(e) if e.status().map(|s| s.as_u16()) == Some(429) => {
+                VibeStreamError::blockchain(BlockchainNetwork::Solana, RATE_LIMITED, "RPC rate limit exceeded")
+            }
+            ClientErrorKind::Reqwest(_) => VibeStreamError::Network { message: err.to_string() },
+            // -32004 bloque no disponible, -32005 nodo no sano, -32016 slot mínimo no alcanzado
+            ClientErrorKind::RpcError(RpcError::RpcResponseError { code: -32004 | -32005 | -32016, message, .. }) => {
+                VibeStreamError::blockchain(BlockchainNetwork::Solana, NODE_UNAVAILABLE, message.clone())
+            }
+            _ => VibeStreamError::blockchain(BlockchainNetwork::Solana, UNKNOWN, err.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "ethereum")]
+impl From<ethers::providers::ProviderError> for VibeStreamError {
+    fn from(err: ethers::providers::ProviderError) -> Self {
+        VibeStreamError::from_provider_error(BlockchainNetwork::Ethereum, err)
+    }
+}
+
+#[cfg(feature = "ethereum")]
+impl VibeStreamError {
+    /// Igual que `From<ProviderError>` para otras redes EVM (Polygon)
+    pub fn from_provider_error(chain: BlockchainNetwork, err: ethers::providers::ProviderError) -> Self {
+        use blockchain_error_codes::*;
+        use ethers::providers::{ProviderError, RpcError};
+
+        if let Some(rpc_error) = err.as_error_response() {
+            let message = rpc_error.message.to_lowercase();
+            let (code, description) = if message.contains("nonce too low") || message.contains("nonce has already been used") {
+                (NONCE_EXPIRED, "nonce expired")
+            } else if message.contains("insufficient funds") {
+                (INSUFFICIENT_FUNDS, "insufficient funds")
+            } else if message.contains("underpriced") {
+                (UNDERPRICED, "transaction underpriced")
+            } else if rpc_error.code == 3 || message.contains("execution reverted") {
+                (EXECUTION_REVERTED, "execution reverted")
+            } else if rpc_error.code == -32005 || message.contains("rate limit") {
+                (RATE_LIMITED, "RPC rate limit exceeded")
+            } else if message.contains("header not found") || message.contains("missing trie node") {
+                (NODE_UNAVAILABLE, "node is not synced")
+            } else {
+                return VibeStreamError::blockchain(chain, UNKNOWN, rpc_error.message.clone());
+            };
+            return VibeStreamError::blockchain(chain, code, format!("{}: {}", description, rpc_error.message));
+        }
+
+        match &err {
+            ProviderError::HTTPError(e) if e.status().map(|s| s.as_u16()) == Some(429) => {
+                VibeStreamError::blockchain(chain, RATE_LIMITED, "RPC rate limit exceeded")
+            }
+            ProviderError::HTTPError(_) => VibeStreamError::Network { message: err.to_string() },
+            ProviderError::SerdeJson(e) => VibeStreamError::Serialization { message: e.to_string() },
+            _ => VibeStreamError::blockchain(chain, UNKNOWN, err.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::blockchain_error_codes::*;
+
+    #[test]
+    fn transient_errors_are_retryable() {
+        assert!(VibeStreamError::Network { message: "timeout".to_string() }.is_retryable());
+        assert!(VibeStreamError::blockchain(BlockchainNetwork::Polygon, NODE_UNAVAILABLE, "behind").is_retryable());
+        assert!(VibeStreamError::blockchain(BlockchainNetwork::Solana, TRANSACTION_EXPIRED, "blockhash").is_retryable());
+        assert!(!VibeStreamError::blockchain(BlockchainNetwork::Solana, INSUFFICIENT_FUNDS, "funds").is_retryable());
+        assert!(!VibeStreamError::blockchain(BlockchainNetwork::Ethereum, NONCE_EXPIRED, "nonce").is_retryable());
+        assert!(!VibeStreamError::Validation { message: "bad".to_string() }.is_retryable());
+    }
+
+    #[cfg(feature = "solana")]
+    mod solana {
+        use super::*;
+        use solana_client::client_error::{ClientError, ClientErrorKind};
+        use solana_client::rpc_request::{RpcError, RpcResponseErrorData};
+        use solana_sdk::instruction::InstructionError;
+        use solana_sdk::transaction::TransactionError;
+
+        fn mapped(error: ClientError) -> (BlockchainNetwork, u32, String) {
+            match VibeStreamError::from(error) {
+                VibeStreamError::Blockchain { chain, code, message } => (chain, code, message),
+                other => panic!("expected a blockchain error, got {:?}", other),
+            }
+        }
+
+        #[test]
+        fn custom_program_error_0x1_is_insufficient_funds() {
+            let error = ClientError::from(TransactionError::InstructionError(2, InstructionError::Custom(1)));
+            let (chain, code, message) = mapped(error);
+            assert_eq!((chain, code), (BlockchainNetwork::Solana, INSUFFICIENT_FUNDS));
+            assert_eq!(message, "insufficient funds (custom program error: 0x1 in instruction 2)");
+        }
+
+        #[test]
+        fn transaction_errors_map_to_codes() {
+            assert_eq!(mapped(ClientError::from(TransactionError::InsufficientFundsForFee)).1, INSUFFICIENT_FUNDS);
+            assert_eq!(mapped(ClientError::from(TransactionError::AccountNotFound)).1, ACCOUNT_NOT_FOUND);
+
+            let expired = VibeStreamError::from(ClientError::from(TransactionError::BlockhashNotFound));
+            assert!(expired.is_retryable());
+        }
+
+        #[test]
+        fn unhealthy_node_is_retryable() {
+            let error = ClientError::from(RpcError::RpcResponseError {
+                code: -32005,
+                message: "Node is behind by 42 slots".to_string(),
+                data: RpcResponseErrorData::Empty,
+            });
+            let mapped = VibeStreamError::from(error);
+            assert!(matches!(mapped, VibeStreamError::Blockchain { code: NODE_UNAVAILABLE, .. }));
+            assert!(mapped.is_retryable());
+        }
+
+        #[test]
+        fn io_errors_are_network_errors() {
+            let error = ClientError::from(ClientErrorKind::Io(std::io::Error::new(std::io::ErrorKind::TimedOut, "timed out")));
+            let mapped = VibeStreamError::from(error);
+            assert!(matches!(mapped, VibeStreamError::Network { .. }));
+            assert!(mapped.is_retryable());
+        }
+    }
+
+    #[cfg(feature = "ethereum")]
+    mod ethereum {
+        use super::*;
+        use ethers::providers::{HttpClientError, JsonRpcError, ProviderError};
+
+        fn rpc_error(code: i64, message: &str) -> ProviderError {
+            ProviderError::JsonRpcClientError(Box::new(HttpClientError::JsonRpcError(JsonRpcError {
+                code,
+                message: message.to_string(),
+                data: None,
+            })))
+        }
+
+        fn code_of(error: VibeStreamError) -> u32 {
+            match error {
+                VibeStreamError::Blockchain { code, .. } => code,
+                other => panic!("expected a blockchain error, got {:?}", other),
+            }
+        }
+
+        #[test]
+        fn nonce_too_low_is_nonce_expired() {
+            let mapped = VibeStreamError::from(rpc_error(-32000, "nonce too low"));
+            assert!(matches!(
+                &mapped,
+                VibeStreamError::Blockchain { chain: BlockchainNetwork::Ethereum, code: NONCE_EXPIRED, message }
+                    if message == "nonce expired: nonce too low"
+            ));
+            assert!(!mapped.is_retryable());
+        }
+
+        #[test]
+        fn common_rpc_errors_map_to_codes() {
+            assert_eq!(code_of(rpc_error(-32000, "insufficient funds for gas * price + value")), INSUFFICIENT_FUNDS);
+            assert_eq!(code_of(rpc_error(-32000, "replacement transaction underpriced")), UNDERPRICED);
+            assert_eq!(code_of(rpc_error(3, "execution reverted: sold out")), EXECUTION_REVERTED);
+            assert_eq!(code_of(rpc_error(-32000, "something else")), UNKNOWN);
+
+            let limited = VibeStreamError::from(rpc_error(-32005, "limit exceeded"));
+            assert_eq!(code_of(limited.clone()), RATE_LIMITED);
+            assert!(limited.is_retryable());
+        }
+
+        #[test]
+        fn polygon_keeps_its_network() {
+            let mapped = VibeStreamError::from_provider_error(BlockchainNetwork::Polygon, rpc_error(-32000, "nonce too low"));
+            assert!(matches!(mapped, VibeStreamError::Blockchain { chain: BlockchainNetwork::Polygon, code: NONCE_EXPIRED, .. }));
+        }
+    }
+}
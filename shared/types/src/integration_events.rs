@@ -44,6 +44,26 @@ impl IntegrationEvent {
             metadata: serde_json::json!({}),
         }
     }
+
+    /// Evento de integración traducido desde un evento de dominio: conserva el
+    /// agregado y el instante del evento original y lleva su payload en `metadata`
+    pub fn from_domain_event(
+        event_type: IntegrationEventType,
+        source_event_type: &str,
+        aggregate_id: Uuid,
+        occurred_at: DateTime<Utc>,
+        correlation_id: Option<Uuid>,
+        payload: serde_json::Value,
+    ) -> Self {
+        Self {
+            timestamp: occurred_at,
+            metadata: serde_json::json!({
+                "source_event_type": source_event_type,
+                "payload": payload,
+            }),
+            ..Self::new(event_type.as_str().to_string(), aggregate_id, correlation_id, None)
+        }
+    }
 }
 
 // =============================================================================
@@ -518,7 +538,7 @@ impl NotificationSentEvent {
 // =============================================================================
 
 /// Enum para todos los tipos de eventos de integración
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum IntegrationEventType {
     // Music Context Events
     SongCreated,
@@ -552,6 +572,25 @@ pub enum IntegrationEventType {
 }
 
 impl IntegrationEventType {
+    pub const ALL: [IntegrationEventType; 16] = [
+        IntegrationEventType::SongCreated,
+        IntegrationEventType::ArtistCreated,
+        IntegrationEventType::SongUpdated,
+        IntegrationEventType::UserRegistered,
+        IntegrationEventType::UserUpdated,
+        IntegrationEventType::CampaignCreated,
+        IntegrationEventType::CampaignActivated,
+        IntegrationEventType::NftPurchased,
+        IntegrationEventType::ListenSessionStarted,
+        IntegrationEventType::ListenSessionCompleted,
+        IntegrationEventType::RewardDistributed,
+        IntegrationEventType::OwnershipContractCreated,
+        IntegrationEventType::SharesPurchased,
+        IntegrationEventType::PaymentProcessed,
+        IntegrationEventType::PaymentFailed,
+        IntegrationEventType::NotificationSent,
+    ];
+
     /// Obtener el nombre del evento como string
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -601,4 +640,4 @@ pub trait IntegrationEventHandler: Send + Sync {
 #[async_trait]
 pub trait IntegrationEventPublisher: Send + Sync {
     async fn publish(&self, event: &IntegrationEvent) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
-} 
\ No newline at end of file
+}
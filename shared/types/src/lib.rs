@@ -43,7 +43,7 @@ pub trait CommandHandler<C: Command>: Send + Sync {
     async fn handle(&self, command: C) -> std::result::Result<Self::Output, Self::Error>;
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct RequestId(pub Uuid);
 
 impl RequestId {
@@ -52,6 +52,12 @@ impl RequestId {
     }
 }
 
+impl std::fmt::Display for RequestId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
 impl Default for RequestId {
     fn default() -> Self {
         Self::new()
@@ -1,11 +1,20 @@
 use serde::{Deserialize, Serialize};
 use crate::{RequestId, Timestamp, Transaction, WalletAddress, Balance, StreamPayment};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServiceMessage<T> {
     pub id: RequestId,
     pub timestamp: Timestamp,
     pub payload: T,
+    /// Traza de quien publicó el mensaje; los mensajes sin ella siguen siendo válidos
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_context: Option<TraceContext>,
+    /// `X-Request-Id` de la petición HTTP que originó el mensaje. Distinto de
+    /// `id`, que identifica al mensaje (una petición puede publicar varios).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<RequestId>,
 }
 
 impl<T> ServiceMessage<T> {
@@ -14,8 +23,47 @@ impl<T> ServiceMessage<T> {
             id: RequestId::new(),
             timestamp: Timestamp::now(),
             payload,
+            trace_context: None,
+            request_id: None,
         }
     }
+
+    pub fn with_request_id(mut self, request_id: Option<RequestId>) -> Self {
+        self.request_id = request_id;
+        self
+    }
+
+    pub fn with_trace_context(mut self, trace_context: Option<TraceContext>) -> Self {
+        self.trace_context = trace_context;
+        self
+    }
+}
+
+/// Cabeceras W3C Trace Context del productor. El consumidor continúa la misma
+/// traza tomando este span como padre.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceContext {
+    /// `00-<trace-id>-<parent-id>-<flags>`
+    pub traceparent: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracestate: Option<String>,
+}
+
+impl TraceContext {
+    /// `(trace_id, parent_span_id)` si `traceparent` está bien formado
+    pub fn ids(&self) -> Option<(&str, &str)> {
+        let mut parts = self.traceparent.split('-');
+        let (version, trace_id, span_id, flags) = (parts.next()?, parts.next()?, parts.next()?, parts.next()?);
+        let is_hex = |value: &str, len: usize| value.len() == len && value.bytes().all(|b| b.is_ascii_hexdigit());
+        let valid = parts.next().is_none()
+            && is_hex(version, 2)
+            && is_hex(trace_id, 32)
+            && is_hex(span_id, 16)
+            && is_hex(flags, 2)
+            && trace_id.bytes().any(|b| b != b'0')
+            && span_id.bytes().any(|b| b != b'0');
+        valid.then_some((trace_id, span_id))
+    }
 }
 
 // Mensajes para Ethereum Service
@@ -61,6 +109,91 @@ pub enum ZkMessage {
     },
 }
 
+// Minteo de NFTs en Solana (api-gateway -> solana-integration)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NFTAttribute {
+    pub trait_type: String,
+    pub value: String,
+}
+
+/// Metadatos Metaplex del NFT a mintear
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NFTMetadata {
+    pub name: String,
+    pub symbol: String,
+    pub description: String,
+    pub uri: String,
+    pub attributes: Vec<NFTAttribute>,
+    /// Dirección de la colección on-chain, si la campaña ya tiene una
+    pub collection: Option<String>,
+    pub seller_fee_basis_points: u16,
+}
+
+/// Petición de minteo. `request_id` es la clave de idempotencia: el servicio de
+/// Solana no debe mintear dos veces el mismo `request_id`, aunque el mensaje se
+/// reintente.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NftMintRequest {
+    pub request_id: Uuid,
+    pub recipient_wallet: String,
+    pub metadata: NFTMetadata,
+    /// Cola donde publicar el `NftMintResult`
+    pub reply_queue: String,
+    pub attempt: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum NftMintOutcome {
+    Minted { mint_address: String, signature: String },
+    Failed { reason: String, retryable: bool },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NftMintResult {
+    pub request_id: Uuid,
+    pub outcome: NftMintOutcome,
+}
+
+/// Petición de transferencia de un NFT ya minteado desde la wallet de custodia
+/// de la plataforma. Igual que el minteo, `request_id` es la clave de idempotencia.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NftTransferRequest {
+    pub request_id: Uuid,
+    pub mint_address: String,
+    pub recipient_wallet: String,
+}
+
+/// Transferencia de un payout de artista desde la wallet de payouts de la
+/// plataforma. `reference` va como memo y es la clave de idempotencia: el
+/// servicio de Solana no debe enviar dos veces la misma referencia.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayoutTransferRequest {
+    pub reference: String,
+    pub recipient_wallet: String,
+    pub amount: f64,
+    pub currency: String,
+    /// Pasado este instante la petición se descarta sin enviar: el gateway ya
+    /// puede haberla reintentado
+    pub valid_until: DateTime<Utc>,
+    /// Cola donde publicar los `PayoutTransferResult`
+    pub reply_queue: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PayoutTransferOutcome {
+    /// Enviada, pendiente de confirmación
+    Sent { signature: String },
+    Confirmed { signature: String },
+    /// `signature` es `None` si la transferencia no llegó a enviarse
+    Failed { signature: Option<String>, reason: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PayoutTransferResult {
+    pub reference: String,
+    pub outcome: PayoutTransferOutcome,
+}
+
 // Respuestas de los servicios
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ServiceResponse {
@@ -113,4 +246,70 @@ impl QueueNames {
     pub const SOLANA: &'static str = "solana_queue";
     pub const ZK: &'static str = "zk_queue";
     pub const RESPONSES: &'static str = "response_queue";
-} 
\ No newline at end of file
+    pub const SOLANA_NFT_MINT: &'static str = "solana_nft_mint_queue";
+    pub const CAMPAIGN_NFT_MINT_RESULTS: &'static str = "campaign_nft_mint_results";
+    pub const WRISTBAND_NFT_MINT_RESULTS: &'static str = "wristband_nft_mint_results";
+    pub const SOLANA_NFT_TRANSFER: &'static str = "solana_nft_transfer_queue";
+    pub const SOLANA_PAYOUT_TRANSFER: &'static str = "solana_payout_transfer_queue";
+    pub const ARTIST_PAYOUT_TRANSFER_RESULTS: &'static str = "artist_payout_transfer_results";
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_context_travels_in_the_envelope() {
+        let trace_context = TraceContext {
+            traceparent: "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string(),
+            tracestate: None,
+        };
+        let message = ServiceMessage::new(ZkMessage::GenerateSolvencyProof { balance: 10, threshold: 5 })
+            .with_trace_context(Some(trace_context.clone()));
+
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["trace_context"]["traceparent"], trace_context.traceparent.as_str());
+        assert!(json["trace_context"].get("tracestate").is_none());
+
+        let received: ServiceMessage<ZkMessage> = serde_json::from_value(json).unwrap();
+        assert_eq!(received.trace_context.as_ref(), Some(&trace_context));
+        assert_eq!(trace_context.ids(), Some(("4bf92f3577b34da6a3ce929d0e0e4736", "00f067aa0ba902b7")));
+    }
+
+    #[test]
+    fn envelopes_without_trace_context_are_still_accepted() {
+        let mut json = serde_json::to_value(ServiceMessage::new(ZkMessage::GenerateSolvencyProof { balance: 10, threshold: 5 })).unwrap();
+        assert!(json.get("trace_context").is_none());
+        json.as_object_mut().unwrap().remove("trace_context");
+        let received: ServiceMessage<ZkMessage> = serde_json::from_value(json).unwrap();
+        assert!(received.trace_context.is_none());
+        assert!(received.request_id.is_none());
+    }
+
+    #[test]
+    fn request_id_travels_in_the_envelope() {
+        let request_id = RequestId::new();
+        let message = ServiceMessage::new(ZkMessage::GenerateSolvencyProof { balance: 10, threshold: 5 })
+            .with_request_id(Some(request_id));
+
+        let json = serde_json::to_value(&message).unwrap();
+        assert_eq!(json["request_id"], request_id.to_string().as_str());
+        assert_ne!(message.id, request_id);
+
+        let received: ServiceMessage<ZkMessage> = serde_json::from_value(json).unwrap();
+        assert_eq!(received.request_id, Some(request_id));
+    }
+
+    #[test]
+    fn malformed_traceparent_has_no_ids() {
+        for traceparent in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-zzzzzzzzzzzzzzzz-01",
+        ] {
+            let trace_context = TraceContext { traceparent: traceparent.to_string(), tracestate: None };
+            assert_eq!(trace_context.ids(), None, "{}", traceparent);
+        }
+    }
+}
@@ -110,6 +110,8 @@ pub struct Playlist {
     pub title: String,
     pub description: Option<String>,
     pub is_public: bool,
+    /// Followers can add and remove songs
+    pub is_collaborative: bool,
     pub cover_image_url: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -120,6 +122,8 @@ pub struct CreatePlaylist {
     pub title: String,
     pub description: Option<String>,
     pub is_public: bool,
+    #[serde(default)]
+    pub is_collaborative: bool,
     pub cover_image_url: Option<String>,
 }
 
@@ -129,6 +133,7 @@ pub struct PlaylistSong {
     pub playlist_id: Uuid,
     pub song_id: Uuid,
     pub position: i32,
+    pub added_by: Uuid,
     pub added_at: DateTime<Utc>,
 }
 